@@ -8,6 +8,8 @@ pub const IMPORTS_TO_LEFT_EDGE_PIXEL_GAP: u32 = 120;
 // VIEWPORT
 pub const VIEWPORT_ZOOM_WHEEL_RATE: f64 = (1. / 600.) * 3.;
 pub const VIEWPORT_ZOOM_MOUSE_RATE: f64 = 1. / 400.;
+/// Zoom rate for trackpad pinch gestures, which browsers report as small scroll wheel movements.
+pub const VIEWPORT_ZOOM_TRACKPAD_PINCH_RATE: f64 = 1. / 100.;
 pub const VIEWPORT_ZOOM_SCALE_MIN: f64 = 0.000_000_1;
 pub const VIEWPORT_ZOOM_SCALE_MAX: f64 = 10_000.;
 pub const VIEWPORT_ZOOM_MIN_FRACTION_COVER: f64 = 0.01;
@@ -27,7 +29,31 @@ pub const VIEWPORT_ROTATE_SNAP_INTERVAL: f64 = 15.;
 pub const VIEWPORT_ZOOM_TO_FIT_PADDING_SCALE_FACTOR: f64 = 0.95;
 
 pub const DRAG_BEYOND_VIEWPORT_MAX_OVEREXTENSION_PIXELS: f64 = 50.;
+/// Default auto-panning speed, in viewport pixels per second for each pixel the pointer is dragged past the edge margin.
 pub const DRAG_BEYOND_VIEWPORT_SPEED_FACTOR: f64 = 20.;
+/// Default distance (in viewport pixels) inside the viewport's edges within which dragging starts auto-panning.
+pub const AUTO_PAN_EDGE_MARGIN: f64 = 0.;
+/// Default side length (in CSS pixels) of the precision cursor, which can be changed in the preferences.
+pub const PRECISION_CURSOR_SIZE: u32 = 32;
+/// Largest precision cursor (in CSS pixels), since browsers ignore larger cursor images.
+pub const PRECISION_CURSOR_MAX_SIZE: u32 = 128;
+
+/// Side length (in viewport pixels) of the tiles which the rendered viewport is snapped to, so panning within them can reuse the previous render.
+pub const VIEWPORT_RENDER_TILE_SIZE: f64 = 256.;
+/// Number of extra tiles rendered beyond each edge of the viewport, giving room to pan before the graph needs to be rendered again.
+pub const VIEWPORT_RENDER_TILE_MARGIN: i32 = 1;
+/// How far (in viewport pixels) beyond the bounds of a changed layer the tiles are rendered again, since its strokes and effects like shadows can reach past the outlines its bounds are measured from.
+pub const VIEWPORT_RENDER_INVALIDATION_MARGIN: f64 = 32.;
+/// How long (in milliseconds) the viewport may wait on a render before the following renders start with a fast low resolution preview that's refined afterwards.
+pub const PROGRESSIVE_RENDER_PREVIEW_DELAY: f64 = 100.;
+/// How long (in milliseconds) after the last scroll wheel input the user is still considered to be zooming or panning, for rendering reduced quality previews while interacting.
+pub const INTERACTION_IDLE_DELAY: u64 = 200;
+/// Multiplier applied to the hit test distances of handles and points while using touch input, since a fingertip is far less precise than a cursor.
+pub const TOUCH_HIT_TARGET_SCALE: f64 = 2.5;
+/// Zoom factor at and above which the pixel view mode outlines each document pixel with a grid.
+pub const PIXEL_GRID_MIN_ZOOM: f64 = 8.;
+/// Extra distance (in viewport pixels) around the layer bounds in the click target index, so rounding errors never exclude a layer from being hit tested.
+pub const CLICK_TARGET_INDEX_TOLERANCE: f64 = 1.;
 
 // SNAPPING POINT
 pub const SNAP_POINT_TOLERANCE: f64 = 5.;
@@ -44,7 +70,9 @@ pub const DRAG_THRESHOLD: f64 = 1.;
 pub const ROTATE_INCREMENT: f64 = 15.;
 pub const SCALE_INCREMENT: f64 = 0.1;
 pub const SLOWING_DIVISOR: f64 = 10.;
+/// Default distance (in document units) moved by the arrow keys, which can be changed in the preferences.
 pub const NUDGE_AMOUNT: f64 = 1.;
+/// Default distance (in document units) moved by the arrow keys while holding Shift, which can be changed in the preferences.
 pub const BIG_NUDGE_AMOUNT: f64 = 10.;
 
 // TOOLS
@@ -113,6 +141,11 @@ pub const LINE_ROTATE_SNAP_ANGLE: f64 = 15.;
 pub const BRUSH_SIZE_CHANGE_KEYBOARD: f64 = 5.;
 pub const DEFAULT_BRUSH_SIZE: f64 = 20.;
 
+// TEXT TOOL
+pub const TEXT_OVERFLOW_PORT_SIZE: f64 = 10.;
+/// How far the port for linking overflowing text to another text box is placed below the bottom right corner of the text box, clear of its resize handle.
+pub const TEXT_OVERFLOW_PORT_OFFSET: f64 = 16.;
+
 // SCROLLBARS
 pub const SCROLLBAR_SPACING: f64 = 0.1;
 pub const ASYMPTOTIC_EFFECT: f64 = 0.5;
@@ -126,9 +159,13 @@ pub const COLOR_OVERLAY_RED: &str = "#ef5454";
 pub const COLOR_OVERLAY_GRAY: &str = "#cccccc";
 pub const COLOR_OVERLAY_WHITE: &str = "#ffffff";
 pub const COLOR_OVERLAY_LABEL_BACKGROUND: &str = "#000000cc";
+pub const COLOR_OVERLAY_PIXEL_GRID: &str = "#80808066";
+pub const COLOR_OVERLAY_HIGH_CONTRAST_ACCENT: &str = "#ff00ff";
+pub const COLOR_OVERLAY_HIGH_CONTRAST_SNAPPING: &str = "#00ffff";
+pub const COLOR_OVERLAY_HIGH_CONTRAST_FILL: &str = "#000000";
 
 // DOCUMENT
 pub const DEFAULT_DOCUMENT_NAME: &str = "Untitled Document";
 pub const FILE_SAVE_SUFFIX: &str = ".graphite";
-pub const MAX_UNDO_HISTORY_LEN: usize = 100; // TODO: Add this to user preferences
+pub const MAX_UNDO_HISTORY_LEN: usize = 100; // Default for the user preference, which applies to the document history but not yet the selection history
 pub const AUTO_SAVE_TIMEOUT_SECONDS: u64 = 15;
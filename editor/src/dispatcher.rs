@@ -13,6 +13,7 @@ pub struct Dispatcher {
 
 #[derive(Debug, Default)]
 pub struct DispatcherMessageHandlers {
+	action_macro_message_handler: ActionMacroMessageHandler,
 	animation_message_handler: AnimationMessageHandler,
 	broadcast_message_handler: BroadcastMessageHandler,
 	debug_message_handler: DebugMessageHandler,
@@ -23,6 +24,7 @@ pub struct DispatcherMessageHandlers {
 	layout_message_handler: LayoutMessageHandler,
 	pub portfolio_message_handler: PortfolioMessageHandler,
 	preferences_message_handler: PreferencesMessageHandler,
+	radial_menu_message_handler: RadialMenuMessageHandler,
 	tool_message_handler: ToolMessageHandler,
 	workspace_message_handler: WorkspaceMessageHandler,
 }
@@ -53,7 +55,7 @@ const SIDE_EFFECT_FREE_MESSAGES: &[MessageDiscriminant] = &[
 ];
 const DEBUG_MESSAGE_BLOCK_LIST: &[MessageDiscriminant] = &[MessageDiscriminant::Broadcast(BroadcastMessageDiscriminant::TriggerEvent(BroadcastEventDiscriminant::AnimationFrame))];
 // TODO: Find a way to combine these with the list above. We use strings for now since these are the standard variant names used by multiple messages. But having these also type-checked would be best.
-const DEBUG_MESSAGE_ENDING_BLOCK_LIST: &[&str] = &["PointerMove", "PointerOutsideViewport", "Overlays", "Draw", "CurrentTime", "Time"];
+const DEBUG_MESSAGE_ENDING_BLOCK_LIST: &[&str] = &["PointerMove", "PointerOutsideViewport", "Overlays", "Draw", "CurrentTime", "Time", "Tick"];
 
 impl Dispatcher {
 	pub fn new() -> Self {
@@ -125,6 +127,9 @@ impl Dispatcher {
 			// Create a new queue for the child messages
 			let mut queue = VecDeque::new();
 
+			// The actions resulting from keyboard shortcuts and widgets (including the menu bar) are what make up a recorded macro
+			let record_actions = self.message_handlers.action_macro_message_handler.recording() && matches!(message, Message::KeyMapping(_) | Message::Layout(_) | Message::RadialMenu(_));
+
 			// Process the action by forwarding it to the relevant message handler, or saving the FrontendMessage to be sent to the frontend
 			match message {
 				Message::StartBuffer => {
@@ -165,6 +170,9 @@ impl Dispatcher {
 					// Display the menu bar at the top of the window
 					queue.add(MenuBarMessage::SendLayout);
 
+					// List the bundled gradient presets, which those saved by the user join once the preferences have loaded
+					self.message_handlers.preferences_message_handler.send_gradient_presets(&mut queue);
+
 					// Send the information for tooltips and categories for each node/input.
 					queue.add(FrontendMessage::SendUIMetadata {
 						input_type_descriptions: Vec::new(),
@@ -175,6 +183,13 @@ impl Dispatcher {
 					// Finish loading persistent data from the browser database
 					queue.add(FrontendMessage::TriggerLoadRestAutoSaveDocuments);
 				}
+				Message::ActionMacro(message) => {
+					let data = ActionMacroMessageData {
+						portfolio: &self.message_handlers.portfolio_message_handler,
+						preferences: &self.message_handlers.preferences_message_handler,
+					};
+					self.message_handlers.action_macro_message_handler.process_message(message, &mut queue, data);
+				}
 				Message::Animation(message) => {
 					self.message_handlers.animation_message_handler.process_message(message, &mut queue, ());
 				}
@@ -189,6 +204,8 @@ impl Dispatcher {
 					let data = DialogMessageData {
 						portfolio: &self.message_handlers.portfolio_message_handler,
 						preferences: &self.message_handlers.preferences_message_handler,
+						key_mapping: &self.message_handlers.key_mapping_message_handler,
+						animation: &self.message_handlers.animation_message_handler,
 					};
 					self.message_handlers.dialog_message_handler.process_message(message, &mut queue, data);
 				}
@@ -210,10 +227,16 @@ impl Dispatcher {
 				}
 				Message::InputPreprocessor(message) => {
 					let keyboard_platform = GLOBAL_PLATFORM.get().copied().unwrap_or_default().as_keyboard_platform_layout();
+					let sticky_modifier_keys = self.message_handlers.preferences_message_handler.sticky_modifier_keys;
 
-					self.message_handlers
-						.input_preprocessor_message_handler
-						.process_message(message, &mut queue, InputPreprocessorMessageData { keyboard_platform });
+					self.message_handlers.input_preprocessor_message_handler.process_message(
+						message,
+						&mut queue,
+						InputPreprocessorMessageData {
+							keyboard_platform,
+							sticky_modifier_keys,
+						},
+					);
 				}
 				Message::KeyMapping(message) => {
 					let input = &self.message_handlers.input_preprocessor_message_handler;
@@ -233,6 +256,7 @@ impl Dispatcher {
 					let preferences = &self.message_handlers.preferences_message_handler;
 					let current_tool = &self.message_handlers.tool_message_handler.tool_state.tool_data.active_tool_type;
 					let message_logging_verbosity = self.message_handlers.debug_message_handler.message_logging_verbosity;
+					let macro_recording = self.message_handlers.action_macro_message_handler.recording();
 					let timing_information = self.message_handlers.animation_message_handler.timing_information();
 					let animation = &self.message_handlers.animation_message_handler;
 
@@ -244,6 +268,7 @@ impl Dispatcher {
 							preferences,
 							current_tool,
 							message_logging_verbosity,
+							macro_recording,
 							timing_information,
 							animation,
 						},
@@ -252,6 +277,17 @@ impl Dispatcher {
 				Message::Preferences(message) => {
 					self.message_handlers.preferences_message_handler.process_message(message, &mut queue, ());
 				}
+				Message::RadialMenu(message) => {
+					let data = RadialMenuMessageData {
+						ipp: &self.message_handlers.input_preprocessor_message_handler,
+						portfolio: &self.message_handlers.portfolio_message_handler,
+						preferences: &self.message_handlers.preferences_message_handler,
+					};
+					self.message_handlers.radial_menu_message_handler.process_message(message, &mut queue, data);
+				}
+				Message::Tool(ToolMessage::ResetToolOptions) if self.message_handlers.portfolio_message_handler.active_document_id().is_none() => {
+					self.message_handlers.tool_message_handler.reset_tool_options(&mut queue);
+				}
 				Message::Tool(message) => {
 					let document_id = self.message_handlers.portfolio_message_handler.active_document_id().unwrap();
 					let Some(document) = self.message_handlers.portfolio_message_handler.documents.get_mut(&document_id) else {
@@ -271,10 +307,19 @@ impl Dispatcher {
 					self.message_handlers.tool_message_handler.process_message(message, &mut queue, data);
 				}
 				Message::Workspace(message) => {
-					self.message_handlers.workspace_message_handler.process_message(message, &mut queue, ());
+					let data = WorkspaceMessageData {
+						portfolio: &self.message_handlers.portfolio_message_handler,
+						preferences: &self.message_handlers.preferences_message_handler,
+					};
+					self.message_handlers.workspace_message_handler.process_message(message, &mut queue, data);
 				}
 			}
 
+			if record_actions {
+				let document = self.message_handlers.portfolio_message_handler.active_document();
+				self.message_handlers.action_macro_message_handler.record(&queue, document);
+			}
+
 			// If there are child messages, append the queue to the list of queues
 			if !queue.is_empty() {
 				self.message_queues.push(queue);
@@ -295,6 +340,7 @@ impl Dispatcher {
 		if let Some(document) = self.message_handlers.portfolio_message_handler.active_document() {
 			if !document.graph_view_overlay_open {
 				list.extend(self.message_handlers.tool_message_handler.actions());
+				list.extend(self.message_handlers.radial_menu_message_handler.actions());
 			}
 		}
 		list.extend(self.message_handlers.portfolio_message_handler.actions());
@@ -302,7 +348,8 @@ impl Dispatcher {
 	}
 
 	pub fn poll_node_graph_evaluation(&mut self, responses: &mut VecDeque<Message>) -> Result<(), String> {
-		self.message_handlers.portfolio_message_handler.poll_node_graph_evaluation(responses)
+		let interacting = self.message_handlers.input_preprocessor_message_handler.is_interacting();
+		self.message_handlers.portfolio_message_handler.poll_node_graph_evaluation(interacting, responses)
 	}
 
 	/// Create the tree structure for logging the messages as a tree
@@ -54,3 +54,31 @@ macro_rules! advertise_actions {
 		}
 	}
 }
+
+/// Implements `ToolOptionsPersistence` for a tool by saving the field holding its options as JSON in the preferences.
+///
+/// # Example
+///
+/// ```ignore
+/// persist_tool_options!(BrushTool, options);
+/// persist_tool_options!(SelectTool, tool_data.nested_selection_behavior);
+/// ```
+macro_rules! persist_tool_options {
+	($tool:ty, $($field:ident).+) => {
+		impl $crate::messages::tool::utility_types::ToolOptionsPersistence for $tool {
+			fn saved_options(&self) -> Option<String> {
+				serde_json::to_string(&self.$($field).+).ok()
+			}
+
+			fn restore_options(&mut self, options: &str) {
+				if let Ok(options) = serde_json::from_str(options) {
+					self.$($field).+ = options;
+				}
+			}
+
+			fn reset_options(&mut self) {
+				self.$($field).+ = Default::default();
+			}
+		}
+	};
+}
@@ -0,0 +1,12 @@
+use crate::messages::prelude::*;
+
+#[impl_message(Message, ActionMacro)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum ActionMacroMessage {
+	// Messages
+	Replay { name: String, each_selected_layer: bool },
+	ReplayStep { step: String },
+	Save { name: String },
+	StartRecording,
+	StopRecording,
+}
@@ -0,0 +1,114 @@
+use super::utility_types::{ActionMacro, is_recordable, record_step, replay_step, selected_layer_chains};
+use crate::messages::prelude::*;
+
+pub struct ActionMacroMessageData<'a> {
+	pub portfolio: &'a PortfolioMessageHandler,
+	pub preferences: &'a PreferencesMessageHandler,
+}
+
+/// Records the actions the user performs with keyboard shortcuts, menus, and other widgets, so they can be saved as a macro and replayed later.
+#[derive(Debug, Clone, Default)]
+pub struct ActionMacroMessageHandler {
+	recording: bool,
+	/// The steps recorded so far, or since recording stopped, the ones waiting to be named and saved.
+	recorded: Vec<String>,
+}
+
+impl MessageHandler<ActionMacroMessage, ActionMacroMessageData<'_>> for ActionMacroMessageHandler {
+	fn process_message(&mut self, message: ActionMacroMessage, responses: &mut VecDeque<Message>, data: ActionMacroMessageData) {
+		let ActionMacroMessageData { portfolio, preferences } = data;
+
+		match message {
+			// Messages
+			ActionMacroMessage::Replay { name, each_selected_layer } => {
+				let Some(action_macro) = preferences.action_macros.iter().find(|action_macro| action_macro.name == name) else {
+					warn!("Tried to replay the nonexistent macro \"{name}\"");
+					return;
+				};
+				// Each step finds the nodes it refers to among the selected layers only once the steps before it have been carried out, since they can change the selection
+				let steps = action_macro.steps.iter().map(|step| ActionMacroMessage::ReplayStep { step: step.clone() });
+
+				if !each_selected_layer {
+					for step in steps {
+						responses.add(step);
+					}
+					return;
+				}
+
+				// Select each of the selected layers on its own in turn and replay the macro on it, then restore the selection
+				let Some(document) = portfolio.active_document() else { return };
+				let selected_nodes = document
+					.network_interface
+					.selected_nodes()
+					.selected_layers(document.metadata())
+					.map(|layer| layer.to_node())
+					.collect::<Vec<_>>();
+				for &node in &selected_nodes {
+					responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![node] });
+					for step in steps.clone() {
+						responses.add(step);
+					}
+				}
+				responses.add(NodeGraphMessage::SelectedNodesSet { nodes: selected_nodes });
+			}
+			ActionMacroMessage::ReplayStep { step } => {
+				let chains = portfolio.active_document().map(selected_layer_chains).unwrap_or_default();
+				match replay_step(&step, &chains) {
+					Ok(message) => responses.add(message),
+					Err(error) => warn!("Skipping a step of a macro which couldn't be replayed: {error}"),
+				}
+			}
+			ActionMacroMessage::Save { name } => {
+				let name = name.trim().to_string();
+				if name.is_empty() || self.recorded.is_empty() {
+					return;
+				}
+
+				let action_macro = ActionMacro {
+					name,
+					steps: std::mem::take(&mut self.recorded),
+				};
+				responses.add(PreferencesMessage::SaveActionMacro { action_macro });
+			}
+			ActionMacroMessage::StartRecording => {
+				self.recording = true;
+				self.recorded.clear();
+
+				// Update the menu to offer stopping the recording
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			ActionMacroMessage::StopRecording => {
+				if !self.recording {
+					return;
+				}
+				self.recording = false;
+
+				responses.add(MenuBarMessage::SendLayout);
+				if !self.recorded.is_empty() {
+					responses.add(DialogMessage::RequestSaveActionMacroDialog { name: String::new() });
+				}
+			}
+		}
+	}
+
+	fn actions(&self) -> ActionList {
+		actions!(ActionMacroMessageDiscriminant;)
+	}
+}
+
+impl ActionMacroMessageHandler {
+	pub fn recording(&self) -> bool {
+		self.recording
+	}
+
+	/// Adds the actions dispatched by a keyboard shortcut or widget to the macro being recorded, referring to the nodes of the layers selected in the document before they're carried out.
+	pub fn record<'a>(&mut self, messages: impl IntoIterator<Item = &'a Message>, document: Option<&DocumentMessageHandler>) {
+		if !self.recording {
+			return;
+		}
+
+		let chains = document.map(selected_layer_chains).unwrap_or_default();
+		self.recorded
+			.extend(messages.into_iter().filter(|message| is_recordable(message)).filter_map(|message| record_step(message, &chains)));
+	}
+}
@@ -0,0 +1,9 @@
+mod action_macro_message;
+mod action_macro_message_handler;
+
+pub mod utility_types;
+
+#[doc(inline)]
+pub use action_macro_message::{ActionMacroMessage, ActionMacroMessageDiscriminant};
+#[doc(inline)]
+pub use action_macro_message_handler::{ActionMacroMessageData, ActionMacroMessageHandler};
@@ -0,0 +1,219 @@
+use crate::messages::portfolio::document::utility_types::network_interface::FlowType;
+use crate::messages::prelude::*;
+use graph_craft::document::NodeId;
+use serde_json::Value;
+
+/// The key of the object put in place of a node ID in a recorded step, holding the [`NodeReference`] it's replaced by.
+const NODE_REFERENCE_KEY: &str = "$node";
+
+/// A named sequence of editor actions which was recorded so it can be replayed.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(default)]
+pub struct ActionMacro {
+	pub name: String,
+	/// The recorded messages, each serialized separately so a step which no longer deserializes after an update can be skipped without losing the rest.
+	/// The IDs of the nodes of the layers which were selected are replaced by [`NodeReference`]s, so the steps apply to whichever layers are selected when they're replayed.
+	pub steps: Vec<String>,
+}
+
+/// Identifies a node of one of the selected layers by where it is rather than by its ID, which differs between layers and documents.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct NodeReference {
+	/// The position of the layer among the selected layers, from the top of the layer stack.
+	pub layer: usize,
+	/// The name of the node's definition, like "Transform".
+	pub reference: Option<String>,
+	/// How many nodes with the same definition come before it, counting from the layer along the chain of nodes feeding into it.
+	pub occurrence: usize,
+}
+
+/// The nodes which recorded steps can refer to for each selected layer, which are the layer itself followed by the chain of nodes feeding into it, along with the names of their definitions.
+pub type LayerChains = Vec<Vec<(NodeId, Option<String>)>>;
+
+pub fn selected_layer_chains(document: &DocumentMessageHandler) -> LayerChains {
+	let network_interface = &document.network_interface;
+	network_interface
+		.selected_nodes()
+		.selected_layers(document.metadata())
+		.map(|layer| {
+			network_interface
+				.upstream_flow_back_from_nodes(vec![layer.to_node()], &[], FlowType::HorizontalFlow)
+				.map(|node_id| (node_id, network_interface.reference(&node_id, &[]).cloned().flatten()))
+				.collect()
+		})
+		.collect()
+}
+
+impl NodeReference {
+	fn find(node_id: NodeId, chains: &LayerChains) -> Option<Self> {
+		chains.iter().enumerate().find_map(|(layer, chain)| {
+			let position = chain.iter().position(|(id, _)| *id == node_id)?;
+			let reference = chain[position].1.clone();
+			let occurrence = chain[..position].iter().filter(|(_, other)| *other == reference).count();
+			Some(Self { layer, reference, occurrence })
+		})
+	}
+
+	fn resolve(&self, chains: &LayerChains) -> Option<NodeId> {
+		let chain = chains.get(self.layer)?;
+		chain.iter().filter(|(_, reference)| *reference == self.reference).nth(self.occurrence).map(|(node_id, _)| *node_id)
+	}
+}
+
+/// Serializes a recorded message, with the IDs of the nodes of the selected layers replaced by references to them.
+pub fn record_step(message: &Message, chains: &LayerChains) -> Option<String> {
+	let mut value = serde_json::to_value(message).ok()?;
+	replace_node_ids(&mut value, chains);
+	serde_json::to_string(&value).ok()
+}
+
+/// Reads a step of a macro, with its node references replaced by the IDs of the matching nodes of the selected layers.
+pub fn replay_step(step: &str, chains: &LayerChains) -> Result<Message, String> {
+	let mut value = serde_json::from_str(step).map_err(|error| error.to_string())?;
+	resolve_node_references(&mut value, chains)?;
+	serde_json::from_value(value).map_err(|error| error.to_string())
+}
+
+// Node IDs (and layer identifiers, which are the IDs of layer nodes) are serialized as plain numbers, which are random enough not to be mistaken for other numbers in a message
+fn replace_node_ids(value: &mut Value, chains: &LayerChains) {
+	match value {
+		Value::Number(number) => {
+			let Some(reference) = number.as_u64().and_then(|id| NodeReference::find(NodeId(id), chains)) else {
+				return;
+			};
+			let Ok(reference) = serde_json::to_value(reference) else { return };
+			*value = Value::Object([(NODE_REFERENCE_KEY.to_string(), reference)].into_iter().collect());
+		}
+		Value::Array(values) => values.iter_mut().for_each(|value| replace_node_ids(value, chains)),
+		Value::Object(map) => map.values_mut().for_each(|value| replace_node_ids(value, chains)),
+		_ => {}
+	}
+}
+
+fn resolve_node_references(value: &mut Value, chains: &LayerChains) -> Result<(), String> {
+	match value {
+		Value::Object(map) if map.len() == 1 && map.contains_key(NODE_REFERENCE_KEY) => {
+			let reference: NodeReference = serde_json::from_value(map[NODE_REFERENCE_KEY].clone()).map_err(|error| error.to_string())?;
+			let node_id = reference.resolve(chains).ok_or_else(|| format!("the selected layers have no node matching {reference:?}"))?;
+			*value = Value::from(node_id.0);
+		}
+		Value::Array(values) => values.iter_mut().try_for_each(|value| resolve_node_references(value, chains))?,
+		Value::Object(map) => map.values_mut().try_for_each(|value| resolve_node_references(value, chains))?,
+		_ => {}
+	}
+	Ok(())
+}
+
+/// Whether a message dispatched by a keyboard shortcut or widget is an edit that makes sense to replay.
+/// This leaves out messages that depend on where the pointer is, like tool interactions and navigating the viewport, as well as changes to the editor's UI and preferences.
+pub fn is_recordable(message: &Message) -> bool {
+	match message {
+		Message::Portfolio(PortfolioMessage::Document(DocumentMessage::Navigation(_) | DocumentMessage::Overlays(_) | DocumentMessage::Noop)) => false,
+		Message::Portfolio(PortfolioMessage::Document(DocumentMessage::NodeGraph(message))) => !matches!(
+			message,
+			NodeGraphMessage::PointerDown { .. }
+				| NodeGraphMessage::PointerMove { .. }
+				| NodeGraphMessage::PointerOutsideViewport { .. }
+				| NodeGraphMessage::PointerUp
+				| NodeGraphMessage::SendClickTargets
+				| NodeGraphMessage::EndSendClickTargets
+		),
+		Message::Portfolio(
+			PortfolioMessage::MenuBar(_)
+			| PortfolioMessage::SplitView(_)
+			| PortfolioMessage::Spreadsheet(_)
+			| PortfolioMessage::Timeline(_)
+			| PortfolioMessage::ColorHarmony(_)
+			| PortfolioMessage::Plugins(_)
+			| PortfolioMessage::Console(_)
+			| PortfolioMessage::Collaboration(_)
+			| PortfolioMessage::Compare(_)
+			| PortfolioMessage::Library(_)
+			| PortfolioMessage::DataMerge(_),
+		) => false,
+		Message::Portfolio(_) => true,
+		Message::Tool(message) => matches!(
+			message,
+			ToolMessage::ActivateTool { .. }
+				| ToolMessage::ActivateToolSelect
+				| ToolMessage::ActivateToolArtboard
+				| ToolMessage::ActivateToolNavigate
+				| ToolMessage::ActivateToolEyedropper
+				| ToolMessage::ActivateToolText
+				| ToolMessage::ActivateToolFill
+				| ToolMessage::ActivateToolGradient
+				| ToolMessage::ActivateToolPath
+				| ToolMessage::ActivateToolPen
+				| ToolMessage::ActivateToolFreehand
+				| ToolMessage::ActivateToolSpline
+				| ToolMessage::ActivateToolLine
+				| ToolMessage::ActivateToolRectangle
+				| ToolMessage::ActivateToolEllipse
+				| ToolMessage::ActivateToolPolygon
+				| ToolMessage::ActivateToolBrush
+				| ToolMessage::ActivateToolImaginate
+				| ToolMessage::ResetColors
+				| ToolMessage::SelectPrimaryColor { .. }
+				| ToolMessage::SelectRandomPrimaryColor
+				| ToolMessage::SelectSecondaryColor { .. }
+				| ToolMessage::SwapColors
+		),
+		Message::Frontend(FrontendMessage::TriggerPaste) => true,
+		_ => false,
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use graph_craft::document::value::TaggedValue;
+
+	fn chains(layers: &[&[(u64, &str)]]) -> LayerChains {
+		layers
+			.iter()
+			.map(|chain| chain.iter().map(|&(id, reference)| (NodeId(id), Some(reference.to_string()))).collect())
+			.collect()
+	}
+
+	fn set_input(node_id: u64) -> Message {
+		NodeGraphMessage::SetInputValue {
+			node_id: NodeId(node_id),
+			input_index: 1,
+			value: TaggedValue::F64(2.),
+		}
+		.into()
+	}
+
+	#[test]
+	fn replays_on_the_matching_node_of_other_layers() {
+		let recorded = chains(&[&[(1001, "Merge"), (1002, "Transform"), (1003, "Fill")]]);
+		let step = record_step(&set_input(1003), &recorded).unwrap();
+		assert!(!step.contains("1003"));
+
+		let replayed = chains(&[&[(2001, "Merge"), (2002, "Fill")]]);
+		assert_eq!(replay_step(&step, &replayed), Ok(set_input(2002)));
+	}
+
+	#[test]
+	fn counts_nodes_with_the_same_definition() {
+		let recorded = chains(&[&[(1001, "Merge"), (1002, "Transform"), (1003, "Transform")]]);
+		let step = record_step(&set_input(1003), &recorded).unwrap();
+
+		let replayed = chains(&[&[(2001, "Merge"), (2002, "Transform"), (2003, "Fill"), (2004, "Transform")]]);
+		assert_eq!(replay_step(&step, &replayed), Ok(set_input(2004)));
+	}
+
+	#[test]
+	fn keeps_the_ids_of_nodes_outside_the_selected_layers() {
+		let recorded = chains(&[&[(1001, "Merge")]]);
+		let step = record_step(&set_input(5000), &recorded).unwrap();
+		assert_eq!(replay_step(&step, &chains(&[])), Ok(set_input(5000)));
+	}
+
+	#[test]
+	fn fails_without_a_matching_node() {
+		let recorded = chains(&[&[(1001, "Merge"), (1002, "Transform")], &[(1003, "Merge")]]);
+		let step = record_step(&set_input(1003), &recorded).unwrap();
+		assert!(replay_step(&step, &chains(&[&[(2001, "Merge"), (2002, "Transform")]])).is_err());
+	}
+}
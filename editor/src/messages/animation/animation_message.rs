@@ -10,6 +10,13 @@ pub enum AnimationMessage {
 	DisableLivePreview,
 	RestartAnimation,
 	SetFrameIndex(f64),
+	/// Moves the animation to the given time in seconds, keeping it playing or paused.
+	SetAnimationTime(f64),
+	/// Pauses playback and moves one frame forward at the current frame rate.
+	StepForward,
+	/// Pauses playback and moves one frame backward at the current frame rate.
+	StepBackward,
+	SetFramesPerSecond(f64),
 	SetTime(f64),
 	UpdateTime,
 	IncrementFrameCounter,
@@ -24,7 +24,7 @@ enum AnimationState {
 	},
 }
 
-#[derive(Default, Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AnimationMessageHandler {
 	/// Used to re-send the UI on the next frame after playback starts
 	live_preview_recently_zero: bool,
@@ -33,13 +33,28 @@ pub struct AnimationMessageHandler {
 	animation_state: AnimationState,
 	fps: f64,
 	animation_time_mode: AnimationTimeMode,
+	/// The last frame submitted for rendering during playback, so the graph is only re-evaluated once per frame at the current frame rate
+	last_rendered_frame: Option<i64>,
+}
+impl Default for AnimationMessageHandler {
+	fn default() -> Self {
+		Self {
+			live_preview_recently_zero: false,
+			timestamp: 0.,
+			frame_index: 0.,
+			animation_state: AnimationState::default(),
+			fps: 30.,
+			animation_time_mode: AnimationTimeMode::default(),
+			last_rendered_frame: None,
+		}
+	}
 }
 impl AnimationMessageHandler {
 	pub(crate) fn timing_information(&self) -> TimingInformation {
 		let animation_time = self.timestamp - self.animation_start();
 		let animation_time = match self.animation_time_mode {
-			AnimationTimeMode::TimeBased => Duration::from_millis(animation_time as u64),
-			AnimationTimeMode::FrameBased => Duration::from_secs((self.frame_index / self.fps) as u64),
+			AnimationTimeMode::TimeBased => Duration::from_secs_f64(animation_time.max(0.) / 1000.),
+			AnimationTimeMode::FrameBased => Duration::from_secs_f64((self.frame_index / self.fps).max(0.)),
 		};
 		TimingInformation { time: self.timestamp, animation_time }
 	}
@@ -55,6 +70,16 @@ impl AnimationMessageHandler {
 	pub fn is_playing(&self) -> bool {
 		matches!(self.animation_state, AnimationState::Playing { .. })
 	}
+
+	pub fn fps(&self) -> f64 {
+		self.fps
+	}
+
+	/// The index of the frame shown at the current animation time, at the current frame rate.
+	fn current_frame(&self) -> i64 {
+		// Allow for rounding error when the time lands exactly on a frame
+		(self.timing_information().animation_time.as_secs_f64() * self.fps + 1e-6).floor() as i64
+	}
 }
 
 impl MessageHandler<AnimationMessage, ()> for AnimationMessageHandler {
@@ -87,6 +112,32 @@ impl MessageHandler<AnimationMessage, ()> for AnimationMessageHandler {
 				// Update the restart and pause/play buttons
 				responses.add(PortfolioMessage::UpdateDocumentWidgets);
 			}
+			AnimationMessage::SetAnimationTime(seconds) => {
+				let start = self.timestamp - seconds.max(0.) * 1000.;
+				self.animation_state = match self.animation_state {
+					AnimationState::Playing { .. } => AnimationState::Playing { start },
+					_ => AnimationState::Paused { start, pause_time: self.timestamp },
+				};
+				self.frame_index = seconds.max(0.) * self.fps;
+				responses.add(PortfolioMessage::SubmitActiveGraphRender);
+				// Update the restart and pause/play buttons
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			AnimationMessage::StepForward | AnimationMessage::StepBackward => {
+				let step = if message == AnimationMessage::StepForward { 1 } else { -1 };
+				let frame = (self.current_frame() + step).max(0);
+
+				responses.add(AnimationMessage::DisableLivePreview);
+				responses.add(AnimationMessage::SetAnimationTime(frame as f64 / self.fps));
+			}
+			AnimationMessage::SetFramesPerSecond(fps) => {
+				self.fps = fps.clamp(1., 120.);
+				self.frame_index = self.timing_information().animation_time.as_secs_f64() * self.fps;
+				self.last_rendered_frame = None;
+
+				// Update the frame rate input
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
 			AnimationMessage::SetTime(time) => {
 				self.timestamp = time;
 				responses.add(AnimationMessage::UpdateTime);
@@ -99,7 +150,12 @@ impl MessageHandler<AnimationMessage, ()> for AnimationMessageHandler {
 			}
 			AnimationMessage::UpdateTime => {
 				if self.is_playing() {
-					responses.add(PortfolioMessage::SubmitActiveGraphRender);
+					// Throttle re-evaluation during playback to once per frame at the current frame rate
+					let frame = self.current_frame();
+					if self.last_rendered_frame != Some(frame) {
+						self.last_rendered_frame = Some(frame);
+						responses.add(PortfolioMessage::SubmitActiveGraphRender);
+					}
 
 					if self.live_preview_recently_zero {
 						// Update the restart and pause/play buttons
@@ -129,5 +185,7 @@ impl MessageHandler<AnimationMessage, ()> for AnimationMessageHandler {
 		ToggleLivePreview,
 		SetFrameIndex,
 		RestartAnimation,
+		StepForward,
+		StepBackward,
 	);
 }
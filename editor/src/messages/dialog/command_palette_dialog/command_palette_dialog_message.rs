@@ -0,0 +1,7 @@
+use crate::messages::prelude::*;
+
+#[impl_message(Message, DialogMessage, CommandPaletteDialog)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum CommandPaletteDialogMessage {
+	Search { query: String },
+}
@@ -0,0 +1,234 @@
+use crate::messages::input_mapper::utility_types::input_keyboard::KeysGroup;
+use crate::messages::input_mapper::utility_types::macros::action_keys;
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::node_graph::document_node_definitions::collect_node_types;
+use crate::messages::prelude::*;
+use crate::messages::tool::utility_types::{ToolAvailability, ToolMetadata, list_tools_in_groups, tool_type_to_activate_tool_message};
+
+/// The most results shown at once, since the list of every command is too long to be useful without a search query.
+const MAX_RESULTS: usize = 12;
+
+/// A single action which can be run from the command palette.
+#[derive(Debug, Clone)]
+pub struct PaletteCommand {
+	pub label: String,
+	pub category: String,
+	pub icon: Option<String>,
+	pub shortcut: String,
+	pub message: Message,
+}
+
+/// A dialog to fuzzy search and run any menu command, tool, or node insertion.
+#[derive(Debug, Clone, Default)]
+pub struct CommandPaletteDialogMessageHandler {
+	pub query: String,
+	pub commands: Vec<PaletteCommand>,
+}
+
+impl MessageHandler<CommandPaletteDialogMessage, ()> for CommandPaletteDialogMessageHandler {
+	fn process_message(&mut self, message: CommandPaletteDialogMessage, responses: &mut VecDeque<Message>, _data: ()) {
+		match message {
+			CommandPaletteDialogMessage::Search { query } => self.query = query,
+		}
+
+		self.send_dialog_to_frontend(responses);
+	}
+
+	advertise_actions! {CommandPaletteDialogUpdate;}
+}
+
+impl CommandPaletteDialogMessageHandler {
+	/// Gathers the commands which are currently available, along with their keyboard shortcuts.
+	pub fn collect_commands(portfolio: &PortfolioMessageHandler, action_input_mapping: &impl Fn(&MessageDiscriminant) -> Vec<KeysGroup>) -> Vec<PaletteCommand> {
+		let mut commands = Vec::new();
+
+		// Menu bar commands, skipping the disabled state of the root menus since it doesn't reflect the availability of their entries
+		if let Layout::MenuLayout(menu_layout) = portfolio.menu_bar_layout() {
+			for root in menu_layout.layout {
+				collect_menu_commands(root.children.0.iter().flatten(), &root.label, action_input_mapping, &mut commands);
+			}
+		}
+
+		let Some(document) = portfolio.active_document() else { return commands };
+
+		// Tools
+		for tool in list_tools_in_groups().into_iter().flatten() {
+			let ToolAvailability::Available(tool) = tool else { continue };
+
+			let tool_type = tool.tool_type();
+			commands.push(PaletteCommand {
+				label: tool.tooltip(),
+				category: "Tool".into(),
+				icon: Some(tool.icon_name()),
+				shortcut: action_keys!(tool_type_to_activate_tool_message(tool_type))
+					.map(|mut keys| keys.to_keys(action_input_mapping))
+					.unwrap_or_default(),
+				message: ToolMessage::ActivateTool { tool_type }.into(),
+			});
+		}
+
+		// Node insertion into the selected layers
+		let selected_layers = document.network_interface.selected_nodes().selected_layers(document.metadata()).collect::<Vec<_>>();
+		if !selected_layers.is_empty() {
+			for node_type in collect_node_types() {
+				let mut followups = vec![DocumentMessage::AddTransaction.into()];
+				followups.extend(selected_layers.iter().map(|&layer| {
+					NodeGraphMessage::CreateNodeInLayerNoTransaction {
+						node_type: node_type.name.clone(),
+						layer,
+					}
+					.into()
+				}));
+				followups.extend([PropertiesPanelMessage::Refresh.into(), NodeGraphMessage::RunDocumentGraph.into()]);
+
+				commands.push(PaletteCommand {
+					label: format!("Add Node › {}", node_type.name),
+					category: node_type.category,
+					icon: Some("Node".into()),
+					shortcut: String::new(),
+					message: Message::Batched(followups.into_boxed_slice()),
+				});
+			}
+		}
+
+		commands
+	}
+
+	/// The commands matching the search query, best matches first.
+	fn results(&self) -> Vec<&PaletteCommand> {
+		let mut results = self
+			.commands
+			.iter()
+			.filter_map(|command| fuzzy_match_score(&self.query, &command.label).map(|score| (score, command)))
+			.collect::<Vec<_>>();
+		results.sort_by(|(a_score, a), (b_score, b)| b_score.cmp(a_score).then(a.label.len().cmp(&b.label.len())));
+
+		results.into_iter().take(MAX_RESULTS).map(|(_, command)| command).collect()
+	}
+}
+
+fn collect_menu_commands<'a>(entries: impl Iterator<Item = &'a MenuBarEntry>, path: &str, action_input_mapping: &impl Fn(&MessageDiscriminant) -> Vec<KeysGroup>, commands: &mut Vec<PaletteCommand>) {
+	for entry in entries {
+		if entry.disabled || entry.label.is_empty() {
+			continue;
+		}
+
+		let label = format!("{path} › {}", entry.label);
+		if !entry.children.0.is_empty() {
+			collect_menu_commands(entry.children.0.iter().flatten(), &label, action_input_mapping, commands);
+			continue;
+		}
+
+		let Widget::InvisibleStandinInput(input) = &entry.action.widget else { continue };
+		let message = (input.on_update.callback)(&());
+		if message == Message::NoOp {
+			continue;
+		}
+
+		commands.push(PaletteCommand {
+			label,
+			category: path.split(" › ").next().unwrap_or_default().to_string(),
+			icon: entry.icon.clone(),
+			shortcut: entry.shortcut.clone().map(|mut keys| keys.to_keys(action_input_mapping)).unwrap_or_default(),
+			message,
+		});
+	}
+}
+
+/// Scores how well the query matches the text as a case-insensitive subsequence, favoring matches at the starts of words and runs of consecutive characters.
+/// Returns `None` if the text doesn't contain every character of the query in order.
+fn fuzzy_match_score(query: &str, text: &str) -> Option<u32> {
+	let text = text.chars().collect::<Vec<_>>();
+
+	let mut score = 0;
+	let mut position = 0;
+	let mut previous_match = None;
+	for query_char in query.chars().filter(|c| !c.is_whitespace()) {
+		let found = (position..text.len()).find(|&index| text[index].to_lowercase().eq(query_char.to_lowercase()))?;
+
+		let word_start = found == 0 || !text[found - 1].is_alphanumeric() || (text[found].is_uppercase() && text[found - 1].is_lowercase());
+		let consecutive = found > 0 && previous_match == Some(found - 1);
+		score += 1 + if word_start { 8 } else { 0 } + if consecutive { 4 } else { 0 };
+
+		previous_match = Some(found);
+		position = found + 1;
+	}
+
+	Some(score)
+}
+
+impl DialogLayoutHolder for CommandPaletteDialogMessageHandler {
+	const ICON: &'static str = "ZoomIn";
+	const TITLE: &'static str = "Command Palette";
+
+	fn layout_buttons(&self) -> Layout {
+		let widgets = vec![TextButton::new("Cancel").on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder()];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
+	}
+}
+
+impl LayoutHolder for CommandPaletteDialogMessageHandler {
+	fn layout(&self) -> Layout {
+		let search = vec![
+			TextInput::new(&self.query)
+				.label(Some("Search".into()))
+				.min_width(400)
+				.on_update(|text_input: &TextInput| CommandPaletteDialogMessage::Search { query: text_input.value.clone() }.into())
+				.widget_holder(),
+		];
+
+		let mut layout = vec![LayoutGroup::Row { widgets: search }];
+
+		let results = self.results();
+		if results.is_empty() {
+			layout.push(LayoutGroup::Row {
+				widgets: vec![TextLabel::new("No matching commands").italic(true).widget_holder()],
+			});
+		}
+
+		// The best match is emphasized since it's run by pressing Enter, while the arrow keys move between the results
+		for (index, command) in results.into_iter().enumerate() {
+			let message = command.message.clone();
+			let widgets = vec![
+				TextButton::new(&command.label)
+					.icon(command.icon.clone())
+					.emphasized(index == 0)
+					.flush(true)
+					.min_width(320)
+					.tooltip(&command.category)
+					.on_update(move |_| DialogMessage::CloseDialogAndThen { followups: vec![message.clone()] }.into())
+					.widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextLabel::new(&command.shortcut).table_align(true).widget_holder(),
+			];
+			layout.push(LayoutGroup::Row { widgets });
+		}
+
+		Layout::WidgetLayout(WidgetLayout::new(layout))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::fuzzy_match_score;
+
+	#[test]
+	fn fuzzy_match_requires_subsequence() {
+		assert!(fuzzy_match_score("udo", "Undo").is_some());
+		assert!(fuzzy_match_score("UNDO", "Undo").is_some());
+		assert!(fuzzy_match_score("odnu", "Undo").is_none());
+		assert_eq!(fuzzy_match_score("", "Undo"), Some(0));
+	}
+
+	#[test]
+	fn fuzzy_match_prefers_word_starts() {
+		let word_starts = fuzzy_match_score("zi", "Zoom In").unwrap();
+		let mid_word = fuzzy_match_score("zi", "Resize Image").unwrap();
+		assert!(word_starts > mid_word);
+
+		let consecutive = fuzzy_match_score("exp", "Export").unwrap();
+		let scattered = fuzzy_match_score("exp", "Flex Shape").unwrap();
+		assert!(consecutive > scattered);
+	}
+}
@@ -0,0 +1,7 @@
+mod command_palette_dialog_message;
+mod command_palette_dialog_message_handler;
+
+#[doc(inline)]
+pub use command_palette_dialog_message::{CommandPaletteDialogMessage, CommandPaletteDialogMessageDiscriminant};
+#[doc(inline)]
+pub use command_palette_dialog_message_handler::{CommandPaletteDialogMessageHandler, PaletteCommand};
@@ -0,0 +1,9 @@
+use crate::messages::prelude::*;
+use graphene_core::Color;
+
+#[impl_message(Message, DialogMessage, ContrastCheckDialog)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum ContrastCheckDialogMessage {
+	/// The color the frontend sampled from the rendered artwork beneath the text, in linear space, or `None` if the text isn't within view.
+	BackgroundSampled { color: Option<Color> },
+}
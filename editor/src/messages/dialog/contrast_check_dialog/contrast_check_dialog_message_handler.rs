@@ -0,0 +1,177 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::*;
+use graphene_core::Color;
+use graphene_core::vector::style::FillChoice;
+
+/// The minimum contrast ratios required by the WCAG 2 success criteria, for normal and large text.
+const AA_NORMAL_TEXT: f32 = 4.5;
+const AA_LARGE_TEXT: f32 = 3.;
+const AAA_NORMAL_TEXT: f32 = 7.;
+const AAA_LARGE_TEXT: f32 = 4.5;
+/// WCAG considers text large from 18 points, or 14 points if bold (at 4/3 pixels per point).
+const LARGE_TEXT_SIZE: f64 = 24.;
+const LARGE_BOLD_TEXT_SIZE: f64 = 18.66;
+
+/// A dialog reporting whether a text layer's fill contrasts enough with the artwork beneath it to pass the WCAG AA and AAA levels.
+#[derive(Debug, Clone, Default)]
+pub struct ContrastCheckDialogMessageHandler {
+	/// The text's fill, in linear space.
+	pub foreground: Color,
+	/// The color sampled beneath the text, in linear space.
+	pub background: Color,
+	/// Whether the text is large enough for the lower contrast requirements of large text.
+	pub large_text: bool,
+}
+
+impl MessageHandler<ContrastCheckDialogMessage, ()> for ContrastCheckDialogMessageHandler {
+	fn process_message(&mut self, message: ContrastCheckDialogMessage, responses: &mut VecDeque<Message>, _data: ()) {
+		match message {
+			ContrastCheckDialogMessage::BackgroundSampled { color } => {
+				let Some(color) = color else {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Unable to check contrast".into(),
+						description: "Pan the text layer into view so the artwork beneath it can be sampled.".into(),
+					});
+					return;
+				};
+				self.background = color;
+			}
+		}
+
+		self.send_dialog_to_frontend(responses);
+	}
+
+	advertise_actions! {ContrastCheckDialogUpdate;}
+}
+
+impl ContrastCheckDialogMessageHandler {
+	/// Whether text of the given font size and style counts as large text.
+	pub fn is_large_text(font_size: f64, font_style: &str) -> bool {
+		// Font styles end in their weight, like "Bold Italic (700)"
+		let weight = font_style.rsplit_once('(').and_then(|(_, weight)| weight.trim_end_matches(')').parse::<u32>().ok()).unwrap_or(400);
+		font_size >= LARGE_TEXT_SIZE || (weight >= 700 && font_size >= LARGE_BOLD_TEXT_SIZE)
+	}
+
+	fn contrast_ratio(&self) -> f32 {
+		// A translucent fill lets the background show through
+		let alpha = self.foreground.a();
+		let blend = |foreground: f32, background: f32| foreground * alpha + background * (1. - alpha);
+		let foreground = Color::from_rgbaf32_unchecked(
+			blend(self.foreground.r(), self.background.r()),
+			blend(self.foreground.g(), self.background.g()),
+			blend(self.foreground.b(), self.background.b()),
+			1.,
+		);
+
+		contrast_ratio(foreground, self.background)
+	}
+}
+
+impl DialogLayoutHolder for ContrastCheckDialogMessageHandler {
+	const ICON: &'static str = "NodeText";
+	const TITLE: &'static str = "Text Contrast";
+
+	fn layout_buttons(&self) -> Layout {
+		let widgets = vec![TextButton::new("OK").emphasized(true).on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder()];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
+	}
+}
+
+impl LayoutHolder for ContrastCheckDialogMessageHandler {
+	fn layout(&self) -> Layout {
+		let color_row = |label: &str, color: Color| {
+			let gamma = color.to_gamma_srgb();
+			LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new(label).table_align(true).min_width(120).widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					ColorInput::new(FillChoice::Solid(gamma)).allow_none(false).disabled(true).widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					TextLabel::new(format!("#{}", gamma.to_rgb_hex_srgb_from_gamma())).widget_holder(),
+				],
+			}
+		};
+
+		let ratio = self.contrast_ratio();
+		let (aa, aaa) = if self.large_text { (AA_LARGE_TEXT, AAA_LARGE_TEXT) } else { (AA_NORMAL_TEXT, AAA_NORMAL_TEXT) };
+		let level_row = |label: &str, minimum: f32| {
+			let passes = ratio >= minimum;
+			LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new(label).table_align(true).min_width(120).widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					IconLabel::new(if passes { "Checkmark" } else { "Warning" }).widget_holder(),
+					Separator::new(SeparatorType::Related).widget_holder(),
+					TextLabel::new(format!("{} (needs {minimum}:1)", if passes { "Pass" } else { "Fail" })).bold(true).widget_holder(),
+				],
+			}
+		};
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![
+			color_row("Text", self.foreground),
+			color_row("Background", self.background),
+			LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new("Contrast Ratio").table_align(true).min_width(120).widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					TextLabel::new(format!("{ratio:.2}:1")).bold(true).widget_holder(),
+				],
+			},
+			level_row("WCAG AA", aa),
+			level_row("WCAG AAA", aaa),
+			LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new(if self.large_text {
+						"Judged as large text (at least 24 px, or 18.66 px if bold)"
+					} else {
+						"Judged as normal text (under 24 px, or 18.66 px if bold)"
+					})
+					.italic(true)
+					.widget_holder(),
+				],
+			},
+		]))
+	}
+}
+
+/// The relative luminance of an opaque linear space color, as defined by WCAG 2.
+pub fn relative_luminance(color: Color) -> f32 {
+	0.2126 * color.r() + 0.7152 * color.g() + 0.0722 * color.b()
+}
+
+/// The WCAG 2 contrast ratio between two opaque linear space colors, from 1 (none) to 21 (black on white).
+pub fn contrast_ratio(a: Color, b: Color) -> f32 {
+	let (a, b) = (relative_luminance(a), relative_luminance(b));
+	(a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn black_on_white_has_the_highest_contrast() {
+		assert!((contrast_ratio(Color::BLACK, Color::WHITE) - 21.).abs() < 1e-4);
+		assert!((contrast_ratio(Color::WHITE, Color::BLACK) - 21.).abs() < 1e-4);
+		assert!((contrast_ratio(Color::WHITE, Color::WHITE) - 1.).abs() < 1e-4);
+	}
+
+	#[test]
+	fn mid_gray_on_white_fails_aa_for_normal_text() {
+		// #777777, a commonly cited example which just misses the 4.5:1 minimum
+		let gray = Color::from_rgb8_srgb(0x77, 0x77, 0x77);
+		let ratio = contrast_ratio(gray, Color::WHITE);
+
+		assert!(ratio < AA_NORMAL_TEXT);
+		assert!(ratio > AA_LARGE_TEXT);
+	}
+
+	#[test]
+	fn bold_text_is_large_at_a_smaller_size() {
+		assert!(ContrastCheckDialogMessageHandler::is_large_text(24., "Regular (400)"));
+		assert!(!ContrastCheckDialogMessageHandler::is_large_text(20., "Regular (400)"));
+		assert!(ContrastCheckDialogMessageHandler::is_large_text(20., "Bold (700)"));
+		assert!(!ContrastCheckDialogMessageHandler::is_large_text(16., "Bold Italic (700)"));
+	}
+}
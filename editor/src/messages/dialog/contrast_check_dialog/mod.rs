@@ -0,0 +1,7 @@
+mod contrast_check_dialog_message;
+mod contrast_check_dialog_message_handler;
+
+#[doc(inline)]
+pub use contrast_check_dialog_message::{ContrastCheckDialogMessage, ContrastCheckDialogMessageDiscriminant};
+#[doc(inline)]
+pub use contrast_check_dialog_message_handler::ContrastCheckDialogMessageHandler;
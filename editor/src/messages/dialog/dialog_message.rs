@@ -1,21 +1,35 @@
 use crate::messages::prelude::*;
+use graphene_core::text::Font;
+use graphene_core::vector::style::GradientStops;
 
 #[impl_message(Message, Dialog)]
 #[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub enum DialogMessage {
 	// Sub-messages
 	#[child]
+	CommandPaletteDialog(CommandPaletteDialogMessage),
+	#[child]
+	ContrastCheckDialog(ContrastCheckDialogMessage),
+	#[child]
 	ExportDialog(ExportDialogMessage),
 	#[child]
+	FindReplaceDialog(FindReplaceDialogMessage),
+	#[child]
+	GlyphPaletteDialog(GlyphPaletteDialogMessage),
+	#[child]
 	NewDocumentDialog(NewDocumentDialogMessage),
 	#[child]
 	PreferencesDialog(PreferencesDialogMessage),
+	#[child]
+	RecolorArtworkDialog(RecolorArtworkDialogMessage),
 
 	// Messages
 	CloseAllDocumentsWithConfirmation,
 	CloseDialogAndThen {
 		followups: Vec<Message>,
 	},
+	/// Sent by the frontend when a dialog is closed without using its buttons, such as by pressing Escape or clicking outside of it.
+	Dismissed,
 	DisplayDialogError {
 		title: String,
 		description: String,
@@ -25,14 +39,37 @@ pub enum DialogMessage {
 		localized_commit_date: String,
 		localized_commit_year: String,
 	},
+	RequestCommandPaletteDialog,
+	RequestContrastCheckDialog,
 	RequestComingSoonDialog {
 		issue: Option<u32>,
 	},
 	RequestDemoArtworkDialog,
 	RequestExportDialog,
+	RequestFindReplaceDialog,
+	RequestGlyphPaletteDialog {
+		font: Font,
+	},
+	RequestKeyboardShortcutsDialog,
+	RequestKeyboardShortcutsDialogWithRejectedShortcut {
+		action: String,
+		shortcut: String,
+		error: String,
+	},
 	RequestLicensesDialogWithLocalizedCommitDate {
 		localized_commit_year: String,
 	},
 	RequestNewDocumentDialog,
 	RequestPreferencesDialog,
+	RequestRecolorArtworkDialog,
+	RequestSaveActionMacroDialog {
+		name: String,
+	},
+	RequestSaveGradientPresetDialog {
+		name: String,
+		stops: GradientStops,
+	},
+	RequestSaveWorkspaceLayoutDialog {
+		name: String,
+	},
 }
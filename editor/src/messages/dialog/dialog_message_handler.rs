@@ -1,28 +1,52 @@
-use super::simple_dialogs::{self, AboutGraphiteDialog, ComingSoonDialog, DemoArtworkDialog, LicensesDialog};
+use super::simple_dialogs::{
+	self, AboutGraphiteDialog, ComingSoonDialog, DemoArtworkDialog, KeyboardShortcutsDialog, LicensesDialog, RejectedShortcut, SaveActionMacroDialog, SaveGradientPresetDialog,
+	SaveWorkspaceLayoutDialog,
+};
+use crate::consts::FILE_SAVE_SUFFIX;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::graph_modification_utils;
+use graphene_core::Color;
+use graphene_core::text::font_characters;
 
 pub struct DialogMessageData<'a> {
 	pub portfolio: &'a PortfolioMessageHandler,
 	pub preferences: &'a PreferencesMessageHandler,
+	pub key_mapping: &'a KeyMappingMessageHandler,
+	pub animation: &'a AnimationMessageHandler,
 }
 
 /// Stores the dialogs which require state. These are the ones that have their own message handlers, and are not the ones defined in `simple_dialogs`.
 #[derive(Debug, Default, Clone)]
 pub struct DialogMessageHandler {
+	command_palette_dialog: CommandPaletteDialogMessageHandler,
+	contrast_check_dialog: ContrastCheckDialogMessageHandler,
 	export_dialog: ExportDialogMessageHandler,
+	find_replace_dialog: FindReplaceDialogMessageHandler,
+	glyph_palette_dialog: GlyphPaletteDialogMessageHandler,
 	new_document_dialog: NewDocumentDialogMessageHandler,
 	preferences_dialog: PreferencesDialogMessageHandler,
+	recolor_artwork_dialog: RecolorArtworkDialogMessageHandler,
 }
 
 impl MessageHandler<DialogMessage, DialogMessageData<'_>> for DialogMessageHandler {
 	fn process_message(&mut self, message: DialogMessage, responses: &mut VecDeque<Message>, data: DialogMessageData) {
-		let DialogMessageData { portfolio, preferences } = data;
+		let DialogMessageData {
+			portfolio,
+			preferences,
+			key_mapping,
+			animation,
+		} = data;
 
 		match message {
+			DialogMessage::CommandPaletteDialog(message) => self.command_palette_dialog.process_message(message, responses, ()),
+			DialogMessage::ContrastCheckDialog(message) => self.contrast_check_dialog.process_message(message, responses, ()),
 			DialogMessage::ExportDialog(message) => self.export_dialog.process_message(message, responses, ExportDialogMessageData { portfolio }),
+			DialogMessage::FindReplaceDialog(message) => self.find_replace_dialog.process_message(message, responses, FindReplaceDialogMessageData { portfolio }),
+			DialogMessage::GlyphPaletteDialog(message) => self.glyph_palette_dialog.process_message(message, responses, ()),
 			DialogMessage::NewDocumentDialog(message) => self.new_document_dialog.process_message(message, responses, ()),
 			DialogMessage::PreferencesDialog(message) => self.preferences_dialog.process_message(message, responses, PreferencesDialogMessageData { preferences }),
+			DialogMessage::RecolorArtworkDialog(message) => self.recolor_artwork_dialog.process_message(message, responses, ()),
 
 			DialogMessage::CloseAllDocumentsWithConfirmation => {
 				let dialog = simple_dialogs::CloseAllDocumentsDialog {
@@ -39,6 +63,10 @@ impl MessageHandler<DialogMessage, DialogMessageData<'_>> for DialogMessageHandl
 				// If it comes before, the dialog reopens (and appears to not close at all).
 				responses.add(FrontendMessage::DisplayDialogDismiss);
 			}
+			DialogMessage::Dismissed => {
+				self.find_replace_dialog
+					.process_message(FindReplaceDialogMessage::Dismissed, responses, FindReplaceDialogMessageData { portfolio });
+			}
 			DialogMessage::DisplayDialogError { title, description } => {
 				let dialog = simple_dialogs::ErrorDialog { title, description };
 				dialog.send_dialog_to_frontend(responses);
@@ -59,6 +87,47 @@ impl MessageHandler<DialogMessage, DialogMessageData<'_>> for DialogMessageHandl
 
 				dialog.send_dialog_to_frontend(responses);
 			}
+			DialogMessage::RequestCommandPaletteDialog => {
+				let action_input_mapping = |action: &MessageDiscriminant| key_mapping.action_input_mapping(action);
+				self.command_palette_dialog = CommandPaletteDialogMessageHandler {
+					query: String::new(),
+					commands: CommandPaletteDialogMessageHandler::collect_commands(portfolio, &action_input_mapping),
+				};
+				self.command_palette_dialog.send_dialog_to_frontend(responses);
+			}
+			DialogMessage::RequestContrastCheckDialog => {
+				let Some(document) = portfolio.active_document() else { return };
+				let error = |description: &str| DialogMessage::DisplayDialogError {
+					title: "Unable to check contrast".into(),
+					description: description.into(),
+				};
+
+				let mut selected_layers = document.network_interface.selected_nodes().selected_layers(document.metadata());
+				let layer = selected_layers.next().filter(|_| selected_layers.next().is_none());
+				let Some((layer, (_, font, typesetting))) = layer.and_then(|layer| Some((layer, graph_modification_utils::get_text(layer, &document.network_interface)?))) else {
+					responses.add(error("Select a single text layer to check its contrast."));
+					return;
+				};
+				let Some(foreground) = graph_modification_utils::get_fill_color(layer, &document.network_interface) else {
+					responses.add(error("Give the text layer a solid fill color to check its contrast."));
+					return;
+				};
+				let Some([top_left, bottom_right]) = document.metadata().bounding_box_viewport(layer) else {
+					responses.add(error("The text layer has no visible area to check the contrast of."));
+					return;
+				};
+
+				self.contrast_check_dialog = ContrastCheckDialogMessageHandler {
+					foreground,
+					background: Color::WHITE,
+					large_text: ContrastCheckDialogMessageHandler::is_large_text(typesetting.font_size, &font.font_style),
+				};
+				// The frontend samples the rendered artwork beneath the text and replies with `ContrastCheckDialogMessage::BackgroundSampled`
+				responses.add(FrontendMessage::TriggerSampleContrastBackground {
+					region: [top_left.into(), bottom_right.into()],
+					text_color: format!("#{}", foreground.to_rgb_hex_srgb()),
+				});
+			}
 			DialogMessage::RequestComingSoonDialog { issue } => {
 				let dialog = ComingSoonDialog { issue };
 				dialog.send_dialog_to_frontend(responses);
@@ -86,9 +155,51 @@ impl MessageHandler<DialogMessage, DialogMessageData<'_>> for DialogMessageHandl
 
 					self.export_dialog.artboards = artboards;
 					self.export_dialog.has_selection = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().is_some();
+					if let Some(region) = document.export_region.or_else(|| document.network_interface.document_bounds_document_space(true)) {
+						self.export_dialog.custom_region = region;
+					}
+
+					// A frame sequence covers the frames within the timeline's loop range
+					let frames_per_second = animation.fps();
+					let (loop_in, loop_out) = document.timeline.loop_range();
+					self.export_dialog.frames_per_second = frames_per_second;
+					self.export_dialog.first_frame = (loop_in * frames_per_second).round() as u32;
+					self.export_dialog.last_frame = ((loop_out * frames_per_second).round() as u32).saturating_sub(1).max(self.export_dialog.first_frame);
+					self.export_dialog.file_name_pattern = format!("{}_####", document.name.trim_end_matches(FILE_SAVE_SUFFIX));
 					self.export_dialog.send_dialog_to_frontend(responses);
 				}
 			}
+			DialogMessage::RequestFindReplaceDialog => {
+				if let Some(document) = portfolio.active_document() {
+					self.find_replace_dialog.open(document, responses);
+				}
+			}
+			DialogMessage::RequestGlyphPaletteDialog { font } => {
+				let font_cache = &portfolio.persistent_data.font_cache;
+				let Some(data) = font_cache.get(&font) else {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Unable to show the glyphs".into(),
+						description: "The font of the text hasn't finished loading yet.".into(),
+					});
+					return;
+				};
+
+				self.glyph_palette_dialog = GlyphPaletteDialogMessageHandler {
+					font_name: format!("{} {}", font.font_family, font.font_style),
+					font_url: font_cache.resolve_font(&font).and_then(|font| font_cache.get_preview_url(font)).cloned(),
+					characters: font_characters(data),
+					query: String::new(),
+				};
+				self.glyph_palette_dialog.send_dialog_to_frontend(responses);
+			}
+			DialogMessage::RequestKeyboardShortcutsDialog => {
+				let dialog = KeyboardShortcutsDialog::new(preferences, None);
+				dialog.send_dialog_to_frontend(responses);
+			}
+			DialogMessage::RequestKeyboardShortcutsDialogWithRejectedShortcut { action, shortcut, error } => {
+				let dialog = KeyboardShortcutsDialog::new(preferences, Some(RejectedShortcut { action, shortcut, error }));
+				dialog.send_dialog_to_frontend(responses);
+			}
 			DialogMessage::RequestLicensesDialogWithLocalizedCommitDate { localized_commit_year } => {
 				let dialog = LicensesDialog { localized_commit_year };
 
@@ -106,13 +217,77 @@ impl MessageHandler<DialogMessage, DialogMessageData<'_>> for DialogMessageHandl
 				self.preferences_dialog = PreferencesDialogMessageHandler {};
 				self.preferences_dialog.send_dialog_to_frontend(responses, preferences);
 			}
+			DialogMessage::RequestRecolorArtworkDialog => {
+				let Some(document) = portfolio.active_document() else { return };
+
+				let colors = document.selected_artwork_colors();
+				if colors.is_empty() {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Nothing to recolor".into(),
+						description: "Select layers with fill or stroke colors to recolor.".into(),
+					});
+					return;
+				}
+
+				self.recolor_artwork_dialog = RecolorArtworkDialogMessageHandler {
+					colors: colors.into_iter().map(|color| (color, color)).collect(),
+					hue_shift: 0.,
+					swatches: document.swatches.iter().map(|swatch| swatch.color.to_gamma_srgb()).collect(),
+				};
+				self.recolor_artwork_dialog.send_dialog_to_frontend(responses);
+			}
+			DialogMessage::RequestSaveActionMacroDialog { name } => {
+				// Suggest a name which isn't taken yet if none has been entered
+				let name = match name.trim() {
+					"" => (1..)
+						.map(|number| format!("Macro {number}"))
+						.find(|name| preferences.action_macros.iter().all(|action_macro| &action_macro.name != name))
+						.unwrap_or_default(),
+					name => name.to_string(),
+				};
+				let replaces_existing = preferences.action_macros.iter().any(|action_macro| action_macro.name == name);
+
+				let dialog = SaveActionMacroDialog { name, replaces_existing };
+				dialog.send_dialog_to_frontend(responses);
+			}
+			DialogMessage::RequestSaveGradientPresetDialog { name, stops } => {
+				// Suggest a name which isn't taken yet if none has been entered
+				let name = match name.trim() {
+					"" => (1..)
+						.map(|number| format!("Gradient {number}"))
+						.find(|name| preferences.gradient_presets.iter().all(|preset| &preset.name != name))
+						.unwrap_or_default(),
+					name => name.to_string(),
+				};
+				let replaces_existing = preferences.gradient_presets.iter().any(|preset| preset.name == name);
+
+				let dialog = SaveGradientPresetDialog { name, stops, replaces_existing };
+				dialog.send_dialog_to_frontend(responses);
+			}
+			DialogMessage::RequestSaveWorkspaceLayoutDialog { name } => {
+				// Suggest a name which isn't taken yet if none has been entered
+				let name = match name.trim() {
+					"" => (1..)
+						.map(|number| format!("Layout {number}"))
+						.find(|name| preferences.workspace_layouts.iter().all(|layout| &layout.name != name))
+						.unwrap_or_default(),
+					name => name.to_string(),
+				};
+				let replaces_existing = preferences.workspace_layouts.iter().any(|layout| layout.name == name);
+
+				let dialog = SaveWorkspaceLayoutDialog { name, replaces_existing };
+				dialog.send_dialog_to_frontend(responses);
+			}
 		}
 	}
 
 	advertise_actions!(DialogMessageDiscriminant;
 		CloseAllDocumentsWithConfirmation,
+		RequestCommandPaletteDialog,
 		RequestExportDialog,
+		RequestFindReplaceDialog,
 		RequestNewDocumentDialog,
 		RequestPreferencesDialog,
+		RequestRecolorArtworkDialog,
 	);
 }
@@ -1,5 +1,6 @@
-use crate::messages::frontend::utility_types::{ExportBounds, FileType};
+use crate::messages::frontend::utility_types::{ExportBounds, FileType, TextExport, TiffCompression};
 use crate::messages::prelude::*;
+use glam::DVec2;
 
 #[impl_message(Message, DialogMessage, ExportDialog)]
 #[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -8,6 +9,18 @@ pub enum ExportDialogMessage {
 	ScaleFactor(f64),
 	TransparentBackground(bool),
 	ExportBounds(ExportBounds),
+	CustomRegion([DVec2; 2]),
+	MetadataTitle(String),
+	MetadataAuthor(String),
+	MetadataCopyright(String),
+	MetadataDpi(f64),
+	StripMetadata(bool),
+	TiffCompression(TiffCompression),
+	TextExport(TextExport),
+	FrameSequence(bool),
+	FirstFrame(u32),
+	LastFrame(u32),
+	FileNamePattern(String),
 
 	Submit,
 }
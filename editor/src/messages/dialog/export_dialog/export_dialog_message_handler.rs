@@ -1,7 +1,8 @@
-use crate::messages::frontend::utility_types::{ExportBounds, FileType};
+use crate::messages::frontend::utility_types::{ExportBounds, ExportMetadata, FileType, FrameSequenceExport, TextExport, TiffCompression};
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
 use crate::messages::prelude::*;
+use glam::DVec2;
 
 pub struct ExportDialogMessageData<'a> {
 	pub portfolio: &'a PortfolioMessageHandler,
@@ -13,9 +14,20 @@ pub struct ExportDialogMessageHandler {
 	pub file_type: FileType,
 	pub scale_factor: f64,
 	pub bounds: ExportBounds,
+	/// The document-space rectangle used by [`ExportBounds::CustomRegion`], which is saved back to the document upon export.
+	pub custom_region: [DVec2; 2],
 	pub transparent_background: bool,
+	pub metadata: ExportMetadata,
+	pub tiff_compression: TiffCompression,
+	pub text_export: TextExport,
 	pub artboards: HashMap<LayerNodeIdentifier, String>,
 	pub has_selection: bool,
+	/// Whether each frame of the animation in the range from [`Self::first_frame`] to [`Self::last_frame`] is exported to its own numbered file.
+	pub frame_sequence: bool,
+	pub first_frame: u32,
+	pub last_frame: u32,
+	pub frames_per_second: f64,
+	pub file_name_pattern: String,
 }
 
 impl Default for ExportDialogMessageHandler {
@@ -24,9 +36,18 @@ impl Default for ExportDialogMessageHandler {
 			file_type: Default::default(),
 			scale_factor: 1.,
 			bounds: Default::default(),
+			custom_region: [DVec2::ZERO, DVec2::new(1920., 1080.)],
 			transparent_background: false,
+			metadata: Default::default(),
+			tiff_compression: Default::default(),
+			text_export: Default::default(),
 			artboards: Default::default(),
 			has_selection: false,
+			frame_sequence: false,
+			first_frame: 0,
+			last_frame: 0,
+			frames_per_second: 30.,
+			file_name_pattern: String::new(),
 		}
 	}
 }
@@ -40,14 +61,43 @@ impl MessageHandler<ExportDialogMessage, ExportDialogMessageData<'_>> for Export
 			ExportDialogMessage::ScaleFactor(factor) => self.scale_factor = factor,
 			ExportDialogMessage::TransparentBackground(transparent_background) => self.transparent_background = transparent_background,
 			ExportDialogMessage::ExportBounds(export_area) => self.bounds = export_area,
+			ExportDialogMessage::CustomRegion([start, end]) => self.custom_region = [start.min(end), start.max(end)],
+			ExportDialogMessage::MetadataTitle(title) => self.metadata.title = title,
+			ExportDialogMessage::MetadataAuthor(author) => self.metadata.author = author,
+			ExportDialogMessage::MetadataCopyright(copyright) => self.metadata.copyright = copyright,
+			ExportDialogMessage::MetadataDpi(dpi) => self.metadata.dpi = dpi,
+			ExportDialogMessage::StripMetadata(strip_metadata) => self.metadata.strip_metadata = strip_metadata,
+			ExportDialogMessage::TiffCompression(tiff_compression) => self.tiff_compression = tiff_compression,
+			ExportDialogMessage::TextExport(text_export) => self.text_export = text_export,
+			ExportDialogMessage::FrameSequence(frame_sequence) => self.frame_sequence = frame_sequence,
+			ExportDialogMessage::FirstFrame(frame) => self.first_frame = frame,
+			ExportDialogMessage::LastFrame(frame) => self.last_frame = frame,
+			ExportDialogMessage::FileNamePattern(pattern) => self.file_name_pattern = pattern,
 
-			ExportDialogMessage::Submit => responses.add_front(PortfolioMessage::SubmitDocumentExport {
-				file_name: portfolio.active_document().map(|document| document.name.clone()).unwrap_or_default(),
-				file_type: self.file_type,
-				scale_factor: self.scale_factor,
-				bounds: self.bounds,
-				transparent_background: self.file_type != FileType::Jpg && self.transparent_background,
-			}),
+			ExportDialogMessage::Submit => {
+				responses.add_front(PortfolioMessage::SubmitDocumentExport {
+					file_name: portfolio.active_document().map(|document| document.name.clone()).unwrap_or_default(),
+					file_type: self.file_type,
+					scale_factor: self.scale_factor,
+					bounds: self.bounds,
+					transparent_background: self.file_type != FileType::Jpg && self.transparent_background,
+					metadata: self.metadata.clone(),
+					tiff_compression: self.tiff_compression,
+					text_export: self.text_export,
+					frame_sequence: self.frame_sequence.then(|| FrameSequenceExport {
+						first_frame: self.first_frame.min(self.last_frame),
+						last_frame: self.first_frame.max(self.last_frame),
+						frames_per_second: self.frames_per_second,
+						file_name_pattern: self.file_name_pattern.clone(),
+					}),
+					export_id: None,
+				});
+
+				// Save the custom region to the document before the export reads it back
+				if self.bounds == ExportBounds::CustomRegion {
+					responses.add_front(DocumentMessage::SetExportRegion { region: Some(self.custom_region) });
+				}
+			}
 		}
 
 		self.send_dialog_to_frontend(responses);
@@ -80,10 +130,17 @@ impl DialogLayoutHolder for ExportDialogMessageHandler {
 
 impl LayoutHolder for ExportDialogMessageHandler {
 	fn layout(&self) -> Layout {
-		let entries = [(FileType::Png, "PNG"), (FileType::Jpg, "JPG"), (FileType::Svg, "SVG")]
-			.into_iter()
-			.map(|(val, name)| RadioEntryData::new(format!("{val:?}")).label(name).on_update(move |_| ExportDialogMessage::FileType(val).into()))
-			.collect();
+		let entries = [
+			(FileType::Png, "PNG"),
+			(FileType::Jpg, "JPG"),
+			(FileType::Svg, "SVG"),
+			(FileType::Tiff, "TIFF"),
+			(FileType::Pdf, "PDF"),
+			(FileType::Eps, "EPS"),
+		]
+		.into_iter()
+		.map(|(val, name)| RadioEntryData::new(format!("{val:?}")).label(name).on_update(move |_| ExportDialogMessage::FileType(val).into()))
+		.collect();
 
 		let export_type = vec![
 			TextLabel::new("File Type").table_align(true).min_width(100).widget_holder(),
@@ -98,7 +155,7 @@ impl LayoutHolder for ExportDialogMessageHandler {
 				.unit("")
 				.min(0.)
 				.max((1_u64 << f64::MANTISSA_DIGITS) as f64)
-				.disabled(self.file_type == FileType::Svg)
+				.disabled(!self.file_type.is_raster())
 				.on_update(|number_input: &NumberInput| ExportDialogMessage::ScaleFactor(number_input.value.unwrap()).into())
 				.min_width(200)
 				.widget_holder(),
@@ -107,6 +164,7 @@ impl LayoutHolder for ExportDialogMessageHandler {
 		let standard_bounds = vec![
 			(ExportBounds::AllArtwork, "All Artwork".to_string(), false),
 			(ExportBounds::Selection, "Selection".to_string(), !self.has_selection),
+			(ExportBounds::CustomRegion, "Custom Region".to_string(), false),
 		];
 		let artboards = self.artboards.iter().map(|(&layer, name)| (ExportBounds::Artboard(layer), name.to_string(), false)).collect();
 		let groups = [standard_bounds, artboards];
@@ -143,6 +201,89 @@ impl LayoutHolder for ExportDialogMessageHandler {
 			DropdownInput::new(entries).selected_index(Some(index as u32)).widget_holder(),
 		];
 
+		let [start, end] = self.custom_region;
+		let size = end - start;
+		let custom_region_origin = vec![
+			TextLabel::new("Region Origin").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(start.x))
+				.label("X")
+				.unit(" px")
+				.min_width(98)
+				.on_update(move |number_input: &NumberInput| {
+					let x = number_input.value.unwrap();
+					ExportDialogMessage::CustomRegion([DVec2::new(x, start.y), DVec2::new(x + size.x, end.y)]).into()
+				})
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			NumberInput::new(Some(start.y))
+				.label("Y")
+				.unit(" px")
+				.min_width(98)
+				.on_update(move |number_input: &NumberInput| {
+					let y = number_input.value.unwrap();
+					ExportDialogMessage::CustomRegion([DVec2::new(start.x, y), DVec2::new(end.x, y + size.y)]).into()
+				})
+				.widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextButton::new("Draw")
+				.tooltip("Close this dialog to draw the region by dragging on the canvas")
+				.on_update(|_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![DocumentMessage::DrawExportRegion.into()],
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let custom_region_size = vec![
+			TextLabel::new("Region Size").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(size.x))
+				.label("W")
+				.unit(" px")
+				.min(0.)
+				.min_width(98)
+				.on_update(move |number_input: &NumberInput| ExportDialogMessage::CustomRegion([start, DVec2::new(start.x + number_input.value.unwrap(), end.y)]).into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			NumberInput::new(Some(size.y))
+				.label("H")
+				.unit(" px")
+				.min(0.)
+				.min_width(98)
+				.on_update(move |number_input: &NumberInput| ExportDialogMessage::CustomRegion([start, DVec2::new(end.x, start.y + number_input.value.unwrap())]).into())
+				.widget_holder(),
+		];
+
+		let compression_entries = [(TiffCompression::None, "None"), (TiffCompression::Lzw, "LZW"), (TiffCompression::Deflate, "Zip")]
+			.into_iter()
+			.map(|(val, name)| RadioEntryData::new(format!("{val:?}")).label(name).on_update(move |_| ExportDialogMessage::TiffCompression(val).into()))
+			.collect();
+
+		let tiff_compression = vec![
+			TextLabel::new("Compression").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(compression_entries).selected_index(Some(self.tiff_compression as u32)).widget_holder(),
+		];
+
+		let text_entries = [(TextExport::Outlines, "Outlines"), (TextExport::EmbeddedFont, "Embedded Font")]
+			.into_iter()
+			.map(|(val, name)| RadioEntryData::new(format!("{val:?}")).label(name).on_update(move |_| ExportDialogMessage::TextExport(val).into()))
+			.collect();
+
+		let text_export = vec![
+			TextLabel::new("Text").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(text_entries)
+				.selected_index(Some(self.text_export as u32))
+				.tooltip(
+					"Write text as the outlines of its glyphs, or draw it with copies of just the glyphs it uses from its fonts embedded in the file. Text which can't be embedded this way is written as outlines",
+				)
+				.widget_holder(),
+		];
+
 		let transparent_background = vec![
 			TextLabel::new("Transparency").table_align(true).min_width(100).widget_holder(),
 			Separator::new(SeparatorType::Unrelated).widget_holder(),
@@ -152,11 +293,111 @@ impl LayoutHolder for ExportDialogMessageHandler {
 				.widget_holder(),
 		];
 
-		Layout::WidgetLayout(WidgetLayout::new(vec![
+		let raster_metadata_disabled = !self.file_type.is_raster() || self.metadata.strip_metadata;
+		let metadata_text_row = |label: &str, value: &str, update: fn(String) -> ExportDialogMessage| {
+			vec![
+				TextLabel::new(label).table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextInput::new(value)
+					.disabled(raster_metadata_disabled)
+					.on_update(move |text_input: &TextInput| update(text_input.value.clone()).into())
+					.min_width(200)
+					.widget_holder(),
+			]
+		};
+		let metadata_title = metadata_text_row("Title", &self.metadata.title, ExportDialogMessage::MetadataTitle);
+		let metadata_author = metadata_text_row("Author", &self.metadata.author, ExportDialogMessage::MetadataAuthor);
+		let metadata_copyright = metadata_text_row("Copyright", &self.metadata.copyright, ExportDialogMessage::MetadataCopyright);
+
+		let metadata_dpi = vec![
+			TextLabel::new("Resolution").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(self.metadata.dpi))
+				.unit(" DPI")
+				.min(1.)
+				.max(100_000.)
+				.disabled(raster_metadata_disabled)
+				.on_update(|number_input: &NumberInput| ExportDialogMessage::MetadataDpi(number_input.value.unwrap()).into())
+				.min_width(200)
+				.widget_holder(),
+		];
+
+		let strip_metadata = vec![
+			TextLabel::new("Strip Metadata").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(self.metadata.strip_metadata)
+				.disabled(!self.file_type.is_raster())
+				.tooltip("Omit all metadata, including the title, author, copyright, and resolution, from the exported file")
+				.on_update(|value: &CheckboxInput| ExportDialogMessage::StripMetadata(value.checked).into())
+				.widget_holder(),
+		];
+
+		let frame_sequence = vec![
+			TextLabel::new("Frame Sequence").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(self.frame_sequence)
+				.tooltip("Export each frame of the animation to its own numbered file, such as for encoding into a video")
+				.on_update(|value: &CheckboxInput| ExportDialogMessage::FrameSequence(value.checked).into())
+				.widget_holder(),
+		];
+
+		let frame_range = vec![
+			TextLabel::new("Frames").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(self.first_frame as f64))
+				.label("First")
+				.int()
+				.min(0.)
+				.min_width(98)
+				.on_update(|number_input: &NumberInput| ExportDialogMessage::FirstFrame(number_input.value.unwrap() as u32).into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			NumberInput::new(Some(self.last_frame as f64))
+				.label("Last")
+				.int()
+				.min(0.)
+				.min_width(98)
+				.on_update(|number_input: &NumberInput| ExportDialogMessage::LastFrame(number_input.value.unwrap() as u32).into())
+				.widget_holder(),
+		];
+
+		let file_name_pattern = vec![
+			TextLabel::new("File Name").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextInput::new(&self.file_name_pattern)
+				.tooltip("The name of each frame's file, where the run of # characters is replaced by the frame number padded with zeros to as many digits")
+				.on_update(|text_input: &TextInput| ExportDialogMessage::FileNamePattern(text_input.value.clone()).into())
+				.min_width(200)
+				.widget_holder(),
+		];
+
+		let mut rows = vec![
 			LayoutGroup::Row { widgets: export_type },
 			LayoutGroup::Row { widgets: resolution },
 			LayoutGroup::Row { widgets: export_area },
-			LayoutGroup::Row { widgets: transparent_background },
-		]))
+		];
+		if current_bounds == ExportBounds::CustomRegion {
+			rows.push(LayoutGroup::Row { widgets: custom_region_origin });
+			rows.push(LayoutGroup::Row { widgets: custom_region_size });
+		}
+		rows.push(LayoutGroup::Row { widgets: frame_sequence });
+		if self.frame_sequence {
+			rows.push(LayoutGroup::Row { widgets: frame_range });
+			rows.push(LayoutGroup::Row { widgets: file_name_pattern });
+		}
+		if self.file_type == FileType::Tiff {
+			rows.push(LayoutGroup::Row { widgets: tiff_compression });
+		}
+		if self.file_type == FileType::Svg {
+			rows.push(LayoutGroup::Row { widgets: text_export });
+		}
+		rows.push(LayoutGroup::Row { widgets: transparent_background });
+		rows.push(LayoutGroup::Row { widgets: metadata_title });
+		rows.push(LayoutGroup::Row { widgets: metadata_author });
+		rows.push(LayoutGroup::Row { widgets: metadata_copyright });
+		rows.push(LayoutGroup::Row { widgets: metadata_dpi });
+		rows.push(LayoutGroup::Row { widgets: strip_metadata });
+
+		Layout::WidgetLayout(WidgetLayout::new(rows))
 	}
 }
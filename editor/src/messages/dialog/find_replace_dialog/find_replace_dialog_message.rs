@@ -0,0 +1,32 @@
+use crate::messages::portfolio::document::overlays::utility_types::OverlayContext;
+use crate::messages::prelude::*;
+
+#[impl_message(Message, DialogMessage, FindReplaceDialog)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum FindReplaceDialogMessage {
+	// Overlays
+	Overlays(OverlayContext),
+
+	/// Stops highlighting the matches once the dialog has been closed.
+	Dismissed,
+	Find {
+		query: String,
+	},
+	Replacement {
+		text: String,
+	},
+	MatchCase {
+		match_case: bool,
+	},
+	LayerNames {
+		layer_names: bool,
+	},
+	Next,
+	Previous,
+	/// Searches the document again after its text has been changed.
+	Refresh,
+	/// Replaces the current match and moves on to the one after it.
+	Replace,
+	/// Replaces every match, as a single step which can be undone.
+	ReplaceAll,
+}
@@ -0,0 +1,472 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::overlays::utility_types::{OverlayColors, OverlayProvider};
+use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
+use crate::messages::portfolio::document::utility_types::network_interface::InputConnector;
+use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::graph_modification_utils;
+use crate::messages::tool::common_functionality::utility_functions::text_caret_positions;
+use graph_craft::document::NodeInput;
+use graph_craft::document::value::TaggedValue;
+use graphene_core::renderer::Quad;
+use graphene_core::text::{TextSpan, splice_spans};
+use std::ops::Range;
+
+const FIND_REPLACE_OVERLAY_PROVIDER: OverlayProvider = |context| FindReplaceDialogMessage::Overlays(context).into();
+
+pub struct FindReplaceDialogMessageData<'a> {
+	pub portfolio: &'a PortfolioMessageHandler,
+}
+
+/// Where in a layer a match was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MatchSource {
+	Text,
+	LayerName,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct FindMatch {
+	layer: LayerNodeIdentifier,
+	source: MatchSource,
+	/// The byte range of the match within the text or the layer name.
+	range: Range<usize>,
+}
+
+/// A dialog to find text in the text layers and layer names of the document, highlighting and stepping through the matches and replacing them.
+#[derive(Debug, Clone)]
+pub struct FindReplaceDialogMessageHandler {
+	pub query: String,
+	pub replacement: String,
+	pub match_case: bool,
+	pub layer_names: bool,
+	matches: Vec<FindMatch>,
+	current: usize,
+	/// The match which was just replaced by [`FindReplaceDialogMessage::Replace`], along with its index, so the search can continue after the replacement.
+	replaced: Option<(usize, FindMatch)>,
+}
+
+impl Default for FindReplaceDialogMessageHandler {
+	fn default() -> Self {
+		Self {
+			query: String::new(),
+			replacement: String::new(),
+			match_case: false,
+			layer_names: true,
+			matches: Vec::new(),
+			current: 0,
+			replaced: None,
+		}
+	}
+}
+
+impl MessageHandler<FindReplaceDialogMessage, FindReplaceDialogMessageData<'_>> for FindReplaceDialogMessageHandler {
+	fn process_message(&mut self, message: FindReplaceDialogMessage, responses: &mut VecDeque<Message>, data: FindReplaceDialogMessageData) {
+		let FindReplaceDialogMessageData { portfolio } = data;
+		let Some(document) = portfolio.active_document() else { return };
+
+		match message {
+			FindReplaceDialogMessage::Overlays(mut overlay_context) => {
+				let font_cache = &portfolio.persistent_data.font_cache;
+				let current_fill = OverlayColors::with_alpha(&overlay_context.colors.accent, 0.5);
+				let fill = OverlayColors::with_alpha(&overlay_context.colors.accent, 0.2);
+
+				// The characters matched in the text are highlighted like a selection made with the Text tool
+				let mut carets = HashMap::new();
+				for (index, found) in self.matches.iter().enumerate().filter(|(_, found)| found.source == MatchSource::Text) {
+					let layer_carets = carets.entry(found.layer).or_insert_with(|| text_caret_positions(found.layer, document, font_cache));
+					let Some((text, _, _)) = graph_modification_utils::get_text(found.layer, &document.network_interface) else {
+						continue;
+					};
+					let characters = text[..found.range.start].chars().count()..text[..found.range.end].chars().count();
+
+					let transform = document.metadata().transform_to_viewport(found.layer);
+					let fill = if index == self.current { &current_fill } else { &fill };
+					for caret in layer_carets.get(characters).unwrap_or_default() {
+						let [start_top, start_bottom] = caret.ends.map(|point| transform.transform_point2(point));
+						let [end_top, end_bottom] = caret.character_ends.map(|point| transform.transform_point2(point));
+						overlay_context.fill_polygon(&[start_top, end_top, end_bottom, start_bottom], fill);
+					}
+				}
+
+				// Layer names aren't shown in the canvas, so the layers with matches in their names are outlined instead
+				let current_layer = self.matches.get(self.current).filter(|found| found.source == MatchSource::LayerName).map(|found| found.layer);
+				let mut layers = self.matches.iter().filter(|found| found.source == MatchSource::LayerName).map(|found| found.layer).collect::<Vec<_>>();
+				layers.dedup();
+				for layer in layers {
+					let Some(bounds) = document.metadata().bounding_box_viewport(layer) else { continue };
+					let quad = Quad::from_box(bounds);
+					if Some(layer) == current_layer {
+						overlay_context.quad(quad, Some(&fill));
+					} else {
+						overlay_context.dashed_quad(quad, None, Some(4.), Some(4.), None);
+					}
+				}
+				return;
+			}
+			FindReplaceDialogMessage::Dismissed => {
+				self.matches.clear();
+				responses.add(OverlaysMessage::RemoveProvider(FIND_REPLACE_OVERLAY_PROVIDER));
+				responses.add(OverlaysMessage::Draw);
+				return;
+			}
+			FindReplaceDialogMessage::Find { query } => {
+				self.query = query;
+				self.current = 0;
+				self.search(document);
+			}
+			FindReplaceDialogMessage::Replacement { text } => self.replacement = text,
+			FindReplaceDialogMessage::MatchCase { match_case } => {
+				self.match_case = match_case;
+				self.current = 0;
+				self.search(document);
+			}
+			FindReplaceDialogMessage::LayerNames { layer_names } => {
+				self.layer_names = layer_names;
+				self.current = 0;
+				self.search(document);
+			}
+			FindReplaceDialogMessage::Next | FindReplaceDialogMessage::Previous => {
+				if self.matches.is_empty() {
+					return;
+				}
+				self.current = match message {
+					FindReplaceDialogMessage::Next => (self.current + 1) % self.matches.len(),
+					_ => (self.current + self.matches.len() - 1) % self.matches.len(),
+				};
+				self.select_current(responses);
+			}
+			FindReplaceDialogMessage::Refresh => {
+				self.search(document);
+				self.select_current(responses);
+			}
+			FindReplaceDialogMessage::Replace => {
+				let Some(found) = self.matches.get(self.current).cloned() else { return };
+				let replaced = FindMatch {
+					range: found.range.start..found.range.start + self.replacement.len(),
+					..found.clone()
+				};
+				self.replaced = Some((self.current, replaced));
+				self.replace(&[found], document, responses);
+				return;
+			}
+			FindReplaceDialogMessage::ReplaceAll => {
+				self.current = 0;
+				self.replace(&self.matches, document, responses);
+				return;
+			}
+		}
+
+		responses.add(OverlaysMessage::Draw);
+		self.send_dialog_to_frontend(responses);
+	}
+
+	advertise_actions! {FindReplaceDialogUpdate;}
+}
+
+impl FindReplaceDialogMessageHandler {
+	/// Searches the document with the query set in the dialog, then opens the dialog and starts highlighting the matches.
+	pub fn open(&mut self, document: &DocumentMessageHandler, responses: &mut VecDeque<Message>) {
+		self.current = 0;
+		self.search(document);
+
+		responses.add(OverlaysMessage::AddProvider(FIND_REPLACE_OVERLAY_PROVIDER));
+		responses.add(OverlaysMessage::Draw);
+		self.send_dialog_to_frontend(responses);
+	}
+
+	/// Finds the matches in the text layers and, if chosen, the layer names, in the order of the layers from the top of the Layers panel.
+	fn search(&mut self, document: &DocumentMessageHandler) {
+		self.matches.clear();
+
+		for layer in document.metadata().all_layers() {
+			if let Some((text, _, _)) = graph_modification_utils::get_text(layer, &document.network_interface) {
+				let found = find_occurrences(text, &self.query, self.match_case).into_iter();
+				self.matches.extend(found.map(|range| FindMatch {
+					layer,
+					source: MatchSource::Text,
+					range,
+				}));
+			}
+			if self.layer_names {
+				let found = find_occurrences(&layer_name(layer, document), &self.query, self.match_case).into_iter();
+				self.matches.extend(found.map(|range| FindMatch {
+					layer,
+					source: MatchSource::LayerName,
+					range,
+				}));
+			}
+		}
+
+		// Continue after the text which replaced the last match, skipping any matches within the replacement itself
+		if let Some((index, replaced)) = self.replaced.take() {
+			let within_replacement = self
+				.matches
+				.iter()
+				.skip(index)
+				.take_while(|found| found.layer == replaced.layer && found.source == replaced.source && found.range.start < replaced.range.end);
+			self.current = index + within_replacement.count();
+		}
+		if self.current >= self.matches.len() {
+			self.current = 0;
+		}
+	}
+
+	/// Selects the layer of the current match so it can be seen and edited.
+	fn select_current(&self, responses: &mut VecDeque<Message>) {
+		let Some(found) = self.matches.get(self.current) else { return };
+		responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![found.layer.to_node()] });
+	}
+
+	/// Replaces the matches with the replacement text, as a single transaction, then searches again once the document has been updated.
+	fn replace(&self, matches: &[FindMatch], document: &DocumentMessageHandler, responses: &mut VecDeque<Message>) {
+		if matches.is_empty() {
+			return;
+		}
+
+		let mut targets = matches.iter().map(|found| (found.layer, found.source)).collect::<Vec<_>>();
+		targets.dedup();
+
+		responses.add(DocumentMessage::AddTransaction);
+		for (layer, source) in targets {
+			// The ranges are replaced from last to first so the earlier ones are left where they are
+			let ranges = matches.iter().filter(|found| found.layer == layer && found.source == source).map(|found| found.range.clone()).rev();
+
+			match source {
+				MatchSource::Text => {
+					let Some(text_node) = graph_modification_utils::get_text_id(layer, &document.network_interface) else {
+						continue;
+					};
+					let Some((text, _, _)) = graph_modification_utils::get_text(layer, &document.network_interface) else {
+						continue;
+					};
+					let spans = graph_modification_utils::get_text_spans(layer, &document.network_interface).cloned().unwrap_or_default();
+					let (text, spans) = replace_ranges(text, &spans, ranges, &self.replacement);
+
+					responses.add(NodeGraphMessage::SetInput {
+						input_connector: InputConnector::node(text_node, 1),
+						input: NodeInput::value(TaggedValue::String(text), false),
+					});
+					responses.add(NodeGraphMessage::SetInput {
+						input_connector: InputConnector::node(text_node, 10),
+						input: NodeInput::value(TaggedValue::TextSpans(spans), false),
+					});
+				}
+				MatchSource::LayerName => {
+					let (name, _) = replace_ranges(&layer_name(layer, document), &[], ranges, &self.replacement);
+
+					responses.add(NodeGraphMessage::SetDisplayName {
+						node_id: layer.to_node(),
+						alias: name,
+						skip_adding_history_step: true,
+					});
+				}
+			}
+		}
+		responses.add(NodeGraphMessage::RunDocumentGraph);
+		responses.add(FindReplaceDialogMessage::Refresh);
+	}
+}
+
+/// The name given to the layer, which is empty if it has the default name of its node.
+fn layer_name(layer: LayerNodeIdentifier, document: &DocumentMessageHandler) -> String {
+	document
+		.network_interface
+		.node_metadata(&layer.to_node(), &[])
+		.map(|node| node.persistent_metadata.display_name.clone())
+		.unwrap_or_default()
+}
+
+/// Replaces the text in each of the byte ranges, which are given from last to first so the earlier ones are left where they are, along with the spans of the text.
+/// The spans are counted in characters, so each span is moved and resized by the difference in characters between the replaced text and the replacement.
+fn replace_ranges(text: &str, spans: &[TextSpan], ranges: impl IntoIterator<Item = Range<usize>>, replacement: &str) -> (String, Vec<TextSpan>) {
+	let mut text = text.to_string();
+	let mut spans = spans.to_vec();
+	for range in ranges {
+		let characters = text[..range.start].chars().count()..text[..range.end].chars().count();
+		spans = splice_spans(&spans, characters, replacement.chars().count());
+		text.replace_range(range, replacement);
+	}
+	(text, spans)
+}
+
+/// The byte ranges of the non-overlapping occurrences of the query in the text, from first to last.
+fn find_occurrences(text: &str, query: &str, match_case: bool) -> Vec<Range<usize>> {
+	let mut occurrences = Vec::new();
+	if query.is_empty() {
+		return occurrences;
+	}
+
+	let mut start = 0;
+	while let Some(character) = text[start..].chars().next() {
+		match matched_length(&text[start..], query, match_case) {
+			Some(length) => {
+				occurrences.push(start..start + length);
+				start += length;
+			}
+			None => start += character.len_utf8(),
+		}
+	}
+	occurrences
+}
+
+/// The length in bytes of the start of the text if it matches the query, comparing the characters regardless of their case unless `match_case` is set.
+fn matched_length(text: &str, query: &str, match_case: bool) -> Option<usize> {
+	if match_case {
+		return text.starts_with(query).then_some(query.len());
+	}
+
+	let mut characters = text.char_indices();
+	for expected in query.chars() {
+		let (_, character) = characters.next()?;
+		if !character.to_lowercase().eq(expected.to_lowercase()) {
+			return None;
+		}
+	}
+	Some(characters.next().map_or(text.len(), |(index, _)| index))
+}
+
+impl DialogLayoutHolder for FindReplaceDialogMessageHandler {
+	const ICON: &'static str = "NodeText";
+	const TITLE: &'static str = "Find & Replace";
+
+	fn layout_buttons(&self) -> Layout {
+		let no_matches = self.matches.is_empty();
+		let widgets = vec![
+			TextButton::new("Replace")
+				.emphasized(true)
+				.disabled(no_matches)
+				.on_update(|_| FindReplaceDialogMessage::Replace.into())
+				.widget_holder(),
+			TextButton::new("Replace All")
+				.disabled(no_matches)
+				.on_update(|_| FindReplaceDialogMessage::ReplaceAll.into())
+				.widget_holder(),
+			TextButton::new("Close")
+				.on_update(|_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![FindReplaceDialogMessage::Dismissed.into()],
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
+	}
+}
+
+impl LayoutHolder for FindReplaceDialogMessageHandler {
+	fn layout(&self) -> Layout {
+		let find = vec![
+			TextLabel::new("Find").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextInput::new(&self.query)
+				.min_width(300)
+				.on_update(|text_input: &TextInput| FindReplaceDialogMessage::Find { query: text_input.value.clone() }.into())
+				.widget_holder(),
+		];
+
+		let replace = vec![
+			TextLabel::new("Replace With").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextInput::new(&self.replacement)
+				.min_width(300)
+				.on_update(|text_input: &TextInput| FindReplaceDialogMessage::Replacement { text: text_input.value.clone() }.into())
+				.widget_holder(),
+		];
+
+		let match_case = vec![
+			TextLabel::new("Match Case").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(self.match_case)
+				.on_update(|checkbox_input: &CheckboxInput| FindReplaceDialogMessage::MatchCase { match_case: checkbox_input.checked }.into())
+				.widget_holder(),
+		];
+
+		let layer_names = vec![
+			TextLabel::new("Layer Names").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(self.layer_names)
+				.tooltip("Also search the names given to layers")
+				.on_update(|checkbox_input: &CheckboxInput| FindReplaceDialogMessage::LayerNames { layer_names: checkbox_input.checked }.into())
+				.widget_holder(),
+		];
+
+		let description = match self.matches.len() {
+			_ if self.query.is_empty() => String::new(),
+			0 => "No matches".to_string(),
+			1 => "1 match".to_string(),
+			count => format!("Match {} of {count}", self.current + 1),
+		};
+		let no_matches = self.matches.is_empty();
+		let results = vec![
+			TextLabel::new(description).table_align(true).min_width(100).italic(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextButton::new("Previous")
+				.disabled(no_matches)
+				.on_update(|_| FindReplaceDialogMessage::Previous.into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			TextButton::new("Next").disabled(no_matches).on_update(|_| FindReplaceDialogMessage::Next.into()).widget_holder(),
+		];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![
+			LayoutGroup::Row { widgets: find },
+			LayoutGroup::Row { widgets: replace },
+			LayoutGroup::Row { widgets: match_case },
+			LayoutGroup::Row { widgets: layer_names },
+			LayoutGroup::Row { widgets: results },
+		]))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::{TextSpan, find_occurrences, replace_ranges};
+
+	fn span(start: usize, end: usize) -> TextSpan {
+		TextSpan {
+			start,
+			end,
+			font: None,
+			font_size: Some(32.),
+			color: None,
+			baseline_shift: None,
+			script: None,
+			underline: None,
+			strikethrough: None,
+		}
+	}
+
+	#[test]
+	fn occurrences_found_with_or_without_case() {
+		assert_eq!(find_occurrences("Graphite graphics", "graph", true), [9..14]);
+		assert_eq!(find_occurrences("Graphite graphics", "graph", false), [0..5, 9..14]);
+		assert_eq!(find_occurrences("aaaa", "aa", true), [0..2, 2..4]);
+		assert_eq!(find_occurrences("Ünïcödé ÜNÏ", "ünï", false), [0..5, 12..17]);
+		assert!(find_occurrences("text", "", false).is_empty());
+	}
+
+	#[test]
+	fn replacing_from_last_to_first_keeps_earlier_ranges() {
+		let ranges = find_occurrences("one two one", "one", true);
+		let (text, _) = replace_ranges("one two one", &[], ranges.into_iter().rev(), "three");
+		assert_eq!(text, "three two three");
+	}
+
+	#[test]
+	fn replacing_moves_spans_by_characters() {
+		// "ü" and "é" take two bytes each, but the spans count them as one character
+		let text = "üb cé dé";
+		let ranges = find_occurrences(text, "cé", true);
+		assert_eq!(ranges, [4..7]);
+		let (text, spans) = replace_ranges(text, &[span(6, 8)], ranges.into_iter().rev(), "x");
+		assert_eq!(text, "üb x dé");
+		assert_eq!(spans, [span(5, 7)]);
+
+		let ranges = find_occurrences(&text, "é", true).into_iter().rev();
+		let (text, spans) = replace_ranges(&text, &[span(0, 7)], ranges, "ee");
+		assert_eq!(text, "üb x dee");
+		assert_eq!(spans, [span(0, 8)]);
+	}
+}
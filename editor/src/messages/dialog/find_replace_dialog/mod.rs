@@ -0,0 +1,7 @@
+mod find_replace_dialog_message;
+mod find_replace_dialog_message_handler;
+
+#[doc(inline)]
+pub use find_replace_dialog_message::{FindReplaceDialogMessage, FindReplaceDialogMessageDiscriminant};
+#[doc(inline)]
+pub use find_replace_dialog_message_handler::{FindReplaceDialogMessageData, FindReplaceDialogMessageHandler};
@@ -0,0 +1,7 @@
+use crate::messages::prelude::*;
+
+#[impl_message(Message, DialogMessage, GlyphPaletteDialog)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum GlyphPaletteDialogMessage {
+	Search { query: String },
+}
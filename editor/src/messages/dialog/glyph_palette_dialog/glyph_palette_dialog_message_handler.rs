@@ -0,0 +1,135 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::*;
+
+/// The most glyphs shown at once, since fonts covering many scripts have thousands of them.
+const MAX_RESULTS: usize = 512;
+/// The number of glyphs in each row of the grid.
+const COLUMNS: usize = 16;
+
+/// A dialog to browse the characters of a font and insert one at the caret of the text being edited.
+#[derive(Debug, Clone, Default)]
+pub struct GlyphPaletteDialogMessageHandler {
+	pub font_name: String,
+	/// The URL of the font file, which the glyphs are drawn with.
+	pub font_url: Option<String>,
+	pub characters: Vec<char>,
+	pub query: String,
+}
+
+impl MessageHandler<GlyphPaletteDialogMessage, ()> for GlyphPaletteDialogMessageHandler {
+	fn process_message(&mut self, message: GlyphPaletteDialogMessage, responses: &mut VecDeque<Message>, _data: ()) {
+		match message {
+			GlyphPaletteDialogMessage::Search { query } => {
+				if query == self.query {
+					return;
+				}
+				self.query = query;
+
+				// Only the glyph grid changes, so it's diffed against what's already shown rather than reopening the dialog
+				self.send_layout(responses, LayoutTarget::DialogColumn1);
+			}
+		}
+	}
+
+	advertise_actions! {GlyphPaletteDialogUpdate;}
+}
+
+impl GlyphPaletteDialogMessageHandler {
+	/// The characters matching the search query, with the character typed as the query itself coming first.
+	fn results(&self) -> Vec<char> {
+		let query = self.query.trim();
+		let mut results = self.characters.iter().copied().filter(|&character| matches_query(query, character)).collect::<Vec<_>>();
+		results.sort_by_key(|&character| character.to_string() != query);
+		results
+	}
+}
+
+/// Whether the search query finds the character, either by being the character itself or the start of its code point in hexadecimal, like "U+00E9" or "e9".
+fn matches_query(query: &str, character: char) -> bool {
+	if query.is_empty() || character.to_string() == query {
+		return true;
+	}
+
+	let hex = query.strip_prefix("U+").or_else(|| query.strip_prefix("u+")).unwrap_or(query);
+	if hex.is_empty() || !hex.chars().all(|digit| digit.is_ascii_hexdigit()) {
+		return false;
+	}
+	let code_point = character as u32;
+	u32::from_str_radix(hex, 16).is_ok_and(|value| value == code_point) || format!("{code_point:04X}").starts_with(&hex.to_ascii_uppercase())
+}
+
+impl DialogLayoutHolder for GlyphPaletteDialogMessageHandler {
+	const ICON: &'static str = "NodeText";
+	const TITLE: &'static str = "Glyph Palette";
+
+	fn layout_buttons(&self) -> Layout {
+		let widgets = vec![TextButton::new("Cancel").on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder()];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
+	}
+}
+
+impl LayoutHolder for GlyphPaletteDialogMessageHandler {
+	fn layout(&self) -> Layout {
+		let search = vec![
+			TextInput::new(&self.query)
+				.label(Some("Search".into()))
+				.tooltip("A character, or its code point like U+00E9")
+				.min_width(400)
+				.on_update(|text_input: &TextInput| GlyphPaletteDialogMessage::Search { query: text_input.value.clone() }.into())
+				.widget_holder(),
+		];
+
+		let mut layout = vec![LayoutGroup::Row { widgets: search }];
+
+		let results = self.results();
+		let description = match results.len() {
+			0 => format!("No matching glyphs in {}", self.font_name),
+			count if count > MAX_RESULTS => format!("Showing the first {MAX_RESULTS} of {count} matching glyphs in {}", self.font_name),
+			count => format!("{count} matching glyphs in {}", self.font_name),
+		};
+		layout.push(LayoutGroup::Row {
+			widgets: vec![TextLabel::new(description).italic(true).widget_holder()],
+		});
+
+		for row in results[..results.len().min(MAX_RESULTS)].chunks(COLUMNS) {
+			let widgets = row
+				.iter()
+				.map(|&character| {
+					TextButton::new(character.to_string())
+						.font_url(self.font_url.clone())
+						.min_width(32)
+						.tooltip(format!("U+{:04X}", character as u32))
+						.on_update(move |_| {
+							let text = character.to_string();
+							DialogMessage::CloseDialogAndThen {
+								followups: vec![TextToolMessage::InsertText { text }.into()],
+							}
+							.into()
+						})
+						.widget_holder()
+				})
+				.collect();
+			layout.push(LayoutGroup::Row { widgets });
+		}
+
+		Layout::WidgetLayout(WidgetLayout::new(layout))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::matches_query;
+
+	#[test]
+	fn glyphs_found_by_character_or_code_point() {
+		assert!(matches_query("é", 'é'));
+		assert!(matches_query("U+00E9", 'é'));
+		assert!(matches_query("u+e9", 'é'));
+		assert!(matches_query("e9", 'é'));
+		assert!(matches_query("00e", 'é'));
+		assert!(!matches_query("e8", 'é'));
+		assert!(!matches_query("U+", 'é'));
+		assert!(matches_query("", 'é'));
+	}
+}
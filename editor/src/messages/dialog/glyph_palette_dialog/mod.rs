@@ -0,0 +1,7 @@
+mod glyph_palette_dialog_message;
+mod glyph_palette_dialog_message_handler;
+
+#[doc(inline)]
+pub use glyph_palette_dialog_message::{GlyphPaletteDialogMessage, GlyphPaletteDialogMessageDiscriminant};
+#[doc(inline)]
+pub use glyph_palette_dialog_message_handler::GlyphPaletteDialogMessageHandler;
@@ -8,9 +8,14 @@
 mod dialog_message;
 mod dialog_message_handler;
 
+pub mod command_palette_dialog;
+pub mod contrast_check_dialog;
 pub mod export_dialog;
+pub mod find_replace_dialog;
+pub mod glyph_palette_dialog;
 pub mod new_document_dialog;
 pub mod preferences_dialog;
+pub mod recolor_artwork_dialog;
 pub mod simple_dialogs;
 
 #[doc(inline)]
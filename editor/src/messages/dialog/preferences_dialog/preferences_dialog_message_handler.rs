@@ -1,7 +1,14 @@
+use crate::messages::frontend::utility_types::FileType;
+use crate::messages::input_mapper::utility_types::misc::{KeymapPreset, MiddleMouseDrag};
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::node_graph::utility_types::GraphWireStyle;
-use crate::messages::preferences::SelectionMode;
+use crate::messages::portfolio::document::overlays::utility_types::OverlayColors;
+use crate::messages::preferences::{CursorStyle, SaveAction, SaveActionEdit, SaveActionKind, SelectionMode};
 use crate::messages::prelude::*;
+use crate::messages::radial_menu::utility_types::{RADIAL_MENU_SLOTS, RadialMenuItem};
+use graphene_core::application_io::Antialiasing;
+use graphene_core::raster::color::Color;
+use graphene_std::vector::style::FillChoice;
 
 pub struct PreferencesDialogMessageData<'a> {
 	pub preferences: &'a PreferencesMessageHandler,
@@ -17,6 +24,8 @@ impl MessageHandler<PreferencesDialogMessage, PreferencesDialogMessageData<'_>>
 
 		match message {
 			PreferencesDialogMessage::Confirm => {}
+			// The caches are filled and evicted by the node graph runtime as it renders, so the readout is only brought up to date when asked
+			PreferencesDialogMessage::RefreshMemoryUsage => {}
 		}
 
 		self.send_dialog_to_frontend(responses, preferences);
@@ -39,20 +48,165 @@ impl PreferencesDialogMessageHandler {
 
 		let navigation_header = vec![TextLabel::new("Navigation").italic(true).widget_holder()];
 
-		let zoom_with_scroll_tooltip = "Use the scroll wheel for zooming instead of vertically panning (not recommended for trackpads)";
-		let zoom_with_scroll = vec![
+		let scroll_wheel_tooltip = "What scrolling does in the viewport and node graph without any modifier keys held. Trackpad pinch gestures zoom either way.";
+		let scroll_wheel_label = vec![
 			Separator::new(SeparatorType::Unrelated).widget_holder(),
 			Separator::new(SeparatorType::Unrelated).widget_holder(),
-			CheckboxInput::new(preferences.zoom_with_scroll)
-				.tooltip(zoom_with_scroll_tooltip)
-				.on_update(|checkbox_input: &CheckboxInput| {
-					PreferencesMessage::ModifyLayout {
-						zoom_with_scroll: checkbox_input.checked,
+			TextLabel::new("Scroll Wheel").tooltip(scroll_wheel_tooltip).widget_holder(),
+		];
+		let scroll_wheel = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(vec![
+				RadioEntryData::new("Pan")
+					.label("Pan")
+					.tooltip("Scroll to pan vertically, Shift+scroll to pan horizontally, and Ctrl+scroll to zoom (recommended for trackpads)")
+					.on_update(|_| PreferencesMessage::ModifyLayout { zoom_with_scroll: false }.into()),
+				RadioEntryData::new("Zoom")
+					.label("Zoom")
+					.tooltip("Scroll to zoom, Ctrl+scroll to pan vertically, and Shift+scroll to pan horizontally")
+					.on_update(|_| PreferencesMessage::ModifyLayout { zoom_with_scroll: true }.into()),
+			])
+			.selected_index(Some(preferences.zoom_with_scroll as u32))
+			.widget_holder(),
+		];
+
+		let middle_mouse_drag_tooltip = "What dragging with the middle mouse button does in the viewport. Panning then moves to the modifier key of the navigation it replaces.";
+		let middle_mouse_drag_label = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Middle Mouse Drag").tooltip(middle_mouse_drag_tooltip).widget_holder(),
+		];
+		let middle_mouse_drag = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(
+				MiddleMouseDrag::ALL
+					.into_iter()
+					.map(|drag| {
+						RadioEntryData::new(drag.to_string())
+							.label(drag.to_string())
+							.on_update(move |_| PreferencesMessage::MiddleMouseDrag { drag }.into())
+					})
+					.collect(),
+			)
+			.selected_index(MiddleMouseDrag::ALL.iter().position(|&drag| drag == preferences.middle_mouse_drag).map(|index| index as u32))
+			.widget_holder(),
+		];
+
+		let auto_pan_edge_margin_tooltip = "Distance inside the edges of the viewport within which dragging starts panning the canvas, so it can be panned without reaching the edge of a small screen";
+		let auto_pan_edge_margin = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Auto-Pan Edge Margin").table_align(true).tooltip(auto_pan_edge_margin_tooltip).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(preferences.auto_pan_edge_margin))
+				.unit(" px")
+				.min(0.)
+				.max(200.)
+				.int()
+				.tooltip(auto_pan_edge_margin_tooltip)
+				.on_update(|number_input: &NumberInput| {
+					PreferencesMessage::AutoPanEdgeMargin {
+						margin: number_input.value.unwrap_or(crate::consts::AUTO_PAN_EDGE_MARGIN),
 					}
 					.into()
 				})
 				.widget_holder(),
-			TextLabel::new("Zoom with Scroll").table_align(true).tooltip(zoom_with_scroll_tooltip).widget_holder(),
+		];
+
+		let auto_pan_speed_tooltip = "How quickly the canvas pans while dragging past the edge margin, relative to the default speed";
+		let auto_pan_speed = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Auto-Pan Speed").table_align(true).tooltip(auto_pan_speed_tooltip).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(preferences.auto_pan_speed / crate::consts::DRAG_BEYOND_VIEWPORT_SPEED_FACTOR * 100.))
+				.unit("%")
+				.min(10.)
+				.max(1000.)
+				.tooltip(auto_pan_speed_tooltip)
+				.on_update(|number_input: &NumberInput| {
+					PreferencesMessage::AutoPanSpeed {
+						speed: number_input.value.map_or(crate::consts::DRAG_BEYOND_VIEWPORT_SPEED_FACTOR, |percent| {
+							percent / 100. * crate::consts::DRAG_BEYOND_VIEWPORT_SPEED_FACTOR
+						}),
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let auto_pan_acceleration_tooltip = "How much faster the canvas pans for each second it keeps panning, so it can start slowly and still cover long distances";
+		let auto_pan_acceleration = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Auto-Pan Acceleration").table_align(true).tooltip(auto_pan_acceleration_tooltip).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(preferences.auto_pan_acceleration * 100.))
+				.unit("% per second")
+				.min(0.)
+				.max(1000.)
+				.tooltip(auto_pan_acceleration_tooltip)
+				.on_update(|number_input: &NumberInput| {
+					PreferencesMessage::AutoPanAcceleration {
+						acceleration: number_input.value.unwrap_or(0.) / 100.,
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		// ========
+		// KEYBOARD
+		// ========
+
+		let keyboard_header = vec![TextLabel::new("Keyboard").italic(true).widget_holder()];
+
+		let keymap_preset_tooltip = "Default shortcuts modeled after another application, to which your customized shortcuts are applied";
+		let keymap_preset_label = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Keymap").tooltip(keymap_preset_tooltip).widget_holder(),
+		];
+		let keymap_preset = RadioInput::new(
+			KeymapPreset::ALL
+				.into_iter()
+				.map(|preset| {
+					RadioEntryData::new(preset.to_string())
+						.label(preset.to_string())
+						.on_update(move |_| PreferencesMessage::KeymapPreset { preset }.into())
+				})
+				.collect(),
+		)
+		.selected_index(KeymapPreset::ALL.iter().position(|&preset| preset == preferences.keymap_preset).map(|index| index as u32))
+		.widget_holder();
+		let customized = preferences.custom_shortcuts.len();
+		let keymap_preset = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			keymap_preset,
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextButton::new(if customized == 0 { "Customize".to_string() } else { format!("Customize ({customized})") })
+				.tooltip("View and rebind the keyboard shortcut of every action")
+				.on_update(|_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![DialogMessage::RequestKeyboardShortcutsDialog.into()],
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let sticky_modifier_keys_tooltip = "Pressing Shift, Alt, Ctrl, or Cmd toggles it on until it's pressed again, instead of requiring it to be held during a drag";
+		let sticky_modifier_keys = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(preferences.sticky_modifier_keys)
+				.tooltip(sticky_modifier_keys_tooltip)
+				.on_update(|checkbox_input: &CheckboxInput| PreferencesMessage::StickyModifierKeys { enabled: checkbox_input.checked }.into())
+				.widget_holder(),
+			TextLabel::new("Sticky Modifier Keys").table_align(true).tooltip(sticky_modifier_keys_tooltip).widget_holder(),
 		];
 
 		// =======
@@ -104,6 +258,382 @@ impl PreferencesDialogMessageHandler {
 			selection_mode,
 		];
 
+		let undo_history_tooltip = "Number of steps which can be undone. Older steps are forgotten beyond this limit.";
+		let undo_history_length = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Undo History").table_align(true).tooltip(undo_history_tooltip).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(preferences.undo_history_length as f64))
+				.unit(" steps")
+				.int()
+				.min(1.)
+				.max(10000.)
+				.tooltip(undo_history_tooltip)
+				.on_update(|number_input: &NumberInput| {
+					PreferencesMessage::UndoHistoryLength {
+						length: number_input.value.unwrap_or_default() as usize,
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let nudge_tooltip = "Distance moved by each arrow key press when nudging the selected layers or points. This is measured in document units, so it's the same at any zoom level.";
+		let nudge_distance = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Nudge Distance").table_align(true).tooltip(nudge_tooltip).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(preferences.nudge_distance))
+				.unit(" px")
+				.min(0.)
+				.tooltip(nudge_tooltip)
+				.on_update(|number_input: &NumberInput| {
+					PreferencesMessage::NudgeDistance {
+						distance: number_input.value.unwrap_or(crate::consts::NUDGE_AMOUNT),
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let large_nudge_tooltip =
+			"Distance moved by each arrow key press while holding Shift when nudging the selected layers or points. This is measured in document units, so it's the same at any zoom level.";
+		let large_nudge_distance = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Large Nudge Distance").table_align(true).tooltip(large_nudge_tooltip).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(preferences.large_nudge_distance))
+				.unit(" px")
+				.min(0.)
+				.tooltip(large_nudge_tooltip)
+				.on_update(|number_input: &NumberInput| {
+					PreferencesMessage::LargeNudgeDistance {
+						distance: number_input.value.unwrap_or(crate::consts::BIG_NUDGE_AMOUNT),
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let external_image_editor_tooltip =
+			"The application image layers are opened in by Layer > Edit Externally, in the desktop app. If empty, the system's default application for PNG files is used.";
+		let external_image_editor = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("External Image Editor").table_align(true).tooltip(external_image_editor_tooltip).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextInput::new(&preferences.external_image_editor)
+				.tooltip(external_image_editor_tooltip)
+				.on_update(|text_input: &TextInput| PreferencesMessage::ExternalImageEditor { path: text_input.value.clone() }.into())
+				.min_width(200)
+				.widget_holder(),
+		];
+
+		// ===========
+		// RADIAL MENU
+		// ===========
+
+		let radial_menu_header = vec![TextLabel::new("Radial Menu").italic(true).widget_holder()];
+
+		let radial_menu_on_hold_tooltip = "Open the radial menu by holding down the right mouse button in the viewport, in addition to pressing Q";
+		let radial_menu_on_hold = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(preferences.radial_menu_on_hold)
+				.tooltip(radial_menu_on_hold_tooltip)
+				.on_update(|checkbox_input: &CheckboxInput| PreferencesMessage::RadialMenuOnHold { enabled: checkbox_input.checked }.into())
+				.widget_holder(),
+			TextLabel::new("Open with Right-Click Hold").table_align(true).tooltip(radial_menu_on_hold_tooltip).widget_holder(),
+		];
+
+		let radial_menu_slots_label = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Slots")
+				.tooltip("The tools and commands placed around the radial menu, clockwise from the top")
+				.widget_holder(),
+		];
+		let radial_menu_slots = (0..RADIAL_MENU_SLOTS)
+			.collect::<Vec<_>>()
+			.chunks(2)
+			.map(|slots| {
+				let mut widgets = vec![Separator::new(SeparatorType::Unrelated).widget_holder(), Separator::new(SeparatorType::Unrelated).widget_holder()];
+				for &slot in slots {
+					let item = preferences.radial_menu_items.get(slot).copied().unwrap_or_default();
+					widgets.push(radial_menu_slot_dropdown(slot, item));
+					widgets.push(Separator::new(SeparatorType::Related).widget_holder());
+				}
+				widgets.pop();
+				LayoutGroup::Row { widgets }
+			})
+			.collect::<Vec<_>>();
+
+		// ========
+		// OVERLAYS
+		// ========
+
+		let overlays_header = vec![TextLabel::new("Overlays").italic(true).widget_holder()];
+
+		let overlay_color_presets = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Colors").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextButton::new("Default")
+				.tooltip("Use the standard overlay colors")
+				.on_update(|_| PreferencesMessage::OverlayColors { colors: OverlayColors::default() }.into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			TextButton::new("High Contrast")
+				.tooltip("Use overlay colors which stand out against most artwork")
+				.on_update(|_| {
+					PreferencesMessage::OverlayColors {
+						colors: OverlayColors::high_contrast(),
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let overlay_color = |label: &str, tooltip: &str, color: &str, update: fn(&mut OverlayColors, String)| {
+			let colors = preferences.overlay_colors.clone();
+			let value = color.strip_prefix('#').and_then(Color::from_rgb_str).unwrap_or(Color::BLACK);
+
+			vec![
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextLabel::new(label).table_align(true).tooltip(tooltip).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				ColorInput::new(FillChoice::Solid(value.to_gamma_srgb()))
+					.allow_none(false)
+					.tooltip(tooltip)
+					.on_update(move |color_input: &ColorInput| {
+						let mut colors = colors.clone();
+						if let Some(color) = color_input.value.as_solid() {
+							update(&mut colors, format!("#{}", color.to_rgb_hex_srgb_from_gamma()));
+						}
+						PreferencesMessage::OverlayColors { colors }.into()
+					})
+					.widget_holder(),
+			]
+		};
+		let overlay_colors = &preferences.overlay_colors;
+		let overlay_accent_color = overlay_color(
+			"Accent",
+			"Color of selections, outlines, paths, handles, and most other overlays",
+			&overlay_colors.accent,
+			|colors, color| colors.accent = color,
+		);
+		let overlay_snapping_color = overlay_color(
+			"Snapping",
+			"Color of the indicators shown where the pointer snaps to something",
+			&overlay_colors.snapping,
+			|colors, color| colors.snapping = color,
+		);
+		let overlay_text_bounds_color = overlay_color(
+			"Text Bounds",
+			"Color of the box around text while it's being placed or edited",
+			&overlay_colors.text_bounds,
+			|colors, color| colors.text_bounds = color,
+		);
+		let overlay_fill_color = overlay_color("Fill", "Color inside unselected handles, anchors, and other hollow markers", &overlay_colors.fill, |colors, color| {
+			colors.fill = color
+		});
+
+		// ======
+		// CURSOR
+		// ======
+
+		let cursor_header = vec![TextLabel::new("Cursor").italic(true).widget_holder()];
+
+		let cursor_style_label = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Crosshair")
+				.tooltip("The cursor shown in the viewport by the tools which draw, like the Pen and Rectangle tools")
+				.widget_holder(),
+		];
+		let cursor_style = RadioInput::new(
+			[CursorStyle::Standard, CursorStyle::FullViewportCrosshair, CursorStyle::Precision]
+				.into_iter()
+				.map(|style| {
+					RadioEntryData::new(style.to_string())
+						.label(style.to_string())
+						.tooltip(style.tooltip_description())
+						.on_update(move |_| PreferencesMessage::CursorStyle { style }.into())
+				})
+				.collect(),
+		)
+		.selected_index(Some(preferences.cursor_style as u32))
+		.widget_holder();
+		let cursor_style = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			cursor_style,
+		];
+
+		let precision_cursor_size_tooltip = "Width and height of the crosshair cursor when the \"Precision\" style is chosen";
+		let precision_cursor_size = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Precision Size").table_align(true).tooltip(precision_cursor_size_tooltip).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(preferences.precision_cursor_size as f64))
+				.unit(" px")
+				.int()
+				.min(1.)
+				.max(crate::consts::PRECISION_CURSOR_MAX_SIZE as f64)
+				.tooltip(precision_cursor_size_tooltip)
+				.on_update(|number_input: &NumberInput| {
+					PreferencesMessage::PrecisionCursorSize {
+						size: number_input.value.map_or(crate::consts::PRECISION_CURSOR_SIZE, |size| size as u32),
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		// =========
+		// RENDERING
+		// =========
+
+		let rendering_header = vec![TextLabel::new("Rendering").italic(true).widget_holder()];
+
+		let antialiasing_tooltip = "Smoothness of the edges of shapes drawn in the viewport, which is faster to render with less. Exports are always fully antialiased.";
+		let antialiasing_label = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Anti-Aliasing").tooltip(antialiasing_tooltip).widget_holder(),
+		];
+		let antialiasing_entries = [
+			(Antialiasing::Off, "Off", "Draw aliased edges, which is fastest"),
+			(Antialiasing::Fast, "Fast", "Smooth edges with fewer samples"),
+			(Antialiasing::Full, "Full", "Smooth edges as precisely as possible"),
+		];
+		let viewport_antialiasing = RadioInput::new(
+			antialiasing_entries
+				.into_iter()
+				.map(|(antialiasing, label, tooltip)| {
+					RadioEntryData::new(label)
+						.label(label)
+						.tooltip(tooltip)
+						.on_update(move |_| PreferencesMessage::ViewportAntialiasing { antialiasing }.into())
+				})
+				.collect(),
+		)
+		.selected_index(Some(preferences.viewport_antialiasing as u32))
+		.widget_holder();
+		let viewport_antialiasing = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			viewport_antialiasing,
+		];
+
+		let degrade_while_interacting_tooltip = "Render the viewport at a reduced resolution without anti-aliasing while dragging or zooming, then refine it to full quality once idle";
+		let degrade_while_interacting = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(preferences.degrade_while_interacting)
+				.tooltip(degrade_while_interacting_tooltip)
+				.on_update(|checkbox_input: &CheckboxInput| PreferencesMessage::DegradeWhileInteracting { enabled: checkbox_input.checked }.into())
+				.widget_holder(),
+			TextLabel::new("Lower Quality While Interacting")
+				.table_align(true)
+				.tooltip(degrade_while_interacting_tooltip)
+				.widget_holder(),
+		];
+
+		// ======
+		// MEMORY
+		// ======
+
+		let memory_header = vec![TextLabel::new("Memory").italic(true).widget_holder()];
+
+		let memory_budget_tooltip = "Maximum memory used for caching images, fonts, and rendered layers. The least recently used are discarded beyond this limit.";
+		let memory_budget = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Cache Budget").table_align(true).tooltip(memory_budget_tooltip).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(preferences.memory_budget_mebibytes as f64))
+				.unit(" MiB")
+				.int()
+				.min(64.)
+				.max(16384.)
+				.tooltip(memory_budget_tooltip)
+				.on_update(|number_input: &NumberInput| {
+					PreferencesMessage::MemoryBudget {
+						mebibytes: number_input.value.unwrap_or_default() as u32,
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let usage = graphene_core::memory_budget::memory_usage();
+		let mebibytes = |bytes: usize| bytes as f64 / (1024. * 1024.);
+		let memory_usage_tooltip = format!(
+			"Images: {:.1} MiB\nFonts: {:.1} MiB\nRendered layers: {:.1} MiB",
+			mebibytes(usage.images),
+			mebibytes(usage.fonts),
+			mebibytes(usage.renders)
+		);
+		let memory_usage = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new(format!("Currently Using {:.1} MiB", mebibytes(usage.total())))
+				.table_align(true)
+				.tooltip(memory_usage_tooltip)
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			IconButton::new("Reload", 24)
+				.tooltip("Update the memory currently used")
+				.on_update(|_| PreferencesDialogMessage::RefreshMemoryUsage.into())
+				.widget_holder(),
+		];
+
+		// ============
+		// SAVE ACTIONS
+		// ============
+
+		let save_actions_header = vec![TextLabel::new("Save Actions").italic(true).widget_holder()];
+
+		let save_actions = preferences
+			.save_actions
+			.iter()
+			.enumerate()
+			.flat_map(|(index, action)| save_action_rows(index, action))
+			.collect::<Vec<_>>();
+
+		let save_action_kinds = [
+			(SaveActionKind::export(), "Export the document after each save, to a folder on desktop or as a download in the browser"),
+			(
+				SaveActionKind::Command { command: String::new() },
+				"Run a shell command after each save, which is only possible in the desktop app",
+			),
+			(SaveActionKind::Webhook { url: String::new() }, "Send a POST request with the document's name to a URL after each save"),
+		];
+		let mut add_save_action = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Add").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+		];
+		add_save_action.extend(save_action_kinds.into_iter().map(|(kind, tooltip)| {
+			TextButton::new(kind.to_string())
+				.tooltip(tooltip)
+				.on_update(move |_| {
+					PreferencesMessage::AddSaveAction {
+						action: SaveAction { enabled: true, kind: kind.clone() },
+					}
+					.into()
+				})
+				.widget_holder()
+		}));
+
 		// ============
 		// EXPERIMENTAL
 		// ============
@@ -182,12 +712,54 @@ impl PreferencesDialogMessageHandler {
 		// 		.widget_holder(),
 		// ];
 
-		Layout::WidgetLayout(WidgetLayout::new(vec![
+		let mut layout = vec![
 			LayoutGroup::Row { widgets: navigation_header },
-			LayoutGroup::Row { widgets: zoom_with_scroll },
+			LayoutGroup::Row { widgets: scroll_wheel_label },
+			LayoutGroup::Row { widgets: scroll_wheel },
+			LayoutGroup::Row { widgets: middle_mouse_drag_label },
+			LayoutGroup::Row { widgets: middle_mouse_drag },
+			LayoutGroup::Row { widgets: auto_pan_edge_margin },
+			LayoutGroup::Row { widgets: auto_pan_speed },
+			LayoutGroup::Row { widgets: auto_pan_acceleration },
+			LayoutGroup::Row { widgets: keyboard_header },
+			LayoutGroup::Row { widgets: keymap_preset_label },
+			LayoutGroup::Row { widgets: keymap_preset },
+			LayoutGroup::Row { widgets: sticky_modifier_keys },
 			LayoutGroup::Row { widgets: editing_header },
 			LayoutGroup::Row { widgets: selection_label },
 			LayoutGroup::Row { widgets: selection_mode },
+			LayoutGroup::Row { widgets: undo_history_length },
+			LayoutGroup::Row { widgets: nudge_distance },
+			LayoutGroup::Row { widgets: large_nudge_distance },
+			LayoutGroup::Row { widgets: external_image_editor },
+			LayoutGroup::Row { widgets: radial_menu_header },
+			LayoutGroup::Row { widgets: radial_menu_on_hold },
+			LayoutGroup::Row { widgets: radial_menu_slots_label },
+		];
+		layout.extend(radial_menu_slots);
+		layout.extend([
+			LayoutGroup::Row { widgets: overlays_header },
+			LayoutGroup::Row { widgets: overlay_color_presets },
+			LayoutGroup::Row { widgets: overlay_accent_color },
+			LayoutGroup::Row { widgets: overlay_snapping_color },
+			LayoutGroup::Row { widgets: overlay_text_bounds_color },
+			LayoutGroup::Row { widgets: overlay_fill_color },
+			LayoutGroup::Row { widgets: cursor_header },
+			LayoutGroup::Row { widgets: cursor_style_label },
+			LayoutGroup::Row { widgets: cursor_style },
+			LayoutGroup::Row { widgets: precision_cursor_size },
+			LayoutGroup::Row { widgets: rendering_header },
+			LayoutGroup::Row { widgets: antialiasing_label },
+			LayoutGroup::Row { widgets: viewport_antialiasing },
+			LayoutGroup::Row { widgets: degrade_while_interacting },
+			LayoutGroup::Row { widgets: memory_header },
+			LayoutGroup::Row { widgets: memory_budget },
+			LayoutGroup::Row { widgets: memory_usage },
+			LayoutGroup::Row { widgets: save_actions_header },
+		]);
+		layout.extend(save_actions);
+		layout.extend([
+			LayoutGroup::Row { widgets: add_save_action },
 			LayoutGroup::Row { widgets: experimental_header },
 			LayoutGroup::Row { widgets: node_graph_wires_label },
 			LayoutGroup::Row { widgets: graph_wire_style },
@@ -195,7 +767,9 @@ impl PreferencesDialogMessageHandler {
 			LayoutGroup::Row { widgets: vector_meshes },
 			// LayoutGroup::Row { widgets: imaginate_server_hostname },
 			// LayoutGroup::Row { widgets: imaginate_refresh_frequency },
-		]))
+		]);
+
+		Layout::WidgetLayout(WidgetLayout::new(layout))
 	}
 
 	pub fn send_layout(&self, responses: &mut VecDeque<Message>, layout_target: LayoutTarget, preferences: &PreferencesMessageHandler) {
@@ -250,3 +824,121 @@ impl PreferencesDialogMessageHandler {
 		});
 	}
 }
+
+/// A dropdown choosing the tool or command in one slot of the radial menu.
+fn radial_menu_slot_dropdown(slot: usize, selected: RadialMenuItem) -> WidgetHolder {
+	let items = RadialMenuItem::list();
+	let entries = items
+		.iter()
+		.map(|&item| {
+			MenuListEntry::new(format!("{item:?}"))
+				.label(item.label())
+				.icon(item.icon().unwrap_or_default())
+				.on_update(move |_| PreferencesMessage::RadialMenuItem { slot, item }.into())
+		})
+		.collect();
+
+	DropdownInput::new(vec![entries])
+		.selected_index(items.iter().position(|&item| item == selected).map(|index| index as u32))
+		.tooltip(format!("Slot {}", slot + 1))
+		.widget_holder()
+}
+
+/// The rows for editing a save action, starting with one to enable or delete it.
+fn save_action_rows(index: usize, action: &SaveAction) -> Vec<LayoutGroup> {
+	let edit = move |edit: SaveActionEdit| -> Message { PreferencesMessage::EditSaveAction { index, edit }.into() };
+	let indent = || [Separator::new(SeparatorType::Unrelated).widget_holder(), Separator::new(SeparatorType::Unrelated).widget_holder()];
+
+	let mut header = Vec::from(indent());
+	header.extend([
+		CheckboxInput::new(action.enabled)
+			.tooltip("Run this action after each save")
+			.on_update(move |checkbox_input: &CheckboxInput| edit(SaveActionEdit::Enabled(checkbox_input.checked)))
+			.widget_holder(),
+		TextLabel::new(action.kind.to_string()).table_align(true).widget_holder(),
+		Separator::new(SeparatorType::Related).widget_holder(),
+		IconButton::new("Trash", 24)
+			.tooltip("Delete Save Action")
+			.on_update(move |_| PreferencesMessage::DeleteSaveAction { index }.into())
+			.widget_holder(),
+	]);
+	let mut rows = vec![LayoutGroup::Row { widgets: header }];
+
+	let text_row = |label: &str, value: &str, tooltip: &str, update: fn(String) -> SaveActionEdit| {
+		let mut widgets = Vec::from(indent());
+		widgets.extend([
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new(label).table_align(true).tooltip(tooltip).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextInput::new(value)
+				.tooltip(tooltip)
+				.on_update(move |text_input: &TextInput| edit(update(text_input.value.clone())))
+				.min_width(200)
+				.widget_holder(),
+		]);
+		LayoutGroup::Row { widgets }
+	};
+
+	match &action.kind {
+		SaveActionKind::Export {
+			file_type,
+			scale_factor,
+			each_artboard,
+			transparent_background,
+			folder,
+		} => {
+			let file_types = [
+				(FileType::Png, "PNG"),
+				(FileType::Jpg, "JPG"),
+				(FileType::Svg, "SVG"),
+				(FileType::Tiff, "TIFF"),
+				(FileType::Pdf, "PDF"),
+				(FileType::Eps, "EPS"),
+			];
+			let entries = file_types
+				.into_iter()
+				.map(|(file_type, name)| MenuListEntry::new(format!("{file_type:?}")).label(name).on_update(move |_| edit(SaveActionEdit::FileType(file_type))))
+				.collect();
+
+			let mut widgets = Vec::from(indent());
+			widgets.extend([
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				DropdownInput::new(vec![entries]).selected_index(Some(*file_type as u32)).tooltip("File Type").widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				NumberInput::new(Some(*scale_factor))
+					.label("Scale")
+					.unit("x")
+					.min(0.)
+					.tooltip("Scale factor of raster exports")
+					.on_update(move |number_input: &NumberInput| edit(SaveActionEdit::ScaleFactor(number_input.value.unwrap_or(1.))))
+					.widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				CheckboxInput::new(*each_artboard)
+					.tooltip("Export each artboard to its own file instead of all the artwork to one")
+					.on_update(move |checkbox_input: &CheckboxInput| edit(SaveActionEdit::EachArtboard(checkbox_input.checked)))
+					.widget_holder(),
+				TextLabel::new("Each Artboard").widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				CheckboxInput::new(*transparent_background)
+					.tooltip("Leave the background transparent, except for JPG which has no transparency")
+					.on_update(move |checkbox_input: &CheckboxInput| edit(SaveActionEdit::TransparentBackground(checkbox_input.checked)))
+					.widget_holder(),
+				TextLabel::new("Transparent").widget_holder(),
+			]);
+			rows.push(LayoutGroup::Row { widgets });
+
+			let folder_tooltip = "The folder the files are written to in the desktop app, which is created if it doesn't exist. If empty, or in the browser, they're downloaded like a normal export.";
+			rows.push(text_row("Folder", folder, folder_tooltip, SaveActionEdit::Folder));
+		}
+		SaveActionKind::Command { command } => {
+			let command_tooltip = "The shell command run after each save in the desktop app, with the document's name in the GRAPHITE_DOCUMENT_NAME environment variable";
+			rows.push(text_row("Command", command, command_tooltip, SaveActionEdit::Command));
+		}
+		SaveActionKind::Webhook { url } => {
+			let url_tooltip = "The URL sent a POST request after each save, with the document's name in a JSON body like {\"document\": \"Untitled Document\"}";
+			rows.push(text_row("URL", url, url_tooltip, SaveActionEdit::Url));
+		}
+	}
+
+	rows
+}
@@ -0,0 +1,7 @@
+mod recolor_artwork_dialog_message;
+mod recolor_artwork_dialog_message_handler;
+
+#[doc(inline)]
+pub use recolor_artwork_dialog_message::{RecolorArtworkDialogMessage, RecolorArtworkDialogMessageDiscriminant};
+#[doc(inline)]
+pub use recolor_artwork_dialog_message_handler::RecolorArtworkDialogMessageHandler;
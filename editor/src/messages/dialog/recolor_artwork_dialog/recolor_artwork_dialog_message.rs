@@ -0,0 +1,13 @@
+use crate::messages::prelude::*;
+use graphene_core::Color;
+
+#[impl_message(Message, DialogMessage, RecolorArtworkDialog)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum RecolorArtworkDialogMessage {
+	Replacement { index: usize, color: Color },
+	HueShift(f64),
+	ReduceToSwatches,
+	Reset,
+
+	Submit,
+}
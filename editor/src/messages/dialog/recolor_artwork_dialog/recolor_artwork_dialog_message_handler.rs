@@ -0,0 +1,125 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::utility_types::recolor;
+use crate::messages::prelude::*;
+use graphene_core::Color;
+use graphene_core::vector::style::FillChoice;
+
+/// A dialog listing the distinct fill and stroke colors of the selected artwork, to remap each of them, shift their hues, or reduce them to the document's swatches.
+#[derive(Debug, Clone, Default)]
+pub struct RecolorArtworkDialogMessageHandler {
+	/// Each original color of the artwork paired with the color replacing it, in gamma space.
+	pub colors: Vec<(Color, Color)>,
+	/// The number of degrees the hues of the original colors were last shifted by.
+	pub hue_shift: f64,
+	/// The document's swatches, in gamma space.
+	pub swatches: Vec<Color>,
+}
+
+impl MessageHandler<RecolorArtworkDialogMessage, ()> for RecolorArtworkDialogMessageHandler {
+	fn process_message(&mut self, message: RecolorArtworkDialogMessage, responses: &mut VecDeque<Message>, _data: ()) {
+		match message {
+			RecolorArtworkDialogMessage::Replacement { index, color } => {
+				if let Some((_, replacement)) = self.colors.get_mut(index) {
+					*replacement = color;
+				}
+			}
+			RecolorArtworkDialogMessage::HueShift(degrees) => {
+				self.hue_shift = degrees;
+				for (original, replacement) in self.colors.iter_mut() {
+					*replacement = recolor::shift_hue(*original, degrees);
+				}
+			}
+			RecolorArtworkDialogMessage::ReduceToSwatches => {
+				for (_, replacement) in self.colors.iter_mut() {
+					*replacement = recolor::nearest_color(*replacement, &self.swatches);
+				}
+			}
+			RecolorArtworkDialogMessage::Reset => {
+				self.hue_shift = 0.;
+				for (original, replacement) in self.colors.iter_mut() {
+					*replacement = *original;
+				}
+			}
+			RecolorArtworkDialogMessage::Submit => {
+				let replacements = self.colors.clone();
+				responses.add(DocumentMessage::RecolorArtwork { replacements });
+			}
+		}
+
+		self.send_dialog_to_frontend(responses);
+	}
+
+	advertise_actions! {RecolorArtworkDialogUpdate;}
+}
+
+impl DialogLayoutHolder for RecolorArtworkDialogMessageHandler {
+	const ICON: &'static str = "CustomColor";
+	const TITLE: &'static str = "Recolor Artwork";
+
+	fn layout_buttons(&self) -> Layout {
+		let widgets = vec![
+			TextButton::new("Recolor")
+				.emphasized(true)
+				.on_update(|_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![RecolorArtworkDialogMessage::Submit.into()],
+					}
+					.into()
+				})
+				.widget_holder(),
+			TextButton::new("Cancel").on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder(),
+		];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
+	}
+}
+
+impl LayoutHolder for RecolorArtworkDialogMessageHandler {
+	fn layout(&self) -> Layout {
+		let hue_shift = vec![
+			TextLabel::new("Shift Hue").table_align(true).min_width(90).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(self.hue_shift))
+				.unit("°")
+				.min(-180.)
+				.max(180.)
+				.min_width(100)
+				.on_update(|number_input: &NumberInput| RecolorArtworkDialogMessage::HueShift(number_input.value.unwrap_or_default()).into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextButton::new("Reduce to Swatches")
+				.tooltip("Replace each color with the closest of the document's swatches")
+				.disabled(self.swatches.is_empty())
+				.on_update(|_| RecolorArtworkDialogMessage::ReduceToSwatches.into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			TextButton::new("Reset")
+				.tooltip("Restore every color to its original")
+				.on_update(|_| RecolorArtworkDialogMessage::Reset.into())
+				.widget_holder(),
+		];
+
+		let mut rows = vec![LayoutGroup::Row { widgets: hue_shift }];
+		rows.extend(self.colors.iter().enumerate().map(|(index, &(original, replacement))| {
+			let widgets = vec![
+				TextLabel::new(format!("#{}", original.to_rgb_hex_srgb_from_gamma())).table_align(true).min_width(90).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				ColorInput::new(FillChoice::Solid(original)).allow_none(false).disabled(true).tooltip("Original color").widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				IconLabel::new("KeyboardArrowRight").widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				ColorInput::new(FillChoice::Solid(replacement))
+					.allow_none(false)
+					.tooltip("Replacement color")
+					.on_update(move |color_input: &ColorInput| match color_input.value.as_solid() {
+						Some(color) => RecolorArtworkDialogMessage::Replacement { index, color }.into(),
+						None => Message::NoOp,
+					})
+					.widget_holder(),
+			];
+			LayoutGroup::Row { widgets }
+		}));
+
+		Layout::WidgetLayout(WidgetLayout::new(rows))
+	}
+}
@@ -0,0 +1,190 @@
+use crate::messages::input_mapper::input_mappings::keymap;
+use crate::messages::input_mapper::key_mapping::MappingVariant;
+use crate::messages::input_mapper::utility_types::input_keyboard::{Key, KeysGroup};
+use crate::messages::input_mapper::utility_types::misc::{CustomShortcut, KeymapPreset, MiddleMouseDrag};
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::*;
+use convert_case::{Case, Casing};
+
+/// A dialog listing every keyboard shortcut in the keymap, where each one can be rebound by typing a new key combination.
+pub struct KeyboardShortcutsDialog {
+	pub mapping_variant: MappingVariant,
+	pub preset: KeymapPreset,
+	pub middle_mouse_drag: MiddleMouseDrag,
+	pub custom_shortcuts: Vec<CustomShortcut>,
+	pub rejected_shortcut: Option<RejectedShortcut>,
+}
+
+/// A key combination typed for an action which couldn't be used as its shortcut, along with the reason why.
+pub struct RejectedShortcut {
+	pub action: String,
+	pub shortcut: String,
+	pub error: String,
+}
+
+/// An action with the key combinations which currently trigger it.
+struct ShortcutRow {
+	action: String,
+	label: String,
+	scope: String,
+	keys: Vec<KeysGroup>,
+	custom: bool,
+}
+
+impl KeyboardShortcutsDialog {
+	pub fn new(preferences: &PreferencesMessageHandler, rejected_shortcut: Option<RejectedShortcut>) -> Self {
+		Self {
+			mapping_variant: preferences.mapping_variant(),
+			preset: preferences.keymap_preset,
+			middle_mouse_drag: preferences.middle_mouse_drag,
+			custom_shortcuts: preferences.custom_shortcuts.clone(),
+			rejected_shortcut,
+		}
+	}
+
+	fn rows(&self) -> Vec<ShortcutRow> {
+		let mapping = keymap(self.mapping_variant.clone(), self.preset, self.middle_mouse_drag, &self.custom_shortcuts);
+
+		let mut rows: Vec<ShortcutRow> = Vec::new();
+		for (entry, key) in mapping.shortcut_entries() {
+			let Ok(action) = serde_json::to_string(&entry.action) else { continue };
+
+			let mut keys = entry.modifiers.iter().filter_map(|index| Key::try_from(index as u8).ok()).collect::<Vec<_>>();
+			keys.push(key);
+			let keys = KeysGroup(keys);
+
+			if let Some(row) = rows.iter_mut().find(|row| row.action == action) {
+				if !row.keys.contains(&keys) {
+					row.keys.push(keys);
+				}
+				continue;
+			}
+
+			let local_name = entry.action.to_discriminant().local_name();
+			let (parent, name) = local_name.rsplit_once('.').unwrap_or(("", &local_name));
+			let category = parent.rsplit('.').next().filter(|category| !category.is_empty()).unwrap_or("General");
+
+			rows.push(ShortcutRow {
+				custom: self.custom_shortcuts.iter().any(|custom| custom.action == action),
+				action,
+				label: format!("{}: {}", category.to_case(Case::Title), name.to_case(Case::Title)),
+				scope: shortcut_scope(parent).to_string(),
+				keys: vec![keys],
+			});
+		}
+
+		rows.sort_by(|a, b| a.label.cmp(&b.label));
+		rows
+	}
+}
+
+/// Shortcuts only conflict with others in the same scope, since tool and node graph shortcuts are only active while that tool or the graph is in use.
+fn shortcut_scope(parent: &str) -> &str {
+	if parent.starts_with("Tool.") || parent.starts_with("Portfolio.Document.NodeGraph") {
+		parent
+	} else {
+		""
+	}
+}
+
+impl DialogLayoutHolder for KeyboardShortcutsDialog {
+	const ICON: &'static str = "Settings";
+	const TITLE: &'static str = "Keyboard Shortcuts";
+
+	fn layout_buttons(&self) -> Layout {
+		let widgets = vec![
+			TextButton::new("Done")
+				.emphasized(true)
+				.on_update(|_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![DialogMessage::RequestPreferencesDialog.into()],
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
+	}
+}
+
+impl LayoutHolder for KeyboardShortcutsDialog {
+	fn layout(&self) -> Layout {
+		let rows = self.rows();
+
+		let description = "Type a new key combination, like \"Ctrl Shift K\", to rebind a shortcut.";
+		let mut layout = vec![
+			LayoutGroup::Row {
+				widgets: vec![TextLabel::new(format!("{} Keymap", self.preset)).bold(true).widget_holder()],
+			},
+			LayoutGroup::Row {
+				widgets: vec![TextLabel::new(description).multiline(true).widget_holder()],
+			},
+		];
+
+		for row in &rows {
+			let conflicts = row
+				.keys
+				.iter()
+				.filter(|keys| {
+					rows.iter().any(|other| {
+						// Only warn about the clashes introduced by customization, not the context-dependent reuse of keys in the presets
+						other.action != row.action && (row.custom || other.custom) && other.scope == row.scope && other.keys.contains(keys)
+					})
+				})
+				.collect::<Vec<_>>();
+
+			let rejected = self.rejected_shortcut.as_ref().filter(|rejected| rejected.action == row.action);
+			let shortcut = match rejected {
+				Some(rejected) => rejected.shortcut.clone(),
+				None => row.keys.first().map(|keys| keys.to_string()).unwrap_or_default(),
+			};
+
+			let mut widgets = vec![
+				TextLabel::new(&row.label).table_align(true).min_width(320).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextInput::new(shortcut)
+					.tooltip(row.keys.iter().map(|keys| keys.to_string()).collect::<Vec<_>>().join("\n"))
+					.min_width(160)
+					.on_update({
+						let action = row.action.clone();
+						move |text_input: &TextInput| {
+							PreferencesMessage::RebindShortcut {
+								action: action.clone(),
+								shortcut: text_input.value.clone(),
+							}
+							.into()
+						}
+					})
+					.widget_holder(),
+			];
+
+			if row.custom {
+				let action = row.action.clone();
+				widgets.push(
+					IconButton::new("Reset", 16)
+						.tooltip("Restore the shortcut from the keymap preset")
+						.on_update(move |_| PreferencesMessage::ResetShortcut { action: action.clone() }.into())
+						.widget_holder(),
+				);
+			}
+
+			if let Some(rejected) = rejected {
+				let tooltip = format!("This shortcut can't be used, so the current one is kept:\n{}", rejected.error);
+				widgets.push(IconLabel::new("Warning").tooltip(tooltip).widget_holder());
+			} else if !conflicts.is_empty() {
+				let clashing = rows
+					.iter()
+					.filter(|other| other.action != row.action && other.scope == row.scope && other.keys.iter().any(|keys| conflicts.contains(&keys)))
+					.map(|other| other.label.as_str())
+					.collect::<Vec<_>>()
+					.join("\n");
+				widgets.push(IconLabel::new("Warning").tooltip(format!("This shortcut is also used by:\n{clashing}")).widget_holder());
+			}
+
+			layout.push(LayoutGroup::Row { widgets });
+		}
+
+		Layout::WidgetLayout(WidgetLayout::new(layout))
+	}
+}
@@ -4,7 +4,11 @@ mod close_document_dialog;
 mod coming_soon_dialog;
 mod demo_artwork_dialog;
 mod error_dialog;
+mod keyboard_shortcuts_dialog;
 mod licenses_dialog;
+mod save_action_macro_dialog;
+mod save_gradient_preset_dialog;
+mod save_workspace_layout_dialog;
 
 pub use about_graphite_dialog::AboutGraphiteDialog;
 pub use close_all_documents_dialog::CloseAllDocumentsDialog;
@@ -13,4 +17,8 @@ pub use coming_soon_dialog::ComingSoonDialog;
 pub use demo_artwork_dialog::ARTWORK;
 pub use demo_artwork_dialog::DemoArtworkDialog;
 pub use error_dialog::ErrorDialog;
+pub use keyboard_shortcuts_dialog::{KeyboardShortcutsDialog, RejectedShortcut};
 pub use licenses_dialog::LicensesDialog;
+pub use save_action_macro_dialog::SaveActionMacroDialog;
+pub use save_gradient_preset_dialog::SaveGradientPresetDialog;
+pub use save_workspace_layout_dialog::SaveWorkspaceLayoutDialog;
@@ -0,0 +1,54 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::*;
+
+/// A dialog for naming the actions recorded since recording started to save them as a macro.
+pub struct SaveActionMacroDialog {
+	pub name: String,
+	/// Whether a saved macro already has this name, in which case saving replaces it.
+	pub replaces_existing: bool,
+}
+
+impl DialogLayoutHolder for SaveActionMacroDialog {
+	const ICON: &'static str = "Record";
+	const TITLE: &'static str = "Save Macro";
+
+	fn layout_buttons(&self) -> Layout {
+		let name = self.name.clone();
+		let widgets = vec![
+			TextButton::new(if self.replaces_existing { "Replace" } else { "Save" })
+				.emphasized(true)
+				.on_update(move |_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![ActionMacroMessage::Save { name: name.clone() }.into()],
+					}
+					.into()
+				})
+				.widget_holder(),
+			TextButton::new("Discard").on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder(),
+		];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
+	}
+}
+
+impl LayoutHolder for SaveActionMacroDialog {
+	fn layout(&self) -> Layout {
+		let mut rows = vec![LayoutGroup::Row {
+			widgets: vec![
+				TextLabel::new("Name").table_align(true).min_width(90).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextInput::new(&self.name)
+					.on_update(|text_input: &TextInput| DialogMessage::RequestSaveActionMacroDialog { name: text_input.value.clone() }.into())
+					.min_width(204)
+					.widget_holder(),
+			],
+		}];
+		if self.replaces_existing {
+			rows.push(LayoutGroup::Row {
+				widgets: vec![TextLabel::new("A macro with this name already exists and will be replaced").italic(true).widget_holder()],
+			});
+		}
+
+		Layout::WidgetLayout(WidgetLayout::new(rows))
+	}
+}
@@ -0,0 +1,67 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::preferences::GradientPreset;
+use crate::messages::prelude::*;
+use graphene_core::vector::style::GradientStops;
+
+/// A dialog for naming the stops of a gradient to save them as a preset.
+pub struct SaveGradientPresetDialog {
+	pub name: String,
+	pub stops: GradientStops,
+	/// Whether a saved preset already has this name, in which case saving replaces it.
+	pub replaces_existing: bool,
+}
+
+impl DialogLayoutHolder for SaveGradientPresetDialog {
+	const ICON: &'static str = "Save";
+	const TITLE: &'static str = "Save Gradient Preset";
+
+	fn layout_buttons(&self) -> Layout {
+		let preset = GradientPreset {
+			name: self.name.clone(),
+			stops: self.stops.clone(),
+		};
+		let widgets = vec![
+			TextButton::new(if self.replaces_existing { "Replace" } else { "Save" })
+				.emphasized(true)
+				.on_update(move |_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![PreferencesMessage::SaveGradientPreset { preset: preset.clone() }.into()],
+					}
+					.into()
+				})
+				.widget_holder(),
+			TextButton::new("Cancel").on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder(),
+		];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
+	}
+}
+
+impl LayoutHolder for SaveGradientPresetDialog {
+	fn layout(&self) -> Layout {
+		let stops = self.stops.clone();
+		let mut rows = vec![LayoutGroup::Row {
+			widgets: vec![
+				TextLabel::new("Name").table_align(true).min_width(90).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextInput::new(&self.name)
+					.on_update(move |text_input: &TextInput| {
+						DialogMessage::RequestSaveGradientPresetDialog {
+							name: text_input.value.clone(),
+							stops: stops.clone(),
+						}
+						.into()
+					})
+					.min_width(204)
+					.widget_holder(),
+			],
+		}];
+		if self.replaces_existing {
+			rows.push(LayoutGroup::Row {
+				widgets: vec![TextLabel::new("A preset with this name already exists and will be replaced").italic(true).widget_holder()],
+			});
+		}
+
+		Layout::WidgetLayout(WidgetLayout::new(rows))
+	}
+}
@@ -0,0 +1,54 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::*;
+
+/// A dialog for naming the current arrangement of the workspace's panels to save it as a layout.
+pub struct SaveWorkspaceLayoutDialog {
+	pub name: String,
+	/// Whether a saved layout already has this name, in which case saving replaces it.
+	pub replaces_existing: bool,
+}
+
+impl DialogLayoutHolder for SaveWorkspaceLayoutDialog {
+	const ICON: &'static str = "Save";
+	const TITLE: &'static str = "Save Workspace Layout";
+
+	fn layout_buttons(&self) -> Layout {
+		let name = self.name.clone();
+		let widgets = vec![
+			TextButton::new(if self.replaces_existing { "Replace" } else { "Save" })
+				.emphasized(true)
+				.on_update(move |_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![WorkspaceMessage::SaveLayout { name: name.clone() }.into()],
+					}
+					.into()
+				})
+				.widget_holder(),
+			TextButton::new("Cancel").on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder(),
+		];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
+	}
+}
+
+impl LayoutHolder for SaveWorkspaceLayoutDialog {
+	fn layout(&self) -> Layout {
+		let mut rows = vec![LayoutGroup::Row {
+			widgets: vec![
+				TextLabel::new("Name").table_align(true).min_width(90).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextInput::new(&self.name)
+					.on_update(|text_input: &TextInput| DialogMessage::RequestSaveWorkspaceLayoutDialog { name: text_input.value.clone() }.into())
+					.min_width(204)
+					.widget_holder(),
+			],
+		}];
+		if self.replaces_existing {
+			rows.push(LayoutGroup::Row {
+				widgets: vec![TextLabel::new("A layout with this name already exists and will be replaced").italic(true).widget_holder()],
+			});
+		}
+
+		Layout::WidgetLayout(WidgetLayout::new(rows))
+	}
+}
@@ -1,13 +1,17 @@
-use super::utility_types::{FrontendDocumentDetails, MouseCursorIcon};
+use super::utility_types::{ExportMetadata, FrontendAudioTrack, FrontendDocumentDetails, FrontendKeyframeTrack, MouseCursorIcon, TiffCompression};
+use crate::messages::input_mapper::utility_types::input_keyboard::LayoutKeysGroup;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::node_graph::utility_types::{
 	BoxSelection, ContextMenuInformation, FrontendClickTargets, FrontendGraphInput, FrontendGraphOutput, FrontendNode, FrontendNodeType, FrontendNodeWire, Transform, WirePath,
 };
 use crate::messages::portfolio::document::utility_types::nodes::{JsRawBuffer, LayerPanelEntry, RawBuffer};
+use crate::messages::portfolio::library::utility_types::FrontendLibraryItem;
+use crate::messages::preferences::{CursorStyle, GradientPreset};
 use crate::messages::prelude::*;
+use crate::messages::radial_menu::utility_types::FrontendRadialMenuItem;
 use crate::messages::tool::utility_types::HintData;
+use crate::messages::workspace::utility_types::FloatingPanel;
 use graph_craft::document::NodeId;
-use graphene_core::raster::color::Color;
 use graphene_core::text::Font;
 
 #[impl_message(Message, Frontend)]
@@ -24,21 +28,18 @@ pub enum FrontendMessage {
 		panic_info: String,
 	},
 	DisplayEditableTextbox {
-		text: String,
-		#[serde(rename = "lineHeightRatio")]
-		line_height_ratio: f64,
-		#[serde(rename = "fontSize")]
-		font_size: f64,
-		color: Color,
-		url: String,
-		transform: [f64; 6],
-		#[serde(rename = "maxWidth")]
-		max_width: Option<f64>,
-		#[serde(rename = "maxHeight")]
-		max_height: Option<f64>,
+		#[serde(rename = "selectedText")]
+		selected_text: String,
+	},
+	DisplayEditableTextboxPosition {
+		position: (f64, f64),
 	},
-	DisplayEditableTextboxTransform {
-		transform: [f64; 6],
+	DisplayRadialMenu {
+		items: Vec<FrontendRadialMenuItem>,
+		position: (f64, f64),
+		/// Whether the menu was opened by a key press, rather than by holding down the right mouse button, so it's the release of that key which picks an item.
+		#[serde(rename = "openedByKey")]
+		opened_by_key: bool,
 	},
 	DisplayRemoveEditableTextbox,
 
@@ -57,16 +58,75 @@ pub enum FrontendMessage {
 		#[serde(rename = "commitDate")]
 		commit_date: String,
 	},
+	/// Places copied layers on the clipboard in several formats at once: their serialization for pasting back into Graphite, and the SVG of their rendering (also rasterized to PNG) for other applications.
+	TriggerClipboardWrite {
+		#[serde(rename = "copiedLayers")]
+		copied_layers: String,
+		svg: String,
+		size: (f64, f64),
+	},
+	/// Opens a WebSocket connection to the collaboration server, replacing any already open.
+	TriggerCollaborationConnect {
+		server: String,
+	},
+	TriggerCollaborationDisconnect,
+	/// Sends a packet, serialized as JSON, through the connection to the collaboration server.
+	TriggerCollaborationSend {
+		packet: String,
+	},
+	TriggerDataMergeChooseImages,
+	TriggerDataMergeImport,
+	/// Loads the images at the paths or URLs named by the data merge's dataset.
+	TriggerDataMergeLoadImages {
+		paths: Vec<String>,
+	},
 	TriggerDelayedZoomCanvasToFitAll,
 	TriggerDownloadImage {
 		svg: String,
 		name: String,
 		mime: String,
 		size: (f64, f64),
+		metadata: ExportMetadata,
+		#[serde(rename = "tiffCompression")]
+		tiff_compression: TiffCompression,
+		#[serde(rename = "iccProfile")]
+		icc_profile: Vec<u8>,
+		#[serde(rename = "exportId")]
+		export_id: Option<u64>,
+	},
+	TriggerDownloadBinaryFile {
+		data: Vec<u8>,
+		name: String,
+		mime: String,
+		#[serde(rename = "exportId")]
+		export_id: Option<u64>,
 	},
 	TriggerDownloadTextFile {
 		document: String,
 		name: String,
+		#[serde(rename = "exportId")]
+		export_id: Option<u64>,
+	},
+	/// Writes an image layer's pixels as a PNG file for another application to edit, reimporting them whenever the file changes, on desktop.
+	TriggerEditImageExternally {
+		#[serde(rename = "documentId")]
+		document_id: DocumentId,
+		layer: NodeId,
+		name: String,
+		data: Vec<u8>,
+		/// The application to open the file with, where an empty one opens it with the system's default for PNG files.
+		editor: String,
+	},
+	/// Tells the frontend the export of the given ID won't be downloaded, so nothing waits for its file any longer.
+	TriggerExportFailed {
+		#[serde(rename = "exportId")]
+		export_id: u64,
+	},
+	/// Rasterizes the rendering of the selected layer and sends its pixels back so the given number of swatches can be extracted from its colors.
+	TriggerExtractSwatches {
+		svg: String,
+		size: (f64, f64),
+		count: u32,
 	},
 	TriggerFetchAndOpenDocument {
 		name: String,
@@ -76,6 +136,7 @@ pub enum FrontendMessage {
 		font: Font,
 	},
 	TriggerImport,
+	TriggerImportAudio,
 	TriggerIndexedDbRemoveDocument {
 		#[serde(rename = "documentId")]
 		document_id: DocumentId,
@@ -84,11 +145,52 @@ pub enum FrontendMessage {
 		document: String,
 		details: FrontendDocumentDetails,
 	},
+	/// Stores the order of the open document tabs without changing which document is restored as the active one.
+	TriggerIndexedDbWriteDocumentOrder,
+	/// Fetches the shared library from its URL, where nothing being there yet is reported as an empty library.
+	TriggerLibraryFetch {
+		url: String,
+	},
+	TriggerLibraryOpenFile,
+	/// Publishes the shared library, serialized as JSON, to its URL, but only if the version there still has the given ETag, or if there's nothing there yet when there's no ETag.
+	TriggerLibraryPublish {
+		url: String,
+		content: String,
+		etag: Option<String>,
+	},
 	TriggerLoadFirstAutoSaveDocument,
 	TriggerLoadRestAutoSaveDocuments,
 	TriggerLoadPreferences,
 	TriggerOpenDocument,
 	TriggerPaste,
+	/// Sends an event, serialized as JSON, to the plugin with the given ID for it to handle.
+	TriggerPluginEvent {
+		plugin: String,
+		event: String,
+	},
+	/// Samples the average color of the rendered artwork within a region of the viewport (given by its top left and bottom right corners), leaving out pixels of the text's color.
+	TriggerSampleContrastBackground {
+		region: [(f64, f64); 2],
+		#[serde(rename = "textColor")]
+		text_color: String,
+	},
+	/// Writes the files of the exports with these IDs to a folder, instead of downloading them, on desktop.
+	TriggerSaveActionExport {
+		folder: String,
+		#[serde(rename = "exportIds")]
+		export_ids: Vec<u64>,
+	},
+	/// Runs a shell command on desktop after a document is saved.
+	TriggerSaveActionCommand {
+		command: String,
+		#[serde(rename = "documentName")]
+		document_name: String,
+	},
+	/// Sends a POST request with a JSON body to a URL after a document is saved.
+	TriggerSaveActionWebhook {
+		url: String,
+		body: String,
+	},
 	TriggerSavePreferences {
 		preferences: PreferencesMessageHandler,
 	},
@@ -96,7 +198,12 @@ pub enum FrontendMessage {
 		#[serde(rename = "documentId")]
 		document_id: DocumentId,
 	},
-	TriggerTextCommit,
+	/// Stops reimporting the pixels of an image layer saved by the application it's being edited in, once the layer has been deleted or its document closed, on desktop.
+	TriggerStopEditingImageExternally {
+		#[serde(rename = "documentId")]
+		document_id: DocumentId,
+		layer: NodeId,
+	},
 	TriggerTextCopy {
 		#[serde(rename = "copyText")]
 		copy_text: String,
@@ -139,6 +246,57 @@ pub enum FrontendMessage {
 		#[serde(rename = "box")]
 		box_selection: Option<BoxSelection>,
 	},
+	UpdateCollaborationLayout {
+		#[serde(rename = "layoutTarget")]
+		layout_target: LayoutTarget,
+		diff: Vec<WidgetDiff>,
+	},
+	UpdateCollaborationState {
+		open: bool,
+	},
+	UpdateColorHarmonyLayout {
+		#[serde(rename = "layoutTarget")]
+		layout_target: LayoutTarget,
+		diff: Vec<WidgetDiff>,
+	},
+	UpdateColorHarmonyState {
+		open: bool,
+	},
+	UpdateCompareLayout {
+		#[serde(rename = "layoutTarget")]
+		layout_target: LayoutTarget,
+		diff: Vec<WidgetDiff>,
+	},
+	UpdateCompareState {
+		open: bool,
+	},
+	UpdateConsoleLayout {
+		#[serde(rename = "layoutTarget")]
+		layout_target: LayoutTarget,
+		diff: Vec<WidgetDiff>,
+	},
+	UpdateConsoleState {
+		open: bool,
+	},
+	UpdateLibraryItems {
+		items: Vec<FrontendLibraryItem>,
+	},
+	UpdateLibraryLayout {
+		#[serde(rename = "layoutTarget")]
+		layout_target: LayoutTarget,
+		diff: Vec<WidgetDiff>,
+	},
+	UpdateLibraryState {
+		open: bool,
+	},
+	UpdateDataMergeLayout {
+		#[serde(rename = "layoutTarget")]
+		layout_target: LayoutTarget,
+		diff: Vec<WidgetDiff>,
+	},
+	UpdateDataMergeState {
+		open: bool,
+	},
 	UpdateContextMenuInformation {
 		#[serde(rename = "contextMenuInformation")]
 		context_menu_information: Option<ContextMenuInformation>,
@@ -150,6 +308,14 @@ pub enum FrontendMessage {
 	UpdateGraphViewOverlay {
 		open: bool,
 	},
+	UpdatePluginsLayout {
+		#[serde(rename = "layoutTarget")]
+		layout_target: LayoutTarget,
+		diff: Vec<WidgetDiff>,
+	},
+	UpdatePluginsState {
+		open: bool,
+	},
 	UpdateSpreadsheetState {
 		open: bool,
 		node: Option<NodeId>,
@@ -159,6 +325,44 @@ pub enum FrontendMessage {
 		layout_target: LayoutTarget,
 		diff: Vec<WidgetDiff>,
 	},
+	UpdateSplitViewArtwork {
+		svg: String,
+	},
+	UpdateSplitViewControlBarLayout {
+		#[serde(rename = "layoutTarget")]
+		layout_target: LayoutTarget,
+		diff: Vec<WidgetDiff>,
+	},
+	UpdateSplitViewState {
+		open: bool,
+	},
+	UpdateTimelineControlBarLayout {
+		#[serde(rename = "layoutTarget")]
+		layout_target: LayoutTarget,
+		diff: Vec<WidgetDiff>,
+	},
+	UpdateTimelineState {
+		open: bool,
+	},
+	UpdateTimelineAudio {
+		audio: Option<FrontendAudioTrack>,
+	},
+	UpdateTimelineTracks {
+		time: f64,
+		playing: bool,
+		duration: f64,
+		#[serde(rename = "loopRange")]
+		loop_range: Option<[f64; 2]>,
+		tracks: Vec<FrontendKeyframeTrack>,
+		#[serde(rename = "curveEditor")]
+		curve_editor: bool,
+		#[serde(rename = "selectedKeyframe")]
+		selected_keyframe: Option<(usize, usize)>,
+		#[serde(rename = "audioOffset")]
+		audio_offset: f64,
+		#[serde(rename = "audioMuted")]
+		audio_muted: bool,
+	},
 	UpdateImportReorderIndex {
 		#[serde(rename = "importIndex")]
 		index: Option<usize>,
@@ -175,6 +379,10 @@ pub enum FrontendMessage {
 		#[serde(rename = "hasLeftInputWire")]
 		has_left_input_wire: HashMap<NodeId, bool>,
 	},
+	UpdateCursorStyle {
+		style: CursorStyle,
+		size: u32,
+	},
 	UpdateDialogButtons {
 		#[serde(rename = "layoutTarget")]
 		layout_target: LayoutTarget,
@@ -235,6 +443,14 @@ pub enum FrontendMessage {
 		#[serde(rename = "setColorChoice")]
 		set_color_choice: Option<String>,
 	},
+	UpdateFloatingPanels {
+		#[serde(rename = "floatingPanels")]
+		floating_panels: Vec<FloatingPanel>,
+	},
+	UpdateGradientPresets {
+		bundled: Vec<GradientPreset>,
+		saved: Vec<GradientPreset>,
+	},
 	UpdateGraphFadeArtwork {
 		percentage: f64,
 	},
@@ -276,15 +492,25 @@ pub enum FrontendMessage {
 		id: NodeId,
 		value: String,
 	},
+	UpdateOnionSkinArtwork {
+		svg: String,
+	},
 	UpdateOpenDocumentsList {
 		#[serde(rename = "openDocuments")]
 		open_documents: Vec<FrontendDocumentDetails>,
 	},
+	UpdatePanelSizes {
+		#[serde(rename = "panelSizes")]
+		panel_sizes: Vec<(String, f64)>,
+	},
 	UpdatePropertyPanelSectionsLayout {
 		#[serde(rename = "layoutTarget")]
 		layout_target: LayoutTarget,
 		diff: Vec<WidgetDiff>,
 	},
+	UpdateStickyModifierKeys {
+		keys: LayoutKeysGroup,
+	},
 	UpdateToolOptionsLayout {
 		#[serde(rename = "layoutTarget")]
 		layout_target: LayoutTarget,
@@ -295,6 +521,11 @@ pub enum FrontendMessage {
 		layout_target: LayoutTarget,
 		diff: Vec<WidgetDiff>,
 	},
+	UpdateTransformBarLayout {
+		#[serde(rename = "layoutTarget")]
+		layout_target: LayoutTarget,
+		diff: Vec<WidgetDiff>,
+	},
 	UpdateWirePathInProgress {
 		#[serde(rename = "wirePath")]
 		wire_path: Option<WirePath>,
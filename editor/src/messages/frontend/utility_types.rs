@@ -11,6 +11,30 @@ pub struct FrontendDocumentDetails {
 	pub id: DocumentId,
 }
 
+/// A keyframed node input shown as a row of the timeline.
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct FrontendKeyframeTrack {
+	pub name: String,
+	#[serde(rename = "keyframeTimes")]
+	pub keyframe_times: Vec<f64>,
+	/// The numbers making up the value of each keyframe, which are each plotted as a curve in the curve editor.
+	#[serde(rename = "keyframeValues")]
+	pub keyframe_values: Vec<Vec<f64>>,
+	/// The handles `[x1, y1, x2, y2]` of the Bézier easing from each keyframe to the next, or `None` for an easing without handles.
+	#[serde(rename = "easingHandles")]
+	pub easing_handles: Vec<Option<[f64; 4]>>,
+	/// The times between the first and last keyframes at which the curves are sampled, along with the numbers making up the value at each.
+	pub curve: Vec<(f64, Vec<f64>)>,
+}
+
+/// The sound file played along with the animation in the timeline, which the frontend decodes to play and draw its waveform.
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct FrontendAudioTrack {
+	pub name: String,
+	pub mime: String,
+	pub data: Vec<u8>,
+}
+
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
 pub enum MouseCursorIcon {
 	#[default]
@@ -35,6 +59,9 @@ pub enum FileType {
 	Png,
 	Jpg,
 	Svg,
+	Tiff,
+	Pdf,
+	Eps,
 }
 
 impl FileType {
@@ -43,14 +70,149 @@ impl FileType {
 			FileType::Png => "image/png",
 			FileType::Jpg => "image/jpeg",
 			FileType::Svg => "image/svg+xml",
+			FileType::Tiff => "image/tiff",
+			FileType::Pdf => "application/pdf",
+			FileType::Eps => "application/postscript",
+		}
+	}
+
+	/// Whether the format stores the artwork as pixels, rather than as its shapes which print sharply at any size.
+	pub fn is_raster(self) -> bool {
+		matches!(self, FileType::Png | FileType::Jpg | FileType::Tiff)
+	}
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum TiffCompression {
+	None,
+	#[default]
+	Lzw,
+	Deflate,
+}
+
+/// How the text of an SVG export is written, where PDF and EPS exports always have the outlines of its glyphs.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum TextExport {
+	/// The outlines of the glyphs as paths.
+	#[default]
+	Outlines,
+	/// Text drawn with subsets of its fonts, with just its glyphs, embedded in the file. Text which can't be embedded, or whose outlines a node has changed, is still written as outlines.
+	EmbeddedFont,
+}
+
+/// Metadata embedded into exported raster image files (PNG and JPEG).
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct ExportMetadata {
+	pub title: String,
+	pub author: String,
+	pub copyright: String,
+	/// The pixel density recorded in the file, used by print and publishing software to determine the physical size of the image.
+	pub dpi: f64,
+	/// Omits all metadata (including the pixel density) from the exported file.
+	#[serde(rename = "stripMetadata")]
+	pub strip_metadata: bool,
+}
+
+impl Default for ExportMetadata {
+	fn default() -> Self {
+		Self {
+			title: String::new(),
+			author: String::new(),
+			copyright: String::new(),
+			dpi: 72.,
+			strip_metadata: false,
 		}
 	}
 }
 
+/// A range of the animation's frames to export, each to its own numbered file.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct FrameSequenceExport {
+	#[serde(rename = "firstFrame")]
+	pub first_frame: u32,
+	#[serde(rename = "lastFrame")]
+	pub last_frame: u32,
+	#[serde(rename = "framesPerSecond")]
+	pub frames_per_second: f64,
+	/// The name given to each file, where the last run of `#` characters is replaced by the frame number padded with zeros to as many digits.
+	#[serde(rename = "fileNamePattern")]
+	pub file_name_pattern: String,
+}
+
+impl FrameSequenceExport {
+	/// The name of the file the given frame is exported to, without its file extension.
+	pub fn file_name(&self, frame: u32) -> String {
+		let pattern = &self.file_name_pattern;
+		let Some(end) = pattern.rfind('#') else {
+			return format!("{pattern}_{frame:04}");
+		};
+		let start = pattern[..end].trim_end_matches('#').len();
+		let digits = end + 1 - start;
+
+		format!("{}{frame:0digits$}{}", &pattern[..start], &pattern[end + 1..])
+	}
+
+	/// The time in seconds at which the given frame is shown.
+	pub fn frame_time(&self, frame: u32) -> f64 {
+		frame as f64 / self.frames_per_second
+	}
+}
+
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
 pub enum ExportBounds {
 	#[default]
 	AllArtwork,
 	Selection,
 	Artboard(LayerNodeIdentifier),
+	/// The user-defined export region saved in the document, independent of any artboard.
+	CustomRegion,
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn sequence(file_name_pattern: &str) -> FrameSequenceExport {
+		FrameSequenceExport {
+			first_frame: 0,
+			last_frame: 0,
+			frames_per_second: 24.,
+			file_name_pattern: file_name_pattern.to_string(),
+		}
+	}
+
+	#[test]
+	fn file_name_pads_frame_to_pattern_width() {
+		assert_eq!(sequence("frame_###").file_name(7), "frame_007");
+		assert_eq!(sequence("#").file_name(7), "7");
+		assert_eq!(sequence("shot_####_final").file_name(42), "shot_0042_final");
+	}
+
+	#[test]
+	fn file_name_keeps_numbers_wider_than_pattern() {
+		assert_eq!(sequence("frame_##").file_name(1234), "frame_1234");
+	}
+
+	#[test]
+	fn file_name_replaces_only_last_run_of_hashes() {
+		assert_eq!(sequence("#1_take_##").file_name(3), "#1_take_03");
+	}
+
+	#[test]
+	fn frames_are_numbered_by_their_position_in_the_animation() {
+		let sequence = FrameSequenceExport {
+			first_frame: 9,
+			last_frame: 11,
+			..sequence("f_##")
+		};
+		let names = (sequence.first_frame..=sequence.last_frame).map(|frame| sequence.file_name(frame)).collect::<Vec<_>>();
+		assert_eq!(names, ["f_09", "f_10", "f_11"]);
+		assert_eq!(sequence.frame_time(12), 0.5);
+	}
+
+	#[test]
+	fn file_name_without_hashes_appends_frame_number() {
+		assert_eq!(sequence("animation").file_name(5), "animation_0005");
+		assert_eq!(sequence("").file_name(12), "_0012");
+	}
 }
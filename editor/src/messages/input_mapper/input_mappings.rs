@@ -1,10 +1,10 @@
 use crate::consts::{BIG_NUDGE_AMOUNT, BRUSH_SIZE_CHANGE_KEYBOARD, NUDGE_AMOUNT};
 use crate::messages::input_mapper::key_mapping::MappingVariant;
-use crate::messages::input_mapper::utility_types::input_keyboard::{Key, KeyStates};
+use crate::messages::input_mapper::utility_types::input_keyboard::{Key, KeyStates, KeysGroup};
 use crate::messages::input_mapper::utility_types::input_mouse::MouseButton;
 use crate::messages::input_mapper::utility_types::macros::*;
 use crate::messages::input_mapper::utility_types::misc::MappingEntry;
-use crate::messages::input_mapper::utility_types::misc::{KeyMappingEntries, Mapping};
+use crate::messages::input_mapper::utility_types::misc::{CustomShortcut, KeyMappingEntries, KeymapPreset, Mapping, MiddleMouseDrag};
 use crate::messages::portfolio::document::node_graph::utility_types::Direction;
 use crate::messages::portfolio::document::utility_types::clipboards::Clipboard;
 use crate::messages::portfolio::document::utility_types::misc::GroupFolderType;
@@ -12,6 +12,7 @@ use crate::messages::portfolio::document::utility_types::transformation::Transfo
 use crate::messages::prelude::*;
 use crate::messages::tool::tool_messages::brush_tool::BrushToolMessageOptionsUpdate;
 use crate::messages::tool::tool_messages::select_tool::SelectToolPointerKeys;
+use crate::messages::tool::tool_messages::text_tool::CaretMovement;
 use glam::DVec2;
 
 impl From<MappingVariant> for Mapping {
@@ -46,6 +47,18 @@ pub fn input_mappings() -> Mapping {
 		entry!(KeyDown(MouseLeft); action_dispatch=NavigationMessage::EndCanvasPTZWithClick { commit_key: MouseLeft }),
 		entry!(KeyDown(MouseMiddle); action_dispatch=NavigationMessage::EndCanvasPTZWithClick { commit_key: MouseMiddle }),
 		entry!(KeyDown(MouseRight); action_dispatch=NavigationMessage::EndCanvasPTZWithClick { commit_key: MouseRight }),
+		// Holding Space turns a click and drag into navigation regardless of the active tool, which ends when Space is released
+		entry!(KeyDown(MouseLeft); modifiers=[Alt, Space], action_dispatch=NavigationMessage::BeginCanvasTilt { was_dispatched_from_menu: false }),
+		entry!(KeyDown(MouseLeft); modifiers=[Control, Space], action_dispatch=NavigationMessage::BeginCanvasZoom),
+		entry!(KeyDown(MouseLeft); modifiers=[Space], action_dispatch=NavigationMessage::BeginCanvasPan),
+		entry!(KeyUp(Space); action_dispatch=NavigationMessage::EndCanvasPTZ { abort_transform: false }),
+		//
+		// DocumentMessage
+		entry!(KeyDown(MouseLeft); action_dispatch=DocumentMessage::DrawExportRegionPointerDown),
+		entry!(PointerMove; action_dispatch=DocumentMessage::DrawExportRegionPointerMove),
+		entry!(KeyUp(MouseLeft); action_dispatch=DocumentMessage::DrawExportRegionPointerUp),
+		entry!(KeyDown(MouseRight); action_dispatch=DocumentMessage::DrawExportRegionAbort),
+		entry!(KeyDown(Escape); action_dispatch=DocumentMessage::DrawExportRegionAbort),
 		//
 		// ===============
 		// NORMAL PRIORITY
@@ -160,6 +173,15 @@ pub fn input_mappings() -> Mapping {
 		entry!(KeyDown(MouseRight); action_dispatch=TextToolMessage::Abort),
 		entry!(KeyDown(Escape); action_dispatch=TextToolMessage::Abort),
 		entry!(KeyDown(Enter); modifiers=[Accel], action_dispatch=TextToolMessage::Abort),
+		entry!(KeyDown(Backspace); action_dispatch=TextToolMessage::DeleteBackward),
+		entry!(KeyDown(Delete); action_dispatch=TextToolMessage::DeleteForward),
+		entry!(KeyDown(ArrowLeft); action_dispatch=TextToolMessage::MoveCaret { movement: CaretMovement::Left, extend: Shift }),
+		entry!(KeyDown(ArrowRight); action_dispatch=TextToolMessage::MoveCaret { movement: CaretMovement::Right, extend: Shift }),
+		entry!(KeyDown(ArrowUp); action_dispatch=TextToolMessage::MoveCaret { movement: CaretMovement::PreviousLine, extend: Shift }),
+		entry!(KeyDown(ArrowDown); action_dispatch=TextToolMessage::MoveCaret { movement: CaretMovement::NextLine, extend: Shift }),
+		entry!(KeyDown(Home); action_dispatch=TextToolMessage::MoveCaret { movement: CaretMovement::LineStart, extend: Shift }),
+		entry!(KeyDown(End); action_dispatch=TextToolMessage::MoveCaret { movement: CaretMovement::LineEnd, extend: Shift }),
+		entry!(KeyDown(KeyA); modifiers=[Accel], action_dispatch=TextToolMessage::SelectAll),
 		//
 		// GradientToolMessage
 		entry!(KeyDown(MouseLeft); action_dispatch=GradientToolMessage::PointerDown),
@@ -226,34 +248,34 @@ pub fn input_mappings() -> Mapping {
 		entry!(KeyUp(MouseLeft); action_dispatch=PathToolMessage::DragStop { extend_selection: Shift, shrink_selection: Alt }),
 		entry!(KeyDown(Enter); action_dispatch=PathToolMessage::Enter { extend_selection: Shift, shrink_selection: Alt }),
 		entry!(DoubleClick(MouseButton::Left); action_dispatch=PathToolMessage::FlipSmoothSharp),
-		entry!(KeyDown(ArrowRight); action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: NUDGE_AMOUNT, delta_y: 0. }),
-		entry!(KeyDown(ArrowRight); modifiers=[Shift], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: BIG_NUDGE_AMOUNT, delta_y: 0. }),
-		entry!(KeyDown(ArrowRight); modifiers=[ArrowUp], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowRight); modifiers=[ArrowDown], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowRight); modifiers=[Shift, ArrowUp], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowRight); modifiers=[Shift, ArrowDown], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowUp); action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 0., delta_y: -NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowUp); modifiers=[Shift], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 0., delta_y: -BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowUp); modifiers=[ArrowLeft], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowUp); modifiers=[ArrowRight], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowLeft], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowRight], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowLeft); action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -NUDGE_AMOUNT, delta_y: 0. }),
-		entry!(KeyDown(ArrowLeft); modifiers=[Shift], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -BIG_NUDGE_AMOUNT, delta_y: 0. }),
-		entry!(KeyDown(ArrowLeft); modifiers=[ArrowUp], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowLeft); modifiers=[ArrowDown], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowLeft); modifiers=[Shift, ArrowUp], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowLeft); modifiers=[Shift, ArrowDown], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowDown); action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 0., delta_y: NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowDown); modifiers=[Shift], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 0., delta_y: BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowDown); modifiers=[ArrowLeft], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowDown); modifiers=[ArrowRight], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowLeft], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowRight], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowRight); action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 1., delta_y: 0., large: false }),
+		entry!(KeyDown(ArrowRight); modifiers=[Shift], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 1., delta_y: 0., large: true }),
+		entry!(KeyDown(ArrowRight); modifiers=[ArrowUp], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 1., delta_y: -1., large: false }),
+		entry!(KeyDown(ArrowRight); modifiers=[ArrowDown], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 1., delta_y: 1., large: false }),
+		entry!(KeyDown(ArrowRight); modifiers=[Shift, ArrowUp], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 1., delta_y: -1., large: true }),
+		entry!(KeyDown(ArrowRight); modifiers=[Shift, ArrowDown], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 1., delta_y: 1., large: true }),
+		entry!(KeyDown(ArrowUp); action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 0., delta_y: -1., large: false }),
+		entry!(KeyDown(ArrowUp); modifiers=[Shift], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 0., delta_y: -1., large: true }),
+		entry!(KeyDown(ArrowUp); modifiers=[ArrowLeft], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -1., delta_y: -1., large: false }),
+		entry!(KeyDown(ArrowUp); modifiers=[ArrowRight], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 1., delta_y: -1., large: false }),
+		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowLeft], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -1., delta_y: -1., large: true }),
+		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowRight], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 1., delta_y: -1., large: true }),
+		entry!(KeyDown(ArrowLeft); action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -1., delta_y: 0., large: false }),
+		entry!(KeyDown(ArrowLeft); modifiers=[Shift], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -1., delta_y: 0., large: true }),
+		entry!(KeyDown(ArrowLeft); modifiers=[ArrowUp], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -1., delta_y: -1., large: false }),
+		entry!(KeyDown(ArrowLeft); modifiers=[ArrowDown], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -1., delta_y: 1., large: false }),
+		entry!(KeyDown(ArrowLeft); modifiers=[Shift, ArrowUp], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -1., delta_y: -1., large: true }),
+		entry!(KeyDown(ArrowLeft); modifiers=[Shift, ArrowDown], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -1., delta_y: 1., large: true }),
+		entry!(KeyDown(ArrowDown); action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 0., delta_y: 1., large: false }),
+		entry!(KeyDown(ArrowDown); modifiers=[Shift], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 0., delta_y: 1., large: true }),
+		entry!(KeyDown(ArrowDown); modifiers=[ArrowLeft], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -1., delta_y: 1., large: false }),
+		entry!(KeyDown(ArrowDown); modifiers=[ArrowRight], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 1., delta_y: 1., large: false }),
+		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowLeft], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -1., delta_y: 1., large: true }),
+		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowRight], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 1., delta_y: 1., large: true }),
 		entry!(KeyDown(KeyJ); modifiers=[Accel], action_dispatch=ToolMessage::Path(PathToolMessage::ClosePath)),
 		//
 		// PenToolMessage
-		entry!(PointerMove; refresh_keys=[Control, Alt, Shift, KeyC], action_dispatch=PenToolMessage::PointerMove { snap_angle: Shift, break_handle: Alt, lock_angle: Control, colinear: KeyC, move_anchor_with_handles: Space }),
+		entry!(PointerMove; refresh_keys=[Control, Alt, Shift, KeyC, Space], action_dispatch=PenToolMessage::PointerMove { snap_angle: Shift, break_handle: Alt, lock_angle: Control, colinear: KeyC, move_anchor_with_handles: Space }),
 		entry!(KeyDown(MouseLeft); action_dispatch=PenToolMessage::DragStart { append_to_selected: Shift }),
 		entry!(KeyUp(MouseLeft); action_dispatch=PenToolMessage::DragStop),
 		entry!(KeyDown(MouseRight); action_dispatch=PenToolMessage::Abort),
@@ -287,6 +309,13 @@ pub fn input_mappings() -> Mapping {
 		entry!(KeyDown(MouseRight); action_dispatch=FillToolMessage::Abort),
 		entry!(KeyDown(Escape); action_dispatch=FillToolMessage::Abort),
 		//
+		// PluginToolMessage
+		entry!(PointerMove; refresh_keys=[Control, Alt, Shift], action_dispatch=PluginToolMessage::PointerMove),
+		entry!(KeyDown(MouseLeft); action_dispatch=PluginToolMessage::PointerDown),
+		entry!(KeyUp(MouseLeft); action_dispatch=PluginToolMessage::PointerUp),
+		entry!(KeyDown(MouseRight); action_dispatch=PluginToolMessage::Abort),
+		entry!(KeyDown(Escape); action_dispatch=PluginToolMessage::Abort),
+		//
 		// BrushToolMessage
 		entry!(PointerMove; action_dispatch=BrushToolMessage::PointerMove),
 		entry!(KeyDown(MouseLeft); action_dispatch=BrushToolMessage::DragStart),
@@ -325,12 +354,14 @@ pub fn input_mappings() -> Mapping {
 		entry!(KeyDown(KeyH); modifiers=[Accel], action_dispatch=DocumentMessage::ToggleSelectedVisibility),
 		entry!(KeyDown(KeyL); modifiers=[Accel], action_dispatch=DocumentMessage::ToggleSelectedLocked),
 		entry!(KeyDown(KeyG); modifiers=[Alt], action_dispatch=DocumentMessage::ToggleGridVisibility),
+		entry!(KeyDown(KeyY); modifiers=[Accel, Shift], action_dispatch=DocumentMessage::ToggleOutlineViewMode),
 		entry!(KeyDown(KeyZ); modifiers=[Accel, Shift], action_dispatch=DocumentMessage::Redo),
 		entry!(KeyDown(KeyY); modifiers=[Accel], action_dispatch=DocumentMessage::Redo),
 		entry!(KeyDown(KeyZ); modifiers=[Accel], action_dispatch=DocumentMessage::Undo),
 		entry!(KeyDown(KeyA); modifiers=[Accel], action_dispatch=DocumentMessage::SelectAllLayers),
 		entry!(KeyDown(KeyA); modifiers=[Accel, Shift], action_dispatch=DocumentMessage::DeselectAllLayers),
 		entry!(KeyDown(KeyS); modifiers=[Accel], action_dispatch=DocumentMessage::SaveDocument),
+		entry!(KeyDown(KeyD); modifiers=[Accel, Shift], action_dispatch=DocumentMessage::RepeatLastAction),
 		entry!(KeyDown(KeyD); modifiers=[Accel], action_dispatch=DocumentMessage::DuplicateSelectedLayers),
 		entry!(KeyDown(KeyJ); modifiers=[Accel], action_dispatch=DocumentMessage::DuplicateSelectedLayers),
 		entry!(KeyDown(KeyG); modifiers=[Accel], action_dispatch=DocumentMessage::GroupSelectedLayers { group_folder_type: GroupFolderType::Layer }),
@@ -348,30 +379,30 @@ pub fn input_mappings() -> Mapping {
 		entry!(KeyDown(BracketRight); modifiers=[Accel, Shift], action_dispatch=DocumentMessage::SelectedLayersRaiseToFront),
 		entry!(KeyDown(BracketLeft); modifiers=[Accel], action_dispatch=DocumentMessage::SelectedLayersLower),
 		entry!(KeyDown(BracketRight); modifiers=[Accel], action_dispatch=DocumentMessage::SelectedLayersRaise),
-		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowLeft], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowRight], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowUp); modifiers=[Shift], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 0., delta_y: -BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowLeft], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowRight], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowDown); modifiers=[Shift], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 0., delta_y: BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowLeft); modifiers=[Shift, ArrowUp], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowLeft); modifiers=[Shift, ArrowDown], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowLeft); modifiers=[Shift], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -BIG_NUDGE_AMOUNT, delta_y: 0., resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowRight); modifiers=[Shift, ArrowUp], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowRight); modifiers=[Shift, ArrowDown], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowRight); modifiers=[Shift], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: BIG_NUDGE_AMOUNT, delta_y: 0., resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowUp); modifiers=[ArrowLeft], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowUp); modifiers=[ArrowRight], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowUp); action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 0., delta_y: -NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowDown); modifiers=[ArrowLeft], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowDown); modifiers=[ArrowRight], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowDown); action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 0., delta_y: NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowLeft); modifiers=[ArrowUp], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowLeft); modifiers=[ArrowDown], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowLeft); action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -NUDGE_AMOUNT, delta_y: 0., resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowRight); modifiers=[ArrowUp], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowRight); modifiers=[ArrowDown], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowRight); action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: NUDGE_AMOUNT, delta_y: 0., resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowLeft], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -1., delta_y: -1., large: true, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowRight], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 1., delta_y: -1., large: true, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowUp); modifiers=[Shift], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 0., delta_y: -1., large: true, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowLeft], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -1., delta_y: 1., large: true, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowRight], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 1., delta_y: 1., large: true, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowDown); modifiers=[Shift], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 0., delta_y: 1., large: true, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowLeft); modifiers=[Shift, ArrowUp], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -1., delta_y: -1., large: true, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowLeft); modifiers=[Shift, ArrowDown], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -1., delta_y: 1., large: true, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowLeft); modifiers=[Shift], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -1., delta_y: 0., large: true, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowRight); modifiers=[Shift, ArrowUp], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 1., delta_y: -1., large: true, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowRight); modifiers=[Shift, ArrowDown], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 1., delta_y: 1., large: true, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowRight); modifiers=[Shift], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 1., delta_y: 0., large: true, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowUp); modifiers=[ArrowLeft], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -1., delta_y: -1., large: false, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowUp); modifiers=[ArrowRight], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 1., delta_y: -1., large: false, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowUp); action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 0., delta_y: -1., large: false, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowDown); modifiers=[ArrowLeft], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -1., delta_y: 1., large: false, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowDown); modifiers=[ArrowRight], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 1., delta_y: 1., large: false, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowDown); action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 0., delta_y: 1., large: false, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowLeft); modifiers=[ArrowUp], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -1., delta_y: -1., large: false, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowLeft); modifiers=[ArrowDown], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -1., delta_y: 1., large: false, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowLeft); action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -1., delta_y: 0., large: false, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowRight); modifiers=[ArrowUp], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 1., delta_y: -1., large: false, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowRight); modifiers=[ArrowDown], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 1., delta_y: 1., large: false, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowRight); action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 1., delta_y: 0., large: false, resize: Alt, resize_opposite_corner: Control }),
 		//
 		// TransformLayerMessage
 		entry!(KeyDown(KeyG); action_dispatch=TransformLayerMessage::BeginGRS { transform_type: TransformType::Grab }),
@@ -390,11 +421,10 @@ pub fn input_mappings() -> Mapping {
 		//
 		// NavigationMessage
 		entry!(KeyDown(MouseMiddle); modifiers=[Alt], action_dispatch=NavigationMessage::BeginCanvasTilt { was_dispatched_from_menu: false }),
-		entry!(KeyDown(MouseLeft); modifiers=[Alt, Space], action_dispatch=NavigationMessage::BeginCanvasTilt { was_dispatched_from_menu: false }),
 		entry!(KeyDown(MouseMiddle); modifiers=[Control], action_dispatch=NavigationMessage::BeginCanvasZoom),
-		entry!(KeyDown(MouseLeft); modifiers=[Control, Space], action_dispatch=NavigationMessage::BeginCanvasZoom),
 		entry!(KeyDown(MouseMiddle); action_dispatch=NavigationMessage::BeginCanvasPan),
-		entry!(KeyDown(MouseLeft); modifiers=[Space], action_dispatch=NavigationMessage::BeginCanvasPan),
+		// Comes after the tool mappings so tools which use Space while dragging (such as the Path and Pen tools) keep it
+		entry!(KeyDown(Space); modifiers=[MouseLeft], action_dispatch=NavigationMessage::BeginCanvasPanMidDrag),
 		entry!(KeyDown(NumpadAdd); modifiers=[Accel], action_dispatch=NavigationMessage::CanvasZoomIncrease { center_on_mouse: false }),
 		entry!(KeyDown(Equal); modifiers=[Accel], action_dispatch=NavigationMessage::CanvasZoomIncrease { center_on_mouse: false }),
 		entry!(KeyDown(Minus); modifiers=[Accel], action_dispatch=NavigationMessage::CanvasZoomDecrease { center_on_mouse: false }),
@@ -424,7 +454,12 @@ pub fn input_mappings() -> Mapping {
 		// DialogMessage
 		entry!(KeyDown(KeyE); modifiers=[Accel], action_dispatch=DialogMessage::RequestExportDialog),
 		entry!(KeyDown(KeyN); modifiers=[Accel], action_dispatch=DialogMessage::RequestNewDocumentDialog),
+		entry!(KeyDown(KeyF); modifiers=[Accel], action_dispatch=DialogMessage::RequestFindReplaceDialog),
 		entry!(KeyDown(Comma); modifiers=[Accel], action_dispatch=DialogMessage::RequestPreferencesDialog),
+		entry!(KeyDown(KeyK); modifiers=[Accel], action_dispatch=DialogMessage::RequestCommandPaletteDialog),
+		//
+		// RadialMenuMessage
+		entry!(KeyDownNoRepeat(KeyQ); action_dispatch=RadialMenuMessage::Open),
 		//
 		// DebugMessage
 		entry!(KeyDown(Digit0); modifiers=[Alt], action_dispatch=DebugMessage::MessageOff),
@@ -433,6 +468,12 @@ pub fn input_mappings() -> Mapping {
 		// AnimationMessage
 		entry!(KeyDown(Space); modifiers=[Shift], action_dispatch=AnimationMessage::ToggleLivePreview),
 		entry!(KeyDown(Home); modifiers=[Shift], action_dispatch=AnimationMessage::RestartAnimation),
+		entry!(KeyDown(Comma); modifiers=[Shift], action_dispatch=AnimationMessage::StepBackward),
+		entry!(KeyDown(Period); modifiers=[Shift], action_dispatch=AnimationMessage::StepForward),
+		// TimelineMessage
+		entry!(KeyDown(Comma); modifiers=[Alt], action_dispatch=TimelineMessage::PreviousFrameLayer),
+		entry!(KeyDown(Period); modifiers=[Alt], action_dispatch=TimelineMessage::NextFrameLayer),
+		entry!(KeyDown(KeyN); modifiers=[Alt], action_dispatch=TimelineMessage::AddFrameLayer),
 	];
 	let (mut key_up, mut key_down, mut key_up_no_repeat, mut key_down_no_repeat, mut double_click, mut wheel_scroll, mut pointer_move) = mappings;
 
@@ -463,7 +504,7 @@ pub fn input_mappings() -> Mapping {
 	}
 }
 
-/// Default mappings except that scrolling without modifier keys held down is bound to zooming instead of vertical panning
+/// Default mappings except that scrolling without modifier keys held down is bound to zooming instead of vertical panning, which moves to Ctrl
 pub fn zoom_with_scroll() -> Mapping {
 	use InputMapperMessage::*;
 
@@ -475,8 +516,8 @@ pub fn zoom_with_scroll() -> Mapping {
 		entry!(WheelScroll; action_dispatch=NavigationMessage::CanvasPanMouseWheel { use_y_as_x: false }),
 	];
 	let add = [
-		entry!(WheelScroll; modifiers=[Control], action_dispatch=NavigationMessage::CanvasPanMouseWheel { use_y_as_x: true }),
-		entry!(WheelScroll; modifiers=[Shift], action_dispatch=NavigationMessage::CanvasPanMouseWheel { use_y_as_x: false }),
+		entry!(WheelScroll; modifiers=[Control], action_dispatch=NavigationMessage::CanvasPanMouseWheel { use_y_as_x: false }),
+		entry!(WheelScroll; modifiers=[Shift], action_dispatch=NavigationMessage::CanvasPanMouseWheel { use_y_as_x: true }),
 		entry!(WheelScroll; action_dispatch=NavigationMessage::CanvasZoomMouseWheel),
 	];
 
@@ -485,6 +526,114 @@ pub fn zoom_with_scroll() -> Mapping {
 	mapping
 }
 
+/// Builds the mapping from the layout variant, then the shortcuts of the chosen preset, then the user's custom shortcuts, each overriding the last.
+/// The middle mouse button drag is remapped to the chosen navigation before any of the shortcuts are applied.
+pub fn keymap(variant: MappingVariant, preset: KeymapPreset, middle_mouse_drag: MiddleMouseDrag, custom_shortcuts: &[CustomShortcut]) -> Mapping {
+	let mut mapping: Mapping = variant.into();
+
+	match middle_mouse_drag {
+		MiddleMouseDrag::Pan => {}
+		MiddleMouseDrag::Tilt => middle_mouse_drag_tilt(&mut mapping),
+		MiddleMouseDrag::Zoom => middle_mouse_drag_zoom(&mut mapping),
+	}
+
+	match preset {
+		KeymapPreset::Graphite => {}
+		KeymapPreset::Illustrator => illustrator_preset(&mut mapping),
+		KeymapPreset::Inkscape => inkscape_preset(&mut mapping),
+	}
+
+	for shortcut in custom_shortcuts {
+		if let Some(action) = shortcut.action() {
+			mapping.rebind(&action, &KeysGroup(shortcut.keys.clone()));
+		}
+	}
+
+	mapping
+}
+
+fn middle_mouse_drag_tilt(mapping: &mut Mapping) {
+	use InputMapperMessage::*;
+	use Key::*;
+
+	let remove = [
+		entry!(KeyDown(MouseMiddle); modifiers=[Alt], action_dispatch=NavigationMessage::BeginCanvasTilt { was_dispatched_from_menu: false }),
+		entry!(KeyDown(MouseMiddle); action_dispatch=NavigationMessage::BeginCanvasPan),
+	];
+	let add = [
+		entry!(KeyDown(MouseMiddle); modifiers=[Alt], action_dispatch=NavigationMessage::BeginCanvasPan),
+		entry!(KeyDown(MouseMiddle); action_dispatch=NavigationMessage::BeginCanvasTilt { was_dispatched_from_menu: false }),
+	];
+
+	apply_mapping_patch(mapping, remove, add);
+}
+
+fn middle_mouse_drag_zoom(mapping: &mut Mapping) {
+	use InputMapperMessage::*;
+	use Key::*;
+
+	let remove = [
+		entry!(KeyDown(MouseMiddle); modifiers=[Control], action_dispatch=NavigationMessage::BeginCanvasZoom),
+		entry!(KeyDown(MouseMiddle); action_dispatch=NavigationMessage::BeginCanvasPan),
+	];
+	let add = [
+		entry!(KeyDown(MouseMiddle); modifiers=[Control], action_dispatch=NavigationMessage::BeginCanvasPan),
+		entry!(KeyDown(MouseMiddle); action_dispatch=NavigationMessage::BeginCanvasZoom),
+	];
+
+	apply_mapping_patch(mapping, remove, add);
+}
+
+fn illustrator_preset(mapping: &mut Mapping) {
+	use InputMapperMessage::*;
+	use Key::*;
+
+	let remove = [
+		entry!(KeyDown(KeyE); action_dispatch=ToolMessage::ActivateToolEllipse),
+		entry!(KeyDown(KeyL); action_dispatch=ToolMessage::ActivateToolLine),
+		entry!(KeyDown(KeyL); modifiers=[Accel], action_dispatch=DocumentMessage::ToggleSelectedLocked),
+		entry!(KeyDown(KeyH); modifiers=[Accel], action_dispatch=DocumentMessage::ToggleSelectedVisibility),
+		entry!(KeyDown(Digit2); modifiers=[Accel], action_dispatch=DocumentMessage::ZoomCanvasTo200Percent),
+	];
+	let add = [
+		entry!(KeyDown(KeyL); action_dispatch=ToolMessage::ActivateToolEllipse),
+		entry!(KeyDown(Backslash); action_dispatch=ToolMessage::ActivateToolLine),
+		entry!(KeyDown(Digit2); modifiers=[Accel], action_dispatch=DocumentMessage::ToggleSelectedLocked),
+		entry!(KeyDown(Digit3); modifiers=[Accel], action_dispatch=DocumentMessage::ToggleSelectedVisibility),
+	];
+
+	apply_mapping_patch(mapping, remove, add);
+}
+
+fn inkscape_preset(mapping: &mut Mapping) {
+	use InputMapperMessage::*;
+	use Key::*;
+
+	let remove = [
+		entry!(KeyDown(KeyA); action_dispatch=ToolMessage::ActivateToolPath),
+		entry!(KeyDown(KeyN); action_dispatch=ToolMessage::ActivateToolFreehand),
+		entry!(KeyDown(KeyP); action_dispatch=ToolMessage::ActivateToolPen),
+		entry!(KeyDown(KeyB); action_dispatch=ToolMessage::ActivateToolBrush),
+		entry!(KeyDown(KeyI); action_dispatch=ToolMessage::ActivateToolEyedropper),
+	];
+	let add = [
+		entry!(KeyDown(KeyN); action_dispatch=ToolMessage::ActivateToolPath),
+		entry!(KeyDown(KeyP); action_dispatch=ToolMessage::ActivateToolFreehand),
+		entry!(KeyDown(KeyB); action_dispatch=ToolMessage::ActivateToolPen),
+		entry!(KeyDown(KeyC); action_dispatch=ToolMessage::ActivateToolBrush),
+		entry!(KeyDown(KeyD); action_dispatch=ToolMessage::ActivateToolEyedropper),
+		entry!(KeyDown(F1); action_dispatch=ToolMessage::ActivateToolSelect),
+		entry!(KeyDown(F2); action_dispatch=ToolMessage::ActivateToolPath),
+		entry!(KeyDown(F3); action_dispatch=ToolMessage::ActivateToolNavigate),
+		entry!(KeyDown(F4); action_dispatch=ToolMessage::ActivateToolRectangle),
+		entry!(KeyDown(F5); action_dispatch=ToolMessage::ActivateToolEllipse),
+		entry!(KeyDown(F6); action_dispatch=ToolMessage::ActivateToolFreehand),
+		entry!(KeyDown(F7); action_dispatch=ToolMessage::ActivateToolEyedropper),
+	];
+
+	apply_mapping_patch(mapping, remove, add);
+}
+
 fn apply_mapping_patch<'a, const N: usize, const M: usize, const X: usize, const Y: usize>(
 	mapping: &mut Mapping,
 	remove: impl IntoIterator<Item = &'a [&'a [MappingEntry; N]; M]>,
@@ -1,3 +1,4 @@
+use crate::messages::input_mapper::utility_types::misc::{CustomShortcut, KeymapPreset, MiddleMouseDrag};
 use crate::messages::prelude::*;
 
 #[impl_message(Message, KeyMapping)]
@@ -7,6 +8,11 @@ pub enum KeyMappingMessage {
 	Lookup(InputMapperMessage),
 	#[child]
 	ModifyMapping(MappingVariant),
+	SetKeymap {
+		preset: KeymapPreset,
+		middle_mouse_drag: MiddleMouseDrag,
+		custom_shortcuts: Vec<CustomShortcut>,
+	},
 }
 
 #[impl_message(Message, KeyMappingMessage, ModifyMapping)]
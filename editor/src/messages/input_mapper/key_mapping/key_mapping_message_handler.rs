@@ -1,5 +1,7 @@
 use crate::messages::input_mapper::input_mapper_message_handler::InputMapperMessageData;
+use crate::messages::input_mapper::input_mappings::keymap;
 use crate::messages::input_mapper::utility_types::input_keyboard::KeysGroup;
+use crate::messages::input_mapper::utility_types::misc::{CustomShortcut, KeymapPreset, MiddleMouseDrag};
 use crate::messages::prelude::*;
 
 pub struct KeyMappingMessageData<'a> {
@@ -10,6 +12,10 @@ pub struct KeyMappingMessageData<'a> {
 #[derive(Debug, Default)]
 pub struct KeyMappingMessageHandler {
 	mapping_handler: InputMapperMessageHandler,
+	variant: MappingVariant,
+	preset: KeymapPreset,
+	middle_mouse_drag: MiddleMouseDrag,
+	custom_shortcuts: Vec<CustomShortcut>,
 }
 
 impl MessageHandler<KeyMappingMessage, KeyMappingMessageData<'_>> for KeyMappingMessageHandler {
@@ -18,13 +24,31 @@ impl MessageHandler<KeyMappingMessage, KeyMappingMessageData<'_>> for KeyMapping
 
 		match message {
 			KeyMappingMessage::Lookup(input_message) => self.mapping_handler.process_message(input_message, responses, InputMapperMessageData { input, actions }),
-			KeyMappingMessage::ModifyMapping(new_layout) => self.mapping_handler.set_mapping(new_layout.into()),
+			KeyMappingMessage::ModifyMapping(new_layout) => {
+				self.variant = new_layout;
+				self.rebuild_mapping();
+			}
+			KeyMappingMessage::SetKeymap {
+				preset,
+				middle_mouse_drag,
+				custom_shortcuts,
+			} => {
+				self.preset = preset;
+				self.middle_mouse_drag = middle_mouse_drag;
+				self.custom_shortcuts = custom_shortcuts;
+				self.rebuild_mapping();
+			}
 		}
 	}
 	advertise_actions!();
 }
 
 impl KeyMappingMessageHandler {
+	fn rebuild_mapping(&mut self) {
+		self.mapping_handler
+			.set_mapping(keymap(self.variant.clone(), self.preset, self.middle_mouse_drag, &self.custom_shortcuts));
+	}
+
 	pub fn action_input_mapping(&self, action_to_find: &MessageDiscriminant) -> Vec<KeysGroup> {
 		self.mapping_handler.action_input_mapping(action_to_find)
 	}
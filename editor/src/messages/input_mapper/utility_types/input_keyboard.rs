@@ -302,6 +302,33 @@ impl fmt::Display for Key {
 	}
 }
 
+impl Key {
+	/// Whether this key is only ever held down in combination with another key, rather than being pressed on its own for a shortcut.
+	pub fn is_modifier(self) -> bool {
+		matches!(self, Self::Control | Self::Command | Self::Alt | Self::Shift | Self::Meta | Self::Accel)
+	}
+
+	pub fn is_mouse_button(self) -> bool {
+		matches!(self, Self::MouseLeft | Self::MouseRight | Self::MouseMiddle | Self::MouseBack | Self::MouseForward)
+	}
+
+	/// Looks up a key by either its name or its displayed label, ignoring case.
+	fn from_name(name: &str) -> Option<Self> {
+		match name.to_lowercase().as_str() {
+			"ctrl" | "control" | "cmd" | "command" | "accel" | "⌘" => return Some(Self::Accel),
+			"⌃" => return Some(Self::Control),
+			"shift" | "⇧" => return Some(Self::Shift),
+			"alt" | "option" | "⌥" => return Some(Self::Alt),
+			"⊞" => return Some(Self::Meta),
+			_ => {}
+		}
+
+		(0..NUMBER_OF_KEYS as u8)
+			.filter_map(|index| Self::try_from(index).ok())
+			.find(|key| format!("{key:?}").eq_ignore_ascii_case(name) || key.to_string().eq_ignore_ascii_case(name))
+	}
+}
+
 impl From<Key> for LayoutKey {
 	fn from(key: Key) -> Self {
 		Self {
@@ -332,7 +359,7 @@ impl fmt::Display for KeysGroup {
 			.iter()
 			.map(|key| {
 				let keyboard_layout = GLOBAL_PLATFORM.get().copied().unwrap_or_default().as_keyboard_platform_layout();
-				let key_is_modifier = matches!(*key, Key::Control | Key::Command | Key::Alt | Key::Shift | Key::Meta | Key::Accel);
+				let key_is_modifier = key.is_modifier();
 
 				if keyboard_layout == KeyboardPlatformLayout::Mac && key_is_modifier {
 					key.to_string()
@@ -351,6 +378,43 @@ impl fmt::Display for KeysGroup {
 	}
 }
 
+impl std::str::FromStr for KeysGroup {
+	type Err = String;
+
+	/// Parses a key combination typed by the user, such as "Ctrl Shift K" or "Ctrl+Shift+K", into its modifiers followed by a single non-modifier key.
+	fn from_str(shortcut: &str) -> Result<Self, Self::Err> {
+		const MODIFIER_SYMBOLS: [char; 5] = ['⌘', '⌃', '⇧', '⌥', '⊞'];
+
+		let mut modifiers = Vec::new();
+		let mut key = None;
+		for token in shortcut.split(|c: char| c.is_whitespace() || c == '+').filter(|token| !token.is_empty()) {
+			// Mac-style labels write the modifier symbols directly in front of the key, such as "⌘⇧K"
+			let mut rest = token;
+			while let Some(symbol) = rest.chars().next().filter(|c| MODIFIER_SYMBOLS.contains(c) && rest.len() > c.len_utf8()) {
+				modifiers.extend(Key::from_name(&symbol.to_string()));
+				rest = &rest[symbol.len_utf8()..];
+			}
+
+			let Some(parsed) = Key::from_name(rest) else { return Err(format!("Unknown key \"{rest}\"")) };
+			if parsed.is_mouse_button() {
+				return Err("Mouse buttons can't be used in keyboard shortcuts".to_string());
+			}
+
+			if parsed.is_modifier() {
+				if !modifiers.contains(&parsed) {
+					modifiers.push(parsed);
+				}
+			} else if key.replace(parsed).is_some() {
+				return Err("A shortcut can only have one key besides its modifiers".to_string());
+			}
+		}
+
+		let key = key.ok_or_else(|| "A shortcut needs a key besides its modifiers".to_string())?;
+		modifiers.push(key);
+		Ok(Self(modifiers))
+	}
+}
+
 impl From<KeysGroup> for String {
 	fn from(keys: KeysGroup) -> Self {
 		let layout_keys: LayoutKeysGroup = keys.into();
@@ -122,6 +122,13 @@ impl EditorMouseState {
 	}
 }
 
+/// A finger in contact with a touchscreen, identified across updates by the pointer ID the browser assigns to it.
+#[derive(Debug, Copy, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TouchPoint {
+	pub id: u32,
+	pub editor_position: EditorPosition,
+}
+
 bitflags! {
 	/// Based on <https://developer.mozilla.org/en-US/docs/Web/API/MouseEvent/buttons#value>.
 	#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -38,6 +38,46 @@ impl Mapping {
 		list.push(new_entry);
 	}
 
+	/// Replaces every keyboard shortcut which dispatches the given action with the single key combination, where the last key is the one pressed while holding the others.
+	pub fn rebind(&mut self, action: &Message, keys: &KeysGroup) {
+		let Some((&key, modifier_keys)) = keys.0.split_last() else { return };
+
+		let existing = self
+			.key_down
+			.iter()
+			.chain(self.key_down_no_repeat.iter())
+			.flat_map(|entries| entries.0.iter())
+			.filter(|entry| entry.action == *action && entry.shortcut_key().is_some())
+			.cloned()
+			.collect::<Vec<_>>();
+
+		// Keep the repeat behavior of the shortcut being replaced
+		let no_repeat = existing.first().is_some_and(|entry| matches!(entry.input, InputMapperMessage::KeyDownNoRepeat(_)));
+		for entry in &existing {
+			self.remove(entry);
+		}
+
+		let mut modifiers = KeyStates::new();
+		for &modifier in modifier_keys {
+			modifiers.set(modifier as usize);
+		}
+		let input = if no_repeat { InputMapperMessage::KeyDownNoRepeat(key) } else { InputMapperMessage::KeyDown(key) };
+		self.add(MappingEntry {
+			action: action.clone(),
+			input,
+			modifiers,
+		});
+	}
+
+	/// Every keyboard shortcut the user is able to rebind, in the form of its entry and the key which triggers it.
+	pub fn shortcut_entries(&self) -> impl Iterator<Item = (&MappingEntry, Key)> {
+		self.key_down
+			.iter()
+			.chain(self.key_down_no_repeat.iter())
+			.flat_map(|entries| entries.0.iter())
+			.filter_map(|entry| entry.shortcut_key().map(|key| (entry, key)))
+	}
+
 	fn associated_entries(&self, message: &InputMapperMessage) -> &KeyMappingEntries {
 		match message {
 			InputMapperMessage::KeyDown(key) => &self.key_down[*key as usize],
@@ -122,6 +162,88 @@ pub struct MappingEntry {
 	pub modifiers: KeyStates,
 }
 
+impl MappingEntry {
+	/// The key pressed to trigger this entry if it is a keyboard shortcut, excluding mouse buttons and the modifier keys which only refresh tool states.
+	pub fn shortcut_key(&self) -> Option<Key> {
+		match self.input {
+			InputMapperMessage::KeyDown(key) | InputMapperMessage::KeyDownNoRepeat(key) if !key.is_modifier() && !key.is_mouse_button() => Some(key),
+			_ => None,
+		}
+	}
+}
+
+/// A set of default shortcuts modeled after another application, which users familiar with it can choose instead of Graphite's own.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum KeymapPreset {
+	#[default]
+	Graphite,
+	Illustrator,
+	Inkscape,
+}
+
+impl KeymapPreset {
+	pub const ALL: [Self; 3] = [Self::Graphite, Self::Illustrator, Self::Inkscape];
+}
+
+impl std::fmt::Display for KeymapPreset {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			KeymapPreset::Graphite => write!(f, "Graphite"),
+			KeymapPreset::Illustrator => write!(f, "Illustrator"),
+			KeymapPreset::Inkscape => write!(f, "Inkscape"),
+		}
+	}
+}
+
+/// The navigation performed by dragging with the middle mouse button, which trades places with the one it replaces so the latter is then done with that one's modifier key.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum MiddleMouseDrag {
+	#[default]
+	Pan,
+	Tilt,
+	Zoom,
+}
+
+impl MiddleMouseDrag {
+	pub const ALL: [Self; 3] = [Self::Pan, Self::Tilt, Self::Zoom];
+}
+
+impl std::fmt::Display for MiddleMouseDrag {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			MiddleMouseDrag::Pan => write!(f, "Pan"),
+			MiddleMouseDrag::Tilt => write!(f, "Tilt"),
+			MiddleMouseDrag::Zoom => write!(f, "Zoom"),
+		}
+	}
+}
+
+/// A user's replacement for the keyboard shortcut of an action, which is applied on top of the chosen keymap preset.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct CustomShortcut {
+	/// The action's message serialized as JSON, which identifies it independently of its position in the mapping.
+	pub action: String,
+	/// The modifier keys followed by the key pressed while holding them.
+	pub keys: Vec<Key>,
+}
+
+impl CustomShortcut {
+	/// Parses the key combination typed by the user for the action, which is given as its message serialized as JSON.
+	/// Returns the reason it can't be used if it isn't a valid shortcut for an existing action.
+	pub fn new(action: String, shortcut: &str) -> Result<Self, String> {
+		let keys = shortcut.parse::<KeysGroup>()?;
+		if serde_json::from_str::<Message>(&action).is_err() {
+			return Err("The action no longer exists".to_string());
+		}
+
+		Ok(Self { action, keys: keys.0 })
+	}
+
+	pub fn action(&self) -> Option<Message> {
+		serde_json::from_str(&self.action).ok()
+	}
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
 pub enum ActionKeys {
 	Action(MessageDiscriminant),
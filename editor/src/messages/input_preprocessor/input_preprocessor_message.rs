@@ -1,6 +1,7 @@
 use crate::messages::input_mapper::utility_types::input_keyboard::{Key, ModifierKeys};
-use crate::messages::input_mapper::utility_types::input_mouse::{EditorMouseState, ViewportBounds};
+use crate::messages::input_mapper::utility_types::input_mouse::{EditorMouseState, TouchPoint, ViewportBounds};
 use crate::messages::prelude::*;
+use glam::DVec2;
 
 #[impl_message(Message, InputPreprocessor)]
 #[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -13,5 +14,8 @@ pub enum InputPreprocessorMessage {
 	PointerMove { editor_mouse_state: EditorMouseState, modifier_keys: ModifierKeys },
 	PointerUp { editor_mouse_state: EditorMouseState, modifier_keys: ModifierKeys },
 	CurrentTime { timestamp: u64 },
+	StylusOrientation { tilt: DVec2, twist: f64 },
+	TouchPoints { touches: Vec<TouchPoint> },
+	TrackpadPinch { editor_mouse_state: EditorMouseState, modifier_keys: ModifierKeys },
 	WheelScroll { editor_mouse_state: EditorMouseState, modifier_keys: ModifierKeys },
 }
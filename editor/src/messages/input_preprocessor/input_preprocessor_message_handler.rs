@@ -1,5 +1,6 @@
-use crate::messages::input_mapper::utility_types::input_keyboard::{Key, KeyStates, ModifierKeys};
-use crate::messages::input_mapper::utility_types::input_mouse::{MouseButton, MouseKeys, MouseState, ViewportBounds};
+use crate::consts::{INTERACTION_IDLE_DELAY, TOUCH_HIT_TARGET_SCALE, VIEWPORT_ZOOM_TRACKPAD_PINCH_RATE};
+use crate::messages::input_mapper::utility_types::input_keyboard::{Key, KeyStates, KeysGroup, ModifierKeys};
+use crate::messages::input_mapper::utility_types::input_mouse::{MouseButton, MouseKeys, MouseState, TouchPoint, ViewportBounds};
 use crate::messages::input_mapper::utility_types::misc::FrameTimeInfo;
 use crate::messages::portfolio::utility_types::KeyboardPlatformLayout;
 use crate::messages::prelude::*;
@@ -7,6 +8,8 @@ use glam::DVec2;
 
 pub struct InputPreprocessorMessageData {
 	pub keyboard_platform: KeyboardPlatformLayout,
+	/// Whether pressing a modifier key toggles it on until it's pressed again, instead of it only applying while held.
+	pub sticky_modifier_keys: bool,
 }
 
 #[derive(Debug, Default)]
@@ -16,11 +19,32 @@ pub struct InputPreprocessorMessageHandler {
 	pub keyboard: KeyStates,
 	pub mouse: MouseState,
 	pub viewport_bounds: ViewportBounds,
+	/// The time of the most recent scroll wheel input.
+	pub last_scroll_time: u64,
+	/// The fingers currently touching the screen, which are tracked to recognize multi-touch navigation gestures.
+	pub touches: Vec<TouchPoint>,
+	/// Whether the most recent pointer input came from a touchscreen, so hit targets can be enlarged for fingers.
+	pub touch_input: bool,
+	/// The tilt of the stylus away from perpendicular to the screen along the X and Y axes, in degrees, or zero when not using a stylus.
+	pub stylus_tilt: DVec2,
+	/// The clockwise rotation of the stylus around its own axis, in degrees, or zero when not using a stylus.
+	pub stylus_twist: f64,
+	/// The modifier keys toggled on while sticky modifier keys are enabled, which count as held until they're pressed again.
+	pub latched_modifier_keys: ModifierKeys,
 }
 
 impl MessageHandler<InputPreprocessorMessage, InputPreprocessorMessageData> for InputPreprocessorMessageHandler {
 	fn process_message(&mut self, message: InputPreprocessorMessage, responses: &mut VecDeque<Message>, data: InputPreprocessorMessageData) {
-		let InputPreprocessorMessageData { keyboard_platform } = data;
+		let InputPreprocessorMessageData {
+			keyboard_platform,
+			sticky_modifier_keys,
+		} = data;
+
+		// Release the latched modifier keys once sticky modifier keys are turned off
+		if !sticky_modifier_keys && !self.latched_modifier_keys.is_empty() {
+			self.latched_modifier_keys = ModifierKeys::empty();
+			self.send_latched_modifier_keys(keyboard_platform, responses);
+		}
 
 		match message {
 			InputPreprocessorMessage::BoundsOfViewports { bounds_of_viewports } => {
@@ -52,6 +76,11 @@ impl MessageHandler<InputPreprocessorMessage, InputPreprocessorMessageData> for
 				}
 			}
 			InputPreprocessorMessage::KeyDown { key, key_repeat, modifier_keys } => {
+				if let Some(modifier) = modifier_of_key(key).filter(|_| sticky_modifier_keys && !key_repeat) {
+					self.latched_modifier_keys.toggle(modifier);
+					self.send_latched_modifier_keys(keyboard_platform, responses);
+				}
+
 				self.update_states_of_modifier_keys(modifier_keys, keyboard_platform, responses);
 				self.keyboard.set(key as usize);
 				if !key_repeat {
@@ -61,6 +90,12 @@ impl MessageHandler<InputPreprocessorMessage, InputPreprocessorMessageData> for
 			}
 			InputPreprocessorMessage::KeyUp { key, key_repeat, modifier_keys } => {
 				self.update_states_of_modifier_keys(modifier_keys, keyboard_platform, responses);
+
+				// A latched modifier key stays held after being released
+				if modifier_of_key(key).is_some_and(|modifier| self.latched_modifier_keys.contains(modifier)) {
+					return;
+				}
+
 				self.keyboard.unset(key as usize);
 				if !key_repeat {
 					responses.add(InputMapperMessage::KeyUpNoRepeat(key));
@@ -81,6 +116,11 @@ impl MessageHandler<InputPreprocessorMessage, InputPreprocessorMessageData> for
 				let mouse_state = editor_mouse_state.to_mouse_state(&self.viewport_bounds);
 				self.mouse.position = mouse_state.position;
 
+				// Only a mouse or stylus can hover without pressing a button
+				if mouse_state.mouse_keys.is_empty() && self.touches.is_empty() {
+					self.touch_input = false;
+				}
+
 				responses.add(InputMapperMessage::PointerMove);
 
 				// While any pointer button is already down, additional button down events are not reported, but they are sent as `pointermove` events
@@ -98,12 +138,53 @@ impl MessageHandler<InputPreprocessorMessage, InputPreprocessorMessageData> for
 				responses.add(AnimationMessage::SetTime(timestamp as f64));
 				self.time = timestamp;
 			}
+			InputPreprocessorMessage::StylusOrientation { tilt, twist } => {
+				self.stylus_tilt = tilt;
+				self.stylus_twist = twist;
+			}
+			InputPreprocessorMessage::TouchPoints { touches } => {
+				self.touch_input = true;
+
+				if touches.len() >= 2 {
+					if self.touches.len() < 2 {
+						// A second finger turns the interaction into a navigation gesture, so cancel whatever the first finger started with the tool
+						responses.add(BroadcastEvent::ToolAbort);
+						let released = MouseState {
+							mouse_keys: MouseKeys::empty(),
+							..self.mouse
+						};
+						self.translate_mouse_event(released, false, responses);
+					} else if let Some(message) = self.pinch_gesture(&touches) {
+						responses.add(message);
+					}
+				}
+
+				self.touches = touches;
+			}
+			InputPreprocessorMessage::TrackpadPinch { editor_mouse_state, modifier_keys } => {
+				self.update_states_of_modifier_keys(modifier_keys, keyboard_platform, responses);
+
+				let mouse_state = editor_mouse_state.to_mouse_state(&self.viewport_bounds);
+				self.mouse.position = mouse_state.position;
+				self.last_scroll_time = self.time;
+				self.touch_input = false;
+
+				// Pinching always zooms about the pointer, regardless of what the scroll wheel is set to do
+				responses.add(NavigationMessage::CanvasPinch {
+					center: self.mouse.position,
+					pan: DVec2::ZERO,
+					zoom_factor: (-mouse_state.scroll_delta.y * VIEWPORT_ZOOM_TRACKPAD_PINCH_RATE).exp(),
+					tilt_delta: 0.,
+				});
+			}
 			InputPreprocessorMessage::WheelScroll { editor_mouse_state, modifier_keys } => {
 				self.update_states_of_modifier_keys(modifier_keys, keyboard_platform, responses);
 
 				let mouse_state = editor_mouse_state.to_mouse_state(&self.viewport_bounds);
 				self.mouse.position = mouse_state.position;
 				self.mouse.scroll_delta = mouse_state.scroll_delta;
+				self.last_scroll_time = self.time;
+				self.touch_input = false;
 
 				responses.add(InputMapperMessage::WheelScroll);
 			}
@@ -117,6 +198,42 @@ impl MessageHandler<InputPreprocessorMessage, InputPreprocessorMessageData> for
 }
 
 impl InputPreprocessorMessageHandler {
+	/// Whether the user is dragging with a mouse button held or has recently scrolled, such as to pan or zoom the viewport.
+	pub fn is_interacting(&self) -> bool {
+		!self.mouse.mouse_keys.is_empty() || self.time.saturating_sub(self.last_scroll_time) < INTERACTION_IDLE_DELAY
+	}
+
+	/// Scales up a hit test distance (in viewport pixels) while using touch input, since fingers need larger targets than a precise cursor.
+	pub fn hit_tolerance(&self, tolerance: f64) -> f64 {
+		if self.touch_input { tolerance * TOUCH_HIT_TARGET_SCALE } else { tolerance }
+	}
+
+	/// Recognizes the panning, zooming, and rotating of the first two fingers since their previous positions.
+	fn pinch_gesture(&self, touches: &[TouchPoint]) -> Option<Message> {
+		let previous_position = |touch: &TouchPoint| self.touches.iter().find(|previous| previous.id == touch.id).map(|previous| previous.editor_position);
+		let [first, second] = [&touches[0], &touches[1]];
+		let (first_old, second_old) = (previous_position(first)?, previous_position(second)?);
+
+		let old_span = second_old - first_old;
+		let new_span = second.editor_position - first.editor_position;
+		if old_span.length() < f64::EPSILON || new_span.length() < f64::EPSILON {
+			return None;
+		}
+
+		let old_center = (first_old + second_old) / 2.;
+		let new_center = (first.editor_position + second.editor_position) / 2.;
+
+		Some(
+			NavigationMessage::CanvasPinch {
+				center: old_center - self.viewport_bounds.top_left,
+				pan: new_center - old_center,
+				zoom_factor: new_span.length() / old_span.length(),
+				tilt_delta: old_span.angle_to(new_span),
+			}
+			.into(),
+		)
+	}
+
 	fn translate_mouse_event(&mut self, mut new_state: MouseState, allow_first_button_down: bool, responses: &mut VecDeque<Message>) {
 		let click_mappings = [
 			(MouseKeys::LEFT, Key::MouseLeft),
@@ -149,6 +266,8 @@ impl InputPreprocessorMessageHandler {
 	}
 
 	fn update_states_of_modifier_keys(&mut self, pressed_modifier_keys: ModifierKeys, keyboard_platform: KeyboardPlatformLayout, responses: &mut VecDeque<Message>) {
+		// Latched modifier keys count as held regardless of whether they're physically pressed
+		let pressed_modifier_keys = pressed_modifier_keys | self.latched_modifier_keys;
 		let is_key_pressed = |key_to_check: ModifierKeys| pressed_modifier_keys.contains(key_to_check);
 
 		// Update the state of the concrete modifier keys based on the source state
@@ -183,16 +302,47 @@ impl InputPreprocessorMessageHandler {
 		}
 	}
 
+	/// Shows the latched modifier keys in the status bar so it's clear which ones are being held.
+	fn send_latched_modifier_keys(&self, keyboard_platform: KeyboardPlatformLayout, responses: &mut VecDeque<Message>) {
+		let meta_or_command = match keyboard_platform {
+			KeyboardPlatformLayout::Mac => Key::Command,
+			KeyboardPlatformLayout::Standard => Key::Meta,
+		};
+		let keys = [
+			(ModifierKeys::CONTROL, Key::Control),
+			(ModifierKeys::META_OR_COMMAND, meta_or_command),
+			(ModifierKeys::ALT, Key::Alt),
+			(ModifierKeys::SHIFT, Key::Shift),
+		]
+		.into_iter()
+		.filter(|(modifier, _)| self.latched_modifier_keys.contains(*modifier))
+		.map(|(_, key)| key)
+		.collect();
+
+		responses.add(FrontendMessage::UpdateStickyModifierKeys { keys: KeysGroup(keys).into() });
+	}
+
 	pub fn document_bounds(&self) -> [DVec2; 2] {
 		// IPP bounds are relative to the entire application
 		[(0., 0.).into(), self.viewport_bounds.bottom_right - self.viewport_bounds.top_left]
 	}
 }
 
+/// The modifier flag corresponding to a modifier key, if it is one.
+fn modifier_of_key(key: Key) -> Option<ModifierKeys> {
+	match key {
+		Key::Shift => Some(ModifierKeys::SHIFT),
+		Key::Alt => Some(ModifierKeys::ALT),
+		Key::Control => Some(ModifierKeys::CONTROL),
+		Key::Meta | Key::Command => Some(ModifierKeys::META_OR_COMMAND),
+		_ => None,
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use crate::messages::input_mapper::utility_types::input_keyboard::{Key, ModifierKeys};
-	use crate::messages::input_mapper::utility_types::input_mouse::{EditorMouseState, MouseKeys, ScrollDelta};
+	use crate::messages::input_mapper::utility_types::input_mouse::{EditorMouseState, MouseKeys, ScrollDelta, TouchPoint};
 	use crate::messages::portfolio::utility_types::KeyboardPlatformLayout;
 	use crate::messages::prelude::*;
 
@@ -212,6 +362,7 @@ mod test {
 
 		let data = InputPreprocessorMessageData {
 			keyboard_platform: KeyboardPlatformLayout::Standard,
+			sticky_modifier_keys: false,
 		};
 		input_preprocessor.process_message(message, &mut responses, data);
 
@@ -231,6 +382,7 @@ mod test {
 
 		let data = InputPreprocessorMessageData {
 			keyboard_platform: KeyboardPlatformLayout::Standard,
+			sticky_modifier_keys: false,
 		};
 		input_preprocessor.process_message(message, &mut responses, data);
 
@@ -250,6 +402,7 @@ mod test {
 
 		let data = InputPreprocessorMessageData {
 			keyboard_platform: KeyboardPlatformLayout::Standard,
+			sticky_modifier_keys: false,
 		};
 		input_preprocessor.process_message(message, &mut responses, data);
 
@@ -271,6 +424,7 @@ mod test {
 
 		let data = InputPreprocessorMessageData {
 			keyboard_platform: KeyboardPlatformLayout::Standard,
+			sticky_modifier_keys: false,
 		};
 		input_preprocessor.process_message(message, &mut responses, data);
 
@@ -291,6 +445,7 @@ mod test {
 
 		let data = InputPreprocessorMessageData {
 			keyboard_platform: KeyboardPlatformLayout::Standard,
+			sticky_modifier_keys: false,
 		};
 		input_preprocessor.process_message(message, &mut responses, data);
 
@@ -299,4 +454,85 @@ mod test {
 		assert!(responses.contains(&InputMapperMessage::KeyDown(Key::Control).into()));
 		assert!(responses.contains(&InputMapperMessage::KeyDown(Key::Control).into()));
 	}
+
+	#[test]
+	fn process_action_touch_points_pinch_gesture() {
+		let mut input_preprocessor = InputPreprocessorMessageHandler::default();
+
+		let data = || InputPreprocessorMessageData {
+			keyboard_platform: KeyboardPlatformLayout::Standard,
+			sticky_modifier_keys: false,
+		};
+		let touches = |second: (f64, f64)| {
+			vec![
+				TouchPoint {
+					id: 1,
+					editor_position: (0., 0.).into(),
+				},
+				TouchPoint {
+					id: 2,
+					editor_position: second.into(),
+				},
+			]
+		};
+
+		let mut responses = VecDeque::new();
+		input_preprocessor.process_message(InputPreprocessorMessage::TouchPoints { touches: touches((10., 0.)) }, &mut responses, data());
+		assert!(input_preprocessor.touch_input);
+		assert!(responses.contains(&BroadcastEvent::ToolAbort.into()));
+
+		let mut responses = VecDeque::new();
+		input_preprocessor.process_message(InputPreprocessorMessage::TouchPoints { touches: touches((0., 20.)) }, &mut responses, data());
+		let Some(Message::Portfolio(PortfolioMessage::Document(DocumentMessage::Navigation(NavigationMessage::CanvasPinch { center, pan, zoom_factor, tilt_delta })))) = responses.pop_front() else {
+			panic!("Expected a pinch gesture");
+		};
+		assert_eq!(center, (5., 0.).into());
+		assert_eq!(pan, (-5., 10.).into());
+		assert!((zoom_factor - 2.).abs() < 1e-9);
+		assert!((tilt_delta - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+	}
+
+	#[test]
+	fn process_action_sticky_modifier_keys_toggle() {
+		let mut input_preprocessor = InputPreprocessorMessageHandler::default();
+
+		let data = || InputPreprocessorMessageData {
+			keyboard_platform: KeyboardPlatformLayout::Standard,
+			sticky_modifier_keys: true,
+		};
+		let mut press = |key_down: bool, modifier_keys: ModifierKeys| {
+			let key = Key::Shift;
+			let key_repeat = false;
+			let message = if key_down {
+				InputPreprocessorMessage::KeyDown { key, key_repeat, modifier_keys }
+			} else {
+				InputPreprocessorMessage::KeyUp { key, key_repeat, modifier_keys }
+			};
+			input_preprocessor.process_message(message, &mut VecDeque::new(), data());
+			input_preprocessor.keyboard.get(Key::Shift as usize)
+		};
+
+		// Pressing and releasing Shift keeps it held
+		assert!(press(true, ModifierKeys::SHIFT));
+		assert!(press(false, ModifierKeys::empty()));
+
+		// Pressing and releasing it again lets go of it
+		assert!(press(true, ModifierKeys::SHIFT));
+		assert!(!press(false, ModifierKeys::empty()));
+
+		// It's also let go of once sticky modifier keys are turned off
+		assert!(press(true, ModifierKeys::SHIFT));
+		assert!(press(false, ModifierKeys::empty()));
+		let editor_mouse_state = EditorMouseState::default();
+		let message = InputPreprocessorMessage::PointerMove {
+			editor_mouse_state,
+			modifier_keys: ModifierKeys::empty(),
+		};
+		let data = InputPreprocessorMessageData {
+			keyboard_platform: KeyboardPlatformLayout::Standard,
+			sticky_modifier_keys: false,
+		};
+		input_preprocessor.process_message(message, &mut VecDeque::new(), data);
+		assert!(!input_preprocessor.keyboard.get(Key::Shift as usize));
+	}
 }
@@ -422,6 +422,13 @@ impl LayoutMessageHandler {
 			LayoutTarget::DialogButtons => FrontendMessage::UpdateDialogButtons { layout_target, diff },
 			LayoutTarget::DialogColumn1 => FrontendMessage::UpdateDialogColumn1 { layout_target, diff },
 			LayoutTarget::DialogColumn2 => FrontendMessage::UpdateDialogColumn2 { layout_target, diff },
+			LayoutTarget::ColorHarmony => FrontendMessage::UpdateColorHarmonyLayout { layout_target, diff },
+			LayoutTarget::Console => FrontendMessage::UpdateConsoleLayout { layout_target, diff },
+			LayoutTarget::Collaboration => FrontendMessage::UpdateCollaborationLayout { layout_target, diff },
+			LayoutTarget::Compare => FrontendMessage::UpdateCompareLayout { layout_target, diff },
+			LayoutTarget::Library => FrontendMessage::UpdateLibraryLayout { layout_target, diff },
+			LayoutTarget::DataMerge => FrontendMessage::UpdateDataMergeLayout { layout_target, diff },
+			LayoutTarget::Plugins => FrontendMessage::UpdatePluginsLayout { layout_target, diff },
 			LayoutTarget::DocumentBar => FrontendMessage::UpdateDocumentBarLayout { layout_target, diff },
 			LayoutTarget::DocumentMode => FrontendMessage::UpdateDocumentModeLayout { layout_target, diff },
 			LayoutTarget::LayersPanelControlBar => FrontendMessage::UpdateLayersPanelControlBarLayout { layout_target, diff },
@@ -429,8 +436,11 @@ impl LayoutMessageHandler {
 			LayoutTarget::NodeGraphControlBar => FrontendMessage::UpdateNodeGraphControlBarLayout { layout_target, diff },
 			LayoutTarget::PropertiesSections => FrontendMessage::UpdatePropertyPanelSectionsLayout { layout_target, diff },
 			LayoutTarget::Spreadsheet => FrontendMessage::UpdateSpreadsheetLayout { layout_target, diff },
+			LayoutTarget::SplitViewControlBar => FrontendMessage::UpdateSplitViewControlBarLayout { layout_target, diff },
+			LayoutTarget::TimelineControlBar => FrontendMessage::UpdateTimelineControlBarLayout { layout_target, diff },
 			LayoutTarget::ToolOptions => FrontendMessage::UpdateToolOptionsLayout { layout_target, diff },
 			LayoutTarget::ToolShelf => FrontendMessage::UpdateToolShelfLayout { layout_target, diff },
+			LayoutTarget::TransformBar => FrontendMessage::UpdateTransformBarLayout { layout_target, diff },
 			LayoutTarget::WorkingColors => FrontendMessage::UpdateWorkingColorsLayout { layout_target, diff },
 
 			LayoutTarget::LayoutTargetLength => panic!("`LayoutTargetLength` is not a valid Layout Target and is used for array indexing"),
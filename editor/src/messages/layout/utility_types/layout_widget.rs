@@ -27,6 +27,20 @@ pub enum LayoutTarget {
 	DialogColumn1,
 	/// Contains the contents of the dialog's secondary column (often blank). Must be shown with the `FrontendMessage::DisplayDialog` message.
 	DialogColumn2,
+	/// The body of the Color Harmony panel containing the scheme generated from the primary working color.
+	ColorHarmony,
+	/// The body of the Console panel containing the script being written and the output of those run.
+	Console,
+	/// The body of the Collaboration panel with the server to join and the collaborators editing the document.
+	Collaboration,
+	/// The body of the Compare panel listing the differences between two versions of a document.
+	Compare,
+	/// The body of the Library panel with the shared library to connect to and the controls to publish to it.
+	Library,
+	/// The body of the Data Merge panel with the imported dataset, the layers bound to its columns, and the controls to merge its records.
+	DataMerge,
+	/// The body of the Plugins panel containing the panels added by each loaded plugin.
+	Plugins,
 	/// Contains the widgets located directly above the canvas to the right, for example the zoom in and out buttons.
 	DocumentBar,
 	/// Contains the dropdown for design / select / guide mode found on the top left of the canvas.
@@ -41,10 +55,16 @@ pub enum LayoutTarget {
 	PropertiesSections,
 	/// The spredsheet panel allows for the visualisation of data in the graph.
 	Spreadsheet,
+	/// Bar at the top of the split view containing the document dropdown and zoom buttons.
+	SplitViewControlBar,
+	/// Bar at the top of the timeline containing the playback controls and keyframe buttons.
+	TimelineControlBar,
 	/// The bar directly above the canvas, left-aligned and to the right of the document mode dropdown.
 	ToolOptions,
 	/// The vertical buttons for all of the tools on the left of the canvas.
 	ToolShelf,
+	/// The position, size, rotation, and skew fields for the selected layers, located in the bar above the canvas to the right of the tool options.
+	TransformBar,
 	/// The color swatch for the working colors and a flip and reset button found at the bottom of the tool shelf.
 	WorkingColors,
 
@@ -101,6 +101,10 @@ pub struct TextButton {
 
 	pub disabled: bool,
 
+	/// The URL of a font file to draw the label with, instead of the interface font.
+	#[serde(rename = "fontUrl")]
+	pub font_url: Option<String>,
+
 	pub tooltip: String,
 
 	#[serde(skip)]
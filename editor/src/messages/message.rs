@@ -10,6 +10,8 @@ pub enum Message {
 	StartBuffer,
 	EndBuffer(graphene_std::renderer::RenderMetadata),
 
+	#[child]
+	ActionMacro(ActionMacroMessage),
 	#[child]
 	Animation(AnimationMessage),
 	#[child]
@@ -33,6 +35,8 @@ pub enum Message {
 	#[child]
 	Preferences(PreferencesMessage),
 	#[child]
+	RadialMenu(RadialMenuMessage),
+	#[child]
 	Tool(ToolMessage),
 	#[child]
 	Workspace(WorkspaceMessage),
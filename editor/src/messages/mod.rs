@@ -1,5 +1,6 @@
 //! The root-level messages forming the first layer of the message system architecture.
 
+pub mod action_macro;
 pub mod animation;
 pub mod broadcast;
 pub mod debug;
@@ -13,5 +14,6 @@ pub mod message;
 pub mod portfolio;
 pub mod preferences;
 pub mod prelude;
+pub mod radial_menu;
 pub mod tool;
 pub mod workspace;
@@ -0,0 +1,33 @@
+use crate::messages::portfolio::document::overlays::utility_types::OverlayContext;
+use crate::messages::prelude::*;
+
+/// Collaboration lets several people edit a document together through a server which relays changes between everyone in the same room.
+#[impl_message(Message, PortfolioMessage, Collaboration)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum CollaborationMessage {
+	ToggleOpen,
+
+	// Overlays
+	Overlays(OverlayContext),
+
+	UpdateServer {
+		server: String,
+	},
+	UpdateName {
+		name: String,
+	},
+	/// Connects to the server to edit the active document together with the others in the room given by the server's URL.
+	Join,
+	Leave,
+
+	/// Sent by the frontend once its connection to the server is open.
+	Connected,
+	/// Sent by the frontend when its connection to the server closes, whether or not it was asked to.
+	Disconnected,
+	/// Handles a packet relayed from another collaborator, serialized as JSON.
+	Received {
+		packet: String,
+	},
+	/// Sent regularly by the frontend while connected, to send the changes made to the document since the last tick and apply those received from the others.
+	Tick,
+}
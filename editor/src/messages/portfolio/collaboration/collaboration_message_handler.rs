@@ -0,0 +1,425 @@
+use super::sync::{self, ClientId, CollaborationPacket, Replica, SyncedKey, SyncedValue};
+use crate::application::generate_uuid;
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::overlays::utility_types::{OverlayContext, OverlayProvider, Pivot};
+use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
+use crate::messages::portfolio::document::utility_types::network_interface::TransactionStatus;
+use crate::messages::prelude::*;
+use glam::{DAffine2, DVec2};
+use graph_craft::document::NodeId;
+use graphene_core::Color;
+use graphene_core::vector::style::FillChoice;
+
+const COLLABORATION_OVERLAY_PROVIDER: OverlayProvider = |context| CollaborationMessage::Overlays(context).into();
+
+/// The colors given to collaborators, chosen by their client ID, as sRGB.
+const COLLABORATOR_COLORS: [(u8, u8, u8); 8] = [
+	(0xe6, 0x4a, 0x4a),
+	(0xe6, 0x9a, 0x2e),
+	(0xc8, 0xc0, 0x2a),
+	(0x4c, 0xb8, 0x4c),
+	(0x2e, 0xb8, 0xb0),
+	(0x4a, 0x8a, 0xe6),
+	(0x9a, 0x5a, 0xe6),
+	(0xe0, 0x5a, 0xb4),
+];
+
+pub struct CollaborationMessageData<'a> {
+	pub documents: &'a mut HashMap<DocumentId, DocumentMessageHandler>,
+	pub active_document_id: Option<DocumentId>,
+	pub ipp: &'a InputPreprocessorMessageHandler,
+}
+
+#[derive(Debug, Clone)]
+struct Collaborator {
+	name: String,
+	pointer: Option<DVec2>,
+	selected_layers: Vec<NodeId>,
+}
+
+/// Editing a document together with the others in a room of the server.
+#[derive(Debug, Clone)]
+struct Session {
+	client: ClientId,
+	document_id: DocumentId,
+	connected: bool,
+	replica: Replica,
+	collaborators: HashMap<ClientId, Collaborator>,
+	/// The document's content when last synced, which the changes made to it since are found from.
+	last_content: HashMap<SyncedKey, SyncedValue>,
+	/// The version of the document's content when last synced, which changes with every edit to it, so the content is only compared once it's been edited.
+	synced_version: u64,
+	/// Set when changes from collaborators have been merged into the replica but not yet applied to the document.
+	remote_changes: bool,
+	last_presence: (Option<DVec2>, Vec<NodeId>),
+}
+
+/// Lets several people edit a document together, syncing the document's content and showing where each of them is pointing and what they have selected.
+#[derive(Debug, Clone)]
+pub struct CollaborationMessageHandler {
+	/// Sets whether or not the Collaboration panel is drawn.
+	pub collaboration_open: bool,
+	server: String,
+	name: String,
+	session: Option<Session>,
+}
+
+impl Default for CollaborationMessageHandler {
+	fn default() -> Self {
+		Self {
+			collaboration_open: false,
+			server: "ws://localhost:3001/room".into(),
+			name: "Anonymous".into(),
+			session: None,
+		}
+	}
+}
+
+impl MessageHandler<CollaborationMessage, CollaborationMessageData<'_>> for CollaborationMessageHandler {
+	fn process_message(&mut self, message: CollaborationMessage, responses: &mut VecDeque<Message>, data: CollaborationMessageData) {
+		let CollaborationMessageData { documents, active_document_id, ipp } = data;
+
+		match message {
+			CollaborationMessage::ToggleOpen => {
+				self.collaboration_open = !self.collaboration_open;
+				// Update checked UI state for open
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			CollaborationMessage::Overlays(mut overlay_context) => {
+				let Some(session) = &self.session else { return };
+				if active_document_id != Some(session.document_id) {
+					return;
+				}
+				let Some(document) = documents.get(&session.document_id) else { return };
+
+				for (&client, collaborator) in &session.collaborators {
+					draw_collaborator(&mut overlay_context, document, client, collaborator);
+				}
+				return;
+			}
+			CollaborationMessage::UpdateServer { server } => {
+				self.server = server;
+				return;
+			}
+			CollaborationMessage::UpdateName { name } => {
+				self.name = name;
+				return;
+			}
+			CollaborationMessage::Join => {
+				let Some(document_id) = active_document_id else { return };
+				let Some(document) = documents.get(&document_id) else { return };
+				if self.session.is_some() {
+					return;
+				}
+
+				let client = generate_uuid();
+				let content = sync::document_content(&document.network_interface);
+				self.session = Some(Session {
+					client,
+					document_id,
+					connected: false,
+					replica: Replica::new(client, content.clone()),
+					last_content: content,
+					collaborators: HashMap::new(),
+					synced_version: document.network_interface.content_version(),
+					remote_changes: false,
+					last_presence: (None, Vec::new()),
+				});
+
+				responses.add(FrontendMessage::TriggerCollaborationConnect { server: self.server.clone() });
+				responses.add(OverlaysMessage::AddProvider(COLLABORATION_OVERLAY_PROVIDER));
+			}
+			CollaborationMessage::Leave => {
+				let Some(session) = self.session.take() else { return };
+
+				if session.connected {
+					send(CollaborationPacket::Goodbye { client: session.client }, responses);
+				}
+				responses.add(FrontendMessage::TriggerCollaborationDisconnect);
+				responses.add(OverlaysMessage::RemoveProvider(COLLABORATION_OVERLAY_PROVIDER));
+				responses.add(OverlaysMessage::Draw);
+			}
+			CollaborationMessage::Connected => {
+				let Some(session) = &mut self.session else { return };
+				session.connected = true;
+
+				send(
+					CollaborationPacket::Hello {
+						client: session.client,
+						name: self.name.clone(),
+						reply: false,
+					},
+					responses,
+				);
+				send(
+					CollaborationPacket::Sync {
+						operations: session.replica.operations(),
+					},
+					responses,
+				);
+			}
+			CollaborationMessage::Disconnected => {
+				// The session has already ended if leaving closed the connection
+				if self.session.take().is_none() {
+					return;
+				}
+
+				responses.add(OverlaysMessage::RemoveProvider(COLLABORATION_OVERLAY_PROVIDER));
+				responses.add(OverlaysMessage::Draw);
+				responses.add(DialogMessage::DisplayDialogError {
+					title: "Collaboration ended".into(),
+					description: format!("The connection to the collaboration server at {} was lost.", self.server),
+				});
+			}
+			CollaborationMessage::Received { packet } => {
+				let Some(session) = &mut self.session else { return };
+				let packet = match serde_json::from_str::<CollaborationPacket>(&packet) {
+					Ok(packet) => packet,
+					Err(error) => {
+						warn!("Ignored a collaboration packet which couldn't be read: {error}");
+						return;
+					}
+				};
+
+				match packet {
+					CollaborationPacket::Hello { client, name, reply } => {
+						session.collaborators.insert(
+							client,
+							Collaborator {
+								name,
+								pointer: None,
+								selected_layers: Vec::new(),
+							},
+						);
+
+						// Introduce ourselves to the newcomer and bring them up to date, with what we joined with claimed so it isn't replaced by what they joined with
+						if !reply {
+							session.replica.claim_initial_entries();
+							send(
+								CollaborationPacket::Hello {
+									client: session.client,
+									name: self.name.clone(),
+									reply: true,
+								},
+								responses,
+							);
+							send(
+								CollaborationPacket::Sync {
+									operations: session.replica.operations(),
+								},
+								responses,
+							);
+						}
+					}
+					CollaborationPacket::Goodbye { client } => {
+						session.collaborators.remove(&client);
+						responses.add(OverlaysMessage::Draw);
+					}
+					CollaborationPacket::Sync { operations } => {
+						for operation in operations {
+							session.remote_changes |= session.replica.merge(operation);
+						}
+						return;
+					}
+					CollaborationPacket::Presence { client, pointer, selected_layers } => {
+						if let Some(collaborator) = session.collaborators.get_mut(&client) {
+							collaborator.pointer = pointer;
+							collaborator.selected_layers = selected_layers;
+						}
+						responses.add(OverlaysMessage::Draw);
+						return;
+					}
+				}
+			}
+			CollaborationMessage::Tick => {
+				let Some(session) = &mut self.session else { return };
+				if !session.connected {
+					return;
+				}
+				let Some(document) = documents.get_mut(&session.document_id) else {
+					// The document has been closed
+					responses.add(CollaborationMessage::Leave);
+					return;
+				};
+
+				// Changes are only synced between transactions, so the others don't see each step of a drag and a drag isn't interrupted by their changes
+				if document.network_interface.transaction_status() == TransactionStatus::Finished {
+					if document.network_interface.content_version() != session.synced_version {
+						let changes = sync::update_content(&mut session.last_content, &document.network_interface);
+						let operations = session.replica.record_local_changes(changes);
+						if !operations.is_empty() {
+							send(CollaborationPacket::Sync { operations }, responses);
+						}
+					}
+
+					if session.remote_changes {
+						let (changes, operations) = session.replica.changes_to_apply(&session.last_content);
+						if !operations.is_empty() {
+							send(CollaborationPacket::Sync { operations }, responses);
+						}
+						if !changes.is_empty() {
+							let network_interface = sync::apply_changes(&document.network_interface, changes);
+							document.apply_synced_changes(network_interface, ipp, responses);
+							sync::update_content(&mut session.last_content, &document.network_interface);
+							responses.add(OverlaysMessage::Draw);
+						}
+						session.remote_changes = false;
+					}
+
+					session.synced_version = document.network_interface.content_version();
+				}
+
+				// Share where the pointer is, while it's over the canvas, and which layers are selected
+				let pointer = ipp
+					.viewport_bounds
+					.in_bounds(ipp.mouse.position)
+					.then(|| document.metadata().document_to_viewport.inverse().transform_point2(ipp.mouse.position));
+				let selected_layers = document
+					.network_interface
+					.selected_nodes()
+					.selected_layers(document.metadata())
+					.map(|layer| layer.to_node())
+					.collect::<Vec<_>>();
+				let presence = (pointer, selected_layers);
+				if presence != session.last_presence {
+					session.last_presence = presence.clone();
+					let (pointer, selected_layers) = presence;
+					send(
+						CollaborationPacket::Presence {
+							client: session.client,
+							pointer,
+							selected_layers,
+						},
+						responses,
+					);
+				}
+				return;
+			}
+		}
+
+		self.update_layout(documents, responses);
+	}
+
+	fn actions(&self) -> ActionList {
+		actions!(CollaborationMessage;)
+	}
+}
+
+fn send(packet: CollaborationPacket, responses: &mut VecDeque<Message>) {
+	match serde_json::to_string(&packet) {
+		Ok(packet) => responses.add(FrontendMessage::TriggerCollaborationSend { packet }),
+		Err(error) => error!("Failed to serialize a collaboration packet: {error}"),
+	}
+}
+
+/// The color of a collaborator's pointer and selection, as sRGB.
+fn collaborator_color(client: ClientId) -> (u8, u8, u8) {
+	COLLABORATOR_COLORS[(client % COLLABORATOR_COLORS.len() as u64) as usize]
+}
+
+/// Outlines the bounds of the layers a collaborator has selected and marks their pointer with their name, in their color.
+fn draw_collaborator(overlay_context: &mut OverlayContext, document: &DocumentMessageHandler, client: ClientId, collaborator: &Collaborator) {
+	let (red, green, blue) = collaborator_color(client);
+	let color = format!("#{red:02x}{green:02x}{blue:02x}");
+
+	for &node_id in &collaborator.selected_layers {
+		let Some([min, max]) = document.metadata().bounding_box_viewport(LayerNodeIdentifier::new_unchecked(node_id)) else {
+			continue;
+		};
+		let corners = [min, DVec2::new(max.x, min.y), max, DVec2::new(min.x, max.y)];
+		for index in 0..corners.len() {
+			overlay_context.line(corners[index], corners[(index + 1) % corners.len()], Some(&color), Some(2.));
+		}
+	}
+
+	if let Some(pointer) = collaborator.pointer {
+		let position = document.metadata().document_to_viewport.transform_point2(pointer);
+		overlay_context.circle(position, 4., Some(&color), Some(&color));
+		let transform = DAffine2::from_translation(position + DVec2::new(8., 8.));
+		overlay_context.text(&collaborator.name, "#ffffff", Some(&color), transform, 4., [Pivot::Start, Pivot::Start]);
+	}
+}
+
+impl CollaborationMessageHandler {
+	fn update_layout(&self, documents: &HashMap<DocumentId, DocumentMessageHandler>, responses: &mut VecDeque<Message>) {
+		responses.add(FrontendMessage::UpdateCollaborationState { open: self.collaboration_open });
+		if !self.collaboration_open {
+			return;
+		}
+
+		let joined = self.session.is_some();
+		let mut layout = vec![
+			LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new("Server").table_align(true).min_width(60).widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					TextInput::new(&self.server)
+						.tooltip("The URL of the collaboration server, ending with the name of the room to join")
+						.disabled(joined)
+						.on_update(|text_input: &TextInput| CollaborationMessage::UpdateServer { server: text_input.value.clone() }.into())
+						.widget_holder(),
+				],
+			},
+			LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new("Name").table_align(true).min_width(60).widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					TextInput::new(&self.name)
+						.tooltip("Shown to the others next to your pointer")
+						.disabled(joined)
+						.on_update(|text_input: &TextInput| CollaborationMessage::UpdateName { name: text_input.value.clone() }.into())
+						.widget_holder(),
+				],
+			},
+		];
+
+		let status = match &self.session {
+			None => "Join to edit the active document with everyone in the room".to_string(),
+			Some(Session { connected: false, .. }) => "Connecting…".to_string(),
+			Some(session) => {
+				let document_name = documents.get(&session.document_id).map(|document| document.name.as_str()).unwrap_or_default();
+				format!("Editing \"{document_name}\"")
+			}
+		};
+		layout.push(LayoutGroup::Row {
+			widgets: vec![
+				if joined {
+					TextButton::new("Leave").on_update(|_| CollaborationMessage::Leave.into()).widget_holder()
+				} else {
+					TextButton::new("Join")
+						.emphasized(true)
+						.tooltip("Merge the active document into the one shared in the room, then keep them in sync")
+						.on_update(|_| CollaborationMessage::Join.into())
+						.widget_holder()
+				},
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextLabel::new(status).italic(true).widget_holder(),
+			],
+		});
+
+		if let Some(session) = &self.session {
+			let mut collaborators = session.collaborators.iter().map(|(&client, collaborator)| (client, collaborator.name.as_str())).collect::<Vec<_>>();
+			collaborators.sort_by_key(|&(client, name)| (name, client));
+
+			let you = format!("{} (you)", self.name);
+			layout.extend(std::iter::once((session.client, you.as_str())).chain(collaborators).map(|(client, name)| {
+				let (red, green, blue) = collaborator_color(client);
+				LayoutGroup::Row {
+					widgets: vec![
+						ColorInput::new(FillChoice::Solid(Color::from_rgb8_srgb(red, green, blue).to_gamma_srgb()))
+							.allow_none(false)
+							.disabled(true)
+							.widget_holder(),
+						Separator::new(SeparatorType::Related).widget_holder(),
+						TextLabel::new(name).widget_holder(),
+					],
+				}
+			}));
+		}
+
+		responses.add(LayoutMessage::SendLayout {
+			layout: Layout::WidgetLayout(WidgetLayout { layout }),
+			layout_target: LayoutTarget::Collaboration,
+		});
+	}
+}
@@ -0,0 +1,9 @@
+mod collaboration_message;
+mod collaboration_message_handler;
+
+pub mod sync;
+
+#[doc(inline)]
+pub use collaboration_message::*;
+#[doc(inline)]
+pub use collaboration_message_handler::*;
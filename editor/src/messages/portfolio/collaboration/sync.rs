@@ -0,0 +1,488 @@
+//! Keeps the content of a document the same for everyone editing it together.
+//!
+//! Each collaborator holds a [`Replica`] of the document split into entries: the exports of the document network and each of its nodes (including the nested networks within them).
+//! Every change to an entry is stamped with a [`Timestamp`] and sent to the others, who keep whichever change has the latest timestamp,
+//! so everyone ends up with the same document no matter the order changes arrive in.
+//! Layers added to the same stack at once are the exception, since keeping only one of the changes to the stack would leave out the others' layers, so they're stacked together instead.
+
+use crate::messages::portfolio::document::utility_types::network_interface::{DocumentNodeMetadata, LayerPosition, NodeNetworkInterface, NodeTypePersistentMetadata};
+use crate::messages::prelude::*;
+use glam::DVec2;
+use graph_craft::document::{DocumentNode, NodeId, NodeInput};
+
+pub type ClientId = u64;
+
+/// The clock the content a collaborator joins with is stamped at, which is earlier than any change.
+const INITIAL_CLOCK: u64 = 0;
+
+/// Orders the changes made to an entry, first by a logical clock which every collaborator advances past the changes it has seen, then by who made them to break ties.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Timestamp {
+	pub clock: u64,
+	pub client: ClientId,
+}
+
+/// A part of the document which collaborators change independently of each other.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+pub enum SyncedKey {
+	Exports,
+	Node(NodeId),
+}
+
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum SyncedValue {
+	Exports(Vec<NodeInput>),
+	Node(Box<(DocumentNode, DocumentNodeMetadata)>),
+}
+
+impl SyncedValue {
+	fn inputs(&self) -> &[NodeInput] {
+		match self {
+			Self::Exports(exports) => exports,
+			Self::Node(node) => &node.0.inputs,
+		}
+	}
+
+	fn is_layer(&self) -> bool {
+		matches!(self, Self::Node(node) if node.1.persistent_metadata.is_layer())
+	}
+}
+
+/// A change to one entry of the document, where a value of `None` deletes it.
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct SyncOperation {
+	pub key: SyncedKey,
+	pub timestamp: Timestamp,
+	pub value: Option<SyncedValue>,
+}
+
+/// Sent between collaborators through the collaboration server, which passes each packet on to everyone else in the same room.
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type")]
+pub enum CollaborationPacket {
+	/// Introduces a collaborator to the others when joining, who reply with their own introduction.
+	Hello {
+		client: ClientId,
+		name: String,
+		reply: bool,
+	},
+	Goodbye {
+		client: ClientId,
+	},
+	Sync {
+		operations: Vec<SyncOperation>,
+	},
+	/// Where a collaborator's pointer is in the document, if it's over the canvas, and which layers they have selected.
+	Presence {
+		client: ClientId,
+		pointer: Option<DVec2>,
+		selected_layers: Vec<NodeId>,
+	},
+}
+
+/// One collaborator's copy of the content of a document.
+#[derive(Default, Clone, Debug)]
+pub struct Replica {
+	client: ClientId,
+	clock: u64,
+	entries: HashMap<SyncedKey, (Timestamp, Option<SyncedValue>)>,
+	/// The values which changes from collaborators have replaced since they were last applied to the document.
+	displaced: Vec<(SyncedKey, SyncedValue)>,
+}
+
+impl Replica {
+	/// Starts with the content of a document stamped at the initial clock, which only fills in the entries the others don't have, so joining merges the document into the one the others already have.
+	pub fn new(client: ClientId, content: HashMap<SyncedKey, SyncedValue>) -> Self {
+		let timestamp = Timestamp { clock: INITIAL_CLOCK, client };
+		let entries = content.into_iter().map(|(key, value)| (key, (timestamp, Some(value)))).collect();
+		Self {
+			client,
+			clock: INITIAL_CLOCK,
+			entries,
+			displaced: Vec::new(),
+		}
+	}
+
+	/// Stamps the entries still holding the content this collaborator joined with as changes, so they win over the content a newcomer joins with.
+	pub fn claim_initial_entries(&mut self) {
+		let mut initial_entries = self.entries.values_mut().filter(|(timestamp, _)| timestamp.clock == INITIAL_CLOCK).peekable();
+		if initial_entries.peek().is_none() {
+			return;
+		}
+
+		self.clock += 1;
+		let timestamp = Timestamp {
+			clock: self.clock,
+			client: self.client,
+		};
+		for (entry_timestamp, _) in initial_entries {
+			*entry_timestamp = timestamp;
+		}
+	}
+
+	/// Records changes made to the document by this collaborator, returning them stamped to be sent to the others.
+	pub fn record_local_changes(&mut self, changes: HashMap<SyncedKey, Option<SyncedValue>>) -> Vec<SyncOperation> {
+		changes
+			.into_iter()
+			.map(|(key, value)| {
+				self.clock += 1;
+				let timestamp = Timestamp {
+					clock: self.clock,
+					client: self.client,
+				};
+				self.entries.insert(key, (timestamp, value.clone()));
+				SyncOperation { key, timestamp, value }
+			})
+			.collect()
+	}
+
+	/// Merges a change made by a collaborator, returning whether it replaced what the replica had.
+	pub fn merge(&mut self, operation: SyncOperation) -> bool {
+		let SyncOperation { key, timestamp, value } = operation;
+		self.clock = self.clock.max(timestamp.clock);
+
+		// Content someone joined with only fills in what's missing, so a newcomer with a blank document can't replace what the others have, even when both are stamped at the initial clock
+		let newer = match self.entries.get(&key) {
+			None => true,
+			Some(_) if timestamp.clock == INITIAL_CLOCK => false,
+			Some((current, _)) => timestamp > *current,
+		};
+		if !newer {
+			return false;
+		}
+
+		if let Some((_, Some(previous))) = self.entries.insert(key, (timestamp, value)) {
+			self.displaced.push((key, previous));
+		}
+		true
+	}
+
+	/// Every entry of the replica, to bring a collaborator who just joined up to date.
+	pub fn operations(&self) -> Vec<SyncOperation> {
+		self.entries
+			.iter()
+			.map(|(&key, (timestamp, value))| SyncOperation {
+				key,
+				timestamp: *timestamp,
+				value: value.clone(),
+			})
+			.collect()
+	}
+
+	/// The changes which bring a document with the given content up to date with the replica.
+	pub fn changes_from(&self, content: &HashMap<SyncedKey, SyncedValue>) -> HashMap<SyncedKey, Option<SyncedValue>> {
+		self.entries
+			.iter()
+			.filter(|(key, (_, value))| value.as_ref() != content.get(key))
+			.map(|(&key, (_, value))| (key, value.clone()))
+			.collect()
+	}
+
+	/// The changes which bring a document with the given content up to date with the changes merged from collaborators since they were last applied.
+	/// Layers these changes leave out of their stacks are put back, which is also returned as operations to send the others.
+	pub fn changes_to_apply(&mut self, content: &HashMap<SyncedKey, SyncedValue>) -> (HashMap<SyncedKey, Option<SyncedValue>>, Vec<SyncOperation>) {
+		let mut changes = self.changes_from(content);
+
+		let restacked = restack_dropped_layers(content, &changes, std::mem::take(&mut self.displaced));
+		let operations = self.record_local_changes(restacked.clone());
+		changes.extend(restacked);
+
+		(changes, operations)
+	}
+}
+
+/// The changes which turn the `old` content of a document into the `new`.
+pub fn content_changes(old: &HashMap<SyncedKey, SyncedValue>, new: &HashMap<SyncedKey, SyncedValue>) -> HashMap<SyncedKey, Option<SyncedValue>> {
+	let removed = old.keys().filter(|key| !new.contains_key(key)).map(|&key| (key, None));
+	let changed = new.iter().filter(|(key, value)| old.get(key) != Some(value)).map(|(&key, value)| (key, Some(value.clone())));
+	changed.chain(removed).collect()
+}
+
+/// Splits the content of a document into the entries which are synced.
+pub fn document_content(network_interface: &NodeNetworkInterface) -> HashMap<SyncedKey, SyncedValue> {
+	let mut content = HashMap::new();
+	update_content(&mut content, network_interface);
+	content
+}
+
+/// Brings the content of a document up to date with the document, returning the changes made to it.
+/// Only the entries which changed are copied, so this is much cheaper than splitting the document up again when little has changed.
+pub fn update_content(content: &mut HashMap<SyncedKey, SyncedValue>, network_interface: &NodeNetworkInterface) -> HashMap<SyncedKey, Option<SyncedValue>> {
+	let (exports, nodes) = network_interface.synced_content();
+	let mut changes = HashMap::new();
+
+	if !matches!(content.get(&SyncedKey::Exports), Some(SyncedValue::Exports(current)) if current == exports) {
+		changes.insert(SyncedKey::Exports, Some(SyncedValue::Exports(exports.to_vec())));
+	}
+	let mut present = HashSet::new();
+	for (id, node, metadata) in nodes {
+		let key = SyncedKey::Node(id);
+		present.insert(key);
+		if !matches!(content.get(&key), Some(SyncedValue::Node(current)) if current.0 == *node && current.1 == *metadata) {
+			changes.insert(key, Some(SyncedValue::Node(Box::new((node.clone(), metadata.clone())))));
+		}
+	}
+	changes.extend(content.keys().filter(|&key| *key != SyncedKey::Exports && !present.contains(key)).map(|&key| (key, None)));
+
+	for (key, value) in &changes {
+		match value {
+			Some(value) => content.insert(*key, value.clone()),
+			None => content.remove(key),
+		};
+	}
+	changes
+}
+
+/// Copies the content of a document with changes to its entries.
+pub fn apply_changes(network_interface: &NodeNetworkInterface, changes: HashMap<SyncedKey, Option<SyncedValue>>) -> NodeNetworkInterface {
+	let mut exports = None;
+	let mut nodes = HashMap::new();
+	for (key, value) in changes {
+		match (key, value) {
+			(SyncedKey::Exports, Some(SyncedValue::Exports(new_exports))) => exports = Some(new_exports),
+			(SyncedKey::Node(id), Some(SyncedValue::Node(node))) => _ = nodes.insert(id, Some(*node)),
+			(SyncedKey::Node(id), None) => _ = nodes.insert(id, None),
+			(key, value) => warn!("Ignored the mismatched change {value:?} to {key:?}"),
+		}
+	}
+
+	network_interface.with_synced_changes(exports, nodes)
+}
+
+/// The value of an entry once the changes to it have been made.
+fn merged_value<'a>(content: &'a HashMap<SyncedKey, SyncedValue>, changes: &'a HashMap<SyncedKey, Option<SyncedValue>>, key: &SyncedKey) -> Option<&'a SyncedValue> {
+	match changes.get(key) {
+		Some(value) => value.as_ref(),
+		None => content.get(key),
+	}
+}
+
+/// Puts back into their stacks the layers which changes from collaborators have left out of them, given the values those changes `displaced`.
+///
+/// When two collaborators add a layer to the same place in a stack at once, both change the input of whatever the new layer feeds into, and only one of the changes is kept.
+/// The layer the other change fed in is then found from the value it displaced, and stacked beneath the one which was kept, above whatever both were added on top of.
+fn restack_dropped_layers(
+	content: &HashMap<SyncedKey, SyncedValue>,
+	changes: &HashMap<SyncedKey, Option<SyncedValue>>,
+	displaced: Vec<(SyncedKey, SyncedValue)>,
+) -> HashMap<SyncedKey, Option<SyncedValue>> {
+	let mut restacked = HashMap::new();
+	if displaced.is_empty() {
+		return restacked;
+	}
+
+	let keys = content.keys().chain(changes.keys()).copied().collect::<HashSet<_>>();
+	let fed_from = keys
+		.iter()
+		.filter_map(|key| merged_value(content, changes, key))
+		.flat_map(|value| value.inputs().iter().filter_map(NodeInput::as_node))
+		.collect::<HashSet<_>>();
+
+	// Pairs each layer left out of its stack with the node which now feeds in where it did, which is where its stack continues
+	let mut dropped = displaced
+		.iter()
+		.filter_map(|(key, previous)| Some((previous, merged_value(content, changes, key)?)))
+		.flat_map(|(previous, current)| {
+			previous
+				.inputs()
+				.iter()
+				.zip(current.inputs())
+				.filter_map(|(previous, current)| Some((previous.as_node()?, current.as_node()?)))
+		})
+		.filter(|&(layer, kept)| layer != kept && !fed_from.contains(&layer) && merged_value(content, changes, &SyncedKey::Node(layer)).is_some_and(SyncedValue::is_layer))
+		.collect::<Vec<_>>();
+	dropped.sort_by_key(|&(layer, _)| layer);
+	dropped.dedup_by_key(|(layer, _)| *layer);
+
+	let value = |restacked: &HashMap<SyncedKey, Option<SyncedValue>>, id| match restacked.get(&SyncedKey::Node(id)) {
+		Some(value) => value.clone(),
+		None => merged_value(content, changes, &SyncedKey::Node(id)).cloned(),
+	};
+	for (layer, kept) in dropped {
+		let Some(SyncedValue::Node(mut dropped_layer)) = value(&restacked, layer) else { continue };
+		let below = dropped_layer.0.inputs.first().and_then(NodeInput::as_node);
+
+		// Go down the stack from the kept layer to the one which feeds from the same node as the dropped layer did, and feed the dropped layer into it instead
+		let mut current = kept;
+		let mut visited = HashSet::new();
+		while visited.insert(current) {
+			let Some(SyncedValue::Node(mut above)) = value(&restacked, current).filter(SyncedValue::is_layer) else {
+				break;
+			};
+			let Some(above_input) = above.0.inputs.first_mut() else { break };
+
+			if above_input.as_node() == below {
+				*above_input = NodeInput::node(layer, 0);
+				restacked.insert(SyncedKey::Node(current), Some(SyncedValue::Node(above)));
+
+				if let NodeTypePersistentMetadata::Layer(layer_metadata) = &mut dropped_layer.1.persistent_metadata.node_type_metadata {
+					layer_metadata.position = LayerPosition::Stack(0);
+				}
+				restacked.insert(SyncedKey::Node(layer), Some(SyncedValue::Node(dropped_layer)));
+				break;
+			}
+
+			let Some(next) = above_input.as_node() else { break };
+			current = next;
+		}
+	}
+
+	restacked
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use glam::IVec2;
+	use graph_craft::document::value::TaggedValue;
+
+	fn node(name: &str) -> SyncedValue {
+		let mut metadata = DocumentNodeMetadata::default();
+		metadata.persistent_metadata.display_name = name.to_string();
+		SyncedValue::Node(Box::new((DocumentNode::default(), metadata)))
+	}
+
+	/// A layer stacked on top of the layer `below`, or at the bottom of its stack.
+	fn layer(below: Option<u64>) -> SyncedValue {
+		let input = below.map_or(NodeInput::value(TaggedValue::None, true), |below| NodeInput::node(NodeId(below), 0));
+		let mut metadata = DocumentNodeMetadata::default();
+		metadata.persistent_metadata.node_type_metadata = NodeTypePersistentMetadata::layer(IVec2::ZERO);
+		SyncedValue::Node(Box::new((
+			DocumentNode {
+				inputs: vec![input],
+				..Default::default()
+			},
+			metadata,
+		)))
+	}
+
+	fn exports(top: u64) -> SyncedValue {
+		SyncedValue::Exports(vec![NodeInput::node(NodeId(top), 0)])
+	}
+
+	/// Applies the changes merged into a replica to the content of its document, as is done between transactions, returning the operations to send the others.
+	fn apply_merged_changes(replica: &mut Replica, content: &mut HashMap<SyncedKey, SyncedValue>) -> Vec<SyncOperation> {
+		let (changes, operations) = replica.changes_to_apply(content);
+		for (key, value) in changes {
+			match value {
+				Some(value) => content.insert(key, value),
+				None => content.remove(&key),
+			};
+		}
+		operations
+	}
+
+	/// The layers of the stack fed into the exports, from top to bottom.
+	fn stack(content: &HashMap<SyncedKey, SyncedValue>) -> Vec<u64> {
+		let mut layers = Vec::new();
+		let mut next = content.get(&SyncedKey::Exports).and_then(|exports| exports.inputs().first()?.as_node());
+		while let Some(id) = next {
+			layers.push(id.0);
+			next = content.get(&SyncedKey::Node(id)).and_then(|layer| layer.inputs().first()?.as_node());
+		}
+		layers
+	}
+
+	#[test]
+	fn replicas_converge_whatever_order_changes_arrive_in() {
+		let content = HashMap::from([(SyncedKey::Node(NodeId(1)), node("Shared"))]);
+		let mut first = Replica::new(1, content.clone());
+		let mut second = Replica::new(2, content.clone());
+
+		// Both rename the same node, and the second also adds a node
+		let first_content = HashMap::from([(SyncedKey::Node(NodeId(1)), node("First"))]);
+		let second_content = HashMap::from([(SyncedKey::Node(NodeId(1)), node("Second")), (SyncedKey::Node(NodeId(2)), node("New"))]);
+		let first_operations = first.record_local_changes(content_changes(&content, &first_content));
+		let second_operations = second.record_local_changes(content_changes(&content, &second_content));
+		assert_eq!(first_operations.len(), 1);
+		assert_eq!(second_operations.len(), 2);
+
+		for operation in second_operations {
+			first.merge(operation);
+		}
+		for operation in first_operations {
+			second.merge(operation);
+		}
+
+		// The second's rename is stamped at least as late as the first's, with ties broken by client, so both keep it
+		let expected = HashMap::from([(SyncedKey::Node(NodeId(1)), node("Second")), (SyncedKey::Node(NodeId(2)), node("New"))]);
+		assert!(first.changes_from(&expected).is_empty());
+		assert!(second.changes_from(&expected).is_empty());
+	}
+
+	#[test]
+	fn deletions_are_synced_and_later_changes_win() {
+		let mut first = Replica::new(1, HashMap::from([(SyncedKey::Node(NodeId(1)), node("Shared"))]));
+		let mut second = Replica::new(2, HashMap::new());
+		for operation in first.operations() {
+			assert!(second.merge(operation));
+		}
+
+		let deletion = second.record_local_changes(HashMap::from([(SyncedKey::Node(NodeId(1)), None)]));
+		assert_eq!(deletion[0].value, None);
+		assert!(first.merge(deletion[0].clone()));
+		assert_eq!(
+			first.changes_from(&HashMap::from([(SyncedKey::Node(NodeId(1)), node("Shared"))])).get(&SyncedKey::Node(NodeId(1))),
+			Some(&None)
+		);
+
+		// A change made after seeing the deletion restores the node, and merging the stale deletion again doesn't undo that
+		let restored = first.record_local_changes(HashMap::from([(SyncedKey::Node(NodeId(1)), node("Restored"))]));
+		assert!(restored[0].timestamp > deletion[0].timestamp);
+		assert!(second.merge(restored[0].clone()));
+		assert!(!second.merge(deletion[0].clone()));
+	}
+
+	#[test]
+	fn a_newcomers_initial_content_never_replaces_the_rooms() {
+		let room_content = HashMap::from([(SyncedKey::Exports, exports(1)), (SyncedKey::Node(NodeId(1)), layer(None))]);
+		let blank_content = HashMap::from([(SyncedKey::Exports, SyncedValue::Exports(vec![NodeInput::value(TaggedValue::None, true)]))]);
+		let mut room = Replica::new(1, room_content.clone());
+		let mut newcomer = Replica::new(2, blank_content.clone());
+
+		// The newcomer's initial content arrives first, and is stamped at the same clock as the room's, by a client which wins ties
+		for operation in newcomer.operations() {
+			room.merge(operation);
+		}
+		room.claim_initial_entries();
+		for operation in room.operations() {
+			newcomer.merge(operation);
+		}
+
+		assert!(room.changes_from(&room_content).is_empty());
+		assert!(newcomer.changes_from(&room_content).is_empty());
+	}
+
+	#[test]
+	fn layers_added_to_the_same_stack_at_once_are_both_kept() {
+		let content = HashMap::from([(SyncedKey::Exports, exports(1)), (SyncedKey::Node(NodeId(1)), layer(None))]);
+		let mut first = Replica::new(1, content.clone());
+		let mut second = Replica::new(2, content.clone());
+
+		// Both add a layer to the top of the stack
+		let mut first_content = content.clone();
+		first_content.extend([(SyncedKey::Exports, exports(2)), (SyncedKey::Node(NodeId(2)), layer(Some(1)))]);
+		let mut second_content = content.clone();
+		second_content.extend([(SyncedKey::Exports, exports(3)), (SyncedKey::Node(NodeId(3)), layer(Some(1)))]);
+		let mut first_operations = first.record_local_changes(content_changes(&content, &first_content));
+		let mut second_operations = second.record_local_changes(content_changes(&content, &second_content));
+
+		// Whichever change to the exports loses, its layer is stacked beneath the other's, and that's sent on in turn
+		while !first_operations.is_empty() || !second_operations.is_empty() {
+			for operation in std::mem::take(&mut second_operations) {
+				first.merge(operation);
+			}
+			for operation in std::mem::take(&mut first_operations) {
+				second.merge(operation);
+			}
+			first_operations = apply_merged_changes(&mut first, &mut first_content);
+			second_operations = apply_merged_changes(&mut second, &mut second_content);
+		}
+
+		assert_eq!(first_content, second_content);
+		let layers = stack(&first_content);
+		assert_eq!(layers.len(), 3);
+		assert!(layers.contains(&2) && layers.contains(&3));
+		assert_eq!(layers.last(), Some(&1));
+	}
+}
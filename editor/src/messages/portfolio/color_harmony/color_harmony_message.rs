@@ -0,0 +1,27 @@
+use crate::messages::prelude::*;
+use graphene_core::Color;
+
+/// The Color Harmony panel generates color schemes from the primary working color.
+#[impl_message(Message, PortfolioMessage, ColorHarmony)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum ColorHarmonyMessage {
+	ToggleOpen,
+
+	UpdatePrimaryColor { color: Color },
+
+	SetScheme { scheme: HarmonyScheme },
+}
+
+/// A rule for picking colors which go together, by their hues' positions around the color wheel.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug, serde::Serialize, serde::Deserialize)]
+pub enum HarmonyScheme {
+	/// The color and the one opposite it.
+	#[default]
+	Complementary,
+	/// The color and its neighbors to either side.
+	Analogous,
+	/// Three colors evenly spaced around the wheel.
+	Triadic,
+	/// Two pairs of complementary colors.
+	Tetradic,
+}
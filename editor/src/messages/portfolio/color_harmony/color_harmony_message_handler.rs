@@ -0,0 +1,154 @@
+use super::HarmonyScheme;
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::utility_types::recolor;
+use crate::messages::prelude::*;
+use graphene_core::Color;
+use graphene_core::vector::style::FillChoice;
+
+/// The Color Harmony panel generates color schemes from the primary working color.
+#[derive(Default, Debug, Clone)]
+pub struct ColorHarmonyMessageHandler {
+	/// Sets whether or not the Color Harmony panel is drawn.
+	pub color_harmony_open: bool,
+	/// The primary working color the scheme is generated from, in linear space.
+	primary_color: Color,
+	scheme: HarmonyScheme,
+}
+
+impl MessageHandler<ColorHarmonyMessage, ()> for ColorHarmonyMessageHandler {
+	fn process_message(&mut self, message: ColorHarmonyMessage, responses: &mut VecDeque<Message>, _data: ()) {
+		match message {
+			ColorHarmonyMessage::ToggleOpen => {
+				self.color_harmony_open = !self.color_harmony_open;
+				// Update checked UI state for open
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			ColorHarmonyMessage::UpdatePrimaryColor { color } => {
+				if self.primary_color == color {
+					return;
+				}
+				self.primary_color = color;
+			}
+			ColorHarmonyMessage::SetScheme { scheme } => {
+				self.scheme = scheme;
+			}
+		}
+
+		self.update_layout(responses);
+	}
+
+	fn actions(&self) -> ActionList {
+		actions!(ColorHarmonyMessage;)
+	}
+}
+
+impl ColorHarmonyMessageHandler {
+	fn update_layout(&self, responses: &mut VecDeque<Message>) {
+		responses.add(FrontendMessage::UpdateColorHarmonyState { open: self.color_harmony_open });
+		if !self.color_harmony_open {
+			return;
+		}
+
+		let entries = [HarmonyScheme::Complementary, HarmonyScheme::Analogous, HarmonyScheme::Triadic, HarmonyScheme::Tetradic]
+			.into_iter()
+			.map(|scheme| {
+				RadioEntryData::new(format!("{scheme:?}"))
+					.label(format!("{scheme:?}"))
+					.on_update(move |_| ColorHarmonyMessage::SetScheme { scheme }.into())
+			})
+			.collect();
+		let mut layout = vec![LayoutGroup::Row {
+			widgets: vec![RadioInput::new(entries).selected_index(Some(self.scheme as u32)).widget_holder()],
+		}];
+
+		let colors = self.scheme.colors(self.primary_color);
+		layout.extend(colors.iter().map(|&color| LayoutGroup::Row {
+			widgets: vec![
+				ColorInput::new(FillChoice::Solid(color.to_gamma_srgb())).allow_none(false).disabled(true).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextLabel::new(format!("#{}", color.to_rgb_hex_srgb())).min_width(60).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				IconButton::new("WorkingColorsPrimary", 24)
+					.tooltip("Make this the primary working color")
+					.on_update(move |_| ToolMessage::SelectPrimaryColor { color }.into())
+					.widget_holder(),
+				IconButton::new("Add", 24)
+					.tooltip("Add this color to the document's swatches")
+					.on_update(move |_| DocumentMessage::AddSwatches { colors: vec![color] }.into())
+					.widget_holder(),
+				IconButton::new("GeneralFillTool", 24)
+					.tooltip("Fill the selected layers with this color")
+					.on_update(move |_| DocumentMessage::FillSelectedLayers { colors: vec![color] }.into())
+					.widget_holder(),
+			],
+		}));
+
+		let swatches_colors = colors.clone();
+		layout.push(LayoutGroup::Row {
+			widgets: vec![
+				TextButton::new("Add All as Swatches")
+					.tooltip("Add every color of the scheme to the document's swatches")
+					.on_update(move |_| DocumentMessage::AddSwatches { colors: swatches_colors.clone() }.into())
+					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				TextButton::new("Fill Selection")
+					.tooltip("Fill the selected layers with the colors of the scheme, taking turns in layer order")
+					.on_update(move |_| DocumentMessage::FillSelectedLayers { colors: colors.clone() }.into())
+					.widget_holder(),
+			],
+		});
+
+		responses.add(LayoutMessage::SendLayout {
+			layout: Layout::WidgetLayout(WidgetLayout { layout }),
+			layout_target: LayoutTarget::ColorHarmony,
+		});
+	}
+}
+
+impl HarmonyScheme {
+	/// The positions of the scheme's hues around the color wheel in degrees, relative to the color it's generated from.
+	pub fn hue_offsets(self) -> &'static [f64] {
+		match self {
+			HarmonyScheme::Complementary => &[0., 180.],
+			HarmonyScheme::Analogous => &[-30., 0., 30.],
+			HarmonyScheme::Triadic => &[0., 120., 240.],
+			HarmonyScheme::Tetradic => &[0., 60., 180., 240.],
+		}
+	}
+
+	/// The colors of the scheme generated from the given linear space color, also in linear space.
+	pub fn colors(self, base: Color) -> Vec<Color> {
+		let base = base.to_gamma_srgb();
+		self.hue_offsets().iter().map(|&degrees| recolor::shift_hue(base, degrees).to_linear_srgb()).collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn schemes_keep_the_base_color() {
+		let base = Color::from_rgbaf32_unchecked(0.8, 0.2, 0.1, 1.);
+
+		for scheme in [HarmonyScheme::Complementary, HarmonyScheme::Analogous, HarmonyScheme::Triadic, HarmonyScheme::Tetradic] {
+			let colors = scheme.colors(base);
+			assert_eq!(colors.len(), scheme.hue_offsets().len());
+			assert!(
+				colors
+					.iter()
+					.any(|color| (color.r() - base.r()).abs() < 1e-4 && (color.g() - base.g()).abs() < 1e-4 && (color.b() - base.b()).abs() < 1e-4)
+			);
+		}
+	}
+
+	#[test]
+	fn complementary_color_is_opposite() {
+		let red = Color::from_rgbaf32_unchecked(1., 0., 0., 1.);
+		let complement = HarmonyScheme::Complementary.colors(red)[1];
+
+		assert!(complement.r().abs() < 1e-4);
+		assert!((complement.g() - 1.).abs() < 1e-4);
+		assert!((complement.b() - 1.).abs() < 1e-4);
+	}
+}
@@ -0,0 +1,7 @@
+mod color_harmony_message;
+mod color_harmony_message_handler;
+
+#[doc(inline)]
+pub use color_harmony_message::*;
+#[doc(inline)]
+pub use color_harmony_message_handler::*;
@@ -0,0 +1,39 @@
+use crate::messages::portfolio::collaboration::sync::SyncedKey;
+use crate::messages::portfolio::document::overlays::utility_types::OverlayContext;
+use crate::messages::prelude::*;
+
+/// Compares two versions of a document, such as two revisions of a file kept in version control, and merges the chosen differences from one into the other.
+#[impl_message(Message, PortfolioMessage, Compare)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum CompareMessage {
+	ToggleOpen,
+
+	// Overlays
+	Overlays(OverlayContext),
+
+	DocumentClosed {
+		document_id: DocumentId,
+	},
+	/// Sets the document which the differences are applied to.
+	SetDocument {
+		document_id: DocumentId,
+	},
+	/// Sets the other version of the document, which the differences are taken from.
+	SetOtherDocument {
+		document_id: DocumentId,
+	},
+	/// Finds the differences between the two versions again, after either has been edited.
+	Refresh,
+	/// Shows the other version in the split view beside the document, which is made active.
+	ShowSideBySide,
+
+	ToggleSelected {
+		key: SyncedKey,
+	},
+	SelectAll {
+		selected: bool,
+	},
+	/// Changes the document to match the other version in the selected differences, as a single step which can be undone.
+	ApplySelected,
+	SendLayout,
+}
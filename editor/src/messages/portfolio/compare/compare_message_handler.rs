@@ -0,0 +1,321 @@
+use super::diff::{self, ChangeKind, Difference};
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::collaboration::sync::{self, SyncedKey};
+use crate::messages::portfolio::document::overlays::utility_types::{OverlayContext, OverlayProvider};
+use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
+use crate::messages::prelude::*;
+use glam::DVec2;
+
+const COMPARE_OVERLAY_PROVIDER: OverlayProvider = |context| CompareMessage::Overlays(context).into();
+
+/// The colors outlining the layers which are added, removed, or changed in the other version.
+const ADDED_COLOR: &str = "#4cb84c";
+const REMOVED_COLOR: &str = "#e64a4a";
+const CHANGED_COLOR: &str = "#e69a2e";
+
+pub struct CompareMessageData<'a> {
+	pub documents: &'a mut HashMap<DocumentId, DocumentMessageHandler>,
+	pub document_ids: &'a VecDeque<DocumentId>,
+	pub active_document_id: Option<DocumentId>,
+	pub split_view_open: bool,
+	pub ipp: &'a InputPreprocessorMessageHandler,
+	pub preferences: &'a PreferencesMessageHandler,
+}
+
+/// Compares a document with another version of it which is also open, listing the layers and nodes which differ and merging those chosen into the document.
+#[derive(Debug, Clone, Default)]
+pub struct CompareMessageHandler {
+	/// Sets whether or not the Compare panel is drawn.
+	pub compare_open: bool,
+	document_id: Option<DocumentId>,
+	other_document_id: Option<DocumentId>,
+	/// The differences found when the versions were last compared.
+	differences: Vec<Difference>,
+	/// The differences chosen to be applied to the document.
+	selected: HashSet<SyncedKey>,
+}
+
+impl MessageHandler<CompareMessage, CompareMessageData<'_>> for CompareMessageHandler {
+	fn process_message(&mut self, message: CompareMessage, responses: &mut VecDeque<Message>, data: CompareMessageData) {
+		let CompareMessageData {
+			documents,
+			document_ids,
+			active_document_id,
+			split_view_open,
+			ipp,
+			preferences,
+		} = data;
+
+		match message {
+			CompareMessage::ToggleOpen => {
+				self.compare_open = !self.compare_open;
+				if self.compare_open {
+					self.document_id = self.document_id.or(active_document_id);
+					responses.add(OverlaysMessage::AddProvider(COMPARE_OVERLAY_PROVIDER));
+				} else {
+					responses.add(OverlaysMessage::RemoveProvider(COMPARE_OVERLAY_PROVIDER));
+				}
+				self.refresh(documents, responses);
+				// Update checked UI state for open
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			CompareMessage::Overlays(mut overlay_context) => {
+				let Some(active_document_id) = active_document_id else { return };
+				let Some(document) = documents.get(&active_document_id) else { return };
+
+				// Each version outlines the layers it has which differ from the other version
+				let kinds: &[ChangeKind] = if Some(active_document_id) == self.document_id {
+					&[ChangeKind::Removed, ChangeKind::Changed]
+				} else if Some(active_document_id) == self.other_document_id {
+					&[ChangeKind::Added, ChangeKind::Changed]
+				} else {
+					return;
+				};
+				for difference in self.differences.iter().filter(|difference| difference.is_layer && kinds.contains(&difference.kind)) {
+					let SyncedKey::Node(node_id) = difference.key else { continue };
+					let Some([min, max]) = document.metadata().bounding_box_viewport(LayerNodeIdentifier::new_unchecked(node_id)) else {
+						continue;
+					};
+					outline(&mut overlay_context, min, max, difference_color(difference.kind));
+				}
+				return;
+			}
+			CompareMessage::DocumentClosed { document_id } => {
+				if self.document_id == Some(document_id) {
+					self.document_id = None;
+				}
+				if self.other_document_id == Some(document_id) {
+					self.other_document_id = None;
+				}
+				self.refresh(documents, responses);
+			}
+			CompareMessage::SetDocument { document_id } => {
+				self.document_id = Some(document_id);
+				if self.other_document_id == Some(document_id) {
+					self.other_document_id = None;
+				}
+				self.selected.clear();
+				self.refresh(documents, responses);
+			}
+			CompareMessage::SetOtherDocument { document_id } => {
+				self.other_document_id = Some(document_id);
+				if self.document_id == Some(document_id) {
+					self.document_id = None;
+				}
+				self.selected.clear();
+				self.refresh(documents, responses);
+			}
+			CompareMessage::Refresh => {
+				self.refresh(documents, responses);
+			}
+			CompareMessage::ShowSideBySide => {
+				let (Some(document_id), Some(other_document_id)) = (self.document_id, self.other_document_id) else {
+					return;
+				};
+
+				responses.add(PortfolioMessage::SelectDocument { document_id });
+				if !split_view_open {
+					responses.add(SplitViewMessage::ToggleOpen);
+				}
+				responses.add(SplitViewMessage::SetDocument { document_id: Some(other_document_id) });
+				return;
+			}
+			CompareMessage::ToggleSelected { key } => {
+				if !self.selected.remove(&key) {
+					self.selected.insert(key);
+				}
+			}
+			CompareMessage::SelectAll { selected } => {
+				self.selected = if selected {
+					self.differences.iter().map(|difference| difference.key).collect()
+				} else {
+					HashSet::new()
+				};
+			}
+			CompareMessage::ApplySelected => {
+				let (Some(document_id), Some(other_document_id)) = (self.document_id, self.other_document_id) else {
+					return;
+				};
+				let (Some(document), Some(other_document)) = (documents.get(&document_id), documents.get(&other_document_id)) else {
+					return;
+				};
+
+				let changes = diff::changes_to_apply(&document.network_interface, &other_document.network_interface, &self.selected);
+				if changes.is_empty() {
+					return;
+				}
+				let network_interface = sync::apply_changes(&document.network_interface, changes);
+
+				let Some(document) = documents.get_mut(&document_id) else { return };
+				document.apply_merged_changes(network_interface, ipp, preferences.undo_history_length, responses);
+
+				self.selected.clear();
+				self.refresh(documents, responses);
+			}
+			CompareMessage::SendLayout => {}
+		}
+
+		self.update_layout(documents, document_ids, responses);
+	}
+
+	fn actions(&self) -> ActionList {
+		actions!(CompareMessage;)
+	}
+}
+
+fn difference_color(kind: ChangeKind) -> &'static str {
+	match kind {
+		ChangeKind::Added => ADDED_COLOR,
+		ChangeKind::Removed => REMOVED_COLOR,
+		ChangeKind::Changed => CHANGED_COLOR,
+	}
+}
+
+fn outline(overlay_context: &mut OverlayContext, min: DVec2, max: DVec2, color: &str) {
+	let corners = [min, DVec2::new(max.x, min.y), max, DVec2::new(min.x, max.y)];
+	for index in 0..corners.len() {
+		overlay_context.line(corners[index], corners[(index + 1) % corners.len()], Some(color), Some(2.));
+	}
+}
+
+impl CompareMessageHandler {
+	/// Compares the versions again, keeping the selection of the differences which remain.
+	fn refresh(&mut self, documents: &HashMap<DocumentId, DocumentMessageHandler>, responses: &mut VecDeque<Message>) {
+		let document = self.document_id.and_then(|document_id| documents.get(&document_id));
+		let other_document = self.other_document_id.and_then(|document_id| documents.get(&document_id));
+		self.differences = match (document, other_document) {
+			(Some(document), Some(other_document)) if self.compare_open => diff::compare(&document.network_interface, &other_document.network_interface),
+			_ => Vec::new(),
+		};
+
+		let differences = &self.differences;
+		self.selected.retain(|key| differences.iter().any(|difference| difference.key == *key));
+
+		responses.add(OverlaysMessage::Draw);
+	}
+
+	fn update_layout(&self, documents: &HashMap<DocumentId, DocumentMessageHandler>, document_ids: &VecDeque<DocumentId>, responses: &mut VecDeque<Message>) {
+		responses.add(FrontendMessage::UpdateCompareState { open: self.compare_open });
+		if !self.compare_open {
+			return;
+		}
+
+		let open_documents = document_ids.iter().filter_map(|&document_id| Some((document_id, documents.get(&document_id)?))).collect::<Vec<_>>();
+		let document_dropdown = |selected: Option<DocumentId>, on_update: fn(DocumentId) -> Message| {
+			let entries = open_documents
+				.iter()
+				.map(|&(document_id, document)| MenuListEntry::new(document_id.0.to_string()).label(document.name.clone()).on_update(move |_| on_update(document_id)))
+				.collect();
+			let selected_index = selected.and_then(|selected| open_documents.iter().position(|&(document_id, _)| document_id == selected));
+			DropdownInput::new(vec![entries]).selected_index(selected_index.map(|index| index as u32))
+		};
+
+		let mut layout = vec![
+			LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new("Document").table_align(true).min_width(100).widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					document_dropdown(self.document_id, |document_id| CompareMessage::SetDocument { document_id }.into())
+						.tooltip("The version of the document which the selected differences are applied to")
+						.widget_holder(),
+				],
+			},
+			LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new("Other Version").table_align(true).min_width(100).widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					document_dropdown(self.other_document_id, |document_id| CompareMessage::SetOtherDocument { document_id }.into())
+						.tooltip("The version of the document which the differences are taken from")
+						.widget_holder(),
+					Separator::new(SeparatorType::Related).widget_holder(),
+					IconButton::new("Folder", 24)
+						.tooltip("Open another version of the document from a file")
+						.on_update(|_| PortfolioMessage::OpenDocument.into())
+						.widget_holder(),
+				],
+			},
+		];
+
+		let comparing = self.document_id.is_some() && self.other_document_id.is_some();
+		let count = |kind: ChangeKind| self.differences.iter().filter(|difference| difference.kind == kind).count();
+		let summary = if !comparing {
+			"Choose two open documents to compare".to_string()
+		} else if self.differences.is_empty() {
+			"The versions are the same".to_string()
+		} else {
+			format!("{} added, {} removed, {} changed", count(ChangeKind::Added), count(ChangeKind::Removed), count(ChangeKind::Changed))
+		};
+		layout.push(LayoutGroup::Row {
+			widgets: vec![
+				IconButton::new("Reload", 24)
+					.tooltip("Compare the versions again after editing either of them")
+					.disabled(!comparing)
+					.on_update(|_| CompareMessage::Refresh.into())
+					.widget_holder(),
+				TextButton::new("Side by Side")
+					.tooltip("Show the other version in the split view beside the document")
+					.disabled(!comparing)
+					.on_update(|_| CompareMessage::ShowSideBySide.into())
+					.widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextLabel::new(summary).italic(true).widget_holder(),
+			],
+		});
+
+		if !self.differences.is_empty() {
+			self.push_differences(&mut layout);
+		}
+
+		responses.add(LayoutMessage::SendLayout {
+			layout: Layout::WidgetLayout(WidgetLayout { layout }),
+			layout_target: LayoutTarget::Compare,
+		});
+	}
+
+	/// Lists the differences side by side, with what's in this version beside what's in the other version, and the controls to apply them.
+	fn push_differences(&self, layout: &mut Vec<LayoutGroup>) {
+		layout.push(LayoutGroup::Row {
+			widgets: vec![
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextLabel::new("This Version").bold(true).min_width(140).widget_holder(),
+				TextLabel::new("Other Version").bold(true).min_width(140).widget_holder(),
+			],
+		});
+		layout.extend(self.differences.iter().map(|difference| {
+			let key = difference.key;
+			let details = match difference.kind {
+				ChangeKind::Added => "Added".to_string(),
+				ChangeKind::Removed => "Removed".to_string(),
+				ChangeKind::Changed => format!("Changed: {}", difference.aspects.join(", ")),
+			};
+			LayoutGroup::Row {
+				widgets: vec![
+					CheckboxInput::new(self.selected.contains(&key))
+						.tooltip("Apply this difference to the document")
+						.on_update(move |_| CompareMessage::ToggleSelected { key }.into())
+						.widget_holder(),
+					Separator::new(SeparatorType::Related).widget_holder(),
+					IconLabel::new(if difference.is_layer { "Layer" } else { "Node" }).widget_holder(),
+					TextLabel::new(difference.name.as_deref().unwrap_or("—")).min_width(120).widget_holder(),
+					TextLabel::new(difference.other_name.as_deref().unwrap_or("—")).min_width(140).widget_holder(),
+					TextLabel::new(details).italic(true).widget_holder(),
+				],
+			}
+		}));
+
+		let all_selected = self.selected.len() == self.differences.len();
+		layout.push(LayoutGroup::Row {
+			widgets: vec![
+				TextButton::new(if all_selected { "Select None" } else { "Select All" })
+					.on_update(move |_| CompareMessage::SelectAll { selected: !all_selected }.into())
+					.widget_holder(),
+				TextButton::new("Apply Selected")
+					.emphasized(true)
+					.tooltip("Change the document to match the other version in the selected differences")
+					.disabled(self.selected.is_empty())
+					.on_update(|_| CompareMessage::ApplySelected.into())
+					.widget_holder(),
+			],
+		});
+	}
+}
@@ -0,0 +1,203 @@
+//! Finds the differences between two versions of a document.
+//!
+//! Versions are compared entry by entry, where the entries are the same as those synced by collaboration: the exports of the document network and each of its nodes,
+//! so a node's nested network is compared as a whole. Layers which have moved within the layer tree are found from the structure of each version.
+
+use crate::messages::portfolio::collaboration::sync::{self, SyncedKey, SyncedValue};
+use crate::messages::portfolio::document::utility_types::document_metadata::{DocumentMetadata, LayerNodeIdentifier};
+use crate::messages::portfolio::document::utility_types::network_interface::{DocumentNodeMetadata, NodeNetworkInterface};
+use crate::messages::prelude::*;
+use graph_craft::document::{DocumentNode, NodeId};
+
+/// How an entry differs in the other version of the document.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum ChangeKind {
+	/// Only in the other version.
+	Added,
+	/// Only in this version.
+	Removed,
+	/// In both versions, but not the same.
+	Changed,
+}
+
+/// A part of the document which isn't the same in the two versions.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Difference {
+	pub key: SyncedKey,
+	pub kind: ChangeKind,
+	/// The name of the entry in this version, if it's in this version.
+	pub name: Option<String>,
+	/// The name of the entry in the other version, if it's in the other version.
+	pub other_name: Option<String>,
+	pub is_layer: bool,
+	/// What has changed about an entry which is in both versions.
+	pub aspects: Vec<&'static str>,
+}
+
+/// The differences between a document and another version of it, with the layers listed before the other nodes.
+pub fn compare(network_interface: &NodeNetworkInterface, other_network_interface: &NodeNetworkInterface) -> Vec<Difference> {
+	let content = sync::document_content(network_interface);
+	let other_content = sync::document_content(other_network_interface);
+
+	let mut differences = sync::content_changes(&content, &other_content)
+		.into_keys()
+		.map(|key| {
+			let (value, other_value) = (content.get(&key), other_content.get(&key));
+			let kind = match (value, other_value) {
+				(None, _) => ChangeKind::Added,
+				(_, None) => ChangeKind::Removed,
+				_ => ChangeKind::Changed,
+			};
+			let aspects = match (value, other_value) {
+				(Some(SyncedValue::Node(node)), Some(SyncedValue::Node(other_node))) => {
+					let SyncedKey::Node(id) = key else { unreachable!() };
+					let placements = (
+						layer_placement(id, network_interface.document_metadata()),
+						layer_placement(id, other_network_interface.document_metadata()),
+					);
+					node_aspects(node, other_node, placements)
+				}
+				(Some(_), Some(_)) => vec!["Connections"],
+				_ => Vec::new(),
+			};
+
+			Difference {
+				key,
+				kind,
+				name: value.map(entry_name),
+				other_name: other_value.map(entry_name),
+				is_layer: [value, other_value].into_iter().flatten().any(is_layer),
+				aspects,
+			}
+		})
+		.collect::<Vec<_>>();
+
+	differences.sort_by(|a, b| {
+		let name = |difference: &Difference| difference.name.clone().or(difference.other_name.clone()).unwrap_or_default();
+		(!a.is_layer, name(a), a.kind).cmp(&(!b.is_layer, name(b), b.kind))
+	});
+	differences
+}
+
+/// The changes which bring the selected entries of a document up to date with the other version.
+pub fn changes_to_apply(network_interface: &NodeNetworkInterface, other_network_interface: &NodeNetworkInterface, selected: &HashSet<SyncedKey>) -> HashMap<SyncedKey, Option<SyncedValue>> {
+	let content = sync::document_content(network_interface);
+	let other_content = sync::document_content(other_network_interface);
+
+	let mut changes = sync::content_changes(&content, &other_content);
+	changes.retain(|key, _| selected.contains(key));
+	changes
+}
+
+fn entry_name(value: &SyncedValue) -> String {
+	match value {
+		SyncedValue::Exports(_) => "Document Exports".into(),
+		SyncedValue::Node(node) => {
+			let metadata = &node.1.persistent_metadata;
+			if !metadata.display_name.is_empty() {
+				metadata.display_name.clone()
+			} else if metadata.is_layer() && metadata.reference.as_deref() == Some("Merge") {
+				"Untitled Layer".into()
+			} else {
+				metadata.reference.clone().unwrap_or("Untitled Node".into())
+			}
+		}
+	}
+}
+
+fn is_layer(value: &SyncedValue) -> bool {
+	matches!(value, SyncedValue::Node(node) if node.1.persistent_metadata.is_layer())
+}
+
+/// Where a layer is in the layer tree, as its parent and its index among the parent's children.
+fn layer_placement(id: NodeId, metadata: &DocumentMetadata) -> Option<(LayerNodeIdentifier, usize)> {
+	let layer = LayerNodeIdentifier::new_unchecked(id);
+	let parent = layer.parent(metadata)?;
+	let index = parent.children(metadata).position(|child| child == layer)?;
+	Some((parent, index))
+}
+
+fn node_aspects(
+	(node, metadata): &(DocumentNode, DocumentNodeMetadata),
+	(other_node, other_metadata): &(DocumentNode, DocumentNodeMetadata),
+	(placement, other_placement): (Option<(LayerNodeIdentifier, usize)>, Option<(LayerNodeIdentifier, usize)>),
+) -> Vec<&'static str> {
+	let (persistent, other_persistent) = (&metadata.persistent_metadata, &other_metadata.persistent_metadata);
+
+	let mut aspects = Vec::new();
+	if persistent.display_name != other_persistent.display_name {
+		aspects.push("Name");
+	}
+	if node.visible != other_node.visible {
+		aspects.push("Visibility");
+	}
+	if persistent.locked != other_persistent.locked {
+		aspects.push("Locking");
+	}
+	if placement != other_placement {
+		aspects.push("Layer Order");
+	}
+	if node.inputs != other_node.inputs {
+		aspects.push("Inputs");
+	}
+	if node.implementation != other_node.implementation {
+		aspects.push("Contents");
+	}
+	if persistent.node_type_metadata != other_persistent.node_type_metadata {
+		aspects.push("Graph Position");
+	}
+	if aspects.is_empty() {
+		aspects.push("Other");
+	}
+	aspects
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn with_nodes(nodes: impl IntoIterator<Item = (u64, &'static str, bool)>) -> NodeNetworkInterface {
+		let nodes = nodes
+			.into_iter()
+			.map(|(id, name, visible)| {
+				let node = DocumentNode { visible, ..Default::default() };
+				let mut metadata = DocumentNodeMetadata::default();
+				metadata.persistent_metadata.display_name = name.to_string();
+				(NodeId(id), Some((node, metadata)))
+			})
+			.collect();
+		NodeNetworkInterface::default().with_synced_changes(None, nodes)
+	}
+
+	#[test]
+	fn finds_added_removed_and_changed_nodes() {
+		let document = with_nodes([(1, "Kept", true), (2, "Removed", true), (3, "Hidden", true), (4, "Renamed", true)]);
+		let other = with_nodes([(1, "Kept", true), (3, "Hidden", false), (4, "New Name", true), (5, "Added", true)]);
+
+		let differences = compare(&document, &other)
+			.into_iter()
+			.map(|difference| (difference.key, difference.kind, difference.aspects))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			differences,
+			vec![
+				(SyncedKey::Node(NodeId(5)), ChangeKind::Added, vec![]),
+				(SyncedKey::Node(NodeId(3)), ChangeKind::Changed, vec!["Visibility"]),
+				(SyncedKey::Node(NodeId(2)), ChangeKind::Removed, vec![]),
+				(SyncedKey::Node(NodeId(4)), ChangeKind::Changed, vec!["Name"]),
+			]
+		);
+	}
+
+	#[test]
+	fn applying_selected_changes_leaves_the_rest() {
+		let document = with_nodes([(1, "First", true), (2, "Second", true)]);
+		let other = with_nodes([(1, "First Renamed", true), (3, "Third", true)]);
+
+		let selected = HashSet::from([SyncedKey::Node(NodeId(1)), SyncedKey::Node(NodeId(3))]);
+		let merged = sync::apply_changes(&document, changes_to_apply(&document, &other, &selected));
+
+		let remaining = compare(&merged, &other).into_iter().map(|difference| (difference.key, difference.kind)).collect::<Vec<_>>();
+		assert_eq!(remaining, vec![(SyncedKey::Node(NodeId(2)), ChangeKind::Removed)]);
+	}
+}
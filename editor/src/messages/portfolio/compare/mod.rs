@@ -0,0 +1,9 @@
+mod compare_message;
+mod compare_message_handler;
+
+pub mod diff;
+
+#[doc(inline)]
+pub use compare_message::*;
+#[doc(inline)]
+pub use compare_message_handler::*;
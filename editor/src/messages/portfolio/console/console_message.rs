@@ -0,0 +1,21 @@
+use crate::messages::prelude::*;
+
+/// The Console panel runs scripts which dispatch editor messages, as a way to automate the editor.
+#[impl_message(Message, PortfolioMessage, Console)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum ConsoleMessage {
+	ToggleOpen,
+
+	UpdateScript {
+		script: String,
+	},
+	Run,
+	ClearOutput,
+
+	/// Adds a line to the output, which is queued among the messages of a script so it appears in order with their results.
+	Print {
+		text: String,
+	},
+	/// Lists the selected layers of the active document in the output.
+	PrintSelection,
+}
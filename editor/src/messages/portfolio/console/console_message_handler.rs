@@ -0,0 +1,143 @@
+use super::script::{self, ScriptCommand};
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::*;
+
+/// The number of lines of output kept before the oldest are dropped.
+const MAX_OUTPUT_LINES: usize = 500;
+
+pub struct ConsoleMessageData<'a> {
+	pub document: Option<&'a DocumentMessageHandler>,
+}
+
+/// The Console panel runs scripts which dispatch editor messages, as a way to automate the editor.
+#[derive(Default, Debug, Clone)]
+pub struct ConsoleMessageHandler {
+	/// Sets whether or not the Console panel is drawn.
+	pub console_open: bool,
+	script: String,
+	output: Vec<String>,
+}
+
+impl MessageHandler<ConsoleMessage, ConsoleMessageData<'_>> for ConsoleMessageHandler {
+	fn process_message(&mut self, message: ConsoleMessage, responses: &mut VecDeque<Message>, data: ConsoleMessageData) {
+		let ConsoleMessageData { document } = data;
+
+		match message {
+			ConsoleMessage::ToggleOpen => {
+				self.console_open = !self.console_open;
+				// Update checked UI state for open
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			ConsoleMessage::UpdateScript { script } => {
+				self.script = script;
+				return;
+			}
+			ConsoleMessage::Run => {
+				let commands = match script::parse_script(&self.script) {
+					Ok(commands) => commands,
+					Err(error) => {
+						self.print(error);
+						self.update_layout(responses);
+						return;
+					}
+				};
+
+				// Everything the script changes in the document is undone in one step
+				let grouped = document.is_some();
+				if grouped {
+					responses.add(DocumentMessage::StartUndoGroup);
+				}
+				for (line, command) in commands {
+					responses.add(ConsoleMessage::Print { text: format!("> {line}") });
+					match command {
+						ScriptCommand::Dispatch(message) => responses.add(message),
+						ScriptCommand::Selection => responses.add(ConsoleMessage::PrintSelection),
+						ScriptCommand::Help => responses.add(ConsoleMessage::Print { text: script::HELP.into() }),
+					}
+				}
+				if grouped {
+					responses.add(DocumentMessage::EndUndoGroup);
+				}
+			}
+			ConsoleMessage::ClearOutput => {
+				self.output.clear();
+			}
+			ConsoleMessage::Print { text } => {
+				self.print(text);
+			}
+			ConsoleMessage::PrintSelection => {
+				let Some(document) = document else {
+					self.print("No document is open".into());
+					self.update_layout(responses);
+					return;
+				};
+
+				let selected_layers = document.network_interface.selected_nodes().selected_layers(document.metadata()).collect::<Vec<_>>();
+				if selected_layers.is_empty() {
+					self.print("No layers are selected".into());
+				}
+				for layer in selected_layers {
+					let name = document.network_interface.frontend_display_name(&layer.to_node(), &[]);
+					self.print(format!("{}  {name}", layer.to_node().0));
+				}
+			}
+		}
+
+		self.update_layout(responses);
+	}
+
+	fn actions(&self) -> ActionList {
+		actions!(ConsoleMessage;)
+	}
+}
+
+impl ConsoleMessageHandler {
+	fn print(&mut self, text: String) {
+		self.output.extend(text.lines().map(String::from));
+
+		let excess = self.output.len().saturating_sub(MAX_OUTPUT_LINES);
+		self.output.drain(..excess);
+	}
+
+	fn update_layout(&self, responses: &mut VecDeque<Message>) {
+		responses.add(FrontendMessage::UpdateConsoleState { open: self.console_open });
+		if !self.console_open {
+			return;
+		}
+
+		let mut layout = vec![
+			LayoutGroup::Row {
+				widgets: vec![
+					TextAreaInput::new(&self.script)
+						.tooltip("One command per line, or \"help\" to list them")
+						.on_update(|text_area_input: &TextAreaInput| {
+							ConsoleMessage::UpdateScript {
+								script: text_area_input.value.clone(),
+							}
+							.into()
+						})
+						.widget_holder(),
+				],
+			},
+			LayoutGroup::Row {
+				widgets: vec![
+					TextButton::new("Run")
+						.emphasized(true)
+						.tooltip("Run the script, grouping its changes to the document into one step of the undo history")
+						.on_update(|_| ConsoleMessage::Run.into())
+						.widget_holder(),
+					Separator::new(SeparatorType::Related).widget_holder(),
+					TextButton::new("Clear Output").on_update(|_| ConsoleMessage::ClearOutput.into()).widget_holder(),
+				],
+			},
+		];
+		layout.extend(self.output.iter().map(|line| LayoutGroup::Row {
+			widgets: vec![TextLabel::new(line).widget_holder()],
+		}));
+
+		responses.add(LayoutMessage::SendLayout {
+			layout: Layout::WidgetLayout(WidgetLayout { layout }),
+			layout_target: LayoutTarget::Console,
+		});
+	}
+}
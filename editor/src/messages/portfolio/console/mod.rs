@@ -0,0 +1,9 @@
+mod console_message;
+mod console_message_handler;
+
+pub mod script;
+
+#[doc(inline)]
+pub use console_message::*;
+#[doc(inline)]
+pub use console_message_handler::*;
@@ -0,0 +1,107 @@
+use crate::messages::prelude::*;
+use serde_json::{Map, Value};
+
+pub const HELP: &str = "Each line of a script is one command, and lines starting with # are skipped.
+  selection  Lists the selected layers
+  help  Shows this help
+  Path.To.Message {\"field\": value}  Dispatches a message, given by its path through the message hierarchy and its fields as JSON (left out for messages without fields), for example: Portfolio.Document.GroupSelectedLayers {\"group_folder_type\": \"Layer\"}
+All the changes made by a script are undone together.";
+
+/// A line of a console script.
+#[derive(PartialEq, Clone, Debug)]
+pub enum ScriptCommand {
+	Dispatch(Message),
+	Selection,
+	Help,
+}
+
+/// Reads each line of a script into a command, paired with the line it came from, or fails with the first line that couldn't be read.
+pub fn parse_script(script: &str) -> Result<Vec<(String, ScriptCommand)>, String> {
+	script
+		.lines()
+		.enumerate()
+		.map(|(index, line)| (index + 1, line.trim()))
+		.filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+		.map(|(number, line)| {
+			let command = match line {
+				"selection" => ScriptCommand::Selection,
+				"help" => ScriptCommand::Help,
+				_ => ScriptCommand::Dispatch(parse_message(line).map_err(|error| format!("Line {number}: {error}"))?),
+			};
+			Ok((line.to_string(), command))
+		})
+		.collect()
+}
+
+/// Builds a message from its path through the message hierarchy, such as `Portfolio.Document.DeleteSelectedLayers`, optionally followed by its fields as JSON.
+fn parse_message(line: &str) -> Result<Message, String> {
+	let (path, fields) = match line.split_once(char::is_whitespace) {
+		Some((path, fields)) => (path, Some(fields.trim())),
+		None => (line, None),
+	};
+	if path.split('.').any(str::is_empty) {
+		return Err(format!("\"{path}\" isn't a path to a message"));
+	}
+
+	// Messages are serialized as nested objects keyed by the name of each variant, ending with the variant's fields or just its name if it has none
+	let mut segments = path.rsplit('.');
+	let innermost = segments.next().unwrap_or_default();
+	let mut value = match fields {
+		Some(fields) => {
+			let fields = serde_json::from_str::<Value>(fields).map_err(|error| format!("The fields of {path} aren't valid JSON: {error}"))?;
+			Value::Object(Map::from_iter([(innermost.to_string(), fields)]))
+		}
+		None => Value::String(innermost.to_string()),
+	};
+	for segment in segments {
+		value = Value::Object(Map::from_iter([(segment.to_string(), value)]));
+	}
+
+	let message = serde_json::from_value::<Message>(value).map_err(|error| format!("{path} isn't a message which can be made from the given fields: {error}"))?;
+	if matches!(message, Message::Portfolio(PortfolioMessage::Console(_))) {
+		return Err("Console messages can't be run from a script".into());
+	}
+
+	Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn messages_are_built_from_their_paths() {
+		let script = "
+			# Comments and blank lines are skipped
+
+			Portfolio.Document.DeleteSelectedLayers
+			Portfolio.Document.SetSwatchSpotColor {\"index\": 2, \"spot\": true, \"overprint\": false}
+			selection
+		";
+		let commands = parse_script(script).unwrap().into_iter().map(|(_, command)| command).collect::<Vec<_>>();
+
+		assert_eq!(
+			commands,
+			vec![
+				ScriptCommand::Dispatch(DocumentMessage::DeleteSelectedLayers.into()),
+				ScriptCommand::Dispatch(
+					DocumentMessage::SetSwatchSpotColor {
+						index: 2,
+						spot: true,
+						overprint: false
+					}
+					.into()
+				),
+				ScriptCommand::Selection,
+			]
+		);
+	}
+
+	#[test]
+	fn unreadable_lines_are_reported() {
+		assert!(parse_script("Portfolio.Document.NotAMessage").unwrap_err().starts_with("Line 1:"));
+		assert!(parse_script("help\nPortfolio..Document").unwrap_err().starts_with("Line 2:"));
+		assert!(parse_script("Portfolio.Document.SetSwatchSpotColor {index: 2}").is_err());
+		assert!(parse_script("Portfolio.Console.Run").is_err());
+	}
+}
@@ -0,0 +1,54 @@
+use crate::messages::frontend::utility_types::FileType;
+use crate::messages::prelude::*;
+use graphene_core::Color;
+use graphene_core::raster::Image;
+
+/// Makes a variant of a template artboard for each record of a dataset, with the text and images of its layers bound to the dataset's columns.
+#[impl_message(Message, PortfolioMessage, DataMerge)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum DataMergeMessage {
+	ToggleOpen,
+
+	/// Asks the frontend for a CSV or JSON file to import as the dataset.
+	ImportDataset,
+	/// Sent by the frontend with the dataset file chosen by the user.
+	DatasetImported {
+		name: String,
+		content: String,
+	},
+	/// Asks the frontend for image files, which stand in for the image paths of the dataset ending in the same file names.
+	ChooseImages,
+	/// Sent by the frontend with an image named by the dataset, either loaded from its path or chosen by the user.
+	ImageLoaded {
+		path: String,
+		image: Image<Color>,
+	},
+	/// Sent by the frontend when an image named by the dataset couldn't be loaded from its path.
+	ImageFailed {
+		path: String,
+	},
+
+	/// Binds each selected text or image layer of the active document to a column of the dataset.
+	BindSelectedLayers {
+		column: String,
+	},
+	SetBindingColumn {
+		index: usize,
+		column: String,
+	},
+	RemoveBinding {
+		index: usize,
+	},
+	SetNameColumn {
+		column: Option<String>,
+	},
+	SetFileType {
+		file_type: FileType,
+	},
+
+	/// Adds a copy of the template artboard showing each record, as a single step which can be undone.
+	GenerateArtboards,
+	/// Exports the template artboard showing each record to its own file, without changing the document.
+	ExportEach,
+	SendLayout,
+}
@@ -0,0 +1,525 @@
+use crate::messages::frontend::utility_types::{ExportBounds, FileType};
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::utility_types::data_merge::{BindingTarget, DataBinding, Dataset};
+use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
+use crate::messages::portfolio::document::utility_types::network_interface::FlowType;
+use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::graph_modification_utils;
+use crate::node_graph_executor::{ExportConfig, NodeGraphExecutor};
+use glam::IVec2;
+use graph_craft::document::value::TaggedValue;
+use graph_craft::document::{NodeId, NodeInput, NodeNetwork};
+use graphene_core::Color;
+use graphene_core::raster::Image;
+use graphene_core::raster::image::ImageFrameTable;
+
+/// The space (in document units) left between the artboards made for the records.
+const ARTBOARD_GAP: i32 = 100;
+
+const FILE_TYPES: [(FileType, &str); 6] = [
+	(FileType::Png, "PNG"),
+	(FileType::Jpg, "JPG"),
+	(FileType::Svg, "SVG"),
+	(FileType::Tiff, "TIFF"),
+	(FileType::Pdf, "PDF"),
+	(FileType::Eps, "EPS"),
+];
+
+pub struct DataMergeMessageData<'a> {
+	pub document: Option<&'a mut DocumentMessageHandler>,
+	pub executor: &'a mut NodeGraphExecutor,
+	pub undo_history_length: usize,
+}
+
+/// Binds the text and image layers of a template artboard to the columns of an imported dataset, then makes an artboard or export for each record.
+#[derive(Debug, Clone, Default)]
+pub struct DataMergeMessageHandler {
+	/// Sets whether or not the Data Merge panel is drawn.
+	pub data_merge_open: bool,
+	/// The name of the file the dataset was imported from.
+	dataset_name: String,
+	dataset: Option<Dataset>,
+	/// The images named by the dataset, by their path or the file name they were chosen by.
+	images: HashMap<String, Image<Color>>,
+	/// The image paths the frontend has been asked to load, so each is only asked for once.
+	requested_images: HashSet<String>,
+	failed_images: HashSet<String>,
+	file_type: FileType,
+	/// Why the dataset couldn't be imported or the records couldn't be merged.
+	error: Option<String>,
+}
+
+impl MessageHandler<DataMergeMessage, DataMergeMessageData<'_>> for DataMergeMessageHandler {
+	fn process_message(&mut self, message: DataMergeMessage, responses: &mut VecDeque<Message>, data: DataMergeMessageData) {
+		let DataMergeMessageData {
+			mut document,
+			executor,
+			undo_history_length,
+		} = data;
+
+		match message {
+			DataMergeMessage::ToggleOpen => {
+				self.data_merge_open = !self.data_merge_open;
+				// Update checked UI state for open
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			DataMergeMessage::ImportDataset => {
+				responses.add(FrontendMessage::TriggerDataMergeImport);
+				return;
+			}
+			DataMergeMessage::DatasetImported { name, content } => match Dataset::from_file(&name, &content) {
+				Ok(dataset) => {
+					self.dataset_name = name;
+					self.dataset = Some(dataset);
+					self.error = None;
+					if let Some(document) = document.as_deref() {
+						self.request_images(document, responses);
+					}
+				}
+				Err(error) => self.error = Some(format!("{name} couldn't be imported: {error}")),
+			},
+			DataMergeMessage::ChooseImages => {
+				responses.add(FrontendMessage::TriggerDataMergeChooseImages);
+				return;
+			}
+			DataMergeMessage::ImageLoaded { path, image } => {
+				self.failed_images.remove(&path);
+				self.images.insert(path, image);
+			}
+			DataMergeMessage::ImageFailed { path } => {
+				self.failed_images.insert(path);
+			}
+			DataMergeMessage::BindSelectedLayers { column } => {
+				let Some(document) = document.as_deref_mut() else { return };
+
+				let selected_layers = document.network_interface.selected_nodes().selected_layers(document.metadata()).collect::<Vec<_>>();
+				let new_bindings = selected_layers
+					.into_iter()
+					.filter_map(|layer| {
+						let target = if graph_modification_utils::get_text_id(layer, &document.network_interface).is_some() {
+							BindingTarget::Text
+						} else if graph_modification_utils::get_image_id(layer, &document.network_interface).is_some() {
+							BindingTarget::Image
+						} else {
+							return None;
+						};
+						Some(DataBinding {
+							layer,
+							column: column.clone(),
+							target,
+						})
+					})
+					.collect::<Vec<_>>();
+				if new_bindings.is_empty() {
+					return;
+				}
+
+				// Bindings are kept outside of the node network, so the state before the change is pushed to the history directly
+				document.add_history_step(undo_history_length, responses);
+				let bindings = &mut document.data_merge.bindings;
+				bindings.retain(|binding| !new_bindings.iter().any(|new_binding| new_binding.layer == binding.layer));
+				bindings.extend(new_bindings);
+				self.request_images(document, responses);
+			}
+			DataMergeMessage::SetBindingColumn { index, column } => {
+				let Some(document) = document.as_deref_mut() else { return };
+				if index >= document.data_merge.bindings.len() {
+					return;
+				}
+				document.add_history_step(undo_history_length, responses);
+				document.data_merge.bindings[index].column = column;
+				self.request_images(document, responses);
+			}
+			DataMergeMessage::RemoveBinding { index } => {
+				let Some(document) = document.as_deref_mut() else { return };
+				if index < document.data_merge.bindings.len() {
+					document.add_history_step(undo_history_length, responses);
+					document.data_merge.bindings.remove(index);
+				}
+			}
+			DataMergeMessage::SetNameColumn { column } => {
+				let Some(document) = document.as_deref_mut() else { return };
+				if document.data_merge.name_column != column {
+					document.add_history_step(undo_history_length, responses);
+					document.data_merge.name_column = column;
+				}
+			}
+			DataMergeMessage::SetFileType { file_type } => {
+				self.file_type = file_type;
+			}
+			DataMergeMessage::GenerateArtboards => {
+				let (Some(document), Some(dataset)) = (document.as_deref_mut(), &self.dataset) else { return };
+				let Some(artboard) = template_artboard(document) else { return };
+				let artboard_node = artboard.to_node();
+
+				let artboard_input = |index: usize| {
+					let input = document.network_interface.document_network().nodes.get(&artboard_node)?.inputs.get(index)?;
+					let &TaggedValue::IVec2(value) = input.as_value()? else { return None };
+					Some(value)
+				};
+				let (Some(location), Some(dimensions)) = (artboard_input(2), artboard_input(3)) else { return };
+
+				let variants = (0..dataset.records.len())
+					.map(|record| (self.record_name(document, dataset, record), self.record_inputs(document, dataset, record)))
+					.collect::<Vec<_>>();
+
+				// Copy the artboard in the same way as when it's copied to the clipboard
+				let mut copy_ids = HashMap::new();
+				copy_ids.insert(artboard_node, NodeId(0));
+				document
+					.network_interface
+					.upstream_flow_back_from_nodes(vec![artboard_node], &[], FlowType::LayerChildrenUpstreamFlow)
+					.enumerate()
+					.for_each(|(index, node_id)| {
+						copy_ids.insert(node_id, NodeId((index + 1) as u64));
+					});
+				let template = document.network_interface.copy_nodes(&copy_ids, &[]).collect::<Vec<_>>();
+
+				// The copies are laid out in a grid to the right of the template
+				let columns = (variants.len() as f64).sqrt().ceil().max(1.) as usize;
+				let spacing = dimensions.abs() + IVec2::splat(ARTBOARD_GAP);
+
+				responses.add(DocumentMessage::AddTransaction);
+				for (record, (name, inputs)) in variants.into_iter().enumerate() {
+					let mut nodes = template.clone();
+					for (copy_id, node) in &mut nodes {
+						if *copy_id == NodeId(0) {
+							let offset = IVec2::new((record % columns) as i32 + 1, (record / columns) as i32) * spacing;
+							node.persistent_node_metadata.display_name = name.clone();
+							node.document_node.inputs[2] = NodeInput::value(TaggedValue::IVec2(location + offset), false);
+						}
+						for (node_id, input_index, value) in &inputs {
+							if copy_ids.get(node_id) == Some(copy_id) {
+								set_value_input(&mut node.document_node.inputs, *input_index, value.clone());
+							}
+						}
+					}
+
+					let new_ids: HashMap<_, _> = nodes.iter().map(|(id, _)| (*id, NodeId::new())).collect();
+					let layer = LayerNodeIdentifier::new_unchecked(new_ids[&NodeId(0)]);
+					responses.add(NodeGraphMessage::AddNodes { nodes, new_ids });
+					responses.add(NodeGraphMessage::MoveLayerToStack {
+						layer,
+						parent: LayerNodeIdentifier::ROOT_PARENT,
+						insert_index: 0,
+					});
+				}
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+			}
+			DataMergeMessage::ExportEach => {
+				let (Some(document), Some(dataset)) = (document.as_deref_mut(), &self.dataset) else { return };
+				let Some(artboard) = template_artboard(document) else { return };
+
+				let variants = (0..dataset.records.len())
+					.map(|record| {
+						let mut network = document.network_interface.document_network().clone();
+						for (node_id, input_index, value) in self.record_inputs(document, dataset, record) {
+							if let Some(node) = network.nodes.get_mut(&node_id) {
+								set_value_input(&mut node.inputs, input_index, value);
+							}
+						}
+						(file_name(&self.record_name(document, dataset, record)), network)
+					})
+					.collect::<Vec<(String, NodeNetwork)>>();
+
+				let export_config = ExportConfig {
+					file_type: self.file_type,
+					scale_factor: 1.,
+					bounds: ExportBounds::Artboard(artboard),
+					..Default::default()
+				};
+				if let Err(description) = executor.submit_variant_exports(document, export_config, variants) {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Unable to export the records".to_string(),
+						description,
+					});
+				}
+				return;
+			}
+			DataMergeMessage::SendLayout => {}
+		}
+
+		self.update_layout(document.as_deref(), responses);
+	}
+
+	fn actions(&self) -> ActionList {
+		actions!(DataMergeMessage;)
+	}
+}
+
+/// The artboard containing the bound layers, which is copied for each record.
+fn template_artboard(document: &DocumentMessageHandler) -> Option<LayerNodeIdentifier> {
+	let binding = document.data_merge.bindings.iter().find(|binding| binding.layer.exists(document.metadata()))?;
+	binding.layer.ancestors(document.metadata()).find(|layer| document.network_interface.is_artboard(&layer.to_node(), &[]))
+}
+
+/// Replaces the value of an input, leaving it alone if it's been connected to another node since it was bound.
+fn set_value_input(inputs: &mut [NodeInput], index: usize, value: TaggedValue) {
+	if let Some(input @ NodeInput::Value { .. }) = inputs.get_mut(index) {
+		*input = NodeInput::value(value, false);
+	}
+}
+
+/// Leaves out the characters which can't be used in file names.
+fn file_name(name: &str) -> String {
+	name.chars().map(|character| if r#"/\:*?"<>|"#.contains(character) { '-' } else { character }).collect()
+}
+
+/// The name of an image path's file, which the image can be chosen by when it can't be loaded from the path.
+fn path_file_name(path: &str) -> &str {
+	path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+/// The dataset's columns as dropdown entries, along with the bound column which may have been left out of the dataset imported since.
+fn column_entries(columns: &[String], current: Option<&str>, on_update: impl Fn(String) -> Message + Clone + Send + Sync + 'static) -> (Vec<MenuListEntry>, Option<u32>) {
+	let mut columns = columns.to_vec();
+	if let Some(current) = current.filter(|current| !columns.iter().any(|column| column == current)) {
+		columns.push(current.to_string());
+	}
+	let selected_index = current.and_then(|current| columns.iter().position(|column| column == current)).map(|index| index as u32);
+
+	let entries = columns
+		.into_iter()
+		.map(|column| {
+			let on_update = on_update.clone();
+			MenuListEntry::new(column.clone()).label(column.clone()).on_update(move |_| on_update(column.clone()))
+		})
+		.collect();
+	(entries, selected_index)
+}
+
+impl DataMergeMessageHandler {
+	fn image(&self, path: &str) -> Option<&Image<Color>> {
+		self.images.get(path).or_else(|| self.images.get(path_file_name(path)))
+	}
+
+	/// The image paths of the bound columns.
+	fn image_paths(&self, document: &DocumentMessageHandler) -> HashSet<String> {
+		let Some(dataset) = &self.dataset else { return HashSet::new() };
+
+		let image_columns = document.data_merge.bindings.iter().filter(|binding| binding.target == BindingTarget::Image);
+		image_columns
+			.flat_map(|binding| (0..dataset.records.len()).map(|record| dataset.value(record, &binding.column).trim().to_string()))
+			.filter(|path| !path.is_empty())
+			.collect()
+	}
+
+	/// Asks the frontend to load the images of the bound columns which haven't been loaded yet.
+	fn request_images(&mut self, document: &DocumentMessageHandler, responses: &mut VecDeque<Message>) {
+		let paths = self
+			.image_paths(document)
+			.into_iter()
+			.filter(|path| self.image(path).is_none() && !self.requested_images.contains(path))
+			.collect::<Vec<_>>();
+		if paths.is_empty() {
+			return;
+		}
+
+		self.requested_images.extend(paths.iter().cloned());
+		responses.add(FrontendMessage::TriggerDataMergeLoadImages { paths });
+	}
+
+	/// The inputs of the template's nodes which show a record's values: the text of each bound text layer and the image of each bound image layer.
+	fn record_inputs(&self, document: &DocumentMessageHandler, dataset: &Dataset, record: usize) -> Vec<(NodeId, usize, TaggedValue)> {
+		let network_interface = &document.network_interface;
+		let text_input_index = 1;
+		let image_input_index = 1;
+
+		document
+			.data_merge
+			.bindings
+			.iter()
+			.filter(|binding| binding.layer.exists(document.metadata()))
+			.filter_map(|binding| {
+				let value = dataset.value(record, &binding.column);
+				match binding.target {
+					BindingTarget::Text => Some((
+						graph_modification_utils::get_text_id(binding.layer, network_interface)?,
+						text_input_index,
+						TaggedValue::String(value.to_string()),
+					)),
+					BindingTarget::Image => {
+						// A record without an image, or whose image couldn't be loaded, keeps the template's image
+						let image = self.image(value.trim())?.clone();
+						let node_id = graph_modification_utils::get_image_id(binding.layer, network_interface)?;
+						Some((node_id, image_input_index, TaggedValue::ImageFrame(ImageFrameTable::new(image))))
+					}
+				}
+			})
+			.collect()
+	}
+
+	/// Names the artboard or file made for a record from the name column, or otherwise by numbering it.
+	fn record_name(&self, document: &DocumentMessageHandler, dataset: &Dataset, record: usize) -> String {
+		let name = document.data_merge.name_column.as_deref().map(|column| dataset.value(record, column).trim());
+		match name {
+			Some(name) if !name.is_empty() => name.to_string(),
+			_ => format!("{} {}", document.name, record + 1),
+		}
+	}
+
+	fn update_layout(&self, document: Option<&DocumentMessageHandler>, responses: &mut VecDeque<Message>) {
+		responses.add(FrontendMessage::UpdateDataMergeState { open: self.data_merge_open });
+		if !self.data_merge_open {
+			return;
+		}
+
+		let columns = self.dataset.as_ref().map(|dataset| dataset.columns.clone()).unwrap_or_default();
+		let summary = match &self.dataset {
+			Some(dataset) => format!("{}: {} records, {} columns", self.dataset_name, dataset.records.len(), dataset.columns.len()),
+			None => "Import a CSV file, or a JSON array of objects, with a record for each artboard".to_string(),
+		};
+		let mut layout = vec![LayoutGroup::Row {
+			widgets: vec![
+				TextLabel::new("Dataset").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextLabel::new(summary).italic(self.dataset.is_none()).widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				IconButton::new("Folder", 24)
+					.tooltip("Import the dataset from a CSV or JSON file")
+					.on_update(|_| DataMergeMessage::ImportDataset.into())
+					.widget_holder(),
+			],
+		}];
+		if let Some(error) = &self.error {
+			layout.push(LayoutGroup::Row {
+				widgets: vec![TextLabel::new(error).italic(true).widget_holder()],
+			});
+		}
+
+		let Some(document) = document else {
+			responses.add(LayoutMessage::SendLayout {
+				layout: Layout::WidgetLayout(WidgetLayout { layout }),
+				layout_target: LayoutTarget::DataMerge,
+			});
+			return;
+		};
+
+		let image_paths = self.image_paths(document);
+		if !image_paths.is_empty() {
+			let loaded = image_paths.iter().filter(|path| self.image(path).is_some()).count();
+			let failed = image_paths.iter().filter(|path| self.image(path).is_none() && self.failed_images.contains(*path)).count();
+			let status = match failed {
+				0 => format!("{loaded} of {} loaded", image_paths.len()),
+				failed => format!("{loaded} of {} loaded, {failed} couldn't be found", image_paths.len()),
+			};
+			layout.push(LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new("Images").table_align(true).min_width(100).widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					TextLabel::new(status).widget_holder(),
+					Separator::new(SeparatorType::Related).widget_holder(),
+					TextButton::new("Choose Files")
+						.tooltip("Choose the image files to use for the paths ending in the same file names, such as those on this computer")
+						.on_update(|_| DataMergeMessage::ChooseImages.into())
+						.widget_holder(),
+				],
+			});
+		}
+
+		layout.push(LayoutGroup::Row {
+			widgets: vec![TextLabel::new("Bindings").bold(true).widget_holder()],
+		});
+		for (index, binding) in document.data_merge.bindings.iter().enumerate() {
+			let exists = binding.layer.exists(document.metadata());
+			let layer_name = if exists {
+				document.network_interface.frontend_display_name(&binding.layer.to_node(), &[])
+			} else {
+				"Deleted Layer".to_string()
+			};
+			let (entries, selected_index) = column_entries(&columns, Some(&binding.column), move |column| DataMergeMessage::SetBindingColumn { index, column }.into());
+
+			layout.push(LayoutGroup::Row {
+				widgets: vec![
+					IconLabel::new(if binding.target == BindingTarget::Text { "NodeText" } else { "Image" }).widget_holder(),
+					TextLabel::new(layer_name).italic(!exists).min_width(120).widget_holder(),
+					DropdownInput::new(vec![entries])
+						.selected_index(selected_index)
+						.tooltip(match binding.target {
+							BindingTarget::Text => "The column giving the layer's text",
+							BindingTarget::Image => "The column giving the path or URL of the layer's image",
+						})
+						.widget_holder(),
+					IconButton::new("Trash", 24)
+						.tooltip("Remove this binding")
+						.on_update(move |_| DataMergeMessage::RemoveBinding { index }.into())
+						.widget_holder(),
+				],
+			});
+		}
+
+		let (entries, _) = column_entries(&columns, None, |column| DataMergeMessage::BindSelectedLayers { column }.into());
+		layout.push(LayoutGroup::Row {
+			widgets: vec![
+				TextLabel::new("Bind Selection").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				DropdownInput::new(vec![entries])
+					.selected_index(None)
+					.disabled(columns.is_empty())
+					.tooltip("Bind the selected text and image layers to a column")
+					.widget_holder(),
+			],
+		});
+
+		let (mut entries, selected_index) = column_entries(&columns, document.data_merge.name_column.as_deref(), |column| {
+			DataMergeMessage::SetNameColumn { column: Some(column) }.into()
+		});
+		entries.insert(
+			0,
+			MenuListEntry::new("Numbered").label("Numbered").on_update(|_| DataMergeMessage::SetNameColumn { column: None }.into()),
+		);
+		layout.push(LayoutGroup::Row {
+			widgets: vec![
+				TextLabel::new("Names").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				DropdownInput::new(vec![entries])
+					.selected_index(Some(selected_index.map_or(0, |index| index + 1)))
+					.tooltip("The column naming each record's artboard or exported file")
+					.widget_holder(),
+			],
+		});
+
+		let template = template_artboard(document);
+		let template_name = match template {
+			Some(artboard) => document.network_interface.frontend_display_name(&artboard.to_node(), &[]),
+			None if document.data_merge.bindings.is_empty() => "Bind layers inside an artboard to use it as the template".to_string(),
+			None => "The bound layers aren't inside an artboard".to_string(),
+		};
+		layout.push(LayoutGroup::Row {
+			widgets: vec![
+				TextLabel::new("Template").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextLabel::new(template_name).italic(template.is_none()).widget_holder(),
+			],
+		});
+
+		let ready = template.is_some() && self.dataset.as_ref().is_some_and(|dataset| !dataset.records.is_empty());
+		let file_types = FILE_TYPES
+			.iter()
+			.map(|&(file_type, label)| MenuListEntry::new(label).label(label).on_update(move |_| DataMergeMessage::SetFileType { file_type }.into()))
+			.collect();
+		let file_type_index = FILE_TYPES.iter().position(|&(file_type, _)| file_type == self.file_type).map(|index| index as u32);
+		layout.push(LayoutGroup::Row {
+			widgets: vec![
+				TextButton::new("Generate Artboards")
+					.emphasized(true)
+					.tooltip("Add a copy of the template artboard showing each record")
+					.disabled(!ready)
+					.on_update(|_| DataMergeMessage::GenerateArtboards.into())
+					.widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextButton::new("Export Each")
+					.tooltip("Export the template artboard showing each record to its own file, without changing the document")
+					.disabled(!ready)
+					.on_update(|_| DataMergeMessage::ExportEach.into())
+					.widget_holder(),
+				DropdownInput::new(vec![file_types]).selected_index(file_type_index).widget_holder(),
+			],
+		});
+
+		responses.add(LayoutMessage::SendLayout {
+			layout: Layout::WidgetLayout(WidgetLayout { layout }),
+			layout_target: LayoutTarget::DataMerge,
+		});
+	}
+}
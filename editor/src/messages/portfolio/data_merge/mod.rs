@@ -0,0 +1,7 @@
+mod data_merge_message;
+mod data_merge_message_handler;
+
+#[doc(inline)]
+pub use data_merge_message::*;
+#[doc(inline)]
+pub use data_merge_message_handler::*;
@@ -1,17 +1,19 @@
 use super::utility_types::misc::{GroupFolderType, SnappingState};
+use super::utility_types::swatches::PaletteFormat;
 use crate::messages::input_mapper::utility_types::input_keyboard::Key;
 use crate::messages::portfolio::document::overlays::utility_types::OverlayContext;
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
-use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, FlipAxis, GridSnapping};
+use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, FlipAxis, GridSnapping, RepeatableAction};
+use crate::messages::portfolio::document::utility_types::numeric_transform::TransformField;
 use crate::messages::portfolio::utility_types::PanelType;
 use crate::messages::prelude::*;
-use glam::DAffine2;
+use glam::{DAffine2, DVec2};
 use graph_craft::document::NodeId;
 use graphene_core::Color;
 use graphene_core::raster::BlendMode;
 use graphene_core::raster::Image;
-use graphene_core::vector::style::ViewMode;
-use graphene_std::renderer::ClickTarget;
+use graphene_core::vector::style::{GradientStops, ViewMode};
+use graphene_std::renderer::{ClickTarget, ColorProfile, ColorVisionDeficiency, ProofProfile};
 use graphene_std::transform::Footprint;
 
 #[impl_message(Message, PortfolioMessage, Document)]
@@ -31,12 +33,37 @@ pub enum DocumentMessage {
 	PropertiesPanel(PropertiesPanelMessage),
 
 	// Messages
+	/// Adds the given linear space colors to the document's swatches, each named after its hex code.
+	/// Adds swatches for the colors most representative of the rasterized rendering of the selected layer.
+	AddExtractedSwatches {
+		image: Image<Color>,
+		count: u32,
+	},
+	AddSwatches {
+		colors: Vec<Color>,
+	},
 	AlignSelectedLayers {
 		axis: AlignAxis,
 		aggregate: AlignAggregate,
 	},
+	/// Fills a layer with a gradient: the given layer, otherwise the one under the mouse (in client coordinates), otherwise the selected layers.
+	ApplyGradient {
+		stops: GradientStops,
+		layer: Option<LayerNodeIdentifier>,
+		mouse: Option<(f64, f64)>,
+	},
+	/// Fills a layer with a gradient preset, chosen in the same way as by [`DocumentMessage::ApplyGradient`].
+	ApplyGradientPreset {
+		name: String,
+		layer: Option<LayerNodeIdentifier>,
+		mouse: Option<(f64, f64)>,
+	},
 	RemoveArtboards,
 	ClearLayersPanel,
+	/// Replaces the Text node of each selected text layer with the outlines of its glyphs, so they can be edited like any other path.
+	ConvertSelectedTextToPaths,
+	/// Fills each selected empty text box with placeholder text sized to roughly fill it, for mock-ups.
+	FillSelectedTextWithPlaceholder,
 	CreateEmptyFolder,
 	DeleteNode {
 		node_id: NodeId,
@@ -47,7 +74,14 @@ pub enum DocumentMessage {
 	DocumentHistoryForward,
 	DocumentStructureChanged,
 	DrawArtboardOverlays(OverlayContext),
+	DrawExportRegion,
+	DrawExportRegionPointerDown,
+	DrawExportRegionPointerMove,
+	DrawExportRegionPointerUp,
+	DrawExportRegionAbort,
 	DuplicateSelectedLayers,
+	/// Opens the selected image layer in another application, reimporting its pixels each time the application saves them.
+	EditImageExternally,
 	EnterNestedNetwork {
 		node_id: NodeId,
 	},
@@ -55,6 +89,16 @@ pub enum DocumentMessage {
 	ExitNestedNetwork {
 		steps_back: usize,
 	},
+	ExportSwatches {
+		format: PaletteFormat,
+	},
+	ExtractSwatches {
+		count: u32,
+	},
+	/// Fills the selected layers with the given linear space colors, taking turns through them if there are more layers than colors.
+	FillSelectedLayers {
+		colors: Vec<Color>,
+	},
 	FlipSelectedLayers {
 		flip_axis: FlipAxis,
 	},
@@ -78,6 +122,9 @@ pub enum DocumentMessage {
 		imaginate_node: Vec<NodeId>,
 		then_generate: bool,
 	},
+	ImportPalette {
+		data: Vec<u8>,
+	},
 	MoveSelectedLayersTo {
 		parent: LayerNodeIdentifier,
 		insert_index: usize,
@@ -88,9 +135,14 @@ pub enum DocumentMessage {
 	NudgeSelectedLayers {
 		delta_x: f64,
 		delta_y: f64,
+		large: bool,
 		resize: Key,
 		resize_opposite_corner: Key,
 	},
+	NumericTransformSelectedLayers {
+		field: TransformField,
+		expression: String,
+	},
 	PasteImage {
 		name: Option<String>,
 		image: Image<Color>,
@@ -103,12 +155,27 @@ pub enum DocumentMessage {
 		mouse: Option<(f64, f64)>,
 		parent_and_insert_index: Option<(LayerNodeIdentifier, usize)>,
 	},
+	/// Swaps each fill and stroke color of the selected layers (and their descendants) listed in the replacements, as pairs of the original and new gamma space color.
+	RecolorArtwork {
+		replacements: Vec<(Color, Color)>,
+	},
+	RecordRepeatableAction {
+		action: RepeatableAction,
+	},
 	Redo,
+	RemoveSwatch {
+		index: usize,
+	},
 	RenameDocument {
 		new_name: String,
 	},
 	RenderRulers,
 	RenderScrollbars,
+	RenderTransformBar,
+	RepeatLastAction,
+	RepeatTransform {
+		transform: DAffine2,
+	},
 	SaveDocument,
 	SelectParentLayer,
 	SelectAllLayers,
@@ -131,6 +198,9 @@ pub enum DocumentMessage {
 	SetBlendModeForSelectedLayers {
 		blend_mode: BlendMode,
 	},
+	SetExportRegion {
+		region: Option<[DVec2; 2]>,
+	},
 	SetGraphFadeArtwork {
 		percentage: f64,
 	},
@@ -152,6 +222,15 @@ pub enum DocumentMessage {
 		closure: Option<for<'a> fn(&'a mut SnappingState) -> &'a mut bool>,
 		snapping_state: bool,
 	},
+	SetSwatchColor {
+		index: usize,
+		color: Color,
+	},
+	SetSwatchSpotColor {
+		index: usize,
+		spot: bool,
+		overprint: bool,
+	},
 	SetToNodeOrLayer {
 		node_id: NodeId,
 		is_layer: bool,
@@ -167,14 +246,30 @@ pub enum DocumentMessage {
 		undo_count: usize,
 	},
 	AddTransaction,
+	/// Begins merging the history steps added until `EndUndoGroup` into a single step, so they're undone together.
+	StartUndoGroup,
+	EndUndoGroup,
 	ToggleLayerExpansion {
 		id: NodeId,
 		recursive: bool,
 	},
 	ToggleSelectedVisibility,
 	ToggleSelectedLocked,
+	ToggleSelectedReferenceImages,
 	ToggleGridVisibility,
 	ToggleOverlaysVisibility,
+	ToggleOutlineViewMode,
+	ToggleProofColors,
+	ToggleDiffFriendlyFormat,
+	SetColorProfile {
+		color_profile: ColorProfile,
+	},
+	SetColorVisionSimulation {
+		color_vision: Option<ColorVisionDeficiency>,
+	},
+	SetProofProfile {
+		proof_profile: ProofProfile,
+	},
 	ToggleSnapping,
 	UpdateUpstreamTransforms {
 		upstream_footprints: HashMap<NodeId, Footprint>,
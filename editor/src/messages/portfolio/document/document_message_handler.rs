@@ -1,37 +1,51 @@
+use super::graph_operation::transform_utils;
 use super::node_graph::document_node_definitions;
 use super::node_graph::utility_types::Transform;
 use super::overlays::utility_types::Pivot;
+use super::utility_types::clipboards::CopyBufferEntry;
+use super::utility_types::data_merge::DataMerge;
+use super::utility_types::document_history::{DocumentHistory, DocumentState};
 use super::utility_types::error::EditorError;
 use super::utility_types::misc::{GroupFolderType, SNAP_FUNCTIONS_FOR_BOUNDING_BOXES, SNAP_FUNCTIONS_FOR_PATHS, SnappingOptions, SnappingState};
 use super::utility_types::network_interface::{self, NodeNetworkInterface, TransactionStatus};
 use super::utility_types::nodes::{CollapsedLayers, SelectedNodes};
+use super::utility_types::recolor;
+use super::utility_types::swatches::{self, Swatch};
+use super::utility_types::timeline::Timeline;
 use crate::application::{GRAPHITE_GIT_COMMIT_HASH, generate_uuid};
 use crate::consts::{ASYMPTOTIC_EFFECT, COLOR_OVERLAY_GRAY, DEFAULT_DOCUMENT_NAME, FILE_SAVE_SUFFIX, SCALE_EFFECT, SCROLLBAR_SPACING, VIEWPORT_ROTATE_SNAP_INTERVAL};
+use crate::messages::frontend::utility_types::MouseCursorIcon;
+use crate::messages::input_mapper::utility_types::input_keyboard::MouseMotion;
 use crate::messages::input_mapper::utility_types::macros::action_keys;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::graph_operation::utility_types::TransformIn;
 use crate::messages::portfolio::document::node_graph::NodeGraphHandlerData;
-use crate::messages::portfolio::document::overlays::grid_overlays::{grid_overlay, overlay_options};
+use crate::messages::portfolio::document::overlays::grid_overlays::{grid_overlay, overlay_options, pixel_grid_overlay};
 use crate::messages::portfolio::document::properties_panel::utility_types::PropertiesPanelMessageHandlerData;
 use crate::messages::portfolio::document::utility_types::document_metadata::{DocumentMetadata, LayerNodeIdentifier};
-use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, DocumentMode, FlipAxis, PTZ};
+use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, DocumentMode, FlipAxis, PTZ, RepeatableAction};
 use crate::messages::portfolio::document::utility_types::network_interface::{FlowType, InputConnector, NodeTemplate};
 use crate::messages::portfolio::document::utility_types::nodes::RawBuffer;
+use crate::messages::portfolio::document::utility_types::numeric_transform::{self, TransformField};
 use crate::messages::portfolio::utility_types::PersistentData;
 use crate::messages::prelude::*;
-use crate::messages::tool::common_functionality::graph_modification_utils::{self, get_blend_mode, get_opacity};
+use crate::messages::tool::common_functionality::graph_modification_utils::{self, NodeGraphLayer, get_blend_mode, get_opacity};
+use crate::messages::tool::common_functionality::utility_functions::{placeholder_text, text_frames, text_outlines};
 use crate::messages::tool::tool_messages::select_tool::SelectToolPointerKeys;
 use crate::messages::tool::tool_messages::tool_prelude::Key;
-use crate::messages::tool::utility_types::ToolType;
+use crate::messages::tool::utility_types::{HintData, HintGroup, HintInfo, ToolType};
 use crate::node_graph_executor::NodeGraphExecutor;
 use bezier_rs::Subpath;
 use glam::{DAffine2, DVec2, IVec2};
 use graph_craft::document::value::TaggedValue;
 use graph_craft::document::{NodeId, NodeInput, NodeNetwork, OldNodeNetwork};
+use graphene_core::Color;
 use graphene_core::raster::BlendMode;
 use graphene_core::raster::image::ImageFrameTable;
-use graphene_core::vector::style::ViewMode;
-use graphene_std::renderer::{ClickTarget, Quad};
+use graphene_core::text::{Font, FontCache};
+use graphene_core::vector::style::{Fill, FillChoice, ViewMode};
+use graphene_std::image_color_palette;
+use graphene_std::renderer::{ClickTarget, ColorManagement, ColorProfile, ColorVisionDeficiency, ProofProfile, Quad};
 use graphene_std::vector::{PointId, path_bool_lib};
 use std::time::Duration;
 
@@ -93,6 +107,23 @@ pub struct DocumentMessageHandler {
 	pub graph_view_overlay_open: bool,
 	/// The current opacity of the faded node graph background that covers up the artwork.
 	pub graph_fade_artwork_percentage: f64,
+	/// The custom rectangle (in document space) used when exporting with the "Custom Region" bounds, if the user has defined one.
+	pub export_region: Option<[DVec2; 2]>,
+	/// The named colors saved with the document, which can be imported from and exported to palette files.
+	pub swatches: Vec<Swatch>,
+	/// The color space the document's colors are specified in, which the viewport converts to the display's colors.
+	pub color_profile: ColorProfile,
+	/// The printing condition simulated in the viewport while proofing colors.
+	pub proof_profile: ProofProfile,
+	/// Sets whether the viewport simulates how the artwork will look once printed with the proof profile.
+	pub proof_colors: bool,
+	/// The keyframes that animate the document's node inputs over time, and the length of the animation.
+	pub timeline: Timeline,
+	/// Sets whether the document is saved as indented JSON with its object keys and node IDs in sorted order,
+	/// so the same document always saves the same way and changes to it diff and merge reasonably when it's kept under version control.
+	pub diff_friendly_format: bool,
+	/// The layers bound to the columns of a dataset, which make a copy of their artboard for each of its records.
+	pub data_merge: DataMerge,
 
 	// =============================================
 	// Fields omitted from the saved document format
@@ -106,10 +137,10 @@ pub struct DocumentMessageHandler {
 	selection_network_path: Vec<NodeId>,
 	/// Stack of document network snapshots for previous history states.
 	#[serde(skip)]
-	document_undo_history: VecDeque<NodeNetworkInterface>,
+	document_undo_history: DocumentHistory,
 	/// Stack of document network snapshots for future history states.
 	#[serde(skip)]
-	document_redo_history: VecDeque<NodeNetworkInterface>,
+	document_redo_history: DocumentHistory,
 	/// Hash of the document snapshot that was most recently saved to disk by the user.
 	#[serde(skip)]
 	saved_hash: Option<u64>,
@@ -120,11 +151,43 @@ pub struct DocumentMessageHandler {
 	/// If the user clicks or Ctrl-clicks one layer, it becomes the start of the range selection and then Shift-clicking another layer selects all layers between the start and end.
 	#[serde(skip)]
 	layer_range_selection_reference: Option<LayerNodeIdentifier>,
+	/// The most recent transformation or duplication, which can be applied again to the current selection.
+	#[serde(skip)]
+	last_repeatable_action: Option<RepeatableAction>,
+	/// The step of the undo history when the current undo group started, whose later steps are merged into one when it ends.
+	#[serde(skip)]
+	undo_group_start: Option<u64>,
+	/// The hash of the network when the linked text frames in the document metadata were last worked out, so they're only typeset again once it changes.
+	#[serde(skip)]
+	text_frames_hash: Option<u64>,
+	/// The export region being drawn by dragging on the canvas, which is started from the export dialog.
+	#[serde(skip)]
+	export_region_drawing: Option<ExportRegionDrawing>,
+	/// The color vision deficiency simulated in the viewport, if any, to check the artwork's accessibility.
+	/// This only changes how the artwork is viewed, so it's left out of the document and reset when it's reopened.
+	#[serde(skip)]
+	pub color_vision_simulation: Option<ColorVisionDeficiency>,
+	/// The bounds (in document space) of each layer's artwork as of its last render, which culls the layers outside the viewport from the renders that follow until they're scrolled into view.
+	/// They're only known once a layer has been rendered, so every layer is evaluated the first time the document is rendered after it's opened.
+	#[serde(skip)]
+	pub layer_bounds: HashMap<NodeId, [DVec2; 2]>,
+	/// The image layers opened in another application, whose files are watched for changes until the layer is deleted or the document is closed.
+	#[serde(skip)]
+	externally_edited_layers: HashSet<LayerNodeIdentifier>,
 	/// Whether or not the editor has executed the network to render the document yet. If this is opened as an inactive tab, it won't be loaded initially because the active tab is prioritized.
 	#[serde(skip)]
 	pub is_loaded: bool,
 }
 
+/// The state of drawing the export region by dragging on the canvas.
+#[derive(Clone, Copy, Debug)]
+struct ExportRegionDrawing {
+	/// The point (in document space) where the drag started, once it has.
+	start: Option<DVec2>,
+	/// The export region from before it was drawn, which is restored if drawing is cancelled.
+	previous_region: Option<[DVec2; 2]>,
+}
+
 impl Default for DocumentMessageHandler {
 	fn default() -> Self {
 		Self {
@@ -150,16 +213,31 @@ impl Default for DocumentMessageHandler {
 			graph_view_overlay_open: false,
 			snapping_state: SnappingState::default(),
 			graph_fade_artwork_percentage: 80.,
+			export_region: None,
+			swatches: Vec::new(),
+			color_profile: ColorProfile::default(),
+			proof_profile: ProofProfile::default(),
+			proof_colors: false,
+			timeline: Timeline::default(),
+			diff_friendly_format: false,
+			data_merge: DataMerge::default(),
 			// =============================================
 			// Fields omitted from the saved document format
 			// =============================================
 			breadcrumb_network_path: Vec::new(),
 			selection_network_path: Vec::new(),
-			document_undo_history: VecDeque::new(),
-			document_redo_history: VecDeque::new(),
+			document_undo_history: DocumentHistory::default(),
+			document_redo_history: DocumentHistory::default(),
 			saved_hash: None,
 			auto_saved_hash: None,
 			layer_range_selection_reference: None,
+			last_repeatable_action: None,
+			undo_group_start: None,
+			text_frames_hash: None,
+			export_region_drawing: None,
+			color_vision_simulation: None,
+			layer_bounds: HashMap::new(),
+			externally_edited_layers: HashSet::new(),
 			is_loaded: false,
 		}
 	}
@@ -206,6 +284,8 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 						overlays_visible,
 						ipp,
 						device_pixel_ratio,
+						render_statistics: &mut executor.render_statistics,
+						overlay_colors: &preferences.overlay_colors,
 					},
 				);
 			}
@@ -214,6 +294,7 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					network_interface: &mut self.network_interface,
 					selection_network_path: &self.selection_network_path,
 					document_name: self.name.as_str(),
+					swatches: &self.swatches,
 					executor,
 				};
 				self.properties_panel_message_handler
@@ -246,6 +327,25 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				let mut graph_operation_message_handler = GraphOperationMessageHandler {};
 				graph_operation_message_handler.process_message(message, responses, data);
 			}
+			DocumentMessage::AddExtractedSwatches { image, count } => {
+				let palette = image_color_palette::cluster_palette(&image.data, count as usize);
+				if palette.is_empty() {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Unable to extract swatches".to_string(),
+						description: "The selected layer has no visible colors to extract as swatches.".to_string(),
+					});
+					return;
+				}
+
+				self.add_history_step(preferences.undo_history_length, responses);
+				self.swatches.extend(palette.into_iter().map(|color| Swatch::new(format!("#{}", color.to_rgb_hex_srgb()), color)));
+				responses.add(PropertiesPanelMessage::Refresh);
+			}
+			DocumentMessage::AddSwatches { colors } => {
+				self.add_history_step(preferences.undo_history_length, responses);
+				self.swatches.extend(colors.into_iter().map(|color| Swatch::new(format!("#{}", color.to_rgb_hex_srgb()), color)));
+				responses.add(PropertiesPanelMessage::Refresh);
+			}
 			DocumentMessage::AlignSelectedLayers { axis, aggregate } => {
 				let axis = match axis {
 					AlignAxis::X => DVec2::X,
@@ -284,6 +384,32 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					});
 				}
 			}
+			DocumentMessage::ApplyGradient { stops, layer, mouse } => {
+				let layers: Vec<_> = match (layer, mouse) {
+					(Some(layer), _) => vec![layer],
+					(None, Some(mouse)) => self.click_at(ipp, DVec2::from(mouse) - ipp.viewport_bounds.top_left).into_iter().collect(),
+					(None, None) => self.network_interface.selected_nodes().selected_unlocked_layers(&self.network_interface).collect(),
+				};
+				if layers.is_empty() {
+					return;
+				}
+
+				responses.add(DocumentMessage::AddTransaction);
+				for layer in layers {
+					// Keep the placement of a gradient the layer already has
+					let existing_gradient = graph_modification_utils::get_gradient(layer, &self.network_interface);
+					let fill = FillChoice::Gradient(stops.clone()).to_fill(existing_gradient.as_ref());
+					responses.add(GraphOperationMessage::FillSet { layer, fill });
+				}
+			}
+			DocumentMessage::ApplyGradientPreset { name, layer, mouse } => {
+				let Some(preset) = preferences.gradient_preset(&name) else { return };
+				responses.add(DocumentMessage::ApplyGradient {
+					stops: preset.stops.clone(),
+					layer,
+					mouse,
+				});
+			}
 			DocumentMessage::RemoveArtboards => {
 				responses.add(GraphOperationMessage::RemoveArtboards);
 			}
@@ -298,6 +424,46 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					layout_target: LayoutTarget::LayersPanelControlBar,
 				});
 			}
+			DocumentMessage::ConvertSelectedTextToPaths => {
+				let text_layers: Vec<_> = self
+					.network_interface
+					.selected_nodes()
+					.selected_layers(self.metadata())
+					.filter(|&layer| graph_modification_utils::is_layer_fed_by_node_of_name(layer, &self.network_interface, "Text"))
+					.collect();
+				if text_layers.is_empty() {
+					return;
+				}
+
+				responses.add(DocumentMessage::AddTransaction);
+				for layer in text_layers {
+					let Some(vector_data) = text_outlines(layer, self, &persistent_data.font_cache) else { continue };
+					responses.add(GraphOperationMessage::ConvertTextToPaths { layer, vector_data });
+				}
+			}
+			DocumentMessage::FillSelectedTextWithPlaceholder => {
+				let placeholders: Vec<_> = self
+					.network_interface
+					.selected_nodes()
+					.selected_layers(self.metadata())
+					.filter_map(|layer| {
+						let text_node = graph_modification_utils::get_text_id(layer, &self.network_interface)?;
+						Some((text_node, placeholder_text(layer, self, &persistent_data.font_cache)?))
+					})
+					.collect();
+				if placeholders.is_empty() {
+					return;
+				}
+
+				responses.add(DocumentMessage::AddTransaction);
+				for (text_node, text) in placeholders {
+					responses.add(NodeGraphMessage::SetInput {
+						input_connector: InputConnector::node(text_node, 1),
+						input: NodeInput::value(TaggedValue::String(text), false),
+					});
+				}
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+			}
 			DocumentMessage::CreateEmptyFolder => {
 				let selected_nodes = self.network_interface.selected_nodes();
 				let id = NodeId::new();
@@ -336,14 +502,26 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![] });
 				self.layer_range_selection_reference = None;
 			}
-			DocumentMessage::DocumentHistoryBackward => self.undo_with_history(ipp, responses),
-			DocumentMessage::DocumentHistoryForward => self.redo_with_history(ipp, responses),
+			DocumentMessage::DocumentHistoryBackward => self.undo_with_history(ipp, preferences.undo_history_length, responses),
+			DocumentMessage::DocumentHistoryForward => self.redo_with_history(ipp, preferences.undo_history_length, responses),
 			DocumentMessage::DocumentStructureChanged => {
 				self.update_layers_panel_control_bar_widgets(responses);
 
 				self.network_interface.load_structure();
 				let data_buffer: RawBuffer = self.serialize_root();
 				responses.add(FrontendMessage::UpdateDocumentLayerStructure { data_buffer });
+
+				// Stop watching the files of image layers which have been deleted
+				let deleted_layers: Vec<_> = self
+					.externally_edited_layers
+					.iter()
+					.copied()
+					.filter(|&layer| !self.metadata().layer_exists(layer) || graph_modification_utils::get_image(layer, &self.network_interface).is_none())
+					.collect();
+				for layer in deleted_layers {
+					self.externally_edited_layers.remove(&layer);
+					responses.add(FrontendMessage::TriggerStopEditingImageExternally { document_id, layer: layer.to_node() });
+				}
 			}
 			DocumentMessage::DrawArtboardOverlays(overlay_context) => {
 				for layer in self.metadata().all_layers() {
@@ -361,9 +539,64 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 
 					overlay_context.text(&name, COLOR_OVERLAY_GRAY, None, transform, 0., [Pivot::Start, Pivot::End]);
 				}
+
+				if let Some(region) = self.export_region {
+					let quad = self.metadata().document_to_viewport * Quad::from_box(region);
+					overlay_context.dashed_quad(quad, None, Some(4.), Some(4.), None);
+
+					let transform = self.metadata().document_to_viewport
+						* DAffine2::from_translation(region[0].min(region[1]))
+						* DAffine2::from_scale(DVec2::splat(self.document_ptz.zoom().recip()))
+						* DAffine2::from_translation(-DVec2::Y * 4.);
+					overlay_context.text("Export Region", COLOR_OVERLAY_GRAY, None, transform, 0., [Pivot::Start, Pivot::End]);
+				}
+			}
+			DocumentMessage::DrawExportRegion => {
+				self.export_region_drawing = Some(ExportRegionDrawing {
+					start: None,
+					previous_region: self.export_region,
+				});
+
+				responses.add(FrontendMessage::UpdateMouseCursor { cursor: MouseCursorIcon::Crosshair });
+				responses.add(FrontendMessage::UpdateInputHints {
+					hint_data: HintData(vec![
+						HintGroup(vec![HintInfo::mouse(MouseMotion::LmbDrag, "Draw Export Region")]),
+						HintGroup(vec![HintInfo::mouse(MouseMotion::Rmb, ""), HintInfo::keys([Key::Escape], "Cancel").prepend_slash()]),
+					]),
+				});
+			}
+			DocumentMessage::DrawExportRegionPointerDown => {
+				let point = self.metadata().document_to_viewport.inverse().transform_point2(ipp.mouse.position);
+				let Some(drawing) = &mut self.export_region_drawing else { return };
+
+				drawing.start = Some(point);
+				self.export_region = Some([point, point]);
+				responses.add(OverlaysMessage::Draw);
+			}
+			DocumentMessage::DrawExportRegionPointerMove => {
+				let Some(start) = self.export_region_drawing.and_then(|drawing| drawing.start) else { return };
+
+				let point = self.metadata().document_to_viewport.inverse().transform_point2(ipp.mouse.position);
+				self.export_region = Some([start.min(point), start.max(point)]);
+				responses.add(OverlaysMessage::Draw);
+			}
+			DocumentMessage::DrawExportRegionPointerUp => {
+				let Some(drawing) = self.export_region_drawing.take() else { return };
+
+				// A click without a drag leaves the region as it was
+				let region = self.export_region.filter(|[min, max]| (max.x - min.x) * (max.y - min.y) > 0.).or(drawing.previous_region);
+				self.export_region = drawing.previous_region;
+				self.finish_drawing_export_region(region, responses);
+			}
+			DocumentMessage::DrawExportRegionAbort => {
+				let Some(drawing) = self.export_region_drawing.take() else { return };
+
+				self.export_region = drawing.previous_region;
+				self.finish_drawing_export_region(drawing.previous_region, responses);
 			}
 			DocumentMessage::DuplicateSelectedLayers => {
 				responses.add(DocumentMessage::AddTransaction);
+				self.last_repeatable_action = Some(RepeatableAction::Duplicate { transform: DAffine2::IDENTITY });
 
 				let mut new_dragging = Vec::new();
 				let mut layers = self.network_interface.shallowest_unique_layers(&[]).collect::<Vec<_>>();
@@ -404,6 +637,27 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				responses.add(NodeGraphMessage::SelectedNodesSet { nodes });
 				responses.add(NodeGraphMessage::RunDocumentGraph);
 			}
+			DocumentMessage::EditImageExternally => {
+				let mut selected_layers = self.network_interface.selected_nodes().selected_layers(self.metadata());
+				let layer = selected_layers.next().filter(|_| selected_layers.next().is_none());
+				let image = layer.and_then(|layer| Some((layer, graph_modification_utils::get_image(layer, &self.network_interface)?)));
+				let Some((layer, image)) = image else {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Unable to edit image externally".to_string(),
+						description: "Select a single image layer to edit it in another application.".to_string(),
+					});
+					return;
+				};
+
+				self.externally_edited_layers.insert(layer);
+				responses.add(FrontendMessage::TriggerEditImageExternally {
+					document_id,
+					layer: layer.to_node(),
+					name: self.network_interface.frontend_display_name(&layer.to_node(), &[]),
+					data: image.one_instance().instance.to_png(),
+					editor: preferences.external_image_editor.clone(),
+				});
+			}
 			DocumentMessage::EnterNestedNetwork { node_id } => {
 				self.breadcrumb_network_path.push(node_id);
 				self.selection_network_path.clone_from(&self.breadcrumb_network_path);
@@ -440,6 +694,45 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				responses.add(NodeGraphMessage::SetGridAlignedEdges);
 				responses.add(NodeGraphMessage::SendGraph);
 			}
+			DocumentMessage::ExportSwatches { format } => {
+				let data = swatches::export_palette(&self.swatches, &self.name, format);
+				let file_suffix = format!(".{}", format.file_extension());
+				let name = match self.name.ends_with(FILE_SAVE_SUFFIX) {
+					true => self.name.replace(FILE_SAVE_SUFFIX, &file_suffix),
+					false => self.name.clone() + &file_suffix,
+				};
+				let mime = format.to_mime().to_string();
+
+				responses.add(FrontendMessage::TriggerDownloadBinaryFile { data, name, mime, export_id: None });
+			}
+			DocumentMessage::ExtractSwatches { count } => {
+				if self.network_interface.selected_nodes().selected_layers(self.metadata()).count() != 1 {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Unable to extract swatches".to_string(),
+						description: "Select a single layer to extract the colors of its artwork as swatches.".to_string(),
+					});
+					return;
+				}
+
+				// The layer is rendered on its own and rasterized by the frontend, which sends its pixels back to have the swatches extracted from them
+				if let Err(error) = executor.submit_swatch_extraction_render(self, count) {
+					warn!("The selected layer couldn't be rendered to extract swatches from: {error}");
+				}
+			}
+			DocumentMessage::FillSelectedLayers { colors } => {
+				let layers: Vec<_> = self.network_interface.selected_nodes().selected_unlocked_layers(&self.network_interface).collect();
+				if layers.is_empty() || colors.is_empty() {
+					return;
+				}
+
+				responses.add(DocumentMessage::AddTransaction);
+				for (layer, color) in layers.into_iter().zip(colors.iter().cycle()) {
+					responses.add(GraphOperationMessage::FillSet {
+						layer,
+						fill: Fill::Solid(color.to_gamma_srgb()),
+					});
+				}
+			}
 			DocumentMessage::FlipSelectedLayers { flip_axis } => {
 				let scale = match flip_axis {
 					FlipAxis::X => DVec2::new(-1., 1.),
@@ -507,6 +800,8 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					responses.add(NodeGraphMessage::SetGridAlignedEdges);
 					responses.add(NodeGraphMessage::UpdateGraphBarRight);
 					responses.add(NodeGraphMessage::SendGraph);
+					// Generate the thumbnails of layers hidden in the Layers panel, since they're shown in the graph
+					responses.add(NodeGraphMessage::RunDocumentGraph);
 				} else {
 					responses.add(ToolMessage::ActivateTool { tool_type: *current_tool });
 				}
@@ -521,6 +816,9 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				responses.add(PortfolioMessage::UpdateDocumentWidgets);
 			}
 			DocumentMessage::GridOverlays(mut overlay_context) => {
+				if self.view_mode == ViewMode::Pixels {
+					pixel_grid_overlay(self, &mut overlay_context)
+				}
 				if self.snapping_state.grid_snapping {
 					grid_overlay(self, &mut overlay_context)
 				}
@@ -608,6 +906,17 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					responses.add(DocumentMessage::ImaginateGenerate { imaginate_node });
 				}
 			}
+			DocumentMessage::ImportPalette { data } => match swatches::import_palette(&data) {
+				Ok(imported) => {
+					self.add_history_step(preferences.undo_history_length, responses);
+					self.swatches.extend(imported);
+					responses.add(PropertiesPanelMessage::Refresh);
+				}
+				Err(description) => responses.add(DialogMessage::DisplayDialogError {
+					title: "Unable to import palette".to_string(),
+					description,
+				}),
+			},
 			DocumentMessage::MoveSelectedLayersTo { parent, insert_index } => {
 				if !self.selection_network_path.is_empty() {
 					log::error!("Moving selected layers is only supported for the Document Network");
@@ -716,11 +1025,15 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 			DocumentMessage::NudgeSelectedLayers {
 				delta_x,
 				delta_y,
+				large,
 				resize,
 				resize_opposite_corner,
 			} => {
 				responses.add(DocumentMessage::AddTransaction);
 
+				let distance = preferences.nudge_distance(large);
+				let (delta_x, delta_y) = (delta_x * distance, delta_y * distance);
+
 				let resize = ipp.keyboard.key(resize);
 				let resize_opposite_corner = ipp.keyboard.key(resize_opposite_corner);
 
@@ -732,6 +1045,9 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				// Nudge translation without resizing
 				if !resize {
 					let transform = DAffine2::from_translation(DVec2::from_angle(-self.document_ptz.tilt()).rotate(DVec2::new(delta_x, delta_y)));
+					responses.add(DocumentMessage::RecordRepeatableAction {
+						action: RepeatableAction::Transform { transform },
+					});
 
 					for layer in self.network_interface.shallowest_unique_layers(&[]).filter(|layer| can_move(*layer)) {
 						responses.add(GraphOperationMessage::TransformChange {
@@ -796,6 +1112,66 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					});
 				}
 			}
+			DocumentMessage::NumericTransformSelectedLayers { field, expression } => {
+				let Some(current) = self.transform_bar_value(field) else { return };
+				let Some(value) = numeric_transform::evaluate_field_expression(&expression, field, current) else {
+					// Put back the current value in place of the invalid expression
+					responses.add(DocumentMessage::RenderTransformBar);
+					return;
+				};
+				let Some([min, max]) = self.network_interface.selected_bounds_document_space(false, &[]) else {
+					return;
+				};
+
+				let around = |pivot: DVec2, transform: DAffine2| DAffine2::from_translation(pivot) * transform * DAffine2::from_translation(-pivot);
+				let shear = |amount: f64| DAffine2::from_cols_array(&[1., 0., amount, 1., 0., 0.]);
+				let size = max - min;
+				let center = (min + max) / 2.;
+
+				let transformation = match field {
+					TransformField::X => DAffine2::from_translation(DVec2::new(value - min.x, 0.)),
+					TransformField::Y => DAffine2::from_translation(DVec2::new(0., value - min.y)),
+					TransformField::Width if size.x.abs() > f64::EPSILON => around(min, DAffine2::from_scale(DVec2::new(value / size.x, 1.))),
+					TransformField::Height if size.y.abs() > f64::EPSILON => around(min, DAffine2::from_scale(DVec2::new(1., value / size.y))),
+					TransformField::Width | TransformField::Height => return,
+					TransformField::Rotation => around(center, DAffine2::from_angle((value - current).to_radians())),
+					// A single layer is skewed along its own horizontal axis, around the center of its own bounds
+					TransformField::Skew => match self.single_selected_layer_transform() {
+						Some((layer, layer_to_document)) => {
+							let [local_min, local_max] = self.metadata().nonzero_bounding_box(layer);
+							let local_skew = around((local_min + local_max) / 2., shear(value.to_radians().tan() - current.to_radians().tan()));
+							layer_to_document * local_skew * layer_to_document.inverse()
+						}
+						None => around(center, shear(value.to_radians().tan())),
+					},
+				};
+				if transformation.abs_diff_eq(DAffine2::IDENTITY, 1e-10) || !transformation.is_finite() {
+					return;
+				}
+
+				responses.add(DocumentMessage::AddTransaction);
+				responses.add(DocumentMessage::RecordRepeatableAction {
+					action: RepeatableAction::Transform { transform: transformation },
+				});
+
+				let document_to_viewport = self.metadata().document_to_viewport;
+				let selected = self.network_interface.selected_nodes();
+				let layers = self
+					.network_interface
+					.shallowest_unique_layers(&[])
+					.filter(|&layer| selected.layer_visible(layer, &self.network_interface) && !selected.layer_locked(layer, &self.network_interface))
+					.filter(|&layer| !self.network_interface.is_artboard(&layer.to_node(), &[]));
+				for layer in layers {
+					let to = document_to_viewport.inverse() * self.metadata().downstream_transform_to_viewport(layer);
+					let original_transform = self.metadata().upstream_transform(layer.to_node());
+					responses.add(GraphOperationMessage::TransformSet {
+						layer,
+						transform: to.inverse() * transformation * to * original_transform,
+						transform_in: TransformIn::Local,
+						skip_rerender: false,
+					});
+				}
+			}
 			DocumentMessage::PasteImage {
 				name,
 				image,
@@ -888,6 +1264,28 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![layer.to_node()] });
 				responses.add(ToolMessage::ActivateTool { tool_type: ToolType::Select });
 			}
+			DocumentMessage::RecordRepeatableAction { action } => {
+				// A transform right after duplicating is repeated along with the duplication, to step and repeat the copies
+				let action = match (self.last_repeatable_action, action) {
+					(Some(RepeatableAction::Duplicate { transform: previous }), RepeatableAction::Transform { transform }) if previous == DAffine2::IDENTITY => {
+						RepeatableAction::Duplicate { transform }
+					}
+					(_, action) => action,
+				};
+				self.last_repeatable_action = Some(action);
+			}
+			DocumentMessage::RecolorArtwork { replacements } => {
+				let replacements: Vec<_> = replacements.into_iter().filter(|(original, replacement)| original != replacement).collect();
+				if replacements.is_empty() {
+					return;
+				}
+
+				responses.add(DocumentMessage::AddTransaction);
+				for layer in self.selected_layers_with_descendants() {
+					recolor::recolor_layer(layer, &self.network_interface, &replacements, responses);
+				}
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+			}
 			DocumentMessage::Redo => {
 				if self.network_interface.transaction_status() != TransactionStatus::Finished {
 					return;
@@ -971,6 +1369,86 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					multiplier: scrollbar_multiplier.into(),
 				});
 			}
+			DocumentMessage::RenderTransformBar => {
+				let has_selection = !self.graph_view_overlay_open && self.network_interface.selected_bounds_document_space(false, &[]).is_some();
+
+				let mut widgets = vec![Separator::new(SeparatorType::Section).widget_holder()];
+				for field in TransformField::list() {
+					let separator = match field {
+						TransformField::X => None,
+						TransformField::Width | TransformField::Rotation => Some(SeparatorType::Unrelated),
+						_ => Some(SeparatorType::Related),
+					};
+					if let Some(separator) = separator {
+						widgets.push(Separator::new(separator).widget_holder());
+					}
+
+					let value = self.transform_bar_value(field).map(|value| field.format_value(value)).unwrap_or_default();
+					widgets.extend([
+						TextLabel::new(field.label()).tooltip(field.tooltip()).widget_holder(),
+						Separator::new(SeparatorType::Related).widget_holder(),
+						TextInput::new(value)
+							.tooltip(format!(
+								"{}\n\nAccepts math expressions with units, such as \"50%+12px\", where percentages are of the current value.",
+								field.tooltip()
+							))
+							.min_width(if field.is_angle() { 56 } else { 72 })
+							.centered(true)
+							.disabled(!has_selection)
+							.on_update(move |text_input: &TextInput| {
+								DocumentMessage::NumericTransformSelectedLayers {
+									field,
+									expression: text_input.value.clone(),
+								}
+								.into()
+							})
+							.widget_holder(),
+					]);
+				}
+
+				responses.add(LayoutMessage::SendLayout {
+					layout: Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }])),
+					layout_target: LayoutTarget::TransformBar,
+				});
+			}
+			DocumentMessage::RepeatLastAction => match self.last_repeatable_action {
+				Some(RepeatableAction::Transform { transform }) => {
+					responses.add(DocumentMessage::AddTransaction);
+					responses.add(DocumentMessage::RepeatTransform { transform });
+				}
+				Some(RepeatableAction::Duplicate { transform }) => {
+					responses.add(DocumentMessage::DuplicateSelectedLayers);
+					responses.add(DocumentMessage::RepeatTransform { transform });
+					responses.add(DocumentMessage::RecordRepeatableAction {
+						action: RepeatableAction::Duplicate { transform },
+					});
+				}
+				None => {}
+			},
+			DocumentMessage::RepeatTransform { transform } => {
+				if transform == DAffine2::IDENTITY {
+					return;
+				}
+				let Some([min, max]) = self.selected_visible_and_unlock_layers_bounding_box_viewport() else {
+					return;
+				};
+
+				// Convert the document space transform to viewport space around the center of the selection
+				let center = (max + min) / 2.;
+				let document_to_viewport = DAffine2::from_mat2(self.metadata().document_to_viewport.matrix2);
+				let transform = document_to_viewport * transform * document_to_viewport.inverse();
+
+				for layer in self.network_interface.selected_nodes().selected_unlocked_layers(&self.network_interface) {
+					responses.add(GraphOperationMessage::TransformChange {
+						layer,
+						transform,
+						transform_in: TransformIn::Scope {
+							scope: DAffine2::from_translation(-center),
+						},
+						skip_rerender: false,
+					});
+				}
+			}
 			DocumentMessage::SaveDocument => {
 				self.set_save_state(true);
 				responses.add(PortfolioMessage::AutoSaveActiveDocument);
@@ -984,7 +1462,9 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				responses.add(FrontendMessage::TriggerDownloadTextFile {
 					document: self.serialize_document(),
 					name,
-				})
+					export_id: None,
+				});
+				responses.add(PortfolioMessage::RunSaveActions { document_id });
 			}
 			DocumentMessage::SelectParentLayer => {
 				let selected_nodes = self.network_interface.selected_nodes();
@@ -1114,6 +1594,21 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					responses.add(GraphOperationMessage::BlendModeSet { layer, blend_mode });
 				}
 			}
+			DocumentMessage::RemoveSwatch { index } => {
+				if index < self.swatches.len() {
+					self.add_history_step(preferences.undo_history_length, responses);
+					self.swatches.remove(index);
+					responses.add(PropertiesPanelMessage::Refresh);
+				}
+			}
+			DocumentMessage::SetExportRegion { region } => {
+				let region = region.map(|[a, b]| [a.min(b), a.max(b)]);
+				if region != self.export_region {
+					self.export_region = region;
+					self.mark_as_unsaved(responses);
+				}
+				responses.add(OverlaysMessage::Draw);
+			}
 			DocumentMessage::SetGraphFadeArtwork { percentage } => {
 				self.graph_fade_artwork_percentage = percentage;
 				responses.add(FrontendMessage::UpdateGraphFadeArtwork { percentage });
@@ -1144,6 +1639,25 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					*closure(&mut self.snapping_state) = snapping_state;
 				}
 			}
+			DocumentMessage::SetSwatchColor { index, color } => {
+				// The color picker adds the history step once when the edit starts, rather than for every color it sends while dragging
+				if let Some(swatch) = self.swatches.get_mut(index) {
+					swatch.color = color;
+					self.mark_as_unsaved(responses);
+					responses.add(PropertiesPanelMessage::Refresh);
+				}
+			}
+			DocumentMessage::SetSwatchSpotColor { index, spot, overprint } => {
+				if index < self.swatches.len() {
+					self.add_history_step(preferences.undo_history_length, responses);
+				}
+				if let Some(swatch) = self.swatches.get_mut(index) {
+					swatch.spot = spot;
+					// Overprinting only applies to the separate ink of a spot color
+					swatch.overprint = spot && overprint;
+					responses.add(PropertiesPanelMessage::Refresh);
+				}
+			}
 			DocumentMessage::SetToNodeOrLayer { node_id, is_layer } => {
 				responses.add(DocumentMessage::StartTransaction);
 				responses.add(NodeGraphMessage::SetToNodeOrLayer { node_id, is_layer });
@@ -1152,15 +1666,14 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 			DocumentMessage::SetViewMode { view_mode } => {
 				self.view_mode = view_mode;
 				responses.add_front(NodeGraphMessage::RunDocumentGraph);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+				responses.add(MenuBarMessage::SendLayout);
 			}
 			// Note: A transaction should never be started in a scope that mutates the network interface, since it will only be run after that scope ends.
 			DocumentMessage::StartTransaction => {
 				self.network_interface.start_transaction();
 				let network_interface_clone = self.network_interface.clone();
-				self.document_undo_history.push_back(network_interface_clone);
-				if self.document_undo_history.len() > crate::consts::MAX_UNDO_HISTORY_LEN {
-					self.document_undo_history.pop_front();
-				}
+				self.document_undo_history.push(network_interface_clone, self.document_state(), preferences.undo_history_length);
 				// Push the UpdateOpenDocumentsList message to the bus in order to update the save status of the open documents
 				responses.add(PortfolioMessage::UpdateOpenDocumentsList);
 			}
@@ -1201,6 +1714,14 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				responses.add_front(DocumentMessage::CommitTransaction);
 				responses.add_front(DocumentMessage::StartTransaction);
 			}
+			DocumentMessage::StartUndoGroup => {
+				self.undo_group_start = Some(self.document_undo_history.step());
+			}
+			DocumentMessage::EndUndoGroup => {
+				let Some(start) = self.undo_group_start.take() else { return };
+				let added = self.document_undo_history.step().saturating_sub(start) as usize;
+				self.document_undo_history.merge_newest(added);
+			}
 			DocumentMessage::ToggleLayerExpansion { id, recursive } => {
 				let layer = LayerNodeIdentifier::new(id, &self.network_interface, &[]);
 				let metadata = self.metadata();
@@ -1223,8 +1744,30 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				}
 
 				responses.add(NodeGraphMessage::SendGraph);
+				// Generate the thumbnails deferred while the layers were hidden
+				responses.add(NodeGraphMessage::RunDocumentGraph);
 			}
 			DocumentMessage::ToggleSelectedLocked => responses.add(NodeGraphMessage::ToggleSelectedLocked),
+			DocumentMessage::ToggleSelectedReferenceImages => {
+				let selected_layers = self.network_interface.selected_nodes().selected_layers_except_artboards(&self.network_interface).collect::<Vec<_>>();
+				// Only raster content can be shown as a reference image
+				let layers = selected_layers
+					.into_iter()
+					.filter(|&layer| NodeGraphLayer::is_raster_layer(layer, &mut self.network_interface))
+					.collect::<Vec<_>>();
+				if layers.is_empty() {
+					return;
+				}
+
+				// If all of the selected layers are reference images, turn them back into regular layers. Otherwise, make them all reference images.
+				let reference_image = !layers.iter().all(|&layer| graph_modification_utils::is_reference_image(layer, &self.network_interface));
+
+				responses.add(DocumentMessage::AddTransaction);
+				for layer in layers {
+					responses.add(GraphOperationMessage::ReferenceImageSet { layer, reference_image });
+				}
+				responses.add(NodeGraphMessage::SendGraph);
+			}
 			DocumentMessage::ToggleSelectedVisibility => {
 				responses.add(NodeGraphMessage::ToggleSelectedVisibility);
 			}
@@ -1238,6 +1781,43 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				responses.add(OverlaysMessage::Draw);
 				responses.add(PortfolioMessage::UpdateDocumentWidgets);
 			}
+			DocumentMessage::ToggleProofColors => {
+				self.proof_colors = !self.proof_colors;
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			DocumentMessage::ToggleDiffFriendlyFormat => {
+				self.diff_friendly_format = !self.diff_friendly_format;
+				self.mark_as_unsaved(responses);
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			DocumentMessage::SetColorProfile { color_profile } => {
+				if color_profile == self.color_profile {
+					return;
+				}
+				self.add_history_step(preferences.undo_history_length, responses);
+				self.color_profile = color_profile;
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			DocumentMessage::SetColorVisionSimulation { color_vision } => {
+				self.color_vision_simulation = color_vision;
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			DocumentMessage::SetProofProfile { proof_profile } => {
+				if proof_profile != self.proof_profile {
+					self.add_history_step(preferences.undo_history_length, responses);
+				}
+				self.proof_profile = proof_profile;
+				self.proof_colors = true;
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			DocumentMessage::ToggleOutlineViewMode => {
+				let view_mode = if self.view_mode == ViewMode::Outline { ViewMode::Normal } else { ViewMode::Outline };
+				responses.add(DocumentMessage::SetViewMode { view_mode });
+			}
 			DocumentMessage::ToggleSnapping => {
 				self.snapping_state.snapping_enabled = !self.snapping_state.snapping_enabled;
 				responses.add(PortfolioMessage::UpdateDocumentWidgets);
@@ -1263,6 +1843,17 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					})
 					.collect();
 				self.network_interface.update_click_targets(layer_click_targets);
+
+				// Layers without click targets keep the bounds they were last rendered with
+				let metadata = self.network_interface.document_metadata();
+				for &layer in metadata.click_targets.keys() {
+					// Rounding the bounds outward errs on the side of evaluating a layer right at the edge of the viewport
+					if let Some([min, max]) = metadata.bounding_box_document(layer) {
+						self.layer_bounds.insert(layer.to_node(), [min.floor(), max.ceil()]);
+					}
+				}
+				let network = self.network_interface.document_network();
+				self.layer_bounds.retain(|node_id, _| network.nodes.contains_key(node_id));
 			}
 			DocumentMessage::UpdateClipTargets { clip_targets } => {
 				self.network_interface.update_clip_targets(clip_targets);
@@ -1457,6 +2048,7 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 			SelectAllLayers,
 			SetSnapping,
 			ToggleGridVisibility,
+			ToggleOutlineViewMode,
 			ToggleOverlaysVisibility,
 			ToggleSnapping,
 			Undo,
@@ -1474,12 +2066,14 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				DeleteSelectedLayers,
 				DuplicateSelectedLayers,
 				GroupSelectedLayers,
+				RepeatLastAction,
 				SelectedLayersLower,
 				SelectedLayersLowerToBack,
 				SelectedLayersRaise,
 				SelectedLayersRaiseToFront,
 				UngroupSelectedLayers,
-				ToggleSelectedLocked
+				ToggleSelectedLocked,
+				ToggleSelectedReferenceImages,
 			);
 			if !self.graph_view_overlay_open {
 				select.extend(actions!(DocumentMessageDiscriminant;
@@ -1497,6 +2091,17 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 			));
 			common.extend(self.node_graph_handler.actions_additional_if_node_graph_is_open());
 		}
+		// Additional actions while the export region is being drawn on the canvas
+		if let Some(drawing) = self.export_region_drawing {
+			common.extend(actions!(DocumentMessageDiscriminant;
+				DrawExportRegionPointerDown,
+				DrawExportRegionPointerMove,
+				DrawExportRegionAbort,
+			));
+			if drawing.start.is_some() {
+				common.extend(actions!(DocumentMessageDiscriminant; DrawExportRegionPointerUp));
+			}
+		}
 		// More additional actions
 		common.extend(self.navigation_handler.actions());
 		common.extend(self.node_graph_handler.actions());
@@ -1572,8 +2177,13 @@ impl DocumentMessageHandler {
 
 	/// Find all of the layers that were clicked on from a viewport space location
 	pub fn click_xray(&self, ipp: &InputPreprocessorMessageHandler) -> impl Iterator<Item = LayerNodeIdentifier> + use<'_> {
+		self.click_xray_at(ipp, ipp.mouse.position)
+	}
+
+	/// Find all of the layers under a given location in viewport space
+	pub fn click_xray_at(&self, ipp: &InputPreprocessorMessageHandler, viewport_position: DVec2) -> impl Iterator<Item = LayerNodeIdentifier> + use<'_> {
 		let document_to_viewport = self.navigation_handler.calculate_offset_transform(ipp.viewport_bounds.center(), &self.document_ptz);
-		let point = document_to_viewport.inverse().transform_point2(ipp.mouse.position);
+		let point = document_to_viewport.inverse().transform_point2(viewport_position);
 		ClickXRayIter::new(&self.network_interface, XRayTarget::Point(point))
 	}
 
@@ -1594,7 +2204,12 @@ impl DocumentMessageHandler {
 
 	/// Find layers under the location in viewport space that was clicked, listed by their depth in the layer tree hierarchy.
 	pub fn click_list<'a>(&'a self, ipp: &InputPreprocessorMessageHandler) -> impl Iterator<Item = LayerNodeIdentifier> + use<'a> {
-		self.click_xray(ipp)
+		self.click_list_at(ipp, ipp.mouse.position)
+	}
+
+	/// Find layers under a given location in viewport space, listed by their depth in the layer tree hierarchy.
+	pub fn click_list_at<'a>(&'a self, ipp: &InputPreprocessorMessageHandler, viewport_position: DVec2) -> impl Iterator<Item = LayerNodeIdentifier> + use<'a> {
+		self.click_xray_at(ipp, viewport_position)
 			.filter(move |&layer| !self.network_interface.is_artboard(&layer.to_node(), &[]))
 			.skip_while(|&layer| layer == LayerNodeIdentifier::ROOT_PARENT)
 			.scan(true, |last_had_children, layer| {
@@ -1612,6 +2227,11 @@ impl DocumentMessageHandler {
 		self.click_list(ipp).last()
 	}
 
+	/// Find the deepest layer under a given location in viewport space.
+	pub fn click_at(&self, ipp: &InputPreprocessorMessageHandler, viewport_position: DVec2) -> Option<LayerNodeIdentifier> {
+		self.click_list_at(ipp, viewport_position).last()
+	}
+
 	/// Get the combined bounding box of the click targets of the selected visible layers in viewport space
 	pub fn selected_visible_layers_bounding_box_viewport(&self) -> Option<[DVec2; 2]> {
 		self.network_interface
@@ -1629,6 +2249,39 @@ impl DocumentMessageHandler {
 			.reduce(graphene_core::renderer::Quad::combine_bounds)
 	}
 
+	/// The layer and its transform to document space, if exactly one layer (which isn't an artboard) is selected.
+	fn single_selected_layer_transform(&self) -> Option<(LayerNodeIdentifier, DAffine2)> {
+		let mut layers = self
+			.network_interface
+			.selected_nodes()
+			.selected_layers(self.metadata())
+			.filter(|layer| !self.network_interface.is_artboard(&layer.to_node(), &[]));
+		let layer = layers.next()?;
+		if layers.next().is_some() {
+			return None;
+		}
+		Some((layer, self.metadata().transform_to_document(layer)))
+	}
+
+	/// The current value of a transform bar field for the selected layers, or `None` if no layers are selected.
+	/// Rotation and skew are those of a single selected layer, or zero for multiple layers since they are then applied relative to the selection.
+	fn transform_bar_value(&self, field: TransformField) -> Option<f64> {
+		let [min, max] = self.network_interface.selected_bounds_document_space(false, &[])?;
+		let (_, angle, _, shear) = self
+			.single_selected_layer_transform()
+			.map(|(_, transform)| transform_utils::compute_scale_angle_translation_shear(transform))
+			.unwrap_or_default();
+
+		Some(match field {
+			TransformField::X => min.x,
+			TransformField::Y => min.y,
+			TransformField::Width => max.x - min.x,
+			TransformField::Height => max.y - min.y,
+			TransformField::Rotation => angle.to_degrees(),
+			TransformField::Skew => shear.x.atan().to_degrees(),
+		})
+	}
+
 	pub fn document_network(&self) -> &NodeNetwork {
 		self.network_interface.document_network()
 	}
@@ -1637,12 +2290,63 @@ impl DocumentMessageHandler {
 		self.network_interface.document_metadata()
 	}
 
+	/// The selected layers followed by all the layers nested within them, each listed once.
+	fn selected_layers_with_descendants(&self) -> Vec<LayerNodeIdentifier> {
+		let metadata = self.metadata();
+		let mut layers = Vec::new();
+		for layer in self.network_interface.selected_nodes().selected_layers(metadata) {
+			for layer in std::iter::once(layer).chain(layer.descendants(metadata)) {
+				if !layers.contains(&layer) {
+					layers.push(layer);
+				}
+			}
+		}
+		layers
+	}
+
+	/// The distinct fill and stroke colors (in gamma space) of the selected layers and their descendants, in the order they're found.
+	pub fn selected_artwork_colors(&self) -> Vec<Color> {
+		let mut colors = Vec::new();
+		for layer in self.selected_layers_with_descendants() {
+			for color in recolor::layer_colors(layer, &self.network_interface) {
+				if !colors.contains(&color) {
+					colors.push(color);
+				}
+			}
+		}
+		colors
+	}
+
 	pub fn serialize_document(&self) -> String {
+		if self.diff_friendly_format {
+			return self.serialize_document_diff_friendly();
+		}
+
 		let val = serde_json::to_string(self);
 		// We fully expect the serialization to succeed
 		val.unwrap()
 	}
 
+	/// Serializes the document with every object's keys sorted and each value on its own line.
+	/// Maps keyed by IDs are already saved sorted by key, so only the order of the objects' keys is left to the order they were inserted in.
+	fn serialize_document_diff_friendly(&self) -> String {
+		fn sort_keys(value: &mut serde_json::Value) {
+			match value {
+				serde_json::Value::Object(map) => {
+					map.sort_keys();
+					map.values_mut().for_each(sort_keys);
+				}
+				serde_json::Value::Array(values) => values.iter_mut().for_each(sort_keys),
+				_ => {}
+			}
+		}
+
+		// We fully expect the serialization to succeed
+		let mut value = serde_json::to_value(self).unwrap();
+		sort_keys(&mut value);
+		serde_json::to_string_pretty(&value).unwrap()
+	}
+
 	pub fn deserialize_document(serialized_content: &str) -> Result<Self, EditorError> {
 		let document_message_handler = serde_json::from_str::<DocumentMessageHandler>(serialized_content)
 			.or_else(|_| {
@@ -1768,18 +2472,16 @@ impl DocumentMessageHandler {
 		structure_section.as_slice().into()
 	}
 
-	pub fn undo_with_history(&mut self, ipp: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) {
-		let Some(previous_network) = self.undo(ipp, responses) else { return };
+	pub fn undo_with_history(&mut self, ipp: &InputPreprocessorMessageHandler, history_length: usize, responses: &mut VecDeque<Message>) {
+		let Some((previous_network, previous_state)) = self.undo(ipp, responses) else { return };
 
-		self.document_redo_history.push_back(previous_network);
-		if self.document_redo_history.len() > crate::consts::MAX_UNDO_HISTORY_LEN {
-			self.document_redo_history.pop_front();
-		}
+		self.document_redo_history.push(previous_network, previous_state, history_length);
 	}
 
-	pub fn undo(&mut self, ipp: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) -> Option<NodeNetworkInterface> {
+	pub fn undo(&mut self, ipp: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) -> Option<(NodeNetworkInterface, DocumentState)> {
 		// If there is no history return and don't broadcast SelectionChanged
-		let mut network_interface = self.document_undo_history.pop_back()?;
+		let (mut network_interface, state) = self.document_undo_history.pop()?;
+		let previous_state = self.replace_document_state(state, responses);
 
 		// Set the previous network navigation metadata to the current navigation metadata
 		network_interface.copy_all_navigation_metadata(&self.network_interface);
@@ -1801,21 +2503,131 @@ impl DocumentMessageHandler {
 		// TODO: Remove once the footprint is used to load the imports/export distances from the edge
 		responses.add(NodeGraphMessage::SetGridAlignedEdges);
 		responses.add(Message::StartBuffer);
-		Some(previous_network)
+		Some((previous_network, previous_state))
 	}
-	pub fn redo_with_history(&mut self, ipp: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) {
-		// Push the UpdateOpenDocumentsList message to the queue in order to update the save status of the open documents
-		let Some(previous_network) = self.redo(ipp, responses) else { return };
+	/// Replaces the document's content with a copy changed by collaborators, keeping the view of it as it is.
+	pub fn apply_synced_changes(&mut self, mut network_interface: NodeNetworkInterface, ipp: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) {
+		network_interface.copy_all_navigation_metadata(&self.network_interface);
+		std::mem::swap(&mut network_interface.resolved_types, &mut self.network_interface.resolved_types);
 
-		self.document_undo_history.push_back(previous_network);
-		if self.document_undo_history.len() > crate::consts::MAX_UNDO_HISTORY_LEN {
-			self.document_undo_history.pop_front();
+		let transform = self.navigation_handler.calculate_offset_transform(ipp.viewport_bounds.center(), &self.document_ptz);
+		network_interface.set_document_to_viewport_transform(transform);
+		network_interface.load_structure();
+
+		self.network_interface = network_interface;
+
+		responses.add(PortfolioMessage::UpdateOpenDocumentsList);
+		responses.add(NodeGraphMessage::SelectedNodesUpdated);
+		responses.add(NodeGraphMessage::ForceRunDocumentGraph);
+		responses.add(NodeGraphMessage::SendGraph);
+	}
+
+	/// Copies the selected layers, along with everything feeding into them, in the order they're stacked and in the form they're put on the clipboard.
+	pub fn copy_selected_layers(&mut self) -> Vec<CopyBufferEntry> {
+		let mut ordered_last_elements = self.network_interface.shallowest_unique_layers(&[]).collect::<Vec<_>>();
+
+		ordered_last_elements.sort_by_key(|layer| {
+			let Some(parent) = layer.parent(self.metadata()) else { return usize::MAX };
+			DocumentMessageHandler::get_calculated_insert_index(self.metadata(), &SelectedNodes(vec![layer.to_node()]), parent)
+		});
+
+		let mut buffer = Vec::new();
+		for layer in ordered_last_elements.into_iter() {
+			let layer_node_id = layer.to_node();
+
+			let mut copy_ids = HashMap::new();
+			copy_ids.insert(layer_node_id, NodeId(0));
+
+			self.network_interface
+				.upstream_flow_back_from_nodes(vec![layer_node_id], &[], FlowType::LayerChildrenUpstreamFlow)
+				.enumerate()
+				.for_each(|(index, node_id)| {
+					copy_ids.insert(node_id, NodeId((index + 1) as u64));
+				});
+
+			buffer.push(CopyBufferEntry {
+				nodes: self.network_interface.copy_nodes(&copy_ids, &[]).collect(),
+				selected: self.network_interface.selected_nodes().selected_layers_contains(layer, self.metadata()),
+				visible: self.network_interface.selected_nodes().layer_visible(layer, &self.network_interface),
+				locked: self.network_interface.selected_nodes().layer_locked(layer, &self.network_interface),
+				collapsed: false,
+			});
 		}
+		buffer
+	}
+
+	/// Copies the nodes selected in the node graph, in the form they're put on the clipboard.
+	pub fn copy_selected_nodes(&mut self) -> Vec<(NodeId, NodeTemplate)> {
+		let all_selected_nodes = self.network_interface.upstream_chain_nodes(&self.selection_network_path);
+		let new_ids = &all_selected_nodes.iter().enumerate().map(|(new, old)| (*old, NodeId(new as u64))).collect();
+		self.network_interface.copy_nodes(new_ids, &self.selection_network_path).collect()
+	}
+
+	/// Replaces the document's content with changes merged into it from another version of the document, as a single step which can be undone.
+	pub fn apply_merged_changes(&mut self, network_interface: NodeNetworkInterface, ipp: &InputPreprocessorMessageHandler, history_length: usize, responses: &mut VecDeque<Message>) {
+		self.document_undo_history.push(self.network_interface.clone(), self.document_state(), history_length);
+		self.document_redo_history.clear();
+		self.apply_synced_changes(network_interface, ipp, responses);
+	}
+
+	/// Adds a step to the undo history for a change about to be made to the parts of the document kept outside of its node network.
+	/// Unlike [`DocumentMessage::AddTransaction`], this takes effect straight away, so the change can be made right after it in the same scope.
+	pub fn add_history_step(&mut self, history_length: usize, responses: &mut VecDeque<Message>) {
+		self.document_undo_history.push(self.network_interface.clone(), self.document_state(), history_length);
+		self.document_redo_history.clear();
+		// These changes aren't covered by the network's hash that the save state is based on
+		self.mark_as_unsaved(responses);
 	}
 
-	pub fn redo(&mut self, ipp: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) -> Option<NodeNetworkInterface> {
+	/// The parts of the document kept outside of its node network which are undone and redone along with it.
+	fn document_state(&self) -> DocumentState {
+		DocumentState {
+			swatches: self.swatches.clone(),
+			timeline: self.timeline.clone(),
+			color_profile: self.color_profile,
+			proof_profile: self.proof_profile,
+			data_merge: self.data_merge.clone(),
+		}
+	}
+
+	/// Restores the parts of the document kept outside of its node network from the undo or redo history, returning what they were before.
+	fn replace_document_state(&mut self, state: DocumentState, responses: &mut VecDeque<Message>) -> DocumentState {
+		let previous_state = self.document_state();
+		if state == previous_state {
+			return previous_state;
+		}
+
+		let DocumentState {
+			swatches,
+			timeline,
+			color_profile,
+			proof_profile,
+			data_merge,
+		} = state;
+		self.swatches = swatches;
+		self.timeline = timeline;
+		self.color_profile = color_profile;
+		self.proof_profile = proof_profile;
+		self.data_merge = data_merge;
+		responses.add(PropertiesPanelMessage::Refresh);
+		responses.add(TimelineMessage::SendLayout);
+		responses.add(MenuBarMessage::SendLayout);
+		responses.add(DataMergeMessage::SendLayout);
+
+		previous_state
+	}
+
+	pub fn redo_with_history(&mut self, ipp: &InputPreprocessorMessageHandler, history_length: usize, responses: &mut VecDeque<Message>) {
+		// Push the UpdateOpenDocumentsList message to the queue in order to update the save status of the open documents
+		let Some((previous_network, previous_state)) = self.redo(ipp, responses) else { return };
+
+		self.document_undo_history.push(previous_network, previous_state, history_length);
+	}
+
+	pub fn redo(&mut self, ipp: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) -> Option<(NodeNetworkInterface, DocumentState)> {
 		// If there is no history return and don't broadcast SelectionChanged
-		let mut network_interface = self.document_redo_history.pop_back()?;
+		let (mut network_interface, state) = self.document_redo_history.pop()?;
+		let previous_state = self.replace_document_state(state, responses);
 
 		// Set the previous network navigation metadata to the current navigation metadata
 		network_interface.copy_all_navigation_metadata(&self.network_interface);
@@ -1831,11 +2643,11 @@ impl DocumentMessageHandler {
 		responses.add(NodeGraphMessage::SelectedNodesUpdated);
 		responses.add(NodeGraphMessage::ForceRunDocumentGraph);
 
-		Some(previous_network)
+		Some((previous_network, previous_state))
 	}
 
 	pub fn current_hash(&self) -> Option<u64> {
-		self.document_undo_history.iter().last().map(|network| network.document_network().current_hash())
+		self.document_undo_history.newest().map(|network| network.document_network().current_hash())
 	}
 
 	pub fn is_auto_saved(&self) -> bool {
@@ -1850,6 +2662,68 @@ impl DocumentMessageHandler {
 		self.graph_view_overlay_open
 	}
 
+	/// The color transform applied to the viewport, from the document's color profile, the proof profile if proofing colors, and any simulated color vision deficiency.
+	pub fn color_management(&self) -> ColorManagement {
+		ColorManagement {
+			profile: self.color_profile,
+			proof: self.proof_colors.then_some(self.proof_profile),
+			color_vision: self.color_vision_simulation,
+		}
+	}
+
+	pub fn selection_network_path(&self) -> &[NodeId] {
+		&self.selection_network_path
+	}
+
+	/// Works out which text layers are linked and the text shown in each, unless the network hasn't changed since they were last worked out.
+	/// This is forced when a font has loaded, since the text might not have been typeset before.
+	pub fn update_text_frames(&mut self, font_cache: &FontCache, force: bool) {
+		let hash = self.network_interface.document_network().current_hash();
+		if !force && self.text_frames_hash == Some(hash) {
+			return;
+		}
+		self.text_frames_hash = Some(hash);
+
+		let text_frames = text_frames(&self.network_interface, font_cache);
+		self.network_interface.update_text_frames(text_frames);
+	}
+
+	/// A copy of the document's network as it's evaluated at the given time, or `None` if it's evaluated unchanged.
+	/// Keyframed inputs are set to their values at that time, and the Text nodes of linked text boxes are given the text overflowing the text box before them.
+	pub fn evaluated_network(&self, time: f64) -> Option<NodeNetwork> {
+		let text_frames = &self.metadata().text_frames;
+		let linked_frames = text_frames.iter().filter(|(_, frame)| frame.previous.is_some());
+		if self.timeline.tracks.is_empty() && linked_frames.clone().next().is_none() {
+			return None;
+		}
+
+		let mut network = self.network_interface.document_network().clone();
+		for track in &self.timeline.tracks {
+			let Some(value) = track.value_at(time) else { continue };
+			let Some(node) = network.nested_network_mut(&track.network_path).and_then(|network| network.nodes.get_mut(&track.node_id)) else {
+				continue;
+			};
+			// Inputs that have since been connected to another node are left alone
+			if let Some(NodeInput::Value { tagged_value, .. }) = node.inputs.get_mut(track.input_index) {
+				*tagged_value = value;
+			}
+		}
+
+		for (&layer, frame) in linked_frames {
+			let Some(node) = graph_modification_utils::get_text_id(layer, &self.network_interface).and_then(|node_id| network.nodes.get_mut(&node_id)) else {
+				continue;
+			};
+			if let Some(NodeInput::Value { tagged_value, .. }) = node.inputs.get_mut(1) {
+				*tagged_value = TaggedValue::String(frame.text.clone());
+			}
+			if let Some(NodeInput::Value { tagged_value, .. }) = node.inputs.get_mut(10) {
+				*tagged_value = TaggedValue::TextSpans(frame.spans.clone());
+			}
+		}
+
+		Some(network)
+	}
+
 	pub fn set_auto_save_state(&mut self, is_saved: bool) {
 		if is_saved {
 			self.auto_saved_hash = self.current_hash();
@@ -1866,6 +2740,21 @@ impl DocumentMessageHandler {
 		}
 	}
 
+	/// Sets the export region once it's done being drawn on the canvas, then hands control back to the tool and reopens the export dialog that drawing it was started from.
+	fn finish_drawing_export_region(&self, region: Option<[DVec2; 2]>, responses: &mut VecDeque<Message>) {
+		responses.add(DocumentMessage::SetExportRegion { region });
+		responses.add(ToolMessage::UpdateCursor);
+		responses.add(ToolMessage::UpdateHints);
+		responses.add(DialogMessage::RequestExportDialog);
+	}
+
+	/// Marks the document as having unsaved changes after editing a setting stored outside of the node network, which the save state's hash doesn't cover.
+	pub fn mark_as_unsaved(&mut self, responses: &mut VecDeque<Message>) {
+		self.set_save_state(false);
+		self.set_auto_save_state(false);
+		responses.add(PortfolioMessage::UpdateOpenDocumentsList);
+	}
+
 	/// Finds the artboard that bounds the point in viewport space and be the container of any newly added layers.
 	pub fn new_layer_bounding_artboard(&self, ipp: &InputPreprocessorMessageHandler) -> LayerNodeIdentifier {
 		let container_based_on_selection = self.new_layer_parent(true);
@@ -1889,9 +2778,26 @@ impl DocumentMessageHandler {
 			return LayerNodeIdentifier::ROOT_PARENT;
 		};
 
-		self.network_interface
+		let parent = self
+			.network_interface
 			.deepest_common_ancestor(&selected_nodes, &self.selection_network_path, include_self)
-			.unwrap_or_else(|| self.network_interface.all_artboards().iter().next().copied().unwrap_or(LayerNodeIdentifier::ROOT_PARENT))
+			.unwrap_or_else(|| self.network_interface.all_artboards().iter().next().copied().unwrap_or(LayerNodeIdentifier::ROOT_PARENT));
+
+		// New layers go into the selected frame of a frame-by-frame group, rather than becoming frames of their own
+		if include_self && parent != LayerNodeIdentifier::ROOT_PARENT && graph_modification_utils::get_frame_sequence_id(parent, &self.network_interface).is_some() {
+			let is_group = |layer: LayerNodeIdentifier| {
+				let content = self.network_interface.input_from_connector(&InputConnector::node(layer.to_node(), 1), &[]);
+				layer.has_children(self.metadata()) || content.is_some_and(|input| input.as_value().is_some())
+			};
+			if let Some(frame) = selected_nodes
+				.selected_layers(self.metadata())
+				.find(|&layer| layer.parent(self.metadata()) == Some(parent) && is_group(layer))
+			{
+				return frame;
+			}
+		}
+
+		parent
 	}
 
 	pub fn get_calculated_insert_index(metadata: &DocumentMetadata, selected_nodes: &SelectedNodes, parent: LayerNodeIdentifier) -> usize {
@@ -1986,17 +2892,33 @@ impl DocumentMessageHandler {
 
 	/// Loads all of the fonts in the document.
 	pub fn load_layer_resources(&self, responses: &mut VecDeque<Message>) {
+		for font in self.fonts_in_use() {
+			responses.add_front(FrontendMessage::TriggerFontLoad { font });
+		}
+	}
+
+	/// Stops watching the files of the image layers opened in another application, for when the document is closed.
+	pub fn stop_editing_images_externally(&mut self, document_id: DocumentId, responses: &mut VecDeque<Message>) {
+		for layer in self.externally_edited_layers.drain() {
+			responses.add(FrontendMessage::TriggerStopEditingImageExternally { document_id, layer: layer.to_node() });
+		}
+	}
+
+	/// The fonts used by any node in the document.
+	pub fn fonts_in_use(&self) -> HashSet<Font> {
 		let mut fonts = HashSet::new();
 		for (_node_id, node) in self.document_network().recursive_nodes() {
 			for input in &node.inputs {
-				if let Some(TaggedValue::Font(font)) = input.as_value() {
-					fonts.insert(font.clone());
+				match input.as_value() {
+					Some(TaggedValue::Font(font)) => {
+						fonts.insert(font.clone());
+					}
+					Some(TaggedValue::TextSpans(spans)) => fonts.extend(spans.iter().filter_map(|span| span.font.clone())),
+					_ => {}
 				}
 			}
 		}
-		for font in fonts {
-			responses.add_front(FrontendMessage::TriggerFontLoad { font });
-		}
+		fonts
 	}
 
 	pub fn update_document_widgets(&self, responses: &mut VecDeque<Message>, animation_is_playing: bool, time: Duration) {
@@ -2137,11 +3059,12 @@ impl DocumentMessageHandler {
 				RadioEntryData::new("pixels")
 					.icon("ViewModePixels")
 					.tooltip("View Mode: Pixels")
-					.on_update(|_| DialogMessage::RequestComingSoonDialog { issue: Some(320) }.into()),
+					.on_update(|_| DocumentMessage::SetViewMode { view_mode: ViewMode::Pixels }.into()),
 			])
 			.selected_index(match self.view_mode {
 				ViewMode::Normal => Some(0),
-				_ => Some(1),
+				ViewMode::Outline => Some(1),
+				ViewMode::Pixels => Some(2),
 			})
 			.widget_holder(),
 			// PopoverButton::new()
@@ -2496,6 +3419,17 @@ enum XRayTarget {
 	Polygon(Subpath<PointId>),
 }
 
+impl XRayTarget {
+	fn bounding_box(&self) -> Option<[DVec2; 2]> {
+		match self {
+			XRayTarget::Point(point) => Some([*point, *point]),
+			XRayTarget::Quad(quad) => Some(quad.bounding_box()),
+			XRayTarget::Path(_) => None,
+			XRayTarget::Polygon(polygon) => polygon.bounding_box(),
+		}
+	}
+}
+
 /// The result for the [`ClickXRayIter`] on the layer
 struct XRayResult {
 	clicked: bool,
@@ -2508,6 +3442,8 @@ pub struct ClickXRayIter<'a> {
 	next_layer: Option<LayerNodeIdentifier>,
 	network_interface: &'a NodeNetworkInterface,
 	parent_targets: Vec<(LayerNodeIdentifier, XRayTarget)>,
+	/// The layers which may be hit (along with their ancestors) found from the spatial index, so all other layers can be skipped without testing their click targets.
+	candidates: Option<HashSet<LayerNodeIdentifier>>,
 }
 
 fn quad_to_path_lib_segments(quad: Quad) -> Vec<path_bool_lib::PathSegment> {
@@ -2528,17 +3464,25 @@ fn click_targets_to_path_lib_segments<'a>(click_targets: impl Iterator<Item = &'
 
 impl<'a> ClickXRayIter<'a> {
 	fn new(network_interface: &'a NodeNetworkInterface, target: XRayTarget) -> Self {
-		if let Some(first_layer) = LayerNodeIdentifier::ROOT_PARENT.first_child(network_interface.document_metadata()) {
+		let metadata = network_interface.document_metadata();
+		if let Some(first_layer) = LayerNodeIdentifier::ROOT_PARENT.first_child(metadata) {
+			// Ancestors of the candidates are included so the layer tree can be descended to reach them
+			let candidates = target
+				.bounding_box()
+				.map(|bounds| metadata.layers_overlapping_bounds(bounds).flat_map(|layer| layer.ancestors(metadata)).collect::<HashSet<_>>());
+
 			Self {
 				network_interface,
 				next_layer: Some(first_layer),
 				parent_targets: vec![(LayerNodeIdentifier::ROOT_PARENT, target)],
+				candidates,
 			}
 		} else {
 			Self {
 				network_interface,
 				next_layer: Default::default(),
 				parent_targets: Default::default(),
+				candidates: None,
 			}
 		}
 	}
@@ -2575,9 +3519,17 @@ impl<'a> ClickXRayIter<'a> {
 
 	/// Handles the checking of the layer to find if it has been clicked
 	fn check_layer(&mut self, layer: LayerNodeIdentifier) -> XRayResult {
+		// Skip layers which the spatial index has ruled out
+		if self.candidates.as_ref().is_some_and(|candidates| !candidates.contains(&layer)) {
+			return XRayResult { clicked: false, use_children: false };
+		}
+
 		let selected_layers = self.network_interface.selected_nodes();
-		// Discard invisible and locked layers
-		if !selected_layers.layer_visible(layer, self.network_interface) || selected_layers.layer_locked(layer, self.network_interface) {
+		// Discard invisible and locked layers, as well as reference images which are only there to be traced over
+		if !selected_layers.layer_visible(layer, self.network_interface)
+			|| selected_layers.layer_locked(layer, self.network_interface)
+			|| graph_modification_utils::is_reference_image(layer, self.network_interface)
+		{
 			return XRayResult { clicked: false, use_children: false };
 		}
 
@@ -9,9 +9,9 @@ use graphene_core::raster::BlendMode;
 use graphene_core::raster::image::ImageFrameTable;
 use graphene_core::text::{Font, TypesettingConfig};
 use graphene_core::vector::PointId;
-use graphene_core::vector::VectorModificationType;
 use graphene_core::vector::brush_stroke::BrushStroke;
 use graphene_core::vector::style::{Fill, Stroke};
+use graphene_core::vector::{VectorData, VectorModificationType};
 use graphene_core::{Artboard, Color};
 
 #[impl_message(Message, DocumentMessage, GraphOperation)]
@@ -33,6 +33,19 @@ pub enum GraphOperationMessage {
 		layer: LayerNodeIdentifier,
 		stroke: Stroke,
 	},
+	ReferenceImageSet {
+		layer: LayerNodeIdentifier,
+		reference_image: bool,
+	},
+	FrameSequenceSet {
+		layer: LayerNodeIdentifier,
+		frame_sequence: bool,
+		frame_rate: f64,
+	},
+	ImageSet {
+		layer: LayerNodeIdentifier,
+		image_frame: ImageFrameTable<Color>,
+	},
 	TransformChange {
 		layer: LayerNodeIdentifier,
 		transform: DAffine2,
@@ -45,6 +58,20 @@ pub enum GraphOperationMessage {
 		transform_in: TransformIn,
 		skip_rerender: bool,
 	},
+	TextPathSet {
+		layer: LayerNodeIdentifier,
+		path_layer: LayerNodeIdentifier,
+		start_offset: f64,
+	},
+	TextPreviousFrameSet {
+		layer: LayerNodeIdentifier,
+		previous_text_node: NodeId,
+	},
+	/// Replaces the layer's Text node with a Path node holding the given outlines of its glyphs.
+	ConvertTextToPaths {
+		layer: LayerNodeIdentifier,
+		vector_data: VectorData,
+	},
 	TransformSetPivot {
 		layer: LayerNodeIdentifier,
 		pivot: DVec2,
@@ -56,6 +56,21 @@ impl MessageHandler<GraphOperationMessage, GraphOperationMessageData<'_>> for Gr
 					modify_inputs.stroke_set(stroke);
 				}
 			}
+			GraphOperationMessage::ReferenceImageSet { layer, reference_image } => {
+				if let Some(mut modify_inputs) = ModifyInputsContext::new_with_layer(layer, network_interface, responses) {
+					modify_inputs.reference_image_set(reference_image);
+				}
+			}
+			GraphOperationMessage::FrameSequenceSet { layer, frame_sequence, frame_rate } => {
+				if let Some(mut modify_inputs) = ModifyInputsContext::new_with_layer(layer, network_interface, responses) {
+					modify_inputs.frame_sequence_set(frame_sequence, frame_rate);
+				}
+			}
+			GraphOperationMessage::ImageSet { layer, image_frame } => {
+				if let Some(mut modify_inputs) = ModifyInputsContext::new_with_layer(layer, network_interface, responses) {
+					modify_inputs.image_set(image_frame);
+				}
+			}
 			GraphOperationMessage::TransformChange {
 				layer,
 				transform,
@@ -77,6 +92,21 @@ impl MessageHandler<GraphOperationMessage, GraphOperationMessageData<'_>> for Gr
 					modify_inputs.transform_set(transform, transform_in, skip_rerender);
 				}
 			}
+			GraphOperationMessage::TextPathSet { layer, path_layer, start_offset } => {
+				if let Some(mut modify_inputs) = ModifyInputsContext::new_with_layer(layer, network_interface, responses) {
+					modify_inputs.text_path_set(path_layer, start_offset);
+				}
+			}
+			GraphOperationMessage::TextPreviousFrameSet { layer, previous_text_node } => {
+				if let Some(mut modify_inputs) = ModifyInputsContext::new_with_layer(layer, network_interface, responses) {
+					modify_inputs.text_previous_frame_set(previous_text_node);
+				}
+			}
+			GraphOperationMessage::ConvertTextToPaths { layer, vector_data } => {
+				if let Some(mut modify_inputs) = ModifyInputsContext::new_with_layer(layer, network_interface, responses) {
+					modify_inputs.convert_text_to_paths(vector_data);
+				}
+			}
 			GraphOperationMessage::TransformSetPivot { layer, pivot } => {
 				if layer == LayerNodeIdentifier::ROOT_PARENT {
 					log::error!("Cannot run TransformSetPivot on ROOT_PARENT");
@@ -190,6 +190,31 @@ impl<'a> ModifyInputsContext<'a> {
 			Some(NodeInput::value(TaggedValue::F64(typesetting.character_spacing), false)),
 			Some(NodeInput::value(TaggedValue::OptionalF64(typesetting.max_width), false)),
 			Some(NodeInput::value(TaggedValue::OptionalF64(typesetting.max_height), false)),
+			Some(NodeInput::value(TaggedValue::TextAlign(typesetting.align), false)),
+			Some(NodeInput::value(TaggedValue::VerticalAlign(typesetting.vertical_align), false)),
+			None,
+			None,
+			None,
+			None,
+			Some(NodeInput::value(TaggedValue::TextDirection(typesetting.direction), false)),
+			Some(NodeInput::value(TaggedValue::WritingMode(typesetting.writing_mode), false)),
+			Some(NodeInput::value(TaggedValue::TextOrientation(typesetting.orientation), false)),
+			Some(NodeInput::value(TaggedValue::FontFeatures(typesetting.features), false)),
+			Some(NodeInput::value(TaggedValue::FontVariations(typesetting.variations), false)),
+			Some(NodeInput::value(TaggedValue::F64(typesetting.baseline_shift), false)),
+			Some(NodeInput::value(TaggedValue::TextScript(typesetting.script), false)),
+			Some(NodeInput::value(TaggedValue::WrapMode(typesetting.wrap), false)),
+			Some(NodeInput::value(TaggedValue::F64(typesetting.space_before), false)),
+			Some(NodeInput::value(TaggedValue::F64(typesetting.space_after), false)),
+			Some(NodeInput::value(TaggedValue::Bool(typesetting.underline), false)),
+			Some(NodeInput::value(TaggedValue::Bool(typesetting.strikethrough), false)),
+			Some(NodeInput::value(TaggedValue::TabStops(typesetting.tab_stops), false)),
+			Some(NodeInput::value(TaggedValue::Bool(typesetting.hyphenate), false)),
+			Some(NodeInput::value(TaggedValue::TextLanguage(typesetting.language), false)),
+			Some(NodeInput::value(TaggedValue::TextCase(typesetting.case), false)),
+			Some(NodeInput::value(TaggedValue::F64(typesetting.left_indent), false)),
+			Some(NodeInput::value(TaggedValue::F64(typesetting.right_indent), false)),
+			Some(NodeInput::value(TaggedValue::F64(typesetting.first_line_indent), false)),
 		]);
 
 		let text_id = NodeId::new();
@@ -347,6 +372,99 @@ impl<'a> ModifyInputsContext<'a> {
 		self.set_input_with_refresh(input_connector, NodeInput::value(TaggedValue::BlendMode(blend_mode), false), false);
 	}
 
+	/// Wires the output of the path layer into the Text node, so the text follows the path from the given distance along it.
+	pub fn text_path_set(&mut self, path_layer: LayerNodeIdentifier, start_offset: f64) {
+		let Some(text_node_id) = self.existing_node_id("Text", false) else { return };
+
+		// The layer outputs a group, so its vector data is flattened out of it for the Text node
+		let flatten = resolve_document_node_type("Flatten Vector Elements")
+			.expect("Flatten Vector Elements node does not exist")
+			.node_template_input_override([Some(NodeInput::node(path_layer.to_node(), 0))]);
+		let flatten_id = NodeId::new();
+		self.network_interface.insert_node(flatten_id, flatten, &[]);
+		// It's placed to the left of the Text node, below the layer chain
+		if let Some(position) = self.network_interface.position(&text_node_id, &[]) {
+			self.network_interface.shift_node(&flatten_id, position + IVec2::new(-8, 3), &[]);
+		}
+
+		self.set_input_with_refresh(InputConnector::node(text_node_id, 11), NodeInput::node(flatten_id, 0), true);
+		self.set_input_with_refresh(InputConnector::node(text_node_id, 12), NodeInput::value(TaggedValue::F64(start_offset), false), false);
+	}
+
+	/// Wires the output of the previous Text node into this layer's Text node, so the text overflowing the previous text box continues in this one.
+	pub fn text_previous_frame_set(&mut self, previous_text_node: NodeId) {
+		let Some(text_node_id) = self.existing_node_id("Text", false) else { return };
+		self.set_input_with_refresh(InputConnector::node(text_node_id, 13), NodeInput::node(previous_text_node, 0), false);
+	}
+
+	/// Replaces the layer's Text node with a Path node holding the outlines of its glyphs, leaving the rest of the layer's chain, like its fill and transform, in place.
+	/// Any text box linked from this one no longer has its text overflowing into it.
+	pub fn convert_text_to_paths(&mut self, vector_data: VectorData) {
+		let Some(text_node_id) = self.existing_node_id("Text", false) else { return };
+		let Some(outputs) = self
+			.network_interface
+			.outward_wires(&[])
+			.and_then(|outward_wires| outward_wires.get(&OutputConnector::node(text_node_id, 0)))
+			.cloned()
+		else {
+			return;
+		};
+		let Some(position) = self.network_interface.position(&text_node_id, &[]) else { return };
+
+		let vector_data = VectorDataTable::new(vector_data);
+		let path = resolve_document_node_type("Path")
+			.expect("Path node does not exist")
+			.node_template_input_override([Some(NodeInput::value(TaggedValue::VectorData(vector_data), false))]);
+		let path_id = NodeId::new();
+		self.network_interface.insert_node(path_id, path, &[]);
+		self.network_interface.shift_node(&path_id, position, &[]);
+
+		// The text boxes linked from this one read the overflow of its Text node, which the Path node doesn't have, so they're left disconnected
+		let linked_from = |input_connector: &InputConnector| match input_connector {
+			InputConnector::Node { node_id, input_index } => *input_index == 13 && self.network_interface.reference(node_id, &[]).is_some_and(|reference| reference.as_deref() == Some("Text")),
+			InputConnector::Export(_) => false,
+		};
+		let outputs: Vec<_> = outputs.into_iter().filter(|input_connector| !linked_from(input_connector)).collect();
+		for input_connector in outputs {
+			self.network_interface.set_input(&input_connector, NodeInput::node(path_id, 0), &[]);
+		}
+
+		// The Flatten Vector Elements node wiring in the path followed by the text is only used by the Text node, so it's removed along with it
+		self.network_interface.delete_nodes(vec![text_node_id], true, &[]);
+		self.responses.add(NodeGraphMessage::RunDocumentGraph);
+	}
+
+	pub fn reference_image_set(&mut self, reference_image: bool) {
+		if reference_image {
+			self.existing_node_id("Reference Image", true);
+		} else if let Some(reference_image_node_id) = self.existing_node_id("Reference Image", false) {
+			self.network_interface.delete_nodes(vec![reference_image_node_id], false, &[]);
+		}
+		self.responses.add(NodeGraphMessage::RunDocumentGraph);
+	}
+
+	/// Adds a Frame Sequence node to the group, showing each of its layers for one frame at the given frame rate, or removes it.
+	pub fn frame_sequence_set(&mut self, frame_sequence: bool, frame_rate: f64) {
+		if !frame_sequence {
+			if let Some(frame_sequence_node_id) = self.existing_node_id("Frame Sequence", false) {
+				self.network_interface.delete_nodes(vec![frame_sequence_node_id], false, &[]);
+			}
+		} else if self.existing_node_id("Frame Sequence", false).is_none() {
+			let Some(frame_sequence_node_id) = self.create_node("Frame Sequence") else { return };
+			self.network_interface
+				.set_input(&InputConnector::node(frame_sequence_node_id, 1), NodeInput::value(TaggedValue::F64(frame_rate), false), &[]);
+		}
+		self.responses.add(NodeGraphMessage::RunDocumentGraph);
+	}
+
+	/// Replaces the pixels of an image layer, leaving layers without an Image node unchanged.
+	pub fn image_set(&mut self, image_frame: ImageFrameTable<Color>) {
+		let Some(image_node_id) = self.existing_node_id("Image", false) else { return };
+
+		let input_connector = InputConnector::node(image_node_id, 1);
+		self.set_input_with_refresh(input_connector, NodeInput::value(TaggedValue::ImageFrame(image_frame), false), false);
+	}
+
 	pub fn stroke_set(&mut self, stroke: Stroke) {
 		let Some(stroke_node_id) = self.existing_node_id("Stroke", true) else { return };
 
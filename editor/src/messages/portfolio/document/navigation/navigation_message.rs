@@ -7,6 +7,7 @@ use glam::DVec2;
 pub enum NavigationMessage {
 	// Messages
 	BeginCanvasPan,
+	BeginCanvasPanMidDrag,
 	BeginCanvasTilt { was_dispatched_from_menu: bool },
 	BeginCanvasZoom,
 	CanvasPan { delta: DVec2 },
@@ -14,6 +15,7 @@ pub enum NavigationMessage {
 	CanvasPanAbort { x_not_y_axis: bool },
 	CanvasPanByViewportFraction { delta: DVec2 },
 	CanvasPanMouseWheel { use_y_as_x: bool },
+	CanvasPinch { center: DVec2, pan: DVec2, zoom_factor: f64, tilt_delta: f64 },
 	CanvasTiltResetAndZoomTo100Percent,
 	CanvasTiltSet { angle_radians: f64 },
 	CanvasZoomDecrease { center_on_mouse: bool },
@@ -28,6 +28,8 @@ pub struct NavigationMessageHandler {
 	mouse_position: ViewportPosition,
 	finish_operation_with_click: bool,
 	abortable_pan_start: Option<f64>,
+	/// Whether the current pan interrupted a tool's drag, which resumes once the pan ends.
+	pan_interrupted_drag: bool,
 }
 
 impl MessageHandler<NavigationMessage, NavigationMessageData<'_>> for NavigationMessageHandler {
@@ -82,6 +84,14 @@ impl MessageHandler<NavigationMessage, NavigationMessageData<'_>> for Navigation
 				};
 				self.navigation_operation = NavigationOperation::Pan { pan_original_for_abort: ptz.pan };
 			}
+			NavigationMessage::BeginCanvasPanMidDrag => {
+				if self.navigation_operation != NavigationOperation::None {
+					return;
+				}
+
+				self.pan_interrupted_drag = true;
+				responses.add(NavigationMessage::BeginCanvasPan);
+			}
 			NavigationMessage::BeginCanvasTilt { was_dispatched_from_menu } => {
 				let Some(ptz) = get_ptz(document_ptz, network_interface, graph_view_overlay_open, breadcrumb_network_path) else {
 					return;
@@ -178,6 +188,42 @@ impl MessageHandler<NavigationMessage, NavigationMessageData<'_>> for Navigation
 				responses.add(NavigationMessage::CanvasPan { delta });
 				responses.add(NodeGraphMessage::SetGridAlignedEdges);
 			}
+			NavigationMessage::CanvasPinch { center, pan, zoom_factor, tilt_delta } => {
+				let document_bounds = if !graph_view_overlay_open {
+					network_interface.document_metadata().document_bounds_viewport_space()
+				} else {
+					network_interface.graph_bounds_viewport_space(breadcrumb_network_path)
+				};
+				let Some(ptz) = get_ptz_mut(document_ptz, network_interface, graph_view_overlay_open, breadcrumb_network_path) else {
+					log::error!("Could not get mutable PTZ in CanvasPinch");
+					return;
+				};
+
+				// Find the point under the center of the gesture so it can be kept beneath the fingers after tilting and zooming
+				let viewport_center = ipp.viewport_bounds.center();
+				let anchor = self.calculate_offset_transform(viewport_center, ptz).inverse().transform_point2(center);
+
+				// The node graph can't be tilted
+				if !graph_view_overlay_open {
+					ptz.set_tilt(ptz.tilt() + tilt_delta);
+				}
+				let zoom = (ptz.zoom() * zoom_factor).clamp(VIEWPORT_ZOOM_SCALE_MIN, VIEWPORT_ZOOM_SCALE_MAX);
+				let zoom = zoom * Self::clamp_zoom(zoom, document_bounds, old_zoom, ipp);
+				ptz.set_zoom(zoom);
+
+				let document_to_viewport = self.calculate_offset_transform(viewport_center, ptz);
+				let offset = center + pan - document_to_viewport.transform_point2(anchor);
+				ptz.pan += document_to_viewport.inverse().transform_vector2(offset);
+
+				if graph_view_overlay_open {
+					responses.add(NodeGraphMessage::UpdateGraphBarRight);
+				} else {
+					responses.add(PortfolioMessage::UpdateDocumentWidgets);
+				}
+				responses.add(BroadcastEvent::CanvasTransformed);
+				responses.add(DocumentMessage::PTZUpdate);
+				responses.add(NodeGraphMessage::SetGridAlignedEdges);
+			}
 			NavigationMessage::CanvasTiltResetAndZoomTo100Percent => {
 				let Some(ptz) = get_ptz_mut(document_ptz, network_interface, graph_view_overlay_open, breadcrumb_network_path) else {
 					log::error!("Could not get mutable PTZ in CanvasTiltResetAndZoomTo100Percent");
@@ -310,6 +356,11 @@ impl MessageHandler<NavigationMessage, NavigationMessageData<'_>> for Navigation
 				responses.add(ToolMessage::UpdateCursor);
 				responses.add(ToolMessage::UpdateHints);
 				responses.add(NavigateToolMessage::End);
+
+				// If the mouse was released while panning, let the interrupted tool finish its drag where the mouse is now
+				if std::mem::take(&mut self.pan_interrupted_drag) && !ipp.keyboard.get(Key::MouseLeft as usize) {
+					responses.add(InputMapperMessage::KeyUp(Key::MouseLeft));
+				}
 			}
 			NavigationMessage::EndCanvasPTZWithClick { commit_key } => {
 				self.finish_operation_with_click = false;
@@ -460,6 +511,7 @@ impl MessageHandler<NavigationMessage, NavigationMessageData<'_>> for Navigation
 	fn actions(&self) -> ActionList {
 		let mut common = actions!(NavigationMessageDiscriminant;
 			BeginCanvasPan,
+			BeginCanvasPanMidDrag,
 			BeginCanvasTilt,
 			BeginCanvasZoom,
 			CanvasPan,
@@ -5,6 +5,7 @@ use crate::messages::portfolio::document::utility_types::network_interface::{
 	DocumentNodeMetadata, DocumentNodePersistentMetadata, NodeNetworkInterface, NodeNetworkMetadata, NodeNetworkPersistentMetadata, NodeTemplate, NodeTypePersistentMetadata, NumberInputSettings,
 	PropertiesRow, Vec2InputSettings, WidgetOverride,
 };
+use crate::messages::portfolio::document::utility_types::swatches::Swatch;
 use crate::messages::portfolio::utility_types::PersistentData;
 use crate::messages::prelude::Message;
 use crate::node_graph_executor::NodeGraphExecutor;
@@ -17,7 +18,7 @@ use graph_craft::imaginate_input::ImaginateSamplingMethod;
 use graphene_core::raster::brush_cache::BrushCache;
 use graphene_core::raster::image::ImageFrameTable;
 use graphene_core::raster::{CellularDistanceFunction, CellularReturnType, Color, DomainWarpType, FractalType, NoiseType, RedGreenBlue, RedGreenBlueAlpha};
-use graphene_core::text::{Font, TypesettingConfig};
+use graphene_core::text::{Font, TypesettingConfig, variation_axes};
 use graphene_core::transform::Footprint;
 use graphene_core::vector::VectorDataTable;
 use graphene_core::*;
@@ -34,6 +35,7 @@ pub struct NodePropertiesContext<'a> {
 	pub network_interface: &'a mut NodeNetworkInterface,
 	pub selection_network_path: &'a [NodeId],
 	pub document_name: &'a str,
+	pub swatches: &'a [Swatch],
 }
 
 impl NodePropertiesContext<'_> {
@@ -2103,6 +2105,31 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 						NodeInput::value(TaggedValue::F64(TypesettingConfig::default().character_spacing), false),
 						NodeInput::value(TaggedValue::OptionalF64(TypesettingConfig::default().max_width), false),
 						NodeInput::value(TaggedValue::OptionalF64(TypesettingConfig::default().max_height), false),
+						NodeInput::value(TaggedValue::TextAlign(TypesettingConfig::default().align), false),
+						NodeInput::value(TaggedValue::VerticalAlign(TypesettingConfig::default().vertical_align), false),
+						NodeInput::value(TaggedValue::TextSpans(Vec::new()), false),
+						NodeInput::value(TaggedValue::VectorData(VectorDataTable::default()), true),
+						NodeInput::value(TaggedValue::F64(0.), false),
+						NodeInput::value(TaggedValue::VectorData(VectorDataTable::default()), true),
+						NodeInput::value(TaggedValue::TextDirection(TypesettingConfig::default().direction), false),
+						NodeInput::value(TaggedValue::WritingMode(TypesettingConfig::default().writing_mode), false),
+						NodeInput::value(TaggedValue::TextOrientation(TypesettingConfig::default().orientation), false),
+						NodeInput::value(TaggedValue::FontFeatures(TypesettingConfig::default().features), false),
+						NodeInput::value(TaggedValue::FontVariations(TypesettingConfig::default().variations), false),
+						NodeInput::value(TaggedValue::F64(TypesettingConfig::default().baseline_shift), false),
+						NodeInput::value(TaggedValue::TextScript(TypesettingConfig::default().script), false),
+						NodeInput::value(TaggedValue::WrapMode(TypesettingConfig::default().wrap), false),
+						NodeInput::value(TaggedValue::F64(TypesettingConfig::default().space_before), false),
+						NodeInput::value(TaggedValue::F64(TypesettingConfig::default().space_after), false),
+						NodeInput::value(TaggedValue::Bool(TypesettingConfig::default().underline), false),
+						NodeInput::value(TaggedValue::Bool(TypesettingConfig::default().strikethrough), false),
+						NodeInput::value(TaggedValue::TabStops(TypesettingConfig::default().tab_stops), false),
+						NodeInput::value(TaggedValue::Bool(TypesettingConfig::default().hyphenate), false),
+						NodeInput::value(TaggedValue::TextLanguage(TypesettingConfig::default().language), false),
+						NodeInput::value(TaggedValue::TextCase(TypesettingConfig::default().case), false),
+						NodeInput::value(TaggedValue::F64(TypesettingConfig::default().left_indent), false),
+						NodeInput::value(TaggedValue::F64(TypesettingConfig::default().right_indent), false),
+						NodeInput::value(TaggedValue::F64(TypesettingConfig::default().first_line_indent), false),
 					],
 					..Default::default()
 				},
@@ -2151,6 +2178,77 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 								..Default::default()
 							}),
 						),
+						"Align".into(),
+						"Vertical Align".into(),
+						PropertiesRow::with_override("Spans", WidgetOverride::Hidden),
+						"Path".into(),
+						PropertiesRow::with_override(
+							"Start Offset",
+							WidgetOverride::Number(NumberInputSettings {
+								unit: Some(" px".to_string()),
+								..Default::default()
+							}),
+						),
+						"Previous Frame".into(),
+						"Direction".into(),
+						"Writing Mode".into(),
+						"Orientation".into(),
+						"Features".into(),
+						PropertiesRow::with_override("Variations", WidgetOverride::Custom("text_font_variations".to_string())),
+						PropertiesRow::with_override(
+							"Baseline Shift",
+							WidgetOverride::Number(NumberInputSettings {
+								unit: Some(" px".to_string()),
+								..Default::default()
+							}),
+						),
+						"Script".into(),
+						"Wrap".into(),
+						PropertiesRow::with_override(
+							"Space Before",
+							WidgetOverride::Number(NumberInputSettings {
+								unit: Some(" px".to_string()),
+								min: Some(0.),
+								..Default::default()
+							}),
+						),
+						PropertiesRow::with_override(
+							"Space After",
+							WidgetOverride::Number(NumberInputSettings {
+								unit: Some(" px".to_string()),
+								min: Some(0.),
+								..Default::default()
+							}),
+						),
+						"Underline".into(),
+						"Strikethrough".into(),
+						"Tab Stops".into(),
+						"Hyphenate".into(),
+						"Language".into(),
+						"Case".into(),
+						PropertiesRow::with_override(
+							"Left Indent",
+							WidgetOverride::Number(NumberInputSettings {
+								unit: Some(" px".to_string()),
+								min: Some(0.),
+								..Default::default()
+							}),
+						),
+						PropertiesRow::with_override(
+							"Right Indent",
+							WidgetOverride::Number(NumberInputSettings {
+								unit: Some(" px".to_string()),
+								min: Some(0.),
+								..Default::default()
+							}),
+						),
+						PropertiesRow::with_override(
+							"First Line Indent",
+							WidgetOverride::Number(NumberInputSettings {
+								unit: Some(" px".to_string()),
+								..Default::default()
+							}),
+						),
 					],
 					output_names: vec!["Vector".to_string()],
 					..Default::default()
@@ -3419,6 +3517,18 @@ fn static_input_properties() -> InputProperties {
 			Ok(result)
 		}),
 	);
+	map.insert(
+		"text_font_variations".to_string(),
+		Box::new(|node_id, index, context| {
+			let (document_node, input_name) = node_properties::query_node_and_input_name(node_id, index, context)?;
+			// The axes are read from the font the Text node is typeset with, once it's been loaded
+			let axes = match document_node.inputs.get(2).and_then(|input| input.as_non_exposed_value()) {
+				Some(TaggedValue::Font(font)) => context.persistent_data.font_cache.get(font).map(|data| variation_axes(data)).unwrap_or_default(),
+				_ => Vec::new(),
+			};
+			Ok(node_properties::font_variations_widget(document_node, node_id, index, input_name, &axes, true))
+		}),
+	);
 	map.insert(
 		"artboard_background".to_string(),
 		Box::new(|node_id, index, context| {
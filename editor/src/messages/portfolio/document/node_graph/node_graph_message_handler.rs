@@ -13,12 +13,14 @@ use crate::messages::portfolio::document::utility_types::network_interface::{
 	self, InputConnector, NodeNetworkInterface, NodeTemplate, NodeTypePersistentMetadata, OutputConnector, Previewing, TypeSource,
 };
 use crate::messages::portfolio::document::utility_types::nodes::{CollapsedLayers, LayerPanelEntry};
+use crate::messages::portfolio::document::utility_types::swatches::Swatch;
 use crate::messages::prelude::*;
 use crate::messages::tool::common_functionality::auto_panning::AutoPanning;
 use glam::{DAffine2, DVec2, IVec2};
 use graph_craft::document::{DocumentNodeImplementation, NodeId, NodeInput};
 use graph_craft::proto::GraphErrors;
 use graphene_core::*;
+use graphene_std::vector::style::FillChoice;
 use renderer::Quad;
 use std::cmp::Ordering;
 
@@ -134,6 +136,8 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 				responses.add(NodeGraphMessage::SendSelectedNodes);
 				responses.add(ArtboardToolMessage::UpdateSelectedArtboard);
 				responses.add(DocumentMessage::DocumentStructureChanged);
+				responses.add(DocumentMessage::RenderTransformBar);
+				responses.add(TimelineMessage::SendLayout);
 				responses.add(OverlaysMessage::Draw);
 				responses.add(NodeGraphMessage::SendGraph);
 			}
@@ -796,7 +800,7 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 
 				// Auto-panning
 				let messages = [NodeGraphMessage::PointerOutsideViewport { shift }.into(), NodeGraphMessage::PointerMove { shift }.into()];
-				self.auto_panning.setup_by_mouse_position(ipp, &messages, responses);
+				self.auto_panning.setup_by_mouse_position(ipp, preferences, &messages, responses);
 
 				let viewport_location = ipp.mouse.position;
 				let point = network_metadata
@@ -1221,7 +1225,7 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 			}
 			NodeGraphMessage::PointerOutsideViewport { shift } => {
 				if self.drag_start.is_some() || self.box_selection_start.is_some() {
-					let _ = self.auto_panning.shift_viewport(ipp, responses);
+					let _ = self.auto_panning.shift_viewport(ipp, preferences, responses);
 				} else {
 					// Auto-panning
 					let messages = [NodeGraphMessage::PointerOutsideViewport { shift }.into(), NodeGraphMessage::PointerMove { shift }.into()];
@@ -1955,6 +1959,8 @@ impl NodeGraphMessageHandler {
 					],
 				}];
 
+				properties.extend(swatches_properties(context.swatches));
+
 				let Some(network) = context.network_interface.nested_network(context.selection_network_path) else {
 					warn!("No network in collate_properties");
 					return Vec::new();
@@ -2302,9 +2308,15 @@ impl NodeGraphMessageHandler {
 			}
 		}
 
+		// The details of layers inside collapsed layers are deferred until they're expanded, which updates the Layers panel again
+		let hidden_layers = collapsed.hidden_layers(network_interface.document_metadata());
+
 		for (&node_id, node_metadata) in &network_interface.document_network_metadata().persistent_metadata.node_metadata {
 			if node_metadata.persistent_metadata.is_layer() {
 				let layer = LayerNodeIdentifier::new(node_id, network_interface, &[]);
+				if hidden_layers.contains(&layer) {
+					continue;
+				}
 
 				let children_allowed =
 						// The layer has other layers as children along the secondary input's horizontal flow
@@ -2536,3 +2548,67 @@ impl PartialEq for NodeGraphMessageHandler {
 			&& self.context_menu == other.context_menu
 	}
 }
+
+/// Lists the document's swatches, each with a color picker to edit it, its print settings as a spot color, and a button to remove it from the document.
+fn swatches_properties(swatches: &[Swatch]) -> Vec<LayoutGroup> {
+	if swatches.is_empty() {
+		return Vec::new();
+	}
+
+	let mut properties = vec![LayoutGroup::Row {
+		widgets: vec![TextLabel::new("Swatches").bold(true).widget_holder()],
+	}];
+
+	properties.extend(swatches.iter().enumerate().map(|(index, swatch)| {
+		let (spot, overprint) = (swatch.spot, swatch.overprint);
+		LayoutGroup::Row {
+			widgets: vec![
+				ColorInput::new(FillChoice::Solid(swatch.color.to_gamma_srgb()))
+					.allow_none(false)
+					.tooltip(&swatch.name)
+					.on_update(move |color_input: &ColorInput| {
+						let Some(color) = color_input.value.as_solid() else { return Message::NoOp };
+						DocumentMessage::SetSwatchColor { index, color: color.to_linear_srgb() }.into()
+					})
+					.on_commit(|_| DocumentMessage::AddTransaction.into())
+					.widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextLabel::new(&swatch.name).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				CheckboxInput::new(swatch.spot)
+					.tooltip("Print as a named spot color with its own premixed ink on a separate plate")
+					.on_update(move |checkbox_input: &CheckboxInput| {
+						DocumentMessage::SetSwatchSpotColor {
+							index,
+							spot: checkbox_input.checked,
+							overprint,
+						}
+						.into()
+					})
+					.widget_holder(),
+				TextLabel::new("Spot").widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				CheckboxInput::new(overprint)
+					.disabled(!spot)
+					.tooltip("Print the spot color's ink over the inks beneath it instead of knocking them out")
+					.on_update(move |checkbox_input: &CheckboxInput| {
+						DocumentMessage::SetSwatchSpotColor {
+							index,
+							spot,
+							overprint: checkbox_input.checked,
+						}
+						.into()
+					})
+					.widget_holder(),
+				TextLabel::new("Overprint").disabled(!spot).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				IconButton::new("Trash", 24)
+					.tooltip("Remove this swatch from the document")
+					.on_update(move |_| DocumentMessage::RemoveSwatch { index }.into())
+					.widget_holder(),
+			],
+		}
+	}));
+
+	properties
+}
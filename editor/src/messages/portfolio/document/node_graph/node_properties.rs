@@ -17,16 +17,19 @@ use graphene_core::raster::{
 	BlendMode, CellularDistanceFunction, CellularReturnType, Color, DomainWarpType, FractalType, LuminanceCalculation, NoiseType, RedGreenBlue, RedGreenBlueAlpha, RelativeAbsolute,
 	SelectiveColorChoice,
 };
-use graphene_core::text::Font;
-use graphene_core::vector::misc::CentroidType;
+use graphene_core::text::{
+	Font, FontAxis, FontFeatures, FontVariations, TabStops, TextAlign, TextCase, TextDirection, TextLanguage, TextOrientation, TextScript, VerticalAlign, WrapMode, WritingMode,
+};
+use graphene_core::vector::misc::{BarcodeFormat, CentroidType, QrErrorCorrection, WarpStyle};
 use graphene_core::vector::style::{GradientType, LineCap, LineJoin};
-use graphene_std::animation::RealTimeMode;
+use graphene_std::animation::{Easing, RealTimeMode};
 use graphene_std::application_io::TextureFrameTable;
 use graphene_std::transform::Footprint;
 use graphene_std::vector::VectorDataTable;
 use graphene_std::vector::misc::BooleanOperation;
 use graphene_std::vector::style::{Fill, FillChoice, FillType, GradientStops};
 use graphene_std::{GraphicGroupTable, RasterFrame};
+use std::sync::Arc;
 
 pub(crate) fn string_properties(text: &str) -> Vec<LayoutGroup> {
 	let widget = TextLabel::new(text).widget_holder();
@@ -167,6 +170,7 @@ pub(crate) fn property_from_type(
 						}
 						Some(x) if x == TypeId::of::<BlendMode>() => blend_mode(document_node, node_id, index, name, true),
 						Some(x) if x == TypeId::of::<RealTimeMode>() => real_time_mode(document_node, node_id, index, name, true),
+						Some(x) if x == TypeId::of::<Easing>() => easing_widget(document_node, node_id, index, name, true),
 						Some(x) if x == TypeId::of::<RedGreenBlue>() => color_channel(document_node, node_id, index, name, true),
 						Some(x) if x == TypeId::of::<RedGreenBlueAlpha>() => rgba_channel(document_node, node_id, index, name, true),
 						Some(x) if x == TypeId::of::<NoiseType>() => noise_type(document_node, node_id, index, name, true),
@@ -214,6 +218,32 @@ pub(crate) fn property_from_type(
 						.into(),
 						Some(x) if x == TypeId::of::<BooleanOperation>() => boolean_operation_radio_buttons(document_node, node_id, index, name, true),
 						Some(x) if x == TypeId::of::<CentroidType>() => centroid_widget(document_node, node_id, index),
+						Some(x) if x == TypeId::of::<BarcodeFormat>() => barcode_format_widget(document_node, node_id, index, name, true),
+						Some(x) if x == TypeId::of::<QrErrorCorrection>() => qr_error_correction_widget(document_node, node_id, index, name, true),
+						Some(x) if x == TypeId::of::<WarpStyle>() => warp_style_widget(document_node, node_id, index, name, true),
+						Some(x) if x == TypeId::of::<TextAlign>() => text_align_widget(document_node, node_id, index, name, true),
+						Some(x) if x == TypeId::of::<VerticalAlign>() => vertical_align_widget(document_node, node_id, index, name, true),
+						Some(x) if x == TypeId::of::<TextDirection>() => text_direction_widget(document_node, node_id, index, name, true),
+						Some(x) if x == TypeId::of::<WritingMode>() => writing_mode_widget(document_node, node_id, index, name, true),
+						Some(x) if x == TypeId::of::<TextOrientation>() => text_orientation_widget(document_node, node_id, index, name, true),
+						Some(x) if x == TypeId::of::<FontFeatures>() => font_features_widget(document_node, node_id, index, name, true),
+						Some(x) if x == TypeId::of::<TextScript>() => {
+							let value = |value: &TaggedValue| if let TaggedValue::TextScript(script) = value { Some(*script) } else { None };
+							enum_dropdown_widget(document_node, node_id, index, name, true, TextScript::list(), value, TaggedValue::TextScript, "Script")
+						}
+						Some(x) if x == TypeId::of::<WrapMode>() => {
+							let value = |value: &TaggedValue| if let TaggedValue::WrapMode(wrap) = value { Some(*wrap) } else { None };
+							enum_dropdown_widget(document_node, node_id, index, name, true, WrapMode::list(), value, TaggedValue::WrapMode, "Wrap")
+						}
+						Some(x) if x == TypeId::of::<TextLanguage>() => {
+							let value = |value: &TaggedValue| if let TaggedValue::TextLanguage(language) = value { Some(*language) } else { None };
+							enum_dropdown_widget(document_node, node_id, index, name, true, TextLanguage::list(), value, TaggedValue::TextLanguage, "Language")
+						}
+						Some(x) if x == TypeId::of::<TextCase>() => {
+							let value = |value: &TaggedValue| if let TaggedValue::TextCase(case) = value { Some(*case) } else { None };
+							enum_dropdown_widget(document_node, node_id, index, name, true, TextCase::list(), value, TaggedValue::TextCase, "Case")
+						}
+						Some(x) if x == TypeId::of::<TabStops>() => tab_stops_widget(document_node, node_id, index, name, true),
 						Some(x) if x == TypeId::of::<LuminanceCalculation>() => luminance_calculation(document_node, node_id, index, name, true),
 						Some(x) if x == TypeId::of::<ImaginateSamplingMethod>() => vec![
 							DropdownInput::new(
@@ -814,6 +844,472 @@ pub fn real_time_mode(document_node: &DocumentNode, node_id: NodeId, index: usiz
 	LayoutGroup::Row { widgets }.with_tooltip("Real Time Mode")
 }
 
+pub fn easing_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	let Some(input) = document_node.inputs.get(index) else {
+		log::warn!("A widget failed to be built because its node's input index is invalid.");
+		return LayoutGroup::Row { widgets: vec![] };
+	};
+	if let Some(&TaggedValue::Easing(easing)) = input.as_non_exposed_value() {
+		let entries = Easing::PRESETS
+			.into_iter()
+			.map(|easing| {
+				MenuListEntry::new(format!("{easing:?}"))
+					.label(easing.to_string())
+					.on_update(update_value(move |_| TaggedValue::Easing(easing), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			DropdownInput::new(vec![entries])
+				.selected_index(Easing::PRESETS.iter().position(|preset| *preset == easing).map(|index| index as u32))
+				.widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("Easing")
+}
+
+pub fn barcode_format_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	let Some(input) = document_node.inputs.get(index) else {
+		log::warn!("A widget failed to be built because its node's input index is invalid.");
+		return LayoutGroup::Row { widgets: vec![] };
+	};
+	if let Some(&TaggedValue::BarcodeFormat(format)) = input.as_non_exposed_value() {
+		let formats = [BarcodeFormat::QrCode, BarcodeFormat::Code128, BarcodeFormat::Ean13, BarcodeFormat::UpcA];
+		let entries = formats
+			.into_iter()
+			.map(|format| {
+				MenuListEntry::new(format!("{format:?}"))
+					.label(format.to_string())
+					.on_update(update_value(move |_| TaggedValue::BarcodeFormat(format), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			DropdownInput::new(vec![entries]).selected_index(Some(format as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("Format")
+}
+
+pub fn qr_error_correction_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	let Some(input) = document_node.inputs.get(index) else {
+		log::warn!("A widget failed to be built because its node's input index is invalid.");
+		return LayoutGroup::Row { widgets: vec![] };
+	};
+	if let Some(&TaggedValue::QrErrorCorrection(error_correction)) = input.as_non_exposed_value() {
+		let levels = [QrErrorCorrection::Low, QrErrorCorrection::Medium, QrErrorCorrection::Quartile, QrErrorCorrection::High];
+		let entries = levels
+			.into_iter()
+			.map(|level| {
+				RadioEntryData::new(format!("{level:?}"))
+					.label(level.to_string())
+					.on_update(update_value(move |_| TaggedValue::QrErrorCorrection(level), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(error_correction as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("Error Correction")
+}
+
+pub fn warp_style_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	let Some(input) = document_node.inputs.get(index) else {
+		log::warn!("A widget failed to be built because its node's input index is invalid.");
+		return LayoutGroup::Row { widgets: vec![] };
+	};
+	if let Some(&TaggedValue::WarpStyle(style)) = input.as_non_exposed_value() {
+		let entries = WarpStyle::list()
+			.into_iter()
+			.map(|style| {
+				MenuListEntry::new(format!("{style:?}"))
+					.label(style.to_string())
+					.on_update(update_value(move |_| TaggedValue::WarpStyle(style), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			DropdownInput::new(vec![entries]).selected_index(Some(style as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("Style")
+}
+
+pub fn text_align_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	let Some(input) = document_node.inputs.get(index) else {
+		log::warn!("A widget failed to be built because its node's input index is invalid.");
+		return LayoutGroup::Row { widgets: vec![] };
+	};
+	if let Some(&TaggedValue::TextAlign(align)) = input.as_non_exposed_value() {
+		let entries = TextAlign::list()
+			.into_iter()
+			.map(|align| {
+				RadioEntryData::new(format!("{align:?}"))
+					.label(align.to_string())
+					.on_update(update_value(move |_| TaggedValue::TextAlign(align), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(align as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("Align")
+}
+
+pub fn vertical_align_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	let Some(input) = document_node.inputs.get(index) else {
+		log::warn!("A widget failed to be built because its node's input index is invalid.");
+		return LayoutGroup::Row { widgets: vec![] };
+	};
+	if let Some(&TaggedValue::VerticalAlign(vertical_align)) = input.as_non_exposed_value() {
+		let entries = VerticalAlign::list()
+			.into_iter()
+			.map(|vertical_align| {
+				RadioEntryData::new(format!("{vertical_align:?}"))
+					.label(vertical_align.to_string())
+					.on_update(update_value(move |_| TaggedValue::VerticalAlign(vertical_align), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(vertical_align as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("Vertical Align")
+}
+
+pub fn text_direction_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	let Some(input) = document_node.inputs.get(index) else {
+		log::warn!("A widget failed to be built because its node's input index is invalid.");
+		return LayoutGroup::Row { widgets: vec![] };
+	};
+	if let Some(&TaggedValue::TextDirection(direction)) = input.as_non_exposed_value() {
+		let entries = TextDirection::list()
+			.into_iter()
+			.map(|direction| {
+				RadioEntryData::new(format!("{direction:?}"))
+					.label(direction.to_string())
+					.on_update(update_value(move |_| TaggedValue::TextDirection(direction), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(direction as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("Direction")
+}
+
+pub fn writing_mode_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	let Some(input) = document_node.inputs.get(index) else {
+		log::warn!("A widget failed to be built because its node's input index is invalid.");
+		return LayoutGroup::Row { widgets: vec![] };
+	};
+	if let Some(&TaggedValue::WritingMode(writing_mode)) = input.as_non_exposed_value() {
+		let entries = WritingMode::list()
+			.into_iter()
+			.map(|writing_mode| {
+				RadioEntryData::new(format!("{writing_mode:?}"))
+					.label(writing_mode.to_string())
+					.on_update(update_value(move |_| TaggedValue::WritingMode(writing_mode), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(writing_mode as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("Writing Mode")
+}
+
+pub fn text_orientation_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	let Some(input) = document_node.inputs.get(index) else {
+		log::warn!("A widget failed to be built because its node's input index is invalid.");
+		return LayoutGroup::Row { widgets: vec![] };
+	};
+	if let Some(&TaggedValue::TextOrientation(orientation)) = input.as_non_exposed_value() {
+		let entries = TextOrientation::list()
+			.into_iter()
+			.map(|orientation| {
+				RadioEntryData::new(format!("{orientation:?}"))
+					.label(orientation.to_string())
+					.on_update(update_value(move |_| TaggedValue::TextOrientation(orientation), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(orientation as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("Orientation")
+}
+
+/// A dropdown menu choosing between the variants of an enum input, which `value` and `tagged_value` convert from and to its [`TaggedValue`].
+pub fn enum_dropdown_widget<E: Copy + PartialEq + std::fmt::Debug + std::fmt::Display + Send + Sync + 'static>(
+	document_node: &DocumentNode,
+	node_id: NodeId,
+	index: usize,
+	name: &str,
+	blank_assist: bool,
+	variants: impl IntoIterator<Item = E>,
+	value: impl Fn(&TaggedValue) -> Option<E>,
+	tagged_value: fn(E) -> TaggedValue,
+	tooltip: &str,
+) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	let Some(input) = document_node.inputs.get(index) else {
+		log::warn!("A widget failed to be built because its node's input index is invalid.");
+		return LayoutGroup::Row { widgets: vec![] };
+	};
+	if let Some(current) = input.as_non_exposed_value().and_then(value) {
+		let variants = variants.into_iter().collect::<Vec<_>>();
+		let entries = variants
+			.iter()
+			.map(|&variant| {
+				MenuListEntry::new(format!("{variant:?}"))
+					.label(variant.to_string())
+					.on_update(update_value(move |_| tagged_value(variant), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			DropdownInput::new(vec![entries])
+				.selected_index(variants.iter().position(|&variant| variant == current).map(|index| index as u32))
+				.widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip(tooltip)
+}
+
+/// The interval between tab stops, followed by the custom stops before them, which are typed as distances separated by commas.
+pub fn tab_stops_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	let Some(input) = document_node.inputs.get(index) else {
+		log::warn!("A widget failed to be built because its node's input index is invalid.");
+		return LayoutGroup::Row { widgets: vec![] };
+	};
+	if let Some(&TaggedValue::TabStops(tab_stops)) = input.as_non_exposed_value() {
+		let stops = tab_stops.stops().map(|stop| stop.to_string()).collect::<Vec<_>>().join(", ");
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(tab_stops.interval))
+				.label("Interval")
+				.unit(" px")
+				.min(1.)
+				.on_update(update_value(
+					move |number_input: &NumberInput| {
+						let mut tab_stops = tab_stops;
+						tab_stops.interval = number_input.value.unwrap();
+						TaggedValue::TabStops(tab_stops)
+					},
+					node_id,
+					index,
+				))
+				.on_commit(commit_value)
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			TextInput::new(stops)
+				.tooltip("Custom tab stops before those at the interval, as distances along the line separated by commas")
+				.on_update(update_value(
+					move |text_input: &TextInput| {
+						let mut tab_stops = tab_stops;
+						tab_stops.set_stops(text_input.value.split(',').filter_map(|stop| stop.trim().parse().ok()));
+						TaggedValue::TabStops(tab_stops)
+					},
+					node_id,
+					index,
+				))
+				.on_commit(commit_value)
+				.widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("Tab Stops")
+}
+
+pub fn font_features_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	let Some(input) = document_node.inputs.get(index) else {
+		log::warn!("A widget failed to be built because its node's input index is invalid.");
+		return LayoutGroup::Row { widgets: vec![] };
+	};
+	if let Some(&TaggedValue::FontFeatures(features)) = input.as_non_exposed_value() {
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			font_features_popover(features, update_value(|features: &FontFeatures| TaggedValue::FontFeatures(*features), node_id, index), true),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("Features")
+}
+
+/// The OpenType features which are turned on and off by a checkbox each, along with their tooltips.
+const FONT_FEATURE_TOGGLES: [(&str, &str, fn(&mut FontFeatures) -> &mut bool); 5] = [
+	("Standard Ligatures", "Join letters like \"fi\" and \"fl\" as the font does by default", |features| {
+		&mut features.standard_ligatures
+	}),
+	(
+		"Discretionary Ligatures",
+		"Join letters with the decorative ligatures of the font, like \"ct\" and \"st\"",
+		|features| &mut features.discretionary_ligatures,
+	),
+	("Small Caps", "Replace lowercase letters with capitals designed at the height of lowercase letters", |features| {
+		&mut features.small_caps
+	}),
+	("Oldstyle Figures", "Use digits which rise above and drop below the baseline like lowercase letters", |features| {
+		&mut features.oldstyle_figures
+	}),
+	("Tabular Figures", "Give all digits the same width so columns of numbers line up", |features| {
+		&mut features.tabular_figures
+	}),
+];
+
+/// A button opening a popover of checkboxes for the OpenType features of the font, each of which passes the features with its change to `update`.
+/// Stylistic sets are given a checkbox each, since fonts use them for whatever alternate glyphs they like.
+pub fn font_features_popover(features: FontFeatures, update: impl Fn(&FontFeatures) -> Message + Send + Sync + 'static, commit: bool) -> WidgetHolder {
+	let update = Arc::new(update);
+	let checkbox = |checked: bool, toggle: Box<dyn Fn(&mut FontFeatures, bool) + Send + Sync>| {
+		let update = update.clone();
+		let checkbox = CheckboxInput::new(checked).on_update(move |input: &CheckboxInput| {
+			let mut features = features;
+			toggle(&mut features, input.checked);
+			update(&features)
+		});
+		if commit { checkbox.on_commit(commit_value) } else { checkbox }
+	};
+
+	let mut layout = vec![LayoutGroup::Row {
+		widgets: vec![TextLabel::new("Features").bold(true).widget_holder()],
+	}];
+	layout.extend(FONT_FEATURE_TOGGLES.into_iter().map(|(name, tooltip, feature)| {
+		let mut current = features;
+		LayoutGroup::Row {
+			widgets: vec![
+				checkbox(*feature(&mut current), Box::new(move |features: &mut FontFeatures, checked: bool| *feature(features) = checked))
+					.tooltip(tooltip)
+					.widget_holder(),
+				TextLabel::new(name).tooltip(tooltip).widget_holder(),
+			],
+		}
+	}));
+
+	layout.push(LayoutGroup::Row {
+		widgets: vec![TextLabel::new("Stylistic Sets").widget_holder()],
+	});
+	let stylistic_sets: Vec<_> = (1..=FontFeatures::STYLISTIC_SET_COUNT)
+		.map(|number| {
+			let tooltip = format!("Stylistic Set {number} (ss{number:02})");
+			vec![
+				checkbox(
+					features.stylistic_set(number),
+					Box::new(move |features: &mut FontFeatures, checked: bool| features.set_stylistic_set(number, checked)),
+				)
+				.tooltip(tooltip.clone())
+				.widget_holder(),
+				TextLabel::new(format!("{number:02}")).tooltip(tooltip).widget_holder(),
+			]
+		})
+		.collect();
+	layout.extend(stylistic_sets.chunks(5).map(|row| LayoutGroup::Row { widgets: row.concat() }));
+
+	PopoverButton::new().tooltip("OpenType Features").popover_layout(layout).widget_holder()
+}
+
+/// A slider for each axis of the variable font, in rows below the first one which is labeled with the input's name.
+/// The axes are detected from the font, so a message is shown in their place when it isn't a variable font or hasn't been loaded yet.
+pub fn font_variations_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, axes: &[FontAxis], blank_assist: bool) -> Vec<LayoutGroup> {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	let Some(input) = document_node.inputs.get(index) else {
+		log::warn!("A widget failed to be built because its node's input index is invalid.");
+		return vec![];
+	};
+	let Some(&TaggedValue::FontVariations(variations)) = input.as_non_exposed_value() else {
+		return vec![LayoutGroup::Row { widgets }];
+	};
+
+	if axes.is_empty() {
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("The font has no variable axes").widget_holder(),
+		]);
+		return vec![LayoutGroup::Row { widgets }];
+	}
+
+	axes.iter()
+		.enumerate()
+		.map(|(axis_index, axis)| {
+			let mut widgets = if axis_index == 0 {
+				widgets.clone()
+			} else {
+				let mut row = vec![TextLabel::new("").widget_holder()];
+				if blank_assist {
+					add_blank_assist(&mut row);
+				}
+				row
+			};
+			widgets.extend_from_slice(&[
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				font_variation_input(
+					variations,
+					axis,
+					update_value(|variations: &FontVariations| TaggedValue::FontVariations(*variations), node_id, index),
+					true,
+				),
+			]);
+			LayoutGroup::Row { widgets }
+		})
+		.collect()
+}
+
+/// A slider for one axis of the variable font, which passes the variations with the axis set to its new value to `update`.
+pub fn font_variation_input(variations: FontVariations, axis: &FontAxis, update: impl Fn(&FontVariations) -> Message + Send + Sync + 'static, commit: bool) -> WidgetHolder {
+	let tag = axis.tag;
+	let input = NumberInput::new(Some(variations.get(tag).unwrap_or(axis.default)))
+		.label(axis.name.clone())
+		.tooltip(format!("{} ({})", axis.name, String::from_utf8_lossy(&tag)))
+		.mode_range()
+		.min(axis.min)
+		.max(axis.max)
+		.on_update(move |input: &NumberInput| {
+			let mut variations = variations;
+			if let Some(value) = input.value {
+				variations.set(tag, value);
+			}
+			update(&variations)
+		});
+	if commit { input.on_commit(commit_value) } else { input }.widget_holder()
+}
+
 pub fn rgba_channel(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
 	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
 	let Some(input) = document_node.inputs.get(index) else {
@@ -1,3 +1,4 @@
+use crate::consts::{COLOR_OVERLAY_PIXEL_GRID, PIXEL_GRID_MIN_ZOOM};
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::overlays::utility_types::OverlayContext;
 use crate::messages::portfolio::document::utility_types::misc::{GridSnapping, GridType};
@@ -174,6 +175,36 @@ fn grid_overlay_isometric_dot(document: &DocumentMessageHandler, overlay_context
 	}
 }
 
+/// Outlines every document pixel when zoomed in far enough in the pixel view mode, showing how the artwork lines up with the pixels it's rasterized to.
+pub fn pixel_grid_overlay(document: &DocumentMessageHandler, overlay_context: &mut OverlayContext) {
+	if document.document_ptz.zoom() < PIXEL_GRID_MIN_ZOOM {
+		return;
+	}
+
+	let document_to_viewport = document.navigation_handler.calculate_offset_transform(overlay_context.size / 2., &document.document_ptz);
+	let [min, max] = (document_to_viewport.inverse() * Quad::from_box([DVec2::ZERO, overlay_context.size])).bounding_box();
+	let (min, max) = (min.floor(), max.ceil());
+
+	for x in min.x as i64..=max.x as i64 {
+		let (start, end) = (DVec2::new(x as f64, min.y), DVec2::new(x as f64, max.y));
+		overlay_context.line(
+			document_to_viewport.transform_point2(start),
+			document_to_viewport.transform_point2(end),
+			Some(COLOR_OVERLAY_PIXEL_GRID),
+			None,
+		);
+	}
+	for y in min.y as i64..=max.y as i64 {
+		let (start, end) = (DVec2::new(min.x, y as f64), DVec2::new(max.x, y as f64));
+		overlay_context.line(
+			document_to_viewport.transform_point2(start),
+			document_to_viewport.transform_point2(end),
+			Some(COLOR_OVERLAY_PIXEL_GRID),
+			None,
+		);
+	}
+}
+
 pub fn grid_overlay(document: &DocumentMessageHandler, overlay_context: &mut OverlayContext) {
 	match document.snapping_state.grid.grid_type {
 		GridType::Rectangle { spacing } => {
@@ -1,6 +1,7 @@
 pub mod grid_overlays;
 mod overlays_message;
 mod overlays_message_handler;
+pub mod render_statistics;
 pub mod utility_functions;
 pub mod utility_types;
 
@@ -1,10 +1,13 @@
-use super::utility_types::OverlayProvider;
+use super::render_statistics::RenderStatistics;
+use super::utility_types::{OverlayColors, OverlayProvider};
 use crate::messages::prelude::*;
 
 pub struct OverlaysMessageData<'a> {
 	pub overlays_visible: bool,
 	pub ipp: &'a InputPreprocessorMessageHandler,
 	pub device_pixel_ratio: f64,
+	pub render_statistics: &'a mut RenderStatistics,
+	pub overlay_colors: &'a OverlayColors,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -20,6 +23,8 @@ impl MessageHandler<OverlaysMessage, OverlaysMessageData<'_>> for OverlaysMessag
 			overlays_visible,
 			ipp,
 			device_pixel_ratio,
+			render_statistics,
+			overlay_colors,
 		} = data;
 
 		match message {
@@ -50,17 +55,29 @@ impl MessageHandler<OverlaysMessage, OverlaysMessageData<'_>> for OverlaysMessag
 				context.clear_rect(0., 0., ipp.viewport_bounds.size().x, ipp.viewport_bounds.size().y);
 				let _ = context.reset_transform();
 
+				render_statistics.record_overlay_draw();
+				if render_statistics.visible {
+					render_statistics.draw(&OverlayContext {
+						render_context: context.clone(),
+						size: size.as_dvec2(),
+						device_pixel_ratio,
+						colors: overlay_colors.clone(),
+					});
+				}
+
 				if overlays_visible {
 					responses.add(DocumentMessage::GridOverlays(OverlayContext {
 						render_context: context.clone(),
 						size: size.as_dvec2(),
 						device_pixel_ratio,
+						colors: overlay_colors.clone(),
 					}));
 					for provider in &self.overlay_providers {
 						responses.add(provider(OverlayContext {
 							render_context: context.clone(),
 							size: size.as_dvec2(),
 							device_pixel_ratio,
+							colors: overlay_colors.clone(),
 						}));
 					}
 				}
@@ -68,7 +85,7 @@ impl MessageHandler<OverlaysMessage, OverlaysMessageData<'_>> for OverlaysMessag
 			#[cfg(not(target_arch = "wasm32"))]
 			OverlaysMessage::Draw => {
 				warn!(
-					"Cannot render overlays on non-Wasm targets.\n{responses:?} {overlays_visible} {ipp:?} {:?} {:?}",
+					"Cannot render overlays on non-Wasm targets.\n{responses:?} {overlays_visible} {ipp:?} {render_statistics:?} {overlay_colors:?} {:?} {:?}",
 					self.canvas, self.context
 				);
 			}
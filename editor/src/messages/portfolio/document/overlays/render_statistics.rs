@@ -0,0 +1,100 @@
+use super::utility_types::{OverlayContext, Pivot};
+use crate::consts::{COLOR_OVERLAY_LABEL_BACKGROUND, COLOR_OVERLAY_WHITE};
+use glam::{DAffine2, DVec2};
+use graphene_core::memo::EvaluationStatistics;
+use std::collections::VecDeque;
+
+/// How far back, in milliseconds, renders and overlay draws are counted towards their rate per second.
+const RATE_WINDOW: f64 = 1000.;
+
+/// Milliseconds elapsed since an arbitrary point in time, used for measuring the duration of graph evaluations and the rate of renders.
+pub fn now_milliseconds() -> f64 {
+	#[cfg(target_arch = "wasm32")]
+	{
+		js_sys::Date::now()
+	}
+	#[cfg(not(target_arch = "wasm32"))]
+	{
+		use once_cell::sync::Lazy;
+		static START: Lazy<std::time::Instant> = Lazy::new(std::time::Instant::now);
+		START.elapsed().as_secs_f64() * 1000.
+	}
+}
+
+/// Measurements of the recent viewport renders and overlay draws, shown on the canvas in a heads-up display when enabled from the View menu.
+#[derive(Debug, Clone, Default)]
+pub struct RenderStatistics {
+	pub visible: bool,
+	/// When each viewport render within the last [`RATE_WINDOW`] arrived.
+	render_times: VecDeque<f64>,
+	/// When each overlay draw within the last [`RATE_WINDOW`] happened.
+	overlay_draw_times: VecDeque<f64>,
+	last_evaluation_milliseconds: Option<f64>,
+	last_evaluation: EvaluationStatistics,
+}
+
+impl RenderStatistics {
+	/// Records a viewport render that has arrived from the node graph runtime, which took the given time to evaluate.
+	pub fn record_render(&mut self, evaluation_milliseconds: f64, evaluation: EvaluationStatistics) {
+		Self::record_time(&mut self.render_times);
+		self.last_evaluation_milliseconds = Some(evaluation_milliseconds);
+		self.last_evaluation = evaluation;
+	}
+
+	pub fn record_overlay_draw(&mut self) {
+		Self::record_time(&mut self.overlay_draw_times);
+	}
+
+	pub fn frames_per_second(&mut self) -> usize {
+		Self::rate(&mut self.render_times)
+	}
+
+	pub fn overlay_draws_per_second(&mut self) -> usize {
+		Self::rate(&mut self.overlay_draw_times)
+	}
+
+	/// The lines of text shown in the heads-up display.
+	pub fn lines(&mut self) -> Vec<String> {
+		let evaluation_time = match self.last_evaluation_milliseconds {
+			Some(milliseconds) => format!("{milliseconds:.1} ms"),
+			None => "—".to_string(),
+		};
+		let cache_hit_rate = match self.last_evaluation.cache_hit_rate() {
+			Some(rate) => format!("{:.0}%", rate * 100.),
+			None => "—".to_string(),
+		};
+
+		vec![
+			format!("FPS: {}", self.frames_per_second()),
+			format!("Evaluation: {evaluation_time}"),
+			format!("Nodes Evaluated: {}", self.last_evaluation.nodes_evaluated),
+			format!("Cache Hit Rate: {cache_hit_rate}"),
+			format!("Overlay Draws: {}/s", self.overlay_draws_per_second()),
+		]
+	}
+
+	/// Draws the heads-up display in the top left corner of the viewport.
+	pub fn draw(&mut self, overlay_context: &OverlayContext) {
+		const LINE_HEIGHT: f64 = 16.;
+		const MARGIN: f64 = 8.;
+
+		for (index, line) in self.lines().iter().enumerate() {
+			let transform = DAffine2::from_translation(DVec2::new(MARGIN, MARGIN + index as f64 * LINE_HEIGHT));
+			overlay_context.text(line, COLOR_OVERLAY_WHITE, Some(COLOR_OVERLAY_LABEL_BACKGROUND), transform, 4., [Pivot::Start, Pivot::Start]);
+		}
+	}
+
+	fn record_time(times: &mut VecDeque<f64>) {
+		times.push_back(now_milliseconds());
+		Self::rate(times);
+	}
+
+	/// Forgets the times older than the [`RATE_WINDOW`], then counts those remaining.
+	fn rate(times: &mut VecDeque<f64>) -> usize {
+		let now = now_milliseconds();
+		while times.front().is_some_and(|&time| now - time > RATE_WINDOW) {
+			times.pop_front();
+		}
+		times.len()
+	}
+}
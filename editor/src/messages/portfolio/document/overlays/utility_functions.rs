@@ -1,4 +1,4 @@
-use super::utility_types::{DrawHandles, OverlayContext};
+use super::utility_types::{DrawHandles, OverlayBatch, OverlayContext};
 use crate::consts::HIDE_HANDLE_DISTANCE;
 use crate::messages::tool::common_functionality::shape_editor::{SelectedLayerState, ShapeState};
 use crate::messages::tool::tool_messages::tool_prelude::{DocumentMessageHandler, PreferencesMessageHandler};
@@ -53,24 +53,24 @@ pub fn selected_segments(document: &DocumentMessageHandler, shape_editor: &mut S
 	selected_segments
 }
 
-fn overlay_bezier_handles(bezier: Bezier, segment_id: SegmentId, transform: DAffine2, is_selected: impl Fn(ManipulatorPointId) -> bool, overlay_context: &mut OverlayContext) {
+fn overlay_bezier_handles(bezier: Bezier, segment_id: SegmentId, transform: DAffine2, is_selected: impl Fn(ManipulatorPointId) -> bool, batch: &mut OverlayBatch) {
 	let bezier = bezier.apply_transformation(|point| transform.transform_point2(point));
 	let not_under_anchor = |position: DVec2, anchor: DVec2| position.distance_squared(anchor) >= HIDE_HANDLE_DISTANCE * HIDE_HANDLE_DISTANCE;
 
 	match bezier.handles {
 		BezierHandles::Quadratic { handle } if not_under_anchor(handle, bezier.start) && not_under_anchor(handle, bezier.end) => {
-			overlay_context.line(handle, bezier.start, None, None);
-			overlay_context.line(handle, bezier.end, None, None);
-			overlay_context.manipulator_handle(handle, is_selected(ManipulatorPointId::PrimaryHandle(segment_id)), None);
+			batch.line(handle, bezier.start);
+			batch.line(handle, bezier.end);
+			batch.manipulator_handle(handle, is_selected(ManipulatorPointId::PrimaryHandle(segment_id)));
 		}
 		BezierHandles::Cubic { handle_start, handle_end } => {
 			if not_under_anchor(handle_start, bezier.start) {
-				overlay_context.line(handle_start, bezier.start, None, None);
-				overlay_context.manipulator_handle(handle_start, is_selected(ManipulatorPointId::PrimaryHandle(segment_id)), None);
+				batch.line(handle_start, bezier.start);
+				batch.manipulator_handle(handle_start, is_selected(ManipulatorPointId::PrimaryHandle(segment_id)));
 			}
 			if not_under_anchor(handle_end, bezier.end) {
-				overlay_context.line(handle_end, bezier.end, None, None);
-				overlay_context.manipulator_handle(handle_end, is_selected(ManipulatorPointId::EndHandle(segment_id)), None);
+				batch.line(handle_end, bezier.end);
+				batch.manipulator_handle(handle_end, is_selected(ManipulatorPointId::EndHandle(segment_id)));
 			}
 		}
 		_ => {}
@@ -84,7 +84,7 @@ pub fn overlay_bezier_handle_specific_point(
 	point_to_render: PointId,
 	transform: DAffine2,
 	is_selected: impl Fn(ManipulatorPointId) -> bool,
-	overlay_context: &mut OverlayContext,
+	batch: &mut OverlayBatch,
 ) {
 	let bezier = bezier.apply_transformation(|point| transform.transform_point2(point));
 	let not_under_anchor = |position: DVec2, anchor: DVec2| position.distance_squared(anchor) >= HIDE_HANDLE_DISTANCE * HIDE_HANDLE_DISTANCE;
@@ -93,18 +93,18 @@ pub fn overlay_bezier_handle_specific_point(
 		BezierHandles::Quadratic { handle } => {
 			if not_under_anchor(handle, bezier.start) && not_under_anchor(handle, bezier.end) {
 				let end = if start == point_to_render { bezier.start } else { bezier.end };
-				overlay_context.line(handle, end, None, None);
-				overlay_context.manipulator_handle(handle, is_selected(ManipulatorPointId::PrimaryHandle(segment_id)), None);
+				batch.line(handle, end);
+				batch.manipulator_handle(handle, is_selected(ManipulatorPointId::PrimaryHandle(segment_id)));
 			}
 		}
 		BezierHandles::Cubic { handle_start, handle_end } => {
 			if not_under_anchor(handle_start, bezier.start) && (point_to_render == start) {
-				overlay_context.line(handle_start, bezier.start, None, None);
-				overlay_context.manipulator_handle(handle_start, is_selected(ManipulatorPointId::PrimaryHandle(segment_id)), None);
+				batch.line(handle_start, bezier.start);
+				batch.manipulator_handle(handle_start, is_selected(ManipulatorPointId::PrimaryHandle(segment_id)));
 			}
 			if not_under_anchor(handle_end, bezier.end) && (point_to_render == end) {
-				overlay_context.line(handle_end, bezier.end, None, None);
-				overlay_context.manipulator_handle(handle_end, is_selected(ManipulatorPointId::EndHandle(segment_id)), None);
+				batch.line(handle_end, bezier.end);
+				batch.manipulator_handle(handle_end, is_selected(ManipulatorPointId::EndHandle(segment_id)));
 			}
 		}
 		_ => {}
@@ -122,10 +122,13 @@ pub fn path_overlays(document: &DocumentMessageHandler, draw_handles: DrawHandle
 
 		let opposite_handles_data: Vec<(PointId, SegmentId)> = shape_editor.selected_points().filter_map(|point_id| vector_data.adjacent_segment(point_id)).collect();
 
+		// Layers may have hundreds of anchors and handles, so they are drawn together in a batch
+		let mut batch = OverlayBatch::default();
+
 		match draw_handles {
 			DrawHandles::All => {
 				vector_data.segment_bezier_iter().for_each(|(segment_id, bezier, _start, _end)| {
-					overlay_bezier_handles(bezier, segment_id, transform, is_selected, overlay_context);
+					overlay_bezier_handles(bezier, segment_id, transform, is_selected, &mut batch);
 				});
 			}
 			DrawHandles::SelectedAnchors(ref selected_segments) => {
@@ -133,12 +136,12 @@ pub fn path_overlays(document: &DocumentMessageHandler, draw_handles: DrawHandle
 					.segment_bezier_iter()
 					.filter(|(segment_id, ..)| selected_segments.contains(segment_id))
 					.for_each(|(segment_id, bezier, _start, _end)| {
-						overlay_bezier_handles(bezier, segment_id, transform, is_selected, overlay_context);
+						overlay_bezier_handles(bezier, segment_id, transform, is_selected, &mut batch);
 					});
 
 				for (segment_id, bezier, start, end) in vector_data.segment_bezier_iter() {
 					if let Some((corresponding_anchor, _)) = opposite_handles_data.iter().find(|(_, adj_segment_id)| adj_segment_id == &segment_id) {
-						overlay_bezier_handle_specific_point(bezier, segment_id, (start, end), *corresponding_anchor, transform, is_selected, overlay_context);
+						overlay_bezier_handle_specific_point(bezier, segment_id, (start, end), *corresponding_anchor, transform, is_selected, &mut batch);
 					}
 				}
 			}
@@ -149,9 +152,9 @@ pub fn path_overlays(document: &DocumentMessageHandler, draw_handles: DrawHandle
 					.for_each(|(segment_id, bezier, start, end)| {
 						if segment_endpoints.get(&segment_id).unwrap().len() == 1 {
 							let point_to_render = segment_endpoints.get(&segment_id).unwrap()[0];
-							overlay_bezier_handle_specific_point(bezier, segment_id, (start, end), point_to_render, transform, is_selected, overlay_context);
+							overlay_bezier_handle_specific_point(bezier, segment_id, (start, end), point_to_render, transform, is_selected, &mut batch);
 						} else {
-							overlay_bezier_handles(bezier, segment_id, transform, is_selected, overlay_context);
+							overlay_bezier_handles(bezier, segment_id, transform, is_selected, &mut batch);
 						}
 					});
 			}
@@ -159,8 +162,10 @@ pub fn path_overlays(document: &DocumentMessageHandler, draw_handles: DrawHandle
 		}
 
 		for (&id, &position) in vector_data.point_domain.ids().iter().zip(vector_data.point_domain.positions()) {
-			overlay_context.manipulator_anchor(transform.transform_point2(position), is_selected(ManipulatorPointId::Anchor(id)), None);
+			batch.manipulator_anchor(transform.transform_point2(position), is_selected(ManipulatorPointId::Anchor(id)));
 		}
+
+		overlay_context.batch(&batch, None);
 	}
 }
 
@@ -1,8 +1,10 @@
 use super::utility_functions::overlay_canvas_context;
 use crate::consts::{
-	COLOR_OVERLAY_BLUE, COLOR_OVERLAY_GREEN, COLOR_OVERLAY_RED, COLOR_OVERLAY_WHITE, COLOR_OVERLAY_YELLOW, COMPASS_ROSE_ARROW_SIZE, COMPASS_ROSE_HOVER_RING_DIAMETER, COMPASS_ROSE_MAIN_RING_DIAMETER,
-	COMPASS_ROSE_RING_INNER_DIAMETER, MANIPULATOR_GROUP_MARKER_SIZE, PIVOT_CROSSHAIR_LENGTH, PIVOT_CROSSHAIR_THICKNESS, PIVOT_DIAMETER,
+	COLOR_OVERLAY_BLUE, COLOR_OVERLAY_GREEN, COLOR_OVERLAY_HIGH_CONTRAST_ACCENT, COLOR_OVERLAY_HIGH_CONTRAST_FILL, COLOR_OVERLAY_HIGH_CONTRAST_SNAPPING, COLOR_OVERLAY_RED, COLOR_OVERLAY_WHITE,
+	COLOR_OVERLAY_YELLOW, COMPASS_ROSE_ARROW_SIZE, COMPASS_ROSE_HOVER_RING_DIAMETER, COMPASS_ROSE_MAIN_RING_DIAMETER, COMPASS_ROSE_RING_INNER_DIAMETER, MANIPULATOR_GROUP_MARKER_SIZE,
+	PIVOT_CROSSHAIR_LENGTH, PIVOT_CROSSHAIR_THICKNESS, PIVOT_DIAMETER,
 };
+use crate::messages::portfolio::document::utility_types::document_metadata::{DocumentMetadata, LayerNodeIdentifier};
 use crate::messages::prelude::Message;
 use bezier_rs::{Bezier, Subpath};
 use core::borrow::Borrow;
@@ -10,11 +12,56 @@ use core::f64::consts::{FRAC_PI_2, TAU};
 use glam::{DAffine2, DVec2};
 use graphene_core::renderer::Quad;
 use graphene_std::vector::{PointId, SegmentId, VectorData};
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use wasm_bindgen::JsValue;
 
 pub type OverlayProvider = fn(OverlayContext) -> Message;
 
+/// The colors of the overlays drawn by the tools, as 6-digit CSS hex colors, which can be changed in the preferences.
+#[derive(PartialEq, Eq, Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(default)]
+pub struct OverlayColors {
+	/// Selections, outlines, paths, handles, and most other overlays.
+	pub accent: String,
+	/// The markers and labels shown where the pointer snaps to something.
+	pub snapping: String,
+	/// The box around text while it's being placed or edited.
+	pub text_bounds: String,
+	/// The inside of unselected handles, anchors, and other hollow markers.
+	pub fill: String,
+}
+
+impl Default for OverlayColors {
+	fn default() -> Self {
+		Self {
+			accent: COLOR_OVERLAY_BLUE.to_string(),
+			snapping: COLOR_OVERLAY_BLUE.to_string(),
+			text_bounds: COLOR_OVERLAY_BLUE.to_string(),
+			fill: COLOR_OVERLAY_WHITE.to_string(),
+		}
+	}
+}
+
+impl OverlayColors {
+	/// Colors which stand out against most artwork, for those who have trouble seeing the default ones.
+	pub fn high_contrast() -> Self {
+		Self {
+			accent: COLOR_OVERLAY_HIGH_CONTRAST_ACCENT.to_string(),
+			snapping: COLOR_OVERLAY_HIGH_CONTRAST_SNAPPING.to_string(),
+			text_bounds: COLOR_OVERLAY_HIGH_CONTRAST_ACCENT.to_string(),
+			fill: COLOR_OVERLAY_HIGH_CONTRAST_FILL.to_string(),
+		}
+	}
+
+	/// Makes a translucent version of one of these colors, as a CSS hex color.
+	pub fn with_alpha(color: &str, alpha: f32) -> String {
+		let color = color.strip_prefix('#').and_then(graphene_std::Color::from_rgb_str).unwrap_or(graphene_std::Color::BLACK);
+		"#".to_string() + &color.with_alpha(alpha).to_rgba_hex_srgb()
+	}
+}
+
 pub fn empty_provider() -> OverlayProvider {
 	|_| Message::NoOp
 }
@@ -29,6 +76,8 @@ pub struct OverlayContext {
 	// The device pixel ratio is a property provided by the browser window and is the CSS pixel size divided by the physical monitor's pixel size.
 	// It allows better pixel density of visualizations on high-DPI displays where the OS display scaling is not 100%, or where the browser is zoomed.
 	pub device_pixel_ratio: f64,
+	/// The colors chosen in the preferences for drawing the overlays.
+	pub colors: OverlayColors,
 }
 // Message hashing isn't used but is required by the message system macros
 impl core::hash::Hash for OverlayContext {
@@ -41,8 +90,8 @@ impl OverlayContext {
 	}
 
 	pub fn draw_triangle(&mut self, base: DVec2, direction: DVec2, size: f64, color_fill: Option<&str>, color_stroke: Option<&str>) {
-		let color_fill = color_fill.unwrap_or(COLOR_OVERLAY_WHITE);
-		let color_stroke = color_stroke.unwrap_or(COLOR_OVERLAY_BLUE);
+		let color_fill = color_fill.unwrap_or(&self.colors.fill);
+		let color_stroke = color_stroke.unwrap_or(&self.colors.accent);
 		let normal = direction.perp();
 		let top = base + direction * size;
 		let edge1 = base + normal * size / 2.;
@@ -72,6 +121,23 @@ impl OverlayContext {
 		self.dashed_polygon(polygon, color_fill, None, None, None);
 	}
 
+	/// Fills the polygon without outlining it, like the highlight behind selected text. The polygon isn't snapped to the pixel grid, so highlights of neighboring characters meet without gaps.
+	pub fn fill_polygon(&mut self, polygon: &[DVec2], color_fill: &str) {
+		let Some(last) = polygon.last() else { return };
+
+		self.start_dpi_aware_transform();
+
+		self.render_context.begin_path();
+		self.render_context.move_to(last.x, last.y);
+		for point in polygon {
+			self.render_context.line_to(point.x, point.y);
+		}
+		self.render_context.set_fill_style_str(color_fill);
+		self.render_context.fill();
+
+		self.end_dpi_aware_transform();
+	}
+
 	pub fn dashed_polygon(&mut self, polygon: &[DVec2], color_fill: Option<&str>, dash_width: Option<f64>, dash_gap_width: Option<f64>, dash_offset: Option<f64>) {
 		if polygon.len() < 2 {
 			return;
@@ -110,7 +176,7 @@ impl OverlayContext {
 			self.render_context.fill();
 		}
 
-		self.render_context.set_stroke_style_str(COLOR_OVERLAY_BLUE);
+		self.render_context.set_stroke_style_str(&self.colors.accent);
 		self.render_context.stroke();
 
 		// Reset the dash pattern back to solid
@@ -161,7 +227,7 @@ impl OverlayContext {
 		self.render_context.move_to(start.x, start.y);
 		self.render_context.line_to(end.x, end.y);
 		self.render_context.set_line_width(thickness.unwrap_or(1.));
-		self.render_context.set_stroke_style_str(color.unwrap_or(COLOR_OVERLAY_BLUE));
+		self.render_context.set_stroke_style_str(color.unwrap_or(&self.colors.accent));
 		self.render_context.stroke();
 		self.render_context.set_line_width(1.);
 
@@ -189,9 +255,9 @@ impl OverlayContext {
 			.arc(position.x, position.y, MANIPULATOR_GROUP_MARKER_SIZE / 2., 0., TAU)
 			.expect("Failed to draw the circle");
 
-		let fill = if selected { COLOR_OVERLAY_BLUE } else { COLOR_OVERLAY_WHITE };
+		let fill = if selected { &self.colors.accent } else { &self.colors.fill };
 		self.render_context.set_fill_style_str(fill);
-		self.render_context.set_stroke_style_str(color.unwrap_or(COLOR_OVERLAY_BLUE));
+		self.render_context.set_stroke_style_str(color.unwrap_or(&self.colors.accent));
 		self.render_context.fill();
 		self.render_context.stroke();
 
@@ -199,9 +265,39 @@ impl OverlayContext {
 	}
 
 	pub fn manipulator_anchor(&mut self, position: DVec2, selected: bool, color: Option<&str>) {
-		let color_stroke = color.unwrap_or(COLOR_OVERLAY_BLUE);
-		let color_fill = if selected { color_stroke } else { COLOR_OVERLAY_WHITE };
-		self.square(position, None, Some(color_fill), Some(color_stroke));
+		let color_stroke = color.map_or_else(|| self.colors.accent.clone(), str::to_string);
+		let color_fill = if selected { color_stroke.clone() } else { self.colors.fill.clone() };
+		self.square(position, None, Some(&color_fill), Some(&color_stroke));
+	}
+
+	/// Draws all the lines, handles, and anchors collected in the batch with only a few draw commands, looking the same as drawing each of them individually.
+	pub fn batch(&mut self, batch: &OverlayBatch, color: Option<&str>) {
+		let color_stroke = color.unwrap_or(&self.colors.accent);
+
+		self.start_dpi_aware_transform();
+		self.render_context.set_stroke_style_str(color_stroke);
+
+		// Lines go beneath the handles and anchors at their ends
+		if let Some(path) = BatchShape::Lines.retained_path(&batch.lines) {
+			self.render_context.stroke_with_path(&path);
+		}
+
+		for (shape, points) in [(BatchShape::Circles, &batch.handles), (BatchShape::Squares, &batch.anchors)] {
+			for (selected, points) in [false, true].into_iter().zip(points) {
+				let Some(path) = shape.retained_path(points) else { continue };
+
+				let color_fill = match (selected, shape) {
+					(false, _) => self.colors.fill.as_str(),
+					(true, BatchShape::Circles) => self.colors.accent.as_str(),
+					(true, _) => color_stroke,
+				};
+				self.render_context.set_fill_style_str(color_fill);
+				self.render_context.fill_with_path_2d(&path);
+				self.render_context.stroke_with_path(&path);
+			}
+		}
+
+		self.end_dpi_aware_transform();
 	}
 
 	/// Transforms the canvas context to adjust for DPI scaling
@@ -223,8 +319,8 @@ impl OverlayContext {
 
 	pub fn square(&mut self, position: DVec2, size: Option<f64>, color_fill: Option<&str>, color_stroke: Option<&str>) {
 		let size = size.unwrap_or(MANIPULATOR_GROUP_MARKER_SIZE);
-		let color_fill = color_fill.unwrap_or(COLOR_OVERLAY_WHITE);
-		let color_stroke = color_stroke.unwrap_or(COLOR_OVERLAY_BLUE);
+		let color_fill = color_fill.unwrap_or(&self.colors.fill);
+		let color_stroke = color_stroke.unwrap_or(&self.colors.accent);
 
 		let position = position.round() - DVec2::splat(0.5);
 		let corner = position - DVec2::splat(size) / 2.;
@@ -243,7 +339,7 @@ impl OverlayContext {
 
 	pub fn pixel(&mut self, position: DVec2, color: Option<&str>) {
 		let size = 1.;
-		let color_fill = color.unwrap_or(COLOR_OVERLAY_WHITE);
+		let color_fill = color.unwrap_or(&self.colors.fill);
 
 		let position = position.round() - DVec2::splat(0.5);
 		let corner = position - DVec2::splat(size) / 2.;
@@ -259,8 +355,8 @@ impl OverlayContext {
 	}
 
 	pub fn circle(&mut self, position: DVec2, radius: f64, color_fill: Option<&str>, color_stroke: Option<&str>) {
-		let color_fill = color_fill.unwrap_or(COLOR_OVERLAY_WHITE);
-		let color_stroke = color_stroke.unwrap_or(COLOR_OVERLAY_BLUE);
+		let color_fill = color_fill.unwrap_or(&self.colors.fill);
+		let color_stroke = color_stroke.unwrap_or(&self.colors.accent);
 		let position = position.round();
 
 		self.start_dpi_aware_transform();
@@ -308,30 +404,26 @@ impl OverlayContext {
 	}
 
 	pub fn draw_angle(&mut self, pivot: DVec2, radius: f64, arc_radius: f64, offset_angle: f64, angle: f64) {
-		let color_line = COLOR_OVERLAY_BLUE;
+		let color_line = self.colors.accent.clone();
 
 		let end_point1 = pivot + radius * DVec2::from_angle(angle + offset_angle);
 		let end_point2 = pivot + radius * DVec2::from_angle(offset_angle);
-		self.line(pivot, end_point1, Some(color_line), None);
-		self.line(pivot, end_point2, Some(color_line), None);
+		self.line(pivot, end_point1, Some(&color_line), None);
+		self.line(pivot, end_point2, Some(&color_line), None);
 
 		self.draw_arc(pivot, arc_radius, offset_angle, (angle) % TAU + offset_angle);
 	}
 
 	pub fn draw_scale(&mut self, start: DVec2, scale: f64, radius: f64, text: &str) {
 		let sign = scale.signum();
-		let mut fill_color = graphene_std::Color::from_rgb_str(crate::consts::COLOR_OVERLAY_WHITE.strip_prefix('#').unwrap())
-			.unwrap()
-			.with_alpha(0.05)
-			.to_rgba_hex_srgb();
-		fill_color.insert(0, '#');
+		let fill_color = OverlayColors::with_alpha(&self.colors.fill, 0.05);
 		let fill_color = Some(fill_color.as_str());
 		self.line(start + DVec2::X * radius * sign, start + DVec2::X * (radius * scale), None, None);
 		self.circle(start, radius, fill_color, None);
 		self.circle(start, radius * scale.abs(), fill_color, None);
 		self.text(
 			text,
-			COLOR_OVERLAY_BLUE,
+			&self.colors.accent,
 			None,
 			DAffine2::from_translation(start + sign * DVec2::X * radius * (1. + scale.abs()) / 2.),
 			2.,
@@ -360,11 +452,7 @@ impl OverlayContext {
 
 		// Hover ring
 		if show_hover_ring {
-			let mut fill_color = graphene_std::Color::from_rgb_str(COLOR_OVERLAY_BLUE.strip_prefix('#').unwrap())
-				.unwrap()
-				.with_alpha(0.5)
-				.to_rgba_hex_srgb();
-			fill_color.insert(0, '#');
+			let fill_color = OverlayColors::with_alpha(&self.colors.accent, 0.5);
 
 			self.render_context.set_line_width(HOVER_RING_STROKE_WIDTH);
 			self.render_context.begin_path();
@@ -404,7 +492,7 @@ impl OverlayContext {
 		self.render_context.set_line_width(MAIN_RING_STROKE_WIDTH);
 		self.render_context.begin_path();
 		self.render_context.arc(center.x, center.y, MAIN_RING_CENTERLINE_RADIUS, 0., TAU).expect("Failed to draw main ring");
-		self.render_context.set_stroke_style_str(COLOR_OVERLAY_BLUE);
+		self.render_context.set_stroke_style_str(&self.colors.accent);
 		self.render_context.stroke();
 
 		// Restore the old line width
@@ -459,7 +547,7 @@ impl OverlayContext {
 			self.bezier_command(bezier, transform, move_to);
 		}
 
-		self.render_context.set_stroke_style_str(COLOR_OVERLAY_BLUE);
+		self.render_context.set_stroke_style_str(&self.colors.accent);
 		self.render_context.stroke();
 
 		self.end_dpi_aware_transform();
@@ -470,7 +558,7 @@ impl OverlayContext {
 
 		self.render_context.begin_path();
 		self.bezier_command(bezier, transform, true);
-		self.render_context.set_stroke_style_str(COLOR_OVERLAY_BLUE);
+		self.render_context.set_stroke_style_str(&self.colors.accent);
 		self.render_context.stroke();
 
 		self.end_dpi_aware_transform();
@@ -494,55 +582,38 @@ impl OverlayContext {
 	}
 
 	pub fn outline(&mut self, subpaths: impl Iterator<Item = impl Borrow<Subpath<PointId>>>, transform: DAffine2) {
-		self.start_dpi_aware_transform();
+		let Some(path) = outline_path(subpaths, transform) else { return };
 
-		self.render_context.begin_path();
-		for subpath in subpaths {
-			let subpath = subpath.borrow();
-			let mut curves = subpath.iter().peekable();
+		self.start_dpi_aware_transform();
+		self.render_context.set_stroke_style_str(&self.colors.accent);
+		self.render_context.stroke_with_path(&path);
+		self.end_dpi_aware_transform();
+	}
 
-			let Some(first) = curves.peek() else {
-				continue;
-			};
+	/// Outlines the click targets of a layer being hovered over, like [`Self::outline`], but reuses the path from the previous frame while the same layer is hovered and it hasn't changed.
+	/// This keeps redrawing the hover outline on every pointer move cheap, since it only depends on the click targets from the last render rather than evaluating the graph.
+	pub fn hover_outline(&mut self, layer: LayerNodeIdentifier, metadata: &DocumentMetadata) {
+		let transform = metadata.transform_to_viewport(layer);
 
-			self.render_context.move_to(transform.transform_point2(first.start()).x, transform.transform_point2(first.start()).y);
-			for curve in curves {
-				match curve.handles {
-					bezier_rs::BezierHandles::Linear => {
-						let a = transform.transform_point2(curve.end());
-						let a = a.round() - DVec2::splat(0.5);
-
-						self.render_context.line_to(a.x, a.y)
-					}
-					bezier_rs::BezierHandles::Quadratic { handle } => {
-						let a = transform.transform_point2(handle);
-						let b = transform.transform_point2(curve.end());
-						let a = a.round() - DVec2::splat(0.5);
-						let b = b.round() - DVec2::splat(0.5);
-
-						self.render_context.quadratic_curve_to(a.x, a.y, b.x, b.y)
-					}
-					bezier_rs::BezierHandles::Cubic { handle_start, handle_end } => {
-						let a = transform.transform_point2(handle_start);
-						let b = transform.transform_point2(handle_end);
-						let c = transform.transform_point2(curve.end());
-						let a = a.round() - DVec2::splat(0.5);
-						let b = b.round() - DVec2::splat(0.5);
-						let c = c.round() - DVec2::splat(0.5);
-
-						self.render_context.bezier_curve_to(a.x, a.y, b.x, b.y, c.x, c.y)
-					}
-				}
-			}
+		let mut hasher = DefaultHasher::new();
+		(layer, metadata.click_target_generation()).hash(&mut hasher);
+		transform.to_cols_array().map(f64::to_bits).hash(&mut hasher);
+		let key = hasher.finish();
 
-			if subpath.closed() {
-				self.render_context.close_path();
+		let path = RETAINED_HOVER_OUTLINE.with_borrow_mut(|retained| {
+			if let Some((_, path)) = retained.as_ref().filter(|(retained_key, _)| *retained_key == key) {
+				return Some(path.clone());
 			}
-		}
 
-		self.render_context.set_stroke_style_str(COLOR_OVERLAY_BLUE);
-		self.render_context.stroke();
+			let path = outline_path(metadata.layer_outline(layer), transform)?;
+			*retained = Some((key, path.clone()));
+			Some(path)
+		});
+		let Some(path) = path else { return };
 
+		self.start_dpi_aware_transform();
+		self.render_context.set_stroke_style_str(&self.colors.accent);
+		self.render_context.stroke_with_path(&path);
 		self.end_dpi_aware_transform();
 	}
 
@@ -583,6 +654,147 @@ impl OverlayContext {
 	}
 }
 
+/// Overlay primitives collected to be drawn together by [`OverlayContext::batch`], instead of issuing separate draw commands for each of them.
+/// Tools drawing hundreds of anchors and handles each frame should use this, since every batch is drawn as just a few paths which are retained between frames while unchanged.
+#[derive(Clone, Debug, Default)]
+pub struct OverlayBatch {
+	/// The start and end of each line, one after the other.
+	lines: Vec<DVec2>,
+	/// Unselected and selected handles.
+	handles: [Vec<DVec2>; 2],
+	/// Unselected and selected anchors.
+	anchors: [Vec<DVec2>; 2],
+}
+
+impl OverlayBatch {
+	pub fn line(&mut self, start: DVec2, end: DVec2) {
+		self.lines.extend([start, end].map(|point| point.round() - DVec2::splat(0.5)));
+	}
+
+	pub fn manipulator_handle(&mut self, position: DVec2, selected: bool) {
+		self.handles[selected as usize].push(position.round() - DVec2::splat(0.5));
+	}
+
+	pub fn manipulator_anchor(&mut self, position: DVec2, selected: bool) {
+		self.anchors[selected as usize].push(position.round() - DVec2::splat(0.5));
+	}
+}
+
+/// Maximum number of batched overlay paths retained between frames before they are all discarded.
+const RETAINED_OVERLAY_PATHS_CAPACITY: usize = 64;
+
+thread_local! {
+	/// Paths built for batched overlays in previous frames, keyed by a hash of their shape and points, so unchanged batches don't have to be built again.
+	static RETAINED_OVERLAY_PATHS: RefCell<HashMap<u64, web_sys::Path2d>> = RefCell::new(HashMap::new());
+}
+
+thread_local! {
+	/// The outline of the layer hovered over in the previous frame, keyed by a hash of the layer, its click targets, and its transform.
+	static RETAINED_HOVER_OUTLINE: RefCell<Option<(u64, web_sys::Path2d)>> = const { RefCell::new(None) };
+}
+
+/// Builds a path through the subpaths, with their anchors and handles snapped to the pixel grid so the outline is crisp.
+fn outline_path(subpaths: impl Iterator<Item = impl Borrow<Subpath<PointId>>>, transform: DAffine2) -> Option<web_sys::Path2d> {
+	let path = web_sys::Path2d::new().ok()?;
+	let snap = |point: DVec2| transform.transform_point2(point).round() - DVec2::splat(0.5);
+
+	for subpath in subpaths {
+		let subpath = subpath.borrow();
+		let mut curves = subpath.iter().peekable();
+
+		let Some(first) = curves.peek() else {
+			continue;
+		};
+
+		let start = transform.transform_point2(first.start());
+		path.move_to(start.x, start.y);
+		for curve in curves {
+			match curve.handles {
+				bezier_rs::BezierHandles::Linear => {
+					let a = snap(curve.end());
+					path.line_to(a.x, a.y)
+				}
+				bezier_rs::BezierHandles::Quadratic { handle } => {
+					let [a, b] = [snap(handle), snap(curve.end())];
+					path.quadratic_curve_to(a.x, a.y, b.x, b.y)
+				}
+				bezier_rs::BezierHandles::Cubic { handle_start, handle_end } => {
+					let [a, b, c] = [snap(handle_start), snap(handle_end), snap(curve.end())];
+					path.bezier_curve_to(a.x, a.y, b.x, b.y, c.x, c.y)
+				}
+			}
+		}
+
+		if subpath.closed() {
+			path.close_path();
+		}
+	}
+
+	Some(path)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum BatchShape {
+	Lines,
+	Circles,
+	Squares,
+}
+
+impl BatchShape {
+	/// Gets the path drawing this shape at every point, reusing the one from a previous frame if the points haven't changed.
+	fn retained_path(self, points: &[DVec2]) -> Option<web_sys::Path2d> {
+		if points.is_empty() {
+			return None;
+		}
+
+		let mut hasher = DefaultHasher::new();
+		self.hash(&mut hasher);
+		points.iter().for_each(|point| point.to_array().map(f64::to_bits).hash(&mut hasher));
+		let key = hasher.finish();
+
+		RETAINED_OVERLAY_PATHS.with_borrow_mut(|retained| {
+			if let Some(path) = retained.get(&key) {
+				return Some(path.clone());
+			}
+
+			let path = self.build_path(points)?;
+			if retained.len() >= RETAINED_OVERLAY_PATHS_CAPACITY {
+				retained.clear();
+			}
+			retained.insert(key, path.clone());
+			Some(path)
+		})
+	}
+
+	fn build_path(self, points: &[DVec2]) -> Option<web_sys::Path2d> {
+		let path = web_sys::Path2d::new().ok()?;
+		let radius = MANIPULATOR_GROUP_MARKER_SIZE / 2.;
+
+		match self {
+			BatchShape::Lines => {
+				for line in points.chunks_exact(2) {
+					path.move_to(line[0].x, line[0].y);
+					path.line_to(line[1].x, line[1].y);
+				}
+			}
+			BatchShape::Circles => {
+				for point in points {
+					// Move to the start of the circle so it isn't joined to the previous one
+					path.move_to(point.x + radius, point.y);
+					path.arc(point.x, point.y, radius, 0., TAU).ok()?;
+				}
+			}
+			BatchShape::Squares => {
+				for point in points {
+					path.rect(point.x - radius, point.y - radius, MANIPULATOR_GROUP_MARKER_SIZE, MANIPULATOR_GROUP_MARKER_SIZE);
+				}
+			}
+		}
+
+		Some(path)
+	}
+}
+
 pub enum Pivot {
 	Start,
 	Middle,
@@ -13,6 +13,7 @@ impl MessageHandler<PropertiesPanelMessage, (&PersistentData, PropertiesPanelMes
 			network_interface,
 			selection_network_path,
 			document_name,
+			swatches,
 			executor,
 		} = data;
 
@@ -30,6 +31,7 @@ impl MessageHandler<PropertiesPanelMessage, (&PersistentData, PropertiesPanelMes
 					network_interface,
 					selection_network_path,
 					document_name,
+					swatches,
 					executor,
 				};
 				let properties_sections = NodeGraphMessageHandler::collate_properties(&mut context);
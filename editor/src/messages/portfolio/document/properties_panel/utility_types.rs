@@ -1,4 +1,5 @@
 use crate::messages::portfolio::document::utility_types::network_interface::NodeNetworkInterface;
+use crate::messages::portfolio::document::utility_types::swatches::Swatch;
 use crate::node_graph_executor::NodeGraphExecutor;
 use graph_craft::document::NodeId;
 
@@ -6,5 +7,6 @@ pub struct PropertiesPanelMessageHandlerData<'a> {
 	pub network_interface: &'a mut NodeNetworkInterface,
 	pub selection_network_path: &'a [NodeId],
 	pub document_name: &'a str,
+	pub swatches: &'a [Swatch],
 	pub executor: &'a mut NodeGraphExecutor,
 }
@@ -0,0 +1,171 @@
+use super::document_metadata::LayerNodeIdentifier;
+
+/// The layers of the document bound to the columns of a dataset, so one copy of their artboard can be made for each record, saved as part of the document.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DataMerge {
+	pub bindings: Vec<DataBinding>,
+	/// The column naming the artboard or file made for each record, which are numbered if unset.
+	pub name_column: Option<String>,
+}
+
+/// A layer whose text or image is replaced by the value a record has in a column.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DataBinding {
+	pub layer: LayerNodeIdentifier,
+	/// The name of the column, which is kept when the dataset is imported again.
+	pub column: String,
+	pub target: BindingTarget,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum BindingTarget {
+	/// The text of a text layer.
+	Text,
+	/// The image of an image layer, where the value is the path or URL of the image file.
+	Image,
+}
+
+/// Records with a value for each of the named columns, imported from a CSV or JSON file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Dataset {
+	pub columns: Vec<String>,
+	pub records: Vec<Vec<String>>,
+}
+
+impl Dataset {
+	/// Reads a CSV file whose first row names the columns, with fields that contain commas, quotes, or line breaks enclosed in double quotes.
+	pub fn from_csv(text: &str) -> Result<Self, String> {
+		let mut rows = Vec::new();
+		let mut row = Vec::new();
+		let mut field = String::new();
+		let mut quoted = false;
+
+		let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+		while let Some(character) = chars.next() {
+			match (character, quoted) {
+				('"', true) if chars.peek() == Some(&'"') => {
+					chars.next();
+					field.push('"');
+				}
+				('"', true) => quoted = false,
+				('"', false) if field.is_empty() => quoted = true,
+				(',', false) => row.push(std::mem::take(&mut field)),
+				('\r', false) => {}
+				('\n', false) => {
+					row.push(std::mem::take(&mut field));
+					rows.push(std::mem::take(&mut row));
+				}
+				(character, _) => field.push(character),
+			}
+		}
+		if quoted {
+			return Err("A quoted field isn't closed".into());
+		}
+		if !field.is_empty() || !row.is_empty() {
+			row.push(field);
+			rows.push(row);
+		}
+
+		// Blank lines separate nothing
+		rows.retain(|row| !(row.len() == 1 && row[0].is_empty()));
+		let mut rows = rows.into_iter();
+		let columns = rows.next().ok_or("The file has no header row naming the columns")?;
+		let records = rows
+			.map(|mut record| {
+				record.resize(columns.len(), String::new());
+				record
+			})
+			.collect();
+		Ok(Self { columns, records })
+	}
+
+	/// Reads a JSON array of objects, where each object is a record and its keys name the columns.
+	/// The keys of each object are read in alphabetical order rather than the order they're written in, so the columns are in that order, followed by those first named in later records.
+	pub fn from_json(text: &str) -> Result<Self, String> {
+		let value = serde_json::from_str::<serde_json::Value>(text).map_err(|error| error.to_string())?;
+		let objects = value.as_array().ok_or("The file isn't an array of records")?;
+
+		let mut columns = Vec::<String>::new();
+		for object in objects {
+			let object = object.as_object().ok_or("Each record must be an object")?;
+			for key in object.keys() {
+				if !columns.contains(key) {
+					columns.push(key.clone());
+				}
+			}
+		}
+
+		let records = objects
+			.iter()
+			.filter_map(|object| object.as_object())
+			.map(|object| {
+				columns
+					.iter()
+					.map(|column| match object.get(column) {
+						None | Some(serde_json::Value::Null) => String::new(),
+						Some(serde_json::Value::String(text)) => text.clone(),
+						Some(value) => value.to_string(),
+					})
+					.collect()
+			})
+			.collect();
+		Ok(Self { columns, records })
+	}
+
+	/// Reads a dataset from a file, as JSON if its name ends with `.json` and as CSV otherwise.
+	pub fn from_file(name: &str, content: &str) -> Result<Self, String> {
+		if name.to_lowercase().ends_with(".json") {
+			Self::from_json(content)
+		} else {
+			Self::from_csv(content)
+		}
+	}
+
+	pub fn column_index(&self, column: &str) -> Option<usize> {
+		self.columns.iter().position(|name| name == column)
+	}
+
+	/// The value a record has in the named column, which is empty if there's no such column.
+	pub fn value(&self, record: usize, column: &str) -> &str {
+		self.column_index(column).and_then(|index| self.records.get(record)?.get(index)).map_or("", |value| value.as_str())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn reads_quoted_csv_fields() {
+		let csv = "name,quote\r\nAda,\"Hello, \"\"world\"\"\"\nGrace,\"Two\nlines\"\n\nAlan\n";
+		let dataset = Dataset::from_csv(csv).unwrap();
+
+		assert_eq!(dataset.columns, vec!["name", "quote"]);
+		assert_eq!(
+			dataset.records,
+			vec![
+				vec!["Ada".to_string(), "Hello, \"world\"".to_string()],
+				vec!["Grace".to_string(), "Two\nlines".to_string()],
+				vec!["Alan".to_string(), String::new()],
+			]
+		);
+		assert_eq!(dataset.value(1, "quote"), "Two\nlines");
+		assert_eq!(dataset.value(1, "missing"), "");
+		assert!(Dataset::from_csv("name\n\"unclosed").is_err());
+	}
+
+	#[test]
+	fn reads_json_records() {
+		let json = r#"[{ "name": "Ada", "year": 1815 }, { "name": "Grace", "photo": "grace.png", "year": null }]"#;
+		let dataset = Dataset::from_json(json).unwrap();
+
+		assert_eq!(dataset.columns, vec!["name", "year", "photo"]);
+		assert_eq!(dataset.records[0], vec!["Ada", "1815", ""]);
+		assert_eq!(dataset.records[1], vec!["Grace", "", "grace.png"]);
+
+		let dataset = Dataset::from_json(r#"[{ "year": 1815, "name": "Ada" }]"#).unwrap();
+		assert_eq!(dataset.columns, vec!["name", "year"]);
+		assert!(Dataset::from_json(r#"{ "name": "Ada" }"#).is_err());
+	}
+}
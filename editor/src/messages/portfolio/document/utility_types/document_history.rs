@@ -0,0 +1,183 @@
+use super::data_merge::DataMerge;
+use super::network_interface::{NetworkInterfaceDelta, NodeNetworkInterface};
+use super::swatches::Swatch;
+use super::timeline::Timeline;
+use graphene_std::renderer::{ColorProfile, ProofProfile};
+use std::collections::VecDeque;
+
+/// The parts of a document kept outside of its node network which are undone and redone along with it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DocumentState {
+	pub swatches: Vec<Swatch>,
+	pub timeline: Timeline,
+	pub color_profile: ColorProfile,
+	pub proof_profile: ProofProfile,
+	pub data_merge: DataMerge,
+}
+
+/// A stack of document states for the undo or redo history.
+///
+/// Only the newest state is stored in full. Each older state is stored as a delta from the state after it, and is reconstructed from that when everything after it has been popped.
+#[derive(Debug, Clone, Default)]
+pub struct DocumentHistory {
+	newest: Option<(NodeNetworkInterface, DocumentState)>,
+	/// Deltas from oldest to newest, where the last one is relative to `newest`.
+	/// Each also holds the rest of the document's state, unless it's the same as in the state after it.
+	older: VecDeque<(NetworkInterfaceDelta, Option<DocumentState>)>,
+	/// The number of states pushed minus the number popped, which unlike [`Self::len`] keeps counting up when the oldest states are discarded.
+	step: u64,
+}
+
+impl DocumentHistory {
+	/// Pushes a state onto the top of the stack, then discards the oldest states until there are no more than `max_len`.
+	pub fn push(&mut self, network_interface: NodeNetworkInterface, state: DocumentState, max_len: usize) {
+		if let Some((previous, previous_state)) = self.newest.take() {
+			let previous_state = (previous_state != state).then_some(previous_state);
+			self.older.push_back((previous.delta_from(&network_interface), previous_state));
+		}
+		self.newest = Some((network_interface, state));
+		self.step += 1;
+
+		self.truncate(max_len);
+	}
+
+	/// Removes the newest state from the stack, reconstructing the state before it in full.
+	pub fn pop(&mut self) -> Option<(NodeNetworkInterface, DocumentState)> {
+		let (newest, newest_state) = self.newest.take()?;
+		self.newest = self.older.pop_back().map(|(delta, state)| (newest.apply_delta(&delta), state.unwrap_or_else(|| newest_state.clone())));
+		self.step -= 1;
+		Some((newest, newest_state))
+	}
+
+	pub fn newest(&self) -> Option<&NodeNetworkInterface> {
+		self.newest.as_ref().map(|(network_interface, _)| network_interface)
+	}
+
+	pub fn len(&self) -> usize {
+		self.older.len() + self.newest.is_some() as usize
+	}
+
+	/// Identifies the position of the newest state, so the number of states pushed since an earlier step can be found even once the stack is full.
+	pub fn step(&self) -> u64 {
+		self.step
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.newest.is_none()
+	}
+
+	/// Merges the newest `count` states into one by keeping only the oldest of them, so undoing it reverts the changes of all of them at once.
+	pub fn merge_newest(&mut self, count: usize) {
+		// The oldest of the states may have been discarded already, in which case the oldest one remaining is kept
+		for _ in 1..count.min(self.len()) {
+			self.pop();
+		}
+	}
+
+	pub fn clear(&mut self) {
+		self.newest = None;
+		self.older.clear();
+	}
+
+	fn truncate(&mut self, max_len: usize) {
+		while self.len() > max_len {
+			// Older states are relative to newer ones, so the oldest can be dropped without affecting the rest
+			if self.older.pop_front().is_none() {
+				self.newest = None;
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::messages::portfolio::document::utility_types::network_interface::DocumentNodeMetadata;
+	use graph_craft::document::{DocumentNode, NodeId};
+	use graphene_core::raster::Color;
+	use std::collections::HashMap;
+
+	fn node(name: &str, visible: bool) -> Option<(DocumentNode, DocumentNodeMetadata)> {
+		let mut metadata = DocumentNodeMetadata::default();
+		metadata.persistent_metadata.display_name = name.to_string();
+		Some((DocumentNode { visible, ..Default::default() }, metadata))
+	}
+
+	/// A succession of edits to a document, each of which is a state of its history.
+	fn edited_states() -> Vec<(NodeNetworkInterface, DocumentState)> {
+		let first = NodeNetworkInterface::default().with_synced_changes(None, HashMap::from([(NodeId(1), node("First", true)), (NodeId(2), node("Second", true))]));
+		let added = first.with_synced_changes(None, HashMap::from([(NodeId(3), node("Third", true))]));
+		let removed = added.with_synced_changes(None, HashMap::from([(NodeId(2), None)]));
+		// Only the node's metadata changes, not the node itself
+		let renamed = removed.with_synced_changes(None, HashMap::from([(NodeId(1), node("Renamed", true))]));
+		let hidden = renamed.with_synced_changes(None, HashMap::from([(NodeId(3), node("Third", false))]));
+
+		let state = DocumentState::default();
+		let with_swatch = DocumentState {
+			swatches: vec![Swatch::new("Red", Color::RED)],
+			..Default::default()
+		};
+		vec![
+			(first, state.clone()),
+			(added, state.clone()),
+			(removed, state.clone()),
+			(renamed.clone(), state),
+			// Only the rest of the document's state changes, not its network
+			(renamed, with_swatch.clone()),
+			(hidden, with_swatch),
+		]
+	}
+
+	fn history_of(states: &[(NodeNetworkInterface, DocumentState)], max_len: usize) -> DocumentHistory {
+		let mut history = DocumentHistory::default();
+		for (network_interface, state) in states {
+			history.push(network_interface.clone(), state.clone(), max_len);
+		}
+		history
+	}
+
+	#[test]
+	fn popping_reconstructs_each_pushed_state() {
+		let states = edited_states();
+		let mut history = history_of(&states, usize::MAX);
+		assert_eq!(history.len(), states.len());
+
+		for expected in states.iter().rev() {
+			assert_eq!(history.pop().as_ref(), Some(expected));
+		}
+		assert!(history.pop().is_none());
+		assert!(history.is_empty());
+	}
+
+	#[test]
+	fn truncating_discards_only_the_oldest_states() {
+		let states = edited_states();
+		let mut history = history_of(&states, 3);
+		assert_eq!(history.len(), 3);
+		assert_eq!(history.step(), states.len() as u64);
+
+		for expected in states[states.len() - 3..].iter().rev() {
+			assert_eq!(history.pop().as_ref(), Some(expected));
+		}
+		assert!(history.pop().is_none());
+	}
+
+	#[test]
+	fn merging_keeps_the_oldest_of_the_merged_states() {
+		let states = edited_states();
+		let mut history = history_of(&states, usize::MAX);
+		history.merge_newest(3);
+		assert_eq!(history.len(), states.len() - 2);
+
+		for expected in states[..states.len() - 2].iter().rev() {
+			assert_eq!(history.pop().as_ref(), Some(expected));
+		}
+		assert!(history.pop().is_none());
+
+		// Once the oldest of the merged states has been discarded, the oldest one remaining is kept
+		let mut history = history_of(&states, 2);
+		history.merge_newest(3);
+		assert_eq!(history.len(), 1);
+		assert_eq!(history.pop().as_ref(), Some(&states[states.len() - 2]));
+	}
+}
@@ -1,10 +1,13 @@
 use super::network_interface::NodeNetworkInterface;
+use super::spatial_index::SpatialIndex;
+use crate::consts::CLICK_TARGET_INDEX_TOLERANCE;
 use crate::messages::portfolio::document::graph_operation::transform_utils;
 use crate::messages::portfolio::document::graph_operation::utility_types::ModifyInputsContext;
 use glam::{DAffine2, DVec2};
 use graph_craft::document::NodeId;
 use graphene_core::renderer::ClickTarget;
 use graphene_core::renderer::Quad;
+use graphene_core::text::TextSpan;
 use graphene_core::transform::Footprint;
 use graphene_std::vector::{PointId, VectorData};
 use std::collections::{HashMap, HashSet};
@@ -22,12 +25,31 @@ pub struct DocumentMetadata {
 	pub local_transforms: HashMap<NodeId, DAffine2>,
 	pub structure: HashMap<LayerNodeIdentifier, NodeRelations>,
 	pub click_targets: HashMap<LayerNodeIdentifier, Vec<ClickTarget>>,
+	/// Document space bounds of each layer's click targets, which is rebuilt along with them.
+	click_target_index: SpatialIndex<LayerNodeIdentifier>,
+	/// Incremented whenever the click targets are rebuilt, so overlays derived from them can tell when they're out of date.
+	click_target_generation: u64,
 	pub clip_targets: HashSet<NodeId>,
 	pub vector_modify: HashMap<NodeId, VectorData>,
+	/// The text layers linked so the text overflowing one text box continues in the next, and those with a max height, along with the text shown in each, worked out again whenever the network changes.
+	pub text_frames: HashMap<LayerNodeIdentifier, TextFrame>,
 	/// Transform from document space to viewport space.
 	pub document_to_viewport: DAffine2,
 }
 
+/// A text layer among the text boxes linked so the text overflowing one continues in the next, or a text box with a max height which isn't linked.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextFrame {
+	pub previous: Option<LayerNodeIdentifier>,
+	pub next: Option<LayerNodeIdentifier>,
+	/// The text shown in the text box, which is the layer's own text for the first of the linked text boxes and the text overflowing the previous one for the others.
+	pub text: String,
+	/// The spans of the text shown, counted from its start.
+	pub spans: Vec<TextSpan>,
+	/// Whether the text shown is cut off by the text box's max height.
+	pub overflows: bool,
+}
+
 impl Default for DocumentMetadata {
 	fn default() -> Self {
 		Self {
@@ -36,7 +58,10 @@ impl Default for DocumentMetadata {
 			structure: HashMap::new(),
 			vector_modify: HashMap::new(),
 			click_targets: HashMap::new(),
+			click_target_index: SpatialIndex::default(),
+			click_target_generation: 0,
 			clip_targets: HashSet::new(),
+			text_frames: HashMap::new(),
 			document_to_viewport: DAffine2::IDENTITY,
 		}
 	}
@@ -133,6 +158,40 @@ impl DocumentMetadata {
 			.reduce(Quad::combine_bounds)
 	}
 
+	/// Rebuilds the spatial index of the click targets, which must happen after they or the layer transforms change.
+	pub fn update_click_target_index(&mut self) {
+		// The bounds are indexed in document space so they stay valid when the view is panned or zoomed before the next rebuild
+		let layer_bounds = self.click_targets.iter().filter_map(|(&layer, click_targets)| {
+			let transform = self.transform_to_document(layer);
+			let bounds = click_targets
+				.iter()
+				.filter_map(|click_target| {
+					// Clicks register within half the stroke width of the outline
+					let [min, max] = click_target.bounding_box()?;
+					let stroke = DVec2::splat(click_target.stroke_width() / 2.);
+					Some((transform * Quad::from_box([min - stroke, max + stroke])).bounding_box())
+				})
+				.reduce(Quad::combine_bounds)?;
+			Some((bounds, layer))
+		});
+
+		self.click_target_index = SpatialIndex::new(layer_bounds.collect::<Vec<_>>());
+		self.click_target_generation = self.click_target_generation.wrapping_add(1);
+	}
+
+	pub fn click_target_generation(&self) -> u64 {
+		self.click_target_generation
+	}
+
+	/// Finds the layers whose click targets may overlap the given document space bounds, without checking every layer in the document.
+	/// This is a coarse test of their bounding boxes, so the click targets of the layers found still need to be checked for an actual intersection.
+	pub fn layers_overlapping_bounds(&self, bounds: [DVec2; 2]) -> impl Iterator<Item = LayerNodeIdentifier> + '_ {
+		let [a, b] = bounds;
+		let [min, max] = [a.min(b), a.max(b)];
+		let tolerance = DVec2::splat(self.document_to_viewport.inverse().transform_vector2(DVec2::X * CLICK_TARGET_INDEX_TOLERANCE).length());
+		self.click_target_index.query([min - tolerance, max + tolerance]).copied()
+	}
+
 	/// Calculate the corners of the bounding box but with a nonzero size.
 	///
 	/// If the layer bounds are `0` in either axis then they are changed to be `1`.
@@ -486,6 +545,28 @@ pub struct NodeRelations {
 // Helper functions
 // ================
 
+#[test]
+fn click_target_index_after_panning() {
+	let layer = LayerNodeIdentifier::new_unchecked(NodeId(1));
+	let mut metadata = DocumentMetadata {
+		document_to_viewport: DAffine2::from_translation(DVec2::new(100., 0.)),
+		..Default::default()
+	};
+	metadata
+		.click_targets
+		.insert(layer, vec![ClickTarget::new(bezier_rs::Subpath::new_rect(DVec2::ZERO, DVec2::splat(10.)), 0.)]);
+	metadata.update_click_target_index();
+
+	// Pan the view without rebuilding the index, then click the middle of the layer where it's now shown in the viewport
+	metadata.document_to_viewport = DAffine2::from_translation(DVec2::new(300., 50.));
+	let click = |metadata: &DocumentMetadata, viewport_position: DVec2| {
+		let document_position = metadata.document_to_viewport.inverse().transform_point2(viewport_position);
+		metadata.layers_overlapping_bounds([document_position; 2]).collect::<Vec<_>>()
+	};
+	assert_eq!(click(&metadata, DVec2::new(305., 55.)), [layer]);
+	assert!(click(&metadata, DVec2::new(105., 5.)).is_empty());
+}
+
 #[test]
 fn test_tree() {
 	let mut metadata = DocumentMetadata::default();
@@ -1,5 +1,5 @@
 use crate::consts::COLOR_OVERLAY_GRAY;
-use glam::DVec2;
+use glam::{DAffine2, DVec2};
 use graphene_core::raster::Color;
 use std::fmt;
 
@@ -13,6 +13,19 @@ pub enum FlipAxis {
 	Y,
 }
 
+/// The most recent transformation or duplication of the selected layers, which the Repeat command applies again to the current selection.
+/// Transforms are in document space, relative to the center of the selection's bounds.
+#[derive(PartialEq, Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+pub enum RepeatableAction {
+	Transform {
+		transform: DAffine2,
+	},
+	/// Duplicates the selected layers and then transforms the copies, such as to step and repeat a layout.
+	Duplicate {
+		transform: DAffine2,
+	},
+}
+
 #[derive(PartialEq, Eq, Clone, Copy, Debug, serde::Serialize, serde::Deserialize, Hash, specta::Type)]
 pub enum AlignAxis {
 	X,
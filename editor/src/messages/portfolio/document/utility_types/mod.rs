@@ -1,7 +1,14 @@
 pub mod clipboards;
+pub mod data_merge;
+pub mod document_history;
 pub mod document_metadata;
 pub mod error;
 pub mod misc;
 pub mod network_interface;
 pub mod nodes;
+pub mod numeric_transform;
+pub mod recolor;
+pub mod spatial_index;
+pub mod swatches;
+pub mod timeline;
 pub mod transformation;
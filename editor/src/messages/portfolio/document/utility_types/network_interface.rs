@@ -1,4 +1,4 @@
-use super::document_metadata::{DocumentMetadata, LayerNodeIdentifier, NodeRelations};
+use super::document_metadata::{DocumentMetadata, LayerNodeIdentifier, NodeRelations, TextFrame};
 use super::misc::PTZ;
 use super::nodes::SelectedNodes;
 use crate::consts::{EXPORTS_TO_RIGHT_EDGE_PIXEL_GAP, EXPORTS_TO_TOP_EDGE_PIXEL_GAP, GRID_SIZE, IMPORTS_TO_LEFT_EDGE_PIXEL_GAP, IMPORTS_TO_TOP_EDGE_PIXEL_GAP};
@@ -20,6 +20,7 @@ use interpreted_executor::node_registry::NODE_REGISTRY;
 use serde_json::{Value, json};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 /// All network modifications should be done through this API, so the fields cannot be public. However, all fields within this struct can be public since it it not possible to have a public mutable reference.
 #[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
@@ -38,6 +39,9 @@ pub struct NodeNetworkInterface {
 	pub resolved_types: ResolvedDocumentNodeTypes,
 	#[serde(skip)]
 	transaction_status: TransactionStatus,
+	/// Changes whenever the content of the network is edited, so it can be told apart from an earlier state without comparing them.
+	#[serde(skip)]
+	content_version: ContentVersion,
 }
 
 impl Clone for NodeNetworkInterface {
@@ -48,6 +52,7 @@ impl Clone for NodeNetworkInterface {
 			document_metadata: Default::default(),
 			resolved_types: Default::default(),
 			transaction_status: TransactionStatus::Finished,
+			content_version: self.content_version,
 		}
 	}
 }
@@ -120,6 +125,11 @@ impl NodeNetworkInterface {
 		self.transaction_status
 	}
 
+	/// Identifies the content of the network, which is the same for two states only if no edit has been made between them.
+	pub fn content_version(&self) -> u64 {
+		self.content_version.0
+	}
+
 	pub fn selected_nodes(&self) -> SelectedNodes {
 		self.selected_nodes_in_nested_network(&[]).unwrap_or_default()
 	}
@@ -1294,8 +1304,12 @@ impl NodeNetworkInterface {
 
 	/// Calculates the document bounds in document space
 	pub fn document_bounds_document_space(&self, include_artboards: bool) -> Option<[DVec2; 2]> {
-		self.document_metadata
-			.all_layers()
+		self.layers_bounds_document_space(self.document_metadata.all_layers(), include_artboards)
+	}
+
+	/// Calculates the combined bounds of the given layers in document space, clipped by the artboards they're in
+	pub fn layers_bounds_document_space(&self, layers: impl Iterator<Item = LayerNodeIdentifier>, include_artboards: bool) -> Option<[DVec2; 2]> {
+		layers
 			.filter(|layer| include_artboards || !self.is_artboard(&layer.to_node(), &[]))
 			.filter_map(|layer| {
 				if !self.is_artboard(&layer.to_node(), &[]) {
@@ -1563,6 +1577,7 @@ impl NodeNetworkInterface {
 			document_metadata: DocumentMetadata::default(),
 			resolved_types: ResolvedDocumentNodeTypes::default(),
 			transaction_status: TransactionStatus::Finished,
+			content_version: ContentVersion::default(),
 		}
 	}
 }
@@ -1663,11 +1678,17 @@ impl NodeNetworkInterface {
 	}
 
 	pub fn transaction_modified(&mut self) {
+		self.content_modified();
 		if self.transaction_status == TransactionStatus::Started {
 			self.transaction_status = TransactionStatus::Modified;
 		}
 	}
 
+	/// Gives the network a new content version, for edits which are made whether or not a transaction has started.
+	fn content_modified(&mut self) {
+		self.content_version = ContentVersion::default();
+	}
+
 	pub fn finish_transaction(&mut self) {
 		self.transaction_status = TransactionStatus::Finished;
 	}
@@ -2740,6 +2761,106 @@ impl NodeNetworkInterface {
 	}
 }
 
+// Structural deltas between states of the document, used to store the undo history without a full copy of the document for every step
+impl NodeNetworkInterface {
+	/// Finds the nodes of the document network (and their metadata) which differ from `base`, so this state can later be reconstructed from `base` with [`Self::apply_delta`].
+	/// Nested networks are compared as part of the root node containing them, and most root nodes are unchanged between neighboring history states, so the delta is usually far smaller than the document.
+	pub fn delta_from(&self, base: &Self) -> NetworkInterfaceDelta {
+		let (nodes, base_nodes) = (&self.network.nodes, &base.network.nodes);
+		let changed_nodes = nodes.iter().filter(|&(id, node)| base_nodes.get(id) != Some(node)).map(|(&id, node)| (id, Some(node.clone())));
+		let removed_nodes = base_nodes.keys().filter(|&id| !nodes.contains_key(id)).map(|&id| (id, None));
+
+		let (metadata, base_metadata) = (&self.network_metadata.persistent_metadata, &base.network_metadata.persistent_metadata);
+		let (node_metadata, base_node_metadata) = (&metadata.node_metadata, &base_metadata.node_metadata);
+		let changed_node_metadata = node_metadata
+			.iter()
+			.filter(|&(id, node_metadata)| base_node_metadata.get(id) != Some(node_metadata))
+			.map(|(&id, node_metadata)| (id, Some(node_metadata.clone())));
+		let removed_node_metadata = base_node_metadata.keys().filter(|&id| !node_metadata.contains_key(id)).map(|&id| (id, None));
+
+		NetworkInterfaceDelta {
+			exports: self.network.exports.clone(),
+			scope_injections: self.network.scope_injections.iter().map(|(key, value)| (key.clone(), value.clone())).collect(),
+			nodes: changed_nodes.chain(removed_nodes).collect(),
+			persistent_metadata: NodeNetworkPersistentMetadata {
+				node_metadata: HashMap::new(),
+				previewing: metadata.previewing,
+				navigation_metadata: metadata.navigation_metadata.clone(),
+				selection_undo_history: metadata.selection_undo_history.clone(),
+				selection_redo_history: metadata.selection_redo_history.clone(),
+			},
+			node_metadata: changed_node_metadata.chain(removed_node_metadata).collect(),
+		}
+	}
+
+	/// Reconstructs the state which `delta` was taken of, where `self` is the base state it was taken relative to.
+	pub fn apply_delta(&self, delta: &NetworkInterfaceDelta) -> Self {
+		let mut network_interface = self.clone();
+
+		let network = &mut network_interface.network;
+		network.exports = delta.exports.clone();
+		network.scope_injections = delta.scope_injections.iter().cloned().collect();
+		for (&id, node) in &delta.nodes {
+			match node {
+				Some(node) => network.nodes.insert(id, node.clone()),
+				None => network.nodes.remove(&id),
+			};
+		}
+
+		let metadata = &mut network_interface.network_metadata.persistent_metadata;
+		let node_metadata = std::mem::take(&mut metadata.node_metadata);
+		*metadata = NodeNetworkPersistentMetadata {
+			node_metadata,
+			..delta.persistent_metadata.clone()
+		};
+		for (&id, node_metadata) in &delta.node_metadata {
+			match node_metadata {
+				Some(node_metadata) => metadata.node_metadata.insert(id, node_metadata.clone()),
+				None => metadata.node_metadata.remove(&id),
+			};
+		}
+		network_interface.content_modified();
+
+		network_interface
+	}
+}
+
+// The content of the document as it's synced between collaborators, one node at a time
+impl NodeNetworkInterface {
+	/// The exports of the document network, and each of its nodes along with their metadata, which are the parts of the document collaborators change independently of each other.
+	pub fn synced_content(&self) -> (&[NodeInput], impl Iterator<Item = (NodeId, &DocumentNode, &DocumentNodeMetadata)>) {
+		let node_metadata = &self.network_metadata.persistent_metadata.node_metadata;
+		let nodes = self.network.nodes.iter().filter_map(|(&id, node)| Some((id, node, node_metadata.get(&id)?)));
+
+		(&self.network.exports, nodes)
+	}
+
+	/// Copies this state with the changes made by collaborators, where a node of `None` has been deleted.
+	pub fn with_synced_changes(&self, exports: Option<Vec<NodeInput>>, nodes: HashMap<NodeId, Option<(DocumentNode, DocumentNodeMetadata)>>) -> Self {
+		let mut network_interface = self.clone();
+
+		if let Some(exports) = exports {
+			network_interface.network.exports = exports;
+		}
+		let node_metadata = &mut network_interface.network_metadata.persistent_metadata.node_metadata;
+		for (id, node) in nodes {
+			match node {
+				Some((node, metadata)) => {
+					network_interface.network.nodes.insert(id, node);
+					node_metadata.insert(id, metadata);
+				}
+				None => {
+					network_interface.network.nodes.remove(&id);
+					node_metadata.remove(&id);
+				}
+			}
+		}
+		network_interface.content_modified();
+
+		network_interface
+	}
+}
+
 // Helper functions for mutable getters
 impl NodeNetworkInterface {
 	pub fn upstream_chain_nodes(&mut self, network_path: &[NodeId]) -> Vec<NodeId> {
@@ -3243,6 +3364,7 @@ impl NodeNetworkInterface {
 	/// Update the cached click targets of the layers
 	pub fn update_click_targets(&mut self, new_click_targets: HashMap<LayerNodeIdentifier, Vec<ClickTarget>>) {
 		self.document_metadata.click_targets = new_click_targets;
+		self.document_metadata.update_click_target_index();
 	}
 
 	/// Update the cached clip targets of the layers
@@ -3254,6 +3376,11 @@ impl NodeNetworkInterface {
 	pub fn update_vector_modify(&mut self, new_vector_modify: HashMap<NodeId, VectorData>) {
 		self.document_metadata.vector_modify = new_vector_modify;
 	}
+
+	/// Update the linked text layers and the text shown in each
+	pub fn update_text_frames(&mut self, new_text_frames: HashMap<LayerNodeIdentifier, TextFrame>) {
+		self.document_metadata.text_frames = new_text_frames;
+	}
 }
 
 // Public mutable methods
@@ -3282,6 +3409,7 @@ impl NodeNetworkInterface {
 			return;
 		};
 		node_metadata.persistent_metadata.reference = reference;
+		self.content_modified();
 	}
 
 	pub fn set_transform(&mut self, transform: DAffine2, network_path: &[NodeId]) {
@@ -3761,6 +3889,7 @@ impl NodeNetworkInterface {
 			return;
 		};
 		node.implementation = implementation;
+		self.content_modified();
 	}
 
 	// TODO: Eventually remove this document upgrade code
@@ -3771,6 +3900,7 @@ impl NodeNetworkInterface {
 			return;
 		};
 		node_metadata.persistent_metadata.network_metadata = metadata.network_metadata;
+		self.content_modified();
 	}
 
 	/// Keep metadata in sync with the new implementation if this is used by anything other than the upgrade scripts
@@ -3784,6 +3914,7 @@ impl NodeNetworkInterface {
 			return;
 		};
 		node.manual_composition = manual_composition;
+		self.content_modified();
 	}
 
 	/// Keep metadata in sync with the new implementation if this is used by anything other than the upgrade scripts
@@ -3796,7 +3927,9 @@ impl NodeNetworkInterface {
 			log::error!("Could not get node in replace_inputs");
 			return Vec::new();
 		};
-		std::mem::replace(&mut node.inputs, inputs)
+		let inputs = std::mem::replace(&mut node.inputs, inputs);
+		self.content_modified();
+		inputs
 	}
 
 	pub fn set_input(&mut self, input_connector: &InputConnector, new_input: NodeInput, network_path: &[NodeId]) {
@@ -6011,6 +6144,18 @@ pub struct NodeNetworkPersistentMetadata {
 	pub selection_redo_history: VecDeque<SelectedNodes>,
 }
 
+/// The difference between two states of the document network and its persistent metadata, created by [`NodeNetworkInterface::delta_from`].
+/// Nodes mapped to `None` don't exist in the state the delta describes.
+#[derive(Debug, Clone)]
+pub struct NetworkInterfaceDelta {
+	exports: Vec<NodeInput>,
+	scope_injections: Vec<(String, (NodeId, Type))>,
+	nodes: HashMap<NodeId, Option<DocumentNode>>,
+	/// The persistent metadata of the document network, with its node metadata left empty since that's stored in `node_metadata`.
+	persistent_metadata: NodeNetworkPersistentMetadata,
+	node_metadata: HashMap<NodeId, Option<DocumentNodeMetadata>>,
+}
+
 /// This is the same as Option, but more clear in the context of having cached metadata either being loaded or unloaded
 #[derive(Debug, Default, Clone)]
 pub enum TransientMetadata<T> {
@@ -6556,3 +6701,15 @@ pub enum TransactionStatus {
 	#[default]
 	Finished,
 }
+
+static NEXT_CONTENT_VERSION: AtomicU64 = AtomicU64::new(0);
+
+/// A version of the content of a network which is never given out twice, including to different networks.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ContentVersion(u64);
+
+impl Default for ContentVersion {
+	fn default() -> Self {
+		Self(NEXT_CONTENT_VERSION.fetch_add(1, Ordering::Relaxed))
+	}
+}
@@ -2,6 +2,7 @@ use super::document_metadata::{DocumentMetadata, LayerNodeIdentifier};
 use super::network_interface::NodeNetworkInterface;
 use graph_craft::document::{NodeId, NodeNetwork};
 use serde::ser::SerializeStruct;
+use std::collections::HashSet;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, specta::Type)]
 pub struct RawBuffer(Vec<u8>);
@@ -152,3 +153,10 @@ impl SelectedNodes {
 
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq, specta::Type)]
 pub struct CollapsedLayers(pub Vec<LayerNodeIdentifier>);
+
+impl CollapsedLayers {
+	/// The layers nested inside a collapsed layer, which aren't shown in the Layers panel until all their collapsed ancestors are expanded.
+	pub fn hidden_layers(&self, metadata: &DocumentMetadata) -> HashSet<LayerNodeIdentifier> {
+		self.0.iter().filter(|&&layer| metadata.layer_exists(layer)).flat_map(|&layer| layer.descendants(metadata)).collect()
+	}
+}
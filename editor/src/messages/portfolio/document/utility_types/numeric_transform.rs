@@ -0,0 +1,189 @@
+use std::fmt::Write;
+
+/// One of the editable fields in the transform bar, which shows the position, size, rotation, and skew of the selected layers.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum TransformField {
+	X,
+	Y,
+	Width,
+	Height,
+	Rotation,
+	Skew,
+}
+
+impl TransformField {
+	pub fn list() -> [TransformField; 6] {
+		[
+			TransformField::X,
+			TransformField::Y,
+			TransformField::Width,
+			TransformField::Height,
+			TransformField::Rotation,
+			TransformField::Skew,
+		]
+	}
+
+	pub fn label(&self) -> &'static str {
+		match self {
+			TransformField::X => "X",
+			TransformField::Y => "Y",
+			TransformField::Width => "W",
+			TransformField::Height => "H",
+			TransformField::Rotation => "R",
+			TransformField::Skew => "Skew",
+		}
+	}
+
+	pub fn tooltip(&self) -> &'static str {
+		match self {
+			TransformField::X => "Left edge of the selection bounds",
+			TransformField::Y => "Top edge of the selection bounds",
+			TransformField::Width => "Width of the selection bounds",
+			TransformField::Height => "Height of the selection bounds",
+			TransformField::Rotation => "Rotation of the selected layer, or for multiple layers, an angle to rotate them around the center of their bounds",
+			TransformField::Skew => "Horizontal skew of the selected layer, or for multiple layers, an angle to skew them by around the center of their bounds",
+		}
+	}
+
+	/// Whether the field is an angle in degrees, rather than a distance in document units.
+	pub fn is_angle(&self) -> bool {
+		matches!(self, TransformField::Rotation | TransformField::Skew)
+	}
+
+	/// Formats a value of this field for display, rounded to two decimal places and followed by its unit.
+	pub fn format_value(&self, value: f64) -> String {
+		// Adding zero turns a negative zero into a positive zero
+		let rounded = (value * 100.).round() / 100. + 0.;
+		if self.is_angle() { format!("{rounded}°") } else { format!("{rounded} px") }
+	}
+
+	/// The factor which converts a value in the given unit into the unit of this field, or `None` if the unit doesn't apply to this field.
+	fn unit_factor(&self, unit: &str) -> Option<f64> {
+		if self.is_angle() {
+			match unit {
+				"°" | "deg" => Some(1.),
+				"rad" => Some(180. / std::f64::consts::PI),
+				"turn" => Some(360.),
+				_ => None,
+			}
+		} else {
+			match unit {
+				"px" => Some(1.),
+				"in" => Some(96.),
+				"cm" => Some(96. / 2.54),
+				"mm" => Some(96. / 25.4),
+				"pt" => Some(96. / 72.),
+				_ => None,
+			}
+		}
+	}
+}
+
+/// Evaluates a math expression typed into a transform bar field, such as `50%+12px`.
+///
+/// Numbers may be followed by a unit suitable for the field (`px`, `in`, `cm`, `mm`, `pt` for distances and `°`, `deg`, `rad`, `turn` for angles),
+/// and numbers without a unit are in the field's own unit (pixels or degrees). A percentage is relative to the field's `current` value.
+/// Returns `None` if the expression can't be evaluated to a finite number.
+pub fn evaluate_field_expression(expression: &str, field: TransformField, current: f64) -> Option<f64> {
+	let mut substituted = String::with_capacity(expression.len());
+	let mut chars = expression.chars().peekable();
+
+	while let Some(&c) = chars.peek() {
+		if !c.is_ascii_digit() && c != '.' {
+			substituted.push(c);
+			chars.next();
+
+			// Skip over identifiers (such as function names) so digits within them aren't treated as numbers
+			if c.is_alphabetic() {
+				while let Some(&c) = chars.peek().filter(|c| c.is_alphanumeric() || **c == '_') {
+					substituted.push(c);
+					chars.next();
+				}
+			}
+			continue;
+		}
+
+		// Read a number, inserting a leading zero before a decimal point lacking a preceding digit since the parser requires it
+		let mut number = String::new();
+		while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit() || **c == '.') {
+			if c == '.' && number.is_empty() {
+				number.push('0');
+			}
+			number.push(c);
+			chars.next();
+		}
+
+		// Read the unit which may follow the number after any whitespace
+		let mut whitespace = String::new();
+		while let Some(&c) = chars.peek().filter(|c| c.is_whitespace()) {
+			whitespace.push(c);
+			chars.next();
+		}
+		let mut unit = String::new();
+		while let Some(&c) = chars.peek().filter(|c| c.is_alphabetic() || **c == '%' || **c == '°') {
+			unit.push(c);
+			chars.next();
+			if c == '%' || c == '°' {
+				break;
+			}
+		}
+
+		if unit == "%" {
+			write!(substituted, "({number}/100*({current}))").ok()?;
+		} else if let Some(factor) = field.unit_factor(&unit) {
+			write!(substituted, "({number}*{factor})").ok()?;
+		} else if unit.is_empty() {
+			substituted.push_str(&number);
+			substituted.push_str(&whitespace);
+		} else {
+			// Leave anything else, like a constant or a unit of the wrong kind, for the parser to accept or reject
+			substituted.push_str(&number);
+			substituted.push_str(&whitespace);
+			substituted.push_str(&unit);
+		}
+	}
+
+	let (value, _) = math_parser::evaluate(&substituted).ok()?;
+	value.ok()?.as_real().filter(|value| value.is_finite())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn evaluate(expression: &str, field: TransformField, current: f64) -> f64 {
+		evaluate_field_expression(expression, field, current).unwrap_or_else(|| panic!("Failed to evaluate \"{expression}\""))
+	}
+
+	#[test]
+	fn plain_numbers() {
+		assert_eq!(evaluate("42", TransformField::X, 0.), 42.);
+		assert_eq!(evaluate("-12.5", TransformField::Y, 0.), -12.5);
+		assert_eq!(evaluate(".5", TransformField::Width, 0.), 0.5);
+		assert_eq!(evaluate("10 + 2 * 3", TransformField::Height, 0.), 16.);
+	}
+
+	#[test]
+	fn percentages_are_relative_to_current_value() {
+		assert_eq!(evaluate("50%", TransformField::Width, 200.), 100.);
+		assert_eq!(evaluate("50%+12px", TransformField::Width, 200.), 112.);
+		assert_eq!(evaluate("150 %", TransformField::Rotation, 10.), 15.);
+	}
+
+	#[test]
+	fn units() {
+		assert_eq!(evaluate("1in", TransformField::X, 0.), 96.);
+		assert!((evaluate("2.54 cm", TransformField::X, 0.) - 96.).abs() < 1e-10);
+		assert!((evaluate("72pt - 1in", TransformField::X, 0.)).abs() < 1e-10);
+		assert_eq!(evaluate("0.25turn", TransformField::Rotation, 0.), 90.);
+		assert_eq!(evaluate("45°", TransformField::Skew, 0.), 45.);
+	}
+
+	#[test]
+	fn invalid_expressions() {
+		assert_eq!(evaluate_field_expression("", TransformField::X, 0.), None);
+		assert_eq!(evaluate_field_expression("abc", TransformField::X, 0.), None);
+		assert_eq!(evaluate_field_expression("1/0", TransformField::X, 0.), None);
+		assert_eq!(evaluate_field_expression("10deg", TransformField::Width, 0.), None);
+	}
+}
@@ -0,0 +1,113 @@
+use super::document_metadata::LayerNodeIdentifier;
+use super::network_interface::NodeNetworkInterface;
+use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::graph_modification_utils::NodeGraphLayer;
+use graph_craft::document::value::TaggedValue;
+use graphene_core::Color;
+use graphene_core::vector::style::Fill;
+
+/// The input of the Fill node holding its fill.
+const FILL_INDEX: usize = 1;
+/// The input of the Stroke node holding its color.
+const STROKE_COLOR_INDEX: usize = 1;
+
+/// The colors of the closest Fill node (including each stop of a gradient) and Stroke node of a layer, in gamma space as they're stored in the graph.
+pub fn layer_colors(layer: LayerNodeIdentifier, network_interface: &NodeNetworkInterface) -> Vec<Color> {
+	let graph_layer = NodeGraphLayer::new(layer, network_interface);
+	let mut colors = Vec::new();
+
+	match graph_layer.find_input("Fill", FILL_INDEX) {
+		Some(TaggedValue::Fill(Fill::Solid(color))) => colors.push(*color),
+		Some(TaggedValue::Fill(Fill::Gradient(gradient))) => colors.extend(gradient.stops.iter().map(|(_, color)| *color)),
+		_ => {}
+	}
+	if let Some(TaggedValue::OptionalColor(Some(color))) = graph_layer.find_input("Stroke", STROKE_COLOR_INDEX) {
+		colors.push(*color);
+	}
+
+	colors
+}
+
+/// Swaps each fill and stroke color of a layer which is listed in the replacements (as pairs of the original and new color) for its new color.
+pub fn recolor_layer(layer: LayerNodeIdentifier, network_interface: &NodeNetworkInterface, replacements: &[(Color, Color)], responses: &mut VecDeque<Message>) {
+	let replace = |color: &Color| replacements.iter().find(|(original, _)| original == color).map(|&(_, replacement)| replacement);
+	let graph_layer = NodeGraphLayer::new(layer, network_interface);
+
+	match graph_layer.find_input("Fill", FILL_INDEX) {
+		Some(TaggedValue::Fill(Fill::Solid(color))) => {
+			if let Some(replacement) = replace(color) {
+				responses.add(GraphOperationMessage::FillSet {
+					layer,
+					fill: Fill::Solid(replacement),
+				});
+			}
+		}
+		Some(TaggedValue::Fill(Fill::Gradient(gradient))) if gradient.stops.iter().any(|(_, color)| replace(color).is_some()) => {
+			let mut gradient = gradient.clone();
+			for (_, color) in gradient.stops.iter_mut() {
+				*color = replace(color).unwrap_or(*color);
+			}
+			responses.add(GraphOperationMessage::FillSet {
+				layer,
+				fill: Fill::Gradient(gradient),
+			});
+		}
+		_ => {}
+	}
+
+	if let Some(TaggedValue::OptionalColor(Some(color))) = graph_layer.find_input("Stroke", STROKE_COLOR_INDEX) {
+		if let (Some(replacement), Some(node_id)) = (replace(color), graph_layer.upstream_node_id_from_name("Stroke")) {
+			responses.add(NodeGraphMessage::SetInputValue {
+				node_id,
+				input_index: STROKE_COLOR_INDEX,
+				value: TaggedValue::OptionalColor(Some(replacement)),
+			});
+		}
+	}
+}
+
+/// Rotates the hue of a gamma space color by the given number of degrees, keeping its saturation, lightness, and alpha.
+pub fn shift_hue(color: Color, degrees: f64) -> Color {
+	let [hue, saturation, lightness, alpha] = color.to_hsla();
+	Color::from_hsla((hue + degrees as f32 / 360.).rem_euclid(1.), saturation, lightness, alpha)
+}
+
+/// The color of the palette closest to the given one (both in gamma space), keeping the given color's alpha.
+pub fn nearest_color(color: Color, palette: &[Color]) -> Color {
+	let distance = |other: &Color| (color.r() - other.r()).powi(2) + (color.g() - other.g()).powi(2) + (color.b() - other.b()).powi(2);
+
+	palette.iter().min_by(|a, b| distance(a).total_cmp(&distance(b))).map_or(color, |nearest| nearest.with_alpha(color.a()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn assert_close(a: Color, b: Color) {
+		let channels = |color: Color| [color.r(), color.g(), color.b(), color.a()];
+		for (a, b) in channels(a).into_iter().zip(channels(b)) {
+			assert!((a - b).abs() < 1e-4, "{a:?} != {b:?}");
+		}
+	}
+
+	#[test]
+	fn shifting_hue_rotates_around_the_color_wheel() {
+		let red = Color::from_rgbaf32_unchecked(1., 0., 0., 1.);
+
+		assert_close(shift_hue(red, 120.), Color::from_rgbaf32_unchecked(0., 1., 0., 1.));
+		assert_close(shift_hue(red, -120.), Color::from_rgbaf32_unchecked(0., 0., 1., 1.));
+		assert_close(shift_hue(red, 360.), red);
+	}
+
+	#[test]
+	fn nearest_color_keeps_alpha() {
+		let palette = [Color::from_rgbaf32_unchecked(0., 0., 0., 1.), Color::from_rgbaf32_unchecked(1., 1., 1., 1.)];
+
+		assert_close(
+			nearest_color(Color::from_rgbaf32_unchecked(0.8, 0.7, 0.9, 0.5), &palette),
+			Color::from_rgbaf32_unchecked(1., 1., 1., 0.5),
+		);
+		assert_close(nearest_color(Color::from_rgbaf32_unchecked(0.2, 0.1, 0.3, 1.), &palette), Color::from_rgbaf32_unchecked(0., 0., 0., 1.));
+		assert_close(nearest_color(Color::from_rgbaf32_unchecked(0.2, 0.1, 0.3, 1.), &[]), Color::from_rgbaf32_unchecked(0.2, 0.1, 0.3, 1.));
+	}
+}
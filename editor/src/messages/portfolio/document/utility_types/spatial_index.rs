@@ -0,0 +1,147 @@
+use glam::DVec2;
+
+/// Maximum number of entries in each node of the tree.
+const NODE_CAPACITY: usize = 8;
+
+/// An R-tree of items with axis-aligned bounding boxes, used to find the items overlapping an area without checking every one of them.
+///
+/// The tree is bulk loaded from all its items at once (using the sort-tile-recursive algorithm), since it's rebuilt whenever they change rather than updated incrementally.
+#[derive(Debug, Clone)]
+pub struct SpatialIndex<T> {
+	nodes: Vec<Node<T>>,
+	root: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+enum Node<T> {
+	Leaf { bounds: [DVec2; 2], item: T },
+	Branch { bounds: [DVec2; 2], children: Vec<usize> },
+}
+
+impl<T> Node<T> {
+	fn bounds(&self) -> [DVec2; 2] {
+		match self {
+			Node::Leaf { bounds, .. } | Node::Branch { bounds, .. } => *bounds,
+		}
+	}
+}
+
+impl<T> Default for SpatialIndex<T> {
+	fn default() -> Self {
+		Self { nodes: Vec::new(), root: None }
+	}
+}
+
+impl<T> SpatialIndex<T> {
+	pub fn new(items: impl IntoIterator<Item = ([DVec2; 2], T)>) -> Self {
+		let mut nodes = Vec::new();
+		let mut level = items
+			.into_iter()
+			.map(|([a, b], item)| {
+				// Normalize the corners so boxes from flipped transforms are still handled correctly
+				nodes.push(Node::Leaf { bounds: [a.min(b), a.max(b)], item });
+				nodes.len() - 1
+			})
+			.collect::<Vec<_>>();
+
+		// Group each level's nodes into parent nodes until a single root remains
+		while level.len() > 1 {
+			level = pack(level, &nodes)
+				.into_iter()
+				.map(|children| {
+					let bounds = children.iter().map(|&child| nodes[child].bounds()).reduce(combine_bounds).unwrap_or_default();
+					nodes.push(Node::Branch { bounds, children });
+					nodes.len() - 1
+				})
+				.collect();
+		}
+
+		Self { root: level.first().copied(), nodes }
+	}
+
+	/// Iterates over the items whose bounding boxes overlap (or touch) the given bounds.
+	pub fn query(&self, bounds: [DVec2; 2]) -> impl Iterator<Item = &T> + '_ {
+		let [a, b] = bounds;
+		let bounds = [a.min(b), a.max(b)];
+		let mut stack = self.root.into_iter().collect::<Vec<_>>();
+
+		core::iter::from_fn(move || {
+			while let Some(index) = stack.pop() {
+				let node = &self.nodes[index];
+				if !overlaps(node.bounds(), bounds) {
+					continue;
+				}
+				match node {
+					Node::Leaf { item, .. } => return Some(item),
+					Node::Branch { children, .. } => stack.extend(children),
+				}
+			}
+			None
+		})
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.root.is_none()
+	}
+}
+
+/// Splits the nodes into groups of at most [`NODE_CAPACITY`] which are close together, by sorting them into vertical slices and then sorting each slice from top to bottom.
+fn pack<T>(mut level: Vec<usize>, nodes: &[Node<T>]) -> Vec<Vec<usize>> {
+	let center = |index: &usize| {
+		let [min, max] = nodes[*index].bounds();
+		(min + max) / 2.
+	};
+
+	let group_count = level.len().div_ceil(NODE_CAPACITY);
+	let slice_count = (group_count as f64).sqrt().ceil() as usize;
+	let slice_size = slice_count * NODE_CAPACITY;
+
+	level.sort_by(|a, b| center(a).x.total_cmp(&center(b).x));
+	level
+		.chunks_mut(slice_size)
+		.flat_map(|slice| {
+			slice.sort_by(|a, b| center(a).y.total_cmp(&center(b).y));
+			slice.chunks(NODE_CAPACITY).map(<[usize]>::to_vec).collect::<Vec<_>>()
+		})
+		.collect()
+}
+
+fn combine_bounds([a_min, a_max]: [DVec2; 2], [b_min, b_max]: [DVec2; 2]) -> [DVec2; 2] {
+	[a_min.min(b_min), a_max.max(b_max)]
+}
+
+fn overlaps([a_min, a_max]: [DVec2; 2], [b_min, b_max]: [DVec2; 2]) -> bool {
+	a_min.cmple(b_max).all() && b_min.cmple(a_max).all()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn query_matches_brute_force() {
+		let items = (0..500)
+			.map(|index| {
+				let position = DVec2::new((index * 37 % 101) as f64, (index * 53 % 97) as f64);
+				([position, position + DVec2::splat((index % 7) as f64)], index)
+			})
+			.collect::<Vec<_>>();
+		let index = SpatialIndex::new(items.clone());
+
+		for query in [[DVec2::ZERO, DVec2::splat(10.)], [DVec2::new(50., 20.), DVec2::new(30., 60.)], [DVec2::splat(200.), DVec2::splat(300.)]] {
+			let mut found = index.query(query).copied().collect::<Vec<_>>();
+			found.sort();
+
+			let [min, max] = [query[0].min(query[1]), query[0].max(query[1])];
+			let expected = items.iter().filter(|(bounds, _)| overlaps(*bounds, [min, max])).map(|(_, item)| *item).collect::<Vec<_>>();
+			assert_eq!(found, expected);
+		}
+	}
+
+	#[test]
+	fn empty_index() {
+		let index = SpatialIndex::<()>::new([]);
+		assert!(index.is_empty());
+		assert_eq!(index.query([DVec2::ZERO, DVec2::ONE]).count(), 0);
+	}
+}
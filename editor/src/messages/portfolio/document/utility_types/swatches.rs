@@ -0,0 +1,333 @@
+use graphene_core::raster::Color;
+use graphene_std::print::SpotColor;
+
+/// A named color saved in the document's swatches.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Swatch {
+	pub name: String,
+	/// The color in linear space, like all other colors used by the document.
+	pub color: Color,
+	/// Whether this is a named spot color, printed with its own premixed ink on a separation of its own rather than mixed from process inks.
+	#[serde(default)]
+	pub spot: bool,
+	/// Whether the spot color's ink is printed on top of the inks beneath it rather than knocking them out.
+	#[serde(default)]
+	pub overprint: bool,
+}
+
+impl Swatch {
+	pub fn new(name: impl Into<String>, color: Color) -> Self {
+		Self {
+			name: name.into(),
+			color,
+			spot: false,
+			overprint: false,
+		}
+	}
+
+	/// The ink this swatch is printed with in PDF and EPS exports, if it's a spot color.
+	pub fn spot_color(&self) -> Option<SpotColor> {
+		let [red, green, blue, _] = self.gamma_rgba8();
+		self.spot.then(|| SpotColor {
+			name: self.name.clone(),
+			color: [red, green, blue],
+			overprint: self.overprint,
+		})
+	}
+
+	/// The straight (not premultiplied) gamma-space channels, rounded to the nearest 8-bit value.
+	fn gamma_rgba8(&self) -> [u8; 4] {
+		let gamma = self.color.to_unassociated_alpha().to_gamma_srgb();
+		[gamma.r(), gamma.g(), gamma.b(), gamma.a()].map(|channel| (channel.clamp(0., 1.) * 255.).round() as u8)
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum PaletteFormat {
+	/// Adobe Swatch Exchange
+	Ase,
+	/// GIMP Palette
+	Gpl,
+	/// CSS custom properties declared on `:root`
+	Css,
+}
+
+impl PaletteFormat {
+	pub fn file_extension(&self) -> &'static str {
+		match self {
+			PaletteFormat::Ase => "ase",
+			PaletteFormat::Gpl => "gpl",
+			PaletteFormat::Css => "css",
+		}
+	}
+
+	pub fn to_mime(self) -> &'static str {
+		match self {
+			PaletteFormat::Ase => "application/octet-stream",
+			PaletteFormat::Gpl => "text/plain",
+			PaletteFormat::Css => "text/css",
+		}
+	}
+}
+
+const ASE_SIGNATURE: &[u8; 4] = b"ASEF";
+const ASE_BLOCK_GROUP_START: u16 = 0xC001;
+const ASE_BLOCK_GROUP_END: u16 = 0xC002;
+const ASE_BLOCK_COLOR: u16 = 0x0001;
+const ASE_COLOR_TYPE_SPOT: u16 = 1;
+const ASE_COLOR_TYPE_NORMAL: u16 = 2;
+const GPL_HEADER: &str = "GIMP Palette";
+
+/// Reads the swatches from an Adobe Swatch Exchange (ASE) or GIMP Palette (GPL) file, detected by its contents.
+pub fn import_palette(data: &[u8]) -> Result<Vec<Swatch>, String> {
+	if data.starts_with(ASE_SIGNATURE) {
+		return parse_ase(data);
+	}
+
+	let text = std::str::from_utf8(data).map_err(|_| "The palette file is not a recognized format".to_string())?;
+	if text.trim_start_matches('\u{feff}').starts_with(GPL_HEADER) {
+		return parse_gpl(text);
+	}
+
+	Err("The palette file is not a recognized format (expected ASE or GPL)".to_string())
+}
+
+/// Writes the swatches as a palette file of the given format, returning the file's bytes.
+pub fn export_palette(swatches: &[Swatch], palette_name: &str, format: PaletteFormat) -> Vec<u8> {
+	match format {
+		PaletteFormat::Ase => write_ase(swatches),
+		PaletteFormat::Gpl => write_gpl(swatches, palette_name).into_bytes(),
+		PaletteFormat::Css => write_css(swatches).into_bytes(),
+	}
+}
+
+struct Reader<'a> {
+	data: &'a [u8],
+	offset: usize,
+}
+
+impl Reader<'_> {
+	fn bytes(&mut self, count: usize) -> Result<&[u8], String> {
+		let bytes = self.data.get(self.offset..self.offset + count).ok_or_else(|| "The ASE file ended unexpectedly".to_string())?;
+		self.offset += count;
+		Ok(bytes)
+	}
+
+	fn u16(&mut self) -> Result<u16, String> {
+		self.bytes(2).map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+	}
+
+	fn u32(&mut self) -> Result<u32, String> {
+		self.bytes(4).map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+	}
+
+	fn f32(&mut self) -> Result<f32, String> {
+		self.u32().map(f32::from_bits)
+	}
+
+	/// Reads a length-prefixed, null-terminated UTF-16 string.
+	fn utf16(&mut self) -> Result<String, String> {
+		let length = self.u16()? as usize;
+		let units = (0..length).map(|_| self.u16()).collect::<Result<Vec<_>, _>>()?;
+		Ok(String::from_utf16_lossy(&units).trim_end_matches('\0').to_string())
+	}
+}
+
+fn parse_ase(data: &[u8]) -> Result<Vec<Swatch>, String> {
+	let mut reader = Reader { data, offset: ASE_SIGNATURE.len() };
+	let _version = (reader.u16()?, reader.u16()?);
+	let block_count = reader.u32()?;
+
+	let mut swatches = Vec::new();
+	for _ in 0..block_count {
+		let block_type = reader.u16()?;
+		let block_length = reader.u32()? as usize;
+		let block_end = reader.offset + block_length;
+
+		if block_type == ASE_BLOCK_COLOR {
+			let name = reader.utf16()?;
+			let model = reader.bytes(4)?.to_vec();
+			let color = match &model[..] {
+				b"RGB " => Color::from_rgbf32_unchecked(reader.f32()?, reader.f32()?, reader.f32()?).to_linear_srgb(),
+				b"Gray" => {
+					let gray = reader.f32()?;
+					Color::from_rgbf32_unchecked(gray, gray, gray).to_linear_srgb()
+				}
+				b"CMYK" => {
+					let [cyan, magenta, yellow, key] = [reader.f32()?, reader.f32()?, reader.f32()?, reader.f32()?];
+					let channel = |value: f32| (1. - value) * (1. - key);
+					Color::from_rgbf32_unchecked(channel(cyan), channel(magenta), channel(yellow)).to_linear_srgb()
+				}
+				b"LAB " => lab_to_linear_srgb(reader.f32()? * 100., reader.f32()?, reader.f32()?),
+				_ => return Err(format!("Unsupported ASE color model \"{}\"", String::from_utf8_lossy(&model))),
+			};
+			// Some files end the block without a color type, in which case it's a normal (process) color
+			let spot = reader.offset + 2 <= block_end && reader.u16()? == ASE_COLOR_TYPE_SPOT;
+
+			swatches.push(Swatch { spot, ..Swatch::new(name, color) });
+		} else if block_type != ASE_BLOCK_GROUP_START && block_type != ASE_BLOCK_GROUP_END {
+			warn!("Skipping unknown ASE block type {block_type:#06x}");
+		}
+
+		// Groups are flattened, so their names (and any trailing data in a block) are skipped
+		reader.offset = block_end;
+	}
+
+	Ok(swatches)
+}
+
+/// Converts a CIELAB color (relative to the D50 white point, as used by ASE files) to linear sRGB.
+fn lab_to_linear_srgb(lightness: f32, a: f32, b: f32) -> Color {
+	const WHITE_D50: [f32; 3] = [0.9642, 1., 0.8251];
+	const EPSILON: f32 = 216. / 24389.;
+	const KAPPA: f32 = 24389. / 27.;
+
+	let f_y = (lightness + 16.) / 116.;
+	let f_x = f_y + a / 500.;
+	let f_z = f_y - b / 200.;
+	let inverse = |f: f32| if f.powi(3) > EPSILON { f.powi(3) } else { (116. * f - 16.) / KAPPA };
+	let [x, y, z] = [inverse(f_x) * WHITE_D50[0], inverse(f_y) * WHITE_D50[1], inverse(f_z) * WHITE_D50[2]];
+
+	// Bradford-adapted XYZ (D50) to linear sRGB (D65) matrix
+	let red = 3.133_856 * x - 1.616_867 * y - 0.490_615 * z;
+	let green = -0.978_768 * x + 1.916_142 * y + 0.033_454 * z;
+	let blue = 0.071_945 * x - 0.228_991 * y + 1.405_243 * z;
+	Color::from_rgbf32_unchecked(red.clamp(0., 1.), green.clamp(0., 1.), blue.clamp(0., 1.))
+}
+
+fn write_ase(swatches: &[Swatch]) -> Vec<u8> {
+	let mut file = ASE_SIGNATURE.to_vec();
+	file.extend(1_u16.to_be_bytes());
+	file.extend(0_u16.to_be_bytes());
+	file.extend((swatches.len() as u32).to_be_bytes());
+
+	for swatch in swatches {
+		let name = swatch.name.encode_utf16().chain(std::iter::once(0)).collect::<Vec<_>>();
+		let gamma = swatch.color.to_unassociated_alpha().to_gamma_srgb();
+
+		let mut block = Vec::new();
+		block.extend((name.len() as u16).to_be_bytes());
+		block.extend(name.iter().flat_map(|unit| unit.to_be_bytes()));
+		block.extend(b"RGB ");
+		block.extend([gamma.r(), gamma.g(), gamma.b()].iter().flat_map(|channel| channel.to_be_bytes()));
+		block.extend(if swatch.spot { ASE_COLOR_TYPE_SPOT } else { ASE_COLOR_TYPE_NORMAL }.to_be_bytes());
+
+		file.extend(ASE_BLOCK_COLOR.to_be_bytes());
+		file.extend((block.len() as u32).to_be_bytes());
+		file.extend(block);
+	}
+
+	file
+}
+
+fn parse_gpl(text: &str) -> Result<Vec<Swatch>, String> {
+	let mut swatches = Vec::new();
+
+	for line in text.lines().skip(1).map(str::trim) {
+		if line.is_empty() || line.starts_with('#') || line.starts_with("Name:") || line.starts_with("Columns:") {
+			continue;
+		}
+
+		let mut parts = line.split_whitespace();
+		let mut channel = || parts.next().and_then(|channel| channel.parse::<u8>().ok()).ok_or_else(|| format!("Invalid GPL color entry \"{line}\""));
+		let (red, green, blue) = (channel()?, channel()?, channel()?);
+		let name = parts.collect::<Vec<_>>().join(" ");
+
+		swatches.push(Swatch::new(name, Color::from_rgb8_srgb(red, green, blue)));
+	}
+
+	Ok(swatches)
+}
+
+fn write_gpl(swatches: &[Swatch], palette_name: &str) -> String {
+	let mut file = format!("{GPL_HEADER}\nName: {palette_name}\nColumns: 0\n#\n");
+
+	for swatch in swatches {
+		let [red, green, blue, _] = swatch.gamma_rgba8();
+		file.push_str(&format!("{red:>3} {green:>3} {blue:>3}\t{}\n", swatch.name));
+	}
+
+	file
+}
+
+fn write_css(swatches: &[Swatch]) -> String {
+	let mut used_names = Vec::new();
+	let mut file = ":root {\n".to_string();
+
+	for (index, swatch) in swatches.iter().enumerate() {
+		// Derive a valid custom property name from the swatch name, falling back to its position if that's empty or a duplicate
+		let mut name = swatch
+			.name
+			.to_lowercase()
+			.split(|c: char| !c.is_alphanumeric())
+			.filter(|part| !part.is_empty())
+			.collect::<Vec<_>>()
+			.join("-");
+		if name.is_empty() || used_names.contains(&name) {
+			name = format!("swatch-{}", index + 1);
+		}
+
+		let [red, green, blue, alpha] = swatch.gamma_rgba8();
+		let hex = match alpha {
+			255 => format!("{red:02x}{green:02x}{blue:02x}"),
+			_ => format!("{red:02x}{green:02x}{blue:02x}{alpha:02x}"),
+		};
+		file.push_str(&format!("\t--{name}: #{hex};\n"));
+
+		used_names.push(name);
+	}
+
+	file.push_str("}\n");
+	file
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn swatches() -> Vec<Swatch> {
+		vec![
+			Swatch::new("Graphite Red", Color::from_rgb8_srgb(0xff, 0x00, 0x00)),
+			Swatch::new("Mid Gray", Color::from_rgb8_srgb(0x80, 0x80, 0x80)),
+		]
+	}
+
+	fn assert_same_colors(imported: &[Swatch], original: &[Swatch]) {
+		assert_eq!(imported.len(), original.len());
+		for (imported, original) in imported.iter().zip(original) {
+			assert_eq!(imported.name, original.name);
+			assert_eq!(imported.gamma_rgba8(), original.gamma_rgba8());
+			assert_eq!(imported.spot, original.spot);
+		}
+	}
+
+	#[test]
+	fn ase_round_trip() {
+		let file = export_palette(&swatches(), "Test", PaletteFormat::Ase);
+		assert_same_colors(&import_palette(&file).unwrap(), &swatches());
+	}
+
+	#[test]
+	fn ase_round_trip_keeps_spot_colors() {
+		let mut swatches = swatches();
+		swatches[0].spot = true;
+
+		let file = export_palette(&swatches, "Test", PaletteFormat::Ase);
+		assert_same_colors(&import_palette(&file).unwrap(), &swatches);
+	}
+
+	#[test]
+	fn gpl_round_trip() {
+		let file = export_palette(&swatches(), "Test", PaletteFormat::Gpl);
+		assert_same_colors(&import_palette(&file).unwrap(), &swatches());
+	}
+
+	#[test]
+	fn css_custom_properties() {
+		let mut swatches = swatches();
+		swatches.push(Swatch::new("mid gray", Color::from_rgb8_srgb(0, 0, 0)));
+
+		let file = String::from_utf8(export_palette(&swatches, "Test", PaletteFormat::Css)).unwrap();
+		assert_eq!(file, ":root {\n\t--graphite-red: #ff0000;\n\t--mid-gray: #808080;\n\t--swatch-3: #000000;\n}\n");
+	}
+}
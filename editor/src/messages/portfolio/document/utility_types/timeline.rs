@@ -0,0 +1,359 @@
+use graph_craft::document::NodeId;
+use graph_craft::document::value::TaggedValue;
+use graphene_core::animation::Easing;
+use graphene_core::raster::Color;
+use std::sync::Arc;
+
+/// Keyframes closer together in time than this (in seconds) are treated as being at the same time.
+const KEYFRAME_TIME_TOLERANCE: f64 = 1e-6;
+
+/// The document's animation: the keyframes driving its node inputs over time, saved as part of the document.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Timeline {
+	/// The length of the animation in seconds, which the loop range is limited to.
+	pub duration: f64,
+	/// The time in seconds playback loops back to, which is the start of the timeline if unset.
+	pub loop_in: Option<f64>,
+	/// The time in seconds playback loops back from, which is the end of the timeline if unset.
+	pub loop_out: Option<f64>,
+	/// The frame rate the animation is played back and stepped through at.
+	pub frames_per_second: f64,
+	pub tracks: Vec<KeyframeTrack>,
+	/// The sound played along with the animation to time it by.
+	pub audio: Option<AudioTrack>,
+}
+
+impl Default for Timeline {
+	fn default() -> Self {
+		Self {
+			duration: 5.,
+			loop_in: None,
+			loop_out: None,
+			frames_per_second: 30.,
+			tracks: Vec::new(),
+			audio: None,
+		}
+	}
+}
+
+/// A sound played along with the animation to time it by, such as dialogue to lip sync or music to move along with.
+/// It isn't part of the artwork, so it's left out of exported images.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct AudioTrack {
+	/// Identifies the sound, so it's only sent to the frontend again once it's replaced.
+	pub id: u64,
+	pub name: String,
+	/// The MIME type of the sound file.
+	pub mime: String,
+	/// The contents of the sound file, which is decoded by the browser.
+	/// It's shared so the copies of the timeline kept in the undo history don't each hold their own.
+	#[serde(serialize_with = "base64_serde::as_base64", deserialize_with = "base64_serde::from_base64")]
+	pub data: Arc<[u8]>,
+	/// The time in seconds the sound starts playing at.
+	pub offset: f64,
+	pub muted: bool,
+}
+
+mod base64_serde {
+	//! Stores the sound file as a [`base64`] string, which is much smaller than a list of numbers in the saved document.
+
+	use base64::Engine;
+	use serde::{Deserialize, Deserializer, Serializer};
+	use std::sync::Arc;
+
+	pub fn as_base64<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(data))
+	}
+
+	pub fn from_base64<'a, D: Deserializer<'a>>(deserializer: D) -> Result<Arc<[u8]>, D::Error> {
+		let string = String::deserialize(deserializer)?;
+		base64::engine::general_purpose::STANDARD.decode(string).map(Arc::from).map_err(serde::de::Error::custom)
+	}
+}
+
+/// The keyframes animating a single node input, sorted by time.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct KeyframeTrack {
+	/// The path to the network containing the node, which is empty for the document's root network.
+	pub network_path: Vec<NodeId>,
+	pub node_id: NodeId,
+	pub input_index: usize,
+	pub keyframes: Vec<Keyframe>,
+}
+
+/// The value a node input takes at a point in time.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Keyframe {
+	/// Seconds since the start of the animation.
+	pub time: f64,
+	pub value: TaggedValue,
+	/// How the value eases from this keyframe to the next one.
+	#[serde(default)]
+	pub easing: Easing,
+}
+
+impl Timeline {
+	pub fn track(&self, network_path: &[NodeId], node_id: NodeId, input_index: usize) -> Option<&KeyframeTrack> {
+		self.tracks.iter().find(|track| track.is_for(network_path, node_id, input_index))
+	}
+
+	/// Adds a keyframe to the input's track, replacing any keyframe already at that time and creating the track if the input has none yet.
+	pub fn set_keyframe(&mut self, network_path: &[NodeId], node_id: NodeId, input_index: usize, time: f64, value: TaggedValue) {
+		let track = match self.tracks.iter().position(|track| track.is_for(network_path, node_id, input_index)) {
+			Some(index) => &mut self.tracks[index],
+			None => {
+				self.tracks.push(KeyframeTrack {
+					network_path: network_path.to_vec(),
+					node_id,
+					input_index,
+					keyframes: Vec::new(),
+				});
+				self.tracks.last_mut().unwrap()
+			}
+		};
+		track.set(time, value);
+	}
+
+	/// Removes the input's keyframe at the given time, along with its track if no keyframes remain on it.
+	pub fn remove_keyframe(&mut self, network_path: &[NodeId], node_id: NodeId, input_index: usize, time: f64) {
+		let Some(index) = self.tracks.iter().position(|track| track.is_for(network_path, node_id, input_index)) else {
+			return;
+		};
+
+		self.tracks[index].keyframes.retain(|keyframe| (keyframe.time - time).abs() > KEYFRAME_TIME_TOLERANCE);
+		if self.tracks[index].keyframes.is_empty() {
+			self.tracks.remove(index);
+		}
+	}
+
+	/// Changes how the value eases from a keyframe to the next one.
+	pub fn set_easing(&mut self, track_index: usize, keyframe_index: usize, easing: Easing) {
+		let Some(keyframe) = self.tracks.get_mut(track_index).and_then(|track| track.keyframes.get_mut(keyframe_index)) else {
+			return;
+		};
+		keyframe.easing = easing;
+	}
+
+	/// The times of the keyframes on all tracks, sorted and without duplicates.
+	pub fn keyframe_times(&self) -> Vec<f64> {
+		let mut times = self.tracks.iter().flat_map(|track| track.keyframes.iter().map(|keyframe| keyframe.time)).collect::<Vec<_>>();
+		times.sort_by(f64::total_cmp);
+		times.dedup_by(|a, b| (*a - *b).abs() <= KEYFRAME_TIME_TOLERANCE);
+		times
+	}
+
+	/// The start and end times of the range playback loops over, limited to the timeline's duration.
+	pub fn loop_range(&self) -> (f64, f64) {
+		let loop_in = self.loop_in.unwrap_or(0.).clamp(0., self.duration);
+		let loop_out = self.loop_out.unwrap_or(self.duration).clamp(0., self.duration);
+
+		if loop_out - loop_in > KEYFRAME_TIME_TOLERANCE { (loop_in, loop_out) } else { (0., self.duration) }
+	}
+
+	/// The time playback should jump back to once it reaches the end of the loop range, or `None` if it hasn't reached it yet.
+	pub fn looped_time(&self, time: f64) -> Option<f64> {
+		let (loop_in, loop_out) = self.loop_range();
+		if loop_out <= 0. || time < loop_out {
+			return None;
+		}
+
+		Some(loop_in + (time - loop_out) % (loop_out - loop_in))
+	}
+}
+
+impl KeyframeTrack {
+	fn is_for(&self, network_path: &[NodeId], node_id: NodeId, input_index: usize) -> bool {
+		self.node_id == node_id && self.input_index == input_index && self.network_path == network_path
+	}
+
+	pub fn keyframe_at(&self, time: f64) -> Option<&Keyframe> {
+		self.keyframes.iter().find(|keyframe| (keyframe.time - time).abs() <= KEYFRAME_TIME_TOLERANCE)
+	}
+
+	fn set(&mut self, time: f64, value: TaggedValue) {
+		match self.keyframes.iter_mut().find(|keyframe| (keyframe.time - time).abs() <= KEYFRAME_TIME_TOLERANCE) {
+			Some(keyframe) => keyframe.value = value,
+			None => {
+				let index = self.keyframes.partition_point(|keyframe| keyframe.time < time);
+				let easing = Default::default();
+				self.keyframes.insert(index, Keyframe { time, value, easing });
+			}
+		}
+	}
+
+	/// The input's value at the given time, interpolated between the keyframes on either side of it with the easing of the earlier one.
+	/// Before the first keyframe and after the last one, the value is held at that keyframe's.
+	pub fn value_at(&self, time: f64) -> Option<TaggedValue> {
+		let next_index = self.keyframes.partition_point(|keyframe| keyframe.time <= time);
+
+		let Some(previous) = next_index.checked_sub(1).map(|index| &self.keyframes[index]) else {
+			return self.keyframes.first().map(|keyframe| keyframe.value.clone());
+		};
+		let Some(next) = self.keyframes.get(next_index) else {
+			return Some(previous.value.clone());
+		};
+
+		let factor = previous.easing.apply((time - previous.time) / (next.time - previous.time));
+		Some(interpolate(&previous.value, &next.value, factor))
+	}
+}
+
+/// Whether a value of this type can be keyframed, which includes the numbers, vectors, transforms, and colors that can be smoothly interpolated.
+pub fn is_keyframeable(value: &TaggedValue) -> bool {
+	matches!(
+		value,
+		TaggedValue::F64(_)
+			| TaggedValue::OptionalF64(Some(_))
+			| TaggedValue::U32(_)
+			| TaggedValue::U64(_)
+			| TaggedValue::DVec2(_)
+			| TaggedValue::OptionalDVec2(Some(_))
+			| TaggedValue::DAffine2(_)
+			| TaggedValue::Color(_)
+			| TaggedValue::OptionalColor(Some(_))
+	)
+}
+
+/// The numbers making up a keyframeable value, which are plotted as separate curves in the timeline's curve editor.
+pub fn value_components(value: &TaggedValue) -> Vec<f64> {
+	match value {
+		TaggedValue::F64(value) | TaggedValue::OptionalF64(Some(value)) => vec![*value],
+		TaggedValue::U32(value) => vec![*value as f64],
+		TaggedValue::U64(value) => vec![*value as f64],
+		TaggedValue::DVec2(value) | TaggedValue::OptionalDVec2(Some(value)) => value.to_array().to_vec(),
+		TaggedValue::DAffine2(value) => value.to_cols_array().to_vec(),
+		TaggedValue::Color(color) | TaggedValue::OptionalColor(Some(color)) => [color.r(), color.g(), color.b(), color.a()].map(|channel| channel as f64).to_vec(),
+		_ => Vec::new(),
+	}
+}
+
+/// Linearly interpolates between two values of the same type by a factor, which may overshoot the range from 0 to 1 when eased.
+/// Values that can't be interpolated, or that differ in type, are held at the first value until the factor reaches 1.
+fn interpolate(from: &TaggedValue, to: &TaggedValue, factor: f64) -> TaggedValue {
+	let lerp = |from: f64, to: f64| from + (to - from) * factor;
+	// Colors can't overshoot their channels' range
+	let lerp_color = |from: &Color, to: &Color| from.lerp(to, factor.clamp(0., 1.) as f32);
+
+	match (from, to) {
+		(TaggedValue::F64(from), TaggedValue::F64(to)) => TaggedValue::F64(lerp(*from, *to)),
+		(TaggedValue::OptionalF64(Some(from)), TaggedValue::OptionalF64(Some(to))) => TaggedValue::OptionalF64(Some(lerp(*from, *to))),
+		(TaggedValue::U32(from), TaggedValue::U32(to)) => TaggedValue::U32(lerp(*from as f64, *to as f64).round() as u32),
+		(TaggedValue::U64(from), TaggedValue::U64(to)) => TaggedValue::U64(lerp(*from as f64, *to as f64).round() as u64),
+		(TaggedValue::DVec2(from), TaggedValue::DVec2(to)) => TaggedValue::DVec2(from.lerp(*to, factor)),
+		(TaggedValue::OptionalDVec2(Some(from)), TaggedValue::OptionalDVec2(Some(to))) => TaggedValue::OptionalDVec2(Some(from.lerp(*to, factor))),
+		(TaggedValue::DAffine2(from), TaggedValue::DAffine2(to)) => TaggedValue::DAffine2(glam::DAffine2::from_cols(
+			from.x_axis.lerp(to.x_axis, factor),
+			from.y_axis.lerp(to.y_axis, factor),
+			from.translation.lerp(to.translation, factor),
+		)),
+		(TaggedValue::Color(from), TaggedValue::Color(to)) => TaggedValue::Color(lerp_color(from, to)),
+		(TaggedValue::OptionalColor(Some(from)), TaggedValue::OptionalColor(Some(to))) => TaggedValue::OptionalColor(Some(lerp_color(from, to))),
+		_ if factor < 1. => from.clone(),
+		_ => to.clone(),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn track(keyframes: &[(f64, f64)]) -> KeyframeTrack {
+		let mut track = KeyframeTrack::default();
+		for &(time, value) in keyframes {
+			track.set(time, TaggedValue::F64(value));
+		}
+		track
+	}
+
+	#[test]
+	fn interpolates_between_keyframes() {
+		let track = track(&[(2., 30.), (0., 10.)]);
+		assert_eq!(track.value_at(1.), Some(TaggedValue::F64(20.)));
+		assert_eq!(track.value_at(1.5), Some(TaggedValue::F64(25.)));
+	}
+
+	#[test]
+	fn holds_values_outside_keyframes() {
+		let track = track(&[(1., 10.), (2., 30.)]);
+		assert_eq!(track.value_at(0.), Some(TaggedValue::F64(10.)));
+		assert_eq!(track.value_at(2.), Some(TaggedValue::F64(30.)));
+		assert_eq!(track.value_at(5.), Some(TaggedValue::F64(30.)));
+		assert_eq!(KeyframeTrack::default().value_at(1.), None);
+	}
+
+	#[test]
+	fn replaces_keyframe_at_same_time() {
+		let track = track(&[(1., 10.), (1., 20.)]);
+		assert_eq!(track.keyframes.len(), 1);
+		assert_eq!(track.value_at(1.), Some(TaggedValue::F64(20.)));
+	}
+
+	#[test]
+	fn steps_between_values_that_cannot_be_interpolated() {
+		let from = TaggedValue::Bool(false);
+		let to = TaggedValue::Bool(true);
+		assert_eq!(interpolate(&from, &to, 0.5), from);
+		assert_eq!(interpolate(&from, &to, 1.), to);
+	}
+
+	#[test]
+	fn eases_between_keyframes() {
+		let mut track = track(&[(0., 0.), (1., 100.)]);
+		track.keyframes[0].easing = Easing::EaseIn;
+		let Some(TaggedValue::F64(value)) = track.value_at(0.5) else { panic!("Expected a number") };
+		assert!(value < 50.);
+
+		track.keyframes[0].easing = Easing::Bounce;
+		assert_eq!(track.value_at(1.), Some(TaggedValue::F64(100.)));
+	}
+
+	#[test]
+	fn removes_empty_tracks() {
+		let mut timeline = Timeline::default();
+		timeline.set_keyframe(&[], NodeId(1), 0, 1., TaggedValue::F64(10.));
+		timeline.set_keyframe(&[], NodeId(1), 0, 2., TaggedValue::F64(20.));
+		timeline.remove_keyframe(&[], NodeId(1), 0, 1.);
+		assert_eq!(timeline.keyframe_times(), vec![2.]);
+		timeline.remove_keyframe(&[], NodeId(1), 0, 2.);
+		assert!(timeline.tracks.is_empty());
+	}
+
+	#[test]
+	fn loops_playback_within_loop_range() {
+		let mut timeline = Timeline { duration: 4., ..Default::default() };
+		assert_eq!(timeline.looped_time(3.), None);
+		assert_eq!(timeline.looped_time(5.), Some(1.));
+
+		timeline.loop_in = Some(1.);
+		timeline.loop_out = Some(3.);
+		assert_eq!(timeline.loop_range(), (1., 3.));
+		assert_eq!(timeline.looped_time(2.), None);
+		assert_eq!(timeline.looped_time(3.5), Some(1.5));
+
+		// An empty loop range falls back to the whole timeline
+		timeline.loop_out = Some(1.);
+		assert_eq!(timeline.loop_range(), (0., 4.));
+	}
+
+	#[test]
+	fn saves_audio_with_the_document() {
+		let timeline = Timeline {
+			audio: Some(AudioTrack {
+				id: 1,
+				name: "Dialogue.wav".to_string(),
+				mime: "audio/wav".to_string(),
+				data: vec![0, 1, 2, 255].into(),
+				offset: 0.5,
+				muted: false,
+			}),
+			..Default::default()
+		};
+
+		let serialized = serde_json::to_string(&timeline).unwrap();
+		assert!(serialized.contains(r#""data":"AAEC/w==""#));
+		assert_eq!(serde_json::from_str::<Timeline>(&serialized).unwrap(), timeline);
+	}
+}
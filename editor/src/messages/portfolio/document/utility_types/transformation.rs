@@ -318,38 +318,43 @@ impl TransformType {
 impl TransformOperation {
 	#[allow(clippy::too_many_arguments)]
 	pub fn apply_transform_operation(&self, selected: &mut Selected, increment_mode: bool, local: bool, quad: Quad, transform: DAffine2, pivot: DVec2, local_transform: DAffine2) {
-		let local_axis_transform_angle = (quad.top_left() - quad.top_right()).to_angle();
 		if self != &TransformOperation::None {
-			let transformation = match self {
-				TransformOperation::Grabbing(translation) => {
-					let translate = DAffine2::from_translation(transform.transform_vector2(translation.to_dvec(local_transform, increment_mode)));
-					if local {
-						let resolved_angle = if local_axis_transform_angle > 0. {
-							local_axis_transform_angle
-						} else {
-							local_axis_transform_angle - PI
-						};
-						DAffine2::from_angle(resolved_angle) * translate * DAffine2::from_angle(-resolved_angle)
-					} else {
-						translate
-					}
-				}
-				TransformOperation::Rotating(rotation) => DAffine2::from_angle(rotation.to_f64(increment_mode)),
-				TransformOperation::Scaling(scale) => {
-					if local {
-						DAffine2::from_angle(local_axis_transform_angle) * DAffine2::from_scale(scale.to_dvec(increment_mode)) * DAffine2::from_angle(-local_axis_transform_angle)
-					} else {
-						DAffine2::from_scale(scale.to_dvec(increment_mode))
-					}
-				}
-				TransformOperation::None => unreachable!(),
-			};
+			let transformation = self.transformation(increment_mode, local, quad, transform, local_transform);
 
 			selected.update_transforms(transformation, Some(pivot), Some(*self));
 			self.hints(selected.responses, local);
 		}
 	}
 
+	/// The viewport space transformation of the operation, relative to its pivot.
+	pub fn transformation(&self, increment_mode: bool, local: bool, quad: Quad, transform: DAffine2, local_transform: DAffine2) -> DAffine2 {
+		let local_axis_transform_angle = (quad.top_left() - quad.top_right()).to_angle();
+		match self {
+			TransformOperation::Grabbing(translation) => {
+				let translate = DAffine2::from_translation(transform.transform_vector2(translation.to_dvec(local_transform, increment_mode)));
+				if local {
+					let resolved_angle = if local_axis_transform_angle > 0. {
+						local_axis_transform_angle
+					} else {
+						local_axis_transform_angle - PI
+					};
+					DAffine2::from_angle(resolved_angle) * translate * DAffine2::from_angle(-resolved_angle)
+				} else {
+					translate
+				}
+			}
+			TransformOperation::Rotating(rotation) => DAffine2::from_angle(rotation.to_f64(increment_mode)),
+			TransformOperation::Scaling(scale) => {
+				if local {
+					DAffine2::from_angle(local_axis_transform_angle) * DAffine2::from_scale(scale.to_dvec(increment_mode)) * DAffine2::from_angle(-local_axis_transform_angle)
+				} else {
+					DAffine2::from_scale(scale.to_dvec(increment_mode))
+				}
+			}
+			TransformOperation::None => DAffine2::IDENTITY,
+		}
+	}
+
 	pub fn axis_constraint(&self) -> Axis {
 		match self {
 			TransformOperation::Grabbing(grabbing) => grabbing.constraint,
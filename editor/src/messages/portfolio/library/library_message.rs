@@ -0,0 +1,74 @@
+use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
+use crate::messages::prelude::*;
+
+/// Keeps a library of assets shared between documents and people, which can be published to and inserted from the Library panel.
+#[impl_message(Message, PortfolioMessage, Library)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum LibraryMessage {
+	ToggleOpen,
+
+	UpdateUrl {
+		url: String,
+	},
+	/// Fetches the library from its URL, replacing one opened from a file.
+	Connect,
+	/// Asks the frontend for a library file to open in place of the one from the URL.
+	OpenFile,
+	/// Sent by the frontend with the contents of the library file chosen by the user.
+	FileOpened {
+		content: String,
+	},
+	/// Sent by the frontend with the library fetched from its URL, which is empty if there's nothing there yet, and the ETag the server gave that version of it.
+	Fetched {
+		content: String,
+		etag: Option<String>,
+	},
+	/// Sent by the frontend when the library couldn't be fetched from its URL.
+	Failed {
+		error: String,
+	},
+	/// Sent by the frontend once the library is published to its URL, with the ETag the server gave the published version.
+	Published {
+		etag: Option<String>,
+	},
+	/// Sent by the frontend when the library wasn't published because someone else published a newer version of it first.
+	PublishConflicted,
+	/// Sent by the frontend when the library couldn't be published to its URL for any other reason.
+	PublishFailed {
+		error: String,
+	},
+	/// Sent regularly by the frontend to fetch the library again and find the items which others have changed.
+	Poll,
+
+	UpdateItemName {
+		name: String,
+	},
+	/// Adds assets from the active document to the library and publishes it, with items of the same name and kind replaced by a newer version.
+	Publish {
+		source: LibrarySource,
+	},
+	Remove {
+		id: u64,
+	},
+	/// Adds an item to the active document. Gradients fill the given layer, otherwise the one under the mouse (in client coordinates), otherwise the selected layers.
+	Insert {
+		id: u64,
+		layer: Option<LayerNodeIdentifier>,
+		mouse: Option<(f64, f64)>,
+	},
+	DismissUpdates,
+	SendLayout,
+}
+
+/// The assets of the active document which are published to the library.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+pub enum LibrarySource {
+	/// The selected layers, published together as a single component.
+	SelectedLayers,
+	/// The nodes selected in the node graph, published together as a single snippet.
+	SelectedNodes,
+	/// Each of the document's swatches.
+	Swatches,
+	/// Each of the gradient presets saved by the user.
+	GradientPresets,
+}
@@ -0,0 +1,498 @@
+use super::LibrarySource;
+use super::utility_types::{FrontendLibraryItem, LIBRARY_FILE_EXTENSION, Library, LibraryContent};
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::*;
+
+pub struct LibraryMessageData<'a> {
+	pub document: Option<&'a mut DocumentMessageHandler>,
+	pub preferences: &'a PreferencesMessageHandler,
+}
+
+/// Publishes assets from documents to a library shared through a file or URL, inserts its items into the active document, and points out the items changed by others.
+#[derive(Debug, Clone, Default)]
+pub struct LibraryMessageHandler {
+	/// Sets whether or not the Library panel is drawn.
+	pub library_open: bool,
+	/// The URL typed into the panel, which is fetched when connecting.
+	url: String,
+	/// The URL the library was fetched from, or `None` if it was opened from a file or hasn't been saved anywhere yet.
+	source_url: Option<String>,
+	library: Option<Library>,
+	/// The items changed since the library was first loaded, which are pointed out until dismissed.
+	updated: HashSet<u64>,
+	/// The name given to the selected layers or nodes when they're published.
+	item_name: String,
+	/// Why the library last couldn't be loaded or published.
+	error: Option<String>,
+	/// The ETag of the version of the library at the URL which this one is based on, so publishing only replaces that version and not one published by someone else since.
+	etag: Option<String>,
+	/// The changes made to the library which haven't been published to its URL yet, which are made again to a newer version of it if someone else published first.
+	unpublished: Vec<LibraryEdit>,
+	/// How many of the unpublished changes are being published right now, where no other publish is started until it's done.
+	publishing: usize,
+}
+
+/// A change made to the library, kept until it's been published.
+#[derive(Debug, Clone)]
+enum LibraryEdit {
+	Publish { name: String, content: LibraryContent },
+	Remove { id: u64 },
+}
+
+impl LibraryEdit {
+	fn apply(&self, library: &mut Library) {
+		match self {
+			LibraryEdit::Publish { name, content } => {
+				library.publish(name.clone(), content.clone());
+			}
+			LibraryEdit::Remove { id } => library.items.retain(|item| item.id != *id),
+		}
+	}
+}
+
+impl MessageHandler<LibraryMessage, LibraryMessageData<'_>> for LibraryMessageHandler {
+	fn process_message(&mut self, message: LibraryMessage, responses: &mut VecDeque<Message>, data: LibraryMessageData) {
+		let LibraryMessageData { document, preferences } = data;
+
+		match message {
+			LibraryMessage::ToggleOpen => {
+				self.library_open = !self.library_open;
+
+				// Reconnect to the library used last time the first time the panel is opened
+				if self.library_open && self.library.is_none() && self.source_url.is_none() && !preferences.library_url.is_empty() {
+					self.url = preferences.library_url.clone();
+					responses.add(LibraryMessage::Connect);
+				}
+				// Update checked UI state for open
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			LibraryMessage::UpdateUrl { url } => {
+				self.url = url.trim().to_string();
+			}
+			LibraryMessage::Connect => {
+				if self.url.is_empty() {
+					return;
+				}
+
+				self.source_url = Some(self.url.clone());
+				self.library = None;
+				self.updated.clear();
+				self.error = None;
+				self.etag = None;
+				self.unpublished.clear();
+				self.publishing = 0;
+				responses.add(PreferencesMessage::LibraryUrl { url: self.url.clone() });
+				responses.add(FrontendMessage::TriggerLibraryFetch { url: self.url.clone() });
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			LibraryMessage::OpenFile => {
+				responses.add(FrontendMessage::TriggerLibraryOpenFile);
+				return;
+			}
+			LibraryMessage::FileOpened { content } => {
+				match serde_json::from_str::<Library>(&content) {
+					Ok(library) => {
+						self.source_url = None;
+						self.library = Some(library);
+						self.updated.clear();
+						self.error = None;
+						self.etag = None;
+						self.unpublished.clear();
+						self.publishing = 0;
+					}
+					Err(error) => self.error = Some(format!("The library file couldn't be read: {error}")),
+				}
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			LibraryMessage::Fetched { content, etag } => {
+				// The library may have been switched for another since it was fetched
+				if self.source_url.is_none() {
+					return;
+				}
+
+				let library = if content.trim().is_empty() {
+					Ok(Library::default())
+				} else {
+					serde_json::from_str::<Library>(&content)
+				};
+				match library {
+					Ok(mut library) => {
+						// Only point out the changes made after the library was first loaded
+						if let Some(previous) = &self.library {
+							self.updated.extend(library.changed_since(previous));
+						}
+
+						// Our own changes which haven't been published yet are made again on top of the fetched version
+						for edit in &self.unpublished {
+							edit.apply(&mut library);
+						}
+						self.updated.retain(|&id| library.item(id).is_some());
+						self.library = Some(library);
+						self.error = None;
+
+						// While publishing, the fetched version may be from before or after the publish, so the ETag it responds with is the one kept instead
+						if self.publishing == 0 {
+							self.etag = etag;
+							if !self.unpublished.is_empty() {
+								self.save(responses);
+							}
+						}
+					}
+					Err(error) => self.error = Some(format!("The library couldn't be read: {error}")),
+				}
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			LibraryMessage::Failed { error } => {
+				self.error = Some(error);
+			}
+			LibraryMessage::Published { etag } => {
+				self.unpublished.drain(..self.publishing.min(self.unpublished.len()));
+				self.publishing = 0;
+				self.error = None;
+
+				match etag {
+					Some(etag) => {
+						self.etag = Some(etag);
+						if !self.unpublished.is_empty() {
+							self.save(responses);
+						}
+					}
+					// Without the ETag of what was just published, the next publish can't be made conditional on it, so it's fetched again to get it
+					None => {
+						self.etag = None;
+						if let Some(url) = &self.source_url {
+							responses.add(FrontendMessage::TriggerLibraryFetch { url: url.clone() });
+						}
+					}
+				}
+			}
+			LibraryMessage::PublishConflicted => {
+				// Someone else published first, so their version is fetched and the unpublished changes are made again on top of it before publishing once more
+				self.publishing = 0;
+				if let Some(url) = &self.source_url {
+					responses.add(FrontendMessage::TriggerLibraryFetch { url: url.clone() });
+				}
+				return;
+			}
+			LibraryMessage::PublishFailed { error } => {
+				// The changes stay unpublished, to be published along with the next change
+				self.publishing = 0;
+				self.error = Some(error);
+			}
+			LibraryMessage::Poll => {
+				if let Some(url) = &self.source_url {
+					responses.add(FrontendMessage::TriggerLibraryFetch { url: url.clone() });
+				}
+				return;
+			}
+			LibraryMessage::UpdateItemName { name } => {
+				self.item_name = name;
+			}
+			LibraryMessage::Publish { source } => {
+				// Publishing before the library has been fetched from its URL would overwrite everything already in it
+				if self.library.is_none() && self.source_url.is_some() {
+					return;
+				}
+				let Some(document) = document else { return };
+
+				let name = |default: &str| {
+					if self.item_name.trim().is_empty() {
+						default.to_string()
+					} else {
+						self.item_name.trim().to_string()
+					}
+				};
+				let contents = match source {
+					LibrarySource::SelectedLayers => {
+						let layers = document.copy_selected_layers();
+						if layers.is_empty() {
+							return;
+						}
+						vec![(name("Untitled Component"), LibraryContent::Component(layers))]
+					}
+					LibrarySource::SelectedNodes => {
+						let nodes = document.copy_selected_nodes();
+						if nodes.is_empty() {
+							return;
+						}
+						vec![(name("Untitled Snippet"), LibraryContent::NodeSnippet(nodes))]
+					}
+					LibrarySource::Swatches => document.swatches.iter().map(|swatch| (swatch.name.clone(), LibraryContent::Swatch(swatch.clone()))).collect(),
+					LibrarySource::GradientPresets => preferences
+						.gradient_presets
+						.iter()
+						.map(|preset| (preset.name.clone(), LibraryContent::Gradient(preset.stops.clone())))
+						.collect(),
+				};
+				if contents.is_empty() {
+					return;
+				}
+
+				let library = self.library.get_or_insert_with(Library::default);
+				for (name, content) in contents {
+					let edit = LibraryEdit::Publish { name, content };
+					edit.apply(library);
+					self.unpublished.push(edit);
+				}
+				self.save(responses);
+			}
+			LibraryMessage::Remove { id } => {
+				let Some(library) = &mut self.library else { return };
+				let edit = LibraryEdit::Remove { id };
+				edit.apply(library);
+				self.unpublished.push(edit);
+				self.updated.remove(&id);
+				self.save(responses);
+			}
+			LibraryMessage::Insert { id, layer, mouse } => {
+				let Some(item) = self.library.as_ref().and_then(|library| library.item(id)) else { return };
+				let Some(document) = document else { return };
+
+				match &item.content {
+					LibraryContent::Component(layers) => {
+						let data = serde_json::to_string(layers).expect("Could not serialize library component");
+						responses.add(PortfolioMessage::PasteSerializedData { data });
+					}
+					LibraryContent::Swatch(swatch) => {
+						document.add_history_step(preferences.undo_history_length, responses);
+
+						// A newer version of a swatch replaces the one of the same name inserted before
+						match document.swatches.iter_mut().find(|existing| existing.name == swatch.name) {
+							Some(existing) => *existing = swatch.clone(),
+							None => document.swatches.push(swatch.clone()),
+						}
+						responses.add(PropertiesPanelMessage::Refresh);
+					}
+					LibraryContent::Gradient(stops) => {
+						responses.add(DocumentMessage::ApplyGradient { stops: stops.clone(), layer, mouse });
+					}
+					LibraryContent::NodeSnippet(nodes) => {
+						let serialized_nodes = serde_json::to_string(nodes).expect("Could not serialize library node snippet");
+						responses.add(NodeGraphMessage::PasteNodes { serialized_nodes });
+					}
+				}
+				return;
+			}
+			LibraryMessage::DismissUpdates => {
+				self.updated.clear();
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			LibraryMessage::SendLayout => {}
+		}
+
+		self.update_layout(responses);
+	}
+
+	fn actions(&self) -> ActionList {
+		actions!(LibraryMessage;)
+	}
+}
+
+impl LibraryMessageHandler {
+	/// The number of items changed by others which haven't been dismissed, which the Window menu points out while the panel is closed.
+	pub fn update_count(&self) -> usize {
+		self.updated.len()
+	}
+
+	/// Publishes the library to its URL, or otherwise downloads it as a file to be shared.
+	/// Publishing only replaces the version of the library this one is based on, and waits for any publish already underway to finish first.
+	fn save(&mut self, responses: &mut VecDeque<Message>) {
+		let Some(library) = &self.library else { return };
+		let content = serde_json::to_string_pretty(library).expect("Could not serialize library");
+
+		match &self.source_url {
+			Some(url) => {
+				if self.publishing > 0 {
+					return;
+				}
+				self.publishing = self.unpublished.len();
+				responses.add(FrontendMessage::TriggerLibraryPublish {
+					url: url.clone(),
+					content,
+					etag: self.etag.clone(),
+				});
+			}
+			None => {
+				self.unpublished.clear();
+				responses.add(FrontendMessage::TriggerDownloadTextFile {
+					document: content,
+					name: format!("library.{LIBRARY_FILE_EXTENSION}"),
+					export_id: None,
+				});
+			}
+		}
+	}
+
+	fn update_layout(&self, responses: &mut VecDeque<Message>) {
+		responses.add(FrontendMessage::UpdateLibraryState { open: self.library_open });
+		if !self.library_open {
+			return;
+		}
+
+		let items = self
+			.library
+			.iter()
+			.flat_map(|library| &library.items)
+			.map(|item| FrontendLibraryItem {
+				id: item.id,
+				name: item.name.clone(),
+				kind: item.content.kind().to_string(),
+				preview: item.content.preview(),
+				updated: self.updated.contains(&item.id),
+			})
+			.collect();
+		responses.add(FrontendMessage::UpdateLibraryItems { items });
+
+		let mut layout = vec![LayoutGroup::Row {
+			widgets: vec![
+				TextLabel::new("URL").table_align(true).min_width(60).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextInput::new(&self.url)
+					.tooltip("The URL of a library shared with others, which is fetched with GET and published to with PUT")
+					.on_update(|text_input: &TextInput| LibraryMessage::UpdateUrl { url: text_input.value.clone() }.into())
+					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				TextButton::new("Connect")
+					.tooltip("Fetch the library from the URL, checking it for changes from then on")
+					.disabled(self.url.is_empty())
+					.on_update(|_| LibraryMessage::Connect.into())
+					.widget_holder(),
+				IconButton::new("Folder", 24)
+					.tooltip("Open a library file instead, which is downloaded again to be shared each time it's published to")
+					.on_update(|_| LibraryMessage::OpenFile.into())
+					.widget_holder(),
+			],
+		}];
+
+		let status = match (&self.error, &self.source_url, &self.library) {
+			(Some(error), _, _) => error.clone(),
+			(None, Some(url), None) => format!("Fetching {url}"),
+			(None, Some(url), Some(_)) => format!("Connected to {url}"),
+			(None, None, Some(_)) => "Opened from a file".to_string(),
+			(None, None, None) => "Connect to a library or open a library file, or publish to start a new one".to_string(),
+		};
+		layout.push(LayoutGroup::Row {
+			widgets: vec![TextLabel::new(status).italic(true).widget_holder()],
+		});
+
+		if !self.updated.is_empty() {
+			let count = self.updated.len();
+			layout.push(LayoutGroup::Row {
+				widgets: vec![
+					IconLabel::new("Resync").widget_holder(),
+					TextLabel::new(format!("{count} {} changed in the library", if count == 1 { "item has" } else { "items have" }))
+						.bold(true)
+						.tooltip("Insert the updated items again to use their latest versions")
+						.widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					TextButton::new("Dismiss").on_update(|_| LibraryMessage::DismissUpdates.into()).widget_holder(),
+				],
+			});
+		}
+
+		let can_publish = self.library.is_some() || self.source_url.is_none();
+		let publish_button = |label: &str, tooltip: &str, source: LibrarySource| {
+			TextButton::new(label)
+				.tooltip(tooltip)
+				.disabled(!can_publish)
+				.on_update(move |_| LibraryMessage::Publish { source }.into())
+				.widget_holder()
+		};
+		layout.push(LayoutGroup::Row {
+			widgets: vec![
+				TextLabel::new("Publish").table_align(true).min_width(60).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextInput::new(&self.item_name)
+					.tooltip("The name given to the selected layers or nodes when published, replacing the item of that name if it's already in the library")
+					.on_update(|text_input: &TextInput| LibraryMessage::UpdateItemName { name: text_input.value.clone() }.into())
+					.widget_holder(),
+			],
+		});
+		layout.push(LayoutGroup::Row {
+			widgets: vec![
+				publish_button("Layers", "Publish the selected layers as a component", LibrarySource::SelectedLayers),
+				publish_button("Nodes", "Publish the nodes selected in the node graph as a snippet", LibrarySource::SelectedNodes),
+				publish_button("Swatches", "Publish each of the document's swatches", LibrarySource::Swatches),
+				publish_button("Gradients", "Publish each of your saved gradient presets", LibrarySource::GradientPresets),
+			],
+		});
+
+		responses.add(LayoutMessage::SendLayout {
+			layout: Layout::WidgetLayout(WidgetLayout { layout }),
+			layout_target: LayoutTarget::Library,
+		});
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::messages::portfolio::document::utility_types::swatches::Swatch;
+	use graphene_core::Color;
+
+	fn process(handler: &mut LibraryMessageHandler, message: LibraryMessage) -> Vec<FrontendMessage> {
+		let preferences = PreferencesMessageHandler::default();
+		let mut responses = VecDeque::new();
+		handler.process_message(
+			message,
+			&mut responses,
+			LibraryMessageData {
+				document: None,
+				preferences: &preferences,
+			},
+		);
+		responses
+			.into_iter()
+			.filter_map(|message| match message {
+				Message::Frontend(message) => Some(message),
+				_ => None,
+			})
+			.collect()
+	}
+
+	fn published_etag(messages: &[FrontendMessage]) -> Option<Option<String>> {
+		messages.iter().find_map(|message| match message {
+			FrontendMessage::TriggerLibraryPublish { etag, .. } => Some(etag.clone()),
+			_ => None,
+		})
+	}
+
+	#[test]
+	fn changes_are_made_again_when_someone_else_published_first() {
+		let mut library = Library::default();
+		let red = library.publish("Red".into(), LibraryContent::Swatch(Swatch::new("Red", Color::RED)));
+		let content = serde_json::to_string(&library).unwrap();
+
+		let mut handler = LibraryMessageHandler {
+			url: "https://example.com/library".into(),
+			..Default::default()
+		};
+		process(&mut handler, LibraryMessage::Connect);
+		process(&mut handler, LibraryMessage::Fetched { content, etag: Some("1".into()) });
+
+		// Publishing is conditional on the fetched version
+		let messages = process(&mut handler, LibraryMessage::Remove { id: red });
+		assert_eq!(published_etag(&messages), Some(Some("1".into())));
+
+		// Someone else published a new item first, so theirs is fetched and the removal is published again on top of it
+		let black = library.publish("Black".into(), LibraryContent::Swatch(Swatch::new("Black", Color::BLACK)));
+		process(&mut handler, LibraryMessage::PublishConflicted);
+		let messages = process(
+			&mut handler,
+			LibraryMessage::Fetched {
+				content: serde_json::to_string(&library).unwrap(),
+				etag: Some("2".into()),
+			},
+		);
+		assert_eq!(published_etag(&messages), Some(Some("2".into())));
+
+		let items = &handler.library.as_ref().unwrap().items;
+		assert_eq!(items.iter().map(|item| item.id).collect::<Vec<_>>(), vec![black]);
+
+		// Once published, nothing is left to be published again
+		let messages = process(&mut handler, LibraryMessage::Published { etag: Some("3".into()) });
+		assert_eq!(published_etag(&messages), None);
+		assert!(handler.unpublished.is_empty());
+		assert_eq!(handler.etag.as_deref(), Some("3"));
+	}
+}
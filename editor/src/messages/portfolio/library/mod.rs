@@ -0,0 +1,9 @@
+mod library_message;
+mod library_message_handler;
+
+pub mod utility_types;
+
+#[doc(inline)]
+pub use library_message::*;
+#[doc(inline)]
+pub use library_message_handler::*;
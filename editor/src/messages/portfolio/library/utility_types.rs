@@ -0,0 +1,127 @@
+//! The shared library file, which holds the assets published by everyone using it so they can be inserted into any document.
+//!
+//! A library is a JSON file kept either on disk or at a URL which is fetched with `GET` and published to with a `PUT` conditional on the ETag of the version it's based on.
+//! Each item keeps its ID as it's republished while its version counts up, so those using the library can tell which items have changed since they last saw it.
+
+use crate::application::generate_uuid;
+use crate::messages::portfolio::document::utility_types::clipboards::CopyBufferEntry;
+use crate::messages::portfolio::document::utility_types::network_interface::NodeTemplate;
+use crate::messages::portfolio::document::utility_types::swatches::Swatch;
+use graph_craft::document::NodeId;
+use graphene_core::vector::style::GradientStops;
+
+pub const LIBRARY_FILE_EXTENSION: &str = "graphite-library";
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Library {
+	pub items: Vec<LibraryItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LibraryItem {
+	pub id: u64,
+	pub name: String,
+	/// Counts up each time the item is republished.
+	pub version: u64,
+	pub content: LibraryContent,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum LibraryContent {
+	/// Layers along with everything feeding into them, in the same form as when they're copied to the clipboard.
+	Component(Vec<CopyBufferEntry>),
+	Swatch(Swatch),
+	/// Gradient stops with colors in gamma space, like those of a gradient preset.
+	Gradient(GradientStops),
+	/// Nodes from the node graph, in the same form as when they're copied to the clipboard.
+	NodeSnippet(Vec<(NodeId, NodeTemplate)>),
+}
+
+impl LibraryContent {
+	pub fn kind(&self) -> &'static str {
+		match self {
+			LibraryContent::Component(_) => "Component",
+			LibraryContent::Swatch(_) => "Swatch",
+			LibraryContent::Gradient(_) => "Gradient",
+			LibraryContent::NodeSnippet(_) => "Node Snippet",
+		}
+	}
+
+	/// A CSS background previewing a swatch or gradient.
+	pub fn preview(&self) -> Option<String> {
+		match self {
+			LibraryContent::Swatch(swatch) => Some(format!("#{}", swatch.color.to_rgb_hex_srgb())),
+			LibraryContent::Gradient(stops) => {
+				let stops = stops
+					.iter()
+					.map(|(position, color)| format!("#{} {}%", color.to_rgb_hex_srgb_from_gamma(), position * 100.))
+					.collect::<Vec<_>>();
+				Some(format!("linear-gradient(to right, {})", stops.join(", ")))
+			}
+			_ => None,
+		}
+	}
+}
+
+impl Library {
+	/// Adds an item, or replaces the item of the same kind and name with a newer version of it, returning the item's ID.
+	pub fn publish(&mut self, name: String, content: LibraryContent) -> u64 {
+		if let Some(item) = self.items.iter_mut().find(|item| item.name == name && item.content.kind() == content.kind()) {
+			item.version += 1;
+			item.content = content;
+			return item.id;
+		}
+
+		let id = generate_uuid();
+		self.items.push(LibraryItem { id, name, version: 1, content });
+		id
+	}
+
+	pub fn item(&self, id: u64) -> Option<&LibraryItem> {
+		self.items.iter().find(|item| item.id == id)
+	}
+
+	/// The items which are new or have been republished since the `previous` version of the library.
+	pub fn changed_since(&self, previous: &Library) -> Vec<u64> {
+		self.items
+			.iter()
+			.filter(|item| previous.item(item.id).is_none_or(|previous_item| item.version > previous_item.version))
+			.map(|item| item.id)
+			.collect()
+	}
+}
+
+#[derive(PartialEq, Eq, Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct FrontendLibraryItem {
+	pub id: u64,
+	pub name: String,
+	pub kind: String,
+	/// A CSS background previewing a swatch or gradient.
+	pub preview: Option<String>,
+	/// Whether the item has changed since the library was first loaded, and the change hasn't been dismissed.
+	pub updated: bool,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use graphene_core::Color;
+
+	#[test]
+	fn republishing_bumps_the_version() {
+		let mut library = Library::default();
+		let id = library.publish("Brand Red".into(), LibraryContent::Swatch(Swatch::new("Brand Red", Color::RED)));
+		let previous = library.clone();
+
+		assert_eq!(library.publish("Brand Red".into(), LibraryContent::Swatch(Swatch::new("Brand Red", Color::BLACK))), id);
+		assert_eq!(library.items.len(), 1);
+		assert_eq!(library.items[0].version, 2);
+
+		// The same name for a different kind of item is a separate item
+		let gradient = library.publish("Brand Red".into(), LibraryContent::Gradient(GradientStops::default()));
+		assert_ne!(gradient, id);
+		assert_eq!(library.changed_since(&previous), vec![id, gradient]);
+		assert!(library.changed_since(&library).is_empty());
+	}
+}
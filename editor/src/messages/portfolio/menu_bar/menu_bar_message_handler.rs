@@ -3,19 +3,53 @@ use crate::messages::input_mapper::utility_types::macros::action_keys;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::utility_types::clipboards::Clipboard;
 use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, FlipAxis, GroupFolderType};
+use crate::messages::portfolio::document::utility_types::swatches::PaletteFormat;
+use crate::messages::portfolio::plugins::plugin_api::PluginManifest;
 use crate::messages::prelude::*;
+use graphene_std::renderer::{ColorProfile, ColorVisionDeficiency, ProofProfile};
 use graphene_std::vector::misc::BooleanOperation;
 
 #[derive(Debug, Clone, Default)]
 pub struct MenuBarMessageHandler {
 	pub has_active_document: bool,
 	pub rulers_visible: bool,
+	pub outline_view_mode: bool,
 	pub node_graph_open: bool,
 	pub has_selected_nodes: bool,
 	pub has_selected_layers: bool,
+	/// Whether any of the selected layers holds raster content, which is what can be turned into a reference image.
+	pub has_selected_raster_layers: bool,
+	/// Whether any of the selected layers is a text layer, which is what can be converted to paths.
+	pub has_selected_text_layers: bool,
+	pub has_selected_empty_text_layers: bool,
+	pub has_swatches: bool,
 	pub has_selection_history: (bool, bool),
 	pub spreadsheet_view_open: bool,
+	pub split_view_open: bool,
+	pub timeline_open: bool,
+	pub color_harmony_open: bool,
+	pub plugins_open: bool,
+	pub console_open: bool,
+	pub collaboration_open: bool,
+	pub compare_open: bool,
+	pub library_open: bool,
+	/// The number of library items changed by others which haven't been looked at yet.
+	pub library_updates: usize,
+	pub data_merge_open: bool,
+	/// The loaded plugins, whose commands are listed in the Plugins menu.
+	pub plugins: Vec<PluginManifest>,
+	/// The names of the workspace layouts saved in the preferences.
+	pub workspace_layouts: Vec<String>,
+	pub render_statistics_visible: bool,
+	pub color_profile: ColorProfile,
+	pub proof_profile: ProofProfile,
+	pub proof_colors: bool,
+	pub diff_friendly_format: bool,
+	pub color_vision_simulation: Option<ColorVisionDeficiency>,
 	pub message_logging_verbosity: MessageLoggingVerbosity,
+	pub macro_recording: bool,
+	/// The names of the action macros saved in the preferences.
+	pub action_macros: Vec<String>,
 }
 
 impl MessageHandler<MenuBarMessage, ()> for MenuBarMessageHandler {
@@ -36,6 +70,10 @@ impl LayoutHolder for MenuBarMessageHandler {
 		let node_graph_open = self.node_graph_open;
 		let has_selected_nodes = self.has_selected_nodes;
 		let has_selected_layers = self.has_selected_layers;
+		let has_selected_raster_layers = self.has_selected_raster_layers;
+		let has_selected_text_layers = self.has_selected_text_layers;
+		let has_selected_empty_text_layers = self.has_selected_empty_text_layers;
+		let has_swatches = self.has_swatches;
 		let has_selection_history = self.has_selection_history;
 		let message_logging_verbosity_off = self.message_logging_verbosity == MessageLoggingVerbosity::Off;
 		let message_logging_verbosity_names = self.message_logging_verbosity == MessageLoggingVerbosity::Names;
@@ -92,14 +130,23 @@ impl LayoutHolder for MenuBarMessageHandler {
 							..MenuBarEntry::default()
 						},
 					],
-					vec![MenuBarEntry {
-						label: "Save".into(),
-						icon: Some("Save".into()),
-						shortcut: action_keys!(DocumentMessageDiscriminant::SaveDocument),
-						action: MenuBarEntry::create_action(|_| DocumentMessage::SaveDocument.into()),
-						disabled: no_active_document,
-						..MenuBarEntry::default()
-					}],
+					vec![
+						MenuBarEntry {
+							label: "Save".into(),
+							icon: Some("Save".into()),
+							shortcut: action_keys!(DocumentMessageDiscriminant::SaveDocument),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::SaveDocument.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Diff-Friendly Format".into(),
+							icon: Some(if self.diff_friendly_format { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::ToggleDiffFriendlyFormat.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+					],
 					vec![
 						MenuBarEntry {
 							label: "Import…".into(),
@@ -116,6 +163,44 @@ impl LayoutHolder for MenuBarMessageHandler {
 							disabled: no_active_document,
 							..MenuBarEntry::default()
 						},
+						MenuBarEntry {
+							label: "Export Swatches".into(),
+							icon: Some("CustomColor".into()),
+							disabled: no_active_document || !has_swatches,
+							children: MenuBarEntryChildren(vec![
+								[
+									(PaletteFormat::Ase, "Adobe Swatch Exchange (.ase)"),
+									(PaletteFormat::Gpl, "GIMP Palette (.gpl)"),
+									(PaletteFormat::Css, "CSS Custom Properties (.css)"),
+								]
+								.into_iter()
+								.map(|(format, label)| MenuBarEntry {
+									label: label.into(),
+									action: MenuBarEntry::create_action(move |_| DocumentMessage::ExportSwatches { format }.into()),
+									disabled: no_active_document || !has_swatches,
+									..MenuBarEntry::default()
+								})
+								.collect(),
+							]),
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Extract Swatches from Layer".into(),
+							icon: Some("CustomColor".into()),
+							disabled: no_active_document || !has_selected_layers,
+							children: MenuBarEntryChildren(vec![
+								[4, 8, 16]
+									.into_iter()
+									.map(|count| MenuBarEntry {
+										label: format!("{count} Colors"),
+										action: MenuBarEntry::create_action(move |_| DocumentMessage::ExtractSwatches { count }.into()),
+										disabled: no_active_document || !has_selected_layers,
+										..MenuBarEntry::default()
+									})
+									.collect(),
+							]),
+							..MenuBarEntry::default()
+						},
 					],
 					vec![MenuBarEntry {
 						label: "Preferences…".into(),
@@ -183,6 +268,14 @@ impl LayoutHolder for MenuBarMessageHandler {
 							disabled: no_active_document || !has_selected_nodes,
 							..MenuBarEntry::default()
 						},
+						MenuBarEntry {
+							label: "Repeat Last Transform".into(),
+							icon: Some("Reload".into()),
+							shortcut: action_keys!(DocumentMessageDiscriminant::RepeatLastAction),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::RepeatLastAction.into()),
+							disabled: no_active_document || !has_selected_nodes,
+							..MenuBarEntry::default()
+						},
 						MenuBarEntry {
 							label: "Delete".into(),
 							icon: Some("Trash".into()),
@@ -192,6 +285,14 @@ impl LayoutHolder for MenuBarMessageHandler {
 							..MenuBarEntry::default()
 						},
 					],
+					vec![MenuBarEntry {
+						label: "Find & Replace…".into(),
+						icon: Some("NodeText".into()),
+						shortcut: action_keys!(DialogMessageDiscriminant::RequestFindReplaceDialog),
+						action: MenuBarEntry::create_action(|_| DialogMessage::RequestFindReplaceDialog.into()),
+						disabled: no_active_document,
+						..MenuBarEntry::default()
+					}],
 					vec![MenuBarEntry {
 						label: "Convert to Infinite Canvas".into(),
 						icon: Some("Artboard".into()),
@@ -199,6 +300,19 @@ impl LayoutHolder for MenuBarMessageHandler {
 						disabled: no_active_document,
 						..MenuBarEntry::default()
 					}],
+					vec![MenuBarEntry {
+						label: "Macros".into(),
+						icon: Some("Record".into()),
+						action: MenuBarEntry::no_action(),
+						children: self.action_macro_entries(),
+						..MenuBarEntry::default()
+					}],
+					vec![MenuBarEntry {
+						label: "Reset Tool Defaults".into(),
+						icon: Some("Reload".into()),
+						action: MenuBarEntry::create_action(|_| ToolMessage::ResetToolOptions.into()),
+						..MenuBarEntry::default()
+					}],
 				]),
 			),
 			MenuBarEntry::new_root(
@@ -253,6 +367,13 @@ impl LayoutHolder for MenuBarMessageHandler {
 							disabled: no_active_document || !has_selected_layers,
 							..MenuBarEntry::default()
 						},
+						MenuBarEntry {
+							label: "Reference Image On/Off".into(),
+							icon: Some("Image".into()),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::ToggleSelectedReferenceImages.into()),
+							disabled: no_active_document || !has_selected_raster_layers,
+							..MenuBarEntry::default()
+						},
 					],
 					vec![
 						MenuBarEntry {
@@ -431,6 +552,43 @@ impl LayoutHolder for MenuBarMessageHandler {
 							}]),
 							..MenuBarEntry::default()
 						},
+						MenuBarEntry {
+							label: "Convert to Paths".into(),
+							icon: Some("NodeShape".into()),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::ConvertSelectedTextToPaths.into()),
+							disabled: no_active_document || !has_selected_text_layers,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Fill with Placeholder Text".into(),
+							icon: Some("NodeText".into()),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::FillSelectedTextWithPlaceholder.into()),
+							disabled: no_active_document || !has_selected_empty_text_layers,
+							..MenuBarEntry::default()
+						},
+					],
+					vec![
+						MenuBarEntry {
+							label: "Recolor Artwork…".into(),
+							icon: Some("CustomColor".into()),
+							action: MenuBarEntry::create_action(|_| DialogMessage::RequestRecolorArtworkDialog.into()),
+							disabled: no_active_document || !has_selected_layers,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Check Text Contrast…".into(),
+							icon: Some("NodeText".into()),
+							action: MenuBarEntry::create_action(|_| DialogMessage::RequestContrastCheckDialog.into()),
+							disabled: no_active_document || !has_selected_layers,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Edit Externally".into(),
+							icon: Some("Image".into()),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::EditImageExternally.into()),
+							disabled: no_active_document || !has_selected_layers,
+							..MenuBarEntry::default()
+						},
 					],
 				]),
 			),
@@ -556,23 +714,175 @@ impl LayoutHolder for MenuBarMessageHandler {
 							..MenuBarEntry::default()
 						},
 					],
+					vec![
+						MenuBarEntry {
+							label: "Rulers".into(),
+							icon: Some(if self.rulers_visible { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+							shortcut: action_keys!(PortfolioMessageDiscriminant::ToggleRulers),
+							action: MenuBarEntry::create_action(|_| PortfolioMessage::ToggleRulers.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Outline Mode".into(),
+							icon: Some(if self.outline_view_mode { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+							shortcut: action_keys!(DocumentMessageDiscriminant::ToggleOutlineViewMode),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::ToggleOutlineViewMode.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+					],
+					vec![
+						MenuBarEntry {
+							label: "Proof Colors".into(),
+							icon: Some(if self.proof_colors { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::ToggleProofColors.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Proof Setup".into(),
+							action: MenuBarEntry::no_action(),
+							disabled: no_active_document,
+							children: MenuBarEntryChildren(vec![
+								ProofProfile::ALL
+									.into_iter()
+									.map(|proof_profile| MenuBarEntry {
+										label: proof_profile.name().into(),
+										icon: (self.proof_profile == proof_profile).then_some("SmallDot".into()),
+										action: MenuBarEntry::create_action(move |_| DocumentMessage::SetProofProfile { proof_profile }.into()),
+										..MenuBarEntry::default()
+									})
+									.collect(),
+							]),
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Simulate Color Blindness".into(),
+							action: MenuBarEntry::no_action(),
+							disabled: no_active_document,
+							children: MenuBarEntryChildren(vec![
+								vec![MenuBarEntry {
+									label: "Off".into(),
+									icon: self.color_vision_simulation.is_none().then_some("SmallDot".into()),
+									action: MenuBarEntry::create_action(|_| DocumentMessage::SetColorVisionSimulation { color_vision: None }.into()),
+									..MenuBarEntry::default()
+								}],
+								ColorVisionDeficiency::ALL
+									.into_iter()
+									.map(|color_vision| MenuBarEntry {
+										label: color_vision.name().into(),
+										icon: (self.color_vision_simulation == Some(color_vision)).then_some("SmallDot".into()),
+										action: MenuBarEntry::create_action(move |_| DocumentMessage::SetColorVisionSimulation { color_vision: Some(color_vision) }.into()),
+										..MenuBarEntry::default()
+									})
+									.collect(),
+							]),
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Document Color Profile".into(),
+							action: MenuBarEntry::no_action(),
+							disabled: no_active_document,
+							children: MenuBarEntryChildren(vec![
+								ColorProfile::ALL
+									.into_iter()
+									.map(|color_profile| MenuBarEntry {
+										label: color_profile.name().into(),
+										icon: (self.color_profile == color_profile).then_some("SmallDot".into()),
+										action: MenuBarEntry::create_action(move |_| DocumentMessage::SetColorProfile { color_profile }.into()),
+										..MenuBarEntry::default()
+									})
+									.collect(),
+							]),
+							..MenuBarEntry::default()
+						},
+					],
 					vec![MenuBarEntry {
-						label: "Rulers".into(),
-						icon: Some(if self.rulers_visible { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
-						shortcut: action_keys!(PortfolioMessageDiscriminant::ToggleRulers),
-						action: MenuBarEntry::create_action(|_| PortfolioMessage::ToggleRulers.into()),
-						disabled: no_active_document,
-						..MenuBarEntry::default()
-					}],
-					vec![MenuBarEntry {
-						label: "Window: Spreadsheet".into(),
-						icon: Some(if self.spreadsheet_view_open { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
-						action: MenuBarEntry::create_action(|_| SpreadsheetMessage::ToggleOpen.into()),
+						label: "Render Statistics".into(),
+						icon: Some(if self.render_statistics_visible { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+						action: MenuBarEntry::create_action(|_| PortfolioMessage::ToggleRenderStatistics.into()),
 						disabled: no_active_document,
 						..MenuBarEntry::default()
 					}],
+					vec![
+						MenuBarEntry {
+							label: "Window: Spreadsheet".into(),
+							icon: Some(if self.spreadsheet_view_open { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+							action: MenuBarEntry::create_action(|_| SpreadsheetMessage::ToggleOpen.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Window: Split View".into(),
+							icon: Some(if self.split_view_open { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+							action: MenuBarEntry::create_action(|_| SplitViewMessage::ToggleOpen.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Window: Timeline".into(),
+							icon: Some(if self.timeline_open { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+							action: MenuBarEntry::create_action(|_| TimelineMessage::ToggleOpen.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Window: Color Harmony".into(),
+							icon: Some(if self.color_harmony_open { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+							action: MenuBarEntry::create_action(|_| ColorHarmonyMessage::ToggleOpen.into()),
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Window: Plugins".into(),
+							icon: Some(if self.plugins_open { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+							action: MenuBarEntry::create_action(|_| PluginsMessage::ToggleOpen.into()),
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Window: Console".into(),
+							icon: Some(if self.console_open { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+							action: MenuBarEntry::create_action(|_| ConsoleMessage::ToggleOpen.into()),
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Window: Collaboration".into(),
+							icon: Some(if self.collaboration_open { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+							action: MenuBarEntry::create_action(|_| CollaborationMessage::ToggleOpen.into()),
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Window: Compare".into(),
+							icon: Some(if self.compare_open { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+							action: MenuBarEntry::create_action(|_| CompareMessage::ToggleOpen.into()),
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: match self.library_updates {
+								0 => "Window: Library".into(),
+								1 => "Window: Library (1 Update)".into(),
+								count => format!("Window: Library ({count} Updates)"),
+							},
+							icon: Some(if self.library_open { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+							action: MenuBarEntry::create_action(|_| LibraryMessage::ToggleOpen.into()),
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Window: Data Merge".into(),
+							icon: Some(if self.data_merge_open { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+							action: MenuBarEntry::create_action(|_| DataMergeMessage::ToggleOpen.into()),
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Workspace Layout".into(),
+							action: MenuBarEntry::no_action(),
+							children: self.workspace_layout_entries(),
+							..MenuBarEntry::default()
+						},
+					],
 				]),
 			),
+			MenuBarEntry::new_root("Plugins".into(), false, self.plugin_entries()),
 			MenuBarEntry::new_root(
 				"Help".into(),
 				true,
@@ -583,6 +893,13 @@ impl LayoutHolder for MenuBarMessageHandler {
 						action: MenuBarEntry::create_action(|_| DialogMessage::RequestAboutGraphiteDialog.into()),
 						..MenuBarEntry::default()
 					}],
+					vec![MenuBarEntry {
+						label: "Command Palette…".into(),
+						icon: Some("ZoomIn".into()),
+						action: MenuBarEntry::create_action(|_| DialogMessage::RequestCommandPaletteDialog.into()),
+						shortcut: action_keys!(DialogMessageDiscriminant::RequestCommandPaletteDialog),
+						..MenuBarEntry::default()
+					}],
 					vec![MenuBarEntry {
 						label: "User Manual".into(),
 						icon: Some("UserManual".into()),
@@ -667,3 +984,178 @@ impl LayoutHolder for MenuBarMessageHandler {
 		Layout::MenuLayout(MenuLayout::new(menu_bar_entries))
 	}
 }
+
+impl MenuBarMessageHandler {
+	/// The saved workspace layouts to switch between, followed by the entries for saving and deleting them.
+	fn workspace_layout_entries(&self) -> MenuBarEntryChildren {
+		let mut sections = Vec::new();
+
+		if !self.workspace_layouts.is_empty() {
+			sections.push(
+				self.workspace_layouts
+					.iter()
+					.map(|name| {
+						let name = name.clone();
+						MenuBarEntry {
+							label: name.clone(),
+							action: MenuBarEntry::create_action(move |_| WorkspaceMessage::ApplyLayout { name: name.clone() }.into()),
+							..MenuBarEntry::default()
+						}
+					})
+					.collect(),
+			);
+		}
+
+		sections.push(vec![
+			MenuBarEntry {
+				label: "Save Current Layout…".into(),
+				icon: Some("Save".into()),
+				action: MenuBarEntry::create_action(|_| DialogMessage::RequestSaveWorkspaceLayoutDialog { name: String::new() }.into()),
+				..MenuBarEntry::default()
+			},
+			MenuBarEntry {
+				label: "Delete Layout".into(),
+				icon: Some("Trash".into()),
+				action: MenuBarEntry::no_action(),
+				disabled: self.workspace_layouts.is_empty(),
+				children: MenuBarEntryChildren(vec![
+					self.workspace_layouts
+						.iter()
+						.map(|name| {
+							let name = name.clone();
+							MenuBarEntry {
+								label: name.clone(),
+								action: MenuBarEntry::create_action(move |_| PreferencesMessage::DeleteWorkspaceLayout { name: name.clone() }.into()),
+								..MenuBarEntry::default()
+							}
+						})
+						.collect(),
+				]),
+				..MenuBarEntry::default()
+			},
+		]);
+
+		MenuBarEntryChildren(sections)
+	}
+
+	/// A submenu for each loaded plugin listing its commands.
+	fn plugin_entries(&self) -> MenuBarEntryChildren {
+		if self.plugins.is_empty() {
+			return MenuBarEntryChildren(vec![vec![MenuBarEntry {
+				label: "No Plugins Loaded".into(),
+				action: MenuBarEntry::no_action(),
+				disabled: true,
+				..MenuBarEntry::default()
+			}]]);
+		}
+
+		let entries = self
+			.plugins
+			.iter()
+			.map(|plugin| MenuBarEntry {
+				label: plugin.name.clone(),
+				action: MenuBarEntry::no_action(),
+				disabled: plugin.commands.is_empty(),
+				children: MenuBarEntryChildren(vec![
+					plugin
+						.commands
+						.iter()
+						.map(|command| {
+							let (plugin, command_id) = (plugin.id.clone(), command.id.clone());
+							MenuBarEntry {
+								label: command.label.clone(),
+								icon: command.icon.clone(),
+								action: MenuBarEntry::create_action(move |_| {
+									PluginsMessage::RunCommand {
+										plugin: plugin.clone(),
+										command: command_id.clone(),
+									}
+									.into()
+								}),
+								..MenuBarEntry::default()
+							}
+						})
+						.collect(),
+				]),
+				..MenuBarEntry::default()
+			})
+			.collect();
+
+		MenuBarEntryChildren(vec![entries])
+	}
+
+	/// The entry for starting or stopping a recording, followed by the saved macros which can each be replayed or deleted.
+	fn action_macro_entries(&self) -> MenuBarEntryChildren {
+		let no_active_document = !self.has_active_document;
+
+		let mut sections = vec![vec![if self.macro_recording {
+			MenuBarEntry {
+				label: "Stop Recording".into(),
+				icon: Some("PlaybackPause".into()),
+				action: MenuBarEntry::create_action(|_| ActionMacroMessage::StopRecording.into()),
+				..MenuBarEntry::default()
+			}
+		} else {
+			MenuBarEntry {
+				label: "Start Recording".into(),
+				icon: Some("Record".into()),
+				action: MenuBarEntry::create_action(|_| ActionMacroMessage::StartRecording.into()),
+				..MenuBarEntry::default()
+			}
+		}]];
+
+		if !self.action_macros.is_empty() {
+			sections.push(
+				self.action_macros
+					.iter()
+					.map(|name| {
+						let (replay_name, replay_each_name, delete_name) = (name.clone(), name.clone(), name.clone());
+						MenuBarEntry {
+							label: name.clone(),
+							action: MenuBarEntry::no_action(),
+							disabled: self.macro_recording,
+							children: MenuBarEntryChildren(vec![
+								vec![
+									MenuBarEntry {
+										label: "Replay".into(),
+										icon: Some("PlaybackPlay".into()),
+										action: MenuBarEntry::create_action(move |_| {
+											ActionMacroMessage::Replay {
+												name: replay_name.clone(),
+												each_selected_layer: false,
+											}
+											.into()
+										}),
+										disabled: no_active_document,
+										..MenuBarEntry::default()
+									},
+									MenuBarEntry {
+										label: "Replay on Each Selected Layer".into(),
+										action: MenuBarEntry::create_action(move |_| {
+											ActionMacroMessage::Replay {
+												name: replay_each_name.clone(),
+												each_selected_layer: true,
+											}
+											.into()
+										}),
+										disabled: no_active_document,
+										..MenuBarEntry::default()
+									},
+								],
+								vec![MenuBarEntry {
+									label: "Delete".into(),
+									icon: Some("Trash".into()),
+									action: MenuBarEntry::create_action(move |_| PreferencesMessage::DeleteActionMacro { name: delete_name.clone() }.into()),
+									..MenuBarEntry::default()
+								}],
+							]),
+							..MenuBarEntry::default()
+						}
+					})
+					.collect(),
+			);
+		}
+
+		MenuBarEntryChildren(sections)
+	}
+}
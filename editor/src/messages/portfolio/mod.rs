@@ -1,9 +1,19 @@
 mod portfolio_message;
 mod portfolio_message_handler;
 
+pub mod collaboration;
+pub mod color_harmony;
+pub mod compare;
+pub mod console;
+pub mod data_merge;
 pub mod document;
+pub mod library;
 pub mod menu_bar;
+pub mod plugins;
+pub mod split_view;
 pub mod spreadsheet;
+pub mod tiff_format;
+pub mod timeline;
 pub mod utility_types;
 
 #[doc(inline)]
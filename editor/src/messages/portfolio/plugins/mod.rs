@@ -0,0 +1,9 @@
+mod plugins_message;
+mod plugins_message_handler;
+
+pub mod plugin_api;
+
+#[doc(inline)]
+pub use plugins_message::*;
+#[doc(inline)]
+pub use plugins_message_handler::*;
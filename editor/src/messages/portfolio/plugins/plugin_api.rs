@@ -0,0 +1,304 @@
+//! The interface between the editor and its plugins, whose JSON form is kept stable across editor versions for plugins built against the same [`PLUGIN_API_VERSION`].
+//!
+//! A plugin is a directory in the plugins directory holding a `manifest.json` with its [`PluginManifest`] and a `plugin.wasm` module.
+//! The module exports its `memory` along with two functions:
+//! - `graphite_alloc(length: i32) -> i32` reserves space in its memory for the editor to write an event into.
+//! - `graphite_handle_event(pointer: i32, length: i32) -> i64` is given a [`PluginEvent`] and returns a list of [`PluginResponse`]s,
+//!   both as UTF-8 JSON, with the pointer to the responses in the upper 32 bits of the returned value and their length in the lower 32 bits.
+
+use crate::messages::prelude::*;
+
+/// Increased whenever a change to this interface would break existing plugins.
+pub const PLUGIN_API_VERSION: u32 = 1;
+
+/// Describes a plugin and what it adds to the editor.
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginManifest {
+	/// Identifies the plugin, so it must differ from that of every other plugin.
+	pub id: String,
+	pub name: String,
+	#[serde(default)]
+	pub version: String,
+	/// The version of this interface the plugin was built against.
+	pub api_version: u32,
+	/// Listed under the plugin's name in the Plugins menu.
+	#[serde(default)]
+	pub commands: Vec<PluginCommand>,
+	/// Shown as sections of the Plugins panel, each laid out by the plugin with [`PluginResponse::UpdatePanel`].
+	#[serde(default)]
+	pub panels: Vec<PluginPanel>,
+	/// Added to the end of the tool shelf, with their inputs on the canvas sent to the plugin as [`PluginEvent::Tool`].
+	#[serde(default)]
+	pub tools: Vec<PluginToolDescription>,
+}
+
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct PluginCommand {
+	pub id: String,
+	pub label: String,
+	#[serde(default)]
+	pub icon: Option<String>,
+}
+
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct PluginPanel {
+	pub id: String,
+	pub title: String,
+}
+
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct PluginToolDescription {
+	pub id: String,
+	pub label: String,
+	/// The name of one of the editor's tool icons to show in the tool shelf, such as `VectorPenTool`.
+	pub icon: String,
+}
+
+/// Sent to a plugin when something it added to the editor is used.
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type")]
+pub enum PluginEvent {
+	/// Sent once after the plugin is registered, so it can lay out its panels.
+	Loaded,
+	Command {
+		command: String,
+	},
+	Widget {
+		panel: String,
+		widget: String,
+		value: PluginWidgetValue,
+	},
+	/// Sent while one of the plugin's tools is active, with what the user did on the canvas.
+	Tool {
+		tool: String,
+		input: PluginToolInput,
+	},
+	/// A change to one of the widgets the plugin placed in a tool's options.
+	ToolOption {
+		tool: String,
+		widget: String,
+		value: PluginWidgetValue,
+	},
+}
+
+/// Input given to a plugin's tool, in document space.
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type")]
+pub enum PluginToolInput {
+	/// The user picked the tool, so it can lay out its options with [`PluginResponse::UpdateToolOptions`].
+	Activated,
+	/// The user cancelled what the tool was doing, or switched to another tool.
+	Abort,
+	PointerDown {
+		pointer: PluginPointer,
+	},
+	PointerMove {
+		pointer: PluginPointer,
+	},
+	PointerUp {
+		pointer: PluginPointer,
+	},
+}
+
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct PluginPointer {
+	pub position: [f64; 2],
+	pub shift: bool,
+	pub alt: bool,
+	/// Ctrl, or Cmd on Mac.
+	pub accel: bool,
+}
+
+/// Returned by a plugin in response to an event.
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type")]
+pub enum PluginResponse {
+	/// Replaces the contents of one of the plugin's panels with rows of widgets.
+	UpdatePanel { panel: String, rows: Vec<Vec<PluginWidget>> },
+	/// Reports a problem to the user in a dialog.
+	Error { title: String, description: String },
+	/// Sends messages straight to the editor, which is how plugins change documents.
+	/// Unlike the rest of this interface, these follow the editor's own messages, so they aren't kept stable between editor versions.
+	/// Only messages editing the active document are accepted, as decided by [`is_permitted_editor_message`], and the whole list is refused if any other is among them.
+	EditorMessages { messages: Vec<Message> },
+	/// Replaces the options of one of the plugin's tools, shown in the control bar while it's active.
+	UpdateToolOptions { tool: String, rows: Vec<Vec<PluginWidget>> },
+	/// Replaces what one of the plugin's tools draws over the canvas while it's active.
+	UpdateToolOverlays { tool: String, overlays: Vec<PluginOverlay> },
+}
+
+/// A shape a plugin's tool draws over the canvas, in document space.
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type")]
+pub enum PluginOverlay {
+	Line { start: [f64; 2], end: [f64; 2] },
+	Circle { center: [f64; 2], radius: f64 },
+	Rectangle { min: [f64; 2], max: [f64; 2] },
+	Text { position: [f64; 2], text: String },
+}
+
+/// A widget a plugin can place in its panels, whose changes are sent back to it as [`PluginEvent::Widget`].
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type")]
+pub enum PluginWidget {
+	Label {
+		text: String,
+	},
+	Button {
+		id: String,
+		label: String,
+	},
+	Checkbox {
+		id: String,
+		checked: bool,
+	},
+	Number {
+		id: String,
+		value: f64,
+		#[serde(default)]
+		min: Option<f64>,
+		#[serde(default)]
+		max: Option<f64>,
+	},
+	Text {
+		id: String,
+		value: String,
+	},
+}
+
+/// The new value of a widget changed by the user, or [`PluginWidgetValue::Pressed`] for buttons.
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum PluginWidgetValue {
+	Pressed,
+	Checked(bool),
+	Number(f64),
+	Text(String),
+}
+
+impl PluginManifest {
+	pub fn parse(json: &str) -> Result<Self, String> {
+		let manifest: Self = serde_json::from_str(json).map_err(|error| format!("The plugin's manifest couldn't be read: {error}"))?;
+
+		if manifest.api_version != PLUGIN_API_VERSION {
+			return Err(format!(
+				"\"{}\" was built for version {} of the plugin interface, but this version of Graphite supports version {PLUGIN_API_VERSION}",
+				manifest.name, manifest.api_version
+			));
+		}
+		if manifest.id.is_empty() {
+			return Err(format!("\"{}\" has no ID in its manifest", manifest.name));
+		}
+
+		Ok(manifest)
+	}
+}
+
+/// Whether a plugin may send the message in [`PluginResponse::EditorMessages`].
+///
+/// Plugins can only edit the active document, so they can't reach the preferences, the save actions, or anything else which runs programs or touches files,
+/// and they can't save, export, or step through the history outside the undo step their changes are grouped into.
+pub fn is_permitted_editor_message(message: &Message) -> bool {
+	let Message::Portfolio(PortfolioMessage::Document(message)) = message else { return false };
+
+	!matches!(
+		message,
+		DocumentMessage::SaveDocument
+			| DocumentMessage::EditImageExternally
+			| DocumentMessage::ExportSwatches { .. }
+			| DocumentMessage::ImaginateGenerate { .. }
+			| DocumentMessage::ImaginateRandom { .. }
+			| DocumentMessage::DocumentHistoryBackward
+			| DocumentMessage::DocumentHistoryForward
+			| DocumentMessage::Undo
+			| DocumentMessage::Redo
+			| DocumentMessage::StartTransaction
+			| DocumentMessage::EndTransaction
+			| DocumentMessage::CommitTransaction
+			| DocumentMessage::AbortTransaction
+			| DocumentMessage::RepeatedAbortTransaction
+			| DocumentMessage::AddTransaction
+			| DocumentMessage::StartUndoGroup
+			| DocumentMessage::EndUndoGroup
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn manifests_of_other_api_versions_are_refused() {
+		let manifest = r#"{ "id": "example", "name": "Example", "apiVersion": 1, "commands": [{ "id": "run", "label": "Run" }] }"#;
+		let parsed = PluginManifest::parse(manifest).unwrap();
+		assert_eq!(parsed.commands[0].label, "Run");
+		assert!(parsed.panels.is_empty());
+
+		assert!(PluginManifest::parse(&manifest.replace("\"apiVersion\": 1", "\"apiVersion\": 0")).is_err());
+		assert!(PluginManifest::parse(&manifest.replace("\"id\": \"example\"", "\"id\": \"\"")).is_err());
+	}
+
+	#[test]
+	fn plugins_may_only_edit_the_document() {
+		assert!(is_permitted_editor_message(&DocumentMessage::DeleteSelectedLayers.into()));
+		assert!(is_permitted_editor_message(&NodeGraphMessage::RunDocumentGraph.into()));
+
+		assert!(!is_permitted_editor_message(&DocumentMessage::SaveDocument.into()));
+		assert!(!is_permitted_editor_message(&DocumentMessage::EditImageExternally.into()));
+		assert!(!is_permitted_editor_message(&DocumentMessage::Undo.into()));
+		assert!(!is_permitted_editor_message(&PortfolioMessage::CloseAllDocuments.into()));
+		assert!(!is_permitted_editor_message(&PreferencesMessage::ResetToDefaults.into()));
+	}
+
+	#[test]
+	fn events_and_responses_keep_their_json_form() {
+		let event = PluginEvent::Widget {
+			panel: "main".into(),
+			widget: "size".into(),
+			value: PluginWidgetValue::Number(2.),
+		};
+		assert_eq!(serde_json::to_string(&event).unwrap(), r#"{"type":"Widget","panel":"main","widget":"size","value":2.0}"#);
+
+		let responses: Vec<PluginResponse> = serde_json::from_str(r#"[{ "type": "UpdatePanel", "panel": "main", "rows": [[{ "type": "Button", "id": "go", "label": "Go" }]] }]"#).unwrap();
+		assert_eq!(
+			responses,
+			vec![PluginResponse::UpdatePanel {
+				panel: "main".into(),
+				rows: vec![vec![PluginWidget::Button { id: "go".into(), label: "Go".into() }]],
+			}]
+		);
+	}
+
+	#[test]
+	fn tools_keep_their_json_form() {
+		let manifest = r#"{ "id": "example", "name": "Example", "apiVersion": 1, "tools": [{ "id": "stamp", "label": "Stamp", "icon": "RasterBrushTool" }] }"#;
+		let parsed = PluginManifest::parse(manifest).unwrap();
+		assert_eq!(parsed.tools[0].label, "Stamp");
+
+		let event = PluginEvent::Tool {
+			tool: "stamp".into(),
+			input: PluginToolInput::PointerDown {
+				pointer: PluginPointer {
+					position: [1., 2.],
+					shift: true,
+					alt: false,
+					accel: false,
+				},
+			},
+		};
+		assert_eq!(
+			serde_json::to_string(&event).unwrap(),
+			r#"{"type":"Tool","tool":"stamp","input":{"type":"PointerDown","pointer":{"position":[1.0,2.0],"shift":true,"alt":false,"accel":false}}}"#
+		);
+
+		let responses: Vec<PluginResponse> = serde_json::from_str(r#"[{ "type": "UpdateToolOverlays", "tool": "stamp", "overlays": [{ "type": "Circle", "center": [1, 2], "radius": 3 }] }]"#).unwrap();
+		assert_eq!(
+			responses,
+			vec![PluginResponse::UpdateToolOverlays {
+				tool: "stamp".into(),
+				overlays: vec![PluginOverlay::Circle { center: [1., 2.], radius: 3. }],
+			}]
+		);
+	}
+}
@@ -0,0 +1,44 @@
+use super::plugin_api::{PluginToolInput, PluginWidgetValue};
+use crate::messages::prelude::*;
+
+/// Plugins are WASM modules loaded by the frontend from the plugins directory, which add menu commands, panels, and tools to the editor.
+#[impl_message(Message, PortfolioMessage, Plugins)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum PluginsMessage {
+	ToggleOpen,
+
+	/// Registers a plugin from its manifest, serialized as JSON.
+	Register {
+		manifest: String,
+	},
+	Unregister {
+		plugin: String,
+	},
+
+	RunCommand {
+		plugin: String,
+		command: String,
+	},
+	WidgetChanged {
+		plugin: String,
+		panel: String,
+		widget: String,
+		value: PluginWidgetValue,
+	},
+	ToolInput {
+		plugin: String,
+		tool: String,
+		input: PluginToolInput,
+	},
+	ToolOptionChanged {
+		plugin: String,
+		tool: String,
+		widget: String,
+		value: PluginWidgetValue,
+	},
+	/// Handles the responses a plugin returned from an event, serialized as JSON.
+	Responses {
+		plugin: String,
+		responses: String,
+	},
+}
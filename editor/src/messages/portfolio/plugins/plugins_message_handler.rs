@@ -0,0 +1,248 @@
+use super::plugin_api::{PluginEvent, PluginManifest, PluginResponse, PluginWidget, PluginWidgetValue, is_permitted_editor_message};
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::*;
+
+/// A registered plugin along with the latest layout it gave each of its panels.
+#[derive(Debug, Clone)]
+struct LoadedPlugin {
+	manifest: PluginManifest,
+	panel_rows: HashMap<String, Vec<Vec<PluginWidget>>>,
+}
+
+/// Keeps track of the loaded plugins, routing uses of their commands and panels to the frontend, which runs the plugins.
+#[derive(Default, Debug, Clone)]
+pub struct PluginsMessageHandler {
+	/// Sets whether or not the Plugins panel is drawn.
+	pub plugins_open: bool,
+	plugins: Vec<LoadedPlugin>,
+}
+
+impl MessageHandler<PluginsMessage, ()> for PluginsMessageHandler {
+	fn process_message(&mut self, message: PluginsMessage, responses: &mut VecDeque<Message>, _data: ()) {
+		match message {
+			PluginsMessage::ToggleOpen => {
+				self.plugins_open = !self.plugins_open;
+				// Update checked UI state for open
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			PluginsMessage::Register { manifest } => {
+				let manifest = match PluginManifest::parse(&manifest) {
+					Ok(manifest) => manifest,
+					Err(description) => {
+						responses.add(DialogMessage::DisplayDialogError {
+							title: "Plugin couldn't be loaded".into(),
+							description,
+						});
+						return;
+					}
+				};
+
+				// Registering a plugin again, such as after it's been updated, replaces the earlier one
+				let plugin = manifest.id.clone();
+				self.plugins.retain(|loaded| loaded.manifest.id != plugin);
+				self.plugins.push(LoadedPlugin { manifest, panel_rows: HashMap::new() });
+
+				responses.add(MenuBarMessage::SendLayout);
+				self.update_tools(responses);
+				send_event(plugin, PluginEvent::Loaded, responses);
+			}
+			PluginsMessage::Unregister { plugin } => {
+				self.plugins.retain(|loaded| loaded.manifest.id != plugin);
+				responses.add(MenuBarMessage::SendLayout);
+				self.update_tools(responses);
+			}
+			PluginsMessage::RunCommand { plugin, command } => {
+				send_event(plugin, PluginEvent::Command { command }, responses);
+				return;
+			}
+			PluginsMessage::WidgetChanged { plugin, panel, widget, value } => {
+				send_event(plugin, PluginEvent::Widget { panel, widget, value }, responses);
+				return;
+			}
+			PluginsMessage::ToolInput { plugin, tool, input } => {
+				send_event(plugin, PluginEvent::Tool { tool, input }, responses);
+				return;
+			}
+			PluginsMessage::ToolOptionChanged { plugin, tool, widget, value } => {
+				send_event(plugin, PluginEvent::ToolOption { tool, widget, value }, responses);
+				return;
+			}
+			PluginsMessage::Responses { plugin, responses: plugin_responses } => {
+				let Some(loaded) = self.plugins.iter_mut().find(|loaded| loaded.manifest.id == plugin) else {
+					warn!("Received responses from the unregistered plugin \"{plugin}\"");
+					return;
+				};
+
+				let plugin_responses = match serde_json::from_str::<Vec<PluginResponse>>(&plugin_responses) {
+					Ok(plugin_responses) => plugin_responses,
+					Err(error) => {
+						responses.add(DialogMessage::DisplayDialogError {
+							title: format!("{} failed", loaded.manifest.name),
+							description: format!("The plugin responded with something the editor couldn't read: {error}"),
+						});
+						return;
+					}
+				};
+
+				for response in plugin_responses {
+					match response {
+						PluginResponse::UpdatePanel { panel, rows } => {
+							if loaded.manifest.panels.iter().any(|declared| declared.id == panel) {
+								loaded.panel_rows.insert(panel, rows);
+							} else {
+								warn!("The plugin \"{plugin}\" tried to lay out the panel \"{panel}\" which it doesn't declare in its manifest");
+							}
+						}
+						PluginResponse::Error { title, description } => responses.add(DialogMessage::DisplayDialogError { title, description }),
+						PluginResponse::EditorMessages { messages } => {
+							if messages.is_empty() {
+								continue;
+							}
+							if let Some(refused) = messages.iter().find(|message| !is_permitted_editor_message(message)) {
+								warn!("The plugin \"{plugin}\" tried to send the message {refused:?}, which plugins aren't permitted to send");
+								responses.add(DialogMessage::DisplayDialogError {
+									title: format!("The plugin \"{}\" was stopped from changing the editor", loaded.manifest.name),
+									description: "Plugins may only send messages which edit the active document, so none of the messages it sent have been applied.".into(),
+								});
+								continue;
+							}
+
+							// Everything a plugin changes in response to one event is undone in a single step
+							responses.add(DocumentMessage::StartUndoGroup);
+							responses.add(DocumentMessage::StartTransaction);
+							for message in messages {
+								responses.add(message);
+							}
+							responses.add(DocumentMessage::EndTransaction);
+							responses.add(DocumentMessage::EndUndoGroup);
+						}
+						PluginResponse::UpdateToolOptions { tool, rows } => {
+							if loaded.manifest.tools.iter().any(|declared| declared.id == tool) {
+								responses.add(PluginToolMessage::UpdateOptions { plugin: plugin.clone(), tool, rows });
+							} else {
+								warn!("The plugin \"{plugin}\" tried to set the options of the tool \"{tool}\" which it doesn't declare in its manifest");
+							}
+						}
+						PluginResponse::UpdateToolOverlays { tool, overlays } => {
+							responses.add(PluginToolMessage::UpdateOverlays {
+								plugin: plugin.clone(),
+								tool,
+								overlays,
+							});
+						}
+					}
+				}
+			}
+		}
+
+		self.update_layout(responses);
+	}
+
+	fn actions(&self) -> ActionList {
+		actions!(PluginsMessage;)
+	}
+}
+
+fn send_event(plugin: String, event: PluginEvent, responses: &mut VecDeque<Message>) {
+	match serde_json::to_string(&event) {
+		Ok(event) => responses.add(FrontendMessage::TriggerPluginEvent { plugin, event }),
+		Err(error) => error!("Failed to serialize an event for the plugin \"{plugin}\": {error}"),
+	}
+}
+
+impl PluginsMessageHandler {
+	/// The manifests of the loaded plugins, in the order they were loaded.
+	pub fn manifests(&self) -> Vec<PluginManifest> {
+		self.plugins.iter().map(|loaded| loaded.manifest.clone()).collect()
+	}
+
+	/// Tells the tool shelf about the tools of every loaded plugin.
+	fn update_tools(&self, responses: &mut VecDeque<Message>) {
+		let tools = self
+			.plugins
+			.iter()
+			.flat_map(|loaded| loaded.manifest.tools.iter().map(|tool| (loaded.manifest.id.clone(), tool.clone())))
+			.collect();
+		responses.add(ToolMessage::UpdatePluginTools { tools });
+	}
+
+	fn update_layout(&self, responses: &mut VecDeque<Message>) {
+		responses.add(FrontendMessage::UpdatePluginsState { open: self.plugins_open });
+		if !self.plugins_open {
+			return;
+		}
+
+		let mut layout = Vec::new();
+		for loaded in &self.plugins {
+			for panel in &loaded.manifest.panels {
+				layout.push(LayoutGroup::Row {
+					widgets: vec![TextLabel::new(format!("{}: {}", loaded.manifest.name, panel.title)).bold(true).widget_holder()],
+				});
+
+				let rows = loaded.panel_rows.get(&panel.id).map(Vec::as_slice).unwrap_or_default();
+				layout.extend(rows.iter().map(|row| LayoutGroup::Row {
+					widgets: row.iter().map(|widget| plugin_widget(widget, panel_widget_changed(&loaded.manifest.id, &panel.id))).collect(),
+				}));
+			}
+		}
+		if layout.is_empty() {
+			layout.push(LayoutGroup::Row {
+				widgets: vec![TextLabel::new("No plugins with panels are loaded").italic(true).widget_holder()],
+			});
+		}
+
+		responses.add(LayoutMessage::SendLayout {
+			layout: Layout::WidgetLayout(WidgetLayout { layout }),
+			layout_target: LayoutTarget::Plugins,
+		});
+	}
+}
+
+fn panel_widget_changed(plugin: &str, panel: &str) -> impl Fn(&str, PluginWidgetValue) -> Message + Send + Sync + 'static {
+	let (plugin, panel) = (plugin.to_string(), panel.to_string());
+	move |widget: &str, value| -> Message {
+		PluginsMessage::WidgetChanged {
+			plugin: plugin.clone(),
+			panel: panel.clone(),
+			widget: widget.to_string(),
+			value,
+		}
+		.into()
+	}
+}
+
+/// Builds the editor's widget for one described by a plugin, with `changed` giving the message that reports a change back to the plugin that owns it.
+pub fn plugin_widget(widget: &PluginWidget, changed: impl Fn(&str, PluginWidgetValue) -> Message + Send + Sync + 'static) -> WidgetHolder {
+	match widget {
+		PluginWidget::Label { text } => TextLabel::new(text).widget_holder(),
+		PluginWidget::Button { id, label } => {
+			let id = id.clone();
+			TextButton::new(label).on_update(move |_| changed(&id, PluginWidgetValue::Pressed)).widget_holder()
+		}
+		PluginWidget::Checkbox { id, checked } => {
+			let id = id.clone();
+			CheckboxInput::new(*checked)
+				.on_update(move |checkbox_input: &CheckboxInput| changed(&id, PluginWidgetValue::Checked(checkbox_input.checked)))
+				.widget_holder()
+		}
+		PluginWidget::Number { id, value, min, max } => {
+			let id = id.clone();
+			let mut number_input = NumberInput::new(Some(*value));
+			if let Some(min) = min {
+				number_input = number_input.min(*min);
+			}
+			if let Some(max) = max {
+				number_input = number_input.max(*max);
+			}
+			number_input
+				.on_update(move |number_input: &NumberInput| changed(&id, PluginWidgetValue::Number(number_input.value.unwrap_or_default())))
+				.widget_holder()
+		}
+		PluginWidget::Text { id, value } => {
+			let id = id.clone();
+			TextInput::new(value)
+				.on_update(move |text_input: &TextInput| changed(&id, PluginWidgetValue::Text(text_input.value.clone())))
+				.widget_holder()
+		}
+	}
+}
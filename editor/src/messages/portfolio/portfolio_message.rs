@@ -1,6 +1,6 @@
 use super::document::utility_types::document_metadata::LayerNodeIdentifier;
 use super::utility_types::PanelType;
-use crate::messages::frontend::utility_types::{ExportBounds, FileType};
+use crate::messages::frontend::utility_types::{ExportBounds, ExportMetadata, FileType, FrameSequenceExport, TextExport, TiffCompression};
 use crate::messages::portfolio::document::utility_types::clipboards::Clipboard;
 use crate::messages::prelude::*;
 use graphene_core::Color;
@@ -17,6 +17,24 @@ pub enum PortfolioMessage {
 	Document(DocumentMessage),
 	#[child]
 	Spreadsheet(SpreadsheetMessage),
+	#[child]
+	SplitView(SplitViewMessage),
+	#[child]
+	Timeline(TimelineMessage),
+	#[child]
+	ColorHarmony(ColorHarmonyMessage),
+	#[child]
+	Plugins(PluginsMessage),
+	#[child]
+	Console(ConsoleMessage),
+	#[child]
+	Collaboration(CollaborationMessage),
+	#[child]
+	Compare(CompareMessage),
+	#[child]
+	Library(LibraryMessage),
+	#[child]
+	DataMerge(DataMergeMessage),
 
 	// Messages
 	DocumentPassMessage {
@@ -102,12 +120,26 @@ pub enum PortfolioMessage {
 		parent_and_insert_index: Option<(LayerNodeIdentifier, usize)>,
 	},
 	PrevDocument,
+	ReorderDocument {
+		document_id: DocumentId,
+		new_index: usize,
+	},
+	/// Runs the save actions set in the preferences after a document is saved.
+	RunSaveActions {
+		document_id: DocumentId,
+	},
 	SetActivePanel {
 		panel: PanelType,
 	},
 	SetDevicePixelRatio {
 		ratio: f64,
 	},
+	/// Replaces the pixels of an image layer with those saved by the application it's being edited in.
+	SetExternallyEditedImage {
+		document_id: DocumentId,
+		layer: LayerNodeIdentifier,
+		image: Image<Color>,
+	},
 	SelectDocument {
 		document_id: DocumentId,
 	},
@@ -117,12 +149,21 @@ pub enum PortfolioMessage {
 		scale_factor: f64,
 		bounds: ExportBounds,
 		transparent_background: bool,
+		metadata: ExportMetadata,
+		tiff_compression: TiffCompression,
+		#[serde(default)]
+		text_export: TextExport,
+		frame_sequence: Option<FrameSequenceExport>,
+		/// Identifies the exported file to the frontend, which takes it instead of downloading it, like the remote control server does.
+		#[serde(default)]
+		export_id: Option<u64>,
 	},
 	SubmitActiveGraphRender,
 	SubmitGraphRender {
 		document_id: DocumentId,
 		ignore_hash: bool,
 	},
+	ToggleRenderStatistics,
 	ToggleRulers,
 	UpdateDocumentWidgets,
 	UpdateOpenDocumentsList,
@@ -1,5 +1,12 @@
+use super::collaboration::{CollaborationMessageData, CollaborationMessageHandler};
+use super::color_harmony::ColorHarmonyMessageHandler;
+use super::compare::{CompareMessageData, CompareMessageHandler};
+use super::console::{ConsoleMessageData, ConsoleMessageHandler};
+use super::data_merge::{DataMergeMessageData, DataMergeMessageHandler};
 use super::document::utility_types::document_metadata::LayerNodeIdentifier;
-use super::document::utility_types::network_interface::{self, InputConnector, OutputConnector};
+use super::document::utility_types::network_interface::{InputConnector, OutputConnector};
+use super::library::{LibraryMessageData, LibraryMessageHandler};
+use super::plugins::PluginsMessageHandler;
 use super::spreadsheet::SpreadsheetMessageHandler;
 use super::utility_types::{PanelType, PersistentData};
 use crate::application::generate_uuid;
@@ -7,22 +14,24 @@ use crate::consts::DEFAULT_DOCUMENT_NAME;
 use crate::messages::animation::TimingInformation;
 use crate::messages::debug::utility_types::MessageLoggingVerbosity;
 use crate::messages::dialog::simple_dialogs;
-use crate::messages::frontend::utility_types::FrontendDocumentDetails;
+use crate::messages::frontend::utility_types::{ExportBounds, FileType, FrontendDocumentDetails};
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::DocumentMessageData;
 use crate::messages::portfolio::document::node_graph::document_node_definitions::resolve_document_node_type;
 use crate::messages::portfolio::document::utility_types::clipboards::{Clipboard, CopyBufferEntry, INTERNAL_CLIPBOARD_COUNT};
-use crate::messages::portfolio::document::utility_types::nodes::SelectedNodes;
-use crate::messages::preferences::SelectionMode;
+use crate::messages::preferences::{SaveActionKind, SelectionMode};
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::graph_modification_utils::{self, NodeGraphLayer};
+use crate::messages::tool::common_functionality::utility_functions::is_empty_text_box;
 use crate::messages::tool::utility_types::{HintData, HintGroup, ToolType};
 use crate::node_graph_executor::{ExportConfig, NodeGraphExecutor};
 use bezier_rs::Subpath;
 use glam::IVec2;
 use graph_craft::document::value::TaggedValue;
 use graph_craft::document::{DocumentNodeImplementation, NodeId, NodeInput};
-use graphene_core::text::{Font, TypesettingConfig};
-use graphene_std::vector::style::{Fill, FillType, Gradient};
+use graphene_core::raster::image::ImageFrameTable;
+use graphene_core::text::Font;
+use graphene_std::vector::style::{Fill, FillType, Gradient, ViewMode};
 use graphene_std::vector::{VectorData, VectorDataTable};
 use interpreted_executor::dynamic_executor::IntrospectError;
 use std::sync::Arc;
@@ -33,6 +42,7 @@ pub struct PortfolioMessageData<'a> {
 	pub preferences: &'a PreferencesMessageHandler,
 	pub current_tool: &'a ToolType,
 	pub message_logging_verbosity: MessageLoggingVerbosity,
+	pub macro_recording: bool,
 	pub timing_information: TimingInformation,
 	pub animation: &'a AnimationMessageHandler,
 }
@@ -50,7 +60,27 @@ pub struct PortfolioMessageHandler {
 	pub selection_mode: SelectionMode,
 	/// The spreadsheet UI allows for instance data to be previewed.
 	pub spreadsheet: SpreadsheetMessageHandler,
+	/// The split view shows a document beside the main viewport, with its own zoom and panning.
+	pub split_view: SplitViewMessageHandler,
+	/// The timeline shows the active document's keyframes along the animation's time.
+	pub timeline: TimelineMessageHandler,
+	/// The Color Harmony panel generates color schemes from the primary working color.
+	pub color_harmony: ColorHarmonyMessageHandler,
+	/// The plugins loaded by the frontend, which add menu commands and the contents of the Plugins panel.
+	pub plugins: PluginsMessageHandler,
+	/// The Console panel runs scripts which dispatch editor messages.
+	pub console: ConsoleMessageHandler,
+	/// Syncs a document with the others editing it together through a collaboration server.
+	pub collaboration: CollaborationMessageHandler,
+	/// The Compare panel lists the differences between two versions of a document and merges those chosen from one into the other.
+	pub compare: CompareMessageHandler,
+	/// The Library panel publishes assets to a library shared through a file or URL and inserts its items into documents.
+	pub library: LibraryMessageHandler,
+	/// The Data Merge panel makes a copy of a template artboard for each record of a dataset, with the text and images of its layers bound to the dataset's columns.
+	pub data_merge: DataMergeMessageHandler,
 	device_pixel_ratio: Option<f64>,
+	/// Fonts unloaded to keep the font cache within its memory budget, which are loaded again if a document starts using them.
+	evicted_fonts: HashSet<Font>,
 }
 
 impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMessageHandler {
@@ -60,6 +90,7 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 			preferences,
 			current_tool,
 			message_logging_verbosity,
+			macro_recording,
 			timing_information,
 			animation,
 		} = data;
@@ -69,20 +100,56 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 			PortfolioMessage::MenuBar(message) => {
 				self.menu_bar_message_handler.has_active_document = false;
 				self.menu_bar_message_handler.rulers_visible = false;
+				self.menu_bar_message_handler.outline_view_mode = false;
 				self.menu_bar_message_handler.node_graph_open = false;
 				self.menu_bar_message_handler.has_selected_nodes = false;
 				self.menu_bar_message_handler.has_selected_layers = false;
+				self.menu_bar_message_handler.has_selected_raster_layers = false;
+				self.menu_bar_message_handler.has_selected_text_layers = false;
+				self.menu_bar_message_handler.has_selected_empty_text_layers = false;
+				self.menu_bar_message_handler.has_swatches = false;
 				self.menu_bar_message_handler.has_selection_history = (false, false);
 				self.menu_bar_message_handler.spreadsheet_view_open = self.spreadsheet.spreadsheet_view_open;
+				self.menu_bar_message_handler.split_view_open = self.split_view.split_view_open;
+				self.menu_bar_message_handler.timeline_open = self.timeline.timeline_open;
+				self.menu_bar_message_handler.color_harmony_open = self.color_harmony.color_harmony_open;
+				self.menu_bar_message_handler.plugins_open = self.plugins.plugins_open;
+				self.menu_bar_message_handler.plugins = self.plugins.manifests();
+				self.menu_bar_message_handler.console_open = self.console.console_open;
+				self.menu_bar_message_handler.collaboration_open = self.collaboration.collaboration_open;
+				self.menu_bar_message_handler.compare_open = self.compare.compare_open;
+				self.menu_bar_message_handler.library_open = self.library.library_open;
+				self.menu_bar_message_handler.library_updates = self.library.update_count();
+				self.menu_bar_message_handler.data_merge_open = self.data_merge.data_merge_open;
+				self.menu_bar_message_handler.workspace_layouts = preferences.workspace_layouts.iter().map(|layout| layout.name.clone()).collect();
+				self.menu_bar_message_handler.render_statistics_visible = self.executor.render_statistics.visible;
+				self.menu_bar_message_handler.proof_colors = false;
+				self.menu_bar_message_handler.diff_friendly_format = false;
+				self.menu_bar_message_handler.color_vision_simulation = None;
 				self.menu_bar_message_handler.message_logging_verbosity = message_logging_verbosity;
+				self.menu_bar_message_handler.macro_recording = macro_recording;
+				self.menu_bar_message_handler.action_macros = preferences.action_macros.iter().map(|action_macro| action_macro.name.clone()).collect();
 
 				if let Some(document) = self.active_document_id.and_then(|document_id| self.documents.get_mut(&document_id)) {
 					self.menu_bar_message_handler.has_active_document = true;
 					self.menu_bar_message_handler.rulers_visible = document.rulers_visible;
+					self.menu_bar_message_handler.outline_view_mode = document.view_mode == ViewMode::Outline;
+					self.menu_bar_message_handler.color_profile = document.color_profile;
+					self.menu_bar_message_handler.proof_profile = document.proof_profile;
+					self.menu_bar_message_handler.proof_colors = document.proof_colors;
+					self.menu_bar_message_handler.diff_friendly_format = document.diff_friendly_format;
+					self.menu_bar_message_handler.has_swatches = !document.swatches.is_empty();
+					self.menu_bar_message_handler.color_vision_simulation = document.color_vision_simulation;
 					self.menu_bar_message_handler.node_graph_open = document.is_graph_overlay_open();
 					let selected_nodes = document.network_interface.selected_nodes();
 					self.menu_bar_message_handler.has_selected_nodes = selected_nodes.selected_nodes().next().is_some();
 					self.menu_bar_message_handler.has_selected_layers = selected_nodes.selected_visible_layers(&document.network_interface).next().is_some();
+					let selected_layers = selected_nodes.selected_layers_except_artboards(&document.network_interface).collect::<Vec<_>>();
+					self.menu_bar_message_handler.has_selected_text_layers = selected_layers
+						.iter()
+						.any(|&layer| graph_modification_utils::is_layer_fed_by_node_of_name(layer, &document.network_interface, "Text"));
+					self.menu_bar_message_handler.has_selected_empty_text_layers = selected_layers.iter().any(|&layer| is_empty_text_box(layer, document, &self.persistent_data.font_cache));
+					self.menu_bar_message_handler.has_selected_raster_layers = selected_layers.into_iter().any(|layer| NodeGraphLayer::is_raster_layer(layer, &mut document.network_interface));
 					self.menu_bar_message_handler.has_selection_history = {
 						let metadata = &document.network_interface.document_network_metadata().persistent_metadata;
 						(!metadata.selection_undo_history.is_empty(), !metadata.selection_redo_history.is_empty())
@@ -94,6 +161,72 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 			PortfolioMessage::Spreadsheet(message) => {
 				self.spreadsheet.process_message(message, responses, ());
 			}
+			PortfolioMessage::SplitView(message) => {
+				let split_view_data = SplitViewMessageData {
+					documents: &mut self.documents,
+					document_ids: &self.document_ids,
+					active_document_id: self.active_document_id,
+					executor: &mut self.executor,
+					timing_information,
+				};
+				self.split_view.process_message(message, responses, split_view_data);
+			}
+			PortfolioMessage::Timeline(message) => {
+				let timeline_data = TimelineMessageData {
+					document: self.active_document_id.and_then(|document_id| self.documents.get_mut(&document_id)),
+					executor: &mut self.executor,
+					viewport_resolution: ipp.viewport_bounds.size().as_uvec2(),
+					timing_information,
+					animation_is_playing: animation.is_playing(),
+					frames_per_second: animation.fps(),
+					undo_history_length: preferences.undo_history_length,
+				};
+				self.timeline.process_message(message, responses, timeline_data);
+			}
+			PortfolioMessage::ColorHarmony(message) => {
+				self.color_harmony.process_message(message, responses, ());
+			}
+			PortfolioMessage::Plugins(message) => {
+				self.plugins.process_message(message, responses, ());
+			}
+			PortfolioMessage::Console(message) => {
+				let document = self.active_document_id.and_then(|id| self.documents.get(&id));
+				self.console.process_message(message, responses, ConsoleMessageData { document });
+			}
+			PortfolioMessage::Collaboration(message) => {
+				let data = CollaborationMessageData {
+					documents: &mut self.documents,
+					active_document_id: self.active_document_id,
+					ipp,
+				};
+				self.collaboration.process_message(message, responses, data);
+			}
+			PortfolioMessage::Compare(message) => {
+				let data = CompareMessageData {
+					documents: &mut self.documents,
+					document_ids: &self.document_ids,
+					active_document_id: self.active_document_id,
+					split_view_open: self.split_view.split_view_open,
+					ipp,
+					preferences,
+				};
+				self.compare.process_message(message, responses, data);
+			}
+			PortfolioMessage::Library(message) => {
+				let data = LibraryMessageData {
+					document: self.active_document_id.and_then(|id| self.documents.get_mut(&id)),
+					preferences,
+				};
+				self.library.process_message(message, responses, data);
+			}
+			PortfolioMessage::DataMerge(message) => {
+				let data = DataMergeMessageData {
+					document: self.active_document_id.and_then(|id| self.documents.get_mut(&id)),
+					executor: &mut self.executor,
+					undo_history_length: preferences.undo_history_length,
+				};
+				self.data_merge.process_message(message, responses, data);
+			}
 			PortfolioMessage::Document(message) => {
 				if let Some(document_id) = self.active_document_id {
 					if let Some(document) = self.documents.get_mut(&document_id) {
@@ -224,49 +357,18 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					return;
 				};
 
-				let mut copy_val = |buffer: &mut Vec<CopyBufferEntry>| {
-					let mut ordered_last_elements = active_document.network_interface.shallowest_unique_layers(&[]).collect::<Vec<_>>();
-
-					ordered_last_elements.sort_by_key(|layer| {
-						let Some(parent) = layer.parent(active_document.metadata()) else { return usize::MAX };
-						DocumentMessageHandler::get_calculated_insert_index(active_document.metadata(), &SelectedNodes(vec![layer.to_node()]), parent)
-					});
-
-					for layer in ordered_last_elements.into_iter() {
-						let layer_node_id = layer.to_node();
-
-						let mut copy_ids = HashMap::new();
-						copy_ids.insert(layer_node_id, NodeId(0));
-
-						active_document
-							.network_interface
-							.upstream_flow_back_from_nodes(vec![layer_node_id], &[], network_interface::FlowType::LayerChildrenUpstreamFlow)
-							.enumerate()
-							.for_each(|(index, node_id)| {
-								copy_ids.insert(node_id, NodeId((index + 1) as u64));
-							});
-
-						buffer.push(CopyBufferEntry {
-							nodes: active_document.network_interface.copy_nodes(&copy_ids, &[]).collect(),
-							selected: active_document.network_interface.selected_nodes().selected_layers_contains(layer, active_document.metadata()),
-							visible: active_document.network_interface.selected_nodes().layer_visible(layer, &active_document.network_interface),
-							locked: active_document.network_interface.selected_nodes().layer_locked(layer, &active_document.network_interface),
-							collapsed: false,
-						});
-					}
-				};
-
 				if clipboard == Clipboard::Device {
-					let mut buffer = Vec::new();
-					copy_val(&mut buffer);
+					let buffer = active_document.copy_selected_layers();
 					let mut copy_text = String::from("graphite/layer: ");
 					copy_text += &serde_json::to_string(&buffer).expect("Could not serialize paste");
 
-					responses.add(FrontendMessage::TriggerTextCopy { copy_text });
+					// The serialization is copied right away, then replaced by the richer formats once the layers are rendered for other applications
+					responses.add(FrontendMessage::TriggerTextCopy { copy_text: copy_text.clone() });
+					if let Err(error) = self.executor.submit_clipboard_render(active_document, copy_text) {
+						warn!("The copied layers couldn't be rendered for other applications: {error}");
+					}
 				} else {
-					let copy_buffer = &mut self.copy_buffer;
-					copy_buffer[clipboard as usize].clear();
-					copy_val(&mut copy_buffer[clipboard as usize]);
+					self.copy_buffer[clipboard as usize] = active_document.copy_selected_layers();
 				}
 			}
 			PortfolioMessage::Cut { clipboard } => {
@@ -275,8 +377,12 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 			}
 			PortfolioMessage::DeleteDocument { document_id } => {
 				let document_index = self.document_index(document_id);
-				self.documents.remove(&document_id);
+				if let Some(mut document) = self.documents.remove(&document_id) {
+					document.stop_editing_images_externally(document_id, responses);
+				}
 				self.document_ids.remove(document_index);
+				responses.add(SplitViewMessage::DocumentClosed { document_id });
+				responses.add(CompareMessage::DocumentClosed { document_id });
 
 				if self.document_ids.is_empty() {
 					self.active_document_id = None;
@@ -294,7 +400,9 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 			}
 			PortfolioMessage::DestroyAllDocuments => {
 				// Empty the list of internal document data
-				self.documents.clear();
+				for (document_id, mut document) in self.documents.drain() {
+					document.stop_editing_images_externally(document_id, responses);
+				}
 				self.document_ids.clear();
 				self.active_document_id = None;
 				responses.add(MenuBarMessage::SendLayout);
@@ -307,17 +415,21 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 			} => {
 				let font = Font::new(font_family, font_style);
 
+				self.evicted_fonts.remove(&font);
 				self.persistent_data.font_cache.insert(font, preview_url, data);
+
+				// Make room for the new font by unloading those which no open document uses anymore
+				let fonts_in_use = self.documents.values().flat_map(DocumentMessageHandler::fonts_in_use).collect();
+				self.evicted_fonts.extend(self.persistent_data.font_cache.evict_unused_in_load_order(&fonts_in_use));
+
 				self.executor.update_font_cache(self.persistent_data.font_cache.clone());
 				for document_id in self.document_ids.iter() {
 					let inspect_node = self.inspect_node_id();
-					let _ = self.executor.submit_node_graph_evaluation(
-						self.documents.get_mut(document_id).expect("Tried to render non-existent document"),
-						ipp.viewport_bounds.size().as_uvec2(),
-						timing_information,
-						inspect_node,
-						true,
-					);
+					let document = self.documents.get_mut(document_id).expect("Tried to render non-existent document");
+					document.update_text_frames(&self.persistent_data.font_cache, true);
+					let _ = self
+						.executor
+						.submit_node_graph_evaluation(document, ipp.viewport_bounds.size().as_uvec2(), timing_information, inspect_node, true, false, responses);
 				}
 
 				if self.active_document_mut().is_some() {
@@ -735,46 +847,46 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 						}
 					}
 
-					// Upgrade Text node to include line height and character spacing, which were previously hardcoded to 1, from https://github.com/GraphiteEditor/Graphite/pull/2016
-					if reference == "Text" && inputs_count != 8 {
+					// Upgrade the Text node with the inputs added to it over time, whose defaults match how the node behaved before each was added:
+					// - 4: Line height, previously hardcoded to 1 (from https://github.com/GraphiteEditor/Graphite/pull/2016)
+					// - 5: Character spacing, previously hardcoded to 1 (from the same PR)
+					// - 6: Max width
+					// - 7: Max height
+					// - 8: Horizontal alignment of its lines, previously always left
+					// - 9: Vertical alignment of its lines, previously always top
+					// - 10: Spans styling ranges of its characters, which previously all had the same style
+					// - 11: Path for it to follow
+					// - 12: Start offset along the path
+					// - 13: Text box it's linked from, whose overflowing text continues into it
+					// - 14: Direction of its paragraphs, previously always left to right
+					// - 15: Writing mode, previously always horizontal
+					// - 16: Orientation of its glyphs in vertical text
+					// - 17: OpenType features it's shaped with
+					// - 18: Values of the axes of its variable font, previously always their defaults
+					// - 19: Baseline shift
+					// - 20: Whether it's superscript or subscript
+					// - 21: How its lines are wrapped, previously always between words
+					// - 22: Spacing before its paragraphs
+					// - 23: Spacing after its paragraphs
+					// - 24: Whether it's underlined
+					// - 25: Whether it's struck through
+					// - 26: Tab stops, which previously didn't move the text after tabs along its line
+					// - 27: Whether it's hyphenated
+					// - 28: Language deciding where it's hyphenated
+					// - 29: Case it's shown in
+					// - 30: Left indent of its lines
+					// - 31: Right indent of its lines
+					// - 32: Indent of the first line of its paragraphs
+					if reference == "Text" && inputs_count != 33 {
 						let node_definition = resolve_document_node_type(reference).unwrap();
 						let document_node = node_definition.default_node_template().document_node;
 						document.network_interface.replace_implementation(node_id, network_path, document_node.implementation.clone());
 
+						// The inputs were only ever added to the end, so the old ones keep their indices and those added since keep the defaults they're given here
 						let old_inputs = document.network_interface.replace_inputs(node_id, document_node.inputs.clone(), network_path);
-
-						document.network_interface.set_input(&InputConnector::node(*node_id, 0), old_inputs[0].clone(), network_path);
-						document.network_interface.set_input(&InputConnector::node(*node_id, 1), old_inputs[1].clone(), network_path);
-						document.network_interface.set_input(&InputConnector::node(*node_id, 2), old_inputs[2].clone(), network_path);
-						document.network_interface.set_input(&InputConnector::node(*node_id, 3), old_inputs[3].clone(), network_path);
-						document.network_interface.set_input(
-							&InputConnector::node(*node_id, 4),
-							if inputs_count == 6 {
-								old_inputs[4].clone()
-							} else {
-								NodeInput::value(TaggedValue::F64(TypesettingConfig::default().line_height_ratio), false)
-							},
-							network_path,
-						);
-						document.network_interface.set_input(
-							&InputConnector::node(*node_id, 5),
-							if inputs_count == 6 {
-								old_inputs[5].clone()
-							} else {
-								NodeInput::value(TaggedValue::F64(TypesettingConfig::default().character_spacing), false)
-							},
-							network_path,
-						);
-						document.network_interface.set_input(
-							&InputConnector::node(*node_id, 6),
-							NodeInput::value(TaggedValue::OptionalF64(TypesettingConfig::default().max_width), false),
-							network_path,
-						);
-						document.network_interface.set_input(
-							&InputConnector::node(*node_id, 7),
-							NodeInput::value(TaggedValue::OptionalF64(TypesettingConfig::default().max_height), false),
-							network_path,
-						);
+						for (i, input) in old_inputs.iter().enumerate() {
+							document.network_interface.set_input(&InputConnector::node(*node_id, i), input.clone(), network_path);
+						}
 					}
 
 					// Upgrade Sine, Cosine, and Tangent nodes to include a boolean input for whether the output should be in radians, which was previously the only option but is now not the default
@@ -1007,6 +1119,17 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					responses.add(PortfolioMessage::SelectDocument { document_id: prev_id });
 				}
 			}
+			PortfolioMessage::ReorderDocument { document_id, new_index } => {
+				let Some(old_index) = self.document_ids.iter().position(|&id| id == document_id) else { return };
+				self.document_ids.remove(old_index);
+				self.document_ids.insert(new_index.min(self.document_ids.len()), document_id);
+
+				responses.add(PortfolioMessage::UpdateOpenDocumentsList);
+				responses.add(FrontendMessage::TriggerIndexedDbWriteDocumentOrder);
+				if let Some(active_document_id) = self.active_document_id {
+					responses.add(FrontendMessage::UpdateActiveDocument { document_id: active_document_id });
+				}
+			}
 			PortfolioMessage::SetActivePanel { panel } => {
 				self.active_panel = panel;
 				responses.add(DocumentMessage::SetActivePanel { active_panel: self.active_panel });
@@ -1015,6 +1138,22 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 				self.device_pixel_ratio = Some(ratio);
 				responses.add(OverlaysMessage::Draw);
 			}
+			PortfolioMessage::SetExternallyEditedImage { document_id, layer, image } => {
+				// The layer may have been deleted, or its document closed, since it was opened in the other application
+				let Some(document) = self.documents.get(&document_id) else { return };
+				if !document.metadata().layer_exists(layer) || graph_modification_utils::get_image(layer, &document.network_interface).is_none() {
+					return;
+				}
+
+				if self.active_document_id != Some(document_id) {
+					responses.add(PortfolioMessage::SelectDocument { document_id });
+				}
+				responses.add(DocumentMessage::AddTransaction);
+				responses.add(GraphOperationMessage::ImageSet {
+					layer,
+					image_frame: ImageFrameTable::new(image),
+				});
+			}
 			PortfolioMessage::SelectDocument { document_id } => {
 				// Auto-save the document we are leaving
 				let mut node_graph_open = false;
@@ -1066,6 +1205,11 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 				scale_factor,
 				bounds,
 				transparent_background,
+				metadata,
+				tiff_compression,
+				text_export,
+				frame_sequence,
+				export_id,
 			} => {
 				let document = self.active_document_id.and_then(|id| self.documents.get_mut(&id)).expect("Tried to render non-existent document");
 				let export_config = ExportConfig {
@@ -1074,17 +1218,107 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					scale_factor,
 					bounds,
 					transparent_background,
+					metadata,
+					tiff_compression,
+					text_export,
+					export_id,
 					..Default::default()
 				};
-				let result = self.executor.submit_document_export(document, export_config);
+				let result = match frame_sequence {
+					Some(frame_sequence) => self.executor.submit_frame_sequence_export(document, export_config, &frame_sequence),
+					None => self.executor.submit_document_export(document, export_config),
+				};
 
 				if let Err(description) = result {
+					// Anything waiting for the file is told it isn't coming
+					if let Some(export_id) = export_id {
+						responses.add(FrontendMessage::TriggerExportFailed { export_id });
+					}
 					responses.add(DialogMessage::DisplayDialogError {
 						title: "Unable to export document".to_string(),
 						description,
 					});
 				}
 			}
+			PortfolioMessage::RunSaveActions { document_id } => {
+				let Some(document) = self.documents.get_mut(&document_id) else { return };
+
+				for action in preferences.save_actions.iter().filter(|action| action.enabled) {
+					match &action.kind {
+						SaveActionKind::Export {
+							file_type,
+							scale_factor,
+							each_artboard,
+							transparent_background,
+							folder,
+						} => {
+							let exports = if *each_artboard {
+								document
+									.metadata()
+									.all_layers()
+									.filter(|&layer| document.network_interface.is_artboard(&layer.to_node(), &[]))
+									.map(|layer| {
+										let name = document
+											.network_interface
+											.node_metadata(&layer.to_node(), &[])
+											.map(|node| node.persistent_metadata.display_name.clone())
+											.filter(|name| !name.is_empty())
+											.unwrap_or_else(|| "Artboard".to_string());
+										(format!("{} - {name}", document.name), ExportBounds::Artboard(layer))
+									})
+									.collect::<Vec<_>>()
+							} else {
+								vec![(document.name.clone(), ExportBounds::AllArtwork)]
+							};
+
+							// Each export is told apart by its ID, since the files of several exports can have the same name
+							let mut export_ids = Vec::new();
+							for (file_name, bounds) in exports {
+								let export_id = generate_uuid();
+								let export_config = ExportConfig {
+									file_name,
+									file_type: *file_type,
+									scale_factor: *scale_factor,
+									bounds,
+									transparent_background: *file_type != FileType::Jpg && *transparent_background,
+									export_id: Some(export_id),
+									..Default::default()
+								};
+								if let Err(description) = self.executor.submit_document_export(document, export_config) {
+									responses.add(DialogMessage::DisplayDialogError {
+										title: "Unable to run a save action's export".to_string(),
+										description,
+									});
+									break;
+								}
+								export_ids.push(export_id);
+							}
+
+							if !folder.is_empty() && !export_ids.is_empty() {
+								responses.add(FrontendMessage::TriggerSaveActionExport { folder: folder.clone(), export_ids });
+							}
+						}
+						SaveActionKind::Command { command } => {
+							if command.trim().is_empty() {
+								continue;
+							}
+							responses.add(FrontendMessage::TriggerSaveActionCommand {
+								command: command.clone(),
+								document_name: document.name.clone(),
+							});
+						}
+						SaveActionKind::Webhook { url } => {
+							if url.trim().is_empty() {
+								continue;
+							}
+							responses.add(FrontendMessage::TriggerSaveActionWebhook {
+								url: url.clone(),
+								body: serde_json::json!({ "document": document.name }).to_string(),
+							});
+						}
+					}
+				}
+			}
 			PortfolioMessage::SubmitActiveGraphRender => {
 				if let Some(document_id) = self.active_document_id {
 					responses.add(PortfolioMessage::SubmitGraphRender { document_id, ignore_hash: false });
@@ -1092,13 +1326,39 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 			}
 			PortfolioMessage::SubmitGraphRender { document_id, ignore_hash } => {
 				let inspect_node = self.inspect_node_id();
-				let result = self.executor.submit_node_graph_evaluation(
-					self.documents.get_mut(&document_id).expect("Tried to render non-existent document"),
-					ipp.viewport_bounds.size().as_uvec2(),
-					timing_information,
-					inspect_node,
-					ignore_hash,
-				);
+				let interacting = preferences.degrade_while_interacting && ipp.is_interacting();
+				let document = self.documents.get_mut(&document_id).expect("Tried to render non-existent document");
+
+				// Jump back to the start of the loop range once playback passes its end, which renders again at the looped time
+				let time = timing_information.animation_time.as_secs_f64();
+				if animation.is_playing() && self.active_document_id == Some(document_id) {
+					if let Some(looped_time) = document.timeline.looped_time(time) {
+						responses.add(AnimationMessage::SetAnimationTime(looped_time));
+						return;
+					}
+				}
+
+				if self.active_document_id == Some(document_id) {
+					// Playback follows the frame rate saved with the active document, which may have changed by switching documents or undoing
+					if document.timeline.frames_per_second != animation.fps() {
+						responses.add(AnimationMessage::SetFramesPerSecond(document.timeline.frames_per_second));
+					}
+					responses.add(TimelineMessage::SendLayout);
+				}
+
+				// Fonts unloaded while no document used them need loading again once one does, like after undoing the deletion of a text layer
+				if !self.evicted_fonts.is_empty() {
+					for font in document.fonts_in_use() {
+						if self.evicted_fonts.remove(&font) {
+							responses.add(FrontendMessage::TriggerFontLoad { font });
+						}
+					}
+				}
+
+				document.update_text_frames(&self.persistent_data.font_cache, false);
+				let result = self
+					.executor
+					.submit_node_graph_evaluation(document, ipp.viewport_bounds.size().as_uvec2(), timing_information, inspect_node, ignore_hash, interacting, responses);
 
 				if let Err(description) = result {
 					responses.add(DialogMessage::DisplayDialogError {
@@ -1107,6 +1367,12 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					});
 				}
 			}
+			PortfolioMessage::ToggleRenderStatistics => {
+				self.executor.render_statistics.visible = !self.executor.render_statistics.visible;
+
+				responses.add(OverlaysMessage::Draw);
+				responses.add(MenuBarMessage::SendLayout);
+			}
 			PortfolioMessage::ToggleRulers => {
 				if let Some(document) = self.active_document_mut() {
 					document.rulers_visible = !document.rulers_visible;
@@ -1119,6 +1385,9 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 				if let Some(document) = self.active_document() {
 					document.update_document_widgets(responses, animation.is_playing(), timing_information.animation_time);
 				}
+				responses.add(TimelineMessage::SendLayout);
+				// Playback may have been paused, which shows the onion skins again
+				responses.add(TimelineMessage::RenderOnionSkins);
 			}
 			PortfolioMessage::UpdateOpenDocumentsList => {
 				// Send the list of document tab names
@@ -1135,6 +1404,10 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					})
 					.collect::<Vec<_>>();
 				responses.add(FrontendMessage::UpdateOpenDocumentsList { open_documents });
+				responses.add(SplitViewMessage::SendLayout);
+				responses.add(TimelineMessage::SendLayout);
+				responses.add(CompareMessage::SendLayout);
+				responses.add(DataMergeMessage::SendLayout);
 			}
 			PortfolioMessage::UpdateVelloPreference => {
 				responses.add(NodeGraphMessage::RunDocumentGraph);
@@ -1153,12 +1426,14 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 			OpenDocument,
 			PasteIntoFolder,
 			PrevDocument,
+			ToggleRenderStatistics,
 			ToggleRulers,
 		);
 
 		// Extend with actions that require an active document
 		if let Some(document) = self.active_document() {
 			common.extend(document.actions());
+			common.extend(self.timeline.actions());
 
 			// Extend with actions that must have a selected layer
 			if document.network_interface.selected_nodes().selected_layers(document.metadata()).next().is_some() {
@@ -1190,6 +1465,10 @@ impl PortfolioMessageHandler {
 		self.documents.get_mut(&document_id)
 	}
 
+	pub fn menu_bar_layout(&self) -> Layout {
+		self.menu_bar_message_handler.layout()
+	}
+
 	pub fn active_document(&self) -> Option<&DocumentMessageHandler> {
 		self.active_document_id.and_then(|id| self.documents.get(&id))
 	}
@@ -1260,11 +1539,13 @@ impl PortfolioMessageHandler {
 		self.document_ids.iter().position(|id| id == &document_id).expect("Active document is missing from document ids")
 	}
 
-	pub fn poll_node_graph_evaluation(&mut self, responses: &mut VecDeque<Message>) -> Result<(), String> {
+	pub fn poll_node_graph_evaluation(&mut self, interacting: bool, responses: &mut VecDeque<Message>) -> Result<(), String> {
 		let Some(active_document) = self.active_document_id.and_then(|id| self.documents.get_mut(&id)) else {
 			return Err("No active document".to_string());
 		};
 
+		self.executor.refine_viewport_render_when_idle(interacting);
+
 		let result = self.executor.poll_node_graph_evaluation(active_document, responses);
 		if result.is_err() {
 			let error = r#"
@@ -1300,3 +1581,46 @@ impl PortfolioMessageHandler {
 		selected_nodes.first().copied()
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use crate::messages::frontend::utility_types::FileType;
+	use crate::messages::preferences::{SaveAction, SaveActionKind};
+	use crate::test_utils::test_prelude::*;
+
+	#[tokio::test]
+	async fn save_action_exports_of_the_same_name_have_their_own_ids() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+
+		// Both artboards are unnamed, so their files are named alike
+		editor.drag_tool(ToolType::Artboard, 0., 0., 100., 100., ModifierKeys::empty()).await;
+		editor.drag_tool(ToolType::Artboard, 200., 0., 300., 100., ModifierKeys::empty()).await;
+
+		let action = SaveAction {
+			enabled: true,
+			kind: SaveActionKind::Export {
+				file_type: FileType::Svg,
+				scale_factor: 1.,
+				each_artboard: true,
+				transparent_background: false,
+				folder: "Exports".into(),
+			},
+		};
+		editor.handle_message(PreferencesMessage::AddSaveAction { action }).await;
+
+		let portfolio = &editor.editor.dispatcher.message_handlers.portfolio_message_handler;
+		let document_id = portfolio.active_document_id.unwrap();
+		let messages = editor.editor.handle_message(PortfolioMessage::RunSaveActions { document_id });
+
+		let export_ids = messages
+			.iter()
+			.find_map(|message| match message {
+				FrontendMessage::TriggerSaveActionExport { folder, export_ids } if folder == "Exports" => Some(export_ids.clone()),
+				_ => None,
+			})
+			.expect("The exports should be written to the folder");
+		assert_eq!(export_ids.len(), 2);
+		assert_ne!(export_ids[0], export_ids[1]);
+	}
+}
@@ -0,0 +1,7 @@
+mod split_view_message;
+mod split_view_message_handler;
+
+#[doc(inline)]
+pub use split_view_message::*;
+#[doc(inline)]
+pub use split_view_message_handler::*;
@@ -0,0 +1,23 @@
+use crate::messages::prelude::*;
+use glam::{DAffine2, DVec2};
+
+/// The split view shows a document beside the main viewport, with its own zoom and panning.
+#[impl_message(Message, PortfolioMessage, SplitView)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum SplitViewMessage {
+	ToggleOpen,
+
+	DocumentClosed { document_id: DocumentId },
+	SetDocument { document_id: Option<DocumentId> },
+	UpdateBounds { size: DVec2 },
+
+	Pan { delta: DVec2 },
+	ZoomAtPoint { zoom_factor: f64, center: DVec2 },
+	ZoomDecrease,
+	ZoomIncrease,
+	ZoomToFit,
+
+	Render,
+	Rendered { svg: String, document_to_viewport: DAffine2 },
+	SendLayout,
+}
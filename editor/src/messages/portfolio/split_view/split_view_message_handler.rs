@@ -0,0 +1,272 @@
+use crate::consts::{VIEWPORT_ZOOM_LEVELS, VIEWPORT_ZOOM_SCALE_MAX, VIEWPORT_ZOOM_SCALE_MIN, VIEWPORT_ZOOM_TO_FIT_PADDING_SCALE_FACTOR};
+use crate::messages::animation::TimingInformation;
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::*;
+use crate::node_graph_executor::NodeGraphExecutor;
+use glam::{DAffine2, DVec2, UVec2};
+use graphene_std::renderer::format_transform_matrix;
+use graphene_std::vector::style::ViewMode;
+
+pub struct SplitViewMessageData<'a> {
+	pub documents: &'a mut HashMap<DocumentId, DocumentMessageHandler>,
+	pub document_ids: &'a VecDeque<DocumentId>,
+	pub active_document_id: Option<DocumentId>,
+	pub executor: &'a mut NodeGraphExecutor,
+	pub timing_information: TimingInformation,
+}
+
+/// Everything a render of the split view depends on, so it's only evaluated again once one of these changes.
+#[derive(Debug, Clone, PartialEq)]
+struct SplitViewRenderKey {
+	document_id: DocumentId,
+	node_graph_hash: u64,
+	document_to_viewport: DAffine2,
+	resolution: UVec2,
+	view_mode: ViewMode,
+}
+
+/// The split view shows a document beside the main viewport, with its own zoom and panning.
+/// It can show a second document, or the active document a second time so it can be seen at two different zoom levels at once.
+#[derive(Debug, Clone, Default)]
+pub struct SplitViewMessageHandler {
+	/// Sets whether or not the split view is drawn.
+	pub split_view_open: bool,
+	/// The document shown in the split view, or `None` to follow whichever document is active.
+	document_id: Option<DocumentId>,
+	document_to_viewport: DAffine2,
+	viewport_size: DVec2,
+	/// Whether the view has been zoomed to fit the document since it was opened or switched to another document.
+	fitted: bool,
+	render_key: Option<SplitViewRenderKey>,
+	/// The most recent artwork and the transform it was rendered with, which is repositioned while a render for the latest transform is in progress.
+	render: Option<(String, DAffine2)>,
+}
+
+impl MessageHandler<SplitViewMessage, SplitViewMessageData<'_>> for SplitViewMessageHandler {
+	fn process_message(&mut self, message: SplitViewMessage, responses: &mut VecDeque<Message>, data: SplitViewMessageData) {
+		let SplitViewMessageData {
+			documents,
+			document_ids,
+			active_document_id,
+			executor,
+			timing_information,
+		} = data;
+
+		match message {
+			SplitViewMessage::ToggleOpen => {
+				self.split_view_open = !self.split_view_open;
+				self.fitted = false;
+				self.render_key = None;
+				self.render = None;
+
+				responses.add(FrontendMessage::UpdateSplitViewState { open: self.split_view_open });
+				responses.add(FrontendMessage::UpdateSplitViewArtwork { svg: String::new() });
+				// Update checked UI state for open
+				responses.add(MenuBarMessage::SendLayout);
+				responses.add(SplitViewMessage::SendLayout);
+			}
+
+			SplitViewMessage::DocumentClosed { document_id } => {
+				if self.document_id == Some(document_id) {
+					responses.add(SplitViewMessage::SetDocument { document_id: None });
+				}
+			}
+			SplitViewMessage::SetDocument { document_id } => {
+				self.document_id = document_id;
+				self.fitted = false;
+				self.render = None;
+
+				responses.add(FrontendMessage::UpdateSplitViewArtwork { svg: String::new() });
+				responses.add(SplitViewMessage::SendLayout);
+				responses.add(SplitViewMessage::Render);
+			}
+			SplitViewMessage::UpdateBounds { size } => {
+				// Keep the center of the view in place as its size changes
+				self.document_to_viewport = DAffine2::from_translation((size - self.viewport_size) / 2.) * self.document_to_viewport;
+				self.viewport_size = size;
+
+				self.send_artwork(responses);
+				responses.add(SplitViewMessage::Render);
+			}
+
+			SplitViewMessage::Pan { delta } => {
+				self.document_to_viewport = DAffine2::from_translation(delta) * self.document_to_viewport;
+
+				self.send_artwork(responses);
+				responses.add(SplitViewMessage::Render);
+			}
+			SplitViewMessage::ZoomAtPoint { zoom_factor, center } => {
+				self.zoom_at_point(zoom_factor, center);
+
+				self.send_artwork(responses);
+				responses.add(SplitViewMessage::SendLayout);
+				responses.add(SplitViewMessage::Render);
+			}
+			SplitViewMessage::ZoomDecrease => {
+				let zoom = self.zoom();
+				let new_zoom = *VIEWPORT_ZOOM_LEVELS.iter().rev().find(|level| **level < zoom).unwrap_or(&zoom);
+				responses.add(SplitViewMessage::ZoomAtPoint {
+					zoom_factor: new_zoom / zoom,
+					center: self.viewport_size / 2.,
+				});
+			}
+			SplitViewMessage::ZoomIncrease => {
+				let zoom = self.zoom();
+				let new_zoom = *VIEWPORT_ZOOM_LEVELS.iter().find(|level| **level > zoom).unwrap_or(&zoom);
+				responses.add(SplitViewMessage::ZoomAtPoint {
+					zoom_factor: new_zoom / zoom,
+					center: self.viewport_size / 2.,
+				});
+			}
+			SplitViewMessage::ZoomToFit => {
+				self.fitted = false;
+				responses.add(SplitViewMessage::Render);
+			}
+
+			SplitViewMessage::Render => {
+				if !self.split_view_open {
+					return;
+				}
+				let Some(document_id) = self.displayed_document_id(document_ids, active_document_id) else {
+					responses.add(FrontendMessage::UpdateSplitViewArtwork { svg: String::new() });
+					return;
+				};
+				let Some(document) = documents.get_mut(&document_id) else { return };
+
+				if !self.fitted && self.viewport_size.cmpgt(DVec2::ZERO).all() {
+					self.fitted = true;
+					if let Some(bounds) = document.network_interface.document_bounds_document_space(true) {
+						self.zoom_to_fit(bounds);
+					}
+					self.send_artwork(responses);
+					responses.add(SplitViewMessage::SendLayout);
+				}
+
+				let resolution = self.viewport_size.as_uvec2();
+				if resolution.cmpeq(UVec2::ZERO).any() {
+					return;
+				}
+
+				let render_key = SplitViewRenderKey {
+					document_id,
+					node_graph_hash: document.network_interface.document_network().current_hash(),
+					document_to_viewport: self.document_to_viewport,
+					resolution,
+					view_mode: document.view_mode,
+				};
+				if self.render_key.as_ref() == Some(&render_key) {
+					return;
+				}
+
+				// Another document's network replaces the active one in the runtime while it renders, so that waits for the viewport's render to finish.
+				// Once the viewport's render arrives, the split view is asked to render again.
+				let other_document = Some(document_id) != active_document_id;
+				if other_document && executor.viewport_render_pending() {
+					return;
+				}
+
+				match executor.submit_split_view_render(document, self.document_to_viewport, resolution, timing_information, other_document) {
+					Ok(()) => self.render_key = Some(render_key),
+					Err(error) => warn!("Failed to render the split view: {error}"),
+				}
+			}
+			SplitViewMessage::Rendered { svg, document_to_viewport } => {
+				self.render = Some((svg, document_to_viewport));
+				self.send_artwork(responses);
+			}
+			SplitViewMessage::SendLayout => self.send_layout(documents, document_ids, responses),
+		}
+	}
+
+	fn actions(&self) -> ActionList {
+		actions!(SplitViewMessage;)
+	}
+}
+
+impl SplitViewMessageHandler {
+	/// The document shown in the split view, falling back to the active document if none has been chosen.
+	fn displayed_document_id(&self, document_ids: &VecDeque<DocumentId>, active_document_id: Option<DocumentId>) -> Option<DocumentId> {
+		self.document_id.filter(|document_id| document_ids.contains(document_id)).or(active_document_id)
+	}
+
+	fn zoom(&self) -> f64 {
+		self.document_to_viewport.matrix2.x_axis.length()
+	}
+
+	fn zoom_at_point(&mut self, zoom_factor: f64, center: DVec2) {
+		let zoom = self.zoom();
+		let zoom_factor = (zoom * zoom_factor).clamp(VIEWPORT_ZOOM_SCALE_MIN, VIEWPORT_ZOOM_SCALE_MAX) / zoom;
+		self.document_to_viewport = DAffine2::from_translation(center) * DAffine2::from_scale(DVec2::splat(zoom_factor)) * DAffine2::from_translation(-center) * self.document_to_viewport;
+	}
+
+	fn zoom_to_fit(&mut self, [min, max]: [DVec2; 2]) {
+		let size = (max - min).max(DVec2::ONE);
+		let zoom = (self.viewport_size / size).min_element() * VIEWPORT_ZOOM_TO_FIT_PADDING_SCALE_FACTOR;
+		let zoom = zoom.clamp(VIEWPORT_ZOOM_SCALE_MIN, VIEWPORT_ZOOM_SCALE_MAX);
+		self.document_to_viewport = DAffine2::from_translation(self.viewport_size / 2.) * DAffine2::from_scale(DVec2::splat(zoom)) * DAffine2::from_translation(-(min + max) / 2.);
+	}
+
+	/// Sends the latest artwork, transformed from where it was rendered to where the document currently sits in the view.
+	fn send_artwork(&self, responses: &mut VecDeque<Message>) {
+		let Some((artwork, rendered_document_to_viewport)) = &self.render else { return };
+
+		let transform = format_transform_matrix(self.document_to_viewport * rendered_document_to_viewport.inverse());
+		// The artwork is only as large as the view, so it mustn't be clipped before it's transformed
+		let artwork = artwork.replacen("<svg", r#"<svg overflow="visible""#, 1);
+		let svg = if transform.is_empty() {
+			format!("<svg>{artwork}</svg>")
+		} else {
+			format!(r#"<svg><g transform="{transform}">{artwork}</g></svg>"#)
+		};
+		responses.add(FrontendMessage::UpdateSplitViewArtwork { svg });
+	}
+
+	fn send_layout(&self, documents: &HashMap<DocumentId, DocumentMessageHandler>, document_ids: &VecDeque<DocumentId>, responses: &mut VecDeque<Message>) {
+		if !self.split_view_open {
+			return;
+		}
+
+		let mut entries = vec![
+			MenuListEntry::new("ActiveDocument")
+				.label("Active Document")
+				.on_update(|_| SplitViewMessage::SetDocument { document_id: None }.into()),
+		];
+		entries.extend(document_ids.iter().filter_map(|&document_id| {
+			let document = documents.get(&document_id)?;
+			Some(
+				MenuListEntry::new(document_id.0.to_string())
+					.label(document.name.clone())
+					.on_update(move |_| SplitViewMessage::SetDocument { document_id: Some(document_id) }.into()),
+			)
+		}));
+		let selected_index = match self.document_id {
+			Some(document_id) => document_ids
+				.iter()
+				.filter(|id| documents.contains_key(id))
+				.position(|&id| id == document_id)
+				.map(|index| index as u32 + 1),
+			None => Some(0),
+		};
+
+		let widgets = vec![
+			DropdownInput::new(vec![entries])
+				.selected_index(selected_index)
+				.tooltip("Document shown in the split view")
+				.widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			IconButton::new("ZoomIn", 24).tooltip("Zoom In").on_update(|_| SplitViewMessage::ZoomIncrease.into()).widget_holder(),
+			IconButton::new("ZoomOut", 24).tooltip("Zoom Out").on_update(|_| SplitViewMessage::ZoomDecrease.into()).widget_holder(),
+			IconButton::new("FrameAll", 24)
+				.tooltip("Zoom to Fit the Document")
+				.on_update(|_| SplitViewMessage::ZoomToFit.into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			TextLabel::new(format!("{}%", (self.zoom() * 100.).round())).widget_holder(),
+		];
+
+		responses.add(LayoutMessage::SendLayout {
+			layout: Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }])),
+			layout_target: LayoutTarget::SplitViewControlBar,
+		});
+	}
+}
@@ -0,0 +1,163 @@
+use crate::messages::frontend::utility_types::{ExportMetadata, TiffCompression};
+use std::io::{Cursor, Read, Seek, Write};
+use tiff::ColorType;
+use tiff::decoder::{Decoder, DecodingResult};
+use tiff::encoder::compression::{Compression, Deflate, DeflateLevel, Lzw, Uncompressed};
+use tiff::encoder::{Rational, TiffEncoder, colortype};
+use tiff::tags::{ResolutionUnit, Tag};
+
+/// The bit of the `NewSubfileType` tag which marks a directory as a reduced-resolution version (such as a thumbnail) of another image in the file.
+const REDUCED_RESOLUTION_SUBFILE: u32 = 1;
+
+/// Decodes a TIFF file into straight (not premultiplied) 8-bit sRGB RGBA pixel data, returned along with its width and height.
+///
+/// Files containing multiple full-resolution images (pages or layers) are flattened by compositing each subsequent image over the first, in file order.
+/// Reduced-resolution subfiles like thumbnails are skipped.
+pub fn decode_tiff(data: &[u8]) -> Result<(Vec<u8>, u32, u32), String> {
+	let mut decoder = Decoder::new(Cursor::new(data)).map_err(|err| format!("Invalid TIFF file: {err}"))?;
+
+	let mut flattened: Option<(Vec<u8>, u32, u32)> = None;
+	loop {
+		let subfile_type = decoder.find_tag(Tag::NewSubfileType).ok().flatten().and_then(|value| value.into_u32().ok()).unwrap_or_default();
+
+		if subfile_type & REDUCED_RESOLUTION_SUBFILE == 0 {
+			let (width, height) = decoder.dimensions().map_err(|err| err.to_string())?;
+			let layer = read_rgba8(&mut decoder)?;
+
+			match &mut flattened {
+				None => flattened = Some((layer, width, height)),
+				Some((base, base_width, base_height)) => composite_over(base, *base_width, *base_height, &layer, width, height),
+			}
+		}
+
+		if !decoder.more_images() {
+			break;
+		}
+		decoder.next_image().map_err(|err| err.to_string())?;
+	}
+
+	flattened.ok_or_else(|| "The TIFF file contains no images".to_string())
+}
+
+/// Reads the current image of the decoder and converts it from its stored color type to 8-bit RGBA.
+fn read_rgba8<R: Read + Seek>(decoder: &mut Decoder<R>) -> Result<Vec<u8>, String> {
+	let color_type = decoder.colortype().map_err(|err| err.to_string())?;
+	let samples: Vec<u8> = match decoder.read_image().map_err(|err| err.to_string())? {
+		DecodingResult::U8(samples) => samples,
+		DecodingResult::U16(samples) => samples.into_iter().map(|sample| (sample >> 8) as u8).collect(),
+		DecodingResult::F32(samples) => samples.into_iter().map(|sample| (sample.clamp(0., 1.) * 255.).round() as u8).collect(),
+		_ => return Err(format!("Unsupported TIFF sample format for color type {color_type:?}")),
+	};
+
+	let rgba = match color_type {
+		ColorType::Gray(_) => samples.into_iter().flat_map(|gray| [gray, gray, gray, 255]).collect(),
+		ColorType::GrayA(_) => samples.chunks_exact(2).flat_map(|pixel| [pixel[0], pixel[0], pixel[0], pixel[1]]).collect(),
+		ColorType::RGB(_) => samples.chunks_exact(3).flat_map(|pixel| [pixel[0], pixel[1], pixel[2], 255]).collect(),
+		ColorType::RGBA(_) => samples,
+		ColorType::CMYK(_) => samples
+			.chunks_exact(4)
+			.flat_map(|pixel| {
+				let key = 255 - pixel[3] as u16;
+				let channel = |value: u8| ((255 - value as u16) * key / 255) as u8;
+				[channel(pixel[0]), channel(pixel[1]), channel(pixel[2]), 255]
+			})
+			.collect(),
+		_ => return Err(format!("Unsupported TIFF color type {color_type:?}")),
+	};
+
+	Ok(rgba)
+}
+
+/// Alpha-composites the `layer` image over the `base` image, aligned at their top left corners and clipped to the bounds of the base.
+fn composite_over(base: &mut [u8], base_width: u32, base_height: u32, layer: &[u8], layer_width: u32, layer_height: u32) {
+	for y in 0..base_height.min(layer_height) {
+		for x in 0..base_width.min(layer_width) {
+			let base_index = (y as usize * base_width as usize + x as usize) * 4;
+			let layer_index = (y as usize * layer_width as usize + x as usize) * 4;
+
+			let source_alpha = layer[layer_index + 3] as f32 / 255.;
+			let destination_alpha = base[base_index + 3] as f32 / 255.;
+			let alpha = source_alpha + destination_alpha * (1. - source_alpha);
+			if alpha <= 0. {
+				continue;
+			}
+
+			for channel in 0..3 {
+				let source = layer[layer_index + channel] as f32;
+				let destination = base[base_index + channel] as f32;
+				base[base_index + channel] = ((source * source_alpha + destination * destination_alpha * (1. - source_alpha)) / alpha).round() as u8;
+			}
+			base[base_index + 3] = (alpha * 255.).round() as u8;
+		}
+	}
+}
+
+/// Encodes straight 8-bit sRGB RGBA pixel data as a TIFF file with the chosen compression, embedding the given metadata unless it's set to be stripped.
+pub fn encode_tiff(rgba: &[u8], width: u32, height: u32, compression: TiffCompression, metadata: &ExportMetadata) -> Result<Vec<u8>, String> {
+	let expected_length = (width as u64).checked_mul(height as u64).and_then(|pixels| pixels.checked_mul(4));
+	if expected_length != Some(rgba.len() as u64) {
+		return Err("The pixel data does not match the image dimensions".to_string());
+	}
+
+	let mut file = Cursor::new(Vec::new());
+	match compression {
+		TiffCompression::None => write_image(&mut file, rgba, width, height, Uncompressed, metadata),
+		TiffCompression::Lzw => write_image(&mut file, rgba, width, height, Lzw, metadata),
+		TiffCompression::Deflate => write_image(&mut file, rgba, width, height, Deflate::with_level(DeflateLevel::Balanced), metadata),
+	}
+	.map_err(|err| format!("Failed to encode TIFF file: {err}"))?;
+
+	Ok(file.into_inner())
+}
+
+fn write_image<W: Write + Seek, C: Compression>(file: &mut W, rgba: &[u8], width: u32, height: u32, compression: C, metadata: &ExportMetadata) -> tiff::TiffResult<()> {
+	let mut encoder = TiffEncoder::new(file)?;
+	let mut image = encoder.new_image_with_compression::<colortype::RGBA8, C>(width, height, compression)?;
+
+	if !metadata.strip_metadata {
+		let dpi = metadata.dpi.round().clamp(1., u32::MAX as f64) as u32;
+		image.resolution(ResolutionUnit::Inch, Rational { n: dpi, d: 1 });
+
+		let text_tags = [(Tag::ImageDescription, &metadata.title), (Tag::Artist, &metadata.author), (Tag::Copyright, &metadata.copyright)];
+		for (tag, text) in text_tags {
+			if !text.is_empty() {
+				image.encoder().write_tag(tag, text.as_str())?;
+			}
+		}
+		image.encoder().write_tag(Tag::Software, "Graphite")?;
+	}
+
+	image.write_data(rgba)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn round_trip_with_each_compression() {
+		let (width, height) = (3, 2);
+		let rgba = (0..width * height * 4).map(|value| (value * 10) as u8).collect::<Vec<_>>();
+
+		for compression in [TiffCompression::None, TiffCompression::Lzw, TiffCompression::Deflate] {
+			let file = encode_tiff(&rgba, width, height, compression, &ExportMetadata::default()).unwrap();
+			let decoded = decode_tiff(&file).unwrap();
+			assert_eq!(decoded, (rgba.clone(), width, height), "Round trip failed for {compression:?}");
+		}
+	}
+
+	#[test]
+	fn dimensions_too_large_for_u32_are_rejected() {
+		// 65536 × 16384 × 4 bytes wraps around to 0 when multiplied in 32 bits
+		let result = encode_tiff(&[], 65536, 16384, TiffCompression::None, &ExportMetadata::default());
+		assert!(result.is_err());
+	}
+
+	#[test]
+	fn composite_layer_over_base() {
+		let mut base = vec![255, 0, 0, 255, 255, 0, 0, 255];
+		let layer = [0, 0, 255, 255, 0, 0, 255, 0];
+		composite_over(&mut base, 2, 1, &layer, 2, 1);
+		assert_eq!(base, vec![0, 0, 255, 255, 255, 0, 0, 255]);
+	}
+}
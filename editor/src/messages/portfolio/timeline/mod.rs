@@ -0,0 +1,7 @@
+mod timeline_message;
+mod timeline_message_handler;
+
+#[doc(inline)]
+pub use timeline_message::*;
+#[doc(inline)]
+pub use timeline_message_handler::*;
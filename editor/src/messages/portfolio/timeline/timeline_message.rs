@@ -0,0 +1,47 @@
+use crate::messages::prelude::*;
+use glam::DVec2;
+use graph_craft::document::NodeId;
+use graphene_core::animation::Easing;
+
+/// The timeline shows the active document's keyframes along the animation's time, where node inputs can be keyframed.
+#[impl_message(Message, PortfolioMessage, Timeline)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum TimelineMessage {
+	ToggleOpen,
+	SendLayout,
+
+	SetDuration { duration: f64 },
+	SetLoopIn { time: Option<f64> },
+	SetLoopOut { time: Option<f64> },
+	ClearLoopRange,
+	SetFramesPerSecond { fps: f64 },
+	SelectInput { node_id: NodeId, input_index: usize },
+
+	AddKeyframe,
+	RemoveKeyframe,
+	RemoveTrack { index: usize },
+
+	SetCurveEditorOpen { open: bool },
+	SelectKeyframe { track_index: usize, keyframe_index: usize },
+	SetKeyframeEasing { track_index: usize, keyframe_index: usize, easing: Easing },
+	MoveKeyframeEasingHandles { track_index: usize, keyframe_index: usize, handles: [DVec2; 2] },
+
+	ImportAudio,
+	SetAudio { name: String, mime: String, data: Vec<u8> },
+	RemoveAudio,
+	SetAudioOffset { offset: f64 },
+	ToggleAudioMute,
+
+	ToggleFrameLayers,
+	AddFrameLayer,
+	PreviousFrameLayer,
+	NextFrameLayer,
+
+	ToggleOnionSkin,
+	SetOnionSkinPrevious { count: u32 },
+	SetOnionSkinNext { count: u32 },
+	SetOnionSkinSpacing { spacing: f64 },
+	SetOnionSkinOpacity { opacity: f64 },
+	RenderOnionSkins,
+	OnionSkinRendered { svg: String },
+}
@@ -0,0 +1,1160 @@
+use crate::application::generate_uuid;
+use crate::messages::animation::TimingInformation;
+use crate::messages::frontend::utility_types::{FrontendAudioTrack, FrontendKeyframeTrack};
+use crate::messages::input_mapper::utility_types::macros::action_keys;
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
+use crate::messages::portfolio::document::utility_types::network_interface::{FlowType, InputConnector};
+use crate::messages::portfolio::document::utility_types::timeline::{AudioTrack, KeyframeTrack, is_keyframeable, value_components};
+use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::graph_modification_utils;
+use crate::node_graph_executor::NodeGraphExecutor;
+use glam::{DAffine2, UVec2};
+use graph_craft::document::NodeId;
+use graph_craft::document::value::TaggedValue;
+use graphene_core::animation::{Easing, sequence_frame_index};
+use graphene_std::renderer::format_transform_matrix;
+use graphene_std::vector::style::ViewMode;
+use std::time::Duration;
+
+pub struct TimelineMessageData<'a> {
+	pub document: Option<&'a mut DocumentMessageHandler>,
+	pub executor: &'a mut NodeGraphExecutor,
+	pub viewport_resolution: UVec2,
+	pub timing_information: TimingInformation,
+	pub animation_is_playing: bool,
+	pub frames_per_second: f64,
+	pub undo_history_length: usize,
+}
+
+/// The settings for ghosting the artwork at the times around the current one beneath it, so its motion can be seen while animating it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct OnionSkin {
+	enabled: bool,
+	/// How many frames before the current time are ghosted.
+	previous: u32,
+	/// How many frames after the current time are ghosted.
+	next: u32,
+	/// The time in seconds between each ghosted frame.
+	spacing: f64,
+	/// The opacity percentage of the frames nearest to the current time, which the frames further away fade out from.
+	opacity: f64,
+}
+
+impl Default for OnionSkin {
+	fn default() -> Self {
+		Self {
+			enabled: false,
+			previous: 2,
+			next: 2,
+			spacing: 0.1,
+			opacity: 30.,
+		}
+	}
+}
+
+impl OnionSkin {
+	/// The frames to ghost around the given time, furthest first so the nearest ones are drawn on top.
+	/// Frames outside the animation's duration are left out.
+	fn frames(&self, time: f64, duration: f64) -> VecDeque<OnionSkinFrame> {
+		let frame = |distance: u32, count: u32, direction: f64| OnionSkinFrame {
+			time: time + direction * distance as f64 * self.spacing,
+			opacity: self.opacity / 100. * (count - distance + 1) as f64 / count as f64,
+		};
+		let previous = (1..=self.previous).map(|distance| (distance, frame(distance, self.previous, -1.)));
+		let next = (1..=self.next).map(|distance| (distance, frame(distance, self.next, 1.)));
+
+		let mut frames = previous.chain(next).filter(|(_, frame)| (0.0..=duration).contains(&frame.time)).collect::<Vec<_>>();
+		frames.sort_by_key(|&(distance, _)| std::cmp::Reverse(distance));
+		frames.into_iter().map(|(_, frame)| frame).collect()
+	}
+}
+
+/// A time the artwork is ghosted at, and how opaque it's drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct OnionSkinFrame {
+	time: f64,
+	opacity: f64,
+}
+
+/// Everything the onion skins depend on, so they're only rendered again once one of these changes.
+#[derive(Debug, Clone, PartialEq)]
+struct OnionSkinRenderKey {
+	node_graph_hash: u64,
+	tracks: Vec<KeyframeTrack>,
+	document_to_viewport: DAffine2,
+	resolution: UVec2,
+	view_mode: ViewMode,
+	time: f64,
+	onion_skin: OnionSkin,
+}
+
+/// A group layer whose layers are shown one at a time as the frames of a frame-by-frame animation, by a Frame Sequence node in its chain.
+#[derive(Debug, Clone, PartialEq)]
+struct FrameLayers {
+	group: LayerNodeIdentifier,
+	node_id: NodeId,
+	/// The group's layers from the first frame to the last, which is from the bottom of the group to the top.
+	frames: Vec<LayerNodeIdentifier>,
+	frame_rate: f64,
+	hold: u32,
+	looping: bool,
+}
+
+impl FrameLayers {
+	/// The frame-by-frame group containing the selection, or which is selected itself.
+	fn from_selection(document: &DocumentMessageHandler) -> Option<Self> {
+		if !document.selection_network_path().is_empty() {
+			return None;
+		}
+		let network_interface = &document.network_interface;
+		let metadata = document.metadata();
+
+		let selected_nodes = network_interface.selected_nodes();
+		let (group, node_id) = selected_nodes
+			.selected_layers(metadata)
+			.flat_map(|layer| layer.ancestors(metadata))
+			.filter(|&layer| layer != LayerNodeIdentifier::ROOT_PARENT)
+			.find_map(|layer| graph_modification_utils::get_frame_sequence_id(layer, network_interface).map(|node_id| (layer, node_id)))?;
+
+		let input = |index: usize| network_interface.input_from_connector(&InputConnector::node(node_id, index), &[]).and_then(|input| input.as_value());
+		let frame_rate = match input(1) {
+			Some(&TaggedValue::F64(frame_rate)) => frame_rate,
+			_ => 30.,
+		};
+		let hold = match input(2) {
+			Some(&TaggedValue::U32(hold)) => hold.max(1),
+			_ => 1,
+		};
+		let looping = matches!(input(3), Some(TaggedValue::Bool(true)));
+
+		let mut frames = group.children(metadata).collect::<Vec<_>>();
+		frames.reverse();
+
+		Some(Self {
+			group,
+			node_id,
+			frames,
+			frame_rate,
+			hold,
+			looping,
+		})
+	}
+
+	/// The index of the frame shown at the time, if any.
+	fn shown_frame(&self, time: f64) -> Option<usize> {
+		sequence_frame_index(time, self.frame_rate, self.hold, self.frames.len(), self.looping)
+	}
+
+	/// The index of the frame shown at the time, or of the frame that would be shown there if the group had enough frames.
+	fn current_frame(&self, time: f64) -> usize {
+		self.shown_frame(time)
+			.or_else(|| sequence_frame_index(time, self.frame_rate, self.hold, usize::MAX, false))
+			.unwrap_or_default()
+	}
+
+	/// The time the frame starts being shown.
+	fn frame_time(&self, index: usize) -> f64 {
+		if self.frame_rate <= 0. {
+			return 0.;
+		}
+		(index * self.hold as usize) as f64 / self.frame_rate
+	}
+
+	/// Selects the frame and moves the time to where it's shown, so new layers are drawn into it.
+	/// If the group doesn't have that many frames yet, blank frames are added up to it.
+	fn select_frame(&self, index: usize, responses: &mut VecDeque<Message>) {
+		let layer = match self.frames.get(index) {
+			Some(layer) => layer.to_node(),
+			None => {
+				responses.add(DocumentMessage::AddTransaction);
+
+				// Each new frame goes on top of the group, after the frames before it
+				let new_frames = (self.frames.len()..=index).map(|_| NodeId::new()).collect::<Vec<_>>();
+				for &id in &new_frames {
+					responses.add(GraphOperationMessage::NewCustomLayer {
+						id,
+						nodes: Vec::new(),
+						parent: self.group,
+						insert_index: 0,
+					});
+				}
+				*new_frames.last().unwrap()
+			}
+		};
+
+		responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![layer] });
+		responses.add(AnimationMessage::SetAnimationTime(self.frame_time(index)));
+	}
+
+	/// Inserts a blank frame after the current one, or at the current time if it's past the last frame, and selects it.
+	fn insert_frame(&self, time: f64, responses: &mut VecDeque<Message>) {
+		let current = self.current_frame(time);
+		if current >= self.frames.len() {
+			self.select_frame(current, responses);
+			return;
+		}
+
+		let index = current + 1;
+		let id = NodeId::new();
+		responses.add(DocumentMessage::AddTransaction);
+		responses.add(GraphOperationMessage::NewCustomLayer {
+			id,
+			nodes: Vec::new(),
+			parent: self.group,
+			insert_index: self.frames.len() - index,
+		});
+		responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![id] });
+		responses.add(AnimationMessage::SetAnimationTime(self.frame_time(index)));
+	}
+}
+
+/// The group layer that can be made into a frame-by-frame group, which is the selected layer if it's a group other than an artboard.
+fn frame_layers_candidate(document: &DocumentMessageHandler) -> Option<LayerNodeIdentifier> {
+	if !document.selection_network_path().is_empty() {
+		return None;
+	}
+	let network_interface = &document.network_interface;
+
+	let selected_nodes = network_interface.selected_nodes();
+	let mut selected_layers = selected_nodes.selected_layers(document.metadata());
+	let (Some(layer), None) = (selected_layers.next(), selected_layers.next()) else {
+		return None;
+	};
+	(layer.has_children(document.metadata()) && !network_interface.is_artboard(&layer.to_node(), &[])).then_some(layer)
+}
+
+/// The timeline shows the active document's keyframes along the animation's time, where node inputs can be keyframed.
+#[derive(Debug, Clone, Default)]
+pub struct TimelineMessageHandler {
+	/// Sets whether or not the timeline is drawn.
+	pub timeline_open: bool,
+	/// The input of the selected node that the keyframe buttons apply to, if the user picked one other than the first.
+	selected_input: Option<(NodeId, usize)>,
+	/// Whether the keyframes are shown as curves of their values over time, instead of in rows.
+	curve_editor_open: bool,
+	/// The track and index of the keyframe whose easing to the next keyframe is edited in the curve editor.
+	selected_keyframe: Option<(usize, usize)>,
+	/// The sound last sent to the frontend, which is only sent again once it's replaced since it may be large.
+	sent_audio_id: Option<u64>,
+	onion_skin: OnionSkin,
+	onion_skin_render_key: Option<OnionSkinRenderKey>,
+	/// The frames of the onion skins being rendered which have yet to arrive, rendered one after another since each may substitute the network in the runtime.
+	onion_skin_queue: VecDeque<OnionSkinFrame>,
+	/// The ghosted frames of the onion skins being rendered which have arrived so far.
+	onion_skin_frames: Vec<String>,
+	/// The most recent onion skins and the transform they were rendered with, which are repositioned while a render for the latest transform is in progress.
+	onion_skin_render: Option<(String, DAffine2)>,
+}
+
+impl MessageHandler<TimelineMessage, TimelineMessageData<'_>> for TimelineMessageHandler {
+	fn process_message(&mut self, message: TimelineMessage, responses: &mut VecDeque<Message>, data: TimelineMessageData) {
+		let TimelineMessageData {
+			document,
+			executor,
+			viewport_resolution,
+			timing_information,
+			animation_is_playing,
+			frames_per_second,
+			undo_history_length,
+		} = data;
+		let time = timing_information.animation_time.as_secs_f64();
+
+		match message {
+			TimelineMessage::ToggleOpen => {
+				self.timeline_open = !self.timeline_open;
+
+				responses.add(FrontendMessage::UpdateTimelineState { open: self.timeline_open });
+				// Update checked UI state for open
+				responses.add(MenuBarMessage::SendLayout);
+				responses.add(TimelineMessage::RenderOnionSkins);
+				self.send_audio(document.as_deref(), responses);
+				self.send_layout(document.as_deref(), time, animation_is_playing, frames_per_second, responses);
+			}
+			TimelineMessage::SendLayout => {
+				self.send_audio(document.as_deref(), responses);
+				self.send_layout(document.as_deref(), time, animation_is_playing, frames_per_second, responses);
+			}
+
+			TimelineMessage::SetDuration { duration } => {
+				let Some(document) = document else { return };
+				if duration > 0. && duration != document.timeline.duration {
+					document.add_history_step(undo_history_length, responses);
+					document.timeline.duration = duration;
+				}
+				self.send_layout(Some(&*document), time, animation_is_playing, frames_per_second, responses);
+			}
+			TimelineMessage::SetLoopIn { time: loop_in } => {
+				let Some(document) = document else { return };
+				let loop_in = loop_in.map(|loop_in| loop_in.clamp(0., document.timeline.duration));
+				if loop_in != document.timeline.loop_in {
+					document.add_history_step(undo_history_length, responses);
+					document.timeline.loop_in = loop_in;
+				}
+				self.send_layout(Some(&*document), time, animation_is_playing, frames_per_second, responses);
+			}
+			TimelineMessage::SetLoopOut { time: loop_out } => {
+				let Some(document) = document else { return };
+				let loop_out = loop_out.map(|loop_out| loop_out.clamp(0., document.timeline.duration));
+				if loop_out != document.timeline.loop_out {
+					document.add_history_step(undo_history_length, responses);
+					document.timeline.loop_out = loop_out;
+				}
+				self.send_layout(Some(&*document), time, animation_is_playing, frames_per_second, responses);
+			}
+			TimelineMessage::ClearLoopRange => {
+				let Some(document) = document else { return };
+				if document.timeline.loop_in.is_some() || document.timeline.loop_out.is_some() {
+					document.add_history_step(undo_history_length, responses);
+					document.timeline.loop_in = None;
+					document.timeline.loop_out = None;
+				}
+				self.send_layout(Some(&*document), time, animation_is_playing, frames_per_second, responses);
+			}
+			TimelineMessage::SetFramesPerSecond { fps } => {
+				let fps = fps.clamp(1., 120.);
+				// The frame rate is saved with the document, and playback is kept in step with it
+				if let Some(document) = document {
+					if fps != document.timeline.frames_per_second {
+						document.add_history_step(undo_history_length, responses);
+						document.timeline.frames_per_second = fps;
+					}
+				}
+				responses.add(AnimationMessage::SetFramesPerSecond(fps));
+			}
+			TimelineMessage::SelectInput { node_id, input_index } => {
+				self.selected_input = Some((node_id, input_index));
+				self.send_layout(document.as_deref(), time, animation_is_playing, frames_per_second, responses);
+			}
+
+			TimelineMessage::AddKeyframe => {
+				let Some(document) = document else { return };
+				let Some((node_id, input_index)) = self.keyframe_input(&keyframeable_inputs(document)) else {
+					return;
+				};
+
+				let network_path = document.selection_network_path().to_vec();
+				let input_connector = InputConnector::node(node_id, input_index);
+				let Some(value) = document
+					.network_interface
+					.input_from_connector(&input_connector, &network_path)
+					.and_then(|input| input.as_value())
+					.cloned()
+				else {
+					return;
+				};
+				document.add_history_step(undo_history_length, responses);
+				document.timeline.set_keyframe(&network_path, node_id, input_index, time, value);
+
+				self.send_layout(Some(&*document), time, animation_is_playing, frames_per_second, responses);
+			}
+			TimelineMessage::RemoveKeyframe => {
+				let Some(document) = document else { return };
+				let Some((node_id, input_index)) = self.keyframe_input(&keyframeable_inputs(document)) else {
+					return;
+				};
+
+				let network_path = document.selection_network_path().to_vec();
+				document.add_history_step(undo_history_length, responses);
+				document.timeline.remove_keyframe(&network_path, node_id, input_index, time);
+
+				// The input now takes its value from the remaining keyframes around this time
+				responses.add(PortfolioMessage::SubmitActiveGraphRender);
+				self.send_layout(Some(&*document), time, animation_is_playing, frames_per_second, responses);
+			}
+			TimelineMessage::RemoveTrack { index } => {
+				let Some(document) = document else { return };
+				if index < document.timeline.tracks.len() {
+					document.add_history_step(undo_history_length, responses);
+					document.timeline.tracks.remove(index);
+				}
+
+				// The input goes back to the value it has in the graph
+				responses.add(PortfolioMessage::SubmitActiveGraphRender);
+				self.send_layout(Some(&*document), time, animation_is_playing, frames_per_second, responses);
+			}
+
+			TimelineMessage::SetCurveEditorOpen { open } => {
+				self.curve_editor_open = open;
+				self.send_layout(document.as_deref(), time, animation_is_playing, frames_per_second, responses);
+			}
+			TimelineMessage::SelectKeyframe { track_index, keyframe_index } => {
+				self.selected_keyframe = Some((track_index, keyframe_index));
+				self.send_layout(document.as_deref(), time, animation_is_playing, frames_per_second, responses);
+			}
+			TimelineMessage::SetKeyframeEasing { track_index, keyframe_index, easing } => {
+				let Some(document) = document else { return };
+
+				document.add_history_step(undo_history_length, responses);
+				document.timeline.set_easing(track_index, keyframe_index, easing);
+				self.selected_keyframe = Some((track_index, keyframe_index));
+
+				// The input's value at the current time may have changed along with the curve
+				responses.add(PortfolioMessage::SubmitActiveGraphRender);
+				self.send_layout(Some(&*document), time, animation_is_playing, frames_per_second, responses);
+			}
+			TimelineMessage::MoveKeyframeEasingHandles {
+				track_index,
+				keyframe_index,
+				handles: [handle_start, handle_end],
+			} => {
+				let Some(document) = document else { return };
+
+				// The handles can't go back in time, which would make the curve loop back on itself
+				let easing = Easing::bezier(handle_start.x.clamp(0., 1.), handle_start.y, handle_end.x.clamp(0., 1.), handle_end.y);
+				// The undo step is added by the transaction when the drag starts, so this only has to record that the document changed
+				document.timeline.set_easing(track_index, keyframe_index, easing);
+				document.mark_as_unsaved(responses);
+				self.selected_keyframe = Some((track_index, keyframe_index));
+
+				responses.add(PortfolioMessage::SubmitActiveGraphRender);
+				self.send_layout(Some(&*document), time, animation_is_playing, frames_per_second, responses);
+			}
+
+			TimelineMessage::ImportAudio => {
+				responses.add(FrontendMessage::TriggerImportAudio);
+			}
+			TimelineMessage::SetAudio { name, mime, data } => {
+				let Some(document) = document else { return };
+
+				// A replaced sound keeps playing from the same time
+				let offset = document.timeline.audio.as_ref().map_or(0., |audio| audio.offset);
+				document.add_history_step(undo_history_length, responses);
+				document.timeline.audio = Some(AudioTrack {
+					id: generate_uuid(),
+					name,
+					mime,
+					data: data.into(),
+					offset,
+					muted: false,
+				});
+				self.send_audio(Some(&*document), responses);
+				self.send_layout(Some(&*document), time, animation_is_playing, frames_per_second, responses);
+			}
+			TimelineMessage::RemoveAudio => {
+				let Some(document) = document else { return };
+				if document.timeline.audio.is_some() {
+					document.add_history_step(undo_history_length, responses);
+					document.timeline.audio = None;
+				}
+				self.send_audio(Some(&*document), responses);
+				self.send_layout(Some(&*document), time, animation_is_playing, frames_per_second, responses);
+			}
+			TimelineMessage::SetAudioOffset { offset } => {
+				let Some(document) = document else { return };
+				// The undo step is added by the transaction when the edit starts, so this only has to record that the document changed
+				if let Some(audio) = &mut document.timeline.audio {
+					audio.offset = offset;
+					document.mark_as_unsaved(responses);
+				}
+				self.send_layout(Some(&*document), time, animation_is_playing, frames_per_second, responses);
+			}
+			TimelineMessage::ToggleAudioMute => {
+				let Some(document) = document else { return };
+				if document.timeline.audio.is_some() {
+					document.add_history_step(undo_history_length, responses);
+				}
+				if let Some(audio) = &mut document.timeline.audio {
+					audio.muted = !audio.muted;
+				}
+				self.send_layout(Some(&*document), time, animation_is_playing, frames_per_second, responses);
+			}
+
+			TimelineMessage::ToggleFrameLayers => {
+				let Some(document) = document else { return };
+
+				let (layer, frame_sequence) = match FrameLayers::from_selection(document) {
+					Some(frame_layers) => (frame_layers.group, false),
+					None => {
+						let Some(group) = frame_layers_candidate(document) else { return };
+						(group, true)
+					}
+				};
+
+				// The node is added or removed by a message so the transaction starts before the network changes
+				responses.add(DocumentMessage::AddTransaction);
+				responses.add(GraphOperationMessage::FrameSequenceSet {
+					layer,
+					frame_sequence,
+					// Each frame is shown for one frame of the timeline
+					frame_rate: frames_per_second,
+				});
+				responses.add(NodeGraphMessage::SendGraph);
+				responses.add(PropertiesPanelMessage::Refresh);
+				responses.add(TimelineMessage::SendLayout);
+			}
+			TimelineMessage::AddFrameLayer => {
+				let Some(frame_layers) = document.as_deref().and_then(FrameLayers::from_selection) else { return };
+				frame_layers.insert_frame(time, responses);
+			}
+			TimelineMessage::PreviousFrameLayer => {
+				let Some(frame_layers) = document.as_deref().and_then(FrameLayers::from_selection) else { return };
+				let current = frame_layers.current_frame(time);
+				frame_layers.select_frame(current.saturating_sub(1).min(frame_layers.frames.len().saturating_sub(1)), responses);
+			}
+			TimelineMessage::NextFrameLayer => {
+				let Some(frame_layers) = document.as_deref().and_then(FrameLayers::from_selection) else { return };
+				// Going past the last frame adds a blank one to draw the next frame in
+				frame_layers.select_frame(frame_layers.current_frame(time) + 1, responses);
+			}
+
+			TimelineMessage::ToggleOnionSkin => {
+				self.onion_skin.enabled = !self.onion_skin.enabled;
+				responses.add(TimelineMessage::RenderOnionSkins);
+				self.send_layout(document.as_deref(), time, animation_is_playing, frames_per_second, responses);
+			}
+			TimelineMessage::SetOnionSkinPrevious { count } => {
+				self.onion_skin.previous = count;
+				responses.add(TimelineMessage::RenderOnionSkins);
+				self.send_layout(document.as_deref(), time, animation_is_playing, frames_per_second, responses);
+			}
+			TimelineMessage::SetOnionSkinNext { count } => {
+				self.onion_skin.next = count;
+				responses.add(TimelineMessage::RenderOnionSkins);
+				self.send_layout(document.as_deref(), time, animation_is_playing, frames_per_second, responses);
+			}
+			TimelineMessage::SetOnionSkinSpacing { spacing } => {
+				if spacing > 0. {
+					self.onion_skin.spacing = spacing;
+				}
+				responses.add(TimelineMessage::RenderOnionSkins);
+				self.send_layout(document.as_deref(), time, animation_is_playing, frames_per_second, responses);
+			}
+			TimelineMessage::SetOnionSkinOpacity { opacity } => {
+				self.onion_skin.opacity = opacity.clamp(0., 100.);
+				responses.add(TimelineMessage::RenderOnionSkins);
+				self.send_layout(document.as_deref(), time, animation_is_playing, frames_per_second, responses);
+			}
+			TimelineMessage::RenderOnionSkins => {
+				// Onion skins are only shown while the animation is paused, since they'd trail behind it during playback
+				let Some(document) = document.filter(|_| self.timeline_open && self.onion_skin.enabled && !animation_is_playing) else {
+					self.clear_onion_skins(executor, responses);
+					return;
+				};
+				// The artboards' backgrounds are drawn beneath the onion skins instead, so the onion skins can be beneath the artwork
+				if !executor.hide_artboard_backgrounds {
+					executor.hide_artboard_backgrounds = true;
+					responses.add(PortfolioMessage::SubmitActiveGraphRender);
+				}
+				let document_to_viewport = document.metadata().document_to_viewport;
+				self.send_onion_skin_artwork(document, responses);
+
+				// Once the render in progress finishes, the onion skins are asked to render again in case they've changed since
+				if !self.onion_skin_queue.is_empty() {
+					return;
+				}
+
+				let render_key = OnionSkinRenderKey {
+					node_graph_hash: document.network_interface.document_network().current_hash(),
+					tracks: document.timeline.tracks.clone(),
+					document_to_viewport,
+					resolution: viewport_resolution,
+					view_mode: document.view_mode,
+					time,
+					onion_skin: self.onion_skin,
+				};
+				if self.onion_skin_render_key.as_ref() == Some(&render_key) || viewport_resolution.cmpeq(UVec2::ZERO).any() {
+					return;
+				}
+
+				// The network may be substituted in the runtime while the onion skins render, so that waits for the viewport's render to finish.
+				// Once the viewport's render arrives, the onion skins are asked to render again.
+				if executor.viewport_render_pending() {
+					return;
+				}
+
+				self.onion_skin_render_key = Some(render_key);
+				self.onion_skin_queue = self.onion_skin.frames(time, document.timeline.duration);
+				self.onion_skin_frames.clear();
+				if self.onion_skin_queue.is_empty() {
+					self.onion_skin_render = None;
+					responses.add(FrontendMessage::UpdateOnionSkinArtwork { svg: String::new() });
+					return;
+				}
+				self.submit_onion_skin(document, executor, viewport_resolution, timing_information);
+			}
+			TimelineMessage::OnionSkinRendered { svg } => {
+				let Some(frame) = self.onion_skin_queue.pop_front() else { return };
+				self.onion_skin_frames.push(format!(r#"<g opacity="{}">{svg}</g>"#, frame.opacity));
+
+				let Some(document) = document else {
+					self.onion_skin_queue.clear();
+					return;
+				};
+				if !self.onion_skin_queue.is_empty() {
+					self.submit_onion_skin(document, executor, viewport_resolution, timing_information);
+					return;
+				}
+
+				// The onion skins may have been hidden while they were rendering
+				if let Some(render_key) = &self.onion_skin_render_key {
+					self.onion_skin_render = Some((self.onion_skin_frames.concat(), render_key.document_to_viewport));
+					self.send_onion_skin_artwork(document, responses);
+				}
+				self.onion_skin_frames.clear();
+				responses.add(TimelineMessage::RenderOnionSkins);
+			}
+		}
+	}
+
+	fn actions(&self) -> ActionList {
+		actions!(TimelineMessageDiscriminant;
+			AddFrameLayer,
+			PreviousFrameLayer,
+			NextFrameLayer,
+		)
+	}
+}
+
+impl TimelineMessageHandler {
+	/// Renders the next frame of the onion skins in the queue.
+	fn submit_onion_skin(&mut self, document: &mut DocumentMessageHandler, executor: &mut NodeGraphExecutor, viewport_resolution: UVec2, timing_information: TimingInformation) {
+		let Some(frame) = self.onion_skin_queue.front() else { return };
+
+		let time = TimingInformation {
+			animation_time: Duration::from_secs_f64(frame.time),
+			..timing_information
+		};
+		if let Err(error) = executor.submit_onion_skin_render(document, viewport_resolution, time) {
+			warn!("Failed to render the onion skins: {error}");
+			self.onion_skin_queue.clear();
+			self.onion_skin_render_key = None;
+		}
+	}
+
+	/// Sends the artboards' backgrounds with the latest onion skins over them, transformed from where they were rendered to where the document currently sits in the viewport.
+	fn send_onion_skin_artwork(&self, document: &DocumentMessageHandler, responses: &mut VecDeque<Message>) {
+		let backgrounds = artboard_backgrounds(document);
+		let svg = match &self.onion_skin_render {
+			Some((artwork, rendered_document_to_viewport)) => {
+				let transform = format_transform_matrix(document.metadata().document_to_viewport * rendered_document_to_viewport.inverse());
+				if transform.is_empty() {
+					format!(r#"<svg overflow="visible">{backgrounds}{artwork}</svg>"#)
+				} else {
+					format!(r#"<svg overflow="visible">{backgrounds}<g transform="{transform}">{artwork}</g></svg>"#)
+				}
+			}
+			None => format!(r#"<svg overflow="visible">{backgrounds}</svg>"#),
+		};
+		responses.add(FrontendMessage::UpdateOnionSkinArtwork { svg });
+	}
+
+	fn send_audio(&mut self, document: Option<&DocumentMessageHandler>, responses: &mut VecDeque<Message>) {
+		let audio = document.and_then(|document| document.timeline.audio.as_ref());
+		if audio.map(|audio| audio.id) == self.sent_audio_id {
+			return;
+		}
+		self.sent_audio_id = audio.map(|audio| audio.id);
+
+		let audio = audio.map(|audio| FrontendAudioTrack {
+			name: audio.name.clone(),
+			mime: audio.mime.clone(),
+			data: audio.data.to_vec(),
+		});
+		responses.add(FrontendMessage::UpdateTimelineAudio { audio });
+	}
+
+	fn clear_onion_skins(&mut self, executor: &mut NodeGraphExecutor, responses: &mut VecDeque<Message>) {
+		// Any render still in progress is left to finish, but isn't shown
+		self.onion_skin_render_key = None;
+		let had_onion_skins = self.onion_skin_render.take().is_some();
+
+		// The viewport's render goes back to including the artboards' backgrounds
+		if executor.hide_artboard_backgrounds {
+			executor.hide_artboard_backgrounds = false;
+			responses.add(PortfolioMessage::SubmitActiveGraphRender);
+		} else if !had_onion_skins {
+			return;
+		}
+		responses.add(FrontendMessage::UpdateOnionSkinArtwork { svg: String::new() });
+	}
+
+	/// The input the keyframe buttons apply to: the one picked by the user if it's still available, otherwise the first.
+	fn keyframe_input(&self, inputs: &[(NodeId, usize)]) -> Option<(NodeId, usize)> {
+		self.selected_input.filter(|input| inputs.contains(input)).or_else(|| inputs.first().copied())
+	}
+
+	fn send_layout(&self, document: Option<&DocumentMessageHandler>, time: f64, animation_is_playing: bool, frames_per_second: f64, responses: &mut VecDeque<Message>) {
+		if !self.timeline_open {
+			return;
+		}
+
+		let mut widgets = vec![
+			IconButton::new("PlaybackToStart", 24)
+				.tooltip("Restart Animation")
+				.tooltip_shortcut(action_keys!(AnimationMessageDiscriminant::RestartAnimation))
+				.on_update(|_| AnimationMessage::RestartAnimation.into())
+				.widget_holder(),
+			IconButton::new("PlaybackStepBackward", 24)
+				.tooltip("Previous Frame")
+				.tooltip_shortcut(action_keys!(AnimationMessageDiscriminant::StepBackward))
+				.on_update(|_| AnimationMessage::StepBackward.into())
+				.widget_holder(),
+			IconButton::new(if animation_is_playing { "PlaybackPause" } else { "PlaybackPlay" }, 24)
+				.tooltip(if animation_is_playing { "Pause Animation" } else { "Play Animation" })
+				.tooltip_shortcut(action_keys!(AnimationMessageDiscriminant::ToggleLivePreview))
+				.on_update(|_| AnimationMessage::ToggleLivePreview.into())
+				.widget_holder(),
+			IconButton::new("PlaybackStepForward", 24)
+				.tooltip("Next Frame")
+				.tooltip_shortcut(action_keys!(AnimationMessageDiscriminant::StepForward))
+				.on_update(|_| AnimationMessage::StepForward.into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(time))
+				.label("Time")
+				.unit(" s")
+				.min(0.)
+				.display_decimal_places(2)
+				.tooltip("Current time of the animation")
+				.on_update(|number_input: &NumberInput| AnimationMessage::SetAnimationTime(number_input.value.unwrap_or_default()).into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			NumberInput::new(Some(frames_per_second))
+				.label("FPS")
+				.min(1.)
+				.max(120.)
+				.display_decimal_places(0)
+				.tooltip("Frame rate the animation is played back and stepped through at")
+				.on_update(|number_input: &NumberInput| {
+					TimelineMessage::SetFramesPerSecond {
+						fps: number_input.value.unwrap_or(30.),
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let Some(document) = document else {
+			responses.add(FrontendMessage::UpdateTimelineTracks {
+				time,
+				playing: animation_is_playing,
+				duration: 0.,
+				loop_range: None,
+				tracks: Vec::new(),
+				curve_editor: self.curve_editor_open,
+				selected_keyframe: None,
+				audio_offset: 0.,
+				audio_muted: false,
+			});
+			send_control_bar(widgets, responses);
+			return;
+		};
+
+		let (loop_in, loop_out) = document.timeline.loop_range();
+		widgets.extend([
+			Separator::new(SeparatorType::Related).widget_holder(),
+			RadioInput::new(vec![
+				RadioEntryData::new("Keyframes")
+					.label("Keyframes")
+					.tooltip("Show the keyframes of each input in rows")
+					.on_update(|_| TimelineMessage::SetCurveEditorOpen { open: false }.into()),
+				RadioEntryData::new("Curves")
+					.label("Curves")
+					.tooltip("Show the values of each input over time as curves, with the easing between keyframes")
+					.on_update(|_| TimelineMessage::SetCurveEditorOpen { open: true }.into()),
+			])
+			.selected_index(Some(self.curve_editor_open as u32))
+			.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			NumberInput::new(Some(document.timeline.duration))
+				.label("Duration")
+				.unit(" s")
+				.min(0.1)
+				.display_decimal_places(2)
+				.tooltip("Length of the animation shown in the timeline")
+				.on_update(|number_input: &NumberInput| {
+					TimelineMessage::SetDuration {
+						duration: number_input.value.unwrap_or(0.1),
+					}
+					.into()
+				})
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			NumberInput::new(Some(loop_in))
+				.label("Loop In")
+				.unit(" s")
+				.min(0.)
+				.max(document.timeline.duration)
+				.display_decimal_places(2)
+				.tooltip("Time playback loops back to once it reaches the loop out point")
+				.on_update(|number_input: &NumberInput| TimelineMessage::SetLoopIn { time: number_input.value }.into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			NumberInput::new(Some(loop_out))
+				.label("Loop Out")
+				.unit(" s")
+				.min(0.)
+				.max(document.timeline.duration)
+				.display_decimal_places(2)
+				.tooltip("Time playback loops back from to the loop in point")
+				.on_update(|number_input: &NumberInput| TimelineMessage::SetLoopOut { time: number_input.value }.into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			IconButton::new("Reset", 24)
+				.tooltip("Loop over the whole timeline")
+				.disabled(document.timeline.loop_in.is_none() && document.timeline.loop_out.is_none())
+				.on_update(|_| TimelineMessage::ClearLoopRange.into())
+				.widget_holder(),
+		]);
+		match &document.timeline.audio {
+			None => widgets.extend([
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextButton::new("Import Audio")
+					.tooltip("Import a sound file to play along with the animation for timing it, such as dialogue to lip sync or music")
+					.on_update(|_| TimelineMessage::ImportAudio.into())
+					.widget_holder(),
+			]),
+			Some(audio) => widgets.extend([
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				CheckboxInput::new(!audio.muted)
+					.tooltip("Play the audio along with the animation, and while scrubbing through it")
+					.on_update(|_| TimelineMessage::ToggleAudioMute.into())
+					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				TextLabel::new("Audio").widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				NumberInput::new(Some(audio.offset))
+					.label("Offset")
+					.unit(" s")
+					.display_decimal_places(2)
+					.tooltip("Time in the animation the audio starts playing at")
+					.on_update(|number_input: &NumberInput| {
+						TimelineMessage::SetAudioOffset {
+							offset: number_input.value.unwrap_or_default(),
+						}
+						.into()
+					})
+					// The transaction is added once when a drag or edit starts, rather than for every value sent while dragging
+					.on_commit(|_| DocumentMessage::AddTransaction.into())
+					.widget_holder(),
+			]),
+		}
+		widgets.extend([
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(self.onion_skin.enabled)
+				.tooltip("Ghost the artwork at the times before and after the current one over it, while the animation is paused")
+				.on_update(|_| TimelineMessage::ToggleOnionSkin.into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			TextLabel::new("Onion Skin").widget_holder(),
+		]);
+		if self.onion_skin.enabled {
+			widgets.extend([
+				Separator::new(SeparatorType::Related).widget_holder(),
+				NumberInput::new(Some(self.onion_skin.previous as f64))
+					.label("Before")
+					.int()
+					.min(0.)
+					.max(10.)
+					.tooltip("Number of frames before the current time to ghost")
+					.on_update(|number_input: &NumberInput| {
+						TimelineMessage::SetOnionSkinPrevious {
+							count: number_input.value.unwrap_or_default() as u32,
+						}
+						.into()
+					})
+					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				NumberInput::new(Some(self.onion_skin.next as f64))
+					.label("After")
+					.int()
+					.min(0.)
+					.max(10.)
+					.tooltip("Number of frames after the current time to ghost")
+					.on_update(|number_input: &NumberInput| {
+						TimelineMessage::SetOnionSkinNext {
+							count: number_input.value.unwrap_or_default() as u32,
+						}
+						.into()
+					})
+					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				NumberInput::new(Some(self.onion_skin.spacing))
+					.label("Spacing")
+					.unit(" s")
+					.min(0.01)
+					.display_decimal_places(2)
+					.tooltip("Time between each ghosted frame")
+					.on_update(|number_input: &NumberInput| {
+						TimelineMessage::SetOnionSkinSpacing {
+							spacing: number_input.value.unwrap_or(0.01),
+						}
+						.into()
+					})
+					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				NumberInput::new(Some(self.onion_skin.opacity))
+					.label("Opacity")
+					.percentage()
+					.tooltip("Opacity of the ghosted frames nearest to the current time, which fades out for those further away")
+					.on_update(|number_input: &NumberInput| {
+						TimelineMessage::SetOnionSkinOpacity {
+							opacity: number_input.value.unwrap_or_default(),
+						}
+						.into()
+					})
+					.widget_holder(),
+			]);
+		}
+
+		let frame_layers = FrameLayers::from_selection(document);
+		if frame_layers.is_some() || frame_layers_candidate(document).is_some() {
+			widgets.extend([
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				CheckboxInput::new(frame_layers.is_some())
+					.tooltip("Show the layers of the selected group one at a time as the frames of a frame-by-frame animation, starting from the bottom-most layer")
+					.on_update(|_| TimelineMessage::ToggleFrameLayers.into())
+					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				TextLabel::new("Frame by Frame").widget_holder(),
+			]);
+		}
+		if let Some(frame_layers) = &frame_layers {
+			let shown_frame = frame_layers.shown_frame(time).map_or("-".to_string(), |index| (index + 1).to_string());
+			let node_id = frame_layers.node_id;
+
+			widgets.extend([
+				Separator::new(SeparatorType::Related).widget_holder(),
+				IconButton::new("PlaybackStepBackward", 24)
+					.tooltip("Previous Frame Layer")
+					.tooltip_shortcut(action_keys!(TimelineMessageDiscriminant::PreviousFrameLayer))
+					.on_update(|_| TimelineMessage::PreviousFrameLayer.into())
+					.widget_holder(),
+				TextLabel::new(format!("Frame {shown_frame} of {}", frame_layers.frames.len())).widget_holder(),
+				IconButton::new("PlaybackStepForward", 24)
+					.tooltip("Next Frame Layer, adding a blank one after the last")
+					.tooltip_shortcut(action_keys!(TimelineMessageDiscriminant::NextFrameLayer))
+					.on_update(|_| TimelineMessage::NextFrameLayer.into())
+					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				TextButton::new("New Frame")
+					.icon(Some("Add".into()))
+					.tooltip("Insert a blank frame after the current one and select it to draw in")
+					.tooltip_shortcut(action_keys!(TimelineMessageDiscriminant::AddFrameLayer))
+					.on_update(|_| TimelineMessage::AddFrameLayer.into())
+					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				NumberInput::new(Some(frame_layers.hold as f64))
+					.label("Hold")
+					.int()
+					.min(1.)
+					.tooltip("Number of timeline frames each frame layer is shown for, such as 2 to animate on twos")
+					.on_update(move |number_input: &NumberInput| {
+						NodeGraphMessage::SetInputValue {
+							node_id,
+							input_index: 2,
+							value: TaggedValue::U32(number_input.value.unwrap_or(1.).max(1.) as u32),
+						}
+						.into()
+					})
+					// The transaction is added once when a drag or edit starts, rather than for every value sent while dragging
+					.on_commit(|_| DocumentMessage::AddTransaction.into())
+					.widget_holder(),
+			]);
+		}
+
+		let inputs = keyframeable_inputs(document);
+		if let Some(selected_input) = self.keyframe_input(&inputs) {
+			let network_path = document.selection_network_path();
+
+			let entries = inputs
+				.iter()
+				.map(|&(node_id, input_index)| {
+					MenuListEntry::new(format!("{node_id}:{input_index}"))
+						.label(input_display_name(document, network_path, node_id, input_index))
+						.on_update(move |_| TimelineMessage::SelectInput { node_id, input_index }.into())
+				})
+				.collect();
+			let selected_index = inputs.iter().position(|&input| input == selected_input).map(|index| index as u32);
+
+			let (node_id, input_index) = selected_input;
+			let has_keyframe = document.timeline.track(network_path, node_id, input_index).is_some_and(|track| track.keyframe_at(time).is_some());
+
+			widgets.extend([
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				DropdownInput::new(vec![entries])
+					.selected_index(selected_index)
+					.tooltip("Input of the selected node to keyframe")
+					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				TextButton::new("Add Keyframe")
+					.icon(Some("Add".into()))
+					.tooltip("Record the input's current value as a keyframe at the current time")
+					.on_update(|_| TimelineMessage::AddKeyframe.into())
+					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				TextButton::new("Remove Keyframe")
+					.icon(Some("Remove".into()))
+					.tooltip("Remove the input's keyframe at the current time")
+					.disabled(!has_keyframe)
+					.on_update(|_| TimelineMessage::RemoveKeyframe.into())
+					.widget_holder(),
+			]);
+		}
+
+		// The easing of the selected keyframe, which only applies if there's a keyframe after it to ease towards
+		let selected_keyframe = self
+			.selected_keyframe
+			.filter(|&(track_index, keyframe_index)| document.timeline.tracks.get(track_index).is_some_and(|track| keyframe_index + 1 < track.keyframes.len()));
+		if let Some((track_index, keyframe_index)) = selected_keyframe.filter(|_| self.curve_editor_open) {
+			let easing = document.timeline.tracks[track_index].keyframes[keyframe_index].easing;
+
+			let mut entries = Easing::PRESETS
+				.into_iter()
+				.map(|easing| {
+					MenuListEntry::new(format!("{easing:?}"))
+						.label(easing.to_string())
+						.on_update(move |_| TimelineMessage::SetKeyframeEasing { track_index, keyframe_index, easing }.into())
+				})
+				.collect::<Vec<_>>();
+			// Easings shaped by dragging the handles don't match any preset
+			let selected_index = Easing::PRESETS.iter().position(|preset| *preset == easing).unwrap_or_else(|| {
+				entries.push(MenuListEntry::new("Custom").label("Custom"));
+				entries.len() - 1
+			});
+
+			widgets.extend([
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextLabel::new("Easing").widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				DropdownInput::new(vec![entries])
+					.selected_index(Some(selected_index as u32))
+					.tooltip("How the value eases from the selected keyframe to the next one")
+					.widget_holder(),
+			]);
+		}
+		send_control_bar(widgets, responses);
+
+		let tracks = document
+			.timeline
+			.tracks
+			.iter()
+			.map(|track| FrontendKeyframeTrack {
+				name: input_display_name(document, &track.network_path, track.node_id, track.input_index),
+				keyframe_times: track.keyframes.iter().map(|keyframe| keyframe.time).collect(),
+				keyframe_values: track.keyframes.iter().map(|keyframe| value_components(&keyframe.value)).collect(),
+				easing_handles: track
+					.keyframes
+					.iter()
+					.map(|keyframe| keyframe.easing.handles().map(|(handle_start, handle_end)| [handle_start.x, handle_start.y, handle_end.x, handle_end.y]))
+					.collect(),
+				curve: if self.curve_editor_open { sample_curve(track) } else { Vec::new() },
+			})
+			.collect();
+		let audio = document.timeline.audio.as_ref();
+		responses.add(FrontendMessage::UpdateTimelineTracks {
+			time,
+			playing: animation_is_playing,
+			duration: document.timeline.duration,
+			loop_range: Some([loop_in, loop_out]),
+			tracks,
+			curve_editor: self.curve_editor_open,
+			selected_keyframe,
+			audio_offset: audio.map_or(0., |audio| audio.offset),
+			audio_muted: audio.is_some_and(|audio| audio.muted),
+		});
+	}
+}
+
+fn send_control_bar(widgets: Vec<WidgetHolder>, responses: &mut VecDeque<Message>) {
+	responses.add(LayoutMessage::SendLayout {
+		layout: Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }])),
+		layout_target: LayoutTarget::TimelineControlBar,
+	});
+}
+
+/// The inputs of the selected node that can be keyframed, which for a layer also includes those of the nodes in its chain, as shown in the Properties panel.
+fn keyframeable_inputs(document: &DocumentMessageHandler) -> Vec<(NodeId, usize)> {
+	let network_interface = &document.network_interface;
+	let network_path = document.selection_network_path();
+
+	let Some(selected_nodes) = network_interface.selected_nodes_in_nested_network(network_path) else {
+		return Vec::new();
+	};
+	let mut selected_nodes = selected_nodes.selected_nodes();
+	let (Some(&node_id), None) = (selected_nodes.next(), selected_nodes.next()) else {
+		return Vec::new();
+	};
+
+	let nodes = if network_interface.is_layer(&node_id, network_path) {
+		// Stop at the next layer, where the flow switches from horizontal to vertical
+		network_interface
+			.upstream_flow_back_from_nodes(vec![node_id], network_path, FlowType::HorizontalFlow)
+			.enumerate()
+			.take_while(|(index, node_id)| *index == 0 || !network_interface.is_layer(node_id, network_path))
+			.map(|(_, node_id)| node_id)
+			.collect::<Vec<_>>()
+	} else {
+		vec![node_id]
+	};
+
+	nodes
+		.into_iter()
+		.flat_map(|node_id| (0..network_interface.number_of_inputs(&node_id, network_path)).map(move |input_index| (node_id, input_index)))
+		.filter(|&(node_id, input_index)| {
+			network_interface
+				.input_from_connector(&InputConnector::node(node_id, input_index), network_path)
+				.and_then(|input| input.as_value())
+				.is_some_and(is_keyframeable)
+		})
+		.collect()
+}
+
+/// Samples the track's values between each pair of its keyframes, for drawing the curves of how they ease from one to the next.
+fn sample_curve(track: &KeyframeTrack) -> Vec<(f64, Vec<f64>)> {
+	const SAMPLES_PER_SEGMENT: usize = 24;
+
+	let mut samples = Vec::new();
+	for (index, keyframe) in track.keyframes.iter().enumerate() {
+		samples.push((keyframe.time, value_components(&keyframe.value)));
+
+		let Some(next) = track.keyframes.get(index + 1) else { continue };
+		for sample in 1..SAMPLES_PER_SEGMENT {
+			let time = keyframe.time + (next.time - keyframe.time) * sample as f64 / SAMPLES_PER_SEGMENT as f64;
+			if let Some(value) = track.value_at(time) {
+				samples.push((time, value_components(&value)));
+			}
+		}
+	}
+	samples
+}
+
+/// The backgrounds of the artboards in the viewport, or a white background filling the viewport if there are no artboards, which are drawn beneath the onion skins in place of those left out of the viewport's render.
+fn artboard_backgrounds(document: &DocumentMessageHandler) -> String {
+	let network_interface = &document.network_interface;
+	let artboards = network_interface.all_artboards();
+	if artboards.is_empty() {
+		return r#"<rect width="100%" height="100%" fill="white" />"#.to_string();
+	}
+
+	let rects = artboards
+		.into_iter()
+		.filter_map(|artboard| {
+			let inputs = &network_interface.document_network().nodes.get(&artboard.to_node())?.inputs;
+			let (Some(&TaggedValue::IVec2(location)), Some(&TaggedValue::IVec2(dimensions)), Some(&TaggedValue::Color(background))) =
+				(inputs.get(2)?.as_value(), inputs.get(3)?.as_value(), inputs.get(4)?.as_value())
+			else {
+				return None;
+			};
+
+			let (min, size) = (location.min(location + dimensions), dimensions.abs());
+			let fill = background.to_rgb_hex_srgb_from_gamma();
+			let opacity = (background.a() * 1000.).round() / 1000.;
+			Some(format!(
+				r##"<rect x="{}" y="{}" width="{}" height="{}" fill="#{fill}" fill-opacity="{opacity}" />"##,
+				min.x, min.y, size.x, size.y
+			))
+		})
+		.collect::<String>();
+
+	let transform = format_transform_matrix(document.metadata().document_to_viewport);
+	if transform.is_empty() { rects } else { format!(r#"<g transform="{transform}">{rects}</g>"#) }
+}
+
+fn input_display_name(document: &DocumentMessageHandler, network_path: &[NodeId], node_id: NodeId, input_index: usize) -> String {
+	let network_interface = &document.network_interface;
+	let node_name = network_interface.frontend_display_name(&node_id, network_path);
+	let input_name = network_interface.input_name(&node_id, input_index, network_path).unwrap_or_default();
+	format!("{node_name}: {input_name}")
+}
@@ -39,23 +39,53 @@ pub enum KeyboardPlatformLayout {
 	Mac,
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, serde::Serialize, serde::Deserialize, specta::Type)]
 pub enum PanelType {
 	#[default]
 	Document,
 	Layers,
 	Properties,
 	Spreadsheet,
+	SplitView,
+	Timeline,
+	ColorHarmony,
+	Plugins,
+	Console,
+	Collaboration,
+	Compare,
+	Library,
+	DataMerge,
 }
 
 impl From<String> for PanelType {
 	fn from(value: String) -> Self {
-		match value.as_str() {
+		Self::from_name(&value).unwrap_or_else(|| panic!("Unknown panel type: {}", value))
+	}
+}
+
+impl PanelType {
+	/// The panel with the name the frontend knows it by, or `None` if there's no such panel.
+	pub fn from_name(name: &str) -> Option<Self> {
+		Some(match name {
 			"Document" => PanelType::Document,
 			"Layers" => PanelType::Layers,
 			"Properties" => PanelType::Properties,
 			"Spreadsheet" => PanelType::Spreadsheet,
-			_ => panic!("Unknown panel type: {}", value),
-		}
+			"SplitView" => PanelType::SplitView,
+			"Timeline" => PanelType::Timeline,
+			"ColorHarmony" => PanelType::ColorHarmony,
+			"Plugins" => PanelType::Plugins,
+			"Console" => PanelType::Console,
+			"Collaboration" => PanelType::Collaboration,
+			"Compare" => PanelType::Compare,
+			"Library" => PanelType::Library,
+			"DataMerge" => PanelType::DataMerge,
+			_ => return None,
+		})
+	}
+
+	/// Whether the panel can be undocked from the workspace into a floating window of its own.
+	pub fn can_float(&self) -> bool {
+		matches!(self, PanelType::Layers | PanelType::Properties | PanelType::Spreadsheet)
 	}
 }
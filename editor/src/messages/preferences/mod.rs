@@ -7,4 +7,4 @@ pub use preferences_message::{PreferencesMessage, PreferencesMessageDiscriminant
 #[doc(inline)]
 pub use preferences_message_handler::PreferencesMessageHandler;
 #[doc(inline)]
-pub use utility_types::SelectionMode;
+pub use utility_types::{CursorStyle, GradientPreset, SaveAction, SaveActionEdit, SaveActionKind, SelectionMode, WorkspaceLayout};
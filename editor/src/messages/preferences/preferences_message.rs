@@ -1,6 +1,12 @@
+use crate::messages::action_macro::utility_types::ActionMacro;
+use crate::messages::input_mapper::utility_types::misc::{KeymapPreset, MiddleMouseDrag};
 use crate::messages::portfolio::document::node_graph::utility_types::GraphWireStyle;
-use crate::messages::preferences::SelectionMode;
+use crate::messages::portfolio::document::overlays::utility_types::OverlayColors;
+use crate::messages::preferences::{CursorStyle, GradientPreset, SaveAction, SaveActionEdit, SelectionMode, WorkspaceLayout};
 use crate::messages::prelude::*;
+use crate::messages::radial_menu::utility_types::RadialMenuItem;
+use crate::messages::tool::utility_types::ToolType;
+use graphene_core::application_io::Antialiasing;
 
 #[impl_message(Message, Preferences)]
 #[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -14,7 +20,39 @@ pub enum PreferencesMessage {
 	SelectionMode { selection_mode: SelectionMode },
 	VectorMeshes { enabled: bool },
 	ModifyLayout { zoom_with_scroll: bool },
+	MiddleMouseDrag { drag: MiddleMouseDrag },
 	GraphWireStyle { style: GraphWireStyle },
+	MemoryBudget { mebibytes: u32 },
+	UndoHistoryLength { length: usize },
+	NudgeDistance { distance: f64 },
+	LargeNudgeDistance { distance: f64 },
+	AutoPanEdgeMargin { margin: f64 },
+	AutoPanSpeed { speed: f64 },
+	AutoPanAcceleration { acceleration: f64 },
+	ViewportAntialiasing { antialiasing: Antialiasing },
+	DegradeWhileInteracting { enabled: bool },
+	OverlayColors { colors: OverlayColors },
+	CursorStyle { style: CursorStyle },
+	PrecisionCursorSize { size: u32 },
+	KeymapPreset { preset: KeymapPreset },
+	StickyModifierKeys { enabled: bool },
+	RebindShortcut { action: String, shortcut: String },
+	ResetShortcut { action: String },
+	SaveWorkspaceLayout { layout: WorkspaceLayout },
+	DeleteWorkspaceLayout { name: String },
+	RadialMenuItem { slot: usize, item: RadialMenuItem },
+	RadialMenuOnHold { enabled: bool },
+	SaveActionMacro { action_macro: ActionMacro },
+	DeleteActionMacro { name: String },
+	SaveGradientPreset { preset: GradientPreset },
+	DeleteGradientPreset { name: String },
+	LibraryUrl { url: String },
+	ExternalImageEditor { path: String },
+	AddSaveAction { action: SaveAction },
+	EditSaveAction { index: usize, edit: SaveActionEdit },
+	DeleteSaveAction { index: usize },
+	ToolOptions { tool_type: ToolType, options: String },
+	ResetToolOptions,
 	// ImaginateRefreshFrequency { seconds: f64 },
 	// ImaginateServerHostname { hostname: String },
 }
@@ -1,18 +1,66 @@
+use crate::messages::action_macro::utility_types::ActionMacro;
 use crate::messages::input_mapper::key_mapping::MappingVariant;
+use crate::messages::input_mapper::utility_types::misc::{CustomShortcut, KeymapPreset, MiddleMouseDrag};
 use crate::messages::portfolio::document::node_graph::utility_types::GraphWireStyle;
-use crate::messages::preferences::SelectionMode;
+use crate::messages::portfolio::document::overlays::utility_types::OverlayColors;
+use crate::messages::preferences::{CursorStyle, GradientPreset, SaveAction, SelectionMode, WorkspaceLayout};
 use crate::messages::prelude::*;
+use crate::messages::radial_menu::utility_types::{RADIAL_MENU_SLOTS, RadialMenuItem};
+use crate::messages::tool::utility_types::ToolType;
 use graph_craft::wasm_application_io::EditorPreferences;
+use graphene_core::application_io::Antialiasing;
 
 #[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(default)]
 pub struct PreferencesMessageHandler {
 	pub imaginate_server_hostname: String,
 	pub imaginate_refresh_frequency: f64,
 	pub selection_mode: SelectionMode,
 	pub zoom_with_scroll: bool,
+	/// The navigation done by dragging with the middle mouse button, with panning moving to the chosen navigation's modifier key if it's not panning.
+	pub middle_mouse_drag: MiddleMouseDrag,
 	pub use_vello: bool,
 	pub vector_meshes: bool,
 	pub graph_wire_style: GraphWireStyle,
+	pub memory_budget_mebibytes: u32,
+	pub undo_history_length: usize,
+	pub nudge_distance: f64,
+	pub large_nudge_distance: f64,
+	/// Distance (in viewport pixels) inside the viewport's edges within which dragging starts auto-panning.
+	pub auto_pan_edge_margin: f64,
+	/// Auto-panning speed, in viewport pixels per second for each pixel the pointer is dragged past the edge margin.
+	pub auto_pan_speed: f64,
+	/// Fraction of the auto-panning speed added for each second auto-panning continues.
+	pub auto_pan_acceleration: f64,
+	pub viewport_antialiasing: Antialiasing,
+	pub degrade_while_interacting: bool,
+	/// The colors used to draw the overlays, such as selections, snapping indicators, and text bounds.
+	pub overlay_colors: OverlayColors,
+	/// What the drawing tools' crosshair cursor is replaced with in the viewport.
+	pub cursor_style: CursorStyle,
+	/// Side length (in CSS pixels) of the precision cursor.
+	pub precision_cursor_size: u32,
+	pub keymap_preset: KeymapPreset,
+	/// Whether pressing a modifier key toggles it on until it's pressed again, for those who can't hold several keys at once.
+	pub sticky_modifier_keys: bool,
+	pub custom_shortcuts: Vec<CustomShortcut>,
+	pub workspace_layouts: Vec<WorkspaceLayout>,
+	/// The tools and commands in the slots of the radial menu, clockwise from the top.
+	pub radial_menu_items: Vec<RadialMenuItem>,
+	/// Whether holding down the right mouse button in the viewport opens the radial menu.
+	pub radial_menu_on_hold: bool,
+	/// The recorded sequences of actions which can be replayed from the Edit menu.
+	pub action_macros: Vec<ActionMacro>,
+	/// The gradient presets saved by the user, listed after the bundled ones.
+	pub gradient_presets: Vec<GradientPreset>,
+	/// The URL of the shared library last connected to in the Library panel.
+	pub library_url: String,
+	/// The actions run after each time a document is saved, in order.
+	pub save_actions: Vec<SaveAction>,
+	/// The application image layers are opened in to be edited externally, which is the system's default for PNG files if empty.
+	pub external_image_editor: String,
+	/// The options last chosen for each tool, serialized separately so a tool whose options no longer deserialize after an update just gets its defaults.
+	pub tool_options: HashMap<ToolType, String>,
 }
 
 impl PreferencesMessageHandler {
@@ -24,9 +72,53 @@ impl PreferencesMessageHandler {
 		EditorPreferences {
 			imaginate_hostname: self.imaginate_server_hostname.clone(),
 			use_vello: self.use_vello && self.supports_wgpu(),
+			memory_budget: self.memory_budget_mebibytes as u64 * 1024 * 1024,
+			viewport_antialiasing: self.viewport_antialiasing,
 		}
 	}
 
+	pub fn mapping_variant(&self) -> MappingVariant {
+		if self.zoom_with_scroll { MappingVariant::ZoomWithScroll } else { MappingVariant::Default }
+	}
+
+	fn send_keymap(&self, responses: &mut VecDeque<Message>) {
+		responses.add(KeyMappingMessage::SetKeymap {
+			preset: self.keymap_preset,
+			middle_mouse_drag: self.middle_mouse_drag,
+			custom_shortcuts: self.custom_shortcuts.clone(),
+		});
+	}
+
+	fn send_cursor_style(&self, responses: &mut VecDeque<Message>) {
+		responses.add(FrontendMessage::UpdateCursorStyle {
+			style: self.cursor_style,
+			size: self.precision_cursor_size,
+		});
+	}
+
+	/// Finds a gradient preset by name, preferring one saved by the user over a bundled one with the same name.
+	pub fn gradient_preset(&self, name: &str) -> Option<GradientPreset> {
+		self.gradient_presets
+			.iter()
+			.find(|preset| preset.name == name)
+			.cloned()
+			.or_else(|| GradientPreset::bundled().into_iter().find(|preset| preset.name == name))
+	}
+
+	/// Lists the bundled and saved gradient presets in the color picker and the Gradient tool's options.
+	pub fn send_gradient_presets(&self, responses: &mut VecDeque<Message>) {
+		responses.add(FrontendMessage::UpdateGradientPresets {
+			bundled: GradientPreset::bundled(),
+			saved: self.gradient_presets.clone(),
+		});
+		responses.add(GradientToolMessage::RefreshPresets);
+	}
+
+	/// The distance (in document units) moved by a single arrow key press, either the small or the large (Shift) nudge.
+	pub fn nudge_distance(&self, large: bool) -> f64 {
+		if large { self.large_nudge_distance } else { self.nudge_distance }
+	}
+
 	pub fn supports_wgpu(&self) -> bool {
 		graph_craft::wasm_application_io::wgpu_available().unwrap_or_default()
 	}
@@ -37,6 +129,8 @@ impl Default for PreferencesMessageHandler {
 		let EditorPreferences {
 			imaginate_hostname: host_name,
 			use_vello,
+			memory_budget,
+			viewport_antialiasing,
 		} = Default::default();
 
 		Self {
@@ -44,9 +138,34 @@ impl Default for PreferencesMessageHandler {
 			imaginate_refresh_frequency: 1.,
 			selection_mode: SelectionMode::Touched,
 			zoom_with_scroll: matches!(MappingVariant::default(), MappingVariant::ZoomWithScroll),
+			middle_mouse_drag: MiddleMouseDrag::default(),
 			use_vello,
 			vector_meshes: false,
 			graph_wire_style: GraphWireStyle::default(),
+			memory_budget_mebibytes: (memory_budget / (1024 * 1024)) as u32,
+			undo_history_length: crate::consts::MAX_UNDO_HISTORY_LEN,
+			nudge_distance: crate::consts::NUDGE_AMOUNT,
+			large_nudge_distance: crate::consts::BIG_NUDGE_AMOUNT,
+			auto_pan_edge_margin: crate::consts::AUTO_PAN_EDGE_MARGIN,
+			auto_pan_speed: crate::consts::DRAG_BEYOND_VIEWPORT_SPEED_FACTOR,
+			auto_pan_acceleration: 0.,
+			viewport_antialiasing,
+			degrade_while_interacting: false,
+			overlay_colors: OverlayColors::default(),
+			cursor_style: CursorStyle::default(),
+			precision_cursor_size: crate::consts::PRECISION_CURSOR_SIZE,
+			keymap_preset: KeymapPreset::default(),
+			sticky_modifier_keys: false,
+			custom_shortcuts: Vec::new(),
+			workspace_layouts: Vec::new(),
+			radial_menu_items: RadialMenuItem::defaults(),
+			radial_menu_on_hold: true,
+			action_macros: Vec::new(),
+			gradient_presets: Vec::new(),
+			library_url: String::new(),
+			save_actions: Vec::new(),
+			external_image_editor: String::new(),
+			tool_options: HashMap::new(),
 		}
 	}
 }
@@ -68,13 +187,21 @@ impl MessageHandler<PreferencesMessage, ()> for PreferencesMessageHandler {
 					responses.add(PreferencesMessage::ModifyLayout {
 						zoom_with_scroll: self.zoom_with_scroll,
 					});
+					self.send_keymap(responses);
+					self.send_cursor_style(responses);
+					self.send_gradient_presets(responses);
+					responses.add(MenuBarMessage::SendLayout);
 				}
 			}
 			PreferencesMessage::ResetToDefaults => {
 				refresh_dialog(responses);
 				responses.add(KeyMappingMessage::ModifyMapping(MappingVariant::Default));
 
-				*self = Self::default()
+				*self = Self::default();
+				self.send_keymap(responses);
+				self.send_cursor_style(responses);
+				self.send_gradient_presets(responses);
+				responses.add(MenuBarMessage::SendLayout);
 			}
 
 			// Per-preference messages
@@ -89,8 +216,11 @@ impl MessageHandler<PreferencesMessage, ()> for PreferencesMessageHandler {
 			PreferencesMessage::ModifyLayout { zoom_with_scroll } => {
 				self.zoom_with_scroll = zoom_with_scroll;
 
-				let variant = if zoom_with_scroll { MappingVariant::ZoomWithScroll } else { MappingVariant::Default };
-				responses.add(KeyMappingMessage::ModifyMapping(variant));
+				responses.add(KeyMappingMessage::ModifyMapping(self.mapping_variant()));
+			}
+			PreferencesMessage::MiddleMouseDrag { drag } => {
+				self.middle_mouse_drag = drag;
+				self.send_keymap(responses);
 			}
 			PreferencesMessage::SelectionMode { selection_mode } => {
 				self.selection_mode = selection_mode;
@@ -99,6 +229,147 @@ impl MessageHandler<PreferencesMessage, ()> for PreferencesMessageHandler {
 				self.graph_wire_style = style;
 				responses.add(NodeGraphMessage::SendGraph);
 			}
+			PreferencesMessage::MemoryBudget { mebibytes } => {
+				self.memory_budget_mebibytes = mebibytes;
+				responses.add(PortfolioMessage::EditorPreferences);
+			}
+			PreferencesMessage::UndoHistoryLength { length } => {
+				self.undo_history_length = length;
+			}
+			PreferencesMessage::NudgeDistance { distance } => {
+				self.nudge_distance = distance;
+			}
+			PreferencesMessage::LargeNudgeDistance { distance } => {
+				self.large_nudge_distance = distance;
+			}
+			PreferencesMessage::AutoPanEdgeMargin { margin } => {
+				self.auto_pan_edge_margin = margin;
+			}
+			PreferencesMessage::AutoPanSpeed { speed } => {
+				self.auto_pan_speed = speed;
+			}
+			PreferencesMessage::AutoPanAcceleration { acceleration } => {
+				self.auto_pan_acceleration = acceleration;
+			}
+			PreferencesMessage::ViewportAntialiasing { antialiasing } => {
+				self.viewport_antialiasing = antialiasing;
+				responses.add(PortfolioMessage::EditorPreferences);
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+			}
+			PreferencesMessage::DegradeWhileInteracting { enabled } => {
+				self.degrade_while_interacting = enabled;
+			}
+			PreferencesMessage::OverlayColors { colors } => {
+				self.overlay_colors = colors;
+				responses.add(OverlaysMessage::Draw);
+			}
+			PreferencesMessage::CursorStyle { style } => {
+				self.cursor_style = style;
+				self.send_cursor_style(responses);
+			}
+			PreferencesMessage::PrecisionCursorSize { size } => {
+				self.precision_cursor_size = size.clamp(1, crate::consts::PRECISION_CURSOR_MAX_SIZE);
+				self.send_cursor_style(responses);
+			}
+			PreferencesMessage::KeymapPreset { preset } => {
+				self.keymap_preset = preset;
+				self.send_keymap(responses);
+			}
+			PreferencesMessage::StickyModifierKeys { enabled } => {
+				self.sticky_modifier_keys = enabled;
+			}
+			PreferencesMessage::RebindShortcut { action, shortcut } => match CustomShortcut::new(action.clone(), &shortcut) {
+				Ok(custom_shortcut) => {
+					self.custom_shortcuts.retain(|custom| custom.action != action);
+					self.custom_shortcuts.push(custom_shortcut);
+					self.send_keymap(responses);
+					responses.add(DialogMessage::RequestKeyboardShortcutsDialog);
+				}
+				// The shortcut is left unchanged, while the dialog keeps what was typed and shows why it was rejected so it can be corrected
+				Err(error) => responses.add(DialogMessage::RequestKeyboardShortcutsDialogWithRejectedShortcut { action, shortcut, error }),
+			},
+			PreferencesMessage::ResetShortcut { action } => {
+				self.custom_shortcuts.retain(|custom| custom.action != action);
+				self.send_keymap(responses);
+				responses.add(DialogMessage::RequestKeyboardShortcutsDialog);
+			}
+			PreferencesMessage::SaveWorkspaceLayout { layout } => {
+				// Saving under an existing name replaces that layout in place
+				match self.workspace_layouts.iter_mut().find(|existing| existing.name == layout.name) {
+					Some(existing) => *existing = layout,
+					None => self.workspace_layouts.push(layout),
+				}
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			PreferencesMessage::DeleteWorkspaceLayout { name } => {
+				self.workspace_layouts.retain(|layout| layout.name != name);
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			PreferencesMessage::RadialMenuItem { slot, item } => {
+				if slot >= RADIAL_MENU_SLOTS {
+					return;
+				}
+				self.radial_menu_items.resize(RADIAL_MENU_SLOTS, RadialMenuItem::Empty);
+				self.radial_menu_items[slot] = item;
+			}
+			PreferencesMessage::RadialMenuOnHold { enabled } => {
+				self.radial_menu_on_hold = enabled;
+			}
+			PreferencesMessage::SaveActionMacro { action_macro } => {
+				// Saving under an existing name replaces that macro in place
+				match self.action_macros.iter_mut().find(|existing| existing.name == action_macro.name) {
+					Some(existing) => *existing = action_macro,
+					None => self.action_macros.push(action_macro),
+				}
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			PreferencesMessage::DeleteActionMacro { name } => {
+				self.action_macros.retain(|action_macro| action_macro.name != name);
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			PreferencesMessage::SaveGradientPreset { preset } => {
+				// Saving under an existing name replaces that preset in place
+				match self.gradient_presets.iter_mut().find(|existing| existing.name == preset.name) {
+					Some(existing) => *existing = preset,
+					None => self.gradient_presets.push(preset),
+				}
+				self.send_gradient_presets(responses);
+			}
+			PreferencesMessage::DeleteGradientPreset { name } => {
+				self.gradient_presets.retain(|preset| preset.name != name);
+				self.send_gradient_presets(responses);
+			}
+			PreferencesMessage::LibraryUrl { url } => {
+				self.library_url = url;
+			}
+			PreferencesMessage::ExternalImageEditor { path } => {
+				self.external_image_editor = path;
+			}
+			PreferencesMessage::AddSaveAction { action } => {
+				self.save_actions.push(action);
+				refresh_dialog(responses);
+			}
+			PreferencesMessage::EditSaveAction { index, edit } => {
+				if let Some(action) = self.save_actions.get_mut(index) {
+					action.apply(edit);
+				}
+			}
+			PreferencesMessage::DeleteSaveAction { index } => {
+				if index < self.save_actions.len() {
+					self.save_actions.remove(index);
+				}
+				refresh_dialog(responses);
+			}
+			PreferencesMessage::ToolOptions { tool_type, options } => {
+				// Avoid saving the preferences again when nothing changed, like when a tool only picked up the new working colors
+				if self.tool_options.get(&tool_type) == Some(&options) {
+					return;
+				}
+				self.tool_options.insert(tool_type, options);
+			}
+			PreferencesMessage::ResetToolOptions => {
+				self.tool_options.clear();
+			}
 		}
 		// TODO: Reenable when Imaginate is restored (and move back up one line since the auto-formatter doesn't like it in that block)
 		// PreferencesMessage::ImaginateRefreshFrequency { seconds } => {
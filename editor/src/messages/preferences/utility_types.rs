@@ -1,3 +1,8 @@
+use crate::messages::frontend::utility_types::FileType;
+use crate::messages::workspace::utility_types::FloatingPanel;
+use graphene_core::Color;
+use graphene_core::vector::style::GradientStops;
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type, Hash)]
 pub enum SelectionMode {
 	#[default]
@@ -25,3 +30,169 @@ impl SelectionMode {
 		}
 	}
 }
+
+/// What replaces the crosshair cursor used by the drawing tools while over the viewport.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type, Hash)]
+pub enum CursorStyle {
+	#[default]
+	Standard = 0,
+	FullViewportCrosshair = 1,
+	Precision = 2,
+}
+
+impl std::fmt::Display for CursorStyle {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			CursorStyle::Standard => write!(f, "Standard"),
+			CursorStyle::FullViewportCrosshair => write!(f, "Full Viewport"),
+			CursorStyle::Precision => write!(f, "Precision"),
+		}
+	}
+}
+
+impl CursorStyle {
+	pub fn tooltip_description(&self) -> &'static str {
+		match self {
+			CursorStyle::Standard => "Use the system's crosshair cursor",
+			CursorStyle::FullViewportCrosshair => "Extend the crosshair's lines across the whole viewport, which helps with lining things up",
+			CursorStyle::Precision => "Use a larger crosshair cursor of the chosen size, which is easier to see on high resolution screens",
+		}
+	}
+}
+
+/// A named arrangement of the workspace's panels, which can be saved and switched between.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(default)]
+pub struct WorkspaceLayout {
+	pub name: String,
+	/// The relative sizes of the workspace's subdivisions, paired with the names the frontend gives them.
+	pub panel_sizes: Vec<(String, f64)>,
+	pub node_graph_open: bool,
+	pub spreadsheet_open: bool,
+	pub split_view_open: bool,
+	pub timeline_open: bool,
+	pub color_harmony_open: bool,
+	pub plugins_open: bool,
+	pub console_open: bool,
+	pub collaboration_open: bool,
+	pub compare_open: bool,
+	pub library_open: bool,
+	pub data_merge_open: bool,
+	/// The panels undocked into floating windows.
+	pub floating_panels: Vec<FloatingPanel>,
+}
+
+/// A named set of gradient stops which can be picked from the gradient picker and the Gradient tool's options, or dropped onto layers.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(default)]
+pub struct GradientPreset {
+	pub name: String,
+	/// The gradient's stops, with colors in gamma space like those of a layer's fill.
+	pub stops: GradientStops,
+}
+
+impl GradientPreset {
+	/// The presets that come with Graphite, which are listed before those saved by the user.
+	pub fn bundled() -> Vec<Self> {
+		let preset = |name: &str, stops: &[(f64, &str)]| Self {
+			name: name.to_string(),
+			stops: GradientStops::new(stops.iter().filter_map(|&(position, hex)| Some((position, Color::from_rgb_str(hex)?.to_gamma_srgb()))).collect()),
+		};
+
+		vec![
+			preset("Black to White", &[(0., "000000"), (1., "ffffff")]),
+			preset("Sunset", &[(0., "2b1055"), (0.5, "d53369"), (1., "fbd72b")]),
+			preset("Ocean", &[(0., "0b3866"), (0.5, "1c92d2"), (1., "a8ebf2")]),
+			preset("Forest", &[(0., "134e19"), (1., "a8d66c")]),
+			preset("Fire", &[(0., "7a0a00"), (0.4, "e63d0e"), (0.75, "f7a325"), (1., "fff06b")]),
+			preset("Pastel", &[(0., "f6c1d8"), (0.5, "c8c2f4"), (1., "b9ecdd")]),
+			preset(
+				"Rainbow",
+				&[
+					(0., "ff0000"),
+					(1. / 6., "ffff00"),
+					(2. / 6., "00ff00"),
+					(3. / 6., "00ffff"),
+					(4. / 6., "0000ff"),
+					(5. / 6., "ff00ff"),
+					(1., "ff0000"),
+				],
+			),
+		]
+	}
+}
+
+/// An action run automatically each time a document is saved, which keeps files generated from it in sync.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct SaveAction {
+	pub enabled: bool,
+	pub kind: SaveActionKind,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum SaveActionKind {
+	/// Exports the document, or each of its artboards, to a folder on desktop or as downloads in the browser.
+	Export {
+		file_type: FileType,
+		scale_factor: f64,
+		each_artboard: bool,
+		transparent_background: bool,
+		/// The folder the files are written to, where an empty folder has them downloaded like a normal export.
+		folder: String,
+	},
+	/// Runs a shell command, which is only possible on desktop. The document's name is given to it in the `GRAPHITE_DOCUMENT_NAME` environment variable.
+	Command { command: String },
+	/// Sends a POST request with the document's name to a URL.
+	Webhook { url: String },
+}
+
+impl SaveActionKind {
+	pub fn export() -> Self {
+		Self::Export {
+			file_type: FileType::Png,
+			scale_factor: 1.,
+			each_artboard: false,
+			transparent_background: false,
+			folder: String::new(),
+		}
+	}
+}
+
+impl std::fmt::Display for SaveActionKind {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			SaveActionKind::Export { .. } => write!(f, "Export"),
+			SaveActionKind::Command { .. } => write!(f, "Command"),
+			SaveActionKind::Webhook { .. } => write!(f, "Webhook"),
+		}
+	}
+}
+
+/// A change to one setting of a save action, made from the preferences dialog.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum SaveActionEdit {
+	Enabled(bool),
+	FileType(FileType),
+	ScaleFactor(f64),
+	EachArtboard(bool),
+	TransparentBackground(bool),
+	Folder(String),
+	Command(String),
+	Url(String),
+}
+
+impl SaveAction {
+	pub fn apply(&mut self, edit: SaveActionEdit) {
+		match (&mut self.kind, edit) {
+			(_, SaveActionEdit::Enabled(enabled)) => self.enabled = enabled,
+			(SaveActionKind::Export { file_type, .. }, SaveActionEdit::FileType(new)) => *file_type = new,
+			(SaveActionKind::Export { scale_factor, .. }, SaveActionEdit::ScaleFactor(new)) => *scale_factor = new,
+			(SaveActionKind::Export { each_artboard, .. }, SaveActionEdit::EachArtboard(new)) => *each_artboard = new,
+			(SaveActionKind::Export { transparent_background, .. }, SaveActionEdit::TransparentBackground(new)) => *transparent_background = new,
+			(SaveActionKind::Export { folder, .. }, SaveActionEdit::Folder(new)) => *folder = new,
+			(SaveActionKind::Command { command }, SaveActionEdit::Command(new)) => *command = new,
+			(SaveActionKind::Webhook { url }, SaveActionEdit::Url(new)) => *url = new,
+			(kind, edit) => warn!("Can't apply {edit:?} to a {kind} save action"),
+		}
+	}
+}
@@ -2,12 +2,18 @@
 pub use crate::utility_traits::{ActionList, AsMessage, MessageHandler, ToDiscriminant, TransitiveChild};
 
 // Message, MessageData, MessageDiscriminant, MessageHandler
+pub use crate::messages::action_macro::{ActionMacroMessage, ActionMacroMessageData, ActionMacroMessageDiscriminant, ActionMacroMessageHandler};
 pub use crate::messages::animation::{AnimationMessage, AnimationMessageDiscriminant, AnimationMessageHandler};
 pub use crate::messages::broadcast::{BroadcastMessage, BroadcastMessageDiscriminant, BroadcastMessageHandler};
 pub use crate::messages::debug::{DebugMessage, DebugMessageDiscriminant, DebugMessageHandler};
+pub use crate::messages::dialog::command_palette_dialog::{CommandPaletteDialogMessage, CommandPaletteDialogMessageDiscriminant, CommandPaletteDialogMessageHandler};
+pub use crate::messages::dialog::contrast_check_dialog::{ContrastCheckDialogMessage, ContrastCheckDialogMessageDiscriminant, ContrastCheckDialogMessageHandler};
 pub use crate::messages::dialog::export_dialog::{ExportDialogMessage, ExportDialogMessageData, ExportDialogMessageDiscriminant, ExportDialogMessageHandler};
+pub use crate::messages::dialog::find_replace_dialog::{FindReplaceDialogMessage, FindReplaceDialogMessageData, FindReplaceDialogMessageDiscriminant, FindReplaceDialogMessageHandler};
+pub use crate::messages::dialog::glyph_palette_dialog::{GlyphPaletteDialogMessage, GlyphPaletteDialogMessageDiscriminant, GlyphPaletteDialogMessageHandler};
 pub use crate::messages::dialog::new_document_dialog::{NewDocumentDialogMessage, NewDocumentDialogMessageDiscriminant, NewDocumentDialogMessageHandler};
 pub use crate::messages::dialog::preferences_dialog::{PreferencesDialogMessage, PreferencesDialogMessageData, PreferencesDialogMessageDiscriminant, PreferencesDialogMessageHandler};
+pub use crate::messages::dialog::recolor_artwork_dialog::{RecolorArtworkDialogMessage, RecolorArtworkDialogMessageDiscriminant, RecolorArtworkDialogMessageHandler};
 pub use crate::messages::dialog::{DialogMessage, DialogMessageData, DialogMessageDiscriminant, DialogMessageHandler};
 pub use crate::messages::frontend::{FrontendMessage, FrontendMessageDiscriminant};
 pub use crate::messages::globals::{GlobalsMessage, GlobalsMessageDiscriminant, GlobalsMessageHandler};
@@ -15,19 +21,29 @@ pub use crate::messages::input_mapper::key_mapping::{KeyMappingMessage, KeyMappi
 pub use crate::messages::input_mapper::{InputMapperMessage, InputMapperMessageData, InputMapperMessageDiscriminant, InputMapperMessageHandler};
 pub use crate::messages::input_preprocessor::{InputPreprocessorMessage, InputPreprocessorMessageData, InputPreprocessorMessageDiscriminant, InputPreprocessorMessageHandler};
 pub use crate::messages::layout::{LayoutMessage, LayoutMessageDiscriminant, LayoutMessageHandler};
+pub use crate::messages::portfolio::collaboration::{CollaborationMessage, CollaborationMessageDiscriminant};
+pub use crate::messages::portfolio::color_harmony::{ColorHarmonyMessage, ColorHarmonyMessageDiscriminant};
+pub use crate::messages::portfolio::compare::{CompareMessage, CompareMessageDiscriminant};
+pub use crate::messages::portfolio::console::{ConsoleMessage, ConsoleMessageDiscriminant};
+pub use crate::messages::portfolio::data_merge::{DataMergeMessage, DataMergeMessageDiscriminant};
 pub use crate::messages::portfolio::document::graph_operation::{GraphOperationMessage, GraphOperationMessageData, GraphOperationMessageDiscriminant, GraphOperationMessageHandler};
 pub use crate::messages::portfolio::document::navigation::{NavigationMessage, NavigationMessageData, NavigationMessageDiscriminant, NavigationMessageHandler};
 pub use crate::messages::portfolio::document::node_graph::{NodeGraphMessage, NodeGraphMessageDiscriminant, NodeGraphMessageHandler};
 pub use crate::messages::portfolio::document::overlays::{OverlaysMessage, OverlaysMessageData, OverlaysMessageDiscriminant, OverlaysMessageHandler};
 pub use crate::messages::portfolio::document::properties_panel::{PropertiesPanelMessage, PropertiesPanelMessageDiscriminant, PropertiesPanelMessageHandler};
 pub use crate::messages::portfolio::document::{DocumentMessage, DocumentMessageData, DocumentMessageDiscriminant, DocumentMessageHandler};
+pub use crate::messages::portfolio::library::{LibraryMessage, LibraryMessageDiscriminant};
 pub use crate::messages::portfolio::menu_bar::{MenuBarMessage, MenuBarMessageDiscriminant, MenuBarMessageHandler};
+pub use crate::messages::portfolio::plugins::{PluginsMessage, PluginsMessageDiscriminant};
+pub use crate::messages::portfolio::split_view::{SplitViewMessage, SplitViewMessageData, SplitViewMessageDiscriminant, SplitViewMessageHandler};
 pub use crate::messages::portfolio::spreadsheet::{SpreadsheetMessage, SpreadsheetMessageDiscriminant};
+pub use crate::messages::portfolio::timeline::{TimelineMessage, TimelineMessageData, TimelineMessageDiscriminant, TimelineMessageHandler};
 pub use crate::messages::portfolio::{PortfolioMessage, PortfolioMessageData, PortfolioMessageDiscriminant, PortfolioMessageHandler};
 pub use crate::messages::preferences::{PreferencesMessage, PreferencesMessageDiscriminant, PreferencesMessageHandler};
+pub use crate::messages::radial_menu::{RadialMenuMessage, RadialMenuMessageData, RadialMenuMessageDiscriminant, RadialMenuMessageHandler};
 pub use crate::messages::tool::transform_layer::{TransformLayerMessage, TransformLayerMessageDiscriminant, TransformLayerMessageHandler};
 pub use crate::messages::tool::{ToolMessage, ToolMessageData, ToolMessageDiscriminant, ToolMessageHandler};
-pub use crate::messages::workspace::{WorkspaceMessage, WorkspaceMessageDiscriminant, WorkspaceMessageHandler};
+pub use crate::messages::workspace::{WorkspaceMessage, WorkspaceMessageData, WorkspaceMessageDiscriminant, WorkspaceMessageHandler};
 
 // Message, MessageDiscriminant
 pub use crate::messages::broadcast::broadcast_event::{BroadcastEvent, BroadcastEventDiscriminant};
@@ -44,6 +60,7 @@ pub use crate::messages::tool::tool_messages::line_tool::{LineToolMessage, LineT
 pub use crate::messages::tool::tool_messages::navigate_tool::{NavigateToolMessage, NavigateToolMessageDiscriminant};
 pub use crate::messages::tool::tool_messages::path_tool::{PathToolMessage, PathToolMessageDiscriminant};
 pub use crate::messages::tool::tool_messages::pen_tool::{PenToolMessage, PenToolMessageDiscriminant};
+pub use crate::messages::tool::tool_messages::plugin_tool::{PluginToolMessage, PluginToolMessageDiscriminant};
 pub use crate::messages::tool::tool_messages::polygon_tool::{PolygonToolMessage, PolygonToolMessageDiscriminant};
 pub use crate::messages::tool::tool_messages::rectangle_tool::{RectangleToolMessage, RectangleToolMessageDiscriminant};
 pub use crate::messages::tool::tool_messages::select_tool::{SelectToolMessage, SelectToolMessageDiscriminant};
@@ -0,0 +1,9 @@
+mod radial_menu_message;
+mod radial_menu_message_handler;
+
+pub mod utility_types;
+
+#[doc(inline)]
+pub use radial_menu_message::{RadialMenuMessage, RadialMenuMessageDiscriminant};
+#[doc(inline)]
+pub use radial_menu_message_handler::{RadialMenuMessageData, RadialMenuMessageHandler};
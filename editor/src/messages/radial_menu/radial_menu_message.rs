@@ -0,0 +1,10 @@
+use crate::messages::prelude::*;
+
+#[impl_message(Message, RadialMenu)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum RadialMenuMessage {
+	// Messages
+	Activate { index: usize },
+	Open,
+	OpenOnHold,
+}
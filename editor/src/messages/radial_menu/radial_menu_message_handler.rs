@@ -0,0 +1,70 @@
+use super::utility_types::{FrontendRadialMenuItem, RadialMenuItem};
+use crate::messages::prelude::*;
+
+pub struct RadialMenuMessageData<'a> {
+	pub ipp: &'a InputPreprocessorMessageHandler,
+	pub portfolio: &'a PortfolioMessageHandler,
+	pub preferences: &'a PreferencesMessageHandler,
+}
+
+/// The radial menu arranges the user's chosen tools and commands around the cursor, so they can be picked without moving over to the toolbar or menus.
+#[derive(Debug, Clone, Default)]
+pub struct RadialMenuMessageHandler {
+	/// The items shown by the radial menu when it was last opened, which the frontend refers to by index.
+	items: Vec<RadialMenuItem>,
+}
+
+impl MessageHandler<RadialMenuMessage, RadialMenuMessageData<'_>> for RadialMenuMessageHandler {
+	fn process_message(&mut self, message: RadialMenuMessage, responses: &mut VecDeque<Message>, data: RadialMenuMessageData) {
+		let RadialMenuMessageData { ipp, portfolio, preferences } = data;
+
+		match message {
+			// Messages
+			RadialMenuMessage::Activate { index } => {
+				let Some(item) = self.items.get(index) else { return };
+				responses.add(item.message());
+			}
+			RadialMenuMessage::Open => self.open(true, ipp, portfolio, preferences, responses),
+			RadialMenuMessage::OpenOnHold => {
+				if preferences.radial_menu_on_hold {
+					self.open(false, ipp, portfolio, preferences, responses);
+				}
+			}
+		}
+	}
+
+	fn actions(&self) -> ActionList {
+		actions!(RadialMenuMessageDiscriminant;
+			Open,
+		)
+	}
+}
+
+impl RadialMenuMessageHandler {
+	fn open(&mut self, opened_by_key: bool, ipp: &InputPreprocessorMessageHandler, portfolio: &PortfolioMessageHandler, preferences: &PreferencesMessageHandler, responses: &mut VecDeque<Message>) {
+		// The tools and commands all act on a document
+		if portfolio.active_document().is_none() {
+			return;
+		}
+
+		self.items = preferences.radial_menu_items.iter().copied().filter(|&item| item != RadialMenuItem::Empty).collect();
+		if self.items.is_empty() {
+			return;
+		}
+
+		let items = self
+			.items
+			.iter()
+			.map(|item| FrontendRadialMenuItem {
+				label: item.label(),
+				icon: item.icon(),
+			})
+			.collect();
+		let position = ipp.viewport_bounds.top_left + ipp.mouse.position;
+		responses.add(FrontendMessage::DisplayRadialMenu {
+			items,
+			position: (position.x, position.y),
+			opened_by_key,
+		});
+	}
+}
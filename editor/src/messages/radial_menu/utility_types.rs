@@ -0,0 +1,140 @@
+use crate::messages::portfolio::document::utility_types::clipboards::Clipboard;
+use crate::messages::portfolio::document::utility_types::misc::GroupFolderType;
+use crate::messages::prelude::*;
+use crate::messages::tool::utility_types::{ToolAvailability, ToolMetadata, ToolType, list_tools_in_groups};
+
+/// The number of slots arranged around the radial menu.
+pub const RADIAL_MENU_SLOTS: usize = 8;
+
+/// A tool or command which can be placed in one of the slots of the radial menu.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum RadialMenuItem {
+	#[default]
+	Empty,
+	Tool(ToolType),
+	Undo,
+	Redo,
+	Copy,
+	Paste,
+	Duplicate,
+	Delete,
+	Group,
+	ZoomToSelection,
+	SwapColors,
+	ToggleNodeGraph,
+	PlaceholderText,
+}
+
+impl RadialMenuItem {
+	pub const COMMANDS: [RadialMenuItem; 11] = [
+		RadialMenuItem::Undo,
+		RadialMenuItem::Redo,
+		RadialMenuItem::Copy,
+		RadialMenuItem::Paste,
+		RadialMenuItem::Duplicate,
+		RadialMenuItem::Delete,
+		RadialMenuItem::Group,
+		RadialMenuItem::ZoomToSelection,
+		RadialMenuItem::SwapColors,
+		RadialMenuItem::ToggleNodeGraph,
+		RadialMenuItem::PlaceholderText,
+	];
+
+	/// The items the radial menu starts out with, a mix of drawing tools and the commands most often needed while drawing.
+	pub fn defaults() -> Vec<RadialMenuItem> {
+		vec![
+			RadialMenuItem::Tool(ToolType::Select),
+			RadialMenuItem::Tool(ToolType::Path),
+			RadialMenuItem::Tool(ToolType::Pen),
+			RadialMenuItem::Tool(ToolType::Freehand),
+			RadialMenuItem::Tool(ToolType::Brush),
+			RadialMenuItem::Tool(ToolType::Eyedropper),
+			RadialMenuItem::Undo,
+			RadialMenuItem::Redo,
+		]
+	}
+
+	/// Every item which can be chosen for a slot, beginning with the empty slot, then the tools, then the commands.
+	pub fn list() -> Vec<RadialMenuItem> {
+		let tools = list_tools_in_groups().into_iter().flatten().filter_map(|tool| match tool {
+			ToolAvailability::Available(tool) => Some(RadialMenuItem::Tool(tool.tool_type())),
+			ToolAvailability::ComingSoon(_) => None,
+		});
+
+		std::iter::once(RadialMenuItem::Empty).chain(tools).chain(RadialMenuItem::COMMANDS).collect()
+	}
+
+	pub fn label(&self) -> String {
+		match self {
+			RadialMenuItem::Empty => "Empty".into(),
+			RadialMenuItem::Tool(tool_type) => tool_metadata(*tool_type).map(|(label, _)| label).unwrap_or_else(|| format!("{tool_type:?} Tool")),
+			RadialMenuItem::Undo => "Undo".into(),
+			RadialMenuItem::Redo => "Redo".into(),
+			RadialMenuItem::Copy => "Copy".into(),
+			RadialMenuItem::Paste => "Paste".into(),
+			RadialMenuItem::Duplicate => "Duplicate".into(),
+			RadialMenuItem::Delete => "Delete".into(),
+			RadialMenuItem::Group => "Group".into(),
+			RadialMenuItem::ZoomToSelection => "Zoom to Selection".into(),
+			RadialMenuItem::SwapColors => "Swap Colors".into(),
+			RadialMenuItem::ToggleNodeGraph => "Node Graph".into(),
+			RadialMenuItem::PlaceholderText => "Placeholder Text".into(),
+		}
+	}
+
+	pub fn icon(&self) -> Option<String> {
+		let icon = match self {
+			RadialMenuItem::Empty => return None,
+			RadialMenuItem::Tool(tool_type) => return tool_metadata(*tool_type).map(|(_, icon)| icon),
+			RadialMenuItem::Undo => "HistoryUndo",
+			RadialMenuItem::Redo => "HistoryRedo",
+			RadialMenuItem::Copy => "Copy",
+			RadialMenuItem::Paste => "Paste",
+			RadialMenuItem::Duplicate => "Copy",
+			RadialMenuItem::Delete => "Trash",
+			RadialMenuItem::Group => "Folder",
+			RadialMenuItem::ZoomToSelection => "FrameSelected",
+			RadialMenuItem::SwapColors => "SwapHorizontal",
+			RadialMenuItem::ToggleNodeGraph => "GraphViewOpen",
+			RadialMenuItem::PlaceholderText => "NodeText",
+		};
+		Some(icon.into())
+	}
+
+	/// The message which runs this item when it's picked from the radial menu.
+	pub fn message(&self) -> Message {
+		match self {
+			RadialMenuItem::Empty => Message::NoOp,
+			RadialMenuItem::Tool(tool_type) => ToolMessage::ActivateTool { tool_type: *tool_type }.into(),
+			RadialMenuItem::Undo => DocumentMessage::Undo.into(),
+			RadialMenuItem::Redo => DocumentMessage::Redo.into(),
+			RadialMenuItem::Copy => PortfolioMessage::Copy { clipboard: Clipboard::Device }.into(),
+			RadialMenuItem::Paste => FrontendMessage::TriggerPaste.into(),
+			RadialMenuItem::Duplicate => DocumentMessage::DuplicateSelectedLayers.into(),
+			RadialMenuItem::Delete => DocumentMessage::DeleteSelectedLayers.into(),
+			RadialMenuItem::Group => DocumentMessage::GroupSelectedLayers {
+				group_folder_type: GroupFolderType::Layer,
+			}
+			.into(),
+			RadialMenuItem::ZoomToSelection => NavigationMessage::FitViewportToSelection.into(),
+			RadialMenuItem::SwapColors => ToolMessage::SwapColors.into(),
+			RadialMenuItem::ToggleNodeGraph => DocumentMessage::GraphViewOverlayToggle.into(),
+			RadialMenuItem::PlaceholderText => DocumentMessage::FillSelectedTextWithPlaceholder.into(),
+		}
+	}
+}
+
+/// The label and icon of a tool, or `None` if the tool isn't available yet.
+fn tool_metadata(tool_type: ToolType) -> Option<(String, String)> {
+	list_tools_in_groups().into_iter().flatten().find_map(|tool| match tool {
+		ToolAvailability::Available(tool) if tool.tool_type() == tool_type => Some((tool.tooltip(), tool.icon_name())),
+		_ => None,
+	})
+}
+
+/// An item of the radial menu as shown by the frontend.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct FrontendRadialMenuItem {
+	pub label: String,
+	pub icon: Option<String>,
+}
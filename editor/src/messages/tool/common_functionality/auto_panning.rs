@@ -1,16 +1,19 @@
-use crate::consts::{DRAG_BEYOND_VIEWPORT_MAX_OVEREXTENSION_PIXELS, DRAG_BEYOND_VIEWPORT_SPEED_FACTOR};
+use crate::consts::DRAG_BEYOND_VIEWPORT_MAX_OVEREXTENSION_PIXELS;
 use crate::messages::prelude::*;
 use crate::messages::tool::tool_messages::tool_prelude::*;
 
 #[derive(Clone, Debug, Default)]
 pub struct AutoPanning {
 	subscribed_to_animation_frame: bool,
+	/// How long the pointer has been held within the edge margin or beyond the viewport, which speeds up the panning when acceleration is enabled.
+	seconds_panning: f64,
 }
 
 impl AutoPanning {
 	pub fn start(&mut self, messages: &[Message], responses: &mut VecDeque<Message>) {
 		if !self.subscribed_to_animation_frame {
 			self.subscribed_to_animation_frame = true;
+			self.seconds_panning = 0.;
 
 			for message in messages {
 				responses.add(BroadcastMessage::SubscribeEvent {
@@ -34,55 +37,52 @@ impl AutoPanning {
 		}
 	}
 
-	pub fn setup_by_mouse_position(&mut self, input: &InputPreprocessorMessageHandler, messages: &[Message], responses: &mut VecDeque<Message>) {
-		let mouse_position = input.mouse.position;
-		let viewport_size = input.viewport_bounds.size();
-		let is_pointer_outside_edge = mouse_position.x < 0. || mouse_position.x > viewport_size.x || mouse_position.y < 0. || mouse_position.y > viewport_size.y;
-
-		match is_pointer_outside_edge {
+	pub fn setup_by_mouse_position(&mut self, input: &InputPreprocessorMessageHandler, preferences: &PreferencesMessageHandler, messages: &[Message], responses: &mut VecDeque<Message>) {
+		match Self::edge_overextension(input, preferences) != DVec2::ZERO {
 			true => self.start(messages, responses),
 			false => self.stop(messages, responses),
 		}
 	}
 
-	/// Shifts the viewport when the mouse reaches the edge of the viewport.
+	/// Shifts the viewport when the mouse reaches the edge margin of the viewport or goes beyond it.
 	///
-	/// If the mouse was beyond any edge, it returns the amount shifted. Otherwise it returns None.
-	/// The shift is proportional to the distance between edge and mouse, and to the duration of the frame.
-	/// It is also guaranteed to be integral.
-	pub fn shift_viewport(&self, input: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) -> Option<DVec2> {
+	/// If the mouse was within the margin or beyond any edge, it returns the amount shifted. Otherwise it returns None.
+	/// The shift is proportional to the distance the mouse has gone past the margin, to the duration of the frame, and to the panning speed preference.
+	/// It grows the longer the mouse is held there if acceleration is enabled in the preferences, and it is guaranteed to be integral.
+	pub fn shift_viewport(&mut self, input: &InputPreprocessorMessageHandler, preferences: &PreferencesMessageHandler, responses: &mut VecDeque<Message>) -> Option<DVec2> {
 		if !self.subscribed_to_animation_frame {
 			return None;
 		}
 
-		let viewport_size = input.viewport_bounds.size();
-		let mouse_position = input.mouse.position.clamp(
-			DVec2::ZERO - DVec2::splat(DRAG_BEYOND_VIEWPORT_MAX_OVEREXTENSION_PIXELS),
-			viewport_size + DVec2::splat(DRAG_BEYOND_VIEWPORT_MAX_OVEREXTENSION_PIXELS),
-		);
-		let mouse_position_percent = mouse_position / viewport_size;
-
-		let mut shift_percent = DVec2::ZERO;
-
-		if mouse_position_percent.x < 0. {
-			shift_percent.x = -mouse_position_percent.x;
-		} else if mouse_position_percent.x > 1. {
-			shift_percent.x = 1. - mouse_position_percent.x;
-		}
-
-		if mouse_position_percent.y < 0. {
-			shift_percent.y = -mouse_position_percent.y;
-		} else if mouse_position_percent.y > 1. {
-			shift_percent.y = 1. - mouse_position_percent.y;
-		}
-
-		if shift_percent.x == 0. && shift_percent.y == 0. {
+		let overextension = Self::edge_overextension(input, preferences);
+		if overextension == DVec2::ZERO {
+			self.seconds_panning = 0.;
 			return None;
 		}
 
 		let time_delta = input.frame_time.frame_duration()?.as_secs_f64();
-		let delta = (shift_percent * DRAG_BEYOND_VIEWPORT_SPEED_FACTOR * viewport_size * time_delta).round();
+		self.seconds_panning += time_delta;
+
+		let speed = preferences.auto_pan_speed * (1. + preferences.auto_pan_acceleration * self.seconds_panning);
+		let delta = (overextension * speed * time_delta).round();
 		responses.add(NavigationMessage::CanvasPan { delta });
 		Some(delta)
 	}
+
+	/// How far (in viewport pixels) the mouse is past the edge margin on each axis, pointing in the direction the viewport should shift.
+	/// The distance beyond the viewport's edge is limited so dragging far outside of it doesn't pan uncontrollably fast.
+	fn edge_overextension(input: &InputPreprocessorMessageHandler, preferences: &PreferencesMessageHandler) -> DVec2 {
+		let viewport_size = input.viewport_bounds.size();
+		// The margin can't reach past the middle of the viewport, so there's always somewhere to hold the mouse without panning
+		let margin = DVec2::splat(preferences.auto_pan_edge_margin.max(0.)).min(viewport_size / 2.);
+
+		let mouse_position = input.mouse.position.clamp(
+			DVec2::ZERO - DVec2::splat(DRAG_BEYOND_VIEWPORT_MAX_OVEREXTENSION_PIXELS),
+			viewport_size + DVec2::splat(DRAG_BEYOND_VIEWPORT_MAX_OVEREXTENSION_PIXELS),
+		);
+
+		let before_start = (margin - mouse_position).max(DVec2::ZERO);
+		let beyond_end = (mouse_position - (viewport_size - margin)).max(DVec2::ZERO);
+		before_start - beyond_end
+	}
 }
@@ -12,9 +12,14 @@ pub enum ToolColorType {
 }
 
 /// Color selector widgets seen in [`LayoutTarget::ToolOptions`] bar.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct ToolColorOptions {
 	pub custom_color: Option<Color>,
+	// The working colors come from the document, so they aren't saved with the tool options
+	#[serde(skip)]
 	pub primary_working_color: Option<Color>,
+	#[serde(skip)]
 	pub secondary_working_color: Option<Color>,
 	pub color_type: ToolColorType,
 }
@@ -1,4 +1,4 @@
-use crate::messages::portfolio::document::graph_operation::utility_types::TransformIn;
+use crate::messages::portfolio::document::graph_operation::utility_types::{ModifyInputsContext, TransformIn};
 use crate::messages::portfolio::document::node_graph::document_node_definitions;
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
 use crate::messages::portfolio::document::utility_types::network_interface::{FlowType, InputConnector, NodeNetworkInterface, NodeTemplate};
@@ -11,7 +11,8 @@ use graph_craft::document::{NodeId, NodeInput};
 use graphene_core::Color;
 use graphene_core::raster::BlendMode;
 use graphene_core::raster::image::ImageFrameTable;
-use graphene_core::text::{Font, TypesettingConfig};
+use graphene_core::text::{Font, TextSpan, TypesettingConfig};
+use graphene_core::vector::misc::WarpStyle;
 use graphene_core::vector::style::Gradient;
 use graphene_std::vector::{ManipulatorPointId, PointId, SegmentId, VectorModificationType};
 use std::collections::VecDeque;
@@ -331,6 +332,10 @@ pub fn get_text_id(layer: LayerNodeIdentifier, network_interface: &NodeNetworkIn
 	NodeGraphLayer::new(layer, network_interface).upstream_node_id_from_name("Text")
 }
 
+pub fn get_image_id(layer: LayerNodeIdentifier, network_interface: &NodeNetworkInterface) -> Option<NodeId> {
+	NodeGraphLayer::new(layer, network_interface).upstream_node_id_from_name("Image")
+}
+
 /// Gets properties from the Text node
 pub fn get_text(layer: LayerNodeIdentifier, network_interface: &NodeNetworkInterface) -> Option<(&String, &Font, TypesettingConfig)> {
 	let inputs = NodeGraphLayer::new(layer, network_interface).find_node_inputs("Text")?;
@@ -342,6 +347,27 @@ pub fn get_text(layer: LayerNodeIdentifier, network_interface: &NodeNetworkInter
 	let Some(&TaggedValue::F64(character_spacing)) = inputs[5].as_value() else { return None };
 	let Some(&TaggedValue::OptionalF64(max_width)) = inputs[6].as_value() else { return None };
 	let Some(&TaggedValue::OptionalF64(max_height)) = inputs[7].as_value() else { return None };
+	let Some(&TaggedValue::TextAlign(align)) = inputs[8].as_value() else { return None };
+	let Some(&TaggedValue::VerticalAlign(vertical_align)) = inputs[9].as_value() else { return None };
+	let Some(&TaggedValue::TextDirection(direction)) = inputs[14].as_value() else { return None };
+	let Some(&TaggedValue::WritingMode(writing_mode)) = inputs[15].as_value() else { return None };
+	let Some(&TaggedValue::TextOrientation(orientation)) = inputs[16].as_value() else { return None };
+	let Some(&TaggedValue::FontFeatures(features)) = inputs[17].as_value() else { return None };
+	let Some(&TaggedValue::FontVariations(variations)) = inputs[18].as_value() else { return None };
+	let Some(&TaggedValue::F64(baseline_shift)) = inputs[19].as_value() else { return None };
+	let Some(&TaggedValue::TextScript(script)) = inputs[20].as_value() else { return None };
+	let Some(&TaggedValue::WrapMode(wrap)) = inputs[21].as_value() else { return None };
+	let Some(&TaggedValue::F64(space_before)) = inputs[22].as_value() else { return None };
+	let Some(&TaggedValue::F64(space_after)) = inputs[23].as_value() else { return None };
+	let Some(&TaggedValue::Bool(underline)) = inputs[24].as_value() else { return None };
+	let Some(&TaggedValue::Bool(strikethrough)) = inputs[25].as_value() else { return None };
+	let Some(&TaggedValue::TabStops(tab_stops)) = inputs[26].as_value() else { return None };
+	let Some(&TaggedValue::Bool(hyphenate)) = inputs[27].as_value() else { return None };
+	let Some(&TaggedValue::TextLanguage(language)) = inputs[28].as_value() else { return None };
+	let Some(&TaggedValue::TextCase(case)) = inputs[29].as_value() else { return None };
+	let Some(&TaggedValue::F64(left_indent)) = inputs[30].as_value() else { return None };
+	let Some(&TaggedValue::F64(right_indent)) = inputs[31].as_value() else { return None };
+	let Some(&TaggedValue::F64(first_line_indent)) = inputs[32].as_value() else { return None };
 
 	let typesetting = TypesettingConfig {
 		font_size,
@@ -349,10 +375,104 @@ pub fn get_text(layer: LayerNodeIdentifier, network_interface: &NodeNetworkInter
 		max_width,
 		character_spacing,
 		max_height,
+		align,
+		vertical_align,
+		direction,
+		writing_mode,
+		orientation,
+		features,
+		variations,
+		baseline_shift,
+		script,
+		wrap,
+		space_before,
+		space_after,
+		underline,
+		strikethrough,
+		tab_stops,
+		hyphenate,
+		language,
+		case,
+		left_indent,
+		right_indent,
+		first_line_indent,
 	};
 	Some((text, font, typesetting))
 }
 
+/// Gets the spans styling ranges of the characters from the Text node
+pub fn get_text_spans(layer: LayerNodeIdentifier, network_interface: &NodeNetworkInterface) -> Option<&Vec<TextSpan>> {
+	let TaggedValue::TextSpans(spans) = NodeGraphLayer::new(layer, network_interface).find_input("Text", 10)? else {
+		return None;
+	};
+	Some(spans)
+}
+
+pub fn get_text_warp_id(layer: LayerNodeIdentifier, network_interface: &NodeNetworkInterface) -> Option<NodeId> {
+	NodeGraphLayer::new(layer, network_interface).upstream_node_id_from_name("Text Warp")
+}
+
+/// Gets the style, bend, and envelope corner offsets from the Text Warp node bending the outlines of a layer
+pub fn get_text_warp(layer: LayerNodeIdentifier, network_interface: &NodeNetworkInterface) -> Option<(WarpStyle, f64, [DVec2; 4])> {
+	let inputs = NodeGraphLayer::new(layer, network_interface).find_node_inputs("Text Warp")?;
+
+	let Some(&TaggedValue::WarpStyle(style)) = inputs[1].as_value() else { return None };
+	let Some(&TaggedValue::F64(bend)) = inputs[2].as_value() else { return None };
+	let corner = |index: usize| match inputs[index].as_value() {
+		Some(&TaggedValue::DVec2(offset)) => Some(offset),
+		_ => None,
+	};
+	Some((style, bend, [corner(3)?, corner(4)?, corner(5)?, corner(6)?]))
+}
+
+/// Checks if the Text node has a path wired into it for its text to follow
+pub fn text_follows_path(layer: LayerNodeIdentifier, network_interface: &NodeNetworkInterface) -> bool {
+	let Some(inputs) = NodeGraphLayer::new(layer, network_interface).find_node_inputs("Text") else {
+		return false;
+	};
+	inputs.get(11).is_some_and(|input| input.as_node().is_some())
+}
+
+/// Gets the path layer which the layer's text follows, through the node flattening its group, and the distance along the path where the text starts
+pub fn get_text_path(layer: LayerNodeIdentifier, network_interface: &NodeNetworkInterface) -> Option<(LayerNodeIdentifier, f64)> {
+	let inputs = NodeGraphLayer::new(layer, network_interface).find_node_inputs("Text")?;
+	let Some(&TaggedValue::F64(start_offset)) = inputs.get(12)?.as_value() else { return None };
+
+	let mut node_id = inputs.get(11)?.as_node()?;
+	if !network_interface.is_layer(&node_id, &[]) {
+		node_id = network_interface.document_network().nodes.get(&node_id)?.inputs.first()?.as_node()?;
+	}
+	network_interface
+		.is_layer(&node_id, &[])
+		.then(|| (LayerNodeIdentifier::new(node_id, network_interface, &[]), start_offset))
+}
+
+/// Gets the Text node wired into this layer's Text node, whose overflowing text continues into this layer's text box
+pub fn get_previous_text_node(layer: LayerNodeIdentifier, network_interface: &NodeNetworkInterface) -> Option<NodeId> {
+	let inputs = NodeGraphLayer::new(layer, network_interface).find_node_inputs("Text")?;
+	inputs.get(13)?.as_node()
+}
+
+/// Gets the text layer linked to this one, whose overflowing text continues into this layer's text box
+pub fn get_previous_text_frame(layer: LayerNodeIdentifier, network_interface: &NodeNetworkInterface) -> Option<LayerNodeIdentifier> {
+	network_interface.document_metadata().text_frames.get(&layer)?.previous
+}
+
+/// Gets the text layer which this one is linked to, continuing the text which overflows this layer's text box
+pub fn get_next_text_frame(layer: LayerNodeIdentifier, network_interface: &NodeNetworkInterface) -> Option<LayerNodeIdentifier> {
+	network_interface.document_metadata().text_frames.get(&layer)?.next
+}
+
+/// Gets the first text layer of the linked text boxes this one belongs to, which holds the text flowing through all of them
+pub fn get_first_text_frame(layer: LayerNodeIdentifier, network_interface: &NodeNetworkInterface) -> LayerNodeIdentifier {
+	let mut first = layer;
+	let mut visited = HashSet::from([layer]);
+	while let Some(previous) = get_previous_text_frame(first, network_interface).filter(|previous| visited.insert(*previous)) {
+		first = previous;
+	}
+	first
+}
+
 pub fn get_stroke_width(layer: LayerNodeIdentifier, network_interface: &NodeNetworkInterface) -> Option<f64> {
 	let weight_node_input_index = 2;
 	if let TaggedValue::F64(width) = NodeGraphLayer::new(layer, network_interface).find_input("Stroke", weight_node_input_index)? {
@@ -362,11 +482,31 @@ pub fn get_stroke_width(layer: LayerNodeIdentifier, network_interface: &NodeNetw
 	}
 }
 
+/// Gets the pixels of a layer made from an image, as stored in its Image node.
+pub fn get_image(layer: LayerNodeIdentifier, network_interface: &NodeNetworkInterface) -> Option<&ImageFrameTable<Color>> {
+	let image_node_input_index = 1;
+	if let TaggedValue::ImageFrame(image) = NodeGraphLayer::new(layer, network_interface).find_input("Image", image_node_input_index)? {
+		Some(image)
+	} else {
+		None
+	}
+}
+
 /// Checks if a specified layer uses an upstream node matching the given name.
 pub fn is_layer_fed_by_node_of_name(layer: LayerNodeIdentifier, network_interface: &NodeNetworkInterface, node_name: &str) -> bool {
 	NodeGraphLayer::new(layer, network_interface).find_node_inputs(node_name).is_some()
 }
 
+/// Checks if a layer is a reference image, which is excluded from exports and clicking in the viewport.
+pub fn is_reference_image(layer: LayerNodeIdentifier, network_interface: &NodeNetworkInterface) -> bool {
+	ModifyInputsContext::locate_node_in_layer_chain("Reference Image", layer, network_interface).is_some()
+}
+
+/// Gets the Frame Sequence node of a group layer, which shows the group's layers one at a time as the frames of a frame-by-frame animation.
+pub fn get_frame_sequence_id(layer: LayerNodeIdentifier, network_interface: &NodeNetworkInterface) -> Option<NodeId> {
+	ModifyInputsContext::locate_node_in_layer_chain("Frame Sequence", layer, network_interface)
+}
+
 /// An immutable reference to a layer within the document node graph for easy access.
 pub struct NodeGraphLayer<'a> {
 	network_interface: &'a NodeNetworkInterface,
@@ -1,4 +1,3 @@
-use crate::consts::COLOR_OVERLAY_BLUE;
 use crate::messages::portfolio::document::overlays::utility_types::{OverlayContext, Pivot};
 use crate::messages::tool::tool_messages::tool_prelude::*;
 use graphene_std::renderer::Rect;
@@ -38,7 +37,7 @@ fn draw_line_with_length(line_start: DVec2, line_end: DVec2, transform: DAffine2
 			(false, _, true) => (Pivot::Middle, Pivot::End),    // Horizontal line, text on top
 			(false, _, false) => (Pivot::Middle, Pivot::Start), // Horizontal line, text on bottom
 		};
-		overlay_context.text(&length, COLOR_OVERLAY_BLUE, None, DAffine2::from_translation(midpoint), TEXT_PADDING, [pivot_x, pivot_y]);
+		overlay_context.text(&length, &overlay_context.colors.accent, None, DAffine2::from_translation(midpoint), TEXT_PADDING, [pivot_x, pivot_y]);
 	}
 }
 
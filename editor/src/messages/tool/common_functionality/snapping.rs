@@ -4,7 +4,7 @@ mod grid_snapper;
 mod layer_snapper;
 mod snap_results;
 
-use crate::consts::{COLOR_OVERLAY_BLUE, COLOR_OVERLAY_LABEL_BACKGROUND, COLOR_OVERLAY_WHITE};
+use crate::consts::{COLOR_OVERLAY_LABEL_BACKGROUND, COLOR_OVERLAY_WHITE};
 use crate::messages::portfolio::document::overlays::utility_types::{OverlayContext, Pivot};
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
 use crate::messages::portfolio::document::utility_types::misc::{GridSnapTarget, PathSnapTarget, SnapTarget};
@@ -447,6 +447,10 @@ impl SnapManager {
 	pub fn draw_overlays(&mut self, snap_data: SnapData, overlay_context: &mut OverlayContext) {
 		let to_viewport = snap_data.document.metadata().document_to_viewport;
 		if let Some(ind) = &self.indicator {
+			// Snapping indicators are drawn in their own color instead of the accent color used by the other overlays
+			let snapping = overlay_context.colors.snapping.clone();
+			let accent = std::mem::replace(&mut overlay_context.colors.accent, snapping.clone());
+
 			for layer in &ind.outline_layers {
 				let &Some(layer) = layer else { continue };
 				overlay_context.outline(snap_data.document.metadata().layer_outline(layer), snap_data.document.metadata().transform_to_viewport(layer));
@@ -475,8 +479,10 @@ impl SnapManager {
 				let text = format!("[{}] from [{}]", ind.target, ind.source);
 				let transform = DAffine2::from_translation(viewport - DVec2::new(0., 4.));
 				overlay_context.text(&text, COLOR_OVERLAY_WHITE, Some(COLOR_OVERLAY_LABEL_BACKGROUND), transform, 4., [Pivot::Start, Pivot::End]);
-				overlay_context.square(viewport, Some(4.), Some(COLOR_OVERLAY_BLUE), Some(COLOR_OVERLAY_BLUE));
+				overlay_context.square(viewport, Some(4.), Some(&snapping), Some(&snapping));
 			}
+
+			overlay_context.colors.accent = accent;
 		}
 	}
 
@@ -1,6 +1,6 @@
 use super::snapping::{self, SnapCandidatePoint, SnapConstraint, SnapData, SnapManager, SnappedPoint};
 use crate::consts::{
-	BOUNDS_ROTATE_THRESHOLD, BOUNDS_SELECT_THRESHOLD, COLOR_OVERLAY_WHITE, MAXIMUM_ALT_SCALE_FACTOR, MIN_LENGTH_FOR_CORNERS_VISIBILITY, MIN_LENGTH_FOR_EDGE_RESIZE_PRIORITY_OVER_CORNERS,
+	BOUNDS_ROTATE_THRESHOLD, BOUNDS_SELECT_THRESHOLD, MAXIMUM_ALT_SCALE_FACTOR, MIN_LENGTH_FOR_CORNERS_VISIBILITY, MIN_LENGTH_FOR_EDGE_RESIZE_PRIORITY_OVER_CORNERS,
 	MIN_LENGTH_FOR_MIDPOINT_VISIBILITY, MIN_LENGTH_FOR_RESIZE_TO_INCLUDE_INTERIOR, MIN_LENGTH_FOR_SKEW_TRIANGLE_VISIBILITY, RESIZE_HANDLE_SIZE, SELECTION_DRAG_ANGLE, SKEW_TRIANGLE_OFFSET,
 	SKEW_TRIANGLE_SIZE,
 };
@@ -585,9 +585,10 @@ impl BoundingBoxManager {
 			self.render_quad(overlay_context);
 		}
 
+		let fill = overlay_context.colors.fill.clone();
 		let mut draw_handle = |point: DVec2, angle: f64| {
 			let quad = DAffine2::from_angle_translation(angle, point) * Quad::from_box([DVec2::splat(-RESIZE_HANDLE_SIZE / 2.), DVec2::splat(RESIZE_HANDLE_SIZE / 2.)]);
-			overlay_context.quad(quad, Some(COLOR_OVERLAY_WHITE));
+			overlay_context.quad(quad, Some(&fill));
 		};
 
 		let horizontal_angle = (quad.top_left() - quad.bottom_left()).to_angle();
@@ -688,13 +689,13 @@ impl BoundingBoxManager {
 	/// Returns which edge in the order:
 	///
 	/// `top, bottom, left, right`
-	pub fn check_selected_edges(&self, cursor: DVec2) -> Option<EdgeBool> {
-		let cursor = self.transform.inverse().transform_point2(cursor);
+	pub fn check_selected_edges(&self, input: &InputPreprocessorMessageHandler) -> Option<EdgeBool> {
+		let cursor = self.transform.inverse().transform_point2(input.mouse.position);
 
 		let min = self.bounds[0].min(self.bounds[1]);
 		let max = self.bounds[0].max(self.bounds[1]);
 
-		let [threshold_x, threshold_y] = self.compute_viewport_threshold(BOUNDS_SELECT_THRESHOLD);
+		let [threshold_x, threshold_y] = self.compute_viewport_threshold(input.hit_tolerance(BOUNDS_SELECT_THRESHOLD));
 		let [corner_min_x, corner_min_y] = self.compute_viewport_threshold(MIN_LENGTH_FOR_CORNERS_VISIBILITY);
 		let [edge_min_x, edge_min_y] = self.compute_viewport_threshold(MIN_LENGTH_FOR_RESIZE_TO_INCLUDE_INTERIOR);
 		let [midpoint_threshold_x, midpoint_threshold_y] = self.compute_viewport_threshold(MIN_LENGTH_FOR_EDGE_RESIZE_PRIORITY_OVER_CORNERS);
@@ -778,7 +779,7 @@ impl BoundingBoxManager {
 
 	/// Gets the required mouse cursor to show resizing bounds or optionally rotation
 	pub fn get_cursor(&self, input: &InputPreprocessorMessageHandler, rotate: bool, dragging_bounds: bool, skew_edge: Option<EdgeBool>) -> MouseCursorIcon {
-		let edges = self.check_selected_edges(input.mouse.position);
+		let edges = self.check_selected_edges(input);
 
 		let is_near_square = edges.is_some_and(|hover_edge| self.over_extended_edge_midpoint(input.mouse.position, hover_edge));
 		if dragging_bounds && is_near_square {
@@ -1,10 +1,11 @@
-use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
+use crate::messages::portfolio::document::utility_types::document_metadata::{LayerNodeIdentifier, TextFrame};
+use crate::messages::portfolio::document::utility_types::network_interface::NodeNetworkInterface;
 use crate::messages::prelude::*;
-use crate::messages::tool::common_functionality::graph_modification_utils::get_text;
-use glam::DVec2;
+use crate::messages::tool::common_functionality::graph_modification_utils::{get_previous_text_node, get_text, get_text_id, get_text_path, get_text_spans, text_follows_path};
+use glam::{DAffine2, DVec2};
 use graphene_core::renderer::Quad;
-use graphene_core::text::{FontCache, load_face};
-use graphene_std::vector::PointId;
+use graphene_core::text::{CaretPosition, FontCache, StyledText, TextPath, TextSpan};
+use graphene_std::vector::{PointId, VectorData};
 
 /// Determines if a path should be extended. Goal in viewport space. Returns the path and if it is extending from the start, if applicable.
 pub fn should_extend(
@@ -57,12 +58,220 @@ where
 
 /// Calculates the bounding box of the layer's text, based on the settings for max width and height specified in the typesetting config.
 pub fn text_bounding_box(layer: LayerNodeIdentifier, document: &DocumentMessageHandler, font_cache: &FontCache) -> Quad {
-	let Some((text, font, typesetting)) = get_text(layer, &document.network_interface) else {
+	let Some((_, font, typesetting)) = get_text(layer, &document.network_interface) else {
 		return Quad::from_box([DVec2::ZERO, DVec2::ZERO]);
 	};
 
-	let buzz_face = font_cache.get(font).map(|data| load_face(data));
-	let far = graphene_core::text::bounding_box(text, buzz_face.as_ref(), typesetting, false);
+	// Text following a path is laid out along it rather than in a box, so its bounds are those of the glyphs placed on the path
+	if text_follows_path(layer, &document.network_interface) {
+		let bounds = document.metadata().bounding_box_with_transform(layer, DAffine2::IDENTITY).unwrap_or_default();
+		return Quad::from_box(bounds);
+	}
+
+	let (text, spans) = text_frame_contents(layer, document).unwrap_or_default();
+	let styled_text = StyledText::new(&text, font, &spans, typesetting, font_cache);
+	let far = styled_text.map_or(DVec2::ZERO, |styled_text| styled_text.bounding_box(&text, typesetting, false));
 
 	Quad::from_box([DVec2::ZERO, far])
 }
+
+/// The text shown in the layer's text box along with its spans, which is the text overflowing the text box it's linked from, if any, or else its own text.
+pub fn text_frame_contents(layer: LayerNodeIdentifier, document: &DocumentMessageHandler) -> Option<(String, Vec<TextSpan>)> {
+	if let Some(frame) = document.metadata().text_frames.get(&layer).filter(|frame| frame.previous.is_some()) {
+		return Some((frame.text.clone(), frame.spans.clone()));
+	}
+
+	let (text, _, _) = get_text(layer, &document.network_interface)?;
+	let spans = get_text_spans(layer, &document.network_interface).cloned().unwrap_or_default();
+	Some((text.clone(), spans))
+}
+
+/// Works out which text layers are linked so the text overflowing one text box continues in the next, and the text shown in each of them.
+/// Text boxes with a max height are included even if they aren't linked, so whether their text overflows is known without typesetting it again.
+pub fn text_frames(network_interface: &NodeNetworkInterface, font_cache: &FontCache) -> HashMap<LayerNodeIdentifier, TextFrame> {
+	let text_layers: HashMap<_, _> = network_interface
+		.document_metadata()
+		.all_layers()
+		.filter_map(|layer| Some((get_text_id(layer, network_interface)?, layer)))
+		.collect();
+
+	// Only one text box can continue from each other text box
+	let mut previous_frames = HashMap::new();
+	let mut next_frames = HashMap::new();
+	for &layer in text_layers.values() {
+		let Some(&previous) = get_previous_text_node(layer, network_interface).and_then(|node_id| text_layers.get(&node_id)) else {
+			continue;
+		};
+		if previous != layer && !next_frames.contains_key(&previous) {
+			previous_frames.insert(layer, previous);
+			next_frames.insert(previous, layer);
+		}
+	}
+
+	// The text flows from the first of the linked text boxes through each of the others in turn
+	let mut frames = HashMap::new();
+	for &first in text_layers.values().filter(|layer| !previous_frames.contains_key(layer)) {
+		let Some((text, _, _)) = get_text(first, network_interface) else { continue };
+		let spans = get_text_spans(first, network_interface).cloned().unwrap_or_default();
+
+		let mut contents = (text.clone(), spans);
+		let mut previous = None;
+		let mut frame = Some(first);
+		while let Some(layer) = frame.filter(|layer| !frames.contains_key(layer)) {
+			let Some((_, font, typesetting)) = get_text(layer, network_interface) else { break };
+			let next = next_frames.get(&layer).copied();
+			if previous.is_none() && next.is_none() && typesetting.max_height.is_none() {
+				break;
+			}
+
+			let (text, spans) = contents;
+			let (overflows, overflow) = match StyledText::new(&text, font, &spans, typesetting, font_cache) {
+				Some(styled_text) => (styled_text.lines_clipping(&text, typesetting), next.and_then(|_| styled_text.overflow(&text, &spans, typesetting))),
+				None => (false, None),
+			};
+			frames.insert(
+				layer,
+				TextFrame {
+					previous,
+					next,
+					text,
+					spans,
+					overflows,
+				},
+			);
+
+			contents = overflow.map(|overflow| (overflow.text, overflow.spans)).unwrap_or_default();
+			previous = Some(layer);
+			frame = next;
+		}
+	}
+
+	frames
+}
+
+/// Outlines the glyphs shown in the layer's text box, in the coordinates of its Text node, with the glyphs of each span color as regions with that color as their own fill.
+pub fn text_outlines(layer: LayerNodeIdentifier, document: &DocumentMessageHandler, font_cache: &FontCache) -> Option<VectorData> {
+	// Text following a path is placed along it by the Text node, so its glyphs are taken from where they were last rendered
+	if text_follows_path(layer, &document.network_interface) {
+		return document.network_interface.compute_modified_vector(layer);
+	}
+
+	let (_, font, typesetting) = get_text(layer, &document.network_interface)?;
+	let (text, spans) = text_frame_contents(layer, document)?;
+	let styled_text = StyledText::new(&text, font, &spans, typesetting, font_cache)?;
+	Some(styled_text.to_vector_data(&text, typesetting, None))
+}
+
+/// The path which the layer's text follows, in the coordinates of its Text node, which are those of the path layer's parent.
+pub fn text_path(layer: LayerNodeIdentifier, document: &DocumentMessageHandler) -> Option<TextPath> {
+	let (path_layer, start_offset) = get_text_path(layer, &document.network_interface)?;
+	let mut subpath = document.network_interface.compute_modified_vector(path_layer)?.stroke_bezier_paths().next()?;
+
+	let metadata = document.metadata();
+	subpath.apply_transform(metadata.downstream_transform_to_viewport(path_layer).inverse() * metadata.transform_to_viewport(path_layer));
+	Some(TextPath::new(&subpath, start_offset))
+}
+
+/// The position of the caret at each boundary between the characters of the layer's own text, in the coordinates of its Text node.
+pub fn text_caret_positions(layer: LayerNodeIdentifier, document: &DocumentMessageHandler, font_cache: &FontCache) -> Vec<CaretPosition> {
+	let Some((text, font, typesetting)) = get_text(layer, &document.network_interface) else {
+		return Vec::new();
+	};
+	let spans = get_text_spans(layer, &document.network_interface).cloned().unwrap_or_default();
+	let path = text_path(layer, document);
+
+	StyledText::new(text, font, &spans, typesetting, font_cache)
+		.map(|styled_text| styled_text.caret_positions(text, typesetting, path.as_ref()))
+		.unwrap_or_default()
+}
+
+/// Checks if the text shown in the layer's text box is cut off by its max height, as of the last time the network changed.
+pub fn text_overflows(layer: LayerNodeIdentifier, document: &DocumentMessageHandler) -> bool {
+	document.metadata().text_frames.get(&layer).is_some_and(|frame| frame.overflows)
+}
+
+/// The passage placeholder text is taken from, with its words repeated for text boxes too large for it.
+const LOREM_IPSUM: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. \
+	Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. \
+	Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. \
+	Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.";
+
+/// The most words of placeholder text given to a text box, however large it is.
+const MAX_PLACEHOLDER_WORDS: usize = 2000;
+
+/// Whether the layer is a text box which shows no text, so it can be given placeholder text.
+pub fn is_empty_text_box(layer: LayerNodeIdentifier, document: &DocumentMessageHandler, font_cache: &FontCache) -> bool {
+	let Some((text, _, _)) = get_text(layer, &document.network_interface) else { return false };
+	text.is_empty() && text_frame_contents(layer, document).is_some_and(|(contents, _)| contents.is_empty())
+}
+
+/// Placeholder text for the layer's empty text box, which roughly fills it, or `None` if the layer isn't a text box or already shows some text.
+pub fn placeholder_text(layer: LayerNodeIdentifier, document: &DocumentMessageHandler, font_cache: &FontCache) -> Option<String> {
+	if !is_empty_text_box(layer, document, font_cache) {
+		return None;
+	}
+	let (_, font, typesetting) = get_text(layer, &document.network_interface)?;
+	let (_, spans) = text_frame_contents(layer, document)?;
+
+	let fits = |text: &str| StyledText::new(text, font, &spans, typesetting, font_cache).is_some_and(|styled_text| !styled_text.lines_clipping(text, typesetting));
+	Some(placeholder_passage(typesetting.max_width.is_some(), typesetting.max_height.is_some(), fits))
+}
+
+/// Placeholder text sized for a text box: one with both a max width and a max height gets as many words as `fits` accepts,
+/// one with only one of them gets a paragraph, and one without either gets a sentence.
+fn placeholder_passage(has_max_width: bool, has_max_height: bool, fits: impl Fn(&str) -> bool) -> String {
+	// The words are finished like a sentence, without a comma left at the end
+	let words: Vec<_> = LOREM_IPSUM.split_whitespace().cycle().take(MAX_PLACEHOLDER_WORDS).collect();
+	let passage = |count: usize| {
+		let text = words[..count].join(" ");
+		let text = text.trim_end_matches(',');
+		if text.ends_with('.') { text.to_string() } else { format!("{text}.") }
+	};
+
+	match (has_max_width, has_max_height) {
+		(true, true) => {
+			// Adding words only ever adds lines, so the most words which fit are found by a binary search between a count known to fit and one known not to
+			let (mut fitting, mut clipped) = (0, words.len() + 1);
+			while clipped - fitting > 1 {
+				let middle = fitting + (clipped - fitting) / 2;
+				if fits(&passage(middle)) {
+					fitting = middle;
+				} else {
+					clipped = middle;
+				}
+			}
+			passage(fitting.max(1))
+		}
+		(false, false) => LOREM_IPSUM.split_inclusive(". ").next().unwrap_or(LOREM_IPSUM).trim_end().to_string(),
+		_ => LOREM_IPSUM.to_string(),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn placeholder_fills_a_fixed_box() {
+		let passage = placeholder_passage(true, true, |text| text.split_whitespace().count() <= 30);
+		assert_eq!(passage.split_whitespace().count(), 30);
+		assert!(passage.ends_with('.') && !passage.ends_with(",."));
+
+		// Even a box too small for any text gets a word, and a huge one no more than the most words given
+		assert_eq!(placeholder_passage(true, true, |_| false), "Lorem.");
+		assert_eq!(placeholder_passage(true, true, |_| true).split_whitespace().count(), MAX_PLACEHOLDER_WORDS);
+	}
+
+	#[test]
+	fn placeholder_for_a_one_sided_box_is_a_paragraph() {
+		assert_eq!(placeholder_passage(true, false, |_| false), LOREM_IPSUM);
+		assert_eq!(placeholder_passage(false, true, |_| false), LOREM_IPSUM);
+	}
+
+	#[test]
+	fn placeholder_for_point_text_is_a_sentence() {
+		let sentence = placeholder_passage(false, false, |_| false);
+		assert!(sentence.starts_with("Lorem ipsum"));
+		assert!(sentence.ends_with("magna aliqua."));
+	}
+}
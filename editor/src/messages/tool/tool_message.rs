@@ -1,4 +1,5 @@
 use super::utility_types::ToolType;
+use crate::messages::portfolio::plugins::plugin_api::PluginToolDescription;
 use crate::messages::preferences::SelectionMode;
 use crate::messages::prelude::*;
 use graphene_core::raster::color::Color;
@@ -57,6 +58,9 @@ pub enum ToolMessage {
 	#[child]
 	Imaginate(ImaginateToolMessage),
 
+	#[child]
+	Plugin(PluginToolMessage),
+
 	// Messages
 	ActivateToolSelect,
 	ActivateToolArtboard,
@@ -81,12 +85,17 @@ pub enum ToolMessage {
 	ActivateTool {
 		tool_type: ToolType,
 	},
+	ActivatePluginTool {
+		plugin: String,
+		tool: String,
+	},
 	DeactivateTools,
 	InitTools,
 	PreUndo,
 	Redo,
 	RefreshToolOptions,
 	ResetColors,
+	ResetToolOptions,
 	SelectPrimaryColor {
 		color: Color,
 	},
@@ -98,6 +107,10 @@ pub enum ToolMessage {
 	Undo,
 	UpdateCursor,
 	UpdateHints,
+	/// Replaces the tools added to the tool shelf by plugins, each along with the ID of the plugin it belongs to.
+	UpdatePluginTools {
+		tools: Vec<(String, PluginToolDescription)>,
+	},
 	UpdateSelectionMode {
 		selection_mode: SelectionMode,
 	},
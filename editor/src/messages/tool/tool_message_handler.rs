@@ -1,5 +1,5 @@
 use super::common_functionality::shape_editor::ShapeState;
-use super::utility_types::{ToolActionHandlerData, ToolFsmState, tool_message_to_tool_type};
+use super::utility_types::{ToolActionHandlerData, ToolFsmState, ToolOptionsPersistence, tool_message_to_tool_type};
 use crate::application::generate_uuid;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::overlays::utility_types::OverlayProvider;
@@ -28,6 +28,19 @@ pub struct ToolMessageHandler {
 	pub tool_is_active: bool,
 }
 
+impl ToolMessageHandler {
+	/// Puts every tool's options back to their defaults and forgets the saved ones, which doesn't need an open document.
+	pub fn reset_tool_options(&mut self, responses: &mut VecDeque<Message>) {
+		for tool in self.tool_state.tool_data.tools.values_mut() {
+			tool.reset_options();
+		}
+		responses.add(PreferencesMessage::ResetToolOptions);
+
+		// The reset options have the default working colors, so bring them back in sync with the document's
+		self.tool_state.document_tool_data.update_working_colors(responses);
+	}
+}
+
 impl MessageHandler<ToolMessage, ToolMessageData<'_>> for ToolMessageHandler {
 	fn process_message(&mut self, message: ToolMessage, responses: &mut VecDeque<Message>, data: ToolMessageData) {
 		let ToolMessageData {
@@ -133,6 +146,44 @@ impl MessageHandler<ToolMessage, ToolMessageData<'_>> for ToolMessageHandler {
 				// Notify the frontend about the new active tool to be displayed
 				tool_data.send_layout(responses, LayoutTarget::ToolShelf);
 			}
+			ToolMessage::ActivatePluginTool { plugin, tool } => {
+				let tool_data = &mut self.tool_state.tool_data;
+				let Some((_, description)) = tool_data.plugin_tools.iter().find(|(owner, description)| *owner == plugin && description.id == tool) else {
+					return;
+				};
+				let label = description.label.clone();
+
+				// Do nothing if switching to the same tool
+				let selected = Some((plugin.clone(), tool.clone()));
+				if self.tool_is_active && tool_data.active_tool_type == ToolType::Plugin && tool_data.active_plugin_tool == selected {
+					return;
+				}
+				tool_data.active_plugin_tool = selected;
+
+				// Every plugin tool is run by the same tool, which stays active when switching between two of them, so it's told which one to run afterwards
+				responses.add_front(PluginToolMessage::Select { plugin, tool, label });
+				responses.add_front(ToolMessage::ActivateTool { tool_type: ToolType::Plugin });
+
+				tool_data.send_layout(responses, LayoutTarget::ToolShelf);
+			}
+			ToolMessage::UpdatePluginTools { tools } => {
+				let tool_data = &mut self.tool_state.tool_data;
+				tool_data.plugin_tools = tools;
+
+				// Leave the active plugin tool if its plugin was removed
+				let active_plugin_tool_exists = tool_data
+					.active_plugin_tool
+					.as_ref()
+					.is_some_and(|(plugin, tool)| tool_data.plugin_tools.iter().any(|(owner, description)| owner == plugin && description.id == *tool));
+				if !active_plugin_tool_exists {
+					tool_data.active_plugin_tool = None;
+					if tool_data.active_tool_type == ToolType::Plugin {
+						responses.add(ToolMessage::ActivateTool { tool_type: ToolType::Select });
+					}
+				}
+
+				tool_data.send_layout(responses, LayoutTarget::ToolShelf);
+			}
 			ToolMessage::DeactivateTools => {
 				let tool_data = &mut self.tool_state.tool_data;
 				tool_data.tools.get(&tool_data.active_tool_type).unwrap().deactivate(responses);
@@ -162,6 +213,13 @@ impl MessageHandler<ToolMessage, ToolMessageData<'_>> for ToolMessageHandler {
 				let document_data = &self.tool_state.document_tool_data;
 				let active_tool = &tool_data.active_tool_type;
 
+				// Restore the tool options saved in the preferences, which always match the current ones after the first time
+				for (tool_type, options) in &preferences.tool_options {
+					if let Some(tool) = tool_data.tools.get_mut(tool_type) {
+						tool.restore_options(options);
+					}
+				}
+
 				// Subscribe tool to broadcast messages
 				tool_data.tools.get(active_tool).unwrap().activate(responses);
 
@@ -215,6 +273,10 @@ impl MessageHandler<ToolMessage, ToolMessageData<'_>> for ToolMessageHandler {
 
 				document_data.update_working_colors(responses); // TODO: Make this an event
 			}
+			ToolMessage::ResetToolOptions => {
+				self.reset_tool_options(responses);
+				responses.add(ToolMessage::RefreshToolOptions);
+			}
 			ToolMessage::SelectPrimaryColor { color } => {
 				let document_data = &mut self.tool_state.document_tool_data;
 				document_data.primary_color = color;
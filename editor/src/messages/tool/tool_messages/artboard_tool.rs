@@ -48,6 +48,8 @@ impl ToolMetadata for ArtboardTool {
 	}
 }
 
+impl ToolOptionsPersistence for ArtboardTool {}
+
 impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for ArtboardTool {
 	fn process_message(&mut self, message: ToolMessage, responses: &mut VecDeque<Message>, tool_data: &mut ToolActionHandlerData<'a>) {
 		self.fsm_state.process_event(message, &mut self.data, tool_data, &(), responses, false);
@@ -125,9 +127,9 @@ impl ArtboardToolData {
 		}
 	}
 
-	fn check_dragging_bounds(&mut self, cursor: DVec2) -> Option<(bool, bool, bool, bool)> {
+	fn check_dragging_bounds(&mut self, input: &InputPreprocessorMessageHandler) -> Option<(bool, bool, bool, bool)> {
 		let bounding_box = self.bounding_box_manager.as_mut()?;
-		let edges = bounding_box.check_selected_edges(cursor)?;
+		let edges = bounding_box.check_selected_edges(input)?;
 		let (top, bottom, left, right) = edges;
 		let selected_edges = SelectedEdges::new(top, bottom, left, right, bounding_box.bounds);
 		bounding_box.opposite_pivot = selected_edges.calculate_pivot();
@@ -218,7 +220,7 @@ impl Fsm for ArtboardToolFsmState {
 	type ToolOptions = ();
 
 	fn transition(self, event: ToolMessage, tool_data: &mut Self::ToolData, tool_action_data: &mut ToolActionHandlerData, _tool_options: &(), responses: &mut VecDeque<Message>) -> Self {
-		let ToolActionHandlerData { document, input, .. } = tool_action_data;
+		let ToolActionHandlerData { document, input, preferences, .. } = tool_action_data;
 
 		let hovered = ArtboardToolData::hovered_artboard(document, input).is_some();
 
@@ -247,7 +249,7 @@ impl Fsm for ArtboardToolFsmState {
 				tool_data.drag_start = to_document.transform_point2(input.mouse.position);
 				tool_data.drag_current = to_document.transform_point2(input.mouse.position);
 
-				let state = if let Some(selected_edges) = tool_data.check_dragging_bounds(input.mouse.position) {
+				let state = if let Some(selected_edges) = tool_data.check_dragging_bounds(input) {
 					tool_data.start_resizing(selected_edges, document, input);
 					tool_data.get_snap_candidates(document, input);
 					ArtboardToolFsmState::ResizingBounds
@@ -272,7 +274,7 @@ impl Fsm for ArtboardToolFsmState {
 					ArtboardToolMessage::PointerOutsideViewport { constrain_axis_or_aspect, center }.into(),
 					ArtboardToolMessage::PointerMove { constrain_axis_or_aspect, center }.into(),
 				];
-				tool_data.auto_panning.setup_by_mouse_position(input, &messages, responses);
+				tool_data.auto_panning.setup_by_mouse_position(input, preferences, &messages, responses);
 
 				ArtboardToolFsmState::ResizingBounds
 			}
@@ -311,7 +313,7 @@ impl Fsm for ArtboardToolFsmState {
 						ArtboardToolMessage::PointerOutsideViewport { constrain_axis_or_aspect, center }.into(),
 						ArtboardToolMessage::PointerMove { constrain_axis_or_aspect, center }.into(),
 					];
-					tool_data.auto_panning.setup_by_mouse_position(input, &messages, responses);
+					tool_data.auto_panning.setup_by_mouse_position(input, preferences, &messages, responses);
 				}
 				ArtboardToolFsmState::Dragging
 			}
@@ -350,7 +352,7 @@ impl Fsm for ArtboardToolFsmState {
 					ArtboardToolMessage::PointerOutsideViewport { constrain_axis_or_aspect, center }.into(),
 					ArtboardToolMessage::PointerMove { constrain_axis_or_aspect, center }.into(),
 				];
-				tool_data.auto_panning.setup_by_mouse_position(input, &messages, responses);
+				tool_data.auto_panning.setup_by_mouse_position(input, preferences, &messages, responses);
 
 				ArtboardToolFsmState::Drawing
 			}
@@ -378,19 +380,19 @@ impl Fsm for ArtboardToolFsmState {
 			}
 			(ArtboardToolFsmState::ResizingBounds, ArtboardToolMessage::PointerOutsideViewport { .. }) => {
 				// AutoPanning
-				let _ = tool_data.auto_panning.shift_viewport(input, responses);
+				let _ = tool_data.auto_panning.shift_viewport(input, preferences, responses);
 
 				ArtboardToolFsmState::ResizingBounds
 			}
 			(ArtboardToolFsmState::Dragging, ArtboardToolMessage::PointerOutsideViewport { .. }) => {
 				// AutoPanning
-				tool_data.auto_panning.shift_viewport(input, responses);
+				tool_data.auto_panning.shift_viewport(input, preferences, responses);
 
 				ArtboardToolFsmState::Dragging
 			}
 			(ArtboardToolFsmState::Drawing, ArtboardToolMessage::PointerOutsideViewport { .. }) => {
 				// AutoPanning
-				tool_data.auto_panning.shift_viewport(input, responses);
+				tool_data.auto_panning.shift_viewport(input, preferences, responses);
 
 				ArtboardToolFsmState::Drawing
 			}
@@ -27,7 +27,11 @@ pub struct BrushTool {
 	options: BrushOptions,
 }
 
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct BrushOptions {
+	/// Only lasts for the session, so the warning is shown again after reloading the editor.
+	#[serde(skip)]
 	legacy_warning_was_shown: bool,
 	diameter: f64,
 	hardness: f64,
@@ -101,6 +105,8 @@ impl ToolMetadata for BrushTool {
 	}
 }
 
+persist_tool_options!(BrushTool, options);
+
 impl LayoutHolder for BrushTool {
 	fn layout(&self) -> Layout {
 		let mut widgets = vec![
@@ -223,6 +229,7 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for BrushTo
 		}
 
 		self.send_layout(responses, LayoutTarget::ToolOptions);
+		self.save_options(responses);
 	}
 
 	fn actions(&self) -> ActionList {
@@ -354,7 +361,11 @@ impl Fsm for BrushToolFsmState {
 						DrawMode::Restore => BlendMode::Restore,
 					};
 					tool_data.strokes.push(BrushStroke {
-						trace: vec![BrushInputSample { position: layer_position }],
+						trace: vec![BrushInputSample {
+							position: layer_position,
+							tilt: input.stylus_tilt,
+							twist: input.stylus_twist,
+						}],
 						style: BrushStyle {
 							color: tool_options.color.active_color().unwrap_or_default(),
 							diameter: tool_options.diameter / layer_scale,
@@ -389,7 +400,11 @@ impl Fsm for BrushToolFsmState {
 							.transform_point2(input.mouse.position);
 						let layer_position = tool_data.transform.inverse().transform_point2(layer_position);
 
-						stroke.trace.push(BrushInputSample { position: layer_position })
+						stroke.trace.push(BrushInputSample {
+							position: layer_position,
+							tilt: input.stylus_tilt,
+							twist: input.stylus_twist,
+						})
 					}
 				}
 				tool_data.update_strokes(responses);
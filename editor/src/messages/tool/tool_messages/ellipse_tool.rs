@@ -20,6 +20,8 @@ pub struct EllipseTool {
 	options: EllipseToolOptions,
 }
 
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct EllipseToolOptions {
 	line_weight: f64,
 	fill: ToolColorOptions,
@@ -74,6 +76,8 @@ impl ToolMetadata for EllipseTool {
 	}
 }
 
+persist_tool_options!(EllipseTool, options);
+
 fn create_weight_widget(line_weight: f64) -> WidgetHolder {
 	NumberInput::new(Some(line_weight))
 		.unit(" px")
@@ -137,6 +141,7 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for Ellipse
 		}
 
 		self.send_layout(responses, LayoutTarget::ToolOptions);
+		self.save_options(responses);
 	}
 
 	fn actions(&self) -> ActionList {
@@ -184,7 +189,11 @@ impl Fsm for EllipseToolFsmState {
 
 	fn transition(self, event: ToolMessage, tool_data: &mut Self::ToolData, tool_action_data: &mut ToolActionHandlerData, tool_options: &Self::ToolOptions, responses: &mut VecDeque<Message>) -> Self {
 		let ToolActionHandlerData {
-			document, global_tool_data, input, ..
+			document,
+			global_tool_data,
+			input,
+			preferences,
+			..
 		} = tool_action_data;
 
 		let shape_data = &mut tool_data.data;
@@ -247,7 +256,7 @@ impl Fsm for EllipseToolFsmState {
 					EllipseToolMessage::PointerOutsideViewport { center, lock_ratio }.into(),
 					EllipseToolMessage::PointerMove { center, lock_ratio }.into(),
 				];
-				tool_data.auto_panning.setup_by_mouse_position(input, &messages, responses);
+				tool_data.auto_panning.setup_by_mouse_position(input, preferences, &messages, responses);
 
 				self
 			}
@@ -258,7 +267,7 @@ impl Fsm for EllipseToolFsmState {
 			}
 			(EllipseToolFsmState::Drawing, EllipseToolMessage::PointerOutsideViewport { .. }) => {
 				// Auto-panning
-				let _ = tool_data.auto_panning.shift_viewport(input, responses);
+				let _ = tool_data.auto_panning.shift_viewport(input, preferences, responses);
 
 				EllipseToolFsmState::Drawing
 			}
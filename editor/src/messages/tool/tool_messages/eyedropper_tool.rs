@@ -33,6 +33,8 @@ impl ToolMetadata for EyedropperTool {
 	}
 }
 
+impl ToolOptionsPersistence for EyedropperTool {}
+
 impl LayoutHolder for EyedropperTool {
 	fn layout(&self) -> Layout {
 		Layout::WidgetLayout(WidgetLayout::default())
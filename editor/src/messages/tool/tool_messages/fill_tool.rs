@@ -30,6 +30,8 @@ impl ToolMetadata for FillTool {
 	}
 }
 
+impl ToolOptionsPersistence for FillTool {}
+
 impl LayoutHolder for FillTool {
 	fn layout(&self) -> Layout {
 		Layout::WidgetLayout(WidgetLayout::default())
@@ -20,6 +20,8 @@ pub struct FreehandTool {
 	options: FreehandOptions,
 }
 
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct FreehandOptions {
 	line_weight: f64,
 	fill: ToolColorOptions,
@@ -80,6 +82,8 @@ impl ToolMetadata for FreehandTool {
 	}
 }
 
+persist_tool_options!(FreehandTool, options);
+
 fn create_weight_widget(line_weight: f64) -> WidgetHolder {
 	NumberInput::new(Some(line_weight))
 		.unit(" px")
@@ -143,6 +147,7 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for Freehan
 		}
 
 		self.send_layout(responses, LayoutTarget::ToolOptions);
+		self.save_options(responses);
 	}
 
 	fn actions(&self) -> ActionList {
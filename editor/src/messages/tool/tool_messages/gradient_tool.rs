@@ -2,6 +2,7 @@ use super::tool_prelude::*;
 use crate::consts::{LINE_ROTATE_SNAP_ANGLE, MANIPULATOR_GROUP_MARKER_SIZE, SELECTION_THRESHOLD};
 use crate::messages::portfolio::document::overlays::utility_types::OverlayContext;
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
+use crate::messages::preferences::GradientPreset;
 use crate::messages::tool::common_functionality::auto_panning::AutoPanning;
 use crate::messages::tool::common_functionality::graph_modification_utils::get_gradient;
 use crate::messages::tool::common_functionality::snapping::SnapManager;
@@ -12,11 +13,16 @@ pub struct GradientTool {
 	fsm_state: GradientToolFsmState,
 	data: GradientToolData,
 	options: GradientOptions,
+	/// The names of the gradient presets saved in the preferences, kept to list them in the tool options.
+	saved_presets: Vec<String>,
 }
 
-#[derive(Default)]
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct GradientOptions {
 	gradient_type: GradientType,
+	/// The preset whose stops newly drawn gradients are given, instead of going from the secondary to the primary working color.
+	preset: Option<String>,
 }
 
 #[impl_message(Message, ToolMessage, Gradient)]
@@ -33,11 +39,14 @@ pub enum GradientToolMessage {
 	PointerMove { constrain_axis: Key },
 	PointerOutsideViewport { constrain_axis: Key },
 	PointerUp,
+	RefreshPresets,
+	SavePreset,
 	UpdateOptions(GradientOptionsUpdate),
 }
 
 #[derive(PartialEq, Eq, Clone, Debug, Hash, serde::Serialize, serde::Deserialize, specta::Type)]
 pub enum GradientOptionsUpdate {
+	Preset(Option<String>),
 	Type(GradientType),
 }
 
@@ -53,13 +62,59 @@ impl ToolMetadata for GradientTool {
 	}
 }
 
+persist_tool_options!(GradientTool, options);
+
 impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for GradientTool {
 	fn process_message(&mut self, message: ToolMessage, responses: &mut VecDeque<Message>, tool_data: &mut ToolActionHandlerData<'a>) {
-		let ToolMessage::Gradient(GradientToolMessage::UpdateOptions(action)) = message else {
-			self.fsm_state.process_event(message, &mut self.data, tool_data, &self.options, responses, false);
-			return;
+		// The tool is only sent messages while it's active, which includes when it's activated, so this keeps the listed presets up to date before its options are shown
+		let saved_presets = tool_data.preferences.gradient_presets.iter().map(|preset| &preset.name);
+		if !self.saved_presets.iter().eq(saved_presets.clone()) {
+			self.saved_presets = saved_presets.cloned().collect();
+		}
+
+		let action = match message {
+			ToolMessage::Gradient(GradientToolMessage::UpdateOptions(action)) => action,
+			ToolMessage::Gradient(GradientToolMessage::RefreshPresets) => {
+				// Forget a preset which has since been deleted
+				if self.options.preset.as_ref().is_some_and(|name| tool_data.preferences.gradient_preset(name).is_none()) {
+					self.options.preset = None;
+					self.save_options(responses);
+				}
+				self.send_layout(responses, LayoutTarget::ToolOptions);
+				return;
+			}
+			ToolMessage::Gradient(GradientToolMessage::SavePreset) => {
+				// Save the gradient being edited, or otherwise that of the first selected layer which has one
+				let document = &tool_data.document;
+				let stops = self.data.selected_gradient.as_ref().map(|selected_gradient| selected_gradient.gradient.stops.clone()).or_else(|| {
+					let mut selected_layers = document.network_interface.selected_nodes().selected_visible_layers(&document.network_interface);
+					selected_layers.find_map(|layer| get_gradient(layer, &document.network_interface)).map(|gradient| gradient.stops)
+				});
+				if let Some(stops) = stops {
+					responses.add(DialogMessage::RequestSaveGradientPresetDialog {
+						name: self.options.preset.clone().filter(|name| self.saved_presets.contains(name)).unwrap_or_default(),
+						stops,
+					});
+				}
+				return;
+			}
+			message => {
+				self.fsm_state.process_event(message, &mut self.data, tool_data, &self.options, responses, false);
+				return;
+			}
 		};
 		match action {
+			GradientOptionsUpdate::Preset(preset) => {
+				// Picking a preset also gives its stops to the selected gradient
+				if let Some(stops) = preset.as_ref().and_then(|name| tool_data.preferences.gradient_preset(name)).map(|preset| preset.stops) {
+					if let Some(selected_gradient) = &mut self.data.selected_gradient {
+						responses.add(DocumentMessage::AddTransaction);
+						selected_gradient.gradient.stops = stops;
+						selected_gradient.render_gradient(responses);
+					}
+				}
+				self.options.preset = preset;
+			}
 			GradientOptionsUpdate::Type(gradient_type) => {
 				self.options.gradient_type = gradient_type;
 				if let Some(selected_gradient) = &mut self.data.selected_gradient {
@@ -68,6 +123,9 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for Gradien
 				}
 			}
 		}
+
+		self.send_layout(responses, LayoutTarget::ToolOptions);
+		self.save_options(responses);
 	}
 
 	advertise_actions!(GradientToolMessageDiscriminant;
@@ -95,7 +153,65 @@ impl LayoutHolder for GradientTool {
 		.selected_index(Some((self.selected_gradient().unwrap_or(self.options.gradient_type) == GradientType::Radial) as u32))
 		.widget_holder();
 
-		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets: vec![gradient_type] }]))
+		let preset_entry = |name: &str| {
+			let preset = Some(name.to_string());
+			MenuListEntry::new(name)
+				.label(name)
+				.on_commit(move |_| GradientToolMessage::UpdateOptions(GradientOptionsUpdate::Preset(preset.clone())).into())
+		};
+		let bundled_presets = GradientPreset::bundled().into_iter().map(|preset| preset.name).collect::<Vec<_>>();
+		let mut preset_entries = vec![
+			vec![
+				MenuListEntry::new("Working Colors")
+					.label("Working Colors")
+					.on_commit(|_| GradientToolMessage::UpdateOptions(GradientOptionsUpdate::Preset(None)).into()),
+			],
+			bundled_presets.iter().map(|name| preset_entry(name)).collect(),
+		];
+		if !self.saved_presets.is_empty() {
+			preset_entries.push(self.saved_presets.iter().map(|name| preset_entry(name)).collect());
+		}
+
+		// A saved preset is listed after (and takes precedence over) a bundled one of the same name
+		let selected_preset = match &self.options.preset {
+			None => Some(0),
+			Some(name) => match self.saved_presets.iter().position(|saved| saved == name) {
+				Some(index) => Some(1 + bundled_presets.len() + index),
+				None => bundled_presets.iter().position(|bundled| bundled == name).map(|index| 1 + index),
+			},
+		};
+		let preset = DropdownInput::new(preset_entries)
+			.selected_index(selected_preset.map(|index| index as u32))
+			.tooltip("The colors given to newly drawn gradients. Picking a preset also gives its colors to the selected gradient.")
+			.widget_holder();
+
+		let save_preset = IconButton::new("Save", 16)
+			.tooltip("Save the selected gradient's colors as a preset")
+			.on_update(|_| GradientToolMessage::SavePreset.into())
+			.widget_holder();
+
+		let saved_preset = self.options.preset.clone().filter(|name| self.saved_presets.contains(name));
+		let delete_preset = IconButton::new("Trash", 16)
+			.tooltip("Delete the chosen saved preset")
+			.disabled(saved_preset.is_none())
+			.on_update(move |_| {
+				PreferencesMessage::DeleteGradientPreset {
+					name: saved_preset.clone().unwrap_or_default(),
+				}
+				.into()
+			})
+			.widget_holder();
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row {
+			widgets: vec![
+				gradient_type,
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				preset,
+				Separator::new(SeparatorType::Related).widget_holder(),
+				save_preset,
+				delete_preset,
+			],
+		}]))
 	}
 }
 
@@ -237,7 +353,11 @@ impl Fsm for GradientToolFsmState {
 
 	fn transition(self, event: ToolMessage, tool_data: &mut Self::ToolData, tool_action_data: &mut ToolActionHandlerData, tool_options: &Self::ToolOptions, responses: &mut VecDeque<Message>) -> Self {
 		let ToolActionHandlerData {
-			document, global_tool_data, input, ..
+			document,
+			global_tool_data,
+			input,
+			preferences,
+			..
 		} = tool_action_data;
 
 		let ToolMessage::Gradient(event) = event else { return self };
@@ -339,7 +459,7 @@ impl Fsm for GradientToolFsmState {
 					let distance = (end - start).angle_to(mouse - start).sin() * (mouse - start).length();
 
 					// If click is on the line then insert point
-					if distance < (SELECTION_THRESHOLD * 2.) {
+					if distance < input.hit_tolerance(SELECTION_THRESHOLD * 2.) {
 						// Try and insert the new stop
 						if let Some(index) = gradient.insert_stop(mouse, transform) {
 							responses.add(DocumentMessage::AddTransaction);
@@ -417,14 +537,18 @@ impl Fsm for GradientToolFsmState {
 							gradient.clone()
 						} else {
 							// Generate a new gradient
-							Gradient::new(
+							let mut gradient = Gradient::new(
 								DVec2::ZERO,
 								global_tool_data.secondary_color,
 								DVec2::ONE,
 								global_tool_data.primary_color,
 								DAffine2::IDENTITY,
 								tool_options.gradient_type,
-							)
+							);
+							if let Some(preset) = tool_options.preset.as_ref().and_then(|name| preferences.gradient_preset(name)) {
+								gradient.stops = preset.stops;
+							}
+							gradient
 						};
 						let selected_gradient = SelectedGradient::new(gradient, layer, document).with_gradient_start(input.mouse.position);
 
@@ -449,13 +573,13 @@ impl Fsm for GradientToolFsmState {
 					GradientToolMessage::PointerOutsideViewport { constrain_axis }.into(),
 					GradientToolMessage::PointerMove { constrain_axis }.into(),
 				];
-				tool_data.auto_panning.setup_by_mouse_position(input, &messages, responses);
+				tool_data.auto_panning.setup_by_mouse_position(input, preferences, &messages, responses);
 
 				GradientToolFsmState::Drawing
 			}
 			(GradientToolFsmState::Drawing, GradientToolMessage::PointerOutsideViewport { .. }) => {
 				// Auto-panning
-				if let Some(shift) = tool_data.auto_panning.shift_viewport(input, responses) {
+				if let Some(shift) = tool_data.auto_panning.shift_viewport(input, preferences, responses) {
 					if let Some(selected_gradient) = &mut tool_data.selected_gradient {
 						selected_gradient.transform.translation += shift;
 					}
@@ -56,6 +56,8 @@ impl ToolMetadata for ImaginateTool {
 	}
 }
 
+impl ToolOptionsPersistence for ImaginateTool {}
+
 impl ToolTransition for ImaginateTool {
 	fn event_to_message_map(&self) -> EventToMessageMap {
 		EventToMessageMap {
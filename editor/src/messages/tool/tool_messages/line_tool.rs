@@ -19,6 +19,8 @@ pub struct LineTool {
 	options: LineOptions,
 }
 
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct LineOptions {
 	line_weight: f64,
 	stroke: ToolColorOptions,
@@ -69,6 +71,8 @@ impl ToolMetadata for LineTool {
 	}
 }
 
+persist_tool_options!(LineTool, options);
+
 fn create_weight_widget(line_weight: f64) -> WidgetHolder {
 	NumberInput::new(Some(line_weight))
 		.unit(" px")
@@ -115,6 +119,7 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for LineToo
 		}
 
 		self.send_layout(responses, LayoutTarget::ToolOptions);
+		self.save_options(responses);
 	}
 
 	fn actions(&self) -> ActionList {
@@ -168,7 +173,11 @@ impl Fsm for LineToolFsmState {
 
 	fn transition(self, event: ToolMessage, tool_data: &mut Self::ToolData, tool_action_data: &mut ToolActionHandlerData, tool_options: &Self::ToolOptions, responses: &mut VecDeque<Message>) -> Self {
 		let ToolActionHandlerData {
-			document, global_tool_data, input, ..
+			document,
+			global_tool_data,
+			input,
+			preferences,
+			..
 		} = tool_action_data;
 
 		let ToolMessage::Line(event) = event else { return self };
@@ -209,8 +218,8 @@ impl Fsm for LineToolFsmState {
 
 				for (layer, [document_start, document_end]) in tool_data.selected_layers_with_position.iter() {
 					let transform = document.metadata().transform_to_viewport(*layer);
-					let viewport_x = transform.transform_vector2(DVec2::X).normalize_or_zero() * BOUNDS_SELECT_THRESHOLD;
-					let viewport_y = transform.transform_vector2(DVec2::Y).normalize_or_zero() * BOUNDS_SELECT_THRESHOLD;
+					let viewport_x = transform.transform_vector2(DVec2::X).normalize_or_zero() * input.hit_tolerance(BOUNDS_SELECT_THRESHOLD);
+					let viewport_y = transform.transform_vector2(DVec2::Y).normalize_or_zero() * input.hit_tolerance(BOUNDS_SELECT_THRESHOLD);
 					let threshold_x = transform.inverse().transform_vector2(viewport_x).length();
 					let threshold_y = transform.inverse().transform_vector2(viewport_y).length();
 
@@ -286,7 +295,7 @@ impl Fsm for LineToolFsmState {
 					LineToolMessage::PointerOutsideViewport { center, snap_angle, lock_angle }.into(),
 					LineToolMessage::PointerMove { center, snap_angle, lock_angle }.into(),
 				];
-				tool_data.auto_panning.setup_by_mouse_position(input, &messages, responses);
+				tool_data.auto_panning.setup_by_mouse_position(input, preferences, &messages, responses);
 
 				LineToolFsmState::Drawing
 			}
@@ -297,7 +306,7 @@ impl Fsm for LineToolFsmState {
 			}
 			(LineToolFsmState::Drawing, LineToolMessage::PointerOutsideViewport { .. }) => {
 				// Auto-panning
-				let _ = tool_data.auto_panning.shift_viewport(input, responses);
+				let _ = tool_data.auto_panning.shift_viewport(input, preferences, responses);
 
 				LineToolFsmState::Drawing
 			}
@@ -10,6 +10,7 @@ pub mod line_tool;
 pub mod navigate_tool;
 pub mod path_tool;
 pub mod pen_tool;
+pub mod plugin_tool;
 pub mod polygon_tool;
 pub mod rectangle_tool;
 pub mod select_tool;
@@ -21,7 +22,7 @@ pub mod tool_prelude {
 	pub use crate::messages::input_mapper::utility_types::input_keyboard::{Key, MouseMotion};
 	pub use crate::messages::layout::utility_types::widget_prelude::*;
 	pub use crate::messages::prelude::*;
-	pub use crate::messages::tool::utility_types::{EventToMessageMap, Fsm, ToolActionHandlerData, ToolMetadata, ToolTransition, ToolType};
+	pub use crate::messages::tool::utility_types::{EventToMessageMap, Fsm, ToolActionHandlerData, ToolMetadata, ToolOptionsPersistence, ToolTransition, ToolType};
 	pub use crate::messages::tool::utility_types::{HintData, HintGroup, HintInfo};
 	pub use glam::{DAffine2, DVec2};
 }
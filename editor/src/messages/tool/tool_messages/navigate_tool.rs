@@ -32,6 +32,8 @@ impl ToolMetadata for NavigateTool {
 	}
 }
 
+impl ToolOptionsPersistence for NavigateTool {}
+
 impl LayoutHolder for NavigateTool {
 	fn layout(&self) -> Layout {
 		Layout::WidgetLayout(WidgetLayout::default())
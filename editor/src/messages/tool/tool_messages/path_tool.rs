@@ -1,10 +1,8 @@
 use super::select_tool::extend_lasso;
 use super::tool_prelude::*;
-use crate::consts::{
-	COLOR_OVERLAY_BLUE, DRAG_DIRECTION_MODE_DETERMINATION_THRESHOLD, DRAG_THRESHOLD, HANDLE_ROTATE_SNAP_ANGLE, INSERT_POINT_ON_SEGMENT_TOO_FAR_DISTANCE, SELECTION_THRESHOLD, SELECTION_TOLERANCE,
-};
+use crate::consts::{DRAG_DIRECTION_MODE_DETERMINATION_THRESHOLD, DRAG_THRESHOLD, HANDLE_ROTATE_SNAP_ANGLE, INSERT_POINT_ON_SEGMENT_TOO_FAR_DISTANCE, SELECTION_THRESHOLD, SELECTION_TOLERANCE};
 use crate::messages::portfolio::document::overlays::utility_functions::{path_overlays, selected_segments};
-use crate::messages::portfolio::document::overlays::utility_types::{DrawHandles, OverlayContext};
+use crate::messages::portfolio::document::overlays::utility_types::{DrawHandles, OverlayColors, OverlayContext};
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
 use crate::messages::portfolio::document::utility_types::network_interface::NodeNetworkInterface;
 use crate::messages::portfolio::document::utility_types::transformation::Axis;
@@ -26,7 +24,8 @@ pub struct PathTool {
 	options: PathToolOptions,
 }
 
-#[derive(Default)]
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct PathToolOptions {
 	path_overlay_mode: PathOverlayMode,
 }
@@ -69,6 +68,7 @@ pub enum PathToolMessage {
 	NudgeSelectedPoints {
 		delta_x: f64,
 		delta_y: f64,
+		large: bool,
 	},
 	PointerMove {
 		equidistant: Key,
@@ -122,6 +122,8 @@ impl ToolMetadata for PathTool {
 	}
 }
 
+persist_tool_options!(PathTool, options);
+
 impl LayoutHolder for PathTool {
 	fn layout(&self) -> Layout {
 		let coordinates = self.tool_data.selection_status.as_one().as_ref().map(|point| point.coordinates);
@@ -231,6 +233,7 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for PathToo
 				PathOptionsUpdate::OverlayModeType(overlay_mode_type) => {
 					self.options.path_overlay_mode = overlay_mode_type;
 					responses.add(OverlaysMessage::Draw);
+					self.save_options(responses);
 				}
 			},
 			ToolMessage::Path(PathToolMessage::ClosePath) => {
@@ -498,7 +501,7 @@ impl PathToolData {
 		let old_selection = shape_editor.selected_points().cloned().collect::<Vec<_>>();
 
 		// Select the first point within the threshold (in pixels)
-		if let Some(selected_points) = shape_editor.change_point_selection(&document.network_interface, input.mouse.position, SELECTION_THRESHOLD, extend_selection) {
+		if let Some(selected_points) = shape_editor.change_point_selection(&document.network_interface, input.mouse.position, input.hit_tolerance(SELECTION_THRESHOLD), extend_selection) {
 			responses.add(DocumentMessage::StartTransaction);
 
 			if let Some(selected_points) = selected_points {
@@ -861,7 +864,13 @@ impl Fsm for PathToolFsmState {
 	type ToolOptions = PathToolOptions;
 
 	fn transition(self, event: ToolMessage, tool_data: &mut Self::ToolData, tool_action_data: &mut ToolActionHandlerData, tool_options: &Self::ToolOptions, responses: &mut VecDeque<Message>) -> Self {
-		let ToolActionHandlerData { document, input, shape_editor, .. } = tool_action_data;
+		let ToolActionHandlerData {
+			document,
+			input,
+			shape_editor,
+			preferences,
+			..
+		} = tool_action_data;
 		let ToolMessage::Path(event) = event else { return self };
 		match (self, event) {
 			(_, PathToolMessage::SelectionChanged) => {
@@ -930,14 +939,10 @@ impl Fsm for PathToolFsmState {
 
 				match self {
 					Self::Drawing { selection_shape } => {
-						let mut fill_color = graphene_std::Color::from_rgb_str(crate::consts::COLOR_OVERLAY_BLUE.strip_prefix('#').unwrap())
-							.unwrap()
-							.with_alpha(0.05)
-							.to_rgba_hex_srgb();
-						fill_color.insert(0, '#');
+						let fill_color = OverlayColors::with_alpha(&overlay_context.colors.accent, 0.05);
 						let fill_color = Some(fill_color.as_str());
 
-						let selection_mode = match tool_action_data.preferences.get_selection_mode() {
+						let selection_mode = match preferences.get_selection_mode() {
 							SelectionMode::Directional => tool_data.calculate_selection_mode_from_direction(),
 							selection_mode => selection_mode,
 						};
@@ -961,20 +966,17 @@ impl Fsm for PathToolFsmState {
 							let origin = tool_data.drag_start_pos;
 							let viewport_diagonal = input.viewport_bounds.size().length();
 
-							let mut faded_blue = graphene_std::Color::from_rgb_str(COLOR_OVERLAY_BLUE.strip_prefix('#').unwrap())
-								.unwrap()
-								.with_alpha(0.25)
-								.to_rgba_hex_srgb();
-							faded_blue.insert(0, '#');
-							let other = faded_blue.as_str();
+							let accent = overlay_context.colors.accent.clone();
+							let faded_accent = OverlayColors::with_alpha(&accent, 0.25);
+							let other = faded_accent.as_str();
 
 							match axis {
 								Axis::Y => {
-									overlay_context.line(origin - DVec2::Y * viewport_diagonal, origin + DVec2::Y * viewport_diagonal, Some(COLOR_OVERLAY_BLUE), None);
+									overlay_context.line(origin - DVec2::Y * viewport_diagonal, origin + DVec2::Y * viewport_diagonal, Some(&accent), None);
 									overlay_context.line(origin - DVec2::X * viewport_diagonal, origin + DVec2::X * viewport_diagonal, Some(other), None);
 								}
 								Axis::X | Axis::Both => {
-									overlay_context.line(origin - DVec2::X * viewport_diagonal, origin + DVec2::X * viewport_diagonal, Some(COLOR_OVERLAY_BLUE), None);
+									overlay_context.line(origin - DVec2::X * viewport_diagonal, origin + DVec2::X * viewport_diagonal, Some(&accent), None);
 									overlay_context.line(origin - DVec2::Y * viewport_diagonal, origin + DVec2::Y * viewport_diagonal, Some(other), None);
 								}
 							}
@@ -984,12 +986,12 @@ impl Fsm for PathToolFsmState {
 						let state = tool_data.update_insertion(shape_editor, document, responses, input);
 
 						if let Some(closest_segment) = &tool_data.segment {
-							overlay_context.manipulator_anchor(closest_segment.closest_point_to_viewport(), false, Some(COLOR_OVERLAY_BLUE));
+							overlay_context.manipulator_anchor(closest_segment.closest_point_to_viewport(), false, None);
 							if let (Some(handle1), Some(handle2)) = closest_segment.handle_positions(document.metadata()) {
-								overlay_context.line(closest_segment.closest_point_to_viewport(), handle1, Some(COLOR_OVERLAY_BLUE), None);
-								overlay_context.line(closest_segment.closest_point_to_viewport(), handle2, Some(COLOR_OVERLAY_BLUE), None);
-								overlay_context.manipulator_handle(handle1, false, Some(COLOR_OVERLAY_BLUE));
-								overlay_context.manipulator_handle(handle2, false, Some(COLOR_OVERLAY_BLUE));
+								overlay_context.line(closest_segment.closest_point_to_viewport(), handle1, None, None);
+								overlay_context.line(closest_segment.closest_point_to_viewport(), handle2, None, None);
+								overlay_context.manipulator_handle(handle1, false, None);
+								overlay_context.manipulator_handle(handle2, false, None);
 							}
 						}
 
@@ -1072,7 +1074,7 @@ impl Fsm for PathToolFsmState {
 					}
 					.into(),
 				];
-				tool_data.auto_panning.setup_by_mouse_position(input, &messages, responses);
+				tool_data.auto_panning.setup_by_mouse_position(input, preferences, &messages, responses);
 
 				PathToolFsmState::Drawing { selection_shape }
 			}
@@ -1160,13 +1162,13 @@ impl Fsm for PathToolFsmState {
 					}
 					.into(),
 				];
-				tool_data.auto_panning.setup_by_mouse_position(input, &messages, responses);
+				tool_data.auto_panning.setup_by_mouse_position(input, preferences, &messages, responses);
 
 				PathToolFsmState::Dragging(tool_data.dragging_state)
 			}
 			(PathToolFsmState::Drawing { selection_shape: selection_type }, PathToolMessage::PointerOutsideViewport { .. }) => {
 				// Auto-panning
-				if let Some(offset) = tool_data.auto_panning.shift_viewport(input, responses) {
+				if let Some(offset) = tool_data.auto_panning.shift_viewport(input, preferences, responses) {
 					tool_data.drag_start_pos += offset;
 				}
 
@@ -1174,7 +1176,7 @@ impl Fsm for PathToolFsmState {
 			}
 			(PathToolFsmState::Dragging(dragging_state), PathToolMessage::PointerOutsideViewport { .. }) => {
 				// Auto-panning
-				if let Some(offset) = tool_data.auto_panning.shift_viewport(input, responses) {
+				if let Some(offset) = tool_data.auto_panning.shift_viewport(input, preferences, responses) {
 					tool_data.drag_start_pos += offset;
 				}
 
@@ -1304,7 +1306,7 @@ impl Fsm for PathToolFsmState {
 
 				let extend_selection = input.keyboard.get(extend_selection as usize);
 
-				let nearest_point = shape_editor.find_nearest_point_indices(&document.network_interface, input.mouse.position, SELECTION_THRESHOLD);
+				let nearest_point = shape_editor.find_nearest_point_indices(&document.network_interface, input.mouse.position, input.hit_tolerance(SELECTION_THRESHOLD));
 
 				if let Some((layer, nearest_point)) = nearest_point {
 					if tool_data.drag_start_pos.distance(input.mouse.position) <= DRAG_THRESHOLD && !extend_selection {
@@ -1352,7 +1354,7 @@ impl Fsm for PathToolFsmState {
 			}
 			(_, PathToolMessage::FlipSmoothSharp) => {
 				// Double-clicked on a point
-				let nearest_point = shape_editor.find_nearest_point_indices(&document.network_interface, input.mouse.position, SELECTION_THRESHOLD);
+				let nearest_point = shape_editor.find_nearest_point_indices(&document.network_interface, input.mouse.position, input.hit_tolerance(SELECTION_THRESHOLD));
 				if nearest_point.is_some() {
 					// Flip the selected point between smooth and sharp
 					if !tool_data.double_click_handled && tool_data.drag_start_pos.distance(input.mouse.position) <= DRAG_THRESHOLD {
@@ -1378,11 +1380,13 @@ impl Fsm for PathToolFsmState {
 				PathToolFsmState::Ready
 			}
 			(_, PathToolMessage::PointerMove { .. }) => self,
-			(_, PathToolMessage::NudgeSelectedPoints { delta_x, delta_y }) => {
+			(_, PathToolMessage::NudgeSelectedPoints { delta_x, delta_y, large }) => {
+				let distance = preferences.nudge_distance(large);
+
 				shape_editor.move_selected_points(
 					tool_data.opposing_handle_lengths.take(),
 					document,
-					(delta_x, delta_y).into(),
+					DVec2::new(delta_x, delta_y) * distance,
 					true,
 					false,
 					tool_data.opposite_handle_position,
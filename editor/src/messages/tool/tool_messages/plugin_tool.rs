@@ -0,0 +1,252 @@
+use super::tool_prelude::*;
+use crate::consts::{COLOR_OVERLAY_BLUE, COLOR_OVERLAY_WHITE};
+use crate::messages::portfolio::document::overlays::utility_types::{OverlayContext, Pivot};
+use crate::messages::portfolio::plugins::plugin_api::{PluginOverlay, PluginPointer, PluginToolInput, PluginWidget};
+use crate::messages::portfolio::plugins::plugin_widget;
+use graphene_core::renderer::Quad;
+
+/// Stands in for whichever tool added by a plugin is picked in the tool shelf, passing its input on to the plugin and showing the options and overlays the plugin gives back.
+#[derive(Default)]
+pub struct PluginTool {
+	fsm_state: PluginToolFsmState,
+	tool_data: PluginToolData,
+}
+
+#[impl_message(Message, ToolMessage, Plugin)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum PluginToolMessage {
+	// Standard messages
+	Abort,
+	Overlays(OverlayContext),
+
+	// Tool-specific messages
+	PointerDown,
+	PointerMove,
+	PointerUp,
+	Select { plugin: String, tool: String, label: String },
+	UpdateOptions { plugin: String, tool: String, rows: Vec<Vec<PluginWidget>> },
+	UpdateOverlays { plugin: String, tool: String, overlays: Vec<PluginOverlay> },
+}
+
+impl ToolMetadata for PluginTool {
+	fn icon_name(&self) -> String {
+		"".into()
+	}
+	fn tooltip(&self) -> String {
+		self.tool_data.label.clone()
+	}
+	fn tool_type(&self) -> crate::messages::tool::utility_types::ToolType {
+		ToolType::Plugin
+	}
+}
+
+impl ToolOptionsPersistence for PluginTool {}
+
+impl LayoutHolder for PluginTool {
+	fn layout(&self) -> Layout {
+		let Some((plugin, tool)) = self.tool_data.selected.clone() else {
+			return Layout::WidgetLayout(WidgetLayout::default());
+		};
+
+		let widgets = self
+			.tool_data
+			.option_rows
+			.iter()
+			.enumerate()
+			.flat_map(|(index, row)| {
+				let separator = (index > 0).then(|| Separator::new(SeparatorType::Unrelated).widget_holder());
+				let row = row.iter().map(|widget| {
+					let (plugin, tool) = (plugin.clone(), tool.clone());
+					plugin_widget(widget, move |widget, value| {
+						PluginsMessage::ToolOptionChanged {
+							plugin: plugin.clone(),
+							tool: tool.clone(),
+							widget: widget.to_string(),
+							value,
+						}
+						.into()
+					})
+				});
+				separator.into_iter().chain(row).collect::<Vec<_>>()
+			})
+			.collect();
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
+	}
+}
+
+impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for PluginTool {
+	fn process_message(&mut self, message: ToolMessage, responses: &mut VecDeque<Message>, tool_data: &mut ToolActionHandlerData<'a>) {
+		self.fsm_state.process_event(message, &mut self.tool_data, tool_data, &(), responses, true);
+	}
+
+	fn actions(&self) -> ActionList {
+		match self.fsm_state {
+			PluginToolFsmState::Ready => actions!(PluginToolMessageDiscriminant;
+				PointerDown,
+				PointerMove,
+			),
+			PluginToolFsmState::Dragging => actions!(PluginToolMessageDiscriminant;
+				PointerMove,
+				PointerUp,
+				Abort,
+			),
+		}
+	}
+}
+
+impl ToolTransition for PluginTool {
+	fn event_to_message_map(&self) -> EventToMessageMap {
+		EventToMessageMap {
+			tool_abort: Some(PluginToolMessage::Abort.into()),
+			overlay_provider: Some(|overlay_context| PluginToolMessage::Overlays(overlay_context).into()),
+			..Default::default()
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum PluginToolFsmState {
+	#[default]
+	Ready,
+	Dragging,
+}
+
+#[derive(Clone, Debug, Default)]
+struct PluginToolData {
+	/// The plugin and the ID of its tool which is picked.
+	selected: Option<(String, String)>,
+	label: String,
+	option_rows: Vec<Vec<PluginWidget>>,
+	overlays: Vec<PluginOverlay>,
+}
+
+impl PluginToolData {
+	fn is_selected(&self, plugin: &str, tool: &str) -> bool {
+		self.selected
+			.as_ref()
+			.is_some_and(|(selected_plugin, selected_tool)| selected_plugin == plugin && selected_tool == tool)
+	}
+
+	fn send_input(&self, input: PluginToolInput, responses: &mut VecDeque<Message>) {
+		if let Some((plugin, tool)) = self.selected.clone() {
+			responses.add(PluginsMessage::ToolInput { plugin, tool, input });
+		}
+	}
+}
+
+impl Fsm for PluginToolFsmState {
+	type ToolData = PluginToolData;
+	type ToolOptions = ();
+
+	fn transition(self, event: ToolMessage, tool_data: &mut Self::ToolData, handler_data: &mut ToolActionHandlerData, _tool_options: &Self::ToolOptions, responses: &mut VecDeque<Message>) -> Self {
+		let ToolActionHandlerData { document, input, .. } = handler_data;
+		let document_to_viewport = document.metadata().document_to_viewport;
+
+		let pointer = || {
+			let position = document_to_viewport.inverse().transform_point2(input.mouse.position);
+			PluginPointer {
+				position: position.to_array(),
+				shift: input.keyboard.key(Key::Shift),
+				alt: input.keyboard.key(Key::Alt),
+				accel: input.keyboard.key(Key::Accel),
+			}
+		};
+
+		let ToolMessage::Plugin(event) = event else { return self };
+		match (self, event) {
+			(_, PluginToolMessage::Overlays(mut overlay_context)) => {
+				draw_overlays(&tool_data.overlays, document_to_viewport, &mut overlay_context);
+				self
+			}
+			(_, PluginToolMessage::Select { plugin, tool, label }) => {
+				if !tool_data.is_selected(&plugin, &tool) {
+					// Switching straight from another of the plugin tools cancels what it was doing, as switching to any other tool would
+					tool_data.send_input(PluginToolInput::Abort, responses);
+
+					*tool_data = PluginToolData {
+						selected: Some((plugin, tool)),
+						label,
+						..Default::default()
+					};
+				}
+				tool_data.send_input(PluginToolInput::Activated, responses);
+
+				responses.add(ToolMessage::RefreshToolOptions);
+				responses.add(ToolMessage::UpdateHints);
+				responses.add(OverlaysMessage::Draw);
+				PluginToolFsmState::Ready
+			}
+			(_, PluginToolMessage::UpdateOptions { plugin, tool, rows }) => {
+				if tool_data.is_selected(&plugin, &tool) {
+					tool_data.option_rows = rows;
+					responses.add(ToolMessage::RefreshToolOptions);
+				}
+				self
+			}
+			(_, PluginToolMessage::UpdateOverlays { plugin, tool, overlays }) => {
+				if tool_data.is_selected(&plugin, &tool) {
+					tool_data.overlays = overlays;
+					responses.add(OverlaysMessage::Draw);
+				}
+				self
+			}
+			(PluginToolFsmState::Ready, PluginToolMessage::PointerDown) => {
+				tool_data.send_input(PluginToolInput::PointerDown { pointer: pointer() }, responses);
+				PluginToolFsmState::Dragging
+			}
+			(_, PluginToolMessage::PointerMove) => {
+				tool_data.send_input(PluginToolInput::PointerMove { pointer: pointer() }, responses);
+				self
+			}
+			(PluginToolFsmState::Dragging, PluginToolMessage::PointerUp) => {
+				tool_data.send_input(PluginToolInput::PointerUp { pointer: pointer() }, responses);
+				PluginToolFsmState::Ready
+			}
+			(_, PluginToolMessage::Abort) => {
+				tool_data.send_input(PluginToolInput::Abort, responses);
+				PluginToolFsmState::Ready
+			}
+			_ => self,
+		}
+	}
+
+	fn update_hints(&self, responses: &mut VecDeque<Message>) {
+		let hint_data = match self {
+			PluginToolFsmState::Ready => HintData(vec![HintGroup(vec![
+				HintInfo::mouse(MouseMotion::Lmb, "Use Tool"),
+				HintInfo::mouse(MouseMotion::LmbDrag, "").prepend_slash(),
+			])]),
+			PluginToolFsmState::Dragging => HintData(vec![HintGroup(vec![HintInfo::mouse(MouseMotion::Rmb, ""), HintInfo::keys([Key::Escape], "Cancel").prepend_slash()])]),
+		};
+
+		responses.add(FrontendMessage::UpdateInputHints { hint_data });
+	}
+
+	fn update_cursor(&self, responses: &mut VecDeque<Message>) {
+		responses.add(FrontendMessage::UpdateMouseCursor { cursor: MouseCursorIcon::Crosshair });
+	}
+}
+
+/// Draws the shapes given by a plugin, which are in document space, over the canvas.
+fn draw_overlays(overlays: &[PluginOverlay], document_to_viewport: DAffine2, overlay_context: &mut OverlayContext) {
+	let viewport = |point: [f64; 2]| document_to_viewport.transform_point2(DVec2::from(point));
+
+	for overlay in overlays {
+		match overlay {
+			PluginOverlay::Line { start, end } => overlay_context.line(viewport(*start), viewport(*end), None, None),
+			PluginOverlay::Circle { center, radius } => {
+				let radius = document_to_viewport.transform_vector2(DVec2::X * *radius).length();
+				overlay_context.circle(viewport(*center), radius, None, Some(COLOR_OVERLAY_BLUE));
+			}
+			PluginOverlay::Rectangle { min, max } => {
+				let quad = document_to_viewport * Quad::from_box([DVec2::from(*min), DVec2::from(*max)]);
+				overlay_context.quad(quad, None);
+			}
+			PluginOverlay::Text { position, text } => {
+				let transform = DAffine2::from_translation(viewport(*position));
+				overlay_context.text(text, COLOR_OVERLAY_WHITE, Some(COLOR_OVERLAY_BLUE), transform, 4., [Pivot::Start, Pivot::End]);
+			}
+		}
+	}
+}
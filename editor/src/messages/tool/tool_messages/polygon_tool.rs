@@ -21,6 +21,8 @@ pub struct PolygonTool {
 	options: PolygonOptions,
 }
 
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct PolygonOptions {
 	line_weight: f64,
 	fill: ToolColorOptions,
@@ -87,6 +89,8 @@ impl ToolMetadata for PolygonTool {
 	}
 }
 
+persist_tool_options!(PolygonTool, options);
+
 fn create_sides_widget(vertices: u32) -> WidgetHolder {
 	NumberInput::new(Some(vertices as f64))
 		.label("Sides")
@@ -182,6 +186,7 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for Polygon
 		}
 
 		self.send_layout(responses, LayoutTarget::ToolOptions);
+		self.save_options(responses);
 	}
 
 	fn actions(&self) -> ActionList {
@@ -229,7 +234,11 @@ impl Fsm for PolygonToolFsmState {
 
 	fn transition(self, event: ToolMessage, tool_data: &mut Self::ToolData, tool_action_data: &mut ToolActionHandlerData, tool_options: &Self::ToolOptions, responses: &mut VecDeque<Message>) -> Self {
 		let ToolActionHandlerData {
-			document, global_tool_data, input, ..
+			document,
+			global_tool_data,
+			input,
+			preferences,
+			..
 		} = tool_action_data;
 
 		let polygon_data = &mut tool_data.data;
@@ -336,7 +345,7 @@ impl Fsm for PolygonToolFsmState {
 					PolygonToolMessage::PointerOutsideViewport { center, lock_ratio }.into(),
 					PolygonToolMessage::PointerMove { center, lock_ratio }.into(),
 				];
-				tool_data.auto_panning.setup_by_mouse_position(input, &messages, responses);
+				tool_data.auto_panning.setup_by_mouse_position(input, preferences, &messages, responses);
 
 				self
 			}
@@ -347,7 +356,7 @@ impl Fsm for PolygonToolFsmState {
 			}
 			(PolygonToolFsmState::Drawing, PolygonToolMessage::PointerOutsideViewport { .. }) => {
 				// Auto-panning
-				let _ = tool_data.auto_panning.shift_viewport(input, responses);
+				let _ = tool_data.auto_panning.shift_viewport(input, preferences, responses);
 
 				PolygonToolFsmState::Drawing
 			}
@@ -20,6 +20,8 @@ pub struct RectangleTool {
 	options: RectangleToolOptions,
 }
 
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct RectangleToolOptions {
 	line_weight: f64,
 	fill: ToolColorOptions,
@@ -125,6 +127,7 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for Rectang
 		}
 
 		self.send_layout(responses, LayoutTarget::ToolOptions);
+		self.save_options(responses);
 	}
 
 	fn actions(&self) -> ActionList {
@@ -154,6 +157,8 @@ impl ToolMetadata for RectangleTool {
 	}
 }
 
+persist_tool_options!(RectangleTool, options);
+
 impl ToolTransition for RectangleTool {
 	fn event_to_message_map(&self) -> EventToMessageMap {
 		EventToMessageMap {
@@ -187,7 +192,11 @@ impl Fsm for RectangleToolFsmState {
 		event: ToolMessage,
 		tool_data: &mut Self::ToolData,
 		ToolActionHandlerData {
-			document, global_tool_data, input, ..
+			document,
+			global_tool_data,
+			input,
+			preferences,
+			..
 		}: &mut ToolActionHandlerData,
 		tool_options: &Self::ToolOptions,
 		responses: &mut VecDeque<Message>,
@@ -252,7 +261,7 @@ impl Fsm for RectangleToolFsmState {
 					RectangleToolMessage::PointerOutsideViewport { center, lock_ratio }.into(),
 					RectangleToolMessage::PointerMove { center, lock_ratio }.into(),
 				];
-				tool_data.auto_panning.setup_by_mouse_position(input, &messages, responses);
+				tool_data.auto_panning.setup_by_mouse_position(input, preferences, &messages, responses);
 
 				self
 			}
@@ -263,7 +272,7 @@ impl Fsm for RectangleToolFsmState {
 			}
 			(RectangleToolFsmState::Drawing, RectangleToolMessage::PointerOutsideViewport { .. }) => {
 				// Auto-panning
-				let _ = tool_data.auto_panning.shift_viewport(input, responses);
+				let _ = tool_data.auto_panning.shift_viewport(input, preferences, responses);
 
 				RectangleToolFsmState::Drawing
 			}
@@ -2,14 +2,14 @@
 
 use super::tool_prelude::*;
 use crate::consts::{
-	COLOR_OVERLAY_BLUE, COLOR_OVERLAY_GREEN, COLOR_OVERLAY_RED, COMPASS_ROSE_HOVER_RING_DIAMETER, DRAG_DIRECTION_MODE_DETERMINATION_THRESHOLD, RESIZE_HANDLE_SIZE, ROTATE_INCREMENT,
-	SELECTION_DRAG_ANGLE, SELECTION_TOLERANCE,
+	COLOR_OVERLAY_GREEN, COLOR_OVERLAY_RED, COMPASS_ROSE_HOVER_RING_DIAMETER, DRAG_DIRECTION_MODE_DETERMINATION_THRESHOLD, RESIZE_HANDLE_SIZE, ROTATE_INCREMENT, SELECTION_DRAG_ANGLE,
+	SELECTION_TOLERANCE,
 };
 use crate::messages::input_mapper::utility_types::input_mouse::ViewportPosition;
 use crate::messages::portfolio::document::graph_operation::utility_types::TransformIn;
-use crate::messages::portfolio::document::overlays::utility_types::OverlayContext;
+use crate::messages::portfolio::document::overlays::utility_types::{OverlayColors, OverlayContext};
 use crate::messages::portfolio::document::utility_types::document_metadata::{DocumentMetadata, LayerNodeIdentifier};
-use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, FlipAxis, GroupFolderType};
+use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, FlipAxis, GroupFolderType, RepeatableAction};
 use crate::messages::portfolio::document::utility_types::network_interface::{FlowType, NodeNetworkInterface, NodeTemplate};
 use crate::messages::portfolio::document::utility_types::nodes::SelectedNodes;
 use crate::messages::portfolio::document::utility_types::transformation::Selected;
@@ -112,6 +112,8 @@ impl ToolMetadata for SelectTool {
 	}
 }
 
+persist_tool_options!(SelectTool, tool_data.nested_selection_behavior);
+
 impl SelectTool {
 	fn deep_selection_widget(&self) -> WidgetHolder {
 		let layer_selection_behavior_entries = [NestedSelectionBehavior::Deepest, NestedSelectionBehavior::Shallowest]
@@ -246,6 +248,7 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for SelectT
 		if let ToolMessage::Select(SelectToolMessage::SelectOptions(SelectOptionsUpdate::NestedSelectionBehavior(nested_selection_behavior))) = message {
 			self.tool_data.nested_selection_behavior = nested_selection_behavior;
 			responses.add(ToolMessage::UpdateHints);
+			self.save_options(responses);
 		}
 
 		self.fsm_state.process_event(message, &mut self.tool_data, tool_data, &(), responses, false);
@@ -490,7 +493,13 @@ impl Fsm for SelectToolFsmState {
 	type ToolOptions = ();
 
 	fn transition(self, event: ToolMessage, tool_data: &mut Self::ToolData, tool_action_data: &mut ToolActionHandlerData, _tool_options: &(), responses: &mut VecDeque<Message>) -> Self {
-		let ToolActionHandlerData { document, input, font_cache, .. } = tool_action_data;
+		let ToolActionHandlerData {
+			document,
+			input,
+			font_cache,
+			preferences,
+			..
+		} = tool_action_data;
 
 		let ToolMessage::Select(event) = event else { return self };
 		match (self, event) {
@@ -552,7 +561,7 @@ impl Fsm for SelectToolFsmState {
 					let click = document.click(input);
 					let not_selected_click = click.filter(|&hovered_layer| !document.network_interface.selected_nodes().selected_layers_contains(hovered_layer, document.metadata()));
 					if let Some(layer) = not_selected_click {
-						overlay_context.outline(document.metadata().layer_outline(layer), document.metadata().transform_to_viewport(layer));
+						overlay_context.hover_outline(layer, document.metadata());
 
 						// Measure with Alt held down
 						// TODO: Don't use `Key::Alt` directly, instead take it as a variable from the input mappings list like in all other places
@@ -612,11 +621,7 @@ impl Fsm for SelectToolFsmState {
 					compass_rose_state.is_ring()
 				};
 
-				let dragging_bounds = tool_data
-					.bounding_box_manager
-					.as_mut()
-					.and_then(|bounding_box| bounding_box.check_selected_edges(input.mouse.position))
-					.is_some();
+				let dragging_bounds = tool_data.bounding_box_manager.as_mut().and_then(|bounding_box| bounding_box.check_selected_edges(input)).is_some();
 
 				let rotating_bounds = tool_data
 					.bounding_box_manager
@@ -627,7 +632,7 @@ impl Fsm for SelectToolFsmState {
 				let is_resizing_or_rotating = matches!(self, SelectToolFsmState::ResizingBounds | SelectToolFsmState::SkewingBounds { .. } | SelectToolFsmState::RotatingBounds);
 
 				if let Some(bounds) = tool_data.bounding_box_manager.as_mut() {
-					let edges = bounds.check_selected_edges(input.mouse.position);
+					let edges = bounds.check_selected_edges(input);
 					let is_skewing = matches!(self, SelectToolFsmState::SkewingBounds { .. });
 					let is_near_square = edges.is_some_and(|hover_edge| bounds.over_extended_edge_midpoint(input.mouse.position, hover_edge));
 					if is_skewing || (dragging_bounds && is_near_square && !is_resizing_or_rotating) {
@@ -707,11 +712,8 @@ impl Fsm for SelectToolFsmState {
 					let angle = -mouse_position.angle_to(DVec2::X);
 					let snapped_angle = (angle / snap_resolution).round() * snap_resolution;
 
-					let mut other = graphene_std::Color::from_rgb_str(COLOR_OVERLAY_BLUE.strip_prefix('#').unwrap())
-						.unwrap()
-						.with_alpha(0.25)
-						.to_rgba_hex_srgb();
-					other.insert(0, '#');
+					let accent = overlay_context.colors.accent.clone();
+					let other = OverlayColors::with_alpha(&accent, 0.25);
 					let other = other.as_str();
 
 					let extension = tool_data.drag_current - tool_data.drag_start;
@@ -721,7 +723,7 @@ impl Fsm for SelectToolFsmState {
 					let edge = DVec2::from_angle(snapped_angle) * viewport_diagonal;
 					let perp = edge.perp();
 
-					overlay_context.line(origin - edge * viewport_diagonal, origin + edge * viewport_diagonal, Some(COLOR_OVERLAY_BLUE), None);
+					overlay_context.line(origin - edge * viewport_diagonal, origin + edge * viewport_diagonal, Some(&accent), None);
 					overlay_context.line(origin - perp * viewport_diagonal, origin + perp * viewport_diagonal, Some(other), None);
 				}
 
@@ -730,7 +732,7 @@ impl Fsm for SelectToolFsmState {
 					// Get the updated selection box bounds
 					let quad = Quad::from_box([tool_data.drag_start, tool_data.drag_current]);
 
-					let current_selection_mode = match tool_action_data.preferences.get_selection_mode() {
+					let current_selection_mode = match preferences.get_selection_mode() {
 						SelectionMode::Directional => tool_data.calculate_selection_mode_from_direction(),
 						SelectionMode::Touched => SelectionMode::Touched,
 						SelectionMode::Enclosed => SelectionMode::Enclosed,
@@ -758,11 +760,7 @@ impl Fsm for SelectToolFsmState {
 					}
 
 					// Update the selection box
-					let mut fill_color = graphene_std::Color::from_rgb_str(crate::consts::COLOR_OVERLAY_BLUE.strip_prefix('#').unwrap())
-						.unwrap()
-						.with_alpha(0.05)
-						.to_rgba_hex_srgb();
-					fill_color.insert(0, '#');
+					let fill_color = OverlayColors::with_alpha(&overlay_context.colors.accent, 0.05);
 					let fill_color = Some(fill_color.as_str());
 
 					let polygon = &tool_data.lasso_polygon;
@@ -802,7 +800,7 @@ impl Fsm for SelectToolFsmState {
 				tool_data.selection_mode = None;
 
 				let dragging_bounds = tool_data.bounding_box_manager.as_mut().and_then(|bounding_box| {
-					let edges = bounding_box.check_selected_edges(input.mouse.position);
+					let edges = bounding_box.check_selected_edges(input);
 
 					bounding_box.selected_edges = edges.map(|(top, bottom, left, right)| {
 						let selected_edges = SelectedEdges::new(top, bottom, left, right, bounding_box.bounds);
@@ -887,7 +885,7 @@ impl Fsm for SelectToolFsmState {
 						bounds.center_of_transformation = selected.mean_average_of_pivots();
 
 						// Check if we're hovering over a skew triangle
-						let edges = bounds.check_selected_edges(input.mouse.position);
+						let edges = bounds.check_selected_edges(input);
 						if let Some(edges) = edges {
 							let closest_edge = bounds.get_closest_edge(edges, input.mouse.position);
 							if bounds.check_skew_handle(input.mouse.position, closest_edge) {
@@ -1031,7 +1029,7 @@ impl Fsm for SelectToolFsmState {
 					SelectToolMessage::PointerOutsideViewport(modifier_keys.clone()).into(),
 					SelectToolMessage::PointerMove(modifier_keys).into(),
 				];
-				tool_data.auto_panning.setup_by_mouse_position(input, &messages, responses);
+				tool_data.auto_panning.setup_by_mouse_position(input, preferences, &messages, responses);
 
 				SelectToolFsmState::Dragging {
 					axis,
@@ -1083,7 +1081,7 @@ impl Fsm for SelectToolFsmState {
 							SelectToolMessage::PointerOutsideViewport(modifier_keys.clone()).into(),
 							SelectToolMessage::PointerMove(modifier_keys).into(),
 						];
-						tool_data.auto_panning.setup_by_mouse_position(input, &messages, responses);
+						tool_data.auto_panning.setup_by_mouse_position(input, preferences, &messages, responses);
 					}
 				}
 				SelectToolFsmState::ResizingBounds
@@ -1172,7 +1170,7 @@ impl Fsm for SelectToolFsmState {
 					SelectToolMessage::PointerOutsideViewport(modifier_keys.clone()).into(),
 					SelectToolMessage::PointerMove(modifier_keys).into(),
 				];
-				tool_data.auto_panning.setup_by_mouse_position(input, &messages, responses);
+				tool_data.auto_panning.setup_by_mouse_position(input, preferences, &messages, responses);
 
 				SelectToolFsmState::DraggingPivot
 			}
@@ -1193,16 +1191,12 @@ impl Fsm for SelectToolFsmState {
 					SelectToolMessage::PointerOutsideViewport(modifier_keys.clone()).into(),
 					SelectToolMessage::PointerMove(modifier_keys).into(),
 				];
-				tool_data.auto_panning.setup_by_mouse_position(input, &messages, responses);
+				tool_data.auto_panning.setup_by_mouse_position(input, preferences, &messages, responses);
 
 				SelectToolFsmState::Drawing { selection_shape, has_drawn: true }
 			}
 			(SelectToolFsmState::Ready { .. }, SelectToolMessage::PointerMove(_)) => {
-				let dragging_bounds = tool_data
-					.bounding_box_manager
-					.as_mut()
-					.and_then(|bounding_box| bounding_box.check_selected_edges(input.mouse.position))
-					.is_some();
+				let dragging_bounds = tool_data.bounding_box_manager.as_mut().and_then(|bounding_box| bounding_box.check_selected_edges(input)).is_some();
 
 				let mut cursor = tool_data
 					.bounding_box_manager
@@ -1227,7 +1221,7 @@ impl Fsm for SelectToolFsmState {
 			}
 			(SelectToolFsmState::Dragging { axis, using_compass, has_dragged }, SelectToolMessage::PointerOutsideViewport(_)) => {
 				// AutoPanning
-				if let Some(shift) = tool_data.auto_panning.shift_viewport(input, responses) {
+				if let Some(shift) = tool_data.auto_panning.shift_viewport(input, preferences, responses) {
 					tool_data.drag_current += shift;
 					tool_data.drag_start += shift;
 				}
@@ -1236,7 +1230,7 @@ impl Fsm for SelectToolFsmState {
 			}
 			(SelectToolFsmState::ResizingBounds | SelectToolFsmState::SkewingBounds { .. }, SelectToolMessage::PointerOutsideViewport(_)) => {
 				// AutoPanning
-				if let Some(shift) = tool_data.auto_panning.shift_viewport(input, responses) {
+				if let Some(shift) = tool_data.auto_panning.shift_viewport(input, preferences, responses) {
 					if let Some(bounds) = &mut tool_data.bounding_box_manager {
 						bounds.center_of_transformation += shift;
 						bounds.original_bound_transform.translation += shift;
@@ -1247,13 +1241,13 @@ impl Fsm for SelectToolFsmState {
 			}
 			(SelectToolFsmState::DraggingPivot, SelectToolMessage::PointerOutsideViewport(_)) => {
 				// AutoPanning
-				let _ = tool_data.auto_panning.shift_viewport(input, responses);
+				let _ = tool_data.auto_panning.shift_viewport(input, preferences, responses);
 
 				self
 			}
 			(SelectToolFsmState::Drawing { .. }, SelectToolMessage::PointerOutsideViewport(_)) => {
 				// AutoPanning
-				if let Some(shift) = tool_data.auto_panning.shift_viewport(input, responses) {
+				if let Some(shift) = tool_data.auto_panning.shift_viewport(input, preferences, responses) {
 					tool_data.drag_start += shift;
 				}
 
@@ -1274,6 +1268,18 @@ impl Fsm for SelectToolFsmState {
 				responses.add(DocumentMessage::EndTransaction);
 				tool_data.axis_align = false;
 
+				// Remember the movement, and any duplication, so it can be repeated
+				if has_dragged {
+					let translation = document.metadata().document_to_viewport.inverse().transform_vector2(tool_data.drag_current - tool_data.drag_start);
+					let transform = DAffine2::from_translation(translation);
+					let action = if tool_data.non_duplicated_layers.is_some() {
+						RepeatableAction::Duplicate { transform }
+					} else {
+						RepeatableAction::Transform { transform }
+					};
+					responses.add(DocumentMessage::RecordRepeatableAction { action });
+				}
+
 				if !has_dragged && input.keyboard.key(remove_from_selection) && tool_data.layer_selected_on_start.is_none() {
 					// When you click on the layer with remove from selection key (shift) pressed, we deselect all nodes that are children.
 					let quad = tool_data.selection_quad();
@@ -1347,7 +1353,7 @@ impl Fsm for SelectToolFsmState {
 			(SelectToolFsmState::Drawing { selection_shape, .. }, SelectToolMessage::DragStop { remove_from_selection }) => {
 				let quad = tool_data.selection_quad();
 
-				let selection_mode = match tool_action_data.preferences.get_selection_mode() {
+				let selection_mode = match preferences.get_selection_mode() {
 					SelectionMode::Directional => tool_data.calculate_selection_mode_from_direction(),
 					selection_mode => selection_mode,
 				};
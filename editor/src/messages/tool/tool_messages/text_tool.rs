@@ -1,9 +1,10 @@
 #![allow(clippy::too_many_arguments)]
 
 use super::tool_prelude::*;
-use crate::consts::{COLOR_OVERLAY_RED, DRAG_THRESHOLD};
+use crate::consts::{COLOR_OVERLAY_RED, DRAG_THRESHOLD, MANIPULATOR_GROUP_MARKER_SIZE, SELECTION_TOLERANCE, TEXT_OVERFLOW_PORT_OFFSET, TEXT_OVERFLOW_PORT_SIZE};
 use crate::messages::portfolio::document::graph_operation::utility_types::TransformIn;
-use crate::messages::portfolio::document::overlays::utility_types::OverlayContext;
+use crate::messages::portfolio::document::node_graph::node_properties::{font_features_popover, font_variation_input};
+use crate::messages::portfolio::document::overlays::utility_types::{OverlayColors, OverlayContext};
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
 use crate::messages::portfolio::document::utility_types::network_interface::InputConnector;
 use crate::messages::tool::common_functionality::auto_panning::AutoPanning;
@@ -13,21 +14,31 @@ use crate::messages::tool::common_functionality::pivot::Pivot;
 use crate::messages::tool::common_functionality::resize::Resize;
 use crate::messages::tool::common_functionality::snapping::{self, SnapCandidatePoint, SnapData};
 use crate::messages::tool::common_functionality::transformation_cage::*;
-use crate::messages::tool::common_functionality::utility_functions::text_bounding_box;
+use crate::messages::tool::common_functionality::utility_functions::{placeholder_text, text_bounding_box, text_outlines, text_overflows, text_path};
+use bezier_rs::TValue;
 use graph_craft::document::value::TaggedValue;
 use graph_craft::document::{NodeId, NodeInput};
 use graphene_core::Color;
 use graphene_core::renderer::Quad;
-use graphene_core::text::{Font, FontCache, TypesettingConfig, lines_clipping, load_face};
+use graphene_core::text::{
+	CaretPosition, Font, FontAxis, FontCache, FontFeatures, FontVariations, HYPHENATION_AVAILABLE, StyledText, TabStops, TextAlign, TextCase, TextDirection, TextLanguage, TextOrientation, TextScript,
+	TextSpan, TypesettingConfig, VerticalAlign, WrapMode, WritingMode, caret_to_side, grapheme_boundaries, merge_spans, splice_spans, variation_axes,
+};
+use graphene_core::vector::misc::WarpStyle;
 use graphene_core::vector::style::Fill;
+use std::ops::Range;
 
 #[derive(Default)]
 pub struct TextTool {
 	fsm_state: TextToolFsmState,
 	tool_data: TextToolData,
 	options: TextOptions,
+	/// The axes of the variable font chosen in the options, once it's been loaded, along with that font.
+	variation_axes: Option<(Font, Vec<FontAxis>)>,
 }
 
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct TextOptions {
 	font_size: f64,
 	line_height_ratio: f64,
@@ -35,6 +46,24 @@ pub struct TextOptions {
 	font_name: String,
 	font_style: String,
 	fill: ToolColorOptions,
+	align: TextAlign,
+	vertical_align: VerticalAlign,
+	direction: TextDirection,
+	writing_mode: WritingMode,
+	orientation: TextOrientation,
+	features: FontFeatures,
+	variations: FontVariations,
+	baseline_shift: f64,
+	script: TextScript,
+	wrap: WrapMode,
+	auto_height: bool,
+	space_before: f64,
+	space_after: f64,
+	underline: bool,
+	strikethrough: bool,
+	case: TextCase,
+	hyphenate: bool,
+	language: TextLanguage,
 }
 
 impl Default for TextOptions {
@@ -46,6 +75,24 @@ impl Default for TextOptions {
 			font_name: graphene_core::consts::DEFAULT_FONT_FAMILY.into(),
 			font_style: graphene_core::consts::DEFAULT_FONT_STYLE.into(),
 			fill: ToolColorOptions::new_primary(),
+			align: TextAlign::default(),
+			vertical_align: VerticalAlign::default(),
+			direction: TextDirection::default(),
+			writing_mode: WritingMode::default(),
+			orientation: TextOrientation::default(),
+			features: FontFeatures::default(),
+			variations: FontVariations::default(),
+			baseline_shift: 0.,
+			script: TextScript::default(),
+			wrap: WrapMode::default(),
+			auto_height: false,
+			space_before: 0.,
+			space_after: 0.,
+			underline: false,
+			strikethrough: false,
+			case: TextCase::default(),
+			hyphenate: false,
+			language: TextLanguage::default(),
 		}
 	}
 }
@@ -59,17 +106,52 @@ pub enum TextToolMessage {
 	Overlays(OverlayContext),
 
 	// Tool-specific messages
+	CompositionUpdate { text: String },
+	DeleteBackward,
+	DeleteForward,
 	DragStart,
 	DragStop,
 	EditSelected,
+	FillWithPlaceholder,
+	InsertText { text: String },
 	Interact,
+	MoveCaret { movement: CaretMovement, extend: Key },
+	OpenGlyphPalette,
 	PointerMove { center: Key, lock_ratio: Key },
 	PointerOutsideViewport { center: Key, lock_ratio: Key },
-	TextChange { new_text: String, is_left_or_right_click: bool },
-	UpdateBounds { new_text: String },
+	SelectAll,
+	SetAutoHeight { enabled: bool },
+	StyleSelection(SelectionStyle),
 	UpdateOptions(TextOptionsUpdate),
 }
 
+/// Where the caret moves to through the text being edited.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum CaretMovement {
+	/// To the closest place on the left, moving through right-to-left text in the order it's shown, or to the previous character in vertical text.
+	Left,
+	/// To the closest place on the right, moving through right-to-left text in the order it's shown, or to the next character in vertical text.
+	Right,
+	/// To the closest place on the line above, or the column to the right in vertical text.
+	PreviousLine,
+	/// To the closest place on the line below, or the column to the left in vertical text.
+	NextLine,
+	LineStart,
+	LineEnd,
+}
+
+/// The style the tool options give the selected characters of the text being edited, where `None` leaves them with the style they have.
+#[derive(PartialEq, Clone, Debug, Default, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct SelectionStyle {
+	font: Option<Font>,
+	font_size: Option<f64>,
+	color: Option<Color>,
+	baseline_shift: Option<f64>,
+	script: Option<TextScript>,
+	underline: Option<bool>,
+	strikethrough: Option<bool>,
+}
+
 #[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
 pub enum TextOptionsUpdate {
 	FillColor(Option<Color>),
@@ -77,7 +159,25 @@ pub enum TextOptionsUpdate {
 	Font { family: String, style: String },
 	FontSize(f64),
 	LineHeightRatio(f64),
+	SpaceBefore(f64),
+	SpaceAfter(f64),
 	CharacterSpacing(f64),
+	BaselineShift(f64),
+	Script(TextScript),
+	Underline(bool),
+	Strikethrough(bool),
+	Case(TextCase),
+	Hyphenate(bool),
+	Language(TextLanguage),
+	Alignment(TextAlign),
+	VerticalAlignment(VerticalAlign),
+	Wrap(WrapMode),
+	AutoHeight(bool),
+	Direction(TextDirection),
+	WritingMode(WritingMode),
+	Orientation(TextOrientation),
+	Features(FontFeatures),
+	Variations(FontVariations),
 	WorkingColors(Option<Color>, Option<Color>),
 }
 
@@ -93,6 +193,8 @@ impl ToolMetadata for TextTool {
 	}
 }
 
+persist_tool_options!(TextTool, options);
+
 fn create_text_widgets(tool: &TextTool) -> Vec<WidgetHolder> {
 	let font = FontInput::new(&tool.options.font_name, &tool.options.font_style)
 		.is_style_picker(false)
@@ -130,6 +232,20 @@ fn create_text_widgets(tool: &TextTool) -> Vec<WidgetHolder> {
 		.step(0.1)
 		.on_update(|number_input: &NumberInput| TextToolMessage::UpdateOptions(TextOptionsUpdate::LineHeightRatio(number_input.value.unwrap())).into())
 		.widget_holder();
+	let space_before = NumberInput::new(Some(tool.options.space_before))
+		.unit(" px")
+		.label("Space Before")
+		.min(0.)
+		.max((1_u64 << f64::MANTISSA_DIGITS) as f64)
+		.on_update(|number_input: &NumberInput| TextToolMessage::UpdateOptions(TextOptionsUpdate::SpaceBefore(number_input.value.unwrap())).into())
+		.widget_holder();
+	let space_after = NumberInput::new(Some(tool.options.space_after))
+		.unit(" px")
+		.label("Space After")
+		.min(0.)
+		.max((1_u64 << f64::MANTISSA_DIGITS) as f64)
+		.on_update(|number_input: &NumberInput| TextToolMessage::UpdateOptions(TextOptionsUpdate::SpaceAfter(number_input.value.unwrap())).into())
+		.widget_holder();
 	let character_spacing = NumberInput::new(Some(tool.options.character_spacing))
 		.label("Char. Spacing")
 		.int()
@@ -138,7 +254,151 @@ fn create_text_widgets(tool: &TextTool) -> Vec<WidgetHolder> {
 		.step(0.1)
 		.on_update(|number_input: &NumberInput| TextToolMessage::UpdateOptions(TextOptionsUpdate::CharacterSpacing(number_input.value.unwrap())).into())
 		.widget_holder();
-	vec![
+	let baseline_shift = NumberInput::new(Some(tool.options.baseline_shift))
+		.unit(" px")
+		.label("Baseline Shift")
+		.on_update(|number_input: &NumberInput| TextToolMessage::UpdateOptions(TextOptionsUpdate::BaselineShift(number_input.value.unwrap())).into())
+		.widget_holder();
+	let script_entries = TextScript::list()
+		.into_iter()
+		.map(|script| {
+			RadioEntryData::new(format!("{script:?}"))
+				.label(script.to_string())
+				.tooltip(format!("Script: {script}"))
+				.on_update(move |_| TextToolMessage::UpdateOptions(TextOptionsUpdate::Script(script)).into())
+		})
+		.collect();
+	let script = RadioInput::new(script_entries).selected_index(Some(tool.options.script as u32)).widget_holder();
+	let underline = CheckboxInput::new(tool.options.underline)
+		.icon("TextUnderline")
+		.tooltip("Underline")
+		.on_update(|&CheckboxInput { checked, .. }| TextToolMessage::UpdateOptions(TextOptionsUpdate::Underline(checked)).into())
+		.widget_holder();
+	let strikethrough = CheckboxInput::new(tool.options.strikethrough)
+		.icon("TextStrikethrough")
+		.tooltip("Strikethrough")
+		.on_update(|&CheckboxInput { checked, .. }| TextToolMessage::UpdateOptions(TextOptionsUpdate::Strikethrough(checked)).into())
+		.widget_holder();
+	let case_entries = TextCase::list()
+		.into_iter()
+		.map(|case| {
+			MenuListEntry::new(format!("{case:?}"))
+				.label(case.to_string())
+				.on_commit(move |_| TextToolMessage::UpdateOptions(TextOptionsUpdate::Case(case)).into())
+		})
+		.collect();
+	let case = DropdownInput::new(vec![case_entries])
+		.selected_index(Some(tool.options.case as u32))
+		.tooltip("Case (shown without changing the text)")
+		.widget_holder();
+	let align_entries = TextAlign::list()
+		.into_iter()
+		.map(|align| {
+			RadioEntryData::new(format!("{align:?}"))
+				.label(align.to_string())
+				.tooltip(format!("Align Text: {align}"))
+				.on_update(move |_| TextToolMessage::UpdateOptions(TextOptionsUpdate::Alignment(align)).into())
+		})
+		.collect();
+	let align = RadioInput::new(align_entries).selected_index(Some(tool.options.align as u32)).widget_holder();
+	let vertical_align_entries = VerticalAlign::list()
+		.into_iter()
+		.map(|vertical_align| {
+			RadioEntryData::new(format!("{vertical_align:?}"))
+				.label(vertical_align.to_string())
+				.tooltip(format!("Align Text: {vertical_align} (in text areas with a fixed height)"))
+				.on_update(move |_| TextToolMessage::UpdateOptions(TextOptionsUpdate::VerticalAlignment(vertical_align)).into())
+		})
+		.collect();
+	let vertical_align = RadioInput::new(vertical_align_entries).selected_index(Some(tool.options.vertical_align as u32)).widget_holder();
+	let wrap_entries = WrapMode::list()
+		.into_iter()
+		.map(|wrap| {
+			let tooltip = match wrap {
+				WrapMode::Word => "Wrap: Between Words (in text areas with a fixed width)",
+				WrapMode::Character => "Wrap: Between Any Characters (in text areas with a fixed width)",
+				WrapMode::None => "Wrap: None (cut off at the width of text areas with a fixed width)",
+			};
+			RadioEntryData::new(format!("{wrap:?}"))
+				.label(wrap.to_string())
+				.tooltip(tooltip)
+				.on_update(move |_| TextToolMessage::UpdateOptions(TextOptionsUpdate::Wrap(wrap)).into())
+		})
+		.collect();
+	let wrap = RadioInput::new(wrap_entries).selected_index(Some(tool.options.wrap as u32)).widget_holder();
+	let auto_height_tooltip = "Text areas drawn with a drag grow to fit their text, keeping the width they're drawn with (or the height, for vertical text)";
+	let auto_height_checkbox = CheckboxInput::new(tool.options.auto_height)
+		.on_update(|&CheckboxInput { checked, .. }| TextToolMessage::UpdateOptions(TextOptionsUpdate::AutoHeight(checked)).into())
+		.tooltip(auto_height_tooltip)
+		.widget_holder();
+	let auto_height_label = TextLabel::new("Auto Height").tooltip(auto_height_tooltip).widget_holder();
+	let hyphenate_tooltip = if HYPHENATION_AVAILABLE {
+		"Break words across lines with a hyphen where the language allows, when they don't fit on the line"
+	} else {
+		"Hyphenation isn't included in this build of Graphite"
+	};
+	let hyphenate_checkbox = CheckboxInput::new(tool.options.hyphenate && HYPHENATION_AVAILABLE)
+		.disabled(!HYPHENATION_AVAILABLE)
+		.on_update(|&CheckboxInput { checked, .. }| TextToolMessage::UpdateOptions(TextOptionsUpdate::Hyphenate(checked)).into())
+		.tooltip(hyphenate_tooltip)
+		.widget_holder();
+	let hyphenate_label = TextLabel::new("Hyphenate").disabled(!HYPHENATION_AVAILABLE).tooltip(hyphenate_tooltip).widget_holder();
+	let language_entries = TextLanguage::list()
+		.into_iter()
+		.map(|language| {
+			MenuListEntry::new(format!("{language:?}"))
+				.label(language.to_string())
+				.on_commit(move |_| TextToolMessage::UpdateOptions(TextOptionsUpdate::Language(language)).into())
+		})
+		.collect();
+	let language = DropdownInput::new(vec![language_entries])
+		.selected_index(Some(tool.options.language as u32))
+		.disabled(!(tool.options.hyphenate && HYPHENATION_AVAILABLE))
+		.tooltip("Language (decides where words are hyphenated)")
+		.widget_holder();
+	let direction_entries = TextDirection::list()
+		.into_iter()
+		.map(|direction| {
+			let tooltip = match direction {
+				TextDirection::Auto => "Text Direction: Auto (from the first letter of each paragraph)",
+				TextDirection::LeftToRight => "Text Direction: Left to Right",
+				TextDirection::RightToLeft => "Text Direction: Right to Left",
+			};
+			RadioEntryData::new(format!("{direction:?}"))
+				.label(direction.to_string())
+				.tooltip(tooltip)
+				.on_update(move |_| TextToolMessage::UpdateOptions(TextOptionsUpdate::Direction(direction)).into())
+		})
+		.collect();
+	let direction = RadioInput::new(direction_entries).selected_index(Some(tool.options.direction as u32)).widget_holder();
+	let writing_mode_entries = WritingMode::list()
+		.into_iter()
+		.map(|writing_mode| {
+			let tooltip = match writing_mode {
+				WritingMode::Horizontal => "Writing Mode: Horizontal",
+				WritingMode::VerticalRightToLeft => "Writing Mode: Vertical (in columns from right to left)",
+			};
+			RadioEntryData::new(format!("{writing_mode:?}"))
+				.label(writing_mode.to_string())
+				.tooltip(tooltip)
+				.on_update(move |_| TextToolMessage::UpdateOptions(TextOptionsUpdate::WritingMode(writing_mode)).into())
+		})
+		.collect();
+	let writing_mode = RadioInput::new(writing_mode_entries).selected_index(Some(tool.options.writing_mode as u32)).widget_holder();
+	let orientation_entries = TextOrientation::list()
+		.into_iter()
+		.map(|orientation| {
+			RadioEntryData::new(format!("{orientation:?}"))
+				.label(orientation.to_string())
+				.tooltip(format!("Glyph Orientation: {orientation} (in vertical text)"))
+				.on_update(move |_| TextToolMessage::UpdateOptions(TextOptionsUpdate::Orientation(orientation)).into())
+		})
+		.collect();
+	let orientation = RadioInput::new(orientation_entries)
+		.selected_index(Some(tool.options.orientation as u32))
+		.disabled(tool.options.writing_mode == WritingMode::Horizontal)
+		.widget_holder();
+	let mut widgets = vec![
 		font,
 		Separator::new(SeparatorType::Related).widget_holder(),
 		style,
@@ -147,8 +407,67 @@ fn create_text_widgets(tool: &TextTool) -> Vec<WidgetHolder> {
 		Separator::new(SeparatorType::Related).widget_holder(),
 		line_height_ratio,
 		Separator::new(SeparatorType::Related).widget_holder(),
+		space_before,
+		Separator::new(SeparatorType::Related).widget_holder(),
+		space_after,
+		Separator::new(SeparatorType::Related).widget_holder(),
 		character_spacing,
-	]
+		Separator::new(SeparatorType::Related).widget_holder(),
+		baseline_shift,
+		Separator::new(SeparatorType::Related).widget_holder(),
+		script,
+		Separator::new(SeparatorType::Related).widget_holder(),
+		underline,
+		strikethrough,
+		Separator::new(SeparatorType::Related).widget_holder(),
+		case,
+		Separator::new(SeparatorType::Related).widget_holder(),
+		align,
+		Separator::new(SeparatorType::Related).widget_holder(),
+		vertical_align,
+		Separator::new(SeparatorType::Related).widget_holder(),
+		wrap,
+		Separator::new(SeparatorType::Related).widget_holder(),
+		auto_height_checkbox,
+		auto_height_label,
+		Separator::new(SeparatorType::Related).widget_holder(),
+		hyphenate_checkbox,
+		hyphenate_label,
+		Separator::new(SeparatorType::Related).widget_holder(),
+		language,
+		Separator::new(SeparatorType::Related).widget_holder(),
+		direction,
+		Separator::new(SeparatorType::Related).widget_holder(),
+		writing_mode,
+		Separator::new(SeparatorType::Related).widget_holder(),
+		orientation,
+		Separator::new(SeparatorType::Related).widget_holder(),
+		font_features_popover(tool.options.features, |features| TextToolMessage::UpdateOptions(TextOptionsUpdate::Features(*features)).into(), false),
+		Separator::new(SeparatorType::Related).widget_holder(),
+		TextButton::new("Glyphs")
+			.tooltip("Insert a special character from the font at the caret of the text being edited")
+			.disabled(tool.fsm_state != TextToolFsmState::Editing)
+			.on_update(|_| TextToolMessage::OpenGlyphPalette.into())
+			.widget_holder(),
+		Separator::new(SeparatorType::Related).widget_holder(),
+		TextButton::new("Placeholder")
+			.tooltip("Fill the empty text box being edited with placeholder text sized to it")
+			.disabled(tool.fsm_state != TextToolFsmState::Editing)
+			.on_update(|_| TextToolMessage::FillWithPlaceholder.into())
+			.widget_holder(),
+	];
+	for axis in tool.variation_axes.iter().flat_map(|(_, axes)| axes) {
+		widgets.extend([
+			Separator::new(SeparatorType::Related).widget_holder(),
+			font_variation_input(
+				tool.options.variations,
+				axis,
+				|variations| TextToolMessage::UpdateOptions(TextOptionsUpdate::Variations(*variations)).into(),
+				false,
+			),
+		]);
+	}
+	widgets
 }
 
 impl LayoutHolder for TextTool {
@@ -171,20 +490,105 @@ impl LayoutHolder for TextTool {
 
 impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for TextTool {
 	fn process_message(&mut self, message: ToolMessage, responses: &mut VecDeque<Message>, tool_data: &mut ToolActionHandlerData<'a>) {
+		// The sliders for the axes of a variable font are shown once the font has been loaded
+		let font = Font::new(self.options.font_name.clone(), self.options.font_style.clone());
+		if self.variation_axes.as_ref().is_none_or(|(axes_font, _)| *axes_font != font) && tool_data.font_cache.loaded_font(&font) {
+			let axes = tool_data.font_cache.get(&font).map(|data| variation_axes(data)).unwrap_or_default();
+			self.variation_axes = Some((font, axes));
+			self.send_layout(responses, LayoutTarget::ToolOptions);
+		}
+
 		let ToolMessage::Text(TextToolMessage::UpdateOptions(action)) = message else {
+			let was_editing = self.fsm_state == TextToolFsmState::Editing;
 			self.fsm_state.process_event(message, &mut self.tool_data, tool_data, &self.options, responses, true);
+
+			// The glyph palette button is only enabled while editing
+			if was_editing != (self.fsm_state == TextToolFsmState::Editing) {
+				self.send_layout(responses, LayoutTarget::ToolOptions);
+			}
 			return;
 		};
+		// While editing, the font, size, fill color, baseline shift, script, underline, and strikethrough options style the selected part of the text
+		if self.fsm_state == TextToolFsmState::Editing {
+			let style = match &action {
+				TextOptionsUpdate::Font { family, style } => Some(SelectionStyle {
+					font: Some(Font::new(family.clone(), style.clone())),
+					..Default::default()
+				}),
+				TextOptionsUpdate::FontSize(font_size) => Some(SelectionStyle {
+					font_size: Some(*font_size),
+					..Default::default()
+				}),
+				TextOptionsUpdate::FillColor(Some(color)) => Some(SelectionStyle {
+					color: Some(color.to_gamma_srgb()),
+					..Default::default()
+				}),
+				TextOptionsUpdate::BaselineShift(baseline_shift) => Some(SelectionStyle {
+					baseline_shift: Some(*baseline_shift),
+					..Default::default()
+				}),
+				TextOptionsUpdate::Script(script) => Some(SelectionStyle {
+					script: Some(*script),
+					..Default::default()
+				}),
+				TextOptionsUpdate::Underline(underline) => Some(SelectionStyle {
+					underline: Some(*underline),
+					..Default::default()
+				}),
+				TextOptionsUpdate::Strikethrough(strikethrough) => Some(SelectionStyle {
+					strikethrough: Some(*strikethrough),
+					..Default::default()
+				}),
+				_ => None,
+			};
+			if let Some(style) = style {
+				responses.add(TextToolMessage::StyleSelection(style));
+			}
+			// The text area being edited follows the auto height option
+			if let TextOptionsUpdate::AutoHeight(enabled) = action {
+				responses.add(TextToolMessage::SetAutoHeight { enabled });
+			}
+		}
+
 		match action {
 			TextOptionsUpdate::Font { family, style } => {
 				self.options.font_name = family;
 				self.options.font_style = style;
+				// The axes of the previous font don't apply to the new one
+				self.options.variations = FontVariations::default();
+				self.variation_axes = None;
 
 				self.send_layout(responses, LayoutTarget::ToolOptions);
 			}
 			TextOptionsUpdate::FontSize(font_size) => self.options.font_size = font_size,
 			TextOptionsUpdate::LineHeightRatio(line_height_ratio) => self.options.line_height_ratio = line_height_ratio,
+			TextOptionsUpdate::SpaceBefore(space_before) => self.options.space_before = space_before,
+			TextOptionsUpdate::SpaceAfter(space_after) => self.options.space_after = space_after,
 			TextOptionsUpdate::CharacterSpacing(character_spacing) => self.options.character_spacing = character_spacing,
+			TextOptionsUpdate::BaselineShift(baseline_shift) => self.options.baseline_shift = baseline_shift,
+			TextOptionsUpdate::Script(script) => self.options.script = script,
+			TextOptionsUpdate::Underline(underline) => self.options.underline = underline,
+			TextOptionsUpdate::Strikethrough(strikethrough) => self.options.strikethrough = strikethrough,
+			TextOptionsUpdate::Case(case) => self.options.case = case,
+			TextOptionsUpdate::Hyphenate(hyphenate) => self.options.hyphenate = hyphenate,
+			TextOptionsUpdate::Language(language) => self.options.language = language,
+			TextOptionsUpdate::Alignment(align) => self.options.align = align,
+			TextOptionsUpdate::VerticalAlignment(vertical_align) => self.options.vertical_align = vertical_align,
+			TextOptionsUpdate::Wrap(wrap) => self.options.wrap = wrap,
+			TextOptionsUpdate::AutoHeight(auto_height) => self.options.auto_height = auto_height,
+			TextOptionsUpdate::Direction(direction) => self.options.direction = direction,
+			TextOptionsUpdate::WritingMode(writing_mode) => {
+				self.options.writing_mode = writing_mode;
+
+				self.send_layout(responses, LayoutTarget::ToolOptions);
+			}
+			TextOptionsUpdate::Orientation(orientation) => self.options.orientation = orientation,
+			TextOptionsUpdate::Features(features) => {
+				self.options.features = features;
+
+				self.send_layout(responses, LayoutTarget::ToolOptions);
+			}
+			TextOptionsUpdate::Variations(variations) => self.options.variations = variations,
 			TextOptionsUpdate::FillColor(color) => {
 				self.options.fill.custom_color = color;
 				self.options.fill.color_type = ToolColorType::Custom;
@@ -197,18 +601,28 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for TextToo
 		}
 
 		self.send_layout(responses, LayoutTarget::ToolOptions);
+		self.save_options(responses);
 	}
 
 	fn actions(&self) -> ActionList {
 		match self.fsm_state {
 			TextToolFsmState::Ready => actions!(TextToolMessageDiscriminant;
 				DragStart,
+				Abort,
 				PointerOutsideViewport,
 				PointerMove,
 			),
 			TextToolFsmState::Editing => actions!(TextToolMessageDiscriminant;
 				DragStart,
+				DragStop,
+				PointerMove,
 				Abort,
+				DeleteBackward,
+				DeleteForward,
+				MoveCaret,
+				OpenGlyphPalette,
+				FillWithPlaceholder,
+				SelectAll,
 			),
 			TextToolFsmState::Placing | TextToolFsmState::Dragging => actions!(TextToolMessageDiscriminant;
 				DragStop,
@@ -222,6 +636,11 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for TextToo
 				PointerMove,
 				PointerOutsideViewport,
 			),
+			TextToolFsmState::DraggingWarpHandle => actions!(TextToolMessageDiscriminant;
+				DragStop,
+				Abort,
+				PointerMove,
+			),
 		}
 	}
 }
@@ -243,7 +662,7 @@ enum TextToolFsmState {
 	/// The tool is ready to place or edit text.
 	#[default]
 	Ready,
-	/// The user is typing in the interactive viewport text area.
+	/// The user is typing in the text being edited, with its caret and selection shown in the viewport.
 	Editing,
 	/// The user is dragging to create a new text area.
 	Placing,
@@ -251,11 +670,31 @@ enum TextToolFsmState {
 	Dragging,
 	/// The user is dragging to resize the text area.
 	ResizingBounds,
+	/// The user is dragging a handle of the Text Warp node bending the outlines of the selected text layer.
+	DraggingWarpHandle,
+}
+
+/// A handle shown in the viewport for reshaping the warp of a text layer by its Text Warp node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum WarpHandle {
+	/// Raises or lowers the top edge of the Arc, Flag, and Bulge styles where it's bent the furthest.
+	Bend,
+	/// One of the corners of the Envelope style, clockwise from the top left.
+	Corner(usize),
+}
+
+impl WarpHandle {
+	/// Where the handle is on the bounding box of the outlines before they're warped.
+	fn unwarped_position(self, style: WarpStyle, bounds: [DVec2; 2]) -> DVec2 {
+		match self {
+			WarpHandle::Bend => DVec2::new(bounds[0].x + (bounds[1].x - bounds[0].x) * style.bend_peak(), bounds[0].y),
+			WarpHandle::Corner(index) => [bounds[0], DVec2::new(bounds[1].x, bounds[0].y), bounds[1], DVec2::new(bounds[0].x, bounds[1].y)][index],
+		}
+	}
 }
 
 #[derive(Clone, Debug)]
 pub struct EditingText {
-	text: String,
 	font: Font,
 	typesetting: TypesettingConfig,
 	color: Option<Color>,
@@ -274,6 +713,15 @@ struct TextToolData {
 	layer: LayerNodeIdentifier,
 	editing_text: Option<EditingText>,
 	new_text: String,
+	new_spans: Vec<TextSpan>,
+	/// The character index in the text being edited where the caret is.
+	caret: usize,
+	/// The other end of the selection from the caret, which is at the caret when nothing is selected.
+	selection_anchor: usize,
+	/// The characters being composed by an input method, which are replaced each time the composition changes until it's committed.
+	composition: Option<Range<usize>>,
+	/// Whether the selection is being dragged out from where the text was clicked.
+	selecting: bool,
 	drag_start: DVec2,
 	drag_current: DVec2,
 	resize: Resize,
@@ -285,12 +733,20 @@ struct TextToolData {
 	snap_candidates: Vec<SnapCandidatePoint>,
 	// TODO: Handle multiple layers in the future
 	layer_dragging: Option<ResizingLayer>,
+	/// Whether the text box being resized is the one being edited, which goes back to being edited once it's resized.
+	resizing_edited_text: bool,
+	/// The text layer whose overflow port was clicked, which the next text box placed is linked to.
+	linking_from: Option<LayerNodeIdentifier>,
+	/// The text layer whose warp is being reshaped, along with the handle being dragged.
+	warp_dragging: Option<(LayerNodeIdentifier, WarpHandle)>,
+	/// Whether the hints shown in the Ready state include reshaping the warp, since the selected text layer has warp handles.
+	warp_hint_shown: bool,
 }
 
 impl TextToolData {
-	fn delete_empty_layer(&mut self, font_cache: &FontCache, responses: &mut VecDeque<Message>) -> TextToolFsmState {
+	fn delete_empty_layer(&mut self, responses: &mut VecDeque<Message>) -> TextToolFsmState {
 		// Remove the editable textbox UI first
-		self.set_editing(false, font_cache, responses);
+		self.set_editing(false, responses);
 
 		// Delete the empty text layer and update the graph
 		responses.add(NodeGraphMessage::DeleteNodes {
@@ -302,18 +758,9 @@ impl TextToolData {
 		TextToolFsmState::Ready
 	}
 	/// Set the editing state of the currently modifying layer
-	fn set_editing(&self, editable: bool, font_cache: &FontCache, responses: &mut VecDeque<Message>) {
-		if let Some(editing_text) = self.editing_text.as_ref().filter(|_| editable) {
-			responses.add(FrontendMessage::DisplayEditableTextbox {
-				text: editing_text.text.clone(),
-				line_height_ratio: editing_text.typesetting.line_height_ratio,
-				font_size: editing_text.typesetting.font_size,
-				color: editing_text.color.unwrap_or(Color::BLACK),
-				url: font_cache.get_preview_url(&editing_text.font).cloned().unwrap_or_default(),
-				transform: editing_text.transform.to_cols_array(),
-				max_width: editing_text.typesetting.max_width,
-				max_height: editing_text.typesetting.max_height,
-			});
+	fn set_editing(&self, editable: bool, responses: &mut VecDeque<Message>) {
+		if self.editing_text.is_some() && editable {
+			self.update_textbox(responses);
 		} else {
 			// Check if DisplayRemoveEditableTextbox is already in the responses queue
 			let has_remove_textbox = responses.iter().any(|msg| matches!(msg, Message::Frontend(FrontendMessage::DisplayRemoveEditableTextbox)));
@@ -325,49 +772,150 @@ impl TextToolData {
 		}
 	}
 
+	/// Gives the frontend's input element, which takes the typing and input method composition, the selected characters so they can be copied and cut from it.
+	fn update_textbox(&self, responses: &mut VecDeque<Message>) {
+		let selection = self.selection();
+		let selected_text = self.new_text.chars().skip(selection.start).take(selection.len()).collect();
+		responses.add(FrontendMessage::DisplayEditableTextbox { selected_text });
+		responses.add(OverlaysMessage::Draw);
+	}
+
+	/// The range of characters selected in the text being edited, which is empty when nothing is selected.
+	fn selection(&self) -> Range<usize> {
+		self.caret.min(self.selection_anchor)..self.caret.max(self.selection_anchor)
+	}
+
+	/// Moves the caret to the character index, extending the selection to it or else leaving nothing selected.
+	fn set_caret(&mut self, index: usize, extend: bool, responses: &mut VecDeque<Message>) {
+		self.caret = index.min(self.new_text.chars().count());
+		if !extend {
+			self.selection_anchor = self.caret;
+		}
+		self.update_textbox(responses);
+	}
+
+	/// The position of the caret at each boundary between the characters of the text being edited, in the coordinates of its layer.
+	fn caret_positions(&self, document: &DocumentMessageHandler, font_cache: &FontCache) -> Vec<CaretPosition> {
+		let Some(editing_text) = &self.editing_text else { return Vec::new() };
+		let path = text_path(self.layer, document);
+		StyledText::new(&self.new_text, &editing_text.font, &self.new_spans, editing_text.typesetting, font_cache)
+			.map(|styled_text| styled_text.caret_positions(&self.new_text, editing_text.typesetting, path.as_ref()))
+			.unwrap_or_default()
+	}
+
+	/// Moves the caret to the boundary between characters closest to the viewport position, like where the text was clicked.
+	fn place_caret(&mut self, position: DVec2, extend: bool, document: &DocumentMessageHandler, font_cache: &FontCache, responses: &mut VecDeque<Message>) {
+		let position = document.metadata().transform_to_viewport(self.layer).inverse().transform_point2(position);
+		let distance = |caret: &CaretPosition| {
+			let [start, end] = caret.ends;
+			let t = (position - start).dot(end - start) / (end - start).length_squared().max(f64::EPSILON);
+			position.distance(start.lerp(end, t.clamp(0., 1.)))
+		};
+		// The caret is only placed between graphemes, never within one
+		let carets = self.caret_positions(document, font_cache);
+		let closest = grapheme_boundaries(&self.new_text)
+			.into_iter()
+			.filter_map(|index| Some((index, carets.get(index)?)))
+			.min_by(|(_, a), (_, b)| distance(a).total_cmp(&distance(b)))
+			.map(|(index, _)| index);
+
+		if let Some(index) = closest {
+			self.set_caret(index, extend, responses);
+		}
+	}
+
+	/// Replaces the characters in the range with the text, keeping the spans in sync, and places the caret after the inserted text.
+	fn replace_text(&mut self, range: Range<usize>, text: &str, document: &DocumentMessageHandler, responses: &mut VecDeque<Message>) {
+		let byte_index = |index: usize| self.new_text.char_indices().nth(index).map_or(self.new_text.len(), |(byte_index, _)| byte_index);
+		let bytes = byte_index(range.start)..byte_index(range.end);
+		self.new_text.replace_range(bytes, text);
+
+		let inserted = text.chars().count();
+		self.new_spans = splice_spans(&self.new_spans, range.clone(), inserted);
+		self.caret = range.start + inserted;
+		self.selection_anchor = self.caret;
+
+		self.update_text_node(document, responses);
+	}
+
+	/// Shows the text being edited, along with its spans, in its layer.
+	fn update_text_node(&self, document: &DocumentMessageHandler, responses: &mut VecDeque<Message>) {
+		let Some(text_id) = graph_modification_utils::get_text_id(self.layer, &document.network_interface) else {
+			return;
+		};
+
+		responses.add(NodeGraphMessage::SetInput {
+			input_connector: InputConnector::node(text_id, 1),
+			input: NodeInput::value(TaggedValue::String(self.new_text.clone()), false),
+		});
+		responses.add(NodeGraphMessage::SetInput {
+			input_connector: InputConnector::node(text_id, 10),
+			input: NodeInput::value(TaggedValue::TextSpans(self.new_spans.clone()), false),
+		});
+		responses.add(NodeGraphMessage::RunDocumentGraph);
+		responses.add(OverlaysMessage::Draw);
+	}
+
+	/// Finishes editing, leaving the edited text in its layer, or deleting the layer if the text was left empty.
+	fn commit(&mut self, responses: &mut VecDeque<Message>) -> TextToolFsmState {
+		self.composition = None;
+		self.selecting = false;
+		if self.new_text.is_empty() {
+			return self.delete_empty_layer(responses);
+		}
+
+		self.set_editing(false, responses);
+		responses.add(OverlaysMessage::Draw);
+
+		TextToolFsmState::Ready
+	}
+
 	fn load_layer_text_node(&mut self, document: &DocumentMessageHandler) -> Option<()> {
 		let transform = document.metadata().transform_to_viewport(self.layer);
 		let color = graph_modification_utils::get_fill_color(self.layer, &document.network_interface).unwrap_or(Color::BLACK);
 		let (text, font, typesetting) = graph_modification_utils::get_text(self.layer, &document.network_interface)?;
+		let spans = graph_modification_utils::get_text_spans(self.layer, &document.network_interface).cloned().unwrap_or_default();
 		self.editing_text = Some(EditingText {
-			text: text.clone(),
 			font: font.clone(),
 			typesetting,
 			color: Some(color),
 			transform,
 		});
 		self.new_text.clone_from(text);
+		self.new_spans = spans;
+		self.caret = self.new_text.chars().count();
+		self.selection_anchor = self.caret;
+		self.composition = None;
 		Some(())
 	}
 
-	fn start_editing_layer(&mut self, layer: LayerNodeIdentifier, tool_state: TextToolFsmState, document: &DocumentMessageHandler, font_cache: &FontCache, responses: &mut VecDeque<Message>) {
+	fn start_editing_layer(&mut self, layer: LayerNodeIdentifier, tool_state: TextToolFsmState, document: &DocumentMessageHandler, responses: &mut VecDeque<Message>) {
 		if layer == LayerNodeIdentifier::ROOT_PARENT {
 			log::error!("Cannot edit ROOT_PARENT in TextTooLData")
 		}
 
 		if tool_state == TextToolFsmState::Editing {
-			self.set_editing(false, font_cache, responses);
+			self.set_editing(false, responses);
 		}
 
-		self.layer = layer;
+		// The text flowing through linked text boxes is edited in the first of them, where it's stored
+		self.layer = graph_modification_utils::get_first_text_frame(layer, &document.network_interface);
 		if self.load_layer_text_node(document).is_some() {
 			responses.add(DocumentMessage::AddTransaction);
 
-			self.set_editing(true, font_cache, responses);
+			self.set_editing(true, responses);
 
 			responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![self.layer.to_node()] });
-			// Make the rendered text invisible while editing
-			responses.add(NodeGraphMessage::SetInput {
-				input_connector: InputConnector::node(graph_modification_utils::get_text_id(self.layer, &document.network_interface).unwrap(), 1),
-				input: NodeInput::value(TaggedValue::String("".to_string()), false),
-			});
-			responses.add(NodeGraphMessage::RunDocumentGraph);
 		};
 	}
 
-	fn new_text(&mut self, document: &DocumentMessageHandler, editing_text: EditingText, font_cache: &FontCache, responses: &mut VecDeque<Message>) {
+	fn new_text(&mut self, document: &DocumentMessageHandler, editing_text: EditingText, path: Option<(LayerNodeIdentifier, f64)>, responses: &mut VecDeque<Message>) {
 		// Create new text
 		self.new_text = String::new();
+		self.new_spans = Vec::new();
+		self.caret = 0;
+		self.selection_anchor = 0;
+		self.composition = None;
 		responses.add(DocumentMessage::AddTransaction);
 
 		self.layer = LayerNodeIdentifier::new_unchecked(NodeId::new());
@@ -377,7 +925,10 @@ impl TextToolData {
 			text: String::new(),
 			font: editing_text.font.clone(),
 			typesetting: editing_text.typesetting,
-			parent: document.new_layer_parent(true),
+			// Text following a path goes alongside it, where the path's coordinates are those of the text
+			parent: path
+				.and_then(|(path_layer, _)| path_layer.parent(document.metadata()))
+				.unwrap_or_else(|| document.new_layer_parent(true)),
 			insert_index: 0,
 		});
 		responses.add(Message::StartBuffer);
@@ -389,34 +940,262 @@ impl TextToolData {
 				Fill::None
 			},
 		});
+		if let Some((path_layer, start_offset)) = path {
+			responses.add(GraphOperationMessage::TextPathSet {
+				layer: self.layer,
+				path_layer,
+				start_offset,
+			});
+		} else {
+			responses.add(GraphOperationMessage::TransformSet {
+				layer: self.layer,
+				transform: editing_text.transform,
+				transform_in: TransformIn::Viewport,
+				skip_rerender: true,
+			});
+		}
+		self.editing_text = Some(editing_text);
+
+		self.set_editing(true, responses);
+
+		responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![self.layer.to_node()] });
+
+		responses.add(NodeGraphMessage::RunDocumentGraph);
+	}
+
+	/// Places a new text box at the given viewport position, linked to the previous text layer so the text overflowing it continues into the new text box.
+	fn new_linked_frame(&mut self, document: &DocumentMessageHandler, previous: LayerNodeIdentifier, position: DVec2, constraint_size: Option<DVec2>, responses: &mut VecDeque<Message>) -> Option<()> {
+		let network_interface = &document.network_interface;
+		let (_, font, typesetting) = graph_modification_utils::get_text(previous, network_interface)?;
+		let previous_text_node = graph_modification_utils::get_text_id(previous, network_interface)?;
+
+		responses.add(DocumentMessage::AddTransaction);
+
+		// The linked text box continues with the style of the text flowing into it
+		self.layer = LayerNodeIdentifier::new_unchecked(NodeId::new());
+		responses.add(GraphOperationMessage::NewTextLayer {
+			id: self.layer.to_node(),
+			text: String::new(),
+			font: font.clone(),
+			typesetting: TypesettingConfig {
+				max_width: constraint_size.map(|size| size.x),
+				max_height: constraint_size.map(|size| size.y),
+				..typesetting
+			},
+			parent: previous.parent(document.metadata()).unwrap_or_else(|| document.new_layer_parent(true)),
+			insert_index: 0,
+		});
+		responses.add(Message::StartBuffer);
+		let fill = graph_modification_utils::get_fill_color(previous, network_interface).map_or(Fill::None, |color| Fill::Solid(color.to_gamma_srgb()));
+		responses.add(GraphOperationMessage::FillSet { layer: self.layer, fill });
 		responses.add(GraphOperationMessage::TransformSet {
 			layer: self.layer,
-			transform: editing_text.transform,
+			transform: DAffine2::from_translation(position),
 			transform_in: TransformIn::Viewport,
 			skip_rerender: true,
 		});
-		self.editing_text = Some(editing_text);
-
-		self.set_editing(true, font_cache, responses);
+		responses.add(GraphOperationMessage::TextPreviousFrameSet {
+			layer: self.layer,
+			previous_text_node,
+		});
 
 		responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![self.layer.to_node()] });
-
 		responses.add(NodeGraphMessage::RunDocumentGraph);
+
+		Some(())
+	}
+
+	/// Shows whether the text overflows its text box with a red line along the edge it's cut off at, and a red badge on the port for linking it to another text box.
+	fn draw_overflow(layer: LayerNodeIdentifier, linking_from: Option<LayerNodeIdentifier>, document: &DocumentMessageHandler, font_cache: &FontCache, overlay_context: &mut OverlayContext) {
+		// The text is cut off along the bottom edge, or the left edge for vertical text
+		let overflows = text_overflows(layer, document);
+		if overflows {
+			let quad = document.metadata().transform_to_viewport(layer) * text_bounding_box(layer, document, font_cache);
+			let vertical = graph_modification_utils::get_text(layer, &document.network_interface).is_some_and(|(_, _, typesetting)| typesetting.writing_mode == WritingMode::VerticalRightToLeft);
+			let (start, end) = if vertical { (3, 0) } else { (2, 3) };
+			overlay_context.line(quad.0[start], quad.0[end], Some(COLOR_OVERLAY_RED), Some(3.));
+		}
+
+		// The port for linking the overflowing text to another text box is filled once it's linked or being linked
+		if let Some(port) = TextToolData::overflow_port(layer, document, font_cache) {
+			let linked = graph_modification_utils::get_next_text_frame(layer, &document.network_interface).is_some() || linking_from == Some(layer);
+			let stroke = if overflows && !linked {
+				COLOR_OVERLAY_RED.to_string()
+			} else {
+				overlay_context.colors.accent.clone()
+			};
+			let fill = if linked { stroke.clone() } else { overlay_context.colors.fill.clone() };
+			overlay_context.square(port, Some(TEXT_OVERFLOW_PORT_SIZE), Some(&fill), Some(&stroke));
+		}
+	}
+
+	/// The viewport position of the port below the text box's bottom right corner, shown when the text box has a max height which its text overflows or it's linked to another text box.
+	/// Vertical text overflows its max width instead, so the port is left of the text box's bottom left corner.
+	fn overflow_port(layer: LayerNodeIdentifier, document: &DocumentMessageHandler, font_cache: &FontCache) -> Option<DVec2> {
+		let (_, _, typesetting) = graph_modification_utils::get_text(layer, &document.network_interface)?;
+		let vertical = typesetting.writing_mode == WritingMode::VerticalRightToLeft;
+		let overflowed_size = if vertical { typesetting.max_width } else { typesetting.max_height };
+		overflowed_size?;
+		if !text_overflows(layer, document) && graph_modification_utils::get_next_text_frame(layer, &document.network_interface).is_none() {
+			return None;
+		}
+
+		let quad = document.metadata().transform_to_viewport(layer) * text_bounding_box(layer, document, font_cache);
+		if vertical {
+			let left = (quad.0[3] - quad.0[2]).try_normalize().unwrap_or(-DVec2::X);
+			return Some(quad.0[3] + left * TEXT_OVERFLOW_PORT_OFFSET);
+		}
+		let down = (quad.0[2] - quad.0[1]).try_normalize().unwrap_or(DVec2::Y);
+		Some(quad.0[2] + down * TEXT_OVERFLOW_PORT_OFFSET)
+	}
+
+	/// The bounding box of the layer's outlines before they're bent by its Text Warp node, in the coordinates of its Text node.
+	fn unwarped_bounds(layer: LayerNodeIdentifier, document: &DocumentMessageHandler, font_cache: &FontCache) -> Option<[DVec2; 2]> {
+		// The outlines of text following a path are taken from where they were last rendered, which is after they've been warped
+		if graph_modification_utils::text_follows_path(layer, &document.network_interface) {
+			return None;
+		}
+
+		text_outlines(layer, document, font_cache)?.bounding_box()
+	}
+
+	/// The handles for reshaping the warp of the layer by its Text Warp node, with where each would be without the warp and where the warp moves it to, in viewport space.
+	fn warp_handles(layer: LayerNodeIdentifier, document: &DocumentMessageHandler, font_cache: &FontCache) -> Vec<(WarpHandle, DVec2, DVec2)> {
+		let Some((style, bend, corners)) = graph_modification_utils::get_text_warp(layer, &document.network_interface) else {
+			return Vec::new();
+		};
+		let Some(bounds) = TextToolData::unwarped_bounds(layer, document, font_cache) else {
+			return Vec::new();
+		};
+		let transform = document.metadata().transform_to_viewport(layer);
+
+		let handles = match style {
+			WarpStyle::Envelope => (0..4).map(WarpHandle::Corner).collect(),
+			_ => vec![WarpHandle::Bend],
+		};
+		handles
+			.into_iter()
+			.map(|handle| {
+				let unwarped = handle.unwarped_position(style, bounds);
+				let warped = style.warp(unwarped, bounds, bend / 100., corners);
+				(handle, transform.transform_point2(unwarped), transform.transform_point2(warped))
+			})
+			.collect()
+	}
+
+	fn draw_warp_handles(layer: LayerNodeIdentifier, document: &DocumentMessageHandler, font_cache: &FontCache, overlay_context: &mut OverlayContext) {
+		let handles = TextToolData::warp_handles(layer, document, font_cache);
+		if handles.is_empty() {
+			return;
+		}
+
+		// The bend handle is joined to where it would be without the warp, and the corners of the envelope are joined to each other
+		match handles.as_slice() {
+			[(WarpHandle::Bend, unwarped, warped)] => overlay_context.dashed_line(*unwarped, *warped, None, None, Some(4.), Some(4.), None),
+			corners => {
+				let polygon = corners.iter().map(|&(_, _, warped)| warped).collect::<Vec<_>>();
+				overlay_context.dashed_polygon(&polygon, None, Some(4.), Some(4.), None);
+			}
+		}
+		for (_, _, warped) in handles {
+			overlay_context.manipulator_handle(warped, false, None);
+		}
 	}
 
 	fn check_click(document: &DocumentMessageHandler, input: &InputPreprocessorMessageHandler, font_cache: &FontCache) -> Option<LayerNodeIdentifier> {
-		document
-			.metadata()
+		let metadata = document.metadata();
+		let mouse = DVec2::new(input.mouse.position.x, input.mouse.position.y);
+		let is_clicked = |&layer: &LayerNodeIdentifier| {
+			is_layer_fed_by_node_of_name(layer, &document.network_interface, "Text") && (metadata.transform_to_viewport(layer) * text_bounding_box(layer, document, font_cache)).contains(mouse)
+		};
+
+		// Text under the cursor is found quickly from the spatial index of the glyphs' click targets,
+		// only falling back to checking every layer when the click lands in the empty space of a text box
+		let document_mouse = metadata.document_to_viewport.inverse().transform_point2(mouse);
+		let candidates = metadata.layers_overlapping_bounds([document_mouse; 2]).collect::<HashSet<_>>();
+
+		metadata
+			.all_layers()
+			.filter(|layer| candidates.contains(layer))
+			.find(is_clicked)
+			.or_else(|| metadata.all_layers().filter(|layer| !candidates.contains(layer)).find(is_clicked))
+	}
+
+	/// Finds the path of a vector layer, other than text, whose outline was clicked, along with the distance along the path to the clicked point.
+	/// The distance is measured along the first subpath of the layer in the coordinates of its parent, which is the part of the path that text follows.
+	fn check_path_click(document: &DocumentMessageHandler, input: &InputPreprocessorMessageHandler) -> Option<(LayerNodeIdentifier, f64)> {
+		let metadata = document.metadata();
+		let mouse = input.mouse.position;
+
+		let viewport_to_document = metadata.document_to_viewport.inverse();
+		let document_mouse = viewport_to_document.transform_point2(mouse);
+		let tolerance = DVec2::splat(viewport_to_document.transform_vector2(DVec2::X * SELECTION_TOLERANCE).length());
+		let candidates = metadata.layers_overlapping_bounds([document_mouse - tolerance, document_mouse + tolerance]).collect::<HashSet<_>>();
+
+		metadata
 			.all_layers()
-			.filter(|&layer| is_layer_fed_by_node_of_name(layer, &document.network_interface, "Text"))
-			.find(|&layer| {
-				let transformed_quad = document.metadata().transform_to_viewport(layer) * text_bounding_box(layer, document, font_cache);
-				let mouse = DVec2::new(input.mouse.position.x, input.mouse.position.y);
+			.filter(|layer| candidates.contains(layer) && !is_layer_fed_by_node_of_name(*layer, &document.network_interface, "Text"))
+			.find_map(|layer| {
+				let vector_data = document.network_interface.compute_modified_vector(layer)?;
+				let mut subpath = vector_data.stroke_bezier_paths().next()?;
+
+				let parent_to_viewport = metadata.downstream_transform_to_viewport(layer);
+				subpath.apply_transform(parent_to_viewport.inverse() * metadata.transform_to_viewport(layer));
+
+				let (segment_index, t) = subpath.project(parent_to_viewport.inverse().transform_point2(mouse))?;
+				let segment = subpath.iter().nth(segment_index)?;
+				let clicked_point = parent_to_viewport.transform_point2(segment.evaluate(TValue::Parametric(t)));
+				if clicked_point.distance(mouse) > SELECTION_TOLERANCE {
+					return None;
+				}
 
-				transformed_quad.contains(mouse)
+				let preceding_length: f64 = subpath.iter().take(segment_index).map(|bezier| bezier.length(None)).sum();
+				let start_offset = preceding_length + segment.trim(TValue::Parametric(0.), TValue::Parametric(t)).length(None);
+				Some((layer, start_offset))
 			})
 	}
 
+	/// Starts resizing the text box of the layer if one of the handles of its bounds was clicked, returning whether it was.
+	fn start_resizing_bounds(
+		&mut self,
+		layer: Option<LayerNodeIdentifier>,
+		document: &DocumentMessageHandler,
+		input: &InputPreprocessorMessageHandler,
+		font_cache: &FontCache,
+		responses: &mut VecDeque<Message>,
+	) -> bool {
+		let dragging_bounds = self.bounding_box_manager.as_mut().and_then(|bounding_box| {
+			let edges = bounding_box.check_selected_edges(input);
+
+			bounding_box.selected_edges = edges.map(|(top, bottom, left, right)| {
+				let selected_edges = SelectedEdges::new(top, bottom, left, right, bounding_box.bounds);
+				bounding_box.opposite_pivot = selected_edges.calculate_pivot();
+				selected_edges
+			});
+
+			edges
+		});
+		if dragging_bounds.is_none() {
+			return false;
+		}
+
+		responses.add(DocumentMessage::StartTransaction);
+
+		// Set the original transform
+		if let Some(id) = layer {
+			let original_transform = document.metadata().transform_to_document(id);
+			self.layer_dragging = Some(ResizingLayer { id, original_transform });
+		}
+
+		if let Some(bounds) = &mut self.bounding_box_manager {
+			bounds.original_bound_transform = bounds.transform;
+			bounds.center_of_transformation = bounds.transform.transform_point2((bounds.bounds[0] + bounds.bounds[1]) / 2.);
+		}
+		self.get_snap_candidates(document, font_cache);
+
+		true
+	}
+
 	fn get_snap_candidates(&mut self, document: &DocumentMessageHandler, font_cache: &FontCache) {
 		self.snap_candidates.clear();
 
@@ -444,6 +1223,26 @@ fn can_edit_selected(document: &DocumentMessageHandler) -> Option<LayerNodeIdent
 	Some(layer)
 }
 
+impl TextToolFsmState {
+	fn ready_hints(warp_handles: bool) -> HintData {
+		let mut hint_groups = vec![
+			HintGroup(vec![HintInfo::mouse(MouseMotion::Lmb, "Place Text")]),
+			HintGroup(vec![
+				HintInfo::mouse(MouseMotion::LmbDrag, "Place Text Box"),
+				HintInfo::keys([Key::Shift], "Constrain Square").prepend_plus(),
+				HintInfo::keys([Key::Alt], "From Center").prepend_plus(),
+			]),
+			HintGroup(vec![HintInfo::mouse(MouseMotion::Lmb, "Edit Text")]),
+			HintGroup(vec![HintInfo::keys_and_mouse([Key::Alt], MouseMotion::Lmb, "Place Text on Path")]),
+			HintGroup(vec![HintInfo::mouse(MouseMotion::Lmb, "Link Overflowing Text to New Text Box")]),
+		];
+		if warp_handles {
+			hint_groups.push(HintGroup(vec![HintInfo::mouse(MouseMotion::LmbDrag, "Reshape Warp")]));
+		}
+		HintData(hint_groups)
+	}
+}
+
 impl Fsm for TextToolFsmState {
 	type ToolData = TextToolData;
 	type ToolOptions = TextOptions;
@@ -454,45 +1253,97 @@ impl Fsm for TextToolFsmState {
 			global_tool_data,
 			input,
 			font_cache,
+			preferences,
 			..
 		} = transition_data;
-		let fill_color = graphene_std::Color::from_rgb_str(crate::consts::COLOR_OVERLAY_BLUE.strip_prefix('#').unwrap())
-			.unwrap()
-			.with_alpha(0.05)
-			.to_rgba_hex_srgb();
 
 		let ToolMessage::Text(event) = event else { return self };
+		// The hints shown upon returning to the Ready state don't include reshaping the warp until the pointer moves
+		if self != TextToolFsmState::Ready {
+			tool_data.warp_hint_shown = false;
+		}
 		match (self, event) {
 			(TextToolFsmState::Editing, TextToolMessage::Overlays(mut overlay_context)) => {
-				responses.add(FrontendMessage::DisplayEditableTextboxTransform {
-					transform: document.metadata().transform_to_viewport(tool_data.layer).to_cols_array(),
-				});
+				// The text's bounds are the only overlay while editing, so they're drawn entirely in their own color
+				overlay_context.colors.accent = overlay_context.colors.text_bounds.clone();
+				let fill_color = OverlayColors::with_alpha(&overlay_context.colors.accent, 0.05);
+				let transform = document.metadata().transform_to_viewport(tool_data.layer);
 				if let Some(editing_text) = tool_data.editing_text.as_mut() {
-					let buzz_face = font_cache.get(&editing_text.font).map(|data| load_face(data));
-					let far = graphene_core::text::bounding_box(&tool_data.new_text, buzz_face.as_ref(), editing_text.typesetting, false);
+					let styled_text = StyledText::new(&tool_data.new_text, &editing_text.font, &tool_data.new_spans, editing_text.typesetting, font_cache);
+					let far = styled_text.map_or(DVec2::ZERO, |styled_text| styled_text.bounding_box(&tool_data.new_text, editing_text.typesetting, false));
 					if far.x != 0. && far.y != 0. {
 						let quad = Quad::from_box([DVec2::ZERO, far]);
-						let transformed_quad = document.metadata().transform_to_viewport(tool_data.layer) * quad;
-						overlay_context.quad(transformed_quad, Some(&("#".to_string() + &fill_color)));
+						overlay_context.quad(transform * quad, Some(&fill_color));
+					}
+				}
+
+				// The handles for resizing the text box being edited, once it has an area to resize
+				let [min, max] = text_bounding_box(tool_data.layer, document, font_cache).bounding_box();
+				if transform.matrix2.determinant() != 0. && min.x != max.x && min.y != max.y {
+					let bounding_box_manager = tool_data.bounding_box_manager.get_or_insert(BoundingBoxManager::default());
+					bounding_box_manager.bounds = [min, max];
+					bounding_box_manager.transform = transform;
+					bounding_box_manager.render_overlays(&mut overlay_context, false);
+				} else {
+					tool_data.bounding_box_manager.take();
+				}
+
+				// The text being typed shows when it no longer fits its text box
+				TextToolData::draw_overflow(tool_data.layer, tool_data.linking_from, document, font_cache, &mut overlay_context);
+
+				// The carets are placed in the coordinates of the text, so the selection and caret follow the text as the viewport is zoomed and rotated
+				let carets = tool_data.caret_positions(document, font_cache);
+				let to_viewport = |ends: [DVec2; 2]| ends.map(|point| transform.transform_point2(point));
+
+				let selection_color = OverlayColors::with_alpha(&overlay_context.colors.accent, 0.3);
+				for caret in carets.get(tool_data.selection()).unwrap_or_default().iter().filter(|caret| caret.ends != caret.character_ends) {
+					let ([start_top, start_bottom], [end_top, end_bottom]) = (to_viewport(caret.ends), to_viewport(caret.character_ends));
+					overlay_context.fill_polygon(&[start_top, end_top, end_bottom, start_bottom], &selection_color);
+				}
+
+				// The characters being composed by an input method are underlined until they're committed
+				if let Some(composition) = tool_data.composition.clone() {
+					for caret in carets.get(composition).unwrap_or_default() {
+						overlay_context.line(to_viewport(caret.ends)[1], to_viewport(caret.character_ends)[1], None, Some(2.));
 					}
 				}
 
+				if let Some(caret) = carets.get(tool_data.caret) {
+					let [top, bottom] = to_viewport(caret.ends);
+					overlay_context.line(top, bottom, None, Some(2.));
+
+					// The input element taking the typing is kept at the caret, where the window of an input method opens
+					responses.add(FrontendMessage::DisplayEditableTextboxPosition { position: bottom.into() });
+				}
+
 				TextToolFsmState::Editing
 			}
 			(_, TextToolMessage::Overlays(mut overlay_context)) => {
 				if matches!(self, Self::Placing) {
+					// Draw the text's bounds in their own color, instead of the accent color used by the other overlays
+					let accent = std::mem::replace(&mut overlay_context.colors.accent, overlay_context.colors.text_bounds.clone());
+					let fill_color = OverlayColors::with_alpha(&overlay_context.colors.accent, 0.05);
+
 					// Get the updated selection box bounds
 					let quad = Quad::from_box(tool_data.cached_resize_bounds);
 
 					// Draw a bounding box on the layers to be selected
 					for layer in document.intersect_quad_no_artboards(quad, input) {
-						overlay_context.quad(
-							Quad::from_box(document.metadata().bounding_box_viewport(layer).unwrap_or([DVec2::ZERO; 2])),
-							Some(&("#".to_string() + &fill_color)),
-						);
+						overlay_context.quad(Quad::from_box(document.metadata().bounding_box_viewport(layer).unwrap_or([DVec2::ZERO; 2])), Some(&fill_color));
 					}
 
-					overlay_context.quad(quad, Some(&("#".to_string() + &fill_color)));
+					overlay_context.quad(quad, Some(&fill_color));
+					overlay_context.colors.accent = accent;
+				}
+
+				// Highlight the text layer which would be edited by clicking, unless the viewport is being panned
+				// TODO: Don't use `Key::MouseMiddle` directly, instead take it as a variable from the input mappings list like in all other places
+				if matches!(self, Self::Ready) && !input.keyboard.get(Key::MouseMiddle as usize) {
+					let hovered = document.click(input).filter(|&layer| is_layer_fed_by_node_of_name(layer, &document.network_interface, "Text"));
+					let selected = document.network_interface.selected_nodes();
+					if let Some(layer) = hovered.filter(|&layer| !selected.selected_layers_contains(layer, document.metadata())) {
+						overlay_context.hover_outline(layer, document.metadata());
+					}
 				}
 
 				// TODO: implement bounding box for multiple layers
@@ -512,14 +1363,8 @@ impl Fsm for TextToolFsmState {
 					bounding_box_manager.transform = layer_transform;
 
 					bounding_box_manager.render_quad(&mut overlay_context);
-					// Draw red overlay if text is clipped
-					let transformed_quad = layer_transform * bounds;
-					if let Some((text, font, typesetting)) = graph_modification_utils::get_text(layer.unwrap(), &document.network_interface) {
-						let buzz_face = font_cache.get(font).map(|data| load_face(data));
-						if lines_clipping(text.as_str(), buzz_face, typesetting) {
-							overlay_context.line(transformed_quad.0[2], transformed_quad.0[3], Some(COLOR_OVERLAY_RED), Some(3.));
-						}
-					}
+					TextToolData::draw_overflow(layer.unwrap(), tool_data.linking_from, document, font_cache, &mut overlay_context);
+					TextToolData::draw_warp_handles(layer.unwrap(), document, font_cache, &mut overlay_context);
 
 					bounding_box_manager.render_overlays(&mut overlay_context, false);
 					tool_data.pivot.update_pivot(document, &mut overlay_context, None);
@@ -533,7 +1378,7 @@ impl Fsm for TextToolFsmState {
 			}
 			(state, TextToolMessage::EditSelected) => {
 				if let Some(layer) = can_edit_selected(document) {
-					tool_data.start_editing_layer(layer, state, document, font_cache, responses);
+					tool_data.start_editing_layer(layer, state, document, responses);
 					return TextToolFsmState::Editing;
 				}
 
@@ -545,37 +1390,45 @@ impl Fsm for TextToolFsmState {
 				tool_data.drag_start = input.mouse.position;
 				tool_data.drag_current = input.mouse.position;
 
-				let dragging_bounds = tool_data.bounding_box_manager.as_mut().and_then(|bounding_box| {
-					let edges = bounding_box.check_selected_edges(input.mouse.position);
+				// After clicking the overflow port, the next text box placed is linked to its text layer
+				if tool_data.linking_from.is_some() {
+					return TextToolFsmState::Placing;
+				}
 
-					bounding_box.selected_edges = edges.map(|(top, bottom, left, right)| {
-						let selected_edges = SelectedEdges::new(top, bottom, left, right, bounding_box.bounds);
-						bounding_box.opposite_pivot = selected_edges.calculate_pivot();
-						selected_edges
+				// Clicking the overflow port of the selected text layer, when it isn't already linked, starts linking it to a new text box
+				let selected = document.network_interface.selected_nodes();
+				let linkable = selected
+					.selected_visible_and_unlocked_layers(&document.network_interface)
+					.filter(|&layer| is_layer_fed_by_node_of_name(layer, &document.network_interface, "Text"))
+					.find(|&layer| {
+						graph_modification_utils::get_next_text_frame(layer, &document.network_interface).is_none()
+							&& TextToolData::overflow_port(layer, document, font_cache).is_some_and(|port| port.distance(input.mouse.position) <= TEXT_OVERFLOW_PORT_SIZE)
 					});
-
-					edges
-				});
+				if let Some(layer) = linkable {
+					tool_data.linking_from = Some(layer);
+					responses.add(OverlaysMessage::Draw);
+					return TextToolFsmState::Ready;
+				}
 
 				let selected = document.network_interface.selected_nodes();
 				let mut all_selected = selected.selected_visible_and_unlocked_layers(&document.network_interface);
 				let selected = all_selected.find(|layer| is_layer_fed_by_node_of_name(*layer, &document.network_interface, "Text"));
 
-				if dragging_bounds.is_some() {
+				// Dragging a handle of the selected text layer's warp reshapes it
+				let warp_handle = selected.and_then(|layer| {
+					TextToolData::warp_handles(layer, document, font_cache)
+						.into_iter()
+						.find(|&(_, _, warped)| warped.distance(input.mouse.position) <= MANIPULATOR_GROUP_MARKER_SIZE.max(SELECTION_TOLERANCE))
+						.map(|(handle, _, _)| (layer, handle))
+				});
+				if let Some(warp_handle) = warp_handle {
 					responses.add(DocumentMessage::StartTransaction);
+					tool_data.warp_dragging = Some(warp_handle);
+					return TextToolFsmState::DraggingWarpHandle;
+				}
 
-					// Set the original transform
-					if let Some(id) = selected {
-						let original_transform = document.metadata().transform_to_document(id);
-						tool_data.layer_dragging = Some(ResizingLayer { id, original_transform });
-					}
-
-					if let Some(bounds) = &mut tool_data.bounding_box_manager {
-						bounds.original_bound_transform = bounds.transform;
-						bounds.center_of_transformation = bounds.transform.transform_point2((bounds.bounds[0] + bounds.bounds[1]) / 2.);
-					}
-					tool_data.get_snap_candidates(document, font_cache);
-
+				if tool_data.start_resizing_bounds(selected, document, input, font_cache, responses) {
+					tool_data.resizing_edited_text = false;
 					return TextToolFsmState::ResizingBounds;
 				} else if let Some(clicked_layer) = TextToolData::check_click(document, input, font_cache) {
 					responses.add(DocumentMessage::StartTransaction);
@@ -607,6 +1460,21 @@ impl Fsm for TextToolFsmState {
 				if layer.is_none() || cursor == MouseCursorIcon::Default {
 					cursor = MouseCursorIcon::Text;
 				}
+				let warp_handles = layer.map(|layer| TextToolData::warp_handles(layer, document, font_cache)).unwrap_or_default();
+				let over_warp_handle = warp_handles
+					.iter()
+					.any(|&(_, _, warped)| warped.distance(input.mouse.position) <= MANIPULATOR_GROUP_MARKER_SIZE.max(SELECTION_TOLERANCE));
+				if over_warp_handle {
+					cursor = MouseCursorIcon::Default;
+				}
+
+				// Only offer to reshape the warp when the selected text has a Text Warp node with handles to drag
+				if tool_data.warp_hint_shown != !warp_handles.is_empty() {
+					tool_data.warp_hint_shown = !warp_handles.is_empty();
+					responses.add(FrontendMessage::UpdateInputHints {
+						hint_data: TextToolFsmState::ready_hints(tool_data.warp_hint_shown),
+					});
+				}
 
 				responses.add(OverlaysMessage::Draw);
 				responses.add(FrontendMessage::UpdateMouseCursor { cursor });
@@ -623,7 +1491,7 @@ impl Fsm for TextToolFsmState {
 					TextToolMessage::PointerOutsideViewport { center, lock_ratio }.into(),
 					TextToolMessage::PointerMove { center, lock_ratio }.into(),
 				];
-				tool_data.auto_panning.setup_by_mouse_position(input, &messages, responses);
+				tool_data.auto_panning.setup_by_mouse_position(input, preferences, &messages, responses);
 
 				TextToolFsmState::Placing
 			}
@@ -646,11 +1514,42 @@ impl Fsm for TextToolFsmState {
 						TextToolMessage::PointerOutsideViewport { center, lock_ratio }.into(),
 						TextToolMessage::PointerMove { center, lock_ratio }.into(),
 					];
-					tool_data.auto_panning.setup_by_mouse_position(input, &messages, responses);
+					tool_data.auto_panning.setup_by_mouse_position(input, preferences, &messages, responses);
 				}
 
 				TextToolFsmState::Dragging
 			}
+			(TextToolFsmState::DraggingWarpHandle, TextToolMessage::PointerMove { .. }) => {
+				let Some((layer, handle)) = tool_data.warp_dragging else { return TextToolFsmState::Ready };
+				let Some(node_id) = graph_modification_utils::get_text_warp_id(layer, &document.network_interface) else {
+					return TextToolFsmState::DraggingWarpHandle;
+				};
+				let (Some((style, _, _)), Some(bounds)) = (
+					graph_modification_utils::get_text_warp(layer, &document.network_interface),
+					TextToolData::unwarped_bounds(layer, document, font_cache),
+				) else {
+					return TextToolFsmState::DraggingWarpHandle;
+				};
+				let transform = document.metadata().transform_to_viewport(layer);
+				if transform.matrix2.determinant() == 0. {
+					return TextToolFsmState::DraggingWarpHandle;
+				}
+
+				// The handle follows the mouse, with the bend being how far it's raised above the top edge relative to the height of the outlines
+				let position = transform.inverse().transform_point2(input.mouse.position);
+				let unwarped = handle.unwarped_position(style, bounds);
+				let (index, value) = match handle {
+					WarpHandle::Bend => (2, TaggedValue::F64((unwarped.y - position.y) / (bounds[1].y - bounds[0].y).max(f64::EPSILON) * 100.)),
+					WarpHandle::Corner(corner) => (3 + corner, TaggedValue::DVec2(position - unwarped)),
+				};
+				responses.add(NodeGraphMessage::SetInput {
+					input_connector: InputConnector::node(node_id, index),
+					input: NodeInput::value(value, false),
+				});
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+
+				TextToolFsmState::DraggingWarpHandle
+			}
 			(TextToolFsmState::ResizingBounds, TextToolMessage::PointerMove { center, lock_ratio }) => {
 				if let Some(bounds) = &mut tool_data.bounding_box_manager {
 					if let Some(movement) = &mut bounds.selected_edges {
@@ -686,13 +1585,25 @@ impl Fsm for TextToolFsmState {
 						// Find the translation necessary from the original position in viewport space
 						let translation_viewport = bounds.original_bound_transform.transform_vector2(translation_bounds_space);
 
+						// A text area growing to fit its lines keeps growing while only the length of its lines is resized
+						let (max_width, max_height) = match graph_modification_utils::get_text(dragging_layer.id, &document.network_interface) {
+							Some((_, _, typesetting)) if typesetting.writing_mode == WritingMode::VerticalRightToLeft => {
+								let auto_height = typesetting.max_width.is_none() && !movement.left && !movement.right;
+								((!auto_height).then_some(size_layer.x), Some(size_layer.y))
+							}
+							Some((_, _, typesetting)) => {
+								let auto_height = typesetting.max_height.is_none() && !movement.top && !movement.bottom;
+								(Some(size_layer.x), (!auto_height).then_some(size_layer.y))
+							}
+							None => (Some(size_layer.x), Some(size_layer.y)),
+						};
 						responses.add(NodeGraphMessage::SetInput {
 							input_connector: InputConnector::node(node_id, 6),
-							input: NodeInput::value(TaggedValue::OptionalF64(Some(size_layer.x)), false),
+							input: NodeInput::value(TaggedValue::OptionalF64(max_width), false),
 						});
 						responses.add(NodeGraphMessage::SetInput {
 							input_connector: InputConnector::node(node_id, 7),
-							input: NodeInput::value(TaggedValue::OptionalF64(Some(size_layer.y)), false),
+							input: NodeInput::value(TaggedValue::OptionalF64(max_height), false),
 						});
 						responses.add(GraphOperationMessage::TransformSet {
 							layer: dragging_layer.id,
@@ -707,11 +1618,24 @@ impl Fsm for TextToolFsmState {
 							TextToolMessage::PointerOutsideViewport { center, lock_ratio }.into(),
 							TextToolMessage::PointerMove { center, lock_ratio }.into(),
 						];
-						tool_data.auto_panning.setup_by_mouse_position(input, &messages, responses);
+						tool_data.auto_panning.setup_by_mouse_position(input, preferences, &messages, responses);
 					}
 				}
 				TextToolFsmState::ResizingBounds
 			}
+			(TextToolFsmState::Editing, TextToolMessage::PointerMove { .. }) => {
+				if tool_data.selecting {
+					tool_data.place_caret(input.mouse.position, true, document, font_cache, responses);
+				}
+
+				let cursor = match tool_data.bounding_box_manager.as_ref().map(|bounds| bounds.get_cursor(input, false, false, None)) {
+					Some(MouseCursorIcon::Default) | None => MouseCursorIcon::Text,
+					Some(cursor) => cursor,
+				};
+				responses.add(FrontendMessage::UpdateMouseCursor { cursor });
+
+				TextToolFsmState::Editing
+			}
 			(_, TextToolMessage::PointerMove { .. }) => {
 				tool_data.resize.snap_manager.preview_draw(&SnapData::new(document, input), input.mouse.position);
 				responses.add(OverlaysMessage::Draw);
@@ -720,13 +1644,13 @@ impl Fsm for TextToolFsmState {
 			}
 			(TextToolFsmState::Placing, TextToolMessage::PointerOutsideViewport { .. }) => {
 				// Auto-panning setup
-				let _ = tool_data.auto_panning.shift_viewport(input, responses);
+				let _ = tool_data.auto_panning.shift_viewport(input, preferences, responses);
 
 				TextToolFsmState::Placing
 			}
 			(TextToolFsmState::ResizingBounds | TextToolFsmState::Dragging, TextToolMessage::PointerOutsideViewport { .. }) => {
 				// AutoPanning
-				if let Some(shift) = tool_data.auto_panning.shift_viewport(input, responses) {
+				if let Some(shift) = tool_data.auto_panning.shift_viewport(input, preferences, responses) {
 					if let Some(bounds) = &mut tool_data.bounding_box_manager {
 						bounds.center_of_transformation += shift;
 						bounds.original_bound_transform.translation += shift;
@@ -745,6 +1669,12 @@ impl Fsm for TextToolFsmState {
 
 				state
 			}
+			(TextToolFsmState::DraggingWarpHandle, TextToolMessage::DragStop) => {
+				responses.add(DocumentMessage::EndTransaction);
+				tool_data.warp_dragging = None;
+
+				TextToolFsmState::Ready
+			}
 			(TextToolFsmState::ResizingBounds, TextToolMessage::DragStop) => {
 				let drag_too_small = input.mouse.position.distance(tool_data.resize.viewport_drag_start(document)) < 10. * f64::EPSILON;
 				let response = if drag_too_small { DocumentMessage::AbortTransaction } else { DocumentMessage::EndTransaction };
@@ -756,36 +1686,85 @@ impl Fsm for TextToolFsmState {
 					bounds.original_transforms.clear();
 				}
 
+				// The text goes on being edited, with its caret placed where it is in the text as typeset in the resized text box
+				if std::mem::take(&mut tool_data.resizing_edited_text) {
+					tool_data.layer_dragging.take();
+					if let (Some(editing_text), Some((_, _, typesetting))) = (&mut tool_data.editing_text, graph_modification_utils::get_text(tool_data.layer, &document.network_interface)) {
+						editing_text.typesetting = typesetting;
+					}
+					tool_data.update_textbox(responses);
+					return TextToolFsmState::Editing;
+				}
+
 				TextToolFsmState::Ready
 			}
 			(TextToolFsmState::Placing, TextToolMessage::DragStop) => {
 				let [start, end] = tool_data.cached_resize_bounds;
 				let has_dragged = (start - end).length_squared() > DRAG_THRESHOLD * DRAG_THRESHOLD;
 
+				// Place the text box which the text overflowing the linked text layer continues into
+				if let Some(previous) = tool_data.linking_from.take() {
+					let constraint_size = has_dragged.then_some((start - end).abs());
+					tool_data.new_linked_frame(document, previous, start.min(end), constraint_size, responses);
+					return TextToolFsmState::Ready;
+				}
+
 				// Check if the user has clicked (no dragging) on some existing text
 				if !has_dragged {
 					if let Some(clicked_text_layer_path) = TextToolData::check_click(document, input, font_cache) {
-						tool_data.start_editing_layer(clicked_text_layer_path, self, document, font_cache, responses);
+						tool_data.start_editing_layer(clicked_text_layer_path, self, document, responses);
+						tool_data.place_caret(input.mouse.position, false, document, font_cache, responses);
 						return TextToolFsmState::Editing;
 					}
 				}
 
+				// Alt-clicking on a path attaches the new text to it, so the text follows the path from the clicked point
+				let path = if has_dragged || !input.keyboard.key(Key::Alt) {
+					None
+				} else {
+					TextToolData::check_path_click(document, input)
+				};
+
 				// Otherwise create some new text
 				let constraint_size = has_dragged.then_some((start - end).abs());
+				// With auto height, the text area only keeps the length of its lines and grows to fit them all
+				let vertical = tool_options.writing_mode == WritingMode::VerticalRightToLeft;
+				let max_width = constraint_size.map(|size| size.x).filter(|_| !(tool_options.auto_height && vertical));
+				let max_height = constraint_size.map(|size| size.y).filter(|_| !(tool_options.auto_height && !vertical));
 				let editing_text = EditingText {
-					text: String::new(),
 					transform: DAffine2::from_translation(start),
 					typesetting: TypesettingConfig {
 						font_size: tool_options.font_size,
 						line_height_ratio: tool_options.line_height_ratio,
-						max_width: constraint_size.map(|size| size.x),
+						max_width,
 						character_spacing: tool_options.character_spacing,
-						max_height: constraint_size.map(|size| size.y),
+						max_height,
+						align: tool_options.align,
+						vertical_align: tool_options.vertical_align,
+						direction: tool_options.direction,
+						writing_mode: tool_options.writing_mode,
+						orientation: tool_options.orientation,
+						features: tool_options.features,
+						variations: tool_options.variations,
+						baseline_shift: tool_options.baseline_shift,
+						script: tool_options.script,
+						wrap: tool_options.wrap,
+						space_before: tool_options.space_before,
+						space_after: tool_options.space_after,
+						underline: tool_options.underline,
+						strikethrough: tool_options.strikethrough,
+						tab_stops: TabStops::default(),
+						hyphenate: tool_options.hyphenate,
+						language: tool_options.language,
+						case: tool_options.case,
+						left_indent: 0.,
+						right_indent: 0.,
+						first_line_indent: 0.,
 					},
 					font: Font::new(tool_options.font_name.clone(), tool_options.font_style.clone()),
 					color: tool_options.fill.active_color(),
 				};
-				tool_data.new_text(document, editing_text, font_cache, responses);
+				tool_data.new_text(document, editing_text, path, responses);
 				TextToolFsmState::Editing
 			}
 			(TextToolFsmState::Dragging, TextToolMessage::DragStop) => {
@@ -801,7 +1780,8 @@ impl Fsm for TextToolFsmState {
 
 				if drag_too_small {
 					if let Some(layer_info) = &tool_data.layer_dragging {
-						tool_data.start_editing_layer(layer_info.id, self, document, font_cache, responses);
+						tool_data.start_editing_layer(layer_info.id, self, document, responses);
+						tool_data.place_caret(input.mouse.position, false, document, font_cache, responses);
 						return TextToolFsmState::Editing;
 					}
 				}
@@ -809,31 +1789,211 @@ impl Fsm for TextToolFsmState {
 
 				TextToolFsmState::Ready
 			}
-			(TextToolFsmState::Editing, TextToolMessage::TextChange { new_text, is_left_or_right_click }) => {
-				tool_data.new_text = new_text;
+			(TextToolFsmState::Editing, TextToolMessage::DragStart) => {
+				// Dragging a handle of the bounds resizes the text box, which goes on being edited afterwards
+				if tool_data.start_resizing_bounds(Some(tool_data.layer), document, input, font_cache, responses) {
+					tool_data.resize.start(document, input);
+					tool_data.resizing_edited_text = true;
+					return TextToolFsmState::ResizingBounds;
+				}
 
-				if !is_left_or_right_click {
-					tool_data.set_editing(false, font_cache, responses);
+				// Clicking in the text being edited moves the caret, and dragging selects the characters it's dragged across, while clicking elsewhere commits the text
+				let mouse = input.mouse.position;
+				let bounds = document.metadata().transform_to_viewport(tool_data.layer) * text_bounding_box(tool_data.layer, document, font_cache);
+				if !bounds.contains(mouse) {
+					return tool_data.commit(responses);
+				}
 
-					responses.add(NodeGraphMessage::SetInput {
-						input_connector: InputConnector::node(graph_modification_utils::get_text_id(tool_data.layer, &document.network_interface).unwrap(), 1),
-						input: NodeInput::value(TaggedValue::String(tool_data.new_text.clone()), false),
-					});
-					responses.add(NodeGraphMessage::RunDocumentGraph);
+				tool_data.composition = None;
+				tool_data.selecting = true;
+				tool_data.place_caret(mouse, input.keyboard.key(Key::Shift), document, font_cache, responses);
 
-					TextToolFsmState::Ready
-				} else {
-					if tool_data.new_text.is_empty() {
-						return tool_data.delete_empty_layer(font_cache, responses);
-					}
+				TextToolFsmState::Editing
+			}
+			(TextToolFsmState::Editing, TextToolMessage::DragStop) => {
+				tool_data.selecting = false;
 
-					responses.add(FrontendMessage::TriggerTextCommit);
-					TextToolFsmState::Editing
+				TextToolFsmState::Editing
+			}
+			(TextToolFsmState::Editing, TextToolMessage::InsertText { text }) => {
+				// Committing a composition replaces the characters shown while it was being composed
+				let range = tool_data.composition.take().unwrap_or_else(|| tool_data.selection());
+				let text = text.replace("\r\n", "\n").replace('\r', "\n");
+				tool_data.replace_text(range, &text, document, responses);
+				tool_data.update_textbox(responses);
+
+				TextToolFsmState::Editing
+			}
+			(TextToolFsmState::Editing, TextToolMessage::CompositionUpdate { text }) => {
+				// The frontend's input element isn't updated while composing, since that would cancel the composition
+				let range = tool_data.composition.take().unwrap_or_else(|| tool_data.selection());
+				let start = range.start;
+				tool_data.replace_text(range, &text, document, responses);
+				if !text.is_empty() {
+					tool_data.composition = Some(start..tool_data.caret);
 				}
+
+				TextToolFsmState::Editing
+			}
+			(TextToolFsmState::Editing, deletion @ (TextToolMessage::DeleteBackward | TextToolMessage::DeleteForward)) => {
+				let selection = tool_data.selection();
+				let caret = tool_data.caret;
+				// A whole grapheme is deleted at once, so emoji and letters with combining marks aren't left in pieces
+				let boundaries = grapheme_boundaries(&tool_data.new_text);
+				let range = match deletion {
+					_ if !selection.is_empty() => selection,
+					TextToolMessage::DeleteBackward => match boundaries.iter().rev().find(|&&boundary| boundary < caret) {
+						Some(&previous) => previous..caret,
+						None => return TextToolFsmState::Editing,
+					},
+					_ => match boundaries.iter().find(|&&boundary| boundary > caret) {
+						Some(&next) => caret..next,
+						None => return TextToolFsmState::Editing,
+					},
+				};
+				tool_data.replace_text(range, "", document, responses);
+				tool_data.update_textbox(responses);
+
+				TextToolFsmState::Editing
 			}
-			(TextToolFsmState::Editing, TextToolMessage::UpdateBounds { new_text }) => {
-				tool_data.new_text = new_text;
+			(TextToolFsmState::Editing, TextToolMessage::MoveCaret { movement, extend }) => {
+				let extend = input.keyboard.key(extend);
+				let selection = tool_data.selection();
+				let caret = tool_data.caret;
+				let carets = &tool_data.caret_positions(document, font_cache);
+				let boundaries = grapheme_boundaries(&tool_data.new_text);
+				let vertical = tool_data
+					.editing_text
+					.as_ref()
+					.is_some_and(|editing_text| editing_text.typesetting.writing_mode == WritingMode::VerticalRightToLeft);
+				let line = carets.get(caret).map(|position| position.line);
+				let on_line = move |line: Option<usize>| carets.iter().enumerate().filter(move |(_, position)| Some(position.line) == line).map(|(index, _)| index);
+
+				let index = match movement {
+					// Moving without extending the selection leaves the caret at the side of the selection it moves towards
+					CaretMovement::Left | CaretMovement::Right if !extend && !selection.is_empty() => {
+						// Within a line, the start of a selection in right-to-left text is on its right
+						let start_on_left = match (carets.get(selection.start), carets.get(selection.end)) {
+							(Some(start), Some(end)) if !vertical && start.line == end.line => start.along <= end.along,
+							_ => true,
+						};
+						if (movement == CaretMovement::Left) == start_on_left { selection.start } else { selection.end }
+					}
+					// Vertical text is moved through in the order of the text, since its columns run down rather than across
+					CaretMovement::Left if vertical => boundaries.iter().rev().find(|&&boundary| boundary < caret).copied().unwrap_or(caret),
+					CaretMovement::Right if vertical => boundaries.iter().find(|&&boundary| boundary > caret).copied().unwrap_or(caret),
+					CaretMovement::Left => caret_to_side(carets, &boundaries, caret, false),
+					CaretMovement::Right => caret_to_side(carets, &boundaries, caret, true),
+					CaretMovement::PreviousLine | CaretMovement::NextLine => {
+						let target_line = match movement {
+							CaretMovement::PreviousLine => line.and_then(|line| line.checked_sub(1)),
+							_ => line.map(|line| line + 1),
+						};
+						// The caret goes to the closest place on the other line, or to the start or end of the text from its first or last line
+						let middle = |index: usize| carets.get(index).map_or(DVec2::ZERO, |position| (position.ends[0] + position.ends[1]) / 2.);
+						let closest = on_line(target_line).min_by(|&a, &b| middle(a).distance(middle(caret)).total_cmp(&middle(b).distance(middle(caret))));
+						closest.unwrap_or(if movement == CaretMovement::PreviousLine { 0 } else { carets.len().saturating_sub(1) })
+					}
+					CaretMovement::LineStart => on_line(line).next().unwrap_or(caret),
+					CaretMovement::LineEnd => on_line(line).last().unwrap_or(caret),
+				};
+				tool_data.set_caret(index, extend, responses);
+
+				TextToolFsmState::Editing
+			}
+			(TextToolFsmState::Editing, TextToolMessage::FillWithPlaceholder) => {
+				let Some(text) = placeholder_text(tool_data.layer, document, font_cache) else {
+					return TextToolFsmState::Editing;
+				};
+				tool_data.replace_text(0..0, &text, document, responses);
+				tool_data.update_textbox(responses);
+
+				TextToolFsmState::Editing
+			}
+			(TextToolFsmState::Editing, TextToolMessage::OpenGlyphPalette) => {
+				let Some(editing_text) = &tool_data.editing_text else { return TextToolFsmState::Editing };
+
+				// The glyphs are those of the font the inserted character would take, which is the font of the first selected character or else the character before the caret
+				let selection = tool_data.selection();
+				let index = if selection.is_empty() { selection.start.saturating_sub(1) } else { selection.start };
+				let font = tool_data
+					.new_spans
+					.iter()
+					.rev()
+					.filter(|span| (span.start..span.end).contains(&index))
+					.find_map(|span| span.font.clone())
+					.unwrap_or_else(|| editing_text.font.clone());
+				responses.add(DialogMessage::RequestGlyphPaletteDialog { font });
+
+				TextToolFsmState::Editing
+			}
+			(TextToolFsmState::Editing, TextToolMessage::SelectAll) => {
+				tool_data.selection_anchor = 0;
+				tool_data.set_caret(tool_data.new_text.chars().count(), true, responses);
+
+				TextToolFsmState::Editing
+			}
+			(TextToolFsmState::Editing, TextToolMessage::SetAutoHeight { enabled }) => {
+				let Some(editing_text) = tool_data.editing_text.as_mut() else {
+					return TextToolFsmState::Editing;
+				};
+				let Some(node_id) = graph_modification_utils::get_text_id(tool_data.layer, &document.network_interface) else {
+					return TextToolFsmState::Editing;
+				};
+				let vertical = editing_text.typesetting.writing_mode == WritingMode::VerticalRightToLeft;
+
+				// Turning auto height off fixes the text area at the size it has grown to
+				let mut typesetting = editing_text.typesetting;
+				if vertical {
+					typesetting.max_width = None;
+				} else {
+					typesetting.max_height = None;
+				}
+				let size = if enabled {
+					None
+				} else {
+					let styled_text = StyledText::new(&tool_data.new_text, &editing_text.font, &tool_data.new_spans, typesetting, font_cache);
+					let far = styled_text.map_or(DVec2::ZERO, |styled_text| styled_text.bounding_box(&tool_data.new_text, typesetting, false));
+					Some(if vertical { far.x } else { far.y })
+				};
+				if vertical {
+					typesetting.max_width = size;
+				} else {
+					typesetting.max_height = size;
+				}
+				editing_text.typesetting = typesetting;
+
+				responses.add(NodeGraphMessage::SetInput {
+					input_connector: InputConnector::node(node_id, if vertical { 6 } else { 7 }),
+					input: NodeInput::value(TaggedValue::OptionalF64(size), false),
+				});
+				responses.add(NodeGraphMessage::RunDocumentGraph);
 				responses.add(OverlaysMessage::Draw);
+
+				TextToolFsmState::Editing
+			}
+			(TextToolFsmState::Editing, TextToolMessage::StyleSelection(style)) => {
+				let selection = tool_data.selection();
+				if selection.is_empty() {
+					return TextToolFsmState::Editing;
+				}
+
+				// The new span is merged with those it overlaps, so restyling the same characters doesn't pile up spans
+				let mut spans = std::mem::take(&mut tool_data.new_spans);
+				spans.push(TextSpan {
+					start: selection.start,
+					end: selection.end,
+					font: style.font,
+					font_size: style.font_size,
+					color: style.color,
+					baseline_shift: style.baseline_shift,
+					script: style.script,
+					underline: style.underline,
+					strikethrough: style.strikethrough,
+				});
+				tool_data.new_spans = merge_spans(&tool_data.new_text, &spans);
+				tool_data.update_text_node(document, responses);
+
 				TextToolFsmState::Editing
 			}
 			(_, TextToolMessage::WorkingColorChanged) => {
@@ -843,15 +2003,21 @@ impl Fsm for TextToolFsmState {
 				)));
 				self
 			}
-			(TextToolFsmState::Editing, TextToolMessage::Abort) => {
-				if tool_data.new_text.is_empty() {
-					return tool_data.delete_empty_layer(font_cache, responses);
-				}
+			(TextToolFsmState::Editing, TextToolMessage::Abort) => tool_data.commit(responses),
+			(TextToolFsmState::DraggingWarpHandle, TextToolMessage::Abort) => {
+				responses.add(DocumentMessage::AbortTransaction);
+				tool_data.warp_dragging = None;
 
-				responses.add(FrontendMessage::TriggerTextCommit);
-				TextToolFsmState::Editing
+				TextToolFsmState::Ready
+			}
+			(TextToolFsmState::Ready, TextToolMessage::Abort) => {
+				tool_data.linking_from = None;
+				responses.add(OverlaysMessage::Draw);
+
+				TextToolFsmState::Ready
 			}
 			(state, TextToolMessage::Abort) => {
+				tool_data.linking_from = None;
 				if matches!(state, TextToolFsmState::ResizingBounds | TextToolFsmState::Dragging) {
 					responses.add(DocumentMessage::AbortTransaction);
 					if let Some(bounds) = &mut tool_data.bounding_box_manager {
@@ -865,6 +2031,12 @@ impl Fsm for TextToolFsmState {
 				}
 				tool_data.resize.cleanup(responses);
 
+				if std::mem::take(&mut tool_data.resizing_edited_text) {
+					tool_data.layer_dragging.take();
+					tool_data.update_textbox(responses);
+					return TextToolFsmState::Editing;
+				}
+
 				TextToolFsmState::Ready
 			}
 			_ => self,
@@ -873,24 +2045,21 @@ impl Fsm for TextToolFsmState {
 
 	fn update_hints(&self, responses: &mut VecDeque<Message>) {
 		let hint_data = match self {
-			TextToolFsmState::Ready => HintData(vec![
-				HintGroup(vec![HintInfo::mouse(MouseMotion::Lmb, "Place Text")]),
+			TextToolFsmState::Ready => TextToolFsmState::ready_hints(false),
+			TextToolFsmState::Editing => HintData(vec![
+				HintGroup(vec![HintInfo::mouse(MouseMotion::LmbDrag, "Select Characters")]),
 				HintGroup(vec![
-					HintInfo::mouse(MouseMotion::LmbDrag, "Place Text Box"),
-					HintInfo::keys([Key::Shift], "Constrain Square").prepend_plus(),
-					HintInfo::keys([Key::Alt], "From Center").prepend_plus(),
+					HintInfo::keys([Key::Control, Key::Enter], "").add_mac_keys([Key::Command, Key::Enter]),
+					HintInfo::keys([Key::Escape], "Commit Changes").prepend_slash(),
 				]),
-				HintGroup(vec![HintInfo::mouse(MouseMotion::Lmb, "Edit Text")]),
 			]),
-			TextToolFsmState::Editing => HintData(vec![HintGroup(vec![
-				HintInfo::keys([Key::Control, Key::Enter], "").add_mac_keys([Key::Command, Key::Enter]),
-				HintInfo::keys([Key::Escape], "Commit Changes").prepend_slash(),
-			])]),
 			TextToolFsmState::Placing => HintData(vec![
 				HintGroup(vec![HintInfo::mouse(MouseMotion::Rmb, ""), HintInfo::keys([Key::Escape], "Cancel").prepend_slash()]),
 				HintGroup(vec![HintInfo::keys([Key::Shift], "Constrain Square"), HintInfo::keys([Key::Alt], "From Center")]),
 			]),
-			TextToolFsmState::Dragging => HintData(vec![HintGroup(vec![HintInfo::mouse(MouseMotion::Rmb, ""), HintInfo::keys([Key::Escape], "Cancel").prepend_slash()])]),
+			TextToolFsmState::Dragging | TextToolFsmState::DraggingWarpHandle => {
+				HintData(vec![HintGroup(vec![HintInfo::mouse(MouseMotion::Rmb, ""), HintInfo::keys([Key::Escape], "Cancel").prepend_slash()])])
+			}
 			TextToolFsmState::ResizingBounds => HintData(vec![
 				HintGroup(vec![HintInfo::mouse(MouseMotion::Rmb, ""), HintInfo::keys([Key::Escape], "Cancel").prepend_slash()]),
 				HintGroup(vec![HintInfo::keys([Key::Shift], "Lock Aspect Ratio"), HintInfo::keys([Key::Alt], "From Center")]),
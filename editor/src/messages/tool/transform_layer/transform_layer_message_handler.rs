@@ -1,8 +1,8 @@
-use crate::consts::{ANGLE_MEASURE_RADIUS_FACTOR, ARC_MEASURE_RADIUS_FACTOR_RANGE, COLOR_OVERLAY_BLUE, SLOWING_DIVISOR};
+use crate::consts::{ANGLE_MEASURE_RADIUS_FACTOR, ARC_MEASURE_RADIUS_FACTOR_RANGE, SLOWING_DIVISOR};
 use crate::messages::input_mapper::utility_types::input_mouse::{DocumentPosition, ViewportPosition};
 use crate::messages::portfolio::document::overlays::utility_types::{OverlayProvider, Pivot};
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
-use crate::messages::portfolio::document::utility_types::misc::PTZ;
+use crate::messages::portfolio::document::utility_types::misc::{PTZ, RepeatableAction};
 use crate::messages::portfolio::document::utility_types::transformation::{Axis, OriginalTransforms, Selected, TransformOperation, TransformType, Typing};
 use crate::messages::prelude::*;
 use crate::messages::tool::common_functionality::shape_editor::ShapeState;
@@ -245,7 +245,7 @@ impl MessageHandler<TransformLayerMessage, TransformData<'_>> for TransformLayer
 								overlay_context.line(quad[0], end, None, None);
 
 								let x_transform = DAffine2::from_translation((quad[0] + end) / 2.);
-								overlay_context.text(&format_rounded(translation.x, 3), COLOR_OVERLAY_BLUE, None, x_transform, 4., [Pivot::Middle, Pivot::End]);
+								overlay_context.text(&format_rounded(translation.x, 3), &overlay_context.colors.accent, None, x_transform, 4., [Pivot::Middle, Pivot::End]);
 							}
 
 							if matches!(axis_constraint, Axis::Both | Axis::Y) && translation.y != 0. {
@@ -255,7 +255,14 @@ impl MessageHandler<TransformLayerMessage, TransformData<'_>> for TransformLayer
 								let y_transform = DAffine2::from_translation((quad[0] + end) / 2. + x_parameter * DVec2::X * 0.);
 								let pivot_selection = if x_parameter >= -1e-3 { Pivot::Start } else { Pivot::End };
 								if axis_constraint != Axis::Both || self.typing.digits.is_empty() || !self.transform_operation.can_begin_typing() {
-									overlay_context.text(&format_rounded(translation.y, 2), COLOR_OVERLAY_BLUE, None, y_transform, 3., [pivot_selection, Pivot::Middle]);
+									overlay_context.text(
+										&format_rounded(translation.y, 2),
+										&overlay_context.colors.accent,
+										None,
+										y_transform,
+										3.,
+										[pivot_selection, Pivot::Middle],
+									);
 								}
 							}
 							if matches!(axis_constraint, Axis::Both) && translation.x != 0. && translation.y != 0. {
@@ -279,7 +286,7 @@ impl MessageHandler<TransformLayerMessage, TransformData<'_>> for TransformLayer
 							overlay_context.line(boundary_point, end_point, None, None);
 
 							let transform = DAffine2::from_translation(boundary_point.midpoint(pivot) + local_edge.perp().normalize_or(DVec2::X) * local_edge.element_product().signum() * 24.);
-							overlay_context.text(&text, COLOR_OVERLAY_BLUE, None, transform, 16., [Pivot::Middle, Pivot::Middle]);
+							overlay_context.text(&text, &overlay_context.colors.accent, None, transform, 16., [Pivot::Middle, Pivot::Middle]);
 						}
 						TransformOperation::Rotating(rotation) => {
 							let angle = rotation.to_f64(self.increments);
@@ -308,7 +315,7 @@ impl MessageHandler<TransformLayerMessage, TransformData<'_>> for TransformLayer
 							);
 							let transform = DAffine2::from_translation(text_texture_position + pivot);
 							overlay_context.draw_angle(pivot, radius, arc_radius, offset_angle, angle);
-							overlay_context.text(&text, COLOR_OVERLAY_BLUE, None, transform, 16., [Pivot::Middle, Pivot::Middle]);
+							overlay_context.text(&text, &overlay_context.colors.accent, None, transform, 16., [Pivot::Middle, Pivot::Middle]);
 						}
 					}
 				}
@@ -318,6 +325,20 @@ impl MessageHandler<TransformLayerMessage, TransformData<'_>> for TransformLayer
 			TransformLayerMessage::ApplyTransformOperation { final_transform } => {
 				selected.original_transforms.clear();
 				self.typing.clear();
+
+				// Remember the transformation of the layers in document space so it can be repeated
+				if final_transform && !using_path_tool && !using_pen_tool && self.transform_operation != TransformOperation::None {
+					let transformation = self
+						.transform_operation
+						.transformation(self.increments, self.local, self.layer_bounding_box, document_to_viewport, self.initial_transform);
+					let document_to_viewport = DAffine2::from_mat2(document_to_viewport.matrix2);
+					selected.responses.add(DocumentMessage::RecordRepeatableAction {
+						action: RepeatableAction::Transform {
+							transform: document_to_viewport.inverse() * transformation * document_to_viewport,
+						},
+					});
+				}
+
 				if final_transform {
 					self.transform_operation = TransformOperation::None;
 					self.operation_count = 0;
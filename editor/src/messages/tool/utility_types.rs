@@ -9,6 +9,7 @@ use crate::messages::input_mapper::utility_types::macros::action_keys;
 use crate::messages::input_mapper::utility_types::misc::ActionKeys;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::overlays::utility_types::OverlayProvider;
+use crate::messages::portfolio::plugins::plugin_api::PluginToolDescription;
 use crate::messages::preferences::PreferencesMessageHandler;
 use crate::messages::prelude::*;
 use crate::node_graph_executor::NodeGraphExecutor;
@@ -28,8 +29,8 @@ pub struct ToolActionHandlerData<'a> {
 	pub preferences: &'a PreferencesMessageHandler,
 }
 
-pub trait ToolCommon: for<'a, 'b> MessageHandler<ToolMessage, &'b mut ToolActionHandlerData<'a>> + LayoutHolder + ToolTransition + ToolMetadata {}
-impl<T> ToolCommon for T where T: for<'a, 'b> MessageHandler<ToolMessage, &'b mut ToolActionHandlerData<'a>> + LayoutHolder + ToolTransition + ToolMetadata {}
+pub trait ToolCommon: for<'a, 'b> MessageHandler<ToolMessage, &'b mut ToolActionHandlerData<'a>> + LayoutHolder + ToolTransition + ToolOptionsPersistence {}
+impl<T> ToolCommon for T where T: for<'a, 'b> MessageHandler<ToolMessage, &'b mut ToolActionHandlerData<'a>> + LayoutHolder + ToolTransition + ToolOptionsPersistence {}
 
 type Tool = dyn ToolCommon + Send + Sync;
 
@@ -140,6 +141,7 @@ impl DocumentToolData {
 		});
 
 		responses.add(BroadcastMessage::TriggerEvent(BroadcastEvent::WorkingColorChanged));
+		responses.add(ColorHarmonyMessage::UpdatePrimaryColor { color: self.primary_color });
 	}
 }
 
@@ -202,9 +204,34 @@ pub trait ToolMetadata {
 	fn tool_type(&self) -> ToolType;
 }
 
+/// Saves a tool's options in the preferences so they survive reloading the editor.
+/// Tools without any options keep the default implementations, which save nothing, and the others implement it with `persist_tool_options!`.
+pub trait ToolOptionsPersistence: ToolMetadata {
+	/// The tool's options serialized to be stored in the preferences.
+	fn saved_options(&self) -> Option<String> {
+		None
+	}
+
+	/// Restores the options saved in the preferences, keeping the current options if they can't be read.
+	fn restore_options(&mut self, _options: &str) {}
+
+	fn reset_options(&mut self) {}
+
+	/// Stores the tool's current options in the preferences, which should be done whenever the user changes them.
+	fn save_options(&self, responses: &mut VecDeque<Message>) {
+		if let Some(options) = self.saved_options() {
+			responses.add(PreferencesMessage::ToolOptions { tool_type: self.tool_type(), options });
+		}
+	}
+}
+
 pub struct ToolData {
 	pub active_tool_type: ToolType,
 	pub tools: HashMap<ToolType, Box<Tool>>,
+	/// The tools added by plugins, each along with the ID of its plugin, which are all run by the [`ToolType::Plugin`] tool.
+	pub plugin_tools: Vec<(String, PluginToolDescription)>,
+	/// Which of the plugin tools the [`ToolType::Plugin`] tool runs.
+	pub active_plugin_tool: Option<(String, String)>,
 }
 
 impl fmt::Debug for ToolData {
@@ -225,7 +252,7 @@ impl ToolData {
 
 impl LayoutHolder for ToolData {
 	fn layout(&self) -> Layout {
-		let tool_groups_layout = list_tools_in_groups()
+		let mut tool_groups_layout = list_tools_in_groups()
 			.iter()
 			.map(|tool_group| tool_group.iter().map(|tool_availability| {
 				match tool_availability {
@@ -257,7 +284,29 @@ impl LayoutHolder for ToolData {
 			})
 			// Skip the initial separator
 			.skip(1)
-			.collect();
+			.collect::<Vec<_>>();
+
+		// The plugin tools are grouped after the built-in ones, without shortcuts
+		if !self.plugin_tools.is_empty() {
+			tool_groups_layout.push(Separator::new(SeparatorType::Section).direction(SeparatorDirection::Vertical).widget_holder());
+		}
+		for (plugin, description) in &self.plugin_tools {
+			let (plugin, tool) = (plugin.clone(), description.id.clone());
+			let active = self.active_tool_type == ToolType::Plugin && self.active_plugin_tool.as_ref() == Some(&(plugin.clone(), tool.clone()));
+			tool_groups_layout.push(
+				IconButton::new(description.icon.clone(), 32)
+					.active(active)
+					.tooltip(description.label.clone())
+					.on_update(move |_| {
+						ToolMessage::ActivatePluginTool {
+							plugin: plugin.clone(),
+							tool: tool.clone(),
+						}
+						.into()
+					})
+					.widget_holder(),
+			);
+		}
 
 		Layout::WidgetLayout(WidgetLayout {
 			layout: vec![LayoutGroup::Row { widgets: tool_groups_layout }],
@@ -294,7 +343,10 @@ impl Default for ToolFsmState {
 						ToolAvailability::Available(tool) => Some((tool.tool_type(), tool)),
 						ToolAvailability::ComingSoon(_) => None,
 					})
+					.chain(std::iter::once((ToolType::Plugin, Box::<plugin_tool::PluginTool>::default() as Box<Tool>)))
 					.collect(),
+				plugin_tools: Vec::new(),
+				active_plugin_tool: None,
 			},
 			document_tool_data: DocumentToolData {
 				primary_color: Color::BLACK,
@@ -342,15 +394,18 @@ pub enum ToolType {
 	Relight,
 	Imaginate,
 	Frame,
+
+	// Runs whichever tool added by a plugin is picked
+	Plugin,
 }
 
-enum ToolAvailability {
+pub enum ToolAvailability {
 	Available(Box<Tool>),
 	ComingSoon(ToolEntry),
 }
 
 /// List of all the tools in their conventional ordering and grouping.
-fn list_tools_in_groups() -> Vec<Vec<ToolAvailability>> {
+pub fn list_tools_in_groups() -> Vec<Vec<ToolAvailability>> {
 	vec![
 		vec![
 			// General tool group
@@ -416,6 +471,8 @@ pub fn tool_message_to_tool_type(tool_message: &ToolMessage) -> ToolType {
 		// ToolMessage::Detail(_) => ToolType::Detail,
 		// ToolMessage::Relight(_) => ToolType::Relight,
 		ToolMessage::Imaginate(_) => ToolType::Imaginate,
+
+		ToolMessage::Plugin(_) => ToolType::Plugin,
 		_ => panic!("Conversion from ToolMessage to ToolType impossible because the given ToolMessage does not have a matching ToolType. Got: {tool_message:?}"),
 	}
 }
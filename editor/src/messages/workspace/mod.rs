@@ -1,7 +1,9 @@
 mod workspace_message;
 mod workspace_message_handler;
 
+pub mod utility_types;
+
 #[doc(inline)]
 pub use workspace_message::{WorkspaceMessage, WorkspaceMessageDiscriminant};
 #[doc(inline)]
-pub use workspace_message_handler::WorkspaceMessageHandler;
+pub use workspace_message_handler::{WorkspaceMessageData, WorkspaceMessageHandler};
@@ -0,0 +1,13 @@
+use crate::messages::portfolio::utility_types::PanelType;
+
+/// A panel which has been undocked from the workspace into its own window, along with that window's position and size in screen coordinates.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(default)]
+pub struct FloatingPanel {
+	#[serde(rename = "panelType")]
+	pub panel_type: PanelType,
+	pub left: f64,
+	pub top: f64,
+	pub width: f64,
+	pub height: f64,
+}
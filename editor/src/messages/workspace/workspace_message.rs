@@ -1,8 +1,16 @@
+use super::utility_types::FloatingPanel;
+use crate::messages::portfolio::utility_types::PanelType;
 use crate::messages::prelude::*;
 
 #[impl_message(Message, Workspace)]
-#[derive(PartialEq, Eq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub enum WorkspaceMessage {
 	// Messages
+	ApplyLayout { name: String },
+	DockPanel { panel_type: PanelType },
+	FloatPanel { floating_panel: FloatingPanel },
 	NodeGraphToggleVisibility,
+	SaveLayout { name: String },
+	UpdateFloatingPanelBounds { floating_panel: FloatingPanel },
+	UpdatePanelSizes { panel_sizes: Vec<(String, f64)> },
 }
@@ -1,17 +1,133 @@
+use super::utility_types::FloatingPanel;
+use crate::messages::preferences::WorkspaceLayout;
 use crate::messages::prelude::*;
 
+pub struct WorkspaceMessageData<'a> {
+	pub portfolio: &'a PortfolioMessageHandler,
+	pub preferences: &'a PreferencesMessageHandler,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct WorkspaceMessageHandler {
 	node_graph_visible: bool,
+	/// The relative sizes of the workspace's subdivisions, as last reported by the frontend.
+	panel_sizes: Vec<(String, f64)>,
+	/// The panels undocked from the workspace into windows of their own.
+	floating_panels: Vec<FloatingPanel>,
 }
 
-impl MessageHandler<WorkspaceMessage, ()> for WorkspaceMessageHandler {
-	fn process_message(&mut self, message: WorkspaceMessage, _responses: &mut VecDeque<Message>, _data: ()) {
+impl MessageHandler<WorkspaceMessage, WorkspaceMessageData<'_>> for WorkspaceMessageHandler {
+	fn process_message(&mut self, message: WorkspaceMessage, responses: &mut VecDeque<Message>, data: WorkspaceMessageData) {
+		let WorkspaceMessageData { portfolio, preferences } = data;
+
 		match message {
 			// Messages
+			WorkspaceMessage::ApplyLayout { name } => {
+				let Some(layout) = preferences.workspace_layouts.iter().find(|layout| layout.name == name) else {
+					warn!("Tried to apply the nonexistent workspace layout \"{name}\"");
+					return;
+				};
+
+				if self.floating_panels != layout.floating_panels {
+					self.floating_panels.clone_from(&layout.floating_panels);
+					self.send_floating_panels(responses);
+				}
+				if !layout.panel_sizes.is_empty() {
+					self.panel_sizes.clone_from(&layout.panel_sizes);
+					responses.add(FrontendMessage::UpdatePanelSizes {
+						panel_sizes: layout.panel_sizes.clone(),
+					});
+				}
+				if portfolio.spreadsheet.spreadsheet_view_open != layout.spreadsheet_open {
+					responses.add(SpreadsheetMessage::ToggleOpen);
+				}
+				if portfolio.split_view.split_view_open != layout.split_view_open {
+					responses.add(SplitViewMessage::ToggleOpen);
+				}
+				if portfolio.timeline.timeline_open != layout.timeline_open {
+					responses.add(TimelineMessage::ToggleOpen);
+				}
+				if portfolio.color_harmony.color_harmony_open != layout.color_harmony_open {
+					responses.add(ColorHarmonyMessage::ToggleOpen);
+				}
+				if portfolio.plugins.plugins_open != layout.plugins_open {
+					responses.add(PluginsMessage::ToggleOpen);
+				}
+				if portfolio.console.console_open != layout.console_open {
+					responses.add(ConsoleMessage::ToggleOpen);
+				}
+				if portfolio.collaboration.collaboration_open != layout.collaboration_open {
+					responses.add(CollaborationMessage::ToggleOpen);
+				}
+				if portfolio.compare.compare_open != layout.compare_open {
+					responses.add(CompareMessage::ToggleOpen);
+				}
+				if portfolio.library.library_open != layout.library_open {
+					responses.add(LibraryMessage::ToggleOpen);
+				}
+				if portfolio.data_merge.data_merge_open != layout.data_merge_open {
+					responses.add(DataMergeMessage::ToggleOpen);
+				}
+				if portfolio.active_document().is_some_and(|document| document.graph_view_overlay_open != layout.node_graph_open) {
+					responses.add(DocumentMessage::GraphViewOverlay { open: layout.node_graph_open });
+				}
+			}
+			WorkspaceMessage::DockPanel { panel_type } => {
+				let count = self.floating_panels.len();
+				self.floating_panels.retain(|floating_panel| floating_panel.panel_type != panel_type);
+
+				if self.floating_panels.len() != count {
+					self.send_floating_panels(responses);
+				}
+			}
+			WorkspaceMessage::FloatPanel { floating_panel } => {
+				if !floating_panel.panel_type.can_float() {
+					warn!("Tried to undock the {:?} panel, which can't float", floating_panel.panel_type);
+					return;
+				}
+				if self.floating_panels.iter().any(|existing| existing.panel_type == floating_panel.panel_type) {
+					return;
+				}
+
+				self.floating_panels.push(floating_panel);
+				self.send_floating_panels(responses);
+			}
 			WorkspaceMessage::NodeGraphToggleVisibility => {
 				self.node_graph_visible = !self.node_graph_visible;
 			}
+			WorkspaceMessage::SaveLayout { name } => {
+				let name = name.trim().to_string();
+				if name.is_empty() {
+					return;
+				}
+
+				let layout = WorkspaceLayout {
+					name,
+					panel_sizes: self.panel_sizes.clone(),
+					node_graph_open: portfolio.active_document().is_some_and(|document| document.graph_view_overlay_open),
+					spreadsheet_open: portfolio.spreadsheet.spreadsheet_view_open,
+					split_view_open: portfolio.split_view.split_view_open,
+					timeline_open: portfolio.timeline.timeline_open,
+					color_harmony_open: portfolio.color_harmony.color_harmony_open,
+					plugins_open: portfolio.plugins.plugins_open,
+					console_open: portfolio.console.console_open,
+					collaboration_open: portfolio.collaboration.collaboration_open,
+					compare_open: portfolio.compare.compare_open,
+					library_open: portfolio.library.library_open,
+					data_merge_open: portfolio.data_merge.data_merge_open,
+					floating_panels: self.floating_panels.clone(),
+				};
+				responses.add(PreferencesMessage::SaveWorkspaceLayout { layout });
+			}
+			WorkspaceMessage::UpdateFloatingPanelBounds { floating_panel } => {
+				// The window was moved or resized by the user, so the frontend already shows it where it is
+				if let Some(existing) = self.floating_panels.iter_mut().find(|existing| existing.panel_type == floating_panel.panel_type) {
+					*existing = floating_panel;
+				}
+			}
+			WorkspaceMessage::UpdatePanelSizes { panel_sizes } => {
+				self.panel_sizes = panel_sizes;
+			}
 		}
 	}
 
@@ -21,3 +137,11 @@ impl MessageHandler<WorkspaceMessage, ()> for WorkspaceMessageHandler {
 		)
 	}
 }
+
+impl WorkspaceMessageHandler {
+	fn send_floating_panels(&self, responses: &mut VecDeque<Message>) {
+		responses.add(FrontendMessage::UpdateFloatingPanels {
+			floating_panels: self.floating_panels.clone(),
+		});
+	}
+}
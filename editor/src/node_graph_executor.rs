@@ -1,8 +1,12 @@
-use crate::consts::FILE_SAVE_SUFFIX;
+use crate::consts::{FILE_SAVE_SUFFIX, PROGRESSIVE_RENDER_PREVIEW_DELAY, VIEWPORT_RENDER_INVALIDATION_MARGIN, VIEWPORT_RENDER_TILE_MARGIN, VIEWPORT_RENDER_TILE_SIZE};
 use crate::messages::animation::TimingInformation;
-use crate::messages::frontend::utility_types::{ExportBounds, FileType};
+use crate::messages::frontend::utility_types::{ExportBounds, ExportMetadata, FileType, FrameSequenceExport, TextExport, TiffCompression};
+use crate::messages::portfolio::document::overlays::render_statistics::{RenderStatistics, now_milliseconds};
+use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
+use crate::messages::portfolio::document::utility_types::swatches::Swatch;
 use crate::messages::prelude::*;
-use glam::{DAffine2, DVec2, UVec2};
+use crate::messages::tool::common_functionality::graph_modification_utils;
+use glam::{DAffine2, DMat2, DVec2, IVec2, UVec2};
 use graph_craft::concrete;
 use graph_craft::document::value::{RenderOutput, TaggedValue};
 use graph_craft::document::{DocumentNode, DocumentNodeImplementation, NodeId, NodeInput, NodeNetwork, generate_uuid};
@@ -11,21 +15,26 @@ use graph_craft::proto::GraphErrors;
 use graph_craft::wasm_application_io::EditorPreferences;
 use graphene_core::Context;
 use graphene_core::application_io::{NodeGraphUpdateMessage, NodeGraphUpdateSender, RenderConfig};
-use graphene_core::memo::IORecord;
-use graphene_core::renderer::{GraphicElementRendered, RenderParams, SvgRender};
+use graphene_core::memo::{EvaluationStatistics, IORecord};
+use graphene_core::renderer::{GraphicElementRendered, Quad, RenderParams, SvgRender};
 use graphene_core::renderer::{RenderSvgSegmentList, SvgSegment};
 use graphene_core::text::FontCache;
-use graphene_core::transform::Footprint;
+use graphene_core::transform::{Footprint, RenderQuality};
 use graphene_core::vector::style::ViewMode;
-use graphene_std::renderer::{RenderMetadata, format_transform_matrix};
+use graphene_std::print::SpotColor;
+use graphene_std::renderer::{ColorProfile, RenderMetadata, format_transform_matrix};
 use graphene_std::vector::{VectorData, VectorDataTable};
 use graphene_std::wasm_application_io::{WasmApplicationIo, WasmEditorApi};
 use interpreted_executor::dynamic_executor::{DynamicExecutor, IntrospectError, ResolvedDocumentNodeTypesDelta};
 use interpreted_executor::util::wrap_network_in_scope;
 use once_cell::sync::Lazy;
 use spin::Mutex;
+use std::fmt::Write;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::mpsc::{Receiver, Sender};
+use std::time::Duration;
 
 /// Persistent data between graph executions. It's updated via message passing from the editor thread with [`NodeRuntimeMessage`]`.
 /// Some of these fields are put into a [`WasmEditorApi`] which is passed to the final compiled graph network upon each execution.
@@ -37,6 +46,8 @@ pub struct NodeRuntime {
 	editor_preferences: EditorPreferences,
 	old_graph: Option<NodeNetwork>,
 	update_thumbnails: bool,
+	/// Layers whose thumbnails aren't visible, so rendering them is deferred until they are.
+	deferred_thumbnails: HashSet<NodeId>,
 
 	editor_api: Arc<WasmEditorApi>,
 	node_graph_errors: GraphErrors,
@@ -49,6 +60,9 @@ pub struct NodeRuntime {
 	/// The current renders of the thumbnails for layer nodes.
 	thumbnail_renders: HashMap<NodeId, Vec<SvgSegment>>,
 	vector_modify: HashMap<NodeId, VectorData>,
+
+	/// Shared with the [`NodeGraphExecutor`] so a viewport render still in progress is canceled once a newer one is requested.
+	cancellation: ExecutionCancellation,
 }
 
 /// Messages passed from the editor thread to the node runtime thread.
@@ -57,6 +71,7 @@ pub enum NodeRuntimeMessage {
 	ExecutionRequest(ExecutionRequest),
 	FontCacheUpdate(FontCache),
 	EditorPreferencesUpdate(EditorPreferences),
+	DeferredThumbnailsUpdate(HashSet<NodeId>),
 }
 
 #[derive(Default, Debug, Clone)]
@@ -66,7 +81,21 @@ pub struct ExportConfig {
 	pub scale_factor: f64,
 	pub bounds: ExportBounds,
 	pub transparent_background: bool,
+	pub metadata: ExportMetadata,
+	pub tiff_compression: TiffCompression,
+	/// How the text of an SVG export is written.
+	pub text_export: TextExport,
+	/// The document's color profile, which is embedded in raster exports since their colors are in it.
+	pub color_profile: ColorProfile,
 	pub size: DVec2,
+	/// The serialization of the copied layers, set when the export is rendered for the clipboard rather than downloaded.
+	pub copied_layers: Option<String>,
+	/// The number of swatches to extract from the colors of the rendering, set when the export is rendered to pick colors from rather than downloaded.
+	pub swatch_count: Option<u32>,
+	/// The document's spot colors, which fills and strokes of the same color are printed with in PDF and EPS exports.
+	pub spot_colors: Vec<SpotColor>,
+	/// Identifies the downloaded file to the frontend, which can take it instead of downloading it, like when a save action writes it to a folder.
+	pub export_id: Option<u64>,
 }
 
 pub struct GraphUpdate {
@@ -88,6 +117,8 @@ pub struct ExecutionResponse {
 	vector_modify: HashMap<NodeId, VectorData>,
 	/// The resulting value from the temporary inspected during execution
 	inspect_result: Option<InspectResult>,
+	evaluation_milliseconds: f64,
+	evaluation_statistics: EvaluationStatistics,
 }
 
 pub struct CompilationResponse {
@@ -97,10 +128,29 @@ pub struct CompilationResponse {
 
 pub enum NodeGraphUpdate {
 	ExecutionResponse(ExecutionResponse),
+	/// The execution with this ID was abandoned because a newer viewport render superseded it.
+	ExecutionCanceled(u64),
 	CompilationResponse(CompilationResponse),
 	NodeGraphUpdateMessage(NodeGraphUpdateMessage),
 }
 
+/// Tracks the most recently requested viewport render, so a render which was superseded while it was in progress stops at its evaluation's next yield point.
+/// Exports are never canceled.
+#[derive(Debug, Clone, Default)]
+struct ExecutionCancellation {
+	latest_viewport_execution: Arc<AtomicU64>,
+}
+
+impl ExecutionCancellation {
+	fn supersede(&self, execution_id: u64) {
+		self.latest_viewport_execution.store(execution_id, Ordering::SeqCst);
+	}
+
+	fn is_superseded(&self, execution_id: u64) -> bool {
+		self.latest_viewport_execution.load(Ordering::SeqCst) != execution_id
+	}
+}
+
 #[derive(Clone)]
 struct InternalNodeGraphUpdateSender(Sender<NodeGraphUpdate>);
 
@@ -112,6 +162,10 @@ impl InternalNodeGraphUpdateSender {
 	fn send_execution_response(&self, response: ExecutionResponse) {
 		self.0.send(NodeGraphUpdate::ExecutionResponse(response)).expect("Failed to send response")
 	}
+
+	fn send_execution_canceled(&self, execution_id: u64) {
+		self.0.send(NodeGraphUpdate::ExecutionCanceled(execution_id)).expect("Failed to send response")
+	}
 }
 
 impl NodeGraphUpdateSender for InternalNodeGraphUpdateSender {
@@ -131,6 +185,7 @@ impl NodeRuntime {
 			editor_preferences: EditorPreferences::default(),
 			old_graph: None,
 			update_thumbnails: true,
+			deferred_thumbnails: HashSet::new(),
 
 			editor_api: WasmEditorApi {
 				font_cache: FontCache::default(),
@@ -148,6 +203,7 @@ impl NodeRuntime {
 
 			thumbnail_renders: Default::default(),
 			vector_modify: Default::default(),
+			cancellation: Default::default(),
 		}
 	}
 
@@ -165,16 +221,36 @@ impl NodeRuntime {
 		let mut font = None;
 		let mut preferences = None;
 		let mut graph = None;
-		let mut execution = None;
+		let mut exports = Vec::new();
+		let mut viewport_execution = None;
 		for request in self.receiver.try_iter() {
 			match request {
+				// Exports are rendered from the network they were requested with, so a network sent after one is compiled after it, such as for each frame of a frame sequence
+				NodeRuntimeMessage::GraphUpdate(_) if !exports.is_empty() => exports.push(request),
 				NodeRuntimeMessage::GraphUpdate(_) => graph = Some(request),
-				NodeRuntimeMessage::ExecutionRequest(_) => execution = Some(request),
+				NodeRuntimeMessage::ExecutionRequest(ref execution) if execution.render_config.for_export => exports.push(request),
+				NodeRuntimeMessage::ExecutionRequest(execution) => {
+					// Only the latest viewport render is worth running, so the ones it supersedes are canceled without ever starting
+					if let Some(superseded) = viewport_execution.replace(execution) {
+						self.sender.send_execution_canceled(superseded.execution_id);
+					}
+				}
 				NodeRuntimeMessage::FontCacheUpdate(_) => font = Some(request),
 				NodeRuntimeMessage::EditorPreferencesUpdate(_) => preferences = Some(request),
+				NodeRuntimeMessage::DeferredThumbnailsUpdate(deferred_thumbnails) => {
+					// Render the thumbnails which are no longer deferred, which may be unchanged since the previous render so they wouldn't be otherwise
+					if self.deferred_thumbnails.iter().any(|layer| !deferred_thumbnails.contains(layer)) {
+						self.update_thumbnails = true;
+					}
+					self.deferred_thumbnails = deferred_thumbnails;
+				}
 			}
 		}
-		let requests = [font, preferences, graph, execution].into_iter().flatten();
+		let requests = [font, preferences, graph]
+			.into_iter()
+			.flatten()
+			.chain(exports)
+			.chain(viewport_execution.map(NodeRuntimeMessage::ExecutionRequest));
 
 		for request in requests {
 			match request {
@@ -192,6 +268,7 @@ impl NodeRuntime {
 					}
 				}
 				NodeRuntimeMessage::EditorPreferencesUpdate(preferences) => {
+					graphene_core::memory_budget::set_memory_budget(preferences.memory_budget);
 					self.editor_preferences = preferences.clone();
 					self.editor_api = WasmEditorApi {
 						font_cache: self.editor_api.font_cache.clone(),
@@ -205,6 +282,8 @@ impl NodeRuntime {
 						let _ = self.update_network(graph).await;
 					}
 				}
+				// This is applied as soon as it's received, above
+				NodeRuntimeMessage::DeferredThumbnailsUpdate(_) => {}
 				NodeRuntimeMessage::GraphUpdate(GraphUpdate { mut network, inspect_node }) => {
 					// Insert the monitor node to manage the inspection
 					self.inspect_state = inspect_node.map(|inspect| InspectState::monitor_inspect_node(&mut network, inspect));
@@ -221,7 +300,30 @@ impl NodeRuntime {
 				NodeRuntimeMessage::ExecutionRequest(ExecutionRequest { execution_id, render_config, .. }) => {
 					let transform = render_config.viewport.transform;
 
-					let result = self.execute_network(render_config).await;
+					// Discard the counts left over from any evaluation whose result was discarded, so they only cover this one
+					graphene_core::memo::take_evaluation_statistics();
+					let evaluation_start = now_milliseconds();
+
+					let for_export = render_config.for_export;
+					let result = if for_export {
+						Some(self.execute_network(render_config).await)
+					} else {
+						// Once a newer viewport render is requested, this one stops at the next point where its evaluation yields, between whole chunks of its nodes' work
+						let cancellation = self.cancellation.clone();
+						graphene_core::parallel::cancelable(self.execute_network(render_config), move || cancellation.is_superseded(execution_id)).await
+					};
+
+					// A render superseded after its last yield point still finishes, but its result is already stale, so it's discarded all the same
+					let result = match result {
+						Some(result) if for_export || !self.cancellation.is_superseded(execution_id) => result,
+						_ => {
+							self.sender.send_execution_canceled(execution_id);
+							continue;
+						}
+					};
+					let evaluation_milliseconds = now_milliseconds() - evaluation_start;
+					let evaluation_statistics = graphene_core::memo::take_evaluation_statistics();
+
 					let mut responses = VecDeque::new();
 					// TODO: Only process monitor nodes if the graph has changed, not when only the Footprint changes
 					self.process_monitor_nodes(&mut responses, self.update_thumbnails);
@@ -237,6 +339,8 @@ impl NodeRuntime {
 						transform,
 						vector_modify: self.vector_modify.clone(),
 						inspect_result,
+						evaluation_milliseconds,
+						evaluation_statistics,
 					});
 				}
 			}
@@ -310,14 +414,16 @@ impl NodeRuntime {
 				continue;
 			};
 
+			let update_thumbnail = update_thumbnails && !self.deferred_thumbnails.contains(&parent_network_node_id);
+
 			if let Some(io) = introspected_data.downcast_ref::<IORecord<Context, graphene_core::GraphicElement>>() {
-				Self::process_graphic_element(&mut self.thumbnail_renders, parent_network_node_id, &io.output, responses, update_thumbnails)
+				Self::process_graphic_element(&mut self.thumbnail_renders, parent_network_node_id, &io.output, responses, update_thumbnail)
 			} else if let Some(io) = introspected_data.downcast_ref::<IORecord<(), graphene_core::GraphicElement>>() {
-				Self::process_graphic_element(&mut self.thumbnail_renders, parent_network_node_id, &io.output, responses, update_thumbnails)
+				Self::process_graphic_element(&mut self.thumbnail_renders, parent_network_node_id, &io.output, responses, update_thumbnail)
 			} else if let Some(io) = introspected_data.downcast_ref::<IORecord<Context, graphene_core::Artboard>>() {
-				Self::process_graphic_element(&mut self.thumbnail_renders, parent_network_node_id, &io.output, responses, update_thumbnails)
+				Self::process_graphic_element(&mut self.thumbnail_renders, parent_network_node_id, &io.output, responses, update_thumbnail)
 			} else if let Some(io) = introspected_data.downcast_ref::<IORecord<(), graphene_core::Artboard>>() {
-				Self::process_graphic_element(&mut self.thumbnail_renders, parent_network_node_id, &io.output, responses, update_thumbnails)
+				Self::process_graphic_element(&mut self.thumbnail_renders, parent_network_node_id, &io.output, responses, update_thumbnail)
 			}
 			// Insert the vector modify if we are dealing with vector data
 			else if let Some(record) = introspected_data.downcast_ref::<IORecord<Context, VectorDataTable>>() {
@@ -397,6 +503,24 @@ pub struct NodeGraphExecutor {
 	futures: HashMap<u64, ExecutionContext>,
 	node_graph_hash: u64,
 	old_inspect_node: Option<NodeId>,
+	/// The layers whose thumbnails the runtime was last told to defer.
+	deferred_thumbnails: HashSet<NodeId>,
+	/// The top level layers left out of, or evaluated by, the network last sent to the runtime.
+	layer_evaluation: LayerEvaluation,
+	/// The content key of each top level layer as of the render which last evaluated it, for which the layer's bounds are accurate.
+	evaluated_layer_contents: HashMap<NodeId, u64>,
+	/// The content keys of the top level layers in the document's current network.
+	layer_content_keys: LayerContentKeys,
+	viewport_tiles: ViewportTileCache,
+	cancellation: ExecutionCancellation,
+	/// How many compilations of a network substituted for the active document's, sent to render another document in the split view, an onion skin with its keyframes, or either of them with the layers left out of the viewport's network, have yet to be reported back.
+	/// Their results don't describe the active document's network, so they're ignored.
+	pending_substitute_compilations: usize,
+	/// The animation time (in seconds) of the viewport's render, which the keyframed inputs of the network sent to the runtime are set for.
+	keyframe_time: f64,
+	/// Leaves the artboards' backgrounds out of the viewport's render, for while they're drawn beneath the onion skins, which are beneath the artwork.
+	pub(crate) hide_artboard_backgrounds: bool,
+	pub render_statistics: RenderStatistics,
 }
 
 /// Which node is inspected and which monitor node is used (if any) for the current execution
@@ -465,13 +589,329 @@ impl InspectState {
 #[derive(Debug, Clone)]
 struct ExecutionContext {
 	export_config: Option<ExportConfig>,
+	viewport_tiles: Option<ViewportTileExecution>,
+	split_view: Option<SplitViewExecution>,
+	onion_skin: Option<OnionSkinExecution>,
+	layer_evaluation: Option<LayerEvaluation>,
+}
+
+/// Which top level layers a render of the viewport left out of the network, and which it evaluated.
+///
+/// Layers last seen entirely outside the region being rendered have their content replaced by an empty group, so none of the nodes feeding into them are evaluated.
+/// They're evaluated once they're panned or zoomed into view, selected, or changed, so the cost of editing a large document follows the artwork in view.
+/// Only top level layers (those in the document or directly in an artboard) are left out, since they aren't moved by a parent's transform and their bounds only change along with their content.
+#[derive(Debug, Clone, Default)]
+struct LayerEvaluation {
+	/// The layers whose content was left out.
+	deferred: HashSet<NodeId>,
+	/// The content key of each top level layer which was evaluated.
+	evaluated: HashMap<NodeId, u64>,
+}
+
+/// The content key of each top level layer in a network at an animation time, which changes whenever anything feeding into the layer does.
+#[derive(Debug, Clone, Default)]
+struct LayerContentKeys {
+	network_hash: u64,
+	animation_time: Duration,
+	keys: HashMap<NodeId, u64>,
+	/// The [`network_structure_key`] of the network.
+	structure_key: u64,
+}
+
+/// The top level layers of the document, which are those in the document or directly inside an artboard.
+fn top_level_layers(document: &DocumentMessageHandler) -> Vec<LayerNodeIdentifier> {
+	let metadata = document.metadata();
+	// Artboards are always evaluated, but the layers directly inside them aren't moved by them so they're also top level
+	LayerNodeIdentifier::ROOT_PARENT
+		.children(metadata)
+		.flat_map(|layer| {
+			if document.network_interface.is_artboard(&layer.to_node(), &[]) {
+				layer.children(metadata).collect()
+			} else {
+				vec![layer]
+			}
+		})
+		.collect()
+}
+
+/// The nodes feeding into the given inputs, including those feeding into the nodes found.
+fn upstream_nodes<'a>(network: &NodeNetwork, inputs: impl IntoIterator<Item = &'a NodeInput>) -> HashSet<NodeId> {
+	let mut upstream = HashSet::new();
+	let mut stack = inputs.into_iter().filter_map(NodeInput::as_node).collect::<Vec<_>>();
+	while let Some(node_id) = stack.pop() {
+		if !upstream.insert(node_id) {
+			continue;
+		}
+		if let Some(node) = network.nodes.get(&node_id) {
+			stack.extend(node.inputs.iter().filter_map(NodeInput::as_node));
+		}
+	}
+	upstream
+}
+
+/// Hashes a top level layer's content, which is everything feeding into its secondary input (the primary input is the stack of layers beneath it).
+fn layer_content_key(network: &NodeNetwork, layer: NodeId, animation_time: Duration) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	animation_time.hash(&mut hasher);
+	let Some(layer_node) = network.nodes.get(&layer) else { return hasher.finish() };
+	layer_node.hash(&mut hasher);
+
+	let mut content = upstream_nodes(network, layer_node.inputs.iter().skip(1)).into_iter().collect::<Vec<_>>();
+	content.sort();
+	for node_id in content {
+		node_id.hash(&mut hasher);
+		network.nodes.get(&node_id).hash(&mut hasher);
+	}
+	hasher.finish()
+}
+
+/// Hashes everything in the network besides the content of its top level layers, so it changes along with anything which may affect the whole render rather than only the layers it feeds into.
+fn network_structure_key(network: &NodeNetwork, top_level_layers: &HashSet<NodeId>) -> u64 {
+	let content_inputs = top_level_layers.iter().filter_map(|layer| network.nodes.get(layer)).flat_map(|node| node.inputs.iter().skip(1));
+	let mut content = upstream_nodes(network, content_inputs);
+	content.extend(top_level_layers);
+
+	// The layers are linked into the rest of the network by their stacks, but nodes of their content may also feed into it directly
+	let links_layer = |input: &&NodeInput| input.as_node().is_some_and(|node_id| top_level_layers.contains(&node_id));
+	let rest = network.nodes.iter().filter(|(node_id, _)| !content.contains(node_id)).flat_map(|(_, node)| &node.inputs);
+	let used = upstream_nodes(network, rest.chain(&network.exports).filter(|input| !links_layer(input)));
+
+	let mut hasher = DefaultHasher::new();
+	network.exports.hash(&mut hasher);
+	let mut structure = network.nodes.keys().filter(|node_id| !content.contains(node_id) || used.contains(node_id)).collect::<Vec<_>>();
+	structure.sort();
+	for node_id in structure {
+		node_id.hash(&mut hasher);
+		network.nodes.get(node_id).hash(&mut hasher);
+	}
+	hasher.finish()
+}
+
+/// A render of the split view, which is kept apart from the active document's viewport.
+#[derive(Debug, Clone, Copy)]
+struct SplitViewExecution {
+	document_to_viewport: DAffine2,
+	/// Whether the render is of a document other than the active one.
+	other_document: bool,
+	/// Whether the render is of a network substituted for the active document's, which has to be restored in the runtime afterwards.
+	substituted: bool,
+}
+
+/// A render of the active document at another time, ghosted beneath the viewport's artwork as an onion skin.
+#[derive(Debug, Clone, Copy)]
+struct OnionSkinExecution {
+	/// Whether the render is of a network substituted for the active document's, which has to be restored in the runtime afterwards.
+	substituted: bool,
+}
+
+/// A rectangular range of the tiles which the viewport's render is snapped to.
+/// The tiles are laid out in canvas space, which is the viewport space before panning is applied, so they stay put while the user pans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ViewportTiles {
+	/// The first tile within the range.
+	min: IVec2,
+	/// The first tile beyond the end of the range.
+	max: IVec2,
+}
+
+impl ViewportTiles {
+	/// The tiles covering the visible viewport, plus a margin of extra tiles around it.
+	fn covering(document_to_viewport: DAffine2, viewport_resolution: UVec2) -> Self {
+		// Panning only translates the canvas, so the top left corner of the viewport in canvas space is the opposite of that translation
+		let start = -document_to_viewport.translation;
+		let end = start + viewport_resolution.as_dvec2();
+
+		let visible = Self::overlapping([start, end]);
+		Self {
+			min: visible.min - VIEWPORT_RENDER_TILE_MARGIN,
+			max: visible.max + VIEWPORT_RENDER_TILE_MARGIN,
+		}
+	}
+
+	/// The tiles overlapping a region of the canvas, in canvas space.
+	fn overlapping([start, end]: [DVec2; 2]) -> Self {
+		Self {
+			min: (start / VIEWPORT_RENDER_TILE_SIZE).floor().as_ivec2(),
+			max: (end / VIEWPORT_RENDER_TILE_SIZE).ceil().as_ivec2(),
+		}
+	}
+
+	/// The tiles drawn over by a layer with the given bounds, in document space, which are those overlapping its bounds once they're extended by [`VIEWPORT_RENDER_INVALIDATION_MARGIN`].
+	fn drawn_over(bounds: [DVec2; 2], document_to_viewport: DAffine2) -> Self {
+		let [start, end] = (DAffine2::from_mat2(document_to_viewport.matrix2) * Quad::from_box(bounds)).bounding_box();
+		Self::overlapping([start - VIEWPORT_RENDER_INVALIDATION_MARGIN, end + VIEWPORT_RENDER_INVALIDATION_MARGIN])
+	}
+
+	/// The smallest range containing all of the given tiles, if there are any.
+	fn bounding(tiles: impl IntoIterator<Item = IVec2>) -> Option<Self> {
+		tiles.into_iter().map(|tile| Self { min: tile, max: tile + 1 }).reduce(|a, b| Self {
+			min: a.min.min(b.min),
+			max: a.max.max(b.max),
+		})
+	}
+
+	/// The tiles within both ranges, which may be none.
+	fn intersection(&self, other: Self) -> Self {
+		Self {
+			min: self.min.max(other.min),
+			max: self.max.min(other.max),
+		}
+	}
+
+	fn contains(&self, tile: IVec2) -> bool {
+		self.min.cmple(tile).all() && self.max.cmpgt(tile).all()
+	}
+
+	fn iter(self) -> impl Iterator<Item = IVec2> {
+		let Self { min, max } = self;
+		(min.y..max.y).flat_map(move |y| (min.x..max.x).map(move |x| IVec2::new(x, y)))
+	}
+
+	/// The top left corner of the first tile, in canvas space.
+	fn origin(&self) -> DVec2 {
+		self.min.as_dvec2() * VIEWPORT_RENDER_TILE_SIZE
+	}
+
+	/// The footprint which renders exactly the region of the canvas covered by these tiles.
+	fn footprint(&self, document_to_viewport: DAffine2) -> Footprint {
+		Footprint {
+			transform: DAffine2::from_translation(-self.origin()) * DAffine2::from_mat2(document_to_viewport.matrix2),
+			resolution: ((self.max - self.min).as_dvec2() * VIEWPORT_RENDER_TILE_SIZE).as_uvec2(),
+			..Default::default()
+		}
+	}
+
+	/// The region of the document covered by these tiles, in document space.
+	fn region(&self, document_to_viewport: DAffine2) -> [DVec2; 2] {
+		let footprint = self.footprint(document_to_viewport);
+		(footprint.transform.inverse() * Quad::from_box([DVec2::ZERO, footprint.resolution.as_dvec2()])).bounding_box()
+	}
+
+	/// The offset from the top left corner of the viewport to the top left corner of the first tile, in viewport space.
+	fn viewport_offset(&self, document_to_viewport: DAffine2) -> DVec2 {
+		self.origin() + document_to_viewport.translation
+	}
+}
+
+/// Everything a render of the viewport depends on besides how far the canvas is panned and the content of the top level layers.
+#[derive(Debug, Clone, PartialEq)]
+struct ViewportRenderKey {
+	structure_key: u64,
+	inspect_node: Option<NodeId>,
+	zoom_and_rotation: DMat2,
+	animation_time: Duration,
+	view_mode: ViewMode,
+	hide_artboard_backgrounds: bool,
+}
+
+/// A render of a range of the viewport's tiles, of which the tiles it's still drawn for are kept.
+#[derive(Debug, Clone)]
+struct ViewportTileRender {
+	tiles: ViewportTiles,
+	/// The artwork in the space of the tiles.
+	artwork: String,
+}
+
+/// A render of the viewport's tiles in progress.
+#[derive(Debug, Clone, Copy)]
+struct ViewportTileExecution {
+	tiles: ViewportTiles,
+	/// The [`ViewportTileCache::generation`] the render was requested in.
+	generation: u64,
+}
+
+/// The renders of the viewport's tiles, kept so that panning within them only needs the artwork to be repositioned, and a change to some of the layers only needs the tiles they're drawn over to be rendered again.
+#[derive(Debug, Clone, Default)]
+struct ViewportTileCache {
+	key: Option<ViewportRenderKey>,
+	/// Changes each time the cache is emptied, so the renders which were in progress before aren't kept once they arrive.
+	generation: u64,
+	/// The content key of each top level layer as of the latest render requested, to find the layers which changed since.
+	layer_contents: HashMap<NodeId, u64>,
+	/// The renders drawn by the tiles, by their execution ID.
+	renders: HashMap<u64, ViewportTileRender>,
+	/// The execution ID of the render each tile is drawn from.
+	tiles: HashMap<IVec2, u64>,
+	/// The tiles which a change to the layers drawn over them has left out of date, or which are missing, and which aren't being rendered yet.
+	dirty: HashSet<IVec2>,
+	/// The metadata of the latest render to arrive, in the space of its tiles.
+	metadata: Option<(RenderMetadata, ViewportTiles)>,
+	/// Whether the renders are drawn to a canvas which each render draws over, so only the latest can be shown and every tile has to be rendered along with it.
+	shared_surface: bool,
+	execution_id: Option<u64>,
+	/// The tiles of the render in progress, or of the full quality render to follow it.
+	rendering: Option<ViewportTiles>,
+	/// The top level layers which changed for the render in progress, whose bounds aren't known until it arrives.
+	changed_layers: HashSet<NodeId>,
+	/// When the viewport began waiting on a render, if it's still waiting. The render is considered to be lagging behind once this exceeds [`PROGRESSIVE_RENDER_PREVIEW_DELAY`].
+	pending_since: Option<f64>,
+	/// The full quality render to follow once the low resolution preview currently in progress arrives.
+	refinement: Option<RenderConfig>,
+	/// Whether the refinement waits for the user to stop interacting, instead of following the preview straight away.
+	refine_when_idle: bool,
+	/// The full quality render held back until the user stops interacting, now that the preview it refines has arrived.
+	idle_refinement: Option<RenderConfig>,
+}
+
+impl ViewportTileCache {
+	/// Empties the cache of its renders, which no longer apply to a render with the given key.
+	fn reset(&mut self, key: Option<ViewportRenderKey>) {
+		*self = Self {
+			key,
+			generation: generate_uuid(),
+			pending_since: self.pending_since,
+			..Default::default()
+		};
+	}
+
+	/// Keeps a render which arrived to be drawn for its tiles, in place of the renders they were drawn from.
+	fn insert_render(&mut self, execution_id: u64, tiles: ViewportTiles, artwork: String, shared_surface: bool) {
+		// The earlier renders were drawn to the same canvas, which no longer shows them
+		if shared_surface {
+			self.renders.clear();
+			self.tiles.clear();
+		}
+		self.shared_surface = shared_surface;
+
+		self.renders.insert(execution_id, ViewportTileRender { tiles, artwork });
+		self.tiles.extend(tiles.iter().map(|tile| (tile, execution_id)));
+		let drawn = self.tiles.values().copied().collect::<HashSet<_>>();
+		self.renders.retain(|execution_id, _| drawn.contains(execution_id));
+	}
+
+	/// Marks the tiles beyond those just rendered which the layers that changed for the render are now drawn over, as found from the bounds in the render's metadata.
+	fn mark_moved_layers(&mut self, metadata: &RenderMetadata, tiles: ViewportTiles) {
+		if self.tiles.keys().all(|&tile| tiles.contains(tile)) {
+			self.changed_layers.clear();
+			return;
+		}
+
+		for node_id in self.changed_layers.drain() {
+			let Some(click_targets) = metadata.click_targets.get(&node_id) else { continue };
+			let footprint = metadata.upstream_footprints.get(&node_id).map(|footprint| footprint.transform).unwrap_or_default();
+			let transform = DAffine2::from_translation(tiles.origin()) * footprint * metadata.local_transforms.get(&node_id).copied().unwrap_or_default();
+			let Some([start, end]) = click_targets
+				.iter()
+				.filter_map(|click_target| click_target.subpath().bounding_box_with_transform(transform))
+				.reduce(Quad::combine_bounds)
+			else {
+				continue;
+			};
+
+			let drawn_over = ViewportTiles::overlapping([start - VIEWPORT_RENDER_INVALIDATION_MARGIN, end + VIEWPORT_RENDER_INVALIDATION_MARGIN]);
+			let outdated = self.tiles.keys().filter(|&&tile| drawn_over.contains(tile) && !tiles.contains(tile));
+			self.dirty.extend(outdated);
+		}
+	}
 }
 
 impl Default for NodeGraphExecutor {
 	fn default() -> Self {
 		let (request_sender, request_receiver) = std::sync::mpsc::channel();
 		let (response_sender, response_receiver) = std::sync::mpsc::channel();
-		futures::executor::block_on(replace_node_runtime(NodeRuntime::new(request_receiver, response_sender)));
+		let node_runtime = NodeRuntime::new(request_receiver, response_sender);
+		let cancellation = node_runtime.cancellation.clone();
+		futures::executor::block_on(replace_node_runtime(node_runtime));
 
 		Self {
 			futures: Default::default(),
@@ -479,6 +919,16 @@ impl Default for NodeGraphExecutor {
 			receiver: response_receiver,
 			node_graph_hash: 0,
 			old_inspect_node: None,
+			deferred_thumbnails: HashSet::new(),
+			layer_evaluation: Default::default(),
+			evaluated_layer_contents: HashMap::new(),
+			layer_content_keys: Default::default(),
+			viewport_tiles: Default::default(),
+			cancellation,
+			pending_substitute_compilations: 0,
+			keyframe_time: 0.,
+			hide_artboard_backgrounds: false,
+			render_statistics: Default::default(),
 		}
 	}
 }
@@ -497,6 +947,16 @@ impl NodeGraphExecutor {
 			receiver: response_receiver,
 			node_graph_hash: 0,
 			old_inspect_node: None,
+			deferred_thumbnails: HashSet::new(),
+			layer_evaluation: Default::default(),
+			evaluated_layer_contents: HashMap::new(),
+			layer_content_keys: Default::default(),
+			viewport_tiles: Default::default(),
+			cancellation: node_runtime.cancellation.clone(),
+			pending_substitute_compilations: 0,
+			keyframe_time: 0.,
+			hide_artboard_backgrounds: false,
+			render_statistics: Default::default(),
 		};
 		(node_runtime, node_executor)
 	}
@@ -504,6 +964,9 @@ impl NodeGraphExecutor {
 	/// Execute the network by flattening it and creating a borrow stack.
 	fn queue_execution(&self, render_config: RenderConfig) -> u64 {
 		let execution_id = generate_uuid();
+		if !render_config.for_export {
+			self.cancellation.supersede(execution_id);
+		}
 		let request = ExecutionRequest { execution_id, render_config };
 		self.sender.send(NodeRuntimeMessage::ExecutionRequest(request)).expect("Failed to send generation request");
 
@@ -514,11 +977,13 @@ impl NodeGraphExecutor {
 		introspect_node(path).await
 	}
 
-	pub fn update_font_cache(&self, font_cache: FontCache) {
+	pub fn update_font_cache(&mut self, font_cache: FontCache) {
+		self.viewport_tiles = Default::default();
 		self.sender.send(NodeRuntimeMessage::FontCacheUpdate(font_cache)).expect("Failed to send font cache update");
 	}
 
-	pub fn update_editor_preferences(&self, editor_preferences: EditorPreferences) {
+	pub fn update_editor_preferences(&mut self, editor_preferences: EditorPreferences) {
+		self.viewport_tiles = Default::default();
 		self.sender
 			.send(NodeRuntimeMessage::EditorPreferencesUpdate(editor_preferences))
 			.expect("Failed to send editor preferences");
@@ -560,10 +1025,10 @@ impl NodeGraphExecutor {
 
 	/// Update the cached network if necessary.
 	fn update_node_graph(&mut self, document: &mut DocumentMessageHandler, inspect_node: Option<NodeId>, ignore_hash: bool) -> Result<(), String> {
-		let network_hash = document.network_interface.document_network().current_hash();
+		let network_hash = self.deferred_network_hash(document);
 		// Refresh the graph when it changes or the inspect node changes
 		if network_hash != self.node_graph_hash || self.old_inspect_node != inspect_node || ignore_hash {
-			let network = document.network_interface.document_network().clone();
+			let network = self.deferred_network(document);
 			self.old_inspect_node = inspect_node;
 			self.node_graph_hash = network_hash;
 
@@ -572,31 +1037,325 @@ impl NodeGraphExecutor {
 		Ok(())
 	}
 
-	/// Adds an evaluate request for whatever current network is cached.
-	pub(crate) fn submit_current_node_graph_evaluation(&mut self, document: &mut DocumentMessageHandler, viewport_resolution: UVec2, time: TimingInformation) -> Result<(), String> {
-		let render_config = RenderConfig {
-			viewport: Footprint {
-				transform: document.metadata().document_to_viewport,
-				resolution: viewport_resolution,
-				..Default::default()
-			},
+	/// The hash of the document's network once the deferred layers are left out of it.
+	fn deferred_network_hash(&self, document: &DocumentMessageHandler) -> u64 {
+		let network_hash = match document.evaluated_network(self.keyframe_time) {
+			Some(network) => network.current_hash(),
+			None => document.network_interface.document_network().current_hash(),
+		};
+		if self.layer_evaluation.deferred.is_empty() {
+			return network_hash;
+		}
+
+		let mut deferred = self.layer_evaluation.deferred.iter().collect::<Vec<_>>();
+		deferred.sort();
+		let mut hasher = DefaultHasher::new();
+		(network_hash, deferred).hash(&mut hasher);
+		hasher.finish()
+	}
+
+	/// The document's network, with its keyframed inputs set for the time of the viewport's render and its linked text boxes given their text, the content of the deferred layers replaced by an empty group, and the nodes which only fed into that content removed.
+	fn deferred_network(&self, document: &DocumentMessageHandler) -> NodeNetwork {
+		let mut network = document.evaluated_network(self.keyframe_time).unwrap_or_else(|| document.network_interface.document_network().clone());
+		if self.layer_evaluation.deferred.is_empty() {
+			return network;
+		}
+
+		let content_inputs = self.layer_evaluation.deferred.iter().filter_map(|layer| network.nodes.get(layer)?.inputs.get(1));
+		let content = upstream_nodes(&network, content_inputs);
+		for layer in &self.layer_evaluation.deferred {
+			if let Some(content_input) = network.nodes.get_mut(layer).and_then(|node| node.inputs.get_mut(1)) {
+				*content_input = NodeInput::value(TaggedValue::GraphicGroup(Default::default()), false);
+			}
+		}
+
+		// Nodes of the content may also feed into the rest of the network, so only those it no longer uses are removed
+		let rest = network.nodes.iter().filter(|(node_id, _)| !content.contains(node_id)).flat_map(|(_, node)| &node.inputs);
+		let used = upstream_nodes(&network, rest.chain(&network.exports));
+		network.nodes.retain(|node_id, _| !content.contains(node_id) || used.contains(node_id));
+		network
+	}
+
+	/// Brings the content key of each top level layer, and the structure key of the network, up to date with the document's network at the animation time.
+	fn update_layer_content_keys(&mut self, document: &DocumentMessageHandler, animation_time: Duration) {
+		let network = document.network_interface.document_network();
+		let network_hash = network.current_hash();
+		if self.layer_content_keys.network_hash == network_hash && self.layer_content_keys.animation_time == animation_time {
+			return;
+		}
+
+		let top_level_layers = top_level_layers(document).into_iter().map(LayerNodeIdentifier::to_node).collect::<HashSet<_>>();
+		self.layer_content_keys = LayerContentKeys {
+			network_hash,
+			animation_time,
+			keys: top_level_layers.iter().map(|&layer| (layer, layer_content_key(network, layer, animation_time))).collect(),
+			structure_key: network_structure_key(network, &top_level_layers),
+		};
+	}
+
+	/// Decides which top level layers to leave out of the network for a render of the given region (in document space), which are those last seen entirely outside of it.
+	/// Selected layers, those that changed since they were last evaluated, and the one containing the inspected node are always evaluated, as is everything while the node graph (which shows each layer's thumbnail) is open.
+	fn update_deferred_layers(&mut self, document: &DocumentMessageHandler, region: [DVec2; 2], inspect_node: Option<NodeId>) {
+		let network = document.network_interface.document_network();
+		let metadata = document.metadata();
+		let editing = document
+			.network_interface
+			.selected_nodes()
+			.selected_layers(metadata)
+			.flat_map(|layer| layer.ancestors(metadata))
+			.collect::<HashSet<_>>();
+
+		let mut layer_evaluation = LayerEvaluation::default();
+		for layer in top_level_layers(document) {
+			let node_id = layer.to_node();
+			let Some(&content_key) = self.layer_content_keys.keys.get(&node_id) else { continue };
+
+			let offscreen = document.layer_bounds.get(&node_id).is_some_and(|&[min, max]| min.cmpgt(region[1]).any() || max.cmplt(region[0]).any());
+			// Layers which haven't been evaluated yet have no bounds, so they aren't offscreen
+			let unchanged = self.evaluated_layer_contents.get(&node_id) == Some(&content_key);
+			let inspected = || {
+				let content = |node: &DocumentNode| upstream_nodes(network, node.inputs.iter().skip(1));
+				inspect_node.is_some_and(|inspect_node| network.nodes.get(&node_id).is_some_and(|node| content(node).contains(&inspect_node)))
+			};
+			if offscreen && unchanged && !document.graph_view_overlay_open && !editing.contains(&layer) && !inspected() {
+				layer_evaluation.deferred.insert(node_id);
+			} else {
+				layer_evaluation.evaluated.insert(node_id, content_key);
+			}
+		}
+		self.layer_evaluation = layer_evaluation;
+	}
+
+	/// Carries over the metadata of the layers a render left out from the document's previous render, since their artwork is unchanged but missing from the new one.
+	fn restore_deferred_metadata(deferred: &HashSet<NodeId>, render_metadata: &mut RenderMetadata, vector_modify: &mut HashMap<NodeId, VectorData>, document: &DocumentMessageHandler) {
+		let previous = document.metadata();
+		for &node_id in deferred {
+			let layer = LayerNodeIdentifier::new_unchecked(node_id);
+			let (Some(footprint), Some(previous_footprint)) = (render_metadata.upstream_footprints.get(&node_id), previous.upstream_footprints.get(&node_id)) else {
+				continue;
+			};
+			// Top level layers are placed by the render's footprint alone, so how that changed since the previous render also moves what's inside them
+			let change = footprint.transform * previous_footprint.transform.inverse();
+
+			for node_id in std::iter::once(node_id).chain(layer.descendants(previous).map(LayerNodeIdentifier::to_node)) {
+				if node_id != layer.to_node() {
+					if let Some(&footprint) = previous.upstream_footprints.get(&node_id) {
+						let transform = change * footprint.transform;
+						render_metadata.upstream_footprints.insert(node_id, Footprint { transform, ..footprint });
+					}
+				}
+				if let Some(&local_transform) = previous.local_transforms.get(&node_id) {
+					render_metadata.local_transforms.insert(node_id, local_transform);
+				}
+				if let Some(click_targets) = previous.click_targets.get(&LayerNodeIdentifier::new_unchecked(node_id)) {
+					render_metadata.click_targets.insert(node_id, click_targets.clone());
+				}
+				if previous.clip_targets.contains(&node_id) {
+					render_metadata.clip_targets.insert(node_id);
+				}
+				if let Some(vector_data) = previous.vector_modify.get(&node_id) {
+					vector_modify.insert(node_id, vector_data.clone());
+				}
+			}
+		}
+	}
+
+	/// Defers rendering the thumbnails of layers hidden inside collapsed layers in the Layers panel, unless the node graph (which shows them too) is open, and those of the layers left out of the network.
+	/// Returns whether any thumbnails are no longer deferred.
+	fn update_deferred_thumbnails(&mut self, document: &DocumentMessageHandler) -> Result<bool, String> {
+		let mut deferred_thumbnails = if document.graph_view_overlay_open {
+			HashSet::new()
+		} else {
+			document.collapsed.hidden_layers(document.metadata()).into_iter().map(LayerNodeIdentifier::to_node).collect()
+		};
+		deferred_thumbnails.extend(&self.layer_evaluation.deferred);
+		if deferred_thumbnails == self.deferred_thumbnails {
+			return Ok(false);
+		}
+
+		let undeferred = self.deferred_thumbnails.iter().any(|layer| !deferred_thumbnails.contains(layer));
+		self.deferred_thumbnails.clone_from(&deferred_thumbnails);
+
+		self.sender.send(NodeRuntimeMessage::DeferredThumbnailsUpdate(deferred_thumbnails)).map_err(|e| e.to_string())?;
+		Ok(undeferred)
+	}
+
+	/// Adds an evaluate request for whatever current network is cached, rendering every tile covering the viewport.
+	pub(crate) fn submit_current_node_graph_evaluation(&mut self, document: &mut DocumentMessageHandler, viewport_resolution: UVec2, time: TimingInformation, interacting: bool) -> Result<(), String> {
+		let tiles = ViewportTiles::covering(document.metadata().document_to_viewport, viewport_resolution);
+		self.viewport_tiles.reset(None);
+		self.submit_viewport_render(document, tiles, time, interacting)
+	}
+
+	/// Adds an evaluate request rendering the given tiles of the viewport.
+	/// When `interacting` is set, the render starts with a low resolution preview that isn't refined to full quality until the user stops dragging or zooming.
+	fn submit_viewport_render(&mut self, document: &mut DocumentMessageHandler, tiles: ViewportTiles, time: TimingInformation, interacting: bool) -> Result<(), String> {
+		let document_to_viewport = document.metadata().document_to_viewport;
+		let pending_since = self.viewport_tiles.pending_since.unwrap_or(time.time);
+
+		let mut render_config = RenderConfig {
+			viewport: tiles.footprint(document_to_viewport),
 			time,
 			#[cfg(any(feature = "resvg", feature = "vello"))]
 			export_format: graphene_core::application_io::ExportFormat::Canvas,
 			#[cfg(not(any(feature = "resvg", feature = "vello")))]
 			export_format: graphene_core::application_io::ExportFormat::Svg,
 			view_mode: document.view_mode,
-			hide_artboards: false,
+			hide_artboards: self.hide_artboard_backgrounds,
 			for_export: false,
+			embed_fonts: false,
+			color_management: document.color_management(),
 		};
 
+		// While the renders are lagging behind the user's edits or the user is interacting, render a fast low resolution preview first and refine it to full quality once that arrives
+		let lagging = time.time - pending_since > PROGRESSIVE_RENDER_PREVIEW_DELAY;
+		let refinement = (lagging || interacting).then(|| {
+			let refinement = render_config;
+			render_config.viewport.quality = RenderQuality::Preview;
+			refinement
+		});
+
 		// Execute the node graph
 		let execution_id = self.queue_execution(render_config);
 
-		self.futures.insert(execution_id, ExecutionContext { export_config: None });
+		let cache = &mut self.viewport_tiles;
+		cache.execution_id = Some(execution_id);
+		cache.rendering = Some(tiles);
+		cache.pending_since = Some(pending_since);
+		cache.refinement = refinement;
+		cache.refine_when_idle = interacting;
+		cache.idle_refinement = None;
+		self.futures.insert(
+			execution_id,
+			ExecutionContext {
+				export_config: None,
+				viewport_tiles: Some(ViewportTileExecution { tiles, generation: cache.generation }),
+				split_view: None,
+				onion_skin: None,
+				layer_evaluation: Some(self.layer_evaluation.clone()),
+			},
+		);
 		Ok(())
 	}
 
+	fn viewport_render_key(&self, document: &DocumentMessageHandler, time: TimingInformation, inspect_node: Option<NodeId>) -> ViewportRenderKey {
+		ViewportRenderKey {
+			structure_key: self.layer_content_keys.structure_key,
+			inspect_node,
+			zoom_and_rotation: document.metadata().document_to_viewport.matrix2,
+			animation_time: time.animation_time,
+			view_mode: document.view_mode,
+			hide_artboard_backgrounds: self.hide_artboard_backgrounds,
+		}
+	}
+
+	/// Finds the tiles covering the viewport which have to be rendered, returning the range of tiles to render them in, if there are any.
+	///
+	/// Those are the tiles which are missing, along with those drawn over by a top level layer that changed since they were rendered, as found from the layer's bounds when it was last rendered.
+	/// Since a layer's new bounds aren't known until it's rendered again, any tiles it's since moved over are found once that render arrives.
+	fn viewport_tiles_to_render(&mut self, document: &DocumentMessageHandler, covering: ViewportTiles, key: ViewportRenderKey) -> Option<ViewportTiles> {
+		let document_to_viewport = document.metadata().document_to_viewport;
+		let in_progress = self.viewport_render_pending() || self.viewport_tiles.idle_refinement.is_some();
+		let layer_contents = &self.layer_content_keys.keys;
+		let cache = &mut self.viewport_tiles;
+
+		// Anything else changing may affect every tile
+		if cache.key.as_ref() != Some(&key) {
+			cache.reset(Some(key));
+		}
+		cache.tiles.retain(|&tile, _| covering.contains(tile));
+		cache.dirty.retain(|&tile| covering.contains(tile));
+		let drawn = cache.tiles.values().copied().collect::<HashSet<_>>();
+		cache.renders.retain(|execution_id, _| drawn.contains(execution_id));
+
+		let changed = layer_contents
+			.iter()
+			.filter(|&(node_id, content_key)| cache.layer_contents.get(node_id) != Some(content_key))
+			.map(|(&node_id, _)| node_id)
+			.collect::<Vec<_>>();
+		let removed = cache.layer_contents.keys().filter(|node_id| !layer_contents.contains_key(node_id));
+		for (node_id, present) in changed.iter().map(|node_id| (node_id, true)).chain(removed.map(|node_id| (node_id, false))) {
+			let drawn_over = document
+				.layer_bounds
+				.get(node_id)
+				.map(|&bounds| ViewportTiles::drawn_over(bounds, document_to_viewport).intersection(covering));
+			match drawn_over {
+				Some(drawn_over) if !present || drawn_over.iter().next().is_some() => cache.dirty.extend(drawn_over.iter()),
+				// There's no telling where a layer that was never rendered was drawn, nor where one that wasn't drawn over the viewport has been moved to within it
+				_ => cache.dirty.extend(covering.iter()),
+			}
+		}
+		cache.layer_contents.clone_from(layer_contents);
+
+		let rendering = cache.rendering.filter(|_| in_progress);
+		let missing = covering
+			.iter()
+			.filter(|&tile| !cache.tiles.contains_key(&tile) && !rendering.is_some_and(|rendering| rendering.contains(tile)));
+		let mut to_render = cache.dirty.iter().copied().chain(missing).collect::<HashSet<_>>();
+		if to_render.is_empty() {
+			return None;
+		}
+
+		// The render in progress is superseded by the new one, so its tiles are rendered again along with it
+		to_render.extend(cache.rendering.iter().flat_map(|rendering| rendering.intersection(covering).iter()));
+		let tiles = if cache.shared_surface { covering } else { ViewportTiles::bounding(to_render)? };
+		cache.dirty.retain(|&tile| !tiles.contains(tile));
+		cache.changed_layers.extend(changed);
+		Some(tiles)
+	}
+
+	/// The artwork of the viewport's tiles, each drawn from the render it's kept from and offset to where the tiles currently sit in the viewport.
+	fn viewport_artwork(&self, document_to_viewport: DAffine2) -> String {
+		let cache = &self.viewport_tiles;
+		let mut svg = String::from("<svg>");
+		for (&execution_id, render) in &cache.renders {
+			let offset = render.tiles.viewport_offset(document_to_viewport);
+			// The tiles extend beyond the viewport, so the artwork mustn't be clipped to the viewport's size before it's offset
+			let artwork = render.artwork.replacen("<svg", r#"<svg overflow="visible""#, 1);
+
+			// The artwork of each render reaches beyond its tiles, so it's clipped to those it's drawn for when other renders are drawn alongside it
+			let mut clip_path = String::new();
+			if cache.renders.len() > 1 {
+				let mut path = String::new();
+				for (tile, _) in cache.tiles.iter().filter(|&(_, &tile_execution_id)| tile_execution_id == execution_id) {
+					let corner = (*tile - render.tiles.min).as_dvec2() * VIEWPORT_RENDER_TILE_SIZE;
+					let _ = write!(path, "M{} {}h{size}v{size}h-{size}Z", corner.x, corner.y, size = VIEWPORT_RENDER_TILE_SIZE);
+				}
+				let _ = write!(svg, r#"<clipPath id="viewport-tiles-{execution_id}"><path d="{path}" /></clipPath>"#);
+				clip_path = format!(r#" clip-path="url(#viewport-tiles-{execution_id})""#);
+			}
+			let _ = write!(svg, r#"<g transform="translate({} {})"{clip_path}>{artwork}</g>"#, offset.x, offset.y);
+		}
+		svg.push_str("</svg>");
+		svg
+	}
+
+	/// Sends the artwork of the viewport's tiles, along with the metadata of the latest render offset to where its tiles currently sit in the viewport.
+	fn send_viewport_render(&self, document_to_viewport: DAffine2, responses: &mut VecDeque<Message>) {
+		let cache = &self.viewport_tiles;
+		let Some((metadata, metadata_tiles)) = &cache.metadata else { return };
+
+		// Until a render of the tiles arrives after they were emptied, the artwork from before is left in place
+		if !cache.renders.is_empty() {
+			responses.add(FrontendMessage::UpdateDocumentArtwork {
+				svg: self.viewport_artwork(document_to_viewport),
+			});
+		}
+
+		let mut metadata = metadata.clone();
+		let offset = metadata_tiles.viewport_offset(document_to_viewport);
+		for footprint in metadata.upstream_footprints.values_mut() {
+			footprint.transform = DAffine2::from_translation(offset) * footprint.transform;
+		}
+		responses.add(Message::EndBuffer(metadata));
+		responses.add(DocumentMessage::RenderScrollbars);
+		responses.add(DocumentMessage::RenderRulers);
+		responses.add(DocumentMessage::RenderTransformBar);
+		responses.add(OverlaysMessage::Draw);
+		// The onion skins follow the viewport, and may have been waiting for this render to finish before substituting the network
+		responses.add(TimelineMessage::RenderOnionSkins);
+	}
+
 	/// Evaluates a node graph, computing the entire graph
 	pub fn submit_node_graph_evaluation(
 		&mut self,
@@ -605,26 +1364,287 @@ impl NodeGraphExecutor {
 		time: TimingInformation,
 		inspect_node: Option<NodeId>,
 		ignore_hash: bool,
+		interacting: bool,
+		responses: &mut VecDeque<Message>,
 	) -> Result<(), String> {
+		let document_to_viewport = document.metadata().document_to_viewport;
+		let covering = ViewportTiles::covering(document_to_viewport, viewport_resolution);
+		self.update_layer_content_keys(document, time.animation_time);
+		if ignore_hash {
+			self.viewport_tiles.key = None;
+		}
+
+		// Only the tiles which are missing or out of date are rendered, with the layers outside of them left out of the network
+		// With nothing to render, the layers are left out as they were for the previous render, which keeps the network from being compiled again
+		let key = self.viewport_render_key(document, time, inspect_node);
+		let tiles = self.viewport_tiles_to_render(document, covering, key);
+		if let Some(tiles) = tiles {
+			self.update_deferred_layers(document, tiles.region(document_to_viewport), inspect_node);
+		}
+
+		// The keyframed inputs are set on a copy of the network sent to the runtime, leaving the document's own values as they are
+		self.keyframe_time = time.animation_time.as_secs_f64();
 		self.update_node_graph(document, inspect_node, ignore_hash)?;
-		self.submit_current_node_graph_evaluation(document, viewport_resolution, time)?;
+		let thumbnails_undeferred = self.update_deferred_thumbnails(document)?;
+
+		let tiles = match tiles {
+			Some(tiles) => tiles,
+			// Thumbnails which are no longer deferred, since the layers hiding them in the Layers panel were expanded, need an execution to be rendered even if none of the tiles do
+			None if thumbnails_undeferred => {
+				self.update_deferred_layers(document, covering.region(document_to_viewport), inspect_node);
+				self.update_node_graph(document, inspect_node, false)?;
+				self.update_deferred_thumbnails(document)?;
+				covering
+			}
+			// The tiles which are already rendered are repositioned for the latest panning, or are positioned once the render in progress arrives
+			None => {
+				self.send_viewport_render(document_to_viewport, responses);
+				return Ok(());
+			}
+		};
+		self.submit_viewport_render(document, tiles, time, interacting)
+	}
+
+	/// Whether a render of the active document's viewport is still in progress.
+	pub fn viewport_render_pending(&self) -> bool {
+		self.viewport_tiles.execution_id.is_some_and(|execution_id| self.futures.contains_key(&execution_id))
+	}
+
+	/// Evaluates a node graph to render the split view, which may show a document other than the active one.
+	pub fn submit_split_view_render(
+		&mut self,
+		document: &mut DocumentMessageHandler,
+		document_to_viewport: DAffine2,
+		viewport_resolution: UVec2,
+		time: TimingInformation,
+		other_document: bool,
+	) -> Result<(), String> {
+		// The split view shows another region of the document than the viewport, so it can't use the network with the layers outside the viewport left out
+		let substituted = other_document || !self.layer_evaluation.deferred.is_empty();
+		if substituted {
+			// The runtime holds a single network, so the active document's network is sent again once this render arrives
+			let network = document
+				.evaluated_network(time.animation_time.as_secs_f64())
+				.unwrap_or_else(|| document.network_interface.document_network().clone());
+			self.sender
+				.send(NodeRuntimeMessage::GraphUpdate(GraphUpdate { network, inspect_node: None }))
+				.map_err(|e| e.to_string())?;
+			self.node_graph_hash = 0;
+			self.pending_substitute_compilations += 1;
+		} else {
+			self.update_node_graph(document, self.old_inspect_node, false)?;
+		}
+
+		let render_config = RenderConfig {
+			viewport: Footprint {
+				transform: document_to_viewport,
+				resolution: viewport_resolution,
+				..Default::default()
+			},
+			time,
+			export_format: graphene_core::application_io::ExportFormat::Svg,
+			view_mode: document.view_mode,
+			hide_artboards: false,
+			// Rendering it like an export keeps it from superseding, or being superseded by, the renders of the viewport
+			for_export: true,
+			embed_fonts: false,
+			color_management: document.color_management(),
+		};
+
+		let execution_id = self.queue_execution(render_config);
+		self.futures.insert(
+			execution_id,
+			ExecutionContext {
+				export_config: None,
+				viewport_tiles: None,
+				split_view: Some(SplitViewExecution {
+					document_to_viewport,
+					other_document,
+					substituted,
+				}),
+				onion_skin: None,
+				layer_evaluation: None,
+			},
+		);
+		Ok(())
+	}
+
+	/// Evaluates the active document's node graph at another time, to be ghosted beneath the viewport's artwork as an onion skin.
+	pub fn submit_onion_skin_render(&mut self, document: &mut DocumentMessageHandler, viewport_resolution: UVec2, time: TimingInformation) -> Result<(), String> {
+		// Keyframes are applied to the network itself rather than evaluated by it, so the network is substituted by one with the keyframed inputs set for that time
+		// The layers left out of the viewport's network may have been elsewhere at that time, so they have to be included as well
+		let evaluated_network = document.evaluated_network(time.animation_time.as_secs_f64());
+		let substituted = evaluated_network.is_some() || !self.layer_evaluation.deferred.is_empty();
+		if substituted {
+			let network = evaluated_network.unwrap_or_else(|| document.network_interface.document_network().clone());
+			self.sender
+				.send(NodeRuntimeMessage::GraphUpdate(GraphUpdate { network, inspect_node: None }))
+				.map_err(|e| e.to_string())?;
+			self.node_graph_hash = 0;
+			self.pending_substitute_compilations += 1;
+		} else {
+			self.update_node_graph(document, self.old_inspect_node, false)?;
+		}
+
+		let render_config = RenderConfig {
+			viewport: Footprint {
+				transform: document.metadata().document_to_viewport,
+				resolution: viewport_resolution,
+				..Default::default()
+			},
+			time,
+			export_format: graphene_core::application_io::ExportFormat::Svg,
+			view_mode: document.view_mode,
+			hide_artboards: true,
+			// Rendering it like an export keeps it from superseding, or being superseded by, the renders of the viewport
+			for_export: true,
+			embed_fonts: false,
+			color_management: document.color_management(),
+		};
 
+		let execution_id = self.queue_execution(render_config);
+		self.futures.insert(
+			execution_id,
+			ExecutionContext {
+				export_config: None,
+				viewport_tiles: None,
+				split_view: None,
+				onion_skin: Some(OnionSkinExecution { substituted }),
+				layer_evaluation: None,
+			},
+		);
 		Ok(())
 	}
 
 	/// Evaluates a node graph for export
-	pub fn submit_document_export(&mut self, document: &mut DocumentMessageHandler, mut export_config: ExportConfig) -> Result<(), String> {
-		let network = document.network_interface.document_network().clone();
+	pub fn submit_document_export(&mut self, document: &mut DocumentMessageHandler, export_config: ExportConfig) -> Result<(), String> {
+		let timing_information = TimingInformation::default();
+		let network = document
+			.evaluated_network(timing_information.animation_time.as_secs_f64())
+			.unwrap_or_else(|| document.network_interface.document_network().clone());
+		self.submit_export(document, network, export_config, timing_information)
+	}
+
+	/// Evaluates a node graph for export at each frame in a range of its animation, saving each to its own numbered file.
+	pub fn submit_frame_sequence_export(&mut self, document: &mut DocumentMessageHandler, export_config: ExportConfig, frame_sequence: &FrameSequenceExport) -> Result<(), String> {
+		if frame_sequence.first_frame > frame_sequence.last_frame {
+			return Err("The first frame comes after the last frame".to_string());
+		}
+
+		for frame in frame_sequence.first_frame..=frame_sequence.last_frame {
+			let time = frame_sequence.frame_time(frame);
+			// Keyframes are applied to the network itself rather than evaluated by it, so each frame is rendered from a network with the keyframed inputs set for its time
+			let network = document.evaluated_network(time).unwrap_or_else(|| document.network_interface.document_network().clone());
+			let timing_information = TimingInformation {
+				time,
+				animation_time: Duration::from_secs_f64(time),
+			};
+			let export_config = ExportConfig {
+				file_name: frame_sequence.file_name(frame),
+				..export_config.clone()
+			};
+			self.submit_export(document, network, export_config, timing_information)?;
+		}
+
+		// The runtime is left with the last frame's network, so the active document's network is sent again before the next render
+		self.node_graph_hash = 0;
+		Ok(())
+	}
+
+	/// Evaluates variants of a document's node graph for export, each saved to its own file, such as one for each record of a data merge.
+	pub fn submit_variant_exports(&mut self, document: &mut DocumentMessageHandler, export_config: ExportConfig, variants: Vec<(String, NodeNetwork)>) -> Result<(), String> {
+		for (file_name, network) in variants {
+			let export_config = ExportConfig { file_name, ..export_config.clone() };
+			self.submit_export(document, network, export_config, Default::default())?;
+		}
+
+		// The runtime is left with the last variant's network, so the active document's network is sent again before the next render
+		self.node_graph_hash = 0;
+		Ok(())
+	}
+
+	/// Evaluates a node graph with only the selected layers shown, to be placed on the clipboard as SVG and PNG alongside their serialization for pasting into other applications.
+	pub fn submit_clipboard_render(&mut self, document: &mut DocumentMessageHandler, copied_layers: String) -> Result<(), String> {
+		let network = Self::selected_layers_network(document);
+		let export_config = ExportConfig {
+			file_type: FileType::Svg,
+			scale_factor: 1.,
+			bounds: ExportBounds::Selection,
+			transparent_background: true,
+			copied_layers: Some(copied_layers),
+			..Default::default()
+		};
+		self.submit_export(document, network, export_config, Default::default())?;
+
+		// The runtime is left with the network of only the selected layers, so the active document's network is sent again before the next render
+		self.node_graph_hash = 0;
+		Ok(())
+	}
+
+	/// Renders the selected layers on their own for the frontend to rasterize, so `count` swatches can be extracted from the colors of their artwork.
+	pub fn submit_swatch_extraction_render(&mut self, document: &mut DocumentMessageHandler, count: u32) -> Result<(), String> {
+		let network = Self::selected_layers_network(document);
+		let export_config = ExportConfig {
+			file_type: FileType::Svg,
+			scale_factor: 1.,
+			bounds: ExportBounds::Selection,
+			transparent_background: true,
+			swatch_count: Some(count),
+			..Default::default()
+		};
+		self.submit_export(document, network, export_config, Default::default())?;
+
+		// The runtime is left with the network of only the selected layers, so the active document's network is sent again before the next render
+		self.node_graph_hash = 0;
+		Ok(())
+	}
+
+	/// The document's network with every layer hidden except the selected ones, along with the groups containing them and the layers inside them.
+	fn selected_layers_network(document: &DocumentMessageHandler) -> NodeNetwork {
+		let metadata = document.metadata();
+		let shown = document
+			.network_interface
+			.selected_nodes()
+			.selected_layers(metadata)
+			.flat_map(|layer| layer.ancestors(metadata).chain(layer.descendants(metadata)))
+			.collect::<HashSet<_>>();
+
+		let mut network = document.network_interface.document_network().clone();
+		for layer in metadata.all_layers().filter(|layer| !shown.contains(layer)) {
+			if let Some(node) = network.nodes.get_mut(&layer.to_node()) {
+				node.visible = false;
+			}
+		}
+		network
+	}
+
+	fn submit_export(&mut self, document: &mut DocumentMessageHandler, mut network: NodeNetwork, mut export_config: ExportConfig, time: TimingInformation) -> Result<(), String> {
+		// Reference images are only there to be traced over, so they're hidden from the exported artwork
+		let reference_images = document
+			.metadata()
+			.all_layers()
+			.filter(|&layer| graph_modification_utils::is_reference_image(layer, &document.network_interface))
+			.collect::<HashSet<_>>();
+		for layer in &reference_images {
+			if let Some(node) = network.nodes.get_mut(&layer.to_node()) {
+				node.visible = false;
+			}
+		}
 
 		// Calculate the bounding box of the region to be exported
 		let bounds = match export_config.bounds {
-			ExportBounds::AllArtwork => document.network_interface.document_bounds_document_space(!export_config.transparent_background),
+			ExportBounds::AllArtwork => {
+				let artwork = document.metadata().all_layers().filter(|layer| !reference_images.contains(layer));
+				document.network_interface.layers_bounds_document_space(artwork, !export_config.transparent_background)
+			}
 			ExportBounds::Selection => document.network_interface.selected_bounds_document_space(!export_config.transparent_background, &[]),
 			ExportBounds::Artboard(id) => document.metadata().bounding_box_document(id),
+			ExportBounds::CustomRegion => document.export_region,
 		}
 		.ok_or_else(|| "No bounding box".to_string())?;
 		let size = bounds[1] - bounds[0];
 		let transform = DAffine2::from_translation(bounds[0]).inverse();
+		export_config.color_profile = document.color_profile;
 
 		let render_config = RenderConfig {
 			viewport: Footprint {
@@ -632,25 +1652,41 @@ impl NodeGraphExecutor {
 				resolution: (size * export_config.scale_factor).as_uvec2(),
 				..Default::default()
 			},
-			time: Default::default(),
+			time,
 			export_format: graphene_core::application_io::ExportFormat::Svg,
 			view_mode: document.view_mode,
 			hide_artboards: export_config.transparent_background,
 			for_export: true,
+			embed_fonts: export_config.file_type == FileType::Svg && export_config.text_export == TextExport::EmbeddedFont,
+			color_management: Default::default(),
 		};
 		export_config.size = size;
+		export_config.spot_colors = document.swatches.iter().filter_map(Swatch::spot_color).collect();
 
 		// Execute the node graph
 		self.sender
 			.send(NodeRuntimeMessage::GraphUpdate(GraphUpdate { network, inspect_node: None }))
 			.map_err(|e| e.to_string())?;
 		let execution_id = self.queue_execution(render_config);
-		let execution_context = ExecutionContext { export_config: Some(export_config) };
+		let execution_context = ExecutionContext {
+			export_config: Some(export_config),
+			viewport_tiles: None,
+			split_view: None,
+			onion_skin: None,
+			layer_evaluation: None,
+		};
 		self.futures.insert(execution_id, execution_context);
 
 		Ok(())
 	}
 
+	/// Lets the frontend know that the file of an export won't be downloaded after all.
+	fn report_failed_export(export_id: Option<u64>, responses: &mut VecDeque<Message>) {
+		if let Some(export_id) = export_id {
+			responses.add(FrontendMessage::TriggerExportFailed { export_id });
+		}
+	}
+
 	fn export(&self, node_graph_output: TaggedValue, export_config: ExportConfig, responses: &mut VecDeque<Message>) -> Result<(), String> {
 		let TaggedValue::RenderOutput(RenderOutput {
 			data: graphene_std::wasm_application_io::RenderOutputType::Svg(svg),
@@ -665,9 +1701,30 @@ impl NodeGraphExecutor {
 			file_name,
 			size,
 			scale_factor,
+			metadata,
+			tiff_compression,
+			color_profile,
+			copied_layers,
+			swatch_count,
+			spot_colors,
+			export_id,
 			..
 		} = export_config;
 
+		if let Some(copied_layers) = copied_layers {
+			responses.add(FrontendMessage::TriggerClipboardWrite {
+				copied_layers,
+				svg,
+				size: size.into(),
+			});
+			return Ok(());
+		}
+
+		if let Some(count) = swatch_count {
+			responses.add(FrontendMessage::TriggerExtractSwatches { svg, size: size.into(), count });
+			return Ok(());
+		}
+
 		let file_suffix = &format!(".{file_type:?}").to_lowercase();
 		let name = match file_name.ends_with(FILE_SAVE_SUFFIX) {
 			true => file_name.replace(FILE_SAVE_SUFFIX, file_suffix),
@@ -675,15 +1732,60 @@ impl NodeGraphExecutor {
 		};
 
 		if file_type == FileType::Svg {
-			responses.add(FrontendMessage::TriggerDownloadTextFile { document: svg, name });
+			responses.add(FrontendMessage::TriggerDownloadTextFile { document: svg, name, export_id });
+		} else if !file_type.is_raster() {
+			let data = match file_type {
+				FileType::Eps => graphene_std::print::svg_to_eps(&svg, &spot_colors)?,
+				_ => graphene_std::print::svg_to_pdf(&svg, &spot_colors)?,
+			};
+			let mime = file_type.to_mime().to_string();
+			responses.add(FrontendMessage::TriggerDownloadBinaryFile { data, name, mime, export_id });
 		} else {
 			let mime = file_type.to_mime().to_string();
 			let size = (size * scale_factor).into();
-			responses.add(FrontendMessage::TriggerDownloadImage { svg, name, mime, size });
+			responses.add(FrontendMessage::TriggerDownloadImage {
+				svg,
+				name,
+				mime,
+				size,
+				metadata,
+				tiff_compression,
+				icc_profile: color_profile.icc_profile(),
+				export_id,
+			});
 		}
 		Ok(())
 	}
 
+	/// Starts the full quality render of the viewport which was held back while the user was interacting, once they've stopped.
+	pub fn refine_viewport_render_when_idle(&mut self, interacting: bool) {
+		if interacting {
+			return;
+		}
+		let Some(tiles) = self.viewport_tiles.rendering else { return };
+		let Some(render_config) = self.viewport_tiles.idle_refinement.take() else { return };
+
+		self.queue_refinement(render_config, tiles);
+	}
+
+	fn queue_refinement(&mut self, render_config: RenderConfig, tiles: ViewportTiles) {
+		let execution_id = self.queue_execution(render_config);
+		self.viewport_tiles.execution_id = Some(execution_id);
+		self.futures.insert(
+			execution_id,
+			ExecutionContext {
+				export_config: None,
+				viewport_tiles: Some(ViewportTileExecution {
+					tiles,
+					generation: self.viewport_tiles.generation,
+				}),
+				split_view: None,
+				onion_skin: None,
+				layer_evaluation: Some(self.layer_evaluation.clone()),
+			},
+		);
+	}
+
 	pub fn poll_node_graph_evaluation(&mut self, document: &mut DocumentMessageHandler, responses: &mut VecDeque<Message>) -> Result<(), String> {
 		let results = self.receiver.try_iter().collect::<Vec<_>>();
 		for response in results {
@@ -696,29 +1798,93 @@ impl NodeGraphExecutor {
 						transform,
 						vector_modify,
 						inspect_result,
+						evaluation_milliseconds,
+						evaluation_statistics,
 					} = execution_response;
 
+					// Renders of the split view are kept apart from the active document
+					if let Some(split_view) = self.futures.get(&execution_id).and_then(|execution_context| execution_context.split_view) {
+						self.futures.remove(&execution_id);
+						if split_view.substituted {
+							// Restore the active document's network unless it's been sent again since
+							self.update_node_graph(document, self.old_inspect_node, false)?;
+						}
+						if !split_view.other_document {
+							responses.extend(existing_responses.into_iter().map(Into::into));
+							document.network_interface.update_vector_modify(vector_modify);
+						}
+
+						match result.and_then(|output| Self::render_output_svg(output, transform)) {
+							Ok((svg, _)) => responses.add(SplitViewMessage::Rendered {
+								svg,
+								document_to_viewport: split_view.document_to_viewport,
+							}),
+							Err(e) => warn!("Split view render failed: {e}"),
+						}
+						continue;
+					}
+
+					// Onion skins show the artwork at other times, so nothing else about their renders applies to the document
+					if let Some(onion_skin) = self.futures.get(&execution_id).and_then(|execution_context| execution_context.onion_skin) {
+						self.futures.remove(&execution_id);
+						if onion_skin.substituted {
+							// Restore the active document's network unless it's been sent again since
+							self.update_node_graph(document, self.old_inspect_node, false)?;
+						}
+
+						let svg = result.and_then(|output| Self::render_output_svg(output, transform)).map(|(svg, _)| svg).unwrap_or_else(|e| {
+							warn!("Onion skin render failed: {e}");
+							String::new()
+						});
+						responses.add(TimelineMessage::OnionSkinRendered { svg });
+						continue;
+					}
+
 					responses.add(OverlaysMessage::Draw);
 
-					let node_graph_output = match result {
+					let mut node_graph_output = match result {
 						Ok(output) => output,
 						Err(e) => {
+							// An export which couldn't be rendered is never downloaded
+							let export_id = self.futures.get(&execution_id).and_then(|execution_context| execution_context.export_config.as_ref()?.export_id);
+							if export_id.is_some() {
+								self.futures.remove(&execution_id);
+								Self::report_failed_export(export_id, responses);
+							}
+
 							// Clear the click targets while the graph is in an un-renderable state
 							document.network_interface.update_click_targets(HashMap::new());
 							document.network_interface.update_vector_modify(HashMap::new());
+							self.viewport_tiles = Default::default();
 							return Err(format!("Node graph evaluation failed:\n{e}"));
 						}
 					};
 
 					responses.extend(existing_responses.into_iter().map(Into::into));
+
+					let execution_context = self.futures.remove(&execution_id);
+					let mut vector_modify = vector_modify;
+					if let Some(layer_evaluation) = execution_context.as_ref().and_then(|execution_context| execution_context.layer_evaluation.as_ref()) {
+						if let TaggedValue::RenderOutput(render_output) = &mut node_graph_output {
+							Self::restore_deferred_metadata(&layer_evaluation.deferred, &mut render_output.metadata, &mut vector_modify, document);
+						}
+						// The bounds of the layers this render evaluated are brought up to date along with the rest of its metadata
+						self.evaluated_layer_contents.extend(&layer_evaluation.evaluated);
+					}
 					document.network_interface.update_vector_modify(vector_modify);
 
-					let execution_context = self.futures.remove(&execution_id).ok_or_else(|| "Invalid generation ID".to_string())?;
+					let execution_context = execution_context.ok_or_else(|| "Invalid generation ID".to_string())?;
 					if let Some(export_config) = execution_context.export_config {
 						// Special handling for exporting the artwork
-						self.export(node_graph_output, export_config, responses)?
+						let export_id = export_config.export_id;
+						if let Err(error) = self.export(node_graph_output, export_config, responses) {
+							Self::report_failed_export(export_id, responses);
+							return Err(error);
+						}
 					} else {
-						self.process_node_graph_output(node_graph_output, transform, responses)?
+						self.render_statistics.record_render(evaluation_milliseconds, evaluation_statistics);
+						let document_to_viewport = document.metadata().document_to_viewport;
+						self.process_node_graph_output(node_graph_output, transform, execution_id, execution_context.viewport_tiles, document_to_viewport, responses)?
 					}
 
 					// Update the spreadsheet on the frontend using the value of the inspect result.
@@ -728,7 +1894,16 @@ impl NodeGraphExecutor {
 						}
 					}
 				}
+				NodeGraphUpdate::ExecutionCanceled(execution_id) => {
+					let export_id = self.futures.remove(&execution_id).and_then(|execution_context| execution_context.export_config?.export_id);
+					Self::report_failed_export(export_id, responses);
+				}
 				NodeGraphUpdate::CompilationResponse(execution_response) => {
+					if self.pending_substitute_compilations > 0 {
+						self.pending_substitute_compilations -= 1;
+						continue;
+					}
+
 					let CompilationResponse { node_graph_errors, result } = execution_response;
 					let type_delta = match result {
 						Err(e) => {
@@ -764,7 +1939,7 @@ impl NodeGraphExecutor {
 		Ok(())
 	}
 
-	fn debug_render(render_object: impl GraphicElementRendered, transform: DAffine2, responses: &mut VecDeque<Message>) {
+	fn debug_render(render_object: impl GraphicElementRendered, transform: DAffine2) -> String {
 		// Setup rendering
 		let mut render = SvgRender::new();
 		let render_params = RenderParams::new(ViewMode::Normal, None, false, false, false);
@@ -774,54 +1949,110 @@ impl NodeGraphExecutor {
 
 		// Concatenate the defs and the SVG into one string
 		render.wrap_with_transform(transform, None);
-		let svg = render.svg.to_svg_string();
-
-		// Send to frontend
-		responses.add(FrontendMessage::UpdateDocumentArtwork { svg });
+		render.svg.to_svg_string()
 	}
 
-	fn process_node_graph_output(&mut self, node_graph_output: TaggedValue, transform: DAffine2, responses: &mut VecDeque<Message>) -> Result<(), String> {
+	/// Turns the output of the node graph into the SVG displaying it, along with the metadata of the render.
+	fn render_output_svg(node_graph_output: TaggedValue, transform: DAffine2) -> Result<(String, RenderMetadata), String> {
 		let mut render_output_metadata = RenderMetadata::default();
-		match node_graph_output {
+		let svg = match node_graph_output {
 			TaggedValue::RenderOutput(render_output) => {
-				match render_output.data {
-					graphene_std::wasm_application_io::RenderOutputType::Svg(svg) => {
-						// Send to frontend
-						responses.add(FrontendMessage::UpdateDocumentArtwork { svg });
-					}
+				let svg = match render_output.data {
+					graphene_std::wasm_application_io::RenderOutputType::Svg(svg) => svg,
 					graphene_std::wasm_application_io::RenderOutputType::CanvasFrame(frame) => {
 						let matrix = format_transform_matrix(frame.transform);
 						let transform = if matrix.is_empty() { String::new() } else { format!(" transform=\"{}\"", matrix) };
-						let svg = format!(
+						format!(
 							r#"<svg><foreignObject width="{}" height="{}"{transform}><div data-canvas-placeholder="canvas{}"></div></foreignObject></svg>"#,
 							frame.resolution.x, frame.resolution.y, frame.surface_id.0
-						);
-						responses.add(FrontendMessage::UpdateDocumentArtwork { svg });
+						)
 					}
 					_ => {
 						return Err(format!("Invalid node graph output type: {:#?}", render_output.data));
 					}
-				}
+				};
 
 				render_output_metadata = render_output.metadata;
+				svg
 			}
-			TaggedValue::Bool(render_object) => Self::debug_render(render_object, transform, responses),
-			TaggedValue::String(render_object) => Self::debug_render(render_object, transform, responses),
-			TaggedValue::F64(render_object) => Self::debug_render(render_object, transform, responses),
-			TaggedValue::DVec2(render_object) => Self::debug_render(render_object, transform, responses),
-			TaggedValue::OptionalColor(render_object) => Self::debug_render(render_object, transform, responses),
-			TaggedValue::VectorData(render_object) => Self::debug_render(render_object, transform, responses),
-			TaggedValue::GraphicGroup(render_object) => Self::debug_render(render_object, transform, responses),
-			TaggedValue::ImageFrame(render_object) => Self::debug_render(render_object, transform, responses),
-			TaggedValue::Palette(render_object) => Self::debug_render(render_object, transform, responses),
+			TaggedValue::Bool(render_object) => Self::debug_render(render_object, transform),
+			TaggedValue::String(render_object) => Self::debug_render(render_object, transform),
+			TaggedValue::F64(render_object) => Self::debug_render(render_object, transform),
+			TaggedValue::DVec2(render_object) => Self::debug_render(render_object, transform),
+			TaggedValue::OptionalColor(render_object) => Self::debug_render(render_object, transform),
+			TaggedValue::VectorData(render_object) => Self::debug_render(render_object, transform),
+			TaggedValue::GraphicGroup(render_object) => Self::debug_render(render_object, transform),
+			TaggedValue::ImageFrame(render_object) => Self::debug_render(render_object, transform),
+			TaggedValue::Palette(render_object) => Self::debug_render(render_object, transform),
 			_ => {
 				return Err(format!("Invalid node graph output type: {node_graph_output:#?}"));
 			}
 		};
-		responses.add(Message::EndBuffer(render_output_metadata));
-		responses.add(DocumentMessage::RenderScrollbars);
-		responses.add(DocumentMessage::RenderRulers);
-		responses.add(OverlaysMessage::Draw);
+
+		Ok((svg, render_output_metadata))
+	}
+
+	fn process_node_graph_output(
+		&mut self,
+		node_graph_output: TaggedValue,
+		transform: DAffine2,
+		execution_id: u64,
+		viewport_tiles: Option<ViewportTileExecution>,
+		document_to_viewport: DAffine2,
+		responses: &mut VecDeque<Message>,
+	) -> Result<(), String> {
+		let shared_surface = matches!(
+			&node_graph_output,
+			TaggedValue::RenderOutput(RenderOutput {
+				data: graphene_std::wasm_application_io::RenderOutputType::CanvasFrame(_),
+				..
+			})
+		);
+		let (svg, render_output_metadata) = Self::render_output_svg(node_graph_output, transform)?;
+
+		let Some(ViewportTileExecution { tiles, generation }) = viewport_tiles else {
+			responses.add(FrontendMessage::UpdateDocumentArtwork { svg });
+			responses.add(Message::EndBuffer(render_output_metadata));
+			responses.add(DocumentMessage::RenderScrollbars);
+			responses.add(DocumentMessage::RenderRulers);
+			responses.add(DocumentMessage::RenderTransformBar);
+			responses.add(OverlaysMessage::Draw);
+			return Ok(());
+		};
+
+		// Keep the render for its tiles so it can be reused while panning, and while editing layers drawn over other tiles, unless it was requested before the renders were last emptied
+		let cache = &mut self.viewport_tiles;
+		if generation == cache.generation {
+			cache.insert_render(execution_id, tiles, svg, shared_surface);
+		}
+
+		if cache.execution_id == Some(execution_id) {
+			cache.mark_moved_layers(&render_output_metadata, tiles);
+
+			match cache.refinement.take() {
+				// Refining the preview while the user is still interacting would only have it superseded by the next preview, so it waits until they stop
+				Some(render_config) if cache.refine_when_idle => {
+					cache.idle_refinement = Some(render_config);
+					cache.pending_since = None;
+				}
+				// Follow the preview that just arrived with the full quality render of the same tiles
+				Some(render_config) => self.queue_refinement(render_config, tiles),
+				None => {
+					cache.pending_since = None;
+					cache.rendering = None;
+					// The tiles which the changed layers have moved over since are rendered next
+					if !cache.dirty.is_empty() {
+						responses.add(PortfolioMessage::SubmitActiveGraphRender);
+					}
+					// The split view may show the same document, or may have been waiting for this render to finish before rendering another one
+					responses.add(SplitViewMessage::Render);
+				}
+			}
+		}
+
+		self.viewport_tiles.metadata = Some((render_output_metadata, tiles));
+		self.send_viewport_render(document_to_viewport, responses);
+
 		Ok(())
 	}
 }
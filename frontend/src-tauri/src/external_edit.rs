@@ -0,0 +1,119 @@
+//! Editing image layers in other applications, like a dedicated raster editor, which saves the pixels back into the document.
+//!
+//! The layer's image is written as a PNG file to a temporary folder and opened in the chosen application. The file is then watched for changes,
+//! and each time the application saves it, its contents are sent to the webview as an `external-edit` event to replace the layer's pixels.
+//! The file stops being watched once the webview calls `stop_editing_image_externally`, when the layer is deleted or its document is closed.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Manager};
+use tokio::process::Command;
+
+/// How often the files being edited are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The latest watcher started for each file being edited, by the ID of its layer, so opening a layer again stops the watcher started before.
+/// A layer's ID is removed once it's deleted or its document is closed, which stops its watcher altogether.
+#[derive(Default, Clone)]
+pub struct ExternalEdits(Arc<Mutex<HashMap<String, u64>>>);
+
+/// Sent to the webview with the contents of a file each time it's saved.
+#[derive(Debug, Clone, serde::Serialize)]
+struct ExternalEditEvent {
+	id: String,
+	data: Vec<u8>,
+}
+
+/// Called by the webview with an image layer's PNG file, which is opened in the given application, or the system's default one if it's empty, and watched for changes.
+#[tauri::command]
+pub async fn edit_image_externally(id: String, name: String, data: Vec<u8>, editor: String, app: AppHandle, external_edits: tauri::State<'_, ExternalEdits>) -> Result<(), String> {
+	let folder = std::env::temp_dir().join("graphite-external-edits");
+	tokio::fs::create_dir_all(&folder)
+		.await
+		.map_err(|error| format!("The folder {} couldn't be created: {error}", folder.display()))?;
+
+	// The ID keeps layers with the same name from sharing a file
+	let name: String = name.chars().map(|character| if r#"/\:*?"<>|"#.contains(character) { '-' } else { character }).collect();
+	let path = folder.join(format!("{} {id}.png", name.trim()));
+	tokio::fs::write(&path, data).await.map_err(|error| format!("{} couldn't be written: {error}", path.display()))?;
+	let modified = tokio::fs::metadata(&path).await.and_then(|metadata| metadata.modified()).ok();
+
+	open(&path, editor.trim())?;
+
+	let generation = {
+		let mut watchers = external_edits.0.lock().unwrap();
+		let generation = watchers.entry(id.clone()).or_default();
+		*generation += 1;
+		*generation
+	};
+	tauri::async_runtime::spawn(watch(app, external_edits.inner().clone(), id, generation, path, modified));
+
+	Ok(())
+}
+
+/// Called by the webview once an image layer being edited has been deleted or its document closed, to stop watching its file.
+#[tauri::command]
+pub fn stop_editing_image_externally(id: String, external_edits: tauri::State<'_, ExternalEdits>) {
+	external_edits.0.lock().unwrap().remove(&id);
+}
+
+/// Opens a file in an application, given by its path or, on macOS, its name.
+fn open(path: &Path, editor: &str) -> Result<(), String> {
+	let mut command = match (editor.is_empty(), cfg!(target_os = "windows"), cfg!(target_os = "macos")) {
+		(false, _, true) => {
+			let mut command = Command::new("open");
+			command.arg("-a").arg(editor);
+			command
+		}
+		(false, _, _) => Command::new(editor),
+		(true, true, _) => {
+			let mut command = Command::new("cmd");
+			command.args(["/C", "start", ""]);
+			command
+		}
+		(true, _, true) => Command::new("open"),
+		(true, _, _) => Command::new("xdg-open"),
+	};
+
+	let application = if editor.is_empty() { "the default application" } else { editor };
+	command.arg(path).spawn().map_err(|error| format!("{} couldn't be opened in {application}: {error}", path.display()))?;
+	Ok(())
+}
+
+/// Sends the file to the webview each time it's saved, until the layer is opened again, deleted, or its document is closed.
+async fn watch(app: AppHandle, external_edits: ExternalEdits, id: String, generation: u64, path: PathBuf, mut modified: Option<SystemTime>) {
+	let mut changed = false;
+
+	loop {
+		tokio::time::sleep(POLL_INTERVAL).await;
+		if external_edits.0.lock().unwrap().get(&id) != Some(&generation) {
+			return;
+		}
+
+		// Applications which save by replacing the file leave it missing for a moment
+		let Ok(metadata) = tokio::fs::metadata(&path).await else { continue };
+		let current = metadata.modified().ok();
+		if current != modified {
+			modified = current;
+			changed = true;
+			continue;
+		}
+
+		// Applications may write a file in several steps, so it's only read once it's stopped changing
+		if !changed {
+			continue;
+		}
+		changed = false;
+
+		let Ok(data) = tokio::fs::read(&path).await else { continue };
+		if data.is_empty() {
+			continue;
+		}
+		let Some(window) = app.get_window("main") else { return };
+		if let Err(error) = window.emit("external-edit", ExternalEditEvent { id: id.clone(), data }) {
+			log::error!("The edits to {} couldn't be sent to the editor: {error}", path.display());
+		}
+	}
+}
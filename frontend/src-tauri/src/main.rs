@@ -3,12 +3,14 @@
 use graphite_editor::application::Editor;
 use graphite_editor::messages::prelude::*;
 
+mod external_edit;
+mod remote_control;
+mod save_actions;
+
 // use axum::body::StreamBody;
 // use axum::extract::Path;
 // use axum::http;
 // use axum::response::IntoResponse;
-use axum::routing::get;
-use axum::Router;
 use fern::colors::{Color, ColoredLevelConfig};
 // use http::{Response, StatusCode};
 use std::cell::RefCell;
@@ -57,19 +59,26 @@ async fn main() {
 	// *(IMAGES.lock().unwrap()) = Some(HashMap::new());
 	graphite_editor::application::set_uuid_seed(0);
 	EDITOR.with(|editor| editor.borrow_mut().replace(Editor::new()));
-	let app = Router::new().route("/", get(|| async { "Hello, World!" }))/*.route("/image/:id", get(respond_to))*/;
 
-	// run it with hyper on localhost:3000
-	tauri::async_runtime::spawn(async {
-		let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
-		axum::serve(listener, app).await.unwrap();
-	});
+	let pending_exports = remote_control::PendingExports::default();
 
 	tauri::Builder::default()
-		.invoke_handler(tauri::generate_handler![set_random_seed, handle_message])
+		.manage(pending_exports.clone())
+		.manage(external_edit::ExternalEdits::default())
+		.invoke_handler(tauri::generate_handler![
+			set_random_seed,
+			handle_message,
+			remote_control::remote_control_export,
+			remote_control::remote_control_failed,
+			save_actions::save_action_write_file,
+			save_actions::save_action_run_command,
+			external_edit::edit_image_externally,
+			external_edit::stop_editing_image_externally
+		])
 		.setup(|_app| {
 			use tauri::Manager;
 			_app.get_window("main").unwrap().open_devtools();
+			remote_control::start(_app.handle(), pending_exports);
 			Ok(())
 		})
 		.run(tauri::generate_context!())
@@ -0,0 +1,521 @@
+//! An optional server on the local machine through which other programs, like scripts and test harnesses, can drive the editor.
+//!
+//! It's only started when the `GRAPHITE_REMOTE_CONTROL_PORT` environment variable gives the port to listen on, and it only accepts connections from this machine.
+//! The editor itself runs in the webview, so each request is passed on to it as a `remote-control` event and the webview answers exports with the commands below.
+//!
+//! Since the messages can do anything the editor can, every request must present the random token printed when the server starts, either as an
+//! `Authorization: Bearer <token>` header or a `?token=<token>` query parameter. Requests carrying an `Origin` header are refused, so web pages open in a browser
+//! on the same machine can't reach the server even if they learn the token.
+//!
+//! HTTP requests:
+//! - `POST /dispatch` takes a JSON list of editor messages, in the same form the Console panel and plugins use, such as
+//!   `[{ "Portfolio": { "Document": "DeleteSelectedLayers" } }]`. It responds with `202 Accepted` once they're handed to the editor, without waiting for them to be processed.
+//!   The messages follow the editor's own types, so unlike the rest of this API they aren't kept stable between versions of Graphite.
+//! - `POST /export` takes an [`ExportRequest`] and responds with the exported file of the active document, or an error if it couldn't be made in time.
+//!
+//! A WebSocket can also be opened at `/websocket` on the same port, to keep one connection open for many requests. Each request is a text message holding a JSON object
+//! with an `"id"` of the client's choosing, and either `"dispatch"` with a list of messages or `"export"` with an [`ExportRequest`]. Requests are handled concurrently,
+//! and each one is answered with a text message holding the same `"id"` and either `"ok": true` for dispatches, the exported file's `"name"`, `"mime"` type,
+//! and base64-encoded `"data"` for exports, or an `"error"` describing why the request failed.
+
+use axum::extract::ws::{Message as SocketMessage, WebSocket, WebSocketUpgrade};
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use base64::Engine;
+use futures::{SinkExt, StreamExt};
+use graphite_editor::messages::frontend::utility_types::{ExportBounds, ExportMetadata, FileType, TextExport, TiffCompression};
+use graphite_editor::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
+use graphite_editor::messages::prelude::*;
+use serde_json::json;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::sync::{mpsc, oneshot};
+
+/// The environment variable holding the port to listen on, which enables the server.
+pub const PORT_VARIABLE: &str = "GRAPHITE_REMOTE_CONTROL_PORT";
+
+/// How long an export may take before the request fails.
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The path which WebSocket connections are opened at.
+const WEBSOCKET_PATH: &str = "/websocket";
+
+/// The longest message which is accepted through a WebSocket.
+const MAX_MESSAGE_LENGTH: usize = 64 << 20;
+
+/// The body of a `POST /export` request, or the `"export"` of a WebSocket request, where every field is optional.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ExportRequest {
+	pub file_type: ExportFileType,
+	pub scale_factor: f64,
+	pub bounds: ExportArea,
+	pub transparent_background: bool,
+}
+
+impl Default for ExportRequest {
+	fn default() -> Self {
+		Self {
+			file_type: ExportFileType::Svg,
+			scale_factor: 1.,
+			bounds: ExportArea::AllArtwork,
+			transparent_background: false,
+		}
+	}
+}
+
+/// The format of an exported file, given as `"svg"`, `"png"`, `"jpg"`, `"tiff"`, `"pdf"`, or `"eps"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFileType {
+	Svg,
+	Png,
+	Jpg,
+	Tiff,
+	Pdf,
+	Eps,
+}
+
+impl From<ExportFileType> for FileType {
+	fn from(file_type: ExportFileType) -> Self {
+		match file_type {
+			ExportFileType::Svg => FileType::Svg,
+			ExportFileType::Png => FileType::Png,
+			ExportFileType::Jpg => FileType::Jpg,
+			ExportFileType::Tiff => FileType::Tiff,
+			ExportFileType::Pdf => FileType::Pdf,
+			ExportFileType::Eps => FileType::Eps,
+		}
+	}
+}
+
+/// Which part of the document is exported, given as `"allArtwork"`, `"selection"`, `"customRegion"`, or `{ "artboard": <node ID of the artboard> }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportArea {
+	AllArtwork,
+	Selection,
+	CustomRegion,
+	Artboard(u64),
+}
+
+impl ExportArea {
+	fn to_bounds(self) -> Result<ExportBounds, RequestError> {
+		Ok(match self {
+			ExportArea::AllArtwork => ExportBounds::AllArtwork,
+			ExportArea::Selection => ExportBounds::Selection,
+			ExportArea::CustomRegion => ExportBounds::CustomRegion,
+			ExportArea::Artboard(node_id) => {
+				// Layer identifiers are stored as their node ID plus one, which is how the editor reads them back
+				let layer = node_id
+					.checked_add(1)
+					.and_then(|stored| serde_json::from_value::<LayerNodeIdentifier>(stored.into()).ok())
+					.ok_or_else(|| RequestError::Invalid(format!("{node_id} isn't the node ID of an artboard")))?;
+				ExportBounds::Artboard(layer)
+			}
+		})
+	}
+}
+
+impl ExportRequest {
+	/// The message making the export, whose file is given back with the ID.
+	fn to_message(&self, export_id: u64) -> Result<Message, RequestError> {
+		Ok(PortfolioMessage::SubmitDocumentExport {
+			file_name: "export".into(),
+			file_type: self.file_type.into(),
+			scale_factor: self.scale_factor,
+			bounds: self.bounds.to_bounds()?,
+			transparent_background: self.transparent_background,
+			metadata: ExportMetadata::default(),
+			tiff_compression: TiffCompression::default(),
+			text_export: TextExport::default(),
+			frame_sequence: None,
+			export_id: Some(export_id),
+		}
+		.into())
+	}
+}
+
+/// A request sent through a WebSocket.
+#[derive(Debug, serde::Deserialize)]
+struct SocketRequest {
+	/// Given back in the response, so the client can match them up.
+	#[serde(default)]
+	id: serde_json::Value,
+	#[serde(flatten)]
+	action: SocketAction,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum SocketAction {
+	Dispatch(Vec<Message>),
+	Export(ExportRequest),
+}
+
+/// Sent to the webview for each request.
+#[derive(Debug, Clone, serde::Serialize)]
+struct RemoteControlEvent {
+	/// Set for exports, whose file is sent back with this ID.
+	export: Option<u64>,
+	/// The JSON list of messages for the editor to process.
+	messages: String,
+}
+
+/// The file made by an export, or why it couldn't be made.
+type ExportResult = Result<(String, Vec<u8>), String>;
+
+struct ExportedFile {
+	name: String,
+	mime: &'static str,
+	data: Vec<u8>,
+}
+
+/// Why a request couldn't be handled, which each transport reports in its own way.
+#[derive(Debug, PartialEq)]
+enum RequestError {
+	Forbidden,
+	Unauthorized,
+	Invalid(String),
+	Unavailable(String),
+	Failed(String),
+	TimedOut,
+}
+
+impl std::fmt::Display for RequestError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			RequestError::Invalid(error) | RequestError::Unavailable(error) | RequestError::Failed(error) => write!(f, "{error}"),
+			RequestError::TimedOut => write!(f, "The editor didn't finish the export in time"),
+			RequestError::Forbidden => write!(f, "Requests from web pages aren't accepted"),
+			RequestError::Unauthorized => write!(f, "The request didn't present the token printed when the remote control server started"),
+		}
+	}
+}
+
+impl IntoResponse for RequestError {
+	fn into_response(self) -> Response {
+		let status = match self {
+			RequestError::Forbidden => StatusCode::FORBIDDEN,
+			RequestError::Unauthorized => StatusCode::UNAUTHORIZED,
+			RequestError::Invalid(_) => StatusCode::BAD_REQUEST,
+			RequestError::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+			RequestError::Failed(_) => StatusCode::UNPROCESSABLE_ENTITY,
+			RequestError::TimedOut => StatusCode::GATEWAY_TIMEOUT,
+		};
+		(status, self.to_string()).into_response()
+	}
+}
+
+/// The exports the webview hasn't answered yet, by their ID.
+#[derive(Default, Clone)]
+pub struct PendingExports(Arc<Mutex<HashMap<u64, oneshot::Sender<ExportResult>>>>);
+
+impl PendingExports {
+	fn wait(&self, id: u64) -> oneshot::Receiver<ExportResult> {
+		let (sender, receiver) = oneshot::channel();
+		self.0.lock().unwrap().insert(id, sender);
+		receiver
+	}
+
+	fn forget(&self, id: u64) {
+		self.0.lock().unwrap().remove(&id);
+	}
+
+	fn finish(&self, id: u64, result: ExportResult) {
+		let sender = self.0.lock().unwrap().remove(&id);
+		match sender {
+			// The request may have timed out in the meantime, so there's no one left to receive the result
+			Some(sender) => _ = sender.send(result),
+			None => log::warn!("Received the result of export {id}, which is no longer awaited"),
+		}
+	}
+}
+
+#[derive(Clone)]
+struct RemoteControl {
+	app: AppHandle,
+	pending_exports: PendingExports,
+	next_export_id: Arc<AtomicU64>,
+	/// Presented by every request, so only programs given it by whoever started the editor can drive it.
+	token: Arc<str>,
+}
+
+/// Starts the server if it's been enabled with [`PORT_VARIABLE`].
+pub fn start(app: AppHandle, pending_exports: PendingExports) {
+	let Ok(port) = std::env::var(PORT_VARIABLE) else { return };
+	let Ok(port) = port.parse::<u16>() else {
+		log::error!("{PORT_VARIABLE} is set to \"{port}\", which isn't a port number");
+		return;
+	};
+
+	let state = RemoteControl {
+		app,
+		pending_exports,
+		next_export_id: Arc::new(AtomicU64::new(0)),
+		token: generate_token().into(),
+	};
+	let token = state.token.clone();
+	let router = Router::new()
+		.route("/dispatch", post(dispatch))
+		.route("/export", post(export))
+		.route(WEBSOCKET_PATH, get(websocket))
+		.route_layer(middleware::from_fn_with_state(state.clone(), authorize))
+		.with_state(state);
+
+	tauri::async_runtime::spawn(async move {
+		let listener = match tokio::net::TcpListener::bind(("127.0.0.1", port)).await {
+			Ok(listener) => listener,
+			Err(error) => {
+				log::error!("The remote control server couldn't listen on port {port}: {error}");
+				return;
+			}
+		};
+		let address = SocketAddr::from(([127, 0, 0, 1], port));
+		log::info!("Listening for remote control requests on http://{address} and ws://{address}{WEBSOCKET_PATH}");
+		// Printed rather than only logged, so the program which started the editor can read it from the output
+		println!("Remote control token: {token}");
+
+		if let Err(error) = axum::serve(listener, router).await {
+			log::error!("The remote control server stopped: {error}");
+		}
+	});
+}
+
+/// Makes the token which requests must present, from 128 bits of the randomly keyed hashing the standard library already seeds from the operating system.
+fn generate_token() -> String {
+	let random_state = std::hash::RandomState::new();
+	(0..2_u64).map(|index| format!("{:016x}", random_state.hash_one(index))).collect()
+}
+
+/// Refuses requests made by web pages, and those which don't present the token.
+async fn authorize(State(state): State<RemoteControl>, request: Request, next: Next) -> Response {
+	match check_access(request.headers(), request.uri().query(), &state.token) {
+		Ok(()) => next.run(request).await,
+		Err(error) => error.into_response(),
+	}
+}
+
+fn check_access(headers: &HeaderMap, query: Option<&str>, token: &str) -> Result<(), RequestError> {
+	// Browsers send an origin with every WebSocket and cross-origin request, and scripts can't leave it out, while other programs don't send one at all
+	if headers.contains_key(header::ORIGIN) {
+		return Err(RequestError::Forbidden);
+	}
+
+	let bearer = headers.get(header::AUTHORIZATION).and_then(|value| value.to_str().ok()).and_then(|value| value.strip_prefix("Bearer "));
+	let parameter = query.and_then(|query| query.split('&').find_map(|pair| pair.strip_prefix("token=")));
+	if [bearer, parameter].into_iter().flatten().any(|presented| presented == token) {
+		Ok(())
+	} else {
+		Err(RequestError::Unauthorized)
+	}
+}
+
+async fn websocket(State(state): State<RemoteControl>, upgrade: WebSocketUpgrade) -> Response {
+	upgrade.max_message_size(MAX_MESSAGE_LENGTH).on_upgrade(move |socket| serve_websocket(socket, state))
+}
+
+/// Answers each request sent through a WebSocket until the client closes it.
+async fn serve_websocket(socket: WebSocket, state: RemoteControl) {
+	let (mut writer, mut reader) = socket.split();
+
+	// Responses are written as each request finishes, so one slow export doesn't hold up the others
+	let (response_sender, mut responses) = mpsc::unbounded_channel::<String>();
+	let writing = tokio::spawn(async move {
+		while let Some(response) = responses.recv().await {
+			if writer.send(SocketMessage::Text(response.into())).await.is_err() {
+				break;
+			}
+		}
+	});
+
+	while let Some(message) = reader.next().await {
+		match message {
+			Ok(SocketMessage::Text(request)) => {
+				let (state, response_sender) = (state.clone(), response_sender.clone());
+				tokio::spawn(async move {
+					let response = state.respond(request.as_str()).await;
+					_ = response_sender.send(response);
+				});
+			}
+			Ok(SocketMessage::Binary(_)) => _ = response_sender.send(json!({ "id": null, "error": "Only text messages are accepted" }).to_string()),
+			Ok(SocketMessage::Close(_)) => break,
+			// Pings are answered by axum itself
+			Ok(SocketMessage::Ping(_) | SocketMessage::Pong(_)) => {}
+			Err(error) => {
+				log::warn!("Closed a remote control WebSocket after an error: {error}");
+				writing.abort();
+				return;
+			}
+		}
+	}
+
+	drop(response_sender);
+	_ = writing.await;
+}
+
+async fn dispatch(State(state): State<RemoteControl>, Json(messages): Json<Vec<Message>>) -> Response {
+	match state.dispatch(&messages) {
+		Ok(()) => StatusCode::ACCEPTED.into_response(),
+		Err(error) => error.into_response(),
+	}
+}
+
+async fn export(State(state): State<RemoteControl>, Json(request): Json<ExportRequest>) -> Response {
+	match state.export(&request).await {
+		Ok(ExportedFile { name, mime, data }) => {
+			let disposition = format!("attachment; filename=\"{name}\"");
+			(StatusCode::OK, [(header::CONTENT_TYPE, mime.to_string()), (header::CONTENT_DISPOSITION, disposition)], data).into_response()
+		}
+		Err(error) => error.into_response(),
+	}
+}
+
+impl RemoteControl {
+	fn dispatch(&self, messages: &[Message]) -> Result<(), RequestError> {
+		self.send(None, messages)
+	}
+
+	async fn export(&self, request: &ExportRequest) -> Result<ExportedFile, RequestError> {
+		let id = self.next_export_id.fetch_add(1, Ordering::Relaxed);
+		let message = request.to_message(id)?;
+
+		let receiver = self.pending_exports.wait(id);
+		if let Err(error) = self.send(Some(id), &[message]) {
+			self.pending_exports.forget(id);
+			return Err(error);
+		}
+
+		match tokio::time::timeout(EXPORT_TIMEOUT, receiver).await {
+			Ok(Ok(Ok((name, data)))) => Ok(ExportedFile {
+				name,
+				mime: FileType::from(request.file_type).to_mime(),
+				data,
+			}),
+			Ok(Ok(Err(error))) => Err(RequestError::Failed(error)),
+			Ok(Err(_)) | Err(_) => {
+				self.pending_exports.forget(id);
+				Err(RequestError::TimedOut)
+			}
+		}
+	}
+
+	/// Handles a request sent through a WebSocket, giving back the JSON of its response.
+	async fn respond(&self, request: &str) -> String {
+		let SocketRequest { id, action } = match serde_json::from_str(request) {
+			Ok(request) => request,
+			Err(error) => return json!({ "id": null, "error": format!("The request isn't valid: {error}") }).to_string(),
+		};
+
+		let response = match action {
+			SocketAction::Dispatch(messages) => self.dispatch(&messages).map(|()| json!({ "id": id, "ok": true })),
+			SocketAction::Export(request) => self
+				.export(&request)
+				.await
+				.map(|file| json!({ "id": id, "name": file.name, "mime": file.mime, "data": base64::engine::general_purpose::STANDARD.encode(&file.data) })),
+		};
+		response.unwrap_or_else(|error| json!({ "id": id, "error": error.to_string() })).to_string()
+	}
+
+	fn send(&self, export: Option<u64>, messages: &[Message]) -> Result<(), RequestError> {
+		let unavailable = |error: String| RequestError::Unavailable(format!("The messages couldn't be passed on to the editor: {error}"));
+
+		let messages = serde_json::to_string(messages).map_err(|error| unavailable(error.to_string()))?;
+		let window = self.app.get_window("main").ok_or_else(|| RequestError::Unavailable("The editor's window isn't open".into()))?;
+		window.emit("remote-control", RemoteControlEvent { export, messages }).map_err(|error| unavailable(error.to_string()))
+	}
+}
+
+/// Called by the webview with the file made for an export.
+#[tauri::command]
+pub fn remote_control_export(id: u64, name: String, data: Vec<u8>, pending_exports: tauri::State<PendingExports>) {
+	pending_exports.finish(id, Ok((name, data)));
+}
+
+/// Called by the webview when an export couldn't be made.
+#[tauri::command]
+pub fn remote_control_failed(id: u64, error: String, pending_exports: tauri::State<PendingExports>) {
+	pending_exports.finish(id, Err(error));
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn export_requests_map_onto_the_editors_export() {
+		let request: ExportRequest = serde_json::from_str(r#"{ "fileType": "png", "bounds": { "artboard": 5 }, "scaleFactor": 2 }"#).unwrap();
+		assert_eq!(
+			request,
+			ExportRequest {
+				file_type: ExportFileType::Png,
+				scale_factor: 2.,
+				bounds: ExportArea::Artboard(5),
+				transparent_background: false,
+			}
+		);
+
+		let Ok(Message::Portfolio(PortfolioMessage::SubmitDocumentExport { file_type, bounds, export_id, .. })) = request.to_message(7) else {
+			panic!("The request didn't make an export");
+		};
+		assert_eq!(file_type, FileType::Png);
+		assert_eq!(serde_json::to_value(bounds).unwrap(), json!({ "Artboard": 6 }));
+		assert_eq!(export_id, Some(7));
+
+		let request: ExportRequest = serde_json::from_str("{}").unwrap();
+		assert_eq!(request, ExportRequest::default());
+		assert!(serde_json::from_str::<ExportRequest>(r#"{ "fileType": "Png" }"#).is_err());
+		assert!(matches!(ExportArea::Artboard(u64::MAX).to_bounds(), Err(RequestError::Invalid(_))));
+	}
+
+	#[test]
+	fn socket_requests_name_their_action() {
+		let request: SocketRequest = serde_json::from_str(r#"{ "id": "first", "export": { "bounds": "selection" } }"#).unwrap();
+		assert_eq!(request.id, json!("first"));
+		assert!(matches!(request.action, SocketAction::Export(ExportRequest { bounds: ExportArea::Selection, .. })));
+
+		let request: SocketRequest = serde_json::from_str(r#"{ "id": 2, "dispatch": [] }"#).unwrap();
+		assert!(matches!(request.action, SocketAction::Dispatch(messages) if messages.is_empty()));
+	}
+
+	#[test]
+	fn requests_must_present_the_token_without_an_origin() {
+		let token = generate_token();
+		assert_eq!(token.len(), 32);
+		assert_ne!(token, generate_token());
+
+		let mut headers = HeaderMap::new();
+		assert_eq!(check_access(&headers, None, &token), Err(RequestError::Unauthorized));
+		assert_eq!(check_access(&headers, Some("token=wrong"), &token), Err(RequestError::Unauthorized));
+		assert_eq!(check_access(&headers, Some(&format!("other=1&token={token}")), &token), Ok(()));
+
+		headers.insert(header::AUTHORIZATION, format!("Bearer {token}").parse().unwrap());
+		assert_eq!(check_access(&headers, None, &token), Ok(()));
+
+		headers.insert(header::ORIGIN, "https://example.com".parse().unwrap());
+		assert_eq!(check_access(&headers, Some(&format!("token={token}")), &token), Err(RequestError::Forbidden));
+	}
+
+	#[test]
+	fn exports_finish_by_their_id() {
+		let pending_exports = PendingExports::default();
+		let mut first = pending_exports.wait(0);
+		let mut second = pending_exports.wait(1);
+
+		pending_exports.finish(1, Ok(("second.svg".into(), vec![2])));
+		assert_eq!(second.try_recv().unwrap(), Ok(("second.svg".into(), vec![2])));
+		assert!(first.try_recv().is_err());
+
+		pending_exports.finish(0, Err("Failed".into()));
+		assert_eq!(first.try_recv().unwrap(), Err("Failed".into()));
+	}
+}
@@ -0,0 +1,51 @@
+//! The parts of the save actions, set in the editor's preferences, which need access to the machine: writing exported files to a folder and running shell commands.
+//!
+//! The webview starts each action as soon as a document is saved, so a command doesn't wait for the files of an export action before it to be written.
+
+use std::path::Path;
+use tokio::process::Command;
+
+/// The environment variable holding the name of the saved document, given to the shell commands run by save actions.
+pub const DOCUMENT_NAME_VARIABLE: &str = "GRAPHITE_DOCUMENT_NAME";
+
+/// Called by the webview with a file exported by a save action, which is written to the action's folder, creating it if needed.
+#[tauri::command]
+pub async fn save_action_write_file(folder: String, name: String, data: Vec<u8>) -> Result<(), String> {
+	// Only the file name is used so a document name can't place the file outside the folder
+	let name = Path::new(&name).file_name().ok_or_else(|| format!("\"{name}\" isn't a valid file name"))?;
+	let folder = Path::new(&folder);
+
+	tokio::fs::create_dir_all(folder)
+		.await
+		.map_err(|error| format!("The folder {} couldn't be created: {error}", folder.display()))?;
+	let path = folder.join(name);
+	tokio::fs::write(&path, data).await.map_err(|error| format!("{} couldn't be written: {error}", path.display()))
+}
+
+/// Called by the webview to run a save action's shell command, failing with its error output if it exits unsuccessfully.
+#[tauri::command]
+pub async fn save_action_run_command(command: String, document_name: String) -> Result<(), String> {
+	let mut shell = if cfg!(target_os = "windows") {
+		let mut shell = Command::new("cmd");
+		shell.arg("/C");
+		shell
+	} else {
+		let mut shell = Command::new("sh");
+		shell.arg("-c");
+		shell
+	};
+
+	let output = shell
+		.arg(&command)
+		.env(DOCUMENT_NAME_VARIABLE, document_name)
+		.output()
+		.await
+		.map_err(|error| format!("`{command}` couldn't be run: {error}"))?;
+
+	if output.status.success() {
+		Ok(())
+	} else {
+		let stderr = String::from_utf8_lossy(&output.stderr);
+		Err(format!("`{command}` failed with {}\n\n{}", output.status, stderr.trim()))
+	}
+}
@@ -1,5 +1,9 @@
 import { type Editor } from "@graphite/editor";
-import { TriggerTextCopy } from "@graphite/messages";
+import { TriggerClipboardWrite, TriggerTextCopy } from "@graphite/messages";
+import { rasterizeSVG } from "@graphite/utility-functions/rasterization";
+
+// The attribute of the HTML format's wrapper element holding the serialization of copied layers, encoded as Base64 so it survives being placed in HTML
+const COPIED_LAYERS_ATTRIBUTE = "data-graphite-layers";
 
 export function createClipboardManager(editor: Editor) {
 	// Subscribe to process backend event
@@ -7,4 +11,69 @@ export function createClipboardManager(editor: Editor) {
 		// If the Clipboard API is supported in the browser, copy text to the clipboard
 		navigator.clipboard?.writeText?.(triggerTextCopy.copyText);
 	});
+
+	editor.subscriptions.subscribeJsMessage(TriggerClipboardWrite, async (triggerClipboardWrite) => {
+		// Browsers without support for writing other formats are left with the serialization already copied as text
+		if (!navigator.clipboard?.write || !("ClipboardItem" in window)) return;
+
+		const { copiedLayers, svg, size } = triggerClipboardWrite;
+
+		// Plain text gets the SVG markup, which design tools can paste as vector artwork, while Graphite reads its own serialization back from the HTML
+		const formats: Record<string, Blob> = {
+			"text/plain": new Blob([svg], { type: "text/plain" }),
+			"text/html": new Blob([`<div ${COPIED_LAYERS_ATTRIBUTE}="${encodeBase64(copiedLayers)}">${svg}</div>`], { type: "text/html" }),
+		};
+
+		// Office apps and image editors take the rendering as a PNG image
+		const width = Math.ceil(size.x);
+		const height = Math.ceil(size.y);
+		if (width > 0 && height > 0) {
+			try {
+				formats["image/png"] = await rasterizeSVG(svg, width, height, "image/png");
+			} catch {
+				// The other formats are still copied without the image
+			}
+		}
+
+		try {
+			await navigator.clipboard.write([new ClipboardItem(formats)]);
+		} catch (error) {
+			// eslint-disable-next-line no-console
+			console.warn("The copied layers could only be placed on the clipboard as text:", error);
+		}
+	});
+}
+
+// Reads the serialization of layers copied from Graphite out of the HTML format of the clipboard, if it came from Graphite
+export function copiedLayersFromHtml(html: string): string | undefined {
+	const encoded = html.match(new RegExp(`${COPIED_LAYERS_ATTRIBUTE}="([^"]*)"`))?.[1];
+	if (encoded === undefined) return undefined;
+
+	const text = decodeBase64(encoded);
+	if (!text?.startsWith("graphite/layer: ")) return undefined;
+	return text.substring(16, text.length);
+}
+
+// Whether plain text from the clipboard is the markup of an SVG document, such as one copied from a code editor or another design tool
+export function textIsSvg(text: string): boolean {
+	const trimmed = text.trimStart();
+	return (trimmed.startsWith("<svg") || trimmed.startsWith("<?xml")) && trimmed.includes("</svg>");
+}
+
+function encodeBase64(text: string): string {
+	const bytes = new TextEncoder().encode(text);
+	let binary = "";
+	bytes.forEach((byte) => {
+		binary += String.fromCharCode(byte);
+	});
+	return btoa(binary);
+}
+
+function decodeBase64(encoded: string): string | undefined {
+	try {
+		const binary = atob(encoded);
+		return new TextDecoder().decode(Uint8Array.from(binary, (character) => character.charCodeAt(0)));
+	} catch {
+		return undefined;
+	}
 }
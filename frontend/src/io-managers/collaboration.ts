@@ -0,0 +1,58 @@
+import { type Editor } from "@graphite/editor";
+import { TriggerCollaborationConnect, TriggerCollaborationDisconnect, TriggerCollaborationSend } from "@graphite/messages";
+
+// How often the changes made to the shared document are exchanged with the other collaborators
+const TICK_INTERVAL_MILLISECONDS = 250;
+
+// The collaboration server only has to pass each text message it receives on to every other connection made to the same URL, which names the room
+export function createCollaborationManager(editor: Editor) {
+	let socket: WebSocket | undefined;
+	let tickInterval: ReturnType<typeof setInterval> | undefined;
+
+	function disconnect() {
+		clearInterval(tickInterval);
+		tickInterval = undefined;
+
+		// Closing it ourselves isn't reported to the editor, which has already ended the session
+		if (socket) {
+			socket.onclose = null;
+			socket.close();
+		}
+		socket = undefined;
+	}
+
+	editor.subscriptions.subscribeJsMessage(TriggerCollaborationConnect, (triggerCollaborationConnect) => {
+		disconnect();
+
+		try {
+			socket = new WebSocket(triggerCollaborationConnect.server);
+		} catch (error) {
+			// eslint-disable-next-line no-console
+			console.error("Failed to connect to the collaboration server:", error);
+			editor.handle.collaborationDisconnected();
+			return;
+		}
+
+		socket.onopen = () => {
+			editor.handle.collaborationConnected();
+			tickInterval = setInterval(() => editor.handle.collaborationTick(), TICK_INTERVAL_MILLISECONDS);
+		};
+		socket.onmessage = (event) => {
+			if (typeof event.data === "string") editor.handle.collaborationReceived(event.data);
+		};
+		socket.onclose = () => {
+			clearInterval(tickInterval);
+			tickInterval = undefined;
+			socket = undefined;
+			editor.handle.collaborationDisconnected();
+		};
+	});
+
+	editor.subscriptions.subscribeJsMessage(TriggerCollaborationDisconnect, () => {
+		disconnect();
+	});
+
+	editor.subscriptions.subscribeJsMessage(TriggerCollaborationSend, (triggerCollaborationSend) => {
+		if (socket?.readyState === WebSocket.OPEN) socket.send(triggerCollaborationSend.packet);
+	});
+}
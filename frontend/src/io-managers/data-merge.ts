@@ -0,0 +1,51 @@
+import { type Editor } from "@graphite/editor";
+import { TriggerDataMergeChooseImages, TriggerDataMergeImport, TriggerDataMergeLoadImages } from "@graphite/messages";
+import { upload } from "@graphite/utility-functions/files";
+import { extractPixelData } from "@graphite/utility-functions/rasterization";
+
+export function createDataMergeManager(editor: Editor) {
+	editor.subscriptions.subscribeJsMessage(TriggerDataMergeImport, async () => {
+		const data = await upload(".csv,.json", "text");
+		editor.handle.dataMergeDatasetImported(data.filename, data.content);
+	});
+
+	// Paths are fetched relative to the page, so local files can only be loaded from a URL or by being chosen by the user
+	editor.subscriptions.subscribeJsMessage(TriggerDataMergeLoadImages, async (triggerDataMergeLoadImages) => {
+		await Promise.all(
+			triggerDataMergeLoadImages.paths.map(async (path) => {
+				try {
+					const response = await fetch(path);
+					if (!response.ok) throw new Error(`The server responded with ${response.status} ${response.statusText}`);
+
+					const imageData = await extractPixelData(await response.blob());
+					editor.handle.dataMergeImageLoaded(path, new Uint8Array(imageData.data), imageData.width, imageData.height);
+				} catch {
+					editor.handle.dataMergeImageFailed(path);
+				}
+			}),
+		);
+	});
+
+	editor.subscriptions.subscribeJsMessage(TriggerDataMergeChooseImages, () => {
+		const element = document.createElement("input");
+		element.type = "file";
+		element.accept = "image/*";
+		element.multiple = true;
+
+		element.addEventListener(
+			"change",
+			async () => {
+				const files = Array.from(element.files || []);
+				await Promise.all(
+					files.map(async (file) => {
+						const imageData = await extractPixelData(file);
+						editor.handle.dataMergeImageLoaded(file.name, new Uint8Array(imageData.data), imageData.width, imageData.height);
+					}),
+				);
+			},
+			{ capture: false, once: true },
+		);
+
+		element.click();
+	});
+}
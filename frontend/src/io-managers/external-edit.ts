@@ -0,0 +1,60 @@
+import { listen } from "@tauri-apps/api/event";
+import { invoke } from "@tauri-apps/api/tauri";
+
+import { type Editor } from "@graphite/editor";
+import { TriggerEditImageExternally, TriggerStopEditingImageExternally } from "@graphite/messages";
+import { extractPixelData } from "@graphite/utility-functions/rasterization";
+
+// Sent by the desktop app, described in its `external_edit` module, each time the file of a layer being edited in another application is saved
+type ExternalEditEvent = {
+	id: string;
+	data: number[];
+};
+
+export function createExternalEditManager(editor: Editor) {
+	const desktop = "__TAURI_IPC__" in window;
+
+	// The layers being edited in other applications, by the ID their file is watched under
+	const editedLayers = new Map<string, { documentId: bigint; layer: bigint }>();
+
+	editor.subscriptions.subscribeJsMessage(TriggerEditImageExternally, async (triggerEditImageExternally) => {
+		// Other applications can only be launched, and their files watched, by the desktop app
+		if (!desktop) {
+			editor.handle.externalImageEditFailed("Images can only be edited in other applications from the desktop app.");
+			return;
+		}
+
+		const { documentId, layer, name, data, editor: application } = triggerEditImageExternally;
+		const id = `${documentId}-${layer}`;
+		editedLayers.set(id, { documentId, layer });
+
+		try {
+			await invoke("edit_image_externally", { id, name, data, editor: application });
+		} catch (error) {
+			editor.handle.externalImageEditFailed(String(error));
+		}
+	});
+
+	// Stop watching the file of a layer once it's been deleted or its document closed, where only those opened from the desktop app are being watched
+	editor.subscriptions.subscribeJsMessage(TriggerStopEditingImageExternally, async (triggerStopEditingImageExternally) => {
+		const { documentId, layer } = triggerStopEditingImageExternally;
+		const id = `${documentId}-${layer}`;
+		if (!editedLayers.delete(id)) return;
+
+		await invoke("stop_editing_image_externally", { id });
+	});
+
+	if (!desktop) return;
+
+	listen<ExternalEditEvent>("external-edit", async (event) => {
+		const editedLayer = editedLayers.get(event.payload.id);
+		if (!editedLayer) return;
+
+		try {
+			const imageData = await extractPixelData(new Blob([new Uint8Array(event.payload.data)], { type: "image/png" }));
+			editor.handle.externalImageEdited(editedLayer.documentId, editedLayer.layer, new Uint8Array(imageData.data), imageData.width, imageData.height);
+		} catch {
+			editor.handle.externalImageEditFailed("The edited image couldn't be read. It must be saved as a PNG file.");
+		}
+	});
+}
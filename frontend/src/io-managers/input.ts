@@ -1,12 +1,13 @@
 import { get } from "svelte/store";
 
 import { type Editor } from "@graphite/editor";
+import { copiedLayersFromHtml, textIsSvg } from "@graphite/io-managers/clipboard";
 import { TriggerPaste } from "@graphite/messages";
 import { type DialogState } from "@graphite/state-providers/dialog";
 import { type DocumentState } from "@graphite/state-providers/document";
 import { type FullscreenState } from "@graphite/state-providers/fullscreen";
 import { type PortfolioState } from "@graphite/state-providers/portfolio";
-import { makeKeyboardModifiersBitfield, textInputCleanup, getLocalizedScanCode } from "@graphite/utility-functions/keyboard-entry";
+import { makeKeyboardModifiersBitfield, getLocalizedScanCode } from "@graphite/utility-functions/keyboard-entry";
 import { platformIsMac } from "@graphite/utility-functions/platform";
 import { extractPixelData } from "@graphite/utility-functions/rasterization";
 import { stripIndents } from "@graphite/utility-functions/strip-indents";
@@ -21,6 +22,8 @@ const BUTTON_FORWARD = 4;
 export const PRESS_REPEAT_DELAY_MS = 400;
 export const PRESS_REPEAT_INTERVAL_MS = 72;
 export const PRESS_REPEAT_INTERVAL_RAPID_MS = 10;
+const RADIAL_MENU_HOLD_DELAY_MS = 350;
+const RADIAL_MENU_HOLD_MOVE_TOLERANCE = 4;
 
 type EventName = keyof HTMLElementEventMap | keyof WindowEventHandlersEventMap | "modifyinputfield";
 type EventListenerTarget = {
@@ -33,8 +36,14 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 	app?.focus();
 
 	let viewportPointerInteractionOngoing = false;
-	let textToolInteractiveInputElement = undefined as undefined | HTMLDivElement;
+	const activeTouches = new Map<number, { x: number; y: number }>();
+	let touchGestureOngoing = false;
+	let stylusOrientation = { tiltX: 0, tiltY: 0, twist: 0 };
+	let textToolInteractiveInputElement = undefined as undefined | HTMLTextAreaElement;
 	let canvasFocused = true;
+	let radialMenuHold = undefined as undefined | { timeout: ReturnType<typeof setTimeout>; x: number; y: number };
+	// Browsers report trackpad pinch gestures as scroll wheel events with Ctrl held, so whether it's physically held tells them apart
+	let controlKeyHeld = false;
 
 	// Event listeners
 
@@ -47,6 +56,7 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 		{ target: window, eventName: "pointermove", action: (e: PointerEvent) => onPointerMove(e) },
 		{ target: window, eventName: "pointerdown", action: (e: PointerEvent) => onPointerDown(e) },
 		{ target: window, eventName: "pointerup", action: (e: PointerEvent) => onPointerUp(e) },
+		{ target: window, eventName: "pointercancel", action: (e: PointerEvent) => onPointerUp(e) },
 		{ target: window, eventName: "mousedown", action: (e: MouseEvent) => onMouseDown(e) },
 		{ target: window, eventName: "mouseup", action: (e: MouseEvent) => onPotentialDoubleClick(e) },
 		{ target: window, eventName: "wheel", action: (e: WheelEvent) => onWheelScroll(e), options: { passive: false } },
@@ -79,6 +89,13 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 		// TODO: Switch to a system where everything is sent to the backend, then the input preprocessor makes decisions and kicks some inputs back to the frontend
 		const accelKey = platformIsMac() ? e.metaKey : e.ctrlKey;
 
+		// Redirect the keys which move the caret and delete characters in the text being edited by the text tool, since it draws the caret and selection itself
+		// Keys typed while an input method is composing characters are left to it
+		if (e.target === textToolInteractiveInputElement && !e.isComposing) {
+			const textEditingKeys = ["ArrowLeft", "ArrowRight", "ArrowUp", "ArrowDown", "Home", "End", "Backspace", "Delete"];
+			if (textEditingKeys.includes(key) || (key === "KeyA" && accelKey)) return true;
+		}
+
 		// Don't redirect user input from text entry into HTML elements
 		if (targetIsTextField(e.target || undefined) && key !== "Escape" && !(accelKey && ["Enter", "NumpadEnter"].includes(key))) return false;
 
@@ -111,6 +128,7 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 	}
 
 	async function onKeyDown(e: KeyboardEvent) {
+		controlKeyHeld = e.ctrlKey;
 		const key = await getLocalizedScanCode(e);
 
 		const NO_KEY_REPEAT_MODIFIER_KEYS = ["ControlLeft", "ControlRight", "ShiftLeft", "ShiftRight", "MetaLeft", "MetaRight", "AltLeft", "AltRight", "AltGraph", "CapsLock", "Fn", "FnLock"];
@@ -125,10 +143,12 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 
 		if (get(dialog).visible && key === "Escape") {
 			dialog.dismissDialog();
+			editor.handle.dialogDismissed();
 		}
 	}
 
 	async function onKeyUp(e: KeyboardEvent) {
+		controlKeyHeld = e.ctrlKey;
 		const key = await getLocalizedScanCode(e);
 
 		if (await shouldRedirectKeyboardEventToBackend(e)) {
@@ -138,11 +158,69 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 		}
 	}
 
+	// Touch gestures
+
+	// Returns true if the pointer event is part of a multi-touch gesture and shouldn't also be sent to the backend as mouse input
+	function updateTouches(e: PointerEvent, lifted: boolean): boolean {
+		if (e.pointerType !== "touch") return false;
+		if (!activeTouches.has(e.pointerId) && (lifted || !viewportPointerInteractionOngoing)) return false;
+
+		if (lifted) activeTouches.delete(e.pointerId);
+		else activeTouches.set(e.pointerId, { x: e.clientX, y: e.clientY });
+
+		// Once a second finger joins, the gesture continues until every finger is lifted so the remaining finger doesn't start using the tool
+		if (activeTouches.size >= 2) touchGestureOngoing = true;
+		const wasGesture = touchGestureOngoing;
+		if (activeTouches.size === 0) touchGestureOngoing = false;
+
+		const touchPoints = new Float64Array([...activeTouches.entries()].flatMap(([id, { x, y }]) => [id, x, y]));
+		editor.handle.onTouchPoints(touchPoints);
+
+		return wasGesture;
+	}
+
+	// Stylus orientation
+
+	// Mice and fingers have no orientation, so they reset it to perpendicular and unrotated
+	function updateStylusOrientation(e: PointerEvent) {
+		const isPen = e.pointerType === "pen";
+		const tiltX = isPen ? e.tiltX : 0;
+		const tiltY = isPen ? e.tiltY : 0;
+		const twist = isPen ? e.twist : 0;
+		if (tiltX === stylusOrientation.tiltX && tiltY === stylusOrientation.tiltY && twist === stylusOrientation.twist) return;
+
+		stylusOrientation = { tiltX, tiltY, twist };
+		editor.handle.onStylusOrientation(tiltX, tiltY, twist);
+	}
+
+	// Radial menu
+
+	// Holding down the right mouse button in place over the viewport opens the radial menu
+	function startRadialMenuHold(e: PointerEvent) {
+		cancelRadialMenuHold();
+
+		const timeout = setTimeout(() => {
+			radialMenuHold = undefined;
+			editor.handle.openRadialMenuOnHold();
+		}, RADIAL_MENU_HOLD_DELAY_MS);
+		radialMenuHold = { timeout, x: e.clientX, y: e.clientY };
+	}
+
+	function cancelRadialMenuHold() {
+		if (radialMenuHold) clearTimeout(radialMenuHold.timeout);
+		radialMenuHold = undefined;
+	}
+
 	// Pointer events
 
 	// While any pointer button is already down, additional button down events are not reported, but they are sent as `pointermove` events and these are handled in the backend
 	function onPointerMove(e: PointerEvent) {
 		if (!e.buttons) viewportPointerInteractionOngoing = false;
+		controlKeyHeld = e.ctrlKey;
+
+		if (radialMenuHold && Math.hypot(e.clientX - radialMenuHold.x, e.clientY - radialMenuHold.y) > RADIAL_MENU_HOLD_MOVE_TOLERANCE) cancelRadialMenuHold();
+
+		if (updateTouches(e, false)) return;
 
 		// Don't redirect pointer movement to the backend if there's no ongoing interaction and it's over a floating menu, or the graph overlay, on top of the canvas
 		// TODO: A better approach is to pass along a boolean to the backend's input preprocessor so it can know if it's being occluded by the GUI.
@@ -159,8 +237,19 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 			app?.focus();
 		}
 
+		updateStylusOrientation(e);
+
 		const modifiers = makeKeyboardModifiersBitfield(e);
-		editor.handle.onMouseMove(e.clientX, e.clientY, e.buttons, modifiers);
+		pointerMoveSamples(e).forEach((sample) => editor.handle.onMouseMove(sample.clientX, sample.clientY, e.buttons, modifiers));
+	}
+
+	// A stylus reports its sub-pixel position more often than the display refreshes, and the browser coalesces those samples into a single event
+	// Sending each of them while drawing keeps slow, precise strokes from being reduced to a few points per frame that trace a jagged path
+	function pointerMoveSamples(e: PointerEvent): PointerEvent[] {
+		if (e.pointerType !== "pen" || !e.buttons || !("getCoalescedEvents" in e)) return [e];
+
+		const samples = e.getCoalescedEvents();
+		return samples.length > 0 ? samples : [e];
 	}
 
 	function onMouseDown(e: MouseEvent) {
@@ -173,21 +262,23 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 		const isTargetingCanvas = target instanceof Element && (target.closest("[data-viewport]") || target.closest("[data-node-graph]"));
 		const inDialog = target instanceof Element && target.closest("[data-dialog] [data-floating-menu-content]");
 		const inContextMenu = target instanceof Element && target.closest("[data-context-menu]");
-		const inTextInput = target === textToolInteractiveInputElement;
 
 		if (get(dialog).visible && !inDialog) {
 			dialog.dismissDialog();
+			editor.handle.dialogDismissed();
 			e.preventDefault();
 			e.stopPropagation();
 		}
 
-		if (!inTextInput && !inContextMenu) {
-			const isLeftOrRightClick = e.button === BUTTON_RIGHT || e.button === BUTTON_LEFT;
-			if (textToolInteractiveInputElement) editor.handle.onChangeText(textInputCleanup(textToolInteractiveInputElement.innerText), isLeftOrRightClick);
-			else viewportPointerInteractionOngoing = isTargetingCanvas instanceof Element;
-		}
+		if (!inContextMenu) viewportPointerInteractionOngoing = isTargetingCanvas instanceof Element;
+
+		if (updateTouches(e, false)) return;
 
 		if (viewportPointerInteractionOngoing) {
+			if (e.button === BUTTON_RIGHT && target instanceof Element && target.closest("[data-viewport]")) startRadialMenuHold(e);
+
+			updateStylusOrientation(e);
+
 			const modifiers = makeKeyboardModifiersBitfield(e);
 			editor.handle.onMouseDown(e.clientX, e.clientY, e.buttons, modifiers);
 		}
@@ -202,15 +293,15 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 
 		if (!e.buttons) viewportPointerInteractionOngoing = false;
 
-		if (textToolInteractiveInputElement) return;
+		cancelRadialMenuHold();
+
+		if (updateTouches(e, true)) return;
 
 		const modifiers = makeKeyboardModifiersBitfield(e);
 		editor.handle.onMouseUp(e.clientX, e.clientY, e.buttons, modifiers);
 	}
 
 	function onPotentialDoubleClick(e: MouseEvent) {
-		if (textToolInteractiveInputElement) return;
-
 		// Allow only double-clicks
 		if (e.detail !== 2) return;
 
@@ -243,6 +334,14 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 		if (isTargetingCanvas) {
 			e.preventDefault();
 			const modifiers = makeKeyboardModifiersBitfield(e);
+
+			// A pinch gesture always zooms, without the Ctrl key the browser pretends is held
+			if (e.ctrlKey && !controlKeyHeld) {
+				const CONTROL_MODIFIER = 1 << 2;
+				editor.handle.onTrackpadPinch(e.clientX, e.clientY, e.buttons, e.deltaX, e.deltaY, e.deltaZ, modifiers & ~CONTROL_MODIFIER);
+				return;
+			}
+
 			editor.handle.onWheelScroll(e.clientX, e.clientY, e.buttons, e.deltaX, e.deltaY, e.deltaZ, modifiers);
 		}
 	}
@@ -283,17 +382,28 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 		if (!dataTransfer || targetIsTextField(e.target || undefined)) return;
 		e.preventDefault();
 
-		Array.from(dataTransfer.items).forEach(async (item) => {
-			if (item.type === "text/plain") {
-				item.getAsString((text) => {
-					if (text.startsWith("graphite/layer: ")) {
-						editor.handle.pasteSerializedData(text.substring(16, text.length));
-					} else if (text.startsWith("graphite/nodes: ")) {
-						editor.handle.pasteSerializedNodes(text.substring(16, text.length));
-					}
-				});
-			}
+		// Layers copied from Graphite come with several formats for other applications, of which only the richest is pasted
+		const copiedLayers = copiedLayersFromHtml(dataTransfer.getData("text/html"));
+		if (copiedLayers !== undefined) {
+			editor.handle.pasteSerializedData(copiedLayers);
+			return;
+		}
 
+		const text = dataTransfer.getData("text/plain");
+		if (text.startsWith("graphite/layer: ")) {
+			editor.handle.pasteSerializedData(text.substring(16, text.length));
+			return;
+		}
+		if (text.startsWith("graphite/nodes: ")) {
+			editor.handle.pasteSerializedNodes(text.substring(16, text.length));
+			return;
+		}
+		if (textIsSvg(text)) {
+			editor.handle.pasteSvg(undefined, text);
+			return;
+		}
+
+		Array.from(dataTransfer.items).forEach(async (item) => {
 			const file = item.getAsFile();
 			if (!file) return;
 
@@ -331,22 +441,30 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 			const clipboardItems = await navigator.clipboard.read();
 			if (!clipboardItems) throw new Error("Clipboard API unsupported");
 
-			// Read any layer data or images from the clipboard
+			// Read any layer data or images from the clipboard, preferring the richest format of each item
 			const success = await Promise.any(
 				Array.from(clipboardItems).map(async (item) => {
-					// Read plain text and, if it is a layer, pass it to the editor
+					// Read the serialization of layers copied from Graphite, which is kept in the HTML format alongside the formats for other applications
+					if (item.types.includes("text/html")) {
+						const copiedLayers = copiedLayersFromHtml(await (await item.getType("text/html")).text());
+						if (copiedLayers !== undefined) {
+							editor.handle.pasteSerializedData(copiedLayers);
+							return true;
+						}
+					}
+
+					// Read plain text and, if it is a layer or the markup of an SVG document, pass it to the editor
 					if (item.types.includes("text/plain")) {
-						const blob = await item.getType("text/plain");
-						const reader = new FileReader();
-						reader.onload = () => {
-							const text = reader.result as string;
-
-							if (text.startsWith("graphite/layer: ")) {
-								editor.handle.pasteSerializedData(text.substring(16, text.length));
-							}
-						};
-						reader.readAsText(blob);
-						return true;
+						const text = await (await item.getType("text/plain")).text();
+
+						if (text.startsWith("graphite/layer: ")) {
+							editor.handle.pasteSerializedData(text.substring(16, text.length));
+							return true;
+						}
+						if (textIsSvg(text)) {
+							editor.handle.pasteSvg(undefined, text);
+							return true;
+						}
 					}
 
 					// Read an image from the clipboard and pass it to the editor to be loaded
@@ -354,27 +472,16 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 
 					// Import the actual SVG content if it's an SVG
 					if (imageType?.includes("svg")) {
-						const blob = await item.getType("text/plain");
-						const reader = new FileReader();
-						reader.onload = () => {
-							const text = reader.result as string;
-							editor.handle.pasteSvg(undefined, text);
-						};
-						reader.readAsText(blob);
+						const text = await (await item.getType(imageType)).text();
+						editor.handle.pasteSvg(undefined, text);
 						return true;
 					}
 
 					// Import the bitmap image if it's an image
 					if (imageType) {
 						const blob = await item.getType(imageType);
-						const reader = new FileReader();
-						reader.onload = async () => {
-							if (reader.result instanceof ArrayBuffer) {
-								const imageData = await extractPixelData(new Blob([reader.result], { type: imageType }));
-								editor.handle.pasteImage(undefined, new Uint8Array(imageData.data), imageData.width, imageData.height);
-							}
-						};
-						reader.readAsArrayBuffer(blob);
+						const imageData = await extractPixelData(blob);
+						editor.handle.pasteImage(undefined, new Uint8Array(imageData.data), imageData.width, imageData.height);
 						return true;
 					}
 
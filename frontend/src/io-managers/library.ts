@@ -0,0 +1,57 @@
+import { type Editor } from "@graphite/editor";
+import { TriggerLibraryFetch, TriggerLibraryOpenFile, TriggerLibraryPublish } from "@graphite/messages";
+import { upload } from "@graphite/utility-functions/files";
+
+// How often the shared library is fetched again to find the items which others have changed
+const POLL_INTERVAL_MILLISECONDS = 30_000;
+
+// A library shared by URL only needs a server which returns the file on GET and stores the body of a PUT, like a WebDAV folder or a storage bucket, which also honors ETags for conditional writes
+export function createLibraryManager(editor: Editor) {
+	setInterval(() => editor.handle.libraryPoll(), POLL_INTERVAL_MILLISECONDS);
+
+	editor.subscriptions.subscribeJsMessage(TriggerLibraryFetch, async (triggerLibraryFetch) => {
+		try {
+			const response = await fetch(triggerLibraryFetch.url, { cache: "no-store" });
+
+			// Nothing has been published to the URL yet, so it starts out as an empty library
+			if (response.status === 404) {
+				editor.handle.libraryFetched("", undefined);
+				return;
+			}
+			if (!response.ok) throw new Error(`The server responded with ${response.status} ${response.statusText}`);
+
+			editor.handle.libraryFetched(await response.text(), response.headers.get("ETag") || undefined);
+		} catch (error) {
+			// eslint-disable-next-line no-console
+			console.error("Failed to fetch the library:", error);
+			editor.handle.libraryFailed(`The library couldn't be fetched: ${error}`);
+		}
+	});
+
+	editor.subscriptions.subscribeJsMessage(TriggerLibraryPublish, async (triggerLibraryPublish) => {
+		// Only replace the version of the library which the published one is based on, or create it if there's no version yet, so a newer version published by someone else isn't overwritten
+		const headers: Record<string, string> = { "Content-Type": "application/json" };
+		if (triggerLibraryPublish.etag) headers["If-Match"] = triggerLibraryPublish.etag;
+		else headers["If-None-Match"] = "*";
+
+		try {
+			const response = await fetch(triggerLibraryPublish.url, { method: "PUT", headers, body: triggerLibraryPublish.content });
+			if (response.status === 412) {
+				editor.handle.libraryPublishConflicted();
+				return;
+			}
+			if (!response.ok) throw new Error(`The server responded with ${response.status} ${response.statusText}`);
+
+			editor.handle.libraryPublished(response.headers.get("ETag") || undefined);
+		} catch (error) {
+			// eslint-disable-next-line no-console
+			console.error("Failed to publish the library:", error);
+			editor.handle.libraryPublishFailed(`The library couldn't be published: ${error}`);
+		}
+	});
+
+	editor.subscriptions.subscribeJsMessage(TriggerLibraryOpenFile, async () => {
+		const data = await upload(".graphite-library", "text");
+		editor.handle.libraryFileOpened(data.content);
+	});
+}
@@ -4,6 +4,7 @@ import { get as getFromStore } from "svelte/store";
 import { type Editor } from "@graphite/editor";
 import {
 	TriggerIndexedDbWriteDocument,
+	TriggerIndexedDbWriteDocumentOrder,
 	TriggerIndexedDbRemoveDocument,
 	TriggerSavePreferences,
 	TriggerLoadPreferences,
@@ -164,6 +165,9 @@ export function createPersistenceManager(editor: Editor, portfolio: PortfolioSta
 	editor.subscriptions.subscribeJsMessage(TriggerIndexedDbWriteDocument, async (autoSaveDocument) => {
 		await storeDocument(autoSaveDocument);
 	});
+	editor.subscriptions.subscribeJsMessage(TriggerIndexedDbWriteDocumentOrder, async () => {
+		await storeDocumentOrder();
+	});
 	editor.subscriptions.subscribeJsMessage(TriggerIndexedDbRemoveDocument, async (removeAutoSaveDocument) => {
 		await removeDocument(removeAutoSaveDocument.documentId);
 	});
@@ -0,0 +1,82 @@
+import { type Editor } from "@graphite/editor";
+import { TriggerPluginEvent } from "@graphite/messages";
+
+// Directory the plugins are served from, whose `index.json` lists the names of the subdirectories each holding a plugin's `manifest.json` and `plugin.wasm`
+const PLUGINS_DIRECTORY = "plugins";
+
+// The functions every plugin's WASM module exports, as described in the editor's `plugin_api` module
+type PluginExports = {
+	memory: WebAssembly.Memory;
+	graphite_alloc: (length: number) => number;
+	graphite_handle_event: (pointer: number, length: number) => bigint;
+};
+
+export function createPluginManager(editor: Editor) {
+	const plugins = new Map<string, PluginExports>();
+	const encoder = new TextEncoder();
+	const decoder = new TextDecoder();
+
+	async function loadPlugin(directory: string) {
+		const manifest = await (await fetch(`${PLUGINS_DIRECTORY}/${directory}/manifest.json`)).text();
+		const { instance } = await WebAssembly.instantiateStreaming(fetch(`${PLUGINS_DIRECTORY}/${directory}/plugin.wasm`), {});
+
+		const id: unknown = JSON.parse(manifest).id;
+		if (typeof id === "string") plugins.set(id, instance.exports as unknown as PluginExports);
+
+		// The editor checks the rest of the manifest and tells the user if the plugin can't be used
+		editor.handle.registerPlugin(manifest);
+	}
+
+	async function loadPlugins() {
+		// Having no plugins directory just means no plugins are installed
+		const response = await fetch(`${PLUGINS_DIRECTORY}/index.json`).catch(() => undefined);
+		if (!response?.ok) return;
+
+		const directories: unknown = await response.json().catch(() => []);
+		if (!Array.isArray(directories)) return;
+
+		await Promise.all(
+			directories.map(async (directory) => {
+				try {
+					await loadPlugin(String(directory));
+				} catch (error) {
+					// eslint-disable-next-line no-console
+					console.error(`Failed to load the plugin in "${directory}":`, error);
+				}
+			}),
+		);
+	}
+
+	// Writes the event into the plugin's memory and reads back the responses it returns, both as JSON
+	function handleEvent(exports: PluginExports, event: string): string {
+		const bytes = encoder.encode(event);
+		const pointer = exports.graphite_alloc(bytes.length);
+		new Uint8Array(exports.memory.buffer, pointer, bytes.length).set(bytes);
+
+		const result = exports.graphite_handle_event(pointer, bytes.length);
+		const responsesPointer = Number(result >> 32n);
+		const responsesLength = Number(result & 0xffffffffn);
+
+		// The memory may have grown while the plugin handled the event, so its buffer is looked up again
+		return decoder.decode(new Uint8Array(exports.memory.buffer, responsesPointer, responsesLength));
+	}
+
+	// Subscribe to process backend event
+	editor.subscriptions.subscribeJsMessage(TriggerPluginEvent, (triggerPluginEvent) => {
+		const { plugin, event } = triggerPluginEvent;
+		const exports = plugins.get(plugin);
+		if (!exports) return;
+
+		try {
+			editor.handle.pluginResponses(plugin, handleEvent(exports, event));
+		} catch (error) {
+			// A plugin which traps is removed so it can't leave the editor in a broken state with later events
+			// eslint-disable-next-line no-console
+			console.error(`The plugin "${plugin}" failed and has been unloaded:`, error);
+			plugins.delete(plugin);
+			editor.handle.unregisterPlugin(plugin);
+		}
+	});
+
+	loadPlugins();
+}
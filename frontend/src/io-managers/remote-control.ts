@@ -0,0 +1,57 @@
+import { listen } from "@tauri-apps/api/event";
+import { invoke } from "@tauri-apps/api/tauri";
+import { get } from "svelte/store";
+
+import { type Editor } from "@graphite/editor";
+import { type PortfolioState } from "@graphite/state-providers/portfolio";
+import { interceptDownloads } from "@graphite/utility-functions/files";
+
+// Sent by the desktop app's remote control server, described in its `remote_control` module, for each request it receives from another program
+type RemoteControlEvent = {
+	export: number | null;
+	messages: string;
+};
+
+export function createRemoteControlManager(editor: Editor, portfolio: PortfolioState) {
+	// The server is only part of the desktop app, so there's nothing to listen to in the browser
+	if (!("__TAURI_IPC__" in window)) return;
+
+	// The IDs of the exports waiting for their file, which the server put in the export messages it sent
+	const pendingExports = new Set<bigint>();
+
+	const failExport = (id: bigint, error: string) => invoke("remote_control_failed", { id: Number(id), error });
+
+	// Files made for requested exports are sent back to the server instead of being downloaded
+	interceptDownloads(
+		(filename, blob, exportId) => {
+			if (exportId === undefined || !pendingExports.delete(exportId)) return false;
+
+			blob.arrayBuffer().then((buffer) => invoke("remote_control_export", { id: Number(exportId), name: filename, data: Array.from(new Uint8Array(buffer)) }));
+			return true;
+		},
+		(exportId) => {
+			if (pendingExports.delete(exportId)) failExport(exportId, "The editor couldn't make the export");
+		},
+	);
+
+	listen<RemoteControlEvent>("remote-control", (event) => {
+		const { messages } = event.payload;
+		const exportId = event.payload.export !== null ? BigInt(event.payload.export) : undefined;
+
+		// Exporting needs a document to export
+		if (exportId !== undefined && get(portfolio).documents.length === 0) {
+			failExport(exportId, "No document is open");
+			return;
+		}
+
+		if (exportId !== undefined) pendingExports.add(exportId);
+
+		try {
+			editor.handle.remoteControlMessages(messages);
+		} catch (error) {
+			// eslint-disable-next-line no-console
+			console.error("Failed to handle a remote control request:", error);
+			if (exportId !== undefined && pendingExports.delete(exportId)) failExport(exportId, String(error));
+		}
+	});
+}
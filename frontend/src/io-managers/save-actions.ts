@@ -0,0 +1,64 @@
+import { invoke } from "@tauri-apps/api/tauri";
+
+import { type Editor } from "@graphite/editor";
+import { TriggerSaveActionCommand, TriggerSaveActionExport, TriggerSaveActionWebhook } from "@graphite/messages";
+import { interceptDownloads } from "@graphite/utility-functions/files";
+
+// Runs the actions set in the preferences after each save. These are started as soon as the document is saved, so commands and webhooks don't wait for exports to be written.
+export function createSaveActionsManager(editor: Editor) {
+	const desktop = "__TAURI_IPC__" in window;
+
+	const fail = (error: unknown) => editor.handle.saveActionFailed(String(error));
+
+	// The folders which the files of these exports, by their IDs, are written to instead of being downloaded
+	const pendingExports = new Map<bigint, string>();
+
+	if (desktop) {
+		interceptDownloads(
+			(filename, blob, exportId) => {
+				const folder = exportId !== undefined ? pendingExports.get(exportId) : undefined;
+				if (exportId === undefined || folder === undefined) return false;
+
+				pendingExports.delete(exportId);
+				blob.arrayBuffer()
+					.then((buffer) => invoke("save_action_write_file", { folder, name: filename, data: Array.from(new Uint8Array(buffer)) }))
+					.catch(fail);
+				return true;
+			},
+			(exportId) => pendingExports.delete(exportId),
+		);
+	}
+
+	// Folders can only be written to by the desktop app, so in the browser the files are downloaded like a normal export
+	editor.subscriptions.subscribeJsMessage(TriggerSaveActionExport, (triggerSaveActionExport) => {
+		if (!desktop) return;
+
+		const { folder, exportIds } = triggerSaveActionExport;
+		exportIds.forEach((exportId) => pendingExports.set(exportId, folder));
+	});
+
+	editor.subscriptions.subscribeJsMessage(TriggerSaveActionCommand, async (triggerSaveActionCommand) => {
+		if (!desktop) {
+			// eslint-disable-next-line no-console
+			console.warn("Save actions can only run commands in the desktop app");
+			return;
+		}
+
+		const { command, documentName } = triggerSaveActionCommand;
+		try {
+			await invoke("save_action_run_command", { command, documentName });
+		} catch (error) {
+			fail(error);
+		}
+	});
+
+	editor.subscriptions.subscribeJsMessage(TriggerSaveActionWebhook, async (triggerSaveActionWebhook) => {
+		const { url, body } = triggerSaveActionWebhook;
+		try {
+			const response = await fetch(url, { method: "POST", headers: { "Content-Type": "application/json" }, body });
+			if (!response.ok) throw new Error(`The webhook at ${url} responded with ${response.status} ${response.statusText}`);
+		} catch (error) {
+			fail(error);
+		}
+	});
+}
@@ -366,6 +366,8 @@ export class TriggerIndexedDbWriteDocument extends JsMessage {
 	version!: string;
 }
 
+export class TriggerIndexedDbWriteDocumentOrder extends JsMessage {}
+
 export class TriggerIndexedDbRemoveDocument extends JsMessage {
 	// Use a string since IndexedDB can not use BigInts for keys
 	@Transform(({ value }: { value: bigint }) => value.toString())
@@ -377,6 +379,10 @@ export class UpdateInputHints extends JsMessage {
 	readonly hintData!: HintData;
 }
 
+export class UpdateStickyModifierKeys extends JsMessage {
+	readonly keys!: LayoutKeysGroup;
+}
+
 export type HintData = HintGroup[];
 
 export type HintGroup = HintInfo[];
@@ -409,6 +415,11 @@ export type HSVA = { h: number; s: number; v: number; a: number };
 export type HSV = { h: number; s: number; v: number };
 export type RGBA = { r: number; g: number; b: number; a: number };
 export type RGB = { r: number; g: number; b: number };
+export type HSLA = { h: number; s: number; l: number; a: number };
+export type HSL = { h: number; s: number; l: number };
+// Perceptually uniform color models, with lightness from 0–1, chroma and the a/b axes roughly within ±0.4, and hue in degrees
+export type Oklab = { l: number; a: number; b: number };
+export type Oklch = { l: number; c: number; h: number };
 
 export class Gradient {
 	readonly stops!: { position: number; color: Color }[];
@@ -454,6 +465,14 @@ export class Gradient {
 	}
 }
 
+function srgbToLinear(channel: number): number {
+	return channel <= 0.04045 ? channel / 12.92 : ((channel + 0.055) / 1.055) ** 2.4;
+}
+
+function linearToSrgb(channel: number): number {
+	return channel <= 0.0031308 ? channel * 12.92 : 1.055 * channel ** (1 / 2.4) - 0.055;
+}
+
 // All channels range are represented by 0-1, sRGB, gamma.
 export class Color {
 	readonly red!: number;
@@ -550,6 +569,31 @@ export class Color {
 		return new Color(r / 255, g / 255, b / 255, a / 255);
 	}
 
+	static fromHSLA({ h, s, l, a }: HSLA): Color {
+		const v = l + s * Math.min(l, 1 - l);
+		return new Color({ h, s: v === 0 ? 0 : 2 * (1 - l / v), v, a });
+	}
+
+	// Colors outside the sRGB gamut, which Oklab can describe, are clipped to the nearest channel values within it
+	// https://bottosson.github.io/posts/oklab/
+	static fromOklab({ l, a, b }: Oklab, alpha: number): Color {
+		const [long, medium, short] = [l + 0.3963377774 * a + 0.2158037573 * b, l - 0.1055613458 * a - 0.0638541728 * b, l - 0.0894841775 * a - 1.291485548 * b].map((channel) => channel ** 3);
+
+		const linear = [
+			4.0767416621 * long - 3.3077115913 * medium + 0.2309699292 * short,
+			-1.2684380046 * long + 2.6097574011 * medium - 0.3413193965 * short,
+			-0.0041960863 * long - 0.7034186147 * medium + 1.707614701 * short,
+		];
+
+		const [red, green, blue] = linear.map((channel) => Math.min(linearToSrgb(Math.max(channel, 0)), 1));
+		return new Color(red, green, blue, alpha);
+	}
+
+	static fromOklch({ l, c, h }: Oklch, alpha: number): Color {
+		const radians = (h * Math.PI) / 180;
+		return Color.fromOklab({ l, a: c * Math.cos(radians), b: c * Math.sin(radians) }, alpha);
+	}
+
 	equals(other: Color): boolean {
 		if (this.none && other.none) return true;
 		return Math.abs(this.red - other.red) < 1e-6 && Math.abs(this.green - other.green) < 1e-6 && Math.abs(this.blue - other.blue) < 1e-6 && Math.abs(this.alpha - other.alpha) < 1e-6;
@@ -663,6 +707,41 @@ export class Color {
 		return { h: hsva.h * 360, s: hsva.s * 100, v: hsva.v * 100, a: hsva.a * 100 };
 	}
 
+	toHSLA(): HSLA | undefined {
+		const hsva = this.toHSVA();
+		if (!hsva) return undefined;
+
+		const { h, s, v, a } = hsva;
+		const l = v * (1 - s / 2);
+		return { h, s: l === 0 || l === 1 ? 0 : (v - l) / Math.min(l, 1 - l), l, a };
+	}
+
+	// https://bottosson.github.io/posts/oklab/
+	toOklab(): Oklab | undefined {
+		if (this.none) return undefined;
+
+		const [red, green, blue] = [this.red, this.green, this.blue].map(srgbToLinear);
+
+		const long = Math.cbrt(0.4122214708 * red + 0.5363325363 * green + 0.0514459929 * blue);
+		const medium = Math.cbrt(0.2119034982 * red + 0.6806995451 * green + 0.1073969566 * blue);
+		const short = Math.cbrt(0.0883024619 * red + 0.2817188376 * green + 0.6299787005 * blue);
+
+		return {
+			l: 0.2104542553 * long + 0.793617785 * medium - 0.0040720468 * short,
+			a: 1.9779984951 * long - 2.428592205 * medium + 0.4505937099 * short,
+			b: 0.0259040371 * long + 0.7827717662 * medium - 0.808675766 * short,
+		};
+	}
+
+	toOklch(): Oklch | undefined {
+		const oklab = this.toOklab();
+		if (!oklab) return undefined;
+
+		const { l, a, b } = oklab;
+		const h = (Math.atan2(b, a) * 180) / Math.PI;
+		return { l, c: Math.sqrt(a * a + b * b), h: h < 0 ? h + 360 : h };
+	}
+
 	opaque(): Color | undefined {
 		if (this.none) return undefined;
 
@@ -712,6 +791,10 @@ export class UpdateDocumentArtwork extends JsMessage {
 	readonly svg!: string;
 }
 
+export class UpdateOnionSkinArtwork extends JsMessage {
+	readonly svg!: string;
+}
+
 export class UpdateDocumentScrollbars extends JsMessage {
 	@TupleToVec2
 	readonly position!: XY;
@@ -777,16 +860,187 @@ export class UpdateSpreadsheetState extends JsMessage {
 	readonly node!: bigint | undefined;
 }
 
+export type FloatingPanelType = "Layers" | "Properties" | "Spreadsheet";
+
+export class FloatingPanel {
+	readonly panelType!: FloatingPanelType;
+
+	readonly left!: number;
+
+	readonly top!: number;
+
+	readonly width!: number;
+
+	readonly height!: number;
+}
+
+export class UpdateFloatingPanels extends JsMessage {
+	@Type(() => FloatingPanel)
+	readonly floatingPanels!: FloatingPanel[];
+}
+
+export type GradientPreset = { name: string; gradient: Gradient };
+
+const GradientPresets = Transform(({ value }: { value: { name: string; stops: [number, { red: number; green: number; blue: number; alpha: number }][] }[] }) =>
+	value.map(({ name, stops }) => ({
+		name,
+		gradient: new Gradient(stops.map(([position, color]) => ({ position, color: new Color(color.red, color.green, color.blue, color.alpha) }))),
+	})),
+);
+
+export class UpdateGradientPresets extends JsMessage {
+	@GradientPresets
+	readonly bundled!: GradientPreset[];
+
+	@GradientPresets
+	readonly saved!: GradientPreset[];
+}
+
+export class UpdatePanelSizes extends JsMessage {
+	readonly panelSizes!: [string, number][];
+}
+
+export class UpdateSplitViewState extends JsMessage {
+	readonly open!: boolean;
+}
+
+export class UpdateSplitViewArtwork extends JsMessage {
+	readonly svg!: string;
+}
+
+export class UpdateTimelineState extends JsMessage {
+	readonly open!: boolean;
+}
+
+export class UpdateColorHarmonyState extends JsMessage {
+	readonly open!: boolean;
+}
+
+export class UpdatePluginsState extends JsMessage {
+	readonly open!: boolean;
+}
+
+export class UpdateConsoleState extends JsMessage {
+	readonly open!: boolean;
+}
+
+export class UpdateCollaborationState extends JsMessage {
+	readonly open!: boolean;
+}
+
+export class UpdateCompareState extends JsMessage {
+	readonly open!: boolean;
+}
+
+export class UpdateLibraryState extends JsMessage {
+	readonly open!: boolean;
+}
+
+export class FrontendLibraryItem {
+	readonly id!: bigint;
+
+	readonly name!: string;
+
+	readonly kind!: string;
+
+	// A CSS background previewing a swatch or gradient
+	readonly preview!: string | undefined;
+
+	readonly updated!: boolean;
+}
+
+export class UpdateLibraryItems extends JsMessage {
+	@Type(() => FrontendLibraryItem)
+	readonly items!: FrontendLibraryItem[];
+}
+
+export class UpdateDataMergeState extends JsMessage {
+	readonly open!: boolean;
+}
+
+export type FrontendKeyframeTrack = {
+	name: string;
+	keyframeTimes: number[];
+	keyframeValues: number[][];
+	easingHandles: ([number, number, number, number] | undefined)[];
+	curve: [number, number[]][];
+};
+
+export type FrontendAudioTrack = {
+	name: string;
+	mime: string;
+	data: number[];
+};
+
+export class UpdateTimelineAudio extends JsMessage {
+	readonly audio!: FrontendAudioTrack | undefined;
+}
+
+export class UpdateTimelineTracks extends JsMessage {
+	readonly time!: number;
+
+	readonly playing!: boolean;
+
+	readonly duration!: number;
+
+	readonly loopRange!: [number, number] | undefined;
+
+	readonly tracks!: FrontendKeyframeTrack[];
+
+	readonly curveEditor!: boolean;
+
+	readonly selectedKeyframe!: [number, number] | undefined;
+
+	readonly audioOffset!: number;
+
+	readonly audioMuted!: boolean;
+}
+
 export class UpdateMouseCursor extends JsMessage {
 	@Transform(({ value }: { value: MouseCursor }) => mouseCursorIconCSSNames[value] || "alias")
 	readonly cursor!: MouseCursorIcon;
 }
 
+export type CursorStyle = "Standard" | "FullViewportCrosshair" | "Precision";
+
+export class UpdateCursorStyle extends JsMessage {
+	readonly style!: CursorStyle;
+
+	readonly size!: number;
+}
+
 export class TriggerLoadFirstAutoSaveDocument extends JsMessage {}
 export class TriggerLoadRestAutoSaveDocuments extends JsMessage {}
 
 export class TriggerLoadPreferences extends JsMessage {}
 
+export class TriggerEditImageExternally extends JsMessage {
+	readonly documentId!: bigint;
+
+	readonly layer!: bigint;
+
+	readonly name!: string;
+
+	readonly data!: number[];
+
+	readonly editor!: string;
+}
+
+export class TriggerStopEditingImageExternally extends JsMessage {
+	readonly documentId!: bigint;
+
+	readonly layer!: bigint;
+}
+
+export class TriggerExtractSwatches extends JsMessage {
+	readonly svg!: string;
+
+	@TupleToVec2
+	readonly size!: XY;
+
+	readonly count!: number;
+}
+
 export class TriggerFetchAndOpenDocument extends JsMessage {
 	readonly name!: string;
 
@@ -797,10 +1051,66 @@ export class TriggerOpenDocument extends JsMessage {}
 
 export class TriggerImport extends JsMessage {}
 
+export class TriggerImportAudio extends JsMessage {}
+
 export class TriggerPaste extends JsMessage {}
 
+export class TriggerPluginEvent extends JsMessage {
+	readonly plugin!: string;
+
+	readonly event!: string;
+}
+
+export class TriggerSampleContrastBackground extends JsMessage {
+	@Transform(({ value }: { value: [[number, number], [number, number]] }) => value.map(([x, y]) => ({ x, y })))
+	readonly region!: [XY, XY];
+
+	readonly textColor!: string;
+}
+
+export class TriggerClipboardWrite extends JsMessage {
+	readonly copiedLayers!: string;
+
+	readonly svg!: string;
+
+	@TupleToVec2
+	readonly size!: XY;
+}
+
+export class TriggerCollaborationConnect extends JsMessage {
+	readonly server!: string;
+}
+
+export class TriggerCollaborationDisconnect extends JsMessage {}
+
+export class TriggerCollaborationSend extends JsMessage {
+	readonly packet!: string;
+}
+
 export class TriggerDelayedZoomCanvasToFitAll extends JsMessage {}
 
+export class TriggerLibraryFetch extends JsMessage {
+	readonly url!: string;
+}
+
+export class TriggerLibraryOpenFile extends JsMessage {}
+
+export class TriggerLibraryPublish extends JsMessage {
+	readonly url!: string;
+
+	readonly content!: string;
+
+	readonly etag!: string | undefined;
+}
+
+export class TriggerDataMergeChooseImages extends JsMessage {}
+
+export class TriggerDataMergeImport extends JsMessage {}
+
+export class TriggerDataMergeLoadImages extends JsMessage {
+	readonly paths!: string[];
+}
+
 export class TriggerDownloadImage extends JsMessage {
 	readonly svg!: string;
 
@@ -810,12 +1120,68 @@ export class TriggerDownloadImage extends JsMessage {
 
 	@TupleToVec2
 	readonly size!: XY;
+
+	readonly metadata!: ExportMetadata;
+
+	readonly tiffCompression!: TiffCompression;
+
+	readonly iccProfile!: number[];
+
+	readonly exportId!: bigint | undefined;
+}
+
+export type TiffCompression = "None" | "Lzw" | "Deflate";
+
+export class ExportMetadata {
+	readonly title!: string;
+
+	readonly author!: string;
+
+	readonly copyright!: string;
+
+	readonly dpi!: number;
+
+	readonly stripMetadata!: boolean;
+}
+
+export class TriggerDownloadBinaryFile extends JsMessage {
+	readonly data!: number[];
+
+	readonly name!: string;
+
+	readonly mime!: string;
+
+	readonly exportId!: bigint | undefined;
 }
 
 export class TriggerDownloadTextFile extends JsMessage {
 	readonly document!: string;
 
 	readonly name!: string;
+
+	readonly exportId!: bigint | undefined;
+}
+
+export class TriggerExportFailed extends JsMessage {
+	readonly exportId!: bigint;
+}
+
+export class TriggerSaveActionCommand extends JsMessage {
+	readonly command!: string;
+
+	readonly documentName!: string;
+}
+
+export class TriggerSaveActionExport extends JsMessage {
+	readonly folder!: string;
+
+	readonly exportIds!: bigint[];
+}
+
+export class TriggerSaveActionWebhook extends JsMessage {
+	readonly url!: string;
+
+	readonly body!: string;
 }
 
 export class TriggerSavePreferences extends JsMessage {
@@ -838,30 +1204,30 @@ export class UpdateDocumentLayerStructureJs extends JsMessage {
 }
 
 export class DisplayEditableTextbox extends JsMessage {
-	readonly text!: string;
-
-	readonly lineHeightRatio!: number;
+	readonly selectedText!: string;
+}
 
-	readonly fontSize!: number;
+export class DisplayEditableTextboxPosition extends JsMessage {
+	readonly position!: [number, number];
+}
 
-	@Type(() => Color)
-	readonly color!: Color;
+export class DisplayRemoveEditableTextbox extends JsMessage {}
 
-	readonly url!: string;
+export class FrontendRadialMenuItem {
+	readonly label!: string;
 
-	readonly transform!: number[];
+	readonly icon!: IconName | undefined;
+}
 
-	readonly maxWidth!: undefined | number;
+export class DisplayRadialMenu extends JsMessage {
+	@Type(() => FrontendRadialMenuItem)
+	readonly items!: FrontendRadialMenuItem[];
 
-	readonly maxHeight!: undefined | number;
-}
+	readonly position!: [number, number];
 
-export class DisplayEditableTextboxTransform extends JsMessage {
-	readonly transform!: number[];
+	readonly openedByKey!: boolean;
 }
 
-export class DisplayRemoveEditableTextbox extends JsMessage {}
-
 export class UpdateDocumentLayerDetails extends JsMessage {
 	@Type(() => LayerPanelEntry)
 	readonly data!: LayerPanelEntry;
@@ -922,8 +1288,6 @@ export class TriggerVisitLink extends JsMessage {
 	url!: string;
 }
 
-export class TriggerTextCommit extends JsMessage {}
-
 export class TriggerTextCopy extends JsMessage {
 	readonly copyText!: string;
 }
@@ -1276,6 +1640,8 @@ export class TextButton extends WidgetProps {
 
 	disabled!: boolean;
 
+	fontUrl!: string | undefined;
+
 	@Transform(({ value }: { value: string }) => value || undefined)
 	tooltip!: string | undefined;
 
@@ -1572,6 +1938,20 @@ export class UpdateDialogColumn1 extends WidgetDiffUpdate {}
 
 export class UpdateDialogColumn2 extends WidgetDiffUpdate {}
 
+export class UpdateColorHarmonyLayout extends WidgetDiffUpdate {}
+
+export class UpdatePluginsLayout extends WidgetDiffUpdate {}
+
+export class UpdateConsoleLayout extends WidgetDiffUpdate {}
+
+export class UpdateCollaborationLayout extends WidgetDiffUpdate {}
+
+export class UpdateCompareLayout extends WidgetDiffUpdate {}
+
+export class UpdateLibraryLayout extends WidgetDiffUpdate {}
+
+export class UpdateDataMergeLayout extends WidgetDiffUpdate {}
+
 export class UpdateDocumentBarLayout extends WidgetDiffUpdate {}
 
 export class UpdateDocumentModeLayout extends WidgetDiffUpdate {}
@@ -1594,10 +1974,16 @@ export class UpdatePropertyPanelSectionsLayout extends WidgetDiffUpdate {}
 
 export class UpdateSpreadsheetLayout extends WidgetDiffUpdate {}
 
+export class UpdateSplitViewControlBarLayout extends WidgetDiffUpdate {}
+
+export class UpdateTimelineControlBarLayout extends WidgetDiffUpdate {}
+
 export class UpdateToolOptionsLayout extends WidgetDiffUpdate {}
 
 export class UpdateToolShelfLayout extends WidgetDiffUpdate {}
 
+export class UpdateTransformBarLayout extends WidgetDiffUpdate {}
+
 export class UpdateWorkingColorsLayout extends WidgetDiffUpdate {}
 
 // eslint-disable-next-line @typescript-eslint/no-explicit-any
@@ -1629,33 +2015,64 @@ export const messageMakers: Record<string, MessageMaker> = {
 	DisplayDialogDismiss,
 	DisplayDialogPanic,
 	DisplayEditableTextbox,
-	DisplayEditableTextboxTransform,
+	DisplayEditableTextboxPosition,
+	DisplayRadialMenu,
 	DisplayRemoveEditableTextbox,
 	SendUIMetadata,
 	TriggerAboutGraphiteLocalizedCommitDate,
+	TriggerClipboardWrite,
+	TriggerCollaborationConnect,
+	TriggerCollaborationDisconnect,
+	TriggerCollaborationSend,
+	TriggerDataMergeChooseImages,
+	TriggerDataMergeImport,
+	TriggerDataMergeLoadImages,
 	TriggerDelayedZoomCanvasToFitAll,
 	TriggerDownloadImage,
+	TriggerDownloadBinaryFile,
 	TriggerDownloadTextFile,
+	TriggerEditImageExternally,
+	TriggerExportFailed,
+	TriggerExtractSwatches,
 	TriggerFetchAndOpenDocument,
 	TriggerFontLoad,
 	TriggerImport,
+	TriggerImportAudio,
 	TriggerIndexedDbRemoveDocument,
 	TriggerIndexedDbWriteDocument,
+	TriggerIndexedDbWriteDocumentOrder,
+	TriggerLibraryFetch,
+	TriggerLibraryOpenFile,
+	TriggerLibraryPublish,
 	TriggerLoadFirstAutoSaveDocument,
 	TriggerLoadPreferences,
 	TriggerLoadRestAutoSaveDocuments,
 	TriggerOpenDocument,
 	TriggerPaste,
+	TriggerPluginEvent,
+	TriggerSampleContrastBackground,
+	TriggerSaveActionCommand,
+	TriggerSaveActionExport,
+	TriggerSaveActionWebhook,
 	TriggerSaveActiveDocument,
 	TriggerSavePreferences,
-	TriggerTextCommit,
+	TriggerStopEditingImageExternally,
 	TriggerTextCopy,
 	TriggerUpgradeDocumentToVectorManipulationFormat,
 	TriggerVisitLink,
 	UpdateActiveDocument,
 	UpdateBox,
 	UpdateClickTargets,
+	UpdateColorHarmonyState,
+	UpdatePluginsState,
+	UpdateConsoleState,
+	UpdateCollaborationState,
+	UpdateCompareState,
+	UpdateLibraryItems,
+	UpdateLibraryState,
+	UpdateDataMergeState,
 	UpdateContextMenuInformation,
+	UpdateCursorStyle,
 	UpdateDialogButtons,
 	UpdateDialogColumn1,
 	UpdateDialogColumn2,
@@ -1668,9 +2085,16 @@ export const messageMakers: Record<string, MessageMaker> = {
 	UpdateDocumentScrollbars,
 	UpdateExportReorderIndex,
 	UpdateEyedropperSamplingState,
+	UpdateFloatingPanels,
+	UpdateGradientPresets,
 	UpdateGraphFadeArtwork,
 	UpdateGraphViewOverlay,
 	UpdateSpreadsheetState,
+	UpdateSplitViewArtwork,
+	UpdateSplitViewState,
+	UpdateTimelineAudio,
+	UpdateTimelineState,
+	UpdateTimelineTracks,
 	UpdateImportReorderIndex,
 	UpdateImportsExports,
 	UpdateInputHints,
@@ -1684,11 +2108,24 @@ export const messageMakers: Record<string, MessageMaker> = {
 	UpdateNodeGraphSelection,
 	UpdateNodeGraphTransform,
 	UpdateNodeThumbnail,
+	UpdateOnionSkinArtwork,
 	UpdateOpenDocumentsList,
+	UpdatePanelSizes,
 	UpdatePropertyPanelSectionsLayout,
 	UpdateSpreadsheetLayout,
+	UpdateColorHarmonyLayout,
+	UpdatePluginsLayout,
+	UpdateConsoleLayout,
+	UpdateCollaborationLayout,
+	UpdateCompareLayout,
+	UpdateLibraryLayout,
+	UpdateDataMergeLayout,
+	UpdateSplitViewControlBarLayout,
+	UpdateTimelineControlBarLayout,
+	UpdateStickyModifierKeys,
 	UpdateToolOptionsLayout,
 	UpdateToolShelfLayout,
+	UpdateTransformBarLayout,
 	UpdateWirePathInProgress,
 	UpdateWorkingColorsLayout,
 } as const;
@@ -4,17 +4,21 @@ import { writable } from "svelte/store";
 import { type Editor } from "@graphite/editor";
 
 import {
+	type CursorStyle,
 	defaultWidgetLayout,
 	patchWidgetLayout,
 	UpdateDocumentBarLayout,
 	UpdateDocumentModeLayout,
 	UpdateToolOptionsLayout,
 	UpdateToolShelfLayout,
+	UpdateTransformBarLayout,
 	UpdateWorkingColorsLayout,
 	UpdateNodeGraphControlBarLayout,
 	UpdateGraphViewOverlay,
 	TriggerDelayedZoomCanvasToFitAll,
 	UpdateGraphFadeArtwork,
+	UpdateCursorStyle,
+	UpdateOnionSkinArtwork,
 } from "@graphite/messages";
 
 // eslint-disable-next-line @typescript-eslint/explicit-function-return-type
@@ -23,6 +27,7 @@ export function createDocumentState(editor: Editor) {
 		// Layouts
 		documentModeLayout: defaultWidgetLayout(),
 		toolOptionsLayout: defaultWidgetLayout(),
+		transformBarLayout: defaultWidgetLayout(),
 		documentBarLayout: defaultWidgetLayout(),
 		toolShelfLayout: defaultWidgetLayout(),
 		workingColorsLayout: defaultWidgetLayout(),
@@ -30,16 +35,34 @@ export function createDocumentState(editor: Editor) {
 		// Graph view overlay
 		graphViewOverlayOpen: false,
 		fadeArtwork: 100,
+		// Cursor preferences
+		cursorStyle: "Standard" as CursorStyle,
+		precisionCursorSize: 32,
+		// Ghosted renders of the artwork at the times around the current one
+		onionSkinArtwork: "",
 	});
 	const { subscribe, update } = state;
 
 	// Update layouts
+	editor.subscriptions.subscribeJsMessage(UpdateCursorStyle, (updateCursorStyle) => {
+		update((state) => {
+			state.cursorStyle = updateCursorStyle.style;
+			state.precisionCursorSize = updateCursorStyle.size;
+			return state;
+		});
+	});
 	editor.subscriptions.subscribeJsMessage(UpdateGraphFadeArtwork, (updateGraphFadeArtwork) => {
 		update((state) => {
 			state.fadeArtwork = updateGraphFadeArtwork.percentage;
 			return state;
 		});
 	});
+	editor.subscriptions.subscribeJsMessage(UpdateOnionSkinArtwork, (updateOnionSkinArtwork) => {
+		update((state) => {
+			state.onionSkinArtwork = updateOnionSkinArtwork.svg;
+			return state;
+		});
+	});
 	editor.subscriptions.subscribeJsMessage(UpdateDocumentModeLayout, async (updateDocumentModeLayout) => {
 		await tick();
 
@@ -56,6 +79,14 @@ export function createDocumentState(editor: Editor) {
 			return state;
 		});
 	});
+	editor.subscriptions.subscribeJsMessage(UpdateTransformBarLayout, async (updateTransformBarLayout) => {
+		await tick();
+
+		update((state) => {
+			patchWidgetLayout(state.transformBarLayout, updateTransformBarLayout);
+			return state;
+		});
+	});
 	editor.subscriptions.subscribeJsMessage(UpdateDocumentBarLayout, async (updateDocumentBarLayout) => {
 		await tick();
 
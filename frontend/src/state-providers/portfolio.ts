@@ -4,25 +4,62 @@ import { writable } from "svelte/store";
 
 import { type Editor } from "@graphite/editor";
 import {
+	type FloatingPanel,
 	type FrontendDocumentDetails,
+	type FrontendKeyframeTrack,
+	type FrontendLibraryItem,
+	type GradientPreset,
+	TriggerExtractSwatches,
 	TriggerFetchAndOpenDocument,
 	TriggerDownloadImage,
+	TriggerDownloadBinaryFile,
 	TriggerDownloadTextFile,
+	TriggerExportFailed,
 	TriggerImport,
+	TriggerImportAudio,
 	TriggerOpenDocument,
 	TriggerUpgradeDocumentToVectorManipulationFormat,
 	UpdateActiveDocument,
+	UpdateCollaborationLayout,
+	UpdateCollaborationState,
+	UpdateColorHarmonyLayout,
+	UpdateColorHarmonyState,
+	UpdateCompareLayout,
+	UpdateCompareState,
+	UpdateConsoleLayout,
+	UpdateConsoleState,
+	UpdateDataMergeLayout,
+	UpdateDataMergeState,
+	UpdateFloatingPanels,
+	UpdateGradientPresets,
+	UpdateLibraryItems,
+	UpdateLibraryLayout,
+	UpdateLibraryState,
 	UpdateOpenDocumentsList,
+	UpdatePluginsLayout,
+	UpdatePluginsState,
 	UpdateSpreadsheetState,
 	defaultWidgetLayout,
 	patchWidgetLayout,
 	UpdateSpreadsheetLayout,
+	UpdateSplitViewArtwork,
+	UpdateSplitViewControlBarLayout,
+	UpdateSplitViewState,
+	UpdateTimelineAudio,
+	UpdateTimelineControlBarLayout,
+	UpdateTimelineState,
+	UpdateTimelineTracks,
 } from "@graphite/messages";
-import { downloadFileText, downloadFileBlob, upload } from "@graphite/utility-functions/files";
-import { extractPixelData, rasterizeSVG } from "@graphite/utility-functions/rasterization";
+import { type DecodedAudio, createAudioPlayer } from "@graphite/utility-functions/audio";
+import { downloadFileText, downloadFileBlob, exportFailed, upload } from "@graphite/utility-functions/files";
+import { applyImageMetadata } from "@graphite/utility-functions/image-metadata";
+import { extractPixelData, rasterizeSVG, rasterizeSVGCanvas } from "@graphite/utility-functions/rasterization";
+import { encodeTiff } from "@graphite-frontend/wasm/pkg/graphite_wasm.js";
 
 // eslint-disable-next-line @typescript-eslint/explicit-function-return-type
 export function createPortfolioState(editor: Editor) {
+	const audioPlayer = createAudioPlayer();
+
 	const { subscribe, update } = writable({
 		unsaved: false,
 		documents: [] as FrontendDocumentDetails[],
@@ -30,6 +67,38 @@ export function createPortfolioState(editor: Editor) {
 		spreadsheetOpen: false,
 		spreadsheetNode: BigInt(0) as bigint | undefined,
 		spreadsheetWidgets: defaultWidgetLayout(),
+		splitViewOpen: false,
+		splitViewArtwork: "",
+		splitViewControlBarLayout: defaultWidgetLayout(),
+		timelineOpen: false,
+		timelineControlBarLayout: defaultWidgetLayout(),
+		timelineTime: 0,
+		timelineDuration: 0,
+		timelineLoopRange: undefined as [number, number] | undefined,
+		timelineTracks: [] as FrontendKeyframeTrack[],
+		timelineCurveEditor: false,
+		timelineSelectedKeyframe: undefined as [number, number] | undefined,
+		timelineAudio: undefined as (DecodedAudio & { name: string }) | undefined,
+		timelineAudioOffset: 0,
+		timelineAudioMuted: false,
+		colorHarmonyOpen: false,
+		colorHarmonyWidgets: defaultWidgetLayout(),
+		pluginsOpen: false,
+		pluginsWidgets: defaultWidgetLayout(),
+		consoleOpen: false,
+		consoleWidgets: defaultWidgetLayout(),
+		collaborationOpen: false,
+		collaborationWidgets: defaultWidgetLayout(),
+		compareOpen: false,
+		compareWidgets: defaultWidgetLayout(),
+		libraryOpen: false,
+		libraryWidgets: defaultWidgetLayout(),
+		libraryItems: [] as FrontendLibraryItem[],
+		dataMergeOpen: false,
+		dataMergeWidgets: defaultWidgetLayout(),
+		floatingPanels: [] as FloatingPanel[],
+		// The bundled gradient presets followed by those saved by the user
+		gradientPresets: [] as GradientPreset[],
 	});
 
 	// Set up message subscriptions on creation
@@ -68,7 +137,13 @@ export function createPortfolioState(editor: Editor) {
 		editor.handle.openDocumentFile(data.filename, data.content);
 	});
 	editor.subscriptions.subscribeJsMessage(TriggerImport, async () => {
-		const data = await upload("image/*", "both");
+		const data = await upload("image/*,audio/*,.ase,.gpl", "both");
+
+		// Sound files are attached to the timeline
+		if (data.type.startsWith("audio/")) {
+			editor.handle.importAudio(data.filename, data.type, data.content.data);
+			return;
+		}
 
 		if (data.type.includes("svg")) {
 			const svg = new TextDecoder().decode(data.content.data);
@@ -76,6 +151,12 @@ export function createPortfolioState(editor: Editor) {
 			return;
 		}
 
+		// Palette files are imported into the active document's swatches
+		if (data.filename.endsWith(".ase") || data.filename.endsWith(".gpl")) {
+			editor.handle.importPalette(data.content.data);
+			return;
+		}
+
 		// In case the user accidentally uploads a Graphite file, open it instead of failing to import it
 		if (data.filename.endsWith(".graphite")) {
 			editor.handle.openDocumentFile(data.filename, data.content.text);
@@ -85,23 +166,63 @@ export function createPortfolioState(editor: Editor) {
 		const imageData = await extractPixelData(new Blob([data.content.data], { type: data.type }));
 		editor.handle.pasteImage(data.filename, new Uint8Array(imageData.data), imageData.width, imageData.height);
 	});
+	editor.subscriptions.subscribeJsMessage(TriggerImportAudio, async () => {
+		const data = await upload("audio/*", "data");
+		editor.handle.importAudio(data.filename, data.type, data.content);
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerDownloadBinaryFile, (triggerFileDownload) => {
+		const blob = new Blob([new Uint8Array(triggerFileDownload.data)], { type: triggerFileDownload.mime });
+		downloadFileBlob(triggerFileDownload.name, blob, triggerFileDownload.exportId);
+	});
 	editor.subscriptions.subscribeJsMessage(TriggerDownloadTextFile, (triggerFileDownload) => {
-		downloadFileText(triggerFileDownload.name, triggerFileDownload.document);
+		downloadFileText(triggerFileDownload.name, triggerFileDownload.document, triggerFileDownload.exportId);
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerExportFailed, (triggerExportFailed) => {
+		exportFailed(triggerExportFailed.exportId);
 	});
 	editor.subscriptions.subscribeJsMessage(TriggerDownloadImage, async (triggerDownloadImage) => {
-		const { svg, name, mime, size } = triggerDownloadImage;
+		const { svg, name, mime, size, metadata, tiffCompression, iccProfile, exportId } = triggerDownloadImage;
 
 		// Fill the canvas with white if it'll be a JPEG (which does not support transparency and defaults to black)
 		const backgroundColor = mime.endsWith("jpeg") ? "white" : undefined;
 
 		// Rasterize the SVG to an image file
 		try {
+			// Browsers can't encode TIFF files, so the rasterized pixels are encoded by the editor backend
+			if (mime === "image/tiff") {
+				const canvas = await rasterizeSVGCanvas(svg, size.x, size.y, backgroundColor);
+				const pixels = canvas.getContext("2d")?.getImageData(0, 0, canvas.width, canvas.height);
+				if (!pixels) throw new Error("The rasterized image has no pixels");
+
+				const tiff = encodeTiff(new Uint8Array(pixels.data.buffer), pixels.width, pixels.height, tiffCompression, metadata);
+				downloadFileBlob(name, new Blob([tiff], { type: mime }), exportId);
+				return;
+			}
+
 			const blob = await rasterizeSVG(svg, size.x, size.y, mime, backgroundColor);
 
+			// Embed the title, author, copyright, and DPI metadata, or strip it all out, along with the color profile the colors are in
+			const blobWithMetadata = await applyImageMetadata(blob, mime, metadata, new Uint8Array(iccProfile));
+
 			// Have the browser download the file to the user's disk
-			downloadFileBlob(name, blob);
+			downloadFileBlob(name, blobWithMetadata, exportId);
+		} catch {
+			// Fail silently if there's an error rasterizing the SVG, such as a zero-sized image, other than letting anything waiting for the file know it isn't coming
+			if (exportId !== undefined) exportFailed(exportId);
+		}
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerExtractSwatches, async (triggerExtractSwatches) => {
+		const { svg, size, count } = triggerExtractSwatches;
+
+		// The swatches are extracted from the colors of the selected layer's artwork as it appears, so it's rasterized first
+		try {
+			const canvas = await rasterizeSVGCanvas(svg, Math.ceil(size.x), Math.ceil(size.y));
+			const pixels = canvas.getContext("2d")?.getImageData(0, 0, canvas.width, canvas.height);
+			if (!pixels) return;
+
+			editor.handle.addExtractedSwatches(new Uint8Array(pixels.data.buffer), pixels.width, pixels.height, count);
 		} catch {
-			// Fail silently if there's an error rasterizing the SVG, such as a zero-sized image
+			editor.handle.errorDialog("Unable to extract swatches", "The selected layer's artwork could not be rasterized to extract its colors.");
 		}
 	});
 	editor.subscriptions.subscribeJsMessage(TriggerUpgradeDocumentToVectorManipulationFormat, async (triggerUpgradeDocumentToVectorManipulationFormat) => {
@@ -125,6 +246,203 @@ export function createPortfolioState(editor: Editor) {
 		});
 	});
 
+	editor.subscriptions.subscribeJsMessage(UpdateSplitViewState, (updateSplitViewState) => {
+		update((state) => {
+			state.splitViewOpen = updateSplitViewState.open;
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateFloatingPanels, (updateFloatingPanels) => {
+		update((state) => {
+			state.floatingPanels = updateFloatingPanels.floatingPanels;
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateGradientPresets, (updateGradientPresets) => {
+		update((state) => {
+			state.gradientPresets = [...updateGradientPresets.bundled, ...updateGradientPresets.saved];
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateSplitViewArtwork, (updateSplitViewArtwork) => {
+		update((state) => {
+			state.splitViewArtwork = updateSplitViewArtwork.svg;
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateSplitViewControlBarLayout, (updateSplitViewControlBarLayout) => {
+		update((state) => {
+			patchWidgetLayout(state.splitViewControlBarLayout, updateSplitViewControlBarLayout);
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateTimelineState, (updateTimelineState) => {
+		update((state) => {
+			state.timelineOpen = updateTimelineState.open;
+			if (!state.timelineOpen) audioPlayer.stop();
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateTimelineControlBarLayout, (updateTimelineControlBarLayout) => {
+		update((state) => {
+			patchWidgetLayout(state.timelineControlBarLayout, updateTimelineControlBarLayout);
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateTimelineTracks, (updateTimelineTracks) => {
+		update((state) => {
+			state.timelineTime = updateTimelineTracks.time;
+			state.timelineDuration = updateTimelineTracks.duration;
+			state.timelineLoopRange = updateTimelineTracks.loopRange;
+			state.timelineTracks = updateTimelineTracks.tracks;
+			state.timelineCurveEditor = updateTimelineTracks.curveEditor;
+			state.timelineSelectedKeyframe = updateTimelineTracks.selectedKeyframe;
+
+			state.timelineAudioOffset = updateTimelineTracks.audioOffset;
+			state.timelineAudioMuted = updateTimelineTracks.audioMuted;
+
+			const { time, playing, audioOffset, audioMuted } = updateTimelineTracks;
+			audioPlayer.update(time, playing, audioOffset, audioMuted);
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateColorHarmonyState, (updateColorHarmonyState) => {
+		update((state) => {
+			state.colorHarmonyOpen = updateColorHarmonyState.open;
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateColorHarmonyLayout, (updateColorHarmonyLayout) => {
+		update((state) => {
+			patchWidgetLayout(state.colorHarmonyWidgets, updateColorHarmonyLayout);
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdatePluginsState, (updatePluginsState) => {
+		update((state) => {
+			state.pluginsOpen = updatePluginsState.open;
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdatePluginsLayout, (updatePluginsLayout) => {
+		update((state) => {
+			patchWidgetLayout(state.pluginsWidgets, updatePluginsLayout);
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateConsoleState, (updateConsoleState) => {
+		update((state) => {
+			state.consoleOpen = updateConsoleState.open;
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateConsoleLayout, (updateConsoleLayout) => {
+		update((state) => {
+			patchWidgetLayout(state.consoleWidgets, updateConsoleLayout);
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateCollaborationState, (updateCollaborationState) => {
+		update((state) => {
+			state.collaborationOpen = updateCollaborationState.open;
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateCollaborationLayout, (updateCollaborationLayout) => {
+		update((state) => {
+			patchWidgetLayout(state.collaborationWidgets, updateCollaborationLayout);
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateCompareState, (updateCompareState) => {
+		update((state) => {
+			state.compareOpen = updateCompareState.open;
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateCompareLayout, (updateCompareLayout) => {
+		update((state) => {
+			patchWidgetLayout(state.compareWidgets, updateCompareLayout);
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateLibraryState, (updateLibraryState) => {
+		update((state) => {
+			state.libraryOpen = updateLibraryState.open;
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateLibraryLayout, (updateLibraryLayout) => {
+		update((state) => {
+			patchWidgetLayout(state.libraryWidgets, updateLibraryLayout);
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateLibraryItems, (updateLibraryItems) => {
+		update((state) => {
+			state.libraryItems = updateLibraryItems.items;
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateDataMergeState, (updateDataMergeState) => {
+		update((state) => {
+			state.dataMergeOpen = updateDataMergeState.open;
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateDataMergeLayout, (updateDataMergeLayout) => {
+		update((state) => {
+			patchWidgetLayout(state.dataMergeWidgets, updateDataMergeLayout);
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdateTimelineAudio, async (updateTimelineAudio) => {
+		const { audio } = updateTimelineAudio;
+		if (!audio) {
+			audioPlayer.unload();
+			update((state) => {
+				state.timelineAudio = undefined;
+				return state;
+			});
+			return;
+		}
+
+		const decoded = await audioPlayer.load(audio.data);
+		// A newer sound was chosen while this one was decoding, which is left to show instead
+		if (decoded === "superseded") return;
+		if (!decoded) {
+			editor.handle.errorDialog("Failed to import audio", "The sound file could not be decoded. It may be in a format this browser doesn't support.");
+			return;
+		}
+		update((state) => {
+			state.timelineAudio = { ...decoded, name: audio.name };
+			return state;
+		});
+	});
+
 	return {
 		subscribe,
 	};
@@ -0,0 +1,33 @@
+const SVG_NAMESPACE = "http://www.w3.org/2000/svg";
+
+// Update the rendered artwork in place, replacing only the elements that differ from the previous render.
+// The browser then only needs to repaint the screen-space regions of the layers which changed, rather than the entire viewport.
+export function patchArtwork(container: Element, markup: string) {
+	const next = window.document.createElementNS(SVG_NAMESPACE, "svg");
+	next.innerHTML = markup;
+
+	patchChildren(container, next);
+}
+
+function patchChildren(current: Element, next: Element) {
+	const currentChildren = Array.from(current.childNodes);
+	const nextChildren = Array.from(next.childNodes);
+
+	nextChildren.forEach((nextChild, index) => {
+		const currentChild = currentChildren.at(index);
+
+		if (!currentChild) current.appendChild(nextChild);
+		else if (currentChild.isEqualNode(nextChild)) return;
+		// Descend into an element (such as a layer's group) whose own attributes are unchanged so that only its changed contents are replaced
+		else if (currentChild instanceof Element && nextChild instanceof Element && hasSameAttributes(currentChild, nextChild)) patchChildren(currentChild, nextChild);
+		else current.replaceChild(nextChild, currentChild);
+	});
+
+	currentChildren.slice(nextChildren.length).forEach((child) => child.remove());
+}
+
+// Layers are tagged with their ID by the renderer, so a different layer in the same position is never mistaken for the previous one
+function hasSameAttributes(current: Element, next: Element): boolean {
+	if (current.tagName !== next.tagName || current.attributes.length !== next.attributes.length) return false;
+	return Array.from(current.attributes).every((attribute) => next.getAttribute(attribute.name) === attribute.value);
+}
@@ -0,0 +1,121 @@
+// Number of loudness peaks the waveform of a sound is drawn from
+const WAVEFORM_PEAKS = 2000;
+// Length in seconds of the snippet of sound played at each new time while scrubbing through the animation
+const SCRUB_SNIPPET_LENGTH = 0.08;
+// How far in seconds the sound may drift from the animation's time during playback before it's restarted in sync with it
+const MAX_DRIFT = 0.1;
+
+export type DecodedAudio = {
+	duration: number;
+	peaks: number[];
+};
+
+// The loudest sample (from 0 to 1) across all channels in each of evenly spaced windows of the sound
+export function waveformPeaks(buffer: AudioBuffer, count: number): number[] {
+	const channels = Array.from({ length: buffer.numberOfChannels }, (_, index) => buffer.getChannelData(index));
+	const windowLength = Math.max(1, Math.floor(buffer.length / count));
+
+	return Array.from({ length: Math.min(count, buffer.length) }, (_, index) => {
+		let peak = 0;
+		channels.forEach((samples) => {
+			for (let sample = index * windowLength; sample < Math.min((index + 1) * windowLength, samples.length); sample += 1) {
+				peak = Math.max(peak, Math.abs(samples[sample]));
+			}
+		});
+		return Math.min(peak, 1);
+	});
+}
+
+// Plays the sound attached to the timeline in sync with the animation, and in short snippets while scrubbing through it
+// eslint-disable-next-line @typescript-eslint/explicit-function-return-type
+export function createAudioPlayer() {
+	let context: AudioContext | undefined;
+	let buffer: AudioBuffer | undefined;
+	let source: AudioBufferSourceNode | undefined;
+	// The time of the audio context when the playing source started, and the position in the sound it started from
+	let sourceStartTime = 0;
+	let sourceStartPosition = 0;
+	// Whether the playing source is a snippet played while scrubbing, rather than playback of the animation
+	let sourceIsSnippet = false;
+	let lastTime: number | undefined;
+	// Counts the sounds loaded or unloaded, so a decode that finishes after another sound has replaced it is ignored
+	let loadRequest = 0;
+
+	// Decodes the sound, resolving to undefined if it can't be decoded, or to "superseded" if another sound was loaded or unloaded meanwhile
+	async function load(data: number[]): Promise<DecodedAudio | undefined | "superseded"> {
+		unload();
+		const request = loadRequest;
+		context = context || new AudioContext();
+
+		try {
+			const decoded = await context.decodeAudioData(new Uint8Array(data).buffer);
+			if (request !== loadRequest) return "superseded";
+
+			buffer = decoded;
+			return { duration: decoded.duration, peaks: waveformPeaks(decoded, WAVEFORM_PEAKS) };
+		} catch {
+			return request === loadRequest ? undefined : "superseded";
+		}
+	}
+
+	function unload() {
+		stop();
+		buffer = undefined;
+		loadRequest += 1;
+	}
+
+	function stop() {
+		source?.stop();
+		source = undefined;
+	}
+
+	function play(position: number, length?: number) {
+		stop();
+		if (!context || !buffer || position >= buffer.duration) return;
+
+		// The context may have started out suspended, since browsers only allow sound to play after the user has interacted with the page
+		if (context.state === "suspended") context.resume();
+
+		const started = context.createBufferSource();
+		started.buffer = buffer;
+		started.connect(context.destination);
+		started.addEventListener("ended", () => {
+			if (source === started) source = undefined;
+		});
+
+		// A sound starting later in the animation waits until its time comes
+		started.start(context.currentTime + Math.max(-position, 0), Math.max(position, 0), length);
+		source = started;
+		sourceStartTime = context.currentTime;
+		sourceStartPosition = position;
+		sourceIsSnippet = length !== undefined;
+	}
+
+	// Follows the animation's time, given the time (in seconds) the sound starts playing at in the animation
+	function update(time: number, playing: boolean, offset: number, muted: boolean) {
+		const previousTime = lastTime;
+		lastTime = time;
+
+		if (!context || !buffer || muted) {
+			stop();
+			return;
+		}
+
+		const position = time - offset;
+		if (playing) {
+			// Keep playing as long as the sound is in sync with the animation, otherwise (such as after looping back) restart it from the animation's time
+			const playingPosition = sourceStartPosition + context.currentTime - sourceStartTime;
+			if (source && Math.abs(playingPosition - position) < MAX_DRIFT) return;
+
+			play(position);
+			return;
+		}
+
+		// Playback stops once paused, but a snippet plays out rather than being cut off by the next update while scrubbing
+		if (source && sourceIsSnippet) return;
+		stop();
+		if (previousTime !== undefined && previousTime !== time && position >= 0) play(position, SCRUB_SNIPPET_LENGTH);
+	}
+
+	return { load, unload, stop, update };
+}
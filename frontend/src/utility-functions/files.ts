@@ -7,7 +7,27 @@ export function downloadFileURL(filename: string, url: string) {
 	element.click();
 }
 
-export function downloadFileBlob(filename: string, blob: Blob) {
+// Given each file about to be downloaded, along with the ID of the export which made it if any, and returns true if it took the file so it shouldn't be downloaded
+type DownloadInterceptor = (filename: string, blob: Blob, exportId: bigint | undefined) => boolean;
+const downloadInterceptors: DownloadInterceptor[] = [];
+
+// Given the ID of each export whose file won't be downloaded after all
+type ExportFailureListener = (exportId: bigint) => void;
+const exportFailureListeners: ExportFailureListener[] = [];
+
+// Interceptors are asked in the order they were added, until one takes the file
+export function interceptDownloads(interceptor: DownloadInterceptor, onExportFailed?: ExportFailureListener) {
+	downloadInterceptors.push(interceptor);
+	if (onExportFailed) exportFailureListeners.push(onExportFailed);
+}
+
+export function exportFailed(exportId: bigint) {
+	exportFailureListeners.forEach((listener) => listener(exportId));
+}
+
+export function downloadFileBlob(filename: string, blob: Blob, exportId?: bigint) {
+	if (downloadInterceptors.some((interceptor) => interceptor(filename, blob, exportId))) return;
+
 	const url = URL.createObjectURL(blob);
 
 	downloadFileURL(filename, url);
@@ -15,11 +35,11 @@ export function downloadFileBlob(filename: string, blob: Blob) {
 	URL.revokeObjectURL(url);
 }
 
-export function downloadFileText(filename: string, text: string) {
+export function downloadFileText(filename: string, text: string, exportId?: bigint) {
 	const type = filename.endsWith(".svg") ? "image/svg+xml;charset=utf-8" : "text/plain;charset=utf-8";
 
 	const blob = new Blob([text], { type });
-	downloadFileBlob(filename, blob);
+	downloadFileBlob(filename, blob, exportId);
 }
 
 export async function upload<T extends "text" | "data" | "both">(acceptedExtensions: string, textOrData: T): Promise<UploadResult<T>> {
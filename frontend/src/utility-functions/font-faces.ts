@@ -0,0 +1,18 @@
+// The URLs of the font files which have already been added to the document's fonts
+const addedFontFaces = new Set<string>();
+
+// Adds the font file at the URL to the document's fonts, named by the URL itself, and returns the CSS font family which draws text with it
+export function fontFaceFamily(url: string): string {
+	if (!addedFontFaces.has(url)) {
+		addedFontFaces.add(url);
+
+		const fontFace = new FontFace(url, `url("${url}")`);
+		document.fonts.add(fontFace);
+		fontFace.load().catch(() => {
+			// eslint-disable-next-line no-console
+			console.error(`Failed to load the font file at ${url}`);
+		});
+	}
+
+	return `"${url}"`;
+}
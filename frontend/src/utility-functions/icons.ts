@@ -40,6 +40,8 @@ import Remove from "@graphite-frontend/assets/icon-12px-solid/remove.svg";
 import Snapping from "@graphite-frontend/assets/icon-12px-solid/snapping.svg";
 import SwapHorizontal from "@graphite-frontend/assets/icon-12px-solid/swap-horizontal.svg";
 import SwapVertical from "@graphite-frontend/assets/icon-12px-solid/swap-vertical.svg";
+import TextStrikethrough from "@graphite-frontend/assets/icon-12px-solid/text-strikethrough.svg";
+import TextUnderline from "@graphite-frontend/assets/icon-12px-solid/text-underline.svg";
 import VerticalEllipsis from "@graphite-frontend/assets/icon-12px-solid/vertical-ellipsis.svg";
 import Warning from "@graphite-frontend/assets/icon-12px-solid/warning.svg";
 import WindowButtonWinClose from "@graphite-frontend/assets/icon-12px-solid/window-button-win-close.svg";
@@ -81,6 +83,8 @@ const SOLID_12PX = {
 	Snapping: { svg: Snapping, size: 12 },
 	SwapHorizontal: { svg: SwapHorizontal, size: 12 },
 	SwapVertical: { svg: SwapVertical, size: 12 },
+	TextStrikethrough: { svg: TextStrikethrough, size: 12 },
+	TextUnderline: { svg: TextUnderline, size: 12 },
 	VerticalEllipsis: { svg: VerticalEllipsis, size: 12 },
 	Warning: { svg: Warning, size: 12 },
 	WindowButtonWinClose: { svg: WindowButtonWinClose, size: 12 },
@@ -165,6 +169,8 @@ import PinActive from "@graphite-frontend/assets/icon-16px-solid/pin-active.svg"
 import PinInactive from "@graphite-frontend/assets/icon-16px-solid/pin-inactive.svg";
 import PlaybackPause from "@graphite-frontend/assets/icon-16px-solid/playback-pause.svg";
 import PlaybackPlay from "@graphite-frontend/assets/icon-16px-solid/playback-play.svg";
+import PlaybackStepBackward from "@graphite-frontend/assets/icon-16px-solid/playback-step-backward.svg";
+import PlaybackStepForward from "@graphite-frontend/assets/icon-16px-solid/playback-step-forward.svg";
 import PlaybackToEnd from "@graphite-frontend/assets/icon-16px-solid/playback-to-end.svg";
 import PlaybackToStart from "@graphite-frontend/assets/icon-16px-solid/playback-to-start.svg";
 import Random from "@graphite-frontend/assets/icon-16px-solid/random.svg";
@@ -285,6 +291,8 @@ const SOLID_16PX = {
 	PinInactive: { svg: PinInactive, size: 16 },
 	PlaybackPause: { svg: PlaybackPause, size: 16 },
 	PlaybackPlay: { svg: PlaybackPlay, size: 16 },
+	PlaybackStepBackward: { svg: PlaybackStepBackward, size: 16 },
+	PlaybackStepForward: { svg: PlaybackStepForward, size: 16 },
 	PlaybackToEnd: { svg: PlaybackToEnd, size: 16 },
 	PlaybackToStart: { svg: PlaybackToStart, size: 16 },
 	Random: { svg: Random, size: 16 },
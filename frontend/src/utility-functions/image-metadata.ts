@@ -0,0 +1,286 @@
+import { type ExportMetadata } from "@graphite/messages";
+
+const PNG_SIGNATURE_LENGTH = 8;
+// PNG ancillary chunks which carry metadata rather than pixel data
+const PNG_METADATA_CHUNKS = ["tEXt", "iTXt", "zTXt", "pHYs", "tIME", "eXIf"];
+// PNG ancillary chunks which describe the color space the pixels are in
+const PNG_COLOR_SPACE_CHUNKS = ["iCCP", "sRGB", "gAMA", "cHRM"];
+const INCHES_PER_METER = 39.3701;
+
+// Embed (or strip) the title, author, copyright, and DPI metadata in a PNG or JPEG file produced by the canvas encoder
+// The ICC color profile is embedded even when the metadata is stripped, since the colors would otherwise be read as sRGB
+// Without a profile to embed, whichever color profile the encoder wrote is kept
+export async function applyImageMetadata(blob: Blob, mime: string, metadata: ExportMetadata, iccProfile: Uint8Array): Promise<Blob> {
+	const data = new Uint8Array(await blob.arrayBuffer());
+
+	let result;
+	if (mime === "image/png") result = applyPngMetadata(data, metadata, iccProfile);
+	else if (mime === "image/jpeg") result = applyJpegMetadata(data, metadata, iccProfile);
+	else return blob;
+
+	return new Blob([result], { type: mime });
+}
+
+function applyPngMetadata(data: Uint8Array, metadata: ExportMetadata, iccProfile: Uint8Array): Uint8Array {
+	const chunks: Uint8Array[] = [data.slice(0, PNG_SIGNATURE_LENGTH)];
+
+	let offset = PNG_SIGNATURE_LENGTH;
+	while (offset < data.length) {
+		const view = new DataView(data.buffer, data.byteOffset + offset);
+		const length = view.getUint32(0);
+		const type = String.fromCharCode(...data.slice(offset + 4, offset + 8));
+		const chunkEnd = offset + 12 + length;
+
+		// Replace any metadata the encoder wrote with our own, along with its color space if we have a profile to replace it with
+		const replaced = PNG_METADATA_CHUNKS.includes(type) || (iccProfile.length > 0 && PNG_COLOR_SPACE_CHUNKS.includes(type));
+		if (!replaced) chunks.push(data.slice(offset, chunkEnd));
+
+		// The color profile has to come before the image data, so it's placed directly after the IHDR chunk, which is always first
+		if (type === "IHDR" && iccProfile.length > 0) chunks.push(pngChunk("iCCP", pngColorProfile(iccProfile)));
+
+		// The metadata chunks are placed directly after it too
+		if (type === "IHDR" && !metadata.stripMetadata) {
+			const pixelsPerMeter = Math.round(metadata.dpi * INCHES_PER_METER);
+			const physical = new Uint8Array(9);
+			new DataView(physical.buffer).setUint32(0, pixelsPerMeter);
+			new DataView(physical.buffer).setUint32(4, pixelsPerMeter);
+			physical[8] = 1; // Unit is the meter
+			chunks.push(pngChunk("pHYs", physical));
+
+			const textEntries: [string, string][] = [
+				["Title", metadata.title],
+				["Author", metadata.author],
+				["Copyright", metadata.copyright],
+				["Software", "Graphite"],
+			];
+			textEntries.forEach(([keyword, text]) => {
+				if (text) chunks.push(pngChunk("iTXt", pngInternationalText(keyword, text)));
+			});
+		}
+
+		offset = chunkEnd;
+	}
+
+	return concatenate(chunks);
+}
+
+// Build an uncompressed iTXt chunk body, which unlike tEXt supports UTF-8 text
+function pngInternationalText(keyword: string, text: string): Uint8Array {
+	const encoder = new TextEncoder();
+	// Keyword, null separator, compression flag, compression method, empty language tag, empty translated keyword, then the text
+	return concatenate([encoder.encode(keyword), new Uint8Array([0, 0, 0, 0, 0]), encoder.encode(text)]);
+}
+
+// Build an iCCP chunk body, which holds the profile as a zlib stream
+function pngColorProfile(iccProfile: Uint8Array): Uint8Array {
+	// Profile name, null separator, then the compression method
+	return concatenate([new TextEncoder().encode("ICC Profile"), new Uint8Array([0, 0]), zlibStored(iccProfile)]);
+}
+
+// Wrap the data in a zlib stream of uncompressed blocks, which any decoder can read without a compressor being needed to write it
+function zlibStored(data: Uint8Array): Uint8Array {
+	const MAX_BLOCK_LENGTH = 0xffff;
+	const parts: Uint8Array[] = [new Uint8Array([0x78, 0x01])];
+
+	for (let offset = 0; offset === 0 || offset < data.length; offset += MAX_BLOCK_LENGTH) {
+		const block = data.subarray(offset, offset + MAX_BLOCK_LENGTH);
+		const header = new Uint8Array(5);
+		const view = new DataView(header.buffer);
+
+		// The final block is marked as such, and each gives its length followed by the length's complement
+		header[0] = offset + MAX_BLOCK_LENGTH >= data.length ? 1 : 0;
+		view.setUint16(1, block.length, true);
+		view.setUint16(3, ~block.length & 0xffff, true);
+		parts.push(header, block);
+	}
+
+	const checksum = new Uint8Array(4);
+	new DataView(checksum.buffer).setUint32(0, adler32(data));
+	parts.push(checksum);
+
+	return concatenate(parts);
+}
+
+function adler32(bytes: Uint8Array): number {
+	let a = 1;
+	let b = 0;
+	bytes.forEach((byte) => {
+		a = (a + byte) % 65521;
+		b = (b + a) % 65521;
+	});
+	return ((b << 16) | a) >>> 0;
+}
+
+function pngChunk(type: string, body: Uint8Array): Uint8Array {
+	const chunk = new Uint8Array(12 + body.length);
+	const view = new DataView(chunk.buffer);
+
+	view.setUint32(0, body.length);
+	chunk.set(new TextEncoder().encode(type), 4);
+	chunk.set(body, 8);
+	view.setUint32(8 + body.length, crc32(chunk.subarray(4, 8 + body.length)));
+
+	return chunk;
+}
+
+let crcTable: Uint32Array | undefined;
+function crc32(bytes: Uint8Array): number {
+	if (!crcTable) {
+		crcTable = new Uint32Array(256);
+		for (let n = 0; n < 256; n += 1) {
+			let c = n;
+			for (let k = 0; k < 8; k += 1) c = c & 1 ? 0xedb88320 ^ (c >>> 1) : c >>> 1;
+			crcTable[n] = c >>> 0;
+		}
+	}
+
+	let crc = 0xffffffff;
+	bytes.forEach((byte) => {
+		crc = (crcTable as Uint32Array)[(crc ^ byte) & 0xff] ^ (crc >>> 8);
+	});
+	return (crc ^ 0xffffffff) >>> 0;
+}
+
+const JPEG_SOI = 0xd8;
+const JPEG_SOS = 0xda;
+const JPEG_APP0 = 0xe0;
+const JPEG_APP1 = 0xe1;
+const JPEG_APP2 = 0xe2;
+const JPEG_APP15 = 0xef;
+const JPEG_COM = 0xfe;
+const JPEG_ICC_PROFILE_IDENTIFIER = "ICC_PROFILE\0";
+
+function applyJpegMetadata(data: Uint8Array, metadata: ExportMetadata, iccProfile: Uint8Array): Uint8Array {
+	const segments: Uint8Array[] = [data.slice(0, 2)];
+	if (data[0] !== 0xff || data[1] !== JPEG_SOI) return data;
+
+	// Always begin with a JFIF header, which is where the pixel density is stored
+	segments.push(jfifSegment(metadata.stripMetadata ? undefined : metadata.dpi));
+	if (iccProfile.length > 0) segments.push(...iccProfileSegments(iccProfile));
+	if (!metadata.stripMetadata) {
+		const exif = exifSegment(metadata);
+		if (exif) segments.push(exif);
+	}
+
+	let offset = 2;
+	while (offset + 4 <= data.length && data[offset] === 0xff) {
+		const marker = data[offset + 1];
+
+		// Everything from the start of the scan onward is entropy-coded image data, which is copied as-is
+		if (marker === JPEG_SOS) break;
+
+		const length = (data[offset + 2] << 8) | data[offset + 3];
+		const segment = data.slice(offset, offset + 2 + length);
+		// The encoder's color profile is kept unless we have one to replace it with, since it's needed to read the colors correctly
+		const isColorProfile = marker === JPEG_APP2 && String.fromCharCode(...segment.slice(4, 4 + JPEG_ICC_PROFILE_IDENTIFIER.length)) === JPEG_ICC_PROFILE_IDENTIFIER;
+		const isMetadata = (marker >= JPEG_APP0 && marker <= JPEG_APP15) || marker === JPEG_COM;
+		if (!isMetadata || (isColorProfile && iccProfile.length === 0)) segments.push(segment);
+
+		offset += 2 + length;
+	}
+	segments.push(data.slice(offset));
+
+	return concatenate(segments);
+}
+
+function jfifSegment(dpi: number | undefined): Uint8Array {
+	const segment = new Uint8Array(18);
+	const view = new DataView(segment.buffer);
+
+	view.setUint16(0, 0xff00 | JPEG_APP0);
+	view.setUint16(2, 16);
+	segment.set([0x4a, 0x46, 0x49, 0x46, 0x00], 4); // "JFIF\0"
+	segment.set([1, 1], 9); // Version 1.01
+	// Without a DPI, the density only describes the (square) pixel aspect ratio
+	segment[11] = dpi === undefined ? 0 : 1;
+	const density = dpi === undefined ? 1 : Math.max(1, Math.min(0xffff, Math.round(dpi)));
+	view.setUint16(12, density);
+	view.setUint16(14, density);
+	// No thumbnail, so the remaining two bytes are left as zero
+
+	return segment;
+}
+
+// Build the APP2 segments holding the ICC profile, split into as many as it takes since each segment is limited to 64 KB
+function iccProfileSegments(iccProfile: Uint8Array): Uint8Array[] {
+	const MAX_CHUNK_LENGTH = 0xffff - 2 - 14;
+	const count = Math.max(1, Math.ceil(iccProfile.length / MAX_CHUNK_LENGTH));
+
+	return Array.from({ length: count }, (_, index) => {
+		const chunk = iccProfile.subarray(index * MAX_CHUNK_LENGTH, (index + 1) * MAX_CHUNK_LENGTH);
+		const header = new Uint8Array(18);
+		const view = new DataView(header.buffer);
+
+		view.setUint16(0, 0xff00 | JPEG_APP2);
+		view.setUint16(2, 2 + 14 + chunk.length);
+		header.set(new TextEncoder().encode(JPEG_ICC_PROFILE_IDENTIFIER), 4);
+		// The segments are numbered from one, followed by how many there are
+		header[16] = index + 1;
+		header[17] = count;
+
+		return concatenate([header, chunk]);
+	});
+}
+
+// Build an APP1 segment containing a minimal big-endian Exif IFD0 with the ASCII text tags
+function exifSegment(metadata: ExportMetadata): Uint8Array | undefined {
+	const encoder = new TextEncoder();
+	const tags: [number, Uint8Array][] = (
+		[
+			[0x010e, metadata.title], // ImageDescription
+			[0x013b, metadata.author], // Artist
+			[0x8298, metadata.copyright], // Copyright
+		] as [number, string][]
+	)
+		.filter(([_, text]) => text)
+		.map(([tag, text]) => [tag, concatenate([encoder.encode(text), new Uint8Array([0])])]);
+	if (tags.length === 0) return undefined;
+
+	const ifdOffset = 8;
+	const ifdLength = 2 + tags.length * 12 + 4;
+	const valuesLength = tags.reduce((total, [_, value]) => total + (value.length > 4 ? value.length : 0), 0);
+	const tiff = new Uint8Array(ifdOffset + ifdLength + valuesLength);
+	const view = new DataView(tiff.buffer);
+
+	tiff.set([0x4d, 0x4d, 0x00, 0x2a], 0); // "MM" byte order marker and TIFF magic number
+	view.setUint32(4, ifdOffset);
+	view.setUint16(ifdOffset, tags.length);
+
+	let valueOffset = ifdOffset + ifdLength;
+	tags.forEach(([tag, value], index) => {
+		const entry = ifdOffset + 2 + index * 12;
+		view.setUint16(entry, tag);
+		view.setUint16(entry + 2, 2); // ASCII type
+		view.setUint32(entry + 4, value.length);
+
+		// Values of up to four bytes are stored inline in the entry
+		if (value.length <= 4) {
+			tiff.set(value, entry + 8);
+		} else {
+			view.setUint32(entry + 8, valueOffset);
+			tiff.set(value, valueOffset);
+			valueOffset += value.length;
+		}
+	});
+	// The next IFD offset is left as zero since there is only one IFD
+
+	const header = new Uint8Array(10);
+	const headerView = new DataView(header.buffer);
+	headerView.setUint16(0, 0xff00 | JPEG_APP1);
+	headerView.setUint16(2, 2 + 6 + tiff.length);
+	header.set([0x45, 0x78, 0x69, 0x66, 0x00, 0x00], 4); // "Exif\0\0"
+
+	return concatenate([header, tiff]);
+}
+
+function concatenate(parts: Uint8Array[]): Uint8Array {
+	const result = new Uint8Array(parts.reduce((total, part) => total + part.length, 0));
+
+	let offset = 0;
+	parts.forEach((part) => {
+		result.set(part, offset);
+		offset += part.length;
+	});
+
+	return result;
+}
@@ -1,3 +1,5 @@
+import { decodeTiff } from "@graphite-frontend/wasm/pkg/graphite_wasm.js";
+
 // Rasterize the string of an SVG document at a given width and height and return the canvas it was drawn onto during the rasterization process
 export async function rasterizeSVGCanvas(svg: string, width: number, height: number, backgroundColor?: string): Promise<HTMLCanvasElement> {
 	// A canvas to render our SVG to in order to get a raster image
@@ -53,6 +55,11 @@ export async function rasterizeSVG(svg: string, width: number, height: number, m
 
 /// Convert an image source (e.g. PNG document) into pixel data, a width, and a height
 export async function extractPixelData(imageData: ImageBitmapSource): Promise<ImageData> {
+	// Browsers can't decode TIFF files, so those are decoded (with any multiple layers flattened) by the editor backend
+	if (imageData instanceof Blob && imageData.type === "image/tiff") {
+		return decodeTiff(new Uint8Array(await imageData.arrayBuffer()));
+	}
+
 	const canvasContext = await imageToCanvasContext(imageData);
 	const width = canvasContext.canvas.width;
 	const height = canvasContext.canvas.height;
@@ -9,12 +9,14 @@ use crate::{EDITOR, EDITOR_HANDLE, EDITOR_HAS_CRASHED, Error};
 use editor::application::Editor;
 use editor::consts::FILE_SAVE_SUFFIX;
 use editor::messages::input_mapper::utility_types::input_keyboard::ModifierKeys;
-use editor::messages::input_mapper::utility_types::input_mouse::{EditorMouseState, ScrollDelta, ViewportBounds};
+use editor::messages::input_mapper::utility_types::input_mouse::{EditorMouseState, ScrollDelta, TouchPoint, ViewportBounds};
 use editor::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
 use editor::messages::portfolio::document::utility_types::network_interface::{ImportOrExport, NodeTemplate};
-use editor::messages::portfolio::utility_types::Platform;
+use editor::messages::portfolio::tiff_format;
+use editor::messages::portfolio::utility_types::{PanelType, Platform};
 use editor::messages::prelude::*;
 use editor::messages::tool::tool_messages::tool_prelude::WidgetId;
+use editor::messages::workspace::utility_types::FloatingPanel;
 use graph_craft::document::NodeId;
 use graphene_core::raster::color::Color;
 use serde::Serialize;
@@ -22,7 +24,9 @@ use serde_wasm_bindgen::{self, from_value};
 use std::cell::RefCell;
 use std::sync::atomic::Ordering;
 use std::time::Duration;
+use wasm_bindgen::Clamped;
 use wasm_bindgen::prelude::*;
+use web_sys::ImageData;
 
 // /// We directly interface with the updateImage JS function for massively increased performance over serializing and deserializing.
 // /// This avoids creating a json with a list millions of numbers long.
@@ -200,6 +204,13 @@ impl EditorHandle {
 		self.dispatch(message);
 	}
 
+	/// Notifies the editor that the dialog was closed without using its buttons
+	#[wasm_bindgen(js_name = dialogDismissed)]
+	pub fn dialog_dismissed(&self) {
+		let message = DialogMessage::Dismissed;
+		self.dispatch(message);
+	}
+
 	/// Answer whether or not the editor has crashed
 	#[wasm_bindgen(js_name = hasCrashed)]
 	pub fn has_crashed(&self) -> bool {
@@ -268,6 +279,14 @@ impl EditorHandle {
 		self.dispatch(message);
 	}
 
+	/// Move a document's tab to a new position among the open documents
+	#[wasm_bindgen(js_name = reorderDocument)]
+	pub fn reorder_document(&self, document_id: u64, new_index: usize) {
+		let document_id = DocumentId(document_id);
+		let message = PortfolioMessage::ReorderDocument { document_id, new_index };
+		self.dispatch(message);
+	}
+
 	#[wasm_bindgen(js_name = newDocumentDialog)]
 	pub fn new_document_dialog(&self) {
 		let message = DialogMessage::RequestNewDocumentDialog;
@@ -342,6 +361,141 @@ impl EditorHandle {
 		self.dispatch(message);
 	}
 
+	/// Send the relative sizes of the workspace's panels after they get resized, so they can be saved as part of a workspace layout
+	#[wasm_bindgen(js_name = updatePanelSizes)]
+	pub fn update_panel_sizes(&self, panel_sizes: JsValue) -> Result<(), JsValue> {
+		let panel_sizes = from_value(panel_sizes).map_err(|error| Error::new(&format!("Could not update the panel sizes\nDetails:\n{error}")))?;
+		let message = WorkspaceMessage::UpdatePanelSizes { panel_sizes };
+		self.dispatch(message);
+		Ok(())
+	}
+
+	/// Undock a panel from the workspace into a floating window at the given position and size in screen coordinates
+	#[wasm_bindgen(js_name = floatPanel)]
+	pub fn float_panel(&self, panel: String, left: f64, top: f64, width: f64, height: f64) -> Result<(), JsValue> {
+		let floating_panel = FloatingPanel {
+			panel_type: panel_type(&panel)?,
+			left,
+			top,
+			width,
+			height,
+		};
+		let message = WorkspaceMessage::FloatPanel { floating_panel };
+		self.dispatch(message);
+		Ok(())
+	}
+
+	/// Send the new position and size of a floating panel's window after it gets moved or resized
+	#[wasm_bindgen(js_name = updateFloatingPanelBounds)]
+	pub fn update_floating_panel_bounds(&self, panel: String, left: f64, top: f64, width: f64, height: f64) -> Result<(), JsValue> {
+		let floating_panel = FloatingPanel {
+			panel_type: panel_type(&panel)?,
+			left,
+			top,
+			width,
+			height,
+		};
+		let message = WorkspaceMessage::UpdateFloatingPanelBounds { floating_panel };
+		self.dispatch(message);
+		Ok(())
+	}
+
+	/// Dock a floating panel back into the workspace
+	#[wasm_bindgen(js_name = dockPanel)]
+	pub fn dock_panel(&self, panel: String) -> Result<(), JsValue> {
+		let message = WorkspaceMessage::DockPanel { panel_type: panel_type(&panel)? };
+		self.dispatch(message);
+		Ok(())
+	}
+
+	/// Open the radial menu after the right mouse button has been held down in the viewport, if that's enabled in the preferences
+	#[wasm_bindgen(js_name = openRadialMenuOnHold)]
+	pub fn open_radial_menu_on_hold(&self) {
+		let message = RadialMenuMessage::OpenOnHold;
+		self.dispatch(message);
+	}
+
+	/// Run the tool or command of the radial menu item that was picked
+	#[wasm_bindgen(js_name = activateRadialMenuItem)]
+	pub fn activate_radial_menu_item(&self, index: usize) {
+		let message = RadialMenuMessage::Activate { index };
+		self.dispatch(message);
+	}
+
+	/// Send the new size of the split view when it gets resized
+	#[wasm_bindgen(js_name = updateSplitViewBounds)]
+	pub fn update_split_view_bounds(&self, width: f64, height: f64) {
+		let message = SplitViewMessage::UpdateBounds { size: (width, height).into() };
+		self.dispatch(message);
+	}
+
+	/// Pan the split view by the given distance in pixels
+	#[wasm_bindgen(js_name = splitViewPan)]
+	pub fn split_view_pan(&self, delta_x: f64, delta_y: f64) {
+		let message = SplitViewMessage::Pan { delta: (delta_x, delta_y).into() };
+		self.dispatch(message);
+	}
+
+	/// Zoom the split view by the given factor around a point in the split view
+	#[wasm_bindgen(js_name = splitViewZoom)]
+	pub fn split_view_zoom(&self, zoom_factor: f64, x: f64, y: f64) {
+		let message = SplitViewMessage::ZoomAtPoint { zoom_factor, center: (x, y).into() };
+		self.dispatch(message);
+	}
+
+	/// Move the animation to the given time in seconds, such as when scrubbing the timeline
+	#[wasm_bindgen(js_name = setAnimationTime)]
+	pub fn set_animation_time(&self, seconds: f64) {
+		let message = AnimationMessage::SetAnimationTime(seconds);
+		self.dispatch(message);
+	}
+
+	/// Remove a keyframed input's track, along with all of its keyframes, from the timeline
+	#[wasm_bindgen(js_name = removeKeyframeTrack)]
+	pub fn remove_keyframe_track(&self, index: usize) {
+		let message = TimelineMessage::RemoveTrack { index };
+		self.dispatch(message);
+	}
+
+	/// Attach a sound file to the timeline to play along with the animation
+	#[wasm_bindgen(js_name = importAudio)]
+	pub fn import_audio(&self, name: String, mime: String, data: Vec<u8>) {
+		let message = TimelineMessage::SetAudio { name, mime, data };
+		self.dispatch(message);
+	}
+
+	/// Remove the sound file attached to the timeline
+	#[wasm_bindgen(js_name = removeAudio)]
+	pub fn remove_audio(&self) {
+		let message = TimelineMessage::RemoveAudio;
+		self.dispatch(message);
+	}
+
+	/// Select a keyframe in the timeline's curve editor, to edit its easing to the next keyframe
+	#[wasm_bindgen(js_name = selectKeyframe)]
+	pub fn select_keyframe(&self, track_index: usize, keyframe_index: usize) {
+		let message = TimelineMessage::SelectKeyframe { track_index, keyframe_index };
+		self.dispatch(message);
+	}
+
+	/// Begin dragging the Bézier handles of a keyframe's easing in the timeline's curve editor, so the whole drag is undone as one step
+	#[wasm_bindgen(js_name = startKeyframeEasingDrag)]
+	pub fn start_keyframe_easing_drag(&self) {
+		let message = DocumentMessage::AddTransaction;
+		self.dispatch(message);
+	}
+
+	/// Shape the easing from a keyframe to the next one by moving its Bézier handles, such as when dragging them in the timeline's curve editor
+	#[wasm_bindgen(js_name = setKeyframeEasingHandles)]
+	pub fn set_keyframe_easing_handles(&self, track_index: usize, keyframe_index: usize, x1: f64, y1: f64, x2: f64, y2: f64) {
+		let message = TimelineMessage::MoveKeyframeEasingHandles {
+			track_index,
+			keyframe_index,
+			handles: [glam::DVec2::new(x1, y1), glam::DVec2::new(x2, y2)],
+		};
+		self.dispatch(message);
+	}
+
 	/// Zoom the canvas to fit all content
 	#[wasm_bindgen(js_name = zoomCanvasToFitAll)]
 	pub fn zoom_canvas_to_fit_all(&self) {
@@ -356,7 +510,7 @@ impl EditorHandle {
 		self.dispatch(message);
 	}
 
-	/// Mouse movement within the screenspace bounds of the viewport
+	/// Mouse movement within the screenspace bounds of the viewport, in fractional pixels when the pointing device is more precise than whole pixels
 	#[wasm_bindgen(js_name = onMouseMove)]
 	pub fn on_mouse_move(&self, x: f64, y: f64, mouse_keys: u8, modifiers: u8) {
 		let editor_mouse_state = EditorMouseState::from_keys_and_editor_position(mouse_keys, (x, y).into());
@@ -379,6 +533,18 @@ impl EditorHandle {
 		self.dispatch(message);
 	}
 
+	/// A trackpad pinch gesture within the screenspace bounds of the viewport, which the browser reports as scrolling with Ctrl held
+	#[wasm_bindgen(js_name = onTrackpadPinch)]
+	pub fn on_trackpad_pinch(&self, x: f64, y: f64, mouse_keys: u8, wheel_delta_x: f64, wheel_delta_y: f64, wheel_delta_z: f64, modifiers: u8) {
+		let mut editor_mouse_state = EditorMouseState::from_keys_and_editor_position(mouse_keys, (x, y).into());
+		editor_mouse_state.scroll_delta = ScrollDelta::new(wheel_delta_x, wheel_delta_y, wheel_delta_z);
+
+		let modifier_keys = ModifierKeys::from_bits(modifiers).expect("Invalid modifier keys");
+
+		let message = InputPreprocessorMessage::TrackpadPinch { editor_mouse_state, modifier_keys };
+		self.dispatch(message);
+	}
+
 	/// A mouse button depressed within screenspace the bounds of the viewport
 	#[wasm_bindgen(js_name = onMouseDown)]
 	pub fn on_mouse_down(&self, x: f64, y: f64, mouse_keys: u8, modifiers: u8) {
@@ -390,6 +556,29 @@ impl EditorHandle {
 		self.dispatch(message);
 	}
 
+	/// The orientation of the stylus, sent before the pointer event it accompanies whenever it changes
+	#[wasm_bindgen(js_name = onStylusOrientation)]
+	pub fn on_stylus_orientation(&self, tilt_x: f64, tilt_y: f64, twist: f64) {
+		let message = InputPreprocessorMessage::StylusOrientation { tilt: (tilt_x, tilt_y).into(), twist };
+		self.dispatch(message);
+	}
+
+	/// The fingers currently touching the screen, sent whenever one is added, moved, or lifted
+	/// [id, x, y]...
+	#[wasm_bindgen(js_name = onTouchPoints)]
+	pub fn on_touch_points(&self, touch_points: &[f64]) {
+		let touches = touch_points
+			.chunks_exact(3)
+			.map(|chunk| TouchPoint {
+				id: chunk[0] as u32,
+				editor_position: (chunk[1], chunk[2]).into(),
+			})
+			.collect();
+
+		let message = InputPreprocessorMessage::TouchPoints { touches };
+		self.dispatch(message);
+	}
+
 	/// A mouse button released
 	#[wasm_bindgen(js_name = onMouseUp)]
 	pub fn on_mouse_up(&self, x: f64, y: f64, mouse_keys: u8, modifiers: u8) {
@@ -436,13 +625,18 @@ impl EditorHandle {
 		self.dispatch(message);
 	}
 
-	/// A text box was committed
-	#[wasm_bindgen(js_name = onChangeText)]
-	pub fn on_change_text(&self, new_text: String, is_left_or_right_click: bool) -> Result<(), JsValue> {
-		let message = TextToolMessage::TextChange { new_text, is_left_or_right_click };
+	/// Text was typed or pasted into the text being edited, replacing the selected characters
+	#[wasm_bindgen(js_name = insertText)]
+	pub fn insert_text(&self, text: String) {
+		let message = TextToolMessage::InsertText { text };
 		self.dispatch(message);
+	}
 
-		Ok(())
+	/// The characters being composed by an input method changed, before they're committed by inserting them
+	#[wasm_bindgen(js_name = composeText)]
+	pub fn compose_text(&self, text: String) {
+		let message = TextToolMessage::CompositionUpdate { text };
+		self.dispatch(message);
 	}
 
 	/// A font has been downloaded
@@ -459,15 +653,6 @@ impl EditorHandle {
 		Ok(())
 	}
 
-	/// A text box was changed
-	#[wasm_bindgen(js_name = updateBounds)]
-	pub fn update_bounds(&self, new_text: String) -> Result<(), JsValue> {
-		let message = TextToolMessage::UpdateBounds { new_text };
-		self.dispatch(message);
-
-		Ok(())
-	}
-
 	/// Begin sampling a pixel color from the document by entering eyedropper sampling mode
 	#[wasm_bindgen(js_name = eyedropperSampleForColorPicker)]
 	pub fn eyedropper_sample_for_color_picker(&self) -> Result<(), JsValue> {
@@ -507,6 +692,199 @@ impl EditorHandle {
 		Ok(())
 	}
 
+	/// Report the color sampled beneath a text layer for its contrast check, with values on a scale from 0 to 1, or none if nothing could be sampled.
+	#[wasm_bindgen(js_name = contrastBackgroundSampled)]
+	pub fn contrast_background_sampled(&self, red: Option<f32>, green: Option<f32>, blue: Option<f32>) {
+		let color = red.zip(green).zip(blue).and_then(|((red, green), blue)| Color::from_rgbaf32(red, green, blue, 1.));
+
+		let message = ContrastCheckDialogMessage::BackgroundSampled {
+			color: color.map(|color| color.to_linear_srgb()),
+		};
+		self.dispatch(message);
+	}
+
+	/// Register a plugin loaded from the plugins directory, given the JSON of its manifest
+	#[wasm_bindgen(js_name = registerPlugin)]
+	pub fn register_plugin(&self, manifest: String) {
+		let message = PluginsMessage::Register { manifest };
+		self.dispatch(message);
+	}
+
+	/// Remove a plugin, such as one which failed while handling an event
+	#[wasm_bindgen(js_name = unregisterPlugin)]
+	pub fn unregister_plugin(&self, plugin: String) {
+		let message = PluginsMessage::Unregister { plugin };
+		self.dispatch(message);
+	}
+
+	/// Hand over the JSON list of responses a plugin returned from handling an event
+	#[wasm_bindgen(js_name = pluginResponses)]
+	pub fn plugin_responses(&self, plugin: String, responses: String) {
+		let message = PluginsMessage::Responses { plugin, responses };
+		self.dispatch(message);
+	}
+
+	/// Tell the editor the connection to the collaboration server is open
+	#[wasm_bindgen(js_name = collaborationConnected)]
+	pub fn collaboration_connected(&self) {
+		let message = CollaborationMessage::Connected;
+		self.dispatch(message);
+	}
+
+	/// Tell the editor the connection to the collaboration server has closed
+	#[wasm_bindgen(js_name = collaborationDisconnected)]
+	pub fn collaboration_disconnected(&self) {
+		let message = CollaborationMessage::Disconnected;
+		self.dispatch(message);
+	}
+
+	/// Hand over a JSON packet relayed by the collaboration server from another collaborator
+	#[wasm_bindgen(js_name = collaborationReceived)]
+	pub fn collaboration_received(&self, packet: String) {
+		let message = CollaborationMessage::Received { packet };
+		self.dispatch(message);
+	}
+
+	/// Exchange the changes made to the shared document with the other collaborators
+	#[wasm_bindgen(js_name = collaborationTick)]
+	pub fn collaboration_tick(&self) {
+		let message = CollaborationMessage::Tick;
+		self.dispatch(message);
+	}
+
+	/// Hand over the shared library fetched from its URL, which is empty if nothing has been published there yet, along with the ETag of that version of it
+	#[wasm_bindgen(js_name = libraryFetched)]
+	pub fn library_fetched(&self, content: String, etag: Option<String>) {
+		let message = LibraryMessage::Fetched { content, etag };
+		self.dispatch(message);
+	}
+
+	/// Hand over the contents of the library file chosen by the user
+	#[wasm_bindgen(js_name = libraryFileOpened)]
+	pub fn library_file_opened(&self, content: String) {
+		let message = LibraryMessage::FileOpened { content };
+		self.dispatch(message);
+	}
+
+	/// Tell the editor the shared library couldn't be fetched from its URL
+	#[wasm_bindgen(js_name = libraryFailed)]
+	pub fn library_failed(&self, error: String) {
+		let message = LibraryMessage::Failed { error };
+		self.dispatch(message);
+	}
+
+	/// Tell the editor the shared library was published to its URL, along with the ETag of the published version
+	#[wasm_bindgen(js_name = libraryPublished)]
+	pub fn library_published(&self, etag: Option<String>) {
+		let message = LibraryMessage::Published { etag };
+		self.dispatch(message);
+	}
+
+	/// Tell the editor the shared library wasn't published because someone else published a newer version of it first
+	#[wasm_bindgen(js_name = libraryPublishConflicted)]
+	pub fn library_publish_conflicted(&self) {
+		let message = LibraryMessage::PublishConflicted;
+		self.dispatch(message);
+	}
+
+	/// Tell the editor the shared library couldn't be published to its URL
+	#[wasm_bindgen(js_name = libraryPublishFailed)]
+	pub fn library_publish_failed(&self, error: String) {
+		let message = LibraryMessage::PublishFailed { error };
+		self.dispatch(message);
+	}
+
+	/// Fetch the shared library again to find the items changed by others
+	#[wasm_bindgen(js_name = libraryPoll)]
+	pub fn library_poll(&self) {
+		let message = LibraryMessage::Poll;
+		self.dispatch(message);
+	}
+
+	/// Insert an item from the shared library into the active document, with gradients filling the layer of the given ID, the one under the mouse, or otherwise the selected layers
+	#[wasm_bindgen(js_name = insertLibraryItem)]
+	pub fn insert_library_item(&self, id: u64, layer_id: Option<u64>, mouse_x: Option<f64>, mouse_y: Option<f64>) {
+		let layer = layer_id.map(|id| LayerNodeIdentifier::new_unchecked(NodeId(id)));
+		let mouse = mouse_x.and_then(|x| mouse_y.map(|y| (x, y)));
+
+		let message = LibraryMessage::Insert { id, layer, mouse };
+		self.dispatch(message);
+	}
+
+	/// Remove an item from the shared library and publish it again
+	#[wasm_bindgen(js_name = removeLibraryItem)]
+	pub fn remove_library_item(&self, id: u64) {
+		let message = LibraryMessage::Remove { id };
+		self.dispatch(message);
+	}
+
+	/// Hand over the CSV or JSON file chosen by the user as the data merge's dataset
+	#[wasm_bindgen(js_name = dataMergeDatasetImported)]
+	pub fn data_merge_dataset_imported(&self, name: String, content: String) {
+		let message = DataMergeMessage::DatasetImported { name, content };
+		self.dispatch(message);
+	}
+
+	/// Hand over an image named by the data merge's dataset, either loaded from its path or chosen by the user under its file name
+	#[wasm_bindgen(js_name = dataMergeImageLoaded)]
+	pub fn data_merge_image_loaded(&self, path: String, image_data: Vec<u8>, width: u32, height: u32) {
+		let image = graphene_core::raster::Image::from_image_data(&image_data, width, height);
+
+		let message = DataMergeMessage::ImageLoaded { path, image };
+		self.dispatch(message);
+	}
+
+	/// Tell the editor an image named by the data merge's dataset couldn't be loaded from its path
+	#[wasm_bindgen(js_name = dataMergeImageFailed)]
+	pub fn data_merge_image_failed(&self, path: String) {
+		let message = DataMergeMessage::ImageFailed { path };
+		self.dispatch(message);
+	}
+
+	/// Tell the editor a save action couldn't be run, such as a command that failed or a folder that couldn't be written to
+	#[wasm_bindgen(js_name = saveActionFailed)]
+	pub fn save_action_failed(&self, error: String) {
+		let message = DialogMessage::DisplayDialogError {
+			title: "Unable to run a save action".to_string(),
+			description: error,
+		};
+		self.dispatch(message);
+	}
+
+	/// Replace the pixels of an image layer with those saved by the application it's being edited in
+	#[wasm_bindgen(js_name = externalImageEdited)]
+	pub fn external_image_edited(&self, document_id: u64, layer_id: u64, image_data: Vec<u8>, width: u32, height: u32) {
+		let image = graphene_core::raster::Image::from_image_data(&image_data, width, height);
+		let layer = LayerNodeIdentifier::new_unchecked(NodeId(layer_id));
+
+		let message = PortfolioMessage::SetExternallyEditedImage {
+			document_id: DocumentId(document_id),
+			layer,
+			image,
+		};
+		self.dispatch(message);
+	}
+
+	/// Tell the editor an image layer couldn't be opened in another application, or its edits couldn't be reimported
+	#[wasm_bindgen(js_name = externalImageEditFailed)]
+	pub fn external_image_edit_failed(&self, error: String) {
+		let message = DialogMessage::DisplayDialogError {
+			title: "Unable to edit image externally".to_string(),
+			description: error,
+		};
+		self.dispatch(message);
+	}
+
+	/// Dispatch a JSON list of editor messages sent by another program through the desktop app's remote control server
+	#[wasm_bindgen(js_name = remoteControlMessages)]
+	pub fn remote_control_messages(&self, messages: String) -> Result<(), JsValue> {
+		let messages = serde_json::from_str::<Vec<Message>>(&messages).map_err(|error| JsValue::from_str(&format!("The messages couldn't be read: {error}")))?;
+		for message in messages {
+			self.dispatch(message);
+		}
+		Ok(())
+	}
+
 	/// Paste layers from a serialized json representation
 	#[wasm_bindgen(js_name = pasteSerializedData)]
 	pub fn paste_serialized_data(&self, data: String) {
@@ -649,6 +1027,22 @@ impl EditorHandle {
 		self.dispatch(message);
 	}
 
+	/// Imports the swatches from an ASE or GPL palette file into the active document
+	#[wasm_bindgen(js_name = importPalette)]
+	pub fn import_palette(&self, data: Vec<u8>) {
+		let message = DocumentMessage::ImportPalette { data };
+		self.dispatch(message);
+	}
+
+	/// Adds swatches to the active document for the colors most representative of the rasterized artwork of its selected layer
+	#[wasm_bindgen(js_name = addExtractedSwatches)]
+	pub fn add_extracted_swatches(&self, image_data: Vec<u8>, width: u32, height: u32, count: u32) {
+		let image = graphene_core::raster::Image::from_image_data(&image_data, width, height);
+
+		let message = DocumentMessage::AddExtractedSwatches { image, count };
+		self.dispatch(message);
+	}
+
 	#[wasm_bindgen(js_name = pasteSvg)]
 	pub fn paste_svg(&self, name: Option<String>, svg: String, mouse_x: Option<f64>, mouse_y: Option<f64>, insert_parent_id: Option<u64>, insert_index: Option<usize>) {
 		let mouse = mouse_x.and_then(|x| mouse_y.map(|y| (x, y)));
@@ -670,6 +1064,16 @@ impl EditorHandle {
 		self.dispatch(message);
 	}
 
+	/// Fill a layer with the gradient preset of the given name, either the layer of the given ID, the one under the mouse, or otherwise the selected layers
+	#[wasm_bindgen(js_name = applyGradientPreset)]
+	pub fn apply_gradient_preset(&self, name: String, layer_id: Option<u64>, mouse_x: Option<f64>, mouse_y: Option<f64>) {
+		let layer = layer_id.map(|id| LayerNodeIdentifier::new_unchecked(NodeId(id)));
+		let mouse = mouse_x.and_then(|x| mouse_y.map(|y| (x, y)));
+
+		let message = DocumentMessage::ApplyGradientPreset { name, layer, mouse };
+		self.dispatch(message);
+	}
+
 	/// Toggle visibility of a layer or node given its node ID
 	#[wasm_bindgen(js_name = toggleNodeVisibilityLayerPanel)]
 	pub fn toggle_node_visibility_layer(&self, id: u64) {
@@ -977,6 +1381,26 @@ pub fn evaluate_math_expression(expression: &str) -> Option<f64> {
 	Some(real)
 }
 
+/// Decodes a TIFF file, which browsers are unable to do natively, flattening any multiple layers into a single image.
+#[wasm_bindgen(js_name = decodeTiff)]
+pub fn decode_tiff(data: &[u8]) -> Result<ImageData, JsValue> {
+	let (rgba, width, height) = tiff_format::decode_tiff(data).map_err(|err| JsValue::from_str(&err))?;
+	ImageData::new_with_u8_clamped_array_and_sh(Clamped(&rgba), width, height)
+}
+
+/// Encodes RGBA pixel data as a TIFF file, which browsers are unable to do natively.
+#[wasm_bindgen(js_name = encodeTiff)]
+pub fn encode_tiff(rgba: &[u8], width: u32, height: u32, compression: JsValue, metadata: JsValue) -> Result<Vec<u8>, JsValue> {
+	let compression = from_value(compression)?;
+	let metadata = from_value(metadata)?;
+	tiff_format::encode_tiff(rgba, width, height, compression, &metadata).map_err(|err| JsValue::from_str(&err))
+}
+
+/// Helper function for reading the name of a panel sent from JS, which is an error rather than a panic if no panel has that name
+fn panel_type(panel: &str) -> Result<PanelType, JsValue> {
+	PanelType::from_name(panel).ok_or_else(|| Error::new(&format!("Unknown panel type: {panel}")).into())
+}
+
 /// Helper function for calling JS's `requestAnimationFrame` with the given closure
 fn request_animation_frame(f: &Closure<dyn FnMut(f64)>) {
 	web_sys::window()
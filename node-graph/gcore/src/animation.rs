@@ -1,4 +1,5 @@
-use crate::{Ctx, ExtractAnimationTime, ExtractTime};
+use crate::{Color, Ctx, ExtractAnimationTime, ExtractTime, GraphicGroupTable};
+use glam::DVec2;
 
 const DAY: f64 = 1000. * 3600. * 24.;
 
@@ -26,6 +27,173 @@ impl core::fmt::Display for RealTimeMode {
 	}
 }
 
+/// The rate at which an interpolated value changes over the course of its transition.
+#[derive(Debug, Clone, Copy, PartialEq, dyn_any::DynAny, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Easing {
+	#[default]
+	Linear,
+	EaseIn,
+	EaseOut,
+	EaseInOut,
+	Hold,
+	/// Falls onto the next value and bounces off it a few times, like a dropped ball coming to rest.
+	Bounce,
+	/// A cubic Bézier curve from (0, 0) to (1, 1), shaped by two handles like CSS's `cubic-bezier()`.
+	/// The handles' progress through time is kept between 0 and 1, but their progress through the values may overshoot.
+	Bezier {
+		handle_start: DVec2,
+		handle_end: DVec2,
+	},
+}
+impl core::fmt::Display for Easing {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			Easing::Linear => write!(f, "Linear"),
+			Easing::EaseIn => write!(f, "Ease In"),
+			Easing::EaseOut => write!(f, "Ease Out"),
+			Easing::EaseInOut => write!(f, "Ease In Out"),
+			Easing::Hold => write!(f, "Hold"),
+			Easing::Bounce => write!(f, "Bounce"),
+			Easing::Bezier { .. } => write!(f, "Custom"),
+		}
+	}
+}
+
+// The handles are floats, so they're hashed by their bits
+impl core::hash::Hash for Easing {
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+		core::mem::discriminant(self).hash(state);
+		if let Easing::Bezier { handle_start, handle_end } = self {
+			for component in [handle_start.x, handle_start.y, handle_end.x, handle_end.y] {
+				component.to_bits().hash(state);
+			}
+		}
+	}
+}
+
+impl Easing {
+	/// The easings that can be picked by name, leaving out the ones shaped by their own handles.
+	pub const PRESETS: [Self; 6] = [Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut, Easing::Hold, Easing::Bounce];
+
+	pub const fn bezier(x1: f64, y1: f64, x2: f64, y2: f64) -> Self {
+		Self::Bezier {
+			handle_start: DVec2::new(x1, y1),
+			handle_end: DVec2::new(x2, y2),
+		}
+	}
+
+	/// The Bézier handles which shape exactly this easing, if it can be drawn as a single cubic Bézier curve.
+	pub fn handles(&self) -> Option<(DVec2, DVec2)> {
+		match *self {
+			// With the handles a third of the way along in time, the curve's progress through the values is a cubic polynomial of the progress through time
+			Easing::Linear => Some((DVec2::new(1. / 3., 1. / 3.), DVec2::new(2. / 3., 2. / 3.))),
+			Easing::EaseIn => Some((DVec2::new(1. / 3., 0.), DVec2::new(2. / 3., 0.))),
+			Easing::EaseOut => Some((DVec2::new(1. / 3., 1.), DVec2::new(2. / 3., 1.))),
+			Easing::Bezier { handle_start, handle_end } => Some((handle_start, handle_end)),
+			Easing::EaseInOut | Easing::Hold | Easing::Bounce => None,
+		}
+	}
+
+	/// Maps the linear progress through a transition (from 0 to 1) to the eased progress, which also starts at 0 and ends at 1.
+	pub fn apply(&self, t: f64) -> f64 {
+		let t = t.clamp(0., 1.);
+		match *self {
+			Easing::Linear => t,
+			Easing::EaseIn => t * t * t,
+			Easing::EaseOut => 1. - (1. - t).powi(3),
+			Easing::EaseInOut => {
+				if t < 0.5 {
+					4. * t * t * t
+				} else {
+					1. - (-2. * t + 2.).powi(3) / 2.
+				}
+			}
+			Easing::Hold => {
+				if t < 1. {
+					0.
+				} else {
+					1.
+				}
+			}
+			Easing::Bounce => {
+				// The standard bounce easing, made of four parabolic arcs that each reach the next value
+				const N: f64 = 7.5625;
+				const D: f64 = 2.75;
+				match t {
+					t if t < 1. / D => N * t * t,
+					t if t < 2. / D => N * (t - 1.5 / D).powi(2) + 0.75,
+					t if t < 2.5 / D => N * (t - 2.25 / D).powi(2) + 0.9375,
+					t => N * (t - 2.625 / D).powi(2) + 0.984375,
+				}
+			}
+			Easing::Bezier { handle_start, handle_end } => {
+				let (x1, x2) = (handle_start.x.clamp(0., 1.), handle_end.x.clamp(0., 1.));
+				let cubic = |a: f64, b: f64, t: f64| 3. * (1. - t).powi(2) * t * a + 3. * (1. - t) * t.powi(2) * b + t.powi(3);
+
+				// The curve's progress through time only ever increases since the handles are kept between 0 and 1, so it can be found by bisection
+				let (mut low, mut high) = (0., 1.);
+				for _ in 0..32 {
+					let middle = (low + high) / 2.;
+					if cubic(x1, x2, middle) < t {
+						low = middle;
+					} else {
+						high = middle;
+					}
+				}
+				cubic(handle_start.y, handle_end.y, (low + high) / 2.)
+			}
+		}
+	}
+}
+
+/// A value that can be blended with another of its type, for animating between them.
+pub trait Interpolate {
+	/// Blends from this value to the other by a factor between 0 (this value) and 1 (the other value).
+	fn interpolate(&self, other: &Self, factor: f64) -> Self;
+}
+
+impl Interpolate for f64 {
+	fn interpolate(&self, other: &Self, factor: f64) -> Self {
+		self + (other - self) * factor
+	}
+}
+
+impl Interpolate for DVec2 {
+	fn interpolate(&self, other: &Self, factor: f64) -> Self {
+		self.lerp(*other, factor)
+	}
+}
+
+impl Interpolate for Color {
+	fn interpolate(&self, other: &Self, factor: f64) -> Self {
+		self.lerp(other, factor.clamp(0., 1.) as f32)
+	}
+}
+
+/// The progress (from 0 to 1) through a transition that begins at the start and lasts for the duration, which is held at either end outside of it.
+fn transition_progress(progress: f64, start: f64, duration: f64) -> f64 {
+	if duration <= 0. {
+		return if progress < start { 0. } else { 1. };
+	}
+	((progress - start) / duration).clamp(0., 1.)
+}
+
+/// Blends along a list of evenly spaced values, easing through each transition from one value to the next, as the factor goes from 0 to 1.
+fn interpolate_values<T: Interpolate + Clone + Default>(values: &[T], factor: f64, easing: Easing) -> T {
+	let (Some(first), Some(last)) = (values.first(), values.last()) else { return T::default() };
+	if values.len() == 1 || factor <= 0. {
+		return first.clone();
+	}
+	if factor >= 1. {
+		return last.clone();
+	}
+
+	let position = factor * (values.len() - 1) as f64;
+	let index = (position.floor() as usize).min(values.len() - 2);
+	values[index].interpolate(&values[index + 1], easing.apply(position - index as f64))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AnimationTimeMode {
 	AnimationTime,
@@ -52,6 +220,70 @@ fn animation_time(ctx: impl Ctx + ExtractAnimationTime) -> f64 {
 	ctx.try_animation_time().unwrap_or_default()
 }
 
+/// Blends from one value to another as the progress goes from the start to the end of the duration, easing through the transition.
+/// Connect the Animation Time node to the progress to animate the value over time, or leave the start at 0 and the duration at 1 to use the progress as a factor from 0 to 1.
+#[node_macro::node(category("Animation"))]
+fn interpolate<T: Interpolate + Clone + Default>(
+	_: impl Ctx,
+	progress: f64,
+	#[implementations(f64, DVec2, Color)] from: T,
+	#[implementations(f64, DVec2, Color)] to: T,
+	start: f64,
+	#[default(1.)] duration: f64,
+	easing: Easing,
+) -> T {
+	interpolate_values(&[from, to], transition_progress(progress, start, duration), easing)
+}
+
+/// Blends along a list of values, spaced evenly over the duration, as the progress goes from the start to the end of the duration, easing through each transition from one value to the next.
+/// Connect the Animation Time node to the progress to animate the value over time, or leave the start at 0 and the duration at 1 to use the progress as a factor from 0 to 1.
+#[node_macro::node(category("Animation"))]
+fn interpolate_list<T: Interpolate + Clone + Default>(
+	_: impl Ctx,
+	progress: f64,
+	#[implementations(Vec<f64>, Vec<DVec2>, Vec<Color>)] values: Vec<T>,
+	start: f64,
+	#[default(1.)] duration: f64,
+	easing: Easing,
+) -> T {
+	interpolate_values(&values, transition_progress(progress, start, duration), easing)
+}
+
+/// The index of the frame shown at the time in a sequence of frames, each held for a number of frames at the frame rate.
+/// Past the last frame, the sequence either starts over or shows no frame.
+pub fn sequence_frame_index(time: f64, frame_rate: f64, hold: u32, frame_count: usize, looping: bool) -> Option<usize> {
+	if frame_count == 0 || frame_rate <= 0. {
+		return None;
+	}
+
+	// Allow for rounding error when the time lands exactly on a frame
+	let frame = (time.max(0.) * frame_rate + 1e-6).floor() as usize;
+	let index = frame / hold.max(1) as usize;
+
+	if looping { Some(index % frame_count) } else { (index < frame_count).then_some(index) }
+}
+
+/// Shows the elements of a group one at a time as the drawings of a frame-by-frame animation, starting from the bottom-most element.
+/// Each drawing is held for a number of frames at the frame rate, such as 2 to animate "on twos".
+#[node_macro::node(category("Animation"))]
+fn frame_sequence(
+	ctx: impl Ctx + ExtractAnimationTime,
+	frames: GraphicGroupTable,
+	#[default(30.)] frame_rate: f64,
+	#[default(1)]
+	#[min(1.)]
+	hold: u32,
+	looping: bool,
+) -> GraphicGroupTable {
+	let time = ctx.try_animation_time().unwrap_or_default();
+
+	let mut output = GraphicGroupTable::empty();
+	if let Some(frame) = sequence_frame_index(time, frame_rate, hold, frames.len(), looping).and_then(|index| frames.get(index)) {
+		output.push_instance(frame);
+	}
+	output
+}
+
 // These nodes require more sophistcated algorithms for giving the correct result
 
 // #[node_macro::node(category("Animation"))]
@@ -62,3 +294,62 @@ fn animation_time(ctx: impl Ctx + ExtractAnimationTime) -> f64 {
 // fn day(ctx: impl Ctx + ExtractTime) -> f64 {
 // 	(ctx.try_time().unwrap_or_default() / DAY
 // }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn easing_starts_and_ends_at_the_values() {
+		for easing in Easing::PRESETS {
+			assert!(easing.apply(0.).abs() < 1e-6);
+			assert!((easing.apply(1.) - 1.).abs() < 1e-6);
+		}
+		assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+		assert_eq!(Easing::Hold.apply(0.5), 0.);
+	}
+
+	#[test]
+	fn bezier_handles_match_the_easing() {
+		for easing in Easing::PRESETS {
+			let Some((handle_start, handle_end)) = easing.handles() else { continue };
+			let bezier = Easing::Bezier { handle_start, handle_end };
+			for t in [0.1, 0.25, 0.5, 0.75, 0.9] {
+				assert!((bezier.apply(t) - easing.apply(t)).abs() < 1e-6, "{easing} differs from its handles at {t}");
+			}
+		}
+	}
+
+	#[test]
+	fn interpolates_between_two_values() {
+		assert_eq!(interpolate_values(&[10., 20.], 0.25, Easing::Linear), 12.5);
+		assert_eq!(interpolate_values(&[DVec2::ZERO, DVec2::new(2., 4.)], 0.5, Easing::Linear), DVec2::new(1., 2.));
+	}
+
+	#[test]
+	fn interpolates_along_a_list() {
+		let values = [0., 10., 30.];
+		assert_eq!(interpolate_values(&values, 0.25, Easing::Linear), 5.);
+		assert_eq!(interpolate_values(&values, 0.75, Easing::Linear), 20.);
+		assert_eq!(interpolate_values(&values, 2., Easing::Linear), 30.);
+		assert_eq!(interpolate_values::<f64>(&[], 0.5, Easing::Linear), 0.);
+	}
+
+	#[test]
+	fn maps_time_into_the_transition() {
+		assert_eq!(transition_progress(3., 2., 4.), 0.25);
+		assert_eq!(transition_progress(1., 2., 4.), 0.);
+		assert_eq!(transition_progress(7., 2., 4.), 1.);
+		assert_eq!(transition_progress(2., 2., 0.), 1.);
+	}
+
+	#[test]
+	fn shows_each_frame_of_a_sequence_in_turn() {
+		assert_eq!(sequence_frame_index(0., 10., 1, 3, false), Some(0));
+		assert_eq!(sequence_frame_index(0.2, 10., 1, 3, false), Some(2));
+		assert_eq!(sequence_frame_index(0.3, 10., 1, 3, false), None);
+		assert_eq!(sequence_frame_index(0.3, 10., 1, 3, true), Some(0));
+		assert_eq!(sequence_frame_index(0.3, 10., 2, 3, false), Some(1));
+		assert_eq!(sequence_frame_index(0.5, 10., 1, 0, true), None);
+	}
+}
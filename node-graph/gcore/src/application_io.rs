@@ -1,4 +1,5 @@
 use crate::instances::Instances;
+use crate::renderer::ColorManagement;
 use crate::text::FontCache;
 use crate::transform::{Footprint, Transform, TransformMut};
 use crate::vector::style::ViewMode;
@@ -238,6 +239,20 @@ impl<T: NodeGraphUpdateSender> NodeGraphUpdateSender for std::sync::Mutex<T> {
 pub trait GetEditorPreferences {
 	fn hostname(&self) -> &str;
 	fn use_vello(&self) -> bool;
+	fn viewport_antialiasing(&self) -> Antialiasing;
+}
+
+/// How smoothly the edges of shapes are drawn in the viewport, trading off quality for rendering speed. Exports are always fully antialiased.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, specta::Type)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Antialiasing {
+	/// Edges are drawn aliased, which is the fastest.
+	Off = 0,
+	/// Edges are smoothed with fewer samples.
+	Fast = 1,
+	/// Edges are smoothed as precisely as the renderer allows.
+	#[default]
+	Full = 2,
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
@@ -265,6 +280,10 @@ pub struct RenderConfig {
 	pub view_mode: ViewMode,
 	pub hide_artboards: bool,
 	pub for_export: bool,
+	/// Whether text is rendered in subsets of its fonts embedded in the SVG, rather than as the outlines of its glyphs.
+	pub embed_fonts: bool,
+	/// Applied to the final composite of the viewport, but not to exports.
+	pub color_management: ColorManagement,
 }
 
 struct Logger;
@@ -285,6 +304,10 @@ impl GetEditorPreferences for DummyPreferences {
 	fn use_vello(&self) -> bool {
 		false
 	}
+
+	fn viewport_antialiasing(&self) -> Antialiasing {
+		Antialiasing::default()
+	}
 }
 
 pub struct EditorApi<Io> {
@@ -1,6 +1,12 @@
+mod color_management;
+mod embedded_text;
+mod image_cache;
+mod layer_cache;
+mod level_of_detail;
 mod quad;
 mod rect;
 
+use crate::consts::{LAYER_OUTLINE_STROKE_COLOR, LAYER_OUTLINE_STROKE_WEIGHT};
 use crate::raster::image::ImageFrameTable;
 use crate::raster::{BlendMode, Image};
 use crate::transform::{Footprint, Transform};
@@ -8,8 +14,8 @@ use crate::uuid::{NodeId, generate_uuid};
 use crate::vector::style::{Fill, Stroke, ViewMode};
 use crate::vector::{PointId, VectorDataTable};
 use crate::{Artboard, ArtboardGroupTable, Color, GraphicElement, GraphicGroupTable, RasterFrame};
-use base64::Engine;
 use bezier_rs::Subpath;
+pub use color_management::{ColorManagement, ColorProfile, ColorVisionDeficiency, ProofProfile};
 use dyn_any::DynAny;
 use glam::{DAffine2, DMat2, DVec2};
 use num_traits::Zero;
@@ -42,6 +48,10 @@ impl ClickTarget {
 		self.bounding_box
 	}
 
+	pub fn stroke_width(&self) -> f64 {
+		self.stroke_width
+	}
+
 	pub fn bounding_box_with_transform(&self, transform: DAffine2) -> Option<[DVec2; 2]> {
 		self.bounding_box.map(|[a, b]| [transform.transform_point2(a), transform.transform_point2(b)])
 	}
@@ -119,6 +129,8 @@ pub struct SvgRender {
 	pub svg: Vec<SvgSegment>,
 	pub svg_defs: String,
 	pub transform: DAffine2,
+	/// Combined transform of the enclosing groups, which is applied by their `<g>` tags rather than to the rendered content itself.
+	pub group_transform: DAffine2,
 	pub image_data: Vec<(u64, Image<Color>)>,
 	indent: usize,
 }
@@ -129,6 +141,7 @@ impl SvgRender {
 			svg: Vec::default(),
 			svg_defs: String::new(),
 			transform: DAffine2::IDENTITY,
+			group_transform: DAffine2::IDENTITY,
 			image_data: Vec::new(),
 			indent: 0,
 		}
@@ -226,6 +239,11 @@ pub struct RenderParams {
 	pub hide_artboards: bool,
 	/// Are we exporting? Causes the text above an artboard to be hidden.
 	pub for_export: bool,
+	/// Transform from document space to viewport pixels, used to simplify dense paths whose detail is too small to be seen.
+	/// Paths are rendered at full detail when this is `None`, such as when exporting.
+	pub viewport_transform: Option<DAffine2>,
+	/// Whether text is rendered in subsets of its fonts embedded in the SVG, rather than as the outlines of its glyphs, where it looks the same either way.
+	pub embed_fonts: bool,
 }
 
 impl RenderParams {
@@ -236,6 +254,8 @@ impl RenderParams {
 			thumbnail,
 			hide_artboards,
 			for_export,
+			viewport_transform: None,
+			embed_fonts: false,
 		}
 	}
 }
@@ -301,6 +321,9 @@ pub trait GraphicElementRendered {
 impl GraphicElementRendered for GraphicGroupTable {
 	fn render_svg(&self, render: &mut SvgRender, render_params: &RenderParams) {
 		for instance in self.instances() {
+			// Layers are only tagged and cached when rendering the viewport
+			let layer = instance.source_node_id.filter(|_| !render_params.for_export && !render_params.thumbnail);
+
 			render.parent_tag(
 				"g",
 				|attributes| {
@@ -309,16 +332,30 @@ impl GraphicElementRendered for GraphicGroupTable {
 						attributes.push("transform", matrix);
 					}
 
-					if instance.alpha_blending.opacity < 1. {
+					// Outline mode shows only the bare geometry, without blending effects
+					let show_blending = render_params.view_mode != ViewMode::Outline;
+
+					if show_blending && instance.alpha_blending.opacity < 1. {
 						attributes.push("opacity", instance.alpha_blending.opacity.to_string());
 					}
 
-					if instance.alpha_blending.blend_mode != BlendMode::default() {
+					if show_blending && instance.alpha_blending.blend_mode != BlendMode::default() {
 						attributes.push("style", instance.alpha_blending.blend_mode.render());
 					}
+
+					// Tag each layer in the viewport so the frontend can identify which ones changed between renders
+					if let Some(layer) = layer {
+						attributes.push("data-layer", layer.to_string());
+					}
 				},
 				|render| {
-					instance.instance.render_svg(render, render_params);
+					let parent_transform = render.group_transform;
+					render.group_transform = parent_transform * *instance.transform;
+					match layer {
+						Some(layer) => layer_cache::render_layer_cached(layer, instance.instance, render, render_params),
+						None => instance.instance.render_svg(render, render_params),
+					}
+					render.group_transform = parent_transform;
 				},
 			);
 		}
@@ -429,34 +466,69 @@ impl GraphicElementRendered for VectorDataTable {
 			let layer_bounds = instance.instance.bounding_box().unwrap_or_default();
 			let transformed_bounds = instance.instance.bounding_box_with_transform(applied_stroke_transform).unwrap_or_default();
 
-			let mut path = String::new();
-			for subpath in instance.instance.stroke_bezier_paths() {
-				let _ = subpath.subpath_to_svg(&mut path, applied_stroke_transform);
+			if embedded_text::render_embedded_text(&instance, render, render_params, element_transform, applied_stroke_transform, layer_bounds, transformed_bounds) {
+				continue;
 			}
 
-			render.leaf_tag("path", |attributes| {
-				attributes.push("d", path);
-				let matrix = format_transform_matrix(element_transform);
-				if !matrix.is_empty() {
-					attributes.push("transform", matrix);
+			// Regions with a fill of their own, like the colored spans of a text, are drawn as separate paths after the rest
+			let mut paths = Vec::new();
+			if instance.instance.region_fills.is_empty() {
+				// When zoomed out far enough that a dense path has detail smaller than a pixel, render a decimated version of it instead
+				let pixels_per_unit = render_params
+					.viewport_transform
+					.map(|viewport_transform| (viewport_transform * render.group_transform * element_transform).decompose_scale().max_element());
+				let decimated_path = pixels_per_unit.and_then(|pixels_per_unit| level_of_detail::decimated_path_data(&instance.instance, applied_stroke_transform, pixels_per_unit));
+				let path = decimated_path.unwrap_or_else(|| {
+					let mut path = String::new();
+					for subpath in instance.instance.stroke_bezier_paths() {
+						let _ = subpath.subpath_to_svg(&mut path, applied_stroke_transform);
+					}
+					path
+				});
+				paths.push((path, instance.instance.style.clone()));
+			} else {
+				let subpaths_to_svg = |subpaths: &[Subpath<PointId>]| {
+					let mut path = String::new();
+					for subpath in subpaths {
+						let _ = subpath.subpath_to_svg(&mut path, applied_stroke_transform);
+					}
+					path
+				};
+
+				let (style_fill, own_fills) = instance.instance.subpaths_by_fill();
+				paths.push((subpaths_to_svg(&style_fill), instance.instance.style.clone()));
+				for (fill, subpaths) in own_fills {
+					let mut style = instance.instance.style.clone();
+					style.set_fill(fill.clone());
+					paths.push((subpaths_to_svg(&subpaths), style));
 				}
+			}
 
-				let defs = &mut attributes.0.svg_defs;
+			for (path, style) in paths {
+				render.leaf_tag("path", |attributes| {
+					attributes.push("d", path);
+					let matrix = format_transform_matrix(element_transform);
+					if !matrix.is_empty() {
+						attributes.push("transform", matrix);
+					}
 
-				let fill_and_stroke = instance
-					.instance
-					.style
-					.render(render_params.view_mode, defs, element_transform, applied_stroke_transform, layer_bounds, transformed_bounds);
-				attributes.push_val(fill_and_stroke);
+					let defs = &mut attributes.0.svg_defs;
 
-				if instance.alpha_blending.opacity < 1. {
-					attributes.push("opacity", instance.alpha_blending.opacity.to_string());
-				}
+					let fill_and_stroke = style.render(render_params.view_mode, defs, element_transform, applied_stroke_transform, layer_bounds, transformed_bounds);
+					attributes.push_val(fill_and_stroke);
 
-				if instance.alpha_blending.blend_mode != BlendMode::default() {
-					attributes.push("style", instance.alpha_blending.blend_mode.render());
-				}
-			});
+					// Outline mode shows only the bare geometry, without blending effects
+					let show_blending = render_params.view_mode != ViewMode::Outline;
+
+					if show_blending && instance.alpha_blending.opacity < 1. {
+						attributes.push("opacity", instance.alpha_blending.opacity.to_string());
+					}
+
+					if show_blending && instance.alpha_blending.blend_mode != BlendMode::default() {
+						attributes.push("style", instance.alpha_blending.blend_mode.render());
+					}
+				});
+			}
 		}
 	}
 
@@ -490,62 +562,90 @@ impl GraphicElementRendered for VectorDataTable {
 				);
 			}
 
+			if embedded_text::render_embedded_text_to_vello(&instance, scene, element_transform, applied_stroke_transform) {
+				if layer {
+					scene.pop_layer();
+				}
+				continue;
+			}
+
 			let to_point = |p: DVec2| kurbo::Point::new(p.x, p.y);
 			let mut path = kurbo::BezPath::new();
 			for subpath in instance.instance.stroke_bezier_paths() {
 				subpath.to_vello_path(applied_stroke_transform, &mut path);
 			}
 
-			match instance.instance.style.fill() {
-				Fill::Solid(color) => {
-					let fill = peniko::Brush::Solid(peniko::Color::new([color.r(), color.g(), color.b(), color.a()]));
-					scene.fill(peniko::Fill::NonZero, kurbo::Affine::new(element_transform.to_cols_array()), &fill, None, &path);
-				}
-				Fill::Gradient(gradient) => {
-					let mut stops = peniko::ColorStops::new();
-					for &(offset, color) in &gradient.stops {
-						stops.push(peniko::ColorStop {
-							offset: offset as f32,
-							color: peniko::color::DynamicColor::from_alpha_color(peniko::Color::new([color.r(), color.g(), color.b(), color.a()])),
-						});
+			// Regions with a fill of their own, like the colored spans of a text, are filled apart from the rest
+			let mut fills = Vec::new();
+			if instance.instance.region_fills.is_empty() {
+				fills.push((instance.instance.style.fill(), None));
+			} else {
+				let to_vello_path = |subpaths: Vec<Subpath<PointId>>| {
+					let mut path = kurbo::BezPath::new();
+					for subpath in subpaths {
+						subpath.to_vello_path(applied_stroke_transform, &mut path);
 					}
-					// Compute bounding box of the shape to determine the gradient start and end points
-					let bounds = instance.instance.nonzero_bounding_box();
-					let bound_transform = DAffine2::from_scale_angle_translation(bounds[1] - bounds[0], 0., bounds[0]);
-
-					let inverse_parent_transform = (parent_transform.matrix2.determinant() != 0.).then(|| parent_transform.inverse()).unwrap_or_default();
-					let mod_points = inverse_parent_transform * multiplied_transform * bound_transform;
+					path
+				};
 
-					let start = mod_points.transform_point2(gradient.start);
-					let end = mod_points.transform_point2(gradient.end);
+				let (style_fill, own_fills) = instance.instance.subpaths_by_fill();
+				fills.push((instance.instance.style.fill(), Some(to_vello_path(style_fill))));
+				fills.extend(own_fills.into_iter().map(|(fill, subpaths)| (fill, Some(to_vello_path(subpaths)))));
+			}
 
-					let fill = peniko::Brush::Gradient(peniko::Gradient {
-						kind: match gradient.gradient_type {
-							GradientType::Linear => peniko::GradientKind::Linear {
-								start: to_point(start),
-								end: to_point(end),
-							},
-							GradientType::Radial => {
-								let radius = start.distance(end);
-								peniko::GradientKind::Radial {
-									start_center: to_point(start),
-									start_radius: 0.,
-									end_center: to_point(start),
-									end_radius: radius as f32,
+			for (fill, fill_path) in &fills {
+				let fill_path = fill_path.as_ref().unwrap_or(&path);
+				match fill {
+					Fill::Solid(color) => {
+						let fill = peniko::Brush::Solid(peniko::Color::new([color.r(), color.g(), color.b(), color.a()]));
+						scene.fill(peniko::Fill::NonZero, kurbo::Affine::new(element_transform.to_cols_array()), &fill, None, fill_path);
+					}
+					Fill::Gradient(gradient) => {
+						let mut stops = peniko::ColorStops::new();
+						for &(offset, color) in &gradient.stops {
+							stops.push(peniko::ColorStop {
+								offset: offset as f32,
+								color: peniko::color::DynamicColor::from_alpha_color(peniko::Color::new([color.r(), color.g(), color.b(), color.a()])),
+							});
+						}
+						// Compute bounding box of the shape to determine the gradient start and end points
+						let bounds = instance.instance.nonzero_bounding_box();
+						let bound_transform = DAffine2::from_scale_angle_translation(bounds[1] - bounds[0], 0., bounds[0]);
+
+						let inverse_parent_transform = (parent_transform.matrix2.determinant() != 0.).then(|| parent_transform.inverse()).unwrap_or_default();
+						let mod_points = inverse_parent_transform * multiplied_transform * bound_transform;
+
+						let start = mod_points.transform_point2(gradient.start);
+						let end = mod_points.transform_point2(gradient.end);
+
+						let fill = peniko::Brush::Gradient(peniko::Gradient {
+							kind: match gradient.gradient_type {
+								GradientType::Linear => peniko::GradientKind::Linear {
+									start: to_point(start),
+									end: to_point(end),
+								},
+								GradientType::Radial => {
+									let radius = start.distance(end);
+									peniko::GradientKind::Radial {
+										start_center: to_point(start),
+										start_radius: 0.,
+										end_center: to_point(start),
+										end_radius: radius as f32,
+									}
 								}
-							}
-						},
-						stops,
-						..Default::default()
-					});
-					// Vello does `element_transform * brush_transform` internally. We don't want element_transform to have any impact so we need to left multiply by the inverse.
-					// This makes the final internal brush transform equal to `parent_transform`, allowing you to stretch a gradient by transforming the parent folder.
-					let inverse_element_transform = (element_transform.matrix2.determinant() != 0.).then(|| element_transform.inverse()).unwrap_or_default();
-					let brush_transform = kurbo::Affine::new((inverse_element_transform * parent_transform).to_cols_array());
-					scene.fill(peniko::Fill::NonZero, kurbo::Affine::new(element_transform.to_cols_array()), &fill, Some(brush_transform), &path);
-				}
-				Fill::None => (),
-			};
+							},
+							stops,
+							..Default::default()
+						});
+						// Vello does `element_transform * brush_transform` internally. We don't want element_transform to have any impact so we need to left multiply by the inverse.
+						// This makes the final internal brush transform equal to `parent_transform`, allowing you to stretch a gradient by transforming the parent folder.
+						let inverse_element_transform = (element_transform.matrix2.determinant() != 0.).then(|| element_transform.inverse()).unwrap_or_default();
+						let brush_transform = kurbo::Affine::new((inverse_element_transform * parent_transform).to_cols_array());
+						scene.fill(peniko::Fill::NonZero, kurbo::Affine::new(element_transform.to_cols_array()), &fill, Some(brush_transform), fill_path);
+					}
+					Fill::None => (),
+				};
+			}
 
 			if let Some(stroke) = instance.instance.style.stroke() {
 				let color = match stroke.color {
@@ -800,7 +900,7 @@ impl GraphicElementRendered for ArtboardGroupTable {
 }
 
 impl GraphicElementRendered for ImageFrameTable<Color> {
-	fn render_svg(&self, render: &mut SvgRender, _render_params: &RenderParams) {
+	fn render_svg(&self, render: &mut SvgRender, render_params: &RenderParams) {
 		for instance in self.instances() {
 			let transform = *instance.transform * render.transform;
 
@@ -809,14 +909,19 @@ impl GraphicElementRendered for ImageFrameTable<Color> {
 				return;
 			}
 
-			let base64_string = image.base64_string.clone().unwrap_or_else(|| {
-				let output = image.to_png();
-				let preamble = "data:image/png;base64,";
-				let mut base64_string = String::with_capacity(preamble.len() + output.len() * 4);
-				base64_string.push_str(preamble);
-				base64::engine::general_purpose::STANDARD.encode_string(output, &mut base64_string);
-				base64_string
-			});
+			// Outline mode shows only the outline of the image's bounds instead of its pixels
+			if render_params.view_mode == ViewMode::Outline {
+				let corners = [DVec2::ZERO, DVec2::X, DVec2::ONE, DVec2::Y].map(|corner| transform.transform_point2(corner));
+				render.leaf_tag("path", |attributes| {
+					let path = corners.iter().map(|corner| format!("{},{}", corner.x, corner.y)).collect::<Vec<_>>().join(" L");
+					attributes.push("d", format!("M{path} Z"));
+					attributes.push("fill", "none");
+					attributes.push_val(Stroke::new(Some(LAYER_OUTLINE_STROKE_COLOR), LAYER_OUTLINE_STROKE_WEIGHT).render());
+				});
+				continue;
+			}
+
+			let base64_string = image_cache::image_data_url(image);
 			render.leaf_tag("image", |attributes| {
 				attributes.push("width", 1.to_string());
 				attributes.push("height", 1.to_string());
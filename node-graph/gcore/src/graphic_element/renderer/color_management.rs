@@ -0,0 +1,393 @@
+use crate::Color;
+use dyn_any::DynAny;
+use std::fmt::Write;
+
+/// The color space which the document's colors are specified in, which is converted to the sRGB display when compositing the viewport.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, DynAny, specta::Type)]
+pub enum ColorProfile {
+	#[default]
+	Srgb,
+	DisplayP3,
+	AdobeRgb,
+}
+
+impl ColorProfile {
+	pub const ALL: [Self; 3] = [Self::Srgb, Self::DisplayP3, Self::AdobeRgb];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Srgb => "sRGB",
+			Self::DisplayP3 => "Display P3",
+			Self::AdobeRgb => "Adobe RGB (1998)",
+		}
+	}
+
+	/// An ICC profile (version 2.1, built from the primaries and transfer curve) describing this color space, which is embedded in exported images so other software reads their colors in it.
+	pub fn icc_profile(self) -> Vec<u8> {
+		// The colorants are the primaries in the profile connection space, adapted from the D65 white point to D50 with the Bradford transform
+		let (colorants, transfer) = match self {
+			Self::Srgb => (
+				[[0.436_075, 0.222_504, 0.013_932], [0.385_065, 0.716_879, 0.097_105], [0.143_080, 0.060_617, 0.714_173]],
+				IccTransfer::Srgb,
+			),
+			Self::DisplayP3 => (
+				[[0.515_121, 0.241_196, -0.001_053], [0.291_977, 0.692_245, 0.041_885], [0.157_104, 0.066_574, 0.784_073]],
+				IccTransfer::Srgb,
+			),
+			Self::AdobeRgb => (
+				[[0.609_756, 0.311_124, 0.019_481], [0.205_240, 0.625_656, 0.060_890], [0.149_224, 0.063_220, 0.744_839]],
+				IccTransfer::Gamma(563. / 256.),
+			),
+		};
+
+		// Tags of the same contents share them, as the three transfer curves do
+		let data = [
+			icc_text_description(self.name()),
+			icc_text("No copyright, use freely"),
+			icc_xyz(D65_WHITE),
+			icc_xyz(colorants[0]),
+			icc_xyz(colorants[1]),
+			icc_xyz(colorants[2]),
+			icc_curve(transfer),
+		];
+		let tags = [
+			(b"desc", 0),
+			(b"cprt", 1),
+			(b"wtpt", 2),
+			(b"rXYZ", 3),
+			(b"gXYZ", 4),
+			(b"bXYZ", 5),
+			(b"rTRC", 6),
+			(b"gTRC", 6),
+			(b"bTRC", 6),
+		];
+
+		let mut offsets = Vec::with_capacity(data.len());
+		let mut offset = ICC_HEADER_LENGTH + 4 + tags.len() * 12;
+		for tag_data in &data {
+			offsets.push(offset);
+			offset += tag_data.len().next_multiple_of(4);
+		}
+		let length = offset;
+
+		let mut profile = Vec::with_capacity(length);
+		profile.extend((length as u32).to_be_bytes());
+		profile.extend([0; 4]); // Preferred CMM
+		profile.extend([0x02, 0x10, 0, 0]); // Version 2.1
+		profile.extend(b"mntrRGB XYZ ");
+		profile.extend([0; 12]); // Creation date
+		profile.extend(b"acsp");
+		profile.extend([0; 28]); // Platform, flags, manufacturer, model, and attributes
+		profile.extend([0; 4]); // Perceptual rendering intent
+		profile.extend(D50_ILLUMINANT.into_iter().flat_map(u32::to_be_bytes));
+		profile.resize(ICC_HEADER_LENGTH, 0);
+
+		profile.extend((tags.len() as u32).to_be_bytes());
+		for (signature, index) in tags {
+			profile.extend(signature);
+			profile.extend((offsets[index] as u32).to_be_bytes());
+			profile.extend((data[index].len() as u32).to_be_bytes());
+		}
+		for tag_data in data {
+			let padded_length = profile.len() + tag_data.len().next_multiple_of(4);
+			profile.extend(tag_data);
+			profile.resize(padded_length, 0);
+		}
+		profile
+	}
+
+	/// The matrix from this profile's linear RGB primaries to linear sRGB, or `None` if it is already sRGB.
+	/// The transfer curves of the wide gamut profiles are treated as the sRGB curve, which they match closely enough for display.
+	fn to_linear_srgb(self) -> Option<[[f32; 3]; 3]> {
+		match self {
+			Self::Srgb => None,
+			Self::DisplayP3 => Some([[1.224_940, -0.224_940, 0.], [-0.042_057, 1.042_057, 0.], [-0.019_638, -0.078_636, 1.098_274]]),
+			Self::AdobeRgb => Some([[1.398_283, -0.398_283, 0.], [0., 1., 0.], [0., -0.042_928, 1.042_928]]),
+		}
+	}
+}
+
+const ICC_HEADER_LENGTH: usize = 128;
+/// The D50 white of the ICC profile connection space, in the exact s15Fixed16 encoding given by the ICC specification.
+const D50_ILLUMINANT: [u32; 3] = [0x0000_f6d6, 0x0001_0000, 0x0000_d32d];
+const D65_WHITE: [f64; 3] = [0.950_455, 1., 1.089_058];
+
+/// The curve an ICC profile decodes its RGB values to linear light with.
+#[derive(Debug, Clone, Copy)]
+enum IccTransfer {
+	/// The piecewise sRGB curve, which is given as a table of samples since version 2 profiles can't describe it exactly.
+	Srgb,
+	Gamma(f64),
+}
+
+/// A `textDescriptionType` tag with only the ASCII description.
+fn icc_text_description(text: &str) -> Vec<u8> {
+	let mut data = b"desc\0\0\0\0".to_vec();
+	data.extend((text.len() as u32 + 1).to_be_bytes());
+	data.extend(text.as_bytes());
+	data.push(0);
+	// The empty Unicode description's language and length, then the empty ScriptCode description's code, length, and fixed 67 bytes
+	data.extend([0; 4 + 4 + 2 + 1 + 67]);
+	data
+}
+
+fn icc_text(text: &str) -> Vec<u8> {
+	let mut data = b"text\0\0\0\0".to_vec();
+	data.extend(text.as_bytes());
+	data.push(0);
+	data
+}
+
+fn icc_xyz(xyz: [f64; 3]) -> Vec<u8> {
+	let mut data = b"XYZ \0\0\0\0".to_vec();
+	data.extend(xyz.into_iter().flat_map(|value| ((value * 65536.).round() as i32).to_be_bytes()));
+	data
+}
+
+fn icc_curve(transfer: IccTransfer) -> Vec<u8> {
+	const SAMPLES: usize = 1024;
+
+	let mut data = b"curv\0\0\0\0".to_vec();
+	match transfer {
+		IccTransfer::Srgb => {
+			data.extend((SAMPLES as u32).to_be_bytes());
+			for index in 0..SAMPLES {
+				let value = index as f64 / (SAMPLES - 1) as f64;
+				let linear = if value <= 0.04045 { value / 12.92 } else { ((value + 0.055) / 1.055).powf(2.4) };
+				data.extend(((linear * 65535.).round() as u16).to_be_bytes());
+			}
+		}
+		// A single entry is the exponent, as an unsigned 8.8 fixed point number
+		IccTransfer::Gamma(gamma) => {
+			data.extend(1_u32.to_be_bytes());
+			data.extend(((gamma * 256.).round() as u16).to_be_bytes());
+		}
+	}
+	data
+}
+
+/// A printing condition simulated on screen when proofing colors, so the viewport previews how the artwork will look once printed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, DynAny, specta::Type)]
+pub enum ProofProfile {
+	#[default]
+	CoatedCmyk,
+	UncoatedCmyk,
+}
+
+impl ProofProfile {
+	pub const ALL: [Self; 2] = [Self::CoatedCmyk, Self::UncoatedCmyk];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::CoatedCmyk => "Coated CMYK",
+			Self::UncoatedCmyk => "Uncoated CMYK",
+		}
+	}
+
+	/// How much of the saturation of the display's gamut the inks can reproduce.
+	fn saturation(self) -> f32 {
+		match self {
+			Self::CoatedCmyk => 0.85,
+			Self::UncoatedCmyk => 0.7,
+		}
+	}
+
+	/// The linear color of the bare paper, which is the brightest white that can be printed.
+	fn paper_white(self) -> [f32; 3] {
+		match self {
+			Self::CoatedCmyk => [0.94, 0.93, 0.9],
+			Self::UncoatedCmyk => [0.9, 0.89, 0.85],
+		}
+	}
+
+	/// The linear color of the densest black the inks can print.
+	fn ink_black(self) -> [f32; 3] {
+		match self {
+			Self::CoatedCmyk => [0.03, 0.03, 0.035],
+			Self::UncoatedCmyk => [0.07, 0.07, 0.075],
+		}
+	}
+}
+
+/// A color vision deficiency simulated on screen, so the artwork can be checked for legibility by people who see colors differently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, DynAny, specta::Type)]
+pub enum ColorVisionDeficiency {
+	Protanopia,
+	Deuteranopia,
+	Tritanopia,
+	Achromatopsia,
+}
+
+impl ColorVisionDeficiency {
+	pub const ALL: [Self; 4] = [Self::Protanopia, Self::Deuteranopia, Self::Tritanopia, Self::Achromatopsia];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Protanopia => "Protanopia (Red-Blind)",
+			Self::Deuteranopia => "Deuteranopia (Green-Blind)",
+			Self::Tritanopia => "Tritanopia (Blue-Blind)",
+			Self::Achromatopsia => "Achromatopsia (Monochrome)",
+		}
+	}
+
+	/// The matrix simulating the deficiency in linear sRGB.
+	/// The dichromacies use the full severity matrices of Machado, Oliveira, and Fernandes (2009), and achromatopsia leaves only the luminance.
+	fn matrix(self) -> [[f32; 3]; 3] {
+		match self {
+			Self::Protanopia => [[0.152_286, 1.052_583, -0.204_868], [0.114_503, 0.786_281, 0.099_216], [-0.003_882, -0.048_116, 1.051_998]],
+			Self::Deuteranopia => [[0.367_322, 0.860_646, -0.227_968], [0.280_085, 0.672_501, 0.047_413], [-0.011_820, 0.042_940, 0.968_881]],
+			Self::Tritanopia => [[1.255_528, -0.076_749, -0.178_779], [-0.078_411, 0.930_809, 0.147_602], [0.004_733, 0.691_367, 0.303_900]],
+			Self::Achromatopsia => [[0.2126, 0.7152, 0.0722]; 3],
+		}
+	}
+}
+
+/// The color transform applied to the final composite of the viewport so its colors match the document's color profile, and optionally a simulated print and color vision deficiency.
+/// This is an approximation using analytic models of each profile rather than ICC profiles, chosen because it can be applied cheaply as an SVG filter.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, DynAny)]
+pub struct ColorManagement {
+	pub profile: ColorProfile,
+	pub proof: Option<ProofProfile>,
+	pub color_vision: Option<ColorVisionDeficiency>,
+}
+
+impl ColorManagement {
+	pub const SVG_FILTER_ID: &'static str = "color-management";
+
+	/// Whether the transform leaves colors unchanged, in which case it can be skipped entirely.
+	pub fn is_identity(&self) -> bool {
+		self.profile == ColorProfile::Srgb && self.proof.is_none() && self.color_vision.is_none()
+	}
+
+	/// Applies the transform to a color in linear sRGB, matching the result of the SVG filter.
+	pub fn transform_color(&self, color: Color) -> Color {
+		let mut rgb = [color.r(), color.g(), color.b()];
+
+		if let Some(matrix) = self.profile.to_linear_srgb() {
+			rgb = matrix.map(|row| (row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2]).clamp(0., 1.));
+		}
+
+		if let Some(proof) = self.proof {
+			let saturation = saturation_matrix(proof.saturation());
+			rgb = saturation.map(|row| (row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2]).clamp(0., 1.));
+
+			let (white, black) = (proof.paper_white(), proof.ink_black());
+			rgb = [0, 1, 2].map(|channel| black[channel] + (white[channel] - black[channel]) * rgb[channel]);
+		}
+
+		if let Some(color_vision) = self.color_vision {
+			rgb = color_vision.matrix().map(|row| (row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2]).clamp(0., 1.));
+		}
+
+		Color::from_rgbaf32_unchecked(rgb[0], rgb[1], rgb[2], color.a())
+	}
+
+	/// The SVG `<filter>` definition applying this transform in linear light, or `None` if it is the identity.
+	pub fn svg_filter(&self) -> Option<String> {
+		if self.is_identity() {
+			return None;
+		}
+
+		let mut filter = format!(r#"<filter id="{}" color-interpolation-filters="linearRGB">"#, Self::SVG_FILTER_ID);
+
+		if let Some(matrix) = self.profile.to_linear_srgb() {
+			let values = matrix.iter().map(|row| format!("{} {} {} 0 0", row[0], row[1], row[2])).collect::<Vec<_>>().join(" ");
+			let _ = write!(filter, r#"<feColorMatrix type="matrix" values="{values} 0 0 0 1 0" />"#);
+		}
+
+		if let Some(proof) = self.proof {
+			let _ = write!(filter, r#"<feColorMatrix type="saturate" values="{}" />"#, proof.saturation());
+
+			let (white, black) = (proof.paper_white(), proof.ink_black());
+			filter.push_str("<feComponentTransfer>");
+			for (channel, function) in ["feFuncR", "feFuncG", "feFuncB"].into_iter().enumerate() {
+				let _ = write!(filter, r#"<{function} type="linear" slope="{}" intercept="{}" />"#, white[channel] - black[channel], black[channel]);
+			}
+			filter.push_str("</feComponentTransfer>");
+		}
+
+		if let Some(color_vision) = self.color_vision {
+			let values = color_vision.matrix().iter().map(|row| format!("{} {} {} 0 0", row[0], row[1], row[2])).collect::<Vec<_>>().join(" ");
+			let _ = write!(filter, r#"<feColorMatrix type="matrix" values="{values} 0 0 0 1 0" />"#);
+		}
+
+		filter.push_str("</filter>");
+		Some(filter)
+	}
+}
+
+/// The matrix used by the SVG `saturate` color matrix filter, so both paths give the same result.
+fn saturation_matrix(s: f32) -> [[f32; 3]; 3] {
+	[
+		[0.213 + 0.787 * s, 0.715 - 0.715 * s, 0.072 - 0.072 * s],
+		[0.213 - 0.213 * s, 0.715 + 0.285 * s, 0.072 - 0.072 * s],
+		[0.213 - 0.213 * s, 0.715 - 0.715 * s, 0.072 + 0.928 * s],
+	]
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn srgb_without_proofing_is_identity() {
+		let color_management = ColorManagement::default();
+		let color = Color::from_rgbaf32_unchecked(0.2, 0.5, 0.8, 0.6);
+
+		assert!(color_management.is_identity());
+		assert!(color_management.svg_filter().is_none());
+		assert_eq!(color_management.transform_color(color), color);
+	}
+
+	#[test]
+	fn proofing_compresses_to_paper_and_ink() {
+		let color_management = ColorManagement {
+			profile: ColorProfile::DisplayP3,
+			proof: Some(ProofProfile::UncoatedCmyk),
+			color_vision: None,
+		};
+
+		let white = color_management.transform_color(Color::WHITE);
+		let black = color_management.transform_color(Color::BLACK);
+		for (channel, (white, black)) in [(white.r(), black.r()), (white.g(), black.g()), (white.b(), black.b())].into_iter().enumerate() {
+			assert!((white - ProofProfile::UncoatedCmyk.paper_white()[channel]).abs() < 1e-3);
+			assert!((black - ProofProfile::UncoatedCmyk.ink_black()[channel]).abs() < 1e-3);
+		}
+		assert!(color_management.svg_filter().is_some_and(|filter| filter.contains("feComponentTransfer")));
+	}
+
+	#[test]
+	fn icc_profiles_are_well_formed() {
+		for profile in ColorProfile::ALL {
+			let icc = profile.icc_profile();
+			let read_u32 = |offset: usize| u32::from_be_bytes(icc[offset..offset + 4].try_into().unwrap()) as usize;
+
+			assert_eq!(read_u32(0), icc.len());
+			assert_eq!(&icc[36..40], b"acsp");
+			let tag_count = read_u32(ICC_HEADER_LENGTH);
+			assert_eq!(tag_count, 9);
+
+			for tag in 0..tag_count {
+				let entry = ICC_HEADER_LENGTH + 4 + tag * 12;
+				let (offset, length) = (read_u32(entry + 4), read_u32(entry + 8));
+				assert_eq!(offset % 4, 0);
+				assert!(offset + length <= icc.len());
+			}
+		}
+	}
+
+	#[test]
+	fn achromatopsia_leaves_only_luminance() {
+		let color_management = ColorManagement {
+			color_vision: Some(ColorVisionDeficiency::Achromatopsia),
+			..Default::default()
+		};
+
+		let gray = color_management.transform_color(Color::from_rgbaf32_unchecked(0., 1., 0., 1.));
+		assert!((gray.r() - 0.7152).abs() < 1e-4);
+		assert_eq!(gray.r(), gray.g());
+		assert_eq!(gray.g(), gray.b());
+		assert!(!color_management.is_identity());
+		assert!(color_management.svg_filter().is_some_and(|filter| filter.contains("0.7152")));
+	}
+}
@@ -0,0 +1,197 @@
+use super::{RenderParams, SvgRender, format_transform_matrix};
+use crate::Color;
+use crate::instances::Instance;
+use crate::raster::BlendMode;
+use crate::vector::style::{Fill, ViewMode};
+use crate::vector::{EmbeddedText, VectorData};
+use base64::Engine;
+use glam::{DAffine2, DVec2};
+use std::fmt::Write;
+
+/// Glyphs next to each other in the same font and color, turned and scaled alike, which are drawn together.
+struct GlyphRun {
+	/// The index of the font in [`EmbeddedText::fonts`].
+	font: usize,
+	color: Color,
+	/// Turns and scales the glyphs of the run, as drawn at a font size of their font's units per em with the y axis pointing down.
+	linear: DAffine2,
+	/// The ID and character of each glyph in the subset of its font, and the position of its origin before it's turned and scaled by the run.
+	glyphs: Vec<(u16, char, DVec2)>,
+}
+
+/// The runs of the glyphs of the text, where it looks the same drawn with its fonts as it does with its outlines.
+///
+/// It wouldn't with a stroke or a gradient fill, or once a node has changed the outlines from those it was typeset with, which leaves the outlines to be rendered as paths instead.
+/// Glyphs without a color of their own take the fill of the text, so they aren't drawn at all without one.
+fn glyph_runs(vector_data: &VectorData, transform: DAffine2) -> Option<(&EmbeddedText, Vec<GlyphRun>, Option<Color>)> {
+	let embedded_text = vector_data.embedded_text.as_deref()?;
+	if vector_data.style.stroke().is_some_and(|stroke| stroke.weight() > 0.) || transform.matrix2.determinant() == 0. {
+		return None;
+	}
+	// A gradient fill can't be shared by glyphs which are each placed by their own transform
+	let style_color = match vector_data.style.fill() {
+		Fill::None => None,
+		Fill::Solid(color) => Some(*color),
+		Fill::Gradient(_) => return None,
+	};
+	if !embedded_text.matches(vector_data) {
+		return None;
+	}
+
+	let mut runs: Vec<GlyphRun> = Vec::new();
+	for glyph in &embedded_text.glyphs {
+		let Some(color) = glyph.color.or(style_color) else { continue };
+		// Fonts are drawn with their y axis pointing down, so it's flipped to match the glyphs placed with it pointing up
+		let glyph_transform = transform * glyph.transform * DAffine2::from_scale(DVec2::new(1., -1.));
+		let linear = DAffine2::from_mat2(glyph_transform.matrix2);
+
+		match runs.last_mut() {
+			Some(run) if run.font == glyph.font && run.color == color && run.linear == linear => {}
+			_ => runs.push(GlyphRun {
+				font: glyph.font,
+				color,
+				linear,
+				glyphs: Vec::new(),
+			}),
+		}
+		let run = runs.last_mut().expect("A run was just pushed");
+		run.glyphs.push((glyph.id, glyph.character, linear.inverse().transform_point2(glyph_transform.translation)));
+	}
+
+	Some((embedded_text, runs, style_color))
+}
+
+/// Renders the text of the vector data as `<text>` in the subsets of its fonts embedded in the SVG, returning whether it was rendered this way.
+///
+/// The text is only rendered like this when fonts are being embedded and it would look the same as its outlines, which it also wouldn't in outline mode.
+pub fn render_embedded_text(
+	instance: &Instance<VectorData>,
+	render: &mut SvgRender,
+	render_params: &RenderParams,
+	element_transform: DAffine2,
+	applied_stroke_transform: DAffine2,
+	layer_bounds: [DVec2; 2],
+	transformed_bounds: [DVec2; 2],
+) -> bool {
+	if !render_params.embed_fonts || render_params.view_mode == ViewMode::Outline {
+		return false;
+	}
+	let Some((embedded_text, runs, style_color)) = glyph_runs(instance.instance, element_transform * applied_stroke_transform) else {
+		return false;
+	};
+
+	for font in &embedded_text.fonts {
+		if !render.svg_defs.contains(&font.family) {
+			let data = base64::engine::general_purpose::STANDARD.encode(&font.data);
+			let _ = write!(render.svg_defs, r#"<style>@font-face{{font-family:"{}";src:url(data:font/ttf;base64,{data})}}</style>"#, font.family);
+		}
+	}
+
+	let fill = |color| Fill::Solid(color).render(&mut String::new(), element_transform, applied_stroke_transform, layer_bounds, transformed_bounds);
+
+	render.parent_tag(
+		"g",
+		|attributes| {
+			if instance.alpha_blending.opacity < 1. {
+				attributes.push("opacity", instance.alpha_blending.opacity.to_string());
+			}
+			if instance.alpha_blending.blend_mode != BlendMode::default() {
+				attributes.push("style", instance.alpha_blending.blend_mode.render());
+			}
+		},
+		|render| {
+			// Each character of a run's text is given its own position
+			for run in &runs {
+				let font = &embedded_text.fonts[run.font];
+
+				let mut text = String::from("<text");
+				let matrix = format_transform_matrix(run.linear);
+				if !matrix.is_empty() {
+					let _ = write!(text, r#" transform="{matrix}""#);
+				}
+				let x: Vec<_> = run.glyphs.iter().map(|(.., position)| position.x.to_string()).collect();
+				let y: Vec<_> = run.glyphs.iter().map(|(.., position)| position.y.to_string()).collect();
+				let _ = write!(
+					text,
+					r#" x="{}" y="{}" font-family="{}" font-size="{}"{}>"#,
+					x.join(" "),
+					y.join(" "),
+					font.family,
+					font.units_per_em,
+					fill(run.color)
+				);
+				for (_, character, _) in &run.glyphs {
+					let _ = write!(text, "&#x{:X};", *character as u32);
+				}
+				text.push_str("</text>");
+				render.leaf_node(text);
+			}
+
+			for (subpath, color) in &embedded_text.decorations {
+				let Some(color) = color.or(style_color) else { continue };
+				render.leaf_tag("path", |attributes| {
+					let mut path = String::new();
+					let _ = subpath.subpath_to_svg(&mut path, applied_stroke_transform);
+					attributes.push("d", path);
+					let matrix = format_transform_matrix(element_transform);
+					if !matrix.is_empty() {
+						attributes.push("transform", matrix);
+					}
+					attributes.push_val(fill(color));
+				});
+			}
+		},
+	);
+
+	true
+}
+
+/// Draws the text of the vector data with the subsets of its fonts, returning whether it was drawn this way.
+///
+/// Vello rasterizes the glyphs on the GPU, keeping the outline of each glyph of a font from one frame to the next so repeated and unchanged glyphs aren't encoded again.
+/// Where the text wouldn't look the same as its outlines, they're left to be drawn as paths instead.
+#[cfg(feature = "vello")]
+pub fn render_embedded_text_to_vello(instance: &Instance<VectorData>, scene: &mut vello::Scene, element_transform: DAffine2, applied_stroke_transform: DAffine2) -> bool {
+	use std::hash::{DefaultHasher, Hash, Hasher};
+	use std::sync::Arc;
+	use vello::{kurbo, peniko};
+
+	let Some((embedded_text, runs, style_color)) = glyph_runs(instance.instance, element_transform * applied_stroke_transform) else {
+		return false;
+	};
+	let brush = |color: Color| peniko::Brush::Solid(peniko::Color::new([color.r(), color.g(), color.b(), color.a()]));
+
+	// Each font keeps the same blob ID between frames, which Vello's cache of glyph outlines is keyed by
+	let fonts: Vec<_> = embedded_text
+		.fonts
+		.iter()
+		.map(|font| {
+			let mut hasher = DefaultHasher::new();
+			font.family.hash(&mut hasher);
+			peniko::Font::new(peniko::Blob::from_raw_parts(Arc::new(font.data.clone()), hasher.finish()), 0)
+		})
+		.collect();
+
+	for run in &runs {
+		let glyphs = run.glyphs.iter().map(|&(id, _, position)| vello::Glyph {
+			id: id as u32,
+			x: position.x as f32,
+			y: position.y as f32,
+		});
+		scene
+			.draw_glyphs(&fonts[run.font])
+			.font_size(embedded_text.fonts[run.font].units_per_em as f32)
+			.transform(kurbo::Affine::new(run.linear.to_cols_array()))
+			.brush(&brush(run.color))
+			.draw(peniko::Fill::NonZero, glyphs);
+	}
+
+	for (subpath, color) in &embedded_text.decorations {
+		let Some(color) = color.or(style_color) else { continue };
+		let mut path = kurbo::BezPath::new();
+		subpath.to_vello_path(applied_stroke_transform, &mut path);
+		scene.fill(peniko::Fill::NonZero, kurbo::Affine::new(element_transform.to_cols_array()), &brush(color), None, &path);
+	}
+
+	true
+}
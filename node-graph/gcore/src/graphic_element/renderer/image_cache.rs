@@ -0,0 +1,35 @@
+use crate::Color;
+use crate::memory_budget::{CacheCategory, LruCache};
+use crate::raster::Image;
+use crate::raster::image::ImageContentId;
+use base64::Engine;
+use std::sync::{LazyLock, Mutex};
+
+/// The PNG data URLs of recently rendered images, keyed by the ID of their pixels, so an image only has to be encoded again after it's evicted.
+static ENCODED_IMAGES: LazyLock<Mutex<LruCache<ImageContentId, String>>> = LazyLock::new(|| Mutex::new(LruCache::new(CacheCategory::Images)));
+
+/// Gets the image as a PNG data URL for the `href` of an SVG `<image>`, encoding it only if it isn't already cached.
+pub fn image_data_url(image: &Image<Color>) -> String {
+	if let Some(base64_string) = &image.base64_string {
+		return base64_string.clone();
+	}
+
+	let key = image.content_id;
+	if let Some(data_url) = lock().get(&key) {
+		return data_url.clone();
+	}
+
+	// The lock isn't held while encoding, which can take a while for large images
+	let output = image.to_png();
+	let preamble = "data:image/png;base64,";
+	let mut data_url = String::with_capacity(preamble.len() + output.len() * 4);
+	data_url.push_str(preamble);
+	base64::engine::general_purpose::STANDARD.encode_string(output, &mut data_url);
+
+	lock().insert(key, data_url.clone(), data_url.len());
+	data_url
+}
+
+fn lock() -> std::sync::MutexGuard<'static, LruCache<ImageContentId, String>> {
+	ENCODED_IMAGES.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
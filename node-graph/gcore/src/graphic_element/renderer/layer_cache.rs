@@ -0,0 +1,122 @@
+use super::{GraphicElementRendered, RenderParams, SvgRender, SvgSegment};
+use crate::instances::{Instance, Instances};
+use crate::memory_budget::{CacheCategory, LruCache};
+use crate::uuid::NodeId;
+use crate::{GraphicElement, RasterFrame};
+use glam::DAffine2;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{LazyLock, Mutex};
+
+/// The SVG previously rendered for a layer's content, along with the key of the content and render state it was rendered from.
+struct CachedLayer {
+	key: u64,
+	svg: Vec<SvgSegment>,
+	svg_defs: String,
+}
+
+impl CachedLayer {
+	fn bytes(&self) -> usize {
+		let segments = self.svg.iter().map(|segment| match segment {
+			SvgSegment::Slice(_) => size_of::<SvgSegment>(),
+			SvgSegment::String(string) => size_of::<SvgSegment>() + string.len(),
+		});
+		segments.sum::<usize>() + self.svg_defs.len()
+	}
+}
+
+/// The most recent render of each layer, so layers which are unchanged since the previous render can be reused instead of rendered again.
+static RENDERED_LAYERS: LazyLock<Mutex<LruCache<NodeId, CachedLayer>>> = LazyLock::new(|| Mutex::new(LruCache::new(CacheCategory::Renders)));
+
+/// Renders the content of a layer, reusing its SVG from the previous render if neither the content nor anything affecting how it's rendered has changed.
+///
+/// Layers are invalidated by their content, so editing one layer only causes it (and the groups containing it) to be rendered again.
+pub fn render_layer_cached(layer: NodeId, element: &GraphicElement, render: &mut SvgRender, render_params: &RenderParams) {
+	let key = render_key(element, render, render_params);
+
+	if let Some(cached) = lock().get(&layer).filter(|cached| cached.key == key) {
+		render.svg.extend(cached.svg.iter().cloned());
+		render.svg_defs.push_str(&cached.svg_defs);
+		return;
+	}
+
+	// The lock isn't held while rendering, since the layers nested within this one are cached too
+	let (svg_start, svg_defs_start) = (render.svg.len(), render.svg_defs.len());
+	element.render_svg(render, render_params);
+
+	let svg = render.svg[svg_start..].to_vec();
+	let svg_defs = render.svg_defs[svg_defs_start..].to_string();
+	let cached = CachedLayer { key, svg, svg_defs };
+	let bytes = cached.bytes();
+	lock().insert(layer, cached, bytes);
+}
+
+fn lock() -> std::sync::MutexGuard<'static, LruCache<NodeId, CachedLayer>> {
+	RENDERED_LAYERS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Hashes everything which determines the SVG rendered for the content of a layer.
+fn render_key(element: &GraphicElement, render: &SvgRender, render_params: &RenderParams) -> u64 {
+	let mut hasher = DefaultHasher::new();
+
+	hash_element(element, &mut hasher);
+	hash_transform(render.transform, &mut hasher);
+	hash_transform(render.group_transform, &mut hasher);
+	render.indent.hash(&mut hasher);
+
+	let RenderParams {
+		view_mode,
+		culling_bounds,
+		thumbnail,
+		hide_artboards,
+		for_export,
+		viewport_transform,
+		embed_fonts,
+	} = render_params;
+	view_mode.hash(&mut hasher);
+	culling_bounds.map(|bounds| bounds.map(|corner| corner.to_array().map(f64::to_bits))).hash(&mut hasher);
+	(thumbnail, hide_artboards, for_export, embed_fonts).hash(&mut hasher);
+	// Only the scale of the viewport affects the render (by simplifying dense paths), so panning doesn't invalidate the cache
+	viewport_transform.map(|transform| transform.matrix2.to_cols_array().map(f64::to_bits)).hash(&mut hasher);
+
+	hasher.finish()
+}
+
+/// Unlike the `Hash` implementation of [`GraphicElement`], this includes the transform and blending of every nested instance, since they are part of the render.
+fn hash_element(element: &GraphicElement, state: &mut impl Hasher) {
+	core::mem::discriminant(element).hash(state);
+
+	match element {
+		GraphicElement::GraphicGroup(group) => {
+			for instance in group.instances() {
+				hash_placement(&instance, state);
+				hash_element(instance.instance, state);
+			}
+		}
+		GraphicElement::VectorData(vector_data) => hash_table(vector_data, state),
+		GraphicElement::RasterFrame(RasterFrame::ImageFrame(image)) => {
+			// Images are told apart by the ID of their pixels, which changes with any edit without hashing them, unlike the `Hash` implementation of `Image` which only samples them
+			for instance in image.instances() {
+				hash_placement(&instance, state);
+				instance.instance.content_id.hash(state);
+			}
+		}
+		GraphicElement::RasterFrame(RasterFrame::TextureFrame(texture)) => hash_table(texture, state),
+	}
+}
+
+fn hash_table<T: Hash>(table: &Instances<T>, state: &mut impl Hasher) {
+	for instance in table.instances() {
+		hash_placement(&instance, state);
+		instance.instance.hash(state);
+	}
+}
+
+fn hash_placement<T>(instance: &Instance<T>, state: &mut impl Hasher) {
+	hash_transform(*instance.transform, state);
+	instance.alpha_blending.hash(state);
+	instance.source_node_id.hash(state);
+}
+
+fn hash_transform(transform: DAffine2, state: &mut impl Hasher) {
+	transform.to_cols_array().map(f64::to_bits).hash(state);
+}
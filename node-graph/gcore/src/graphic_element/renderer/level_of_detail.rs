@@ -0,0 +1,96 @@
+use crate::memory_budget::{CacheCategory, LruCache};
+use crate::vector::{PointId, VectorData};
+use bezier_rs::{ManipulatorGroup, Subpath};
+use glam::DAffine2;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{LazyLock, Mutex};
+
+/// Distance in viewport pixels below which neighboring anchors are merged together when rendering a dense path.
+const DECIMATION_PIXEL_TOLERANCE: f64 = 0.5;
+/// Paths with fewer anchors than this are always rendered at full detail, since decimating them wouldn't save enough work to be worthwhile.
+const DECIMATION_MIN_ANCHORS: usize = 2000;
+
+/// The SVG path data of previously decimated paths, keyed by the hash of their vector data and transform along with their zoom bucket.
+static DECIMATED_PATHS: LazyLock<Mutex<LruCache<(u64, i32), String>>> = LazyLock::new(|| Mutex::new(LruCache::new(CacheCategory::Renders)));
+
+/// Builds the SVG path data for the vector data with anchors closer together than a pixel merged, or `None` if it isn't dense enough to be worth decimating.
+///
+/// The `pixels_per_unit` is the scale from the coordinates after `transform` is applied to the viewport.
+/// Zoom levels are grouped into buckets by powers of two, so the result is cached and reused while zooming within the same bucket.
+pub fn decimated_path_data(vector_data: &VectorData, transform: DAffine2, pixels_per_unit: f64) -> Option<String> {
+	if vector_data.point_domain.ids().len() < DECIMATION_MIN_ANCHORS || !pixels_per_unit.is_finite() || pixels_per_unit <= 0. {
+		return None;
+	}
+
+	// Round the tolerance down to a power of two so it never merges anchors that are visibly apart
+	let bucket = (DECIMATION_PIXEL_TOLERANCE / pixels_per_unit).log2().floor() as i32;
+	let tolerance = 2_f64.powi(bucket);
+
+	let mut hasher = DefaultHasher::new();
+	vector_data.hash(&mut hasher);
+	transform.to_cols_array().map(f64::to_bits).hash(&mut hasher);
+	let key = (hasher.finish(), bucket);
+
+	let mut cache = DECIMATED_PATHS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+	if let Some(path) = cache.get(&key) {
+		return Some(path.clone());
+	}
+
+	let mut path = String::new();
+	for mut subpath in vector_data.stroke_bezier_paths() {
+		subpath.apply_transform(transform);
+		let _ = decimate(&subpath, tolerance).subpath_to_svg(&mut path, DAffine2::IDENTITY);
+	}
+
+	cache.insert(key, path.clone(), path.len());
+
+	Some(path)
+}
+
+/// Drops each anchor lying within `tolerance` of the previously kept anchor, always keeping the first and last anchors.
+/// Segments spanning dropped anchors become straight lines, since the original handles no longer describe them.
+fn decimate(subpath: &Subpath<PointId>, tolerance: f64) -> Subpath<PointId> {
+	let groups = subpath.manipulator_groups();
+	let mut decimated: Vec<ManipulatorGroup<PointId>> = Vec::new();
+	let mut skipped = false;
+
+	for (index, group) in groups.iter().enumerate() {
+		let is_end = index == 0 || index == groups.len() - 1;
+		if !is_end && decimated.last().is_some_and(|last| last.anchor.distance_squared(group.anchor) < tolerance * tolerance) {
+			skipped = true;
+			continue;
+		}
+
+		let mut group = group.clone();
+		if skipped {
+			if let Some(last) = decimated.last_mut() {
+				last.out_handle = None;
+			}
+			group.in_handle = None;
+			skipped = false;
+		}
+		decimated.push(group);
+	}
+
+	Subpath::new(decimated, subpath.closed)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use glam::DVec2;
+
+	#[test]
+	fn decimate_merges_close_anchors() {
+		let anchors = (0..=100).map(|index| DVec2::new(index as f64 * 0.1, 0.));
+		let subpath = Subpath::<PointId>::from_anchors_linear(anchors, false);
+
+		let decimated = decimate(&subpath, 1.);
+		let anchors = decimated.manipulator_groups().iter().map(|group| group.anchor).collect::<Vec<_>>();
+
+		assert_eq!(anchors.first(), Some(&DVec2::ZERO));
+		assert_eq!(anchors.last(), Some(&DVec2::new(10., 0.)));
+		assert!(anchors.len() <= 12, "Expected roughly one anchor per unit, got {}", anchors.len());
+		assert!(anchors[..anchors.len() - 1].windows(2).all(|pair| pair[0].distance(pair[1]) >= 1.));
+	}
+}
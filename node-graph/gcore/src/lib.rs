@@ -19,7 +19,11 @@ pub mod context;
 pub mod generic;
 pub mod instances;
 pub mod logic;
+#[cfg(feature = "std")]
+pub mod memory_budget;
 pub mod ops;
+#[cfg(feature = "std")]
+pub mod parallel;
 pub mod structural;
 #[cfg(feature = "std")]
 pub mod text;
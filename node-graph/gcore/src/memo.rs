@@ -6,6 +6,48 @@ use core::ops::Deref;
 use dyn_any::DynFuture;
 use std::hash::DefaultHasher;
 use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static NODE_EVALUATIONS: AtomicU64 = AtomicU64::new(0);
+static CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+static CACHE_MISSES: AtomicU64 = AtomicU64::new(0);
+
+/// Counts of the nodes evaluated and the lookups made into the caches of memo nodes, accumulated until they're taken with [`take_evaluation_statistics`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EvaluationStatistics {
+	pub nodes_evaluated: u64,
+	pub cache_hits: u64,
+	pub cache_misses: u64,
+}
+
+impl EvaluationStatistics {
+	/// The fraction of cache lookups which found a cached value, or `None` if no caches were used.
+	pub fn cache_hit_rate(&self) -> Option<f64> {
+		let lookups = self.cache_hits + self.cache_misses;
+		(lookups > 0).then(|| self.cache_hits as f64 / lookups as f64)
+	}
+}
+
+/// Called by every node defined with `#[node_macro::node]` each time it's evaluated.
+#[doc(hidden)]
+#[inline]
+pub fn record_node_evaluation() {
+	NODE_EVALUATIONS.fetch_add(1, Ordering::Relaxed);
+}
+
+fn record_cache_lookup(hit: bool) {
+	let counter = if hit { &CACHE_HITS } else { &CACHE_MISSES };
+	counter.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Returns the counts accumulated since this was last called, resetting them to zero.
+pub fn take_evaluation_statistics() -> EvaluationStatistics {
+	EvaluationStatistics {
+		nodes_evaluated: NODE_EVALUATIONS.swap(0, Ordering::Relaxed),
+		cache_hits: CACHE_HITS.swap(0, Ordering::Relaxed),
+		cache_misses: CACHE_MISSES.swap(0, Ordering::Relaxed),
+	}
+}
 
 /// Caches the output of a given Node and acts as a proxy
 #[derive(Default)]
@@ -26,8 +68,10 @@ where
 		input.hash(&mut hasher);
 		let hash = hasher.finish();
 		if let Some(data) = self.cache.lock().as_ref().unwrap().as_ref().and_then(|data| (data.0 == hash).then_some(data.1.clone())) {
+			record_cache_lookup(true);
 			Box::pin(async move { data })
 		} else {
+			record_cache_lookup(false);
 			let fut = self.node.eval(input);
 			let cache = self.cache.clone();
 			Box::pin(async move {
@@ -70,9 +114,11 @@ where
 	type Output = DynFuture<'i, T>;
 	fn eval(&'i self, input: I) -> Self::Output {
 		if let Some(cached_value) = self.cache.lock().as_ref().unwrap().deref() {
+			record_cache_lookup(true);
 			let data = cached_value.clone();
 			Box::pin(async move { data })
 		} else {
+			record_cache_lookup(false);
 			let fut = self.node.eval(input);
 			let cache = self.cache.clone();
 			Box::pin(async move {
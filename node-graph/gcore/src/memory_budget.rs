@@ -0,0 +1,220 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// Memory budget used until the editor preferences are loaded, in bytes.
+pub const DEFAULT_MEMORY_BUDGET: u64 = 512 * 1024 * 1024;
+
+static MEMORY_BUDGET: AtomicU64 = AtomicU64::new(DEFAULT_MEMORY_BUDGET);
+static USAGE: [AtomicUsize; 3] = [AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0)];
+
+/// The kinds of cached data which share the memory budget, each of which is evicted separately once it exceeds its share.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CacheCategory {
+	/// Images encoded for display in the rendered SVG.
+	Images,
+	/// Font files used for typesetting text.
+	Fonts,
+	/// Rendered layers and simplified paths reused between renders.
+	Renders,
+}
+
+impl CacheCategory {
+	/// The portion of the total memory budget available to this category, which is limited to the address space on 32-bit targets like wasm.
+	pub fn budget(self) -> usize {
+		let budget = usize::try_from(memory_budget()).unwrap_or(usize::MAX);
+		match self {
+			Self::Images => budget / 2,
+			Self::Fonts => budget / 8,
+			Self::Renders => budget - budget / 2 - budget / 8,
+		}
+	}
+
+	fn usage(self) -> &'static AtomicUsize {
+		&USAGE[self as usize]
+	}
+}
+
+/// Sets the total size in bytes which cached data may occupy, shared between every [`CacheCategory`].
+/// Caches over their new share evict their least recently used entries when they're next added to.
+pub fn set_memory_budget(bytes: u64) {
+	MEMORY_BUDGET.store(bytes, Ordering::Relaxed);
+}
+
+pub fn memory_budget() -> u64 {
+	MEMORY_BUDGET.load(Ordering::Relaxed)
+}
+
+/// Records the size of the data held in a category by a cache which tracks its own size, rather than using [`LruCache`].
+pub fn record_usage(category: CacheCategory, bytes: usize) {
+	category.usage().store(bytes, Ordering::Relaxed);
+}
+
+/// The approximate size in bytes of the cached data in each category.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+	pub images: usize,
+	pub fonts: usize,
+	pub renders: usize,
+}
+
+impl MemoryUsage {
+	pub fn total(&self) -> usize {
+		self.images + self.fonts + self.renders
+	}
+}
+
+pub fn memory_usage() -> MemoryUsage {
+	MemoryUsage {
+		images: CacheCategory::Images.usage().load(Ordering::Relaxed),
+		fonts: CacheCategory::Fonts.usage().load(Ordering::Relaxed),
+		renders: CacheCategory::Renders.usage().load(Ordering::Relaxed),
+	}
+}
+
+struct LruEntry<V> {
+	value: V,
+	bytes: usize,
+	last_used: u64,
+}
+
+/// A cache which evicts its least recently used entries when their total size exceeds the budget of its [`CacheCategory`].
+pub struct LruCache<K, V> {
+	category: CacheCategory,
+	entries: HashMap<K, LruEntry<V>>,
+	/// The key of each entry by when it was last used, so the least recently used are found without sorting every entry.
+	by_last_use: BTreeMap<u64, K>,
+	bytes: usize,
+	clock: u64,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+	pub fn new(category: CacheCategory) -> Self {
+		Self {
+			category,
+			entries: HashMap::new(),
+			by_last_use: BTreeMap::new(),
+			bytes: 0,
+			clock: 0,
+		}
+	}
+
+	/// Gets the value for the key, marking it as the most recently used entry.
+	pub fn get(&mut self, key: &K) -> Option<&V> {
+		self.clock += 1;
+		let entry = self.entries.get_mut(key)?;
+		if let Some(key) = self.by_last_use.remove(&entry.last_used) {
+			self.by_last_use.insert(self.clock, key);
+		}
+		entry.last_used = self.clock;
+		Some(&entry.value)
+	}
+
+	/// Inserts the value, which occupies roughly the given number of bytes, then evicts the least recently used entries until the cache is within its budget.
+	pub fn insert(&mut self, key: K, value: V, bytes: usize) {
+		self.clock += 1;
+		let entry = LruEntry { value, bytes, last_used: self.clock };
+		if let Some(replaced) = self.entries.insert(key.clone(), entry) {
+			self.by_last_use.remove(&replaced.last_used);
+			self.remove_bytes(replaced.bytes);
+		}
+		self.by_last_use.insert(self.clock, key);
+		self.add_bytes(bytes);
+
+		self.evict(self.category.budget());
+	}
+
+	pub fn bytes(&self) -> usize {
+		self.bytes
+	}
+
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	pub fn clear(&mut self) {
+		self.entries.clear();
+		self.by_last_use.clear();
+		self.remove_bytes(self.bytes);
+	}
+
+	/// Removes entries from least to most recently used until the size is within the budget, always keeping the newest entry so it can be used at least once.
+	fn evict(&mut self, budget: usize) {
+		while self.bytes > budget && self.by_last_use.len() > 1 {
+			let Some((_, key)) = self.by_last_use.pop_first() else { break };
+			if let Some(evicted) = self.entries.remove(&key) {
+				self.remove_bytes(evicted.bytes);
+			}
+		}
+	}
+
+	fn add_bytes(&mut self, bytes: usize) {
+		self.bytes += bytes;
+		self.category.usage().fetch_add(bytes, Ordering::Relaxed);
+	}
+
+	fn remove_bytes(&mut self, bytes: usize) {
+		self.bytes -= bytes;
+		self.category.usage().fetch_sub(bytes, Ordering::Relaxed);
+	}
+}
+
+impl<K, V> Drop for LruCache<K, V> {
+	fn drop(&mut self) {
+		self.category.usage().fetch_sub(self.bytes, Ordering::Relaxed);
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn evicts_least_recently_used() {
+		let mut cache = LruCache::new(CacheCategory::Renders);
+		let budget = CacheCategory::Renders.budget();
+		let size = budget / 3;
+
+		cache.insert(1, "a", size);
+		cache.insert(2, "b", size);
+		cache.insert(3, "c", size);
+		assert!(cache.get(&1).is_some());
+
+		// Entry 2 is now the least recently used, so it's the one evicted to make room
+		cache.insert(4, "d", size);
+		assert!(cache.get(&2).is_none());
+		assert!(cache.get(&1).is_some());
+		assert!(cache.get(&4).is_some());
+		assert!(cache.bytes() <= budget);
+	}
+
+	#[test]
+	fn keeps_newest_entry_over_budget() {
+		let mut cache = LruCache::new(CacheCategory::Images);
+		cache.insert("small", (), 1);
+		cache.insert("huge", (), CacheCategory::Images.budget() + 1);
+
+		assert!(cache.get(&"huge").is_some());
+		assert!(cache.get(&"small").is_none());
+	}
+
+	#[test]
+	fn replacing_an_entry_makes_it_most_recently_used() {
+		let mut cache = LruCache::new(CacheCategory::Renders);
+		let size = CacheCategory::Renders.budget() / 3;
+
+		cache.insert(1, "a", size);
+		cache.insert(2, "b", size);
+		cache.insert(1, "c", size);
+		cache.insert(3, "d", size);
+		cache.insert(4, "e", size);
+
+		assert!(cache.get(&2).is_none());
+		assert_eq!(cache.get(&1), Some(&"c"));
+		assert_eq!(cache.len(), 3);
+	}
+}
@@ -0,0 +1,202 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use dyn_any::{WasmNotSend, WasmNotSync};
+use std::cell::{Cell, RefCell};
+
+// Evaluations are polled on a single thread, so the cancellation of the one in progress is kept per thread
+thread_local! {
+	/// Checked at each [`yield_now`] point of the evaluation run by [`cancelable`], which stops there once it returns true.
+	static CANCELLATION_CHECK: RefCell<Option<Box<dyn Fn() -> bool>>> = const { RefCell::new(None) };
+	/// Set by the yield point where a canceled evaluation stopped, so [`cancelable`] knows to give up on it.
+	static STOPPED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// How many items of heavy geometry work are processed between each point where the evaluation yields back to the executor.
+pub const CHUNK_SIZE: usize = 16;
+
+/// Maps each item on the thread pool, keeping the order of the items.
+/// On Wasm, where there is no thread pool, the items are mapped one after another on the current thread.
+pub fn par_map<T: WasmNotSend, R: WasmNotSend>(items: Vec<T>, f: impl Fn(T) -> R + WasmNotSend + WasmNotSync) -> Vec<R> {
+	#[cfg(not(target_arch = "wasm32"))]
+	{
+		use rayon::prelude::*;
+		items.into_par_iter().map(f).collect()
+	}
+	#[cfg(target_arch = "wasm32")]
+	{
+		items.into_iter().map(f).collect()
+	}
+}
+
+/// Maps each item like [`par_map`], but in chunks of [`CHUNK_SIZE`] which yield to the executor in between so a long operation doesn't hold up everything else waiting on it.
+pub async fn par_map_chunked<T: WasmNotSend, R: WasmNotSend>(items: Vec<T>, f: impl Fn(T) -> R + WasmNotSend + WasmNotSync) -> Vec<R> {
+	let mut results = Vec::with_capacity(items.len());
+	let mut items = items.into_iter().peekable();
+
+	while items.peek().is_some() {
+		let chunk = items.by_ref().take(CHUNK_SIZE).collect();
+		results.extend(par_map(chunk, &f));
+		yield_now().await;
+	}
+
+	results
+}
+
+/// Combines the items pairwise in a balanced tree, with each level of the tree run in parallel.
+///
+/// The order is preserved, so `f` is always called with its earlier item first, and the result is the same as folding from the front whenever `f` is associative.
+pub fn par_reduce<T: WasmNotSend>(mut items: Vec<T>, f: impl Fn(T, T) -> T + WasmNotSend + WasmNotSync) -> Option<T> {
+	while items.len() > 1 {
+		items = reduce_level(items, &f);
+	}
+
+	items.pop()
+}
+
+/// Combines the items like [`par_reduce`], but yields to the executor after each level of the tree.
+pub async fn par_reduce_chunked<T: WasmNotSend>(mut items: Vec<T>, f: impl Fn(T, T) -> T + WasmNotSend + WasmNotSync) -> Option<T> {
+	while items.len() > 1 {
+		items = reduce_level(items, &f);
+		yield_now().await;
+	}
+
+	items.pop()
+}
+
+fn reduce_level<T: WasmNotSend>(items: Vec<T>, f: &(impl Fn(T, T) -> T + WasmNotSend + WasmNotSync)) -> Vec<T> {
+	let mut pairs = Vec::with_capacity(items.len().div_ceil(2));
+	let mut remaining = items.into_iter();
+	while let Some(first) = remaining.next() {
+		pairs.push((first, remaining.next()));
+	}
+
+	par_map(pairs, |(first, second)| match second {
+		Some(second) => f(first, second),
+		None => first,
+	})
+}
+
+/// Runs the future, such as the evaluation of a graph, until it finishes or until `is_canceled` returns true at one of its [`yield_now`] points.
+///
+/// A canceled future is dropped at the yield point where it noticed, which always falls between whole chunks of work, never while a node is storing its result.
+/// Gives back `None` if it was canceled. Only one future is run like this at a time on each thread, since the check is shared by every yield point on it.
+pub async fn cancelable<F: Future>(future: F, is_canceled: impl Fn() -> bool + 'static) -> Option<F::Output> {
+	struct Uninstall;
+	impl Drop for Uninstall {
+		fn drop(&mut self) {
+			CANCELLATION_CHECK.with_borrow_mut(|check| *check = None);
+		}
+	}
+
+	CANCELLATION_CHECK.with_borrow_mut(|check| *check = Some(Box::new(is_canceled)));
+	STOPPED.set(false);
+	let _uninstall = Uninstall;
+
+	let mut future = core::pin::pin!(future);
+	core::future::poll_fn(|cx| {
+		if let Poll::Ready(output) = future.as_mut().poll(cx) {
+			return Poll::Ready(Some(output));
+		}
+		if STOPPED.get() { Poll::Ready(None) } else { Poll::Pending }
+	})
+	.await
+}
+
+fn is_canceled() -> bool {
+	CANCELLATION_CHECK.with_borrow(|check| check.as_ref().is_some_and(|is_canceled| is_canceled()))
+}
+
+/// Returns control to the executor once, letting other pending work run before this future continues.
+/// If the evaluation run by [`cancelable`] has been canceled in the meantime, it stops here instead and never continues.
+///
+/// On Wasm, the future is woken from a `setTimeout` callback. Waking it straight away would only queue it as a microtask, which still runs before the browser gets to handle input or paint.
+pub fn yield_now() -> YieldNow {
+	YieldNow { yielded: false }
+}
+
+pub struct YieldNow {
+	yielded: bool,
+}
+
+impl Future for YieldNow {
+	type Output = ();
+
+	fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		if is_canceled() {
+			// Woken so the enclosing `cancelable` is polled again and sees that it stopped
+			STOPPED.set(true);
+			cx.waker().wake_by_ref();
+			return Poll::Pending;
+		}
+		if self.yielded {
+			return Poll::Ready(());
+		}
+
+		self.yielded = true;
+		#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+		wake_in_macrotask(cx.waker().clone());
+		#[cfg(not(all(target_arch = "wasm32", feature = "wasm")))]
+		cx.waker().wake_by_ref();
+		Poll::Pending
+	}
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+fn wake_in_macrotask(waker: core::task::Waker) {
+	use wasm_bindgen::prelude::*;
+
+	#[wasm_bindgen]
+	extern "C" {
+		// Bound to the global function rather than `Window`'s method so it also works in a worker
+		#[wasm_bindgen(js_name = setTimeout)]
+		fn set_timeout(handler: &JsValue, timeout: i32);
+	}
+
+	set_timeout(&Closure::once_into_js(move || waker.wake()), 0);
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn reduce_preserves_order() {
+		let items = (0..37).map(|i| i.to_string()).collect::<Vec<_>>();
+		let expected = items.concat();
+
+		assert_eq!(par_reduce(items, |a, b| a + &b), Some(expected));
+		assert_eq!(par_reduce(Vec::<String>::new(), |a, b| a + &b), None);
+	}
+
+	#[tokio::test]
+	async fn cancelable_stops_at_a_yield_point() {
+		use std::sync::Arc;
+		use std::sync::atomic::{AtomicUsize, Ordering};
+
+		let steps = Arc::new(AtomicUsize::new(0));
+		let evaluation = |steps: Arc<AtomicUsize>| async move {
+			for _ in 0..10 {
+				steps.fetch_add(1, Ordering::SeqCst);
+				yield_now().await;
+			}
+			"finished"
+		};
+
+		let checked_steps = steps.clone();
+		let canceled = cancelable(evaluation(steps.clone()), move || checked_steps.load(Ordering::SeqCst) >= 3).await;
+		assert_eq!(canceled, None);
+		assert_eq!(steps.load(Ordering::SeqCst), 3);
+
+		assert_eq!(cancelable(evaluation(steps.clone()), || false).await, Some("finished"));
+		// Outside of `cancelable`, yield points never stop
+		yield_now().await;
+	}
+
+	#[tokio::test]
+	async fn map_chunked_preserves_order() {
+		let items = (0..100).collect::<Vec<u32>>();
+		let mapped = par_map_chunked(items, |i| i * 2).await;
+		assert_eq!(mapped, (0..100).map(|i| i * 2).collect::<Vec<_>>());
+	}
+}
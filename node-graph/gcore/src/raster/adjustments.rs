@@ -3,7 +3,7 @@
 #[cfg(feature = "alloc")]
 use crate::raster::curve::{Curve, CurveManipulatorGroup, ValueMapperNode};
 #[cfg(feature = "alloc")]
-use crate::raster::image::{Image, ImageFrameTable};
+use crate::raster::image::{Image, ImageContentId, ImageFrameTable};
 use crate::raster::{Channel, Color, Pixel};
 use crate::registry::types::{Angle, Percentage, SignedPercentage};
 use crate::vector::VectorDataTable;
@@ -347,6 +347,26 @@ fn make_opaque<T: Adjust<Color>>(
 	input
 }
 
+/// Marks the layer as a reference image for tracing over, which is left out of exports and can't be clicked on in the viewport.
+/// The image may be dimmed and desaturated so the artwork drawn over it stands out.
+#[node_macro::node(category("Raster"))]
+fn reference_image<T: Adjust<Color> + MultiplyAlpha>(
+	_: impl Ctx,
+	#[implementations(
+		ImageFrameTable<Color>,
+	)]
+	mut image: T,
+	#[default(50.)] dim: Percentage,
+	#[default(true)] desaturate: bool,
+) -> T {
+	if desaturate {
+		image.adjust(|color| color.map_rgb(|_| color.luminance_srgb()));
+	}
+	// Dimming only lowers the opacity of the image, so it is still composited in a single pass without touching its pixels
+	image.multiply_alpha(1. - dim / 100.);
+	image
+}
+
 // Aims for interoperable compatibility with:
 // https://www.adobe.com/devnet-apps/photoshop/fileformatashtml/#:~:text=%27%20%3D%20Brightness/Contrast-,%27levl%27%20%3D%20Levels,-%27curv%27%20%3D%20Curves
 //
@@ -606,6 +626,7 @@ impl Blend<Color> for ImageFrameTable<Color> {
 				width: over.instance.width,
 				height: over.instance.height,
 				base64_string: None,
+				content_id: ImageContentId::new(),
 			};
 		}
 
@@ -732,6 +753,7 @@ where
 {
 	fn adjust(&mut self, map_fn: impl Fn(&P) -> P) {
 		for instance in self.instances_mut() {
+			instance.instance.content_id = ImageContentId::new();
 			for c in instance.instance.data.iter_mut() {
 				*c = map_fn(c);
 			}
@@ -44,7 +44,27 @@ mod base64_serde {
 	}
 }
 
-#[derive(Clone, PartialEq, Default, specta::Type)]
+/// Identifies the pixels of an [`Image`], so caches can recognize an image they've seen before without hashing or comparing every pixel.
+///
+/// Each image is given a new ID when it's created and whenever its pixels are changed through [`BitmapMut`], while clones keep the ID of the image they're cloned from.
+/// Code which changes the `data` of an image directly must give it a new ID with [`ImageContentId::new`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, specta::Type)]
+pub struct ImageContentId(u64);
+
+impl ImageContentId {
+	pub fn new() -> Self {
+		static NEXT_ID: core::sync::atomic::AtomicU64 = core::sync::atomic::AtomicU64::new(0);
+		Self(NEXT_ID.fetch_add(1, core::sync::atomic::Ordering::Relaxed))
+	}
+}
+
+impl Default for ImageContentId {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[derive(Clone, Default, specta::Type)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Image<P: Pixel> {
 	pub width: u32,
@@ -55,10 +75,19 @@ pub struct Image<P: Pixel> {
 	/// to an svg string. This is used as a cache in order to not have to encode the data on every graph evaluation.
 	#[cfg_attr(feature = "serde", serde(skip))]
 	pub base64_string: Option<String>,
+	/// Identifies the pixels for caching, so it's ignored when comparing images and isn't saved.
+	#[cfg_attr(feature = "serde", serde(skip))]
+	pub content_id: ImageContentId,
 	// TODO: Add an `origin` field to store where in the local space the image is anchored.
 	// TODO: Currently it is always anchored at the top left corner at (0, 0). The bottom right corner of the new origin field would correspond to (1, 1).
 }
 
+impl<P: Pixel + PartialEq> PartialEq for Image<P> {
+	fn eq(&self, other: &Self) -> bool {
+		self.width == other.width && self.height == other.height && self.data == other.data && self.base64_string == other.base64_string
+	}
+}
+
 impl<P: Pixel + Debug> Debug for Image<P> {
 	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
 		let length = self.data.len();
@@ -97,6 +126,7 @@ impl<P: Copy + Pixel> Bitmap for Image<P> {
 
 impl<P: Copy + Pixel> BitmapMut for Image<P> {
 	fn get_pixel_mut(&mut self, x: u32, y: u32) -> Option<&mut P> {
+		self.content_id = ImageContentId::new();
 		self.data.get_mut((x + y * self.width) as usize)
 	}
 }
@@ -122,6 +152,7 @@ impl<P: Pixel> Image<P> {
 			height,
 			data: vec![color; (width * height) as usize],
 			base64_string: None,
+			content_id: ImageContentId::new(),
 		}
 	}
 }
@@ -135,6 +166,7 @@ impl Image<Color> {
 			height,
 			data,
 			base64_string: None,
+			content_id: ImageContentId::new(),
 		}
 	}
 
@@ -363,6 +395,7 @@ where
 
 impl<P: Copy + Pixel> Image<P> {
 	pub fn get_mut(&mut self, x: usize, y: usize) -> &mut P {
+		self.content_id = ImageContentId::new();
 		&mut self.data[y * (self.width as usize) + x]
 	}
 
@@ -389,6 +422,7 @@ impl From<Image<Color>> for Image<SRGBA8> {
 			width: image.width,
 			height: image.height,
 			base64_string: None,
+			content_id: ImageContentId::new(),
 		}
 	}
 }
@@ -401,6 +435,7 @@ impl From<Image<SRGBA8>> for Image<Color> {
 			width: image.width,
 			height: image.height,
 			base64_string: None,
+			content_id: ImageContentId::new(),
 		}
 	}
 }
@@ -416,6 +451,7 @@ mod test {
 			height: 2,
 			data: vec![Color::WHITE, Color::BLACK, Color::RED, Color::GREEN],
 			base64_string: None,
+			content_id: ImageContentId::new(),
 		};
 
 		let serialized = serde_json::to_string(&image).unwrap();
@@ -425,4 +461,17 @@ mod test {
 
 		assert_eq!(image, deserialized);
 	}
+
+	#[test]
+	fn content_id_changes_with_the_pixels() {
+		use super::*;
+		use crate::Color;
+		let image = Image::new(2, 2, Color::WHITE);
+		let mut edited = image.clone();
+		assert_eq!(edited.content_id, image.content_id);
+
+		edited.set_pixel(1, 1, Color::BLACK);
+		assert_ne!(edited.content_id, image.content_id);
+		assert_ne!(Image::new(2, 2, Color::WHITE).content_id, image.content_id);
+	}
 }
@@ -1,5 +1,11 @@
 mod font_cache;
+mod hyphenate;
+mod span;
+mod subset;
 mod to_path;
 
 pub use font_cache::*;
+pub use hyphenate::{HYPHENATION_AVAILABLE, TextLanguage};
+pub use span::*;
+pub use subset::{FontSubset, subset_font};
 pub use to_path::*;
@@ -1,5 +1,6 @@
+use crate::memory_budget::{self, CacheCategory};
 use dyn_any::DynAny;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// A font type (storing font family and font style and an optional preview URL)
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Hash, PartialEq, Eq, DynAny, specta::Type)]
@@ -26,6 +27,9 @@ pub struct FontCache {
 	font_file_data: HashMap<Font, Vec<u8>>,
 	/// Web font preview URLs used for showing fonts when live editing
 	preview_urls: HashMap<Font, String>,
+	/// Loaded fonts from least to most recently loaded, which is the order they're evicted in by [`Self::evict_unused_in_load_order`]
+	#[serde(skip)]
+	load_order: Vec<Font>,
 }
 impl FontCache {
 	/// Returns the font family name if the font is cached, otherwise returns the fallback font family name if that is cached
@@ -51,8 +55,43 @@ impl FontCache {
 
 	/// Insert a new font into the cache
 	pub fn insert(&mut self, font: Font, perview_url: String, data: Vec<u8>) {
+		self.load_order.retain(|loaded| *loaded != font);
+		self.load_order.push(font.clone());
 		self.font_file_data.insert(font.clone(), data);
 		self.preview_urls.insert(font, perview_url);
+
+		memory_budget::record_usage(CacheCategory::Fonts, self.bytes());
+	}
+
+	/// Removes unused fonts first in, first out until the font data fits within its share of the memory budget, returning the fonts which were removed.
+	/// The fonts are removed in the order they were loaded, regardless of how recently they were read, since that's only known to the copy of the cache held by the node graph's runtime.
+	/// Fonts which are in use (and the default font used as a fallback) are never removed, since they'd just have to be loaded again.
+	pub fn evict_unused_in_load_order(&mut self, in_use: &HashSet<Font>) -> Vec<Font> {
+		let budget = CacheCategory::Fonts.budget();
+		let mut bytes = self.bytes();
+		let mut evicted = Vec::new();
+
+		for font in self.load_order.clone() {
+			if bytes <= budget {
+				break;
+			}
+			if in_use.contains(&font) || font == Font::default() {
+				continue;
+			}
+
+			bytes -= self.font_file_data.remove(&font).map_or(0, |data| data.len());
+			self.preview_urls.remove(&font);
+			self.load_order.retain(|loaded| *loaded != font);
+			evicted.push(font);
+		}
+
+		memory_budget::record_usage(CacheCategory::Fonts, bytes);
+		evicted
+	}
+
+	/// The total size of the loaded font files.
+	pub fn bytes(&self) -> usize {
+		self.font_file_data.values().map(Vec::len).sum()
 	}
 
 	/// Gets the preview URL for showing in text field when live editing
@@ -0,0 +1,105 @@
+use dyn_any::DynAny;
+#[cfg(feature = "hyphenation")]
+use hyphenation::{Hyphenator, Language, Load, Standard};
+#[cfg(feature = "hyphenation")]
+use std::sync::OnceLock;
+
+/// Whether text can be hyphenated, which needs the patterns of each language embedded by the `hyphenation` feature.
+pub const HYPHENATION_AVAILABLE: bool = cfg!(feature = "hyphenation");
+
+/// The language of the text, which decides where its words can be hyphenated when they're broken across lines.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum TextLanguage {
+	#[default]
+	EnglishUS,
+	EnglishUK,
+	German,
+	French,
+	Spanish,
+	Italian,
+	Portuguese,
+	Dutch,
+}
+
+impl core::fmt::Display for TextLanguage {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			TextLanguage::EnglishUS => write!(f, "English (US)"),
+			TextLanguage::EnglishUK => write!(f, "English (UK)"),
+			TextLanguage::German => write!(f, "German"),
+			TextLanguage::French => write!(f, "French"),
+			TextLanguage::Spanish => write!(f, "Spanish"),
+			TextLanguage::Italian => write!(f, "Italian"),
+			TextLanguage::Portuguese => write!(f, "Portuguese"),
+			TextLanguage::Dutch => write!(f, "Dutch"),
+		}
+	}
+}
+
+impl TextLanguage {
+	pub fn list() -> [TextLanguage; 8] {
+		[
+			TextLanguage::EnglishUS,
+			TextLanguage::EnglishUK,
+			TextLanguage::German,
+			TextLanguage::French,
+			TextLanguage::Spanish,
+			TextLanguage::Italian,
+			TextLanguage::Portuguese,
+			TextLanguage::Dutch,
+		]
+	}
+
+	/// The Knuth–Liang hyphenation patterns of the language, which are only loaded the first time they're needed.
+	#[cfg(feature = "hyphenation")]
+	fn dictionary(self) -> Option<&'static Standard> {
+		static DICTIONARIES: [OnceLock<Option<Standard>>; 8] = [const { OnceLock::new() }; 8];
+
+		let language = match self {
+			TextLanguage::EnglishUS => Language::EnglishUS,
+			TextLanguage::EnglishUK => Language::EnglishGB,
+			TextLanguage::German => Language::German1996,
+			TextLanguage::French => Language::French,
+			TextLanguage::Spanish => Language::Spanish,
+			TextLanguage::Italian => Language::Italian,
+			TextLanguage::Portuguese => Language::Portuguese,
+			TextLanguage::Dutch => Language::Dutch,
+		};
+		DICTIONARIES[self as usize].get_or_init(|| Standard::from_embedded(language).ok()).as_ref()
+	}
+}
+
+/// The byte indices within the word where it can be broken across lines with a hyphen, in order.
+/// Punctuation around the word isn't hyphenated, and neither is either side of a hyphen already in the word.
+#[cfg(feature = "hyphenation")]
+pub(super) fn hyphenation_points(word: &str, language: TextLanguage) -> Vec<usize> {
+	let Some(dictionary) = language.dictionary() else { return Vec::new() };
+
+	let letters = word.trim_matches(|character: char| !character.is_alphabetic());
+	let offset = letters.as_ptr() as usize - word.as_ptr() as usize;
+	dictionary
+		.hyphenate(letters)
+		.breaks
+		.into_iter()
+		.filter(|&index| !letters[..index].ends_with('-') && !letters[index..].starts_with('-'))
+		.map(|index| offset + index)
+		.collect()
+}
+
+/// Without the hyphenation patterns, words are never hyphenated and are wrapped to the next line whole instead.
+#[cfg(not(feature = "hyphenation"))]
+pub(super) fn hyphenation_points(_word: &str, _language: TextLanguage) -> Vec<usize> {
+	Vec::new()
+}
+
+#[cfg(all(test, feature = "hyphenation"))]
+mod test {
+	use super::*;
+
+	#[test]
+	fn hyphenation_points_skip_punctuation() {
+		assert_eq!(hyphenation_points("hyphenation", TextLanguage::EnglishUS), [2, 6]);
+		assert_eq!(hyphenation_points("(hyphenation),", TextLanguage::EnglishUS), [3, 7]);
+		assert!(hyphenation_points("a", TextLanguage::EnglishUS).is_empty());
+	}
+}
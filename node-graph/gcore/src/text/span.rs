@@ -0,0 +1,533 @@
+use super::{
+	CaretPosition, Font, FontCache, TextCase, TextOutlines, TextPath, TextScript, TextStyle, TypesettingConfig, bounding_box_styled, caret_positions_styled, load_face, outline_styled,
+	overflow_index_styled, subset_font, to_path_styled,
+};
+use crate::Color;
+use crate::vector::style::Fill;
+use crate::vector::{EmbeddedFont, EmbeddedGlyph, EmbeddedText, PointId, VectorData};
+use bezier_rs::Subpath;
+use dyn_any::DynAny;
+use glam::DVec2;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Character styling for a range of a text, overriding the font, size, color, baseline shift, script, underline, or strikethrough the rest of the text is typeset with.
+///
+/// The range is counted in characters (Unicode scalar values) rather than bytes so it can be kept in sync with the text while it's being edited.
+/// Where spans overlap, the properties set by later spans take precedence.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, DynAny, specta::Type)]
+pub struct TextSpan {
+	pub start: usize,
+	pub end: usize,
+	pub font: Option<Font>,
+	#[serde(rename = "fontSize")]
+	pub font_size: Option<f64>,
+	pub color: Option<Color>,
+	#[serde(default, rename = "baselineShift")]
+	pub baseline_shift: Option<f64>,
+	#[serde(default)]
+	pub script: Option<TextScript>,
+	#[serde(default)]
+	pub underline: Option<bool>,
+	#[serde(default)]
+	pub strikethrough: Option<bool>,
+}
+
+impl core::hash::Hash for TextSpan {
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+		self.start.hash(state);
+		self.end.hash(state);
+		self.font.hash(state);
+		self.font_size.map(f64::to_bits).hash(state);
+		self.color.hash(state);
+		self.baseline_shift.map(f64::to_bits).hash(state);
+		self.script.hash(state);
+		self.underline.hash(state);
+		self.strikethrough.hash(state);
+	}
+}
+
+/// The part of a text cut off by the max height of its text box, which continues in the next text box linked to it.
+#[derive(Debug, Clone, PartialEq, Hash, serde::Serialize, serde::Deserialize, DynAny)]
+pub struct TextOverflow {
+	pub text: String,
+	/// The spans of the text which cover the overflowing part, counted from its start.
+	pub spans: Vec<TextSpan>,
+}
+
+/// Keeps the parts of the spans covering the text from the given character onwards, counting their ranges from that character.
+pub fn spans_from(spans: &[TextSpan], character_index: usize) -> Vec<TextSpan> {
+	spans
+		.iter()
+		.filter(|span| span.end > character_index)
+		.map(|span| TextSpan {
+			start: span.start.saturating_sub(character_index),
+			end: span.end - character_index,
+			..span.clone()
+		})
+		.collect()
+}
+
+/// Keeps the spans in sync with the text when the characters in the removed range are replaced by the given number of inserted characters.
+///
+/// The inserted characters take the style of the first character they replace, or else of the character before them, so typing continues in the style of the text around the caret.
+pub fn splice_spans(spans: &[TextSpan], removed: Range<usize>, inserted: usize) -> Vec<TextSpan> {
+	let shift = |index: usize| index - removed.len() + inserted;
+	let style_source = if removed.is_empty() { removed.start.checked_sub(1) } else { Some(removed.start) };
+
+	spans
+		.iter()
+		.filter_map(|span| {
+			let mut start = if span.start < removed.start {
+				span.start
+			} else if span.start >= removed.end {
+				shift(span.start)
+			} else {
+				removed.start + inserted
+			};
+			let mut end = if span.end <= removed.start {
+				span.end
+			} else if span.end >= removed.end {
+				shift(span.end)
+			} else {
+				removed.start
+			};
+
+			if style_source.is_some_and(|index| (span.start..span.end).contains(&index)) {
+				start = start.min(removed.start);
+				end = end.max(removed.start + inserted);
+			}
+
+			(start < end).then(|| TextSpan { start, end, ..span.clone() })
+		})
+		.collect()
+}
+
+/// Combines the spans into the fewest spans giving the text the same style, none of which overlap.
+pub fn merge_spans(text: &str, spans: &[TextSpan]) -> Vec<TextSpan> {
+	let (styles, runs) = style_runs(text, spans);
+
+	let mut merged = Vec::new();
+	let mut start = 0;
+	for (range, style_index) in runs {
+		let end = start + text[range].chars().count();
+		let style = &styles[style_index];
+		if style_index != 0 {
+			merged.push(TextSpan {
+				start,
+				end,
+				font: style.font.clone(),
+				font_size: style.font_size,
+				color: style.color,
+				baseline_shift: style.baseline_shift,
+				script: style.script,
+				underline: style.underline,
+				strikethrough: style.strikethrough,
+			});
+		}
+		start = end;
+	}
+	merged
+}
+
+/// The properties the spans covering part of a text override, where `None` leaves the text's own property.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpanStyle {
+	pub font: Option<Font>,
+	pub font_size: Option<f64>,
+	pub color: Option<Color>,
+	pub baseline_shift: Option<f64>,
+	pub script: Option<TextScript>,
+	pub underline: Option<bool>,
+	pub strikethrough: Option<bool>,
+}
+
+/// Finds each distinct style the spans give parts of the text, along with the runs of the text in each style, given as a byte range and the index of the style.
+/// The first style is always the text's own style without any spans.
+pub fn style_runs(text: &str, spans: &[TextSpan]) -> (Vec<SpanStyle>, Vec<(Range<usize>, usize)>) {
+	let mut styles = vec![SpanStyle::default()];
+	let mut runs: Vec<(Range<usize>, usize)> = Vec::new();
+
+	for (character_index, (byte_index, character)) in text.char_indices().enumerate() {
+		let mut style = SpanStyle::default();
+		for span in spans.iter().filter(|span| (span.start..span.end).contains(&character_index)) {
+			style.font = span.font.clone().or(style.font);
+			style.font_size = span.font_size.or(style.font_size);
+			style.color = span.color.or(style.color);
+			style.baseline_shift = span.baseline_shift.or(style.baseline_shift);
+			style.script = span.script.or(style.script);
+			style.underline = span.underline.or(style.underline);
+			style.strikethrough = span.strikethrough.or(style.strikethrough);
+		}
+
+		let style_index = styles.iter().position(|existing| *existing == style).unwrap_or_else(|| {
+			styles.push(style);
+			styles.len() - 1
+		});
+
+		let end = byte_index + character.len_utf8();
+		match runs.last_mut() {
+			Some((range, last_style_index)) if *last_style_index == style_index => range.end = end,
+			_ => runs.push((byte_index..end, style_index)),
+		}
+	}
+
+	(styles, runs)
+}
+
+/// A text with the styles of its spans loaded from the font cache, ready to be typeset.
+pub struct StyledText<'a> {
+	pub styles: Vec<TextStyle<'a>>,
+	/// The color of the glyphs in each style, where `None` leaves them with the fill of the text.
+	pub colors: Vec<Option<Color>>,
+	/// The runs of the text in each style, given as a byte range and the index of the style.
+	pub runs: Vec<(Range<usize>, usize)>,
+}
+
+impl<'a> StyledText<'a> {
+	/// Returns `None` if neither the font of the text nor the fallback font is loaded. Spans with a font which isn't loaded use the fallback font, or else the font of the text.
+	pub fn new(text: &str, font: &Font, spans: &[TextSpan], typesetting: TypesettingConfig, font_cache: &'a FontCache) -> Option<Self> {
+		let base_face = load_face(font_cache.get(font)?, &typesetting.variations);
+		let (span_styles, runs) = style_runs(text, spans);
+
+		let styles = span_styles
+			.iter()
+			.map(|style| {
+				let face = style
+					.font
+					.as_ref()
+					.and_then(|font| font_cache.get(font))
+					.map_or_else(|| base_face.clone(), |data| load_face(data, &typesetting.variations));
+				TextStyle::new(
+					face,
+					style.font_size.unwrap_or(typesetting.font_size),
+					style.baseline_shift.unwrap_or(typesetting.baseline_shift),
+					style.script.unwrap_or(typesetting.script),
+					style.underline.unwrap_or(typesetting.underline),
+					style.strikethrough.unwrap_or(typesetting.strikethrough),
+				)
+			})
+			.collect();
+		let colors = span_styles.into_iter().map(|style| style.color).collect();
+
+		let mut styled_text = Self { styles, colors, runs };
+		if typesetting.case == TextCase::SmallCaps {
+			styled_text.synthesize_small_caps(text);
+		}
+		Some(styled_text)
+	}
+
+	/// Moves the lowercase letters in each style whose font doesn't have its own small caps into a smaller style of the same color, where they're shown as capitals.
+	fn synthesize_small_caps(&mut self, text: &str) {
+		let has_small_caps: Vec<_> = self.styles.iter().map(TextStyle::has_small_caps).collect();
+		let mut small_caps_styles = vec![None; self.styles.len()];
+
+		let mut runs: Vec<(Range<usize>, usize)> = Vec::new();
+		for (range, style_index) in core::mem::take(&mut self.runs) {
+			for (index, character) in text[range.clone()].char_indices() {
+				let start = range.start + index;
+				let style_index = if character.is_lowercase() && !has_small_caps[style_index] {
+					*small_caps_styles[style_index].get_or_insert_with(|| {
+						self.styles.push(self.styles[style_index].synthesized_small_caps());
+						self.colors.push(self.colors[style_index]);
+						self.styles.len() - 1
+					})
+				} else {
+					style_index
+				};
+
+				let end = start + character.len_utf8();
+				match runs.last_mut() {
+					Some((range, last_style_index)) if *last_style_index == style_index => range.end = end,
+					_ => runs.push((start..end, style_index)),
+				}
+			}
+		}
+		self.runs = runs;
+	}
+
+	/// Outlines the glyphs of the text, grouped by the style they're typeset in, optionally following a path.
+	pub fn to_path(&self, text: &str, typesetting: TypesettingConfig, path: Option<&TextPath>) -> Vec<Vec<Subpath<PointId>>> {
+		to_path_styled(text, &self.styles, &self.runs, typesetting, path)
+	}
+
+	/// Outlines the glyphs of the text as one vector data, optionally following a path. Glyphs without a span color are left for the fill of the text, while the glyphs of each span color are regions with that color as their own fill.
+	///
+	/// The vector data also has the glyphs as characters of subsets of their fonts, for exporting the text as text, where each of the fonts can be subset.
+	pub fn to_vector_data(&self, text: &str, typesetting: TypesettingConfig, path: Option<&TextPath>) -> VectorData {
+		let outlines = outline_styled(text, &self.styles, &self.runs, typesetting, path);
+		let embedded_text = self.embedded_text(&outlines);
+
+		let mut vector_data = vector_data_by_color(outlines.subpaths, &self.colors);
+		vector_data.embedded_text = embedded_text.map(|embedded_text| {
+			Arc::new(EmbeddedText {
+				outlines_hash: vector_data.outlines_hash(),
+				..embedded_text
+			})
+		});
+		vector_data
+	}
+
+	/// The placed glyphs of the text as characters of subsets of their fonts, or `None` if one of the fonts can't be subset or is set to a variation other than its default.
+	/// The hash of the outlines is left to be filled in once they're made into vector data.
+	fn embedded_text(&self, outlines: &TextOutlines) -> Option<EmbeddedText> {
+		// Styles share a font where their faces were loaded from the same font file
+		let mut font_files: Vec<(&[u8], u16, Vec<u16>)> = Vec::new();
+		let mut style_fonts = Vec::with_capacity(self.styles.len());
+		for style in &self.styles {
+			if style.face.variation_coordinates().iter().any(|coordinate| coordinate.get() != 0) {
+				return None;
+			}
+
+			let data = style.face.raw_face().data;
+			let index = font_files.iter().position(|(existing, ..)| core::ptr::eq(*existing, data)).unwrap_or_else(|| {
+				font_files.push((data, style.face.units_per_em(), Vec::new()));
+				font_files.len() - 1
+			});
+			style_fonts.push(index);
+		}
+		for glyph in &outlines.glyphs {
+			font_files[style_fonts[glyph.style]].2.push(glyph.id);
+		}
+
+		let subsets = font_files.iter().map(|(data, _, glyphs)| subset_font(data, glyphs)).collect::<Option<Vec<_>>>()?;
+		let glyphs = outlines
+			.glyphs
+			.iter()
+			.map(|glyph| {
+				let font = style_fonts[glyph.style];
+				Some(EmbeddedGlyph {
+					font,
+					id: glyph.id,
+					character: *subsets[font].characters.get(&glyph.id)?,
+					transform: glyph.transform,
+					color: self.colors[glyph.style],
+				})
+			})
+			.collect::<Option<Vec<_>>>()?;
+		let fonts = subsets
+			.into_iter()
+			.zip(&font_files)
+			.map(|(subset, &(_, units_per_em, _))| {
+				let mut hasher = DefaultHasher::new();
+				subset.data.hash(&mut hasher);
+				EmbeddedFont {
+					family: format!("graphite-embedded-{:016x}", hasher.finish()),
+					data: subset.data.into(),
+					units_per_em,
+				}
+			})
+			.collect();
+		let decorations = outlines
+			.decorations
+			.iter()
+			.zip(&self.colors)
+			.flat_map(|(decorations, &color)| decorations.iter().map(move |decoration| (decoration.clone(), color)))
+			.collect();
+
+		Some(EmbeddedText {
+			fonts,
+			glyphs,
+			decorations,
+			outlines_hash: 0,
+		})
+	}
+
+	pub fn bounding_box(&self, text: &str, typesetting: TypesettingConfig, for_clipping_test: bool) -> DVec2 {
+		bounding_box_styled(text, &self.styles, &self.runs, typesetting, for_clipping_test)
+	}
+
+	/// The part of the text cut off by the max height, along with its spans, or `None` if the text fits.
+	pub fn overflow(&self, text: &str, spans: &[TextSpan], typesetting: TypesettingConfig) -> Option<TextOverflow> {
+		let index = overflow_index_styled(text, &self.styles, &self.runs, typesetting)?;
+		Some(TextOverflow {
+			text: text[index..].to_string(),
+			spans: spans_from(spans, text[..index].chars().count()),
+		})
+	}
+
+	/// The position of the caret at each boundary between the characters of the text, from before its first character to after its last, following the path if there is one.
+	pub fn caret_positions(&self, text: &str, typesetting: TypesettingConfig, path: Option<&TextPath>) -> Vec<CaretPosition> {
+		caret_positions_styled(text, &self.styles, &self.runs, typesetting, path)
+	}
+
+	pub fn lines_clipping(&self, text: &str, typesetting: TypesettingConfig) -> bool {
+		typesetting.clips(self.bounding_box(text, typesetting, true))
+	}
+}
+
+/// Joins the outlines of each style into one vector data, where the outlines of each color become regions with that color as their fill.
+fn vector_data_by_color(subpaths_by_style: Vec<Vec<Subpath<PointId>>>, colors: &[Option<Color>]) -> VectorData {
+	let mut uncolored = Vec::new();
+	let mut colored: Vec<(Color, Vec<Subpath<PointId>>)> = Vec::new();
+	for (subpaths, color) in subpaths_by_style.into_iter().zip(colors) {
+		match color {
+			None => uncolored.extend(subpaths),
+			Some(color) => match colored.iter_mut().find(|(existing, _)| existing == color) {
+				Some((_, existing_subpaths)) => existing_subpaths.extend(subpaths),
+				None => colored.push((*color, subpaths)),
+			},
+		}
+	}
+
+	let mut vector_data = VectorData::from_subpaths(uncolored, false);
+	for (color, subpaths) in colored {
+		vector_data.append_subpaths_with_fill(subpaths, Fill::Solid(color));
+	}
+	vector_data
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn span(start: usize, end: usize, font_size: Option<f64>, color: Option<Color>) -> TextSpan {
+		TextSpan {
+			start,
+			end,
+			font: None,
+			font_size,
+			color,
+			baseline_shift: None,
+			script: None,
+			underline: None,
+			strikethrough: None,
+		}
+	}
+
+	#[test]
+	fn text_without_spans_is_one_run() {
+		let (styles, runs) = style_runs("hello world", &[]);
+		assert_eq!(styles, vec![SpanStyle::default()]);
+		assert_eq!(runs, vec![(0..11, 0)]);
+	}
+
+	#[test]
+	fn spans_split_text_into_runs() {
+		let (styles, runs) = style_runs("hello world", &[span(6, 11, Some(32.), None)]);
+		assert_eq!(styles.len(), 2);
+		assert_eq!(styles[1].font_size, Some(32.));
+		assert_eq!(runs, vec![(0..6, 0), (6..11, 1)]);
+	}
+
+	#[test]
+	fn later_spans_override_earlier_ones() {
+		let (styles, runs) = style_runs("abcd", &[span(0, 4, Some(10.), Some(Color::RED)), span(1, 3, Some(20.), None)]);
+		assert_eq!(runs, vec![(0..1, 1), (1..3, 2), (3..4, 1)]);
+		assert_eq!(
+			styles[1],
+			SpanStyle {
+				font: None,
+				font_size: Some(10.),
+				color: Some(Color::RED),
+				baseline_shift: None,
+				script: None,
+				underline: None,
+				strikethrough: None,
+			}
+		);
+		assert_eq!(
+			styles[2],
+			SpanStyle {
+				font: None,
+				font_size: Some(20.),
+				color: Some(Color::RED),
+				baseline_shift: None,
+				script: None,
+				underline: None,
+				strikethrough: None,
+			}
+		);
+	}
+
+	#[test]
+	fn spans_from_character_drop_earlier_spans() {
+		let spans = [span(0, 2, Some(10.), None), span(1, 5, None, Some(Color::RED)), span(6, 8, Some(20.), None)];
+		let shifted = spans_from(&spans, 3);
+		assert_eq!(shifted, vec![span(0, 2, None, Some(Color::RED)), span(3, 5, Some(20.), None)]);
+	}
+
+	#[test]
+	fn inserted_characters_continue_the_style_before_them() {
+		let spans = [span(0, 3, Some(10.), None), span(3, 5, None, Some(Color::RED))];
+		assert_eq!(splice_spans(&spans, 3..3, 2), vec![span(0, 5, Some(10.), None), span(5, 7, None, Some(Color::RED))]);
+		assert_eq!(splice_spans(&spans, 0..0, 1), vec![span(1, 4, Some(10.), None), span(4, 6, None, Some(Color::RED))]);
+	}
+
+	#[test]
+	fn replaced_characters_take_the_style_of_the_first_one() {
+		let spans = [span(0, 3, Some(10.), None), span(3, 5, None, Some(Color::RED))];
+		assert_eq!(splice_spans(&spans, 2..4, 3), vec![span(0, 5, Some(10.), None), span(5, 6, None, Some(Color::RED))]);
+		assert_eq!(splice_spans(&spans, 3..5, 1), vec![span(0, 3, Some(10.), None), span(3, 4, None, Some(Color::RED))]);
+	}
+
+	#[test]
+	fn deleting_a_span_removes_it() {
+		let spans = [span(1, 2, Some(10.), None), span(3, 5, None, Some(Color::RED))];
+		assert_eq!(splice_spans(&spans, 1..2, 0), vec![span(2, 4, None, Some(Color::RED))]);
+		assert_eq!(splice_spans(&spans, 0..4, 0), vec![span(0, 1, None, Some(Color::RED))]);
+	}
+
+	#[test]
+	fn merged_spans_dont_overlap() {
+		let merged = merge_spans("abcdef", &[span(0, 4, Some(10.), None), span(2, 6, Some(10.), None), span(3, 4, None, Some(Color::RED))]);
+		assert_eq!(merged, vec![span(0, 3, Some(10.), None), span(3, 4, Some(10.), Some(Color::RED)), span(4, 6, Some(10.), None)]);
+		assert!(merge_spans("añb", &[span(1, 2, None, None)]).is_empty());
+	}
+
+	#[test]
+	fn scripts_and_baseline_shifts_override_separately() {
+		let spans = [
+			TextSpan {
+				baseline_shift: Some(4.),
+				..span(0, 3, None, None)
+			},
+			TextSpan {
+				script: Some(TextScript::Superscript),
+				..span(1, 2, None, None)
+			},
+		];
+		let (styles, runs) = style_runs("abc", &spans);
+		assert_eq!(runs, vec![(0..1, 1), (1..2, 2), (2..3, 1)]);
+		assert_eq!(styles[2].baseline_shift, Some(4.));
+		assert_eq!(styles[2].script, Some(TextScript::Superscript));
+	}
+
+	#[test]
+	fn later_spans_can_remove_underlines() {
+		let spans = [
+			TextSpan {
+				underline: Some(true),
+				strikethrough: Some(true),
+				..span(0, 3, None, None)
+			},
+			TextSpan {
+				underline: Some(false),
+				..span(1, 2, None, None)
+			},
+		];
+		let (styles, runs) = style_runs("abc", &spans);
+		assert_eq!(runs, vec![(0..1, 1), (1..2, 2), (2..3, 1)]);
+		assert_eq!((styles[1].underline, styles[1].strikethrough), (Some(true), Some(true)));
+		assert_eq!((styles[2].underline, styles[2].strikethrough), (Some(false), Some(true)));
+	}
+
+	#[test]
+	fn span_ranges_count_characters() {
+		let (_, runs) = style_runs("añb", &[span(1, 2, None, Some(Color::BLUE))]);
+		assert_eq!(runs, vec![(0..1, 0), (1..3, 1), (3..4, 0)]);
+	}
+
+	#[test]
+	fn styles_of_one_color_share_a_fill() {
+		let glyph = |x: f64| vec![Subpath::new_rect(DVec2::new(x, 0.), DVec2::new(x + 1., 1.))];
+		let vector_data = vector_data_by_color(vec![glyph(0.), glyph(2.), glyph(4.), glyph(6.)], &[None, Some(Color::RED), Some(Color::BLUE), Some(Color::RED)]);
+
+		let (style_fill, own_fills) = vector_data.subpaths_by_fill();
+		assert_eq!(style_fill.len(), 1);
+		assert_eq!(own_fills.len(), 2);
+		assert_eq!((own_fills[0].0, own_fills[0].1.len()), (&Fill::Solid(Color::RED), 2));
+		assert_eq!((own_fills[1].0, own_fills[1].1.len()), (&Fill::Solid(Color::BLUE), 1));
+	}
+}
@@ -0,0 +1,363 @@
+//! Subsetting of TrueType fonts, so an exported file can embed just the glyphs its text uses.
+//!
+//! The subset keeps every glyph's ID, with the outlines of the unused glyphs left empty, which avoids renumbering the glyphs in the tables that refer to them.
+//! The font's own character map and its shaping tables are replaced by a map giving each kept glyph a character of its own in the Private Use Area,
+//! so the glyphs are drawn exactly as they were typeset without being shaped again.
+
+use std::collections::{BTreeSet, HashMap};
+
+/// The first of the characters given to the glyphs of a subset.
+const FIRST_CHARACTER: u32 = 0xE000;
+
+/// The tables copied unchanged from the original font, which are the ones needed to draw its glyphs and hint them.
+const COPIED_TABLES: [&[u8; 4]; 8] = [b"OS/2", b"cvt ", b"fpgm", b"gasp", b"hhea", b"hmtx", b"maxp", b"prep"];
+
+/// The `fsType` flag of the `OS/2` table marking a font whose license doesn't allow it to be embedded.
+const RESTRICTED_LICENSE_EMBEDDING: u16 = 0x0002;
+
+/// A font with the outlines of only some of the glyphs of another.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FontSubset {
+	/// The TrueType font file.
+	pub data: Vec<u8>,
+	/// The character each of the glyphs asked for was given, from the Private Use Area.
+	pub characters: HashMap<u16, char>,
+}
+
+/// Makes a font with just the given glyphs, and those their outlines are made of, out of the TrueType font file.
+///
+/// Gives back `None` for fonts which can't be subset like this, such as those with CFF outlines and font collections, or whose license doesn't allow embedding.
+pub fn subset_font(data: &[u8], glyphs: &[u16]) -> Option<FontSubset> {
+	let tables = read_tables(data)?;
+	let table = |tag: &[u8; 4]| tables.get(tag).copied();
+	let (head, maxp, loca, glyf) = (table(b"head")?, table(b"maxp")?, table(b"loca")?, table(b"glyf")?);
+	if table(b"OS/2").and_then(|os2| read_u16(os2, 8)).is_some_and(|fs_type| fs_type & RESTRICTED_LICENSE_EMBEDDING != 0) {
+		return None;
+	}
+
+	let glyph_count = read_u16(maxp, 4)? as usize;
+	let long_offsets = read_u16(head, 50)? != 0;
+	let offsets = (0..=glyph_count)
+		.map(|index| {
+			if long_offsets {
+				read_u32(loca, index * 4).map(|offset| offset as usize)
+			} else {
+				read_u16(loca, index * 2).map(|offset| offset as usize * 2)
+			}
+		})
+		.collect::<Option<Vec<_>>>()?;
+	let glyph_data = |id: u16| glyf.get(offsets[id as usize]..offsets[id as usize + 1]);
+
+	// The missing glyph is always kept, since it's the one drawn for any character a font doesn't have
+	let requested: BTreeSet<u16> = glyphs.iter().copied().filter(|&id| (id as usize) < glyph_count).collect();
+	let mut kept: BTreeSet<u16> = requested.iter().copied().chain([0]).collect();
+	let mut pending: Vec<u16> = kept.iter().copied().collect();
+	while let Some(id) = pending.pop() {
+		for component in components(glyph_data(id)?) {
+			if (component as usize) < glyph_count && kept.insert(component) {
+				pending.push(component);
+			}
+		}
+	}
+
+	let mut new_glyf = Vec::new();
+	let mut new_loca = Vec::with_capacity((glyph_count + 1) * 4);
+	for id in 0..glyph_count as u16 {
+		new_loca.extend_from_slice(&(new_glyf.len() as u32).to_be_bytes());
+		if kept.contains(&id) {
+			new_glyf.extend_from_slice(glyph_data(id)?);
+			// Glyphs start on an even offset, as the short form of `loca` requires and some readers expect of the long form too
+			if new_glyf.len() % 2 != 0 {
+				new_glyf.push(0);
+			}
+		}
+	}
+	new_loca.extend_from_slice(&(new_glyf.len() as u32).to_be_bytes());
+
+	let characters: HashMap<u16, char> = requested.iter().zip(FIRST_CHARACTER..).filter_map(|(&id, code)| Some((id, char::from_u32(code)?))).collect();
+
+	// The checksum adjustment is recalculated once the font is written, and `loca` is always written in its long form
+	let mut new_head = head.to_vec();
+	new_head.get_mut(8..12)?.fill(0);
+	new_head.get_mut(50..52)?.copy_from_slice(&1_u16.to_be_bytes());
+
+	// Version 3 of `post` has no glyph names, which are only a fallback for the character map being replaced
+	let mut post = table(b"post").and_then(|post| post.get(..32)).map_or_else(|| vec![0; 32], <[u8]>::to_vec);
+	post[..4].copy_from_slice(&0x0003_0000_u32.to_be_bytes());
+
+	let mut new_tables: Vec<([u8; 4], Vec<u8>)> = COPIED_TABLES.iter().filter_map(|&tag| Some((*tag, table(tag)?.to_vec()))).collect();
+	new_tables.extend([
+		(*b"cmap", character_map(&characters)),
+		(*b"glyf", new_glyf),
+		(*b"head", new_head),
+		(*b"loca", new_loca),
+		(*b"name", table(b"name").map_or_else(empty_name_table, <[u8]>::to_vec)),
+		(*b"post", post),
+	]);
+	new_tables.sort_by_key(|(tag, _)| *tag);
+
+	Some(FontSubset {
+		data: write_font(&new_tables),
+		characters,
+	})
+}
+
+/// Reads the table directory of a TrueType font, giving the data of each table by its tag.
+fn read_tables(data: &[u8]) -> Option<HashMap<[u8; 4], &[u8]>> {
+	// Only TrueType outlines are subset, so fonts with CFF outlines (`OTTO`) and font collections (`ttcf`) aren't read
+	if !matches!(read_u32(data, 0)?, 0x0001_0000 | 0x7472_7565) {
+		return None;
+	}
+
+	let table_count = read_u16(data, 4)? as usize;
+	(0..table_count)
+		.map(|index| {
+			let record = data.get(12 + index * 16..12 + (index + 1) * 16)?;
+			let tag: [u8; 4] = record[..4].try_into().ok()?;
+			let offset = read_u32(record, 8)? as usize;
+			let length = read_u32(record, 12)? as usize;
+			Some((tag, data.get(offset..offset.checked_add(length)?)?))
+		})
+		.collect()
+}
+
+/// The glyphs a composite glyph is made of, or none for a simple glyph.
+fn components(glyph: &[u8]) -> Vec<u16> {
+	const ARGS_ARE_WORDS: u16 = 0x0001;
+	const HAS_SCALE: u16 = 0x0008;
+	const MORE_COMPONENTS: u16 = 0x0020;
+	const HAS_X_AND_Y_SCALE: u16 = 0x0040;
+	const HAS_TWO_BY_TWO: u16 = 0x0080;
+
+	let mut components = Vec::new();
+	// Simple glyphs have a contour count of zero or more, and empty glyphs have no data at all
+	if read_u16(glyph, 0).is_none_or(|contour_count| (contour_count as i16) >= 0) {
+		return components;
+	}
+
+	let mut offset = 10;
+	while let (Some(flags), Some(component)) = (read_u16(glyph, offset), read_u16(glyph, offset + 2)) {
+		components.push(component);
+
+		offset += 4 + if flags & ARGS_ARE_WORDS != 0 { 4 } else { 2 };
+		offset += match flags {
+			flags if flags & HAS_SCALE != 0 => 2,
+			flags if flags & HAS_X_AND_Y_SCALE != 0 => 4,
+			flags if flags & HAS_TWO_BY_TWO != 0 => 8,
+			_ => 0,
+		};
+		if flags & MORE_COMPONENTS == 0 {
+			break;
+		}
+	}
+	components
+}
+
+/// A `cmap` table with a single format 12 subtable mapping each character to its glyph.
+fn character_map(characters: &HashMap<u16, char>) -> Vec<u8> {
+	let mut groups: Vec<(u32, u16)> = characters.iter().map(|(&id, &character)| (character as u32, id)).collect();
+	groups.sort();
+
+	let subtable_length = 16 + groups.len() * 12;
+	let mut table = Vec::with_capacity(12 + subtable_length);
+	// The header and the one encoding record, for Unicode with characters beyond the Basic Multilingual Plane on Windows, which every platform reads
+	table.extend_from_slice(&[0, 0, 0, 1, 0, 3, 0, 10]);
+	table.extend_from_slice(&12_u32.to_be_bytes());
+
+	table.extend_from_slice(&[0, 12, 0, 0]);
+	table.extend_from_slice(&(subtable_length as u32).to_be_bytes());
+	table.extend_from_slice(&0_u32.to_be_bytes());
+	table.extend_from_slice(&(groups.len() as u32).to_be_bytes());
+	for (character, id) in groups {
+		table.extend_from_slice(&character.to_be_bytes());
+		table.extend_from_slice(&character.to_be_bytes());
+		table.extend_from_slice(&(id as u32).to_be_bytes());
+	}
+	table
+}
+
+/// A `name` table without any names, for fonts which lack one since it's required.
+fn empty_name_table() -> Vec<u8> {
+	vec![0, 0, 0, 0, 0, 6]
+}
+
+/// Writes the tables, sorted by their tag, into a TrueType font file.
+fn write_font(tables: &[([u8; 4], Vec<u8>)]) -> Vec<u8> {
+	let table_count = tables.len() as u16;
+	let entry_selector = table_count.checked_ilog2().unwrap_or(0) as u16;
+	let search_range = (1 << entry_selector) * 16;
+
+	let mut font = Vec::new();
+	font.extend_from_slice(&0x0001_0000_u32.to_be_bytes());
+	for value in [table_count, search_range, entry_selector, table_count * 16 - search_range] {
+		font.extend_from_slice(&value.to_be_bytes());
+	}
+
+	let mut offset = 12 + tables.len() * 16;
+	let mut head_offset = None;
+	for (tag, data) in tables {
+		if tag == b"head" {
+			head_offset = Some(offset);
+		}
+		font.extend_from_slice(tag);
+		font.extend_from_slice(&checksum(data).to_be_bytes());
+		font.extend_from_slice(&(offset as u32).to_be_bytes());
+		font.extend_from_slice(&(data.len() as u32).to_be_bytes());
+		offset += data.len().next_multiple_of(4);
+	}
+	for (_, data) in tables {
+		font.extend_from_slice(data);
+		font.resize(font.len().next_multiple_of(4), 0);
+	}
+
+	// The checksum of the whole font, with the adjustment set in `head`, comes out to this magic number
+	if let Some(head_offset) = head_offset {
+		let adjustment = 0xB1B0_AFBA_u32.wrapping_sub(checksum(&font));
+		font[head_offset + 8..head_offset + 12].copy_from_slice(&adjustment.to_be_bytes());
+	}
+	font
+}
+
+/// The sum of the table as big-endian 32-bit words, padded with zeros to a whole word.
+fn checksum(data: &[u8]) -> u32 {
+	data.chunks(4).fold(0_u32, |sum, word| {
+		let mut padded = [0; 4];
+		padded[..word.len()].copy_from_slice(word);
+		sum.wrapping_add(u32::from_be_bytes(padded))
+	})
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+	Some(u16::from_be_bytes(data.get(offset..offset + 2)?.try_into().ok()?))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+	Some(u32::from_be_bytes(data.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use rustybuzz::ttf_parser::{self, GlyphId, OutlineBuilder};
+
+	/// Counts the segments of a glyph's outline.
+	struct SegmentCounter(usize);
+
+	impl OutlineBuilder for SegmentCounter {
+		fn move_to(&mut self, _: f32, _: f32) {}
+		fn line_to(&mut self, _: f32, _: f32) {
+			self.0 += 1;
+		}
+		fn quad_to(&mut self, _: f32, _: f32, _: f32, _: f32) {
+			self.0 += 1;
+		}
+		fn curve_to(&mut self, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32) {
+			self.0 += 1;
+		}
+		fn close(&mut self) {}
+	}
+
+	/// A simple glyph of one triangular contour.
+	fn triangle() -> Vec<u8> {
+		let mut glyph = Vec::new();
+		// One contour with its bounds, ending at the third point, without instructions
+		for value in [1_i16, 0, 0, 100, 100, 2, 0] {
+			glyph.extend_from_slice(&value.to_be_bytes());
+		}
+		// Each point is on the curve, with its coordinates as 16-bit values relative to the previous point
+		glyph.extend_from_slice(&[1, 1, 1]);
+		for x in [0_i16, 100, -50] {
+			glyph.extend_from_slice(&x.to_be_bytes());
+		}
+		for y in [0_i16, 0, 100] {
+			glyph.extend_from_slice(&y.to_be_bytes());
+		}
+		glyph
+	}
+
+	/// A composite glyph made of the given glyph, moved by byte offsets.
+	fn composite(component: u16) -> Vec<u8> {
+		let mut glyph = Vec::new();
+		for value in [-1_i16, 0, 0, 100, 100] {
+			glyph.extend_from_slice(&value.to_be_bytes());
+		}
+		// Its arguments are offsets (`ARGS_ARE_XY_VALUES`) held in single bytes
+		glyph.extend_from_slice(&0x0002_u16.to_be_bytes());
+		glyph.extend_from_slice(&component.to_be_bytes());
+		glyph.extend_from_slice(&[10, 10]);
+		glyph
+	}
+
+	/// A font of four glyphs: an empty missing glyph, two triangles, and a composite of the second triangle.
+	fn test_font() -> Vec<u8> {
+		let glyphs = [Vec::new(), triangle(), triangle(), composite(2)];
+		let mut glyf = Vec::new();
+		let mut loca = Vec::new();
+		for glyph in &glyphs {
+			loca.extend_from_slice(&((glyf.len() / 2) as u16).to_be_bytes());
+			glyf.extend_from_slice(glyph);
+			glyf.resize(glyf.len().next_multiple_of(2), 0);
+		}
+		loca.extend_from_slice(&((glyf.len() / 2) as u16).to_be_bytes());
+
+		let mut head = vec![0; 54];
+		head[..4].copy_from_slice(&0x0001_0000_u32.to_be_bytes());
+		head[12..16].copy_from_slice(&0x5F0F_3CF5_u32.to_be_bytes());
+		head[18..20].copy_from_slice(&1000_u16.to_be_bytes());
+
+		let mut hhea = vec![0; 36];
+		hhea[..4].copy_from_slice(&0x0001_0000_u32.to_be_bytes());
+		hhea[34..36].copy_from_slice(&4_u16.to_be_bytes());
+
+		let mut maxp = vec![0; 6];
+		maxp[..4].copy_from_slice(&0x0000_5000_u32.to_be_bytes());
+		maxp[4..6].copy_from_slice(&4_u16.to_be_bytes());
+
+		let hmtx = [500_u16, 0].repeat(4).into_iter().flat_map(u16::to_be_bytes).collect();
+
+		let mut tables = vec![(*b"glyf", glyf), (*b"head", head), (*b"hhea", hhea), (*b"hmtx", hmtx), (*b"loca", loca), (*b"maxp", maxp)];
+		tables.sort_by_key(|(tag, _)| *tag);
+		write_font(&tables)
+	}
+
+	fn has_outline(face: &ttf_parser::Face, id: u16) -> bool {
+		let mut counter = SegmentCounter(0);
+		face.outline_glyph(GlyphId(id), &mut counter);
+		counter.0 > 0
+	}
+
+	#[test]
+	fn subsets_keep_only_the_used_glyphs_and_their_components() {
+		let font = test_font();
+		let original = ttf_parser::Face::parse(&font, 0).unwrap();
+		assert_eq!([1, 2, 3].map(|id| has_outline(&original, id)), [true, true, true]);
+
+		let subset = subset_font(&font, &[3]).unwrap();
+		let face = ttf_parser::Face::parse(&subset.data, 0).unwrap();
+		assert_eq!(face.number_of_glyphs(), 4);
+		assert_eq!(face.units_per_em(), 1000);
+		// The unused triangle is left empty, while the composite keeps the triangle it's made of
+		assert_eq!([1, 2, 3].map(|id| has_outline(&face, id)), [false, true, true]);
+
+		let character = subset.characters[&3];
+		assert_eq!(character, '\u{E000}');
+		assert_eq!(face.glyph_index(character), Some(GlyphId(3)));
+		assert_eq!(subset.characters.len(), 1);
+	}
+
+	#[test]
+	fn subsets_have_a_valid_checksum() {
+		let subset = subset_font(&test_font(), &[1, 2]).unwrap();
+		assert_eq!(checksum(&subset.data), 0xB1B0_AFBA);
+		assert_eq!(subset.characters[&1], '\u{E000}');
+		assert_eq!(subset.characters[&2], '\u{E001}');
+	}
+
+	#[test]
+	fn fonts_which_cant_be_subset_are_refused() {
+		let mut font = test_font();
+		font[..4].copy_from_slice(b"OTTO");
+		assert_eq!(subset_font(&font, &[1]), None);
+		assert_eq!(subset_font(&[], &[1]), None);
+	}
+}
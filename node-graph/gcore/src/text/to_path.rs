@@ -1,14 +1,20 @@
+use super::hyphenate::{TextLanguage, hyphenation_points};
 use crate::vector::PointId;
-use bezier_rs::{ManipulatorGroup, Subpath};
-use glam::DVec2;
-use rustybuzz::ttf_parser::{GlyphId, OutlineBuilder};
-use rustybuzz::{GlyphBuffer, UnicodeBuffer};
+use bezier_rs::{Bezier, ManipulatorGroup, Subpath, TValue};
+use core::f64::consts::FRAC_PI_2;
+use dyn_any::DynAny;
+use glam::{DAffine2, DVec2};
+use rustybuzz::ttf_parser::{GlyphId, LineMetrics, OutlineBuilder, Tag};
+use rustybuzz::{Direction, Feature, GlyphBuffer, GlyphInfo, GlyphPosition, UnicodeBuffer};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::Range;
+use unicode_bidi::{BidiClass, BidiInfo, Level, bidi_class};
 
+/// Builds the outline of a single glyph, positioned relative to the glyph's origin.
 struct Builder {
 	current_subpath: Subpath<PointId>,
 	other_subpaths: Vec<Subpath<PointId>>,
-	text_cursor: DVec2,
-	offset: DVec2,
 	ascender: f64,
 	scale: f64,
 	id: PointId,
@@ -16,7 +22,57 @@ struct Builder {
 
 impl Builder {
 	fn point(&self, x: f32, y: f32) -> DVec2 {
-		self.text_cursor + self.offset + DVec2::new(x as f64, self.ascender - y as f64) * self.scale
+		DVec2::new(x as f64, self.ascender - y as f64) * self.scale
+	}
+}
+
+/// The outlines of each distinct glyph in the text, so a glyph is only outlined once no matter how many times it repeats.
+/// Each occurrence of a glyph is then placed by copying its outline to where it's typeset. This only saves outlining work on the CPU,
+/// while it's the Vello renderer which draws the glyphs on the GPU from their fonts, given where they're placed by [`outline_styled`].
+struct GlyphOutlineCache<'a> {
+	buzz_face: &'a rustybuzz::Face<'a>,
+	ascender: f64,
+	scale: f64,
+	outlines: HashMap<GlyphId, Vec<Subpath<PointId>>>,
+}
+
+impl<'a> GlyphOutlineCache<'a> {
+	fn new(buzz_face: &'a rustybuzz::Face<'a>, ascender: f64, scale: f64) -> Self {
+		Self {
+			buzz_face,
+			ascender,
+			scale,
+			outlines: HashMap::new(),
+		}
+	}
+
+	fn outline(&mut self, glyph_id: GlyphId) -> &[Subpath<PointId>] {
+		self.outlines.entry(glyph_id).or_insert_with(|| {
+			let mut builder = Builder {
+				current_subpath: Subpath::new(Vec::new(), false),
+				other_subpaths: Vec::new(),
+				ascender: self.ascender,
+				scale: self.scale,
+				id: PointId::ZERO,
+			};
+			self.buzz_face.outline_glyph(glyph_id, &mut builder);
+			if !builder.current_subpath.is_empty() {
+				builder.other_subpaths.push(builder.current_subpath);
+			}
+			builder.other_subpaths
+		})
+	}
+
+	/// Copies the outline of the glyph into `subpaths` where the transform places it, giving its points new IDs which are unique within the text.
+	fn place(&mut self, glyph_id: GlyphId, transform: DAffine2, id: &mut PointId, subpaths: &mut Vec<Subpath<PointId>>) {
+		subpaths.extend(self.outline(glyph_id).iter().map(|subpath| {
+			let mut subpath = subpath.clone();
+			subpath.apply_transform(transform);
+			for manipulator_group in subpath.manipulator_groups_mut() {
+				manipulator_group.id = id.next_id();
+			}
+			subpath
+		}));
 	}
 }
 
@@ -51,30 +107,716 @@ impl OutlineBuilder for Builder {
 	}
 }
 
-fn font_properties(buzz_face: &rustybuzz::Face, font_size: f64, line_height_ratio: f64) -> (f64, f64, UnicodeBuffer) {
-	let scale = (buzz_face.units_per_em() as f64).recip() * font_size;
-	let line_height = font_size * line_height_ratio;
-	let buffer = UnicodeBuffer::new();
-	(scale, line_height, buffer)
+/// The font face and size which a run of the text is typeset with.
+#[derive(Clone)]
+pub struct TextStyle<'a> {
+	pub face: rustybuzz::Face<'a>,
+	pub font_size: f64,
+	/// How far the glyphs are raised above the baseline of their line, or lowered below it when negative.
+	pub baseline_shift: f64,
+	pub underline: bool,
+	pub strikethrough: bool,
+	/// Whether lowercase letters in this style are shown as capitals, which make up the small caps of fonts without their own.
+	pub small_caps: bool,
+}
+
+impl<'a> TextStyle<'a> {
+	/// The style of text with the given font size and baseline shift, which superscript and subscript make smaller and shift further.
+	pub fn new(face: rustybuzz::Face<'a>, font_size: f64, baseline_shift: f64, script: TextScript, underline: bool, strikethrough: bool) -> Self {
+		Self {
+			face,
+			font_size: font_size * script.scale(),
+			baseline_shift: baseline_shift + font_size * script.shift(),
+			underline,
+			strikethrough,
+			small_caps: false,
+		}
+	}
+
+	/// Whether the font has its own small caps (`smcp`), which are substituted for lowercase letters while shaping.
+	pub fn has_small_caps(&self) -> bool {
+		self.face.tables().gsub.is_some_and(|gsub| gsub.features.find(Tag::from_bytes(b"smcp")).is_some())
+	}
+
+	/// The style of lowercase letters shown as small caps in a font without its own, which are capitals shrunk to about the height of lowercase letters.
+	pub fn synthesized_small_caps(&self) -> Self {
+		Self {
+			font_size: self.font_size * 0.7,
+			small_caps: true,
+			..self.clone()
+		}
+	}
+
+	/// The heights above the baseline of the top and bottom of the underline and strikethrough of glyphs in this style, where they have them.
+	/// They're placed where the font suggests, or else where they usually are in other fonts.
+	fn decorations(&self) -> impl Iterator<Item = (f64, f64)> {
+		let units_per_em = self.face.units_per_em() as f64;
+		let metrics = |metrics: Option<LineMetrics>, position: f64| {
+			metrics
+				.filter(|metrics| metrics.thickness > 0)
+				.map_or((position * units_per_em, 0.05 * units_per_em), |metrics| (metrics.position as f64, metrics.thickness as f64))
+		};
+		let underline = self.underline.then(|| metrics(self.face.underline_metrics(), -0.1));
+		let strikethrough = self.strikethrough.then(|| metrics(self.face.strikeout_metrics(), 0.3));
+
+		let scale = self.scale();
+		[underline, strikethrough].into_iter().flatten().map(move |(top, thickness)| (top * scale, (top - thickness) * scale))
+	}
+
+	/// Places the underline and strikethrough of the glyphs in this style running along a line between the given distances along it.
+	/// The line maps a distance along it and a height above the baseline of the glyphs to where they're placed.
+	/// They're added to the outlines of the style, and to its decorations too.
+	fn place_decorations(&self, start: f64, end: f64, line: impl Fn(f64, f64) -> DVec2, id: &mut PointId, subpaths: &mut Vec<Subpath<PointId>>, decorations: &mut Vec<Subpath<PointId>>) {
+		for (top, bottom) in self.decorations() {
+			let corners = [(start, top), (end, top), (end, bottom), (start, bottom)];
+			let manipulator_groups = corners.map(|(along, height)| ManipulatorGroup::new_anchor_with_id(line(along, height), id.next_id()));
+			let subpath = Subpath::new(manipulator_groups.to_vec(), true);
+			decorations.push(subpath.clone());
+			subpaths.push(subpath);
+		}
+	}
+
+	fn scale(&self) -> f64 {
+		(self.face.units_per_em() as f64).recip() * self.font_size
+	}
+
+	/// The distance from the top of a line to the baseline of glyphs in this style.
+	fn ascent(&self) -> f64 {
+		self.face.ascender() as f64 / self.face.height() as f64 * self.font_size
+	}
 }
 
 fn push_str(buffer: &mut UnicodeBuffer, word: &str) {
 	buffer.push_str(word);
 }
 
-fn wrap_word(max_width: Option<f64>, glyph_buffer: &GlyphBuffer, font_size: f64, character_spacing: f64, x_pos: f64, space_glyph: Option<GlyphId>) -> bool {
-	if let Some(max_width) = max_width {
-		// We don't word wrap spaces (to match the browser)
-		let all_glyphs = glyph_buffer.glyph_positions().iter().zip(glyph_buffer.glyph_infos());
-		let non_space_glyphs = all_glyphs.take_while(|(_, info)| space_glyph != Some(GlyphId(info.glyph_id as u16)));
-		let word_length: f64 = non_space_glyphs.map(|(pos, _)| pos.x_advance as f64 * character_spacing).sum();
-		let scaled_word_length = word_length * font_size;
+/// Part of a word typeset in a single style and direction, shaped on its own.
+struct ShapedPiece {
+	/// The index of the style the piece is typeset with.
+	style: usize,
+	/// The byte index in the text where the piece starts.
+	start: usize,
+	/// The embedding level given to the piece by the Unicode bidirectional algorithm, which is odd for right-to-left text.
+	level: Level,
+	/// Whether the piece's glyphs stand upright in a column of vertical text, where they're shaped top to bottom, rather than being turned on their side.
+	upright: bool,
+	/// Whether the piece is a hyphen added where a word is broken across lines, which isn't part of the text.
+	hyphen: bool,
+	glyph_buffer: GlyphBuffer,
+}
+
+impl ShapedPiece {
+	fn new(str: &str, range: Range<usize>, style: usize, level: Level, upright: bool, styles: &[TextStyle], features: &[Feature]) -> Self {
+		let mut buffer = UnicodeBuffer::new();
+		push_str(&mut buffer, &str[range.clone()]);
+		buffer.set_direction(match (upright, level.is_rtl()) {
+			(true, _) => Direction::TopToBottom,
+			(false, true) => Direction::RightToLeft,
+			(false, false) => Direction::LeftToRight,
+		});
+
+		Self {
+			style,
+			start: range.start,
+			level,
+			upright,
+			hyphen: false,
+			glyph_buffer: rustybuzz::shape(&styles[style].face, features, buffer),
+		}
+	}
+
+	/// A hyphen ending a line where a word is broken before the byte index in the text, shaped like the part of the word it follows.
+	fn hyphen(index: usize, before: &ShapedPiece, styles: &[TextStyle], features: &[Feature]) -> Self {
+		Self {
+			start: index,
+			hyphen: true,
+			..Self::new("-", 0..1, before.style, before.level, before.upright, styles, features)
+		}
+	}
+
+	/// The shaped glyphs in the order of the text, rather than the visual order that right-to-left text is shaped in.
+	fn logical_glyphs(&self) -> Vec<(&GlyphPosition, &GlyphInfo)> {
+		let mut glyphs: Vec<_> = self.glyph_buffer.glyph_positions().iter().zip(self.glyph_buffer.glyph_infos()).collect();
+		if self.level.is_rtl() && !self.upright {
+			glyphs.reverse();
+		}
+		glyphs
+	}
+
+	/// How far the glyph moves the glyphs after it along its line, or down its column for upright glyphs, in font units.
+	fn advance(&self, glyph_position: &GlyphPosition) -> i32 {
+		if self.upright { -glyph_position.y_advance } else { glyph_position.x_advance }
+	}
+
+	/// The offset of the glyph from where it's placed along its line, in font units.
+	/// Upright glyphs are offset down their column and leftwards from its center to their baseline origin, which are given along and across the column.
+	fn offset(&self, glyph_position: &GlyphPosition) -> DVec2 {
+		if self.upright {
+			DVec2::new(-glyph_position.y_offset as f64, -glyph_position.x_offset as f64)
+		} else {
+			DVec2::new(glyph_position.x_offset as f64, glyph_position.y_offset as f64)
+		}
+	}
+}
+
+/// Whether the character at the byte index in the text is a tab.
+fn is_tab(str: &str, index: usize) -> bool {
+	str[index..].starts_with('\t')
+}
+
+/// The width of a word, shaped in a piece for each style and direction it's typeset with, up to the spaces at its end which aren't wrapped (to match the browser).
+fn word_width(str: &str, pieces: &[ShapedPiece], styles: &[TextStyle], character_spacing: f64) -> f64 {
+	let mut width = 0.;
+	for piece in pieces {
+		let style = &styles[piece.style];
+		let space_glyph = style.face.glyph_index(' ');
+		for (glyph_position, glyph_info) in piece.logical_glyphs() {
+			if space_glyph == Some(GlyphId(glyph_info.glyph_id as u16)) || is_tab(str, piece.start + glyph_info.cluster as usize) {
+				return width;
+			}
+			width += piece.advance(glyph_position) as f64 * style.scale() * character_spacing;
+		}
+	}
+	width
+}
+
+/// How the lines of text are placed across the width of the text box, or the widest line when the text has no max width.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum TextAlign {
+	#[default]
+	Left,
+	Center,
+	Right,
+	/// Widens the spaces between words so lines fill the width, except for the last line of each paragraph.
+	Justify,
+}
+
+impl core::fmt::Display for TextAlign {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			TextAlign::Left => write!(f, "Left"),
+			TextAlign::Center => write!(f, "Center"),
+			TextAlign::Right => write!(f, "Right"),
+			TextAlign::Justify => write!(f, "Justify"),
+		}
+	}
+}
+
+impl TextAlign {
+	pub fn list() -> [TextAlign; 4] {
+		[TextAlign::Left, TextAlign::Center, TextAlign::Right, TextAlign::Justify]
+	}
+}
+
+/// How the lines of text are placed within the height of a text box with a max height.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum VerticalAlign {
+	#[default]
+	Top,
+	Middle,
+	Bottom,
+}
+
+impl core::fmt::Display for VerticalAlign {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			VerticalAlign::Top => write!(f, "Top"),
+			VerticalAlign::Middle => write!(f, "Middle"),
+			VerticalAlign::Bottom => write!(f, "Bottom"),
+		}
+	}
+}
+
+impl VerticalAlign {
+	pub fn list() -> [VerticalAlign; 3] {
+		[VerticalAlign::Top, VerticalAlign::Middle, VerticalAlign::Bottom]
+	}
+}
+
+/// How the lines of text are wrapped to fit its max width, or its max height for the columns of vertical text.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum WrapMode {
+	/// Lines are wrapped between words, and only within a word too long to fit on a line of its own.
+	#[default]
+	Word,
+	/// Lines are wrapped between any characters, like Chinese and Japanese text, which doesn't put spaces between its words.
+	Character,
+	/// Lines only end at line breaks, with the text beyond the max width cut off.
+	None,
+}
+
+impl core::fmt::Display for WrapMode {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			WrapMode::Word => write!(f, "Word"),
+			WrapMode::Character => write!(f, "Character"),
+			WrapMode::None => write!(f, "None"),
+		}
+	}
+}
+
+impl WrapMode {
+	pub fn list() -> [WrapMode; 3] {
+		[WrapMode::Word, WrapMode::Character, WrapMode::None]
+	}
+}
+
+/// The direction each paragraph of the text runs in, which decides the order of runs of text in opposite directions, like numbers within Arabic or Hebrew.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum TextDirection {
+	/// Follows the first letter of each paragraph with a direction, running left to right when there isn't one.
+	#[default]
+	Auto,
+	LeftToRight,
+	RightToLeft,
+}
+
+impl core::fmt::Display for TextDirection {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			TextDirection::Auto => write!(f, "Auto"),
+			TextDirection::LeftToRight => write!(f, "LTR"),
+			TextDirection::RightToLeft => write!(f, "RTL"),
+		}
+	}
+}
+
+impl TextDirection {
+	pub fn list() -> [TextDirection; 3] {
+		[TextDirection::Auto, TextDirection::LeftToRight, TextDirection::RightToLeft]
+	}
+
+	/// The level paragraphs are given by the Unicode bidirectional algorithm, or `None` to find it from their text.
+	fn paragraph_level(self) -> Option<Level> {
+		match self {
+			TextDirection::Auto => None,
+			TextDirection::LeftToRight => Some(Level::ltr()),
+			TextDirection::RightToLeft => Some(Level::rtl()),
+		}
+	}
+}
+
+/// Whether the text runs in horizontal lines, or in vertical columns as is traditional for Chinese, Japanese, and Korean.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum WritingMode {
+	#[default]
+	Horizontal,
+	/// Runs down in columns which are placed from right to left. The max height limits the length of each column, and the text overflows the max width.
+	VerticalRightToLeft,
+}
+
+impl core::fmt::Display for WritingMode {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			WritingMode::Horizontal => write!(f, "Horizontal"),
+			WritingMode::VerticalRightToLeft => write!(f, "Vertical"),
+		}
+	}
+}
+
+impl WritingMode {
+	pub fn list() -> [WritingMode; 2] {
+		[WritingMode::Horizontal, WritingMode::VerticalRightToLeft]
+	}
+}
+
+/// How the glyphs of vertical text are turned in its columns.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum TextOrientation {
+	/// Glyphs of scripts written vertically, like Chinese, Japanese, and Korean, stand upright, while those of other scripts are turned on their side.
+	#[default]
+	Mixed,
+	Upright,
+	/// Every glyph is turned clockwise on its side, as if the text was written horizontally and turned.
+	Sideways,
+}
+
+impl core::fmt::Display for TextOrientation {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			TextOrientation::Mixed => write!(f, "Mixed"),
+			TextOrientation::Upright => write!(f, "Upright"),
+			TextOrientation::Sideways => write!(f, "Sideways"),
+		}
+	}
+}
+
+impl TextOrientation {
+	pub fn list() -> [TextOrientation; 3] {
+		[TextOrientation::Mixed, TextOrientation::Upright, TextOrientation::Sideways]
+	}
+
+	/// Whether the glyph of the character stands upright in vertical text.
+	fn is_upright(self, character: char) -> bool {
+		match self {
+			TextOrientation::Mixed => upright_in_mixed_orientation(character),
+			TextOrientation::Upright => true,
+			TextOrientation::Sideways => false,
+		}
+	}
+}
+
+/// Whether the text is made smaller and raised as superscript, like exponents and ordinals, or lowered as subscript, like chemical formulas.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum TextScript {
+	#[default]
+	Normal,
+	Superscript,
+	Subscript,
+}
+
+impl core::fmt::Display for TextScript {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			TextScript::Normal => write!(f, "Normal"),
+			TextScript::Superscript => write!(f, "Superscript"),
+			TextScript::Subscript => write!(f, "Subscript"),
+		}
+	}
+}
+
+impl TextScript {
+	pub fn list() -> [TextScript; 3] {
+		[TextScript::Normal, TextScript::Superscript, TextScript::Subscript]
+	}
+
+	/// The size of the text relative to the font size it's given.
+	fn scale(self) -> f64 {
+		match self {
+			TextScript::Normal => 1.,
+			TextScript::Superscript | TextScript::Subscript => 0.6,
+		}
+	}
+
+	/// How far the baseline of the text is shifted up, relative to the font size it's given.
+	fn shift(self) -> f64 {
+		match self {
+			TextScript::Normal => 0.,
+			TextScript::Superscript => 0.35,
+			TextScript::Subscript => -0.15,
+		}
+	}
+}
+
+/// How the letters of the text are shown in upper or lower case, without changing the text itself.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum TextCase {
+	#[default]
+	None,
+	Uppercase,
+	Lowercase,
+	/// The first letter of each word is shown as a capital.
+	TitleCase,
+	/// Lowercase letters are shown as capitals the height of lowercase letters, which are made by shrinking the capitals of fonts without their own small caps.
+	SmallCaps,
+}
+
+impl core::fmt::Display for TextCase {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			TextCase::None => write!(f, "None"),
+			TextCase::Uppercase => write!(f, "UPPERCASE"),
+			TextCase::Lowercase => write!(f, "lowercase"),
+			TextCase::TitleCase => write!(f, "Title Case"),
+			TextCase::SmallCaps => write!(f, "Small Caps"),
+		}
+	}
+}
+
+impl TextCase {
+	pub fn list() -> [TextCase; 5] {
+		[TextCase::None, TextCase::Uppercase, TextCase::Lowercase, TextCase::TitleCase, TextCase::SmallCaps]
+	}
+
+	/// The text as it's shown in this case, with each of its runs, given as a byte range and the index of its style, changed in its own style.
+	/// Each character is only changed into another of the same length, so the positions of the characters stay the same, and those whose other case is longer, like "ß" in uppercase, are left as they are.
+	/// In small caps, only lowercase letters in styles with synthesized small caps are changed into capitals, since fonts with their own small caps substitute them while shaping.
+	fn apply<'a>(self, str: &'a str, styles: &[TextStyle], runs: &[(Range<usize>, usize)]) -> Cow<'a, str> {
+		if self == TextCase::None {
+			return Cow::Borrowed(str);
+		}
+
+		fn single(mut characters: impl Iterator<Item = char>) -> Option<char> {
+			let character = characters.next()?;
+			characters.next().is_none().then_some(character)
+		}
+
+		let mut text = String::with_capacity(str.len());
+		let mut previous: Option<char> = None;
+		for (range, style_index) in runs {
+			for character in str[range.clone()].chars() {
+				let word_start = previous.is_none_or(|previous| !previous.is_alphanumeric() && !matches!(previous, '\'' | '’'));
+				let changed = match self {
+					TextCase::Uppercase => single(character.to_uppercase()),
+					TextCase::Lowercase => single(character.to_lowercase()),
+					TextCase::TitleCase if word_start => single(character.to_uppercase()),
+					TextCase::SmallCaps if styles[*style_index].small_caps => single(character.to_uppercase()),
+					_ => None,
+				};
+				text.push(changed.filter(|changed| changed.len_utf8() == character.len_utf8()).unwrap_or(character));
+				previous = Some(character);
+			}
+		}
+		Cow::Owned(text)
+	}
+}
+
+/// Whether the character belongs to one of the blocks of scripts and symbols which are written upright in vertical text, approximating its Unicode vertical orientation.
+fn upright_in_mixed_orientation(character: char) -> bool {
+	matches!(
+		character,
+		// Hangul Jamo
+		'\u{1100}'..='\u{11FF}'
+		// CJK radicals, Kangxi radicals, and ideographic description characters
+		| '\u{2E80}'..='\u{2FFF}'
+		// CJK symbols and punctuation, Hiragana, Katakana, Bopomofo, Hangul compatibility Jamo, Kanbun, and enclosed CJK letters
+		| '\u{3000}'..='\u{33FF}'
+		// CJK unified ideographs, Yi, and Hangul syllables
+		| '\u{3400}'..='\u{4DBF}'
+		| '\u{4E00}'..='\u{9FFF}'
+		| '\u{A000}'..='\u{A4CF}'
+		| '\u{AC00}'..='\u{D7AF}'
+		// CJK compatibility ideographs, vertical forms, and CJK compatibility forms
+		| '\u{F900}'..='\u{FAFF}'
+		| '\u{FE10}'..='\u{FE1F}'
+		| '\u{FE30}'..='\u{FE4F}'
+		// Fullwidth forms
+		| '\u{FF01}'..='\u{FF60}'
+		| '\u{FFE0}'..='\u{FFE7}'
+		// Ideographs of the supplementary and tertiary planes
+		| '\u{20000}'..='\u{3FFFD}'
+	)
+}
+
+/// The OpenType features of the font which the text is shaped with, where the font has them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+#[serde(default)]
+pub struct FontFeatures {
+	/// Ligatures the font uses by default, like "fi" and "fl" (`liga` and `clig`).
+	pub standard_ligatures: bool,
+	/// Decorative ligatures the font only uses when asked to, like "ct" and "st" (`dlig`).
+	pub discretionary_ligatures: bool,
+	/// Lowercase letters replaced by capitals designed at the height of lowercase letters (`smcp`).
+	pub small_caps: bool,
+	/// Digits which rise above and drop below the baseline like lowercase letters (`onum`).
+	pub oldstyle_figures: bool,
+	/// Digits which all have the same width, so columns of numbers line up (`tnum`).
+	pub tabular_figures: bool,
+	/// The stylistic sets `ss01` to `ss20`, each enabled by the bit at its number minus one.
+	pub stylistic_sets: u32,
+}
+
+impl Default for FontFeatures {
+	fn default() -> Self {
+		Self {
+			standard_ligatures: true,
+			discretionary_ligatures: false,
+			small_caps: false,
+			oldstyle_figures: false,
+			tabular_figures: false,
+			stylistic_sets: 0,
+		}
+	}
+}
+
+impl FontFeatures {
+	/// The number of stylistic sets which fonts can have.
+	pub const STYLISTIC_SET_COUNT: u32 = 20;
+
+	/// Whether the stylistic set with the given number, from 1 to 20, is enabled.
+	pub fn stylistic_set(&self, number: u32) -> bool {
+		(1..=Self::STYLISTIC_SET_COUNT).contains(&number) && self.stylistic_sets & (1 << (number - 1)) != 0
+	}
+
+	pub fn set_stylistic_set(&mut self, number: u32, enabled: bool) {
+		if !(1..=Self::STYLISTIC_SET_COUNT).contains(&number) {
+			return;
+		}
+		if enabled {
+			self.stylistic_sets |= 1 << (number - 1);
+		} else {
+			self.stylistic_sets &= !(1 << (number - 1));
+		}
+	}
+
+	/// The features to shape the whole text with. Standard ligatures are always given so they can be turned off, while the rest are only given when they're turned on.
+	fn opentype_features(&self) -> Vec<Feature> {
+		let feature = |tag: &[u8; 4], enabled: bool| Feature::new(Tag::from_bytes(tag), enabled as u32, ..);
+
+		let mut features = vec![feature(b"liga", self.standard_ligatures), feature(b"clig", self.standard_ligatures)];
+		let optional = [
+			(b"dlig", self.discretionary_ligatures),
+			(b"smcp", self.small_caps),
+			(b"onum", self.oldstyle_figures),
+			(b"tnum", self.tabular_figures),
+		];
+		features.extend(optional.into_iter().filter(|&(_, enabled)| enabled).map(|(tag, _)| feature(tag, true)));
+		features.extend((1..=Self::STYLISTIC_SET_COUNT).filter(|&number| self.stylistic_set(number)).map(|number| {
+			let [tens, ones] = [b'0' + (number / 10) as u8, b'0' + (number % 10) as u8];
+			feature(&[b's', b's', tens, ones], true)
+		}));
+		features
+	}
+}
+
+/// The value of one of the axes of a variable font, like its weight (`wght`) or width (`wdth`).
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize, DynAny, specta::Type)]
+pub struct FontVariation {
+	pub tag: [u8; 4],
+	pub value: f64,
+}
+
+/// The values the text sets for the axes of a variable font, where the axes it doesn't set keep their default value.
+#[derive(Default, Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize, DynAny, specta::Type)]
+#[serde(default)]
+pub struct FontVariations {
+	axes: [Option<FontVariation>; FontVariations::MAX_AXES],
+}
+
+impl core::hash::Hash for FontVariations {
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+		for variation in self.iter() {
+			variation.tag.hash(state);
+			variation.value.to_bits().hash(state);
+		}
+	}
+}
+
+impl FontVariations {
+	/// The number of axes which can be set, which is more than fonts usually have.
+	pub const MAX_AXES: usize = 8;
+
+	pub fn get(&self, tag: [u8; 4]) -> Option<f64> {
+		self.iter().find(|variation| variation.tag == tag).map(|variation| variation.value)
+	}
+
+	/// Sets the value of the axis, which is ignored if the values of as many other axes as can be set are already set.
+	pub fn set(&mut self, tag: [u8; 4], value: f64) {
+		let slot = self.axes.iter().position(|axis| axis.is_some_and(|variation| variation.tag == tag));
+		let Some(slot) = slot.or_else(|| self.axes.iter().position(Option::is_none)) else { return };
+		self.axes[slot] = Some(FontVariation { tag, value });
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = &FontVariation> {
+		self.axes.iter().flatten()
+	}
+}
+
+/// Where tab characters move the text after them along its line: to the next of the custom stops, and past the last of them, to the next multiple of the interval.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize, DynAny, specta::Type)]
+#[serde(default)]
+pub struct TabStops {
+	/// The distance between the stops after the custom ones, measured from the start of the line.
+	pub interval: f64,
+	/// The custom stops in order along the line.
+	stops: [Option<f64>; TabStops::MAX_STOPS],
+}
 
-		if scaled_word_length + x_pos > max_width {
-			return true;
+impl Default for TabStops {
+	fn default() -> Self {
+		Self {
+			interval: 48.,
+			stops: [None; TabStops::MAX_STOPS],
+		}
+	}
+}
+
+impl core::hash::Hash for TabStops {
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+		self.interval.to_bits().hash(state);
+		for stop in self.stops() {
+			stop.to_bits().hash(state);
 		}
 	}
-	false
+}
+
+impl TabStops {
+	/// The number of custom stops which can be set.
+	pub const MAX_STOPS: usize = 16;
+
+	pub fn stops(&self) -> impl Iterator<Item = f64> {
+		self.stops.into_iter().flatten()
+	}
+
+	/// Sets the custom stops, which are put in order. Stops which aren't past the start of the line, and those beyond as many as can be set, are ignored.
+	pub fn set_stops(&mut self, stops: impl IntoIterator<Item = f64>) {
+		let mut stops: Vec<_> = stops.into_iter().filter(|stop| stop.is_finite() && *stop > 0.).collect();
+		stops.sort_by(f64::total_cmp);
+		stops.dedup();
+
+		self.stops = [None; Self::MAX_STOPS];
+		for (slot, stop) in self.stops.iter_mut().zip(stops) {
+			*slot = Some(stop);
+		}
+	}
+
+	/// The distance along the line of the first stop after the given distance.
+	fn next_stop(&self, position: f64) -> f64 {
+		if let Some(stop) = self.stops().find(|&stop| stop > position) {
+			return stop;
+		}
+		// Without an interval, tabs past the custom stops take up no space
+		if self.interval < 1. {
+			return position;
+		}
+		((position / self.interval).floor() + 1.) * self.interval
+	}
+}
+
+/// An axis of a variable font, along with the range of values it can be set to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontAxis {
+	pub tag: [u8; 4],
+	pub name: String,
+	pub min: f64,
+	pub default: f64,
+	pub max: f64,
+}
+
+/// The axes of the font which can be varied, or none if it isn't a variable font. Axes the font marks as hidden are left out.
+pub fn variation_axes(data: &[u8]) -> Vec<FontAxis> {
+	let Ok(face) = rustybuzz::ttf_parser::Face::parse(data, 0) else { return Vec::new() };
+
+	face.variation_axes()
+		.into_iter()
+		.filter(|axis| !axis.hidden)
+		.map(|axis| {
+			let tag = axis.tag.to_bytes();
+			let name = match &tag {
+				b"wght" => "Weight".to_string(),
+				b"wdth" => "Width".to_string(),
+				b"slnt" => "Slant".to_string(),
+				b"ital" => "Italic".to_string(),
+				b"opsz" => "Optical Size".to_string(),
+				_ => String::from_utf8_lossy(&tag).trim_end().to_string(),
+			};
+			FontAxis {
+				tag,
+				name,
+				min: axis.min_value as f64,
+				default: axis.def_value as f64,
+				max: axis.max_value as f64,
+			}
+		})
+		.collect()
+}
+
+/// The characters the font has glyphs for, in the order of their code points. Control characters, which have nothing to show, are left out.
+pub fn font_characters(data: &[u8]) -> Vec<char> {
+	let Ok(face) = rustybuzz::ttf_parser::Face::parse(data, 0) else { return Vec::new() };
+	let Some(cmap) = face.tables().cmap else { return Vec::new() };
+
+	let mut characters = Vec::new();
+	for subtable in cmap.subtables.into_iter().filter(|subtable| subtable.is_unicode()) {
+		subtable.codepoints(|codepoint| {
+			if subtable.glyph_index(codepoint).is_some_and(|glyph| glyph.0 != 0) {
+				characters.extend(char::from_u32(codepoint).filter(|character| !character.is_control()));
+			}
+		});
+	}
+	characters.sort_unstable();
+	characters.dedup();
+	characters
 }
 
 #[derive(PartialEq, Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
@@ -84,6 +826,55 @@ pub struct TypesettingConfig {
 	pub character_spacing: f64,
 	pub max_width: Option<f64>,
 	pub max_height: Option<f64>,
+	#[serde(default)]
+	pub align: TextAlign,
+	#[serde(default)]
+	pub vertical_align: VerticalAlign,
+	#[serde(default)]
+	pub direction: TextDirection,
+	#[serde(default)]
+	pub writing_mode: WritingMode,
+	#[serde(default)]
+	pub orientation: TextOrientation,
+	#[serde(default)]
+	pub features: FontFeatures,
+	#[serde(default)]
+	pub variations: FontVariations,
+	/// How far the baseline of the text is raised, or lowered when negative, without changing the spacing of its lines.
+	#[serde(default)]
+	pub baseline_shift: f64,
+	#[serde(default)]
+	pub script: TextScript,
+	#[serde(default)]
+	pub wrap: WrapMode,
+	/// The extra space above each paragraph except the first, or to its right in vertical text.
+	#[serde(default)]
+	pub space_before: f64,
+	/// The extra space below each paragraph except the last, or to its left in vertical text.
+	#[serde(default)]
+	pub space_after: f64,
+	#[serde(default)]
+	pub underline: bool,
+	#[serde(default)]
+	pub strikethrough: bool,
+	#[serde(default)]
+	pub tab_stops: TabStops,
+	/// Whether words too long for the rest of a wrapped line are broken across lines with a hyphen, where the language allows.
+	#[serde(default)]
+	pub hyphenate: bool,
+	#[serde(default)]
+	pub language: TextLanguage,
+	#[serde(default)]
+	pub case: TextCase,
+	/// The space between the left of the text box and its lines, or the top of the columns of vertical text.
+	#[serde(default)]
+	pub left_indent: f64,
+	/// The space between the right of the text box and its lines, or the bottom of the columns of vertical text.
+	#[serde(default)]
+	pub right_indent: f64,
+	/// The extra indent of the first line of each paragraph on the side it starts from, which hangs the line out past the others when negative.
+	#[serde(default)]
+	pub first_line_indent: f64,
 }
 
 impl Default for TypesettingConfig {
@@ -94,106 +885,678 @@ impl Default for TypesettingConfig {
 			character_spacing: 1.,
 			max_width: None,
 			max_height: None,
+			align: TextAlign::default(),
+			vertical_align: VerticalAlign::default(),
+			direction: TextDirection::default(),
+			writing_mode: WritingMode::default(),
+			orientation: TextOrientation::default(),
+			features: FontFeatures::default(),
+			variations: FontVariations::default(),
+			baseline_shift: 0.,
+			script: TextScript::default(),
+			wrap: WrapMode::default(),
+			space_before: 0.,
+			space_after: 0.,
+			underline: false,
+			strikethrough: false,
+			tab_stops: TabStops::default(),
+			hyphenate: false,
+			language: TextLanguage::default(),
+			case: TextCase::default(),
+			left_indent: 0.,
+			right_indent: 0.,
+			first_line_indent: 0.,
 		}
 	}
 }
 
-pub fn to_path(str: &str, buzz_face: Option<rustybuzz::Face>, typesetting: TypesettingConfig) -> Vec<Subpath<PointId>> {
-	let Some(buzz_face) = buzz_face else { return vec![] };
-	let space_glyph = buzz_face.glyph_index(' ');
-
-	let (scale, line_height, mut buffer) = font_properties(&buzz_face, typesetting.font_size, typesetting.line_height_ratio);
-
-	let mut builder = Builder {
-		current_subpath: Subpath::new(Vec::new(), false),
-		other_subpaths: Vec::new(),
-		text_cursor: DVec2::ZERO,
-		offset: DVec2::ZERO,
-		ascender: (buzz_face.ascender() as f64 / buzz_face.height() as f64) * typesetting.font_size / scale,
-		scale,
-		id: PointId::ZERO,
-	};
+impl TypesettingConfig {
+	fn is_vertical(&self) -> bool {
+		self.writing_mode == WritingMode::VerticalRightToLeft
+	}
 
-	for line in str.split('\n') {
-		for (index, word) in SplitWordsIncludingSpaces::new(line).enumerate() {
-			push_str(&mut buffer, word);
-			let glyph_buffer = rustybuzz::shape(&buzz_face, &[], buffer);
+	/// The typesetting of the lines of the text. The columns of vertical text are laid out like lines of horizontal text, so its max height limits their length and its max width clips them.
+	fn line_layout(self) -> Self {
+		if !self.is_vertical() {
+			return self;
+		}
+		Self {
+			max_width: self.max_height,
+			max_height: self.max_width,
+			..self
+		}
+	}
 
-			// Don't wrap the first word
-			if index != 0 && wrap_word(typesetting.max_width, &glyph_buffer, scale, typesetting.character_spacing, builder.text_cursor.x, space_glyph) {
-				builder.text_cursor = DVec2::new(0., builder.text_cursor.y + line_height);
+	/// Whether text with the given bounds is cut off by the max height, or the max width for vertical text, whose columns overflow it on the left.
+	pub fn clips(&self, bounds: DVec2) -> bool {
+		if self.is_vertical() {
+			self.max_width.is_some_and(|max_width| max_width < bounds.x)
+		} else {
+			self.max_height.is_some_and(|max_height| max_height < bounds.y)
+		}
+	}
+}
+
+/// A glyph placed on a line of text, before the line is aligned.
+struct TypesetGlyph {
+	id: GlyphId,
+	/// The index of the style the glyph is typeset with.
+	style: usize,
+	/// The position of the glyph relative to the start of its line.
+	position: DVec2,
+	/// How far the glyph moves the glyphs after it along its line.
+	advance: f64,
+	/// The embedding level of the glyph's text, which is odd for right-to-left text.
+	level: Level,
+	/// Whether the glyph stands upright in a column of vertical text, rather than being turned on its side.
+	upright: bool,
+	is_space: bool,
+	/// The number of spaces between words before the glyph on its line, each of which is widened when the line is justified.
+	gaps_before: usize,
+	/// The byte index in the text where the cluster of characters the glyph displays starts.
+	cluster: usize,
+	/// Whether the glyph is a hyphen added where a word is broken across lines, so it has no caret positions.
+	hyphen: bool,
+}
+
+/// A line of text, broken at a line break or wrapped to fit the max width, laid out from the left before it's aligned.
+/// The columns of vertical text are laid out as lines running down from the top, which are placed from the right rather than the top.
+#[derive(Default)]
+struct TypesetLine {
+	glyphs: Vec<TypesetGlyph>,
+	/// The byte index in the text where the line starts.
+	start: usize,
+	/// The top of the line.
+	y: f64,
+	/// The height of the line, which fits the largest text placed on it.
+	height: f64,
+	/// The distance from the top of the line to its baseline, which fits the text placed on it reaching highest above the baseline.
+	ascent: f64,
+	/// The width of the line up to the end of its last glyph which isn't a space.
+	width: f64,
+	/// The width of the line including any spaces at its end, which is where the next glyph is placed.
+	advance: f64,
+	/// The spaces between words placed on the line so far.
+	spaces: usize,
+	/// The spaces between words before the line's last glyph which isn't a space, so spaces at the end of the line aren't widened when it's justified.
+	gaps: usize,
+	/// Whether the line was wrapped to fit the max width, rather than ending at a line break or the end of the text, since only wrapped lines are justified.
+	wrapped: bool,
+	/// Whether the line's paragraph runs right to left.
+	rtl: bool,
+	/// Whether the line is the first of its paragraph, which is indented by the first line indent.
+	first: bool,
+}
+
+impl TypesetLine {
+	/// Finishes the line, starting the next one below it. A line without any glyphs takes the line height of the text itself.
+	fn break_line(&mut self, empty_line_height: f64, wrapped: bool) -> Self {
+		if self.glyphs.is_empty() {
+			self.height = empty_line_height;
+		}
+		let next = Self {
+			y: self.y + self.height,
+			rtl: self.rtl,
+			..Default::default()
+		};
+		let mut line = core::mem::replace(self, next);
+		line.wrapped = wrapped;
+		line.reorder_visually();
+		line
+	}
+
+	/// Places the glyphs of the line, which are laid out in the order of the text, in the order they're displayed in by the Unicode bidirectional algorithm.
+	/// The spaces at the end of a right-to-left line hang outside of its width on the left, like those at the end of a left-to-right line do on the right.
+	fn reorder_visually(&mut self) {
+		if !self.rtl && self.glyphs.iter().all(|glyph| glyph.level.is_ltr()) {
+			return;
+		}
+
+		// Spaces at the end of the line take the direction of the paragraph (rule L1), so they stay at the end the paragraph runs towards
+		let trailing_spaces = self.glyphs.iter().rev().take_while(|glyph| glyph.is_space).count();
+		let paragraph_level = if self.rtl { Level::rtl() } else { Level::ltr() };
+		let mut levels: Vec<_> = self.glyphs.iter().map(|glyph| glyph.level).collect();
+		let content_end = levels.len() - trailing_spaces;
+		levels[content_end..].fill(paragraph_level);
+
+		// The offset of each glyph from where it was placed along the line, from shaping
+		let mut logical_x = 0.;
+		let offsets: Vec<_> = self
+			.glyphs
+			.iter()
+			.map(|glyph| {
+				let offset = glyph.position.x - logical_x;
+				logical_x += glyph.advance;
+				offset
+			})
+			.collect();
+
+		let trailing_width: f64 = self.glyphs[content_end..].iter().map(|glyph| glyph.advance).sum();
+		let mut x = if self.rtl { -trailing_width } else { 0. };
+		let mut spaces = 0;
+		let mut started = false;
+		let mut glyphs: Vec<_> = self.glyphs.drain(..).map(Some).collect();
+		for index in BidiInfo::reorder_visual(&levels) {
+			let Some(mut glyph) = glyphs[index].take() else { continue };
+			glyph.position.x = x + offsets[index];
+			x += glyph.advance;
+
+			// Only spaces between the words of the line are widened when it's justified
+			glyph.gaps_before = spaces;
+			if glyph.is_space && started && index < content_end {
+				spaces += 1;
 			}
+			started |= !glyph.is_space;
+
+			self.glyphs.push(glyph);
+		}
+	}
+
+	/// The space left empty on the left and right of the line. The first line indent is on the side the paragraph starts from, which is the right in right-to-left text.
+	fn indents(&self, typesetting: TypesettingConfig) -> (f64, f64) {
+		let first_line_indent = if self.first { typesetting.first_line_indent } else { 0. };
+		if self.rtl {
+			(typesetting.left_indent, typesetting.right_indent + first_line_indent)
+		} else {
+			(typesetting.left_indent + first_line_indent, typesetting.right_indent)
+		}
+	}
+
+	/// The horizontal offset of the line's start, and the extra width of each of its spaces between words, to align it between its indents within the available width.
+	fn alignment(&self, typesetting: TypesettingConfig, available_width: f64) -> (f64, f64) {
+		let (left_indent, right_indent) = self.indents(typesetting);
+		// Lines which aren't wrapped can be wider than the available width, so they overflow from the side they're aligned to
+		let extra_width = available_width - left_indent - right_indent - self.width;
+		let extra_width = if typesetting.wrap == WrapMode::None { extra_width } else { extra_width.max(0.) };
+		let (offset, gap_width) = match typesetting.align {
+			TextAlign::Left => (0., 0.),
+			TextAlign::Center => (extra_width / 2., 0.),
+			TextAlign::Right => (extra_width, 0.),
+			TextAlign::Justify if self.wrapped && self.gaps > 0 => (0., extra_width / self.gaps as f64),
+			// The lines of a paragraph which aren't justified stay at the side it starts from
+			TextAlign::Justify if self.rtl => (extra_width, 0.),
+			TextAlign::Justify => (0., 0.),
+		};
+		(left_indent + offset, gap_width)
+	}
+}
+
+/// Whether a glyph of a line which isn't wrapped, placed at a position along the line, reaches past either edge of the max width.
+fn glyph_clipped(along: f64, advance: f64, max_width: f64) -> bool {
+	along < 0. || along + advance > max_width
+}
+
+/// Breaks the text into lines at line breaks and wherever its wrap mode wraps it to fit the max width, placing the glyphs of each line from its left.
+///
+/// Each run of the text, given as a byte range and the index of its style, is shaped with its own style, so runs must cover the text in order.
+/// The typesetting is that of the lines rather than the whole text, given by [`TypesettingConfig::line_layout`].
+fn typeset_lines(str: &str, styles: &[TextStyle], runs: &[(Range<usize>, usize)], typesetting: TypesettingConfig) -> Vec<TypesetLine> {
+	let empty_line_height = typesetting.font_size * typesetting.line_height_ratio;
+	let vertical = typesetting.is_vertical();
+	let features = FontFeatures {
+		small_caps: typesetting.features.small_caps || typesetting.case == TextCase::SmallCaps,
+		..typesetting.features
+	}
+	.opentype_features();
+	let cased = typesetting.case.apply(str, styles, runs);
+	let str = cased.as_ref();
+	// Lines are wrapped to fit between their indents
+	let wrap_width = |line: &TypesetLine| {
+		let (left_indent, right_indent) = line.indents(typesetting);
+		typesetting
+			.max_width
+			.filter(|_| typesetting.wrap != WrapMode::None)
+			.map(|max_width| max_width - left_indent - right_indent)
+	};
+
+	let mut lines = Vec::new();
+	let mut line = TypesetLine::default();
+
+	for (paragraph_index, paragraph) in str.split('\n').enumerate() {
+		let paragraph_start = paragraph.as_ptr() as usize - str.as_ptr() as usize;
+		line.start = paragraph_start;
+		line.first = true;
+		// Paragraphs are separated by the space after the one before and the space before the next, with none added at the top and bottom of the text
+		if paragraph_index != 0 {
+			line.y += typesetting.space_after + typesetting.space_before;
+		}
+
+		// The Unicode bidirectional algorithm finds the direction of each part of the paragraph,
+		// except in vertical text with upright glyphs, which all run down their columns in the order of the text
+		let bidi = !(vertical && typesetting.orientation == TextOrientation::Upright);
+		let bidi_info = BidiInfo::new(paragraph, typesetting.direction.paragraph_level());
+		line.rtl = bidi
+			&& bidi_info
+				.paragraphs
+				.first()
+				.map_or(typesetting.direction == TextDirection::RightToLeft, |paragraph| paragraph.level.is_rtl());
+		let level_at = |index: usize| {
+			let level = bidi_info.levels.get(index - paragraph_start).copied().unwrap_or(Level::ltr());
+			if bidi { level } else { Level::ltr() }
+		};
+		let upright_at = |index: usize| vertical && str[index..].chars().next().is_some_and(|character| typesetting.orientation.is_upright(character));
+		let shaping_at = |index: usize| (level_at(index), upright_at(index));
 
-			for (glyph_position, glyph_info) in glyph_buffer.glyph_positions().iter().zip(glyph_buffer.glyph_infos()) {
-				let glyph_id = GlyphId(glyph_info.glyph_id as u16);
-				if let Some(max_width) = typesetting.max_width {
-					if space_glyph != Some(glyph_id) && builder.text_cursor.x + (glyph_position.x_advance as f64 * builder.scale * typesetting.character_spacing) >= max_width {
-						builder.text_cursor = DVec2::new(0., builder.text_cursor.y + line_height);
+		// Shape each part of the text typeset in a different style, direction, or orientation separately
+		let shape = |text: Range<usize>| {
+			let mut pieces = Vec::new();
+			for (range, style_index) in runs.iter().filter(|(range, _)| range.start < text.end && range.end > text.start) {
+				let run_end = range.end.min(text.end);
+				let mut piece_start = range.start.max(text.start);
+				for (index, _) in str[piece_start..run_end].char_indices().skip(1) {
+					let index = piece_start + index;
+					if shaping_at(index) != shaping_at(piece_start) {
+						let (level, upright) = shaping_at(piece_start);
+						pieces.push(ShapedPiece::new(str, piece_start..index, *style_index, level, upright, styles, &features));
+						piece_start = index;
 					}
 				}
-				// Clip when the height is exceeded
-				if typesetting.max_height.is_some_and(|max_height| builder.text_cursor.y > max_height - line_height) {
-					return builder.other_subpaths;
+				let (level, upright) = shaping_at(piece_start);
+				pieces.push(ShapedPiece::new(str, piece_start..run_end, *style_index, level, upright, styles, &features));
+			}
+			pieces
+		};
+		// The last of the places a word can be hyphenated where its part from the start fits within the width, as the byte index its next part starts and the pieces of this part followed by a hyphen
+		let hyphenated = |points: &[usize], start: usize, width: f64| {
+			points.iter().rev().filter(|&&index| index > start).find_map(|&index| {
+				let mut pieces = shape(start..index);
+				let hyphen = ShapedPiece::hyphen(index, pieces.last()?, styles, &features);
+				pieces.push(hyphen);
+				(word_width(str, &pieces, styles, typesetting.character_spacing) <= width).then_some((index, pieces))
+			})
+		};
+
+		for word in SplitWordsIncludingSpaces::new(paragraph) {
+			let word_offset = word.as_ptr() as usize - str.as_ptr() as usize;
+			let word_end = word_offset + word.len();
+			let mut word_start = word_offset;
+			// The byte indices where the word can be hyphenated, which are only found once it doesn't fit on its line
+			let mut word_hyphenation_points: Option<Vec<usize>> = None;
+			// A word is typeset in parts when it's hyphenated, with each part but the last ending its line
+			while word_start < word_end {
+				let mut pieces = shape(word_start..word_end);
+				let mut rest = word_end;
+
+				// Only wrap between words when wrapping them, and not at the start of a line
+				let overflow = wrap_width(&line).filter(|_| typesetting.wrap == WrapMode::Word).and_then(|max_width| {
+					let width = max_width - line.advance;
+					(word_width(str, &pieces, styles, typesetting.character_spacing) > width).then_some(width)
+				});
+				if let Some(width) = overflow {
+					// The word is hyphenated where as much of it as possible fits on the line, or else wrapped to the next line to try again there
+					let points = typesetting.hyphenate.then(|| {
+						word_hyphenation_points
+							.get_or_insert_with(|| hyphenation_points(word, typesetting.language).into_iter().map(|index| word_offset + index).collect())
+							.as_slice()
+					});
+					if let Some((index, hyphenated)) = points.and_then(|points| hyphenated(points, word_start, width)) {
+						pieces = hyphenated;
+						rest = index;
+					} else if !line.glyphs.is_empty() {
+						lines.push(line.break_line(empty_line_height, true));
+						line.start = word_start;
+						continue;
+					}
 				}
 
-				builder.offset = DVec2::new(glyph_position.x_offset as f64, glyph_position.y_offset as f64) * builder.scale;
-				buzz_face.outline_glyph(glyph_id, &mut builder);
-				if !builder.current_subpath.is_empty() {
-					builder.other_subpaths.push(core::mem::replace(&mut builder.current_subpath, Subpath::new(Vec::new(), false)));
+				for piece in &pieces {
+					let style = &styles[piece.style];
+					let scale = style.scale();
+					let space_glyph = style.face.glyph_index(' ');
+
+					for (glyph_position, glyph_info) in piece.logical_glyphs() {
+						let cluster = piece.start + glyph_info.cluster as usize;
+						// Tabs are spaces reaching to the next tab stop, rather than whatever glyph the font has for them
+						let tab = is_tab(str, cluster);
+						let glyph_id = GlyphId(glyph_info.glyph_id as u16);
+						let is_space = tab || space_glyph == Some(glyph_id);
+						let advance = if tab {
+							typesetting.tab_stops.next_stop(line.advance) - line.advance
+						} else {
+							piece.advance(glyph_position) as f64 * scale * typesetting.character_spacing
+						};
+						if let Some(max_width) = wrap_width(&line) {
+							if !is_space && !piece.hyphen && line.advance + advance >= max_width {
+								lines.push(line.break_line(empty_line_height, true));
+								line.start = cluster;
+							}
+						}
+
+						let offset = if tab { DVec2::ZERO } else { piece.offset(glyph_position) * scale };
+						line.glyphs.push(TypesetGlyph {
+							id: glyph_id,
+							style: piece.style,
+							position: DVec2::new(line.advance, 0.) + offset,
+							advance,
+							level: piece.level,
+							upright: piece.upright,
+							is_space,
+							gaps_before: line.spaces,
+							cluster,
+							hyphen: piece.hyphen,
+						});
+						line.advance += advance;
+						line.height = line.height.max(style.font_size * typesetting.line_height_ratio);
+						line.ascent = line.ascent.max(style.ascent());
+
+						if !is_space {
+							line.width = line.advance;
+							line.gaps = line.spaces;
+						} else if line.width > 0. {
+							// Spaces indenting the start of a line aren't gaps between words
+							line.spaces += 1;
+						}
+					}
 				}
 
-				builder.text_cursor += DVec2::new(glyph_position.x_advance as f64 * typesetting.character_spacing, glyph_position.y_advance as f64) * builder.scale;
+				// The line ends after the first part of a hyphenated word, with the rest of the word typeset on the next line
+				if rest < word_end {
+					lines.push(line.break_line(empty_line_height, true));
+					line.start = rest;
+				}
+				word_start = rest;
 			}
-
-			buffer = glyph_buffer.clear();
 		}
 
-		builder.text_cursor = DVec2::new(0., builder.text_cursor.y + line_height);
+		lines.push(line.break_line(empty_line_height, false));
 	}
 
-	builder.other_subpaths
+	lines
 }
 
-pub fn bounding_box(str: &str, buzz_face: Option<&rustybuzz::Face>, typesetting: TypesettingConfig, for_clipping_test: bool) -> DVec2 {
-	// Show blank layer if font has not loaded
-	let Some(buzz_face) = buzz_face else { return DVec2::ZERO };
-	let space_glyph = buzz_face.glyph_index(' ');
+/// The width the lines are aligned within, which is the max width or else the width of the widest line along with its indents.
+fn available_width(lines: &[TypesetLine], typesetting: TypesettingConfig) -> f64 {
+	typesetting.max_width.unwrap_or_else(|| {
+		lines
+			.iter()
+			.map(|line| {
+				let (left_indent, right_indent) = line.indents(typesetting);
+				left_indent + line.width + right_indent
+			})
+			.fold(0., f64::max)
+	})
+}
 
-	let (scale, line_height, mut buffer) = font_properties(buzz_face, typesetting.font_size, typesetting.line_height_ratio);
+/// Whether the line is cut off by the max height.
+fn line_clipped(line: &TypesetLine, typesetting: TypesettingConfig) -> bool {
+	typesetting.max_height.is_some_and(|max_height| line.y > max_height - line.height)
+}
 
-	let [mut text_cursor, mut bounds] = [DVec2::ZERO; 2];
-	if !for_clipping_test {
-		if let (Some(max_height), Some(max_width)) = (typesetting.max_height, typesetting.max_width) {
-			return DVec2::new(max_width, max_height);
+/// How far the lines are moved down to align them within the max height. Text taller than the max height stays at the top, where its last lines are cut off.
+fn vertical_offset(lines: &[TypesetLine], typesetting: TypesettingConfig) -> f64 {
+	let Some(max_height) = typesetting.max_height else { return 0. };
+
+	let visible_height = lines.iter().filter(|line| !line_clipped(line, typesetting)).map(|line| line.y + line.height).fold(0., f64::max);
+	let extra_height = (max_height - visible_height).max(0.);
+	match typesetting.vertical_align {
+		VerticalAlign::Top => 0.,
+		VerticalAlign::Middle => extra_height / 2.,
+		VerticalAlign::Bottom => extra_height,
+	}
+}
+
+/// A path which the baseline of the text follows, rather than running in a straight line.
+pub struct TextPath {
+	beziers: Vec<Bezier>,
+	lengths: Vec<f64>,
+	start_offset: f64,
+}
+
+impl TextPath {
+	/// Follows the subpath from the given distance along it.
+	pub fn new(subpath: &Subpath<PointId>, start_offset: f64) -> Self {
+		let beziers: Vec<_> = subpath.iter().collect();
+		let lengths = beziers.iter().map(|bezier| bezier.length(None)).collect();
+		Self { beziers, lengths, start_offset }
+	}
+
+	/// The point and direction of the path at the given distance along it from the start offset, or `None` beyond either end of the path.
+	fn point_and_tangent(&self, distance: f64) -> Option<(DVec2, DVec2)> {
+		let mut distance = distance + self.start_offset;
+		if distance < 0. {
+			return None;
+		}
+
+		for (bezier, &length) in self.beziers.iter().zip(&self.lengths) {
+			if length > 0. && distance <= length {
+				let t = TValue::Euclidean(distance / length);
+				return Some((bezier.evaluate(t), bezier.tangent(t)));
+			}
+			distance -= length;
 		}
+		None
+	}
+
+	/// Where a point of the text typeset in a straight line goes when its baseline follows the path instead, given the baseline which runs along the path.
+	/// Points beyond either end of the path continue straight on from it.
+	fn place(&self, point: DVec2, baseline: f64) -> DVec2 {
+		let length: f64 = self.lengths.iter().sum();
+		let distance = point.x.clamp(-self.start_offset, length - self.start_offset);
+		let Some((on_path, tangent)) = self.point_and_tangent(distance) else { return point };
+		on_path + tangent * (point.x - distance) + tangent.perp() * (point.y - baseline)
 	}
+}
+
+/// The single run typesetting the whole text in one style.
+fn single_run(str: &str) -> [(Range<usize>, usize); 1] {
+	[(0..str.len(), 0)]
+}
+
+pub fn to_path(str: &str, buzz_face: Option<rustybuzz::Face>, typesetting: TypesettingConfig) -> Vec<Subpath<PointId>> {
+	let Some(face) = buzz_face else { return vec![] };
+
+	let styles = [TextStyle::new(
+		face,
+		typesetting.font_size,
+		typesetting.baseline_shift,
+		typesetting.script,
+		typesetting.underline,
+		typesetting.strikethrough,
+	)];
+	to_path_styled(str, &styles, &single_run(str), typesetting, None).into_iter().flatten().collect()
+}
+
+/// Outlines the text with each of its runs, given as a byte range and the index of its style, typeset in its own style. The outlines are grouped by the style of their glyphs.
+///
+/// Along a path, each glyph is turned to follow the path at the middle of its baseline, and glyphs beyond either end of the path are left out. Text along a path is always horizontal.
+pub fn to_path_styled(str: &str, styles: &[TextStyle], runs: &[(Range<usize>, usize)], typesetting: TypesettingConfig, path: Option<&TextPath>) -> Vec<Vec<Subpath<PointId>>> {
+	outline_styled(str, styles, runs, typesetting, path).subpaths
+}
+
+/// A glyph of a text placed where it's typeset.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlacedGlyph {
+	/// The index of the style the glyph is typeset with.
+	pub style: usize,
+	pub id: u16,
+	/// Places the glyph, as drawn in the units of its font with its origin on the baseline and the y axis pointing up, where its outline was placed.
+	pub transform: DAffine2,
+}
+
+/// The outlines of a text grouped by the style of their glyphs, along with where each glyph is placed and the underlines and strikethroughs among the outlines.
+#[derive(Clone, Debug, Default)]
+pub struct TextOutlines {
+	pub subpaths: Vec<Vec<Subpath<PointId>>>,
+	pub glyphs: Vec<PlacedGlyph>,
+	/// The underlines and strikethroughs of each style, which are also among its outlines.
+	pub decorations: Vec<Vec<Subpath<PointId>>>,
+}
+
+/// Outlines the text like [`to_path_styled`], also giving where each of its glyphs is placed so the text can be drawn with its fonts instead.
+pub fn outline_styled(str: &str, styles: &[TextStyle], runs: &[(Range<usize>, usize)], typesetting: TypesettingConfig, path: Option<&TextPath>) -> TextOutlines {
+	let typesetting = match path {
+		Some(_) => TypesettingConfig {
+			writing_mode: WritingMode::Horizontal,
+			..typesetting
+		},
+		None => typesetting,
+	};
+
+	let mut glyph_outlines: Vec<_> = styles
+		.iter()
+		.map(|style| {
+			let scale = style.scale();
+			GlyphOutlineCache::new(&style.face, style.ascent() / scale, scale)
+		})
+		.collect();
+	let mut subpaths = vec![Vec::new(); styles.len()];
+	let mut decorations = vec![Vec::new(); styles.len()];
+	let mut glyphs = Vec::new();
+	let mut id = PointId::ZERO;
 
-	for line in str.split('\n') {
-		for (index, word) in SplitWordsIncludingSpaces::new(line).enumerate() {
-			push_str(&mut buffer, word);
+	let line_layout = typesetting.line_layout();
+	let lines = typeset_lines(str, styles, runs, line_layout);
+	let available_width = available_width(&lines, line_layout);
+	let vertical_offset = vertical_offset(&lines, line_layout);
+	// The baseline of the first line runs along the path, with the lines after it running alongside
+	let path_baseline = lines.first().map_or(0., |line| line.y + line.ascent) + vertical_offset;
+	// The columns of vertical text are placed leftwards from the right edge of the text
+	let right = typesetting.max_width.unwrap_or_else(|| lines.iter().map(|line| line.y + line.height).fold(0., f64::max));
 
-			let glyph_buffer = rustybuzz::shape(buzz_face, &[], buffer);
+	for line in &lines {
+		// Clip when the height is exceeded, or the width for vertical text
+		if line_clipped(line, line_layout) {
+			break;
+		}
 
-			// Don't wrap the first word
-			if index != 0 && wrap_word(typesetting.max_width, &glyph_buffer, scale, typesetting.character_spacing, text_cursor.x, space_glyph) {
-				text_cursor = DVec2::new(0., text_cursor.y + line_height);
+		let (start, gap_width) = line.alignment(typesetting, available_width);
+		let baseline = line.y + line.ascent + vertical_offset;
+		let column_center = right - (line.y + vertical_offset + line.height / 2.);
+		// The baseline of each style runs along the line, across the middle of the column in vertical text, where it's turned like the glyphs on their side
+		let line_point = |style: &TextStyle, along: f64, height: f64| {
+			if typesetting.is_vertical() {
+				let em_center = (style.face.ascender() as f64 + style.face.descender() as f64) / 2. * style.scale();
+				DVec2::new(column_center - em_center + style.baseline_shift + height, along)
+			} else {
+				DVec2::new(along, baseline - style.baseline_shift - height)
 			}
+		};
 
-			for (glyph_position, glyph_info) in glyph_buffer.glyph_positions().iter().zip(glyph_buffer.glyph_infos()) {
-				let glyph_id = GlyphId(glyph_info.glyph_id as u16);
-				if let Some(max_width) = typesetting.max_width {
-					if space_glyph != Some(glyph_id) && text_cursor.x + (glyph_position.x_advance as f64 * scale * typesetting.character_spacing) >= max_width {
-						text_cursor = DVec2::new(0., text_cursor.y + line_height);
-					}
+		// The style and the range along the line of the underline or strikethrough running along the glyphs placed so far
+		let mut decoration: Option<(usize, f64, f64)> = None;
+		for glyph in &line.glyphs {
+			let style = &styles[glyph.style];
+			let along = glyph.position.x + start + glyph.gaps_before as f64 * gap_width;
+			// Lines which aren't wrapped are cut off at both edges of the max width
+			if typesetting.wrap == WrapMode::None && path.is_none() && line_layout.max_width.is_some_and(|max_width| glyph_clipped(along, glyph.advance, max_width)) {
+				continue;
+			}
+
+			// Decorations run unbroken along the glyphs next to each other in the same style, including the spaces between their words but not those at either end
+			if let Some((index, decoration_start, decoration_end)) = decoration.filter(|&(index, ..)| index != glyph.style) {
+				let decoration_style = &styles[index];
+				decoration_style.place_decorations(
+					decoration_start,
+					decoration_end,
+					|along, height| line_point(decoration_style, along, height),
+					&mut id,
+					&mut subpaths[index],
+					&mut decorations[index],
+				);
+				decoration = None;
+			}
+			if path.is_none() && (style.underline || style.strikethrough) && !glyph.is_space {
+				decoration = Some((glyph.style, decoration.map_or(along, |(_, decoration_start, _)| decoration_start), along + glyph.advance));
+			}
+
+			// Glyphs of every style on the line share its baseline, from which they're shifted by their style
+			let position = DVec2::new(along, baseline - style.ascent() + glyph.position.y - style.baseline_shift);
+
+			let transform = match path {
+				Some(path) => {
+					let anchor = DVec2::new(position.x + glyph.advance / 2., baseline);
+					let Some((point, tangent)) = path.point_and_tangent(anchor.x) else { continue };
+					let angle = tangent.y.atan2(tangent.x);
+					DAffine2::from_translation(point + tangent.perp() * (anchor.y - path_baseline)) * DAffine2::from_angle(angle) * DAffine2::from_translation(position - anchor)
 				}
-				text_cursor += DVec2::new(glyph_position.x_advance as f64 * typesetting.character_spacing, glyph_position.y_advance as f64) * scale;
-				bounds = bounds.max(text_cursor + DVec2::new(0., line_height));
+				// Upright glyphs are centered across their column by their shaping offset, down to their baseline origin
+				// The baseline of vertical text is shifted rightwards across its column, towards the top of the glyphs on their side
+				None if typesetting.is_vertical() && glyph.upright => DAffine2::from_translation(DVec2::new(column_center - glyph.position.y + style.baseline_shift, along - style.ascent())),
+				// Glyphs on their side are turned clockwise, with the middle of their em box running down the center of their column
+				None if typesetting.is_vertical() => {
+					let em_center = (style.face.ascender() as f64 + style.face.descender() as f64) / 2. * style.scale();
+					DAffine2::from_translation(DVec2::new(column_center - em_center + style.baseline_shift, along))
+						* DAffine2::from_angle(FRAC_PI_2)
+						* DAffine2::from_translation(DVec2::new(0., glyph.position.y - style.ascent()))
+				}
+				None => DAffine2::from_translation(position),
+			};
+			// Spaces have no outline to place, and tabs are left empty like them
+			if !glyph.is_space {
+				glyph_outlines[glyph.style].place(glyph.id, transform, &mut id, &mut subpaths[glyph.style]);
+
+				// The outline is made of the glyph's points scaled to the font size and flipped to point down, with the baseline lowered to the ascent
+				let scale = style.scale();
+				glyphs.push(PlacedGlyph {
+					style: glyph.style,
+					id: glyph.id.0,
+					transform: transform * DAffine2::from_translation(DVec2::new(0., style.ascent())) * DAffine2::from_scale(DVec2::new(scale, -scale)),
+				});
+			}
+
+			// Along a path, the part of the decoration under each glyph is turned with it
+			if path.is_some() {
+				let glyph_point = |along: f64, height: f64| transform.transform_point2(DVec2::new(along, baseline - style.baseline_shift - height) - position);
+				style.place_decorations(along, along + glyph.advance, glyph_point, &mut id, &mut subpaths[glyph.style], &mut decorations[glyph.style]);
 			}
+		}
 
-			buffer = glyph_buffer.clear();
+		if let Some((index, decoration_start, decoration_end)) = decoration {
+			let decoration_style = &styles[index];
+			decoration_style.place_decorations(
+				decoration_start,
+				decoration_end,
+				|along, height| line_point(decoration_style, along, height),
+				&mut id,
+				&mut subpaths[index],
+				&mut decorations[index],
+			);
 		}
-		text_cursor = DVec2::new(0., text_cursor.y + line_height);
-		bounds = bounds.max(text_cursor);
+	}
+
+	TextOutlines { subpaths, glyphs, decorations }
+}
+
+pub fn bounding_box(str: &str, buzz_face: Option<&rustybuzz::Face>, typesetting: TypesettingConfig, for_clipping_test: bool) -> DVec2 {
+	// Show blank layer if font has not loaded
+	let Some(face) = buzz_face else { return DVec2::ZERO };
+
+	let styles = [TextStyle::new(
+		face.clone(),
+		typesetting.font_size,
+		typesetting.baseline_shift,
+		typesetting.script,
+		typesetting.underline,
+		typesetting.strikethrough,
+	)];
+	bounding_box_styled(str, &styles, &single_run(str), typesetting, for_clipping_test)
+}
+
+/// The size of the text with each of its runs, given as a byte range and the index of its style, typeset in its own style.
+pub fn bounding_box_styled(str: &str, styles: &[TextStyle], runs: &[(Range<usize>, usize)], typesetting: TypesettingConfig, for_clipping_test: bool) -> DVec2 {
+	// The columns of vertical text are measured like lines, with their length running down the text
+	let bounds = line_bounds(str, styles, runs, typesetting.line_layout(), for_clipping_test);
+	if typesetting.is_vertical() { DVec2::new(bounds.y, bounds.x) } else { bounds }
+}
+
+/// The size of the lines of the text, along and then across them.
+fn line_bounds(str: &str, styles: &[TextStyle], runs: &[(Range<usize>, usize)], typesetting: TypesettingConfig, for_clipping_test: bool) -> DVec2 {
+	if !for_clipping_test {
+		if let (Some(max_height), Some(max_width)) = (typesetting.max_height, typesetting.max_width) {
+			return DVec2::new(max_width, max_height);
+		}
+	}
+
+	let lines = typeset_lines(str, styles, runs, typesetting);
+	let available_width = available_width(&lines, typesetting);
+
+	let mut bounds = DVec2::ZERO;
+	for line in &lines {
+		let (start, gap_width) = line.alignment(typesetting, available_width);
+		// The spaces at the end of a right-to-left line hang outside of it on the left
+		let line_advance = if line.rtl { line.width } else { line.advance };
+		let end = if line.glyphs.is_empty() { 0. } else { start + line_advance + line.gaps as f64 * gap_width };
+		bounds = bounds.max(DVec2::new(end, line.y + line.height));
 	}
 
 	if !for_clipping_test {
@@ -208,14 +1571,212 @@ pub fn bounding_box(str: &str, buzz_face: Option<&rustybuzz::Face>, typesetting:
 	bounds
 }
 
-pub fn load_face(data: &[u8]) -> rustybuzz::Face {
-	rustybuzz::Face::from_slice(data, 0).expect("Loading font failed")
+/// The byte index in the text where the lines cut off by the max height begin, or `None` if the text fits or only empty lines are cut off.
+pub fn overflow_index_styled(str: &str, styles: &[TextStyle], runs: &[(Range<usize>, usize)], typesetting: TypesettingConfig) -> Option<usize> {
+	let typesetting = typesetting.line_layout();
+	typesetting.max_height?;
+
+	let lines = typeset_lines(str, styles, runs, typesetting);
+	first_clipped_line_start(&lines, str.len(), typesetting)
+}
+
+/// Where the first line cut off by the max height starts, unless it's an empty line at the very end of the text.
+fn first_clipped_line_start(lines: &[TypesetLine], text_len: usize, typesetting: TypesettingConfig) -> Option<usize> {
+	lines.iter().find(|line| line_clipped(line, typesetting)).map(|line| line.start).filter(|&start| start < text_len)
+}
+
+/// Where the caret is placed at a boundary between the characters of a text, in the coordinates of the text.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CaretPosition {
+	/// The index of the line the caret is on, or of the column in vertical text.
+	pub line: usize,
+	/// The ends of the caret across its line, from the top of the line, or the left of the column in vertical text.
+	pub ends: [DVec2; 2],
+	/// The ends of the caret after the character following this caret, which bound that character along with this caret when it's selected.
+	pub character_ends: [DVec2; 2],
+	/// How far along its line the caret is, from the left of the line or the top of the column in vertical text, before the line follows any path.
+	pub along: f64,
+}
+
+/// The position of the caret at each boundary between the characters of the text with each of its runs, given as a byte range and the index of its style, typeset in its own style.
+/// There's a caret before each character and one after the last, including for line breaks, and each is on the line the character after it starts.
+///
+/// The caret is at the leading edge of the character after it, which is its left edge in left-to-right text and its right edge in right-to-left text.
+/// Along a path, the carets are turned with the path like the glyphs, and those beyond either end of the path continue straight on from it.
+pub fn caret_positions_styled(str: &str, styles: &[TextStyle], runs: &[(Range<usize>, usize)], typesetting: TypesettingConfig, path: Option<&TextPath>) -> Vec<CaretPosition> {
+	let typesetting = match path {
+		Some(_) => TypesettingConfig {
+			writing_mode: WritingMode::Horizontal,
+			..typesetting
+		},
+		None => typesetting,
+	};
+	let line_layout = typesetting.line_layout();
+	let lines = typeset_lines(str, styles, runs, line_layout);
+	let available_width = available_width(&lines, line_layout);
+	let vertical_offset = vertical_offset(&lines, line_layout);
+	let path_baseline = lines.first().map_or(0., |line| line.y + line.ascent) + vertical_offset;
+	let right = typesetting.max_width.unwrap_or_else(|| lines.iter().map(|line| line.y + line.height).fold(0., f64::max));
+
+	let boundaries: Vec<_> = str.char_indices().map(|(index, _)| index).chain([str.len()]).collect();
+	let mut carets = Vec::with_capacity(boundaries.len());
+	for (line_index, line) in lines.iter().enumerate() {
+		let line_end = lines.get(line_index + 1).map_or(str.len() + 1, |next| next.start);
+		// The text of the line doesn't include the line break ending it
+		let text_end = line_end.min(str.len());
+		let text_end = if str[..text_end].ends_with('\n') && text_end > line.start { text_end - 1 } else { text_end };
+
+		let (start, gap_width) = line.alignment(typesetting, available_width);
+		let mut clusters: Vec<CaretCluster> = Vec::new();
+		for glyph in line.glyphs.iter().filter(|glyph| !glyph.hyphen) {
+			let left = glyph.position.x + start + glyph.gaps_before as f64 * gap_width;
+			let rtl = glyph.level.is_rtl();
+			let (leading, trailing) = if rtl { (left + glyph.advance, left) } else { (left, left + glyph.advance) };
+			match clusters.iter_mut().find(|cluster| cluster.start == glyph.cluster) {
+				Some(cluster) if rtl => (cluster.leading, cluster.trailing) = (cluster.leading.max(leading), cluster.trailing.min(trailing)),
+				Some(cluster) => (cluster.leading, cluster.trailing) = (cluster.leading.min(leading), cluster.trailing.max(trailing)),
+				None => clusters.push(CaretCluster {
+					start: glyph.cluster,
+					leading,
+					trailing,
+				}),
+			}
+		}
+		clusters.sort_by_key(|cluster| cluster.start);
+
+		let top = line.y + vertical_offset;
+		let ends = |along: f64| {
+			if typesetting.is_vertical() {
+				[DVec2::new(right - top - line.height, along), DVec2::new(right - top, along)]
+			} else {
+				let ends = [DVec2::new(along, top), DVec2::new(along, top + line.height)];
+				path.map_or(ends, |path| ends.map(|point| path.place(point, path_baseline)))
+			}
+		};
+		for &boundary in boundaries.iter().filter(|&&boundary| boundary >= line.start && boundary < line_end) {
+			let before = caret_edge(str, &clusters, text_end, boundary, false).unwrap_or(start);
+			let after = caret_edge(str, &clusters, text_end, boundary, true).unwrap_or(start);
+			carets.push(CaretPosition {
+				line: line_index,
+				ends: ends(before),
+				character_ends: ends(after),
+				along: before,
+			});
+		}
+	}
+
+	carets
+}
+
+/// The characters of a line of text displayed by one or more glyphs, like a ligature, and the edges of those glyphs along the line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct CaretCluster {
+	/// The byte index in the text where the cluster starts.
+	start: usize,
+	/// The edge of the cluster the text runs from, which is its left edge in left-to-right text.
+	leading: f64,
+	trailing: f64,
+}
+
+/// The position along its line of the caret before the character at the byte index, or after it, given the clusters of the line in the order of the text and where the line's text ends.
+/// Each character within a cluster takes an equal part of it. Returns `None` for a line without any glyphs.
+fn caret_edge(str: &str, clusters: &[CaretCluster], text_end: usize, index: usize, after: bool) -> Option<f64> {
+	let cluster_index = clusters.iter().rposition(|cluster| cluster.start <= index).or((!clusters.is_empty()).then_some(0))?;
+	let cluster = clusters[cluster_index];
+	let cluster_end = clusters.get(cluster_index + 1).map_or(text_end, |next| next.start).max(cluster.start);
+	let index = index.clamp(cluster.start, cluster_end);
+
+	let characters = str[cluster.start..cluster_end].chars().count().max(1);
+	let mut characters_before = str[cluster.start..index].chars().count();
+	if after && index < cluster_end {
+		characters_before += 1;
+	}
+
+	let fraction = (characters_before as f64 / characters as f64).min(1.);
+	Some(cluster.leading + (cluster.trailing - cluster.leading) * fraction)
+}
+
+/// The indices of the characters which start each grapheme cluster of the text, followed by the number of characters, which are where the caret can be placed.
+/// A grapheme keeps combining marks, variation selectors, and emoji modifiers and tags with the character before them, emoji joined by a zero width joiner, the pairs of regional indicators making up flags, and a carriage return followed by a line feed.
+pub fn grapheme_boundaries(str: &str) -> Vec<usize> {
+	const ZERO_WIDTH_JOINER: char = '\u{200D}';
+	let extends = |character: char| {
+		character == ZERO_WIDTH_JOINER
+			|| bidi_class(character) == BidiClass::NSM
+			|| matches!(character, '\u{FE00}'..='\u{FE0F}' | '\u{1F3FB}'..='\u{1F3FF}' | '\u{E0020}'..='\u{E007F}' | '\u{E0100}'..='\u{E01EF}')
+	};
+	let regional_indicator = |character: char| matches!(character, '\u{1F1E6}'..='\u{1F1FF}');
+
+	let mut boundaries = Vec::new();
+	let mut previous: Option<char> = None;
+	let mut regional_indicators = 0;
+	let mut count = 0;
+	for character in str.chars() {
+		let joined = previous
+			.is_some_and(|previous| (previous == '\r' && character == '\n') || extends(character) || previous == ZERO_WIDTH_JOINER || (regional_indicator(character) && regional_indicators % 2 == 1));
+		if !joined {
+			boundaries.push(count);
+		}
+
+		regional_indicators = if regional_indicator(character) { regional_indicators + 1 } else { 0 };
+		previous = Some(character);
+		count += 1;
+	}
+	boundaries.push(count);
+	boundaries
+}
+
+/// The caret the arrow key moves to from the given caret, which is the closest caret to the left or right of it on its line, going through right-to-left text in the order it's shown.
+/// The caret only stops at the given boundaries between graphemes. From either end of a line, it goes on to the line before or after it, whichever continues the text on that side.
+pub fn caret_to_side(carets: &[CaretPosition], boundaries: &[usize], caret: usize, rightwards: bool) -> usize {
+	let previous = boundaries.iter().rev().find(|&&boundary| boundary < caret).copied().unwrap_or(caret);
+	let next = boundaries.iter().find(|&&boundary| boundary > caret).copied().unwrap_or(caret);
+	let Some(current) = carets.get(caret) else {
+		return if rightwards { next } else { previous };
+	};
+
+	let on_line = || {
+		boundaries
+			.iter()
+			.copied()
+			.filter(|&boundary| carets.get(boundary).is_some_and(|position| position.line == current.line))
+	};
+	let towards = |along: f64| if rightwards { along > current.along + f64::EPSILON } else { along < current.along - f64::EPSILON };
+	let closest = on_line().filter(|&boundary| towards(carets[boundary].along)).min_by(|&a, &b| {
+		(carets[a].along - current.along)
+			.abs()
+			.total_cmp(&(carets[b].along - current.along).abs())
+			.then(a.abs_diff(caret).cmp(&b.abs_diff(caret)))
+	});
+	if let Some(closest) = closest {
+		return closest;
+	}
+
+	// At the end of the line, the side which the text continues from depends on whether the line runs left to right or right to left
+	let (first, last) = (on_line().next(), on_line().last());
+	let line_rtl = first.zip(last).is_some_and(|(first, last)| carets[first].along > carets[last].along);
+	if rightwards != line_rtl { next } else { previous }
+}
+
+/// Loads the font, with the values set for the axes of a variable font applied. Fonts without variable axes are loaded unchanged.
+pub fn load_face<'a>(data: &'a [u8], variations: &FontVariations) -> rustybuzz::Face<'a> {
+	let mut face = rustybuzz::Face::from_slice(data, 0).expect("Loading font failed");
+	let coordinates: Vec<_> = variations
+		.iter()
+		.map(|variation| rustybuzz::Variation {
+			tag: Tag::from_bytes(&variation.tag),
+			value: variation.value as f32,
+		})
+		.collect();
+	if !coordinates.is_empty() {
+		face.set_variations(&coordinates);
+	}
+	face
 }
 
 pub fn lines_clipping(str: &str, buzz_face: Option<rustybuzz::Face>, typesetting: TypesettingConfig) -> bool {
-	let Some(max_height) = typesetting.max_height else { return false };
 	let bounds = bounding_box(str, buzz_face.as_ref(), typesetting, true);
-	max_height < bounds.y
+	typesetting.clips(bounds)
 }
 
 struct SplitWordsIncludingSpaces<'a> {
@@ -232,7 +1793,9 @@ impl<'a> SplitWordsIncludingSpaces<'a> {
 impl<'a> Iterator for SplitWordsIncludingSpaces<'a> {
 	type Item = &'a str;
 	fn next(&mut self) -> Option<Self::Item> {
-		let mut eaten_chars = self.text[self.start_byte..].char_indices().skip_while(|(_, c)| *c != ' ').skip_while(|(_, c)| *c == ' ');
+		// Tabs separate words like spaces do
+		let is_space = |c: char| c == ' ' || c == '\t';
+		let mut eaten_chars = self.text[self.start_byte..].char_indices().skip_while(|&(_, c)| !is_space(c)).skip_while(|&(_, c)| is_space(c));
 		let start_byte = self.start_byte;
 		self.start_byte = eaten_chars.next().map_or(self.text.len(), |(offset, _)| self.start_byte + offset);
 		(self.start_byte > start_byte).then(|| self.text.get(start_byte..self.start_byte)).flatten()
@@ -246,4 +1809,405 @@ fn split_words_including_spaces() {
 	assert_eq!(split_words.next(), Some("world     "));
 	assert_eq!(split_words.next(), Some("."));
 	assert_eq!(split_words.next(), None);
+
+	let mut split_words = SplitWordsIncludingSpaces::new("name\t 12");
+	assert_eq!(split_words.next(), Some("name\t "));
+	assert_eq!(split_words.next(), Some("12"));
+	assert_eq!(split_words.next(), None);
+}
+
+#[test]
+fn mixed_orientation_keeps_cjk_upright() {
+	assert!(upright_in_mixed_orientation('漢'));
+	assert!(upright_in_mixed_orientation('か'));
+	assert!(upright_in_mixed_orientation('한'));
+	assert!(upright_in_mixed_orientation('、'));
+	assert!(!upright_in_mixed_orientation('A'));
+	assert!(!upright_in_mixed_orientation('1'));
+	assert!(!upright_in_mixed_orientation('ש'));
+}
+
+#[test]
+fn font_features_enable_stylistic_sets() {
+	let mut features = FontFeatures::default();
+	features.set_stylistic_set(1, true);
+	features.set_stylistic_set(20, true);
+	features.set_stylistic_set(21, true);
+	assert!(features.stylistic_set(1) && features.stylistic_set(20));
+	assert!(!features.stylistic_set(2) && !features.stylistic_set(21));
+
+	let tags: Vec<_> = features.opentype_features().iter().filter(|feature| feature.value == 1).map(|feature| feature.tag).collect();
+	assert_eq!(tags, [b"liga", b"clig", b"ss01", b"ss20"].map(Tag::from_bytes));
+}
+
+#[test]
+fn font_variations_replace_existing_axes() {
+	let mut variations = FontVariations::default();
+	variations.set(*b"wght", 400.);
+	variations.set(*b"wdth", 75.);
+	variations.set(*b"wght", 700.);
+	assert_eq!(variations.get(*b"wght"), Some(700.));
+	assert_eq!(variations.get(*b"wdth"), Some(75.));
+	assert_eq!(variations.get(*b"slnt"), None);
+	assert_eq!(variations.iter().count(), 2);
+}
+
+#[test]
+fn caret_edges_divide_ligatures_evenly() {
+	// "ffi" shaped as a ligature, then "x", followed by a line break
+	let clusters = [
+		CaretCluster { start: 0, leading: 0., trailing: 30. },
+		CaretCluster {
+			start: 3,
+			leading: 30.,
+			trailing: 40.,
+		},
+	];
+	let str = "ffix\n";
+	assert_eq!(caret_edge(str, &clusters, 4, 0, false), Some(0.));
+	assert_eq!(caret_edge(str, &clusters, 4, 1, false), Some(10.));
+	assert_eq!(caret_edge(str, &clusters, 4, 2, true), Some(30.));
+	assert_eq!(caret_edge(str, &clusters, 4, 3, false), Some(30.));
+	assert_eq!(caret_edge(str, &clusters, 4, 3, true), Some(40.));
+	// The caret at the line break is after the last character, where the character after it has no width
+	assert_eq!(caret_edge(str, &clusters, 4, 4, false), Some(40.));
+	assert_eq!(caret_edge(str, &clusters, 4, 4, true), Some(40.));
+	assert_eq!(caret_edge(str, &[], 0, 0, false), None);
+}
+
+#[test]
+fn caret_edges_run_leftwards_in_right_to_left_text() {
+	let clusters = [
+		CaretCluster {
+			start: 0,
+			leading: 20.,
+			trailing: 10.,
+		},
+		CaretCluster { start: 2, leading: 10., trailing: 0. },
+	];
+	let str = "אב";
+	assert_eq!(caret_edge(str, &clusters, 4, 0, false), Some(20.));
+	assert_eq!(caret_edge(str, &clusters, 4, 0, true), Some(10.));
+	assert_eq!(caret_edge(str, &clusters, 4, 4, false), Some(0.));
+}
+
+#[test]
+fn vertical_alignment_includes_paragraph_spacing() {
+	// Two paragraphs of one line each, with 5 px between them
+	let lines = [
+		TypesetLine {
+			y: 0.,
+			height: 10.,
+			..Default::default()
+		},
+		TypesetLine {
+			y: 15.,
+			height: 10.,
+			..Default::default()
+		},
+	];
+	let typesetting = TypesettingConfig {
+		max_height: Some(45.),
+		vertical_align: VerticalAlign::Bottom,
+		..Default::default()
+	};
+	assert_eq!(vertical_offset(&lines, typesetting), 20.);
+}
+
+#[test]
+fn tabs_reach_the_next_stop() {
+	let mut tab_stops = TabStops::default();
+	assert_eq!(tab_stops.next_stop(0.), 48.);
+	assert_eq!(tab_stops.next_stop(48.), 96.);
+	assert_eq!(tab_stops.next_stop(50.), 96.);
+
+	// Custom stops come first, followed by multiples of the interval from the start of the line
+	tab_stops.set_stops([120., -5., 30.]);
+	assert_eq!(tab_stops.stops().collect::<Vec<_>>(), [30., 120.]);
+	assert_eq!(tab_stops.next_stop(10.), 30.);
+	assert_eq!(tab_stops.next_stop(30.), 120.);
+	assert_eq!(tab_stops.next_stop(130.), 144.);
+}
+
+#[test]
+fn case_keeps_character_positions() {
+	let text = "the straße's end";
+	let runs = single_run(text);
+	assert_eq!(TextCase::Uppercase.apply(text, &[], &runs), "THE STRAßE'S END");
+	assert_eq!(TextCase::TitleCase.apply(text, &[], &runs), "The Straße's End");
+	assert_eq!(TextCase::Lowercase.apply("ÀB", &[], &single_run("ÀB")), "àb");
+}
+
+#[test]
+fn indents_narrow_the_aligned_width() {
+	let typesetting = TypesettingConfig {
+		left_indent: 10.,
+		right_indent: 20.,
+		first_line_indent: 5.,
+		..Default::default()
+	};
+	let first = TypesetLine {
+		width: 50.,
+		first: true,
+		..Default::default()
+	};
+	let next = TypesetLine { width: 50., ..Default::default() };
+	assert_eq!(first.alignment(typesetting, 100.), (15., 0.));
+	assert_eq!(next.alignment(typesetting, 100.), (10., 0.));
+
+	// Right aligned lines end at the right indent, with the first line indent on the left where the paragraph starts
+	let right = TypesettingConfig {
+		align: TextAlign::Right,
+		..typesetting
+	};
+	assert_eq!(first.alignment(right, 100.), (30., 0.));
+	assert_eq!(next.alignment(right, 100.), (30., 0.));
+
+	// Right-to-left paragraphs start from the right, so their first line indent is there instead
+	let first_rtl = TypesetLine { rtl: true, ..first };
+	assert_eq!(first_rtl.alignment(right, 100.), (25., 0.));
+}
+
+#[cfg(test)]
+fn clipping_test_line(start: usize, y: f64) -> TypesetLine {
+	TypesetLine {
+		start,
+		y,
+		height: 10.,
+		..Default::default()
+	}
+}
+
+#[test]
+fn text_path_follows_each_segment_from_start_offset() {
+	let subpath = Subpath::from_anchors([DVec2::ZERO, DVec2::new(100., 0.), DVec2::new(100., 100.)], false);
+	let path = TextPath::new(&subpath, 20.);
+
+	let (point, tangent) = path.point_and_tangent(30.).unwrap();
+	assert!(point.abs_diff_eq(DVec2::new(50., 0.), 1e-6));
+	assert!(tangent.abs_diff_eq(DVec2::X, 1e-6));
+
+	// Past the end of the first segment, the text continues down the second one
+	let (point, tangent) = path.point_and_tangent(130.).unwrap();
+	assert!(point.abs_diff_eq(DVec2::new(100., 50.), 1e-6));
+	assert!(tangent.abs_diff_eq(DVec2::Y, 1e-6));
+}
+
+#[test]
+fn text_path_ends_with_its_subpath() {
+	let subpath = Subpath::from_anchors([DVec2::ZERO, DVec2::new(100., 0.)], false);
+	let path = TextPath::new(&subpath, 20.);
+
+	assert!(path.point_and_tangent(-30.).is_none());
+	assert!(path.point_and_tangent(90.).is_none());
+	assert!(path.point_and_tangent(-20.).unwrap().0.abs_diff_eq(DVec2::ZERO, 1e-6));
+}
+
+#[test]
+fn text_path_places_points_beside_it() {
+	let subpath = Subpath::from_anchors([DVec2::ZERO, DVec2::new(100., 0.), DVec2::new(100., 100.)], false);
+	let path = TextPath::new(&subpath, 0.);
+
+	// Points below the baseline go to the right of the path as it runs downwards
+	assert!(path.place(DVec2::new(150., 20.), 10.).abs_diff_eq(DVec2::new(90., 50.), 1e-6));
+	// Beyond the end of the path, points continue straight on from it
+	assert!(path.place(DVec2::new(220., 10.), 10.).abs_diff_eq(DVec2::new(100., 120.), 1e-6));
+	assert!(path.place(DVec2::new(-10., 10.), 10.).abs_diff_eq(DVec2::new(-10., 0.), 1e-6));
+}
+
+#[test]
+fn graphemes_keep_combining_characters_together() {
+	// "e" with a combining acute accent, then a family emoji joined by zero width joiners
+	assert_eq!(grapheme_boundaries("ae\u{301}b"), vec![0, 1, 3, 4]);
+	assert_eq!(grapheme_boundaries("a\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}b"), vec![0, 1, 6, 7]);
+	// A thumbs up with a skin tone, and two flags of two regional indicators each
+	assert_eq!(grapheme_boundaries("\u{1F44D}\u{1F3FD}\u{1F1EB}\u{1F1F7}\u{1F1EF}\u{1F1F5}"), vec![0, 2, 4, 6]);
+	assert_eq!(grapheme_boundaries("a\r\nb"), vec![0, 1, 3, 4]);
+	assert_eq!(grapheme_boundaries(""), vec![0]);
+}
+
+#[cfg(test)]
+fn caret_at(line: usize, along: f64) -> CaretPosition {
+	let ends = [DVec2::new(along, 0.), DVec2::new(along, 10.)];
+	CaretPosition {
+		line,
+		ends,
+		character_ends: ends,
+		along,
+	}
+}
+
+#[test]
+fn arrow_keys_move_through_right_to_left_text_as_shown() {
+	// "ab" followed by the right-to-left "CD", shown as "abDC", with the caret after them at the left edge of "D"
+	let carets = [caret_at(0, 0.), caret_at(0, 10.), caret_at(0, 40.), caret_at(0, 30.), caret_at(0, 20.)];
+	let boundaries = [0, 1, 2, 3, 4];
+	assert_eq!(caret_to_side(&carets, &boundaries, 1, true), 4);
+	assert_eq!(caret_to_side(&carets, &boundaries, 4, true), 3);
+	assert_eq!(caret_to_side(&carets, &boundaries, 3, true), 2);
+	assert_eq!(caret_to_side(&carets, &boundaries, 2, false), 3);
+	assert_eq!(caret_to_side(&carets, &boundaries, 4, false), 1);
+}
+
+#[test]
+fn arrow_keys_continue_on_the_next_line_of_right_to_left_text() {
+	// Two right-to-left lines, each running leftwards from the right edge
+	let carets = [caret_at(0, 30.), caret_at(0, 20.), caret_at(0, 10.), caret_at(1, 30.), caret_at(1, 20.)];
+	let boundaries = [0, 1, 2, 3, 4];
+	assert_eq!(caret_to_side(&carets, &boundaries, 2, false), 3);
+	assert_eq!(caret_to_side(&carets, &boundaries, 3, true), 2);
+	assert_eq!(caret_to_side(&carets, &boundaries, 1, false), 2);
+}
+
+#[test]
+fn arrow_keys_skip_over_graphemes() {
+	let carets = [caret_at(0, 0.), caret_at(0, 10.), caret_at(0, 10.), caret_at(0, 20.)];
+	let boundaries = [0, 1, 3];
+	assert_eq!(caret_to_side(&carets, &boundaries, 1, true), 3);
+	assert_eq!(caret_to_side(&carets, &boundaries, 3, false), 1);
+}
+
+#[test]
+fn text_without_max_height_never_overflows() {
+	assert_eq!(overflow_index_styled("hello\nworld", &[], &[], TypesettingConfig::default()), None);
+}
+
+#[test]
+fn overflow_starts_at_first_clipped_line() {
+	let typesetting = TypesettingConfig {
+		max_height: Some(25.),
+		..Default::default()
+	};
+	let lines = [clipping_test_line(0, 0.), clipping_test_line(6, 10.), clipping_test_line(12, 20.), clipping_test_line(18, 30.)];
+	assert_eq!(first_clipped_line_start(&lines, 24, typesetting), Some(12));
+	assert_eq!(first_clipped_line_start(&lines[..2], 12, typesetting), None);
+}
+
+#[test]
+fn empty_line_at_end_doesnt_overflow() {
+	let typesetting = TypesettingConfig {
+		max_height: Some(15.),
+		..Default::default()
+	};
+	// The text ends with a line break, leaving an empty last line starting at its end
+	let lines = [clipping_test_line(0, 0.), clipping_test_line(6, 10.)];
+	assert_eq!(first_clipped_line_start(&lines, 6, typesetting), None);
+	assert_eq!(first_clipped_line_start(&lines, 12, typesetting), Some(6));
+}
+
+#[test]
+fn vertical_text_overflows_max_width() {
+	let typesetting = TypesettingConfig {
+		max_width: Some(15.),
+		writing_mode: WritingMode::VerticalRightToLeft,
+		..Default::default()
+	}
+	.line_layout();
+	let lines = [clipping_test_line(0, 0.), clipping_test_line(4, 10.)];
+	assert_eq!(first_clipped_line_start(&lines, 8, typesetting), Some(4));
+}
+
+/// A line of glyphs each 10 wide, placed one after another in the order of the text, with each glyph given as its cluster, embedding level, and whether it's a space.
+#[cfg(test)]
+fn bidi_test_line(glyphs: &[(usize, u8, bool)], rtl: bool) -> TypesetLine {
+	let glyphs = glyphs
+		.iter()
+		.enumerate()
+		.map(|(index, &(cluster, level, is_space))| TypesetGlyph {
+			id: GlyphId(0),
+			style: 0,
+			position: DVec2::new(index as f64 * 10., 0.),
+			advance: 10.,
+			level: Level::new(level).unwrap(),
+			upright: false,
+			is_space,
+			gaps_before: 0,
+			cluster,
+			hyphen: false,
+		})
+		.collect();
+	TypesetLine { glyphs, rtl, ..Default::default() }
+}
+
+#[cfg(test)]
+fn visual_order(line: &TypesetLine) -> Vec<(usize, f64)> {
+	line.glyphs.iter().map(|glyph| (glyph.cluster, glyph.position.x)).collect()
+}
+
+#[test]
+fn left_to_right_lines_keep_their_order() {
+	let mut line = bidi_test_line(&[(0, 0, false), (1, 0, true), (2, 0, false)], false);
+	line.reorder_visually();
+	assert_eq!(visual_order(&line), [(0, 0.), (1, 10.), (2, 20.)]);
+}
+
+#[test]
+fn right_to_left_runs_are_reversed_within_left_to_right_lines() {
+	// "ab", then the right-to-left "CD" shown as "DC", then "e"
+	let mut line = bidi_test_line(&[(0, 0, false), (1, 0, false), (2, 1, false), (3, 1, false), (4, 0, false)], false);
+	line.reorder_visually();
+	assert_eq!(visual_order(&line), [(0, 0.), (1, 10.), (3, 20.), (2, 30.), (4, 40.)]);
+}
+
+#[test]
+fn left_to_right_runs_keep_their_order_within_right_to_left_lines() {
+	// The right-to-left "AB", then the left-to-right "xy" and a space ending the line, which hangs off its left end
+	let mut line = bidi_test_line(&[(0, 1, false), (1, 1, false), (2, 2, false), (3, 2, false), (4, 1, true)], true);
+	line.reorder_visually();
+	assert_eq!(visual_order(&line), [(4, -10.), (2, 0.), (3, 10.), (1, 20.), (0, 30.)]);
+}
+
+#[test]
+fn trailing_spaces_take_the_direction_of_the_paragraph() {
+	// A left-to-right line ending with right-to-left text and a space, which stays at the right end of the line
+	let mut line = bidi_test_line(&[(0, 0, false), (1, 1, false), (2, 1, false), (3, 1, true)], false);
+	line.reorder_visually();
+	assert_eq!(visual_order(&line), [(0, 0.), (2, 10.), (1, 20.), (3, 30.)]);
+}
+
+#[test]
+fn wrap_modes_are_listed_by_name() {
+	let names: Vec<_> = WrapMode::list().iter().map(ToString::to_string).collect();
+	assert_eq!(names, ["Word", "Character", "None"]);
+	assert_eq!(WrapMode::default(), WrapMode::Word);
+}
+
+#[test]
+fn unwrapped_lines_overflow_from_the_side_theyre_aligned_to() {
+	let line = TypesetLine { width: 150., ..Default::default() };
+	let typesetting = |align, wrap| TypesettingConfig {
+		max_width: Some(100.),
+		align,
+		wrap,
+		..Default::default()
+	};
+	assert_eq!(line.alignment(typesetting(TextAlign::Left, WrapMode::None), 100.).0, 0.);
+	assert_eq!(line.alignment(typesetting(TextAlign::Center, WrapMode::None), 100.).0, -25.);
+	assert_eq!(line.alignment(typesetting(TextAlign::Right, WrapMode::None), 100.).0, -50.);
+	// Wrapped lines only overflow when a single word is too wide, which stays at the start of the line
+	assert_eq!(line.alignment(typesetting(TextAlign::Right, WrapMode::Word), 100.).0, 0.);
+}
+
+#[test]
+fn right_to_left_unwrapped_lines_overflow_on_the_left() {
+	let line = TypesetLine {
+		width: 150.,
+		rtl: true,
+		..Default::default()
+	};
+	let typesetting = TypesettingConfig {
+		max_width: Some(100.),
+		align: TextAlign::Justify,
+		wrap: WrapMode::None,
+		..Default::default()
+	};
+	assert_eq!(line.alignment(typesetting, 100.).0, -50.);
+}
+
+#[test]
+fn unwrapped_glyphs_are_clipped_at_both_edges() {
+	assert!(!glyph_clipped(0., 10., 100.));
+	assert!(!glyph_clipped(90., 10., 100.));
+	assert!(glyph_clipped(95., 10., 100.));
+	assert!(glyph_clipped(-5., 10., 100.));
 }
@@ -81,6 +81,19 @@ pub enum RenderQuality {
 	/// Render at full quality
 	Full,
 }
+
+impl RenderQuality {
+	/// Fraction of the full resolution that nodes generating pixels should produce at this quality.
+	/// The viewport renderer also simplifies vector paths to the detail that can be seen at this resolution.
+	pub fn resolution_scale(&self) -> f64 {
+		match self {
+			Self::Preview => 0.25,
+			Self::Scale(scale) => (*scale as f64).clamp(f64::EPSILON, 1.),
+			Self::Probability(_) | Self::Full => 1.,
+		}
+	}
+}
+
 #[derive(Debug, Clone, Copy, dyn_any::DynAny, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Footprint {
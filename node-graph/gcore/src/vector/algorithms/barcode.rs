@@ -0,0 +1,690 @@
+use crate::vector::misc::{BarcodeFormat, QrErrorCorrection};
+
+/// The dark (`true`) and light modules of a barcode, row by row, with a single row for 1D barcodes.
+pub(crate) fn encode(text: &str, format: BarcodeFormat, error_correction: QrErrorCorrection) -> Option<Vec<Vec<bool>>> {
+	match format {
+		BarcodeFormat::QrCode => qr_code(text.as_bytes(), error_correction),
+		BarcodeFormat::Code128 => code_128(text).map(|row| vec![row]),
+		BarcodeFormat::Ean13 => ean_13(text).map(|row| vec![row]),
+		// A UPC-A code is the EAN-13 code of the same number with a leading zero
+		BarcodeFormat::UpcA => ean_13(&format!("0{}", text.trim())).map(|row| vec![row]),
+	}
+}
+
+/// Appends the modules of alternating bars and spaces of the given widths, starting with a bar.
+fn push_widths(modules: &mut Vec<bool>, widths: &[u8]) {
+	for (index, &width) in widths.iter().enumerate() {
+		modules.extend(std::iter::repeat_n(index % 2 == 0, width as usize));
+	}
+}
+
+// ==============
+// Code 128
+// ==============
+
+/// The bar and space widths of each Code 128 symbol value, with 103 to 105 being the start symbols of code sets A to C.
+const CODE_128_PATTERNS: [[u8; 6]; 106] = [
+	[2, 1, 2, 2, 2, 2],
+	[2, 2, 2, 1, 2, 2],
+	[2, 2, 2, 2, 2, 1],
+	[1, 2, 1, 2, 2, 3],
+	[1, 2, 1, 3, 2, 2],
+	[1, 3, 1, 2, 2, 2],
+	[1, 2, 2, 2, 1, 3],
+	[1, 2, 2, 3, 1, 2],
+	[1, 3, 2, 2, 1, 2],
+	[2, 2, 1, 2, 1, 3],
+	[2, 2, 1, 3, 1, 2],
+	[2, 3, 1, 2, 1, 2],
+	[1, 1, 2, 2, 3, 2],
+	[1, 2, 2, 1, 3, 2],
+	[1, 2, 2, 2, 3, 1],
+	[1, 1, 3, 2, 2, 2],
+	[1, 2, 3, 1, 2, 2],
+	[1, 2, 3, 2, 2, 1],
+	[2, 2, 3, 2, 1, 1],
+	[2, 2, 1, 1, 3, 2],
+	[2, 2, 1, 2, 3, 1],
+	[2, 1, 3, 2, 1, 2],
+	[2, 2, 3, 1, 1, 2],
+	[3, 1, 2, 1, 3, 1],
+	[3, 1, 1, 2, 2, 2],
+	[3, 2, 1, 1, 2, 2],
+	[3, 2, 1, 2, 2, 1],
+	[3, 1, 2, 2, 1, 2],
+	[3, 2, 2, 1, 1, 2],
+	[3, 2, 2, 2, 1, 1],
+	[2, 1, 2, 1, 2, 3],
+	[2, 1, 2, 3, 2, 1],
+	[2, 3, 2, 1, 2, 1],
+	[1, 1, 1, 3, 2, 3],
+	[1, 3, 1, 1, 2, 3],
+	[1, 3, 1, 3, 2, 1],
+	[1, 1, 2, 3, 1, 3],
+	[1, 3, 2, 1, 1, 3],
+	[1, 3, 2, 3, 1, 1],
+	[2, 1, 1, 3, 1, 3],
+	[2, 3, 1, 1, 1, 3],
+	[2, 3, 1, 3, 1, 1],
+	[1, 1, 2, 1, 3, 3],
+	[1, 1, 2, 3, 3, 1],
+	[1, 3, 2, 1, 3, 1],
+	[1, 1, 3, 1, 2, 3],
+	[1, 1, 3, 3, 2, 1],
+	[1, 3, 3, 1, 2, 1],
+	[3, 1, 3, 1, 2, 1],
+	[2, 1, 1, 3, 3, 1],
+	[2, 3, 1, 1, 3, 1],
+	[2, 1, 3, 1, 1, 3],
+	[2, 1, 3, 3, 1, 1],
+	[2, 1, 3, 1, 3, 1],
+	[3, 1, 1, 1, 2, 3],
+	[3, 1, 1, 3, 2, 1],
+	[3, 3, 1, 1, 2, 1],
+	[3, 1, 2, 1, 1, 3],
+	[3, 1, 2, 3, 1, 1],
+	[3, 3, 2, 1, 1, 1],
+	[3, 1, 4, 1, 1, 1],
+	[2, 2, 1, 4, 1, 1],
+	[4, 3, 1, 1, 1, 1],
+	[1, 1, 1, 2, 2, 4],
+	[1, 1, 1, 4, 2, 2],
+	[1, 2, 1, 1, 2, 4],
+	[1, 2, 1, 4, 2, 1],
+	[1, 4, 1, 1, 2, 2],
+	[1, 4, 1, 2, 2, 1],
+	[1, 1, 2, 2, 1, 4],
+	[1, 1, 2, 4, 1, 2],
+	[1, 2, 2, 1, 1, 4],
+	[1, 2, 2, 4, 1, 1],
+	[1, 4, 2, 1, 1, 2],
+	[1, 4, 2, 2, 1, 1],
+	[2, 4, 1, 2, 1, 1],
+	[2, 2, 1, 1, 1, 4],
+	[4, 1, 3, 1, 1, 1],
+	[2, 4, 1, 1, 1, 2],
+	[1, 3, 4, 1, 1, 1],
+	[1, 1, 1, 2, 4, 2],
+	[1, 2, 1, 1, 4, 2],
+	[1, 2, 1, 2, 4, 1],
+	[1, 1, 4, 2, 1, 2],
+	[1, 2, 4, 1, 1, 2],
+	[1, 2, 4, 2, 1, 1],
+	[4, 1, 1, 2, 1, 2],
+	[4, 2, 1, 1, 1, 2],
+	[4, 2, 1, 2, 1, 1],
+	[2, 1, 2, 1, 4, 1],
+	[2, 1, 4, 1, 2, 1],
+	[4, 1, 2, 1, 2, 1],
+	[1, 1, 1, 1, 4, 3],
+	[1, 1, 1, 3, 4, 1],
+	[1, 3, 1, 1, 4, 1],
+	[1, 1, 4, 1, 1, 3],
+	[1, 1, 4, 3, 1, 1],
+	[4, 1, 1, 1, 1, 3],
+	[4, 1, 1, 3, 1, 1],
+	[1, 1, 3, 1, 4, 1],
+	[1, 1, 4, 1, 3, 1],
+	[3, 1, 1, 1, 4, 1],
+	[4, 1, 1, 1, 3, 1],
+	[2, 1, 1, 4, 1, 2],
+	[2, 1, 1, 2, 1, 4],
+	[2, 1, 1, 2, 3, 2],
+];
+const CODE_128_STOP: [u8; 7] = [2, 3, 3, 1, 1, 1, 2];
+const CODE_128_START_B: usize = 104;
+const CODE_128_START_C: usize = 105;
+
+/// Encodes printable ASCII text with code set B, or an even number of digits more compactly with code set C.
+fn code_128(text: &str) -> Option<Vec<bool>> {
+	if text.is_empty() {
+		return None;
+	}
+
+	let (start, values) = if text.len() % 2 == 0 && text.bytes().all(|byte| byte.is_ascii_digit()) {
+		let values = text.as_bytes().chunks(2).map(|pair| ((pair[0] - b'0') * 10 + (pair[1] - b'0')) as usize).collect::<Vec<_>>();
+		(CODE_128_START_C, values)
+	} else if text.bytes().all(|byte| (32..128).contains(&byte)) {
+		(CODE_128_START_B, text.bytes().map(|byte| (byte - 32) as usize).collect())
+	} else {
+		return None;
+	};
+
+	let checksum = values.iter().enumerate().fold(start, |sum, (index, value)| sum + (index + 1) * value) % 103;
+
+	let mut modules = Vec::new();
+	for value in std::iter::once(start).chain(values).chain(std::iter::once(checksum)) {
+		push_widths(&mut modules, &CODE_128_PATTERNS[value]);
+	}
+	push_widths(&mut modules, &CODE_128_STOP);
+	Some(modules)
+}
+
+// ==============
+// EAN-13
+// ==============
+
+/// The modules of each digit in the left half of the code with odd parity, from which the other two encodings are derived.
+const EAN_L_CODES: [u8; 10] = [0b0001101, 0b0011001, 0b0010011, 0b0111101, 0b0100011, 0b0110001, 0b0101111, 0b0111011, 0b0110111, 0b0001011];
+/// The parities of the left half's six digits which encode the first digit, where a set bit is even parity.
+const EAN_PARITIES: [u8; 10] = [0b000000, 0b001011, 0b001101, 0b001110, 0b010011, 0b011001, 0b011100, 0b010101, 0b010110, 0b011010];
+
+/// The check digit making the weighted sum of an EAN-13 code's digits a multiple of 10.
+fn ean_check_digit(digits: &[u8]) -> u8 {
+	let sum: u32 = digits.iter().enumerate().map(|(index, &digit)| digit as u32 * if index % 2 == 0 { 1 } else { 3 }).sum();
+	((10 - sum % 10) % 10) as u8
+}
+
+/// Encodes 12 digits followed by their check digit, which is added if left out and must otherwise be correct.
+fn ean_13(text: &str) -> Option<Vec<bool>> {
+	let mut digits = text.trim().bytes().map(|byte| byte.is_ascii_digit().then(|| byte - b'0')).collect::<Option<Vec<_>>>()?;
+	match digits.len() {
+		12 => digits.push(ean_check_digit(&digits)),
+		13 if digits[12] == ean_check_digit(&digits[..12]) => {}
+		_ => return None,
+	}
+
+	let push_code = |modules: &mut Vec<bool>, code: u8| modules.extend((0..7).rev().map(|bit| code >> bit & 1 == 1));
+	let mirror = |code: u8| code.reverse_bits() >> 1;
+
+	let mut modules = vec![true, false, true];
+	let parities = EAN_PARITIES[digits[0] as usize];
+	for (index, &digit) in digits[1..7].iter().enumerate() {
+		let l_code = EAN_L_CODES[digit as usize];
+		let even_parity = parities >> (5 - index) & 1 == 1;
+		push_code(&mut modules, if even_parity { mirror(!l_code & 0b1111111) } else { l_code });
+	}
+	modules.extend([false, true, false, true, false]);
+	for &digit in &digits[7..] {
+		push_code(&mut modules, !EAN_L_CODES[digit as usize] & 0b1111111);
+	}
+	modules.extend([true, false, true]);
+	Some(modules)
+}
+
+// ==============
+// QR code
+// ==============
+
+/// The number of error correction codewords in each block, indexed by error correction level and then version.
+const QR_ECC_CODEWORDS_PER_BLOCK: [[u8; 41]; 4] = [
+	[
+		0, 7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28, 30, 28, 28, 28, 28, 30, 30, 26, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+	],
+	[
+		0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28,
+	],
+	[
+		0, 13, 22, 18, 26, 18, 24, 18, 22, 20, 24, 28, 26, 24, 20, 30, 24, 28, 28, 26, 30, 28, 30, 30, 30, 30, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+	],
+	[
+		0, 17, 28, 22, 16, 22, 28, 26, 26, 24, 28, 24, 28, 22, 24, 24, 30, 28, 28, 26, 28, 30, 24, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+	],
+];
+/// The number of blocks the codewords are split into, indexed by error correction level and then version.
+const QR_ERROR_CORRECTION_BLOCKS: [[u8; 41]; 4] = [
+	[
+		0, 1, 1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 6, 6, 6, 6, 7, 8, 8, 9, 9, 10, 12, 12, 12, 13, 14, 15, 16, 17, 18, 19, 19, 20, 21, 22, 24, 25,
+	],
+	[
+		0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17, 17, 18, 20, 21, 23, 25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49,
+	],
+	[
+		0, 1, 1, 2, 2, 4, 4, 6, 6, 8, 8, 8, 10, 12, 16, 12, 17, 16, 18, 21, 20, 23, 23, 25, 27, 29, 34, 34, 35, 38, 40, 43, 45, 48, 51, 53, 56, 59, 62, 65, 68,
+	],
+	[
+		0, 1, 1, 2, 4, 4, 4, 5, 6, 8, 8, 11, 11, 16, 16, 18, 16, 19, 21, 25, 25, 25, 34, 30, 32, 35, 37, 40, 42, 45, 48, 51, 54, 57, 60, 63, 66, 70, 74, 77, 81,
+	],
+];
+
+fn qr_level_index(error_correction: QrErrorCorrection) -> usize {
+	match error_correction {
+		QrErrorCorrection::Low => 0,
+		QrErrorCorrection::Medium => 1,
+		QrErrorCorrection::Quartile => 2,
+		QrErrorCorrection::High => 3,
+	}
+}
+
+/// The two bits identifying the error correction level in the format information.
+fn qr_level_format_bits(error_correction: QrErrorCorrection) -> u32 {
+	match error_correction {
+		QrErrorCorrection::Low => 1,
+		QrErrorCorrection::Medium => 0,
+		QrErrorCorrection::Quartile => 3,
+		QrErrorCorrection::High => 2,
+	}
+}
+
+/// The number of modules available for codewords, after the function patterns and format and version information.
+fn qr_raw_data_modules(version: usize) -> usize {
+	let mut result = (16 * version + 128) * version + 64;
+	if version >= 2 {
+		let alignment_count = version / 7 + 2;
+		result -= (25 * alignment_count - 10) * alignment_count - 55;
+		if version >= 7 {
+			result -= 36;
+		}
+	}
+	result
+}
+
+fn qr_data_codewords(version: usize, level: usize) -> usize {
+	qr_raw_data_modules(version) / 8 - QR_ECC_CODEWORDS_PER_BLOCK[level][version] as usize * QR_ERROR_CORRECTION_BLOCKS[level][version] as usize
+}
+
+/// The centers of the alignment patterns along each axis.
+fn qr_alignment_positions(version: usize) -> Vec<usize> {
+	if version == 1 {
+		return Vec::new();
+	}
+	let size = version * 4 + 17;
+	let count = version / 7 + 2;
+	let step = (version * 8 + count * 3 + 5) / (count * 4 - 4) * 2;
+
+	let mut result = (0..count - 1).map(|index| size - 7 - index * step).collect::<Vec<_>>();
+	result.push(6);
+	result.reverse();
+	result
+}
+
+fn gf_multiply(x: u8, y: u8) -> u8 {
+	// Multiplication in GF(2^8) modulo the polynomial x^8 + x^4 + x^3 + x^2 + 1
+	let mut z: u32 = 0;
+	for bit in (0..8).rev() {
+		z = (z << 1) ^ ((z >> 7) * 0x11D);
+		z ^= ((y as u32 >> bit) & 1) * x as u32;
+	}
+	z as u8
+}
+
+/// The coefficients of the Reed-Solomon generator polynomial of the given degree, from highest to lowest power and leaving out the leading 1.
+fn reed_solomon_divisor(degree: usize) -> Vec<u8> {
+	let mut result = vec![0; degree];
+	result[degree - 1] = 1;
+
+	let mut root = 1;
+	for _ in 0..degree {
+		for index in 0..degree {
+			result[index] = gf_multiply(result[index], root);
+			if index + 1 < degree {
+				result[index] ^= result[index + 1];
+			}
+		}
+		root = gf_multiply(root, 0x02);
+	}
+	result
+}
+
+/// The error correction codewords of a block of data codewords.
+fn reed_solomon_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+	let mut result = vec![0; divisor.len()];
+	for &byte in data {
+		let factor = byte ^ result.remove(0);
+		result.push(0);
+		for (coefficient, &divisor) in result.iter_mut().zip(divisor) {
+			*coefficient ^= gf_multiply(divisor, factor);
+		}
+	}
+	result
+}
+
+/// The data codewords of bytes encoded in byte mode in the smallest version able to hold them.
+fn qr_data(bytes: &[u8], level: usize) -> Option<(usize, Vec<u8>)> {
+	let version = (1..=40).find(|&version| {
+		let count_bits = if version < 10 { 8 } else { 16 };
+		4 + count_bits + bytes.len() * 8 <= qr_data_codewords(version, level) * 8
+	})?;
+	let capacity_bits = qr_data_codewords(version, level) * 8;
+
+	let mut bits = Vec::with_capacity(capacity_bits);
+	let mut push_bits = |value: usize, count: usize| bits.extend((0..count).rev().map(|bit| value >> bit & 1 == 1));
+	push_bits(0b0100, 4);
+	push_bits(bytes.len(), if version < 10 { 8 } else { 16 });
+	for &byte in bytes {
+		push_bits(byte as usize, 8);
+	}
+
+	// The terminator, followed by zeros up to the next whole byte
+	let terminator = (capacity_bits - bits.len()).min(4);
+	bits.extend(std::iter::repeat_n(false, terminator));
+	bits.extend(std::iter::repeat_n(false, (8 - bits.len() % 8) % 8));
+
+	let mut codewords = bits.chunks(8).map(|byte| byte.iter().fold(0, |value, &bit| (value << 1) | bit as u8)).collect::<Vec<_>>();
+	for pad in [0xEC, 0x11].into_iter().cycle().take(capacity_bits / 8 - codewords.len()) {
+		codewords.push(pad);
+	}
+	Some((version, codewords))
+}
+
+/// Splits the data codewords into blocks, adds the error correction codewords of each, and interleaves the blocks.
+fn qr_codewords(data: &[u8], version: usize, level: usize) -> Vec<u8> {
+	let block_count = QR_ERROR_CORRECTION_BLOCKS[level][version] as usize;
+	let ecc_length = QR_ECC_CODEWORDS_PER_BLOCK[level][version] as usize;
+	let raw_codewords = qr_raw_data_modules(version) / 8;
+	let short_block_count = block_count - raw_codewords % block_count;
+	let short_block_length = raw_codewords / block_count;
+	let divisor = reed_solomon_divisor(ecc_length);
+
+	let mut blocks = Vec::with_capacity(block_count);
+	let mut start = 0;
+	for index in 0..block_count {
+		let length = short_block_length - ecc_length + if index < short_block_count { 0 } else { 1 };
+		let mut block = data[start..start + length].to_vec();
+		start += length;
+
+		let ecc = reed_solomon_remainder(&block, &divisor);
+		// Short blocks are padded so the codewords line up when interleaving, then the padding is skipped
+		if index < short_block_count {
+			block.push(0);
+		}
+		block.extend(ecc);
+		blocks.push(block);
+	}
+
+	let mut result = Vec::with_capacity(raw_codewords);
+	for position in 0..blocks[0].len() {
+		for (index, block) in blocks.iter().enumerate() {
+			if position != short_block_length - ecc_length || index >= short_block_count {
+				result.push(block[position]);
+			}
+		}
+	}
+	result
+}
+
+struct QrCode {
+	size: usize,
+	modules: Vec<Vec<bool>>,
+	/// The modules of the finder, timing, and alignment patterns and the format and version information, which aren't masked.
+	function: Vec<Vec<bool>>,
+}
+
+impl QrCode {
+	fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+		self.modules[y][x] = dark;
+		self.function[y][x] = true;
+	}
+
+	fn draw_function_patterns(&mut self, version: usize) {
+		let size = self.size;
+
+		for index in 0..size {
+			self.set_function(6, index, index % 2 == 0);
+			self.set_function(index, 6, index % 2 == 0);
+		}
+
+		for (center_x, center_y) in [(3, 3), (size - 4, 3), (3, size - 4)] {
+			for dy in -4_i32..=4 {
+				for dx in -4_i32..=4 {
+					let (x, y) = (center_x as i32 + dx, center_y as i32 + dy);
+					if (0..size as i32).contains(&x) && (0..size as i32).contains(&y) {
+						let distance = dx.abs().max(dy.abs());
+						self.set_function(x as usize, y as usize, distance != 2 && distance != 4);
+					}
+				}
+			}
+		}
+
+		let positions = qr_alignment_positions(version);
+		let last = positions.len().saturating_sub(1);
+		for (i, &center_x) in positions.iter().enumerate() {
+			for (j, &center_y) in positions.iter().enumerate() {
+				// The corners with finder patterns are left out
+				if (i == 0 && j == 0) || (i == 0 && j == last) || (i == last && j == 0) {
+					continue;
+				}
+				for dy in -2_i32..=2 {
+					for dx in -2_i32..=2 {
+						let (x, y) = ((center_x as i32 + dx) as usize, (center_y as i32 + dy) as usize);
+						self.set_function(x, y, dx.abs().max(dy.abs()) != 1);
+					}
+				}
+			}
+		}
+
+		// Reserve the format information modules, which are drawn once the mask is chosen
+		self.draw_format_bits(0);
+
+		if version >= 7 {
+			let mut remainder = version as u32;
+			for _ in 0..12 {
+				remainder = (remainder << 1) ^ ((remainder >> 11) * 0x1F25);
+			}
+			let bits = (version as u32) << 12 | remainder;
+			for index in 0..18 {
+				let dark = bits >> index & 1 == 1;
+				let (a, b) = (size - 11 + index % 3, index / 3);
+				self.set_function(a, b, dark);
+				self.set_function(b, a, dark);
+			}
+		}
+	}
+
+	fn draw_format_bits(&mut self, format: u32) {
+		let mut remainder = format;
+		for _ in 0..10 {
+			remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+		}
+		let bits = (format << 10 | remainder) ^ 0x5412;
+		let bit = |index: usize| bits >> index & 1 == 1;
+		let size = self.size;
+
+		// The copy around the top left finder pattern
+		for index in 0..6 {
+			self.set_function(8, index, bit(index));
+		}
+		self.set_function(8, 7, bit(6));
+		self.set_function(8, 8, bit(7));
+		self.set_function(7, 8, bit(8));
+		for index in 9..15 {
+			self.set_function(14 - index, 8, bit(index));
+		}
+
+		// The copy split between the other two finder patterns
+		for index in 0..8 {
+			self.set_function(size - 1 - index, 8, bit(index));
+		}
+		for index in 8..15 {
+			self.set_function(8, size - 15 + index, bit(index));
+		}
+		self.set_function(8, size - 8, true);
+	}
+
+	/// Places the codewords in the zigzag order running up and down pairs of columns from the right.
+	fn draw_codewords(&mut self, codewords: &[u8]) {
+		let size = self.size;
+		let mut index = 0;
+
+		let mut right = size - 1;
+		while right >= 1 {
+			if right == 6 {
+				right = 5;
+			}
+			for vertical in 0..size {
+				for column in 0..2 {
+					let x = right - column;
+					let upward = (right + 1) & 2 == 0;
+					let y = if upward { size - 1 - vertical } else { vertical };
+					if !self.function[y][x] && index < codewords.len() * 8 {
+						self.modules[y][x] = codewords[index >> 3] >> (7 - (index & 7)) & 1 == 1;
+						index += 1;
+					}
+				}
+			}
+			if right < 2 {
+				break;
+			}
+			right -= 2;
+		}
+	}
+
+	fn apply_mask(&mut self, mask: usize) {
+		for y in 0..self.size {
+			for x in 0..self.size {
+				let invert = match mask {
+					0 => (x + y) % 2 == 0,
+					1 => y % 2 == 0,
+					2 => x % 3 == 0,
+					3 => (x + y) % 3 == 0,
+					4 => (x / 3 + y / 2) % 2 == 0,
+					5 => x * y % 2 + x * y % 3 == 0,
+					6 => (x * y % 2 + x * y % 3) % 2 == 0,
+					_ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+				};
+				if invert && !self.function[y][x] {
+					self.modules[y][x] = !self.modules[y][x];
+				}
+			}
+		}
+	}
+
+	/// Scores how hard the code is to scan, so the mask making it easiest can be chosen.
+	fn penalty(&self) -> usize {
+		let size = self.size;
+		let module = |x: usize, y: usize| self.modules[y][x];
+		let mut penalty = 0;
+
+		let lines = (0..size)
+			.map(|y| (0..size).map(|x| module(x, y)).collect::<Vec<_>>())
+			.chain((0..size).map(|x| (0..size).map(|y| module(x, y)).collect()));
+		for line in lines {
+			// Runs of five or more modules of the same color
+			let mut run = 1;
+			for index in 1..=size {
+				if index < size && line[index] == line[index - 1] {
+					run += 1;
+				} else {
+					if run >= 5 {
+						penalty += run - 2;
+					}
+					run = 1;
+				}
+			}
+
+			// Patterns which look like finder patterns
+			const FINDER_LIKE: [bool; 11] = [true, false, true, true, true, false, true, false, false, false, false];
+			for window in line.windows(11) {
+				if window == FINDER_LIKE || window.iter().rev().eq(FINDER_LIKE.iter()) {
+					penalty += 40;
+				}
+			}
+		}
+
+		// Blocks of 2x2 modules of the same color
+		for y in 0..size - 1 {
+			for x in 0..size - 1 {
+				let color = module(x, y);
+				if module(x + 1, y) == color && module(x, y + 1) == color && module(x + 1, y + 1) == color {
+					penalty += 3;
+				}
+			}
+		}
+
+		// A balance of dark and light modules far from half and half
+		let dark = self.modules.iter().flatten().filter(|&&dark| dark).count();
+		let percent = dark * 100 / (size * size);
+		penalty += percent.abs_diff(50) / 5 * 10;
+
+		penalty
+	}
+}
+
+/// Encodes bytes in a QR code of the smallest version able to hold them with the given error correction level.
+fn qr_code(bytes: &[u8], error_correction: QrErrorCorrection) -> Option<Vec<Vec<bool>>> {
+	let level = qr_level_index(error_correction);
+	let (version, data) = qr_data(bytes, level)?;
+	let codewords = qr_codewords(&data, version, level);
+
+	let size = version * 4 + 17;
+	let mut code = QrCode {
+		size,
+		modules: vec![vec![false; size]; size],
+		function: vec![vec![false; size]; size],
+	};
+	code.draw_function_patterns(version);
+	code.draw_codewords(&codewords);
+
+	let format = |mask: usize| qr_level_format_bits(error_correction) << 3 | mask as u32;
+	let best_mask = (0..8)
+		.min_by_key(|&mask| {
+			code.apply_mask(mask);
+			code.draw_format_bits(format(mask));
+			let penalty = code.penalty();
+			// Masking again undoes it
+			code.apply_mask(mask);
+			penalty
+		})
+		.unwrap_or_default();
+
+	code.apply_mask(best_mask);
+	code.draw_format_bits(format(best_mask));
+	Some(code.modules)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn reed_solomon_error_correction() {
+		// The data codewords of "HELLO WORLD" in a version 1 code with medium error correction
+		let data = [32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17];
+		let ecc = reed_solomon_remainder(&data, &reed_solomon_divisor(10));
+		assert_eq!(ecc, [196, 35, 39, 119, 235, 215, 231, 226, 93, 23]);
+	}
+
+	#[test]
+	fn qr_code_size_and_patterns() {
+		// Version 1 with low error correction holds 17 bytes
+		let small = qr_code(&[b'a'; 17], QrErrorCorrection::Low).unwrap();
+		assert_eq!(small.len(), 21);
+		let larger = qr_code(&[b'a'; 18], QrErrorCorrection::Low).unwrap();
+		assert_eq!(larger.len(), 25);
+
+		// The finder pattern's outer ring and the timing pattern
+		assert!((0..7).all(|index| small[0][index] && small[6][index] && small[index][0] && small[index][20 - 6]));
+		assert!((8..13).all(|index| small[6][index] == (index % 2 == 0)));
+
+		assert!(qr_code(&[0; 3000], QrErrorCorrection::High).is_none());
+	}
+
+	#[test]
+	fn qr_format_bits() {
+		let mut code = QrCode {
+			size: 21,
+			modules: vec![vec![false; 21]; 21],
+			function: vec![vec![false; 21]; 21],
+		};
+		// Medium error correction with mask 0
+		code.draw_format_bits(0);
+		let first_copy = [(8, 0), (8, 1), (8, 2), (8, 3), (8, 4), (8, 5), (8, 7), (8, 8), (7, 8), (5, 8), (4, 8), (3, 8), (2, 8), (1, 8), (0, 8)];
+		let format = first_copy.iter().rev().map(|&(x, y)| if code.modules[y][x] { '1' } else { '0' }).collect::<String>();
+		assert_eq!(format, "101010000010010");
+	}
+
+	#[test]
+	fn code_128_symbols() {
+		assert!(CODE_128_PATTERNS.iter().all(|pattern| pattern.iter().sum::<u8>() == 11));
+
+		// Start, 2 symbols, checksum, then the 13 module stop pattern
+		let modules = code_128("Hi").unwrap();
+		assert_eq!(modules.len(), 11 * 4 + 13);
+		let digits = code_128("123456").unwrap();
+		assert_eq!(digits.len(), 11 * 5 + 13);
+		assert!(code_128("é").is_none());
+	}
+
+	#[test]
+	fn ean_13_check_digit() {
+		let modules = ean_13("400638133393").unwrap();
+		assert_eq!(modules.len(), 95);
+		assert_eq!(ean_13("4006381333931"), Some(modules));
+		assert!(ean_13("4006381333932").is_none());
+
+		// The first digit of 4 gives the left half a parity of odd, even, odd, odd, even, even
+		let first = ean_13("400000000000").unwrap();
+		let even_zero = [false, true, false, false, true, true, true];
+		assert_eq!(first[10..17], even_zero);
+	}
+}
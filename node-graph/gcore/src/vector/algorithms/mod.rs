@@ -1 +1,2 @@
+pub(crate) mod barcode;
 mod merge_by_distance;
@@ -60,13 +60,23 @@ pub struct BrushInputSample {
 	// The position of the sample in layer space, in pixels.
 	// The origin of layer space is not specified.
 	pub position: DVec2,
-	// Future work: pressure, stylus angle, etc.
+	// The tilt of the stylus away from perpendicular to the screen along the X and Y axes, in degrees.
+	// This is zero for input devices which don't report their orientation.
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub tilt: DVec2,
+	// The clockwise rotation of the stylus around its own axis, in degrees.
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub twist: f64,
+	// Future work: pressure, etc.
 }
 
 impl Hash for BrushInputSample {
 	fn hash<H: Hasher>(&self, state: &mut H) {
 		self.position.x.to_bits().hash(state);
 		self.position.y.to_bits().hash(state);
+		self.tilt.x.to_bits().hash(state);
+		self.tilt.y.to_bits().hash(state);
+		self.twist.to_bits().hash(state);
 	}
 }
 
@@ -3,7 +3,8 @@ use crate::vector::{HandleId, VectorData, VectorDataTable};
 use bezier_rs::Subpath;
 use glam::DVec2;
 
-use super::misc::AsU64;
+use super::algorithms::barcode;
+use super::misc::{AsU64, BarcodeFormat, QrErrorCorrection};
 
 trait CornerRadius {
 	fn generate(self, size: DVec2, clamped: bool) -> VectorDataTable;
@@ -108,3 +109,54 @@ fn star<T: AsU64>(
 fn line(_: impl Ctx, _primary: (), #[default((0., -50.))] start: DVec2, #[default((0., 50.))] end: DVec2) -> VectorDataTable {
 	VectorDataTable::new(VectorData::from_subpath(Subpath::new_line(start, end)))
 }
+
+/// Generates a QR code or 1D barcode encoding the text, made of a rectangle for each run of dark modules, with its top left corner (including the quiet zone) at the origin.
+#[node_macro::node(category("Vector: Shape"))]
+fn barcode(
+	_: impl Ctx,
+	_primary: (),
+	/// The text to encode, which for EAN-13 and UPC-A barcodes is the product number with or without its check digit.
+	#[default(Graphite)]
+	text: String,
+	format: BarcodeFormat,
+	/// How much of a QR code can be damaged while it can still be scanned. Higher levels make larger codes.
+	error_correction: QrErrorCorrection,
+	/// The margin of light modules around the code which scanners need to find it, where the code should be placed over a light background.
+	#[default(4)]
+	quiet_zone: u32,
+	/// The width of each module, the smallest square or bar of the code.
+	#[default(10)]
+	module_size: f64,
+	/// The height of the bars of 1D barcodes.
+	#[default(100)]
+	bar_height: f64,
+) -> VectorDataTable {
+	let Some(rows) = barcode::encode(&text, format, error_correction) else {
+		warn!("The text `{text}` can't be encoded as a {format} barcode");
+		return VectorDataTable::default();
+	};
+
+	let row_height = if rows.len() == 1 { bar_height } else { module_size };
+	let margin = DVec2::splat(quiet_zone as f64 * module_size);
+
+	let mut subpaths = Vec::new();
+	for (y, row) in rows.iter().enumerate() {
+		let top = margin.y + y as f64 * row_height;
+		let mut x = 0;
+		while x < row.len() {
+			if !row[x] {
+				x += 1;
+				continue;
+			}
+			let start = x;
+			while x < row.len() && row[x] {
+				x += 1;
+			}
+			let left = margin.x + start as f64 * module_size;
+			let right = margin.x + x as f64 * module_size;
+			subpaths.push(Subpath::new_rect(DVec2::new(left, top), DVec2::new(right, top + row_height)));
+		}
+	}
+
+	VectorDataTable::new(VectorData::from_subpaths(subpaths, false))
+}
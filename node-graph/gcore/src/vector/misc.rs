@@ -1,4 +1,6 @@
+use core::f64::consts::TAU;
 use dyn_any::DynAny;
+use glam::DVec2;
 
 /// Represents different ways of calculating the centroid.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
@@ -48,6 +50,117 @@ impl core::fmt::Display for BooleanOperation {
 	}
 }
 
+/// The symbologies the Barcode node can encode text as.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum BarcodeFormat {
+	/// A 2D code holding any text, such as a URL.
+	#[default]
+	QrCode,
+	/// A 1D barcode holding printable ASCII text, as used on shipping labels and tickets.
+	Code128,
+	/// A 1D barcode holding a 13 digit product number, as used on retail products.
+	Ean13,
+	/// A 1D barcode holding a 12 digit product number, as used on retail products in North America.
+	UpcA,
+}
+
+impl core::fmt::Display for BarcodeFormat {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			BarcodeFormat::QrCode => write!(f, "QR Code"),
+			BarcodeFormat::Code128 => write!(f, "Code 128"),
+			BarcodeFormat::Ean13 => write!(f, "EAN-13"),
+			BarcodeFormat::UpcA => write!(f, "UPC-A"),
+		}
+	}
+}
+
+/// How much of a QR code can be damaged or covered up while it can still be scanned, in exchange for a larger code.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum QrErrorCorrection {
+	/// Recovers about 7% of the code.
+	Low,
+	/// Recovers about 15% of the code.
+	#[default]
+	Medium,
+	/// Recovers about 25% of the code.
+	Quartile,
+	/// Recovers about 30% of the code.
+	High,
+}
+
+impl core::fmt::Display for QrErrorCorrection {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			QrErrorCorrection::Low => write!(f, "Low"),
+			QrErrorCorrection::Medium => write!(f, "Medium"),
+			QrErrorCorrection::Quartile => write!(f, "Quartile"),
+			QrErrorCorrection::High => write!(f, "High"),
+		}
+	}
+}
+
+/// The shapes the Text Warp node can bend outlines into.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum WarpStyle {
+	/// Raises the middle of the outlines above their ends, like a rainbow.
+	#[default]
+	Arc,
+	/// Waves the outlines up and down, like a flag in the wind.
+	Flag,
+	/// Swells the middle of the outlines both upwards and downwards.
+	Bulge,
+	/// Stretches the outlines to fit between four freely placed corners.
+	Envelope,
+}
+
+impl core::fmt::Display for WarpStyle {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			WarpStyle::Arc => write!(f, "Arc"),
+			WarpStyle::Flag => write!(f, "Flag"),
+			WarpStyle::Bulge => write!(f, "Bulge"),
+			WarpStyle::Envelope => write!(f, "Envelope"),
+		}
+	}
+}
+
+impl WarpStyle {
+	pub fn list() -> [WarpStyle; 4] {
+		[WarpStyle::Arc, WarpStyle::Flag, WarpStyle::Bulge, WarpStyle::Envelope]
+	}
+
+	/// The fraction of the way across the outlines where their top edge is bent the furthest, which is by the bend times their height.
+	pub fn bend_peak(self) -> f64 {
+		match self {
+			WarpStyle::Flag => 0.25,
+			_ => 0.5,
+		}
+	}
+
+	/// Moves a point within the bounding box of the outlines to where the warp puts it.
+	/// The bend is a fraction of the height of the bounding box, and the envelope's corners are offsets from its corners, clockwise from the top left.
+	pub fn warp(self, point: DVec2, bounds: [DVec2; 2], bend: f64, corners: [DVec2; 4]) -> DVec2 {
+		let size = (bounds[1] - bounds[0]).max(DVec2::splat(f64::EPSILON));
+		let DVec2 { x: u, y: v } = (point - bounds[0]) / size;
+
+		// Bends to nothing at the left and right ends, and all the way at the middle
+		let arch = 1. - (2. * u - 1.).powi(2);
+		let raised = match self {
+			WarpStyle::Arc => bend * arch,
+			WarpStyle::Flag => bend * (TAU * u).sin(),
+			WarpStyle::Bulge => bend * arch * (1. - 2. * v),
+			WarpStyle::Envelope => {
+				let [top_left, top_right, bottom_right, bottom_left] = corners;
+				let top = (bounds[0] + top_left).lerp(DVec2::new(bounds[1].x, bounds[0].y) + top_right, u);
+				let bottom = (DVec2::new(bounds[0].x, bounds[1].y) + bottom_left).lerp(bounds[1] + bottom_right, u);
+				return top.lerp(bottom, v);
+			}
+		};
+		point - DVec2::Y * raised * size.y
+	}
+}
+
 pub trait AsU64 {
 	fn as_u64(&self) -> u64;
 }
@@ -1,18 +1,21 @@
 mod attributes;
+mod embedded_text;
 mod indexed;
 mod modification;
 
-use super::style::{PathStyle, Stroke};
+use super::style::{Fill, PathStyle, Stroke};
 use crate::instances::Instances;
 use crate::{AlphaBlending, Color, GraphicGroupTable};
 pub use attributes::*;
 use bezier_rs::ManipulatorGroup;
 use core::borrow::Borrow;
 use dyn_any::DynAny;
+pub use embedded_text::*;
 use glam::{DAffine2, DVec2};
 pub use indexed::VectorDataIndex;
 pub use modification::*;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 // TODO: Eventually remove this migration document upgrade code
 pub fn migrate_vector_data<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<VectorDataTable, D::Error> {
@@ -57,6 +60,8 @@ pub fn migrate_vector_data<'de, D: serde::Deserializer<'de>>(deserializer: D) ->
 				segment_domain: old.segment_domain,
 				region_domain: old.region_domain,
 				upstream_graphic_group: old.upstream_graphic_group,
+				region_fills: Vec::new(),
+				embedded_text: None,
 			});
 			*vector_data_table.one_instance_mut().transform = old.transform;
 			*vector_data_table.one_instance_mut().alpha_blending = old.alpha_blending;
@@ -87,6 +92,16 @@ pub struct VectorData {
 
 	// Used to store the upstream graphic group during destructive Boolean Operations (and other nodes with a similar effect) so that click targets can be preserved.
 	pub upstream_graphic_group: Option<GraphicGroupTable>,
+
+	/// The fills of regions painted apart from the rest of the path, like the colored spans of a text, by the fill ID given to those regions.
+	/// Regions with any other fill ID take the fill of the style.
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub region_fills: Vec<(FillId, Fill)>,
+
+	/// The glyphs of a text as characters of its fonts, for drawing the text with its fonts rather than the outlines of its glyphs.
+	/// Nodes which change the outlines needn't clear it, since it's only used while the outlines are unchanged.
+	#[cfg_attr(feature = "serde", serde(skip))]
+	pub embedded_text: Option<Arc<EmbeddedText>>,
 }
 
 impl core::hash::Hash for VectorData {
@@ -96,6 +111,7 @@ impl core::hash::Hash for VectorData {
 		self.region_domain.hash(state);
 		self.style.hash(state);
 		self.colinear_manipulators.hash(state);
+		self.region_fills.hash(state);
 	}
 }
 
@@ -110,6 +126,8 @@ impl VectorData {
 			segment_domain: SegmentDomain::new(),
 			region_domain: RegionDomain::new(),
 			upstream_graphic_group: None,
+			region_fills: Vec::new(),
+			embedded_text: None,
 		}
 	}
 
@@ -176,6 +194,19 @@ impl VectorData {
 		}
 	}
 
+	/// Push subpaths whose closed regions are painted with `fill` rather than the fill of the style, like the colored spans of a text.
+	pub fn append_subpaths_with_fill(&mut self, subpaths: impl IntoIterator<Item = impl Borrow<bezier_rs::Subpath<PointId>>>, fill: Fill) {
+		let fill_id = FillId::generate();
+		let first_region = self.region_domain.ids().len();
+		for subpath in subpaths {
+			self.append_subpath(subpath, false);
+		}
+		for (_, region_fill) in self.region_domain.fill_mut().skip(first_region) {
+			*region_fill = fill_id;
+		}
+		self.region_fills.push((fill_id, fill));
+	}
+
 	/// Construct some new vector data from subpaths with an identity transform and black fill.
 	pub fn from_subpaths(subpaths: impl IntoIterator<Item = impl Borrow<bezier_rs::Subpath<PointId>>>, preserve_id: bool) -> Self {
 		let mut vector_data = Self::empty();
@@ -385,6 +416,7 @@ impl VectorData {
 
 		// TODO: properly deal with fills such as gradients
 		self.style = other.style.clone();
+		self.region_fills.extend(other.region_fills.iter().cloned());
 
 		self.colinear_manipulators.extend(other.colinear_manipulators.iter().copied());
 	}
@@ -600,3 +632,19 @@ fn construct_many_subpath() {
 	let generated = vector_data.stroke_bezier_paths().collect::<Vec<_>>();
 	assert_subpath_eq(&generated, &[curve, circle]);
 }
+
+#[test]
+fn subpaths_with_their_own_fill_are_grouped_apart() {
+	let square = bezier_rs::Subpath::new_rect(DVec2::ZERO, DVec2::ONE);
+	let circle = bezier_rs::Subpath::new_ellipse(DVec2::NEG_ONE, DVec2::ONE);
+	let red = super::style::Fill::Solid(Color::RED);
+
+	let mut vector_data = VectorData::from_subpath(&square);
+	vector_data.append_subpaths_with_fill([&circle], red.clone());
+
+	let (style_fill, own_fills) = vector_data.subpaths_by_fill();
+	assert_subpath_eq(&style_fill, &[square]);
+	assert_eq!(own_fills.len(), 1);
+	assert_eq!(own_fills[0].0, &red);
+	assert_subpath_eq(&own_fills[0].1, &[circle]);
+}
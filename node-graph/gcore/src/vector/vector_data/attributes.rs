@@ -644,6 +644,30 @@ impl VectorData {
 			})
 	}
 
+	/// Groups the subpaths by what they're painted with: first those taking the fill of the style, then those of the regions with a fill of their own in `region_fills`.
+	pub fn subpaths_by_fill(&self) -> (Vec<bezier_rs::Subpath<PointId>>, Vec<(&crate::vector::style::Fill, Vec<bezier_rs::Subpath<PointId>>)>) {
+		let mut own_fills: Vec<_> = self.region_fills.iter().map(|(_, fill)| (fill, Vec::new())).collect();
+
+		let mut own_fill_of_point = HashMap::new();
+		for (_, segment_range, fill_id) in self.region_domain.iter() {
+			let Some(index) = self.region_fills.iter().position(|(id, _)| *id == fill_id) else { continue };
+			let Some(range) = self.segment_domain.resolve_range(&segment_range) else { continue };
+			for &start in &self.segment_domain.start_point[range] {
+				own_fill_of_point.insert(self.point_domain.ids()[start], index);
+			}
+		}
+
+		let mut style_fill = Vec::new();
+		for subpath in self.stroke_bezier_paths() {
+			match subpath.manipulator_groups().first().and_then(|group| own_fill_of_point.get(&group.id)) {
+				Some(&index) => own_fills[index].1.push(subpath),
+				None => style_fill.push(subpath),
+			}
+		}
+
+		(style_fill, own_fills)
+	}
+
 	/// Construct a [`bezier_rs::Bezier`] curve for stroke.
 	pub fn stroke_bezier_paths(&self) -> StrokePathIter<'_> {
 		let mut points = vec![StrokePathIterPointMetadata::default(); self.point_domain.ids().len()];
@@ -0,0 +1,62 @@
+use super::{PointId, VectorData};
+use crate::Color;
+use bezier_rs::Subpath;
+use glam::DAffine2;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
+
+/// The subset of a font with the glyphs of a text, which is embedded in an exported SVG to draw them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EmbeddedFont {
+	/// A name for the font which is unique to the font, for it to be referred to by within the file it's embedded in.
+	pub family: String,
+	/// The TrueType font file of the subset.
+	pub data: Arc<[u8]>,
+	pub units_per_em: u16,
+}
+
+/// A glyph of a text, drawn with the character it was given in the subset of its font.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EmbeddedGlyph {
+	/// The index of the glyph's font in [`EmbeddedText::fonts`].
+	pub font: usize,
+	/// The ID of the glyph, which it keeps in the subset of its font.
+	pub id: u16,
+	pub character: char,
+	/// Places the glyph, as drawn at a font size of its font's units per em with its origin on the baseline, where its outline was placed.
+	pub transform: DAffine2,
+	/// The color of the glyph, where `None` leaves it with the fill of the text.
+	pub color: Option<Color>,
+}
+
+/// The glyphs of a text as characters of subsets of its fonts, which an SVG export and the Vello renderer may draw in place of the glyph outlines making up the vector data.
+///
+/// This is only used while the outlines are exactly those the text was typeset with, which is checked against the hash of the outlines so any change made to them by a node further along falls back to drawing the outlines.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EmbeddedText {
+	pub fonts: Vec<EmbeddedFont>,
+	pub glyphs: Vec<EmbeddedGlyph>,
+	/// The underlines and strikethroughs of the text, which aren't part of any glyph so they're drawn as paths, along with their color.
+	pub decorations: Vec<(Subpath<PointId>, Option<Color>)>,
+	/// The [`VectorData::outlines_hash`] of the outlines of the text.
+	pub outlines_hash: u64,
+}
+
+impl EmbeddedText {
+	/// Whether the glyphs still match the outlines of the vector data they were embedded in.
+	pub fn matches(&self, vector_data: &VectorData) -> bool {
+		self.outlines_hash == vector_data.outlines_hash()
+	}
+}
+
+impl VectorData {
+	/// A hash of the geometry of the vector data and the fills of its regions, not including its style.
+	pub fn outlines_hash(&self) -> u64 {
+		let mut hasher = DefaultHasher::new();
+		self.point_domain.hash(&mut hasher);
+		self.segment_domain.hash(&mut hasher);
+		self.region_domain.hash(&mut hasher);
+		self.region_fills.hash(&mut hasher);
+		hasher.finish()
+	}
+}
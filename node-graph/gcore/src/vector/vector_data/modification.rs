@@ -12,6 +12,7 @@ use std::collections::{HashMap, HashSet};
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointModification {
 	add: Vec<PointId>,
+	#[serde(serialize_with = "serialize_hashset")]
 	remove: HashSet<PointId>,
 	#[serde(serialize_with = "serialize_hashmap", deserialize_with = "deserialize_hashmap")]
 	delta: HashMap<PointId, DVec2>,
@@ -84,6 +85,7 @@ impl PointModification {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SegmentModification {
 	add: Vec<SegmentId>,
+	#[serde(serialize_with = "serialize_hashset")]
 	remove: HashSet<SegmentId>,
 	#[serde(serialize_with = "serialize_hashmap", deserialize_with = "deserialize_hashmap")]
 	start_point: HashMap<SegmentId, PointId>,
@@ -257,6 +259,7 @@ impl SegmentModification {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RegionModification {
 	add: Vec<RegionId>,
+	#[serde(serialize_with = "serialize_hashset")]
 	remove: HashSet<RegionId>,
 	#[serde(serialize_with = "serialize_hashmap", deserialize_with = "deserialize_hashmap")]
 	segment_range: HashMap<RegionId, core::ops::RangeInclusive<SegmentId>>,
@@ -304,7 +307,9 @@ pub struct VectorModification {
 	points: PointModification,
 	segments: SegmentModification,
 	regions: RegionModification,
+	#[serde(serialize_with = "serialize_hashset")]
 	add_g1_continuous: HashSet<[HandleId; 2]>,
+	#[serde(serialize_with = "serialize_hashset")]
 	remove_g1_continuous: HashSet<[HandleId; 2]>,
 }
 
@@ -492,6 +497,24 @@ fn modify_existing() {
 	);
 }
 
+#[test]
+fn serialization_is_independent_of_insertion_order() {
+	let ids = (0..20).map(|_| PointId::generate()).collect::<Vec<_>>();
+	let modification = |ids: &mut dyn Iterator<Item = &PointId>| {
+		let mut modification = PointModification::default();
+		for &id in ids {
+			modification.remove.insert(id);
+			modification.delta.insert(id, DVec2::splat(id.inner() as f64));
+		}
+		modification
+	};
+	let forwards = modification(&mut ids.iter());
+	let backwards = modification(&mut ids.iter().rev());
+	assert_eq!(forwards, backwards);
+
+	assert_eq!(serde_json::to_string(&forwards).unwrap(), serde_json::to_string(&backwards).unwrap());
+}
+
 // Do we want to enforce that all serialized/deserialized hashmaps are a vec of tuples?
 // TODO: Eventually remove this document upgrade code
 use serde::de::{SeqAccess, Visitor};
@@ -499,20 +522,41 @@ use serde::ser::SerializeSeq;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use std::hash::Hash;
+/// Serializes the entries sorted by key, so saving the same map always gives the same output regardless of the order it was built in.
 pub fn serialize_hashmap<K, V, S, H>(hashmap: &HashMap<K, V, H>, serializer: S) -> Result<S::Ok, S::Error>
 where
-	K: Serialize + Eq + Hash,
+	K: Serialize + Eq + Hash + Ord,
 	V: Serialize,
 	S: Serializer,
 	H: BuildHasher,
 {
-	let mut seq = serializer.serialize_seq(Some(hashmap.len()))?;
-	for (key, value) in hashmap {
+	let mut entries = hashmap.iter().collect::<Vec<_>>();
+	entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+	let mut seq = serializer.serialize_seq(Some(entries.len()))?;
+	for (key, value) in entries {
 		seq.serialize_element(&(key, value))?;
 	}
 	seq.end()
 }
 
+/// Serializes the elements sorted, for the same reason as [`serialize_hashmap`]. Sets deserialize from a sequence as usual.
+pub fn serialize_hashset<T, S, H>(hashset: &HashSet<T, H>, serializer: S) -> Result<S::Ok, S::Error>
+where
+	T: Serialize + Eq + Hash + Ord,
+	S: Serializer,
+	H: BuildHasher,
+{
+	let mut elements = hashset.iter().collect::<Vec<_>>();
+	elements.sort_unstable();
+
+	let mut seq = serializer.serialize_seq(Some(elements.len()))?;
+	for element in elements {
+		seq.serialize_element(element)?;
+	}
+	seq.end()
+}
+
 pub fn deserialize_hashmap<'de, K, V, D, H>(deserializer: D) -> Result<HashMap<K, V, H>, D::Error>
 where
 	K: Deserialize<'de> + Eq + Hash,
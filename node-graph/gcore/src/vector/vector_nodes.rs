@@ -1,14 +1,14 @@
-use super::misc::CentroidType;
+use super::misc::{CentroidType, WarpStyle};
 use super::style::{Fill, Gradient, GradientStops, Stroke};
 use super::{PointId, SegmentDomain, SegmentId, StrokeId, VectorData, VectorDataTable};
 use crate::instances::{InstanceMut, Instances};
-use crate::registry::types::{Angle, Fraction, IntegerCount, Length, Percentage, PixelLength, SeedValue};
+use crate::registry::types::{Angle, Fraction, IntegerCount, Length, Percentage, PixelLength, SeedValue, SignedPercentage};
 use crate::renderer::GraphicElementRendered;
 use crate::transform::{Footprint, Transform, TransformMut};
 use crate::vector::PointDomain;
 use crate::vector::style::LineJoin;
 use crate::{CloneVarArgs, Color, Context, Ctx, ExtractAll, GraphicElement, GraphicGroupTable, OwnedContextImpl};
-use bezier_rs::{Cap, Join, ManipulatorGroup, Subpath, SubpathTValue, TValue};
+use bezier_rs::{Bezier, BezierHandles, Cap, Join, ManipulatorGroup, Subpath, SubpathTValue, TValue};
 use core::f64::consts::PI;
 use glam::{DAffine2, DVec2};
 use rand::{Rng, SeedableRng};
@@ -687,6 +687,82 @@ fn bilinear_interpolate(t: DVec2, quad: &[DVec2; 4]) -> DVec2 {
 	tl * (1. - t.x) * (1. - t.y) + tr * t.x * (1. - t.y) + br * t.x * t.y + bl * (1. - t.x) * t.y
 }
 
+/// The number of pieces each segment is split into before it's warped, so that even straight edges bend along with the warp.
+const WARP_SUBDIVISIONS: usize = 8;
+
+/// Bends the outlines of text, or any other vector data, into an arc, a waving flag, or a bulge, or stretches them to fit an envelope with four freely placed corners.
+/// The outlines are warped within their own coordinates, so the warp moves along with the transform of the layer.
+#[node_macro::node(category("Vector"), path(graphene_core::vector))]
+async fn text_warp(
+	_: impl Ctx,
+	vector_data: VectorDataTable,
+	style: WarpStyle,
+	/// How far the Arc, Flag, and Bulge styles bend the top edge of the outlines, relative to their height.
+	#[default(50.)]
+	bend: SignedPercentage,
+	/// How far the top left corner of the Envelope style is moved from the top left of the outlines' bounding box.
+	top_left: DVec2,
+	/// How far the top right corner of the Envelope style is moved from the top right of the outlines' bounding box.
+	top_right: DVec2,
+	/// How far the bottom right corner of the Envelope style is moved from the bottom right of the outlines' bounding box.
+	bottom_right: DVec2,
+	/// How far the bottom left corner of the Envelope style is moved from the bottom left of the outlines' bounding box.
+	bottom_left: DVec2,
+) -> VectorDataTable {
+	// Each colored part of a text is its own instance, and they're all warped together within the bounding box around them all
+	let Some(bounds) = vector_data
+		.instances()
+		.filter_map(|instance| instance.instance.bounding_box())
+		.reduce(|a, b| [a[0].min(b[0]), a[1].max(b[1])])
+	else {
+		return vector_data;
+	};
+	let warp = |point| style.warp(point, bounds, bend / 100., [top_left, top_right, bottom_right, bottom_left]);
+
+	let mut vector_data = vector_data;
+	for instance in vector_data.instances_mut() {
+		let subpaths = instance.instance.stroke_bezier_paths().map(|subpath| warp_subpath(&subpath, warp)).collect::<Vec<_>>();
+		let warped = VectorData::from_subpaths(subpaths, false);
+
+		instance.instance.point_domain = warped.point_domain;
+		instance.instance.segment_domain = warped.segment_domain;
+		instance.instance.region_domain = warped.region_domain;
+		instance.instance.colinear_manipulators.clear();
+	}
+
+	vector_data
+}
+
+/// Splits each segment of the subpath into cubic pieces and moves all their points by the warp.
+fn warp_subpath(subpath: &Subpath<PointId>, warp: impl Fn(DVec2) -> DVec2) -> Subpath<PointId> {
+	if subpath.len_segments() == 0 {
+		let manipulator_groups = subpath.manipulator_groups().iter().map(|group| ManipulatorGroup::new_anchor_linear(warp(group.anchor))).collect();
+		return Subpath::new(manipulator_groups, subpath.closed());
+	}
+
+	let pieces = subpath.iter().flat_map(|bezier| {
+		(0..WARP_SUBDIVISIONS).map(move |index| {
+			let t = |index: usize| TValue::Parametric(index as f64 / WARP_SUBDIVISIONS as f64);
+			bezier.trim(t(index), t(index + 1))
+		})
+	});
+	let beziers = pieces
+		.map(|piece| {
+			// Straight pieces get handles a third of the way along them, since handles on top of their anchors would keep them straight
+			let (handle_start, handle_end) = match piece.handles {
+				BezierHandles::Linear => (piece.start.lerp(piece.end, 1. / 3.), piece.start.lerp(piece.end, 2. / 3.)),
+				_ => {
+					let cubic = piece.to_cubic();
+					(cubic.handle_start().unwrap_or(piece.start), cubic.handle_end().unwrap_or(piece.end))
+				}
+			};
+			Bezier::from_cubic_dvec2(warp(piece.start), warp(handle_start), warp(handle_end), warp(piece.end))
+		})
+		.collect::<Vec<_>>();
+
+	Subpath::from_beziers(&beziers, subpath.closed())
+}
+
 #[node_macro::node(category("Vector"), path(graphene_core::vector))]
 async fn remove_handles(
 	_: impl Ctx,
@@ -944,20 +1020,22 @@ async fn offset_path(_: impl Ctx, vector_data: VectorDataTable, distance: f64, l
 	result.style = vector_data.style.clone();
 	result.style.set_stroke_transform(DAffine2::IDENTITY);
 
-	// Perform operation on all subpaths in this shape.
-	for mut subpath in subpaths {
+	let join = match line_join {
+		LineJoin::Miter => Join::Miter(Some(miter_limit)),
+		LineJoin::Bevel => Join::Bevel,
+		LineJoin::Round => Join::Round,
+	};
+
+	// Perform operation on all subpaths in this shape, spread across threads.
+	let subpaths_out = crate::parallel::par_map_chunked(subpaths.collect(), |mut subpath: Subpath<PointId>| {
 		subpath.apply_transform(vector_data_transform);
 
 		// Taking the existing stroke data and passing it to Bezier-rs to generate new paths.
-		let subpath_out = subpath.offset(
-			-distance,
-			match line_join {
-				LineJoin::Miter => Join::Miter(Some(miter_limit)),
-				LineJoin::Bevel => Join::Bevel,
-				LineJoin::Round => Join::Round,
-			},
-		);
+		subpath.offset(-distance, join)
+	})
+	.await;
 
+	for subpath_out in subpaths_out {
 		// One closed subpath, open path.
 		result.append_subpath(subpath_out, false);
 	}
@@ -975,26 +1053,32 @@ async fn solidify_stroke(_: impl Ctx, vector_data: VectorDataTable) -> VectorDat
 	let subpaths = vector_data.stroke_bezier_paths();
 	let mut result = VectorData::empty();
 
-	// Perform operation on all subpaths in this shape.
-	for mut subpath in subpaths {
-		let stroke = style.stroke().unwrap();
+	// Without a stroke there is nothing to solidify
+	let Some(stroke) = style.stroke() else {
+		return VectorDataTable::new(result);
+	};
+	let radius = stroke.weight / 2.; // Diameter to radius.
+	let join = match stroke.line_join {
+		LineJoin::Miter => Join::Miter(Some(stroke.line_join_miter_limit)),
+		LineJoin::Bevel => Join::Bevel,
+		LineJoin::Round => Join::Round,
+	};
+	let cap = match stroke.line_cap {
+		crate::vector::style::LineCap::Butt => Cap::Butt,
+		crate::vector::style::LineCap::Round => Cap::Round,
+		crate::vector::style::LineCap::Square => Cap::Square,
+	};
+
+	// Perform operation on all subpaths in this shape, spread across threads.
+	let subpaths_out = crate::parallel::par_map_chunked(subpaths.collect(), |mut subpath: Subpath<PointId>| {
 		subpath.apply_transform(vector_data_transform);
 
 		// Taking the existing stroke data and passing it to Bezier-rs to generate new paths.
-		let subpath_out = subpath.outline(
-			stroke.weight / 2., // Diameter to radius.
-			match stroke.line_join {
-				LineJoin::Miter => Join::Miter(Some(stroke.line_join_miter_limit)),
-				LineJoin::Bevel => Join::Bevel,
-				LineJoin::Round => Join::Round,
-			},
-			match stroke.line_cap {
-				crate::vector::style::LineCap::Butt => Cap::Butt,
-				crate::vector::style::LineCap::Round => Cap::Round,
-				crate::vector::style::LineCap::Square => Cap::Square,
-			},
-		);
+		subpath.outline(radius, join, cap)
+	})
+	.await;
 
+	for subpath_out in subpaths_out {
 		// This is where we determine whether we have a closed or open path. Ex: Oval vs line segment.
 		if subpath_out.1.is_some() {
 			// Two closed subpaths, closed shape. Add both subpaths.
@@ -1832,6 +1916,31 @@ mod test {
 		);
 	}
 
+	#[tokio::test]
+	async fn text_warp() {
+		let rectangle = || vector_node(Subpath::new_rect(DVec2::ZERO, DVec2::new(100., 20.)));
+		let bounds = |warped: VectorDataTable| warped.instances().next().unwrap().instance.bounding_box().unwrap();
+
+		// The middle of the arc is raised by half the height, while its ends stay where they were
+		let arc = super::text_warp(Footprint::default(), rectangle(), WarpStyle::Arc, 50., DVec2::ZERO, DVec2::ZERO, DVec2::ZERO, DVec2::ZERO).await;
+		let [top_left, bottom_right] = bounds(arc);
+		assert!(top_left.abs_diff_eq(DVec2::new(0., -10.), 1e-5), "{top_left}");
+		assert!(bottom_right.abs_diff_eq(DVec2::new(100., 20.), 1e-5), "{bottom_right}");
+
+		// The bulge grows both upwards and downwards
+		let bulge = super::text_warp(Footprint::default(), rectangle(), WarpStyle::Bulge, 50., DVec2::ZERO, DVec2::ZERO, DVec2::ZERO, DVec2::ZERO).await;
+		let [top_left, bottom_right] = bounds(bulge);
+		assert!(top_left.abs_diff_eq(DVec2::new(0., -10.), 1e-5), "{top_left}");
+		assert!(bottom_right.abs_diff_eq(DVec2::new(100., 30.), 1e-5), "{bottom_right}");
+
+		// The envelope moves its corners by their offsets
+		let offset = DVec2::new(-10., -10.);
+		let envelope = super::text_warp(Footprint::default(), rectangle(), WarpStyle::Envelope, 50., offset, DVec2::ZERO, -offset, DVec2::ZERO).await;
+		let [top_left, bottom_right] = bounds(envelope);
+		assert!(top_left.abs_diff_eq(offset, 1e-5), "{top_left}");
+		assert!(bottom_right.abs_diff_eq(DVec2::new(110., 30.), 1e-5), "{bottom_right}");
+	}
+
 	#[track_caller]
 	fn contains_segment(vector: VectorData, target: bezier_rs::Bezier) {
 		let segments = vector.segment_bezier_iter().map(|x| x.1);
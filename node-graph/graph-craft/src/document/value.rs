@@ -183,6 +183,7 @@ tagged_value! {
 	VecDVec2(Vec<DVec2>),
 	RedGreenBlue(graphene_core::raster::RedGreenBlue),
 	RealTimeMode(graphene_core::animation::RealTimeMode),
+	Easing(graphene_core::animation::Easing),
 	RedGreenBlueAlpha(graphene_core::raster::RedGreenBlueAlpha),
 	NoiseType(graphene_core::raster::NoiseType),
 	FractalType(graphene_core::raster::FractalType),
@@ -213,6 +214,22 @@ tagged_value! {
 	VectorModification(Box<graphene_core::vector::VectorModification>),
 	CentroidType(graphene_core::vector::misc::CentroidType),
 	BooleanOperation(graphene_core::vector::misc::BooleanOperation),
+	BarcodeFormat(graphene_core::vector::misc::BarcodeFormat),
+	QrErrorCorrection(graphene_core::vector::misc::QrErrorCorrection),
+	WarpStyle(graphene_core::vector::misc::WarpStyle),
+	TextAlign(graphene_core::text::TextAlign),
+	VerticalAlign(graphene_core::text::VerticalAlign),
+	TextDirection(graphene_core::text::TextDirection),
+	WritingMode(graphene_core::text::WritingMode),
+	TextOrientation(graphene_core::text::TextOrientation),
+	FontFeatures(graphene_core::text::FontFeatures),
+	FontVariations(graphene_core::text::FontVariations),
+	TextScript(graphene_core::text::TextScript),
+	WrapMode(graphene_core::text::WrapMode),
+	TabStops(graphene_core::text::TabStops),
+	TextLanguage(graphene_core::text::TextLanguage),
+	TextCase(graphene_core::text::TextCase),
+	TextSpans(Vec<graphene_core::text::TextSpan>),
 	FontCache(Arc<graphene_core::text::FontCache>),
 }
 
@@ -1,6 +1,6 @@
 use dyn_any::StaticType;
 use graphene_core::application_io::SurfaceHandleFrame;
-use graphene_core::application_io::{ApplicationError, ApplicationIo, ResourceFuture, SurfaceHandle, SurfaceId};
+use graphene_core::application_io::{Antialiasing, ApplicationError, ApplicationIo, ResourceFuture, SurfaceHandle, SurfaceId};
 #[cfg(target_arch = "wasm32")]
 use js_sys::{Object, Reflect};
 use std::collections::HashMap;
@@ -276,6 +276,9 @@ pub type WasmSurfaceHandleFrame = SurfaceHandleFrame<wgpu_executor::Window>;
 pub struct EditorPreferences {
 	pub imaginate_hostname: String,
 	pub use_vello: bool,
+	/// The total size in bytes of the images, fonts, and renders cached by the runtime.
+	pub memory_budget: u64,
+	pub viewport_antialiasing: Antialiasing,
 }
 
 impl graphene_core::application_io::GetEditorPreferences for EditorPreferences {
@@ -285,6 +288,9 @@ impl graphene_core::application_io::GetEditorPreferences for EditorPreferences {
 	fn use_vello(&self) -> bool {
 		self.use_vello
 	}
+	fn viewport_antialiasing(&self) -> Antialiasing {
+		self.viewport_antialiasing
+	}
 }
 
 impl Default for EditorPreferences {
@@ -292,6 +298,8 @@ impl Default for EditorPreferences {
 		Self {
 			imaginate_hostname: "http://localhost:7860/".into(),
 			use_vello: false,
+			memory_budget: graphene_core::memory_budget::DEFAULT_MEMORY_BUDGET,
+			viewport_antialiasing: Antialiasing::default(),
 		}
 	}
 }
@@ -1,18 +1,21 @@
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use fern::colors::{Color, ColoredLevelConfig};
 use futures::executor::block_on;
+use glam::{DAffine2, DVec2, IVec2, UVec2};
+use graph_craft::document::value::{RenderOutput, RenderOutputType, TaggedValue};
 use graph_craft::document::*;
 use graph_craft::graphene_compiler::{Compiler, Executor};
 use graph_craft::proto::ProtoNetwork;
 use graph_craft::util::load_network;
 use graph_craft::wasm_application_io::EditorPreferences;
-use graphene_core::application_io::{ApplicationIo, NodeGraphUpdateSender};
+use graphene_core::application_io::{ApplicationIo, ExportFormat, NodeGraphUpdateSender, RenderConfig};
 use graphene_core::text::FontCache;
+use graphene_core::transform::Footprint;
 use graphene_std::wasm_application_io::{WasmApplicationIo, WasmEditorApi};
 use interpreted_executor::dynamic_executor::DynamicExecutor;
 use interpreted_executor::util::wrap_network_in_scope;
 use std::error::Error;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 struct UpdateLogger {}
@@ -56,6 +59,47 @@ enum Command {
 		#[clap(long, short = 'l')]
 		run_loop: bool,
 	},
+	/// Render a document to an image file without the editor, such as in CI or on a server.
+	Export {
+		/// Path to the .graphite document
+		document: PathBuf,
+
+		/// Path of the exported file
+		#[clap(long, short = 'o')]
+		output: PathBuf,
+
+		/// File format of the export, taken from the extension of the output path if not given
+		#[clap(long, short = 'f', value_enum)]
+		format: Option<ExportFileType>,
+
+		/// Name of the artboard to export, or its index counting from the top left. All artboards are exported together if not given, or all of the artwork in documents without artboards
+		#[clap(long, short = 'a')]
+		artboard: Option<String>,
+
+		/// Scale factor of the exported artwork, which sets the pixel size of raster exports
+		#[clap(long, short = 's', default_value_t = 1.)]
+		scale: f64,
+
+		/// Leave out the background of the artboards
+		#[clap(long, short = 't')]
+		transparent: bool,
+	},
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ExportFileType {
+	Svg,
+	Png,
+	Pdf,
+	Eps,
+}
+
+/// The region of an artboard in document space, read from the inputs of its Artboard node.
+#[derive(Debug, Clone, PartialEq)]
+struct ArtboardBounds {
+	label: String,
+	location: IVec2,
+	dimensions: IVec2,
 }
 
 #[derive(Debug, Args)]
@@ -76,6 +120,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
 	let document_path = match app.command {
 		Command::Compile { ref document, .. } => document,
 		Command::Run { ref document, .. } => document,
+		Command::Export { ref document, .. } => document,
 	};
 
 	let document_string = std::fs::read_to_string(document_path).expect("Failed to read document");
@@ -99,7 +144,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
 		editor_preferences: Box::new(preferences),
 	});
 
-	let proto_graph = compile_graph(document_string, editor_api)?;
+	let proto_graph = compile_graph(document_string.clone(), editor_api)?;
 
 	match app.command {
 		Command::Compile { print_proto, .. } => {
@@ -126,6 +171,80 @@ async fn main() -> Result<(), Box<dyn Error>> {
 				std::thread::sleep(std::time::Duration::from_millis(16));
 			}
 		}
+		Command::Export {
+			ref output,
+			format,
+			ref artboard,
+			scale,
+			transparent,
+			..
+		} => {
+			let format = match format {
+				Some(format) => format,
+				None => output
+					.extension()
+					.and_then(|extension| ExportFileType::from_str(&extension.to_string_lossy(), true).ok())
+					.ok_or("The export format can't be told from the output path, so it must be given with --format")?,
+			};
+			if scale <= 0. {
+				return Err("The scale must be greater than zero".into());
+			}
+
+			std::thread::spawn(move || {
+				loop {
+					std::thread::sleep(std::time::Duration::from_nanos(10));
+					device.poll(wgpu::Maintain::Poll);
+				}
+			});
+			let executor = create_executor(proto_graph)?;
+
+			let artboards = document_artboards(&load_network(&document_string));
+			let (location, dimensions) = match artboard {
+				Some(artboard) => {
+					let artboard = find_artboard(&artboards, artboard).ok_or_else(|| format!("The document has no artboard named or numbered \"{artboard}\""))?;
+					(artboard.location, artboard.dimensions)
+				}
+				None if artboards.is_empty() => {
+					// Without artboards, the region to export is found by measuring the artwork rendered in document space
+					let render_config = RenderConfig {
+						viewport: Footprint {
+							resolution: UVec2::ONE,
+							..Default::default()
+						},
+						export_format: ExportFormat::Svg,
+						for_export: true,
+						..Default::default()
+					};
+					let TaggedValue::RenderOutput(RenderOutput { data: RenderOutputType::Svg(svg), .. }) = (&executor).execute(render_config).await? else {
+						return Err("The document didn't render to an SVG".into());
+					};
+					artwork_bounds(&svg)?
+				}
+				None => {
+					let min = artboards.iter().map(|artboard| artboard.location).reduce(IVec2::min).unwrap_or_default();
+					let max = artboards.iter().map(|artboard| artboard.location + artboard.dimensions).reduce(IVec2::max).unwrap_or(min);
+					(min, max - min)
+				}
+			};
+
+			let render_config = RenderConfig {
+				viewport: Footprint {
+					transform: DAffine2::from_scale(DVec2::splat(scale)) * DAffine2::from_translation(-location.as_dvec2()),
+					resolution: (dimensions.as_dvec2() * scale).round().as_uvec2(),
+					..Default::default()
+				},
+				export_format: ExportFormat::Svg,
+				hide_artboards: transparent,
+				for_export: true,
+				..Default::default()
+			};
+
+			let TaggedValue::RenderOutput(RenderOutput { data: RenderOutputType::Svg(svg), .. }) = (&executor).execute(render_config).await? else {
+				return Err("The document didn't render to an SVG".into());
+			};
+			write_export(&svg, format, output)?;
+			log::info!("exported {}", output.display());
+		}
 	}
 
 	Ok(())
@@ -180,6 +299,85 @@ fn fix_nodes(network: &mut NodeNetwork) {
 		}
 	}
 }
+
+/// Finds the artboards at the root of the document's network, ordered from the top left.
+fn document_artboards(network: &NodeNetwork) -> Vec<ArtboardBounds> {
+	let mut artboards = network
+		.nodes
+		.values()
+		.filter_map(|node| {
+			let DocumentNodeImplementation::Network(nested_network) = &node.implementation else { return None };
+			let to_artboard = nested_network
+				.nodes
+				.values()
+				.find(|nested_node| matches!(&nested_node.implementation, DocumentNodeImplementation::ProtoNode(identifier) if identifier.name.ends_with("ToArtboardNode")))?;
+
+			// The label is kept in sync with the name of the layer on the inner node, while the other inputs are exposed on the Artboard node itself
+			let label = match to_artboard.inputs.get(1).and_then(NodeInput::as_value) {
+				Some(TaggedValue::String(label)) => label.clone(),
+				_ => String::new(),
+			};
+			let (Some(TaggedValue::IVec2(location)), Some(TaggedValue::IVec2(dimensions))) = (node.inputs.get(2).and_then(NodeInput::as_value), node.inputs.get(3).and_then(NodeInput::as_value))
+			else {
+				return None;
+			};
+
+			Some(ArtboardBounds {
+				label,
+				location: (*location).min(*location + *dimensions),
+				dimensions: dimensions.abs(),
+			})
+		})
+		.collect::<Vec<_>>();
+
+	artboards.sort_by_key(|artboard| (artboard.location.y, artboard.location.x));
+	artboards
+}
+
+/// Picks an artboard by its name, or otherwise by its index in the artboards ordered from the top left.
+fn find_artboard<'a>(artboards: &'a [ArtboardBounds], name_or_index: &str) -> Option<&'a ArtboardBounds> {
+	artboards
+		.iter()
+		.find(|artboard| artboard.label == name_or_index)
+		.or_else(|| name_or_index.parse::<usize>().ok().and_then(|index| artboards.get(index)))
+}
+
+/// Finds the region covered by the artwork, with its strokes, in an SVG rendered in document space, as its top left corner and its size.
+fn artwork_bounds(svg: &str) -> Result<(IVec2, IVec2), Box<dyn Error>> {
+	let tree = resvg::usvg::Tree::from_str(svg, &resvg::usvg::Options::default())?;
+	if !tree.root().has_children() {
+		return Err("The document has no artwork to export".into());
+	}
+
+	let bounds = tree.root().abs_stroke_bounding_box();
+	let min = DVec2::new(bounds.left() as f64, bounds.top() as f64).floor().as_ivec2();
+	let max = DVec2::new(bounds.right() as f64, bounds.bottom() as f64).ceil().as_ivec2();
+	if (max - min).cmple(IVec2::ZERO).any() {
+		return Err("The document's artwork has no area to export".into());
+	}
+	Ok((min, max - min))
+}
+
+fn write_export(svg: &str, format: ExportFileType, output: &Path) -> Result<(), Box<dyn Error>> {
+	match format {
+		ExportFileType::Svg => std::fs::write(output, svg)?,
+		ExportFileType::Png => {
+			let mut options = resvg::usvg::Options::default();
+			options.fontdb_mut().load_system_fonts();
+			let tree = resvg::usvg::Tree::from_str(svg, &options)?;
+
+			let size = tree.size().to_int_size();
+			let mut pixmap = resvg::tiny_skia::Pixmap::new(size.width(), size.height()).ok_or("The exported image has no area")?;
+			resvg::render(&tree, resvg::tiny_skia::Transform::default(), &mut pixmap.as_mut());
+			pixmap.save_png(output)?;
+		}
+		// Documents have no spot colors of their own, since they're kept with the swatches by the editor
+		ExportFileType::Pdf => std::fs::write(output, graphene_std::print::svg_to_pdf(svg, &[])?)?,
+		ExportFileType::Eps => std::fs::write(output, graphene_std::print::svg_to_eps(svg, &[])?)?,
+	}
+	Ok(())
+}
+
 fn compile_graph(document_string: String, editor_api: Arc<WasmEditorApi>) -> Result<ProtoNetwork, Box<dyn Error>> {
 	let mut network = load_network(&document_string);
 	fix_nodes(&mut network);
@@ -193,3 +391,39 @@ fn create_executor(proto_network: ProtoNetwork) -> Result<DynamicExecutor, Box<d
 	let executor = block_on(DynamicExecutor::new(proto_network)).map_err(|errors| errors.iter().map(|e| format!("{e:?}")).reduce(|acc, e| format!("{acc}\n{e}")).unwrap_or_default())?;
 	Ok(executor)
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn artboard(label: &str, location: (i32, i32)) -> ArtboardBounds {
+		ArtboardBounds {
+			label: label.to_string(),
+			location: location.into(),
+			dimensions: IVec2::new(100, 100),
+		}
+	}
+
+	#[test]
+	fn artboards_are_found_by_name_then_index() {
+		let artboards = [artboard("Cover", (0, 0)), artboard("1", (200, 0)), artboard("Back", (400, 0))];
+
+		assert_eq!(find_artboard(&artboards, "Back"), Some(&artboards[2]));
+		// A name which is also an index picks the artboard with that name
+		assert_eq!(find_artboard(&artboards, "1"), Some(&artboards[1]));
+		assert_eq!(find_artboard(&artboards, "0"), Some(&artboards[0]));
+		assert_eq!(find_artboard(&artboards, "3"), None);
+	}
+
+	#[test]
+	fn artwork_bounds_cover_shapes_and_strokes() {
+		let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="1" height="1"><rect x="-10.5" y="20" width="30" height="40" fill="#000" /><circle cx="100" cy="100" r="10" stroke="#000" stroke-width="4" /></svg>"##;
+
+		assert_eq!(artwork_bounds(svg).unwrap(), (IVec2::new(-11, 20), IVec2::new(123, 92)));
+	}
+
+	#[test]
+	fn documents_without_artwork_have_no_bounds() {
+		assert!(artwork_bounds(r#"<svg xmlns="http://www.w3.org/2000/svg" width="1" height="1"></svg>"#).is_err());
+	}
+}
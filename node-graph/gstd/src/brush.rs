@@ -5,7 +5,7 @@ use graph_craft::proto::FutureWrapperNode;
 use graphene_core::raster::adjustments::blend_colors;
 use graphene_core::raster::bbox::{AxisAlignedBbox, Bbox};
 use graphene_core::raster::brush_cache::BrushCache;
-use graphene_core::raster::image::{Image, ImageFrameTable};
+use graphene_core::raster::image::{Image, ImageContentId, ImageFrameTable};
 use graphene_core::raster::{Alpha, Bitmap, BlendMode, Color, Pixel, Sample};
 use graphene_core::transform::{Transform, TransformMut};
 use graphene_core::value::{ClonedNode, CopiedNode, ValueNode};
@@ -95,6 +95,7 @@ where
 	if positions.is_empty() {
 		return target;
 	}
+	target.one_instance_mut().instance.content_id = ImageContentId::new();
 
 	let target_width = target.one_instance().instance.width;
 	let target_height = target.one_instance().instance.height;
@@ -353,7 +354,11 @@ mod test {
 			ImageFrameTable::<Color>::default(),
 			ImageFrameTable::<Color>::default(),
 			vec![BrushStroke {
-				trace: vec![crate::vector::brush_stroke::BrushInputSample { position: DVec2::ZERO }],
+				trace: vec![crate::vector::brush_stroke::BrushInputSample {
+					position: DVec2::ZERO,
+					tilt: DVec2::ZERO,
+					twist: 0.,
+				}],
 				style: BrushStyle {
 					color: Color::BLACK,
 					diameter: 20.,
@@ -1,5 +1,5 @@
 use graph_craft::proto::types::Percentage;
-use graphene_core::raster::image::{Image, ImageFrameTable};
+use graphene_core::raster::image::{Image, ImageContentId, ImageFrameTable};
 use graphene_core::transform::{Transform, TransformMut};
 use graphene_core::{Color, Ctx};
 use image::{DynamicImage, GenericImage, GenericImageView, GrayImage, ImageBuffer, Luma, Rgba, RgbaImage};
@@ -29,6 +29,7 @@ async fn dehaze(_: impl Ctx, image_frame: ImageFrameTable<Color>, strength: Perc
 		height: image.height,
 		data: color_vec,
 		base64_string: None,
+		content_id: ImageContentId::new(),
 	};
 
 	let mut result = ImageFrameTable::new(dehazed_image);
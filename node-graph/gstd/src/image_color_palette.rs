@@ -61,10 +61,99 @@ async fn image_color_palette(
 	palette
 }
 
+/// The most pixels sampled from an image when clustering its colors, so large images don't take long to extract a palette from.
+const MAX_PALETTE_SAMPLES: usize = 16_384;
+/// The most rounds of refining the clusters before settling on the palette.
+const MAX_PALETTE_ITERATIONS: usize = 32;
+
+/// Finds up to `count` colors representative of the pixels by k-means clustering, ordered from the most to the least common.
+///
+/// Pixels are clustered in gamma space, which is closer to how different colors look than linear space.
+/// Transparent pixels are ignored and the palette's colors are opaque.
+pub fn cluster_palette(pixels: &[Color], count: usize) -> Vec<Color> {
+	let step = pixels.len().div_ceil(MAX_PALETTE_SAMPLES).max(1);
+	let samples = pixels
+		.iter()
+		.step_by(step)
+		.filter(|pixel| pixel.a() > 0.)
+		.map(|pixel| {
+			let gamma = pixel.to_unassociated_alpha().to_gamma_srgb();
+			[gamma.r(), gamma.g(), gamma.b()]
+		})
+		.collect::<Vec<_>>();
+	if samples.is_empty() || count == 0 {
+		return Vec::new();
+	}
+
+	let distance = |a: [f32; 3], b: [f32; 3]| a.iter().zip(b).map(|(a, b)| (a - b).powi(2)).sum::<f32>();
+	let nearest = |centers: &[[f32; 3]], sample: [f32; 3]| {
+		(0..centers.len())
+			.min_by(|&a, &b| distance(centers[a], sample).total_cmp(&distance(centers[b], sample)))
+			.unwrap_or_default()
+	};
+
+	// Start from colors spread as far apart as possible, each the sample farthest from those chosen so far, so the same image always gives the same palette
+	let mut centers = vec![samples[0]];
+	while centers.len() < count {
+		let Some((farthest, farthest_distance)) = samples
+			.iter()
+			.map(|&sample| (sample, distance(centers[nearest(&centers, sample)], sample)))
+			.max_by(|a, b| a.1.total_cmp(&b.1))
+		else {
+			break;
+		};
+		// Every sample is already one of the colors
+		if farthest_distance == 0. {
+			break;
+		}
+		centers.push(farthest);
+	}
+
+	// Move each color to the average of the samples nearest to it until they settle
+	let mut sizes = vec![0; centers.len()];
+	for _ in 0..MAX_PALETTE_ITERATIONS {
+		let mut sums = vec![[0.; 3]; centers.len()];
+		sizes = vec![0; centers.len()];
+		for &sample in &samples {
+			let cluster = nearest(&centers, sample);
+			sums[cluster].iter_mut().zip(sample).for_each(|(sum, channel)| *sum += channel);
+			sizes[cluster] += 1;
+		}
+
+		let moved = centers
+			.iter()
+			.zip(sums.iter().zip(&sizes))
+			.map(|(&center, (sum, &size))| if size == 0 { center } else { sum.map(|channel| channel / size as f32) })
+			.collect::<Vec<_>>();
+		let settled = moved == centers;
+		centers = moved;
+		if settled {
+			break;
+		}
+	}
+
+	let mut clusters = centers.into_iter().zip(sizes).filter(|&(_, size)| size > 0).collect::<Vec<_>>();
+	clusters.sort_by(|a, b| b.1.cmp(&a.1));
+	clusters.into_iter().map(|([r, g, b], _)| Color::from_rgbf32_unchecked(r, g, b).to_linear_srgb()).collect()
+}
+
+/// Extracts a palette of the colors most representative of the image by clustering its pixels, ordered from the most to the least common.
+#[node_macro::node(category("Raster"))]
+async fn extract_palette(
+	_: impl Ctx,
+	image: ImageFrameTable<Color>,
+	#[default(5)]
+	#[min(1.)]
+	#[max(64.)]
+	colors: u32,
+) -> Vec<Color> {
+	cluster_palette(&image.one_instance().instance.data, colors as usize)
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
-	use graphene_core::raster::image::{Image, ImageFrameTable};
+	use graphene_core::raster::image::{Image, ImageContentId, ImageFrameTable};
 
 	#[test]
 	fn test_image_color_palette() {
@@ -75,9 +164,32 @@ mod test {
 				height: 100,
 				data: vec![Color::from_rgbaf32(0., 0., 0., 1.).unwrap(); 10000],
 				base64_string: None,
+				content_id: ImageContentId::new(),
 			}),
 			1,
 		);
 		assert_eq!(futures::executor::block_on(result), [Color::from_rgbaf32(0., 0., 0., 1.).unwrap()]);
 	}
+
+	#[test]
+	fn extracts_the_most_common_colors_first() {
+		let red = Color::from_rgbaf32(1., 0., 0., 1.).unwrap();
+		let blue = Color::from_rgbaf32(0., 0., 1., 1.).unwrap();
+		let mut data = vec![red; 70];
+		data.extend([blue; 30]);
+		data.push(Color::TRANSPARENT);
+
+		let image = ImageFrameTable::new(Image {
+			width: 101,
+			height: 1,
+			data,
+			base64_string: None,
+			content_id: ImageContentId::new(),
+		});
+		let palette = futures::executor::block_on(extract_palette((), image, 4));
+
+		assert_eq!(palette.len(), 2);
+		assert!(palette[0].to_gamma_srgb().r() > 0.999 && palette[0].b() < 0.001);
+		assert!(palette[1].to_gamma_srgb().b() > 0.999 && palette[1].r() < 0.001);
+	}
 }
@@ -0,0 +1,313 @@
+use crate::image_color_palette::cluster_palette;
+use bezier_rs::{ManipulatorGroup, Subpath};
+use glam::{DAffine2, DVec2, IVec2};
+use graphene_core::raster::image::ImageFrameTable;
+use graphene_core::registry::types::{Angle, Percentage};
+use graphene_core::vector::style::Fill;
+use graphene_core::vector::{PointId, VectorData, VectorDataTable};
+use graphene_core::{Color, Ctx, GraphicElement, GraphicGroupTable};
+use std::collections::BTreeMap;
+
+/// The most the outlines are allowed to stray from the edges of the pixels when they're simplified at full smoothing, in pixels.
+const MAX_SIMPLIFY_TOLERANCE: f64 = 1.5;
+
+/// Traces the image into editable shapes, one for each of its colors, so scanned sketches and logos can be turned into vector artwork.
+#[node_macro::node(category("Vector"))]
+async fn vectorize(
+	_: impl Ctx,
+	image: ImageFrameTable<Color>,
+	/// The number of colors the image is reduced to, each traced as its own shape. With one color, only the pixels darker than the threshold are traced, in black.
+	#[default(1)]
+	#[min(1.)]
+	#[max(32.)]
+	colors: u32,
+	/// The lightness below which pixels are traced when tracing a single color.
+	#[default(50.)]
+	threshold: Percentage,
+	/// How much the outlines are simplified and rounded into curves, instead of following the edges of the pixels.
+	#[default(50.)]
+	smoothing: Percentage,
+	/// The sharpest turn in an outline which is rounded into a curve. Sharper turns are kept as corners.
+	#[default(60.)]
+	corner_threshold: Angle,
+) -> GraphicGroupTable {
+	let mut result = GraphicGroupTable::default();
+
+	let image = image.one_instance();
+	let transform = *image.transform;
+	let image = image.instance;
+	let (width, height) = (image.width as usize, image.height as usize);
+	if width == 0 || height == 0 {
+		return result;
+	}
+
+	// Pixels which are mostly transparent are left out of every shape
+	let opaque = |pixel: &Color| pixel.a() >= 0.5;
+
+	// The index of the color each pixel is traced as
+	let (palette, labels) = if colors <= 1 {
+		let threshold = (threshold / 100.) as f32;
+		let labels = image
+			.data
+			.iter()
+			.map(|pixel| (opaque(pixel) && pixel.to_unassociated_alpha().luminance_perceptual() < threshold).then_some(0))
+			.collect::<Vec<_>>();
+		(vec![Color::BLACK], labels)
+	} else {
+		let palette = cluster_palette(&image.data, colors as usize);
+		let gamma_palette = palette.iter().map(|color| color.to_gamma_srgb()).collect::<Vec<_>>();
+		let distance = |a: Color, b: Color| (a.r() - b.r()).powi(2) + (a.g() - b.g()).powi(2) + (a.b() - b.b()).powi(2);
+
+		let labels = image
+			.data
+			.iter()
+			.map(|pixel| {
+				if !opaque(pixel) {
+					return None;
+				}
+				let gamma = pixel.to_unassociated_alpha().to_gamma_srgb();
+				(0..gamma_palette.len()).min_by(|&a, &b| distance(gamma_palette[a], gamma).total_cmp(&distance(gamma_palette[b], gamma)))
+			})
+			.collect::<Vec<_>>();
+		(palette, labels)
+	};
+
+	let smoothing = (smoothing / 100.).clamp(0., 1.);
+	let tolerance = smoothing * MAX_SIMPLIFY_TOLERANCE;
+
+	// The shapes are traced in pixels, so they're scaled down to the unit square the image's transform is given for
+	let pixel_transform = transform * DAffine2::from_scale(DVec2::new(1. / width as f64, 1. / height as f64));
+
+	// The palette is ordered from the most to the least common color, so the largest shapes, like the background, end up at the bottom
+	for (index, color) in palette.into_iter().enumerate() {
+		let mask = labels.iter().map(|&label| label == Some(index)).collect::<Vec<_>>();
+		let subpaths = trace_outlines(&mask, width, height)
+			.iter()
+			.map(|outline| fit_curves(&simplify(outline, tolerance), smoothing, corner_threshold))
+			.collect::<Vec<_>>();
+		if subpaths.is_empty() {
+			continue;
+		}
+
+		let mut vector_data = VectorData::from_subpaths(subpaths, false);
+		vector_data.style.set_fill(Fill::Solid(color));
+		let instance = result.push(GraphicElement::VectorData(VectorDataTable::new(vector_data)));
+		*instance.transform = pixel_transform;
+	}
+
+	result
+}
+
+/// Follows the edges between the filled and empty pixels of the mask, given row by row, into closed outlines made of the corners of the pixel grid where the edges turn.
+///
+/// Outlines go clockwise around the filled pixels and counterclockwise around the holes in them, so they're filled correctly by the nonzero fill rule.
+/// Pixels touching only at their corners are traced as separate shapes.
+fn trace_outlines(mask: &[bool], width: usize, height: usize) -> Vec<Vec<DVec2>> {
+	let filled = |x: i32, y: i32| x >= 0 && y >= 0 && (x as usize) < width && (y as usize) < height && mask[y as usize * width + x as usize];
+
+	// The directions of the edges leaving each corner of the grid, keyed by row then column so the outlines are always traced in the same order
+	let mut edges: BTreeMap<(i32, i32), Vec<IVec2>> = BTreeMap::new();
+	let mut add_edge = |corner: IVec2, direction: IVec2| edges.entry((corner.y, corner.x)).or_default().push(direction);
+	for y in 0..height as i32 {
+		for x in 0..width as i32 {
+			if !filled(x, y) {
+				continue;
+			}
+			let corner = IVec2::new(x, y);
+			if !filled(x, y - 1) {
+				add_edge(corner, IVec2::X);
+			}
+			if !filled(x + 1, y) {
+				add_edge(corner + IVec2::X, IVec2::Y);
+			}
+			if !filled(x, y + 1) {
+				add_edge(corner + IVec2::ONE, IVec2::NEG_X);
+			}
+			if !filled(x - 1, y) {
+				add_edge(corner + IVec2::Y, IVec2::NEG_Y);
+			}
+		}
+	}
+
+	let take_edge = |edges: &mut BTreeMap<(i32, i32), Vec<IVec2>>, corner: IVec2, direction: IVec2| {
+		let key = (corner.y, corner.x);
+		let Some(directions) = edges.get_mut(&key) else { return };
+		directions.retain(|&other| other != direction);
+		if directions.is_empty() {
+			edges.remove(&key);
+		}
+	};
+
+	let mut outlines = Vec::new();
+	while let Some((&(y, x), directions)) = edges.first_key_value() {
+		let start = IVec2::new(x, y);
+		let first_direction = directions[0];
+		take_edge(&mut edges, start, first_direction);
+
+		let mut points = Vec::new();
+		let mut corner = start;
+		let mut direction = first_direction;
+		loop {
+			corner += direction;
+
+			let mut candidates = edges.get(&(corner.y, corner.x)).cloned().unwrap_or_default();
+			if corner == start {
+				candidates.push(first_direction);
+			}
+
+			// Turning right first keeps to the pixels being traced where two of them touch diagonally
+			let turns = [IVec2::new(-direction.y, direction.x), direction, IVec2::new(direction.y, -direction.x)];
+			let Some(next) = turns.into_iter().find(|turn| candidates.contains(turn)) else { break };
+
+			if next != direction {
+				points.push(corner.as_dvec2());
+			}
+			if corner == start && next == first_direction {
+				break;
+			}
+			take_edge(&mut edges, corner, next);
+			direction = next;
+		}
+
+		outlines.push(points);
+	}
+
+	outlines
+}
+
+/// Removes the points of a closed outline which stray less than the tolerance from the straight line between the points around them, by the Ramer–Douglas–Peucker algorithm.
+///
+/// This turns the staircases of pixel edges into diagonal lines. Outlines which would collapse to fewer than three points are left as they are.
+fn simplify(points: &[DVec2], tolerance: f64) -> Vec<DVec2> {
+	if tolerance <= 0. || points.len() <= 3 {
+		return points.to_vec();
+	}
+
+	let distance_to_line = |point: DVec2, start: DVec2, end: DVec2| {
+		let line = end - start;
+		let length_squared = line.length_squared();
+		if length_squared == 0. {
+			return point.distance(start);
+		}
+		let t = ((point - start).dot(line) / length_squared).clamp(0., 1.);
+		point.distance(start + line * t)
+	};
+
+	// The outline is split at the point farthest from the first, and each half is simplified as an open line ending where the other begins
+	let farthest = (1..points.len())
+		.max_by(|&a, &b| points[0].distance_squared(points[a]).total_cmp(&points[0].distance_squared(points[b])))
+		.unwrap_or_default();
+	let point = |index: usize| points[index % points.len()];
+
+	let mut keep = vec![false; points.len()];
+	keep[0] = true;
+	keep[farthest] = true;
+	let mut spans = vec![(0, farthest), (farthest, points.len())];
+	while let Some((start, end)) = spans.pop() {
+		let Some((index, distance)) = (start + 1..end)
+			.map(|index| (index, distance_to_line(point(index), point(start), point(end))))
+			.max_by(|a, b| a.1.total_cmp(&b.1))
+		else {
+			continue;
+		};
+		if distance > tolerance {
+			keep[index] = true;
+			spans.push((start, index));
+			spans.push((index, end));
+		}
+	}
+
+	let simplified = points.iter().zip(keep).filter(|&(_, keep)| keep).map(|(&point, _)| point).collect::<Vec<_>>();
+	if simplified.len() < 3 { points.to_vec() } else { simplified }
+}
+
+/// Builds a closed subpath through the points of an outline, rounding each turn gentler than the corner threshold (in degrees) into a curve with handles scaled by the smoothing.
+fn fit_curves(points: &[DVec2], smoothing: f64, corner_threshold: f64) -> Subpath<PointId> {
+	let count = points.len();
+	let manipulator_groups = (0..count)
+		.map(|index| {
+			let previous = points[(index + count - 1) % count];
+			let anchor = points[index];
+			let next = points[(index + 1) % count];
+
+			let turn = (anchor - previous).angle_to(next - anchor).abs().to_degrees();
+			if smoothing <= 0. || turn > corner_threshold {
+				return ManipulatorGroup::new(anchor, None, None);
+			}
+
+			// The handles follow the direction from the previous point to the next, reaching up to a third of the way along each side as a Catmull-Rom spline would
+			let tangent = (next - previous).normalize_or_zero();
+			let in_handle = anchor - tangent * anchor.distance(previous) * smoothing / 3.;
+			let out_handle = anchor + tangent * anchor.distance(next) * smoothing / 3.;
+			ManipulatorGroup::new(anchor, Some(in_handle), Some(out_handle))
+		})
+		.collect();
+
+	Subpath::new(manipulator_groups, true)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn mask(rows: &[&str]) -> (Vec<bool>, usize, usize) {
+		let mask = rows.iter().flat_map(|row| row.chars().map(|pixel| pixel == '#')).collect();
+		(mask, rows[0].len(), rows.len())
+	}
+
+	fn signed_area(points: &[DVec2]) -> f64 {
+		points.iter().zip(points.iter().cycle().skip(1)).map(|(a, b)| a.perp_dot(*b)).sum::<f64>() / 2.
+	}
+
+	#[test]
+	fn traces_a_rectangle_by_its_corners() {
+		let (mask, width, height) = mask(&["....", ".##.", ".##.", "...."]);
+		let outlines = trace_outlines(&mask, width, height);
+
+		assert_eq!(outlines.len(), 1);
+		let mut corners = outlines[0].clone();
+		corners.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+		assert_eq!(corners, [DVec2::new(1., 1.), DVec2::new(1., 3.), DVec2::new(3., 1.), DVec2::new(3., 3.)]);
+	}
+
+	#[test]
+	fn traces_holes_in_the_opposite_direction() {
+		let (mask, width, height) = mask(&["###", "#.#", "###"]);
+		let outlines = trace_outlines(&mask, width, height);
+
+		assert_eq!(outlines.len(), 2);
+		let areas = outlines.iter().map(|outline| signed_area(outline)).collect::<Vec<_>>();
+		assert_eq!(areas.iter().sum::<f64>().abs(), 8.);
+		assert!(areas[0].signum() != areas[1].signum());
+	}
+
+	#[test]
+	fn separates_pixels_touching_diagonally() {
+		let (mask, width, height) = mask(&["#.", ".#"]);
+		let outlines = trace_outlines(&mask, width, height);
+
+		assert_eq!(outlines.len(), 2);
+		assert!(outlines.iter().all(|outline| outline.len() == 4 && signed_area(outline).abs() == 1.));
+	}
+
+	#[test]
+	fn simplifies_staircases_into_diagonals() {
+		let (mask, width, height) = mask(&["#...", "##..", "###.", "####"]);
+		let outlines = trace_outlines(&mask, width, height);
+
+		assert_eq!(outlines.len(), 1);
+		assert_eq!(simplify(&outlines[0], 0.), outlines[0]);
+		assert_eq!(simplify(&outlines[0], 1.).len(), 3);
+	}
+
+	#[test]
+	fn keeps_sharp_turns_as_corners() {
+		let square = [DVec2::ZERO, DVec2::new(10., 0.), DVec2::new(10., 10.), DVec2::new(0., 10.)];
+
+		let sharp = fit_curves(&square, 1., 60.);
+		assert!(sharp.manipulator_groups().iter().all(|group| group.in_handle.is_none() && group.out_handle.is_none()));
+
+		let rounded = fit_curves(&square, 1., 120.);
+		assert!(rounded.manipulator_groups().iter().all(|group| group.in_handle.is_some() && group.out_handle.is_some()));
+	}
+}
@@ -7,6 +7,7 @@ use glam::{DVec2, U64Vec2};
 use graph_craft::imaginate_input::{ImaginateController, ImaginateMaskStartingFill, ImaginateSamplingMethod, ImaginateServerStatus, ImaginateStatus, ImaginateTerminationHandle};
 use graph_craft::wasm_application_io::EditorPreferences;
 use graphene_core::application_io::NodeGraphUpdateMessage;
+use graphene_core::raster::image::ImageContentId;
 use graphene_core::raster::{Color, Image, Luma, Pixel};
 use image::{DynamicImage, ImageBuffer, ImageFormat};
 use reqwest::Url;
@@ -497,6 +498,7 @@ fn base64_to_image<D: AsRef<[u8]>, P: Pixel>(base64_data: D) -> Result<Image<P>,
 		width,
 		height,
 		base64_string: None,
+		content_id: ImageContentId::new(),
 	})
 }
 
@@ -22,6 +22,10 @@ pub use graphene_core::*;
 
 pub mod image_color_palette;
 
+pub mod image_trace;
+
+pub mod print;
+
 pub mod brush;
 
 #[cfg(feature = "wasm")]
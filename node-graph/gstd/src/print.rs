@@ -0,0 +1,879 @@
+//! Conversion of rendered SVG artwork into the PDF and EPS formats used by print workflows.
+//!
+//! The SVG is parsed with usvg, which resolves its styles, transforms, and references, and the resulting tree is written out with the drawing operators of each format.
+//! Fills and strokes whose color matches one of the given spot colors are written in a Separation color space, so they're printed on a plate of their own.
+
+use glam::{DAffine2, DVec2};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use usvg::tiny_skia_path::{self, PathVerb};
+
+/// A named ink printed on a separation of its own, rather than mixed from the process inks.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpotColor {
+	pub name: String,
+	/// The gamma-space RGB color used to find the fills and strokes printed with this ink, and to preview it.
+	pub color: [u8; 3],
+	/// Whether the ink is printed on top of the inks beneath it rather than knocking them out.
+	pub overprint: bool,
+}
+
+impl SpotColor {
+	/// Fills and strokes are matched within a step of rounding, since colors can be rounded differently on their way to the SVG.
+	fn matches(&self, color: usvg::Color) -> bool {
+		let [red, green, blue] = self.color;
+		red.abs_diff(color.red) <= 1 && green.abs_diff(color.green) <= 1 && blue.abs_diff(color.blue) <= 1
+	}
+}
+
+fn find_spot_color(spot_colors: &[SpotColor], paint: &usvg::Paint) -> Option<usize> {
+	let usvg::Paint::Color(color) = paint else { return None };
+	spot_colors.iter().position(|spot| spot.matches(*color))
+}
+
+fn parse_svg(svg: &str) -> Result<usvg::Tree, String> {
+	usvg::Tree::from_str(svg, &usvg::Options::default()).map_err(|error| format!("The artwork could not be read: {error}"))
+}
+
+fn to_affine(transform: usvg::Transform) -> DAffine2 {
+	DAffine2::from_cols_array(&[
+		transform.sx as f64,
+		transform.ky as f64,
+		transform.kx as f64,
+		transform.sy as f64,
+		transform.tx as f64,
+		transform.ty as f64,
+	])
+}
+
+/// Formats a number as briefly as possible, since neither format accepts exponents and the precision beyond a few decimal places is invisible.
+fn number(value: f64) -> String {
+	if !value.is_finite() {
+		return "0".to_string();
+	}
+	let formatted = format!("{value:.4}");
+	let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+	if trimmed == "-0" { "0".to_string() } else { trimmed.to_string() }
+}
+
+fn matrix(transform: DAffine2) -> String {
+	transform.to_cols_array().map(number).join(" ")
+}
+
+fn channels(color: usvg::Color) -> [f64; 3] {
+	[color.red, color.green, color.blue].map(|channel| channel as f64 / 255.)
+}
+
+/// Writes the segments of a path with the operators of the format, which are named differently but take the same operands.
+fn write_path(out: &mut String, data: &tiny_skia_path::Path, transform: DAffine2, operators: [&str; 4]) {
+	let [move_to, line_to, curve_to, close] = operators;
+	let point = |point: &tiny_skia_path::Point| transform.transform_point2(DVec2::new(point.x as f64, point.y as f64));
+	let mut points = data.points().iter();
+	let mut last = DVec2::ZERO;
+
+	for verb in data.verbs() {
+		match verb {
+			PathVerb::Move => {
+				let Some(to) = points.next().map(point) else { break };
+				let _ = writeln!(out, "{} {} {move_to}", number(to.x), number(to.y));
+				last = to;
+			}
+			PathVerb::Line => {
+				let Some(to) = points.next().map(point) else { break };
+				let _ = writeln!(out, "{} {} {line_to}", number(to.x), number(to.y));
+				last = to;
+			}
+			PathVerb::Quad => {
+				let (Some(handle), Some(to)) = (points.next().map(point), points.next().map(point)) else { break };
+				// Neither format has quadratic curves, so they're raised to the equivalent cubic ones
+				let [first, second] = [last + (handle - last) * (2. / 3.), to + (handle - to) * (2. / 3.)];
+				let _ = writeln!(
+					out,
+					"{} {} {} {} {} {} {curve_to}",
+					number(first.x),
+					number(first.y),
+					number(second.x),
+					number(second.y),
+					number(to.x),
+					number(to.y)
+				);
+				last = to;
+			}
+			PathVerb::Cubic => {
+				let (Some(first), Some(second), Some(to)) = (points.next().map(point), points.next().map(point), points.next().map(point)) else {
+					break;
+				};
+				let _ = writeln!(
+					out,
+					"{} {} {} {} {} {} {curve_to}",
+					number(first.x),
+					number(first.y),
+					number(second.x),
+					number(second.y),
+					number(to.x),
+					number(to.y)
+				);
+				last = to;
+			}
+			PathVerb::Close => {
+				let _ = writeln!(out, "{close}");
+			}
+		}
+	}
+}
+
+/// The paths making up a clip path, in the coordinates of the group it clips, and whether they're all filled by the even-odd rule.
+fn clip_paths(clip_path: &usvg::ClipPath) -> (Vec<(&tiny_skia_path::Path, DAffine2)>, bool) {
+	fn collect<'a>(group: &'a usvg::Group, transform: DAffine2, paths: &mut Vec<(&'a tiny_skia_path::Path, DAffine2)>, even_odd: &mut bool) {
+		for child in group.children() {
+			match child {
+				usvg::Node::Group(group) => collect(group, transform * to_affine(group.transform()), paths, even_odd),
+				usvg::Node::Path(path) => {
+					*even_odd &= path.fill().is_some_and(|fill| fill.rule() == usvg::FillRule::EvenOdd);
+					paths.push((path.data(), transform));
+				}
+				usvg::Node::Text(text) => collect(text.flattened(), transform, paths, even_odd),
+				usvg::Node::Image(_) => {}
+			}
+		}
+	}
+
+	let mut paths = Vec::new();
+	let mut even_odd = true;
+	collect(clip_path.root(), to_affine(clip_path.transform()), &mut paths, &mut even_odd);
+	(paths, even_odd && !paths.is_empty())
+}
+
+/// The color function and coordinates of a gradient, written the same way in both formats, along with the opacity shared by its stops.
+struct Shading {
+	dictionary: String,
+	transform: DAffine2,
+	opacity: f64,
+}
+
+fn gradient_shading(paint: &usvg::Paint) -> Option<Shading> {
+	let (shading_type, coords, base) = match paint {
+		usvg::Paint::LinearGradient(linear) => (2, [linear.x1(), linear.y1(), linear.x2(), linear.y2()].map(|coord| number(coord as f64)).join(" "), &***linear),
+		usvg::Paint::RadialGradient(radial) => (
+			3,
+			[radial.fx(), radial.fy(), 0., radial.cx(), radial.cy(), radial.r().get()].map(|coord| number(coord as f64)).join(" "),
+			&***radial,
+		),
+		_ => return None,
+	};
+	let stops = base.stops();
+	let first = stops.first()?;
+	let last = stops.last()?;
+
+	// Each pair of neighboring stops is interpolated by a function of its own, stitched together over the length of the gradient
+	let mut offsets = vec![0.];
+	let mut colors = vec![channels(first.color())];
+	for stop in stops {
+		offsets.push((stop.offset().get() as f64).max(*offsets.last().unwrap_or(&0.)));
+		colors.push(channels(stop.color()));
+	}
+	offsets.push(1.);
+	colors.push(channels(last.color()));
+
+	let color = |channels: [f64; 3]| channels.map(number).join(" ");
+	let functions = colors
+		.windows(2)
+		.map(|pair| format!("<< /FunctionType 2 /Domain [0 1] /C0 [{}] /C1 [{}] /N 1 >>", color(pair[0]), color(pair[1])))
+		.collect::<Vec<_>>();
+	let bounds = offsets[1..offsets.len() - 1].iter().map(|&offset| number(offset)).collect::<Vec<_>>().join(" ");
+	let encode = vec!["0 1"; functions.len()].join(" ");
+	let function = format!("<< /FunctionType 3 /Domain [0 1] /Functions [{}] /Bounds [{bounds}] /Encode [{encode}] >>", functions.join(" "));
+
+	// Transparency that varies along a gradient can't be expressed in its color function, so it's only kept when all of its stops share it
+	let mut opacity = first.opacity().get() as f64;
+	if stops.iter().any(|stop| stop.opacity().get() as f64 != opacity) {
+		warn!("Gradients with stops of differing opacity are printed without their transparency");
+		opacity = 1.;
+	}
+
+	Some(Shading {
+		dictionary: format!("<< /ShadingType {shading_type} /ColorSpace /DeviceRGB /Coords [{coords}] /Function {function} /Extend [true true] >>"),
+		transform: to_affine(base.transform()),
+		opacity,
+	})
+}
+
+/// An image's pixels as 8-bit RGB channels, followed by its alpha channel if it has any transparency.
+struct DecodedImage {
+	width: u32,
+	height: u32,
+	rgb: Vec<u8>,
+	alpha: Option<Vec<u8>>,
+}
+
+fn decode_image(kind: &usvg::ImageKind) -> Option<DecodedImage> {
+	let data = match kind {
+		usvg::ImageKind::PNG(data) | usvg::ImageKind::JPEG(data) => data,
+		_ => {
+			warn!("Only PNG and JPEG images can be printed");
+			return None;
+		}
+	};
+	let image = image::load_from_memory(data)
+		.inspect_err(|error| warn!("An image could not be decoded to be printed: {error}"))
+		.ok()?
+		.to_rgba8();
+
+	let rgb = image.pixels().flat_map(|pixel| [pixel[0], pixel[1], pixel[2]]).collect();
+	let alpha = image.pixels().any(|pixel| pixel[3] < u8::MAX).then(|| image.pixels().map(|pixel| pixel[3]).collect());
+	Some(DecodedImage {
+		width: image.width(),
+		height: image.height(),
+		rgb,
+		alpha,
+	})
+}
+
+/// Converts rendered SVG artwork into a single page PDF document the size of the SVG.
+pub fn svg_to_pdf(svg: &str, spot_colors: &[SpotColor]) -> Result<Vec<u8>, String> {
+	let tree = parse_svg(svg)?;
+	let size = tree.size();
+	let (width, height) = (size.width() as f64, size.height() as f64);
+
+	let mut pdf = PdfWriter::new(spot_colors);
+	let catalog = pdf.reserve();
+	let pages = pdf.reserve();
+	let page = pdf.reserve();
+
+	// SVG coordinates run down from the top left, while PDF's run up from the bottom left
+	let flip = DAffine2::from_cols_array(&[1., 0., 0., -1., 0., height]);
+	let mut content = PdfContent::default();
+	let _ = writeln!(content.operators, "{} cm", matrix(flip));
+	pdf.write_group(&mut content, tree.root(), flip);
+	let (content, resources) = pdf.finish_content(content);
+
+	pdf.set(catalog, format!("<< /Type /Catalog /Pages {pages} 0 R >>").into_bytes());
+	pdf.set(pages, format!("<< /Type /Pages /Kids [{page} 0 R] /Count 1 >>").into_bytes());
+	pdf.set(
+		page,
+		format!(
+			"<< /Type /Page /Parent {pages} 0 R /MediaBox [0 0 {} {}] /Resources {resources} 0 R /Contents {content} 0 R >>",
+			number(width),
+			number(height)
+		)
+		.into_bytes(),
+	);
+
+	Ok(pdf.finish(catalog))
+}
+
+/// A content stream being written, along with the resources its operators refer to by name.
+#[derive(Default)]
+struct PdfContent {
+	operators: String,
+	resources: BTreeMap<&'static str, BTreeMap<String, usize>>,
+}
+
+impl PdfContent {
+	fn use_resource(&mut self, category: &'static str, name: String, object: usize) -> String {
+		self.resources.entry(category).or_default().insert(name.clone(), object);
+		name
+	}
+}
+
+struct PdfWriter<'a> {
+	/// The bodies of the objects, numbered from one.
+	objects: Vec<Vec<u8>>,
+	spot_colors: &'a [SpotColor],
+	/// The color space object of each spot color, written once it's first used.
+	spot_color_spaces: Vec<Option<usize>>,
+	/// The graphics state objects for each combination of fill opacity, stroke opacity, and overprinting, so they're written only once.
+	graphics_states: BTreeMap<(String, String, bool), usize>,
+}
+
+impl<'a> PdfWriter<'a> {
+	fn new(spot_colors: &'a [SpotColor]) -> Self {
+		Self {
+			objects: Vec::new(),
+			spot_colors,
+			spot_color_spaces: vec![None; spot_colors.len()],
+			graphics_states: BTreeMap::new(),
+		}
+	}
+
+	/// Reserves the number of an object to be written later, so other objects can refer to it first.
+	fn reserve(&mut self) -> usize {
+		self.objects.push(Vec::new());
+		self.objects.len()
+	}
+
+	fn set(&mut self, object: usize, body: Vec<u8>) {
+		self.objects[object - 1] = body;
+	}
+
+	fn add(&mut self, body: Vec<u8>) -> usize {
+		self.objects.push(body);
+		self.objects.len()
+	}
+
+	fn add_stream(&mut self, dictionary: &str, data: &[u8]) -> usize {
+		let mut body = format!("<< {dictionary}/Length {} >>\nstream\n", data.len()).into_bytes();
+		body.extend_from_slice(data);
+		body.extend_from_slice(b"\nendstream");
+		self.add(body)
+	}
+
+	/// Writes a content stream and the dictionary of its resources, returning the numbers of both.
+	fn finish_content(&mut self, content: PdfContent) -> (usize, usize) {
+		let resources = self.add_resources(&content);
+		(self.add_stream("", content.operators.as_bytes()), resources)
+	}
+
+	fn add_resources(&mut self, content: &PdfContent) -> usize {
+		let mut resources = String::from("<< /ProcSet [/PDF /ImageC]");
+		for (category, entries) in &content.resources {
+			let _ = write!(resources, " /{category} <<");
+			for (name, object) in entries {
+				let _ = write!(resources, " /{name} {object} 0 R");
+			}
+			resources.push_str(" >>");
+		}
+		resources.push_str(" >>");
+
+		self.add(resources.into_bytes())
+	}
+
+	fn graphics_state(&mut self, content: &mut PdfContent, fill_opacity: f64, stroke_opacity: f64, overprint: bool) -> Option<String> {
+		if fill_opacity >= 1. && stroke_opacity >= 1. && !overprint {
+			return None;
+		}
+
+		let key = (number(fill_opacity), number(stroke_opacity), overprint);
+		let object = match self.graphics_states.get(&key) {
+			Some(&object) => object,
+			None => {
+				let overprint = if overprint { " /OP true /op true /OPM 1" } else { "" };
+				let object = self.add(format!("<< /Type /ExtGState /ca {} /CA {}{overprint} >>", key.0, key.1).into_bytes());
+				self.graphics_states.insert(key, object);
+				object
+			}
+		};
+		Some(content.use_resource("ExtGState", format!("GS{object}"), object))
+	}
+
+	fn spot_color_space(&mut self, content: &mut PdfContent, index: usize) -> String {
+		let object = match self.spot_color_spaces[index] {
+			Some(object) => object,
+			None => {
+				let spot = &self.spot_colors[index];
+				// Viewers without the ink preview it by mixing its color with white in proportion to the tint
+				let color = spot.color.map(|channel| number(channel as f64 / 255.)).join(" ");
+				let tint_transform = format!("<< /FunctionType 2 /Domain [0 1] /C0 [1 1 1] /C1 [{color}] /N 1 >>");
+				let object = self.add(format!("[/Separation /{} /DeviceRGB {tint_transform}]", pdf_name(&spot.name)).into_bytes());
+				self.spot_color_spaces[index] = Some(object);
+				object
+			}
+		};
+		content.use_resource("ColorSpace", format!("CS{object}"), object)
+	}
+
+	/// Writes a group's children, where `transform` maps the group's coordinates to those the content stream's patterns are placed in.
+	fn write_group(&mut self, content: &mut PdfContent, group: &usvg::Group, transform: DAffine2) {
+		for child in group.children() {
+			match child {
+				usvg::Node::Group(child) => self.write_child_group(content, child, transform),
+				usvg::Node::Path(path) => self.write_path(content, path, transform),
+				usvg::Node::Image(image) => self.write_image(content, image, transform),
+				usvg::Node::Text(text) => self.write_group(content, text.flattened(), transform),
+			}
+		}
+	}
+
+	fn write_child_group(&mut self, content: &mut PdfContent, group: &usvg::Group, parent_transform: DAffine2) {
+		if group.mask().is_some() {
+			warn!("Masks can't be printed, so the masked artwork is printed in full");
+		}
+
+		let _ = writeln!(content.operators, "q");
+		let local = to_affine(group.transform());
+		if local != DAffine2::IDENTITY {
+			let _ = writeln!(content.operators, "{} cm", matrix(local));
+		}
+		if let Some(clip_path) = group.clip_path() {
+			self.write_clip(content, clip_path);
+		}
+
+		let opacity = group.opacity().get() as f64;
+		if opacity < 1. {
+			// The group is drawn together before being made transparent, so its overlapping parts don't show through each other
+			let mut form = PdfContent::default();
+			self.write_group(&mut form, group, DAffine2::IDENTITY);
+			let resources = self.add_resources(&form);
+			// The bounding box is only there to clip the form, which the group's clip path has already been applied to
+			let form = self.add_stream(
+				&format!("/Type /XObject /Subtype /Form /BBox [-100000 -100000 100000 100000] /Group << /S /Transparency >> /Resources {resources} 0 R "),
+				form.operators.as_bytes(),
+			);
+
+			if let Some(state) = self.graphics_state(content, opacity, opacity, false) {
+				let _ = writeln!(content.operators, "/{state} gs");
+			}
+			let name = content.use_resource("XObject", format!("Fm{form}"), form);
+			let _ = writeln!(content.operators, "/{name} Do");
+		} else {
+			self.write_group(content, group, parent_transform * local);
+		}
+
+		let _ = writeln!(content.operators, "Q");
+	}
+
+	fn write_clip(&mut self, content: &mut PdfContent, clip_path: &usvg::ClipPath) {
+		// Clip paths clipped by others are the intersection of both, which is what clipping by one then the other gives
+		if let Some(inner) = clip_path.clip_path() {
+			self.write_clip(content, inner);
+		}
+
+		let (paths, even_odd) = clip_paths(clip_path);
+		for (data, transform) in paths {
+			write_path(&mut content.operators, data, transform, ["m", "l", "c", "h"]);
+		}
+		let _ = writeln!(content.operators, "{} n", if even_odd { "W*" } else { "W" });
+	}
+
+	fn write_path(&mut self, content: &mut PdfContent, path: &usvg::Path, transform: DAffine2) {
+		if !path.is_visible() {
+			return;
+		}
+
+		let fill = |writer: &mut Self, content: &mut PdfContent| {
+			if let Some(fill) = path.fill() {
+				let operator = if fill.rule() == usvg::FillRule::EvenOdd { "f*" } else { "f" };
+				writer.write_paint(content, path.data(), fill.paint(), fill.opacity().get() as f64, transform, false, operator);
+			}
+		};
+		let stroke = |writer: &mut Self, content: &mut PdfContent| {
+			if let Some(stroke) = path.stroke() {
+				let _ = write!(
+					content.operators,
+					"{} w {} J {} j {} M ",
+					number(stroke.width().get() as f64),
+					line_cap(stroke.linecap()),
+					line_join(stroke.linejoin()),
+					number(stroke.miterlimit().get() as f64)
+				);
+				let dashes = stroke
+					.dasharray()
+					.map(|dashes| dashes.iter().map(|&dash| number(dash as f64)).collect::<Vec<_>>().join(" "))
+					.unwrap_or_default();
+				let _ = writeln!(content.operators, "[{dashes}] {} d", number(stroke.dashoffset() as f64));
+				writer.write_paint(content, path.data(), stroke.paint(), stroke.opacity().get() as f64, transform, true, "S");
+			}
+		};
+
+		match path.paint_order() {
+			usvg::PaintOrder::FillAndStroke => {
+				fill(self, content);
+				stroke(self, content);
+			}
+			usvg::PaintOrder::StrokeAndFill => {
+				stroke(self, content);
+				fill(self, content);
+			}
+		}
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	fn write_paint(&mut self, content: &mut PdfContent, data: &tiny_skia_path::Path, paint: &usvg::Paint, opacity: f64, transform: DAffine2, stroke: bool, operator: &str) {
+		let _ = writeln!(content.operators, "q");
+
+		let spot = find_spot_color(self.spot_colors, paint);
+		let (color_space, color_operator) = if stroke { ("CS", "SCN") } else { ("cs", "scn") };
+		let mut opacity = opacity;
+		match (paint, spot) {
+			(_, Some(index)) => {
+				let name = self.spot_color_space(content, index);
+				let _ = writeln!(content.operators, "/{name} {color_space} 1 {color_operator}");
+			}
+			(usvg::Paint::Color(color), None) => {
+				let operator = if stroke { "RG" } else { "rg" };
+				let _ = writeln!(content.operators, "{} {operator}", channels(*color).map(number).join(" "));
+			}
+			_ => match gradient_shading(paint) {
+				Some(shading) => {
+					opacity *= shading.opacity;
+					// Patterns are placed in the coordinates of the content stream rather than those of the path
+					let pattern = self.add(
+						format!(
+							"<< /Type /Pattern /PatternType 2 /Shading {} /Matrix [{}] >>",
+							shading.dictionary,
+							matrix(transform * shading.transform)
+						)
+						.into_bytes(),
+					);
+					let name = content.use_resource("Pattern", format!("P{pattern}"), pattern);
+					let _ = writeln!(content.operators, "/Pattern {color_space} /{name} {color_operator}");
+				}
+				None => {
+					warn!("Patterns can't be printed, so they're replaced by black");
+					let operator = if stroke { "RG" } else { "rg" };
+					let _ = writeln!(content.operators, "0 0 0 {operator}");
+				}
+			},
+		}
+
+		let overprint = spot.is_some_and(|index| self.spot_colors[index].overprint);
+		let (fill_opacity, stroke_opacity) = if stroke { (1., opacity) } else { (opacity, 1.) };
+		if let Some(state) = self.graphics_state(content, fill_opacity, stroke_opacity, overprint) {
+			let _ = writeln!(content.operators, "/{state} gs");
+		}
+
+		write_path(&mut content.operators, data, DAffine2::IDENTITY, ["m", "l", "c", "h"]);
+		let _ = writeln!(content.operators, "{operator}\nQ");
+	}
+
+	fn write_image(&mut self, content: &mut PdfContent, image: &usvg::Image, transform: DAffine2) {
+		if !image.is_visible() {
+			return;
+		}
+		let size = image.size();
+		let (width, height) = (size.width() as f64, size.height() as f64);
+
+		if let usvg::ImageKind::SVG(tree) = image.kind() {
+			let scale = DAffine2::from_scale(DVec2::new(width / tree.size().width() as f64, height / tree.size().height() as f64));
+			let _ = writeln!(content.operators, "q\n{} cm", matrix(scale));
+			self.write_group(content, tree.root(), transform * scale);
+			let _ = writeln!(content.operators, "Q");
+			return;
+		}
+
+		let Some(decoded) = decode_image(image.kind()) else { return };
+		let dimensions = format!("/Width {} /Height {} /BitsPerComponent 8", decoded.width, decoded.height);
+		let mask = decoded
+			.alpha
+			.map(|alpha| self.add_stream(&format!("/Type /XObject /Subtype /Image {dimensions} /ColorSpace /DeviceGray "), &alpha));
+		let mask = mask.map(|mask| format!(" /SMask {mask} 0 R")).unwrap_or_default();
+		let object = self.add_stream(&format!("/Type /XObject /Subtype /Image {dimensions} /ColorSpace /DeviceRGB{mask} "), &decoded.rgb);
+
+		// Images fill the unit square upwards from its bottom left, so it's flipped to fill the image's area down from its top left
+		let name = content.use_resource("XObject", format!("Im{object}"), object);
+		let _ = writeln!(content.operators, "q\n{} 0 0 {} 0 {} cm\n/{name} Do\nQ", number(width), number(-height), number(height));
+	}
+
+	fn finish(self, catalog: usize) -> Vec<u8> {
+		// The comment of bytes above 127 marks the file as binary for programs transferring it
+		let mut file = b"%PDF-1.7\n%\xE2\xE3\xCF\xD3\n".to_vec();
+		let mut offsets = Vec::with_capacity(self.objects.len());
+		for (index, body) in self.objects.iter().enumerate() {
+			offsets.push(file.len());
+			file.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+			file.extend_from_slice(body);
+			file.extend_from_slice(b"\nendobj\n");
+		}
+
+		let cross_reference = file.len();
+		let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", self.objects.len() + 1);
+		for offset in offsets {
+			let _ = writeln!(table, "{offset:010} 00000 n ");
+		}
+		let _ = write!(table, "trailer\n<< /Size {} /Root {catalog} 0 R >>\nstartxref\n{cross_reference}\n%%EOF\n", self.objects.len() + 1);
+		file.extend_from_slice(table.as_bytes());
+		file
+	}
+}
+
+fn line_cap(cap: usvg::LineCap) -> u8 {
+	match cap {
+		usvg::LineCap::Butt => 0,
+		usvg::LineCap::Round => 1,
+		usvg::LineCap::Square => 2,
+	}
+}
+
+fn line_join(join: usvg::LineJoin) -> u8 {
+	match join {
+		usvg::LineJoin::Miter | usvg::LineJoin::MiterClip => 0,
+		usvg::LineJoin::Round => 1,
+		usvg::LineJoin::Bevel => 2,
+	}
+}
+
+/// Writes a PDF name, where bytes other than printable ASCII and the delimiters of the syntax are escaped as hex codes.
+fn pdf_name(name: &str) -> String {
+	name.bytes()
+		.map(|byte| match byte {
+			b'!'..=b'~' if !b"()<>[]{}/%#".contains(&byte) => (byte as char).to_string(),
+			_ => format!("#{byte:02X}"),
+		})
+		.collect()
+}
+
+/// Writes a PostScript string literal, escaping the characters that would otherwise end it.
+fn postscript_string(text: &str) -> String {
+	let mut escaped = String::from("(");
+	for character in text.chars() {
+		match character {
+			'(' | ')' | '\\' => {
+				escaped.push('\\');
+				escaped.push(character);
+			}
+			' '..='~' => escaped.push(character),
+			_ => escaped.push('?'),
+		}
+	}
+	escaped.push(')');
+	escaped
+}
+
+/// Converts rendered SVG artwork into an Encapsulated PostScript file the size of the SVG.
+///
+/// PostScript has no transparency, so everything is printed opaque and images with transparency are composited over white.
+pub fn svg_to_eps(svg: &str, spot_colors: &[SpotColor]) -> Result<Vec<u8>, String> {
+	let tree = parse_svg(svg)?;
+	let size = tree.size();
+	let (width, height) = (size.width() as f64, size.height() as f64);
+
+	let mut out = String::new();
+	let _ = writeln!(out, "%!PS-Adobe-3.0 EPSF-3.0");
+	let _ = writeln!(out, "%%BoundingBox: 0 0 {} {}", width.ceil(), height.ceil());
+	let _ = writeln!(out, "%%HiResBoundingBox: 0 0 {} {}", number(width), number(height));
+	let _ = writeln!(out, "%%LanguageLevel: 3");
+	for spot in spot_colors {
+		let _ = writeln!(out, "%%DocumentCustomColors: {}", postscript_string(&spot.name));
+	}
+	let _ = writeln!(out, "%%EndComments");
+	let _ = writeln!(out, "save");
+
+	// SVG coordinates run down from the top left, while PostScript's run up from the bottom left
+	let _ = writeln!(out, "[1 0 0 -1 0 {}] concat", number(height));
+	EpsWriter { out: &mut out, spot_colors }.write_group(tree.root());
+
+	let _ = writeln!(out, "restore\nshowpage\n%%EOF");
+	Ok(out.into_bytes())
+}
+
+struct EpsWriter<'a> {
+	out: &'a mut String,
+	spot_colors: &'a [SpotColor],
+}
+
+impl EpsWriter<'_> {
+	fn write_group(&mut self, group: &usvg::Group) {
+		for child in group.children() {
+			match child {
+				usvg::Node::Group(child) => {
+					let _ = writeln!(self.out, "gsave");
+					let local = to_affine(child.transform());
+					if local != DAffine2::IDENTITY {
+						let _ = writeln!(self.out, "[{}] concat", matrix(local));
+					}
+					if let Some(clip_path) = child.clip_path() {
+						self.write_clip(clip_path);
+					}
+					self.write_group(child);
+					let _ = writeln!(self.out, "grestore");
+				}
+				usvg::Node::Path(path) => self.write_path(path),
+				usvg::Node::Image(image) => self.write_image(image),
+				usvg::Node::Text(text) => self.write_group(text.flattened()),
+			}
+		}
+	}
+
+	fn write_clip(&mut self, clip_path: &usvg::ClipPath) {
+		if let Some(inner) = clip_path.clip_path() {
+			self.write_clip(inner);
+		}
+
+		let (paths, even_odd) = clip_paths(clip_path);
+		let _ = writeln!(self.out, "newpath");
+		for (data, transform) in paths {
+			write_path(self.out, data, transform, ["moveto", "lineto", "curveto", "closepath"]);
+		}
+		let _ = writeln!(self.out, "{} newpath", if even_odd { "eoclip" } else { "clip" });
+	}
+
+	fn write_path(&mut self, path: &usvg::Path) {
+		if !path.is_visible() {
+			return;
+		}
+
+		let fill = |writer: &mut Self| {
+			if let Some(fill) = path.fill() {
+				let even_odd = fill.rule() == usvg::FillRule::EvenOdd;
+				writer.write_paint(path.data(), fill.paint(), if even_odd { "eofill" } else { "fill" }, if even_odd { "eoclip" } else { "clip" });
+			}
+		};
+		let stroke = |writer: &mut Self| {
+			if let Some(stroke) = path.stroke() {
+				let _ = write!(
+					writer.out,
+					"{} setlinewidth {} setlinecap {} setlinejoin {} setmiterlimit ",
+					number(stroke.width().get() as f64),
+					line_cap(stroke.linecap()),
+					line_join(stroke.linejoin()),
+					number(stroke.miterlimit().get() as f64)
+				);
+				let dashes = stroke
+					.dasharray()
+					.map(|dashes| dashes.iter().map(|&dash| number(dash as f64)).collect::<Vec<_>>().join(" "))
+					.unwrap_or_default();
+				let _ = writeln!(writer.out, "[{dashes}] {} setdash", number(stroke.dashoffset() as f64));
+				// Gradients are painted within the outline of the stroke
+				writer.write_paint(path.data(), stroke.paint(), "stroke", "strokepath clip");
+			}
+		};
+
+		match path.paint_order() {
+			usvg::PaintOrder::FillAndStroke => {
+				fill(self);
+				stroke(self);
+			}
+			usvg::PaintOrder::StrokeAndFill => {
+				stroke(self);
+				fill(self);
+			}
+		}
+	}
+
+	fn write_paint(&mut self, data: &tiny_skia_path::Path, paint: &usvg::Paint, paint_operator: &str, clip_operator: &str) {
+		let _ = writeln!(self.out, "gsave newpath");
+		write_path(self.out, data, DAffine2::IDENTITY, ["moveto", "lineto", "curveto", "closepath"]);
+
+		match (paint, find_spot_color(self.spot_colors, paint)) {
+			(_, Some(index)) => {
+				let spot = &self.spot_colors[index];
+				// The tint transform previews the ink by mixing its color with white in proportion to the tint
+				let [red, green, blue] = spot.color.map(|channel| number(1. - channel as f64 / 255.));
+				let _ = writeln!(
+					self.out,
+					"[/Separation {} /DeviceRGB {{dup dup {red} mul 1 exch sub 3 1 roll {green} mul 1 exch sub exch {blue} mul 1 exch sub}}] setcolorspace 1 setcolor",
+					postscript_string(&spot.name)
+				);
+				if spot.overprint {
+					let _ = writeln!(self.out, "true setoverprint");
+				}
+				let _ = writeln!(self.out, "{paint_operator}");
+			}
+			(usvg::Paint::Color(color), None) => {
+				let _ = writeln!(self.out, "{} setrgbcolor {paint_operator}", channels(*color).map(number).join(" "));
+			}
+			_ => match gradient_shading(paint) {
+				Some(shading) => {
+					let _ = writeln!(self.out, "{clip_operator} [{}] concat {} shfill", matrix(shading.transform), shading.dictionary);
+				}
+				None => {
+					warn!("Patterns can't be printed, so they're replaced by black");
+					let _ = writeln!(self.out, "0 0 0 setrgbcolor {paint_operator}");
+				}
+			},
+		}
+
+		let _ = writeln!(self.out, "grestore");
+	}
+
+	fn write_image(&mut self, image: &usvg::Image) {
+		if !image.is_visible() {
+			return;
+		}
+		let size = image.size();
+		let (width, height) = (size.width() as f64, size.height() as f64);
+
+		if let usvg::ImageKind::SVG(tree) = image.kind() {
+			let _ = writeln!(
+				self.out,
+				"gsave [{} 0 0 {} 0 0] concat",
+				number(width / tree.size().width() as f64),
+				number(height / tree.size().height() as f64)
+			);
+			self.write_group(tree.root());
+			let _ = writeln!(self.out, "grestore");
+			return;
+		}
+
+		let Some(decoded) = decode_image(image.kind()) else { return };
+		let (columns, rows) = (decoded.width, decoded.height);
+		// Images fill the unit square with their first row at the top, since the coordinates run downwards
+		let _ = writeln!(self.out, "gsave [{} 0 0 {} 0 0] concat", number(width), number(height));
+		let _ = writeln!(
+			self.out,
+			"/DeviceRGB setcolorspace << /ImageType 1 /Width {columns} /Height {rows} /BitsPerComponent 8 /Decode [0 1 0 1 0 1] /ImageMatrix [{columns} 0 0 {rows} 0 0] /DataSource currentfile /ASCIIHexDecode filter >> image"
+		);
+
+		let mut line = 0;
+		for (index, &channel) in decoded.rgb.iter().enumerate() {
+			// Transparent pixels are composited over white, which is what they'd be printed on
+			let alpha = decoded.alpha.as_ref().map_or(u8::MAX, |alpha| alpha[index / 3]) as u32;
+			let composited = (channel as u32 * alpha + u8::MAX as u32 * (u8::MAX as u32 - alpha)) / u8::MAX as u32;
+			let _ = write!(self.out, "{composited:02X}");
+			line += 2;
+			if line >= 78 {
+				self.out.push('\n');
+				line = 0;
+			}
+		}
+		let _ = writeln!(self.out, ">\ngrestore");
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	const SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="50"><rect width="40" height="20" fill="#e4002b" /><rect x="50" width="40" height="20" fill="#00ff00" stroke="#e4002b" /></svg>"##;
+
+	fn spot() -> SpotColor {
+		SpotColor {
+			name: "PANTONE 185 C".to_string(),
+			color: [0xe4, 0x00, 0x2b],
+			overprint: true,
+		}
+	}
+
+	#[test]
+	fn pdf_has_a_page_the_size_of_the_artwork() {
+		let pdf = String::from_utf8_lossy(&svg_to_pdf(SVG, &[]).unwrap()).into_owned();
+
+		assert!(pdf.starts_with("%PDF-1.7"));
+		assert!(pdf.contains("/MediaBox [0 0 100 50]"));
+		assert!(pdf.trim_end().ends_with("%%EOF"));
+		assert!(!pdf.contains("/Separation"));
+	}
+
+	#[test]
+	fn pdf_cross_reference_points_at_each_object() {
+		let pdf = svg_to_pdf(SVG, &[spot()]).unwrap();
+		let text = String::from_utf8_lossy(&pdf);
+
+		let table = text.rsplit_once("\nxref\n").unwrap().1;
+		for (index, entry) in table.lines().skip(2).take_while(|line| line.ends_with(" n ")).enumerate() {
+			let offset = entry[..10].parse::<usize>().unwrap();
+			assert!(pdf[offset..].starts_with(format!("{} 0 obj", index + 1).as_bytes()));
+		}
+	}
+
+	#[test]
+	fn pdf_prints_matching_colors_as_separations() {
+		let pdf = String::from_utf8_lossy(&svg_to_pdf(SVG, &[spot()]).unwrap()).into_owned();
+
+		// The color space is shared by the fill and the stroke which use the spot color
+		assert_eq!(pdf.matches("/Separation /PANTONE#20185#20C /DeviceRGB").count(), 1);
+		assert!(pdf.contains(" cs 1 scn"));
+		assert!(pdf.contains(" CS 1 SCN"));
+		assert!(pdf.contains("/OP true /op true /OPM 1"));
+		// The other fill is left as a process color
+		assert!(pdf.contains("0 1 0 rg"));
+	}
+
+	#[test]
+	fn eps_prints_matching_colors_as_separations() {
+		let eps = String::from_utf8(svg_to_eps(SVG, &[spot()]).unwrap()).unwrap();
+
+		assert!(eps.starts_with("%!PS-Adobe-3.0 EPSF-3.0"));
+		assert!(eps.contains("%%BoundingBox: 0 0 100 50"));
+		assert!(eps.contains("%%DocumentCustomColors: (PANTONE 185 C)"));
+		assert_eq!(eps.matches("[/Separation (PANTONE 185 C) /DeviceRGB").count(), 2);
+		assert!(eps.contains("true setoverprint"));
+		assert!(eps.contains("0 1 0 setrgbcolor fill"));
+	}
+
+	#[test]
+	fn names_and_strings_are_escaped() {
+		assert_eq!(pdf_name("Spot (1)/A#"), "Spot#20#281#29#2FA#23");
+		assert_eq!(postscript_string("Spot (1) \\"), "(Spot \\(1\\) \\\\)");
+	}
+}
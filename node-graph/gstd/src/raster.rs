@@ -2,7 +2,7 @@ use dyn_any::DynAny;
 use fastnoise_lite;
 use glam::{DAffine2, DVec2, Vec2};
 use graphene_core::raster::bbox::Bbox;
-use graphene_core::raster::image::{Image, ImageFrameTable};
+use graphene_core::raster::image::{Image, ImageContentId, ImageFrameTable};
 use graphene_core::raster::{
 	Alpha, AlphaMut, Bitmap, BitmapMut, CellularDistanceFunction, CellularReturnType, DomainWarpType, FractalType, Linear, LinearChannel, Luminance, NoiseType, Pixel, RGBMut, RedGreenBlue, Sample,
 };
@@ -56,8 +56,9 @@ fn sample_image(ctx: impl ExtractFootprint + Clone + Send, image_frame: ImageFra
 	let offset_px = image_size.transform_vector2(offset).as_uvec2();
 	let cropped = dynamic_image.crop_imm(offset_px.x, offset_px.y, size_px.x, size_px.y);
 
-	let viewport_resolution_x = footprint.transform.transform_vector2(DVec2::X * size.x).length();
-	let viewport_resolution_y = footprint.transform.transform_vector2(DVec2::Y * size.y).length();
+	let quality_scale = footprint.quality.resolution_scale();
+	let viewport_resolution_x = footprint.transform.transform_vector2(DVec2::X * size.x).length() * quality_scale;
+	let viewport_resolution_y = footprint.transform.transform_vector2(DVec2::Y * size.y).length() * quality_scale;
 	let mut new_width = size_px.x;
 	let mut new_height = size_px.y;
 
@@ -78,6 +79,7 @@ fn sample_image(ctx: impl ExtractFootprint + Clone + Send, image_frame: ImageFra
 		height: new_height,
 		data: vec,
 		base64_string: None,
+		content_id: ImageContentId::new(),
 	};
 	// we need to adjust the offset if we truncate the offset calculation
 
@@ -514,7 +516,7 @@ fn noise_pattern(
 		return ImageFrameTable::one_empty_image();
 	}
 
-	let footprint_scale = footprint.scale();
+	let footprint_scale = footprint.scale() * footprint.quality.resolution_scale();
 	let width = (size.x * footprint_scale.x) as u32;
 	let height = (size.y * footprint_scale.y) as u32;
 
@@ -640,7 +642,7 @@ fn mandelbrot(ctx: impl ExtractFootprint + Send) -> ImageFrameTable<Color> {
 		return ImageFrameTable::one_empty_image();
 	}
 
-	let scale = footprint.scale();
+	let scale = footprint.scale() * footprint.quality.resolution_scale();
 	let width = (size.x * scale.x) as u32;
 	let height = (size.y * scale.y) as u32;
 
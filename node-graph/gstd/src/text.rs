@@ -1,11 +1,14 @@
 use crate::vector::{VectorData, VectorDataTable};
 use graph_craft::wasm_application_io::WasmEditorApi;
 use graphene_core::Ctx;
-use graphene_core::text::TypesettingConfig;
 pub use graphene_core::text::{Font, FontCache, bounding_box, load_face, to_path};
+use graphene_core::text::{
+	FontFeatures, FontVariations, StyledText, TabStops, TextAlign, TextCase, TextDirection, TextLanguage, TextOrientation, TextPath, TextScript, TextSpan, TypesettingConfig, VerticalAlign, WrapMode,
+	WritingMode,
+};
 
 #[node_macro::node(category(""))]
-fn text<'i: 'n>(
+async fn text<'i: 'n>(
 	_: impl Ctx,
 	editor: &'i WasmEditorApi,
 	text: String,
@@ -15,18 +18,98 @@ fn text<'i: 'n>(
 	#[default(1.)] character_spacing: f64,
 	#[default(None)] max_width: Option<f64>,
 	#[default(None)] max_height: Option<f64>,
+	align: TextAlign,
+	vertical_align: VerticalAlign,
+	/// Character styling for ranges of the text, overriding its font, size, or color.
+	spans: Vec<TextSpan>,
+	/// A path for the text to follow, with the baseline of its first line running along the path's first subpath. The text runs in a straight line without one.
+	#[expose]
+	path: VectorDataTable,
+	/// The distance along the path where the text starts.
+	start_offset: f64,
+	/// The text box this one is linked from, whose text cut off by its max height continues here. The editor sets that text as this node's text when it evaluates the graph.
+	#[expose]
+	previous_frame: VectorDataTable,
+	/// The direction each paragraph runs in, which is found from its first letter with a direction when it's set to auto.
+	direction: TextDirection,
+	/// Whether the text runs in horizontal lines, or in vertical columns placed from right to left. Text following a path is always horizontal.
+	writing_mode: WritingMode,
+	/// How the glyphs of vertical text are turned in its columns.
+	orientation: TextOrientation,
+	/// The OpenType features of the font, like ligatures and small caps, which the text is shaped with.
+	features: FontFeatures,
+	/// The values of the axes of a variable font, like its weight and width, where the font has them.
+	variations: FontVariations,
+	/// How far the baseline of the text is raised, or lowered when negative, without changing the spacing of its lines.
+	baseline_shift: f64,
+	/// Whether the text is made smaller and raised as superscript or lowered as subscript.
+	script: TextScript,
+	/// Whether lines are wrapped to fit the max width between words or between any characters, or aren't wrapped and are cut off at the max width.
+	wrap: WrapMode,
+	/// The extra space above each paragraph except the first, or to its right in vertical text.
+	space_before: f64,
+	/// The extra space below each paragraph except the last, or to its left in vertical text.
+	space_after: f64,
+	/// Whether the text is underlined, with a line below its baseline where its font suggests.
+	underline: bool,
+	/// Whether the text is struck through, with a line across its middle where its font suggests.
+	strikethrough: bool,
+	/// Where tab characters move the text after them along its line.
+	tab_stops: TabStops,
+	/// Whether words too long for the rest of a wrapped line are broken across lines with a hyphen, where the language allows.
+	hyphenate: bool,
+	/// The language of the text, which decides where its words can be hyphenated.
+	language: TextLanguage,
+	/// The case the letters of the text are shown in, which leaves the text itself unchanged.
+	case: TextCase,
+	/// The space between the left of the text box and its lines, or the top of the columns of vertical text.
+	left_indent: f64,
+	/// The space between the right of the text box and its lines, or the bottom of the columns of vertical text.
+	right_indent: f64,
+	/// The extra indent of the first line of each paragraph, which hangs the line out past the others when negative.
+	first_line_indent: f64,
 ) -> VectorDataTable {
-	let buzz_face = editor.font_cache.get(&font_name).map(|data| load_face(data));
-
 	let typesetting = TypesettingConfig {
 		font_size,
 		line_height_ratio,
 		character_spacing,
 		max_width,
 		max_height,
+		align,
+		vertical_align,
+		direction,
+		writing_mode,
+		orientation,
+		features,
+		variations,
+		baseline_shift,
+		script,
+		wrap,
+		space_before,
+		space_after,
+		underline,
+		strikethrough,
+		tab_stops,
+		hyphenate,
+		language,
+		case,
+		left_indent,
+		right_indent,
+		first_line_indent,
+	};
+
+	// The link is only read by the editor, which gives this node the text overflowing the previous text box
+	let _ = previous_frame;
+
+	let Some(styled_text) = StyledText::new(&text, &font_name, &spans, typesetting, &editor.font_cache) else {
+		return VectorDataTable::new(VectorData::empty());
 	};
 
-	let result = VectorData::from_subpaths(to_path(&text, buzz_face, typesetting), false);
+	let text_path = path.instances().find_map(|instance| {
+		let mut subpath = instance.instance.stroke_bezier_paths().next()?;
+		subpath.apply_transform(*instance.transform);
+		Some(TextPath::new(&subpath, start_offset))
+	});
 
-	VectorDataTable::new(result)
+	VectorDataTable::new(styled_text.to_vector_data(&text, typesetting, text_path.as_ref()))
 }
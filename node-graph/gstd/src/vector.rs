@@ -1,5 +1,6 @@
 use bezier_rs::{ManipulatorGroup, Subpath};
 use glam::{DAffine2, DVec2};
+use graphene_core::parallel;
 use graphene_core::transform::Transform;
 use graphene_core::transform::TransformMut;
 use graphene_core::vector::misc::BooleanOperation;
@@ -12,203 +13,173 @@ use std::ops::Mul;
 
 #[node_macro::node(category(""))]
 async fn boolean_operation(_: impl Ctx, group_of_paths: GraphicGroupTable, operation: BooleanOperation) -> VectorDataTable {
-	fn flatten_vector_data(graphic_group_table: &GraphicGroupTable) -> Vec<VectorDataTable> {
-		graphic_group_table
-			.instances()
-			.map(|element| match element.instance.clone() {
-				GraphicElement::VectorData(mut vector_data) => {
-					// Apply the parent group's transform to each element of vector data
-					for sub_vector_data in vector_data.instances_mut() {
-						*sub_vector_data.transform = *element.transform * *sub_vector_data.transform;
-					}
+	// The first index is the bottom of the stack
+	let mut result_vector_data_table = boolean_operation_on_vector_data(flatten_vector_data(&group_of_paths), operation).await;
 
-					vector_data
-				}
-				GraphicElement::RasterFrame(mut image) => {
-					// Apply the parent group's transform to each element of raster data
-					match &mut image {
-						graphene_core::RasterFrame::ImageFrame(image) => {
-							for instance in image.instances_mut() {
-								*instance.transform = *element.transform * *instance.transform;
-							}
-						}
-						graphene_core::RasterFrame::TextureFrame(image) => {
-							for instance in image.instances_mut() {
-								*instance.transform = *element.transform * *instance.transform;
-							}
-						}
-					}
+	// Replace the transformation matrix with a mutation of the vector points themselves
+	let result_vector_data_table_transform = result_vector_data_table.transform();
+	*result_vector_data_table.transform_mut() = DAffine2::IDENTITY;
+	let result_vector_data = result_vector_data_table.one_instance_mut().instance;
+	VectorData::transform(result_vector_data, result_vector_data_table_transform);
+	result_vector_data.style.set_stroke_transform(DAffine2::IDENTITY);
+	result_vector_data.upstream_graphic_group = Some(group_of_paths.clone());
+
+	result_vector_data_table
+}
+
+fn flatten_vector_data(graphic_group_table: &GraphicGroupTable) -> Vec<VectorDataTable> {
+	let elements = graphic_group_table.instances().map(|element| (element.instance.clone(), *element.transform)).collect();
 
-					// Convert the image frame into a rectangular subpath with the image's transform
-					let mut subpath = Subpath::new_rect(DVec2::ZERO, DVec2::ONE);
-					subpath.apply_transform(image.transform());
+	// Each element is flattened on its own thread, since nested groups require their own boolean operation
+	parallel::par_map(elements, |(element, parent_transform)| match element {
+		GraphicElement::VectorData(mut vector_data) => {
+			// Apply the parent group's transform to each element of vector data
+			for sub_vector_data in vector_data.instances_mut() {
+				*sub_vector_data.transform = parent_transform * *sub_vector_data.transform;
+			}
 
-					// Create a vector data table from the rectangular subpath, with a default black fill
-					let mut vector_data = VectorData::from_subpath(subpath);
-					vector_data.style.set_fill(Fill::Solid(Color::BLACK));
-					VectorDataTable::new(vector_data)
+			vector_data
+		}
+		GraphicElement::RasterFrame(mut image) => {
+			// Apply the parent group's transform to each element of raster data
+			match &mut image {
+				graphene_core::RasterFrame::ImageFrame(image) => {
+					for instance in image.instances_mut() {
+						*instance.transform = parent_transform * *instance.transform;
+					}
 				}
-				GraphicElement::GraphicGroup(mut graphic_group) => {
-					// Apply the parent group's transform to each element of inner group
-					for sub_element in graphic_group.instances_mut() {
-						*sub_element.transform = *element.transform * *sub_element.transform;
+				graphene_core::RasterFrame::TextureFrame(image) => {
+					for instance in image.instances_mut() {
+						*instance.transform = parent_transform * *instance.transform;
 					}
-
-					// Recursively flatten the inner group into vector data
-					boolean_operation_on_vector_data(&flatten_vector_data(&graphic_group), BooleanOperation::Union)
 				}
-			})
-			.collect()
-	}
-
-	fn subtract<'a>(vector_data: impl Iterator<Item = &'a VectorDataTable>) -> VectorDataTable {
-		let mut vector_data = vector_data.into_iter();
-		let mut result = vector_data.next().cloned().unwrap_or_default();
-		let mut next_vector_data = vector_data.next();
+			}
 
-		while let Some(lower_vector_data) = next_vector_data {
-			let transform_of_lower_into_space_of_upper = result.transform().inverse() * lower_vector_data.transform();
+			// Convert the image frame into a rectangular subpath with the image's transform
+			let mut subpath = Subpath::new_rect(DVec2::ZERO, DVec2::ONE);
+			subpath.apply_transform(image.transform());
 
-			let result = result.one_instance_mut().instance;
+			// Create a vector data table from the rectangular subpath, with a default black fill
+			let mut vector_data = VectorData::from_subpath(subpath);
+			vector_data.style.set_fill(Fill::Solid(Color::BLACK));
+			VectorDataTable::new(vector_data)
+		}
+		GraphicElement::GraphicGroup(mut graphic_group) => {
+			// Apply the parent group's transform to each element of inner group
+			for sub_element in graphic_group.instances_mut() {
+				*sub_element.transform = parent_transform * *sub_element.transform;
+			}
 
-			let upper_path_string = to_path(result, DAffine2::IDENTITY);
-			let lower_path_string = to_path(lower_vector_data.one_instance().instance, transform_of_lower_into_space_of_upper);
+			// Recursively flatten the inner group into vector data
+			let mut vector_data = flatten_vector_data(&graphic_group);
+			vector_data.reverse();
+			boolean_reduce(vector_data, boolean_union)
+		}
+	})
+}
 
-			#[allow(unused_unsafe)]
-			let boolean_operation_string = unsafe { boolean_subtract(upper_path_string, lower_path_string) };
-			let boolean_operation_result = from_path(&boolean_operation_string);
+async fn boolean_operation_on_vector_data(vector_data_table: Vec<VectorDataTable>, boolean_operation: BooleanOperation) -> VectorDataTable {
+	match boolean_operation {
+		BooleanOperation::Union => {
+			// Reverse vector data so that the result style is the style of the first vector data
+			let mut vector_data_table = vector_data_table;
+			vector_data_table.reverse();
+			boolean_reduce_chunked(vector_data_table, boolean_union).await
+		}
+		BooleanOperation::SubtractFront => subtract(vector_data_table).await,
+		BooleanOperation::SubtractBack => {
+			let mut vector_data_table = vector_data_table;
+			vector_data_table.reverse();
+			subtract(vector_data_table).await
+		}
+		BooleanOperation::Intersect => {
+			let mut vector_data_table = vector_data_table;
+			vector_data_table.reverse();
+			boolean_reduce_chunked(vector_data_table, boolean_intersect).await
+		}
+		BooleanOperation::Difference => {
+			let mut lower_vector_data_table = vector_data_table.iter().rev().cloned().collect::<Vec<_>>();
+			if lower_vector_data_table.is_empty() {
+				lower_vector_data_table.push(VectorDataTable::default());
+			}
 
-			result.colinear_manipulators = boolean_operation_result.colinear_manipulators;
-			result.point_domain = boolean_operation_result.point_domain;
-			result.segment_domain = boolean_operation_result.segment_domain;
-			result.region_domain = boolean_operation_result.region_domain;
+			// Find where each vector data intersects with any of the others, with each one on its own thread
+			let intersections = parallel::par_map_chunked(lower_vector_data_table, |lower_vector_data| {
+				let mut all_other_vector_data = vector_data_table.iter().filter(|v| *v != &lower_vector_data).cloned().collect::<Vec<_>>();
+				all_other_vector_data.reverse();
+				let all_other_vector_data = boolean_reduce(all_other_vector_data, boolean_union);
 
-			next_vector_data = vector_data.next();
+				boolean_pair(all_other_vector_data, &lower_vector_data, boolean_intersect)
+			})
+			.await;
+
+			// Combine them into everywhere that all vector data intersect at least once
+			let mut intersections = intersections;
+			intersections.push(VectorDataTable::default());
+			let any_intersection = boolean_reduce_chunked(intersections, boolean_union).await;
+
+			// Subtract the area where they intersect at least once from the union of all vector data
+			let mut union = vector_data_table;
+			union.reverse();
+			let union = boolean_reduce_chunked(union, boolean_union).await;
+			subtract(vec![union, any_intersection]).await
 		}
-
-		result
 	}
+}
 
-	fn boolean_operation_on_vector_data(vector_data_table: &[VectorDataTable], boolean_operation: BooleanOperation) -> VectorDataTable {
-		match boolean_operation {
-			BooleanOperation::Union => {
-				// Reverse vector data so that the result style is the style of the first vector data
-				let mut vector_data_table = vector_data_table.iter().rev();
-				let mut result_vector_data_table = vector_data_table.next().cloned().unwrap_or_default();
-
-				// Loop over all vector data and union it with the result
-				let default = VectorDataTable::default();
-				let mut second_vector_data = Some(vector_data_table.next().unwrap_or(&default));
-				while let Some(lower_vector_data) = second_vector_data {
-					let transform_of_lower_into_space_of_upper = result_vector_data_table.transform().inverse() * lower_vector_data.transform();
-
-					let result_vector_data = result_vector_data_table.one_instance_mut().instance;
-
-					let upper_path_string = to_path(result_vector_data, DAffine2::IDENTITY);
-					let lower_path_string = to_path(lower_vector_data.one_instance().instance, transform_of_lower_into_space_of_upper);
-
-					#[allow(unused_unsafe)]
-					let boolean_operation_string = unsafe { boolean_union(upper_path_string, lower_path_string) };
-					let boolean_operation_result = from_path(&boolean_operation_string);
-
-					result_vector_data.colinear_manipulators = boolean_operation_result.colinear_manipulators;
-					result_vector_data.point_domain = boolean_operation_result.point_domain;
-					result_vector_data.segment_domain = boolean_operation_result.segment_domain;
-					result_vector_data.region_domain = boolean_operation_result.region_domain;
-
-					second_vector_data = vector_data_table.next();
-				}
-
-				result_vector_data_table
-			}
-			BooleanOperation::SubtractFront => subtract(vector_data_table.iter()),
-			BooleanOperation::SubtractBack => subtract(vector_data_table.iter().rev()),
-			BooleanOperation::Intersect => {
-				let mut vector_data = vector_data_table.iter().rev();
-				let mut result = vector_data.next().cloned().unwrap_or_default();
-				let default = VectorDataTable::default();
-				let mut second_vector_data = Some(vector_data.next().unwrap_or(&default));
-
-				// For each vector data, set the result to the intersection of that data and the result
-				while let Some(lower_vector_data) = second_vector_data {
-					let transform_of_lower_into_space_of_upper = result.transform().inverse() * lower_vector_data.transform();
-
-					let result = result.one_instance_mut().instance;
-
-					let upper_path_string = to_path(result, DAffine2::IDENTITY);
-					let lower_path_string = to_path(lower_vector_data.one_instance().instance, transform_of_lower_into_space_of_upper);
-
-					#[allow(unused_unsafe)]
-					let boolean_operation_string = unsafe { boolean_intersect(upper_path_string, lower_path_string) };
-					let boolean_operation_result = from_path(&boolean_operation_string);
-
-					result.colinear_manipulators = boolean_operation_result.colinear_manipulators;
-					result.point_domain = boolean_operation_result.point_domain;
-					result.segment_domain = boolean_operation_result.segment_domain;
-					result.region_domain = boolean_operation_result.region_domain;
-					second_vector_data = vector_data.next();
-				}
-
-				result
-			}
-			BooleanOperation::Difference => {
-				let mut vector_data_iter = vector_data_table.iter().rev();
-				let mut any_intersection = VectorDataTable::default();
-				let default = VectorDataTable::default();
-				let mut second_vector_data = Some(vector_data_iter.next().unwrap_or(&default));
-
-				// Find where all vector data intersect at least once
-				while let Some(lower_vector_data) = second_vector_data {
-					let all_other_vector_data = boolean_operation_on_vector_data(&vector_data_table.iter().filter(|v| v != &lower_vector_data).cloned().collect::<Vec<_>>(), BooleanOperation::Union);
-					let all_other_vector_data_instance = all_other_vector_data.one_instance();
+/// Subtracts every other vector data from the first, by subtracting the union of the others all at once.
+async fn subtract(vector_data_table: Vec<VectorDataTable>) -> VectorDataTable {
+	let mut vector_data_table = vector_data_table.into_iter();
+	let result = vector_data_table.next().unwrap_or_default();
 
-					let transform_of_lower_into_space_of_upper = all_other_vector_data.transform().inverse() * lower_vector_data.transform();
+	let lower_vector_data_table = vector_data_table.collect::<Vec<_>>();
+	if lower_vector_data_table.is_empty() {
+		return result;
+	}
+	let lower_vector_data = boolean_reduce_chunked(lower_vector_data_table, boolean_union).await;
 
-					let upper_path_string = to_path(all_other_vector_data_instance.instance, DAffine2::IDENTITY);
-					let lower_path_string = to_path(lower_vector_data.one_instance().instance, transform_of_lower_into_space_of_upper);
+	boolean_pair(result, &lower_vector_data, boolean_subtract)
+}
 
-					#[allow(unused_unsafe)]
-					let boolean_intersection_string = unsafe { boolean_intersect(upper_path_string, lower_path_string) };
-					let mut boolean_intersection_result = VectorDataTable::new(from_path(&boolean_intersection_string));
-					*boolean_intersection_result.transform_mut() = *all_other_vector_data_instance.transform;
+/// Combines all the vector data with the boolean operation, the first being the upper one whose style and transform is kept.
+/// A single vector data is combined with empty vector data so the result is always the output of the operation.
+fn boolean_reduce(mut vector_data_table: Vec<VectorDataTable>, operation: fn(Path, Path) -> Vec<Path>) -> VectorDataTable {
+	if vector_data_table.len() < 2 {
+		vector_data_table.resize_with(2, VectorDataTable::default);
+	}
 
-					boolean_intersection_result.one_instance_mut().instance.style = all_other_vector_data_instance.instance.style.clone();
-					*boolean_intersection_result.one_instance_mut().alpha_blending = *all_other_vector_data_instance.alpha_blending;
+	parallel::par_reduce(vector_data_table, |upper, lower| boolean_pair(upper, &lower, operation)).unwrap_or_default()
+}
 
-					let transform_of_lower_into_space_of_upper = boolean_intersection_result.one_instance_mut().transform.inverse() * any_intersection.transform();
+/// Combines the vector data like [`boolean_reduce`], but yields to the executor between each level of the operations.
+async fn boolean_reduce_chunked(mut vector_data_table: Vec<VectorDataTable>, operation: fn(Path, Path) -> Vec<Path>) -> VectorDataTable {
+	if vector_data_table.len() < 2 {
+		vector_data_table.resize_with(2, VectorDataTable::default);
+	}
 
-					let upper_path_string = to_path(boolean_intersection_result.one_instance_mut().instance, DAffine2::IDENTITY);
-					let lower_path_string = to_path(any_intersection.one_instance_mut().instance, transform_of_lower_into_space_of_upper);
+	parallel::par_reduce_chunked(vector_data_table, |upper, lower| boolean_pair(upper, &lower, operation))
+		.await
+		.unwrap_or_default()
+}
 
-					#[allow(unused_unsafe)]
-					let union_result = from_path(&unsafe { boolean_union(upper_path_string, lower_path_string) });
-					*any_intersection.one_instance_mut().instance = union_result;
+/// Applies the boolean operation to the paths of the upper and lower vector data, keeping the style and transform of the upper.
+fn boolean_pair(mut upper: VectorDataTable, lower: &VectorDataTable, operation: fn(Path, Path) -> Vec<Path>) -> VectorDataTable {
+	let transform_of_lower_into_space_of_upper = upper.transform().inverse() * lower.transform();
 
-					*any_intersection.transform_mut() = boolean_intersection_result.transform();
-					any_intersection.one_instance_mut().instance.style = boolean_intersection_result.one_instance_mut().instance.style.clone();
-					any_intersection.one_instance_mut().alpha_blending = boolean_intersection_result.one_instance_mut().alpha_blending;
+	let result = upper.one_instance_mut().instance;
 
-					second_vector_data = vector_data_iter.next();
-				}
-				// Subtract the area where they intersect at least once from the union of all vector data
-				let union = boolean_operation_on_vector_data(vector_data_table, BooleanOperation::Union);
-				boolean_operation_on_vector_data(&[union, any_intersection], BooleanOperation::SubtractFront)
-			}
-		}
-	}
+	let upper_path_string = to_path(result, DAffine2::IDENTITY);
+	let lower_path_string = to_path(lower.one_instance().instance, transform_of_lower_into_space_of_upper);
 
-	// The first index is the bottom of the stack
-	let mut result_vector_data_table = boolean_operation_on_vector_data(&flatten_vector_data(&group_of_paths), operation);
+	let boolean_operation_string = operation(upper_path_string, lower_path_string);
+	let boolean_operation_result = from_path(&boolean_operation_string);
 
-	// Replace the transformation matrix with a mutation of the vector points themselves
-	let result_vector_data_table_transform = result_vector_data_table.transform();
-	*result_vector_data_table.transform_mut() = DAffine2::IDENTITY;
-	let result_vector_data = result_vector_data_table.one_instance_mut().instance;
-	VectorData::transform(result_vector_data, result_vector_data_table_transform);
-	result_vector_data.style.set_stroke_transform(DAffine2::IDENTITY);
-	result_vector_data.upstream_graphic_group = Some(group_of_paths.clone());
+	result.colinear_manipulators = boolean_operation_result.colinear_manipulators;
+	result.point_domain = boolean_operation_result.point_domain;
+	result.segment_domain = boolean_operation_result.segment_domain;
+	result.region_domain = boolean_operation_result.region_domain;
 
-	result_vector_data_table
+	upper
 }
 
 fn to_path(vector: &VectorData, transform: DAffine2) -> Vec<path_bool::PathSegment> {
@@ -3,18 +3,19 @@ pub use graph_craft::document::value::RenderOutputType;
 pub use graph_craft::wasm_application_io::*;
 #[cfg(target_arch = "wasm32")]
 use graphene_core::application_io::SurfaceHandle;
-use graphene_core::application_io::{ApplicationIo, ExportFormat, RenderConfig};
+use graphene_core::application_io::{Antialiasing, ApplicationIo, ExportFormat, RenderConfig};
 #[cfg(target_arch = "wasm32")]
 use graphene_core::instances::Instances;
 #[cfg(target_arch = "wasm32")]
 use graphene_core::raster::bbox::Bbox;
 use graphene_core::raster::image::{Image, ImageFrameTable};
 use graphene_core::renderer::RenderMetadata;
-use graphene_core::renderer::{GraphicElementRendered, RenderParams, RenderSvgSegmentList, SvgRender, format_transform_matrix};
-use graphene_core::transform::Footprint;
+use graphene_core::renderer::{ColorManagement, GraphicElementRendered, RenderParams, RenderSvgSegmentList, SvgRender, format_transform_matrix};
 #[cfg(target_arch = "wasm32")]
 use graphene_core::transform::TransformMut;
+use graphene_core::transform::{Footprint, RenderQuality};
 use graphene_core::vector::VectorDataTable;
+use graphene_core::vector::style::ViewMode;
 use graphene_core::{Color, Context, Ctx, ExtractFootprint, GraphicGroupTable, OwnedContextImpl, WasmNotSend};
 
 #[cfg(target_arch = "wasm32")]
@@ -92,7 +93,14 @@ fn decode_image(_: impl Ctx, data: Arc<[u8]>) -> ImageFrameTable<Color> {
 	ImageFrameTable::new(image)
 }
 
-fn render_svg(data: impl GraphicElementRendered, mut render: SvgRender, render_params: RenderParams, footprint: Footprint) -> RenderOutputType {
+fn render_svg(
+	data: impl GraphicElementRendered,
+	mut render: SvgRender,
+	render_params: RenderParams,
+	footprint: Footprint,
+	antialiasing: Antialiasing,
+	color_management: ColorManagement,
+) -> RenderOutputType {
 	if !data.contains_artboard() && !render_params.hide_artboards {
 		render.leaf_tag("rect", |attributes| {
 			attributes.push("x", "0");
@@ -107,8 +115,101 @@ fn render_svg(data: impl GraphicElementRendered, mut render: SvgRender, render_p
 		});
 	}
 
-	data.render_svg(&mut render, &render_params);
+	// The browser's default rendering is already fully antialiased, so the hint is only needed to reduce it
+	let shape_rendering = match antialiasing {
+		Antialiasing::Off => Some("crispEdges"),
+		Antialiasing::Fast => Some("optimizeSpeed"),
+		Antialiasing::Full => None,
+	};
+	match shape_rendering {
+		Some(shape_rendering) => render.parent_tag("g", |attributes| attributes.push("shape-rendering", shape_rendering), |render| data.render_svg(render, &render_params)),
+		None => data.render_svg(&mut render, &render_params),
+	}
 
+	apply_color_management(&mut render, color_management);
+	render.wrap_with_transform(footprint.transform, Some(footprint.resolution.as_dvec2()));
+
+	RenderOutputType::Svg(render.svg.to_svg_string())
+}
+
+/// Composites the whole render, including its background, through the color management filter. This must be done before it's wrapped in its `<svg>` tag.
+fn apply_color_management(render: &mut SvgRender, color_management: ColorManagement) {
+	let Some(filter) = color_management.svg_filter() else { return };
+
+	render.svg_defs.push_str(&filter);
+	render.svg.insert(0, format!(r#"<g filter="url(#{})">"#, ColorManagement::SVG_FILTER_ID).into());
+	render.svg.push("</g>".into());
+}
+
+/// Upper limit on the number of document pixels rasterized for the pixel preview, beyond which (when zoomed far out) the artwork is drawn as vectors instead.
+#[cfg(target_arch = "wasm32")]
+const PIXEL_PREVIEW_MAX_PIXELS: u32 = 4096 * 4096;
+
+/// The region of document pixels visible in the viewport, snapped outwards to whole pixels, as its top left corner and size.
+/// Returns `None` if it is empty or too large to rasterize.
+#[cfg(target_arch = "wasm32")]
+fn pixel_preview_region(footprint: Footprint) -> Option<(glam::DVec2, glam::UVec2)> {
+	use graphene_core::renderer::Quad;
+
+	let [min, max] = (footprint.transform.inverse() * Quad::from_box([glam::DVec2::ZERO, footprint.resolution.as_dvec2()])).bounding_box();
+	let (min, max) = (min.floor(), max.ceil());
+	let size = (max - min).as_uvec2();
+
+	(size.x > 0 && size.y > 0 && size.x.saturating_mul(size.y) <= PIXEL_PREVIEW_MAX_PIXELS).then_some((min, size))
+}
+
+/// Rasterizes the visible part of the artwork at the document's own pixel resolution, then magnifies it with nearest-neighbor sampling
+/// so every document pixel appears as a crisp square, exactly as it will look once exported as a bitmap.
+#[cfg(target_arch = "wasm32")]
+async fn render_pixel_preview(
+	data: impl GraphicElementRendered,
+	mut render_params: RenderParams,
+	footprint: Footprint,
+	(min, size): (glam::DVec2, glam::UVec2),
+	color_management: ColorManagement,
+) -> RenderOutputType {
+	let pixels_footprint = Footprint {
+		transform: DAffine2::from_translation(-min),
+		resolution: size,
+		..footprint
+	};
+	render_params.viewport_transform = Some(pixels_footprint.transform);
+	let RenderOutputType::Svg(svg_string) = render_svg(data, SvgRender::new(), render_params, pixels_footprint, Antialiasing::Full, ColorManagement::default()) else {
+		unreachable!("SVG rendering always produces an SVG string");
+	};
+
+	let preamble = "data:image/svg+xml;base64,";
+	let mut base64_string = String::with_capacity(preamble.len() + svg_string.len() * 4);
+	base64_string.push_str(preamble);
+	base64::engine::general_purpose::STANDARD.encode_string(svg_string, &mut base64_string);
+
+	let rasterize = async {
+		let canvas = web_sys::window()?.document()?.create_element("canvas").ok()?.dyn_into::<HtmlCanvasElement>().ok()?;
+		canvas.set_width(size.x);
+		canvas.set_height(size.y);
+		let context = canvas.get_context("2d").ok()??.dyn_into::<CanvasRenderingContext2d>().ok()?;
+
+		let image_data = web_sys::HtmlImageElement::new().ok()?;
+		image_data.set_src(base64_string.as_str());
+		wasm_bindgen_futures::JsFuture::from(image_data.decode()).await.ok()?;
+		context.draw_image_with_html_image_element(&image_data, 0., 0.).ok()?;
+		canvas.to_data_url().ok()
+	};
+	// If the browser fails to rasterize the artwork, it is still shown at the right place, just not pixelated
+	let rasterized = rasterize.await.unwrap_or_else(|| {
+		log::error!("Failed to rasterize the pixel preview");
+		base64_string.clone()
+	});
+
+	let mut render = SvgRender::new();
+	render.leaf_tag("image", |attributes| {
+		attributes.push("width", size.x.to_string());
+		attributes.push("height", size.y.to_string());
+		attributes.push("transform", format_transform_matrix(DAffine2::from_translation(min)));
+		attributes.push("style", "image-rendering: pixelated");
+		attributes.push("href", rasterized);
+	});
+	apply_color_management(&mut render, color_management);
 	render.wrap_with_transform(footprint.transform, Some(footprint.resolution.as_dvec2()));
 
 	RenderOutputType::Svg(render.svg.to_svg_string())
@@ -116,7 +217,13 @@ fn render_svg(data: impl GraphicElementRendered, mut render: SvgRender, render_p
 
 #[cfg(feature = "vello")]
 #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
-async fn render_canvas(render_config: RenderConfig, data: impl GraphicElementRendered, editor: &WasmEditorApi, surface_handle: wgpu_executor::WgpuSurface) -> RenderOutputType {
+async fn render_canvas(
+	render_config: RenderConfig,
+	data: impl GraphicElementRendered,
+	editor: &WasmEditorApi,
+	surface_handle: wgpu_executor::WgpuSurface,
+	antialiasing: Antialiasing,
+) -> RenderOutputType {
 	use graphene_core::SurfaceFrame;
 
 	let footprint = render_config.viewport;
@@ -134,11 +241,19 @@ async fn render_canvas(render_config: RenderConfig, data: impl GraphicElementRen
 	// TODO: Instead of applying the transform here, pass the transform during the translation to avoid the O(n) cost
 	scene.append(&child, Some(kurbo::Affine::new(footprint.transform.to_cols_array())));
 
-	let mut background = Color::from_rgb8_srgb(0x22, 0x22, 0x22);
-	if !data.contains_artboard() && !render_config.hide_artboards {
-		background = Color::WHITE;
-	}
-	exec.render_vello_scene(&scene, &surface_handle, footprint.resolution.x, footprint.resolution.y, &context, background)
+	// Without the artboards, whatever is drawn beneath the canvas shows through it
+	let background = match render_config.hide_artboards {
+		true => Color::TRANSPARENT,
+		false if !data.contains_artboard() => Color::WHITE,
+		false => Color::from_rgb8_srgb(0x22, 0x22, 0x22),
+	};
+	// Vello has no aliased mode, so the cheapest multisampling stands in for turning antialiasing off, while its analytic area coverage is the most precise
+	let antialiasing_method = match antialiasing {
+		Antialiasing::Off => AaConfig::Msaa8,
+		Antialiasing::Fast => AaConfig::Msaa16,
+		Antialiasing::Full => AaConfig::Area,
+	};
+	exec.render_vello_scene(&scene, &surface_handle, footprint.resolution.x, footprint.resolution.y, &context, background, antialiasing_method)
 		.await
 		.expect("Failed to render Vello scene");
 
@@ -242,8 +357,17 @@ async fn render<'a: 'n, T: 'n + GraphicElementRendered + WasmNotSend>(
 		.into_context();
 	ctx.footprint();
 
-	let RenderConfig { hide_artboards, for_export, .. } = render_config;
-	let render_params = RenderParams::new(render_config.view_mode, None, false, hide_artboards, for_export);
+	let RenderConfig {
+		hide_artboards,
+		for_export,
+		embed_fonts,
+		..
+	} = render_config;
+	let mut render_params = RenderParams::new(render_config.view_mode, None, false, hide_artboards, for_export);
+	render_params.embed_fonts = embed_fonts;
+	// Exports are always rendered at full detail, but the viewport can skip detail too small to see
+	// A preview simplifies paths as if they were drawn at its lower resolution, the same as the nodes generating pixels do
+	render_params.viewport_transform = (!for_export).then(|| DAffine2::from_scale(glam::DVec2::splat(footprint.quality.resolution_scale())) * footprint.transform);
 
 	let data = data.eval(ctx.clone()).await;
 	let editor_api = editor_api.eval(ctx.clone()).await;
@@ -255,6 +379,15 @@ async fn render<'a: 'n, T: 'n + GraphicElementRendered + WasmNotSend>(
 	#[cfg(all(feature = "vello", target_arch = "wasm32"))]
 	let use_vello = use_vello && surface_handle.is_some();
 
+	// Exports are always fully antialiased, while fast previews of the viewport skip antialiasing altogether
+	let antialiasing = match footprint.quality {
+		_ if for_export => Antialiasing::Full,
+		RenderQuality::Preview => Antialiasing::Off,
+		_ => editor_api.editor_preferences.viewport_antialiasing(),
+	};
+	// Exports keep the document's colors as they are, while the viewport shows them converted for the display
+	let color_management = if for_export { ColorManagement::default() } else { render_config.color_management };
+
 	let mut metadata = RenderMetadata {
 		upstream_footprints: HashMap::new(),
 		local_transforms: HashMap::new(),
@@ -263,20 +396,29 @@ async fn render<'a: 'n, T: 'n + GraphicElementRendered + WasmNotSend>(
 	};
 	data.collect_metadata(&mut metadata, footprint, None);
 
+	#[cfg(target_arch = "wasm32")]
+	if render_config.view_mode == ViewMode::Pixels && !for_export {
+		if let Some(region) = pixel_preview_region(footprint) {
+			let data = render_pixel_preview(data, render_params, footprint, region, color_management).await;
+			return RenderOutput { data, metadata };
+		}
+	}
+
 	let output_format = render_config.export_format;
 	let data = match output_format {
-		ExportFormat::Svg => render_svg(data, SvgRender::new(), render_params, footprint),
+		ExportFormat::Svg => render_svg(data, SvgRender::new(), render_params, footprint, antialiasing, color_management),
 		ExportFormat::Canvas => {
-			if use_vello && editor_api.application_io.as_ref().unwrap().gpu_executor().is_some() {
+			// The GPU renderer only supports the normal view mode without color management, so the others fall back to SVG
+			if use_vello && render_config.view_mode == ViewMode::Normal && color_management.is_identity() && editor_api.application_io.as_ref().unwrap().gpu_executor().is_some() {
 				#[cfg(all(feature = "vello", target_arch = "wasm32"))]
 				return RenderOutput {
-					data: render_canvas(render_config, data, editor_api, surface_handle.unwrap()).await,
+					data: render_canvas(render_config, data, editor_api, surface_handle.unwrap(), antialiasing).await,
 					metadata,
 				};
 				#[cfg(not(all(feature = "vello", target_arch = "wasm32")))]
-				render_svg(data, SvgRender::new(), render_params, footprint)
+				render_svg(data, SvgRender::new(), render_params, footprint, antialiasing, color_management)
 			} else {
-				render_svg(data, SvgRender::new(), render_params, footprint)
+				render_svg(data, SvgRender::new(), render_params, footprint, antialiasing, color_management)
 			}
 		}
 		_ => todo!("Non-SVG render output for {output_format:?}"),
@@ -235,6 +235,7 @@ pub(crate) fn generate_node_code(parsed: &ParsedNodeFn) -> syn::Result<TokenStre
 		type Output = #graphene_core::registry::DynFuture<'n, #output_type>;
 		#[inline]
 		fn eval(&'n self, __input: #input_type) -> Self::Output {
+			#graphene_core::memo::record_node_evaluation();
 			Box::pin(async move {
 				#(#eval_args)*
 				self::#fn_name(__input #(, #field_names)*) #await_keyword
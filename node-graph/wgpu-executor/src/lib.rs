@@ -9,7 +9,7 @@ use futures::Future;
 use glam::{DAffine2, UVec2};
 use gpu_executor::{ComputePassDimensions, GPUConstant, StorageBufferOptions, TextureBufferOptions, TextureBufferType, ToStorageBuffer, ToUniformBuffer};
 use graphene_core::application_io::{ApplicationIo, EditorApi, ImageTexture, SurfaceHandle};
-use graphene_core::raster::image::ImageFrameTable;
+use graphene_core::raster::image::{ImageContentId, ImageFrameTable};
 use graphene_core::raster::{Image, SRGBA8};
 use graphene_core::transform::{Footprint, Transform};
 use graphene_core::{Color, Cow, Ctx, ExtractFootprint, Node, SurfaceFrame, Type};
@@ -133,7 +133,7 @@ pub use graphene_core::renderer::RenderContext;
 // }
 
 impl WgpuExecutor {
-	pub async fn render_vello_scene(&self, scene: &Scene, surface: &WgpuSurface, width: u32, height: u32, context: &RenderContext, background: Color) -> Result<()> {
+	pub async fn render_vello_scene(&self, scene: &Scene, surface: &WgpuSurface, width: u32, height: u32, context: &RenderContext, background: Color, antialiasing_method: AaConfig) -> Result<()> {
 		let surface = &surface.surface.inner;
 		let surface_caps = surface.get_capabilities(&self.context.adapter);
 		surface.configure(
@@ -158,7 +158,7 @@ impl WgpuExecutor {
 			base_color: vello::peniko::Color::from_rgba8(r, g, b, 0xff),
 			width,
 			height,
-			antialiasing_method: AaConfig::Msaa16,
+			antialiasing_method,
 		};
 
 		{
@@ -920,6 +920,7 @@ async fn upload_texture<'a: 'n>(_: impl ExtractFootprint + Ctx, input: ImageFram
 		height: input.height,
 		data: new_data,
 		base64_string: None,
+		content_id: ImageContentId::new(),
 	};
 
 	let shader_input = executor.create_texture_buffer(new_image, TextureBufferOptions::Texture).unwrap();